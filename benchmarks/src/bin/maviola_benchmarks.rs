@@ -4,10 +4,16 @@ use std::time::Duration;
 
 #[cfg(feature = "async")]
 use maviola_benchmarks::asnc::benchmark_async_unix_sockets;
+#[cfg(all(feature = "ipc", unix))]
+use maviola_benchmarks::ipc::benchmark_ipc_latency;
 #[cfg(feature = "mpmc")]
-use maviola_benchmarks::mpmc::{benchmark_mpmc_broadcast, benchmark_mpmc_collect};
+use maviola_benchmarks::mpmc::{
+    benchmark_mpmc_broadcast, benchmark_mpmc_collect, benchmark_mpmc_drain, benchmark_mpmc_wakeups,
+};
 #[cfg(feature = "sync")]
-use maviola_benchmarks::sync::benchmark_unix_sockets;
+use maviola_benchmarks::sync::{
+    benchmark_network_routing, benchmark_tcp_server_io_pool, benchmark_unix_sockets,
+};
 
 #[global_allocator]
 static GLOBAL: maviola_benchmarks::trallocator::Trallocator<System> =
@@ -47,6 +53,20 @@ fn main() {
             benchmark_mpmc_collect(1_000, 1_000);
             debug_memory("benchmark_mpmc_collect", base_mem);
         }
+
+        {
+            log::info!("[benchmark_mpmc_drain]");
+            let base_mem = GLOBAL.get();
+            benchmark_mpmc_drain(8, 1_000_000);
+            debug_memory("benchmark_mpmc_drain", base_mem);
+        }
+
+        {
+            log::info!("[benchmark_mpmc_wakeups]");
+            let base_mem = GLOBAL.get();
+            benchmark_mpmc_wakeups(10, Duration::from_secs(1));
+            debug_memory("benchmark_mpmc_wakeups", base_mem);
+        }
     }
 
     #[cfg(feature = "sync")]
@@ -57,6 +77,34 @@ fn main() {
         debug_memory("benchmark_unix_sockets", base_mem);
     }
 
+    #[cfg(feature = "sync")]
+    {
+        log::info!("[benchmark_network_routing]");
+        let base_mem = GLOBAL.get();
+        benchmark_network_routing(10, 20_000);
+        debug_memory("benchmark_network_routing", base_mem);
+    }
+
+    #[cfg(feature = "sync")]
+    {
+        log::info!("[benchmark_tcp_server_io_pool]");
+        let base_mem = GLOBAL.get();
+        benchmark_tcp_server_io_pool(100, 1_000, None);
+        debug_memory("benchmark_tcp_server_io_pool", base_mem);
+
+        let base_mem = GLOBAL.get();
+        benchmark_tcp_server_io_pool(100, 1_000, Some(4));
+        debug_memory("benchmark_tcp_server_io_pool", base_mem);
+    }
+
+    #[cfg(all(feature = "ipc", unix))]
+    {
+        log::info!("[benchmark_ipc_latency]");
+        let base_mem = GLOBAL.get();
+        benchmark_ipc_latency(10_000);
+        debug_memory("benchmark_ipc_latency", base_mem);
+    }
+
     #[cfg(feature = "async")]
     {
         log::info!("[benchmark_async_unix_sockets]");
@@ -84,12 +132,42 @@ mod benchmark_tests {
         super::benchmark_mpmc_broadcast(100, 100);
     }
 
+    #[test]
+    #[cfg(feature = "mpmc")]
+    fn run_benchmark_mpmc_drain() {
+        super::benchmark_mpmc_drain(4, 1_000);
+    }
+
+    #[test]
+    #[cfg(feature = "mpmc")]
+    fn run_benchmark_mpmc_wakeups() {
+        super::benchmark_mpmc_wakeups(4, std::time::Duration::from_millis(100));
+    }
+
     #[test]
     #[cfg(feature = "sync")]
     fn run_benchmark_unix_sockets() {
         super::benchmark_unix_sockets(10, 1_000);
     }
 
+    #[test]
+    #[cfg(feature = "sync")]
+    fn run_benchmark_network_routing() {
+        super::benchmark_network_routing(5, 1_000);
+    }
+
+    #[test]
+    #[cfg(feature = "sync")]
+    fn run_benchmark_tcp_server_io_pool() {
+        super::benchmark_tcp_server_io_pool(10, 100, Some(2));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipc", unix))]
+    fn run_benchmark_ipc_latency() {
+        super::benchmark_ipc_latency(1_000);
+    }
+
     #[tokio::test]
     #[cfg(feature = "async")]
     async fn run_benchmark_async_unix_sockets() {
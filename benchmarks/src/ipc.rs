@@ -0,0 +1,152 @@
+use std::fs::remove_file;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use maviola::core::io::{IpcClient, IpcServer};
+use maviola::dialects::minimal::messages::Heartbeat;
+use maviola::error::RecvTimeoutError;
+use maviola::sync::io::ConnectionBuilder;
+
+use maviola::prelude::*;
+use maviola::sync::prelude::*;
+
+const WAIT_DURATION: Duration = Duration::from_millis(500);
+
+fn wait() {
+    thread::sleep(WAIT_DURATION);
+}
+
+fn make_node<C: ConnectionBuilder<V2> + 'static>(id: u8, connection: C) -> EdgeNode<V2> {
+    Node::sync::<V2>()
+        .system_id(id)
+        .component_id(0)
+        .connection(connection)
+        .build()
+        .unwrap()
+}
+
+fn clean_path(path: &Path) {
+    if Path::exists(path) {
+        remove_file(path).unwrap();
+    }
+}
+
+/// Round-trip latencies of a transport sorted in ascending order.
+struct Latencies(Vec<Duration>);
+
+impl Latencies {
+    fn mean(&self) -> Duration {
+        self.0.iter().sum::<Duration>() / self.0.len().max(1) as u32
+    }
+
+    fn percentile(&self, percentile: usize) -> Duration {
+        if self.0.is_empty() {
+            return Duration::ZERO;
+        }
+        self.0[(self.0.len() - 1) * percentile / 100]
+    }
+}
+
+/// Measures round-trip latency between a client and a server, that echoes each received frame.
+fn measure_round_trip(
+    name: &'static str,
+    server: EdgeNode<V2>,
+    make_client: impl FnOnce() -> EdgeNode<V2>,
+    n_iter: usize,
+) -> Latencies {
+    let done = Arc::new(AtomicBool::new(false));
+    let echo_handler = {
+        let done = done.clone();
+        thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                let (frame, callback) = match server.recv_frame_timeout(WAIT_DURATION) {
+                    Ok(value) => value,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                    Err(_) => continue,
+                };
+                if let Err(err) = callback.respond(&frame) {
+                    log::error!("[{name}] echo error: {err:?}");
+                    break;
+                }
+            }
+        })
+    };
+
+    let client = make_client();
+    wait();
+
+    let mut latencies = Vec::with_capacity(n_iter);
+    for _ in 0..n_iter {
+        let start = Instant::now();
+        if let Err(err) = client.send(&Heartbeat::default()) {
+            log::error!("[{name}] send error: {err:?}");
+            break;
+        }
+        match client.recv_frame_timeout(WAIT_DURATION) {
+            Ok(_) => latencies.push(start.elapsed()),
+            Err(err) => {
+                log::error!("[{name}] receive error: {err:?}");
+                break;
+            }
+        }
+    }
+
+    drop(client);
+    done.store(true, Ordering::Relaxed);
+    echo_handler.join().unwrap();
+    wait();
+
+    if latencies.len() < n_iter {
+        log::warn!(
+            "[{name}] frame loss: {}%",
+            (n_iter - latencies.len()) as f32 / n_iter as f32 * 100.0
+        );
+    }
+
+    latencies.sort();
+    Latencies(latencies)
+}
+
+/// Compares round-trip latency of shared memory and Unix socket transports.
+pub fn benchmark_ipc_latency(n_iter: usize) {
+    let dir = match Path::new("/dev/shm") {
+        shm if shm.is_dir() => shm.to_path_buf(),
+        _ => std::env::temp_dir(),
+    };
+
+    let sock_path = PathBuf::from("/tmp/maviola_benchmarks_latency.sock");
+    clean_path(sock_path.as_path());
+    let sock = measure_round_trip(
+        "unix_sockets",
+        make_node(1, SockServer::new(sock_path.as_path()).unwrap()),
+        || make_node(2, SockClient::new(sock_path.as_path()).unwrap()),
+        n_iter,
+    );
+    clean_path(sock_path.as_path());
+
+    let ipc_path = dir.join("maviola_benchmarks_latency.ipc");
+    clean_path(ipc_path.as_path());
+    let ipc = measure_round_trip(
+        "shared_memory",
+        make_node(1, IpcServer::new(ipc_path.as_path()).unwrap()),
+        || make_node(2, IpcClient::new(ipc_path.as_path()).unwrap()),
+        n_iter,
+    );
+
+    for (name, latencies) in [("unix_sockets", &sock), ("shared_memory", &ipc)] {
+        log::info!(
+            "[benchmark_ipc_latency] {name}: {} round trips, mean: {}µs, p50: {}µs, p99: {}µs",
+            latencies.0.len(),
+            latencies.mean().as_micros(),
+            latencies.percentile(50).as_micros(),
+            latencies.percentile(99).as_micros(),
+        );
+    }
+    log::info!(
+        "[benchmark_ipc_latency] shared memory is {:.1}x faster than Unix sockets",
+        sock.mean().as_secs_f64() / ipc.mean().as_secs_f64().max(f64::EPSILON)
+    );
+}
@@ -8,3 +8,5 @@ pub mod sync;
 
 #[cfg(feature = "async")]
 pub mod asnc;
+#[cfg(all(feature = "ipc", unix))]
+pub mod ipc;
@@ -1,9 +1,10 @@
 use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
-use maviola::sync::utils::mpmc;
+use maviola::sync::utils::mpmc::{self, ChannelFlavor, CrossbeamChannel, FlumeChannel, StdChannel};
 
 const PAYLOAD_SIZE: usize = 255;
 
@@ -125,3 +126,194 @@ pub fn benchmark_mpmc_collect(n_threads: usize, n_senders: usize) {
         duration.as_secs_f32()
     )
 }
+
+pub fn benchmark_mpmc_drain(n_workers: usize, n_iter: usize) {
+    for batched in [false, true] {
+        let (tx, rx) = mpmc::channel();
+        let (collect_tx, collect_rx) = mpsc::channel();
+        let group = rx.join_group("workers");
+
+        let start_pair = Arc::new((Mutex::new(false), Condvar::new()));
+        let done = Arc::new(AtomicBool::new(false));
+
+        for _ in 0..n_workers {
+            let worker = group.clone();
+            let start_pair = start_pair.clone();
+            let done = done.clone();
+            let collect_tx = collect_tx.clone();
+
+            thread::spawn(move || {
+                let (lock, cvar) = &*start_pair;
+                let mut started = lock.lock().unwrap();
+                while !*started {
+                    started = cvar.wait(started).unwrap();
+                }
+                drop(started);
+
+                let mut buffer = Vec::new();
+                while !done.load(Ordering::Acquire) {
+                    let received = if batched {
+                        worker.drain_into(&mut buffer, 1_000)
+                    } else {
+                        let mut received = 0;
+                        while received < 1_000 {
+                            match worker.try_recv() {
+                                Ok(payload) => buffer.push(payload),
+                                Err(_) => break,
+                            }
+                            received += 1;
+                        }
+                        received
+                    };
+
+                    if received > 0 {
+                        buffer.clear();
+                        collect_tx.send(received).unwrap();
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+            });
+        }
+
+        // Fill the group before workers are started to measure consumption only
+        for i in 0..n_iter {
+            tx.send(Payload::new(i)).unwrap();
+        }
+        while group.len() < n_iter {
+            thread::yield_now();
+        }
+
+        let start = SystemTime::now();
+        {
+            let (lock, cvar) = &*start_pair;
+            let mut started = lock.lock().unwrap();
+            *started = true;
+            cvar.notify_all();
+        }
+
+        let mut collected = 0;
+        while collected < n_iter {
+            collected += collect_rx.recv().unwrap();
+        }
+
+        let end = SystemTime::now();
+        let duration = end.duration_since(start).unwrap();
+        done.store(true, Ordering::Release);
+
+        log::info!(
+            "[benchmark_mpmc_drain] {} {n_iter} of {:?} by {n_workers} group members: {}s",
+            if batched {
+                "drain_into"
+            } else {
+                "try_recv loop"
+            },
+            Payload::default(),
+            duration.as_secs_f32()
+        )
+    }
+}
+
+pub fn benchmark_mpmc_wakeups(n_receivers: usize, idle: Duration) {
+    benchmark_polling_relay_wakeups(n_receivers, idle);
+    benchmark_flavor_wakeups::<StdChannel>("std", n_receivers, idle);
+    benchmark_flavor_wakeups::<CrossbeamChannel>("crossbeam", n_receivers, idle);
+    benchmark_flavor_wakeups::<FlumeChannel>("flume", n_receivers, idle);
+}
+
+/// Relay, that polls its input with a timeout the same way as MPMC bus did before it was blocking
+/// on the input channel.
+fn benchmark_polling_relay_wakeups(n_receivers: usize, idle: Duration) {
+    const POLLING_TIMEOUT: Duration = Duration::from_micros(50);
+
+    let (tx, rx) = mpsc::channel::<Instant>();
+    let (out_txs, out_rxs): (Vec<_>, Vec<_>) =
+        (0..n_receivers).map(|_| mpsc::channel::<Instant>()).unzip();
+
+    thread::spawn(move || loop {
+        match rx.recv_timeout(POLLING_TIMEOUT) {
+            Ok(sent_at) => {
+                for out_tx in &out_txs {
+                    out_tx.send(sent_at).unwrap();
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    });
+
+    let receivers = out_rxs
+        .into_iter()
+        .map(|out_rx| thread::spawn(move || out_rx.recv().unwrap().elapsed()))
+        .collect();
+
+    measure_wakeups("polling relay", idle, receivers, move || {
+        tx.send(Instant::now()).unwrap()
+    });
+}
+
+fn benchmark_flavor_wakeups<F: ChannelFlavor>(name: &str, n_receivers: usize, idle: Duration) {
+    let (tx, rx) = mpmc::channel_with::<F, Instant>();
+
+    let receivers = (0..n_receivers)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || rx.recv().unwrap().elapsed())
+        })
+        .collect();
+
+    measure_wakeups(name, idle, receivers, move || {
+        tx.send(Instant::now()).unwrap()
+    });
+}
+
+fn measure_wakeups(
+    name: &str,
+    idle: Duration,
+    receivers: Vec<thread::JoinHandle<Duration>>,
+    send: impl FnOnce(),
+) {
+    let n_receivers = receivers.len();
+
+    // Let all threads park
+    thread::sleep(Duration::from_millis(10));
+
+    let before = context_switches();
+    thread::sleep(idle);
+    let after = context_switches();
+
+    send();
+    let latency = receivers
+        .into_iter()
+        .map(|receiver| receiver.join().unwrap())
+        .max()
+        .unwrap_or_default();
+
+    let wakeups = match (before, after) {
+        (Some(before), Some(after)) => after.saturating_sub(before).to_string(),
+        _ => "n/a".to_string(),
+    };
+
+    log::info!(
+        "[benchmark_mpmc_wakeups] {name}: {wakeups} wakeups in {}s of idle, delivered to {n_receivers} receivers in {}s",
+        idle.as_secs_f32(),
+        latency.as_secs_f32(),
+    )
+}
+
+/// Total number of voluntary context switches of all process threads.
+///
+/// Available only on Linux, where every wakeup of a parked thread is counted by the kernel.
+fn context_switches() -> Option<u64> {
+    let mut total = 0;
+    for task in std::fs::read_dir("/proc/self/task").ok()? {
+        let status = std::fs::read_to_string(task.ok()?.path().join("status")).ok()?;
+        total += status
+            .lines()
+            .find_map(|line| line.strip_prefix("voluntary_ctxt_switches:"))?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+    }
+    Some(total)
+}
@@ -1,11 +1,17 @@
 use std::fs::remove_file;
+use std::net::TcpStream;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Barrier};
 use std::thread;
 use std::time::{Duration, SystemTime};
 
+use maviola::core::io::Sender;
 use maviola::dialects::minimal::enums::{MavAutopilot, MavModeFlag, MavState, MavType};
 use maviola::dialects::minimal::messages::Heartbeat;
+use maviola::error::RecvTimeoutError;
+use maviola::protocol::Endpoint;
+use maviola::sync::io::IoPool;
 
 use maviola::prelude::*;
 use maviola::sync::prelude::*;
@@ -130,3 +136,219 @@ pub fn benchmark_unix_sockets(n_clients: u16, n_iter: usize) {
         (duration.as_secs_f64() / n_received_frames as f64 * 1_000.0) as f32
     )
 }
+
+fn make_tcp_router(ingress: &str, egress: &str) -> EdgeNode<V2> {
+    Node::sync::<V2>()
+        .system_id(1)
+        .component_id(0)
+        .heartbeat_interval(HEARTBEAT_INTERVAL)
+        .heartbeat_timeout(HEARTBEAT_TIMEOUT)
+        .connection(
+            Network::sync()
+                .add_connection(TcpServer::new(ingress).unwrap())
+                .add_connection(TcpServer::new(egress).unwrap()),
+        )
+        .build()
+        .unwrap()
+}
+
+fn make_tcp_client(addr: &str, id: u16) -> EdgeNode<V2> {
+    let bytes: [u8; 2] = id.to_le_bytes();
+    let system_id = bytes[0];
+    let component_id = bytes[1];
+
+    Node::sync::<V2>()
+        .system_id(system_id)
+        .component_id(component_id)
+        .heartbeat_interval(HEARTBEAT_INTERVAL)
+        .heartbeat_timeout(HEARTBEAT_TIMEOUT)
+        .connection(TcpClient::new(addr).unwrap())
+        .build()
+        .unwrap()
+}
+
+pub fn benchmark_network_routing(n_clients: u16, n_iter: usize) {
+    let n_interaction = n_clients as u32 * n_iter as u32;
+    let ingress = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+    let egress = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+
+    let router = make_tcp_router(ingress.as_str(), egress.as_str());
+    wait();
+
+    let done = Arc::new(AtomicBool::new(false));
+    let router_handler = {
+        let done = done.clone();
+        thread::spawn(move || {
+            // Route frames from ingress clients to egress until the traffic is over
+            while !done.load(Ordering::Relaxed) {
+                let (frame, callback) = match router.recv_frame_timeout(WAIT_DURATION) {
+                    Ok(value) => value,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                    Err(_) => continue,
+                };
+                if let Err(err) = callback.broadcast_except(&frame) {
+                    log::error!("[router] routing error: {err:?}");
+                    break;
+                }
+            }
+        })
+    };
+
+    let sink = make_tcp_client(egress.as_str(), u16::MAX);
+    wait();
+
+    let barrier = Arc::new(Barrier::new(n_clients as usize + 1));
+
+    for i in 0..n_clients {
+        let addr = ingress.clone();
+        let barrier = barrier.clone();
+        let done = done.clone();
+
+        thread::spawn(move || {
+            let client = make_tcp_client(addr.as_str(), i + 1);
+            barrier.wait();
+
+            for _ in 0..n_iter {
+                if let Err(err) = client.send(&Heartbeat::default()) {
+                    log::error!("[client #{i}] send error: {err:?}");
+                    break;
+                }
+            }
+
+            // Keep connection open until all frames are routed
+            while !done.load(Ordering::Relaxed) {
+                wait();
+            }
+        });
+    }
+
+    barrier.wait();
+    log::info!("[benchmark_network_routing] started");
+
+    let mut n_received_frames = 0;
+    let start = SystemTime::now();
+    while n_received_frames < n_interaction {
+        match sink.recv_frame_timeout(WAIT_DURATION) {
+            Ok(_) => n_received_frames += 1,
+            Err(err) => {
+                log::error!("[sink] error: {err:?}");
+                break;
+            }
+        }
+    }
+    let duration = start.elapsed().unwrap();
+
+    drop(sink);
+    done.store(true, Ordering::Relaxed);
+    router_handler.join().unwrap();
+    wait();
+
+    if n_received_frames < n_interaction {
+        log::warn!(
+            "[benchmark_network_routing] frame loss: {}%",
+            (n_interaction - n_received_frames) as f32 / n_interaction as f32 * 100.0
+        );
+    }
+
+    log::info!(
+        "[benchmark_network_routing] route {n_iter} frames from {n_clients} clients ({n_interaction} total): {}s, ({}ms per frame)",
+        duration.as_secs_f32(),
+        (duration.as_secs_f64() / n_received_frames as f64 * 1_000.0) as f32
+    )
+}
+
+/// Number of threads of the current process.
+///
+/// Returns [`None`] on platforms other than Linux.
+fn count_threads() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|threads| threads.trim().parse().ok())
+}
+
+fn make_pooled_tcp_server(addr: &str, pool: Option<&IoPool>) -> EdgeNode<V2> {
+    let builder = Node::sync::<V2>()
+        .system_id(1)
+        .component_id(0)
+        .heartbeat_interval(HEARTBEAT_INTERVAL)
+        .heartbeat_timeout(HEARTBEAT_TIMEOUT)
+        .connection(TcpServer::new(addr).unwrap());
+
+    match pool {
+        Some(pool) => builder.io_pool(pool.clone()).build().unwrap(),
+        None => builder.build().unwrap(),
+    }
+}
+
+pub fn benchmark_tcp_server_io_pool(n_clients: u16, n_iter: usize, pool_size: Option<usize>) {
+    let n_interaction = n_clients as u32 * n_iter as u32;
+    let addr = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+    let pool = pool_size.map(IoPool::new);
+
+    let threads_before = count_threads();
+    let server = make_pooled_tcp_server(addr.as_str(), pool.as_ref());
+    wait();
+
+    // Clients are plain TCP streams fed by a single thread, so only server threads are counted
+    let mut clients: Vec<_> = (0..n_clients)
+        .map(|i| {
+            let stream = TcpStream::connect(addr.as_str()).unwrap();
+            let bytes = (i + 1).to_le_bytes();
+            let endpoint = Endpoint::v2(MavLinkId::new(bytes[0], bytes[1]));
+            (Sender::new(stream), endpoint)
+        })
+        .collect();
+    wait();
+
+    let server_threads = match (threads_before, count_threads()) {
+        (Some(before), Some(after)) => format!("{}", after.saturating_sub(before)),
+        _ => "unknown".to_string(),
+    };
+    log::info!("[benchmark_tcp_server_io_pool] started, pool: {pool_size:?}");
+
+    let start = SystemTime::now();
+    let feeder = thread::spawn(move || {
+        for _ in 0..n_iter {
+            for (sender, endpoint) in clients.iter_mut() {
+                let frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+                if let Err(err) = sender.send(&frame) {
+                    log::error!("[clients] send error: {err:?}");
+                    return;
+                }
+            }
+        }
+        // Keep connections open until all frames are received
+        wait();
+    });
+
+    let mut n_received_frames = 0;
+    while n_received_frames < n_interaction {
+        match server.recv_frame_timeout(WAIT_DURATION) {
+            Ok(_) => n_received_frames += 1,
+            Err(err) => {
+                log::error!("[server] error: {err:?}");
+                break;
+            }
+        }
+    }
+    let duration = start.elapsed().unwrap();
+
+    feeder.join().unwrap();
+    drop(server);
+    wait();
+
+    if n_received_frames < n_interaction {
+        log::warn!(
+            "[benchmark_tcp_server_io_pool] frame loss: {}%",
+            (n_interaction - n_received_frames) as f32 / n_interaction as f32 * 100.0
+        );
+    }
+
+    log::info!(
+        "[benchmark_tcp_server_io_pool] receive {n_iter} frames from {n_clients} clients ({n_interaction} total) with pool {pool_size:?}: {}s, ({}ms per frame), server threads: {server_threads}",
+        duration.as_secs_f32(),
+        (duration.as_secs_f64() / n_received_frames as f64 * 1_000.0) as f32
+    )
+}
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use maviola::fuzz::fuzz_incoming;
+use maviola::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    let report = fuzz_incoming::<Versionless>(data);
+    assert!(report.trailing() <= data.len());
+
+    let report = fuzz_incoming::<V2>(data);
+    assert!(report.trailing() <= data.len());
+});
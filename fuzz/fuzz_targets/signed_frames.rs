@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use maviola::dialects::Common;
+use maviola::fuzz::{FrameFuzzer, FuzzOutcome};
+use maviola::prelude::*;
+use maviola::protocol::{
+    CompatProcessor, CompatStrategy, IncompatFlags, KnownDialects, SignStrategy,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let signer = FrameSigner::builder()
+        .link_id(1)
+        .key("fuzz")
+        .incoming(SignStrategy::Strict)
+        .outgoing(SignStrategy::ReSign)
+        .build();
+    let compat = CompatProcessor::builder()
+        .incompat_flags(IncompatFlags::MAVLINK_IFLAG_SIGNED)
+        .incoming(CompatStrategy::Reject)
+        .outgoing(CompatStrategy::Enforce)
+        .build();
+
+    let fuzzer = FrameFuzzer::<V2>::new()
+        .with_dialects(KnownDialects::new().with_dialect(Common::spec()))
+        .with_signer(signer)
+        .with_compat(compat);
+
+    for outcome in fuzzer.feed(data).outcomes() {
+        // Signing is strict, so only frames with valid signatures are accepted
+        if let FuzzOutcome::Accepted { frame, .. } = outcome {
+            assert!(frame.is_signed());
+        }
+    }
+});
@@ -1,6 +1,5 @@
 use std::fs::remove_file;
 use std::path::PathBuf;
-use std::time::Duration;
 
 use maviola::dialects::minimal as dialect;
 
@@ -76,6 +75,8 @@ async fn main() {
 #[cfg(test)]
 #[tokio::test]
 async fn async_file_rw() {
+    use std::time::Duration;
+
     let path = PathBuf::from("/tmp/maviola_async_file_rw.bin");
     if path.exists() {
         remove_file(path.as_path()).unwrap();
@@ -39,44 +39,40 @@ fn make_authorized_client(address: &str, link_id: SignedLinkId, key: &str) -> Re
     Ok(authorised_client)
 }
 
-fn server_receive_unsigned_and_respond_signed(server: EdgeNode<V2>) -> Result<()> {
-    for event in server.events() {
-        match event {
-            Event::Frame(frame, callback) => {
-                assert!(
-                    frame.is_signed(),
-                    "frame should be signed by server upon receiving"
-                );
-                log::info!(
-                    "[server] received signed frame with link ID: {}",
-                    frame.link_id().unwrap()
-                );
-                callback.broadcast(&frame).unwrap();
-                log::info!("[server] respond with signed frames to everyone but sender");
-                break;
-            }
-            _ => {}
+fn server_receive_unsigned_and_respond_signed(
+    events: impl Iterator<Item = Event<V2>>,
+) -> Result<()> {
+    for event in events {
+        if let Event::Frame(frame, callback) = event {
+            assert!(
+                frame.is_signed(),
+                "frame should be signed by server upon receiving"
+            );
+            log::info!(
+                "[server] received signed frame with link ID: {}",
+                frame.link_id().unwrap()
+            );
+            callback.broadcast(&frame).unwrap();
+            log::info!("[server] respond with signed frames to everyone but sender");
+            break;
         }
     }
 
     Ok(())
 }
 
-fn authorized_client_receive_signed(authorized_client: EdgeNode<V2>) -> Result<()> {
-    for event in authorized_client.events() {
-        match event {
-            Event::Frame(frame, _) => {
-                assert!(
-                    frame.is_signed(),
-                    "authorized client should receive signed frame"
-                );
-                log::info!(
-                    "[authorised_client] received signed frame with link ID: {}",
-                    frame.link_id().unwrap()
-                );
-                break;
-            }
-            _ => {}
+fn authorized_client_receive_signed(events: impl Iterator<Item = Event<V2>>) -> Result<()> {
+    for event in events {
+        if let Event::Frame(frame, _) = event {
+            assert!(
+                frame.is_signed(),
+                "authorized client should receive signed frame"
+            );
+            log::info!(
+                "[authorised_client] received signed frame with link ID: {}",
+                frame.link_id().unwrap()
+            );
+            break;
         }
     }
 
@@ -92,11 +88,16 @@ fn run() -> Result<()> {
     let unauthorized_client = make_unauthorized_client(addr.as_str())?;
     let authorized_client = make_authorized_client(addr.as_str(), link_id, key)?;
 
+    // Subscribe to events before sending, since new receivers get only events emitted after they
+    // were created
+    let server_events = server.events();
+    let authorized_events = authorized_client.events();
+
     unauthorized_client.send(&Heartbeat::default()).unwrap();
     log::info!("[unauthorized_client] send unsigned frame");
 
-    server_receive_unsigned_and_respond_signed(server)?;
-    authorized_client_receive_signed(authorized_client)?;
+    server_receive_unsigned_and_respond_signed(server_events)?;
+    authorized_client_receive_signed(authorized_events)?;
 
     log::warn!("[all] finished");
     Ok(())
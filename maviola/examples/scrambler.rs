@@ -45,8 +45,8 @@ impl<V: MaybeVersioned> UpdateFrameUnsafe<V> for Scrambler {
         _: &mut Checksum,
         _: &mut Option<Signature>,
     ) {
-        for i in 0..payload.len() {
-            payload[i] = payload[i] ^ 0xff;
+        for byte in payload.iter_mut() {
+            *byte ^= 0xff;
         }
     }
 }
@@ -8,7 +8,32 @@ pub(crate) const NETWORK_RETRY_EVENTS_CHAN_CAPACITY: usize = 1024 * 32;
 pub(crate) const CHANNEL_STOP_POOLING_INTERVAL: Duration = Duration::from_micros(100);
 pub(crate) const CHANNEL_STOP_JOIN_POOLING_INTERVAL: Duration = Duration::from_millis(100);
 pub(crate) const CHANNEL_STOP_JOIN_ATTEMPTS: usize = 30;
+pub(crate) const CHANNEL_PAUSE_POOLING_INTERVAL: Duration = Duration::from_millis(5);
 
-pub(crate) const EVENTS_RECV_POOLING_INTERVAL: Duration = Duration::from_millis(1);
+pub(crate) const EVENTS_STATE_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+pub(crate) const CONN_EVENTS_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+pub(crate) const RECONNECT_RELAY_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+pub(crate) const TLOG_RELAY_POOLING_INTERVAL: Duration = Duration::from_millis(10);
 
 pub(crate) const CONN_STOP_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+pub(crate) const UDP_FAILOVER_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+pub(crate) const TCP_FAILOVER_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+pub(crate) const TCP_FAILBACK_POOLING_INTERVAL: Duration = Duration::from_millis(50);
+
+pub(crate) const HOST_RESOLUTION_POOLING_INTERVAL: Duration = Duration::from_millis(50);
+
+#[cfg(feature = "zmq")]
+pub(crate) const ZMQ_PIPE_CAPACITY: usize = 1024 * 32;
+
+#[cfg(feature = "mqtt")]
+pub(crate) const MQTT_PIPE_CAPACITY: usize = 1024 * 32;
+
+#[cfg(feature = "nats")]
+pub(crate) const NATS_PIPE_CAPACITY: usize = 1024 * 32;
+
+#[cfg(feature = "redis")]
+pub(crate) const REDIS_PIPE_CAPACITY: usize = 1024 * 32;
+
+pub(crate) const SHUTDOWN_FLUSH_POOLING_INTERVAL: Duration = Duration::from_millis(5);
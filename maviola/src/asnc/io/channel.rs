@@ -1,24 +1,32 @@
-use tokio::io::{AsyncRead, AsyncWrite};
+use crate::asnc::runtime;
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
 
 use crate::asnc::consts::{
-    CHANNEL_STOP_JOIN_ATTEMPTS, CHANNEL_STOP_JOIN_POOLING_INTERVAL, CHANNEL_STOP_POOLING_INTERVAL,
+    CHANNEL_PAUSE_POOLING_INTERVAL, CHANNEL_STOP_JOIN_ATTEMPTS, CHANNEL_STOP_JOIN_POOLING_INTERVAL,
+    CHANNEL_STOP_POOLING_INTERVAL,
 };
 use crate::asnc::io::{IncomingFrameProducer, OutgoingFrameHandler, OutgoingFrameSender};
 use crate::core::io::{AsyncReceiver, AsyncSender, IncomingFrame};
-use crate::core::io::{ChannelInfo, ConnectionInfo};
-use crate::core::utils::{Closable, SharedCloser};
+use crate::core::io::{
+    Captured, ChannelInfo, ConnectionEvent, ConnectionInfo, EgressQueue, FlushTracker, PauseSwitch,
+    SharedTap, Tapped,
+};
+use crate::core::node::ValidationReport;
+use crate::core::utils::{trace, Closable, SharedCloser};
 
 use crate::prelude::*;
 
 /// <sup>[`async`](crate::asnc)</sup>
 /// Factory that produces a channels withing associated [`AsyncConnection`](super::Connection).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ChannelFactory<V: MaybeVersioned> {
     pub(crate) info: ConnectionInfo,
     pub(crate) state: Closable,
     pub(crate) sender: OutgoingFrameSender<V>,
     pub(crate) send_handler: OutgoingFrameHandler<V>,
     pub(crate) producer: IncomingFrameProducer<V>,
+    pub(crate) events: mpsc::UnboundedSender<ConnectionEvent>,
 }
 
 impl<V: MaybeVersioned> ChannelFactory<V> {
@@ -37,6 +45,10 @@ impl<V: MaybeVersioned> ChannelFactory<V> {
             writer,
             send_handler: self.send_handler.clone(),
             producer: self.producer.clone(),
+            events: self.events.clone(),
+            tracker: self.sender.flush_tracker().clone(),
+            tap: self.info.tap().cloned(),
+            pause: self.info.pause_switch().clone(),
         }
     }
 
@@ -64,6 +76,10 @@ impl<V: MaybeVersioned> ChannelFactory<V> {
     pub fn send_handler(&mut self) -> &mut OutgoingFrameHandler<V> {
         &mut self.send_handler
     }
+
+    pub(in crate::asnc) fn event_sender(&self) -> &mpsc::UnboundedSender<ConnectionEvent> {
+        &self.events
+    }
 }
 
 /// <sup>[`async`](crate::asnc)</sup>
@@ -78,6 +94,10 @@ pub struct Channel<V: MaybeVersioned, R: AsyncRead, W: AsyncWrite> {
     writer: W,
     send_handler: OutgoingFrameHandler<V>,
     producer: IncomingFrameProducer<V>,
+    events: mpsc::UnboundedSender<ConnectionEvent>,
+    tracker: FlushTracker,
+    tap: Option<SharedTap>,
+    pause: PauseSwitch,
 }
 
 impl<
@@ -99,35 +119,49 @@ impl<
         let info = self.info;
         let conn_state = self.conn_state;
         let state = SharedCloser::new();
+        let channel_guard = self.tracker.open_channel();
+        let span = trace::channel_span(&info);
 
         log::trace!("[{info:?}] spawning connection channel");
+        let events = self.events;
+        _ = events.send(ConnectionEvent::ChannelOpened(info.clone()));
 
         let write_handler = {
+            let handler_state =
+                HandlerState::new(self.pause.clone(), state.clone(), conn_state.clone());
             let info = info.clone();
             let send_handler = self.send_handler;
-            let frame_writer = AsyncSender::new(self.writer);
+            let frame_writer =
+                AsyncSender::new(Tapped::new(self.writer, self.tap.clone(), info.clone()));
 
-            tokio::spawn(async move { Self::write_handler(info, send_handler, frame_writer).await })
+            let task = async move {
+                Self::write_handler(info, handler_state, send_handler, frame_writer).await
+            };
+            runtime::spawn(trace::instrument(task, span.clone()))
         };
 
         let read_handler = {
-            let conn_state = conn_state.clone();
-            let state = state.clone();
+            let handler_state = HandlerState::new(self.pause, state.clone(), conn_state.clone());
             let info = info.clone();
             let producer = self.producer;
-            let frame_reader = AsyncReceiver::new(self.reader);
+            let events = events.clone();
+            let reader = Captured::new(Tapped::new(self.reader, self.tap, info.clone()));
 
-            tokio::spawn(async move {
-                Self::read_handler(state, conn_state, info, producer, frame_reader).await
-            })
+            let task = async move {
+                Self::read_handler(info, handler_state, producer, events, reader).await
+            };
+            runtime::spawn(trace::instrument(task, span.clone()))
         };
 
         {
             let info = info.clone();
             let state = state.clone();
-            tokio::spawn(async move {
-                Self::handle_stop(state, conn_state, info, write_handler, read_handler).await;
-            });
+            let task = async move {
+                Self::handle_stop(state, conn_state, info, events, write_handler, read_handler)
+                    .await;
+                drop(channel_guard);
+            };
+            runtime::spawn(trace::instrument(task, span));
         }
 
         state.clone()
@@ -135,23 +169,35 @@ impl<
 
     async fn write_handler(
         info: ChannelInfo,
+        state: HandlerState,
         mut send_handler: OutgoingFrameHandler<V>,
-        mut frame_writer: AsyncSender<W, V>,
+        mut frame_writer: AsyncSender<Tapped<W>, V>,
     ) -> Result<()> {
+        let mut queue = EgressQueue::new();
+
         loop {
-            let out_frame = match send_handler.recv().await {
-                Ok(out_frame) => out_frame,
-                Err(err) => {
-                    frame_writer.flush().await.map_err(Error::from)?;
-                    return Err(Error::from(err));
+            if queue.is_empty() {
+                match send_handler.recv().await {
+                    Ok(out_frame) if out_frame.should_send_to(info.id()) => queue.push(out_frame),
+                    Ok(_) => continue,
+                    Err(err) => {
+                        frame_writer.flush().await.map_err(Error::from)?;
+                        return Err(Error::from(err));
+                    }
                 }
-            };
-
-            if !out_frame.should_send_to(info.id()) {
-                continue;
             }
+            queue.fill(info.id(), || send_handler.try_recv());
+
+            let out_frame = match queue.pop() {
+                Some(out_frame) => out_frame,
+                None => continue,
+            };
 
             log::trace!("[{info:?}] received outgoing frame from API");
+            // Handler could wait for outgoing frames, when connection was paused
+            if !state.wait_resumed().await {
+                return Ok(());
+            }
             loop {
                 if let Err(err) = frame_writer.send(out_frame.frame()).await {
                     let err = Error::from(err);
@@ -161,6 +207,8 @@ impl<
                         }
                         return Err(Error::Io(err));
                     }
+                } else {
+                    out_frame.record_written(info.connection_id());
                 }
                 log::trace!("[{info:?}] written outgoing frame");
                 break;
@@ -169,18 +217,18 @@ impl<
     }
 
     async fn read_handler(
-        state: SharedCloser,
-        conn_state: Closable,
         info: ChannelInfo,
+        state: HandlerState,
         producer: IncomingFrameProducer<V>,
-        mut frame_reader: AsyncReceiver<R, V>,
+        events: mpsc::UnboundedSender<ConnectionEvent>,
+        mut reader: Captured<Tapped<R>>,
     ) -> Result<()> {
         loop {
-            if conn_state.is_closed() || state.is_closed() {
+            if state.is_closed() || !state.wait_resumed().await {
                 return Ok(());
             }
 
-            let frame = match frame_reader.recv().await {
+            let frame = match AsyncReceiver::new::<V>(&mut reader).recv().await {
                 Ok(frame) => frame,
                 Err(err) => {
                     let err = Error::from(err);
@@ -194,7 +242,16 @@ impl<
                 }
             };
             log::trace!("[{info:?}] received incoming frame");
+            // Handler could wait for incoming frames, when connection was paused
+            if !state.wait_resumed().await {
+                return Ok(());
+            }
 
+            if let Some(skipped) = reader.take_skipped(&frame) {
+                log::trace!("[{info:?}] skipped {} malformed bytes", skipped.len());
+                let report = ValidationReport::malformed::<V>(skipped);
+                _ = events.send(ConnectionEvent::Malformed(info.clone(), report));
+            }
             producer.send(IncomingFrame::new(frame, info.clone()))?;
             log::trace!("[{info:?}] sent incoming frame to API");
         }
@@ -204,23 +261,25 @@ impl<
         mut state: SharedCloser,
         conn_state: Closable,
         info: ChannelInfo,
-        write_handler: tokio::task::JoinHandle<Result<()>>,
-        read_handler: tokio::task::JoinHandle<Result<()>>,
+        events: mpsc::UnboundedSender<ConnectionEvent>,
+        write_handler: runtime::JoinHandle<Result<()>>,
+        read_handler: runtime::JoinHandle<Result<()>>,
     ) {
         while !(state.is_closed()
             || conn_state.is_closed()
             || write_handler.is_finished()
             || read_handler.is_finished())
         {
-            tokio::time::sleep(CHANNEL_STOP_POOLING_INTERVAL).await;
+            runtime::sleep(CHANNEL_STOP_POOLING_INTERVAL).await;
         }
+        _ = events.send(ConnectionEvent::ChannelClosed(info.clone()));
         state.close();
 
         for i in 0..CHANNEL_STOP_JOIN_ATTEMPTS {
             if write_handler.is_finished() && read_handler.is_finished() {
                 break;
             }
-            tokio::time::sleep(CHANNEL_STOP_JOIN_POOLING_INTERVAL).await;
+            runtime::sleep(CHANNEL_STOP_JOIN_POOLING_INTERVAL).await;
             if i == CHANNEL_STOP_JOIN_ATTEMPTS - 1 {
                 log::warn!(
                     "[{info:?}] write/read handlers are stuck, finished: write={}, read={}",
@@ -244,3 +303,38 @@ impl<
         log::trace!("[{info:?}] handlers stopped");
     }
 }
+
+/// State of a channel shared by its read and write handlers.
+struct HandlerState {
+    pause: PauseSwitch,
+    state: SharedCloser,
+    conn_state: Closable,
+}
+
+impl HandlerState {
+    fn new(pause: PauseSwitch, state: SharedCloser, conn_state: Closable) -> Self {
+        Self {
+            pause,
+            state,
+            conn_state,
+        }
+    }
+
+    /// Returns `true`, if channel or connection is closed.
+    fn is_closed(&self) -> bool {
+        self.state.is_closed() || self.conn_state.is_closed()
+    }
+
+    /// Waits, while connection is paused.
+    ///
+    /// Returns `false`, if channel or connection was closed while waiting.
+    async fn wait_resumed(&self) -> bool {
+        while self.pause.is_paused() {
+            if self.is_closed() {
+                return false;
+            }
+            runtime::sleep(CHANNEL_PAUSE_POOLING_INTERVAL).await;
+        }
+        true
+    }
+}
@@ -1,28 +1,103 @@
 use std::fmt::Debug;
 use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
 
-use async_trait::async_trait;
-use tokio::task::JoinHandle;
+use crate::asnc::runtime;
+use crate::asnc::runtime::JoinHandle;
+use tokio::sync::mpsc;
 
 use crate::asnc::consts::CONN_STOP_POOLING_INTERVAL;
 use crate::asnc::io::{
-    incoming_channel, outgoing_channel, ChannelFactory, IncomingFrameReceiver, OutgoingFrameSender,
+    incoming_channel, outgoing_channel, ChannelFactory, IncomingFrameProducer,
+    IncomingFrameReceiver, OutgoingFrameSender,
 };
 use crate::asnc::marker::AsyncConnConf;
-use crate::core::io::{ConnectionConf, ConnectionInfo};
+use crate::core::io::{ConnectionConf, ConnectionEvent, ConnectionInfo};
+use crate::core::network::NetworkHandle;
 use crate::core::utils::{Closable, SharedCloser};
+use crate::error::ConfigDiagnostic;
 
 use crate::prelude::*;
 
 /// <sup>[`async`](crate::asnc)</sup>
 /// Connection builder used to create a [`Connection`].
-#[async_trait]
+///
+/// Builders are implemented with native `async fn`, neither boxing nor `async_trait` is required.
+///
+/// # Usage
+///
+/// ```rust
+/// # #[tokio::main] async fn main() {
+/// # #[cfg(feature = "unstable")] {
+/// use maviola::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+/// use maviola::asnc::marker::AsyncConnConf;
+/// use maviola::core::io::{ChannelDetails, ConnectionConf, ConnectionDetails, ConnectionInfo};
+/// use maviola::core::utils::SharedCloser;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// /// Transport, that returns outgoing frames back to the node.
+/// #[derive(Clone, Debug)]
+/// struct Loopback(ConnectionInfo);
+///
+/// impl ConnectionConf for Loopback {
+///     fn info(&self) -> &ConnectionInfo {
+///         &self.0
+///     }
+/// }
+///
+/// impl<V: MaybeVersioned> ConnectionBuilder<V> for Loopback {
+///     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+///         let (connection, chan_factory) = Connection::new(self.0.clone(), SharedCloser::new());
+///
+///         let (outgoing, incoming) = tokio::io::duplex(1024);
+///         let (reader, _) = tokio::io::split(incoming);
+///         let (_, writer) = tokio::io::split(outgoing);
+///
+///         let chan_info = connection.info().make_channel_info(ChannelDetails::Custom {
+///             conn_name: "loopback".to_string(),
+///             channel_name: "loopback".to_string(),
+///             details: String::new(),
+///         });
+///         let channel_state = chan_factory.build(chan_info, reader, writer).spawn().await;
+///
+///         Ok((connection, ConnectionHandler::spawn_from_state(channel_state)))
+///     }
+///
+///     fn to_conf(&self) -> AsyncConnConf<V> {
+///         AsyncConnConf::new(self.clone())
+///     }
+/// }
+///
+/// let mut node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(Loopback(ConnectionInfo::new(ConnectionDetails::Custom {
+///         name: "loopback".to_string(),
+///         details: String::new(),
+///     })))
+///     .build().await.unwrap();
+///
+/// node.send(&maviola::dialects::minimal::messages::Heartbeat::default()).unwrap();
+/// let (frame, _) = node
+///     .recv_frame_timeout(std::time::Duration::from_secs(1))
+///     .await
+///     .unwrap();
+/// assert_eq!(frame.system_id(), 1);
+/// # }}
+/// ```
+///
+/// Returned future has to be [`Send`], which means that builder should be [`Sync`], if it is used
+/// across `.await` points.
+///
+/// Builders are stored by nodes as [`DynConnectionBuilder`] trait objects, which is implemented
+/// for all connection builders.
 pub trait ConnectionBuilder<V: MaybeVersioned>: ConnectionConf {
     /// Builds connection from provided configuration.
     ///
     /// Returns the new connection and its main handler. Once handler is finished, the connection
     /// is considered to be closed.
-    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)>;
+    fn build(&self) -> impl Future<Output = Result<(Connection<V>, ConnectionHandler)>> + Send;
 
     /// Converts connection builder to [`AsyncConnConf`]
     fn to_conf(&self) -> AsyncConnConf<V>;
@@ -33,6 +108,56 @@ pub trait ConnectionBuilder<V: MaybeVersioned>: ConnectionConf {
     fn is_repairable(&self) -> bool {
         false
     }
+
+    /// Problems found in connection configuration, that prevent connection from working properly.
+    ///
+    /// Nodes are validated against these diagnostics before being built. A blanket implementation
+    /// returns no diagnostics.
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Vec::new()
+    }
+}
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Object-safe counterpart of [`ConnectionBuilder`].
+///
+/// Implemented for all connection builders. Allows to store builders of different transports as
+/// trait objects. Connection future is boxed once per connection build, frames are not affected.
+pub trait DynConnectionBuilder<V: MaybeVersioned>: ConnectionConf {
+    /// Builds connection from provided configuration.
+    ///
+    /// See [`ConnectionBuilder::build`].
+    fn build(&self) -> Pin<Box<dyn Future<Output = BuildResult<V>> + Send + '_>>;
+
+    /// Converts connection builder to [`AsyncConnConf`].
+    fn to_conf(&self) -> AsyncConnConf<V>;
+
+    /// If `true`, then this connection can be safely restored on failure.
+    fn is_repairable(&self) -> bool;
+
+    /// Problems found in connection configuration, that prevent connection from working properly.
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic>;
+}
+
+/// Result of a connection build.
+type BuildResult<V> = Result<(Connection<V>, ConnectionHandler)>;
+
+impl<V: MaybeVersioned, B: ConnectionBuilder<V>> DynConnectionBuilder<V> for B {
+    fn build(&self) -> Pin<Box<dyn Future<Output = BuildResult<V>> + Send + '_>> {
+        Box::pin(ConnectionBuilder::build(self))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        ConnectionBuilder::to_conf(self)
+    }
+
+    fn is_repairable(&self) -> bool {
+        ConnectionBuilder::is_repairable(self)
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        ConnectionBuilder::diagnostics(self)
+    }
 }
 
 /// <sup>[`async`](crate::asnc)</sup>
@@ -42,7 +167,11 @@ pub struct Connection<V: MaybeVersioned> {
     info: ConnectionInfo,
     sender: OutgoingFrameSender<V>,
     receiver: IncomingFrameReceiver<V>,
+    producer: IncomingFrameProducer<V>,
+    events: Mutex<Option<mpsc::UnboundedReceiver<ConnectionEvent>>>,
+    event_sender: mpsc::UnboundedSender<ConnectionEvent>,
     state: SharedCloser,
+    network: Option<NetworkHandle<V, AsyncConnConf<V>>>,
 }
 
 /// <sup>[`async`](crate::asnc)</sup>
@@ -58,7 +187,7 @@ impl ConnectionHandler {
         F: Future<Output = Result<()>> + Send + 'static,
     {
         Self {
-            inner: tokio::spawn(task),
+            inner: runtime::spawn(task),
         }
     }
 
@@ -66,7 +195,7 @@ impl ConnectionHandler {
     pub fn spawn_from_state(state: SharedCloser) -> Self {
         Self::spawn(async move {
             while !state.is_closed() {
-                tokio::time::sleep(CONN_STOP_POOLING_INTERVAL).await;
+                runtime::sleep(CONN_STOP_POOLING_INTERVAL).await;
             }
             Ok(())
         })
@@ -75,9 +204,13 @@ impl ConnectionHandler {
     pub(crate) fn handle<V: MaybeVersioned>(self, conn: &Connection<V>) {
         let mut state = conn.state.clone();
         let info = conn.info.clone();
+        let events = conn.event_sender.clone();
 
-        tokio::task::spawn(async move {
+        runtime::spawn(async move {
             let result = self.inner.await;
+            if !state.is_closed() {
+                _ = events.send(ConnectionEvent::Lost);
+            }
             state.close();
 
             match result {
@@ -96,12 +229,17 @@ impl<V: MaybeVersioned> Connection<V> {
     pub fn new(info: ConnectionInfo, state: SharedCloser) -> (Self, ChannelFactory<V>) {
         let (sender, send_handler) = outgoing_channel(state.to_closable());
         let (producer, receiver) = incoming_channel();
+        let (event_sender, events) = mpsc::unbounded_channel();
 
         let connection = Self {
             info,
             sender: sender.clone(),
             receiver,
+            producer: producer.clone(),
+            events: Mutex::new(Some(events)),
+            event_sender: event_sender.clone(),
             state,
+            network: None,
         };
 
         let builder = ChannelFactory {
@@ -110,6 +248,7 @@ impl<V: MaybeVersioned> Connection<V> {
             sender,
             send_handler,
             producer,
+            events: event_sender,
         };
 
         (connection, builder)
@@ -136,6 +275,37 @@ impl<V: MaybeVersioned> Connection<V> {
         self.receiver.clone()
     }
 
+    /// Producer of incoming frames, that allows to inject frames into the connection.
+    pub(in crate::asnc) fn producer(&self) -> &IncomingFrameProducer<V> {
+        &self.producer
+    }
+
+    /// Takes receiver of connection lifecycle events.
+    ///
+    /// Receiver holds all events since connection was created, so it can be taken only once.
+    /// Returns [`None`] for subsequent calls and for [reused](Self::reuse) connections, since
+    /// events are reported to the original connection.
+    pub(in crate::asnc) fn take_events(&self) -> Option<mpsc::UnboundedReceiver<ConnectionEvent>> {
+        match self.events.lock() {
+            Ok(mut events) => events.take(),
+            Err(err) => err.into_inner().take(),
+        }
+    }
+
+    /// Binds connection to a [`Network`], that can be managed through a [`NetworkHandle`].
+    pub(in crate::asnc) fn with_network(
+        mut self,
+        handle: NetworkHandle<V, AsyncConnConf<V>>,
+    ) -> Self {
+        self.network = Some(handle);
+        self
+    }
+
+    /// Handle of a [`Network`], if this is a network connection.
+    pub(in crate::asnc) fn network_handle(&self) -> Option<&NetworkHandle<V, AsyncConnConf<V>>> {
+        self.network.as_ref()
+    }
+
     pub(in crate::asnc) fn reuse(&self) -> Connection<V> {
         let mut state = SharedCloser::new();
 
@@ -143,14 +313,18 @@ impl<V: MaybeVersioned> Connection<V> {
             info: self.info.clone(),
             sender: self.sender.clone(),
             receiver: self.receiver.clone(),
+            producer: self.producer.clone(),
+            events: Mutex::new(None),
+            event_sender: self.event_sender.clone(),
             state: state.clone(),
+            network: self.network.clone(),
         };
 
         let parent_state = self.state.to_closable();
 
-        tokio::task::spawn(async move {
+        runtime::spawn(async move {
             while !parent_state.is_closed() && !state.is_closed() {
-                tokio::time::sleep(CONN_STOP_POOLING_INTERVAL).await;
+                runtime::sleep(CONN_STOP_POOLING_INTERVAL).await;
             }
             state.close();
         });
@@ -172,3 +346,18 @@ impl<V: MaybeVersioned> Drop for Connection<V> {
         self.close();
     }
 }
+
+/// Forwards pending lifecycle events from one connection to another.
+pub(in crate::asnc) fn forward_events(
+    receiver: Option<&mut mpsc::UnboundedReceiver<ConnectionEvent>>,
+    sender: &mpsc::UnboundedSender<ConnectionEvent>,
+) {
+    let receiver = match receiver {
+        Some(receiver) => receiver,
+        None => return,
+    };
+
+    while let Ok(event) = receiver.try_recv() {
+        _ = sender.send(event);
+    }
+}
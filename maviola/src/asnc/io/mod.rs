@@ -8,17 +8,28 @@
 //! I/O is based on two main abstraction: connections and channels. [`Connection`] represents an
 //! interface to an underlying transport, while [`Channel`] is an individual stream withing a
 //! connection. Connections are created by implementors of [`ConnectionBuilder`] trait and channels
-//! are constructed by [`ChannelFactory`] which is bounded to a particular connection.
+//! are constructed by [`ChannelFactory`] which is bounded to a particular connection. Connection
+//! builders are implemented with native `async fn` and stored by nodes as [`DynConnectionBuilder`]
+//! trait objects.
 //!
 //! In most cases channels and connections are hidden to library user. Dealing with these
 //! abstractions is necessary only to those who are interested in creating custom connections.
+//!
+//! ## Transport extensions
+//!
+//! [`TcpHandshake`] allows to authenticate clients of a [`TcpServer`](crate::core::io::TcpServer)
+//! before they are attached to a connection.
 
 mod bus;
 mod channel;
 mod connection;
 mod transport;
 
+pub(crate) use transport::DynTcpHandshake;
+pub use transport::TcpHandshake;
+
 pub(super) use bus::{incoming_channel, outgoing_channel};
+pub(super) use connection::forward_events;
 
 /// <sup>`⍚` |</sup>
 #[cfg(feature = "unstable")]
@@ -30,7 +41,7 @@ pub use bus::{
 pub use channel::{Channel, ChannelFactory};
 /// <sup>`⍚` |</sup>
 #[cfg(feature = "unstable")]
-pub use connection::{Connection, ConnectionBuilder, ConnectionHandler};
+pub use connection::{Connection, ConnectionBuilder, ConnectionHandler, DynConnectionBuilder};
 
 #[cfg(not(feature = "unstable"))]
 pub(in crate::asnc) use bus::{
@@ -39,4 +50,6 @@ pub(in crate::asnc) use bus::{
 #[cfg(not(feature = "unstable"))]
 pub(in crate::asnc) use channel::ChannelFactory;
 #[cfg(not(feature = "unstable"))]
-pub(in crate::asnc) use connection::{Connection, ConnectionBuilder, ConnectionHandler};
+pub use connection::Connection;
+#[cfg(not(feature = "unstable"))]
+pub(in crate::asnc) use connection::{ConnectionBuilder, ConnectionHandler, DynConnectionBuilder};
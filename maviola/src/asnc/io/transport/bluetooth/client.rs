@@ -0,0 +1,43 @@
+use tokio::net::UnixStream;
+
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::io::{rfcomm, BluetoothClient, ChannelDetails};
+use crate::core::utils::SharedCloser;
+use crate::error::SyncError;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for BluetoothClient {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let (addr, channel) = (self.addr, self.channel);
+        // BlueZ connects RFCOMM sockets synchronously, so connection is established in a
+        // blocking task
+        let stream = runtime::spawn_blocking(move || rfcomm::connect(addr, channel))
+            .await
+            .map_err(|err| Error::from(SyncError::ThreadJoin(err.to_string())))??;
+        stream.set_nonblocking(true)?;
+        let (reader, writer) = UnixStream::from_std(stream)?.into_split();
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::BluetoothClient { addr, channel });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = channel.spawn().await;
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
@@ -1,4 +1,3 @@
-use async_trait::async_trait;
 use tokio::fs::File;
 use tokio::io::BufReader;
 
@@ -10,7 +9,6 @@ use crate::core::utils::SharedCloser;
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for FileReader {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let path = self.path.clone();
@@ -1,7 +1,23 @@
 //! # 🔒 Asynchronous transport implementations
 
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+mod bluetooth;
 mod file;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis;
+mod resolution;
 #[cfg(unix)]
 mod sock;
+mod spec;
 mod tcp;
+mod tlog;
 mod udp;
+#[cfg(feature = "zmq")]
+mod zmq;
+
+pub(crate) use tcp::DynTcpHandshake;
+pub use tcp::TcpHandshake;
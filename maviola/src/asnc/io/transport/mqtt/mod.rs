@@ -0,0 +1,181 @@
+mod bridge;
+mod stream;
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod mqtt_tests {
+    use std::time::Duration;
+
+    use mavio::io::Sender;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use crate::asnc::runtime;
+    use crate::core::io::mqtt_wire::{self, MqttDecoder, MqttPacket};
+    use crate::core::io::{ChannelDetails, MqttBridge};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    use crate::asnc::prelude::*;
+    use crate::prelude::*;
+
+    const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+    async fn next_packet(stream: &mut TcpStream, decoder: &mut MqttDecoder) -> MqttPacket {
+        let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+        loop {
+            if let Some(packet) = decoder.next().unwrap() {
+                return packet;
+            }
+            let bytes_read = stream.read(&mut buffer).await.unwrap();
+            assert!(bytes_read > 0, "client disconnected");
+            decoder.push(&buffer[..bytes_read]);
+        }
+    }
+
+    /// Accepts a single client, publishes `frame` and returns topic of the first client message.
+    async fn run_broker(listener: TcpListener, frame: Vec<u8>) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut decoder = MqttDecoder::default();
+
+        assert_eq!(
+            next_packet(&mut stream, &mut decoder).await,
+            MqttPacket::Other(0x10)
+        );
+        stream.write_all(&[0x20, 2, 0, 0]).await.unwrap();
+        assert_eq!(
+            next_packet(&mut stream, &mut decoder).await,
+            MqttPacket::Other(0x82)
+        );
+        stream.write_all(&[0x90, 3, 0, 1, 0]).await.unwrap();
+
+        stream
+            .write_all(&mqtt_wire::publish("gcs/mavlink", &frame).unwrap())
+            .await
+            .unwrap();
+
+        loop {
+            if let MqttPacket::Publish { topic, .. } = next_packet(&mut stream, &mut decoder).await
+            {
+                return topic;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn bridge_publishes_and_receives() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let broker_addr = listener.local_addr().unwrap();
+
+        let frame = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let mut frame_bytes = Vec::new();
+        Sender::versioned(&mut frame_bytes, V2)
+            .send(&frame)
+            .unwrap();
+        let broker = runtime::spawn(run_broker(listener, frame_bytes));
+
+        let mut node = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                MqttBridge::new(&format!("mqtt://{broker_addr}"))
+                    .unwrap()
+                    .with_subscription("gcs/mavlink"),
+            )
+            .build()
+            .await
+            .unwrap();
+
+        let (frame, callback) = node.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert!(matches!(
+            callback.info().details(),
+            ChannelDetails::MqttBridge { broker_addr: addr, .. } if *addr == broker_addr
+        ));
+
+        node.send(&Heartbeat::default()).unwrap();
+        let topic = runtime::timeout(RECV_TIMEOUT, broker)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topic, "mavlink/1/1/0");
+    }
+
+    #[cfg(feature = "definitions")]
+    #[tokio::test]
+    async fn bridge_receives_json() {
+        use mavinspect::protocol::builders::{MessageBuilder, MessageFieldBuilder};
+        use mavinspect::protocol::MavType;
+        use mavinspect::utils::Builder;
+
+        use crate::core::io::MqttFormat;
+        use crate::dialects::Minimal;
+        use crate::protocol::MessageDefinitions;
+
+        let fields = [
+            ("type", MavType::UInt8),
+            ("autopilot", MavType::UInt8),
+            ("base_mode", MavType::UInt8),
+            ("custom_mode", MavType::UInt32),
+            ("system_status", MavType::UInt8),
+            ("mavlink_version", MavType::UInt8MavlinkVersion),
+        ];
+        let heartbeat = MessageBuilder::new()
+            .set_id(0)
+            .set_name("HEARTBEAT")
+            .set_fields(
+                fields
+                    .into_iter()
+                    .map(|(name, r#type)| {
+                        MessageFieldBuilder::new()
+                            .set_name(name)
+                            .set_type(r#type)
+                            .build()
+                    })
+                    .collect(),
+            )
+            .build();
+        let definitions = MessageDefinitions::new([heartbeat], []);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let broker_addr = listener.local_addr().unwrap();
+
+        let json = r#"{"system_id":2,"component_id":1,"message_id":0,"fields":{"custom_mode":42,"mavlink_version":3}}"#;
+        let broker = runtime::spawn(run_broker(listener, json.as_bytes().to_vec()));
+
+        let mut node = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                MqttBridge::new(&format!("mqtt://{broker_addr}"))
+                    .unwrap()
+                    .with_subscription("gcs/mavlink")
+                    .with_format(MqttFormat::Json(definitions)),
+            )
+            .build()
+            .await
+            .unwrap();
+
+        let (frame, _) = node.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 2);
+        let heartbeat = frame.decode::<Minimal>().unwrap();
+        assert!(matches!(
+            heartbeat,
+            Minimal::Heartbeat(Heartbeat {
+                custom_mode: 42,
+                mavlink_version: 3,
+                ..
+            })
+        ));
+
+        node.send(&Heartbeat::default()).unwrap();
+        let topic = runtime::timeout(RECV_TIMEOUT, broker)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topic, "mavlink/1/1/0");
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use crate::asnc::runtime::{self, AsyncRead, AsyncWrite};
+use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
+use crate::core::io::mqtt_wire::{self, MqttDecoder, MqttPacket, MqttPublisher, MqttSubscriber};
+use crate::core::io::MqttBridge;
+use crate::core::utils::Closable;
+
+use crate::prelude::*;
+
+/// Establishes MQTT session over an asynchronous `stream` and subscribes to topics.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) async fn handshake<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    conf: &MqttBridge,
+) -> Result<MqttDecoder> {
+    let mut decoder = MqttDecoder::default();
+
+    stream
+        .write_all(&mqtt_wire::connect(&conf.session())?)
+        .await?;
+    mqtt_wire::check_connack(next_packet(stream, &mut decoder).await?)?;
+
+    if !conf.subscriptions.is_empty() {
+        stream
+            .write_all(&mqtt_wire::subscribe(&conf.subscriptions)?)
+            .await?;
+        let packet = loop {
+            match next_packet(stream, &mut decoder).await? {
+                MqttPacket::SubAck(codes) => break MqttPacket::SubAck(codes),
+                packet => log::trace!("packet received before subscription: {packet:?}"),
+            }
+        };
+        mqtt_wire::check_suback(packet, &conf.subscriptions)?;
+    }
+
+    Ok(decoder)
+}
+
+/// Writes frames received from subscribed topics to `pipe` until either side is closed.
+pub(super) async fn relay_published(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: MqttDecoder,
+    mut subscriber: MqttSubscriber,
+    mut pipe: impl AsyncWrite + Unpin,
+) -> Result<()> {
+    let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(packet) = decoder.next()? {
+            if let Some(frame) = subscriber.next_frame(packet) {
+                pipe.write_all(&frame).await?;
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+/// Publishes frames written to `pipe` to a broker.
+pub(super) async fn publish<W: AsyncWrite + Unpin>(
+    mut pipe: impl AsyncRead + Unpin,
+    writer: Arc<Mutex<W>>,
+    mut publisher: MqttPublisher,
+) -> Result<()> {
+    let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+
+    loop {
+        let bytes_read = pipe.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        publisher.push(&buffer[..bytes_read]);
+
+        while let Some(packet) = publisher.next() {
+            writer.lock().await.write_all(&packet).await?;
+        }
+    }
+}
+
+/// Sends `PINGREQ` packets with the specified `interval` until `state` is closed.
+pub(super) async fn ping<W: AsyncWrite + Unpin>(
+    writer: Arc<Mutex<W>>,
+    interval: Duration,
+    state: Closable,
+) -> Result<()> {
+    let mut last_ping = Instant::now();
+
+    while !state.is_closed() {
+        runtime::sleep(SERVER_HANG_UP_TIMEOUT.min(interval)).await;
+        if last_ping.elapsed() < interval {
+            continue;
+        }
+        last_ping = Instant::now();
+
+        writer.lock().await.write_all(&mqtt_wire::PINGREQ).await?;
+    }
+
+    Ok(())
+}
+
+async fn next_packet<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    decoder: &mut MqttDecoder,
+) -> Result<MqttPacket> {
+    let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(packet) = decoder.next()? {
+            return Ok(packet);
+        }
+        let bytes_read = stream.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::asnc::runtime::TcpStream;
+use tokio::sync::Mutex;
+
+use crate::asnc::consts::NATS_PIPE_CAPACITY;
+use crate::asnc::io::transport::nats::stream::{handshake, publish, relay_messages};
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::nats_wire::NatsPublisher;
+use crate::core::io::{ChannelDetails, ConnectionConf, NatsBridge};
+use crate::core::utils::SharedCloser;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for NatsBridge {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let mut stream = TcpStream::connect(self.addr).await?;
+
+        let decoder =
+            match runtime::timeout(TCP_HANDSHAKE_TIMEOUT, handshake(&mut stream, self)).await {
+                Ok(decoder) => decoder?,
+                Err(_) => {
+                    return Err(Error::from(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "handshake timed out",
+                    )))
+                }
+            };
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::NatsBridge {
+                server_addr: self.addr,
+                subject: self.subject.clone(),
+            });
+
+        let (socket_reader, socket_writer) = stream.into_split();
+        let socket_writer = Arc::new(Mutex::new(socket_writer));
+        let (chan_pipe, relay_pipe) = tokio::io::duplex(NATS_PIPE_CAPACITY);
+        let (chan_reader, chan_writer) = tokio::io::split(chan_pipe);
+        let (relay_reader, relay_writer) = tokio::io::split(relay_pipe);
+
+        {
+            let info = self.info().clone();
+            let socket_writer = socket_writer.clone();
+            runtime::spawn(async move {
+                if let Err(err) =
+                    relay_messages(socket_reader, decoder, relay_writer, socket_writer).await
+                {
+                    log::debug!("[{info:?}] subscription failed: {err:?}");
+                }
+            });
+        }
+        {
+            let info = self.info().clone();
+            let publisher = NatsPublisher::new(&self.subject);
+            runtime::spawn(async move {
+                if let Err(err) = publish(relay_reader, socket_writer, publisher).await {
+                    log::debug!("[{info:?}] can't publish to server: {err:?}");
+                }
+            });
+        }
+
+        let channel = chan_factory.build(chan_info, chan_reader, chan_writer);
+        let channel_state = channel.spawn().await;
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
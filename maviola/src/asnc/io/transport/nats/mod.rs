@@ -0,0 +1,102 @@
+mod bridge;
+mod stream;
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod nats_tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use crate::asnc::runtime;
+    use crate::core::io::{ChannelDetails, NatsBridge, Sender};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    use crate::asnc::prelude::*;
+    use crate::prelude::*;
+
+    const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+    /// Accepts a single client, delivers `frame` and returns the first published message.
+    async fn run_server(listener: TcpListener, frame: Vec<u8>) -> (String, Vec<u8>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut line = String::new();
+
+        stream
+            .write_all(b"INFO {\"server_id\":\"test\"}\r\n")
+            .await
+            .unwrap();
+        stream.read_line(&mut line).await.unwrap();
+        assert!(line.starts_with("CONNECT {"));
+        line.clear();
+        stream.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "PING\r\n");
+        stream.write_all(b"PONG\r\n").await.unwrap();
+        line.clear();
+        stream.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "SUB vehicle.1 1\r\n");
+
+        stream.write_all(b"PING\r\n").await.unwrap();
+        let mut message = format!("MSG vehicle.1 1 {}\r\n", frame.len()).into_bytes();
+        message.extend(&frame);
+        message.extend(b"\r\n");
+        stream.write_all(&message).await.unwrap();
+
+        line.clear();
+        stream.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "PONG\r\n");
+
+        line.clear();
+        stream.read_line(&mut line).await.unwrap();
+        let size: usize = line.trim().rsplit_once(' ').unwrap().1.parse().unwrap();
+        let mut payload = vec![0u8; size + 2];
+        stream.read_exact(&mut payload).await.unwrap();
+        payload.truncate(size);
+
+        (line, payload)
+    }
+
+    #[tokio::test]
+    async fn bridge_publishes_and_receives() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = listener.local_addr().unwrap();
+
+        let frame = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let mut frame_bytes = Vec::new();
+        Sender::versioned(&mut frame_bytes, V2)
+            .send(&frame)
+            .unwrap();
+        let server = runtime::spawn(run_server(listener, frame_bytes));
+
+        let mut node = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(NatsBridge::new(&format!("nats://{server_addr}"), "vehicle.1").unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        let (frame, callback) = node.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert!(matches!(
+            callback.info().details(),
+            ChannelDetails::NatsBridge { subject, .. } if subject == "vehicle.1"
+        ));
+
+        node.send(&Heartbeat::default()).unwrap();
+        let (line, payload) = runtime::timeout(RECV_TIMEOUT, server)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(line.starts_with("PUB vehicle.1 "));
+        // Frame of the node: magic byte followed by payload length, ..., system ID
+        assert_eq!(payload[5], 1);
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+use crate::core::io::nats_wire::{self, NatsDecoder, NatsMessage, NatsPublisher};
+use crate::core::io::NatsBridge;
+
+use crate::prelude::*;
+
+/// Establishes NATS session over an asynchronous `stream` and subscribes to the subject.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) async fn handshake<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    conf: &NatsBridge,
+) -> Result<NatsDecoder> {
+    let mut decoder = NatsDecoder::default();
+
+    nats_wire::check_info(next_message(stream, &mut decoder).await?)?;
+    stream
+        .write_all(&nats_wire::connect(&conf.session()))
+        .await?;
+    while !nats_wire::check_connected(next_message(stream, &mut decoder).await?)? {}
+    stream
+        .write_all(&nats_wire::subscribe(&conf.subject))
+        .await?;
+
+    Ok(decoder)
+}
+
+/// Writes frames delivered to the subscription to `pipe` until either side is closed.
+///
+/// Answers server pings using the shared `writer`.
+pub(super) async fn relay_messages<W: AsyncWrite + Unpin>(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: NatsDecoder,
+    mut pipe: impl AsyncWrite + Unpin,
+    writer: Arc<Mutex<W>>,
+) -> Result<()> {
+    let mut buffer = [0u8; nats_wire::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(message) = decoder.next()? {
+            match message {
+                NatsMessage::Msg { payload, .. } => pipe.write_all(&payload).await?,
+                NatsMessage::Ping => writer.lock().await.write_all(nats_wire::PONG).await?,
+                NatsMessage::Err(err) => log::warn!("NATS server error: {err}"),
+                _ => {}
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+/// Publishes frames written to `pipe` to a subject.
+pub(super) async fn publish<W: AsyncWrite + Unpin>(
+    mut pipe: impl AsyncRead + Unpin,
+    writer: Arc<Mutex<W>>,
+    mut publisher: NatsPublisher,
+) -> Result<()> {
+    let mut buffer = [0u8; nats_wire::READ_BUFFER_SIZE];
+
+    loop {
+        let bytes_read = pipe.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        publisher.push(&buffer[..bytes_read]);
+
+        while let Some(message) = publisher.next() {
+            writer.lock().await.write_all(&message).await?;
+        }
+    }
+}
+
+async fn next_message<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    decoder: &mut NatsDecoder,
+) -> Result<NatsMessage> {
+    let mut buffer = [0u8; nats_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(message) = decoder.next()? {
+            return Ok(message);
+        }
+        let bytes_read = stream.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
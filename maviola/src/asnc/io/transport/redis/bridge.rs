@@ -0,0 +1,88 @@
+use crate::asnc::consts::REDIS_PIPE_CAPACITY;
+use crate::asnc::io::transport::redis::stream::{
+    drain_replies, handshake, publish, relay_messages,
+};
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::redis_wire::{Echoes, RedisPublisher};
+use crate::core::io::{ChannelDetails, ConnectionConf, RedisBridge};
+use crate::core::utils::SharedCloser;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for RedisBridge {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let ((sub_stream, sub_decoder), (pub_stream, pub_decoder)) =
+            match runtime::timeout(TCP_HANDSHAKE_TIMEOUT, handshake(self)).await {
+                Ok(streams) => streams?,
+                Err(_) => {
+                    return Err(Error::from(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "handshake timed out",
+                    )))
+                }
+            };
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::RedisBridge {
+                server_addr: self.addr,
+                channel: self.channel.clone(),
+            });
+
+        let (pub_reader, pub_writer) = pub_stream.into_split();
+        let (chan_pipe, relay_pipe) = tokio::io::duplex(REDIS_PIPE_CAPACITY);
+        let (chan_reader, chan_writer) = tokio::io::split(chan_pipe);
+        let (relay_reader, relay_writer) = tokio::io::split(relay_pipe);
+        let echoes = Echoes::default();
+
+        {
+            let info = self.info().clone();
+            let channel = self.channel.clone();
+            let echoes = echoes.clone();
+            runtime::spawn(async move {
+                if let Err(err) =
+                    relay_messages(sub_stream, sub_decoder, relay_writer, channel, echoes).await
+                {
+                    log::debug!("[{info:?}] subscription failed: {err:?}");
+                }
+            });
+        }
+        {
+            let info = self.info().clone();
+            let publisher = RedisPublisher::new(&self.channel, echoes);
+            runtime::spawn(async move {
+                if let Err(err) = publish(relay_reader, pub_writer, publisher).await {
+                    log::debug!("[{info:?}] can't publish to server: {err:?}");
+                }
+            });
+        }
+        {
+            let info = self.info().clone();
+            runtime::spawn(async move {
+                if let Err(err) = drain_replies(pub_reader, pub_decoder).await {
+                    log::debug!("[{info:?}] publisher connection failed: {err:?}");
+                }
+            });
+        }
+
+        let channel = chan_factory.build(chan_info, chan_reader, chan_writer);
+        let channel_state = channel.spawn().await;
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
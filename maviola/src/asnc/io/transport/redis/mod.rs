@@ -0,0 +1,127 @@
+mod bridge;
+mod stream;
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod redis_tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use crate::asnc::runtime;
+    use crate::core::io::redis_wire::{self, RedisDecoder, RedisReply};
+    use crate::core::io::{ChannelDetails, RedisBridge, Sender};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    use crate::asnc::prelude::*;
+    use crate::prelude::*;
+
+    const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+    fn heartbeat_bytes(system_id: u8) -> Vec<u8> {
+        let frame = Endpoint::v2(MavLinkId::new(system_id, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let mut bytes = Vec::new();
+        Sender::versioned(&mut bytes, V2).send(&frame).unwrap();
+        bytes
+    }
+
+    /// Reads a command, commands are encoded the same way as array replies.
+    async fn next_command(stream: &mut TcpStream, decoder: &mut RedisDecoder) -> Vec<Vec<u8>> {
+        let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+        loop {
+            if let Some(RedisReply::Array(Some(items))) = decoder.next().unwrap() {
+                return items
+                    .into_iter()
+                    .map(|item| match item {
+                        RedisReply::Bulk(Some(arg)) => arg,
+                        _ => panic!("invalid command"),
+                    })
+                    .collect();
+            }
+            let bytes_read = stream.read(&mut buffer).await.unwrap();
+            assert!(bytes_read > 0, "client disconnected");
+            decoder.push(&buffer[..bytes_read]);
+        }
+    }
+
+    fn message(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = format!(
+            "*3\r\n$7\r\nmessage\r\n$7\r\nmavlink\r\n${}\r\n",
+            payload.len()
+        )
+        .into_bytes();
+        bytes.extend(payload);
+        bytes.extend(b"\r\n");
+        bytes
+    }
+
+    /// Serves subscriber and publisher connections of a single client.
+    ///
+    /// Delivers published messages back to the subscriber followed by a frame from `system_id` 3.
+    async fn run_server(listener: TcpListener) {
+        let (mut sub_stream, _) = listener.accept().await.unwrap();
+        let mut sub_decoder = RedisDecoder::default();
+        assert_eq!(
+            next_command(&mut sub_stream, &mut sub_decoder).await,
+            vec![b"SUBSCRIBE".to_vec(), b"mavlink".to_vec()]
+        );
+        sub_stream
+            .write_all(b"*3\r\n$9\r\nsubscribe\r\n$7\r\nmavlink\r\n:1\r\n")
+            .await
+            .unwrap();
+
+        let (mut pub_stream, _) = listener.accept().await.unwrap();
+        let mut pub_decoder = RedisDecoder::default();
+
+        sub_stream
+            .write_all(&message(&heartbeat_bytes(2)))
+            .await
+            .unwrap();
+
+        let command = next_command(&mut pub_stream, &mut pub_decoder).await;
+        assert_eq!(&command[..2], &[b"PUBLISH".to_vec(), b"mavlink".to_vec()]);
+        pub_stream.write_all(b":1\r\n").await.unwrap();
+
+        sub_stream.write_all(&message(&command[2])).await.unwrap();
+        sub_stream
+            .write_all(&message(&heartbeat_bytes(3)))
+            .await
+            .unwrap();
+
+        // Keep connections open until the client is done
+        let mut buffer = [0u8; 1];
+        _ = sub_stream.read(&mut buffer).await;
+    }
+
+    #[tokio::test]
+    async fn bridge_publishes_and_skips_echoes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        runtime::spawn(run_server(listener));
+
+        let mut node = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(RedisBridge::new(&format!("redis://{server_addr}"), "mavlink").unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        let (frame, callback) = node.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert!(matches!(
+            callback.info().details(),
+            ChannelDetails::RedisBridge { channel, .. } if channel == "mavlink"
+        ));
+
+        node.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = node.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 3);
+    }
+}
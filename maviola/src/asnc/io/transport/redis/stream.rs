@@ -0,0 +1,140 @@
+use std::net::SocketAddr;
+
+use crate::asnc::runtime::TcpStream;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+use crate::core::io::redis_wire::{self, Echoes, RedisDecoder, RedisPublisher, RedisReply};
+use crate::core::io::RedisBridge;
+
+use crate::prelude::*;
+
+/// Opens subscriber and publisher connections.
+///
+/// Subscribed clients can't publish, so each direction has its own server connection.
+pub(super) async fn handshake(
+    conf: &RedisBridge,
+) -> Result<((TcpStream, RedisDecoder), (TcpStream, RedisDecoder))> {
+    let (mut sub_stream, mut sub_decoder) = connect(conf.addr, conf).await?;
+    subscribe(&mut sub_stream, &mut sub_decoder, &conf.channel).await?;
+    let publisher = connect(conf.addr, conf).await?;
+
+    Ok(((sub_stream, sub_decoder), publisher))
+}
+
+/// Connects to a Redis server and authenticates, if credentials are set.
+async fn connect(addr: SocketAddr, conf: &RedisBridge) -> Result<(TcpStream, RedisDecoder)> {
+    let mut stream = TcpStream::connect(addr).await?;
+
+    let mut decoder = RedisDecoder::default();
+    if let Some((username, password)) = &conf.credentials {
+        stream
+            .write_all(&redis_wire::auth(username.as_deref(), password))
+            .await?;
+        redis_wire::check_ok(next_reply(&mut stream, &mut decoder).await?)?;
+    }
+
+    Ok((stream, decoder))
+}
+
+/// Subscribes to the channel.
+async fn subscribe<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    decoder: &mut RedisDecoder,
+    channel: &str,
+) -> Result<()> {
+    stream.write_all(&redis_wire::subscribe(channel)).await?;
+    redis_wire::check_subscribed(next_reply(stream, decoder).await?, channel)?;
+    Ok(())
+}
+
+/// Writes frames published to the channel to `pipe` until either side is closed.
+///
+/// Skips echoes of messages published by the bridge itself.
+pub(super) async fn relay_messages(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: RedisDecoder,
+    mut pipe: impl AsyncWrite + Unpin,
+    channel: String,
+    echoes: Echoes,
+) -> Result<()> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(reply) = decoder.next()? {
+            if let Some(payload) = redis_wire::published_payload(reply, &channel) {
+                if !echoes.is_echo(&payload) {
+                    pipe.write_all(&payload).await?;
+                }
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+/// Publishes frames written to `pipe` to the channel.
+pub(super) async fn publish(
+    mut pipe: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    mut publisher: RedisPublisher,
+) -> Result<()> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+
+    loop {
+        let bytes_read = pipe.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        publisher.push(&buffer[..bytes_read]);
+
+        while let Some(command) = publisher.next() {
+            writer.write_all(&command).await?;
+        }
+    }
+}
+
+/// Reads replies to published messages until the stream is closed.
+///
+/// Replies are only checked for errors, since number of receivers doesn't matter.
+pub(super) async fn drain_replies(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: RedisDecoder,
+) -> Result<()> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(reply) = decoder.next()? {
+            if let RedisReply::Error(err) = reply {
+                log::warn!("Redis server error: {err}");
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+async fn next_reply<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    decoder: &mut RedisDecoder,
+) -> Result<RedisReply> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(reply) = decoder.next()? {
+            return Ok(reply);
+        }
+        let bytes_read = stream.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
@@ -1,4 +1,3 @@
-use async_trait::async_trait;
 use tokio::net::UnixStream;
 
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
@@ -8,7 +7,6 @@ use crate::core::utils::SharedCloser;
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for SockClient {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let path = self.path.clone();
@@ -1,17 +1,16 @@
 use std::path::PathBuf;
 
-use async_trait::async_trait;
 use tokio::net::{UnixListener, UnixStream};
 
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
 use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
 use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
 use crate::core::utils::{Closable, Closer};
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for SockServer {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let path = self.path.clone();
@@ -48,9 +47,9 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for SockServer {
 }
 
 fn on_close_handler(state: Closable, path: PathBuf, info: ConnectionInfo) {
-    tokio::spawn(async move {
+    runtime::spawn(async move {
         while !state.is_closed() {
-            tokio::time::sleep(SERVER_HANG_UP_TIMEOUT).await;
+            runtime::sleep(SERVER_HANG_UP_TIMEOUT).await;
         }
 
         log::debug!("[{info:?}] spawn wake-up connection to close server listening loop");
@@ -0,0 +1,64 @@
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler, DynConnectionBuilder};
+use crate::asnc::marker::AsyncConnConf;
+use crate::core::io::{ConnectionConf, ConnectionSpec};
+use crate::error::{ConfigDiagnostic, ConfigError};
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ConnectionSpec {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        ConfigError::check(<Self as ConnectionBuilder<V>>::diagnostics(self))?;
+
+        match builder(self) {
+            Some(builder) => builder.build().await,
+            None => Err(Error::from(std::io::Error::from(
+                std::io::ErrorKind::Unsupported,
+            ))),
+        }
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        builder::<V>(self)
+            .map(|builder| builder.is_repairable())
+            .unwrap_or_default()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        match builder::<V>(self) {
+            Some(builder) => builder.diagnostics(),
+            None => vec![ConfigDiagnostic::UnsupportedTransport(
+                self.info().details().clone(),
+            )],
+        }
+    }
+}
+
+/// Returns [`None`] for transports, that are not available for asynchronous API.
+fn builder<V: MaybeVersioned>(spec: &ConnectionSpec) -> Option<&dyn DynConnectionBuilder<V>> {
+    match spec {
+        ConnectionSpec::TcpClient(conn) => Some(conn),
+        ConnectionSpec::TcpServer(conn) => Some(conn),
+        ConnectionSpec::UdpClient(conn) => Some(conn),
+        ConnectionSpec::UdpServer(conn) => Some(conn),
+        #[cfg(feature = "serial")]
+        ConnectionSpec::SerialPort(_) => None,
+    }
+}
+
+impl Connection<Versionless> {
+    /// Parses connection configuration from an endpoint `url`, like `tcpout:127.0.0.1:5760` or
+    /// `udpin:0.0.0.0:14550`.
+    ///
+    /// Returned [`ConnectionSpec`] can be used by nodes of any protocol version. See
+    /// [`ConnectionSpec`] for supported URLs. Serial ports are not available for asynchronous
+    /// API, such URLs are rejected.
+    pub fn parse(url: &str) -> Result<ConnectionSpec> {
+        let spec = ConnectionSpec::parse(url)?;
+        ConfigError::check(<ConnectionSpec as ConnectionBuilder<Versionless>>::diagnostics(&spec))?;
+        Ok(spec)
+    }
+}
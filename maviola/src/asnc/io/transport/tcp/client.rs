@@ -1,31 +1,47 @@
-use async_trait::async_trait;
-use tokio::net::TcpStream;
+use std::net::SocketAddr;
 
+use crate::asnc::runtime::TcpStream;
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+
+use crate::asnc::io::transport::resolution::{resolve_client_addr, spawn_client_handler};
+use crate::asnc::io::transport::tcp::failover::FailoverTcpStream;
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::asnc::marker::AsyncConnConf;
 use crate::core::io::ChannelDetails;
 use crate::core::utils::SharedCloser;
+use crate::error::{ConfigDiagnostic, ConfigError};
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpClient {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
-        let server_addr = self.addr;
-        let stream = TcpStream::connect(server_addr).await?;
-        let (reader, writer) = stream.into_split();
+        let addr = resolve_client_addr(self.resolution.as_ref(), self.addr).await?;
 
-        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+        ConfigError::check(<Self as ConnectionBuilder<V>>::diagnostics(self))?;
 
-        let chan_info = connection
-            .info()
-            .make_channel_info(ChannelDetails::TcpClient { server_addr });
-        let channel = chan_factory.build(chan_info, reader, writer);
-        let channel_state = channel.spawn().await;
+        #[cfg(feature = "tls")]
+        if let Some(connector) = &self.tls {
+            let stream = super::tls::connect(connector, TcpStream::connect(addr).await?).await?;
+            let (reader, writer) = tokio::io::split(stream);
+            return Ok(self.spawn_async_channel(addr, reader, writer).await);
+        }
 
-        let handler = ConnectionHandler::spawn_from_state(channel_state);
+        if self.fallback_addrs.is_empty() {
+            let stream = TcpStream::connect(addr).await?;
+            let (reader, writer) = stream.into_split();
 
-        Ok((connection, handler))
+            Ok(self.spawn_async_channel(addr, reader, writer).await)
+        } else {
+            let addrs = std::iter::once(addr).chain(self.fallback_addrs.iter().copied());
+            let writer = FailoverTcpStream::connect(addrs.collect()).await?;
+            let reader = writer.clone();
+            let failback = writer.clone();
+
+            let (connection, handler) = self.spawn_async_channel(addr, reader, writer).await;
+            failback.spawn_failback(self.failback_interval, connection.state());
+
+            Ok((connection, handler))
+        }
     }
 
     fn to_conf(&self) -> AsyncConnConf<V> {
@@ -35,4 +51,29 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpClient {
     fn is_repairable(&self) -> bool {
         true
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.tls_diagnostics()
+    }
+}
+
+impl TcpClient {
+    async fn spawn_async_channel<V: MaybeVersioned>(
+        &self,
+        addr: SocketAddr,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+        writer: impl AsyncWrite + Send + Unpin + 'static,
+    ) -> (Connection<V>, ConnectionHandler) {
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::TcpClient { server_addr: addr });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = channel.spawn().await;
+
+        let handler = spawn_client_handler(channel_state, self.resolution.as_ref(), addr);
+
+        (connection, handler)
+    }
 }
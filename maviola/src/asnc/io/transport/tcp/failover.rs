@@ -0,0 +1,335 @@
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use crate::asnc::runtime::TcpStream;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::asnc::consts::{TCP_FAILBACK_POOLING_INTERVAL, TCP_FAILOVER_CONNECT_TIMEOUT};
+use crate::asnc::runtime;
+use crate::core::utils::Closable;
+
+/// Asynchronous TCP stream that reconnects to a priority-ordered list of server addresses.
+///
+/// Implements [`AsyncRead`] and [`AsyncWrite`]. All clones share the same underlying connection.
+/// When connection is lost, the first reachable address becomes active. If none of the addresses
+/// are reachable, then the original error is returned.
+pub struct FailoverTcpStream {
+    shared: Arc<Shared>,
+    stream: Arc<TcpStream>,
+    generation: u64,
+}
+
+struct Shared {
+    addrs: Vec<SocketAddr>,
+    current: Mutex<Current>,
+}
+
+struct Current {
+    stream: Arc<TcpStream>,
+    index: usize,
+    generation: u64,
+    reconnecting: bool,
+    failed: Option<ErrorKind>,
+    wakers: Vec<Waker>,
+}
+
+impl FailoverTcpStream {
+    /// Connects to the first reachable address among `addrs`.
+    pub async fn connect(addrs: Vec<SocketAddr>) -> std::io::Result<Self> {
+        let (stream, index) = connect_first(&addrs, addrs.len()).await?;
+        let stream = Arc::new(stream);
+
+        Ok(Self {
+            stream: stream.clone(),
+            generation: 0,
+            shared: Arc::new(Shared {
+                addrs,
+                current: Mutex::new(Current {
+                    stream,
+                    index,
+                    generation: 0,
+                    reconnecting: false,
+                    failed: None,
+                    wakers: Vec::new(),
+                }),
+            }),
+        })
+    }
+
+    /// Spawns a task that periodically checks higher-priority addresses and reconnects to them
+    /// once they are available.
+    ///
+    /// Stops and fails the connection when `state` is closed.
+    pub fn spawn_failback(&self, interval: Duration, state: Closable) {
+        let shared = self.shared.clone();
+
+        runtime::spawn(async move {
+            let mut last_check = Instant::now();
+
+            while !state.is_closed() {
+                runtime::sleep(TCP_FAILBACK_POOLING_INTERVAL).await;
+                if last_check.elapsed() < interval {
+                    continue;
+                }
+                last_check = Instant::now();
+
+                let (index, generation) = match shared.lock() {
+                    Ok(current) if !current.reconnecting => (current.index, current.generation),
+                    Ok(_) => continue,
+                    Err(_) => break,
+                };
+                if index == 0 {
+                    continue;
+                }
+
+                if let Ok((stream, new_index)) = connect_first(&shared.addrs, index).await {
+                    if let Ok(mut current) = shared.lock() {
+                        if current.generation == generation && !current.reconnecting {
+                            log::info!(
+                                "failing back from {} to {}",
+                                shared.addrs[current.index],
+                                shared.addrs[new_index]
+                            );
+                            current.replace(stream, new_index);
+                        }
+                    }
+                }
+            }
+
+            if let Ok(mut current) = shared.lock() {
+                current.fail(ErrorKind::NotConnected);
+            }
+        });
+    }
+
+    fn refresh(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let mut current = self.shared.lock()?;
+
+        if let Some(kind) = current.failed {
+            return Poll::Ready(Err(kind.into()));
+        }
+        if current.reconnecting {
+            current.subscribe(cx.waker());
+            return Poll::Pending;
+        }
+        if current.generation != self.generation {
+            self.stream = current.stream.clone();
+            self.generation = current.generation;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn subscribe(&self, cx: &mut Context<'_>) -> std::io::Result<()> {
+        self.shared.lock()?.subscribe(cx.waker());
+        Ok(())
+    }
+
+    fn reconnect(&self, err: std::io::Error) -> std::io::Result<()> {
+        let mut current = self.shared.lock()?;
+
+        if current.failed.is_some() {
+            return Err(err);
+        }
+        if current.generation != self.generation || current.reconnecting {
+            return Ok(());
+        }
+        current.reconnecting = true;
+
+        let shared = self.shared.clone();
+        let kind = err.kind();
+        runtime::spawn(async move {
+            let result = connect_first(&shared.addrs, shared.addrs.len()).await;
+
+            if let Ok(mut current) = shared.lock() {
+                current.reconnecting = false;
+                match result {
+                    Ok((stream, index)) => {
+                        log::info!(
+                            "failing over from {} to {}",
+                            shared.addrs[current.index],
+                            shared.addrs[index]
+                        );
+                        current.replace(stream, index);
+                    }
+                    Err(_) => current.fail(kind),
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> std::io::Result<MutexGuard<'_, Current>> {
+        self.current
+            .lock()
+            .map_err(|err| std::io::Error::other(err.to_string()))
+    }
+}
+
+impl Current {
+    fn replace(&mut self, stream: TcpStream, index: usize) {
+        self.stream = Arc::new(stream);
+        self.index = index;
+        self.generation += 1;
+        self.wake();
+    }
+
+    fn fail(&mut self, kind: ErrorKind) {
+        self.failed = Some(kind);
+        self.wake();
+    }
+
+    fn subscribe(&mut self, waker: &Waker) {
+        if !self.wakers.iter().any(|known| known.will_wake(waker)) {
+            self.wakers.push(waker.clone());
+        }
+    }
+
+    fn wake(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl AsyncRead for FailoverTcpStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        loop {
+            std::task::ready!(self.refresh(cx))?;
+
+            match self.stream.poll_read_ready(cx) {
+                Poll::Pending => {
+                    // Wake up when connection is replaced
+                    self.subscribe(cx)?;
+                    return Poll::Pending;
+                }
+                Poll::Ready(Err(err)) => self.reconnect(err)?,
+                Poll::Ready(Ok(())) => match self.stream.try_read(buf.initialize_unfilled()) {
+                    Ok(0) if buf.remaining() > 0 => {
+                        self.reconnect(ErrorKind::UnexpectedEof.into())?;
+                    }
+                    Ok(bytes_read) => {
+                        buf.advance(bytes_read);
+                        return Poll::Ready(Ok(()));
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => continue,
+                    Err(err) => self.reconnect(err)?,
+                },
+            }
+        }
+    }
+}
+
+impl AsyncWrite for FailoverTcpStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        loop {
+            std::task::ready!(self.refresh(cx))?;
+
+            let err = match self.stream.poll_write_ready(cx) {
+                Poll::Pending => {
+                    self.subscribe(cx)?;
+                    return Poll::Pending;
+                }
+                Poll::Ready(Err(err)) => err,
+                Poll::Ready(Ok(())) => match self.stream.try_write(buf) {
+                    Ok(bytes_written) => return Poll::Ready(Ok(bytes_written)),
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => continue,
+                    Err(err) => err,
+                },
+            };
+
+            self.reconnect(err)?;
+            // Signal the caller to retry writing the whole frame to a new connection
+            return Poll::Ready(Err(ErrorKind::TimedOut.into()));
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Clone for FailoverTcpStream {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            stream: self.stream.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+async fn connect_first(addrs: &[SocketAddr], limit: usize) -> std::io::Result<(TcpStream, usize)> {
+    let mut last_err = std::io::Error::from(ErrorKind::NotFound);
+
+    for (index, addr) in addrs.iter().enumerate().take(limit) {
+        match runtime::timeout(TCP_FAILOVER_CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => return Ok((stream, index)),
+            Ok(Err(err)) => last_err = err,
+            Err(_) => last_err = ErrorKind::TimedOut.into(),
+        }
+    }
+
+    Err(last_err)
+}
+
+#[cfg(test)]
+mod failover_tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::core::utils::net::pick_unused_port;
+    use crate::core::utils::SharedCloser;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[tokio::test]
+    async fn failover_and_failback() {
+        let (primary, secondary) = (
+            addr(pick_unused_port().unwrap()),
+            addr(pick_unused_port().unwrap()),
+        );
+        let secondary_listener = TcpListener::bind(secondary).await.unwrap();
+
+        let mut stream = FailoverTcpStream::connect(vec![primary, secondary])
+            .await
+            .unwrap();
+        let (mut secondary_stream, _) = secondary_listener.accept().await.unwrap();
+
+        stream.write_all(&[1u8; 10]).await.unwrap();
+        let mut buf = [0u8; 10];
+        secondary_stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1u8; 10]);
+
+        let primary_listener = TcpListener::bind(primary).await.unwrap();
+        let state = SharedCloser::new();
+        stream.spawn_failback(Duration::from_millis(50), state.to_closable());
+        let (mut primary_stream, _) = primary_listener.accept().await.unwrap();
+
+        primary_stream.write_all(&[2u8; 10]).await.unwrap();
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [2u8; 10]);
+    }
+}
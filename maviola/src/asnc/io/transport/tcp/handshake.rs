@@ -0,0 +1,90 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::asnc::runtime::TcpStream;
+
+use crate::core::io::HandshakeOutcome;
+
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Authentication handshake performed by an asynchronous [`TcpServer`] with each accepted client.
+///
+/// Handshake is performed before the client channel is attached to the connection, so the client
+/// neither receives nor sends frames until it is [accepted](HandshakeOutcome::Accept). It has
+/// direct access to the client stream and can, for example, exchange tokens over the first bytes
+/// or read and verify the first frame. Errors are considered as rejections.
+///
+/// Each handshake is performed in a separate task and is rejected, if not completed within
+/// [`TCP_HANDSHAKE_TIMEOUT`].
+///
+/// Set handshake by [`TcpServer::asnc_handshake`].
+///
+/// Handshakes are implemented with native `async fn`, neither boxing nor `async_trait` is required.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use tokio::io::AsyncReadExt;
+/// use maviola::asnc::runtime::TcpStream;
+/// use maviola::core::io::HandshakeOutcome;
+/// use maviola::asnc::io::TcpHandshake;
+/// use maviola::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct TokenHandshake([u8; 4]);
+///
+/// impl TcpHandshake for TokenHandshake {
+///     async fn handshake(&self, stream: &mut TcpStream) -> Result<HandshakeOutcome> {
+///         let mut token = [0u8; 4];
+///         stream.read_exact(&mut token).await?;
+///
+///         Ok(match token == self.0 {
+///             true => HandshakeOutcome::Accept,
+///             false => HandshakeOutcome::Reject,
+///         })
+///     }
+/// }
+///
+/// let node = Node::asnc::<V2>()
+///     .connection(
+///         TcpServer::new("127.0.0.1:5600").unwrap()
+///             .asnc_handshake(TokenHandshake(*b"mav1"))
+///     ).build().await.unwrap();
+/// # }
+/// ```
+///
+/// Returned future has to be [`Send`], since each handshake is performed in a separate task.
+///
+/// [`TCP_HANDSHAKE_TIMEOUT`]: crate::core::consts::TCP_HANDSHAKE_TIMEOUT
+pub trait TcpHandshake: Debug + Send + Sync + 'static {
+    /// Performs handshake with a client connected through `stream`.
+    fn handshake(
+        &self,
+        stream: &mut TcpStream,
+    ) -> impl Future<Output = Result<HandshakeOutcome>> + Send;
+}
+
+/// Object-safe counterpart of [`TcpHandshake`].
+///
+/// Allows to store handshakes as trait objects. Handshake future is boxed once per accepted client.
+pub(crate) trait DynTcpHandshake: Debug + Send + Sync + 'static {
+    /// Performs handshake with a client connected through `stream`.
+    ///
+    /// See [`TcpHandshake::handshake`].
+    fn handshake<'a>(
+        &'a self,
+        stream: &'a mut TcpStream,
+    ) -> Pin<Box<dyn Future<Output = Result<HandshakeOutcome>> + Send + 'a>>;
+}
+
+impl<H: TcpHandshake> DynTcpHandshake for H {
+    fn handshake<'a>(
+        &'a self,
+        stream: &'a mut TcpStream,
+    ) -> Pin<Box<dyn Future<Output = Result<HandshakeOutcome>> + Send + 'a>> {
+        Box::pin(TcpHandshake::handshake(self, stream))
+    }
+}
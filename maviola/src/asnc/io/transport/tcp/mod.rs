@@ -1,2 +1,9 @@
 pub mod client;
+mod failover;
+mod handshake;
 pub mod server;
+#[cfg(feature = "tls")]
+mod tls;
+
+pub(crate) use handshake::DynTcpHandshake;
+pub use handshake::TcpHandshake;
@@ -1,19 +1,27 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use async_trait::async_trait;
-use tokio::net::{TcpListener, TcpStream};
+use crate::asnc::runtime::{TcpListener, TcpStream};
+use tokio::io::AsyncWriteExt;
 
-use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::io::{
+    ChannelFactory, Connection, ConnectionBuilder, ConnectionHandler, DynTcpHandshake,
+};
 use crate::asnc::marker::AsyncConnConf;
-use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
-use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
+use crate::asnc::runtime;
+use crate::core::consts::{SERVER_HANG_UP_TIMEOUT, TCP_HANDSHAKE_TIMEOUT};
+use crate::core::io::{
+    ChannelDetails, ChannelInfo, ConnectionConf, ConnectionInfo, HandshakeOutcome, ServerHandshake,
+};
 use crate::core::utils::{Closable, Closer};
+use crate::error::{ConfigDiagnostic, ConfigError};
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        ConfigError::check(<Self as ConnectionBuilder<V>>::diagnostics(self))?;
+
         let server_addr = self.addr;
         let listener = TcpListener::bind(self.addr).await?;
 
@@ -21,6 +29,13 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
         let (connection, chan_factory) = Connection::new(self.info.clone(), conn_state.to_shared());
 
         let info = self.info().clone();
+        let handshake = match &self.handshake {
+            Some(ServerHandshake::Async(handshake)) => Some(handshake.clone()),
+            _ => None,
+        };
+
+        #[cfg(feature = "tls")]
+        let tls = self.tls.clone();
 
         let handler = ConnectionHandler::spawn(async move {
             on_close_handler(conn_state.to_closable(), server_addr, info.clone());
@@ -28,14 +43,29 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
             while !conn_state.is_closed() {
                 let (stream, peer_addr) = listener.accept().await?;
 
-                let (reader, writer) = stream.into_split();
-
                 let chan_info = info.make_channel_info(ChannelDetails::TcpServer {
                     server_addr,
                     peer_addr,
                 });
-                let channel = chan_factory.build(chan_info, reader, writer);
-                channel.spawn().await.discard();
+
+                #[cfg(feature = "tls")]
+                if let Some(acceptor) = tls.clone() {
+                    let chan_factory = chan_factory.clone();
+                    runtime::spawn(async move {
+                        attach_tls_channel(&acceptor, &chan_factory, chan_info, stream).await
+                    });
+                    continue;
+                }
+
+                match handshake.clone() {
+                    None => attach_channel(&chan_factory, chan_info, stream).await,
+                    Some(handshake) => {
+                        let chan_factory = chan_factory.clone();
+                        runtime::spawn(async move {
+                            authenticate(handshake, &chan_factory, chan_info, stream).await
+                        });
+                    }
+                }
             }
 
             Ok(())
@@ -47,12 +77,93 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
     fn to_conf(&self) -> AsyncConnConf<V> {
         AsyncConnConf::new(self.clone())
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = match &self.handshake {
+            #[cfg(feature = "sync")]
+            Some(ServerHandshake::Sync(_)) => vec![ConfigDiagnostic::HandshakeModeMismatch(
+                self.info.details().clone(),
+            )],
+            _ => Vec::new(),
+        };
+
+        diagnostics.extend(self.tls_diagnostics());
+
+        diagnostics
+    }
+}
+
+async fn attach_channel<V: MaybeVersioned>(
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    stream: TcpStream,
+) {
+    let (reader, writer) = stream.into_split();
+    let channel = chan_factory.build(chan_info, reader, writer);
+    channel.spawn().await.discard();
+}
+
+/// Establishes TLS session with a client and attaches its channel, if session is established.
+#[cfg(feature = "tls")]
+async fn attach_tls_channel<V: MaybeVersioned>(
+    acceptor: &crate::core::io::TlsAcceptor,
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    stream: TcpStream,
+) {
+    match super::tls::accept(acceptor, stream).await {
+        Ok(stream) => {
+            log::debug!("[{chan_info:?}] TLS session established");
+            let (reader, writer) = tokio::io::split(stream);
+            let channel = chan_factory.build(chan_info, reader, writer);
+            channel.spawn().await.discard();
+        }
+        Err(err) => {
+            log::info!("[{chan_info:?}] TLS handshake failed: {err:?}");
+        }
+    }
+}
+
+/// Performs handshake with a client and attaches its channel, if client is accepted.
+async fn authenticate<V: MaybeVersioned>(
+    handshake: Arc<dyn DynTcpHandshake>,
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    mut stream: TcpStream,
+) {
+    let outcome = runtime::timeout(TCP_HANDSHAKE_TIMEOUT, handshake.handshake(&mut stream))
+        .await
+        .unwrap_or_else(|_| {
+            Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "handshake timed out",
+            )))
+        });
+
+    match outcome {
+        Ok(HandshakeOutcome::Accept) => {
+            log::debug!("[{chan_info:?}] client accepted");
+            attach_channel(chan_factory, chan_info, stream).await;
+        }
+        Ok(HandshakeOutcome::Reject) => {
+            log::info!("[{chan_info:?}] client rejected");
+            _ = stream.shutdown().await;
+        }
+        Err(err) => {
+            log::info!("[{chan_info:?}] client rejected: {err:?}");
+            _ = stream.shutdown().await;
+        }
+    }
 }
 
-fn on_close_handler(state: Closable, addr: SocketAddr, info: ConnectionInfo) {
-    tokio::spawn(async move {
+pub(in crate::asnc::io::transport) fn on_close_handler(
+    state: Closable,
+    addr: SocketAddr,
+    info: ConnectionInfo,
+) {
+    runtime::spawn(async move {
         while !state.is_closed() {
-            tokio::time::sleep(SERVER_HANG_UP_TIMEOUT).await;
+            runtime::sleep(SERVER_HANG_UP_TIMEOUT).await;
         }
 
         log::debug!("[{info:?}] spawn wake-up connection to close server listening loop");
@@ -0,0 +1,42 @@
+use std::future::Future;
+
+use crate::asnc::runtime::TcpStream;
+use tokio_rustls::TlsStream;
+
+use crate::asnc::runtime;
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::{TlsAcceptor, TlsConnector};
+
+/// Establishes TLS session with a client over server-side `stream`.
+pub(super) async fn accept(
+    acceptor: &TlsAcceptor,
+    stream: TcpStream,
+) -> std::io::Result<TlsStream<TcpStream>> {
+    let acceptor = tokio_rustls::TlsAcceptor::from(acceptor.config().clone());
+    with_timeout(acceptor.accept(stream)).await.map(Into::into)
+}
+
+/// Establishes TLS session with a server over client-side `stream`.
+pub(super) async fn connect(
+    connector: &TlsConnector,
+    stream: TcpStream,
+) -> std::io::Result<TlsStream<TcpStream>> {
+    let server_name = connector.server_name().clone();
+    let connector = tokio_rustls::TlsConnector::from(connector.config().clone());
+    with_timeout(connector.connect(server_name, stream))
+        .await
+        .map(Into::into)
+}
+
+async fn with_timeout<T>(
+    handshake: impl Future<Output = std::io::Result<T>>,
+) -> std::io::Result<T> {
+    runtime::timeout(TCP_HANDSHAKE_TIMEOUT, handshake)
+        .await
+        .unwrap_or_else(|_| {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "TLS handshake timed out",
+            ))
+        })
+}
@@ -0,0 +1,84 @@
+use std::time::Instant;
+
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
+
+use crate::asnc::consts::TLOG_RELAY_POOLING_INTERVAL;
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler, IncomingFrameProducer};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::io::{AsyncReceiver, ChannelDetails, ChannelInfo, IncomingFrame, TlogPlayback};
+use crate::core::utils::{Closable, SharedCloser};
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for TlogReader {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let path = self.path.clone();
+        let reader = BufReader::new(File::open(path.as_path()).await?);
+
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), state.clone());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::TlogReader { path });
+        let playback = TlogPlayback::new(self.speed);
+
+        let handler = ConnectionHandler::spawn(async move {
+            let producer = chan_factory.producer().clone();
+            replay(state.to_closable(), chan_info, producer, playback, reader).await
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+}
+
+/// Replays records until the end of the log is reached or connection is closed.
+async fn replay<V: MaybeVersioned>(
+    state: Closable,
+    info: ChannelInfo,
+    producer: IncomingFrameProducer<V>,
+    mut playback: TlogPlayback,
+    mut reader: impl AsyncRead + Unpin,
+) -> Result<()> {
+    let mut timestamp = [0u8; 8];
+
+    loop {
+        if let Err(err) = reader.read_exact(&mut timestamp).await {
+            if let std::io::ErrorKind::UnexpectedEof = err.kind() {
+                log::debug!("[{info:?}] telemetry log replayed");
+                return Ok(());
+            }
+            return Err(Error::from(err));
+        }
+        let frame = AsyncReceiver::versionless(&mut reader).recv().await?;
+        let deadline = playback.schedule(u64::from_be_bytes(timestamp));
+
+        let frame = match frame.try_into_versioned::<V>() {
+            Ok(frame) => frame,
+            Err(err) => {
+                log::trace!("[{info:?}] skipping frame: {err:?}");
+                continue;
+            }
+        };
+
+        loop {
+            if state.is_closed() {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            runtime::sleep(TLOG_RELAY_POOLING_INTERVAL.min(deadline - now)).await;
+        }
+
+        producer.send(IncomingFrame::new(frame, info.clone()))?;
+        log::trace!("[{info:?}] replayed incoming frame");
+    }
+}
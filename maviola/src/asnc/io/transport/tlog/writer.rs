@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+
+use crate::asnc::consts::TLOG_RELAY_POOLING_INTERVAL;
+use crate::asnc::io::{forward_events, Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::io::{tlog_timestamp, AsyncSender};
+use crate::core::utils::{Closable, SharedCloser};
+use crate::error::{ConfigDiagnostic, RecvTimeoutError};
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned, C: ConnectionBuilder<V> + Clone + Sync + 'static> ConnectionBuilder<V>
+    for TlogWriter<C>
+{
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path.as_path())
+            .await?;
+
+        let (inner, inner_handler) = self.connection.build().await?;
+        inner_handler.handle(&inner);
+
+        let state = SharedCloser::new();
+        let (connection, mut chan_factory) = Connection::new(inner.info().clone(), state.clone());
+        let (records, records_rx) = mpsc::unbounded_channel();
+
+        {
+            let (state, inner_state) = (state.to_closable(), inner.state());
+            let mut receiver = inner.receiver();
+            let producer = chan_factory.producer().clone();
+            let mut events = (inner.take_events(), chan_factory.event_sender().clone());
+            let records = records.clone();
+
+            runtime::spawn(async move {
+                while is_open(&state, &inner_state) {
+                    forward_events(events.0.as_mut(), &events.1);
+
+                    let frame = match receiver.recv_timeout(TLOG_RELAY_POOLING_INTERVAL).await {
+                        Ok(frame) => frame,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+                    };
+
+                    _ = records.send((SystemTime::now(), frame.shared_frame().clone()));
+                    if producer.send(frame).is_err() {
+                        break;
+                    }
+                }
+                forward_events(events.0.as_mut(), &events.1);
+            });
+        }
+
+        {
+            let (state, inner_state) = (state.to_closable(), inner.state());
+            let mut send_handler = chan_factory.send_handler().clone();
+            let sender = inner.sender();
+
+            runtime::spawn(async move {
+                while is_open(&state, &inner_state) {
+                    let frame = match send_handler.recv_timeout(TLOG_RELAY_POOLING_INTERVAL).await {
+                        Ok(frame) => frame,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+                    };
+
+                    _ = records.send((SystemTime::now(), frame.shared_frame().clone()));
+                    if sender.send_raw(frame).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        let handler = ConnectionHandler::spawn(async move {
+            let result = write_records(file, records_rx).await;
+            drop(inner);
+            result
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        self.connection.is_repairable()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.connection.diagnostics()
+    }
+}
+
+fn is_open(state: &Closable, inner_state: &Closable) -> bool {
+    !state.is_closed() && !inner_state.is_closed()
+}
+
+/// Writes records until both relays are finished.
+async fn write_records<V: MaybeVersioned>(
+    file: File,
+    mut records: mpsc::UnboundedReceiver<(SystemTime, Arc<Frame<V>>)>,
+) -> Result<()> {
+    let mut writer = BufWriter::new(file);
+
+    loop {
+        let (time, frame) =
+            match runtime::timeout(TLOG_RELAY_POOLING_INTERVAL, records.recv()).await {
+                Ok(Some(record)) => record,
+                Ok(None) => break,
+                Err(_) => {
+                    writer.flush().await?;
+                    continue;
+                }
+            };
+
+        writer
+            .write_all(&tlog_timestamp(time).to_be_bytes())
+            .await?;
+        AsyncSender::new(&mut writer).send(frame.as_ref()).await?;
+    }
+
+    writer.flush().await?;
+    Ok(())
+}
@@ -1,51 +1,153 @@
-use async_trait::async_trait;
-use tokio::net::UdpSocket;
+use std::net::SocketAddr;
+use std::time::Instant;
 
+use crate::asnc::runtime::UdpSocket;
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+
+use crate::asnc::io::transport::resolution::{resolve_client_addr, spawn_client_handler};
+use crate::asnc::io::transport::udp::failover_rw::FailoverUdpRW;
 use crate::asnc::io::transport::udp::udp_rw::UdpRW;
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::asnc::marker::AsyncConnConf;
-use crate::core::io::ChannelDetails;
+use crate::core::io::{AddressFailover, ChannelDetails};
 use crate::core::utils::net::{pick_unused_port, resolve_socket_addr};
 use crate::core::utils::SharedCloser;
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpClient {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let bind_addr = match self.bind_addr {
             None => resolve_socket_addr(format!("{}:{}", self.host, pick_unused_port()?))?,
             Some(bind_addr) => bind_addr,
         };
-        let server_addr = self.addr;
 
+        let addr = resolve_client_addr(self.resolution.as_ref(), self.addr).await?;
         let udp_socket = UdpSocket::bind(bind_addr).await?;
-        udp_socket.connect(server_addr).await?;
 
-        let writer = UdpRW::new(udp_socket);
-        let reader = writer.clone();
+        if self.fallback_addrs.is_empty() {
+            udp_socket.connect(addr).await?;
+
+            let writer = UdpRW::new(udp_socket);
+            let reader = writer.clone();
+
+            Ok(self
+                .spawn_async_channel(addr, bind_addr, reader, writer)
+                .await)
+        } else {
+            let failover = AddressFailover::new(
+                std::iter::once(addr)
+                    .chain(self.fallback_addrs.iter().copied())
+                    .collect(),
+                self.failover_timeout,
+                self.failback_interval,
+                Instant::now(),
+            );
+
+            let writer = FailoverUdpRW::new(udp_socket, failover);
+            let reader = writer.clone();
+
+            Ok(self
+                .spawn_async_channel(addr, bind_addr, reader, writer)
+                .await)
+        }
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
 
+impl UdpClient {
+    async fn spawn_async_channel<V: MaybeVersioned>(
+        &self,
+        addr: SocketAddr,
+        bind_addr: SocketAddr,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+        writer: impl AsyncWrite + Send + Unpin + 'static,
+    ) -> (Connection<V>, ConnectionHandler) {
         let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
 
         let chan_info = connection
             .info()
             .make_channel_info(ChannelDetails::UdpClient {
-                server_addr,
+                server_addr: addr,
                 bind_addr,
             });
         let channel = chan_factory.build(chan_info, reader, writer);
         let channel_state = channel.spawn().await;
 
-        let handler = ConnectionHandler::spawn_from_state(channel_state);
+        let handler = spawn_client_handler(channel_state, self.resolution.as_ref(), addr);
 
-        Ok((connection, handler))
+        (connection, handler)
     }
+}
 
-    fn to_conf(&self) -> AsyncConnConf<V> {
-        AsyncConnConf::new(self.clone())
-    }
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
 
-    fn is_repairable(&self) -> bool {
-        true
+#[cfg(test)]
+mod client_tests {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+
+    use crate::asnc::prelude::*;
+    use crate::core::io::RetryStrategy;
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unreachable_server_is_reported() {
+        let addr = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+
+        let client = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(UdpClient::new(addr.as_str()).unwrap())
+            .retry(RetryStrategy::Always(Duration::from_millis(50)))
+            .build()
+            .await
+            .unwrap();
+        let mut events = client.events().unwrap();
+
+        let lost = tokio::time::timeout(Duration::from_secs(1), async {
+            loop {
+                _ = client.send(&Heartbeat::default());
+                tokio::select! {
+                    Some(Event::ConnectionLost(_)) = events.next() => return,
+                    _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                }
+            }
+        })
+        .await;
+        assert!(
+            lost.is_ok(),
+            "port unreachable errors should close connection"
+        );
+
+        let server = Node::asnc::<V2>()
+            .connection(UdpServer::new(addr.as_str()).unwrap())
+            .build()
+            .await
+            .unwrap();
+        let mut frames = server.events().unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                _ = client.send(&Heartbeat::default());
+                tokio::select! {
+                    Some(Event::Frame(frame, _)) = frames.next() => return frame,
+                    _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(received.system_id(), 1);
     }
 }
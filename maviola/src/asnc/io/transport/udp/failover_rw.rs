@@ -0,0 +1,134 @@
+use std::io::Error;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use crate::asnc::runtime::UdpSocket;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::asnc::consts::UDP_FAILOVER_CHECK_INTERVAL;
+use crate::core::io::AddressFailover;
+
+/// A wrapper around unconnected [`UdpSocket`] that implements [`AsyncRead`] and [`AsyncWrite`] and
+/// switches between remote addresses according to [`AddressFailover`].
+///
+/// Incoming datagrams are buffered, so frames can be read in arbitrary chunks.
+pub struct FailoverUdpRW {
+    socket: Arc<UdpSocket>,
+    failover: Arc<Mutex<AddressFailover>>,
+    checks: Interval,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl FailoverUdpRW {
+    /// Creates a new UDP reader/writer.
+    ///
+    /// Should be called within Tokio runtime.
+    pub fn new(socket: UdpSocket, failover: AddressFailover) -> Self {
+        Self::from_shared(Arc::new(socket), Arc::new(Mutex::new(failover)))
+    }
+
+    fn from_shared(socket: Arc<UdpSocket>, failover: Arc<Mutex<AddressFailover>>) -> Self {
+        let mut checks = interval(UDP_FAILOVER_CHECK_INTERVAL);
+        checks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        Self {
+            socket,
+            failover,
+            checks,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn with_failover<T>(&self, f: impl FnOnce(&mut AddressFailover) -> T) -> std::io::Result<T> {
+        match self.failover.lock() {
+            Ok(mut failover) => Ok(f(&mut failover)),
+            Err(err) => Err(Error::other(err.to_string())),
+        }
+    }
+}
+
+impl Clone for FailoverUdpRW {
+    fn clone(&self) -> Self {
+        Self::from_shared(self.socket.clone(), self.failover.clone())
+    }
+}
+
+impl AsyncRead for FailoverUdpRW {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        // Check the active address even if nothing is received
+        while self.checks.poll_tick(cx).is_ready() {
+            self.with_failover(|failover| failover.check(Instant::now()))?;
+        }
+
+        while self.pos >= self.buf.len() {
+            let this = &mut *self;
+            this.buf.resize(u16::MAX as usize, 0);
+            this.pos = 0;
+
+            let mut datagram = ReadBuf::new(&mut this.buf);
+            let from = match this.socket.poll_recv_from(cx, &mut datagram) {
+                Poll::Ready(Ok(from)) => from,
+                Poll::Ready(Err(err)) => {
+                    this.buf.clear();
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Pending => {
+                    this.buf.clear();
+                    return Poll::Pending;
+                }
+            };
+            let bytes_read = datagram.filled().len();
+
+            if this.with_failover(|failover| failover.on_received(from, Instant::now()))? {
+                this.buf.truncate(bytes_read);
+            } else {
+                this.buf.clear();
+            }
+        }
+
+        let bytes_read = buf.remaining().min(self.buf.len() - self.pos);
+        buf.put_slice(&self.buf[self.pos..self.pos + bytes_read]);
+        self.pos += bytes_read;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for FailoverUdpRW {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, Error>> {
+        let (active, probes) = self.with_failover(|failover| {
+            let now = Instant::now();
+            failover.check(now);
+            (failover.active(), failover.probe(now).to_vec())
+        })?;
+
+        for addr in probes {
+            if let Err(err) = self.socket.try_send_to(buf, addr) {
+                log::trace!("failed to probe {addr}: {err:?}");
+            }
+        }
+
+        self.socket.poll_send_to(cx, buf, active)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,22 +1,22 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Instant;
 
-use async_trait::async_trait;
-use tokio::net::UdpSocket;
+use crate::asnc::runtime::UdpSocket;
 use tokio::sync::mpsc;
 
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
 use crate::asnc::utils::{MpscReader, MpscWriter};
 use crate::core::consts::{DEFAULT_UDP_HOST, SERVER_HANG_UP_TIMEOUT};
-use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
+use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionEvent, ConnectionInfo};
 use crate::core::utils::net::{pick_unused_port, resolve_socket_addr};
 use crate::core::utils::{Closable, Closer};
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let server_addr = self.addr;
@@ -29,6 +29,13 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
         let on_close_bind_addr =
             resolve_socket_addr(format!("{}:{}", DEFAULT_UDP_HOST, pick_unused_port()?))?;
 
+        let client_timeout = self.client_timeout;
+        let max_clients = self.max_clients;
+        let known_peers = self.peers.clone();
+        known_peers.clear();
+        let guard = self.guard.clone();
+        guard.reset();
+
         let handler = ConnectionHandler::spawn(async move {
             on_close_handler(
                 conn_state.to_closable(),
@@ -37,20 +44,67 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                 info.clone(),
             );
 
-            let mut peers = HashMap::new();
+            let mut peers: HashMap<SocketAddr, (mpsc::Sender<Vec<u8>>, Instant)> = HashMap::new();
             let mut buf = [0u8; 512];
 
-            while !conn_state.is_closed() {
-                let (bytes_read, peer_addr) = udp_socket.recv_from(buf.as_mut_slice()).await?;
+            let result = loop {
+                if conn_state.is_closed() {
+                    break Ok(());
+                }
+
+                if let Some(timeout) = client_timeout {
+                    peers.retain(|peer_addr, (_, last_seen)| {
+                        let is_active = last_seen.elapsed() < timeout;
+                        if !is_active {
+                            log::debug!("[{info:?}] UDP peer {peer_addr} expired");
+                            known_peers.remove(peer_addr);
+                        }
+                        is_active
+                    });
+                }
+
+                let received = match client_timeout {
+                    Some(_) => match runtime::timeout(
+                        SERVER_HANG_UP_TIMEOUT,
+                        udp_socket.recv_from(buf.as_mut_slice()),
+                    )
+                    .await
+                    {
+                        Ok(received) => received,
+                        Err(_) => continue,
+                    },
+                    None => udp_socket.recv_from(buf.as_mut_slice()).await,
+                };
+                let (bytes_read, peer_addr) = match received {
+                    Ok(received) => received,
+                    Err(err) => break Err(err.into()),
+                };
 
                 #[allow(clippy::map_entry)]
                 if !peers.contains_key(&peer_addr) {
+                    if !known_peers.has_capacity(max_clients) {
+                        log::trace!("[{info:?}] UDP peer {peer_addr} rejected: too many clients");
+                        continue;
+                    }
+
+                    if !guard.admit(peer_addr) {
+                        log::trace!(
+                            "[{info:?}] UDP peer {peer_addr} rejected: sender is not allowed"
+                        );
+                        if guard.reject(peer_addr) {
+                            _ = chan_factory
+                                .event_sender()
+                                .send(ConnectionEvent::SenderRejected(info.clone(), peer_addr));
+                        }
+                        continue;
+                    }
+
                     let udp_socket = udp_socket.clone();
 
                     let (writer_tx, writer_rx) = mpsc::channel(1024);
                     let (reader_tx, reader_rx) = mpsc::channel(1024);
 
-                    peers.insert(peer_addr, reader_tx);
+                    peers.insert(peer_addr, (reader_tx, Instant::now()));
 
                     let writer = MpscWriter::new(writer_tx);
                     let reader = MpscReader::new(reader_rx);
@@ -58,7 +112,9 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                     let chan_info = info.make_channel_info(ChannelDetails::UdpServer {
                         server_addr,
                         peer_addr,
+                        locked: guard.is_locking(),
                     });
+                    known_peers.insert(peer_addr, chan_info.clone());
                     let channel = chan_factory.build(chan_info, reader, writer);
                     channel.spawn().await.discard();
 
@@ -71,11 +127,15 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                     );
                 }
 
-                let reader_tx = peers.get(&peer_addr).unwrap();
-                reader_tx.send(buf[0..bytes_read].to_vec()).await?;
-            }
+                let (reader_tx, last_seen) = peers.get_mut(&peer_addr).unwrap();
+                *last_seen = Instant::now();
+                if let Err(err) = reader_tx.send(buf[0..bytes_read].to_vec()).await {
+                    break Err(err.into());
+                }
+            };
 
-            Ok(())
+            known_peers.clear();
+            result
         });
 
         Ok((connection, handler))
@@ -94,7 +154,7 @@ impl UdpServer {
         udp_socket: Arc<UdpSocket>,
         mut writer_rx: mpsc::Receiver<Vec<u8>>,
     ) {
-        tokio::spawn(async move {
+        runtime::spawn(async move {
             loop {
                 if conn_state.is_closed() {
                     return;
@@ -122,9 +182,9 @@ fn on_close_handler(
     server_addr: SocketAddr,
     info: ConnectionInfo,
 ) {
-    tokio::spawn(async move {
+    runtime::spawn(async move {
         while !state.is_closed() {
-            tokio::time::sleep(SERVER_HANG_UP_TIMEOUT).await;
+            runtime::sleep(SERVER_HANG_UP_TIMEOUT).await;
         }
 
         log::debug!("[{info:?}] spawn wake-up connection to close server listening loop");
@@ -1,15 +1,22 @@
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use crate::asnc::runtime::UdpSocket;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
-use tokio::net::UdpSocket;
 
-/// A wrapper around [`UdpSocket`] that implements [`AsyncRead`] and [`AsyncWrite`].
-#[derive(Clone)]
+/// A wrapper around connected [`UdpSocket`] that implements [`AsyncRead`] and [`AsyncWrite`].
+///
+/// Reads and writes wait for socket readiness. Errors reported by a connected socket, like
+/// [`ErrorKind::ConnectionRefused`](std::io::ErrorKind::ConnectionRefused) caused by ICMP port
+/// unreachable messages, are returned to the caller.
+///
+/// Incoming datagrams are buffered, so frames can be read in arbitrary chunks.
 pub struct UdpRW {
     socket: Arc<UdpSocket>,
+    buf: Vec<u8>,
+    pos: usize,
 }
 
 impl UdpRW {
@@ -17,26 +24,55 @@ impl UdpRW {
     pub fn new(socket: UdpSocket) -> Self {
         Self {
             socket: Arc::new(socket),
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Clone for UdpRW {
+    fn clone(&self) -> Self {
+        Self {
+            socket: self.socket.clone(),
+            buf: Vec::new(),
+            pos: 0,
         }
     }
 }
 
 impl AsyncRead for UdpRW {
     fn poll_read(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
-        match self.socket.try_recv_buf(buf) {
-            Ok(_) => Poll::Ready(Ok(())),
-            Err(err) => match err.kind() {
-                ErrorKind::WouldBlock => {
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
+        while self.pos >= self.buf.len() {
+            let this = &mut *self;
+            this.buf.resize(u16::MAX as usize, 0);
+            this.pos = 0;
+
+            let mut datagram = ReadBuf::new(&mut this.buf);
+            match this.socket.poll_recv(cx, &mut datagram) {
+                Poll::Ready(Ok(())) => {
+                    let bytes_read = datagram.filled().len();
+                    this.buf.truncate(bytes_read);
+                }
+                Poll::Ready(Err(err)) => {
+                    this.buf.clear();
+                    return Poll::Ready(Err(err));
                 }
-                _ => Poll::Ready(Err(err)),
-            },
+                Poll::Pending => {
+                    this.buf.clear();
+                    return Poll::Pending;
+                }
+            }
         }
+
+        let bytes_read = buf.remaining().min(self.buf.len() - self.pos);
+        buf.put_slice(&self.buf[self.pos..self.pos + bytes_read]);
+        self.pos += bytes_read;
+
+        Poll::Ready(Ok(()))
     }
 }
 
@@ -46,16 +82,7 @@ impl AsyncWrite for UdpRW {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<Result<usize, Error>> {
-        match self.socket.try_send(buf) {
-            Ok(bytes_sent) => Poll::Ready(Ok(bytes_sent)),
-            Err(err) => match err.kind() {
-                ErrorKind::WouldBlock => {
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
-                }
-                _ => Poll::Ready(Err(err)),
-            },
-        }
+        self.socket.poll_send(cx, buf)
     }
 
     fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
@@ -0,0 +1,52 @@
+mod publisher;
+mod stream;
+mod subscriber;
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod zmq_tests {
+    use std::time::Duration;
+
+    use crate::asnc::runtime;
+    use crate::core::io::{ChannelDetails, ZmqPub, ZmqSub};
+    use crate::core::utils::net::pick_unused_port;
+    use crate::dialects::minimal::messages::Heartbeat;
+
+    use crate::asnc::prelude::*;
+    use crate::prelude::*;
+
+    const WAIT_DURATION: Duration = Duration::from_millis(100);
+    const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+    #[tokio::test]
+    async fn publish_and_subscribe() {
+        let endpoint = format!("tcp://127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let publisher = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(ZmqPub::new(&endpoint).unwrap().with_topic("mavlink/1"))
+            .build()
+            .await
+            .unwrap();
+        let mut subscriber = Node::asnc::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(ZmqSub::new(&endpoint).unwrap().with_topic("mavlink/"))
+            .build()
+            .await
+            .unwrap();
+        // Subscription should reach publisher before frames are sent
+        runtime::sleep(WAIT_DURATION).await;
+
+        publisher.send(&Heartbeat::default()).unwrap();
+
+        let (frame, callback) = subscriber.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 1);
+        assert!(matches!(
+            callback.info().details(),
+            ChannelDetails::ZmqSub { topic, .. } if topic == "mavlink/"
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use crate::asnc::runtime::{TcpListener, TcpStream};
+use tokio::io::AsyncWriteExt;
+
+use crate::asnc::consts::ZMQ_PIPE_CAPACITY;
+use crate::asnc::io::transport::tcp::server::on_close_handler;
+use crate::asnc::io::transport::zmq::stream::{handshake, publish, receive_subscriptions};
+use crate::asnc::io::{ChannelFactory, Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::zmtp::{SocketType, Subscriptions};
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionConf, ZmqPub};
+use crate::core::utils::Closer;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ZmqPub {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let server_addr = self.addr;
+        let listener = TcpListener::bind(self.addr).await?;
+
+        let conn_state = Closer::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), conn_state.to_shared());
+
+        let info = self.info().clone();
+        let topic = self.topic.clone();
+
+        let handler = ConnectionHandler::spawn(async move {
+            on_close_handler(conn_state.to_closable(), server_addr, info.clone());
+
+            while !conn_state.is_closed() {
+                let (stream, peer_addr) = listener.accept().await?;
+
+                let chan_info = info.make_channel_info(ChannelDetails::ZmqPub {
+                    server_addr,
+                    peer_addr,
+                    topic: topic.clone(),
+                });
+
+                let chan_factory = chan_factory.clone();
+                let topic = topic.clone();
+                runtime::spawn(async move {
+                    accept_subscriber(&chan_factory, chan_info, stream, topic).await
+                });
+            }
+
+            Ok(())
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+}
+
+/// Performs ZMTP handshake with a subscriber and attaches its channel.
+async fn accept_subscriber<V: MaybeVersioned>(
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    mut stream: TcpStream,
+    topic: String,
+) {
+    let decoder = runtime::timeout(
+        TCP_HANDSHAKE_TIMEOUT,
+        handshake(&mut stream, SocketType::Pub),
+    )
+    .await
+    .unwrap_or_else(|_| {
+        Err(Error::from(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "handshake timed out",
+        )))
+    });
+
+    let decoder = match decoder {
+        Ok(decoder) => decoder,
+        Err(err) => {
+            log::info!("[{chan_info:?}] subscriber rejected: {err:?}");
+            _ = stream.shutdown().await;
+            return;
+        }
+    };
+    log::debug!("[{chan_info:?}] subscriber connected");
+
+    let (socket_reader, socket_writer) = stream.into_split();
+    let (chan_pipe, relay_pipe) = tokio::io::duplex(ZMQ_PIPE_CAPACITY);
+    let (chan_reader, chan_writer) = tokio::io::split(chan_pipe);
+    let (relay_reader, relay_writer) = tokio::io::split(relay_pipe);
+    let subscriptions = Arc::new(Mutex::new(Subscriptions::default()));
+
+    {
+        let chan_info = chan_info.clone();
+        let subscriptions = subscriptions.clone();
+        runtime::spawn(async move {
+            if let Err(err) = receive_subscriptions(socket_reader, decoder, subscriptions).await {
+                log::debug!("[{chan_info:?}] subscriber failed: {err:?}");
+            }
+            // Channel reader receives EOF once subscriber is gone
+            drop(relay_writer);
+        });
+    }
+    {
+        let chan_info = chan_info.clone();
+        runtime::spawn(async move {
+            if let Err(err) = publish(relay_reader, socket_writer, topic, subscriptions).await {
+                log::debug!("[{chan_info:?}] can't publish to subscriber: {err:?}");
+            }
+        });
+    }
+
+    let channel = chan_factory.build(chan_info, chan_reader, chan_writer);
+    channel.spawn().await.discard();
+}
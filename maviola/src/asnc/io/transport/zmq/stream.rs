@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::asnc::runtime::{AsyncRead, AsyncWrite};
+use crate::core::io::zmtp::{self, SocketType, Subscriptions, ZmtpDecoder};
+use crate::core::io::FrameSplitter;
+
+use crate::prelude::*;
+
+/// Performs ZMTP handshake over an asynchronous `stream`.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) async fn handshake<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    socket_type: SocketType,
+) -> Result<ZmtpDecoder> {
+    stream.write_all(&zmtp::greeting()).await?;
+    let mut greeting = [0u8; zmtp::GREETING_SIZE];
+    stream.read_exact(&mut greeting).await?;
+    zmtp::check_greeting(&greeting)?;
+
+    stream.write_all(&zmtp::ready(socket_type)).await?;
+    let mut decoder = ZmtpDecoder::default();
+    let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+    let event = loop {
+        if let Some(event) = decoder.next()? {
+            break event;
+        }
+        let bytes_read = stream.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    };
+    zmtp::check_ready(event, socket_type)?;
+
+    Ok(decoder)
+}
+
+/// Writes frames published by a `PUB` peer to `pipe` until either side is closed.
+pub(super) async fn relay_published(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: ZmtpDecoder,
+    mut pipe: impl AsyncWrite + Unpin,
+) -> Result<()> {
+    let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(event) = decoder.next()? {
+            if let Some(frame) = zmtp::published_frame(event) {
+                pipe.write_all(&frame).await?;
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+/// Receives subscriptions of a `SUB` peer until it disconnects.
+pub(super) async fn receive_subscriptions(
+    mut reader: impl AsyncRead + Unpin,
+    mut decoder: ZmtpDecoder,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+) -> Result<()> {
+    let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(event) = decoder.next()? {
+            match subscriptions.lock() {
+                Ok(mut subscriptions) => subscriptions.handle(&event),
+                Err(err) => err.into_inner().handle(&event),
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+/// Publishes frames written to `pipe` to a `SUB` peer according to its subscriptions.
+pub(super) async fn publish(
+    mut pipe: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    topic: String,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+) -> Result<()> {
+    let topic = topic.as_bytes();
+    let mut splitter = FrameSplitter::default();
+    let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+
+    loop {
+        let bytes_read = pipe.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        splitter.push(&buffer[..bytes_read]);
+
+        while let Some(frame) = splitter.next() {
+            let key = zmtp::publication_key(topic, &frame);
+            let is_subscribed = match subscriptions.lock() {
+                Ok(subscriptions) => subscriptions.matches(key),
+                Err(err) => err.into_inner().matches(key),
+            };
+            if is_subscribed {
+                writer.write_all(&zmtp::publication(topic, &frame)).await?;
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::asnc::runtime::TcpStream;
+use tokio::io::AsyncWriteExt;
+
+use crate::asnc::consts::ZMQ_PIPE_CAPACITY;
+use crate::asnc::io::transport::zmq::stream::{handshake, relay_published};
+use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::zmtp::{self, SocketType};
+use crate::core::io::{ChannelDetails, ConnectionConf, ZmqSub};
+use crate::core::utils::SharedCloser;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ZmqSub {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let mut stream = TcpStream::connect(self.addr).await?;
+
+        let decoder = match runtime::timeout(
+            TCP_HANDSHAKE_TIMEOUT,
+            handshake(&mut stream, SocketType::Sub),
+        )
+        .await
+        {
+            Ok(decoder) => decoder?,
+            Err(_) => {
+                return Err(Error::from(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "handshake timed out",
+                )))
+            }
+        };
+        stream
+            .write_all(&zmtp::subscription(self.topic.as_bytes()))
+            .await?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection.info().make_channel_info(ChannelDetails::ZmqSub {
+            server_addr: self.addr,
+            topic: self.topic.clone(),
+        });
+        let (chan_pipe, relay_pipe) = tokio::io::duplex(ZMQ_PIPE_CAPACITY);
+
+        {
+            let info = self.info().clone();
+            runtime::spawn(async move {
+                if let Err(err) = relay_published(stream, decoder, relay_pipe).await {
+                    log::debug!("[{info:?}] subscription failed: {err:?}");
+                }
+            });
+        }
+
+        // Subscribers can't send messages, outgoing frames are discarded
+        let channel = chan_factory.build(chan_info, chan_pipe, tokio::io::sink());
+        let channel_state = channel.spawn().await;
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
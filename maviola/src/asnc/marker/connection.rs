@@ -1,30 +1,50 @@
-use crate::asnc::io::ConnectionBuilder;
+use crate::asnc::io::{ConnectionBuilder, DynConnectionBuilder};
+use crate::core::io::ConnectionInfo;
 use crate::core::marker::{HasConnConf, MaybeConnConf};
 use crate::core::utils::Sealed;
+use crate::error::ConfigDiagnostic;
 
 use crate::prelude::*;
 
 /// <sup>[`async`](crate::asnc)</sup>
 /// Variant of a node configuration which has an asynchronous connection config.
 #[derive(Debug)]
-pub struct AsyncConnConf<V: MaybeVersioned>(pub(crate) Box<dyn ConnectionBuilder<V>>);
+pub struct AsyncConnConf<V: MaybeVersioned>(pub(crate) Box<dyn DynConnectionBuilder<V>>);
 
 unsafe impl<V: MaybeVersioned> Sync for AsyncConnConf<V> {}
 
 impl<V: MaybeVersioned> Sealed for AsyncConnConf<V> {}
 impl<V: MaybeVersioned> HasConnConf for AsyncConnConf<V> {
+    fn info(&self) -> &ConnectionInfo {
+        self.0.info()
+    }
+
     fn is_repairable(&self) -> bool {
         self.0.is_repairable()
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.0.diagnostics()
+    }
 }
 impl<V: MaybeVersioned> MaybeConnConf for AsyncConnConf<V> {}
 
 impl<V: MaybeVersioned> AsyncConnConf<V> {
-    pub(in crate::asnc) fn new(builder: impl ConnectionBuilder<V> + 'static) -> Self {
+    /// <sup>`⍚` |</sup>
+    /// Creates connection configuration from a connection `builder`.
+    ///
+    /// Used by custom transports to implement [`ConnectionBuilder::to_conf`].
+    #[cfg(feature = "unstable")]
+    pub fn new(builder: impl ConnectionBuilder<V> + 'static) -> Self {
+        Self(Box::new(builder))
+    }
+
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) fn new(builder: impl ConnectionBuilder<V> + 'static) -> Self {
         Self(Box::new(builder))
     }
 
-    pub(in crate::asnc) fn connection(&self) -> &dyn ConnectionBuilder<V> {
+    pub(in crate::asnc) fn connection(&self) -> &dyn DynConnectionBuilder<V> {
         self.0.as_ref()
     }
 }
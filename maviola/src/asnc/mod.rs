@@ -63,6 +63,13 @@
 //! It is possible to get a list of active peers by [`Node::peers`] or check for peers availability
 //! using [`Node::has_peers`].
 //!
+//! Peers, that belong to the same MAVLink system, are aggregated into a
+//! [`RemoteSystem`](crate::protocol::RemoteSystem). Its state (mode, status, autopilot) is
+//! available through [`Node::system`]. Changes are emitted as [`Event::SystemChanged`], once enabled by
+//! [`Node::system_events`].
+//! Components of a system are available through [`Node::components`]. New components are reported
+//! as [`Event::NewComponent`] with their kind derived from heartbeats.
+//!
 //! ## Custom connections
 //!
 //! It is possible to create a custom connection by implementing a
@@ -71,6 +78,12 @@
 //! [`ChannelDetails::Custom`](crate::core::io::ChannelDetails::Custom) variants. Check for other
 //! relevant abstractions in [`io`] module.
 //!
+//! ## Runtime
+//!
+//! Asynchronous API runs on [Tokio](https://tokio.rs) only. Tasks, timers, and sockets are accessed
+//! through the [`runtime`] module, custom connections should prefer it to spawning Tokio tasks
+//! directly.
+//!
 //! ## Low-level I/O
 //!
 //! Low-level I/O primitives are available in [`core::io`](crate::core::io). Most of these
@@ -87,6 +100,7 @@ pub mod io;
 pub mod marker;
 pub mod node;
 pub mod prelude;
+pub mod runtime;
 
 mod network;
 #[cfg(not(feature = "unstable"))]
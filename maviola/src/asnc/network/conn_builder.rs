@@ -1,19 +1,20 @@
-use async_trait::async_trait;
 use std::marker::PhantomData;
 
 use crate::asnc::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::asnc::marker::AsyncConnConf;
 use crate::asnc::network::conn_handler::NetworkConnectionHandler;
+use crate::core::network::ConnectionOptions;
 use crate::core::utils::Closer;
+use crate::error::ConfigDiagnostic;
 
 use crate::prelude::*;
 
-#[async_trait]
 impl<V: MaybeVersioned> ConnectionBuilder<V> for Network<V, AsyncConnConf<V>> {
     async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
         let state = Closer::new();
 
         let (conn, chan_factory) = Connection::new(self.info.clone(), state.to_shared());
+        let conn = conn.with_network(self.handle.clone());
 
         let conn_handler = NetworkConnectionHandler::new(state, self, chan_factory).await?;
         let handler = ConnectionHandler::spawn(async move { conn_handler.handle().await });
@@ -25,9 +26,35 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for Network<V, AsyncConnConf<V>> {
         AsyncConnConf::new(Network {
             info: self.info.clone(),
             nodes: self.nodes.clone(),
+            options: self.options.clone(),
+            max_frame_ages: self.max_frame_ages.clone(),
+            dedup: self.dedup,
+            routing: self.routing,
+            routing_table: self.routing_table.clone(),
+            tap: self.tap.clone(),
+            injectors: self.injectors.clone(),
+            handle: self.handle.clone(),
             retry: self.retry,
             stop_on_node_down: self.stop_on_node_down,
             _version: PhantomData,
         })
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Network::diagnostics(self)
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ConnectionOptions<V, AsyncConnConf<V>> {
+    async fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        self.network().build().await
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        self.network().to_conf()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Network::diagnostics(self.network())
+    }
 }
@@ -1,10 +1,22 @@
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
 
-use crate::asnc::io::ConnectionBuilder;
+use async_stream::stream;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::Stream;
+
+use crate::asnc::io::{outgoing_channel, ConnectionBuilder, OutgoingFrameHandler};
 use crate::asnc::marker::AsyncConnConf;
-use crate::core::io::{ConnectionDetails, ConnectionInfo};
-use crate::core::marker::Unset;
-use crate::core::utils::UniqueId;
+use crate::asnc::node::FrameSender;
+use crate::asnc::runtime;
+use crate::core::consts::{NETWORK_POOLING_INTERVAL, NETWORK_TAP_CAPACITY};
+use crate::core::io::{ConnectionDetails, ConnectionId, ConnectionInfo};
+use crate::core::marker::{Proxy, Unset};
+use crate::core::network::{ConnectionOptions, InjectionTargets, NetworkHandle, TappedFrame};
+use crate::core::node::{PeerLocations, TrafficStats};
+use crate::core::utils::Closable;
+use crate::error::RecvTimeoutError;
+use crate::protocol::FrameProcessor;
 
 use crate::prelude::*;
 
@@ -15,6 +27,14 @@ impl Network<Versionless, Unset> {
         Network {
             info: ConnectionInfo::new(ConnectionDetails::Network),
             nodes: Default::default(),
+            options: Default::default(),
+            max_frame_ages: Default::default(),
+            dedup: Default::default(),
+            routing: Default::default(),
+            routing_table: Default::default(),
+            tap: Default::default(),
+            injectors: Default::default(),
+            handle: Default::default(),
             retry: Default::default(),
             stop_on_node_down: Default::default(),
             _version: PhantomData,
@@ -25,17 +45,13 @@ impl Network<Versionless, Unset> {
 impl<V: MaybeVersioned> Network<V, AsyncConnConf<V>> {
     /// <sup>[`async`](crate::asnc)</sup>
     /// Adds connection to a network.
+    ///
+    /// Returns [`ConnectionOptions`], that configure how the network treats this connection.
     pub fn add_connection(
-        mut self,
+        self,
         conn_conf: impl ConnectionBuilder<V> + 'static,
-    ) -> Network<V, AsyncConnConf<V>> {
-        let node = Node::asnc::<V>().connection(conn_conf).conf();
-        self.nodes.insert(UniqueId::new(), node);
-
-        Network {
-            nodes: self.nodes,
-            ..self
-        }
+    ) -> ConnectionOptions<V, AsyncConnConf<V>> {
+        self.add_node(Node::asnc::<V>().connection(conn_conf).conf())
     }
 
     /// <sup>[`async`](crate::asnc)</sup>
@@ -44,21 +60,216 @@ impl<V: MaybeVersioned> Network<V, AsyncConnConf<V>> {
     /// Signed connections will process incoming and outgoing frames according to corresponding
     /// signing strategies.
     pub fn add_signed_connection(
-        mut self,
+        self,
         conn_conf: impl ConnectionBuilder<V> + 'static,
         signer: FrameSigner,
-    ) -> Network<V, AsyncConnConf<V>> {
-        let node = Node::asnc::<V>()
-            .connection(conn_conf)
-            .signer(signer)
-            .conf();
-        self.nodes.insert(UniqueId::new(), node);
+    ) -> ConnectionOptions<V, AsyncConnConf<V>> {
+        self.add_node(
+            Node::asnc::<V>()
+                .connection(conn_conf)
+                .signer(signer)
+                .conf(),
+        )
+    }
 
-        Network {
-            nodes: self.nodes,
-            ..self
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Creates a frame sender, that injects frames into a network connection with specified `ID`.
+    ///
+    /// Injected frames are sent directly to the connection, bypassing filters, routing, and
+    /// conversions of the network. Frames are sent as they are, without signing or other
+    /// processing. Frames injected into connections, that are not running at the moment, are
+    /// dropped.
+    ///
+    /// Injector should be created before network is passed to a node. It stays valid, when the
+    /// connection is restarted.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    /// # use maviola::dialects::minimal::messages::Heartbeat;
+    /// # let frame = Endpoint::v2(MavLinkId::new(1, 1)).next_frame(&Heartbeat::default()).unwrap();
+    ///
+    /// let autopilot = TcpClient::new("127.0.0.1:5760").unwrap();
+    /// let autopilot_id = autopilot.id();
+    ///
+    /// let network = Network::asnc()
+    ///     .add_connection(autopilot)
+    ///     .add_connection(UdpServer::new("127.0.0.1:14550").unwrap());
+    /// let injector = network.injector(autopilot_id);
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(network)
+    ///     .build().await.unwrap();
+    ///
+    /// // Send frame to autopilot only
+    /// injector.send_frame(&frame).unwrap();
+    /// # }
+    /// ```
+    pub fn injector(&self, connection_id: ConnectionId) -> FrameSender<V, Proxy> {
+        let (sender, handler) = outgoing_channel(self.injectors.state());
+        let handler = Arc::new(Mutex::new(Some(handler)));
+
+        self.injectors.add(Box::new(move |state, targets| {
+            let handler = handler.clone();
+            runtime::spawn(async move {
+                pump_injected_frames(connection_id, &handler, &state, &targets).await
+            });
+        }));
+
+        FrameSender::new(
+            sender,
+            Arc::new(FrameProcessor::default()),
+            None,
+            TrafficStats::default(),
+            None,
+            PeerLocations::default(),
+        )
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Captures frames passing through the network.
+    ///
+    /// Returns a stream of frames received or sent by network connections (see [`TappedFrame`]
+    /// for details). Tap is read-only and does not affect routing. Stream finishes, once network
+    /// is stopped. Frames are dropped, when tap is not consumed fast enough and
+    /// [`NETWORK_TAP_CAPACITY`] frames are pending.
+    ///
+    /// Tap should be created before network is passed to a node.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let network = Network::asnc()
+    ///     .add_connection(TcpClient::new("127.0.0.1:5760").unwrap())
+    ///     .add_connection(UdpServer::new("127.0.0.1:14550").unwrap());
+    /// let tap = network.tap();
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(network)
+    ///     .build().await.unwrap();
+    ///
+    /// let mut tap = Box::pin(tap);
+    /// while let Some(tapped) = tap.next().await {
+    ///     println!(
+    ///         "{:?} {:?}: message #{}",
+    ///         tapped.direction(),
+    ///         tapped.connection_id(),
+    ///         tapped.frame().message_id()
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`NETWORK_TAP_CAPACITY`]: crate::core::consts::NETWORK_TAP_CAPACITY
+    pub fn tap(&self) -> impl Stream<Item = TappedFrame<V>> {
+        let (tx, mut rx) = mpsc::channel(NETWORK_TAP_CAPACITY);
+
+        self.tap
+            .subscribe(Box::new(move |frame| match tx.try_send(frame.clone()) {
+                Ok(_) | Err(TrySendError::Full(_)) => true,
+                Err(TrySendError::Closed(_)) => false,
+            }));
+
+        stream! {
+            while let Some(frame) = rx.recv().await {
+                yield frame;
+            }
+        }
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionOptions<V, AsyncConnConf<V>> {
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Adds connection to a network.
+    ///
+    /// See [`Network::add_connection`].
+    pub fn add_connection(
+        self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+    ) -> ConnectionOptions<V, AsyncConnConf<V>> {
+        Network::from(self).add_connection(conn_conf)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Adds a signed connection to a network.
+    ///
+    /// See [`Network::add_signed_connection`].
+    pub fn add_signed_connection(
+        self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+        signer: FrameSigner,
+    ) -> ConnectionOptions<V, AsyncConnConf<V>> {
+        Network::from(self).add_signed_connection(conn_conf, signer)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Creates a frame sender, that injects frames into a network connection with specified `ID`.
+    ///
+    /// See [`Network::injector`].
+    pub fn injector(&self, connection_id: ConnectionId) -> FrameSender<V, Proxy> {
+        self.network().injector(connection_id)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Captures frames passing through the network.
+    ///
+    /// See [`Network::tap`].
+    pub fn tap(&self) -> impl Stream<Item = TappedFrame<V>> {
+        self.network().tap()
+    }
+}
+
+impl<V: MaybeVersioned> NetworkHandle<V, AsyncConnConf<V>> {
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Adds connection to a running network.
+    ///
+    /// See [`NetworkHandle::add_node`] for details.
+    pub fn add_connection(
+        &self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+    ) -> Result<ConnectionId> {
+        self.add_node(Node::asnc::<V>().connection(conn_conf).conf())
+    }
+}
+
+/// Passes frames of an injector to a connection until network `state` is closed.
+///
+/// Injector `handler` is taken from a shared slot and returned back, once network is stopped, so
+/// it can be reused, when network is started again.
+async fn pump_injected_frames<V: MaybeVersioned>(
+    connection_id: ConnectionId,
+    slot: &Mutex<Option<OutgoingFrameHandler<V>>>,
+    state: &Closable,
+    targets: &InjectionTargets<V>,
+) {
+    let handler = match slot.lock() {
+        Ok(mut slot) => slot.take(),
+        Err(err) => err.into_inner().take(),
+    };
+    let mut handler = match handler {
+        Some(handler) => handler,
+        None => return,
+    };
+
+    while !state.is_closed() {
+        match handler.recv_timeout(NETWORK_POOLING_INTERVAL).await {
+            Ok(frame) => targets.inject(connection_id, frame),
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
         }
     }
+
+    match slot.lock() {
+        Ok(mut slot) => *slot = Some(handler),
+        Err(err) => *err.into_inner() = Some(handler),
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -71,6 +282,7 @@ mod tests {
 
     use std::time::Duration;
 
+    use crate::asnc::runtime;
     use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
     use crate::core::io::RetryStrategy;
     use crate::core::utils::net::pick_unused_port;
@@ -85,7 +297,7 @@ mod tests {
     const RECV_TIMEOUT: Duration = WAIT_DURATION;
 
     async fn wait() {
-        tokio::time::sleep(WAIT_DURATION).await;
+        runtime::sleep(WAIT_DURATION).await;
     }
 
     #[tokio::test]
@@ -97,7 +309,7 @@ mod tests {
             .add_connection(TcpServer::new(addr_1.as_str()).unwrap())
             .add_connection(TcpServer::new(addr_2.as_str()).unwrap());
 
-        assert_eq!(network.nodes.len(), 2);
+        assert_eq!(network.network().nodes.len(), 2);
 
         let mut server = Node::asnc::<V2>()
             .id(MavLinkId::new(1, 0))
@@ -139,6 +351,73 @@ mod tests {
         assert_eq!(frame.component_id(), 1);
     }
 
+    #[tokio::test]
+    async fn network_tap_and_injector() {
+        use crate::core::network::TapDirection;
+
+        let addr_a = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_b = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let server_a = TcpServer::new(addr_a.as_str()).unwrap();
+        let server_b = TcpServer::new(addr_b.as_str()).unwrap();
+        let (id_a, id_b) = (server_a.id(), server_b.id());
+
+        let network = Network::asnc()
+            .add_connection(server_a)
+            .add_connection(server_b);
+        let mut tap = Box::pin(network.tap());
+        let injector = network.injector(id_a);
+
+        let mut server = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build()
+            .await
+            .unwrap();
+
+        let mut client_a = Node::asnc::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_a.as_str()).unwrap())
+            .build()
+            .await
+            .unwrap();
+        let mut client_b = Node::asnc::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_b.as_str()).unwrap())
+            .build()
+            .await
+            .unwrap();
+        wait().await;
+
+        // Injected frames are sent only to the specified connection
+        let frame = Endpoint::v2(MavLinkId::new(9, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        injector.send_frame(&frame).unwrap();
+        let (frame, _) = client_a.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 9);
+        assert!(client_b.recv_frame_timeout(RECV_TIMEOUT).await.is_err());
+
+        let tapped = tap.next().await.unwrap();
+        assert_eq!(tapped.direction(), TapDirection::Outgoing);
+        assert_eq!(tapped.connection_id(), id_a);
+        assert_eq!(tapped.frame().system_id(), 9);
+
+        // Incoming frames are tapped with connection, that received them
+        client_b.send(&Heartbeat::default()).unwrap();
+        server.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+
+        let tapped = tap.next().await.unwrap();
+        assert_eq!(tapped.direction(), TapDirection::Incoming);
+        assert_eq!(tapped.connection_id(), id_b);
+        assert_eq!(tapped.frame().system_id(), 3);
+
+        // Tap finishes, once network is stopped
+        drop(server);
+        wait().await;
+        assert!(tap.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn network_reconnect() {
         let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
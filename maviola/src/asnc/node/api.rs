@@ -1,24 +1,57 @@
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_stream::stream;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tokio_stream::Stream;
 
 use crate::asnc::consts::CONN_BROADCAST_CHAN_CAPACITY;
 use crate::asnc::io::{Connection, ConnectionHandler};
 use crate::asnc::node::event::EventStream;
-use crate::asnc::node::handler::{HeartbeatEmitter, InactivePeersHandler, IncomingFramesHandler};
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+use crate::asnc::node::handler::MicroservicesHandler;
+use crate::asnc::node::handler::{
+    BlackBoxHandler, ConnectionEventsHandler, HeartbeatEmitter, InactivePeersHandler,
+    IncomingFramesHandler, KeepaliveHandler, PeriodicHandler, StatsReporter,
+};
 use crate::asnc::node::Event;
-use crate::core::io::{BroadcastScope, ConnectionInfo, OutgoingFrame};
+use crate::core::io::{
+    BroadcastScope, ChannelInfo, ConnectionInfo, EgressPriorities, FlushTracker, IncomingFrame,
+    OutgoingFrame,
+};
 use crate::core::marker::Proxy;
-use crate::core::node::{NodeApi, NodeApiInternal};
-use crate::core::utils::{Guarded, Sealed, SharedCloser, Switch};
-use crate::error::SendError;
-use crate::protocol::{DialectVersion, Endpoint, FrameProcessor, Peer};
+#[cfg(feature = "msrv-utils-params")]
+use crate::core::msrv::params::{ParamServer, ParamService};
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+use crate::core::msrv::registry::{Microservice, ServiceRegistry};
+#[cfg(feature = "msrv-utils-streams")]
+use crate::core::msrv::streams::{StreamRateController, StreamService};
+#[cfg(feature = "msrv-utils-timesync")]
+use crate::core::msrv::timesync::TimesyncResponder;
+use crate::core::node::{
+    BlackBox, ConnectionStatus, EventFilter, HealthTracker, Keepalive, LatencyStats, NodeApi,
+    NodeApiInternal, NodeHealth, PeerLocations, PeriodicSender, PeriodicTasks, QueueDepths,
+    TrafficStats,
+};
+use crate::core::utils::{Closer, Guarded, Sealed, SharedCloser, Switch};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::error::{SendError, SendResult};
+use crate::protocol::{
+    AnomalyDetector, AnomalyTracker, DialectVersion, Endpoint, FrameProcessor, LinkQualityMonitor,
+    Peer, RateGovernor, RemoteComponent, RemoteSystem, SystemId, SystemRegistry,
+};
 
 use crate::asnc::prelude::*;
 use crate::prelude::*;
@@ -30,8 +63,23 @@ pub struct AsyncApi<V: MaybeVersioned> {
     sender: FrameSender<V, Proxy>,
     processor: Arc<FrameProcessor>,
     peers: Arc<RwLock<HashMap<MavLinkId, Peer>>>,
+    locations: PeerLocations,
+    systems: SystemRegistry,
+    channel_events: Arc<AtomicBool>,
+    peers_watch: Arc<watch::Sender<Vec<Peer>>>,
+    status_watch: Arc<watch::Sender<ConnectionStatus>>,
     event_sender: EventSender<V>,
     event_receiver: EventReceiver<V>,
+    stats: TrafficStats,
+    stats_reporter: Mutex<Option<Closer>>,
+    health: HealthTracker,
+    periodic: PeriodicTasks,
+    #[cfg(any(
+        feature = "msrv-utils-params",
+        feature = "msrv-utils-streams",
+        feature = "msrv-utils-timesync"
+    ))]
+    services: ServiceRegistry<V>,
 }
 
 impl<V: MaybeVersioned> Sealed for AsyncApi<V> {}
@@ -47,6 +95,12 @@ impl<V: MaybeVersioned> NodeApiInternal<V> for AsyncApi<V> {
             .map_err(Error::from)
     }
 
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()> {
+        self.sender
+            .send_raw(OutgoingFrame::targeted(frame))
+            .map_err(Error::from)
+    }
+
     #[inline(always)]
     fn processor_internal(&self) -> &FrameProcessor {
         self.processor()
@@ -60,19 +114,55 @@ impl<V: MaybeVersioned> Debug for AsyncApi<V> {
 }
 
 impl<V: MaybeVersioned> AsyncApi<V> {
-    pub(super) fn new(connection: Connection<V>, processor: Arc<FrameProcessor>) -> Self {
+    pub(super) fn new(
+        connection: Connection<V>,
+        processor: Arc<FrameProcessor>,
+        latency: Option<LatencyStats>,
+        priorities: Option<EgressPriorities>,
+        health: HealthTracker,
+    ) -> Self {
         let (events_tx, events_rx) = mpmc::channel(CONN_BROADCAST_CHAN_CAPACITY);
+        let stats = TrafficStats::default();
+        let locations = PeerLocations::default();
 
-        let sender = FrameSender::new(connection.sender(), processor.clone());
-        let event_receiver = EventReceiver::new(events_rx, connection.state(), processor.clone());
+        let sender = FrameSender::new(
+            connection.sender(),
+            processor.clone(),
+            latency.clone(),
+            stats.clone(),
+            priorities,
+            locations.clone(),
+        );
+        let event_receiver = EventReceiver::new(
+            events_rx,
+            connection.state(),
+            processor.clone(),
+            latency,
+            stats.clone(),
+        );
 
         AsyncApi {
             connection,
             sender,
             processor: processor.clone(),
             peers: Arc::new(Default::default()),
+            locations,
+            systems: SystemRegistry::default(),
+            channel_events: Arc::new(AtomicBool::new(false)),
+            peers_watch: Arc::new(watch::channel(Vec::new()).0),
+            status_watch: Arc::new(watch::channel(ConnectionStatus::default()).0),
             event_sender: EventSender::new(events_tx),
             event_receiver,
+            stats,
+            stats_reporter: Mutex::new(None),
+            health,
+            periodic: PeriodicTasks::default(),
+            #[cfg(any(
+                feature = "msrv-utils-params",
+                feature = "msrv-utils-streams",
+                feature = "msrv-utils-timesync"
+            ))]
+            services: ServiceRegistry::default(),
         }
     }
 
@@ -108,6 +198,34 @@ impl<V: MaybeVersioned> AsyncApi<V> {
         !self.peers.read().await.is_empty()
     }
 
+    pub(super) fn system(&self, system_id: SystemId) -> Option<RemoteSystem> {
+        self.systems.get(system_id)
+    }
+
+    pub(super) fn systems(&self) -> Vec<RemoteSystem> {
+        self.systems.list()
+    }
+
+    pub(super) fn components(&self, system_id: SystemId) -> Vec<RemoteComponent> {
+        self.systems.components(system_id)
+    }
+
+    pub(super) fn system_events(&self, enabled: bool) {
+        self.systems.set_events(enabled)
+    }
+
+    pub(super) fn channel_events(&self, enabled: bool) {
+        self.channel_events.store(enabled, Ordering::Relaxed)
+    }
+
+    pub(super) fn watch_peers(&self) -> watch::Receiver<Vec<Peer>> {
+        self.peers_watch.subscribe()
+    }
+
+    pub(super) fn watch_connection(&self) -> watch::Receiver<ConnectionStatus> {
+        self.status_watch.subscribe()
+    }
+
     pub(super) fn frame_sender(&self) -> &FrameSender<V, Proxy> {
         &self.sender
     }
@@ -116,6 +234,11 @@ impl<V: MaybeVersioned> AsyncApi<V> {
         EventStream::new(self.event_receiver.clone())
     }
 
+    pub(super) fn events_filtered(&self, filter: EventFilter) -> EventReceiver<V> {
+        self.event_receiver
+            .subscribed(self.event_sender.subscribe(filter))
+    }
+
     pub(super) fn event_receiver(&self) -> &EventReceiver<V> {
         &self.event_receiver
     }
@@ -124,48 +247,211 @@ impl<V: MaybeVersioned> AsyncApi<V> {
         &mut self.event_receiver
     }
 
-    pub(super) async fn start_default_handlers(&self, heartbeat_timeout: Duration) {
-        self.handle_incoming_frames();
+    pub(super) async fn start_default_handlers(
+        &self,
+        heartbeat_timeout: Duration,
+        anomaly_detector: Option<&AnomalyDetector>,
+        rate_governor: Option<&RateGovernor>,
+        link_quality: Option<&LinkQualityMonitor>,
+        black_box: Option<&BlackBox>,
+        keepalive: Option<&Keepalive>,
+    ) {
+        if let Some(black_box) = black_box {
+            self.handle_black_box(black_box);
+        }
+        if let Some(keepalive) = keepalive {
+            self.handle_keepalive(keepalive);
+        }
+        let anomalies = anomaly_detector.map(AnomalyDetector::tracker);
+        self.handle_incoming_frames(anomalies.clone(), rate_governor, link_quality);
         self.handle_inactive_peers(heartbeat_timeout);
+        self.handle_connection_events(anomalies);
     }
 
     pub(super) async fn handle_conn_stop(&self, handler: ConnectionHandler) {
         handler.handle(&self.connection);
     }
 
+    #[allow(clippy::result_large_err)]
+    pub(super) fn emit(&self, event: Event<V>) -> SendResult<Event<V>> {
+        self.event_sender.send(event)
+    }
+
+    pub(super) fn inject_incoming(&self, frame: Frame<V>, origin: ChannelInfo) -> Result<()> {
+        if self.connection.state().is_closed() {
+            return Err(SendError(frame).into());
+        }
+        self.connection
+            .producer()
+            .send(IncomingFrame::new(frame, origin))?;
+        Ok(())
+    }
+
     pub(super) fn connection(&self) -> &Connection<V> {
         &self.connection
     }
 
-    fn handle_incoming_frames(&self) {
+    pub(super) fn flush_tracker(&self) -> FlushTracker {
+        self.connection.sender().flush_tracker().clone()
+    }
+
+    pub(super) fn health_tracker(&self) -> &HealthTracker {
+        &self.health
+    }
+
+    pub(super) fn health(
+        &self,
+        is_heartbeat_active: bool,
+        heartbeat_interval: Duration,
+    ) -> NodeHealth {
+        let queues = QueueDepths {
+            events: self.event_receiver.pending(),
+            outgoing: self.flush_tracker().pending(),
+        };
+        self.health.snapshot(
+            self.status_watch.borrow().clone(),
+            is_heartbeat_active,
+            heartbeat_interval,
+            queues,
+        )
+    }
+
+    pub(super) fn start_stats_report(&self, interval: Duration) {
+        let reporter_state = Closer::new();
+        let reporter_closable = reporter_state.to_closable();
+
+        match self.stats_reporter.lock() {
+            // Dropping the previous closer stops the previous reporter
+            Ok(mut stats_reporter) => {
+                stats_reporter.replace(reporter_state);
+            }
+            Err(err) => {
+                log::error!("[{:?}] can't start stats reporter: {err:?}", self.info());
+                return;
+            }
+        }
+        self.stats.enable();
+
+        let reporter = StatsReporter {
+            info: self.info().clone(),
+            stats: self.stats.clone(),
+            interval,
+            event_sender: self.event_sender.clone(),
+        };
+        reporter.spawn(self.connection.state(), reporter_closable);
+    }
+
+    fn handle_incoming_frames(
+        &self,
+        anomalies: Option<AnomalyTracker>,
+        rate_governor: Option<&RateGovernor>,
+        link_quality: Option<&LinkQualityMonitor>,
+    ) {
         let handler = IncomingFramesHandler {
             info: self.info().clone(),
             peers: self.peers.clone(),
+            locations: self.locations.clone(),
+            peers_watch: self.peers_watch.clone(),
+            systems: self.systems.clone(),
             receiver: self.connection.receiver(),
             event_sender: self.event_sender.clone(),
             sender: self.sender.clone(),
+            anomalies,
+            governor: rate_governor.map(RateGovernor::tracker),
+            link_quality: link_quality.map(LinkQualityMonitor::tracker),
+            stats: self.stats.clone(),
+            health: self.health.clone(),
         };
         handler.spawn(self.connection.share_state().to_closable());
     }
 
+    fn handle_black_box(&self, black_box: &BlackBox) {
+        let handler = BlackBoxHandler {
+            info: self.info().clone(),
+            black_box: black_box.clone(),
+            receiver: self.event_receiver.clone(),
+        };
+        handler.spawn();
+    }
+
+    fn handle_keepalive(&self, keepalive: &Keepalive) {
+        if keepalive.is_noop() {
+            return;
+        }
+
+        let handler = KeepaliveHandler {
+            info: self.info().clone(),
+            keepalive: keepalive.clone(),
+            dialect_version: self.processor().main_dialect().version(),
+            sender: self.sender.clone(),
+            receiver: self.event_receiver.clone(),
+            event_sender: self.event_sender.clone(),
+        };
+        handler.spawn();
+    }
+
     fn handle_inactive_peers(&self, timeout: Duration) {
         let handler = InactivePeersHandler {
             info: self.info().clone(),
             peers: self.peers.clone(),
+            locations: self.locations.clone(),
+            peers_watch: self.peers_watch.clone(),
+            systems: self.systems.clone(),
             timeout,
             event_sender: self.event_sender.clone(),
         };
 
         handler.spawn(self.connection.share_state().to_closable());
     }
+
+    fn handle_connection_events(&self, anomalies: Option<AnomalyTracker>) {
+        let receiver = match self.connection.take_events() {
+            Some(receiver) => receiver,
+            None => return,
+        };
+
+        let handler = ConnectionEventsHandler {
+            info: self.info().clone(),
+            receiver,
+            status_watch: self.status_watch.clone(),
+            event_sender: self.event_sender.clone(),
+            health: self.health.clone(),
+            anomalies,
+            channel_events: self.channel_events.clone(),
+        };
+
+        handler.spawn(self.connection.share_state().to_closable());
+    }
 }
 
 impl<V: Versioned> AsyncApi<V> {
+    pub(super) fn start_periodic<M: Message + 'static>(
+        &self,
+        endpoint: Endpoint<V>,
+        interval: Duration,
+        generator: impl FnMut() -> M + Send + 'static,
+    ) -> PeriodicSender {
+        let (task, should_spawn) = self.periodic.add(interval, generator);
+
+        if should_spawn {
+            let handler = PeriodicHandler {
+                info: self.info().clone(),
+                endpoint,
+                tasks: self.periodic.clone(),
+                sender: self.sender.clone(),
+            };
+            handler.spawn(self.connection.state());
+        }
+
+        task
+    }
+
     pub(crate) fn start_sending_heartbeats(
         &self,
         endpoint: Endpoint<V>,
         interval: Duration,
         is_active: Guarded<SharedCloser, Switch>,
+        heartbeat: Heartbeat,
         dialect_version: Option<DialectVersion>,
     ) {
         let emitter = HeartbeatEmitter {
@@ -173,11 +459,65 @@ impl<V: Versioned> AsyncApi<V> {
             endpoint,
             interval,
             sender: self.sender.clone(),
+            heartbeat,
             dialect_version,
+            health: Some(self.health.clone()),
             _version: PhantomData::<V>,
         };
         emitter.spawn(is_active);
     }
+
+    #[cfg(feature = "msrv-utils-params")]
+    pub(super) fn start_param_server(&self, endpoint: Endpoint<V>, server: &ParamServer) {
+        self.start_service(endpoint, Box::new(ParamService::new(server)));
+    }
+
+    #[cfg(feature = "msrv-utils-streams")]
+    pub(super) fn start_stream_controller(
+        &self,
+        endpoint: Endpoint<V>,
+        controller: &StreamRateController,
+    ) {
+        self.start_service(endpoint, Box::new(StreamService::new(controller)));
+    }
+
+    #[cfg(feature = "msrv-utils-timesync")]
+    pub(super) fn start_timesync_responder(
+        &self,
+        endpoint: Endpoint<V>,
+        is_active: Guarded<SharedCloser, Switch>,
+    ) {
+        if let Ok(true) = self.services.contains(TimesyncResponder::NAME) {
+            return;
+        }
+        self.start_service(endpoint, Box::new(TimesyncResponder::new(is_active)));
+    }
+
+    #[cfg(any(
+        feature = "msrv-utils-params",
+        feature = "msrv-utils-streams",
+        feature = "msrv-utils-timesync"
+    ))]
+    fn start_service(&self, endpoint: Endpoint<V>, service: Box<dyn Microservice<V>>) {
+        match self.services.register(service) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(err) => {
+                log::error!("[{:?}] can't register microservice: {err:?}", self.info());
+                return;
+            }
+        }
+
+        let handler = MicroservicesHandler {
+            info: self.info().clone(),
+            endpoint,
+            registry: self.services.clone(),
+            receiver: self.event_receiver.clone(),
+            sender: self.sender.clone(),
+            event_sender: self.event_sender.clone(),
+        };
+        handler.spawn();
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -187,15 +527,60 @@ impl<V: Versioned> AsyncApi<V> {
 #[derive(Clone)]
 pub(super) struct EventSender<V: MaybeVersioned> {
     inner: mpmc::Sender<Event<V>>,
+    subscriptions: Arc<Mutex<Vec<Subscription<V>>>>,
+}
+
+struct Subscription<V: MaybeVersioned> {
+    filter: EventFilter,
+    sender: mpmc::Sender<Event<V>>,
 }
 
 impl<V: MaybeVersioned> EventSender<V> {
     pub(super) fn new(sender: mpmc::Sender<Event<V>>) -> Self {
-        Self { inner: sender }
+        Self {
+            inner: sender,
+            subscriptions: Default::default(),
+        }
+    }
+
+    /// Registers a filtered subscription, that receives only matching events.
+    pub(super) fn subscribe(&self, filter: EventFilter) -> mpmc::Receiver<Event<V>> {
+        let (sender, receiver) = mpmc::channel(CONN_BROADCAST_CHAN_CAPACITY);
+        self.subscriptions().push(Subscription { filter, sender });
+        receiver
     }
 
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub(super) fn send(&self, event: Event<V>) -> core::result::Result<(), SendError<Event<V>>> {
-        self.inner.send(event).map_err(SendError::from).map(|_| ())
+        {
+            let mut subscriptions = self.subscriptions();
+            if !subscriptions.is_empty() {
+                // Subscriptions without receivers are removed
+                subscriptions.retain(|subscription| {
+                    !subscription.matches(&event) || subscription.sender.send(event.clone()).is_ok()
+                });
+            }
+        }
+
+        self.inner.send(event).map(|_| ())
+    }
+
+    fn subscriptions(&self) -> std::sync::MutexGuard<'_, Vec<Subscription<V>>> {
+        match self.subscriptions.lock() {
+            Ok(subscriptions) => subscriptions,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Subscription<V> {
+    fn matches(&self, event: &Event<V>) -> bool {
+        match event {
+            Event::Frame(frame, _) | Event::Invalid(frame, _, _) => {
+                self.filter.matches_frame(frame)
+            }
+            _ => self.filter.matches_other(),
+        }
     }
 }
@@ -3,16 +3,24 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use crate::asnc::io::ConnectionBuilder;
+use crate::asnc::io::{ConnectionBuilder, DynConnectionBuilder};
 use crate::asnc::marker::AsyncConnConf;
 use crate::asnc::node::{AsyncApi, EdgeNode, ProxyNode};
+use crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL;
 use crate::core::marker::{
     Edge, HasComponentId, HasSystemId, MaybeComponentId, MaybeConnConf, MaybeSystemId, NodeKind,
     Unset,
 };
+#[cfg(feature = "config")]
+use crate::core::network::Network;
+#[cfg(feature = "config")]
+use crate::core::node::{ConnectionConfig, NetworkConfig};
 use crate::core::node::{Node, NodeBuilder, NodeConf};
 use crate::core::utils::Guarded;
 
+#[cfg(feature = "config")]
+use crate::error::{ConfigDiagnostic, ConfigError};
+
 use crate::prelude::*;
 
 impl NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
@@ -33,10 +41,33 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned> NodeBuilder<S, C,
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: PhantomData,
         }
@@ -60,10 +91,33 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
             _api: PhantomData,
         }
@@ -85,20 +139,69 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned>
             conn_conf: AsyncConnConf::new(conn_conf),
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: self._api,
         }
     }
+
+    /// <sup>[`async`](crate::asnc)</sup> <sup>`config`</sup>
+    /// Set [`NodeConf::connection`] from a configuration, this builder was created from by
+    /// [`NodeBuilder::from_config`].
+    ///
+    /// Network configuration becomes a [`Network`] with a node for each connection.
+    ///
+    /// Returns [`ConfigDiagnostic::MissingConfigValue`], if configuration doesn't define a
+    /// connection.
+    #[cfg(feature = "config")]
+    #[allow(clippy::type_complexity)]
+    pub fn connection_from_config(
+        self,
+    ) -> Result<NodeBuilder<S, C, V, AsyncConnConf<V>, AsyncApi<V>>> {
+        match self
+            .config
+            .as_ref()
+            .and_then(|config| config.connection.clone())
+        {
+            Some(ConnectionConfig::Url(spec)) => Ok(self.connection(spec)),
+            Some(ConnectionConfig::Network(config)) => Ok(self.connection(network(&config))),
+            None => {
+                Err(ConfigError::from(ConfigDiagnostic::MissingConfigValue("connection")).into())
+            }
+        }
+    }
 }
 
 impl<K: NodeKind, V: MaybeVersioned> NodeConf<K, V, AsyncConnConf<V>> {
     /// <sup>[`async`](crate::asnc)</sup>
     /// Synchronous connection configuration.
-    pub fn connection(&self) -> &dyn ConnectionBuilder<V> {
+    pub fn connection(&self) -> &dyn DynConnectionBuilder<V> {
         self.connection_conf.connection()
     }
 }
@@ -115,6 +218,7 @@ impl<V: MaybeVersioned> NodeBuilder<Unset, Unset, V, AsyncConnConf<V>, AsyncApi<
     /// <sup>[`async`](crate::asnc)</sup>
     /// Creates an [`ProxyNode`] with synchronous API.
     pub async fn build(self) -> Result<ProxyNode<V>> {
+        self.validate()?;
         Node::try_from_async_conf(self.conf()).await
     }
 }
@@ -123,6 +227,7 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, AsyncConnCon
     /// <sup>[`async`](crate::asnc)</sup>
     /// Creates an [`EdgeNode`] with synchronous API.
     pub async fn build(self) -> Result<EdgeNode<V>> {
+        self.validate()?;
         Node::try_from_async_conf(self.conf()).await
     }
 }
@@ -136,9 +241,9 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, Async
     /// several nodes per connection representing different MAVLink components.
     ///
     /// The new edge node will inherit known dialects and frame processing settings from a "parent"
-    /// node. If [`signer`] and [`compat`] are not set explicitly, then they will be inherited as
-    /// well. All [custom processors](crate::docs::c3__custom_processing) from the "parent" node
-    /// will be added to the new one.
+    /// node. If [`signer`], [`compat`], and [`processing_order`] are not set explicitly, then they
+    /// will be inherited as well. All [custom processors](crate::docs::c3__custom_processing) from
+    /// the "parent" node will be added to the new one.
     ///
     /// **⚠** The [`heartbeat_timeout`] setting of a "parent" [`ProxyNode`] node will be ignored!
     ///
@@ -186,6 +291,7 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, Async
     ///
     /// [`signer`]: Self::signer
     /// [`compat`]: Self::compat
+    /// [`processing_order`]: Self::processing_order
     /// [`heartbeat_timeout`]: Self::heartbeat_timeout
     pub fn build_from(self, node: &ProxyNode<V>) -> EdgeNode<V> {
         let processor = Arc::new(self.reuse_processor(node.processor.as_ref()));
@@ -196,13 +302,39 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, Async
                 self.system_id.0,
                 self.component_id.0,
             ))),
-            api: AsyncApi::new(connection, processor.clone()),
+            api: AsyncApi::new(
+                connection,
+                processor.clone(),
+                self.latency_stats.clone(),
+                self.egress_priorities.clone(),
+                node.api.health_tracker().reuse(),
+            ),
             state: Default::default(),
             is_active: Guarded::from(node.api.share_state()),
             heartbeat_timeout: self.heartbeat_timeout,
-            heartbeat_interval: self.heartbeat_interval,
+            heartbeat_interval: self
+                .heartbeat_interval
+                .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL),
+            heartbeat: self.heartbeat,
+            shutdown_messages: self.shutdown_messages,
             processor: processor.clone(),
+            components: Default::default(),
             _version: node._version,
         }
     }
 }
+
+#[cfg(feature = "config")]
+fn network<V: MaybeVersioned>(config: &NetworkConfig) -> Network<V, AsyncConnConf<V>> {
+    let mut network = Network::asnc::<V>().stop_on_node_down(config.stop_on_node_down);
+    for spec in &config.connections {
+        network = network.add_connection(spec.clone()).into();
+    }
+    if let Some(retry) = config.retry {
+        network = network.retry(retry);
+    }
+    if let Some(window) = config.dedup {
+        network = network.dedup(window);
+    }
+    network
+}
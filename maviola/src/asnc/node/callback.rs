@@ -1,7 +1,8 @@
 use std::sync::Arc;
+use std::time::Instant;
 
-use crate::core::io::ChannelInfo;
 use crate::core::io::OutgoingFrame;
+use crate::core::io::{ChannelInfo, FrameMeta};
 use crate::core::node::CallbackApiInternal;
 use crate::core::utils::Sealed;
 use crate::protocol::FrameProcessor;
@@ -28,16 +29,23 @@ use crate::prelude::*;
 /// * [`Callback::forward`] forward a frame to all channels of a specific connection.
 #[derive(Clone, Debug)]
 pub struct Callback<V: MaybeVersioned> {
-    channel_info: ChannelInfo,
+    meta: FrameMeta,
     sender: FrameSender<V, Proxy>,
 }
 
 impl<V: MaybeVersioned> Callback<V> {
-    pub(super) fn new(channel_info: ChannelInfo, sender: FrameSender<V, Proxy>) -> Self {
-        Self {
-            channel_info,
-            sender,
-        }
+    pub(super) fn new(meta: FrameMeta, sender: FrameSender<V, Proxy>) -> Self {
+        Self { meta, sender }
+    }
+
+    /// Instant when the original frame was received.
+    pub fn received_at(&self) -> Instant {
+        self.meta.received_at()
+    }
+
+    /// Metadata of the original frame captured when it was received.
+    pub fn meta(&self) -> &FrameMeta {
+        &self.meta
     }
 
     pub(in crate::asnc) fn set_processor(&mut self, processor: Arc<FrameProcessor>) {
@@ -57,16 +65,26 @@ impl<V: MaybeVersioned> CallbackApiInternal<V> for Callback<V> {
         self.sender.processor().process_outgoing(&mut frame)?;
         Ok(frame)
     }
+
+    fn original_received_at(&self) -> Instant {
+        self.meta.received_at()
+    }
 }
 
 impl<V: MaybeVersioned> CallbackApi<V> for Callback<V> {
     fn info(&self) -> &ChannelInfo {
-        &self.channel_info
+        self.meta.channel()
     }
 }
 
 impl<V: MaybeVersioned> From<Callback<V>> for ChannelInfo {
     fn from(value: Callback<V>) -> Self {
-        value.channel_info
+        value.meta.into()
+    }
+}
+
+impl<V: MaybeVersioned> From<Callback<V>> for FrameMeta {
+    fn from(value: Callback<V>) -> Self {
+        value.meta
     }
 }
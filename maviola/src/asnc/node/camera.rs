@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::core::msrv::camera::{
+    CameraCommand, CameraInfo, CameraInformationRequest, CameraOperation, CameraSettings,
+    CameraStep, CapturedImage, ImageCapture,
+};
+use crate::error::RecvTimeoutError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc) | `msrv-utils-camera`</sup>
+/// Asynchronous [camera protocol](crate::core::msrv::camera) client bound to an edge node and a camera.
+///
+/// Created by [`Node::camera_client`]. Each method resolves once the camera acknowledges the command
+/// and sends the requested message, if any. Only frames received after an operation is started
+/// are passed to it.
+///
+/// Client numbers single image captures, so the same client should be used for consecutive
+/// captures.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main(flavor = "current_thread")] async fn main() {
+/// use maviola::core::msrv::camera::CameraSettings;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().await.unwrap();
+///
+/// let camera = node.camera_client(CameraSettings::new(MavLinkId::new(1, 100)));
+/// let image = camera.capture_image().await.unwrap();
+/// # }
+/// ```
+pub struct CameraClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: CameraSettings,
+    sequence: AtomicU32,
+}
+
+impl<'a, V: Versioned> CameraClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: CameraSettings) -> Self {
+        Self {
+            node,
+            settings,
+            sequence: AtomicU32::new(1),
+        }
+    }
+
+    /// Settings of camera operations.
+    pub fn settings(&self) -> &CameraSettings {
+        &self.settings
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Requests camera information.
+    pub async fn information(&self) -> Result<CameraInfo> {
+        self.run(CameraInformationRequest::new(self.settings)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Captures a single image and waits until the camera reports it.
+    pub async fn capture_image(&self) -> Result<CapturedImage> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        self.run(ImageCapture::new(self.settings, sequence)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Starts capturing `count` images every `interval`.
+    ///
+    /// If `count` is `0`, then images are captured until
+    /// [`stop_image_capture`](Self::stop_image_capture).
+    pub async fn start_image_capture(&self, interval: Duration, count: u32) -> Result<()> {
+        self.run(CameraCommand::start_image_capture(
+            self.settings,
+            interval,
+            count,
+        ))
+        .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Stops image capture.
+    pub async fn stop_image_capture(&self) -> Result<()> {
+        self.run(CameraCommand::stop_image_capture(self.settings))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Starts video streaming. Stream `0` addresses all streams of the camera.
+    pub async fn start_video_streaming(&self, stream_id: u8) -> Result<()> {
+        self.run(CameraCommand::start_video_streaming(
+            self.settings,
+            stream_id,
+        ))
+        .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Stops video streaming. Stream `0` addresses all streams of the camera.
+    pub async fn stop_video_streaming(&self, stream_id: u8) -> Result<()> {
+        self.run(CameraCommand::stop_video_streaming(
+            self.settings,
+            stream_id,
+        ))
+        .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Runs a camera `operation` over node connection.
+    ///
+    /// Operation uses its own settings instead of the client ones.
+    pub async fn run<T: CameraOperation>(&self, mut operation: T) -> Result<T::Output> {
+        let mut receiver = self.node.receiver_cloned();
+        let mut step = operation.start(Instant::now());
+
+        loop {
+            match step {
+                CameraStep::Wait => {}
+                CameraStep::Send(command) => self.node.send(&command)?,
+                CameraStep::Finished(result) => return result,
+            }
+
+            let timeout = operation
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match operation.handle(&frame, Instant::now()) {
+                    CameraStep::Wait => operation.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    operation.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
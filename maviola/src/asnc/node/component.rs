@@ -0,0 +1,242 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio_stream::Stream;
+
+use crate::asnc::node::event::EventStream;
+use crate::asnc::node::handler::HeartbeatEmitter;
+use crate::core::io::{BroadcastScope, ConnectionInfo};
+use crate::core::marker::{Edge, Proxy};
+use crate::core::node::{ComponentLease, SendFrameInternal, SendMessageInternal};
+use crate::core::utils::{default_heartbeat_message, Guarded, Sealed, SharedCloser, Switch};
+use crate::error::{NodeError, RecvResult, RecvTimeoutResult, TryRecvResult};
+use crate::protocol::{Behold, ComponentId, DialectVersion, FrameProcessor, SystemId};
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Additional MAVLink component exposed by an edge node.
+///
+/// Components allow a single process to expose several MAVLink components, like a camera, a gimbal,
+/// and an onboard computer, through one connection. Each component has its own component `ID`,
+/// sequence counter and heartbeats, while the connection and incoming events are shared with the
+/// parent node.
+///
+/// Components are created by [`Node::add_component`]. Component [receiver](Self::receiver_mut) skips
+/// frames addressed to other components. Like nodes, components have to be
+/// [activated](Self::activate) to emit heartbeats. Component `ID` is released, once component is
+/// dropped.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .build().await.unwrap();
+///
+/// // MAV_COMP_ID_CAMERA
+/// let mut camera = node.add_component(100).unwrap();
+/// camera.activate().await.unwrap();
+///
+/// let mut frames = camera.frames().unwrap();
+/// while let Some((frame, callback)) = frames.next().await {
+///     /* handle frames addressed to the camera */
+/// }
+/// # }
+/// ```
+pub struct NodeComponent<V: MaybeVersioned> {
+    kind: Edge<V>,
+    info: ConnectionInfo,
+    sender: FrameSender<V, Proxy>,
+    receiver: EventReceiver<V>,
+    is_active: Guarded<SharedCloser, Switch>,
+    heartbeat_interval: Duration,
+    dialect_version: Option<DialectVersion>,
+    _lease: ComponentLease,
+}
+
+impl<V: MaybeVersioned> NodeComponent<V> {
+    pub(super) fn new(node: &Node<Edge<V>, V, AsyncApi<V>>, lease: ComponentLease) -> Self {
+        let id = MavLinkId::new(node.system_id(), lease.component_id());
+
+        Self {
+            kind: Edge::new(Endpoint::new::<V>(id)),
+            info: node.info().clone(),
+            sender: node.frame_sender().clone(),
+            receiver: node.receiver_cloned().addressed_to(id),
+            is_active: Guarded::from(node.api.share_state()),
+            heartbeat_interval: node.heartbeat_interval,
+            dialect_version: node.dialect().version(),
+            _lease: lease,
+        }
+    }
+
+    /// MAVLink system `ID`, which is the same as for the parent node.
+    pub fn system_id(&self) -> SystemId {
+        self.kind.endpoint.system_id()
+    }
+
+    /// MAVLink component `ID`.
+    pub fn component_id(&self) -> ComponentId {
+        self.kind.endpoint.component_id()
+    }
+
+    /// Returns `true`, if component is active and emits heartbeats.
+    pub fn is_active(&self) -> bool {
+        self.is_active.is()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a mutable reference to an event receiver, that skips frames addressed to other
+    /// components.
+    ///
+    /// See [`EventReceiver::addressed_to`] for details.
+    #[inline(always)]
+    pub fn receiver_mut(&mut self) -> &mut EventReceiver<V> {
+        &mut self.receiver
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a new event receiver cloned from the component one.
+    #[inline(always)]
+    pub fn receiver_cloned(&self) -> EventReceiver<V> {
+        self.receiver.clone()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a new instance of a frame sender, that uses component endpoint.
+    pub fn sender(&self) -> FrameSender<V, Edge<V>> {
+        self.sender.clone().into_edge(self.kind.clone())
+    }
+
+    /// Deactivates the component.
+    ///
+    /// Inactive components do not emit heartbeats. [`NodeComponent::deactivate`] is idempotent.
+    pub fn deactivate(&mut self) {
+        self.is_active.set(false);
+    }
+}
+
+impl<V: Versioned> NodeComponent<V> {
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Activates the component.
+    ///
+    /// Active components emit heartbeats with the heartbeat interval of the parent node,
+    /// regardless of whether the node itself is active.
+    ///
+    /// [`NodeComponent::activate`] is idempotent while node is connected. Otherwise, it will
+    /// return [`NodeError::Inactive`] variant of [`Error::Node`].
+    pub async fn activate(&mut self) -> Result<()> {
+        if self.is_active.is() {
+            return Ok(());
+        }
+
+        self.is_active.set(true);
+        if !self.is_active.is() {
+            return Err(Error::Node(NodeError::Inactive));
+        }
+
+        let emitter = HeartbeatEmitter {
+            info: self.info.clone(),
+            endpoint: self.kind.endpoint.clone(),
+            interval: self.heartbeat_interval,
+            sender: self.sender.clone(),
+            heartbeat: default_heartbeat_message(),
+            dialect_version: self.dialect_version,
+            health: None,
+            _version: PhantomData::<V>,
+        };
+        emitter.spawn(self.is_active.clone());
+
+        Ok(())
+    }
+}
+
+impl<V: MaybeVersioned> Debug for NodeComponent<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeComponent")
+            .field("id", &self.kind.endpoint.id())
+            .field("info", &self.info)
+            .field("is_active", &self.is_active())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V: MaybeVersioned> Drop for NodeComponent<V> {
+    fn drop(&mut self) {
+        self.deactivate();
+    }
+}
+
+impl<V: MaybeVersioned> Sealed for NodeComponent<V> {}
+
+impl<V: MaybeVersioned> SendFrameInternal<V> for NodeComponent<V> {
+    #[inline(always)]
+    fn processor_internal(&self) -> &FrameProcessor {
+        self.sender.processor()
+    }
+
+    #[inline(always)]
+    unsafe fn route_frame_internal(&self, frame: Frame<V>, scope: BroadcastScope) -> Result<()> {
+        self.sender.route_frame_internal(frame, scope)
+    }
+
+    #[inline(always)]
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()> {
+        self.sender.route_targeted_internal(frame)
+    }
+}
+
+impl<V: MaybeVersioned> SendMessageInternal<V> for NodeComponent<V> {
+    fn endpoint(&self) -> &Endpoint<V> {
+        &self.kind.endpoint
+    }
+}
+
+impl<V: MaybeVersioned> SendFrame<V> for NodeComponent<V> {}
+impl<V: Versioned> SendMessage<V> for NodeComponent<V> {}
+impl SendVersionlessMessage for NodeComponent<Versionless> {}
+
+#[async_trait]
+impl<V: MaybeVersioned> ReceiveEvent<V> for NodeComponent<V> {
+    #[inline(always)]
+    async fn recv(&mut self) -> RecvResult<Event<V>> {
+        self.receiver.recv().await
+    }
+
+    #[inline(always)]
+    async fn recv_timeout(&mut self, timeout: Duration) -> RecvTimeoutResult<Event<V>> {
+        self.receiver.recv_timeout(timeout).await
+    }
+
+    #[inline(always)]
+    fn try_recv(&mut self) -> TryRecvResult<Event<V>> {
+        self.receiver.try_recv()
+    }
+
+    #[inline(always)]
+    async fn recv_many(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize> {
+        self.receiver.recv_many(buffer, max).await
+    }
+
+    #[inline(always)]
+    fn drain(&mut self, max: usize) -> Vec<Event<V>> {
+        self.receiver.drain(max)
+    }
+
+    #[inline(always)]
+    fn events(&self) -> Behold<impl Stream<Item = Event<V>>> {
+        Behold::new(EventStream::new(self.receiver.clone()))
+    }
+}
+
+#[async_trait]
+impl<V: MaybeVersioned> ReceiveFrame<V> for NodeComponent<V> {}
@@ -4,9 +4,15 @@ use std::task::{ready, Context, Poll};
 use tokio_stream::Stream;
 use tokio_util::sync::ReusableBoxFuture;
 
-use crate::asnc::consts::EVENTS_RECV_POOLING_INTERVAL;
-use crate::error::{FrameError, RecvError, TryRecvError};
-use crate::protocol::Peer;
+use crate::asnc::consts::EVENTS_STATE_CHECK_INTERVAL;
+use std::net::SocketAddr;
+
+use crate::core::io::{ChannelInfo, ConnectionInfo};
+use crate::core::node::{CustomEvent, StatsReport, ValidationReport};
+use crate::error::{RecvError, RecvTimeoutError};
+use crate::protocol::{
+    Anomaly, ComponentId, ComponentKind, LinkQuality, Peer, RemoteSystem, SystemId,
+};
 
 use crate::asnc::prelude::*;
 use crate::prelude::*;
@@ -19,12 +25,118 @@ pub enum Event<V: MaybeVersioned> {
     NewPeer(Peer),
     /// A [`Peer`] was lost due to the timeout.
     PeerLost(Peer),
+    /// Quality of the link to a [`Peer`] dropped below the threshold of [`LinkQualityMonitor`].
+    ///
+    /// Emitted once per degradation episode. If link quality returns to normal, the next degradation
+    /// will be reported again.
+    ///
+    /// [`LinkQualityMonitor`]: crate::protocol::LinkQualityMonitor
+    PeerDegraded(Peer, LinkQuality),
+    /// New [`RemoteSystem`] appeared or its state (mode, status, autopilot) has changed.
+    ///
+    /// Contains a snapshot of the system state. Current state is available through
+    /// [`Node::system`](crate::core::node::Node::system). Emitted only when enabled by
+    /// [`Node::system_events`](crate::core::node::Node::system_events).
+    SystemChanged(RemoteSystem),
+    /// New component of a remote system sent its first heartbeat.
+    ///
+    /// Unlike [`Event::NewPeer`], carries the [`ComponentKind`] derived from the heartbeat.
+    /// Components of a system are available through
+    /// [`Node::components`](crate::core::node::Node::components). Emitted only when enabled by
+    /// [`Node::system_events`](crate::core::node::Node::system_events).
+    NewComponent {
+        /// MAVLink system `ID`.
+        system_id: SystemId,
+        /// MAVLink component `ID`.
+        component_id: ComponentId,
+        /// Kind of the component.
+        component_kind: ComponentKind,
+    },
+    /// Node connection was lost due to failure of the underlying transport.
+    ///
+    /// If [`NodeConf::retry`] strategy is set, node attempts to restore the connection and emits
+    /// [`Event::ConnectionRestored`] once succeeded.
+    ///
+    /// [`NodeConf::retry`]: crate::core::node::NodeConf::retry
+    ConnectionLost(ConnectionInfo),
+    /// Node connection was restored after failure according to the [`NodeConf::retry`] strategy.
+    ///
+    /// [`NodeConf::retry`]: crate::core::node::NodeConf::retry
+    ConnectionRestored(ConnectionInfo),
+    /// No frames were received by node connection for the duration specified by
+    /// [`Keepalive::stale_after`].
+    ///
+    /// Emitted once per staleness episode. Once frames are received again, node emits
+    /// [`Event::ConnectionAlive`].
+    ///
+    /// [`Keepalive::stale_after`]: crate::core::node::Keepalive::stale_after
+    ConnectionStale(ConnectionInfo),
+    /// Node connection received frames after it was reported by [`Event::ConnectionStale`].
+    ConnectionAlive(ConnectionInfo),
+    /// Connection was added to a running [`Network`] by a [`NetworkHandle`].
+    ///
+    /// [`NetworkHandle`]: crate::core::network::NetworkHandle
+    ConnectionAdded(ConnectionInfo),
+    /// Connection was removed from a running [`Network`] by a [`NetworkHandle`] or was given up
+    /// by the network after all repair attempts have failed.
+    ///
+    /// [`NetworkHandle`]: crate::core::network::NetworkHandle
+    ConnectionRemoved(ConnectionInfo),
+    /// Connection became the active link of a [`FailoverGroup`] within a [`Network`].
+    ///
+    /// Emitted, once the first link is activated and on each switchover.
+    ///
+    /// [`FailoverGroup`]: crate::core::network::FailoverGroup
+    LinkActivated(ConnectionInfo),
+    /// New channel was opened within node connection, for example, a client connected to
+    /// a [`TcpServer`](crate::core::io::TcpServer).
+    ///
+    /// Emitted only when enabled by
+    /// [`Node::channel_events`](crate::core::node::Node::channel_events).
+    ChannelOpened(ChannelInfo),
+    /// Channel within node connection was closed, for example, a client disconnected from
+    /// a [`TcpServer`](crate::core::io::TcpServer).
+    ///
+    /// Emitted only when enabled by
+    /// [`Node::channel_events`](crate::core::node::Node::channel_events).
+    ChannelClosed(ChannelInfo),
     /// New [`Frame`] received.
+    ///
+    /// Arrival time and channel of the frame are available as [`Callback::meta`].
     Frame(Frame<V>, Callback<V>),
     /// New [`Frame`] received, but it hasn't passed validation.
-    Invalid(Frame<V>, FrameError, Callback<V>),
+    ///
+    /// The [`ValidationReport`] tells, why frame was rejected.
+    Invalid(Frame<V>, ValidationReport, Callback<V>),
+    /// Channel dropped bytes, that can't be parsed as a [`Frame`].
+    ///
+    /// Raw bytes are available as [`ValidationReport::raw_bytes`].
+    Malformed(ChannelInfo, ValidationReport),
+    /// Connection dropped data from a sender with the specified address, since sender is not
+    /// allowed.
+    ///
+    /// Emitted by a [`UdpServer`](crate::core::io::UdpServer) locked to a particular sender or
+    /// restricted by an allowlist. Each rejected sender is reported once.
+    SenderRejected(ConnectionInfo, SocketAddr),
+    /// Suspicious peer behavior detected by [`AnomalyDetector`].
+    ///
+    /// [`AnomalyDetector`]: crate::protocol::AnomalyDetector
+    Anomaly(Anomaly),
+    /// Periodic summary of node traffic enabled by [`Node::stats_report`].
+    ///
+    /// [`Node::stats_report`]: crate::core::node::Node::stats_report
+    StatsReport(StatsReport),
+    /// Application-defined event published by [`Node::emit`].
+    ///
+    /// [`Node::emit`]: crate::core::node::Node::emit
+    Custom(CustomEvent),
 }
 
+/// Stream of events received by an [`EventReceiver`].
+///
+/// Receiving future is owned by the stream and polled only from [`Stream::poll_next`]. Since
+/// receivers are cancellation-safe, dropping the stream never consumes an event, that was not
+/// yielded.
 pub(crate) struct EventStream<V: MaybeVersioned> {
     inner: ReusableBoxFuture<'static, (RecvResult<V>, EventReceiver<V>)>,
 }
@@ -42,31 +154,21 @@ impl<V: MaybeVersioned> EventStream<V> {
 async fn make_future<V: MaybeVersioned>(
     mut rx: EventReceiver<V>,
 ) -> (RecvResult<V>, EventReceiver<V>) {
-    let handler = tokio::task::spawn(async move {
-        let result = loop {
-            if rx.state().is_closed() {
-                break match rx.try_recv() {
-                    Ok(event) => Ok(event),
-                    Err(_) => Err(RecvError::Disconnected),
-                };
-            }
+    let result = loop {
+        if rx.state().is_closed() {
+            break rx.try_recv().map_err(|_| RecvError::Disconnected);
+        }
 
-            break match rx.try_recv() {
-                Ok(event) => Ok(event),
-                Err(err) => match err {
-                    TryRecvError::Empty => {
-                        tokio::time::sleep(EVENTS_RECV_POOLING_INTERVAL).await;
-                        continue;
-                    }
-                    TryRecvError::Disconnected => Err(RecvError::Disconnected),
-                    TryRecvError::Lagged(n) => Err(RecvError::Lagged(n)),
-                },
-            };
+        // Receiver is checked for closure between waits
+        break match rx.recv_timeout(EVENTS_STATE_CHECK_INTERVAL).await {
+            Ok(event) => Ok(event),
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => Err(RecvError::Disconnected),
+            Err(RecvTimeoutError::Lagged(n)) => Err(RecvError::Lagged(n)),
         };
+    };
 
-        (result, rx)
-    });
-    handler.await.unwrap()
+    (result, rx)
 }
 
 impl<V: MaybeVersioned> Stream for EventStream<V> {
@@ -95,14 +197,20 @@ mod async_event_tests {
     use crate::core::utils::Closer;
     use crate::protocol::FrameProcessor;
     use std::sync::Arc;
+    use std::time::Duration;
     use tokio_stream::StreamExt;
 
     #[tokio::test]
     async fn test_event_stream() {
         let state = Closer::new();
         let (tx, rx) = mpmc::channel(2);
-        let event_receiver =
-            EventReceiver::new(rx, state.to_closable(), Arc::new(FrameProcessor::default()));
+        let event_receiver = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        );
 
         let mut stream: EventStream<V2> = EventStream::new(event_receiver);
 
@@ -112,4 +220,121 @@ mod async_event_tests {
         assert!(matches!(stream.next().await.unwrap(), Event::NewPeer(_)));
         assert!(matches!(stream.next().await.unwrap(), Event::NewPeer(_)));
     }
+
+    #[tokio::test]
+    async fn test_recv_many() {
+        let state = Closer::new();
+        let (tx, rx) = mpmc::channel(8);
+        let mut event_receiver: EventReceiver<V2> = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        );
+
+        for _ in 0..5 {
+            tx.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        }
+
+        let mut buffer = Vec::new();
+        assert_eq!(event_receiver.recv_many(&mut buffer, 3).await.unwrap(), 3);
+        assert_eq!(event_receiver.recv_many(&mut buffer, 0).await.unwrap(), 0);
+        assert_eq!(event_receiver.drain(10).len(), 2);
+        assert!(event_receiver.drain(10).is_empty());
+        assert_eq!(buffer.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_subscription() {
+        use crate::asnc::node::api::EventSender;
+        use crate::core::node::{CustomEvent, EventFilter};
+
+        let state = Closer::new();
+        let (tx, _rx) = mpmc::channel(8);
+        let sender = EventSender::new(tx);
+        let subscribe = |filter| -> EventReceiver<V2> {
+            EventReceiver::new(
+                sender.subscribe(filter),
+                state.to_closable(),
+                Arc::new(FrameProcessor::default()),
+                None,
+                Default::default(),
+            )
+        };
+        let mut all_events = subscribe(EventFilter::new());
+        let mut frames = subscribe(EventFilter::new().frames_only());
+
+        sender.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        sender.send(Event::Custom(CustomEvent::new(42))).unwrap();
+
+        assert_eq!(all_events.drain(10).len(), 2);
+        assert!(frames.drain(10).is_empty());
+
+        // Dropped subscriptions do not affect delivery
+        drop(frames);
+        sender.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        assert_eq!(all_events.drain(10).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recv_in_select() {
+        let state = Closer::new();
+        let (tx, rx) = mpmc::channel(16);
+        let mut event_receiver: EventReceiver<V2> = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        );
+
+        tokio::spawn(async move {
+            for i in 0..10u8 {
+                tokio::time::sleep(Duration::from_millis(3)).await;
+                tx.send(Event::NewPeer(Peer::new(i, 1))).unwrap();
+            }
+        });
+
+        let mut received = Vec::new();
+        while received.len() < 10 {
+            tokio::select! {
+                biased;
+                _ = tokio::time::sleep(Duration::from_millis(1)) => continue,
+                event = event_receiver.recv() => match event.unwrap() {
+                    Event::NewPeer(peer) => received.push(peer.system_id()),
+                    _ => unreachable!(),
+                },
+            }
+        }
+
+        assert_eq!(received, (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_stream_keeps_events() {
+        let state = Closer::new();
+        let (tx, rx) = mpmc::channel(16);
+        let receiver: EventReceiver<V2> = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        )
+        .join_group("workers");
+        let mut other = receiver.clone();
+
+        let mut stream = EventStream::new(receiver);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), stream.next())
+                .await
+                .is_err()
+        );
+        drop(stream);
+
+        tx.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        let event = other.recv_timeout(Duration::from_millis(100)).await;
+        assert!(matches!(event, Ok(Event::NewPeer(_))));
+    }
 }
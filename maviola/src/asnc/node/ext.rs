@@ -3,16 +3,62 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
+use tokio::sync::watch;
 use tokio_stream::Stream;
 
+use crate::asnc::consts::SHUTDOWN_FLUSH_POOLING_INTERVAL;
 use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::node::event::EventStream;
+use crate::asnc::node::handler::ConnectionSupervisor;
+#[cfg(feature = "msrv-utils-camera")]
+use crate::asnc::node::CameraClient;
+#[cfg(feature = "msrv-utils-ftp")]
+use crate::asnc::node::FtpClient;
+#[cfg(feature = "msrv-utils-gcs")]
+use crate::asnc::node::GcsClient;
+#[cfg(feature = "msrv-utils-gimbal")]
+use crate::asnc::node::GimbalClient;
+use crate::asnc::node::{NodeComponent, NodeStreamSink};
+use crate::asnc::runtime;
+use crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL;
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionId, FlushProgress};
 use crate::core::marker::{Edge, NodeKind, Proxy};
-use crate::core::node::{NodeBuilder, NodeConf};
-use crate::core::utils::Guarded;
-use crate::error::{NodeError, RecvResult, RecvTimeoutResult, TryRecvResult};
-use crate::protocol::{Behold, Peer, Unset};
+#[cfg(feature = "msrv-utils-camera")]
+use crate::core::msrv::camera::CameraSettings;
+#[cfg(feature = "msrv-utils-ftp")]
+use crate::core::msrv::ftp::FtpSettings;
+#[cfg(feature = "msrv-utils-gcs")]
+use crate::core::msrv::gcs::GcsSettings;
+#[cfg(feature = "msrv-utils-gimbal")]
+use crate::core::msrv::gimbal::GimbalSettings;
+#[cfg(feature = "msrv-utils-mission")]
+use crate::core::msrv::mission::{
+    MissionReceiver, MissionSender, MissionSettings, MissionStep, MissionTransfer,
+};
+#[cfg(feature = "msrv-utils-params")]
+use crate::core::msrv::params::ParamServer;
+#[cfg(feature = "msrv-utils-streams")]
+use crate::core::msrv::streams::StreamRateController;
+#[cfg(feature = "msrv-utils-timesync")]
+use crate::core::msrv::timesync::{
+    LatencyMeasurement, TimesyncSettings, TimesyncStats, TimesyncStep,
+};
+use crate::core::network::NetworkHandle;
+use crate::core::node::{
+    ConnectionStatus, EventFilter, HealthTracker, NodeBuilder, NodeConf, NodeHealth,
+    PeriodicSender, ShutdownReport,
+};
+use crate::core::utils::{decode_message, trace, Guarded};
+#[cfg(feature = "msrv-utils-mission")]
+use crate::dialects::common::messages::MissionItemInt;
+use crate::error::RecvTimeoutError;
+use crate::error::{NodeError, RecvResult, RecvTimeoutResult, SendResult, TryRecvResult};
+use crate::protocol::{
+    Behold, ComponentId, Payload, Peer, RemoteComponent, RemoteSystem, SystemId, Unset,
+};
 
 use crate::asnc::prelude::*;
 use crate::prelude::*;
@@ -35,10 +81,26 @@ impl<K: NodeKind, V: MaybeVersioned> Node<K, V, AsyncApi<V>> {
     ///
     /// Creates an instance of [`Node`] from [`NodeConf`].
     pub async fn try_from_async_conf(conf: NodeConf<K, V, AsyncConnConf<V>>) -> Result<Self> {
-        let (conn, conn_handler) = conf.connection().build().await?;
+        let span = trace::connection_span(conf.connection().info());
+        trace::instrument(Self::instantiate(conf), span).await
+    }
+
+    async fn instantiate(conf: NodeConf<K, V, AsyncConnConf<V>>) -> Result<Self> {
+        let health = HealthTracker::default();
+        let (conn, conn_handler) = match ConnectionSupervisor::new(&conf, health.clone()) {
+            Some(supervisor) => supervisor.connect().await?,
+            None => conf.connection().build().await?,
+        };
 
         let processor = Arc::new(conf.make_processor());
-        let api = AsyncApi::new(conn, processor.clone());
+        let api = AsyncApi::new(
+            conn,
+            processor.clone(),
+            conf.latency_stats.clone(),
+            conf.egress_priorities.clone(),
+            health,
+        );
+        api.channel_events(conf.channel_events);
 
         let state = api.share_state();
         let is_active = Guarded::from(&state);
@@ -49,13 +111,25 @@ impl<K: NodeKind, V: MaybeVersioned> Node<K, V, AsyncApi<V>> {
             state,
             is_active,
             heartbeat_timeout: conf.heartbeat_timeout,
-            heartbeat_interval: conf.heartbeat_interval,
+            heartbeat_interval: conf
+                .heartbeat_interval
+                .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL),
+            heartbeat: conf.heartbeat,
+            shutdown_messages: conf.shutdown_messages,
             processor,
+            components: Default::default(),
             _version: PhantomData,
         };
 
         node.api
-            .start_default_handlers(node.heartbeat_timeout)
+            .start_default_handlers(
+                node.heartbeat_timeout,
+                conf.anomaly_detector.as_ref(),
+                conf.rate_governor.as_ref(),
+                conf.link_quality.as_ref(),
+                conf.black_box.as_ref(),
+                conf.keepalive.as_ref(),
+            )
             .await;
         node.api.handle_conn_stop(conn_handler).await;
 
@@ -80,6 +154,215 @@ impl<K: NodeKind, V: MaybeVersioned> Node<K, V, AsyncApi<V>> {
         self.api.peers().await
     }
 
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a snapshot of a remote system with the specified `ID`, if it is known.
+    ///
+    /// Remote systems are tracked by their heartbeats. Changes of system state are reported as
+    /// [`Event::SystemChanged`] events, once enabled by [`Node::system_events`].
+    pub fn system(&self, system_id: SystemId) -> Option<RemoteSystem> {
+        self.api.system(system_id)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns snapshots of all known remote systems ordered by their `ID`s.
+    pub fn systems(&self) -> Vec<RemoteSystem> {
+        self.api.systems()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns snapshots of active components of a remote system with the specified `ID` ordered
+    /// by their `ID`s.
+    ///
+    /// New components are reported as [`Event::NewComponent`] events, once enabled by
+    /// [`Node::system_events`]. Returns an empty list, if system is unknown.
+    pub fn components(&self, system_id: SystemId) -> Vec<RemoteComponent> {
+        self.api.components(system_id)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Enables or disables [`Event::SystemChanged`] and [`Event::NewComponent`] events (disabled by
+    /// default).
+    ///
+    /// When enabled, node emits an event each time a remote system appears or changes its mode,
+    /// status, or autopilot, and each time a new component of a remote system appears.
+    pub fn system_events(&self, enabled: bool) {
+        self.api.system_events(enabled)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Enables or disables [`Event::ChannelOpened`] and [`Event::ChannelClosed`] events (disabled by
+    /// default).
+    ///
+    /// When enabled, node emits an event each time a channel of its connection is opened or closed,
+    /// for example, when a client connects to or disconnects from a server.
+    pub fn channel_events(&self, enabled: bool) {
+        self.api.channel_events(enabled)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a [`watch::Receiver`] over the current set of peers ordered by their `ID`s.
+    ///
+    /// The set changes only when peers appear or are lost, heartbeats of known peers do not wake
+    /// up the receiver. This allows to await changes without consuming and filtering node
+    /// [`events`](Node::events).
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .id(MavLinkId::new(255, 190))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let mut peers = node.watch_peers();
+    /// while peers.changed().await.is_ok() {
+    ///     println!("peers: {:?}", *peers.borrow_and_update());
+    /// }
+    /// # }
+    /// ```
+    pub fn watch_peers(&self) -> watch::Receiver<Vec<Peer>> {
+        self.api.watch_peers()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a [`watch::Receiver`] over the [`ConnectionStatus`] of node connection.
+    ///
+    /// Status changes when connection is lost or restored, when channels are opened or closed,
+    /// and finally when connection is closed.
+    pub fn watch_connection(&self) -> watch::Receiver<ConnectionStatus> {
+        self.api.watch_connection()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a snapshot of node health.
+    ///
+    /// The snapshot includes connection status, the last time a frame was received from each open
+    /// channel, status of connection restoration and heartbeat emitter, and depths of node queues.
+    /// Unlike node events, health can be checked at any moment, which is useful for liveness and
+    /// readiness probes in Kubernetes or systemd watchdog notifications.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// # #[tokio::main] async fn main() {
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let health = node.health();
+    /// if !health.is_ready() {
+    ///     println!("not ready: {:?}", health.connection);
+    /// }
+    /// # }
+    /// ```
+    pub fn health(&self) -> NodeHealth {
+        self.api
+            .health(self.is_active.is(), self.heartbeat_interval)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a [`NetworkHandle`], if node connection is a [`Network`].
+    ///
+    /// Network handle allows to add and remove connections, while network is running.
+    pub fn network_handle(&self) -> Option<NetworkHandle<V, AsyncConnConf<V>>> {
+        self.api.connection().network_handle().cloned()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Pauses node connection without closing it.
+    ///
+    /// Paused connection stops reading from its transport, so transport flow control, like TCP
+    /// backpressure, applies to the remote side. Outgoing frames are kept by the connection and
+    /// written, once it is [resumed](Node::resume). This allows another process to temporarily
+    /// take over a transport, for example, a serial port during firmware upload.
+    ///
+    /// For a [`Network`], all its connections are paused, including connections added later by
+    /// [`NetworkHandle`]. Use [`Node::pause_connection`] to pause a particular network connection.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .id(MavLinkId::new(255, 190))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// node.pause();
+    /// /* transport is used by another process */
+    /// node.resume();
+    /// # }
+    /// ```
+    pub fn pause(&self) {
+        self.info().pause_switch().pause();
+        if let Some(network) = self.api.connection().network_handle() {
+            network.set_paused(true);
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Resumes node connection paused by [`Node::pause`].
+    ///
+    /// For a [`Network`], all its connections are resumed.
+    pub fn resume(&self) {
+        self.info().pause_switch().resume();
+        if let Some(network) = self.api.connection().network_handle() {
+            network.set_paused(false);
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns `true`, if node connection is paused by [`Node::pause`].
+    pub fn is_paused(&self) -> bool {
+        self.info().is_paused()
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Pauses connection with specified `ID` without closing it.
+    ///
+    /// Accepts `ID` of node connection, which is the same as [`Node::pause`], or `ID` of one of
+    /// [`Network`] connections. In the latter case, other network connections keep running. See
+    /// [`NetworkHandle::pause_connection`] for details.
+    ///
+    /// Returns [`NodeError::UnknownConnection`], if there is no connection with such `ID`.
+    pub fn pause_connection(&self, connection_id: ConnectionId) -> Result<()> {
+        if connection_id == self.info().id() {
+            self.pause();
+            return Ok(());
+        }
+
+        match self.api.connection().network_handle() {
+            Some(network) => network.pause_connection(connection_id),
+            None => Err(NodeError::UnknownConnection(connection_id).into()),
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Resumes connection paused by [`Node::pause_connection`].
+    ///
+    /// Returns [`NodeError::UnknownConnection`], if there is no connection with such `ID`.
+    pub fn resume_connection(&self, connection_id: ConnectionId) -> Result<()> {
+        if connection_id == self.info().id() {
+            self.resume();
+            return Ok(());
+        }
+
+        match self.api.connection().network_handle() {
+            Some(network) => network.resume_connection(connection_id),
+            None => Err(NodeError::UnknownConnection(connection_id).into()),
+        }
+    }
+
     /// Returns a mutable reference to an event receiver.
     ///
     /// This receiver can be cloned and passed to other threads.
@@ -104,10 +387,337 @@ impl<K: NodeKind, V: MaybeVersioned> Node<K, V, AsyncApi<V>> {
         self.api.event_receiver().clone()
     }
 
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Subscribes to node events, that match the `filter`.
+    ///
+    /// Unlike filtering the stream returned by [`events`](Node::events), the filter is applied
+    /// when events are distributed to subscribers. Events, that don't match the filter, are not
+    /// cloned into the subscription. See [`EventFilter`] for details.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    /// use maviola::core::node::EventFilter;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let mut events = node.events_filtered(EventFilter::message_ids([0, 33]).system_id(1))
+    ///     .unwrap();
+    /// while let Some(event) = events.next().await {
+    ///     /* only frames of heartbeats and positions of system `1`, and other events */
+    /// }
+    /// # }
+    /// ```
+    pub fn events_filtered(&self, filter: EventFilter) -> Behold<impl Stream<Item = Event<V>>> {
+        Behold::new(EventStream::new(self.api.events_filtered(filter)))
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Turns node into an adapter, that implements both [`Stream`] of events and
+    /// [`Sink`](futures_sink::Sink) of outgoing frames.
+    ///
+    /// This allows to use node with stream combinators, codecs, and `select!` loops. The node is
+    /// closed, once the adapter is dropped. See [`NodeStreamSink`] for details.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let mut stream_sink = node.into_stream_sink();
+    /// while let Some(event) = stream_sink.next().await {
+    ///     if let Event::Frame(frame, _) = event {
+    ///         /* forward frames with `futures::SinkExt::send` */
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn into_stream_sink(self) -> NodeStreamSink<K, V> {
+        NodeStreamSink::new(self)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Returns a new event receiver, that receives only events matching the `filter`.
+    ///
+    /// Same as [`events_filtered`](Node::events_filtered) but returns [`EventReceiver`].
+    ///
+    /// **⚠** In order to have access to [`EventReceiver`] methods, you have to import
+    /// [`ReceiveEvent`] and [`ReceiveFrame`] traits. You may import [`asnc::prelude`] as well.
+    ///
+    /// [`asnc::prelude`]: crate::asnc::prelude
+    pub fn receiver_filtered(&self, filter: EventFilter) -> EventReceiver<V> {
+        self.api.events_filtered(filter)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Publishes an `event` to all node event subscribers.
+    ///
+    /// This is intended for application-defined [`Event::Custom`] events. It allows to deliver
+    /// application-level signals through the same receivers as frames and peer updates. Other
+    /// events are delivered as is, except emitted frames which still pass through the node's frame
+    /// processor.
+    ///
+    /// Returns the event back within [`SendError`] if node is disconnected.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    /// use maviola::core::node::CustomEvent;
+    ///
+    /// struct MissionUploaded;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// node.emit(Event::Custom(CustomEvent::new(MissionUploaded))).unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`SendError`]: crate::error::SendError
+    #[allow(clippy::result_large_err)]
+    pub fn emit(&self, event: Event<V>) -> SendResult<Event<V>> {
+        self.api.emit(event)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Creates channel info for frames injected from the specified `origin`.
+    ///
+    /// The result is intended to be passed to [`Node::inject_incoming`]. Injected frames will
+    /// carry [`ChannelDetails::Injected`] with the provided `origin` name.
+    pub fn injection_origin(&self, origin: impl Into<String>) -> ChannelInfo {
+        self.api.info().make_channel_info(ChannelDetails::Injected {
+            origin: origin.into(),
+        })
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Pushes a `frame` into the node's incoming pipeline as if it was received from `origin`.
+    ///
+    /// Injected frames are handled exactly like the ones received from the connection: they
+    /// update peers and statistics, pass through the node's frame processor, and are emitted as
+    /// a regular [`Event::Frame`] (or [`Event::Invalid`], if validation fails). The callback of
+    /// such frame is bound to the `origin` channel, that has no transport. Use
+    /// [`Node::injection_origin`] to create origin info.
+    ///
+    /// This is useful for bridging frames from non-standard sources like CAN buses or
+    /// application-level tunnels.
+    ///
+    /// Returns [`Error::Sync`] if node is disconnected.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    /// use maviola::dialects::minimal::messages::Heartbeat;
+    ///
+    /// let mut node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let origin = node.injection_origin("can-bus");
+    /// let frame = Endpoint::v2(MavLinkId::new(17, 1))
+    ///     .next_frame(&Heartbeat::default())
+    ///     .unwrap();
+    ///
+    /// node.inject_incoming(frame, origin).unwrap();
+    /// let (frame, callback) = node.recv_frame().await.unwrap();
+    /// # }
+    /// ```
+    pub fn inject_incoming(&self, frame: Frame<V>, origin: ChannelInfo) -> Result<()> {
+        self.api.inject_incoming(frame, origin)
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Waits for the first frame, that satisfies the `predicate`, within a `timeout`.
+    ///
+    /// Frames are received by a separate subscriber, so other receivers of this node still get
+    /// all events including the matched frame. Only frames received after this method is called
+    /// are considered. Invalid frames are skipped.
+    ///
+    /// Use [`Node::recv_message_matching`] to wait for a message of a particular type.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .id(MavLinkId::new(255, 190))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let (frame, _) = node
+    ///     .recv_matching(|frame| frame.system_id() == 1, Duration::from_secs(1))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn recv_matching<F>(
+        &self,
+        mut predicate: F,
+        timeout: Duration,
+    ) -> RecvTimeoutResult<(Frame<V>, Callback<V>)>
+    where
+        F: FnMut(&Frame<V>) -> bool,
+    {
+        let mut receiver = self.receiver_cloned();
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, callback)) => {
+                    if predicate(&frame) {
+                        return Ok((frame, callback));
+                    }
+                }
+                Err(RecvTimeoutError::Lagged(_)) => {}
+                Err(err) => return Err(err),
+            }
+
+            if Instant::now() >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Waits for the first message of type `M`, that satisfies the `predicate`, within a
+    /// `timeout`.
+    ///
+    /// Returns decoded message along with the frame, that contains it. Behaves the same way as
+    /// [`Node::recv_matching`].
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    /// use maviola::dialects::minimal::messages::Heartbeat;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .id(MavLinkId::new(255, 190))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let (heartbeat, frame, _) = node
+    ///     .recv_message_matching::<Heartbeat, _>(|_| true, Duration::from_secs(1))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn recv_message_matching<M, F>(
+        &self,
+        mut predicate: F,
+        timeout: Duration,
+    ) -> RecvTimeoutResult<(M, Frame<V>, Callback<V>)>
+    where
+        M: Message + for<'a> TryFrom<&'a Payload>,
+        F: FnMut(&M) -> bool,
+    {
+        let mut matched = None;
+        let (frame, callback) = self
+            .recv_matching(
+                |frame| match decode_message::<M, V>(frame) {
+                    Some(message) if predicate(&message) => {
+                        matched = Some(message);
+                        true
+                    }
+                    _ => false,
+                },
+                timeout,
+            )
+            .await?;
+
+        match matched {
+            Some(message) => Ok((message, frame, callback)),
+            None => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Starts emitting [`Event::StatsReport`] events with node traffic summary every `interval`.
+    ///
+    /// Traffic statistics are collected only after this method was called. Each [`StatsReport`]
+    /// covers the traffic since the previous report. Calling this method again restarts reporting
+    /// with the new `interval`.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use std::time::Duration;
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// node.stats_report(Duration::from_secs(10));
+    ///
+    /// let mut events = node.events().unwrap();
+    /// while let Some(event) = events.next().await {
+    ///     if let Event::StatsReport(report) = event {
+    ///         println!("top talkers: {:?}", report.top_talkers);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`StatsReport`]: crate::core::node::StatsReport
+    pub fn stats_report(&self, interval: Duration) {
+        self.api.start_stats_report(interval);
+    }
+
     #[inline(always)]
     pub(in crate::asnc) fn frame_sender(&self) -> &FrameSender<V, Proxy> {
         self.api.frame_sender()
     }
+
+    /// Stops accepting new frames, waits until queued frames are flushed and channels are stopped,
+    /// and closes the node.
+    async fn close_gracefully(self, start: FlushProgress, deadline: Instant) -> ShutdownReport {
+        let tracker = self.api.flush_tracker();
+        tracker.drain();
+
+        while tracker.pending() > 0 && Instant::now() < deadline {
+            runtime::sleep(SHUTDOWN_FLUSH_POOLING_INTERVAL).await;
+        }
+        let progress = tracker.progress();
+
+        drop(self);
+        while tracker.channels() > 0 && Instant::now() < deadline {
+            runtime::sleep(SHUTDOWN_FLUSH_POOLING_INTERVAL).await;
+        }
+
+        let timed_out = progress.pending() > 0 || tracker.channels() > 0;
+        ShutdownReport::new(&start, &progress, timed_out)
+    }
 }
 
 impl<V: MaybeVersioned> Node<Proxy, V, AsyncApi<V>> {
@@ -122,6 +732,21 @@ impl<V: MaybeVersioned> Node<Proxy, V, AsyncApi<V>> {
     pub fn sender(&self) -> FrameSender<V, Proxy> {
         self.api.frame_sender().clone()
     }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Gracefully shuts down the node within the specified `timeout`.
+    ///
+    /// Stops accepting new outgoing frames, waits until already queued frames are flushed to
+    /// transports and connection channels are stopped, and then closes the node. Frames, that were
+    /// not flushed before the deadline, are dropped. Dropping the node closes connection
+    /// immediately.
+    ///
+    /// Returns [`ShutdownReport`] with the number of flushed and dropped frames.
+    pub async fn shutdown(self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let start = self.api.flush_tracker().progress();
+        self.close_gracefully(start, deadline).await
+    }
 }
 
 impl<V: MaybeVersioned> Node<Edge<V>, V, AsyncApi<V>> {
@@ -133,6 +758,23 @@ impl<V: MaybeVersioned> Node<Edge<V>, V, AsyncApi<V>> {
     pub fn sender(&self) -> FrameSender<V, Edge<V>> {
         self.api.frame_sender().clone().into_edge(self.kind.clone())
     }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Adds a MAVLink component with specified `component_id` to the node.
+    ///
+    /// Component shares node connection and events, but has its own sequence counter and
+    /// heartbeats. Returns [`NodeError::ComponentInUse`] if `component_id` is already used by the
+    /// node or by another of its components.
+    ///
+    /// See [`NodeComponent`] for details.
+    pub fn add_component(&self, component_id: ComponentId) -> Result<NodeComponent<V>> {
+        if component_id == self.component_id() {
+            return Err(NodeError::ComponentInUse(component_id).into());
+        }
+
+        let lease = self.components.acquire(component_id)?;
+        Ok(NodeComponent::new(self, lease))
+    }
 }
 
 impl<V: Versioned> Node<Edge<V>, V, AsyncApi<V>> {
@@ -161,11 +803,261 @@ impl<V: Versioned> Node<Edge<V>, V, AsyncApi<V>> {
             self.kind.endpoint.clone(),
             self.heartbeat_interval,
             self.is_active.clone(),
+            self.heartbeat.clone(),
             self.dialect().version(),
         );
+        #[cfg(feature = "msrv-utils-timesync")]
+        self.api
+            .start_timesync_responder(self.kind.endpoint.clone(), self.is_active.clone());
 
         Ok(())
     }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Gracefully shuts down the node within the specified `timeout`.
+    ///
+    /// Deactivates the node, emits [`NodeConf::shutdown_messages`] to all channels, and stops
+    /// accepting new outgoing frames. Then waits until queued frames, including shutdown messages,
+    /// are flushed to transports and connection channels are stopped, and closes the node. Frames,
+    /// that were not flushed before the deadline, are dropped. Dropping the node closes connection
+    /// immediately without sending shutdown messages.
+    ///
+    /// Returns [`ShutdownReport`] with the number of flushed and dropped frames.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .id(MavLinkId::new(1, 1))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let report = node.shutdown(Duration::from_secs(1)).await;
+    /// if report.dropped() > 0 {
+    ///     println!("{} frames were not delivered", report.dropped());
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`NodeConf::shutdown_messages`]: crate::core::node::NodeConf::shutdown_messages
+    pub async fn shutdown(mut self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let start = self.api.flush_tracker().progress();
+        self.send_shutdown_messages();
+        self.close_gracefully(start, deadline).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Sends messages produced by `generator` every `interval`.
+    ///
+    /// The first message is sent immediately. All periodic tasks of a node run on a single
+    /// scheduler, that stops them once node is closed. Tasks do not require node to be active.
+    ///
+    /// Returns a [`PeriodicSender`] handle, that controls the task. See [`PeriodicSender`] for
+    /// details.
+    pub fn send_periodically<M: Message + 'static>(
+        &self,
+        interval: Duration,
+        generator: impl FnMut() -> M + Send + 'static,
+    ) -> PeriodicSender {
+        self.api
+            .start_periodic(self.kind.endpoint.clone(), interval, generator)
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-params`</sup>
+    /// Attaches a parameter protocol `server` to the node.
+    ///
+    /// Node will answer parameter requests addressed to its system and component `ID`s, announce
+    /// parameters changed by [`ParamServer::set`], and emit [`ParamChanged`] custom events when
+    /// peers change parameters. The server works until the node is closed and does not require
+    /// node to be active.
+    ///
+    /// See [`ParamServer`] for details.
+    ///
+    /// [`ParamChanged`]: crate::core::msrv::params::ParamChanged
+    #[cfg(feature = "msrv-utils-params")]
+    pub fn attach_param_server(&self, server: &ParamServer) {
+        self.api
+            .start_param_server(self.kind.endpoint.clone(), server);
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-streams`</sup>
+    /// Attaches a message interval `controller` to the node.
+    ///
+    /// Node will emit registered streams at their current rates and answer message interval
+    /// commands addressed to its system and component `ID`s. When peers change stream rates, node
+    /// emits [`IntervalChanged`] custom events. Rates changed locally by
+    /// [`StreamRateController::set_interval`] are applied within a few milliseconds. The controller
+    /// works until the node is closed and does not require node to be active.
+    ///
+    /// See [`StreamRateController`] for details.
+    ///
+    /// [`IntervalChanged`]: crate::core::msrv::streams::IntervalChanged
+    #[cfg(feature = "msrv-utils-streams")]
+    pub fn attach_stream_controller(&self, controller: &StreamRateController) {
+        self.api
+            .start_stream_controller(self.kind.endpoint.clone(), controller);
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-mission`</sup>
+    /// Uploads mission `items` to a peer defined by `settings`.
+    ///
+    /// Resolves once the peer accepts the mission. Returns [`MissionError::Rejected`] if peer
+    /// rejects the mission, or [`MissionError::Timeout`] if peer stops responding.
+    ///
+    /// [`MissionError::Rejected`]: crate::error::MissionError::Rejected
+    /// [`MissionError::Timeout`]: crate::error::MissionError::Timeout
+    #[cfg(feature = "msrv-utils-mission")]
+    pub async fn upload_mission(
+        &self,
+        settings: MissionSettings,
+        items: Vec<MissionItemInt>,
+    ) -> Result<()> {
+        self.run_mission_transfer(MissionSender::new(settings, items))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-mission`</sup>
+    /// Downloads mission items from a peer defined by `settings`.
+    ///
+    /// Resolves once all items are received.
+    #[cfg(feature = "msrv-utils-mission")]
+    pub async fn download_mission(&self, settings: MissionSettings) -> Result<Vec<MissionItemInt>> {
+        self.run_mission_transfer(MissionReceiver::new(settings))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-mission`</sup>
+    /// Runs a mission `transfer` over node connection.
+    ///
+    /// Resolves once the transfer is finished. Use this method to serve transfers initiated by
+    /// peers. For example, pass [`MissionReceiver::for_upload`] once `MISSION_COUNT` is received,
+    /// or [`MissionSender`] once `MISSION_REQUEST_LIST` is received.
+    ///
+    /// Only frames received after this method is called are passed to the transfer.
+    #[cfg(feature = "msrv-utils-mission")]
+    pub async fn run_mission_transfer<T: MissionTransfer>(
+        &self,
+        mut transfer: T,
+    ) -> Result<T::Output> {
+        let mut receiver = self.receiver_cloned();
+        let mut step = transfer.start(Instant::now());
+
+        loop {
+            match step {
+                MissionStep::Wait => {}
+                MissionStep::Send(message) => message.send(self)?,
+                MissionStep::Finished(message, result) => {
+                    if let Some(message) = message {
+                        message.send(self)?;
+                    }
+                    return result;
+                }
+            }
+
+            let timeout = transfer
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match transfer.handle(&frame, Instant::now()) {
+                    MissionStep::Wait => transfer.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    transfer.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-ftp`</sup>
+    /// Creates a [MAVLink FTP](crate::core::msrv::ftp) client for a server defined by `settings`.
+    ///
+    /// See [`FtpClient`] for details.
+    #[cfg(feature = "msrv-utils-ftp")]
+    pub fn ftp_client(&self, settings: FtpSettings) -> FtpClient<'_, V> {
+        FtpClient::new(self, settings)
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-camera`</sup>
+    /// Creates a [camera protocol](crate::core::msrv::camera) client for a camera defined by
+    /// `settings`.
+    ///
+    /// See [`CameraClient`] for details.
+    #[cfg(feature = "msrv-utils-camera")]
+    pub fn camera_client(&self, settings: CameraSettings) -> CameraClient<'_, V> {
+        CameraClient::new(self, settings)
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-gimbal`</sup>
+    /// Creates a [gimbal protocol](crate::core::msrv::gimbal) client for a gimbal defined by
+    /// `settings`.
+    ///
+    /// See [`GimbalClient`] for details.
+    #[cfg(feature = "msrv-utils-gimbal")]
+    pub fn gimbal_client(&self, settings: GimbalSettings) -> GimbalClient<'_, V> {
+        GimbalClient::new(self, settings)
+    }
+
+    /// <sup>[`asnc`](crate::asnc) | `msrv-utils-gcs`</sup>
+    /// Creates a [ground control station](crate::core::msrv::gcs) client for a vehicle defined by
+    /// `settings`.
+    ///
+    /// See [`GcsClient`] for details.
+    #[cfg(feature = "msrv-utils-gcs")]
+    pub fn gcs_client(&self, settings: GcsSettings) -> GcsClient<'_, V> {
+        GcsClient::new(self, settings)
+    }
+
+    /// <sup>[`async`](crate::asnc) | `msrv-utils-timesync`</sup>
+    /// Measures latency to a peer with [time synchronisation](crate::core::msrv::timesync)
+    /// protocol.
+    ///
+    /// Accepts either a peer [`MavLinkId`] or [`TimesyncSettings`]. Resolves once all `TIMESYNC`
+    /// requests are answered or lost. Returns [`TimesyncError::Timeout`] if peer didn't answer any
+    /// of them.
+    ///
+    /// Only frames received after this method is called are considered.
+    ///
+    /// [`TimesyncError::Timeout`]: crate::error::TimesyncError::Timeout
+    #[cfg(feature = "msrv-utils-timesync")]
+    pub async fn measure_latency(
+        &self,
+        settings: impl Into<TimesyncSettings>,
+    ) -> Result<TimesyncStats> {
+        let mut receiver = self.receiver_cloned();
+        let mut measurement = LatencyMeasurement::new(settings.into());
+        let mut step = measurement.start(Instant::now());
+
+        loop {
+            match step {
+                TimesyncStep::Wait => {}
+                TimesyncStep::Send(request) => self.send(&request)?,
+                TimesyncStep::Finished(result) => return result,
+            }
+
+            let timeout = measurement
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match measurement.handle(&frame, Instant::now()) {
+                    TimesyncStep::Wait => measurement.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    measurement.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
 }
 
 #[async_trait]
@@ -185,6 +1077,16 @@ impl<K: NodeKind, V: MaybeVersioned> ReceiveEvent<V> for Node<K, V, AsyncApi<V>>
         self.api.event_receiver_mut().try_recv()
     }
 
+    #[inline(always)]
+    async fn recv_many(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize> {
+        self.api.event_receiver_mut().recv_many(buffer, max).await
+    }
+
+    #[inline(always)]
+    fn drain(&mut self, max: usize) -> Vec<Event<V>> {
+        self.api.event_receiver_mut().drain(max)
+    }
+
     #[inline(always)]
     fn events(&self) -> Behold<impl Stream<Item = Event<V>>> {
         Behold::new(self.api.events())
@@ -0,0 +1,106 @@
+use std::time::Instant;
+
+use crate::core::msrv::ftp::{
+    DirectoryListing, FileDownload, FileUpload, FtpEntry, FtpSettings, FtpStep, FtpTransfer,
+};
+use crate::error::RecvTimeoutError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc) | `msrv-utils-ftp`</sup>
+/// Asynchronous [MAVLink FTP](crate::core::msrv::ftp) client bound to an edge node and an FTP server.
+///
+/// Created by [`Node::ftp_client`]. Each method resolves once the transfer is finished. Only
+/// frames received after a transfer is started are passed to it, so transfers should not be run
+/// concurrently with the same server.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main(flavor = "current_thread")] async fn main() {
+/// use maviola::core::msrv::ftp::FtpSettings;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().await.unwrap();
+///
+/// let ftp = node.ftp_client(FtpSettings::new(MavLinkId::new(1, 1)));
+/// let data = ftp.read_file("/fs/microsd/params").await.unwrap();
+/// # }
+/// ```
+pub struct FtpClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: FtpSettings,
+}
+
+impl<'a, V: Versioned> FtpClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: FtpSettings) -> Self {
+        Self { node, settings }
+    }
+
+    /// Settings of transfers.
+    pub fn settings(&self) -> &FtpSettings {
+        &self.settings
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Lists entries of a remote directory at `path`.
+    pub async fn list_directory(&self, path: &str) -> Result<Vec<FtpEntry>> {
+        self.run(DirectoryListing::new(self.settings, path)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Reads contents of a remote file at `path`.
+    pub async fn read_file(&self, path: &str) -> Result<Vec<u8>> {
+        self.run(FileDownload::new(self.settings, path)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Writes `data` to a remote file at `path`.
+    ///
+    /// File is created or truncated, if already exists.
+    pub async fn write_file(&self, path: &str, data: &[u8]) -> Result<()> {
+        self.run(FileUpload::new(self.settings, path, data.to_vec()))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Runs an FTP `transfer` over node connection.
+    ///
+    /// Transfer uses its own settings instead of the client ones.
+    pub async fn run<T: FtpTransfer>(&self, mut transfer: T) -> Result<T::Output> {
+        let mut receiver = self.node.receiver_cloned();
+        let mut step = transfer.start(Instant::now());
+
+        loop {
+            match step {
+                FtpStep::Wait => {}
+                FtpStep::Send(message) => self.node.send(&message)?,
+                FtpStep::Finished(message, result) => {
+                    if let Some(message) = message {
+                        self.node.send(&message)?;
+                    }
+                    return result;
+                }
+            }
+
+            let timeout = transfer
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match transfer.handle(&frame, Instant::now()) {
+                    FtpStep::Wait => transfer.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    transfer.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
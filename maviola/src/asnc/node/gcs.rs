@@ -0,0 +1,143 @@
+use std::time::Instant;
+
+use crate::core::msrv::gcs::{
+    GcsOperation, GcsSettings, GcsStep, ParamRead, ParamWrite, VehicleCommand,
+};
+use crate::core::msrv::params::ParamValue;
+use crate::dialects::common::enums::MavCmd;
+use crate::dialects::common::messages::MissionItemInt;
+use crate::error::RecvTimeoutError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc) | `msrv-utils-gcs`</sup>
+/// Asynchronous [ground control station](crate::core::msrv::gcs) client bound to an edge node and
+/// a vehicle.
+///
+/// Created by [`Node::gcs_client`]. Combines vehicle commands, parameter protocol, and mission
+/// protocol in a single API. Each method resolves once the vehicle responds. Only frames received
+/// after an operation is started are passed to it.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::core::msrv::gcs::GcsSettings;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().await.unwrap();
+///
+/// let gcs = node.gcs_client(GcsSettings::new(MavLinkId::new(1, 1)));
+/// let sysid = gcs.read_param("MAV_SYS_ID").await.unwrap();
+/// gcs.arm().await.unwrap();
+/// # }
+/// ```
+pub struct GcsClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: GcsSettings,
+}
+
+impl<'a, V: Versioned> GcsClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: GcsSettings) -> Self {
+        Self { node, settings }
+    }
+
+    /// Settings of ground control station operations.
+    pub fn settings(&self) -> &GcsSettings {
+        &self.settings
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Sends an arbitrary `command` with `params` to the vehicle and waits for acknowledgement.
+    pub async fn command(&self, command: MavCmd, params: [f32; 7]) -> Result<()> {
+        self.run(VehicleCommand::new(self.settings, command, params))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Arms the vehicle.
+    ///
+    /// Use [`VehicleCommand::arm_disarm`] with [`run`](Self::run) to bypass pre-arm checks.
+    pub async fn arm(&self) -> Result<()> {
+        self.run(VehicleCommand::arm(self.settings)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Disarms the vehicle.
+    pub async fn disarm(&self) -> Result<()> {
+        self.run(VehicleCommand::disarm(self.settings)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Reads parameter with specified `name`.
+    pub async fn read_param(&self, name: &str) -> Result<ParamValue> {
+        self.run(ParamRead::new(self.settings, name)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Sets parameter with specified `name` to `value`.
+    ///
+    /// Returns the value reported by the vehicle, which may differ from the requested one.
+    pub async fn write_param(
+        &self,
+        name: &str,
+        value: impl Into<ParamValue>,
+    ) -> Result<ParamValue> {
+        self.run(ParamWrite::new(self.settings, name, value.into()))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Uploads mission `items` to the vehicle.
+    ///
+    /// See [`Node::upload_mission`] for details.
+    pub async fn upload_mission(&self, items: Vec<MissionItemInt>) -> Result<()> {
+        self.node
+            .upload_mission(self.settings.mission(), items)
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Downloads mission items from the vehicle.
+    ///
+    /// See [`Node::download_mission`] for details.
+    pub async fn download_mission(&self) -> Result<Vec<MissionItemInt>> {
+        self.node.download_mission(self.settings.mission()).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Runs a ground control station `operation` over node connection.
+    ///
+    /// Operation uses its own settings instead of the client ones.
+    pub async fn run<T: GcsOperation>(&self, mut operation: T) -> Result<T::Output> {
+        let mut receiver = self.node.receiver_cloned();
+        let mut step = operation.start(Instant::now());
+
+        loop {
+            match step {
+                GcsStep::Wait => {}
+                GcsStep::Send(message) => message.send(self.node)?,
+                GcsStep::Finished(result) => return result,
+            }
+
+            let timeout = operation
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match operation.handle(&frame, Instant::now()) {
+                    GcsStep::Wait => operation.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    operation.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
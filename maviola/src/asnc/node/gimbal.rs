@@ -0,0 +1,193 @@
+use std::time::Instant;
+
+use tokio_stream::{Stream, StreamExt};
+
+use crate::core::msrv::gimbal::{
+    GimbalAttitude, GimbalCommand, GimbalDeviceInfo, GimbalDeviceInformationRequest,
+    GimbalDiscovery, GimbalInfo, GimbalManagerInfo, GimbalManagerInformationRequest,
+    GimbalOperation, GimbalSetpoint, GimbalSettings, GimbalStep,
+};
+use crate::core::node::EventFilter;
+use crate::dialects::common::enums::GimbalManagerFlags;
+use crate::dialects::common::messages::GimbalDeviceAttitudeStatus;
+use crate::error::{GimbalError, RecvTimeoutError};
+use crate::protocol::Behold;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc) | `msrv-utils-gimbal`</sup>
+/// Asynchronous [gimbal protocol](crate::core::msrv::gimbal) client bound to an edge node and a gimbal.
+///
+/// Created by [`Node::gimbal_client`]. Each command method resolves once the gimbal manager
+/// acknowledges the command and sends the requested message, if any. Only frames received after
+/// an operation is started are passed to it.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::core::msrv::gimbal::GimbalSettings;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().await.unwrap();
+///
+/// let gimbal = node.gimbal_client(GimbalSettings::new(MavLinkId::new(1, 1)));
+/// gimbal.take_control().await.unwrap();
+/// gimbal.set_pitch_yaw(-45.0, 0.0).await.unwrap();
+/// # }
+/// ```
+pub struct GimbalClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: GimbalSettings,
+}
+
+impl<'a, V: Versioned> GimbalClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: GimbalSettings) -> Self {
+        Self { node, settings }
+    }
+
+    /// Settings of gimbal operations.
+    pub fn settings(&self) -> &GimbalSettings {
+        &self.settings
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Discovers gimbal device of the gimbal manager.
+    ///
+    /// See [`GimbalDiscovery`] for details.
+    pub async fn discover(&self) -> Result<GimbalInfo> {
+        self.run(GimbalDiscovery::new(self.settings)).await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Requests gimbal manager information.
+    pub async fn manager_information(&self) -> Result<GimbalManagerInfo> {
+        self.run(GimbalManagerInformationRequest::new(self.settings))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Requests gimbal device information.
+    pub async fn device_information(&self) -> Result<GimbalDeviceInfo> {
+        self.run(GimbalDeviceInformationRequest::new(self.settings))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Takes primary control over the gimbal on behalf of the node.
+    pub async fn take_control(&self) -> Result<()> {
+        let id = MavLinkId::new(self.node.system_id(), self.node.component_id());
+        self.run(GimbalCommand::take_control(self.settings, id))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Releases primary control over the gimbal.
+    pub async fn release_control(&self) -> Result<()> {
+        self.run(GimbalCommand::release_control(self.settings))
+            .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Points the gimbal to `pitch` and `yaw` angles in degrees. Yaw is relative to vehicle
+    /// heading.
+    ///
+    /// Use [`GimbalCommand::pitch_yaw`] with [`run`](Self::run) to control angular rates and
+    /// yaw lock.
+    pub async fn set_pitch_yaw(&self, pitch: f32, yaw: f32) -> Result<()> {
+        self.run(GimbalCommand::pitch_yaw(
+            self.settings,
+            pitch,
+            yaw,
+            f32::NAN,
+            f32::NAN,
+            GimbalManagerFlags::default(),
+        ))
+        .await
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Sends an attitude `setpoint` to the gimbal manager.
+    ///
+    /// Setpoints are not acknowledged. They should be streamed at a constant rate while the
+    /// gimbal is controlled this way.
+    pub fn set_attitude(&self, setpoint: &GimbalSetpoint) -> Result<()> {
+        self.node.send(&setpoint.message(&self.settings))
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Waits for the next gimbal attitude report within the settings timeout.
+    pub async fn attitude(&self) -> Result<GimbalAttitude> {
+        let mut attitude = None;
+        let result = self
+            .node
+            .recv_matching(
+                |frame| {
+                    attitude = GimbalAttitude::from_frame(frame, &self.settings);
+                    attitude.is_some()
+                },
+                self.settings.timeout(),
+            )
+            .await;
+
+        match (result, attitude) {
+            (Ok(_), Some(attitude)) => Ok(attitude),
+            (Err(err @ RecvTimeoutError::Disconnected), _) => Err(err.into()),
+            _ => Err(GimbalError::Timeout.into()),
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Subscribes to gimbal attitude reports.
+    ///
+    /// Stream is active while the node is active.
+    pub fn attitudes(&self) -> Behold<impl Stream<Item = GimbalAttitude>> {
+        let settings = self.settings;
+        let filter = EventFilter::message_ids([GimbalDeviceAttitudeStatus::message_id()])
+            .system_id(settings.manager().system)
+            .frames_only();
+
+        Behold::new(self.node.events_filtered(filter).unwrap().filter_map(
+            move |event| match event {
+                Event::Frame(frame, _) => GimbalAttitude::from_frame(&frame, &settings),
+                _ => None,
+            },
+        ))
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Runs a gimbal `operation` over node connection.
+    ///
+    /// Operation uses its own settings instead of the client ones.
+    pub async fn run<T: GimbalOperation>(&self, mut operation: T) -> Result<T::Output> {
+        let mut receiver = self.node.receiver_cloned();
+        let mut step = operation.start(Instant::now());
+
+        loop {
+            match step {
+                GimbalStep::Wait => {}
+                GimbalStep::Send(command) => self.node.send(&command)?,
+                GimbalStep::Finished(result) => return result,
+            }
+
+            let timeout = operation
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout).await {
+                Ok((frame, _)) => match operation.handle(&frame, Instant::now()) {
+                    GimbalStep::Wait => operation.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    operation.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
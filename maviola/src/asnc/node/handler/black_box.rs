@@ -0,0 +1,70 @@
+use crate::asnc::node::Event;
+use crate::asnc::runtime;
+use crate::core::consts::BLACK_BOX_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::node::{BlackBox, BlackBoxEntry, BlackBoxRecord};
+use crate::error::RecvTimeoutError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+pub(in crate::asnc::node) struct BlackBoxHandler<V: MaybeVersioned> {
+    pub(in crate::asnc::node) info: ConnectionInfo,
+    pub(in crate::asnc::node) black_box: BlackBox,
+    pub(in crate::asnc::node) receiver: EventReceiver<V>,
+}
+
+impl<V: MaybeVersioned> BlackBoxHandler<V> {
+    pub(in crate::asnc::node) fn spawn(mut self) {
+        runtime::spawn(async move {
+            let info = self.info.clone();
+
+            while !self.receiver.state().is_closed() {
+                match self.receiver.recv_timeout(BLACK_BOX_POOLING_INTERVAL).await {
+                    Ok(event) => self.handle_event(event),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Lagged(skipped)) => {
+                        self.black_box.record(BlackBoxRecord::new(
+                            None,
+                            BlackBoxEntry::Event(format!("Lagged({skipped})")),
+                        ));
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            log::debug!("[{info:?}] black box handler stopped");
+        });
+    }
+
+    fn handle_event(&self, event: Event<V>) {
+        let is_connection_lost = matches!(event, Event::ConnectionLost(_));
+
+        let record = match event {
+            Event::Frame(frame, callback) => BlackBoxRecord::received(
+                callback.meta(),
+                BlackBoxEntry::Frame(frame.into_versionless()),
+            ),
+            Event::Invalid(frame, report, callback) => BlackBoxRecord::received(
+                callback.meta(),
+                BlackBoxEntry::Invalid(frame.into_versionless(), report),
+            ),
+            event => {
+                let channel = match &event {
+                    Event::ChannelOpened(channel)
+                    | Event::ChannelClosed(channel)
+                    | Event::Malformed(channel, _) => Some(channel.clone()),
+                    _ => None,
+                };
+                BlackBoxRecord::new(channel, BlackBoxEntry::Event(format!("{event:?}")))
+            }
+        };
+        self.black_box.record(record);
+
+        if is_connection_lost {
+            if let Err(err) = self.black_box.dump_on_error("connection lost") {
+                log::error!("[{:?}] can't dump black box: {err:?}", self.info);
+            }
+        }
+    }
+}
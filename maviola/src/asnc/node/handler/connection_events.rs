@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, watch};
+
+use crate::asnc::consts::CONN_EVENTS_POOLING_INTERVAL;
+use crate::asnc::node::api::EventSender;
+use crate::asnc::node::Event;
+use crate::asnc::runtime;
+use crate::core::io::{ConnectionEvent, ConnectionInfo};
+use crate::core::node::{ConnectionStatus, HealthTracker};
+use crate::core::utils::Closable;
+use crate::protocol::AnomalyTracker;
+
+use crate::prelude::*;
+
+/// Reports lifecycle events of a node connection as node [`Event`]s.
+pub(in crate::asnc::node) struct ConnectionEventsHandler<V: MaybeVersioned> {
+    pub(in crate::asnc::node) info: ConnectionInfo,
+    pub(in crate::asnc::node) receiver: mpsc::UnboundedReceiver<ConnectionEvent>,
+    pub(in crate::asnc::node) status_watch: Arc<watch::Sender<ConnectionStatus>>,
+    pub(in crate::asnc::node) event_sender: EventSender<V>,
+    pub(in crate::asnc::node) health: HealthTracker,
+    pub(in crate::asnc::node) anomalies: Option<AnomalyTracker>,
+    pub(in crate::asnc::node) channel_events: Arc<AtomicBool>,
+}
+
+impl<V: MaybeVersioned> ConnectionEventsHandler<V> {
+    pub(in crate::asnc::node) fn spawn(mut self, state: Closable) {
+        runtime::spawn(async move {
+            let info = self.info.clone();
+            let mut lost = false;
+
+            loop {
+                // Events sent right before connection was closed should still be reported
+                let is_closed = state.is_closed();
+
+                let event = match runtime::timeout(
+                    CONN_EVENTS_POOLING_INTERVAL,
+                    self.receiver.recv(),
+                )
+                .await
+                {
+                    Ok(Some(event)) => event,
+                    Ok(None) => break,
+                    Err(_) => {
+                        if is_closed {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+
+                self.status_watch
+                    .send_if_modified(|status| status.apply(&event));
+
+                let event = match event {
+                    ConnectionEvent::ChannelOpened(channel) => {
+                        if !self.channel_events.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        Event::ChannelOpened(channel)
+                    }
+                    ConnectionEvent::ChannelClosed(channel) => {
+                        self.health.forget_channel(channel.id());
+                        if let Some(anomalies) = self.anomalies.as_ref() {
+                            anomalies.forget_channel(channel.id());
+                        }
+                        if !self.channel_events.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        Event::ChannelClosed(channel)
+                    }
+                    ConnectionEvent::Lost if lost => continue,
+                    ConnectionEvent::Lost => {
+                        lost = true;
+                        Event::ConnectionLost(info.clone())
+                    }
+                    ConnectionEvent::Restored => {
+                        lost = false;
+                        Event::ConnectionRestored(info.clone())
+                    }
+                    ConnectionEvent::Malformed(channel, report) => {
+                        Event::Malformed(channel, report)
+                    }
+                    ConnectionEvent::ConnectionAdded(info) => Event::ConnectionAdded(info),
+                    ConnectionEvent::ConnectionRemoved(info) => Event::ConnectionRemoved(info),
+                    ConnectionEvent::LinkActivated(info) => Event::LinkActivated(info),
+                    ConnectionEvent::SenderRejected(info, addr) => {
+                        Event::SenderRejected(info, addr)
+                    }
+                };
+
+                if let Err(err) = self.event_sender.send(event) {
+                    log::trace!("[{info:?}] failed to report connection event: {err:?}");
+                    break;
+                }
+            }
+
+            self.status_watch.send_if_modified(ConnectionStatus::close);
+            log::trace!("[{info:?}] connection events handler stopped");
+        });
+    }
+}
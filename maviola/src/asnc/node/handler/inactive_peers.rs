@@ -3,27 +3,32 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use crate::asnc::node::api::EventSender;
-use tokio::sync::RwLock;
+use crate::asnc::runtime;
+use tokio::sync::{watch, RwLock};
 
 use crate::asnc::node::Event;
 use crate::core::io::ConnectionInfo;
+use crate::core::node::{peer_list, PeerLocations};
 use crate::core::utils::Closable;
-use crate::protocol::Peer;
+use crate::protocol::{Peer, SystemRegistry};
 
 use crate::prelude::*;
 
 pub(in crate::asnc::node) struct InactivePeersHandler<V: MaybeVersioned> {
     pub(in crate::asnc::node) info: ConnectionInfo,
     pub(in crate::asnc::node) peers: Arc<RwLock<HashMap<MavLinkId, Peer>>>,
+    pub(in crate::asnc::node) locations: PeerLocations,
+    pub(in crate::asnc::node) peers_watch: Arc<watch::Sender<Vec<Peer>>>,
+    pub(in crate::asnc::node) systems: SystemRegistry,
     pub(in crate::asnc::node) timeout: Duration,
     pub(in crate::asnc::node) event_sender: EventSender<V>,
 }
 
 impl<V: MaybeVersioned> InactivePeersHandler<V> {
     pub(in crate::asnc::node) fn spawn(self, state: Closable) {
-        tokio::spawn(async move {
+        runtime::spawn(async move {
             while !state.is_closed() {
-                tokio::time::sleep(self.timeout).await;
+                runtime::sleep(self.timeout).await;
 
                 let inactive_peers = self.collect_inactive_peers().await;
 
@@ -54,15 +59,25 @@ impl<V: MaybeVersioned> InactivePeersHandler<V> {
     pub async fn handle_inactive_peers(&self, inactive_peers: HashSet<MavLinkId>) -> Result<()> {
         let mut peers = self.peers.write().await;
 
-        for id in inactive_peers {
-            if let Some(peer) = peers.remove(&id) {
-                if let Err(err) = self.event_sender.send(Event::PeerLost(peer)) {
-                    log::trace!(
-                        "[{:?}] failed to report lost peer event: {err:?}",
-                        &self.info
-                    );
-                    return Err(Error::from(err));
-                }
+        let lost_peers: Vec<Peer> = inactive_peers
+            .iter()
+            .filter_map(|id| peers.remove(id))
+            .collect();
+        if !lost_peers.is_empty() {
+            self.peers_watch.send_replace(peer_list(&peers));
+        }
+        self.systems.handle_lost_peers(&lost_peers);
+        for peer in &lost_peers {
+            self.locations.forget(peer.id);
+        }
+
+        for peer in lost_peers {
+            if let Err(err) = self.event_sender.send(Event::PeerLost(peer)) {
+                log::trace!(
+                    "[{:?}] failed to report lost peer event: {err:?}",
+                    &self.info
+                );
+                return Err(Error::from(err));
             }
         }
 
@@ -75,8 +90,13 @@ impl<V: MaybeVersioned> InactivePeersHandler<V> {
         for peer in peers.values() {
             let _ = self.event_sender.send(Event::PeerLost(peer.clone()));
         }
-        peers.clear();
+        if !peers.is_empty() {
+            peers.clear();
+            self.peers_watch.send_replace(Vec::new());
+        }
 
+        self.systems.clear();
+        self.locations.clear();
         log::trace!("[{:?}] inactive peers handler stopped", self.info);
     }
 }
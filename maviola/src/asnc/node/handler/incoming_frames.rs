@@ -1,17 +1,23 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 
 use crate::asnc::io::IncomingFrameReceiver;
 use crate::asnc::node::api::EventSender;
+use crate::asnc::runtime;
 use crate::core::consts::INCOMING_FRAMES_POOLING_INTERVAL;
-use crate::core::io::ConnectionInfo;
+use crate::core::io::{ChannelInfo, ConnectionInfo};
 use crate::core::marker::Proxy;
-use crate::core::utils::Closable;
+use crate::core::node::{peer_list, HealthTracker, PeerLocations, TrafficStats};
+use crate::core::utils::{trace, Closable};
+use crate::dialects::minimal::messages::Heartbeat;
 use crate::dialects::Minimal;
 use crate::error::RecvTimeoutError;
-use crate::protocol::Peer;
+use crate::protocol::{
+    AnomalyTracker, LinkQuality, LinkQualityTracker, Peer, RateTracker, SystemRegistry,
+};
 
 use crate::asnc::prelude::*;
 use crate::prelude::*;
@@ -19,15 +25,23 @@ use crate::prelude::*;
 pub(in crate::asnc::node) struct IncomingFramesHandler<V: MaybeVersioned> {
     pub(in crate::asnc::node) info: ConnectionInfo,
     pub(in crate::asnc::node) peers: Arc<RwLock<HashMap<MavLinkId, Peer>>>,
+    pub(in crate::asnc::node) locations: PeerLocations,
+    pub(in crate::asnc::node) peers_watch: Arc<watch::Sender<Vec<Peer>>>,
     pub(in crate::asnc::node) receiver: IncomingFrameReceiver<V>,
     pub(in crate::asnc::node) event_sender: EventSender<V>,
     pub(in crate::asnc::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::asnc::node) anomalies: Option<AnomalyTracker>,
+    pub(in crate::asnc::node) governor: Option<RateTracker>,
+    pub(in crate::asnc::node) link_quality: Option<LinkQualityTracker>,
+    pub(in crate::asnc::node) systems: SystemRegistry,
+    pub(in crate::asnc::node) stats: TrafficStats,
+    pub(in crate::asnc::node) health: HealthTracker,
 }
 
 impl<V: MaybeVersioned> IncomingFramesHandler<V> {
     pub(in crate::asnc::node) fn spawn(mut self, state: Closable) {
-        tokio::spawn(async move {
-            let info = &self.info;
+        runtime::spawn(async move {
+            let info = self.info.clone();
 
             while !state.is_closed() {
                 let (frame, callback) = match self
@@ -36,8 +50,8 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
                     .await
                 {
                     Ok(frame) => {
-                        let (frame, channel) = frame.into();
-                        let callback = Callback::new(channel, self.sender.clone());
+                        let (frame, meta) = frame.into();
+                        let callback = Callback::new(meta, self.sender.clone());
                         (frame, callback)
                     }
                     Err(err) => match err {
@@ -48,16 +62,11 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
                     },
                 };
 
-                if let Ok(Minimal::Heartbeat(_)) = frame.decode() {
-                    let peer = Peer::new(frame.system_id(), frame.component_id());
-                    log::trace!("[{info:?}] received heartbeat from {peer:?}");
-
-                    if self.handle_new_peer(peer).await.is_err() {
-                        break;
-                    }
-                }
-
-                if self.handle_incoming_frame(frame, callback).is_err() {
+                let span = trace::incoming_frame_span(&frame, callback.info());
+                if trace::instrument(self.handle_frame(frame, callback), span)
+                    .await
+                    .is_err()
+                {
                     break;
                 }
             }
@@ -66,6 +75,47 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
         });
     }
 
+    async fn handle_frame(&mut self, frame: Frame<V>, callback: Callback<V>) -> Result<()> {
+        self.stats
+            .record_incoming(&frame, callback.info().connection_id());
+        self.health
+            .record_frame(callback.info().id(), callback.received_at());
+        if let Some(tracker) = self.link_quality.as_mut() {
+            tracker.record(&frame);
+        }
+
+        if let Ok(Minimal::Heartbeat(heartbeat)) = frame.decode() {
+            let mut peer = Peer::new(frame.system_id(), frame.component_id());
+            log::trace!("[{:?}] received heartbeat from {peer:?}", &self.info);
+
+            self.handle_anomalies(&heartbeat, peer.id, callback.info())?;
+
+            let degraded = self.inspect_link(&mut peer, callback.received_at());
+
+            self.locations.observe(peer.id, callback.info().id());
+            self.handle_new_peer(peer).await?;
+
+            if let Some((peer, quality)) = degraded {
+                self.handle_degraded_peer(peer, quality)?;
+            }
+
+            self.handle_system(&frame, &heartbeat)?;
+        } else {
+            self.systems.handle_frame(&frame);
+        }
+
+        if !self.is_within_rate(&frame, callback.received_at()) {
+            log::trace!(
+                "[{:?}] frame dropped due to exceeded rate: {frame:?}",
+                &self.info
+            );
+            self.stats.record_rate_limited();
+            return Ok(());
+        }
+
+        self.handle_incoming_frame(frame, callback)
+    }
+
     async fn handle_new_peer(&self, peer: Peer) -> Result<()> {
         let mut peers = self.peers.write().await;
 
@@ -73,6 +123,7 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
         peers.insert(peer.id, peer.clone());
 
         if !has_peer {
+            self.peers_watch.send_replace(peer_list(&peers));
             if let Err(err) = self.event_sender.send(Event::NewPeer(peer)) {
                 log::trace!(
                     "[{:?}] failed to report new peer event: {err:?}",
@@ -85,6 +136,98 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
         Ok(())
     }
 
+    fn handle_system(&self, frame: &Frame<V>, heartbeat: &Heartbeat) -> Result<()> {
+        let update = self.systems.handle_heartbeat(frame, heartbeat);
+
+        if let Some(component) = update.new_component {
+            let event = Event::NewComponent {
+                system_id: component.system_id(),
+                component_id: component.component_id(),
+                component_kind: component.kind(),
+            };
+            if let Err(err) = self.event_sender.send(event) {
+                log::trace!("[{:?}] failed to report new component: {err:?}", &self.info);
+                return Err(Error::from(err));
+            }
+        }
+
+        let system = match update.changed_system {
+            Some(system) => system,
+            None => return Ok(()),
+        };
+
+        if let Err(err) = self.event_sender.send(Event::SystemChanged(system)) {
+            log::trace!("[{:?}] failed to report system state: {err:?}", &self.info);
+            return Err(Error::from(err));
+        }
+
+        Ok(())
+    }
+
+    fn handle_anomalies(
+        &mut self,
+        heartbeat: &Heartbeat,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+    ) -> Result<()> {
+        let tracker = match self.anomalies.as_ref() {
+            Some(tracker) => tracker,
+            None => return Ok(()),
+        };
+
+        for anomaly in tracker.inspect(heartbeat, id, channel) {
+            log::warn!("[{:?}] peer anomaly detected: {anomaly:?}", &self.info);
+            self.stats.record_anomaly();
+            if let Err(err) = self.event_sender.send(Event::Anomaly(anomaly)) {
+                log::trace!("[{:?}] failed to report anomaly: {err:?}", &self.info);
+                return Err(Error::from(err));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inspect_link(
+        &mut self,
+        peer: &mut Peer,
+        received_at: Instant,
+    ) -> Option<(Peer, LinkQuality)> {
+        let tracker = self.link_quality.as_mut()?;
+
+        let (quality, is_degraded) = tracker.heartbeat(peer.id, received_at);
+        peer.link_quality = Some(quality);
+
+        if is_degraded {
+            Some((peer.clone(), quality))
+        } else {
+            None
+        }
+    }
+
+    fn handle_degraded_peer(&self, peer: Peer, quality: LinkQuality) -> Result<()> {
+        log::warn!(
+            "[{:?}] link to {peer:?} is degraded: {quality:?}",
+            &self.info
+        );
+        if let Err(err) = self.event_sender.send(Event::PeerDegraded(peer, quality)) {
+            log::trace!("[{:?}] failed to report degraded peer: {err:?}", &self.info);
+            return Err(Error::from(err));
+        }
+
+        Ok(())
+    }
+
+    fn is_within_rate(&mut self, frame: &Frame<V>, received_at: Instant) -> bool {
+        match self.governor.as_mut() {
+            Some(tracker) => tracker.allow(
+                MavLinkId::new(frame.system_id(), frame.component_id()),
+                frame.message_id(),
+                received_at,
+            ),
+            None => true,
+        }
+    }
+
     fn handle_incoming_frame(&self, frame: Frame<V>, callback: Callback<V>) -> Result<()> {
         let event_send_result = self
             .event_sender
@@ -0,0 +1,85 @@
+use std::time::Instant;
+
+use crate::asnc::node::api::EventSender;
+use crate::asnc::node::Event;
+use crate::asnc::runtime;
+use crate::core::consts::MICROSERVICES_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::marker::Proxy;
+use crate::core::msrv::registry::{ServiceOutput, ServiceRegistry};
+use crate::error::RecvTimeoutError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+pub(in crate::asnc::node) struct MicroservicesHandler<V: Versioned> {
+    pub(in crate::asnc::node) info: ConnectionInfo,
+    pub(in crate::asnc::node) endpoint: Endpoint<V>,
+    pub(in crate::asnc::node) registry: ServiceRegistry<V>,
+    pub(in crate::asnc::node) receiver: EventReceiver<V>,
+    pub(in crate::asnc::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::asnc::node) event_sender: EventSender<V>,
+}
+
+impl<V: Versioned> MicroservicesHandler<V> {
+    pub(in crate::asnc::node) fn spawn(mut self) {
+        runtime::spawn(async move {
+            let info = self.info.clone();
+
+            while !self.receiver.state().is_closed() {
+                // Service output is not `Send` and should not be held across await points
+                let next_due = match self.poll() {
+                    Ok(next_due) => next_due,
+                    Err(_) => break,
+                };
+
+                let timeout = next_due
+                    .map(|next_due| next_due.saturating_duration_since(Instant::now()))
+                    .unwrap_or(MICROSERVICES_POOLING_INTERVAL)
+                    .min(MICROSERVICES_POOLING_INTERVAL);
+
+                match self.receiver.recv_timeout(timeout).await {
+                    Ok(Event::Frame(frame, _)) => {
+                        if self.handle_frame(&frame).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) | Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            log::debug!("[{info:?}] microservices handler stopped");
+        });
+    }
+
+    fn poll(&self) -> Result<Option<Instant>> {
+        let (output, next_due) = self.registry.poll(Instant::now())?;
+        self.emit(output)?;
+        Ok(next_due)
+    }
+
+    fn handle_frame(&self, frame: &Frame<V>) -> Result<()> {
+        let output = self.registry.dispatch(frame, self.endpoint.id())?;
+        self.emit(output)
+    }
+
+    fn emit(&self, output: ServiceOutput) -> Result<()> {
+        for message in output.messages {
+            let frame = self.endpoint.next_frame(message.as_ref())?;
+            self.sender.send_frame(&frame).map_err(|err| {
+                log::trace!(
+                    "[{:?}] microservice message can't be sent: {err:?}",
+                    self.info
+                );
+                err
+            })?;
+        }
+
+        for event in output.events {
+            self.event_sender.send(Event::Custom(event))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Instant;
+
+use crate::asnc::runtime;
+use crate::core::consts::PERIODIC_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::marker::Proxy;
+use crate::core::node::PeriodicTasks;
+use crate::core::utils::Closable;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+pub(in crate::asnc::node) struct PeriodicHandler<V: Versioned> {
+    pub(in crate::asnc::node) info: ConnectionInfo,
+    pub(in crate::asnc::node) endpoint: Endpoint<V>,
+    pub(in crate::asnc::node) tasks: PeriodicTasks,
+    pub(in crate::asnc::node) sender: FrameSender<V, Proxy>,
+}
+
+impl<V: Versioned> PeriodicHandler<V> {
+    pub(in crate::asnc::node) fn spawn(self, state: Closable) {
+        runtime::spawn(async move {
+            let info = &self.info;
+
+            while !state.is_closed() {
+                // Generated messages are not `Send` and should not be held across await points
+                let next_due = self.send_due();
+
+                let timeout = next_due
+                    .map(|next_due| next_due.saturating_duration_since(Instant::now()))
+                    .unwrap_or(PERIODIC_POOLING_INTERVAL)
+                    .min(PERIODIC_POOLING_INTERVAL);
+                runtime::sleep(timeout).await;
+            }
+
+            self.tasks.stop_all();
+            log::debug!("[{info:?}] periodic tasks scheduler stopped");
+        });
+    }
+
+    fn send_due(&self) -> Option<Instant> {
+        let info = &self.info;
+        let (messages, next_due) = self.tasks.poll(Instant::now());
+
+        for (task, message) in messages {
+            let frame = match self.endpoint.next_frame(message.as_ref()) {
+                Ok(frame) => frame,
+                Err(err) => {
+                    log::error!("[{info:?}] can't encode periodic message: {err:?}");
+                    task.stop();
+                    continue;
+                }
+            };
+
+            match self.sender.send_frame(&frame) {
+                Ok(_) => task.record_sent(),
+                Err(err) => {
+                    log::trace!("[{info:?}] periodic message can't be sent: {err:?}");
+                }
+            }
+        }
+
+        next_due
+    }
+}
@@ -0,0 +1,278 @@
+use std::time::Duration;
+
+use crate::asnc::runtime;
+use crate::asnc::runtime::Instant;
+use crate::asnc::runtime::JoinHandle;
+
+use crate::asnc::consts::{CONN_STOP_POOLING_INTERVAL, RECONNECT_RELAY_POOLING_INTERVAL};
+use crate::asnc::io::{
+    forward_events, ChannelFactory, Connection, ConnectionHandler, OutgoingFrameHandler,
+};
+use crate::asnc::marker::AsyncConnConf;
+use crate::core::io::{ConnectionEvent, ConnectionInfo, OutboundQueue, RetryStrategy};
+use crate::core::marker::NodeKind;
+use crate::core::node::{HealthTracker, NodeConf};
+use crate::core::utils::{Closable, SharedCloser};
+use crate::error::{NodeError, RecvTimeoutError, SendError};
+
+use crate::prelude::*;
+
+/// Restores node connection according to [`NodeConf::retry`] strategy.
+///
+/// Node is attached to a connection, that outlives the underlying transports. Frames are relayed
+/// between this connection and the current transport, which is rebuilt once it fails. If
+/// [`NodeConf::outbound_queue`] is set, frames sent while transport is being restored are spooled
+/// and transmitted once transport is rebuilt.
+pub(in crate::asnc::node) struct ConnectionSupervisor<V: MaybeVersioned> {
+    conf: AsyncConnConf<V>,
+    retry: RetryStrategy,
+    queue: Option<OutboundQueue>,
+    health: HealthTracker,
+}
+
+type Transport<V> = (Connection<V>, ConnectionHandler);
+
+impl<V: MaybeVersioned> ConnectionSupervisor<V> {
+    /// Creates supervisor for a node configuration.
+    ///
+    /// Returns [`None`], if connection should not be restored.
+    pub(in crate::asnc::node) fn new<K: NodeKind>(
+        conf: &NodeConf<K, V, AsyncConnConf<V>>,
+        health: HealthTracker,
+    ) -> Option<Self> {
+        if matches!(conf.retry, RetryStrategy::Never) || !conf.is_repairable() {
+            return None;
+        }
+        health.supervise();
+
+        Some(Self {
+            conf: conf.connection_conf.clone(),
+            retry: conf.retry,
+            queue: conf.outbound_queue.clone(),
+            health,
+        })
+    }
+
+    /// Builds the initial transport and returns a connection, that survives its failures.
+    pub(in crate::asnc::node) async fn connect(&self) -> Result<Transport<V>> {
+        let transport = self.conf.connection().build().await?;
+        if let Some(queue) = &self.queue {
+            queue.load();
+        }
+
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = Connection::new(transport.0.info().clone(), state.clone());
+
+        let supervisor = Self {
+            conf: self.conf.clone(),
+            retry: self.retry,
+            queue: self.queue.clone(),
+            health: self.health.clone(),
+        };
+        let handler = ConnectionHandler::spawn(async move {
+            supervisor
+                .handle(state.to_closable(), chan_factory, transport)
+                .await
+        });
+
+        Ok((connection, handler))
+    }
+
+    async fn handle(
+        self,
+        state: Closable,
+        mut chan_factory: ChannelFactory<V>,
+        transport: Transport<V>,
+    ) -> Result<()> {
+        let info = chan_factory.info().clone();
+        let mut transport = transport;
+        // Outgoing frames are received by the same handler across transports, so frames sent
+        // while connection is being restored are not lost.
+        let mut send_handler = chan_factory.send_handler().clone();
+
+        loop {
+            let (connection, handler) = transport;
+            handler.handle(&connection);
+            let outgoing = relay(
+                &state,
+                &chan_factory,
+                send_handler,
+                self.queue.clone(),
+                &connection,
+            );
+
+            let conn_state = connection.state();
+            while !state.is_closed() && !conn_state.is_closed() {
+                runtime::sleep(CONN_STOP_POOLING_INTERVAL).await;
+            }
+            drop(connection);
+            send_handler = match outgoing.await {
+                Ok(send_handler) => send_handler,
+                Err(_) => chan_factory.send_handler().clone(),
+            };
+
+            if state.is_closed() {
+                return Ok(());
+            }
+            log::info!("[{info:?}] transport failed, restoring connection");
+            _ = chan_factory.event_sender().send(ConnectionEvent::Lost);
+            self.health.record_lost();
+
+            transport = match self.restore(&state, &info, &mut send_handler).await {
+                Some(transport) => transport,
+                None if state.is_closed() => return Ok(()),
+                None => {
+                    log::info!("[{info:?}] no attempts left to restore connection, giving up");
+                    self.health.record_exhausted();
+                    return Err(Error::Node(NodeError::Inactive));
+                }
+            };
+
+            log::info!("[{info:?}] connection restored");
+            self.health.record_restored();
+            _ = chan_factory.event_sender().send(ConnectionEvent::Restored);
+        }
+    }
+
+    async fn restore(
+        &self,
+        state: &Closable,
+        info: &ConnectionInfo,
+        send_handler: &mut OutgoingFrameHandler<V>,
+    ) -> Option<Transport<V>> {
+        let mut retry = self.retry;
+
+        loop {
+            let interval = match retry {
+                RetryStrategy::Never | RetryStrategy::Attempts(0, _) => return None,
+                RetryStrategy::Always(interval) => interval,
+                RetryStrategy::Attempts(attempts, interval) => {
+                    retry = RetryStrategy::Attempts(attempts - 1, interval);
+                    interval
+                }
+            };
+
+            if !sleep_while_open(state, interval, send_handler, self.queue.as_ref()).await {
+                return None;
+            }
+
+            match self.conf.connection().build().await {
+                Ok(transport) => return Some(transport),
+                Err(err) => {
+                    log::debug!("[{info:?}] attempt to restore connection failed: {err:?}");
+                    self.health.record_failed_attempt();
+                }
+            }
+        }
+    }
+}
+
+/// Relays frames and lifecycle events between a node connection and its current transport until
+/// either is closed.
+///
+/// Spooled frames are transmitted before the new outgoing frames. Frames, that can't be sent to
+/// the transport, are spooled. Returns a handle to outgoing relay, that gives back the outgoing
+/// frames handler once finished.
+#[allow(clippy::result_large_err)]
+fn relay<V: MaybeVersioned>(
+    state: &Closable,
+    chan_factory: &ChannelFactory<V>,
+    mut send_handler: OutgoingFrameHandler<V>,
+    queue: Option<OutboundQueue>,
+    transport: &Connection<V>,
+) -> JoinHandle<OutgoingFrameHandler<V>> {
+    let info = transport.info().clone();
+    let incoming = (state.clone(), transport.state());
+    let mut receiver = transport.receiver();
+    let producer = chan_factory.producer().clone();
+    let mut events = (transport.take_events(), chan_factory.event_sender().clone());
+    runtime::spawn(async move {
+        let (state, conn_state) = incoming;
+
+        while !state.is_closed() && !conn_state.is_closed() {
+            forward_events(events.0.as_mut(), &events.1);
+
+            let frame = match receiver
+                .recv_timeout(RECONNECT_RELAY_POOLING_INTERVAL)
+                .await
+            {
+                Ok(frame) => frame,
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+            };
+
+            if producer.send(frame).is_err() {
+                break;
+            }
+        }
+        forward_events(events.0.as_mut(), &events.1);
+        log::trace!("[{info:?}] incoming relay stopped");
+    });
+
+    let info = transport.info().clone();
+    let outgoing = (state.clone(), transport.state());
+    let sender = transport.sender();
+    runtime::spawn(async move {
+        let (state, conn_state) = outgoing;
+
+        if let Some(queue) = &queue {
+            let sent = queue.drain(|frame| sender.send_raw(frame));
+            if sent > 0 {
+                log::debug!("[{info:?}] {sent} spooled frames transmitted");
+            }
+        }
+
+        while !state.is_closed() && !conn_state.is_closed() {
+            let frame = match send_handler
+                .recv_timeout(RECONNECT_RELAY_POOLING_INTERVAL)
+                .await
+            {
+                Ok(frame) => frame,
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+            };
+
+            if let Err(SendError(frame)) = sender.send_raw(frame) {
+                if let Some(queue) = &queue {
+                    queue.push(&frame);
+                }
+                break;
+            }
+        }
+        log::trace!("[{info:?}] outgoing relay stopped");
+        send_handler
+    })
+}
+
+/// Sleeps for `duration` or until `state` is closed.
+///
+/// If `queue` is set, outgoing frames received from `send_handler` in the meantime are spooled.
+///
+/// Returns `false` if `state` was closed.
+async fn sleep_while_open<V: MaybeVersioned>(
+    state: &Closable,
+    duration: Duration,
+    send_handler: &mut OutgoingFrameHandler<V>,
+    queue: Option<&OutboundQueue>,
+) -> bool {
+    let deadline = Instant::now() + duration;
+
+    while Instant::now() < deadline {
+        if state.is_closed() {
+            return false;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let timeout = CONN_STOP_POOLING_INTERVAL.min(remaining);
+
+        match queue {
+            Some(queue) => match send_handler.recv_timeout(timeout).await {
+                Ok(frame) => queue.push(&frame),
+                Err(RecvTimeoutError::Disconnected) => runtime::sleep(timeout).await,
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => {}
+            },
+            None => runtime::sleep(timeout).await,
+        }
+    }
+
+    !state.is_closed()
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use crate::asnc::runtime;
+use crate::asnc::runtime::Instant;
+
+use crate::asnc::node::api::EventSender;
+use crate::asnc::node::Event;
+use crate::core::io::ConnectionInfo;
+use crate::core::node::TrafficStats;
+use crate::core::utils::Closable;
+
+use crate::prelude::*;
+
+pub(in crate::asnc::node) struct StatsReporter<V: MaybeVersioned> {
+    pub(in crate::asnc::node) info: ConnectionInfo,
+    pub(in crate::asnc::node) stats: TrafficStats,
+    pub(in crate::asnc::node) interval: Duration,
+    pub(in crate::asnc::node) event_sender: EventSender<V>,
+}
+
+impl<V: MaybeVersioned> StatsReporter<V> {
+    pub(in crate::asnc::node) fn spawn(self, state: Closable, reporter_state: Closable) {
+        runtime::spawn(async move {
+            let info = &self.info;
+            let mut reported_at = Instant::now();
+
+            loop {
+                runtime::sleep(self.interval).await;
+                if state.is_closed() || reporter_state.is_closed() {
+                    break;
+                }
+
+                let report = self.stats.take_report(reported_at.elapsed());
+                reported_at = Instant::now();
+
+                if let Err(err) = self.event_sender.send(Event::StatsReport(report)) {
+                    log::trace!("[{info:?}] failed to report stats: {err:?}");
+                    break;
+                }
+            }
+
+            log::trace!("[{info:?}] stats reporter stopped");
+        });
+    }
+}
@@ -20,18 +20,54 @@ pub trait ReceiveEvent<V: MaybeVersioned>: Sealed {
     /// Receives the next node [`Event`].
     ///
     /// Blocks until event received.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If it is used as the event in a `tokio::select!` statement and
+    /// some other branch completes first, it is guaranteed that no events were received.
     async fn recv(&mut self) -> RecvResult<Event<V>>;
 
     /// <sup>[`async`](crate::asnc)</sup>
     /// Attempts to receive the next node [`Event`] within a `timeout`.
     ///
     /// Blocks until event received or deadline is reached.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. See [`recv`](Self::recv) for details.
     async fn recv_timeout(&mut self, timeout: Duration) -> RecvTimeoutResult<Event<V>>;
 
     /// <sup>[`async`](crate::asnc)</sup>
     /// Attempts to receive MAVLink [`Event`] without blocking.
     fn try_recv(&mut self) -> TryRecvResult<Event<V>>;
 
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Receives up to `max` node [`Event`]s into a `buffer`.
+    ///
+    /// Waits until at least one event received, then takes all pending events without waiting
+    /// until `max` events are collected. Received events are appended to the `buffer`. Returns the
+    /// number of received events which is `0` only if `max` is `0`.
+    ///
+    /// Pending events are taken from the channel in batches instead of one by one. For receivers,
+    /// that joined a group, each batch is taken under a single lock.
+    ///
+    /// Use this method instead of [`recv`] when you need to process a high volume of events, and
+    /// you want to reuse the same buffer between calls.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If it is cancelled, then no events were appended to the
+    /// `buffer`.
+    ///
+    /// [`recv`]: Self::recv
+    async fn recv_many(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize>;
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Takes up to `max` pending node [`Event`]s without blocking.
+    ///
+    /// Returns an empty vector if there are no pending events or the channel is closed.
+    fn drain(&mut self, max: usize) -> Vec<Event<V>>;
+
     /// <sup>[`async`](crate::asnc)</sup>
     /// Subscribes to node events.
     ///
@@ -61,7 +97,40 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`recv`] instead to receive [`Event::Invalid`] event that contain invalid
-    /// frame with the corresponding error.
+    /// frame with the corresponding validation report.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If it is used as the event in a `tokio::select!` statement and
+    /// some other branch completes first, it is guaranteed that no frames were received. Events
+    /// other than valid frames may be skipped before cancellation, as they would be skipped
+    /// anyway.
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use std::time::Duration;
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let mut node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    /// let mut ticks = tokio::time::interval(Duration::from_millis(100));
+    ///
+    /// loop {
+    ///     tokio::select! {
+    ///         // No frames are lost, when ticks win the race
+    ///         result = node.recv_frame() => {
+    ///             let (frame, callback) = result.unwrap();
+    ///             /* process frame */
+    ///         }
+    ///         _ = ticks.tick() => {
+    ///             /* do periodic work */
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
     ///
     /// [`recv_frame_timeout`]: Self::recv_frame_timeout
     /// [`try_recv_frame`]: Self::try_recv_frame
@@ -88,7 +157,11 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`recv_timeout`] instead to receive [`Event::Invalid`] event that contains
-    /// invalid frame with the corresponding error.
+    /// invalid frame with the corresponding validation report.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. See [`recv_frame`](Self::recv_frame) for details.
     ///
     /// [`recv_frame`]: Self::recv_frame
     /// [`try_recv_frame`]: Self::try_recv_frame
@@ -133,7 +206,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`try_recv`] instead to receive [`Event::Invalid`] event that contains invalid
-    /// frame with the corresponding error.
+    /// frame with the corresponding validation report.
     ///
     /// [`recv_frame`]: Self::recv_frame
     /// [`recv_frame_timeout`]: Self::recv_frame_timeout
@@ -154,7 +227,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`], [`recv`], [`recv_timeout`], or [`try_recv`] instead to receive
-    /// [`Event::Invalid`] event that contains invalid frame with the corresponding error.
+    /// [`Event::Invalid`] event that contains invalid frame with the corresponding validation report.
     ///
     /// [`recv`]: ReceiveEvent::recv
     /// [`recv_timeout`]: ReceiveEvent::recv_timeout
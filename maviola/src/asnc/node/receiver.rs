@@ -1,16 +1,17 @@
 use mavio::protocol::Behold;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use tokio_stream::Stream;
 
 use crate::asnc::node::event::EventStream;
-use crate::core::utils::{Closable, Sealed};
+use crate::core::node::{LatencyStats, TrafficStats, ValidationReport};
+use crate::core::utils::{trace, Closable, Sealed};
 use crate::error::{
     RecvError, RecvResult, RecvTimeoutError, RecvTimeoutResult, TryRecvError, TryRecvResult,
 };
-use crate::protocol::FrameProcessor;
+use crate::protocol::{FrameProcessor, TargetFields};
 
 use crate::asnc::prelude::*;
 use crate::prelude::*;
@@ -21,12 +22,23 @@ use crate::prelude::*;
 /// **⚠** In order to have access to [`EventReceiver`] methods, you have to import
 /// [`ReceiveEvent`] and [`ReceiveFrame`] traits. You may import [`asnc::prelude`] as well.
 ///
+/// By default, each receiver gets all node events. Receivers that joined a group by
+/// [`EventReceiver::join_group`] share events: each event is delivered to exactly one member of
+/// the group.
+///
+/// All receiving methods are cancel safe: an event is taken from the channel only by the future,
+/// that returns it. This allows to use receivers in `tokio::select!` loops without losing events.
+///
 /// [`asnc::prelude`]: crate::asnc::prelude
 #[derive(Clone)]
 pub struct EventReceiver<V: MaybeVersioned> {
     inner: mpmc::Receiver<Event<V>>,
+    group: Option<mpmc::GroupReceiver<Event<V>>>,
+    target: Option<MavLinkId>,
     state: Closable,
     processor: Arc<FrameProcessor>,
+    latency: Option<LatencyStats>,
+    stats: TrafficStats,
 }
 
 impl<V: MaybeVersioned> EventReceiver<V> {
@@ -34,11 +46,90 @@ impl<V: MaybeVersioned> EventReceiver<V> {
         receiver: mpmc::Receiver<Event<V>>,
         state: Closable,
         processor: Arc<FrameProcessor>,
+        latency: Option<LatencyStats>,
+        stats: TrafficStats,
     ) -> Self {
         Self {
             inner: receiver,
+            group: None,
+            target: None,
             state,
             processor,
+            latency,
+            stats,
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Creates a receiver, that joined a group of receivers with specified `name`.
+    ///
+    /// Each event is delivered to exactly one member of the group, which allows to process
+    /// expensive frames in parallel by a pool of workers. Cloning the returned receiver adds a new
+    /// member to the same group. Groups with different names are independent of each other and
+    /// of broadcast receivers.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[tokio::main] async fn main() {
+    /// use maviola::prelude::*;
+    /// use maviola::asnc::prelude::*;
+    ///
+    /// let node = Node::asnc::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().await.unwrap();
+    ///
+    /// let workers = node.receiver_cloned().join_group("workers");
+    ///
+    /// for _ in 0..4 {
+    ///     let worker = workers.clone();
+    ///     tokio::spawn(async move {
+    ///         let mut frames = worker.frames().unwrap();
+    ///         while let Some((frame, callback)) = frames.next().await {
+    ///             /* each frame is processed by exactly one worker */
+    ///         }
+    ///     });
+    /// }
+    /// # }
+    /// ```
+    pub fn join_group(&self, name: &str) -> Self {
+        let group = match &self.group {
+            Some(group) if group.group() == name => group.clone(),
+            _ => self.inner.join_group(name),
+        };
+
+        Self {
+            inner: self.inner.clone(),
+            group: Some(group),
+            target: self.target,
+            state: self.state.clone(),
+            processor: self.processor.clone(),
+            latency: self.latency.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Creates a receiver, that skips frames addressed to other systems or components.
+    ///
+    /// Frames of messages without target fields and frames addressed to all systems or components
+    /// are kept, as well as events other than frames. Used by [`NodeComponent::receiver_mut`] to
+    /// deliver frames addressed to a particular component.
+    ///
+    /// [`NodeComponent::receiver_mut`]: crate::asnc::node::NodeComponent::receiver_mut
+    pub fn addressed_to(&self, id: MavLinkId) -> Self {
+        Self {
+            target: Some(id),
+            ..self.clone()
+        }
+    }
+
+    /// Creates a receiver with the same settings, that listens to another channel.
+    pub(super) fn subscribed(&self, receiver: mpmc::Receiver<Event<V>>) -> Self {
+        Self {
+            inner: receiver,
+            group: None,
+            ..self.clone()
         }
     }
 
@@ -46,22 +137,111 @@ impl<V: MaybeVersioned> EventReceiver<V> {
         &self.state
     }
 
+    /// Number of events, that were sent to this receiver, but not yet received.
+    ///
+    /// Events of a receiver group are not accounted.
+    pub(in crate::asnc) fn pending(&self) -> usize {
+        self.inner.len()
+    }
+
     pub(super) async fn recv(&mut self) -> core::result::Result<Event<V>, RecvError> {
-        let event = self.inner.recv().await?;
-        Ok(self.process_event(event))
+        loop {
+            let event = match &mut self.group {
+                Some(group) => group.recv().await?,
+                None => self.inner.recv().await?,
+            };
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
     }
 
     pub(in crate::asnc) async fn recv_timeout(
         &mut self,
         timeout: Duration,
     ) -> core::result::Result<Event<V>, RecvTimeoutError> {
-        let event = self.inner.recv_timeout(timeout).await?;
-        Ok(self.process_event(event))
+        let deadline = Instant::now() + timeout;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let event = match &mut self.group {
+                Some(group) => group.recv_timeout(timeout).await?,
+                None => self.inner.recv_timeout(timeout).await?,
+            };
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
     }
 
     pub(super) fn try_recv(&mut self) -> core::result::Result<Event<V>, TryRecvError> {
-        let event = self.inner.try_recv()?;
-        Ok(self.process_event(event))
+        loop {
+            let event = self.try_recv_raw()?;
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
+    }
+
+    pub(super) async fn recv_many(
+        &mut self,
+        buffer: &mut Vec<Event<V>>,
+        max: usize,
+    ) -> core::result::Result<usize, RecvError> {
+        if max == 0 {
+            return Ok(0);
+        }
+
+        let event = self.recv().await?;
+        buffer.push(event);
+        Ok(1 + self.drain_into(buffer, max - 1))
+    }
+
+    pub(super) fn drain(&mut self, max: usize) -> Vec<Event<V>> {
+        let mut buffer = Vec::new();
+        self.drain_into(&mut buffer, max);
+        buffer
+    }
+
+    /// Takes pending events in batches, skipping events not accepted by the receiver.
+    fn drain_into(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> usize {
+        let mut events = Vec::new();
+        let mut received = 0;
+        while received < max {
+            if self.drain_raw(&mut events, max - received) == 0 {
+                break;
+            }
+            for event in events.drain(..) {
+                if self.accepts(&event) {
+                    buffer.push(self.process_event(event));
+                    received += 1;
+                }
+            }
+        }
+        received
+    }
+
+    fn drain_raw(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> usize {
+        match &mut self.group {
+            Some(group) => group.drain_into(buffer, max),
+            None => self.inner.drain_into(buffer, max),
+        }
+    }
+
+    fn try_recv_raw(&mut self) -> core::result::Result<Event<V>, TryRecvError> {
+        match &mut self.group {
+            Some(group) => group.try_recv(),
+            None => self.inner.try_recv(),
+        }
+    }
+
+    /// Returns `false` for frames, that are not addressed to the receiver target (if any).
+    fn accepts(&self, event: &Event<V>) -> bool {
+        match (&self.target, event) {
+            (Some(id), Event::Frame(frame, _)) | (Some(id), Event::Invalid(frame, _, _)) => {
+                TargetFields::is_addressed_to(frame, *id)
+            }
+            _ => true,
+        }
     }
 
     fn process_event(&self, event: Event<V>) -> Event<V> {
@@ -69,18 +249,51 @@ impl<V: MaybeVersioned> EventReceiver<V> {
             Event::Frame(mut frame, mut callback) => {
                 callback.set_processor(self.processor.clone());
 
-                if let Err(err) = self.processor.process_incoming(&mut frame) {
-                    return Event::Invalid(frame, err, callback);
+                let span = trace::incoming_frame_span(&frame, callback.info());
+                if let Err(report) = span
+                    .in_scope(|| ValidationReport::validate_incoming(&mut frame, &self.processor))
+                {
+                    self.stats.record_invalid();
+                    return Event::Invalid(frame, report, callback);
+                }
+
+                if let Some(latency) = &self.latency {
+                    latency.record_incoming(frame.message_id(), callback.received_at().elapsed());
                 }
 
                 Event::Frame(frame, callback)
             }
-            Event::Invalid(frame, err, mut callback) => {
+            Event::Invalid(frame, report, mut callback) => {
                 callback.set_processor(self.processor.clone());
-                Event::Invalid(frame, err, callback)
+                Event::Invalid(frame, report, callback)
             }
+            Event::Malformed(channel, report) => Event::Malformed(channel, report),
+            Event::SenderRejected(info, addr) => Event::SenderRejected(info, addr),
             Event::NewPeer(peer) => Event::NewPeer(peer),
             Event::PeerLost(peer) => Event::PeerLost(peer),
+            Event::PeerDegraded(peer, quality) => Event::PeerDegraded(peer, quality),
+            Event::SystemChanged(system) => Event::SystemChanged(system),
+            Event::NewComponent {
+                system_id,
+                component_id,
+                component_kind,
+            } => Event::NewComponent {
+                system_id,
+                component_id,
+                component_kind,
+            },
+            Event::Anomaly(anomaly) => Event::Anomaly(anomaly),
+            Event::ConnectionLost(info) => Event::ConnectionLost(info),
+            Event::ConnectionRestored(info) => Event::ConnectionRestored(info),
+            Event::ConnectionStale(info) => Event::ConnectionStale(info),
+            Event::ConnectionAlive(info) => Event::ConnectionAlive(info),
+            Event::ConnectionAdded(info) => Event::ConnectionAdded(info),
+            Event::ConnectionRemoved(info) => Event::ConnectionRemoved(info),
+            Event::LinkActivated(info) => Event::LinkActivated(info),
+            Event::ChannelOpened(info) => Event::ChannelOpened(info),
+            Event::ChannelClosed(info) => Event::ChannelClosed(info),
+            Event::StatsReport(report) => Event::StatsReport(report),
+            Event::Custom(event) => Event::Custom(event),
         }
     }
 }
@@ -104,6 +317,16 @@ impl<V: MaybeVersioned> ReceiveEvent<V> for EventReceiver<V> {
         self.try_recv()
     }
 
+    #[inline(always)]
+    async fn recv_many(&mut self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize> {
+        self.recv_many(buffer, max).await
+    }
+
+    #[inline(always)]
+    fn drain(&mut self, max: usize) -> Vec<Event<V>> {
+        self.drain(max)
+    }
+
     fn events(&self) -> Behold<impl Stream<Item = Event<V>>> {
         Behold::new(EventStream::new(self.clone()))
     }
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
 use crate::asnc::io::OutgoingFrameSender;
-use crate::core::io::{BroadcastScope, OutgoingFrame};
+use crate::core::io::{BroadcastScope, EgressPriorities, OutgoingFrame};
 use crate::core::marker::{Edge, NodeKind, Proxy};
-use crate::core::node::{SendFrameInternal, SendMessageInternal};
-use crate::core::utils::Sealed;
+use crate::core::node::{
+    LatencyStats, PeerLocations, SendFrameInternal, SendMessageInternal, TrafficStats,
+};
+use crate::core::utils::{trace, Sealed};
 use crate::error::SendResult;
 use crate::protocol::FrameProcessor;
 
@@ -22,16 +24,31 @@ use crate::prelude::*;
 pub struct FrameSender<V: MaybeVersioned, K: NodeKind> {
     inner: OutgoingFrameSender<V>,
     processor: Arc<FrameProcessor>,
+    latency: Option<LatencyStats>,
+    stats: TrafficStats,
+    priorities: Option<Arc<EgressPriorities>>,
+    locations: PeerLocations,
     kind: K,
 }
 
 impl<V: MaybeVersioned> FrameSender<V, Proxy> {
     /// <sup>⛔</sup>
     /// Creates a new proxy frame sender.
-    pub(super) fn new(sender: OutgoingFrameSender<V>, processor: Arc<FrameProcessor>) -> Self {
+    pub(in crate::asnc) fn new(
+        sender: OutgoingFrameSender<V>,
+        processor: Arc<FrameProcessor>,
+        latency: Option<LatencyStats>,
+        stats: TrafficStats,
+        priorities: Option<EgressPriorities>,
+        locations: PeerLocations,
+    ) -> Self {
         Self {
             inner: sender,
             processor,
+            latency,
+            stats,
+            priorities: priorities.map(Arc::new),
+            locations,
             kind: Proxy,
         }
     }
@@ -42,6 +59,10 @@ impl<V: MaybeVersioned> FrameSender<V, Proxy> {
         FrameSender {
             inner: self.inner,
             processor: self.processor,
+            latency: self.latency,
+            stats: self.stats,
+            priorities: self.priorities,
+            locations: self.locations,
             kind,
         }
     }
@@ -50,11 +71,33 @@ impl<V: MaybeVersioned> FrameSender<V, Proxy> {
 impl<V: MaybeVersioned, K: NodeKind> FrameSender<V, K> {
     /// <sup>⛔ | 💢</sup>
     /// Sends outgoing frame without processing.
+    #[allow(clippy::result_large_err)]
     pub(in crate::asnc) unsafe fn send_raw(
         &self,
-        frame: OutgoingFrame<V>,
+        mut frame: OutgoingFrame<V>,
     ) -> SendResult<OutgoingFrame<V>> {
-        self.inner.send_raw(frame)
+        frame.track_latency(self.latency.as_ref());
+        frame.track_stats(&self.stats);
+        frame.resolve_peers(&self.locations);
+        if let Some(priorities) = &self.priorities {
+            frame.prioritize(priorities);
+        }
+        trace::outgoing_frame_span(&frame).in_scope(|| self.inner.send_raw(frame))
+    }
+
+    /// <sup>⛔</sup>
+    /// Processes and sends an automatic heartbeat `frame` emitted by a node or its component.
+    ///
+    /// Unlike [`SendFrame::send_frame`], marks frame as a node heartbeat, so networks may suppress
+    /// it for particular connections.
+    pub(in crate::asnc) fn send_heartbeat(&self, frame: &Frame<V>) -> Result<()> {
+        let mut frame = frame.clone();
+        self.processor.process_outgoing(&mut frame)?;
+        let mut frame = OutgoingFrame::node_heartbeat(frame);
+        if let Some(priorities) = &self.priorities {
+            frame.prioritize(priorities);
+        }
+        self.inner.send_raw(frame).map_err(Error::from)
     }
 
     /// <sup>⛔</sup>
@@ -84,6 +127,13 @@ impl<V: MaybeVersioned, K: NodeKind> SendFrameInternal<V> for FrameSender<V, K>
             .send_raw(OutgoingFrame::scoped(frame, scope))
             .map_err(Error::from)
     }
+
+    #[inline(always)]
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()> {
+        self.inner
+            .send_raw(OutgoingFrame::targeted(frame))
+            .map_err(Error::from)
+    }
 }
 
 impl<V: MaybeVersioned, K: NodeKind> SendFrame<V> for FrameSender<V, K> {}
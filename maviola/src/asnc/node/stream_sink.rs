@@ -0,0 +1,163 @@
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_sink::Sink;
+use tokio_stream::Stream;
+
+use crate::asnc::node::event::EventStream;
+use crate::core::marker::NodeKind;
+use crate::error::NodeError;
+
+use crate::asnc::prelude::*;
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Node adapter, that implements [`Stream`] of node events and [`Sink`] of outgoing frames.
+///
+/// Created by [`Node::into_stream_sink`]. Allows to use nodes with stream combinators,
+/// `tokio::select!` loops, and other tools from the async ecosystem, that operate on
+/// `futures::Stream` and `futures::Sink`.
+///
+/// Stream yields the same events as [`Node::events`](ReceiveEvent::events). Frames sent into the
+/// sink are processed and routed in the same way as by [`SendFrame::send_frame`]. Sending never
+/// waits for transport, so flushing the sink completes immediately. Closing the sink rejects
+/// further frames with [`NodeError::Inactive`], while the stream keeps yielding events. The node
+/// is closed, once the adapter is dropped, or can be taken back by
+/// [`NodeStreamSink::into_inner`].
+pub struct NodeStreamSink<K: NodeKind, V: MaybeVersioned> {
+    node: Node<K, V, AsyncApi<V>>,
+    events: EventStream<V>,
+    is_closed: bool,
+}
+
+impl<K: NodeKind, V: MaybeVersioned> NodeStreamSink<K, V> {
+    pub(super) fn new(node: Node<K, V, AsyncApi<V>>) -> Self {
+        let events = EventStream::new(node.api.event_receiver().clone());
+        Self {
+            node,
+            events,
+            is_closed: false,
+        }
+    }
+
+    /// Returns a reference to the underlying node.
+    pub fn node(&self) -> &Node<K, V, AsyncApi<V>> {
+        &self.node
+    }
+
+    /// Returns the underlying node.
+    ///
+    /// Events, that were not yet consumed from the stream, remain available for node receivers.
+    pub fn into_inner(self) -> Node<K, V, AsyncApi<V>> {
+        self.node
+    }
+
+    fn check_ready(&self) -> Result<()> {
+        if self.is_closed || !self.node.is_connected() {
+            return Err(NodeError::Inactive.into());
+        }
+        Ok(())
+    }
+}
+
+// Adapter is never structurally pinned, its fields are accessed only through mutable references.
+impl<K: NodeKind, V: MaybeVersioned> Unpin for NodeStreamSink<K, V> {}
+
+impl<K: NodeKind, V: MaybeVersioned> Debug for NodeStreamSink<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeStreamSink")
+            .field("info", self.node.info())
+            .field("is_closed", &self.is_closed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: NodeKind, V: MaybeVersioned> Stream for NodeStreamSink<K, V> {
+    type Item = Event<V>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+impl<K: NodeKind, V: MaybeVersioned> Sink<Frame<V>> for NodeStreamSink<K, V> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(self.check_ready())
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Frame<V>) -> Result<()> {
+        self.check_ready()?;
+        self.node.send_frame(&frame)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+        self.is_closed = true;
+        Poll::Ready(Ok(()))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod stream_sink_tests {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::dialects::minimal::messages::Heartbeat;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn frames_are_sent_and_received() {
+        let addr = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+
+        let server = Node::asnc::<V2>()
+            .connection(TcpServer::new(addr.as_str()).unwrap())
+            .build()
+            .await
+            .unwrap();
+        let client = Node::asnc::<V2>()
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let mut server = server.into_stream_sink();
+        let mut client = client.into_stream_sink();
+
+        let frame = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        Pin::new(&mut client).start_send(frame).unwrap();
+
+        let received = tokio::time::timeout(Duration::from_millis(500), async {
+            while let Some(event) = server.next().await {
+                if let Event::Frame(frame, _) = event {
+                    return frame;
+                }
+            }
+            unreachable!()
+        })
+        .await
+        .unwrap();
+        assert_eq!(received.system_id(), 1);
+
+        let waker = std::task::Waker::noop();
+        let mut cx = Context::from_waker(waker);
+        assert!(matches!(
+            Pin::new(&mut client).poll_close(&mut cx),
+            Poll::Ready(Ok(()))
+        ));
+        assert!(Pin::new(&mut client).start_send(received.clone()).is_err());
+    }
+}
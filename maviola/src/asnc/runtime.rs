@@ -0,0 +1,123 @@
+//! # Asynchronous runtime
+//!
+//! Tasks, timers, and network sockets of the asynchronous API.
+//!
+//! The asynchronous API runs on [Tokio](https://tokio.rs) only. Nodes and connections have to be
+//! built within Tokio runtime context. This module gathers Tokio primitives used by
+//! [`asnc`](crate::asnc) in one place. Custom transports should use [`spawn`], [`sleep`], and
+//! [`timeout`] from here, since they preserve tracing spans of the caller, if `tracing` feature is
+//! enabled.
+
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::time::Duration;
+
+use crate::core::utils::trace::{self, Span};
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Reads bytes from an asynchronous source.
+pub use tokio::io::AsyncRead;
+/// <sup>[`async`](crate::asnc)</sup>
+/// Writes bytes to an asynchronous sink.
+pub use tokio::io::AsyncWrite;
+/// <sup>[`async`](crate::asnc)</sup>
+/// TCP listener.
+pub use tokio::net::TcpListener;
+/// <sup>[`async`](crate::asnc)</sup>
+/// TCP stream.
+pub use tokio::net::TcpStream;
+/// <sup>[`async`](crate::asnc)</sup>
+/// UDP socket.
+pub use tokio::net::UdpSocket;
+/// <sup>[`async`](crate::asnc)</sup>
+/// Handle to a task spawned by [`spawn`] or [`spawn_blocking`].
+///
+/// Awaiting a handle returns task output or an error, if task has panicked or was aborted.
+pub use tokio::task::JoinHandle;
+/// <sup>[`async`](crate::asnc)</sup>
+/// Monotonic clock of the runtime.
+///
+/// Unlike [`std::time::Instant`], respects runtime time control, such as paused time in tests.
+pub use tokio::time::Instant;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Error returned by [`timeout`], when deadline has been reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Spawns a new asynchronous task.
+///
+/// If `tracing` feature is enabled, task runs within the current tracing span.
+pub fn spawn<F>(task: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(trace::instrument(task, Span::current()))
+}
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Runs blocking function on a thread, where blocking is acceptable.
+///
+/// If `tracing` feature is enabled, function runs within the current tracing span.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let span = Span::current();
+    tokio::task::spawn_blocking(move || span.in_scope(f))
+}
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Waits until `duration` has elapsed.
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Requires `future` to complete before `duration` has elapsed.
+///
+/// Returns [`Elapsed`] error, if future has not completed in time.
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| Elapsed)
+}
+
+impl Display for Elapsed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod runtime_tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn timers() {
+        let start = Instant::now();
+        sleep(Duration::from_secs(1)).await;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+
+        let result = timeout(Duration::from_millis(10), sleep(Duration::from_secs(1))).await;
+        assert_eq!(result, Err(Elapsed));
+
+        let result = timeout(Duration::from_secs(1), async { 42 }).await;
+        assert_eq!(result, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn tasks() {
+        assert_eq!(spawn(async { 1 }).await.unwrap(), 1);
+        assert_eq!(spawn_blocking(|| 2).await.unwrap(), 2);
+    }
+}
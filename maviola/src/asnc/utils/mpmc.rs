@@ -1,10 +1,16 @@
 //! # <sup>`⍚` | [`asnc`](crate::asnc)</sup> Multiple producers / multiple consumers broadcast channel
+//!
+//! In addition to broadcasting, receivers may join a named group by [`Receiver::join_group`]. Each
+//! message is delivered to exactly one member of a [`GroupReceiver`] group.
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Mutex};
 
+use crate::asnc::runtime;
 use crate::error::{RecvError, RecvTimeoutError, SendError, TryRecvError};
 
 /// <sup>`⍚` | [`asnc`](crate::asnc)</sup>
@@ -29,8 +35,48 @@ pub struct Sender<T> {
 /// Each cloned receiver will receive its own message.
 pub struct Receiver<T> {
     inner: broadcast::Receiver<T>,
+    groups: Groups<T>,
 }
 
+/// <sup>`⍚` | [`asnc`](crate::asnc)</sup>
+/// Member of a group of MPMC receivers.
+///
+/// Unlike [`Receiver`], each message is delivered to exactly one member of the group. Members
+/// compete for messages in the order they started waiting. Groups are identified by name and
+/// created by [`Receiver::join_group`].
+///
+/// Cloning a group receiver adds a new member to the same group.
+///
+/// # Usage
+///
+/// ```rust
+/// # #[tokio::main] async fn main() {
+/// # #[cfg(feature = "unstable")]{
+/// use maviola::asnc::utils::mpmc;
+///
+/// let (tx, mut rx) = mpmc::channel(16);
+/// let mut worker_1 = rx.join_group("workers");
+/// let mut worker_2 = rx.join_group("workers");
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// // Broadcast receiver gets all messages
+/// assert_eq!(rx.recv().await.unwrap(), 1);
+/// assert_eq!(rx.recv().await.unwrap(), 2);
+///
+/// // Each message is delivered to a single group member
+/// assert_eq!(worker_1.recv().await.unwrap(), 1);
+/// assert_eq!(worker_2.recv().await.unwrap(), 2);
+/// # }}
+/// ```
+pub struct GroupReceiver<T> {
+    name: Arc<str>,
+    inner: Arc<Mutex<broadcast::Receiver<T>>>,
+}
+
+type Groups<T> = Arc<std::sync::Mutex<HashMap<String, Weak<Mutex<broadcast::Receiver<T>>>>>>;
+
 impl<T> Sender<T> {
     /// Attempts to send a value on this channel, returning it back if it could
     /// not be sent.
@@ -62,7 +108,7 @@ impl<T: Clone> Receiver<T> {
     /// Behaves similar to [`broadcast::Receiver::recv`] but returns [`RecvTimeoutError`] and stops,
     /// when deadline is reached.
     pub async fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
-        match tokio::time::timeout(timeout, self.inner.recv()).await {
+        match runtime::timeout(timeout, self.inner.recv()).await {
             Ok(result) => match result {
                 Ok(value) => Ok(value),
                 Err(err) => Err(match err {
@@ -81,10 +127,57 @@ impl<T: Clone> Receiver<T> {
         self.inner.try_recv().map_err(TryRecvError::from)
     }
 
+    /// Moves up to `max` pending values to `buffer` without waiting.
+    ///
+    /// Returns the number of moved values. Stops at the first error, including lagging.
+    pub fn drain_into(&mut self, buffer: &mut Vec<T>, max: usize) -> usize {
+        drain_broadcast(&mut self.inner, buffer, max)
+    }
+
+    /// Number of messages, that were sent to the channel, but not yet received by this receiver.
+    ///
+    /// Behaves identical to [`broadcast::Receiver::len`].
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true`, if there are no messages waiting to be received.
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     /// Creates a new receiver subscribed to the channel.
     pub fn resubscribe(&self) -> Receiver<T> {
         Self {
             inner: self.inner.resubscribe(),
+            groups: self.groups.clone(),
+        }
+    }
+
+    /// Joins a group of receivers with specified `name`.
+    ///
+    /// Each message will be delivered to exactly one member of the group. If group does not exist,
+    /// it will be created. See [`GroupReceiver`] for details.
+    pub fn join_group(&self, name: &str) -> GroupReceiver<T> {
+        let mut groups = match self.groups.lock() {
+            Ok(groups) => groups,
+            Err(err) => err.into_inner(),
+        };
+        groups.retain(|_, group| group.strong_count() > 0);
+
+        let inner = match groups.get(name).and_then(Weak::upgrade) {
+            Some(inner) => inner,
+            None => {
+                let inner = Arc::new(Mutex::new(self.inner.resubscribe()));
+                groups.insert(name.to_string(), Arc::downgrade(&inner));
+                inner
+            }
+        };
+
+        GroupReceiver {
+            name: Arc::from(name),
+            inner,
         }
     }
 
@@ -95,6 +188,84 @@ impl<T: Clone> Receiver<T> {
     }
 }
 
+impl<T: Clone> GroupReceiver<T> {
+    /// Attempts to wait for a value on this receiver, returning an error if the
+    /// corresponding channel has hung up.
+    ///
+    /// Waits while other members of the group, that called this method earlier, are waiting for
+    /// their messages.
+    ///
+    /// This method is cancel safe. If it is cancelled, then the member leaves the queue of waiting
+    /// members, and no message is taken from the group.
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        self.inner
+            .lock()
+            .await
+            .recv()
+            .await
+            .map_err(RecvError::from)
+    }
+
+    /// Attempts to wait for a value on this receiver, returning an error if the
+    /// corresponding channel has hung up, or if it waits more than `timeout`.
+    ///
+    /// The time spent waiting for other members of the group counts towards `timeout`.
+    pub async fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match runtime::timeout(timeout, self.recv()).await {
+            Ok(result) => result.map_err(|err| match err {
+                RecvError::Lagged(n) => RecvTimeoutError::Lagged(n),
+                RecvError::Disconnected => RecvTimeoutError::Disconnected,
+            }),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    /// Attempts to return a pending value on this receiver without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if another member of the group is waiting for a message.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.inner.try_lock() {
+            Ok(mut inner) => inner.try_recv().map_err(TryRecvError::from),
+            Err(_) => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Moves up to `max` pending values to `buffer` without waiting.
+    ///
+    /// Returns the number of moved values. Values are taken from the group under a single lock, so
+    /// other members can't take messages in between. Returns `0`, if another member of the group
+    /// is waiting for a message.
+    pub fn drain_into(&mut self, buffer: &mut Vec<T>, max: usize) -> usize {
+        match self.inner.try_lock() {
+            Ok(mut inner) => drain_broadcast(&mut inner, buffer, max),
+            Err(_) => 0,
+        }
+    }
+
+    /// Name of the group.
+    pub fn group(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<T> Clone for GroupReceiver<T> {
+    /// Adds a new member to the same group.
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for GroupReceiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GroupReceiver")
+            .field("group", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
 unsafe impl<T: Send> Send for Sender<T> {}
 unsafe impl<T: Send> Sync for Sender<T> {}
 
@@ -133,6 +304,73 @@ impl<T: Clone> Clone for Receiver<T> {
 pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
     let (tx, rx) = broadcast::channel(capacity);
     let sender = Sender { inner: tx };
-    let receiver = Receiver { inner: rx };
+    let receiver = Receiver {
+        inner: rx,
+        groups: Default::default(),
+    };
     (sender, receiver)
 }
+
+fn drain_broadcast<T: Clone>(
+    receiver: &mut broadcast::Receiver<T>,
+    buffer: &mut Vec<T>,
+    max: usize,
+) -> usize {
+    let mut received = 0;
+    while received < max {
+        match receiver.try_recv() {
+            Ok(value) => buffer.push(value),
+            Err(_) => break,
+        }
+        received += 1;
+    }
+    received
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn group_members_receive_each_message_once() {
+        let (tx, rx) = channel(16);
+        let mut workers = [rx.join_group("workers"), rx.join_group("workers")];
+        let mut other = rx.join_group("other");
+
+        for i in 0..4 {
+            tx.send(i).unwrap();
+        }
+
+        let mut received = Vec::new();
+        for worker in workers.iter_mut() {
+            while let Ok(value) = worker.try_recv() {
+                received.push(value);
+            }
+        }
+        assert_eq!(received, vec![0, 1, 2, 3]);
+
+        for i in 0..4 {
+            assert_eq!(other.recv().await.unwrap(), i);
+        }
+    }
+
+    #[tokio::test]
+    async fn drain_into_takes_batch() {
+        let (tx, mut rx) = channel(16);
+        let mut group = rx.join_group("workers");
+
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+
+        let mut buffer = Vec::new();
+        assert_eq!(rx.drain_into(&mut buffer, 3), 3);
+        assert_eq!(rx.drain_into(&mut buffer, 10), 2);
+        assert_eq!(rx.drain_into(&mut buffer, 10), 0);
+        assert_eq!(buffer, vec![0, 1, 2, 3, 4]);
+
+        let mut buffer = Vec::new();
+        assert_eq!(group.drain_into(&mut buffer, 4), 4);
+        assert_eq!(buffer, vec![0, 1, 2, 3]);
+    }
+}
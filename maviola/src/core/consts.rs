@@ -9,13 +9,24 @@ mod _default_dialect {
     #[cfg(all(not(feature = "all"), feature = "ardupilotmega"))]
     pub use crate::dialects::Ardupilotmega as DefaultDialect;
 
-    #[cfg(all(not(feature = "ardupilotmega"), feature = "common"))]
+    #[cfg(all(
+        not(any(feature = "all", feature = "ardupilotmega")),
+        feature = "common"
+    ))]
     pub use crate::dialects::Common as DefaultDialect;
 
-    #[cfg(all(not(feature = "common"), feature = "standard"))]
+    #[cfg(all(
+        not(any(feature = "all", feature = "ardupilotmega", feature = "common")),
+        feature = "standard"
+    ))]
     pub use crate::dialects::Standard as DefaultDialect;
 
-    #[cfg(not(feature = "standard"))]
+    #[cfg(not(any(
+        feature = "all",
+        feature = "ardupilotmega",
+        feature = "common",
+        feature = "standard"
+    )))]
     pub use crate::dialects::Minimal as DefaultDialect;
 
     #[cfg(feature = "all")]
@@ -24,13 +35,24 @@ mod _default_dialect {
     #[cfg(all(not(feature = "all"), feature = "ardupilotmega"))]
     pub use crate::dialects::ardupilotmega as default_dialect;
 
-    #[cfg(all(not(feature = "ardupilotmega"), feature = "common"))]
+    #[cfg(all(
+        not(any(feature = "all", feature = "ardupilotmega")),
+        feature = "common"
+    ))]
     pub use crate::dialects::common as default_dialect;
 
-    #[cfg(all(not(feature = "common"), feature = "standard"))]
+    #[cfg(all(
+        not(any(feature = "all", feature = "ardupilotmega", feature = "common")),
+        feature = "standard"
+    ))]
     pub use crate::dialects::standard as default_dialect;
 
-    #[cfg(not(feature = "standard"))]
+    #[cfg(not(any(
+        feature = "all",
+        feature = "ardupilotmega",
+        feature = "common",
+        feature = "standard"
+    )))]
     pub use crate::dialects::minimal as default_dialect;
 }
 
@@ -80,8 +102,161 @@ pub use _default_dialect::default_dialect;
 pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_millis(1200);
 /// Default heartbeat interval.
 pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(1000);
+/// Default number of heartbeats per [`DEFAULT_HEARTBEAT_WINDOW`] after which a peer is considered
+/// to be flooding (see [`AnomalyDetector`](crate::protocol::AnomalyDetector)).
+pub const DEFAULT_MAX_HEARTBEATS: usize = 10;
+/// Default time window for measuring heartbeat rate.
+pub const DEFAULT_HEARTBEAT_WINDOW: Duration = Duration::from_secs(1);
+/// Default time after which [`AnomalyDetector`](crate::protocol::AnomalyDetector) forgets a peer,
+/// that has sent no heartbeats.
+pub const DEFAULT_ANOMALY_PEER_TTL: Duration = Duration::from_secs(60);
+/// Default number of the last received frames used to estimate link losses (see
+/// [`LinkQualityMonitor`](crate::protocol::LinkQualityMonitor)).
+pub const DEFAULT_LINK_QUALITY_WINDOW: usize = 100;
+/// Default percentage of lost frames, above which a link is considered to be degraded.
+pub const DEFAULT_MAX_LINK_LOSS: f32 = 10.0;
+/// Default interval between saves of a signing timestamp to a
+/// [`TimestampStore`](crate::protocol::TimestampStore). Saved timestamp is ahead of the last used
+/// one by up to two intervals.
+pub const DEFAULT_TIMESTAMP_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+/// Default maximum number of records kept by a [`BlackBox`](crate::core::node::BlackBox).
+pub const DEFAULT_BLACK_BOX_CAPACITY: usize = 1000;
+/// Default baud rate of serial ports configured by [`ConnectionSpec`] URLs without baud rate.
+///
+/// [`ConnectionSpec`]: crate::core::io::ConnectionSpec
+pub const DEFAULT_SERIAL_BAUD_RATE: u32 = 57600;
+/// Default capacity in bytes of each ring buffer of shared memory segments created by `IpcServer`.
+pub const DEFAULT_IPC_CAPACITY: usize = 64 * 1024;
+/// Maximum capacity in bytes of each ring buffer of shared memory segments.
+pub const MAX_IPC_CAPACITY: usize = 1 << 30;
 /// Default host for client to bind to.
 pub const DEFAULT_UDP_HOST: &str = "127.0.0.1";
+/// Default interval between health checks of higher-priority addresses for clients with fallback
+/// addresses (see [`TcpClient::with_fallback_addr`] and [`UdpClient::with_fallback_addr`]).
+///
+/// [`TcpClient::with_fallback_addr`]: crate::core::io::TcpClient::with_fallback_addr
+/// [`UdpClient::with_fallback_addr`]: crate::core::io::UdpClient::with_fallback_addr
+pub const DEFAULT_FAILBACK_INTERVAL: Duration = Duration::from_secs(5);
+/// Default period of silence after which UDP client switches to the next fallback address (see
+/// [`UdpClient::with_failover_timeout`](crate::core::io::UdpClient::with_failover_timeout)).
+pub const DEFAULT_FAILOVER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Default maximum number of frames spooled by an outbound queue.
+pub const DEFAULT_OUTBOUND_QUEUE_CAPACITY: usize = 1024;
+/// Default maximum number of frames, that are reordered by priority before being written to
+/// a channel (see [`FramePriority`](crate::core::io::FramePriority)).
+pub const DEFAULT_EGRESS_QUEUE_CAPACITY: usize = 128;
+
+/// Default time to wait for a response from a peer during mission transfer.
+#[cfg(feature = "msrv-utils-mission")]
+pub const DEFAULT_MISSION_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Default number of times a mission protocol message is resent before transfer fails.
+#[cfg(feature = "msrv-utils-mission")]
+pub const DEFAULT_MISSION_RETRIES: usize = 5;
+
+/// Default time to wait for a response from an FTP server.
+#[cfg(feature = "msrv-utils-ftp")]
+pub const DEFAULT_FTP_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Default number of times an FTP request is resent before transfer fails.
+#[cfg(feature = "msrv-utils-ftp")]
+pub const DEFAULT_FTP_RETRIES: usize = 5;
+
+/// Default time to wait for a camera to acknowledge a command or to send a requested message.
+#[cfg(feature = "msrv-utils-camera")]
+pub const DEFAULT_CAMERA_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Default number of times a camera command is resent before it fails.
+#[cfg(feature = "msrv-utils-camera")]
+pub const DEFAULT_CAMERA_RETRIES: usize = 3;
+
+/// Default time to wait for a camera to report a captured image once capture is acknowledged.
+#[cfg(feature = "msrv-utils-camera")]
+pub const DEFAULT_CAMERA_CAPTURE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time to wait for a gimbal manager to acknowledge a command or to send a requested
+/// message.
+#[cfg(feature = "msrv-utils-gimbal")]
+pub const DEFAULT_GIMBAL_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Default number of times a gimbal command is resent before it fails.
+#[cfg(feature = "msrv-utils-gimbal")]
+pub const DEFAULT_GIMBAL_RETRIES: usize = 3;
+
+/// Default time to wait for a vehicle to acknowledge a command or to report a parameter value.
+#[cfg(feature = "msrv-utils-gcs")]
+pub const DEFAULT_GCS_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Default number of times a ground control station request is resent before it fails.
+#[cfg(feature = "msrv-utils-gcs")]
+pub const DEFAULT_GCS_RETRIES: usize = 3;
+
+/// Default number of `TIMESYNC` exchanges performed by a latency measurement.
+#[cfg(feature = "msrv-utils-timesync")]
+pub const DEFAULT_TIMESYNC_SAMPLES: usize = 10;
+
+/// Default interval between `TIMESYNC` requests of a latency measurement.
+#[cfg(feature = "msrv-utils-timesync")]
+pub const DEFAULT_TIMESYNC_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default time to wait for a `TIMESYNC` response before the sample is considered lost.
+#[cfg(feature = "msrv-utils-timesync")]
+pub const DEFAULT_TIMESYNC_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Default interval between invariant checks of a soak test.
+#[cfg(feature = "soak")]
+pub const DEFAULT_SOAK_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default time after the start of a soak test, when memory baseline is taken.
+#[cfg(feature = "soak")]
+pub const DEFAULT_SOAK_WARMUP: Duration = Duration::from_secs(10);
+
+/// Default time without incoming frames, after which a soak test reports a channel as stuck.
+#[cfg(feature = "soak")]
+pub const DEFAULT_SOAK_STUCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default memory growth over the baseline in bytes, that a soak test tolerates.
+#[cfg(feature = "soak")]
+pub const DEFAULT_SOAK_MAX_MEMORY_GROWTH: usize = 16 * 1024 * 1024;
+
+/// Maximum number of violations recorded in a soak test report. Violations are still counted once
+/// this limit is reached.
+#[cfg(feature = "soak")]
+pub const SOAK_MAX_RECORDED_VIOLATIONS: usize = 100;
+
+/// Number of the most frequent incoming messages included into
+/// [`StatsReport::top_talkers`](crate::core::node::StatsReport::top_talkers).
+pub const STATS_REPORT_TOP_TALKERS: usize = 10;
+
+/// Time given to a client of [`TcpServer`](crate::core::io::TcpServer) to complete authentication
+/// handshake before it is rejected.
+pub const TCP_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default keep alive interval of MQTT bridge.
+#[cfg(feature = "mqtt")]
+pub const DEFAULT_MQTT_KEEP_ALIVE: Duration = Duration::from_secs(60);
+
+/// Default topic template, under which MQTT bridge publishes frames.
+#[cfg(feature = "mqtt")]
+pub const DEFAULT_MQTT_PUBLISH_TOPIC: &str = "mavlink/{system_id}/{component_id}/{message_id}";
+
+/// Default client name, that NATS bridge reports to the server.
+#[cfg(feature = "nats")]
+pub const DEFAULT_NATS_CLIENT_NAME: &str = "maviola";
+
+/// Default time a half-duplex [`SerialPort`](crate::core::io::SerialPort) waits before taking or
+/// releasing the line (see [`HalfDuplex`](crate::core::io::HalfDuplex)).
+#[cfg(feature = "serial")]
+pub const DEFAULT_HALF_DUPLEX_TURNAROUND: Duration = Duration::from_millis(2);
+
+/// Maximum number of frames pending in a [`Network`](crate::core::network::Network) tap. Once this
+/// limit is reached, new frames are dropped until the tap is consumed.
+pub const NETWORK_TAP_CAPACITY: usize = 1024;
+
+/// Maximum number of rejected senders remembered by a [`UdpServer`](crate::core::io::UdpServer).
+/// Each rejected sender is reported once, until this limit is reached and senders are forgotten.
+pub const UDP_MAX_REPORTED_SENDERS: usize = 1024;
 
 /// Time out after which it is guaranteed, that server connection will initiate closing procedure.
 pub const SERVER_HANG_UP_TIMEOUT: Duration = Duration::from_millis(50);
@@ -91,3 +266,30 @@ pub(crate) const INCOMING_FRAMES_POOLING_INTERVAL: Duration = Duration::from_mic
 
 /// Specifies a pooling interval for network nodes.
 pub(crate) const NETWORK_POOLING_INTERVAL: Duration = Duration::from_micros(50);
+
+/// Specifies a maximum pooling interval for the handler of microservices attached to a node.
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+pub(crate) const MICROSERVICES_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Specifies a maximum pooling interval for the handler of a node black box.
+pub(crate) const BLACK_BOX_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Specifies a maximum pooling interval for the scheduler of node periodic tasks.
+pub(crate) const PERIODIC_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Specifies a maximum pooling interval for the keepalive handler of a node.
+pub(crate) const KEEPALIVE_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Specifies a maximum pooling interval for soak test monitors and traffic generators.
+#[cfg(feature = "soak")]
+pub(crate) const SOAK_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Minimum interval between attempts to save a signing timestamp after the store has failed.
+pub(crate) const TIMESTAMP_SAVE_MIN_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum interval between attempts to save a signing timestamp after the store has failed.
+pub(crate) const TIMESTAMP_SAVE_MAX_RETRY_INTERVAL: Duration = Duration::from_secs(5);
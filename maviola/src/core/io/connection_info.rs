@@ -1,19 +1,23 @@
 use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
 use std::path::PathBuf;
-use std::sync::Once;
+use std::sync::OnceLock;
 
-use crate::core::io::{ChannelId, ConnectionId};
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+use crate::core::io::BluetoothAddr;
+use crate::core::io::{ChannelId, ChannelTap, ConnectionId, PauseSwitch, SharedTap};
 
 /// Information about a connection.
 #[derive(Clone)]
 pub struct ConnectionInfo {
     id: ConnectionId,
     details: ConnectionDetails,
+    tap: Option<SharedTap>,
+    pause: PauseSwitch,
 }
 
 /// Information about a connection.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ConnectionDetails {
     /// TCP server.
     TcpServer {
@@ -45,6 +49,11 @@ pub enum ConnectionDetails {
         /// File path.
         path: PathBuf,
     },
+    /// Replays a telemetry log in `.tlog` format.
+    TlogReader {
+        /// File path.
+        path: PathBuf,
+    },
     /// <sup>`unix`</sup>
     /// Unix socket server.
     #[cfg(unix)]
@@ -59,6 +68,83 @@ pub enum ConnectionDetails {
         /// Server address.
         path: PathBuf,
     },
+    /// <sup>`ipc`</sup>
+    /// Shared memory server.
+    #[cfg(all(feature = "ipc", unix))]
+    IpcServer {
+        /// Segment path.
+        path: PathBuf,
+    },
+    /// <sup>`ipc`</sup>
+    /// Shared memory client.
+    #[cfg(all(feature = "ipc", unix))]
+    IpcClient {
+        /// Segment path.
+        path: PathBuf,
+    },
+    /// <sup>`serial`</sup>
+    /// Serial port.
+    #[cfg(feature = "serial")]
+    SerialPort {
+        /// Device path.
+        path: PathBuf,
+        /// Baud rate.
+        baud_rate: u32,
+    },
+    /// <sup>`bluetooth`</sup>
+    /// Bluetooth classic serial client.
+    #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+    BluetoothClient {
+        /// Device address.
+        addr: BluetoothAddr,
+        /// RFCOMM channel.
+        channel: u8,
+    },
+    /// <sup>`zmq`</sup>
+    /// ZeroMQ publisher.
+    #[cfg(feature = "zmq")]
+    ZmqPub {
+        /// Publisher address.
+        bind_addr: SocketAddr,
+        /// Topic of published frames.
+        topic: String,
+    },
+    /// <sup>`zmq`</sup>
+    /// ZeroMQ subscriber.
+    #[cfg(feature = "zmq")]
+    ZmqSub {
+        /// Publisher address.
+        remote_addr: SocketAddr,
+        /// Subscribed topic prefix.
+        topic: String,
+    },
+    /// <sup>`mqtt`</sup>
+    /// MQTT bridge.
+    #[cfg(feature = "mqtt")]
+    MqttBridge {
+        /// Broker address.
+        broker_addr: SocketAddr,
+        /// Client identifier.
+        client_id: String,
+    },
+    /// <sup>`nats`</sup>
+    /// NATS bridge.
+    #[cfg(feature = "nats")]
+    NatsBridge {
+        /// Server address.
+        server_addr: SocketAddr,
+        /// Subject.
+        subject: String,
+    },
+    /// <sup>`redis`</sup>
+    /// Redis Pub/Sub bridge.
+    #[cfg(feature = "redis")]
+    RedisBridge {
+        /// Server address.
+        server_addr: SocketAddr,
+        /// Pub/Sub channel.
+        channel: String,
+    },
     /// Network with multiple connections.
     Network,
     /// Custom connection.
@@ -104,6 +190,10 @@ pub enum ChannelDetails {
         server_addr: SocketAddr,
         /// Peer address.
         peer_addr: SocketAddr,
+        /// Whether server is locked to this peer and ignores other senders.
+        ///
+        /// See [`UdpServer::lock_to_first_sender`](crate::core::io::UdpServer::lock_to_first_sender).
+        locked: bool,
     },
     /// UDP client.
     UdpClient {
@@ -122,6 +212,11 @@ pub enum ChannelDetails {
         /// File path.
         path: PathBuf,
     },
+    /// Replays a telemetry log in `.tlog` format.
+    TlogReader {
+        /// File path.
+        path: PathBuf,
+    },
     /// <sup>`unix`</sup>
     /// Unix socket server.
     #[cfg(unix)]
@@ -136,6 +231,92 @@ pub enum ChannelDetails {
         /// Socket path.
         path: PathBuf,
     },
+    /// <sup>`ipc`</sup>
+    /// Shared memory server.
+    #[cfg(all(feature = "ipc", unix))]
+    IpcServer {
+        /// Segment path.
+        path: PathBuf,
+    },
+    /// <sup>`ipc`</sup>
+    /// Shared memory client.
+    #[cfg(all(feature = "ipc", unix))]
+    IpcClient {
+        /// Segment path.
+        path: PathBuf,
+    },
+    /// <sup>`serial`</sup>
+    /// Serial port.
+    #[cfg(feature = "serial")]
+    SerialPort {
+        /// Device path.
+        path: PathBuf,
+        /// Baud rate.
+        baud_rate: u32,
+    },
+    /// <sup>`bluetooth`</sup>
+    /// Bluetooth classic serial client.
+    #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+    BluetoothClient {
+        /// Device address.
+        addr: BluetoothAddr,
+        /// RFCOMM channel.
+        channel: u8,
+    },
+    /// <sup>`zmq`</sup>
+    /// ZeroMQ publisher.
+    #[cfg(feature = "zmq")]
+    ZmqPub {
+        /// Publisher address.
+        server_addr: SocketAddr,
+        /// Subscriber address.
+        peer_addr: SocketAddr,
+        /// Topic of published frames.
+        topic: String,
+    },
+    /// <sup>`zmq`</sup>
+    /// ZeroMQ subscriber.
+    #[cfg(feature = "zmq")]
+    ZmqSub {
+        /// Publisher address.
+        server_addr: SocketAddr,
+        /// Subscribed topic prefix.
+        topic: String,
+    },
+    /// <sup>`mqtt`</sup>
+    /// MQTT bridge.
+    #[cfg(feature = "mqtt")]
+    MqttBridge {
+        /// Broker address.
+        broker_addr: SocketAddr,
+        /// Client identifier.
+        client_id: String,
+    },
+    /// <sup>`nats`</sup>
+    /// NATS bridge.
+    #[cfg(feature = "nats")]
+    NatsBridge {
+        /// Server address.
+        server_addr: SocketAddr,
+        /// Subject.
+        subject: String,
+    },
+    /// <sup>`redis`</sup>
+    /// Redis Pub/Sub bridge.
+    #[cfg(feature = "redis")]
+    RedisBridge {
+        /// Server address.
+        server_addr: SocketAddr,
+        /// Pub/Sub channel.
+        channel: String,
+    },
+    /// Virtual channel of frames injected into a node by user code.
+    ///
+    /// See `Node::inject_incoming`.
+    Injected {
+        /// Name of the frame origin.
+        origin: String,
+    },
     /// Custom channel.
     #[cfg(feature = "unstable")]
     Custom {
@@ -188,10 +369,44 @@ impl ConnectionInfo {
         self.make_channel_info_inner(details)
     }
 
+    /// Registers a [`ChannelTap`] for all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    #[cfg(feature = "unstable")]
+    #[inline(always)]
+    pub fn set_tap(&mut self, tap: impl ChannelTap) {
+        self.tap = Some(SharedTap::new(tap));
+    }
+
+    /// Registers a [`ChannelTap`] for all channels of this connection.
+    #[cfg(not(feature = "unstable"))]
+    #[inline(always)]
+    pub(crate) fn set_tap(&mut self, tap: impl ChannelTap) {
+        self.tap = Some(SharedTap::new(tap));
+    }
+
+    pub(crate) fn tap(&self) -> Option<&SharedTap> {
+        self.tap.as_ref()
+    }
+
+    /// Returns `true`, if connection is paused.
+    ///
+    /// Paused connections neither read from nor write to their transports. Connections are
+    /// paused and resumed by nodes, see `Node::pause` and `Node::pause_connection`.
+    pub fn is_paused(&self) -> bool {
+        self.pause.is_paused()
+    }
+
+    pub(crate) fn pause_switch(&self) -> &PauseSwitch {
+        &self.pause
+    }
+
     fn new_inner(details: ConnectionDetails) -> Self {
         Self {
             id: ConnectionId::new(),
             details,
+            tap: None,
+            pause: PauseSwitch::default(),
         }
     }
 
@@ -249,14 +464,10 @@ impl Debug for ChannelInfo {
     }
 }
 
-static mut UNKNOWN_CONNECTION: Option<ConnectionInfo> = None;
-static INIT_UNKNOWN_CONNECTION: Once = Once::new();
+static UNKNOWN_CONNECTION: OnceLock<ConnectionInfo> = OnceLock::new();
 
 impl ConnectionInfo {
     pub(in crate::core) fn unknown() -> &'static ConnectionInfo {
-        INIT_UNKNOWN_CONNECTION.call_once(|| unsafe {
-            UNKNOWN_CONNECTION = Some(ConnectionInfo::new(ConnectionDetails::Unknown));
-        });
-        unsafe { UNKNOWN_CONNECTION.as_ref().unwrap() }
+        UNKNOWN_CONNECTION.get_or_init(|| ConnectionInfo::new(ConnectionDetails::Unknown))
     }
 }
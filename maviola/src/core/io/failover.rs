@@ -0,0 +1,147 @@
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// <sup>⛔</sup>
+/// Priority-ordered list of remote addresses with the currently active one.
+///
+/// The first address has the highest priority. Connection-less transports use
+/// [`AddressFailover::check`] to switch to the next address, when the active one is silent for too
+/// long, and [`AddressFailover::on_received`] to fail back, once a higher-priority address shows
+/// signs of life. Higher-priority addresses should be probed each time [`AddressFailover::probe`]
+/// returns a non-empty list.
+#[derive(Clone, Debug)]
+pub(crate) struct AddressFailover {
+    addrs: Vec<SocketAddr>,
+    active: usize,
+    last_seen: Instant,
+    last_probe: Instant,
+    failover_timeout: Duration,
+    failback_interval: Duration,
+}
+
+impl AddressFailover {
+    /// Creates failover state with the first of `addrs` being active.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `addrs` is empty.
+    pub(crate) fn new(
+        addrs: Vec<SocketAddr>,
+        failover_timeout: Duration,
+        failback_interval: Duration,
+        now: Instant,
+    ) -> Self {
+        assert!(!addrs.is_empty(), "at least one address is required");
+
+        Self {
+            addrs,
+            active: 0,
+            last_seen: now,
+            last_probe: now,
+            failover_timeout,
+            failback_interval,
+        }
+    }
+
+    /// Currently active address.
+    pub(crate) fn active(&self) -> SocketAddr {
+        self.addrs[self.active]
+    }
+
+    /// Handles data received `from` a remote address.
+    ///
+    /// Returns `true` if data comes from the active address and should be accepted. If `from` has
+    /// higher priority than the active address, then it becomes active.
+    pub(crate) fn on_received(&mut self, from: SocketAddr, now: Instant) -> bool {
+        let priority = match self.addrs.iter().position(|addr| *addr == from) {
+            Some(priority) => priority,
+            None => return false,
+        };
+
+        if priority > self.active {
+            return false;
+        }
+
+        if priority < self.active {
+            log::info!("failing back from {} to {from}", self.active());
+            self.active = priority;
+        }
+        self.last_seen = now;
+
+        true
+    }
+
+    /// Switches to the next address, if the active one was silent longer than failover timeout.
+    ///
+    /// Returns the new active address, if switched.
+    pub(crate) fn check(&mut self, now: Instant) -> Option<SocketAddr> {
+        if self.addrs.len() < 2
+            || now.saturating_duration_since(self.last_seen) < self.failover_timeout
+        {
+            return None;
+        }
+
+        let failed = self.active();
+        self.active = (self.active + 1) % self.addrs.len();
+        self.last_seen = now;
+        log::info!("failing over from {failed} to {}", self.active());
+
+        Some(self.active())
+    }
+
+    /// Returns higher-priority addresses, that should be probed now.
+    ///
+    /// Returns an empty slice if the primary address is active or failback interval hasn't passed
+    /// since the previous probe.
+    pub(crate) fn probe(&mut self, now: Instant) -> &[SocketAddr] {
+        if self.active == 0
+            || now.saturating_duration_since(self.last_probe) < self.failback_interval
+        {
+            return &[];
+        }
+
+        self.last_probe = now;
+        &self.addrs[..self.active]
+    }
+}
+
+#[cfg(test)]
+mod failover_tests {
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn failover_and_failback() {
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        let mut failover = AddressFailover::new(
+            vec![addr(1), addr(2), addr(3)],
+            second * 3,
+            second * 5,
+            start,
+        );
+
+        assert_eq!(failover.active(), addr(1));
+        assert!(failover.on_received(addr(1), start + second));
+        assert!(!failover.on_received(addr(2), start + second));
+        assert!(!failover.on_received(addr(4), start + second));
+        assert!(failover.probe(start + second * 10).is_empty());
+
+        // Primary is silent
+        assert!(failover.check(start + second * 3).is_none());
+        assert_eq!(failover.check(start + second * 4), Some(addr(2)));
+        assert_eq!(failover.check(start + second * 7), Some(addr(3)));
+
+        // Probe higher-priority addresses once per failback interval
+        assert_eq!(failover.probe(start + second * 7), &[addr(1), addr(2)]);
+        assert!(failover.probe(start + second * 8).is_empty());
+
+        // Secondary responds, fail back
+        assert!(failover.on_received(addr(2), start + second * 8));
+        assert_eq!(failover.active(), addr(2));
+        assert!(!failover.on_received(addr(3), start + second * 8));
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// <sup>⛔</sup>
+/// Tracks outgoing frames of a connection, that are not yet written to transports.
+///
+/// Each frame sent to a connection receives a sequence number. Channels process outgoing frames
+/// in order, so once a frame is written, all previous frames are considered handled by the
+/// channel. A frame is considered flushed, once it or any subsequent frame was written by at
+/// least one channel.
+///
+/// Tracker also counts running channels of the connection and allows to stop accepting new frames
+/// while the connection is shutting down.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FlushTracker {
+    inner: Arc<FlushTrackerInner>,
+}
+
+/// <sup>⛔</sup>
+/// Position of an outgoing frame in the sequence of a [`FlushTracker`].
+#[derive(Clone, Debug)]
+pub(crate) struct FlushTicket {
+    tracker: FlushTracker,
+    seq: u64,
+}
+
+/// <sup>⛔</sup>
+/// Keeps a channel registered in a [`FlushTracker`] until dropped.
+#[derive(Debug)]
+pub(crate) struct ChannelGuard {
+    tracker: FlushTracker,
+}
+
+/// <sup>⛔</sup>
+/// Snapshot of [`FlushTracker`] counters.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FlushProgress {
+    submitted: u64,
+    written: u64,
+}
+
+#[derive(Debug, Default)]
+struct FlushTrackerInner {
+    submitted: AtomicU64,
+    written: AtomicU64,
+    channels: AtomicUsize,
+    draining: AtomicBool,
+}
+
+impl FlushTracker {
+    /// Assigns the next sequence number to an outgoing frame.
+    pub(crate) fn ticket(&self) -> FlushTicket {
+        let seq = self.inner.submitted.fetch_add(1, Ordering::AcqRel) + 1;
+        FlushTicket {
+            tracker: self.clone(),
+            seq,
+        }
+    }
+
+    /// Stops accepting new outgoing frames.
+    pub(crate) fn drain(&self) {
+        self.inner.draining.store(true, Ordering::Release);
+    }
+
+    /// Returns `true`, if tracker no longer accepts new outgoing frames.
+    pub(crate) fn is_draining(&self) -> bool {
+        self.inner.draining.load(Ordering::Acquire)
+    }
+
+    /// Number of frames, that are not yet handled by any channel.
+    pub(crate) fn pending(&self) -> usize {
+        self.progress().pending()
+    }
+
+    /// Current counters.
+    pub(crate) fn progress(&self) -> FlushProgress {
+        FlushProgress {
+            submitted: self.inner.submitted.load(Ordering::Acquire),
+            written: self.inner.written.load(Ordering::Acquire),
+        }
+    }
+
+    /// Registers a running channel.
+    pub(crate) fn open_channel(&self) -> ChannelGuard {
+        self.inner.channels.fetch_add(1, Ordering::AcqRel);
+        ChannelGuard {
+            tracker: self.clone(),
+        }
+    }
+
+    /// Number of running channels.
+    pub(crate) fn channels(&self) -> usize {
+        self.inner.channels.load(Ordering::Acquire)
+    }
+}
+
+impl FlushTicket {
+    /// Marks this frame and all previous frames as handled.
+    pub(crate) fn record_written(&self) {
+        self.tracker
+            .inner
+            .written
+            .fetch_max(self.seq, Ordering::AcqRel);
+    }
+}
+
+impl Drop for ChannelGuard {
+    fn drop(&mut self) {
+        self.tracker.inner.channels.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl FlushProgress {
+    /// Number of frames, that are not yet handled by any channel.
+    pub(crate) fn pending(&self) -> usize {
+        self.submitted.saturating_sub(self.written) as usize
+    }
+
+    /// Number of frames handled since the `earlier` snapshot, including frames, that were pending
+    /// at that moment.
+    pub(crate) fn flushed_since(&self, earlier: &FlushProgress) -> usize {
+        self.written.saturating_sub(earlier.written) as usize
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod flush_tests {
+    use super::*;
+
+    #[test]
+    fn written_frames_flush_previous_ones() {
+        let tracker = FlushTracker::default();
+        let tickets: Vec<FlushTicket> = (0..5).map(|_| tracker.ticket()).collect();
+        assert_eq!(tracker.pending(), 5);
+
+        let start = tracker.progress();
+        tickets[2].record_written();
+        assert_eq!(tracker.pending(), 2);
+
+        // Late writes of earlier frames do not move progress back
+        tickets[0].record_written();
+        assert_eq!(tracker.pending(), 2);
+
+        tickets[4].record_written();
+        assert_eq!(tracker.pending(), 0);
+        assert_eq!(tracker.progress().flushed_since(&start), 5);
+    }
+
+    #[test]
+    fn channels_are_counted() {
+        let tracker = FlushTracker::default();
+        let first = tracker.open_channel();
+        let second = tracker.open_channel();
+        assert_eq!(tracker.channels(), 2);
+
+        drop(first);
+        assert_eq!(tracker.channels(), 1);
+        drop(second);
+        assert_eq!(tracker.channels(), 0);
+
+        assert!(!tracker.is_draining());
+        tracker.drain();
+        assert!(tracker.is_draining());
+    }
+}
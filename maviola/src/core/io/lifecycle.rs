@@ -0,0 +1,31 @@
+use std::net::SocketAddr;
+
+use crate::core::io::{ChannelInfo, ConnectionInfo};
+use crate::core::node::ValidationReport;
+
+/// <sup>⛔</sup>
+/// Transport-level lifecycle event of a connection.
+///
+/// Emitted by channels and connection handlers, and delivered to nodes as the corresponding
+/// node events.
+#[derive(Clone, Debug)]
+pub(crate) enum ConnectionEvent {
+    /// Channel was opened within a connection.
+    ChannelOpened(ChannelInfo),
+    /// Channel was closed.
+    ChannelClosed(ChannelInfo),
+    /// Connection was closed due to failure of the underlying transport.
+    Lost,
+    /// Connection was restored after failure.
+    Restored,
+    /// Channel dropped bytes, that can't be parsed as a frame.
+    Malformed(ChannelInfo, ValidationReport),
+    /// Connection was added to a running network.
+    ConnectionAdded(ConnectionInfo),
+    /// Connection was removed from a running network.
+    ConnectionRemoved(ConnectionInfo),
+    /// Connection dropped data from a sender, that is not allowed.
+    SenderRejected(ConnectionInfo, SocketAddr),
+    /// Connection became the active link of a failover group within a network.
+    LinkActivated(ConnectionInfo),
+}
@@ -7,10 +7,23 @@
 //!
 //! The following transports are currently available:
 //!
-//! * TCP: [`TcpServer`] / [`TcpClient`]
+//! * TCP: [`TcpServer`] / [`TcpClient`] (can be encrypted by TLS with `tls` feature)
 //! * UDP: [`UdpServer`] / [`UdpClient`]
 //! * File: [`FileWriter`] / [`FileReader`]
+//! * Telemetry log: [`TlogWriter`] (records frames of another connection) / [`TlogReader`]
+//!   (replays recorded frames with the original timing)
 //! * Unix socket: [`SockServer`] / [`SockClient`] (only on Unix-like systems such as Linux or OS X)
+//! * Shared memory: [`IpcServer`] / [`IpcClient`] (only for synchronous API on Unix-like systems,
+//!   requires `ipc` feature)
+//! * Serial port: [`SerialPort`] (only for synchronous API, requires `serial` feature)
+//! * Bluetooth: [`BluetoothClient`] (only on Linux, requires `bluetooth` feature)
+//! * ZeroMQ: [`ZmqPub`] / [`ZmqSub`] (requires `zmq` feature)
+//! * MQTT: [`MqttBridge`] (requires `mqtt` feature)
+//! * NATS: [`NatsBridge`] (requires `nats` feature)
+//! * Redis Pub/Sub: [`RedisBridge`] (requires `redis` feature)
+//!
+//! TCP, UDP, and serial connections can be also configured from endpoint URLs, like
+//! `tcpout:127.0.0.1:5760` or `serial:/dev/ttyACM0:115200`, see [`ConnectionSpec`].
 //!
 //! ## API modes
 //!
@@ -25,21 +38,158 @@
 //! Low-level I/O primitives are re-exported from [Mavio](https://crates.io/crates/mavio), a
 //! low-level MAVLink library which serves as a basis for Maviola.
 
+// Links to transports behind disabled features lead to `docs.rs`.
+#![cfg_attr(
+    all(feature = "ipc", unix),
+    doc = "",
+    doc = "[`IpcServer`]: crate::core::io::IpcServer",
+    doc = "[`IpcClient`]: crate::core::io::IpcClient"
+)]
+#![cfg_attr(
+    not(all(feature = "ipc", unix)),
+    doc = "",
+    doc = "[`IpcServer`]: https://docs.rs/maviola/latest/maviola/core/io/struct.IpcServer.html",
+    doc = "[`IpcClient`]: https://docs.rs/maviola/latest/maviola/core/io/struct.IpcClient.html"
+)]
+#![cfg_attr(
+    feature = "mqtt",
+    doc = "",
+    doc = "[`MqttBridge`]: crate::core::io::MqttBridge"
+)]
+#![cfg_attr(
+    not(feature = "mqtt"),
+    doc = "",
+    doc = "[`MqttBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.MqttBridge.html"
+)]
+#![cfg_attr(
+    all(feature = "bluetooth", target_os = "linux"),
+    doc = "",
+    doc = "[`BluetoothClient`]: crate::core::io::BluetoothClient"
+)]
+#![cfg_attr(
+    not(all(feature = "bluetooth", target_os = "linux")),
+    doc = "",
+    doc = "[`BluetoothClient`]: https://docs.rs/maviola/latest/maviola/core/io/struct.BluetoothClient.html"
+)]
+#![cfg_attr(
+    feature = "serial",
+    doc = "",
+    doc = "[`SerialPort`]: crate::core::io::SerialPort"
+)]
+#![cfg_attr(
+    not(feature = "serial"),
+    doc = "",
+    doc = "[`SerialPort`]: https://docs.rs/maviola/latest/maviola/core/io/struct.SerialPort.html"
+)]
+#![cfg_attr(
+    feature = "zmq",
+    doc = "",
+    doc = "[`ZmqPub`]: crate::core::io::ZmqPub",
+    doc = "[`ZmqSub`]: crate::core::io::ZmqSub"
+)]
+#![cfg_attr(
+    not(feature = "zmq"),
+    doc = "",
+    doc = "[`ZmqPub`]: https://docs.rs/maviola/latest/maviola/core/io/struct.ZmqPub.html",
+    doc = "[`ZmqSub`]: https://docs.rs/maviola/latest/maviola/core/io/struct.ZmqSub.html"
+)]
+#![cfg_attr(
+    feature = "nats",
+    doc = "",
+    doc = "[`NatsBridge`]: crate::core::io::NatsBridge"
+)]
+#![cfg_attr(
+    not(feature = "nats"),
+    doc = "",
+    doc = "[`NatsBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.NatsBridge.html"
+)]
+#![cfg_attr(
+    feature = "redis",
+    doc = "",
+    doc = "[`RedisBridge`]: crate::core::io::RedisBridge"
+)]
+#![cfg_attr(
+    not(feature = "redis"),
+    doc = "",
+    doc = "[`RedisBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.RedisBridge.html"
+)]
+
 mod connection_conf;
 mod connection_info;
 mod core;
+mod failover;
+mod flush;
+mod lifecycle;
+mod pause;
+mod priority;
+mod resolver;
 mod retry;
 mod routing;
+mod spool;
+mod tap;
 mod transport;
 
-pub use transport::{FileReader, FileWriter, TcpClient, TcpServer, UdpClient, UdpServer};
+#[cfg(feature = "nats")]
+pub use transport::NatsBridge;
+#[cfg(feature = "redis")]
+pub use transport::RedisBridge;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+pub use transport::{BluetoothAddr, BluetoothClient};
+pub use transport::{
+    CidrRange, ConnectionSpec, FileReader, FileWriter, HandshakeOutcome, TcpClient, TcpServer,
+    TlogReader, TlogWriter, UdpClient, UdpServer,
+};
+#[cfg(feature = "serial")]
+pub use transport::{HalfDuplex, SerialPort};
+#[cfg(all(feature = "ipc", unix))]
+pub use transport::{IpcClient, IpcServer};
+#[cfg(feature = "mqtt")]
+pub use transport::{MqttBridge, MqttFormat};
 #[cfg(unix)]
 pub use transport::{SockClient, SockServer};
+#[cfg(feature = "tls")]
+pub use transport::{TlsAcceptor, TlsConnector};
+#[cfg(feature = "zmq")]
+pub use transport::{ZmqPub, ZmqSub};
 
 pub use connection_conf::ConnectionConf;
 pub use connection_info::{ChannelDetails, ChannelInfo, ConnectionDetails, ConnectionInfo};
+pub use priority::{EgressPriorities, FramePriority};
+pub use resolver::{Resolver, SystemResolver};
 pub use retry::RetryStrategy;
-pub use routing::{BroadcastScope, ChannelId, ConnectionId};
+pub use routing::{BroadcastScope, ChannelId, ConnectionId, FrameMeta};
+pub use spool::{FileSpool, OutboundQueue, SpoolStorage, SpooledFrame};
+pub use tap::ChannelTap;
+
+pub(crate) use failover::AddressFailover;
+#[cfg(feature = "sync")]
+pub(crate) use flush::ChannelGuard;
+pub(crate) use flush::{FlushProgress, FlushTracker};
+pub(crate) use lifecycle::ConnectionEvent;
+pub(crate) use pause::PauseSwitch;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use priority::EgressQueue;
+pub(crate) use resolver::HostResolution;
+pub(crate) use tap::SharedTap;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use tap::{Captured, Tapped};
+#[cfg(feature = "mqtt")]
+pub(crate) use transport::mqtt_wire;
+#[cfg(feature = "nats")]
+pub(crate) use transport::nats_wire;
+#[cfg(feature = "redis")]
+pub(crate) use transport::redis_wire;
+#[cfg(all(
+    feature = "bluetooth",
+    target_os = "linux",
+    any(feature = "sync", feature = "async")
+))]
+pub(crate) use transport::rfcomm;
+#[cfg(feature = "zmq")]
+pub(crate) use transport::zmtp;
+#[cfg(any(feature = "zmq", feature = "mqtt", feature = "nats", feature = "redis"))]
+pub(crate) use transport::FrameSplitter;
+pub(crate) use transport::{tlog_timestamp, ServerHandshake, TlogPlayback};
 
 #[cfg(feature = "unstable")]
 pub use routing::{IncomingFrame, OutgoingFrame};
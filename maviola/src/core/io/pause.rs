@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// <sup>⛔</sup>
+/// Pauses and resumes channels of a connection.
+///
+/// Switch is shared between all clones of [`ConnectionInfo`](super::ConnectionInfo), so
+/// connections restored after failure keep their pause state.
+///
+/// While switch is paused, channels stop reading from their transports and stop writing outgoing
+/// frames, which are kept in the outgoing channel of the connection until it is resumed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PauseSwitch {
+    paused: Arc<AtomicBool>,
+}
+
+impl PauseSwitch {
+    /// Pauses channels.
+    ///
+    /// Returns `false`, if channels were already paused.
+    pub(crate) fn pause(&self) -> bool {
+        !self.paused.swap(true, Ordering::AcqRel)
+    }
+
+    /// Resumes channels.
+    ///
+    /// Returns `false`, if channels weren't paused.
+    pub(crate) fn resume(&self) -> bool {
+        self.paused.swap(false, Ordering::AcqRel)
+    }
+
+    /// Returns `true`, if channels are paused.
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod pause_tests {
+    use super::*;
+
+    #[test]
+    fn pause_is_shared_between_clones() {
+        let switch = PauseSwitch::default();
+        let other = switch.clone();
+        assert!(!other.is_paused());
+
+        assert!(switch.pause());
+        assert!(!switch.pause());
+        assert!(other.is_paused());
+
+        assert!(other.resume());
+        assert!(!other.resume());
+        assert!(!switch.is_paused());
+    }
+}
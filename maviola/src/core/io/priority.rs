@@ -0,0 +1,236 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::consts::DEFAULT_EGRESS_QUEUE_CAPACITY;
+use crate::core::io::{ChannelId, OutgoingFrame};
+use crate::error::{TryRecvError, TryRecvResult};
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+/// Messages, that are sent with [`FramePriority::Command`] by default.
+const COMMAND_MESSAGES: &[MessageId] = &[
+    0,  // HEARTBEAT
+    11, // SET_MODE
+    20, // PARAM_REQUEST_READ
+    23, // PARAM_SET
+    69, // MANUAL_CONTROL
+    70, // RC_CHANNELS_OVERRIDE
+    75, // COMMAND_INT
+    76, // COMMAND_LONG
+    77, // COMMAND_ACK
+    80, // COMMAND_CANCEL
+    82, // SET_ATTITUDE_TARGET
+    84, // SET_POSITION_TARGET_LOCAL_NED
+    86, // SET_POSITION_TARGET_GLOBAL_INT
+];
+
+/// Messages, that are sent with [`FramePriority::Bulk`] by default.
+const BULK_MESSAGES: &[MessageId] = &[
+    110, // FILE_TRANSFER_PROTOCOL
+    118, // LOG_ENTRY
+    120, // LOG_DATA
+    126, // SERIAL_CONTROL
+    130, // DATA_TRANSMISSION_HANDSHAKE
+    131, // ENCAPSULATED_DATA
+    266, // LOGGING_DATA
+    267, // LOGGING_DATA_ACKED
+];
+
+/// Priority class of an outgoing frame.
+///
+/// Each channel writes pending frames with higher priority first, frames with the same priority
+/// are written in the order they were sent. This way commands and heartbeats are not stuck behind
+/// a burst of bulk transfers on a slow link.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FramePriority {
+    /// Bulk transfers, like logs and files.
+    Bulk,
+    /// Regular telemetry (default for unknown messages).
+    #[default]
+    Telemetry,
+    /// Commands, control messages, and heartbeats.
+    Command,
+}
+
+/// Table of [`FramePriority`] classes of outgoing messages.
+///
+/// By default, heartbeats, commands, mode changes, parameter changes, and manual control have
+/// [`FramePriority::Command`], log and file transfers have [`FramePriority::Bulk`], and all other
+/// messages have [`FramePriority::Telemetry`]. Default classes can be overridden for particular
+/// messages.
+///
+/// Frames sent by nodes without priority table are classified according to default table.
+///
+/// Set table with
+/// [`NodeBuilder::egress_priorities`](crate::core::node::NodeBuilder::egress_priorities).
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::io::{EgressPriorities, FramePriority};
+/// use maviola::protocol::MessageId;
+///
+/// const GPS_RTCM_DATA: MessageId = 233;
+/// const LOG_DATA: MessageId = 120;
+///
+/// let priorities = EgressPriorities::new()
+///     .with_priority(GPS_RTCM_DATA, FramePriority::Command);
+///
+/// assert_eq!(priorities.priority(GPS_RTCM_DATA), FramePriority::Command);
+/// assert_eq!(priorities.priority(LOG_DATA), FramePriority::Bulk);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EgressPriorities {
+    overrides: HashMap<MessageId, FramePriority>,
+}
+
+/// Priority-aware queue of frames pending to be written to a channel.
+pub(crate) struct EgressQueue<V: MaybeVersioned> {
+    queues: [VecDeque<OutgoingFrame<V>>; 3],
+    len: usize,
+}
+
+impl FramePriority {
+    /// Default priority of messages with specified `message_id`.
+    pub fn of(message_id: MessageId) -> Self {
+        if COMMAND_MESSAGES.contains(&message_id) {
+            FramePriority::Command
+        } else if BULK_MESSAGES.contains(&message_id) {
+            FramePriority::Bulk
+        } else {
+            FramePriority::Telemetry
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            FramePriority::Command => 0,
+            FramePriority::Telemetry => 1,
+            FramePriority::Bulk => 2,
+        }
+    }
+}
+
+impl EgressPriorities {
+    /// Creates a table with default priority classes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides `priority` of messages with specified `message_id`.
+    pub fn with_priority(mut self, message_id: MessageId, priority: FramePriority) -> Self {
+        self.overrides.insert(message_id, priority);
+        self
+    }
+
+    /// Priority of messages with specified `message_id`.
+    pub fn priority(&self, message_id: MessageId) -> FramePriority {
+        self.overrides
+            .get(&message_id)
+            .copied()
+            .unwrap_or_else(|| FramePriority::of(message_id))
+    }
+}
+
+impl<V: MaybeVersioned> EgressQueue<V> {
+    /// Creates an empty queue.
+    pub(crate) fn new() -> Self {
+        Self {
+            queues: Default::default(),
+            len: 0,
+        }
+    }
+
+    /// Returns `true`, if there are no pending frames.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true`, if queue reached [`DEFAULT_EGRESS_QUEUE_CAPACITY`].
+    ///
+    /// Frames, that can't be queued, remain in the outgoing frames channel.
+    pub(crate) fn is_full(&self) -> bool {
+        self.len >= DEFAULT_EGRESS_QUEUE_CAPACITY
+    }
+
+    /// Adds a pending `frame`.
+    pub(crate) fn push(&mut self, frame: OutgoingFrame<V>) {
+        self.queues[frame.priority().index()].push_back(frame);
+        self.len += 1;
+    }
+
+    /// Moves frames addressed to channel `id` from the `try_recv` source into the queue, until
+    /// either queue is full or source has no pending frames.
+    pub(crate) fn fill(
+        &mut self,
+        id: ChannelId,
+        mut try_recv: impl FnMut() -> TryRecvResult<OutgoingFrame<V>>,
+    ) {
+        while !self.is_full() {
+            match try_recv() {
+                Ok(frame) if frame.should_send_to(id) => self.push(frame),
+                Ok(_) | Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+    }
+
+    /// Takes the oldest frame with the highest priority.
+    pub(crate) fn pop(&mut self) -> Option<OutgoingFrame<V>> {
+        let frame = self.queues.iter_mut().find_map(VecDeque::pop_front)?;
+        self.len -= 1;
+        Some(frame)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod priority_tests {
+    use super::*;
+
+    fn frame(message_id: MessageId) -> OutgoingFrame<V2> {
+        OutgoingFrame::new(
+            Frame::builder()
+                .sequence(0)
+                .system_id(1)
+                .component_id(1)
+                .version(V2)
+                .message_id(message_id)
+                .payload(&[0; 9])
+                .crc_extra(0)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn frames_are_ordered_by_priority() {
+        let mut queue = EgressQueue::new();
+        queue.push(frame(120));
+        queue.push(frame(33));
+        queue.push(frame(120));
+        queue.push(frame(76));
+        queue.push(frame(30));
+
+        let order: Vec<MessageId> = std::iter::from_fn(|| queue.pop())
+            .map(|frame| frame.frame().message_id())
+            .collect();
+        assert_eq!(order, vec![76, 33, 30, 120, 120]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn priorities_can_be_overridden() {
+        let priorities = EgressPriorities::new()
+            .with_priority(120, FramePriority::Command)
+            .with_priority(0, FramePriority::Telemetry);
+
+        assert_eq!(priorities.priority(120), FramePriority::Command);
+        assert_eq!(priorities.priority(0), FramePriority::Telemetry);
+        assert_eq!(priorities.priority(76), FramePriority::Command);
+        assert_eq!(priorities.priority(33), FramePriority::Telemetry);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::utils::net::resolve_socket_addr;
+
+use crate::prelude::*;
+
+/// Resolves hosts of client transports into socket addresses.
+///
+/// By default, [`TcpClient`] and [`UdpClient`] resolve their server address once, when
+/// configuration is created. Clients created by [`TcpClient::from_host`] or
+/// [`UdpClient::from_host`] resolve the host each time connection is built, which allows to
+/// reconnect to servers behind dynamic DNS. Implement this trait to replace system resolver, for
+/// example, with DNS-over-HTTPS or a service registry, and pass it to `from_host_with_resolver`
+/// constructor of a client.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::net::SocketAddr;
+/// use maviola::core::io::Resolver;
+/// use maviola::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct StaticResolver(SocketAddr);
+///
+/// impl Resolver for StaticResolver {
+///     fn resolve(&self, _: &str) -> Result<SocketAddr> {
+///         Ok(self.0)
+///     }
+/// }
+///
+/// let client = TcpClient::from_host_with_resolver(
+///     "relay.example.com:5600",
+///     StaticResolver("10.0.0.1:5600".parse().unwrap()),
+/// ).unwrap();
+/// ```
+pub trait Resolver: Debug + Send + Sync + 'static {
+    /// Resolves `host` into a socket address.
+    ///
+    /// Host is passed as it was provided to a client configuration, for example,
+    /// `relay.example.com:5600`.
+    fn resolve(&self, host: &str) -> Result<SocketAddr>;
+}
+
+/// Resolver that uses system DNS, prefers IPv4 addresses if available.
+///
+/// This is the default [`Resolver`] for client transports.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve(&self, host: &str) -> Result<SocketAddr> {
+        resolve_socket_addr(host)
+    }
+}
+
+/// <sup>⛔</sup>
+/// Host of a client transport with a resolver used to obtain its current address.
+#[derive(Clone, Debug)]
+pub(crate) struct HostResolution {
+    host: String,
+    resolver: Arc<dyn Resolver>,
+    refresh_interval: Option<Duration>,
+}
+
+impl HostResolution {
+    /// Creates resolution of a `host` by a `resolver`.
+    pub(crate) fn new(host: String, resolver: impl Resolver) -> Self {
+        Self {
+            host,
+            resolver: Arc::new(resolver),
+            refresh_interval: None,
+        }
+    }
+
+    /// Host, that is resolved.
+    pub(crate) fn host(&self) -> &str {
+        self.host.as_str()
+    }
+
+    /// Interval between checks of host address for established connections, if enabled.
+    pub(crate) fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+
+    pub(crate) fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = Some(interval);
+    }
+
+    /// Resolves the current address of the host.
+    pub(crate) fn resolve(&self) -> Result<SocketAddr> {
+        self.resolver.resolve(self.host.as_str())
+    }
+}
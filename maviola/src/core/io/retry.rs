@@ -5,6 +5,7 @@ use std::time::Duration;
 /// When an entity goes down and can be repaired, then it may be rebuilt/restored according to this
 /// strategy.
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RetryStrategy {
     /// Never restore (default value).
     #[default]
@@ -12,6 +13,7 @@ pub enum RetryStrategy {
     /// Always retry to restore with a specified interval.
     Always(
         /// Interval between attempts.
+        #[cfg_attr(feature = "serde", serde(with = "crate::core::utils::duration_secs"))]
         Duration,
     ),
     /// Perform several restore attempts with a specified interval.
@@ -19,6 +21,7 @@ pub enum RetryStrategy {
         /// Number of attempts.
         usize,
         /// Interval between attempts.
+        #[cfg_attr(feature = "serde", serde(with = "crate::core::utils::duration_secs"))]
         Duration,
     ),
 }
@@ -1,9 +1,13 @@
-use crate::core::io::ChannelInfo;
+use crate::core::io::flush::{FlushTicket, FlushTracker};
+use crate::core::io::{ChannelInfo, EgressPriorities, FramePriority};
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::core::node::{LatencyStats, PeerLocations, TrafficStats};
 use crate::core::utils::UniqueId;
-use crate::protocol::{Frame, MaybeVersioned};
+use crate::protocol::{Frame, MaybeVersioned, SystemId};
 
 /// Connection `ID`.
 ///
@@ -27,17 +31,44 @@ pub struct ChannelId {
 }
 
 /// Incoming MAVLink frame.
+///
+/// The underlying [`Frame`] is shared between clones, so broadcasting incoming frames to several
+/// subscribers does not copy frame payload.
 #[derive(Clone, Debug)]
 pub struct IncomingFrame<V: MaybeVersioned> {
-    frame: Frame<V>,
+    frame: Arc<Frame<V>>,
+    meta: FrameMeta,
+}
+
+/// Metadata of an incoming frame.
+///
+/// Captured when frame is read from a transport, and preserved when frame is routed between
+/// nodes. Therefore, [`FrameMeta::received_at`] does not depend on when the frame is consumed.
+#[derive(Clone, Debug)]
+pub struct FrameMeta {
     channel: ChannelInfo,
+    received_at: Instant,
+    timestamp: SystemTime,
 }
 
 /// Outgoing MAVLink frame.
+///
+/// The underlying [`Frame`] is shared between clones, so broadcasting outgoing frames to several
+/// channels does not copy frame payload. The frame is copied only by stages that change it, such
+/// as version pinning, system `ID` translation or processing of frames sent from callbacks.
 #[derive(Clone, Debug)]
 pub struct OutgoingFrame<V: MaybeVersioned> {
     frame: Arc<Frame<V>>,
     scope: BroadcastScope,
+    submitted_at: Instant,
+    received_at: Option<Instant>,
+    latency: Option<LatencyStats>,
+    stats: Option<TrafficStats>,
+    flush: Option<FlushTicket>,
+    peer_channels: Option<Arc<HashSet<ChannelId>>>,
+    node_heartbeat: bool,
+    targeted: bool,
+    priority: FramePriority,
 }
 
 /// Defines, how frame should be broadcast.
@@ -56,6 +87,17 @@ pub enum BroadcastScope {
     ExactConnection(ConnectionId),
     /// Broadcast to all connections except the specified one.
     ExceptConnection(ConnectionId),
+    /// Broadcast only to channels, where system with the specified `ID` was seen.
+    ///
+    /// Channels are resolved against peers known to the node at the moment of sending. Only
+    /// channels, that received heartbeats from the system, are considered. If system is unknown,
+    /// then frame won't be sent at all.
+    System(SystemId),
+    /// Broadcast to all channels except those, where system with the specified `ID` was seen.
+    ///
+    /// Channels are resolved in the same way as for [`BroadcastScope::System`]. Channels shared by
+    /// the excluded system with other peers are skipped as well.
+    ExceptSystem(SystemId),
 }
 
 impl ConnectionId {
@@ -114,29 +156,180 @@ impl From<ConnectionId> for ChannelId {
 impl<V: MaybeVersioned> IncomingFrame<V> {
     /// Creates an incoming from MAVLink [`Frame`] and [`ChannelId`].
     pub fn new(frame: Frame<V>, channel: ChannelInfo) -> Self {
-        Self { frame, channel }
+        Self::shared(Arc::new(frame), channel)
+    }
+
+    /// Creates an incoming frame from a shared MAVLink [`Frame`] and [`ChannelId`].
+    ///
+    /// Unlike [`IncomingFrame::new`], the frame is not moved to a new allocation.
+    pub fn shared(frame: Arc<Frame<V>>, channel: ChannelInfo) -> Self {
+        Self {
+            frame,
+            meta: FrameMeta::new(channel),
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates an incoming frame with existing metadata.
+    ///
+    /// Used when frames are re-routed between nodes, to preserve the original receive time.
+    pub(crate) fn with_meta(frame: Frame<V>, meta: FrameMeta) -> Self {
+        Self {
+            frame: Arc::new(frame),
+            meta,
+        }
+    }
+
+    /// Reference to the underlying MAVLink [`Frame`].
+    #[inline]
+    pub fn frame(&self) -> &Frame<V> {
+        self.frame.as_ref()
+    }
+
+    /// Shared reference to the underlying MAVLink [`Frame`].
+    #[inline]
+    pub fn shared_frame(&self) -> &Arc<Frame<V>> {
+        &self.frame
+    }
+
+    /// Instant when this frame was received.
+    #[inline]
+    pub fn received_at(&self) -> Instant {
+        self.meta.received_at
+    }
+
+    /// Frame metadata.
+    #[inline]
+    pub fn meta(&self) -> &FrameMeta {
+        &self.meta
+    }
+}
+
+impl<V: MaybeVersioned> From<IncomingFrame<V>> for (Frame<V>, FrameMeta) {
+    /// Takes the underlying frame out of an incoming frame.
+    ///
+    /// The frame is cloned only if it is still shared with other subscribers.
+    fn from(value: IncomingFrame<V>) -> Self {
+        let frame = Arc::try_unwrap(value.frame).unwrap_or_else(|frame| frame.as_ref().clone());
+        (frame, value.meta)
     }
 }
 
 impl<V: MaybeVersioned> From<IncomingFrame<V>> for (Frame<V>, ChannelInfo) {
+    /// Takes the underlying frame out of an incoming frame.
+    ///
+    /// The frame is cloned only if it is still shared with other subscribers.
     fn from(value: IncomingFrame<V>) -> Self {
-        (value.frame, value.channel)
+        let (frame, meta): (Frame<V>, FrameMeta) = value.into();
+        (frame, meta.channel)
+    }
+}
+
+impl FrameMeta {
+    /// Creates metadata for a frame received right now from the specified channel.
+    pub fn new(channel: ChannelInfo) -> Self {
+        Self {
+            channel,
+            received_at: Instant::now(),
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Channel from which the frame was received.
+    #[inline]
+    pub fn channel(&self) -> &ChannelInfo {
+        &self.channel
+    }
+
+    /// `ID` of a connection from which the frame was received.
+    #[inline]
+    pub fn connection_id(&self) -> ConnectionId {
+        self.channel.connection_id()
+    }
+
+    /// Monotonic instant when the frame was received.
+    ///
+    /// Use it to measure latency and intervals between frames.
+    #[inline]
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// System time when the frame was received.
+    ///
+    /// Use it for logging. Unlike [`FrameMeta::received_at`], it is affected by system clock
+    /// adjustments.
+    #[inline]
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+impl From<FrameMeta> for ChannelInfo {
+    fn from(value: FrameMeta) -> Self {
+        value.channel
     }
 }
 
 impl<V: MaybeVersioned> OutgoingFrame<V> {
     /// Creates an outgoing frame from MAVLink [`Frame`].
     pub fn new(frame: Frame<V>) -> Self {
-        Self {
-            frame: Arc::new(frame),
-            scope: BroadcastScope::All,
-        }
+        Self::scoped(frame, BroadcastScope::All)
+    }
+
+    /// Creates an outgoing frame from a shared MAVLink [`Frame`].
+    ///
+    /// Unlike [`OutgoingFrame::new`], the frame is not moved to a new allocation.
+    pub fn shared(frame: Arc<Frame<V>>) -> Self {
+        Self::shared_scoped(frame, BroadcastScope::All)
     }
 
     pub(crate) fn scoped(frame: Frame<V>, scope: BroadcastScope) -> Self {
+        Self::shared_scoped(Arc::new(frame), scope)
+    }
+
+    fn shared_scoped(frame: Arc<Frame<V>>, scope: BroadcastScope) -> Self {
+        let priority = FramePriority::of(frame.message_id());
         Self {
-            frame: Arc::new(frame),
+            frame,
             scope,
+            submitted_at: Instant::now(),
+            received_at: None,
+            latency: None,
+            stats: None,
+            flush: None,
+            peer_channels: None,
+            node_heartbeat: false,
+            targeted: false,
+            priority,
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates an outgoing frame for an automatic heartbeat emitted by a node or its component.
+    pub(crate) fn node_heartbeat(frame: Frame<V>) -> Self {
+        Self {
+            node_heartbeat: true,
+            ..Self::new(frame)
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates an outgoing frame, that should be sent only to connections, where its target was
+    /// seen.
+    pub(crate) fn targeted(frame: Frame<V>) -> Self {
+        Self {
+            targeted: true,
+            ..Self::new(frame)
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates an outgoing frame in response to a frame, that was received at `received_at`.
+    pub(crate) fn forwarded(frame: Frame<V>, scope: BroadcastScope, received_at: Instant) -> Self {
+        Self {
+            received_at: Some(received_at),
+            ..Self::scoped(frame, scope)
         }
     }
 
@@ -146,6 +339,57 @@ impl<V: MaybeVersioned> OutgoingFrame<V> {
         self.frame.as_ref()
     }
 
+    /// Shared reference to the underlying MAVLink [`Frame`].
+    #[inline]
+    pub fn shared_frame(&self) -> &Arc<Frame<V>> {
+        &self.frame
+    }
+
+    /// <sup>⛔</sup>
+    /// Replaces the underlying frame keeping broadcast scope, timing and statistics.
+    pub(crate) fn replace_frame(&mut self, frame: Frame<V>) {
+        self.frame = Arc::new(frame);
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if frame is an automatic heartbeat emitted by a node or its component.
+    ///
+    /// Frames created with [`OutgoingFrame::new`] are never considered automatic heartbeats,
+    /// even if they contain `HEARTBEAT` messages.
+    #[inline]
+    pub(crate) fn is_node_heartbeat(&self) -> bool {
+        self.node_heartbeat
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if frame was forwarded in response to a received frame.
+    #[inline]
+    pub(crate) fn is_forwarded(&self) -> bool {
+        self.received_at.is_some()
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if frame should be sent only to connections, where its target was seen.
+    #[inline]
+    pub(crate) fn is_targeted(&self) -> bool {
+        self.targeted
+    }
+
+    /// Priority class of the frame.
+    ///
+    /// By default, priority is defined by [`FramePriority::of`] the message `ID`.
+    #[inline]
+    pub fn priority(&self) -> FramePriority {
+        self.priority
+    }
+
+    /// <sup>⛔</sup>
+    /// Sets priority class according to the `priorities` table.
+    #[inline]
+    pub(crate) fn prioritize(&mut self, priorities: &EgressPriorities) {
+        self.priority = priorities.priority(self.frame.message_id());
+    }
+
     /// Broadcast scope.
     #[inline]
     pub fn scope(&self) -> BroadcastScope {
@@ -158,6 +402,96 @@ impl<V: MaybeVersioned> OutgoingFrame<V> {
         self.scope = scope;
     }
 
+    /// Instant when this frame was submitted for sending.
+    #[inline]
+    pub fn submitted_at(&self) -> Instant {
+        self.submitted_at
+    }
+
+    /// Instant when the original frame was received, if this frame was sent from a callback.
+    ///
+    /// Frames routed between connections by callbacks keep the receive time of the original frame,
+    /// which allows to measure time spent by a frame inside a router.
+    #[inline]
+    pub fn received_at(&self) -> Option<Instant> {
+        self.received_at
+    }
+
+    /// Time elapsed since this frame entered the node.
+    ///
+    /// For forwarded frames, the age is counted from the moment when the original frame was
+    /// [received](Self::received_at). Otherwise, from the moment of [submission](Self::submitted_at).
+    pub fn age(&self) -> Duration {
+        self.received_at.unwrap_or(self.submitted_at).elapsed()
+    }
+
+    /// <sup>⛔</sup>
+    /// Sets latency statistics, that will be updated once frame is written.
+    ///
+    /// Keeps already assigned statistics untouched, so frames routed through several nodes are
+    /// accounted by the node which submitted them first.
+    pub(crate) fn track_latency(&mut self, latency: Option<&LatencyStats>) {
+        if self.latency.is_none() {
+            self.latency = latency.cloned();
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Sets traffic statistics, that will be updated once frame is written.
+    ///
+    /// Similar to [`Self::track_latency`], keeps already assigned statistics untouched.
+    pub(crate) fn track_stats(&mut self, stats: &TrafficStats) {
+        if self.stats.is_none() {
+            self.stats = Some(stats.clone());
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Assigns frame a position in the outgoing sequence of a connection, which allows to track,
+    /// whether it was flushed.
+    ///
+    /// Similar to [`Self::track_latency`], keeps already assigned position untouched, so frames
+    /// routed through several connections are accounted by the connection which received them
+    /// first.
+    pub(crate) fn track_flush(&mut self, tracker: &FlushTracker) {
+        if self.flush.is_none() {
+            self.flush = Some(tracker.ticket());
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Resolves [`BroadcastScope::System`] and [`BroadcastScope::ExceptSystem`] scopes into
+    /// channels, where peers of the corresponding system were seen.
+    ///
+    /// Similar to [`Self::track_latency`], keeps already resolved channels untouched.
+    pub(crate) fn resolve_peers(&mut self, locations: &PeerLocations) {
+        match self.scope {
+            BroadcastScope::System(system_id) | BroadcastScope::ExceptSystem(system_id)
+                if self.peer_channels.is_none() =>
+            {
+                self.peer_channels = Some(Arc::new(locations.channels_of(system_id)));
+            }
+            _ => {}
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Records latency of this frame since its submission and its traffic for a connection with
+    /// specified `connection_id`.
+    ///
+    /// Should be called once the frame is written to the wire.
+    pub(crate) fn record_written(&self, connection_id: ConnectionId) {
+        if let Some(latency) = &self.latency {
+            latency.record_outgoing(self.frame.message_id(), self.submitted_at.elapsed());
+        }
+        if let Some(stats) = &self.stats {
+            stats.record_outgoing(self.frame.as_ref(), connection_id);
+        }
+        if let Some(flush) = &self.flush {
+            flush.record_written();
+        }
+    }
+
     /// Matches frame against a particular connection and changes broadcast scope if necessary.
     ///
     /// The rules are the following:
@@ -189,6 +523,16 @@ impl<V: MaybeVersioned> OutgoingFrame<V> {
             }
             BroadcastScope::ExactConnection(conn_id) => conn_id.contains(channel_id),
             BroadcastScope::ExceptConnection(conn_id) => !conn_id.contains(channel_id),
+            BroadcastScope::System(_) => self.has_peers_at(channel_id),
+            BroadcastScope::ExceptSystem(_) => !self.has_peers_at(channel_id),
+        }
+    }
+
+    /// Returns `true`, if peers of a resolved system scope were seen at the specified channel.
+    fn has_peers_at(&self, channel_id: ChannelId) -> bool {
+        match &self.peer_channels {
+            Some(channels) => channels.contains(&channel_id),
+            None => false,
         }
     }
 }
@@ -0,0 +1,572 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mavio::io::{Receiver, Sender};
+
+use crate::core::consts::DEFAULT_OUTBOUND_QUEUE_CAPACITY;
+use crate::core::io::{tlog_timestamp, OutgoingFrame};
+use crate::error::{SendError, SendResult};
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+/// Queue of outgoing frames, that were sent while connection was down.
+///
+/// Intermittently connected links (for example, LTE on a drone) lose frames sent while transport
+/// is being restored. Once outbound queue is set for a node with a
+/// [retry strategy](crate::core::io::RetryStrategy), such frames are spooled and transmitted in
+/// the original order after reconnection. Node heartbeats are never spooled, since they are
+/// emitted continuously anyway.
+///
+/// Queue keeps frames in memory. When queue is full, frames with the lowest priority are evicted
+/// first, the oldest frame is evicted among frames with the same priority. New frames with a
+/// priority lower than all spooled frames are dropped instead. Frames, that have been spooled for
+/// longer than [`OutboundQueue::max_age`], are dropped before transmission.
+///
+/// Optionally, queue can be backed by a [`SpoolStorage`], such as [`FileSpool`]. Spooled frames
+/// are persisted to storage and loaded, once node is created, so frames survive restarts of the
+/// application. Frames are removed from storage once transmitted, frames transmitted right before
+/// a crash may be sent again.
+///
+/// Clones of the queue share spooled frames and counters, so you can keep a clone to inspect them
+/// while node is running.
+///
+/// Set queue with [`NodeBuilder::outbound_queue`](crate::core::node::NodeBuilder::outbound_queue).
+///
+/// # Usage
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use maviola::core::io::{FileSpool, OutboundQueue};
+/// use maviola::protocol::MessageId;
+///
+/// const STATUSTEXT: MessageId = 253;
+///
+/// let queue = OutboundQueue::new()
+///     .with_capacity(512)
+///     .with_max_age(Duration::from_secs(60))
+///     .with_priority(STATUSTEXT, 10)
+///     .with_storage(FileSpool::new("/tmp/maviola-outbound.tlog"));
+///
+/// assert_eq!(queue.capacity(), 512);
+/// assert_eq!(queue.priority(STATUSTEXT), 10);
+/// assert!(queue.is_empty());
+/// ```
+#[derive(Clone)]
+pub struct OutboundQueue {
+    capacity: usize,
+    max_age: Option<Duration>,
+    priorities: HashMap<MessageId, u8>,
+    inner: Arc<Mutex<QueueInner>>,
+}
+
+/// Frame stored in an [`OutboundQueue`].
+#[derive(Clone, Debug)]
+pub struct SpooledFrame {
+    frame: Frame<Versionless>,
+    spooled_at: SystemTime,
+}
+
+/// <sup>🔌</sup>
+/// Persistent storage of an [`OutboundQueue`].
+///
+/// Storage is used only by the queue it is attached to. Errors are logged and do not affect
+/// in-memory queue.
+pub trait SpoolStorage: Send {
+    /// Loads frames persisted by the previous runs in the original order.
+    fn load(&mut self) -> Result<Vec<SpooledFrame>>;
+
+    /// Persists a frame appended to the end of the queue.
+    fn append(&mut self, frame: &SpooledFrame) -> Result<()>;
+
+    /// Replaces persisted frames with the current contents of the queue.
+    fn rewrite(&mut self, frames: &[SpooledFrame]) -> Result<()>;
+}
+
+/// File storage of an [`OutboundQueue`].
+///
+/// Frames are stored in [`.tlog`](crate::core::io::TlogWriter) format, so spool file can be
+/// inspected by telemetry log tools. Missing file is treated as an empty queue.
+#[derive(Clone, Debug)]
+pub struct FileSpool {
+    path: PathBuf,
+}
+
+struct QueueInner {
+    frames: VecDeque<SpooledFrame>,
+    storage: Option<Box<dyn SpoolStorage>>,
+    is_loaded: bool,
+    evicted: u64,
+    expired: u64,
+}
+
+impl OutboundQueue {
+    /// Creates a memory-only queue with [`DEFAULT_OUTBOUND_QUEUE_CAPACITY`] and without age limit.
+    ///
+    /// All messages have priority `0`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets maximum number of spooled frames.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets maximum time a frame can be spooled before it is dropped.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets eviction `priority` for messages with specified `message_id`.
+    ///
+    /// Frames with higher priority are evicted last.
+    pub fn with_priority(mut self, message_id: MessageId, priority: u8) -> Self {
+        self.priorities.insert(message_id, priority);
+        self
+    }
+
+    /// Sets persistent `storage` of spooled frames.
+    pub fn with_storage(self, storage: impl SpoolStorage + 'static) -> Self {
+        self.lock().storage = Some(Box::new(storage));
+        self
+    }
+
+    /// Maximum number of spooled frames.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Maximum time a frame can be spooled, if any.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Eviction priority of messages with specified `message_id`.
+    pub fn priority(&self, message_id: MessageId) -> u8 {
+        self.priorities
+            .get(&message_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Number of spooled frames.
+    pub fn len(&self) -> usize {
+        self.lock().frames.len()
+    }
+
+    /// Returns `true`, if there are no spooled frames.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of frames evicted or dropped, because queue was full.
+    pub fn evicted(&self) -> u64 {
+        self.lock().evicted
+    }
+
+    /// Number of frames dropped, because they were spooled longer than [`OutboundQueue::max_age`].
+    pub fn expired(&self) -> u64 {
+        self.lock().expired
+    }
+
+    /// <sup>⛔</sup>
+    /// Loads frames from storage, unless they were already loaded.
+    pub(crate) fn load(&self) {
+        let mut inner = self.lock();
+        if inner.is_loaded {
+            return;
+        }
+        inner.is_loaded = true;
+
+        let loaded = match inner.storage.as_mut().map(|storage| storage.load()) {
+            Some(Ok(loaded)) => loaded,
+            Some(Err(err)) => {
+                log::warn!("can't load spooled frames: {err:?}");
+                return;
+            }
+            None => return,
+        };
+        if !loaded.is_empty() {
+            log::debug!("{} spooled frames loaded", loaded.len());
+        }
+
+        for frame in loaded {
+            self.insert(&mut inner, frame);
+        }
+        inner.persist();
+    }
+
+    /// <sup>⛔</sup>
+    /// Spools outgoing `frame`, unless it is a node heartbeat.
+    pub(crate) fn push<V: MaybeVersioned>(&self, frame: &OutgoingFrame<V>) {
+        if frame.is_node_heartbeat() {
+            return;
+        }
+        let mut inner = self.lock();
+        let spooled = SpooledFrame::new(frame.frame().to_versionless(), SystemTime::now());
+
+        if self.insert(&mut inner, spooled.clone()) {
+            if let Some(storage) = inner.storage.as_mut() {
+                if let Err(err) = storage.append(&spooled) {
+                    log::warn!("can't persist spooled frame: {err:?}");
+                }
+            }
+        } else {
+            inner.persist();
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Transmits spooled frames in the original order with `send` until queue is empty or `send`
+    /// fails.
+    ///
+    /// Returns the number of transmitted frames. The frame, that failed to be sent, is returned to
+    /// the head of the queue.
+    pub(crate) fn drain<V: MaybeVersioned>(
+        &self,
+        mut send: impl FnMut(OutgoingFrame<V>) -> SendResult<OutgoingFrame<V>>,
+    ) -> usize {
+        let mut sent = 0;
+
+        while let Some((frame, spooled_at)) = self.pop::<V>() {
+            if let Err(SendError(frame)) = send(OutgoingFrame::new(frame)) {
+                self.unpop(frame.frame(), spooled_at);
+                break;
+            }
+            sent += 1;
+        }
+        if sent > 0 {
+            self.lock().persist();
+        }
+
+        sent
+    }
+
+    /// Takes the oldest frame, that is not expired, out of the queue.
+    ///
+    /// Returns frame with the time it was spooled. Frames, that can't be converted to protocol
+    /// version `V`, are dropped.
+    fn pop<V: MaybeVersioned>(&self) -> Option<(Frame<V>, SystemTime)> {
+        let mut inner = self.lock();
+        let now = SystemTime::now();
+
+        while let Some(spooled) = inner.frames.pop_front() {
+            if self.is_expired(&spooled, now) {
+                inner.expired += 1;
+                continue;
+            }
+            match spooled.frame.try_into_versioned::<V>() {
+                Ok(frame) => return Some((frame, spooled.spooled_at)),
+                Err(err) => log::debug!("dropping spooled frame: {err:?}"),
+            }
+        }
+        None
+    }
+
+    /// Returns a `frame`, that was taken by [`OutboundQueue::pop`] but not transmitted, to the head
+    /// of the queue.
+    fn unpop<V: MaybeVersioned>(&self, frame: &Frame<V>, spooled_at: SystemTime) {
+        let spooled = SpooledFrame::new(frame.to_versionless(), spooled_at);
+        self.lock().frames.push_front(spooled);
+    }
+
+    /// Inserts a frame evicting frames with lower priorities, if queue is full.
+    ///
+    /// Returns `true`, if frame was appended without eviction.
+    fn insert(&self, inner: &mut QueueInner, frame: SpooledFrame) -> bool {
+        if self.is_expired(&frame, SystemTime::now()) {
+            inner.expired += 1;
+            return false;
+        }
+        if inner.frames.len() < self.capacity {
+            inner.frames.push_back(frame);
+            return true;
+        }
+
+        inner.evicted += 1;
+        let priority = self.priority(frame.frame.message_id());
+        let lowest = inner
+            .frames
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, spooled)| self.priority(spooled.frame.message_id()))
+            .map(|(index, spooled)| (index, self.priority(spooled.frame.message_id())));
+
+        match lowest {
+            Some((index, lowest)) if lowest <= priority => {
+                inner.frames.remove(index);
+                inner.frames.push_back(frame);
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn is_expired(&self, frame: &SpooledFrame, now: SystemTime) -> bool {
+        match self.max_age {
+            Some(max_age) => now
+                .duration_since(frame.spooled_at)
+                .is_ok_and(|age| age > max_age),
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueInner> {
+        match self.inner.lock() {
+            Ok(inner) => inner,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl QueueInner {
+    fn persist(&mut self) {
+        let frames: Vec<SpooledFrame> = match self.storage {
+            Some(_) => self.frames.iter().cloned().collect(),
+            None => return,
+        };
+        if let Some(storage) = self.storage.as_mut() {
+            if let Err(err) = storage.rewrite(&frames) {
+                log::warn!("can't persist spooled frames: {err:?}");
+            }
+        }
+    }
+}
+
+impl Default for OutboundQueue {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_OUTBOUND_QUEUE_CAPACITY,
+            max_age: None,
+            priorities: Default::default(),
+            inner: Arc::new(Mutex::new(QueueInner {
+                frames: Default::default(),
+                storage: None,
+                is_loaded: false,
+                evicted: 0,
+                expired: 0,
+            })),
+        }
+    }
+}
+
+impl Debug for OutboundQueue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutboundQueue")
+            .field("capacity", &self.capacity)
+            .field("max_age", &self.max_age)
+            .field("priorities", &self.priorities)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpooledFrame {
+    /// Creates a frame spooled at the specified time.
+    pub fn new(frame: Frame<Versionless>, spooled_at: SystemTime) -> Self {
+        Self { frame, spooled_at }
+    }
+
+    /// Spooled frame.
+    pub fn frame(&self) -> &Frame<Versionless> {
+        &self.frame
+    }
+
+    /// Time, when frame was spooled.
+    pub fn spooled_at(&self) -> SystemTime {
+        self.spooled_at
+    }
+}
+
+impl FileSpool {
+    /// Creates file storage at the specified `path`.
+    ///
+    /// File is created once the first frame is spooled.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path to the spool file.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn write(writer: &mut impl Write, frame: &SpooledFrame) -> Result<()> {
+        writer.write_all(&tlog_timestamp(frame.spooled_at).to_be_bytes())?;
+        Sender::new(writer).send(&frame.frame)?;
+        Ok(())
+    }
+}
+
+impl SpoolStorage for FileSpool {
+    fn load(&mut self) -> Result<Vec<SpooledFrame>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut frames = Vec::new();
+        let mut timestamp = [0u8; 8];
+
+        loop {
+            if let Err(err) = reader.read_exact(&mut timestamp) {
+                if err.kind() == ErrorKind::UnexpectedEof {
+                    return Ok(frames);
+                }
+                return Err(err.into());
+            }
+            let frame = match Receiver::versionless(&mut reader).recv() {
+                Ok(frame) => frame,
+                // Spool file may be truncated by a crash
+                Err(err) => {
+                    log::warn!("spool file {:?} is corrupted: {err:?}", self.path);
+                    return Ok(frames);
+                }
+            };
+            let spooled_at = UNIX_EPOCH + Duration::from_micros(u64::from_be_bytes(timestamp));
+            frames.push(SpooledFrame::new(frame, spooled_at));
+        }
+    }
+
+    fn append(&mut self, frame: &SpooledFrame) -> Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut writer = BufWriter::new(file);
+        Self::write(&mut writer, frame)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn rewrite(&mut self, frames: &[SpooledFrame]) -> Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for frame in frames {
+            Self::write(&mut writer, frame)?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod spool_tests {
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::protocol::Endpoint;
+
+    use super::*;
+
+    const LOW: MessageId = Heartbeat::message_id();
+    const HIGH: MessageId = ProtocolVersion::message_id();
+
+    /// Creates frames with consecutive sequence numbers.
+    fn frames(message_ids: &[MessageId]) -> Vec<Frame<V2>> {
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        message_ids
+            .iter()
+            .map(|message_id| match *message_id {
+                HIGH => endpoint.next_frame(&ProtocolVersion::default()).unwrap(),
+                _ => endpoint.next_frame(&Heartbeat::default()).unwrap(),
+            })
+            .collect()
+    }
+
+    fn push(queue: &OutboundQueue, frame: &Frame<V2>) {
+        queue.push(&OutgoingFrame::new(frame.clone()));
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn sequences(queue: &OutboundQueue) -> Vec<u8> {
+        let mut sequences = Vec::new();
+        queue.drain::<V2>(|frame| {
+            sequences.push(frame.frame().sequence());
+            Ok(())
+        });
+        sequences
+    }
+
+    #[test]
+    fn frames_are_evicted_by_priority() {
+        let queue = OutboundQueue::new().with_capacity(3).with_priority(HIGH, 1);
+
+        let frames = frames(&[HIGH, LOW, LOW, HIGH, LOW, HIGH, LOW]);
+
+        push(&queue, &frames[0]);
+        push(&queue, &frames[1]);
+        push(&queue, &frames[2]);
+        // Evicts the oldest low-priority frame
+        push(&queue, &frames[3]);
+        // Evicts the remaining low-priority frame
+        push(&queue, &frames[4]);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.evicted(), 2);
+
+        push(&queue, &frames[5]);
+        // Low-priority frame is dropped
+        push(&queue, &frames[6]);
+        assert_eq!(queue.evicted(), 4);
+
+        assert_eq!(sequences(&queue), vec![0, 3, 5]);
+    }
+
+    #[test]
+    fn expired_frames_are_dropped() {
+        let queue = OutboundQueue::new().with_max_age(Duration::from_millis(20));
+        let frames = frames(&[LOW, LOW]);
+
+        push(&queue, &frames[0]);
+        std::thread::sleep(Duration::from_millis(40));
+        push(&queue, &frames[1]);
+
+        assert_eq!(sequences(&queue), vec![1]);
+        assert_eq!(queue.expired(), 1);
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn frames_are_persisted_to_file() {
+        let path = std::env::temp_dir().join(format!("maviola-spool-{}.tlog", std::process::id()));
+        _ = fs::remove_file(&path);
+
+        let queue = OutboundQueue::new().with_storage(FileSpool::new(&path));
+        queue.load();
+        for frame in frames(&[LOW, HIGH, LOW]) {
+            push(&queue, &frame);
+        }
+
+        // The second frame fails to be sent and remains spooled
+        let mut sent = 0;
+        let transmitted = queue.drain::<V2>(|frame| {
+            sent += 1;
+            match sent {
+                1 => Ok(()),
+                _ => Err(SendError(frame)),
+            }
+        });
+        assert_eq!(transmitted, 1);
+        assert_eq!(queue.len(), 2);
+
+        let restored = OutboundQueue::new().with_storage(FileSpool::new(&path));
+        restored.load();
+        assert_eq!(sequences(&restored), vec![1, 2]);
+
+        _ = fs::remove_file(&path);
+    }
+}
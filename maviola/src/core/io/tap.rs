@@ -0,0 +1,259 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+
+use crate::core::io::ChannelInfo;
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::protocol::{Frame, MaybeVersioned};
+
+/// Observer of raw bytes passing through connection channels.
+///
+/// Channel taps receive bytes exactly as they were read from the underlying transport, before they
+/// are parsed into MAVLink frames, and bytes of serialized frames, once they were written to the
+/// transport. This is useful for wire-level logging, detection of foreign traffic (like `RTCM`
+/// corrections) injected into the stream, or debugging of framing errors.
+///
+/// Taps are registered on connection builders by `with_tap` methods (for example,
+/// [`TcpClient::with_tap`](crate::core::io::TcpClient::with_tap)) and apply to all channels of
+/// a connection. Taps are called from I/O threads or tasks of the channels, and should return
+/// quickly.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "sync")] {
+/// use maviola::core::io::{ChannelInfo, ChannelTap};
+/// use maviola::prelude::*;
+///
+/// struct WireLogger;
+///
+/// impl ChannelTap for WireLogger {
+///     fn on_read(&self, channel: &ChannelInfo, bytes: &[u8]) {
+///         println!("[{channel:?}] << {bytes:02x?}");
+///     }
+///
+///     fn on_write(&self, channel: &ChannelInfo, bytes: &[u8]) {
+///         println!("[{channel:?}] >> {bytes:02x?}");
+///     }
+/// }
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TcpClient::new("127.0.0.1:5600").unwrap()
+///                 .with_tap(WireLogger)
+///         ).build().unwrap();
+/// # }
+/// ```
+pub trait ChannelTap: Send + Sync + 'static {
+    /// Called with bytes read from a `channel` before they are parsed into MAVLink frames.
+    ///
+    /// A blanket implementation does nothing.
+    fn on_read(&self, channel: &ChannelInfo, bytes: &[u8]) {
+        _ = (channel, bytes);
+    }
+
+    /// Called with bytes of serialized frames written to a `channel`.
+    ///
+    /// A blanket implementation does nothing.
+    fn on_write(&self, channel: &ChannelInfo, bytes: &[u8]) {
+        _ = (channel, bytes);
+    }
+}
+
+impl<T: ChannelTap + ?Sized> ChannelTap for Arc<T> {
+    fn on_read(&self, channel: &ChannelInfo, bytes: &[u8]) {
+        (**self).on_read(channel, bytes)
+    }
+
+    fn on_write(&self, channel: &ChannelInfo, bytes: &[u8]) {
+        (**self).on_write(channel, bytes)
+    }
+}
+
+/// <sup>⛔</sup>
+/// Shared [`ChannelTap`] registered for a connection.
+#[derive(Clone)]
+pub(crate) struct SharedTap(Arc<dyn ChannelTap>);
+
+impl SharedTap {
+    pub(crate) fn new(tap: impl ChannelTap) -> Self {
+        Self(Arc::new(tap))
+    }
+}
+
+impl Debug for SharedTap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedTap").finish_non_exhaustive()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Reader or writer of a channel, that reports transferred bytes to an optional tap.
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) struct Tapped<T> {
+    inner: T,
+    tap: Option<SharedTap>,
+    info: ChannelInfo,
+}
+
+#[cfg(any(feature = "sync", feature = "async"))]
+impl<T> Tapped<T> {
+    pub(crate) fn new(inner: T, tap: Option<SharedTap>, info: ChannelInfo) -> Self {
+        Self { inner, tap, info }
+    }
+
+    fn report_read(&self, bytes: &[u8]) {
+        if let (Some(tap), false) = (&self.tap, bytes.is_empty()) {
+            tap.0.on_read(&self.info, bytes);
+        }
+    }
+
+    fn report_written(&self, bytes: &[u8]) {
+        if let (Some(tap), false) = (&self.tap, bytes.is_empty()) {
+            tap.0.on_write(&self.info, bytes);
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<R: std::io::Read> std::io::Read for Tapped<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.report_read(&buf[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<W: std::io::Write> std::io::Write for Tapped<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.report_written(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<R: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for Tapped<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            self.report_read(&buf.filled()[filled..]);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "async")]
+impl<W: tokio::io::AsyncWrite + Unpin> tokio::io::AsyncWrite for Tapped<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = result {
+            self.report_written(&buf[..n]);
+        }
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// <sup>⛔</sup>
+/// Reader of a channel, that keeps bytes read since the last received frame.
+///
+/// Frame receivers silently skip bytes preceding a frame start. Captured bytes allow to recover
+/// skipped bytes, once the frame is received. Only the last [`MAX_CAPTURED_BYTES`] are kept.
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) struct Captured<R> {
+    inner: R,
+    bytes: Vec<u8>,
+}
+
+/// Maximum number of bytes kept by [`Captured`] reader.
+#[cfg(any(feature = "sync", feature = "async"))]
+const MAX_CAPTURED_BYTES: usize = 1024;
+
+#[cfg(any(feature = "sync", feature = "async"))]
+impl<R> Captured<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    #[cfg(feature = "fuzz")]
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns bytes skipped before a received `frame`, if any.
+    ///
+    /// Captured bytes are cleared.
+    pub(crate) fn take_skipped<V: MaybeVersioned>(&mut self, frame: &Frame<V>) -> Option<Vec<u8>> {
+        let frame_size = frame.header().size() + frame.body_length();
+        let skipped = self.bytes.len().saturating_sub(frame_size);
+        let result = (skipped > 0).then(|| self.bytes[..skipped].to_vec());
+        self.bytes.clear();
+        result
+    }
+
+    fn capture(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+        if self.bytes.len() > MAX_CAPTURED_BYTES {
+            let excess = self.bytes.len() - MAX_CAPTURED_BYTES;
+            self.bytes.drain(..excess);
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<R: std::io::Read> std::io::Read for Captured<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.capture(&buf[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<R: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for Captured<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            self.capture(&buf.filled()[filled..]);
+        }
+        result
+    }
+}
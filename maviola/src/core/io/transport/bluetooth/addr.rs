@@ -0,0 +1,101 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use crate::prelude::*;
+
+/// <sup>`bluetooth`</sup>
+/// Bluetooth device address.
+///
+/// Parsed from and displayed in the conventional `AA:BB:CC:DD:EE:FF` notation, `-` separators are
+/// accepted as well.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BluetoothAddr([u8; 6]);
+
+impl BluetoothAddr {
+    /// Creates address from bytes in the order they are written, the most significant first.
+    pub fn new(bytes: [u8; 6]) -> Self {
+        Self(bytes)
+    }
+
+    /// Address bytes in the order they are written, the most significant first.
+    pub fn bytes(&self) -> [u8; 6] {
+        self.0
+    }
+
+    /// Parses address in `AA:BB:CC:DD:EE:FF` notation.
+    ///
+    /// Returns [`std::io::ErrorKind::InvalidInput`] error, if address is malformed.
+    pub fn parse(addr: &str) -> Result<Self> {
+        let mut bytes = [0u8; 6];
+        let mut parts = addr.split([':', '-']);
+
+        for byte in bytes.iter_mut() {
+            let part = parts
+                .next()
+                .filter(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_hexdigit()))
+                .ok_or_else(|| invalid_addr(addr))?;
+            *byte = u8::from_str_radix(part, 16).map_err(|_| invalid_addr(addr))?;
+        }
+        if parts.next().is_some() {
+            return Err(invalid_addr(addr));
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+impl FromStr for BluetoothAddr {
+    type Err = Error;
+
+    fn from_str(addr: &str) -> Result<Self> {
+        Self::parse(addr)
+    }
+}
+
+impl Display for BluetoothAddr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02X}:{b:02X}:{c:02X}:{d:02X}:{e:02X}:{g:02X}")
+    }
+}
+
+impl Debug for BluetoothAddr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BluetoothAddr({self})")
+    }
+}
+
+fn invalid_addr(addr: &str) -> Error {
+    Error::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("invalid Bluetooth address: {addr:?}"),
+    ))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod addr_tests {
+    use super::*;
+
+    #[test]
+    fn addresses_are_parsed() {
+        let addr = BluetoothAddr::parse("98:d3:31:F5:0A:1b").unwrap();
+        assert_eq!(addr.bytes(), [0x98, 0xD3, 0x31, 0xF5, 0x0A, 0x1B]);
+        assert_eq!(addr.to_string(), "98:D3:31:F5:0A:1B");
+        assert_eq!("98-D3-31-F5-0A-1B".parse::<BluetoothAddr>().unwrap(), addr);
+
+        for addr in [
+            "",
+            "98:D3:31:F5:0A",
+            "98:D3:31:F5:0A:1B:00",
+            "98:D3:31:F5:0A:1",
+            "98:D3:31:F5:0A:XY",
+            "98:D3:31:F5:0A:+1",
+        ] {
+            assert!(BluetoothAddr::parse(addr).is_err(), "{addr:?}");
+        }
+    }
+}
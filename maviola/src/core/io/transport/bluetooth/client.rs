@@ -0,0 +1,116 @@
+use crate::core::io::{
+    BluetoothAddr, ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo,
+};
+
+use crate::prelude::*;
+
+/// <sup>`bluetooth`</sup>
+/// Bluetooth classic serial client configuration.
+///
+/// Connects to an RFCOMM channel of a remote device, such as HC-05 modules or Bluetooth bridges of
+/// telemetry radios. Available on Linux with BlueZ stack. Device has to be paired in advance,
+/// for example, with `bluetoothctl`.
+///
+/// Most serial port profile modules listen on RFCOMM channel `1`.
+///
+/// # Usage
+///
+/// Create a synchronous Bluetooth client node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::BluetoothClient;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             BluetoothClient::new("98:D3:31:F5:0A:1B", 1)    // Configure Bluetooth client
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous Bluetooth client node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::BluetoothClient;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             BluetoothClient::new("98:D3:31:F5:0A:1B", 1)    // Configure Bluetooth client
+///                 .unwrap()
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BluetoothClient {
+    pub(crate) addr: BluetoothAddr,
+    pub(crate) channel: u8,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl BluetoothClient {
+    /// Instantiates a Bluetooth client configuration.
+    ///
+    /// Accepts device address `mac_addr` in `AA:BB:CC:DD:EE:FF` notation and RFCOMM `channel`,
+    /// which must be within `1..=30`.
+    pub fn new(mac_addr: &str, channel: u8) -> Result<Self> {
+        let addr = BluetoothAddr::parse(mac_addr)?;
+
+        if !(1..=30).contains(&channel) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid RFCOMM channel: {channel}"),
+            )));
+        }
+
+        let info = ConnectionInfo::new(ConnectionDetails::BluetoothClient { addr, channel });
+        Ok(Self {
+            addr,
+            channel,
+            info,
+        })
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for BluetoothClient {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+
+    #[test]
+    fn clients_are_validated() {
+        let client = BluetoothClient::new("98:D3:31:F5:0A:1B", 1).unwrap();
+        assert!(matches!(
+            client.info().details(),
+            ConnectionDetails::BluetoothClient { channel: 1, .. }
+        ));
+
+        assert!(BluetoothClient::new("98:D3:31:F5:0A", 1).is_err());
+        assert!(BluetoothClient::new("98:D3:31:F5:0A:1B", 0).is_err());
+        assert!(BluetoothClient::new("98:D3:31:F5:0A:1B", 31).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod addr;
+pub mod client;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) mod rfcomm;
@@ -0,0 +1,54 @@
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::net::UnixStream;
+
+use crate::core::io::BluetoothAddr;
+
+/// RFCOMM protocol of `AF_BLUETOOTH` socket family (see `bluetooth/bluetooth.h` of BlueZ).
+const BTPROTO_RFCOMM: libc::c_int = 3;
+
+/// RFCOMM socket address (`struct sockaddr_rc` of BlueZ).
+#[repr(C)]
+struct SockAddrRc {
+    rc_family: libc::sa_family_t,
+    /// Device address in little-endian byte order.
+    rc_bdaddr: [u8; 6],
+    rc_channel: u8,
+}
+
+/// Connects to RFCOMM `channel` of a device with address `addr`.
+///
+/// Blocks until connection is established. RFCOMM sockets are stream sockets and support the same
+/// I/O operations as Unix sockets, so connected socket is returned as [`UnixStream`].
+pub(crate) fn connect(addr: BluetoothAddr, channel: u8) -> std::io::Result<UnixStream> {
+    let mut rc_bdaddr = addr.bytes();
+    rc_bdaddr.reverse();
+    let sock_addr = SockAddrRc {
+        rc_family: libc::AF_BLUETOOTH as libc::sa_family_t,
+        rc_bdaddr,
+        rc_channel: channel,
+    };
+
+    unsafe {
+        let fd = libc::socket(
+            libc::AF_BLUETOOTH,
+            libc::SOCK_STREAM | libc::SOCK_CLOEXEC,
+            BTPROTO_RFCOMM,
+        );
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // Socket is closed on drop if connection fails
+        let fd = OwnedFd::from_raw_fd(fd);
+
+        let result = libc::connect(
+            fd.as_raw_fd(),
+            &sock_addr as *const SockAddrRc as *const libc::sockaddr,
+            std::mem::size_of::<SockAddrRc>() as libc::socklen_t,
+        );
+        if result < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(UnixStream::from(fd))
+    }
+}
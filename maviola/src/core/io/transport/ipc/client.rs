@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// <sup>[`sync`](crate::sync) | `ipc`</sup>
+/// Shared memory client configuration.
+///
+/// Attaches to a shared memory segment created by an [`IpcServer`]. Connection fails, if server
+/// is not running or already has a client.
+///
+/// Available only for synchronous API on Unix-like systems.
+///
+/// [`IpcServer`]: crate::core::io::IpcServer
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::IpcClient;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             IpcClient::new("/dev/shm/maviola.ipc")    // Configure shared memory client
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpcClient {
+    pub(crate) path: PathBuf,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl IpcClient {
+    /// Instantiates a shared memory client configuration.
+    ///
+    /// Accepts as `path` anything that can be converted to [`PathBuf`], validates that path exists.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path: PathBuf = path.into();
+
+        if !Path::exists(path.as_path()) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("shared memory segment does not exists: {path:?}"),
+            )));
+        }
+
+        let info = ConnectionInfo::new(ConnectionDetails::IpcClient { path: path.clone() });
+        Ok(Self { path, info })
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for IpcClient {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
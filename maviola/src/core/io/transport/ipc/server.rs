@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::consts::{DEFAULT_IPC_CAPACITY, MAX_IPC_CAPACITY};
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// Minimum capacity of a ring buffer, enough to hold several frames of maximum size.
+const MIN_IPC_CAPACITY: usize = 4096;
+
+/// <sup>[`sync`](crate::sync) | `ipc`</sup>
+/// Shared memory server configuration.
+///
+/// Creates a shared memory segment at `path` and waits for an [`IpcClient`] to attach to it.
+/// Frames are passed between processes through a pair of ring buffers, one for each direction,
+/// bypassing the network stack. This is the fastest way to exchange high-rate telemetry between
+/// processes running on the same host, for example, on a companion computer.
+///
+/// Server accepts a single client at a time, each attached client is a separate channel. Once
+/// a client is detached or has stopped responding, server waits for the next one. The segment is
+/// removed, once server is closed.
+///
+/// Use a path on a memory-backed file system, such as `/dev/shm` on Linux, to avoid disk writes.
+///
+/// Available only for synchronous API on Unix-like systems.
+///
+/// [`IpcClient`]: crate::core::io::IpcClient
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::IpcServer;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             IpcServer::new("/dev/shm/maviola.ipc")    // Configure shared memory server
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpcServer {
+    pub(crate) path: PathBuf,
+    pub(crate) capacity: usize,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl IpcServer {
+    /// Instantiates a shared memory server configuration.
+    ///
+    /// Accepts as `path` anything that can be converted to [`PathBuf`], validates that path does
+    /// not exist.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path: PathBuf = path.into();
+
+        if Path::exists(path.as_path()) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                format!("shared memory path already exists: {path:?}"),
+            )));
+        }
+
+        let info = ConnectionInfo::new(ConnectionDetails::IpcServer { path: path.clone() });
+        Ok(Self {
+            path,
+            capacity: DEFAULT_IPC_CAPACITY,
+            info,
+        })
+    }
+
+    /// Sets capacity in bytes of each ring buffer.
+    ///
+    /// Capacity is rounded up to the nearest power of two, is at least `4096` bytes and at most
+    /// [`MAX_IPC_CAPACITY`]. Default capacity is [`DEFAULT_IPC_CAPACITY`]. Writers wait, once the
+    /// buffer is full, so larger buffers absorb longer stalls of a reader.
+    ///
+    /// [`DEFAULT_IPC_CAPACITY`]: crate::core::consts::DEFAULT_IPC_CAPACITY
+    /// [`MAX_IPC_CAPACITY`]: crate::core::consts::MAX_IPC_CAPACITY
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        Self {
+            capacity: capacity
+                .clamp(MIN_IPC_CAPACITY, MAX_IPC_CAPACITY)
+                .next_power_of_two(),
+            ..self
+        }
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for IpcServer {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
@@ -1,17 +1,83 @@
 //! # 🔒 Transport interfaces
 
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+mod bluetooth;
 mod file;
+#[cfg(all(feature = "ipc", unix))]
+mod ipc;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "serial")]
+mod serial;
 #[cfg(unix)]
 mod sock;
+mod spec;
+#[cfg(any(feature = "zmq", feature = "mqtt", feature = "nats", feature = "redis"))]
+mod splitter;
 mod tcp;
+mod tlog;
 mod udp;
+#[cfg(feature = "zmq")]
+mod zmq;
 
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+pub use bluetooth::addr::BluetoothAddr;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+pub use bluetooth::client::BluetoothClient;
 pub use file::reader::FileReader;
 pub use file::writer::FileWriter;
+#[cfg(all(feature = "ipc", unix))]
+pub use ipc::client::IpcClient;
+#[cfg(all(feature = "ipc", unix))]
+pub use ipc::server::IpcServer;
+#[cfg(feature = "mqtt")]
+pub use mqtt::bridge::{MqttBridge, MqttFormat};
+#[cfg(feature = "nats")]
+pub use nats::bridge::NatsBridge;
+#[cfg(feature = "redis")]
+pub use redis::bridge::RedisBridge;
+#[cfg(feature = "serial")]
+pub use serial::duplex::HalfDuplex;
+#[cfg(feature = "serial")]
+pub use serial::port::SerialPort;
+pub use spec::ConnectionSpec;
 pub use tcp::client::TcpClient;
+pub use tcp::handshake::HandshakeOutcome;
 pub use tcp::server::TcpServer;
+#[cfg(feature = "tls")]
+pub use tcp::tls::{TlsAcceptor, TlsConnector};
+pub use tlog::reader::TlogReader;
+pub use tlog::writer::TlogWriter;
 pub use udp::client::UdpClient;
+pub use udp::guard::CidrRange;
 pub use udp::server::UdpServer;
+#[cfg(feature = "zmq")]
+pub use zmq::publisher::ZmqPub;
+#[cfg(feature = "zmq")]
+pub use zmq::subscriber::ZmqSub;
+
+#[cfg(all(
+    feature = "bluetooth",
+    target_os = "linux",
+    any(feature = "sync", feature = "async")
+))]
+pub(crate) use bluetooth::rfcomm;
+#[cfg(feature = "mqtt")]
+pub(crate) use mqtt::wire as mqtt_wire;
+#[cfg(feature = "nats")]
+pub(crate) use nats::wire as nats_wire;
+#[cfg(feature = "redis")]
+pub(crate) use redis::wire as redis_wire;
+#[cfg(any(feature = "zmq", feature = "mqtt", feature = "nats", feature = "redis"))]
+pub(crate) use splitter::FrameSplitter;
+pub(crate) use tcp::handshake::ServerHandshake;
+pub(crate) use tlog::{tlog_timestamp, TlogPlayback};
+#[cfg(feature = "zmq")]
+pub(crate) use zmq::zmtp;
 
 #[cfg(unix)]
 pub use sock::client::SockClient;
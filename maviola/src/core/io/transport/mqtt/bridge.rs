@@ -0,0 +1,225 @@
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_MQTT_KEEP_ALIVE, DEFAULT_MQTT_PUBLISH_TOPIC};
+use crate::core::io::transport::mqtt::parse_broker;
+use crate::core::io::transport::mqtt::wire::MqttSession;
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+#[cfg(feature = "definitions")]
+use crate::protocol::MessageDefinitions;
+
+use crate::prelude::*;
+
+/// Counter of generated client identifiers.
+static CLIENT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// <sup>`mqtt`</sup>
+/// MQTT bridge configuration.
+///
+/// Connects to an MQTT broker, publishes outgoing frames and receives frames published by other
+/// clients. This allows to feed MAVLink data into IoT backends without a separate bridge daemon.
+/// Broker connection is a single channel.
+///
+/// Bridge speaks MQTT 3.1.1 over plain TCP with clean session and QoS 0 delivery. Each outgoing
+/// frame is published as a separate message to a topic set by [`MqttBridge::with_publish_topic`]
+/// ([`DEFAULT_MQTT_PUBLISH_TOPIC`] by default). Topic may contain `{system_id}`,
+/// `{component_id}`, and `{message_id}` placeholders, that are substituted from the frame header.
+/// Frames are published as raw serialized MAVLink frames, or as JSON, if `MqttFormat::Json` is set
+/// by [`MqttBridge::with_format`] (requires `definitions` feature).
+///
+/// Bridge subscribes to topic filters added by [`MqttBridge::with_subscription`] and expects
+/// payloads of received messages to be in the same format. In JSON format, received messages are
+/// encoded into frames according to message definitions. Without subscriptions,
+/// bridge only publishes frames. Make sure, that subscriptions do not match the publication topic,
+/// otherwise bridge will receive its own frames.
+///
+/// [`DEFAULT_MQTT_PUBLISH_TOPIC`]: crate::core::consts::DEFAULT_MQTT_PUBLISH_TOPIC
+///
+/// # Usage
+///
+/// Create a synchronous bridge node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::MqttBridge;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             MqttBridge::new("mqtt://127.0.0.1:1883")    // Configure MQTT bridge
+///                 .unwrap()
+///                 .with_publish_topic("vehicles/{system_id}/mavlink")
+///                 .with_subscription("gcs/mavlink")
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous bridge node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::MqttBridge;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             MqttBridge::new("mqtt://127.0.0.1:1883")    // Configure MQTT bridge
+///                 .unwrap()
+///                 .with_publish_topic("vehicles/{system_id}/mavlink")
+///                 .with_subscription("gcs/mavlink")
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MqttBridge {
+    pub(crate) addr: SocketAddr,
+    pub(crate) client_id: String,
+    pub(crate) credentials: Option<(String, String)>,
+    pub(crate) keep_alive: Duration,
+    pub(crate) publish_topic: String,
+    pub(crate) subscriptions: Vec<String>,
+    pub(crate) format: MqttFormat,
+    pub(crate) info: ConnectionInfo,
+}
+
+/// <sup>`mqtt`</sup>
+/// Format of frames published and received by [`MqttBridge`].
+#[derive(Clone, Debug, Default)]
+pub enum MqttFormat {
+    /// Serialized MAVLink frames.
+    #[default]
+    Raw,
+    /// <sup>`definitions`</sup>
+    /// JSON produced by [`DynamicMessage::to_json`](crate::protocol::DynamicMessage::to_json).
+    ///
+    /// Frames, that are not present in message definitions, are not published. Received JSON
+    /// messages are encoded into `MAVLink 2` frames (or `MAVLink 1` for nodes of this protocol
+    /// version), messages, that can't be encoded, are dropped.
+    #[cfg(feature = "definitions")]
+    Json(MessageDefinitions),
+}
+
+impl MqttBridge {
+    /// Instantiates an MQTT bridge configuration.
+    ///
+    /// Accepts `mqtt://` or `tcp://` broker URLs such as `mqtt://127.0.0.1:1883`. Scheme can be
+    /// omitted, port defaults to `1883`. Secure connections are not supported.
+    ///
+    /// Client identifier is generated and unique within the process.
+    pub fn new(broker: &str) -> Result<Self> {
+        let addr = parse_broker(broker)?;
+        let client_id = format!(
+            "maviola-{}-{}",
+            std::process::id(),
+            CLIENT_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        Ok(Self {
+            addr,
+            info: Self::make_info(addr, &client_id),
+            client_id,
+            credentials: None,
+            keep_alive: DEFAULT_MQTT_KEEP_ALIVE,
+            publish_topic: DEFAULT_MQTT_PUBLISH_TOPIC.to_string(),
+            subscriptions: Vec::new(),
+            format: MqttFormat::default(),
+        })
+    }
+
+    /// Sets client identifier.
+    ///
+    /// Brokers disconnect the previous client with the same identifier.
+    pub fn with_client_id(self, client_id: impl Into<String>) -> Self {
+        let client_id = client_id.into();
+        Self {
+            info: Self::make_info(self.addr, &client_id),
+            client_id,
+            ..self
+        }
+    }
+
+    /// Sets user name and password.
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Sets keep alive interval announced to the broker.
+    ///
+    /// Bridge pings the broker twice per interval, so broker doesn't consider it lost.
+    /// Zero duration disables keep alive. Interval is rounded down to seconds.
+    pub fn with_keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Sets topic template, under which outgoing frames are published.
+    pub fn with_publish_topic(mut self, topic: impl Into<String>) -> Self {
+        self.publish_topic = topic.into();
+        self
+    }
+
+    /// Adds topic filter to subscribe to.
+    ///
+    /// Filters may contain MQTT wildcards such as `+` and `#`.
+    pub fn with_subscription(mut self, filter: impl Into<String>) -> Self {
+        self.subscriptions.push(filter.into());
+        self
+    }
+
+    /// Sets format of published frames.
+    pub fn with_format(mut self, format: MqttFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+
+    /// Session parameters sent to the broker.
+    pub(crate) fn session(&self) -> MqttSession<'_> {
+        MqttSession {
+            client_id: &self.client_id,
+            keep_alive_secs: self.keep_alive.as_secs().min(u16::MAX as u64) as u16,
+            credentials: self
+                .credentials
+                .as_ref()
+                .map(|(username, password)| (username.as_str(), password.as_str())),
+        }
+    }
+
+    /// Interval between pings, if keep alive is enabled.
+    pub(crate) fn ping_interval(&self) -> Option<Duration> {
+        match self.session().keep_alive_secs {
+            0 => None,
+            secs => Some(Duration::from_secs(secs as u64) / 2),
+        }
+    }
+
+    fn make_info(broker_addr: SocketAddr, client_id: &str) -> ConnectionInfo {
+        ConnectionInfo::new(ConnectionDetails::MqttBridge {
+            broker_addr,
+            client_id: client_id.to_string(),
+        })
+    }
+}
+
+impl ConnectionConf for MqttBridge {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
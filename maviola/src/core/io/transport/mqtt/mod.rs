@@ -0,0 +1,22 @@
+pub mod bridge;
+pub(crate) mod wire;
+
+use std::net::SocketAddr;
+
+use crate::core::utils::net::{resolve_with_default_port, strip_url_scheme};
+use crate::error::MqttError;
+
+use crate::prelude::*;
+
+const MQTT_SCHEMES: [&str; 2] = ["mqtt://", "tcp://"];
+const DEFAULT_MQTT_PORT: u16 = 1883;
+
+/// Parses MQTT broker URL.
+///
+/// Accepts `mqtt://` and `tcp://` URLs as well as plain addresses. Port defaults to `1883`.
+fn parse_broker(url: &str) -> Result<SocketAddr> {
+    match strip_url_scheme(url, &MQTT_SCHEMES) {
+        Some(addr) => resolve_with_default_port(addr, DEFAULT_MQTT_PORT),
+        None => Err(MqttError::UnsupportedBroker(url.to_string()).into()),
+    }
+}
@@ -0,0 +1,530 @@
+//! Minimal implementation of [MQTT 3.1.1](https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/mqtt-v3.1.1.html)
+//! client packets with QoS 0 delivery.
+//!
+//! Codec does not perform any I/O and is shared by synchronous and asynchronous transports.
+
+use std::io::Cursor;
+
+use mavio::io::Receiver;
+#[cfg(feature = "definitions")]
+use mavio::io::Sender;
+
+use crate::core::io::{FrameSplitter, MqttFormat};
+use crate::error::MqttError;
+#[cfg(feature = "definitions")]
+use crate::protocol::{
+    DynamicMessage, MavLinkVersion, MessageDefinitions, Sequence, Versioned, V1, V2,
+};
+use crate::protocol::{Frame, MaybeVersioned, Versionless};
+
+/// Size of a buffer for reading from MQTT streams.
+pub(crate) const READ_BUFFER_SIZE: usize = 1024;
+/// `PINGREQ` packet.
+pub(crate) const PINGREQ: [u8; 2] = [0xC0, 0x00];
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const SUBSCRIBE: u8 = 0x82;
+const SUBACK: u8 = 0x90;
+const PINGRESP: u8 = 0xD0;
+
+const PROTOCOL_NAME: &[u8] = b"MQTT";
+const PROTOCOL_LEVEL: u8 = 4;
+const FLAG_CLEAN_SESSION: u8 = 0x02;
+const FLAG_PASSWORD: u8 = 0x40;
+const FLAG_USERNAME: u8 = 0x80;
+const CONNACK_ACCEPTED: u8 = 0x00;
+const SUBACK_FAILURE: u8 = 0x80;
+const QOS_MASK: u8 = 0x06;
+/// Packet identifier of the subscription request sent after connecting.
+const SUBSCRIBE_PACKET_ID: u16 = 1;
+/// Packets larger than this are considered malformed and are not sent, MAVLink frames are much
+/// smaller.
+const MAX_PACKET_SIZE: usize = 256 * 1024;
+
+/// Placeholders of publication topic templates.
+const TOPIC_SYSTEM_ID: &str = "{system_id}";
+const TOPIC_COMPONENT_ID: &str = "{component_id}";
+const TOPIC_MESSAGE_ID: &str = "{message_id}";
+
+/// Packet received from an MQTT broker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MqttPacket {
+    /// Connection acknowledgement with a return code.
+    ConnAck(u8),
+    /// Application message.
+    Publish { topic: String, payload: Vec<u8> },
+    /// Subscription acknowledgement with return codes of each topic filter.
+    SubAck(Vec<u8>),
+    /// Ping response.
+    PingResp,
+    /// Other packet identified by the first byte of its fixed header.
+    Other(u8),
+}
+
+/// Client session parameters sent in `CONNECT` packet.
+#[derive(Clone, Debug)]
+pub(crate) struct MqttSession<'a> {
+    pub(crate) client_id: &'a str,
+    pub(crate) keep_alive_secs: u16,
+    pub(crate) credentials: Option<(&'a str, &'a str)>,
+}
+
+/// Incremental decoder of MQTT packets.
+#[derive(Debug, Default)]
+pub(crate) struct MqttDecoder {
+    buffer: Vec<u8>,
+}
+
+/// Converts payloads of `PUBLISH` packets into serialized MAVLink frames.
+#[derive(Debug)]
+pub(crate) struct MqttSubscriber {
+    format: MqttFormat,
+    #[cfg(feature = "definitions")]
+    version: MavLinkVersion,
+    #[cfg(feature = "definitions")]
+    sequence: Sequence,
+}
+
+/// Converts a stream of serialized MAVLink frames into `PUBLISH` packets.
+#[derive(Debug)]
+pub(crate) struct MqttPublisher {
+    topic: String,
+    format: MqttFormat,
+    splitter: FrameSplitter,
+}
+
+impl MqttDecoder {
+    /// Appends bytes received from a broker.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next complete packet.
+    ///
+    /// Returns [`None`], if more bytes are required.
+    pub(crate) fn next(&mut self) -> Result<Option<MqttPacket>, MqttError> {
+        let header = match self.buffer.first() {
+            Some(&header) => header,
+            None => return Ok(None),
+        };
+        let (body_size, header_size) = match decode_remaining_length(&self.buffer[1..])? {
+            Some((size, len)) => (size, len + 1),
+            None => return Ok(None),
+        };
+        if self.buffer.len() < header_size + body_size {
+            return Ok(None);
+        }
+
+        let body: Vec<u8> = self
+            .buffer
+            .drain(..header_size + body_size)
+            .skip(header_size)
+            .collect();
+
+        parse_packet(header, &body).map(Some)
+    }
+}
+
+impl MqttSubscriber {
+    /// Creates subscriber, that produces frames for nodes of protocol version `V`.
+    ///
+    /// Frames decoded from JSON are `MAVLink 2` frames, unless node accepts only `MAVLink 1`.
+    #[cfg_attr(
+        not(feature = "definitions"),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    pub(crate) fn new<V: MaybeVersioned>(format: MqttFormat) -> Self {
+        Self {
+            format,
+            #[cfg(feature = "definitions")]
+            version: match V::matches(MavLinkVersion::V2) {
+                true => MavLinkVersion::V2,
+                false => MavLinkVersion::V1,
+            },
+            #[cfg(feature = "definitions")]
+            sequence: 0,
+        }
+    }
+
+    /// Extracts serialized frame from an application message.
+    ///
+    /// Other packets are ignored. In JSON format, messages, that can't be encoded according to
+    /// message definitions, are dropped.
+    pub(crate) fn next_frame(&mut self, packet: MqttPacket) -> Option<Vec<u8>> {
+        let payload = match packet {
+            MqttPacket::Publish { payload, .. } => payload,
+            _ => return None,
+        };
+
+        match &self.format {
+            MqttFormat::Raw => Some(payload),
+            #[cfg(feature = "definitions")]
+            MqttFormat::Json(definitions) => {
+                let sequence = self.sequence;
+                let result = match self.version {
+                    MavLinkVersion::V1 => encode_json::<V1>(definitions, &payload, sequence),
+                    MavLinkVersion::V2 => encode_json::<V2>(definitions, &payload, sequence),
+                };
+                match result {
+                    Ok(bytes) => {
+                        self.sequence = self.sequence.wrapping_add(1);
+                        Some(bytes)
+                    }
+                    Err(err) => {
+                        log::trace!("published message is not received: {err:?}");
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MqttPublisher {
+    pub(crate) fn new(topic: &str, format: MqttFormat) -> Self {
+        Self {
+            topic: topic.to_string(),
+            format,
+            splitter: FrameSplitter::default(),
+        }
+    }
+
+    /// Appends serialized frame bytes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.splitter.push(bytes);
+    }
+
+    /// Returns the next `PUBLISH` packet, if a complete frame is available.
+    ///
+    /// Bytes, that can't be parsed as frames, are dropped. In JSON format, frames unknown to
+    /// message definitions are dropped as well.
+    pub(crate) fn next(&mut self) -> Option<Vec<u8>> {
+        while let Some(bytes) = self.splitter.next() {
+            let frame = match Receiver::new::<Versionless>(&mut Cursor::new(&bytes)).recv() {
+                Ok(frame) => frame,
+                Err(_) => continue,
+            };
+            let topic = publication_topic(&self.topic, &frame);
+
+            match &self.format {
+                MqttFormat::Raw => match publish(&topic, &bytes) {
+                    Ok(packet) => return Some(packet),
+                    Err(err) => log::trace!("frame is not published: {err:?}"),
+                },
+                #[cfg(feature = "definitions")]
+                MqttFormat::Json(definitions) => match definitions
+                    .decode(&frame)
+                    .map_err(|err| format!("{err:?}"))
+                    .and_then(|message| {
+                        publish(&topic, message.to_json().as_bytes())
+                            .map_err(|err| format!("{err:?}"))
+                    }) {
+                    Ok(packet) => return Some(packet),
+                    Err(err) => log::trace!("frame is not published: {err}"),
+                },
+            }
+        }
+        None
+    }
+}
+
+/// Creates `CONNECT` packet with clean session.
+pub(crate) fn connect(session: &MqttSession) -> Result<Vec<u8>, MqttError> {
+    let mut body = Vec::new();
+    encode_bytes(&mut body, PROTOCOL_NAME)?;
+    body.push(PROTOCOL_LEVEL);
+
+    let mut flags = FLAG_CLEAN_SESSION;
+    if session.credentials.is_some() {
+        flags |= FLAG_USERNAME | FLAG_PASSWORD;
+    }
+    body.push(flags);
+    body.extend_from_slice(&session.keep_alive_secs.to_be_bytes());
+
+    encode_bytes(&mut body, session.client_id.as_bytes())?;
+    if let Some((username, password)) = session.credentials {
+        encode_bytes(&mut body, username.as_bytes())?;
+        encode_bytes(&mut body, password.as_bytes())?;
+    }
+
+    encode_packet(CONNECT, &body)
+}
+
+/// Validates broker response to `CONNECT` packet.
+pub(crate) fn check_connack(packet: MqttPacket) -> Result<(), MqttError> {
+    match packet {
+        MqttPacket::ConnAck(CONNACK_ACCEPTED) => Ok(()),
+        MqttPacket::ConnAck(code) => Err(MqttError::ConnectionRefused(code)),
+        _ => Err(MqttError::MalformedPacket),
+    }
+}
+
+/// Creates `SUBSCRIBE` packet for topic `filters` with QoS 0.
+pub(crate) fn subscribe(filters: &[String]) -> Result<Vec<u8>, MqttError> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&SUBSCRIBE_PACKET_ID.to_be_bytes());
+    for filter in filters {
+        encode_bytes(&mut body, filter.as_bytes())?;
+        body.push(0);
+    }
+    encode_packet(SUBSCRIBE, &body)
+}
+
+/// Validates broker response to `SUBSCRIBE` packet.
+pub(crate) fn check_suback(packet: MqttPacket, filters: &[String]) -> Result<(), MqttError> {
+    let codes = match packet {
+        MqttPacket::SubAck(codes) => codes,
+        _ => return Err(MqttError::MalformedPacket),
+    };
+    for (filter, code) in filters.iter().zip(codes) {
+        if code == SUBACK_FAILURE {
+            return Err(MqttError::SubscriptionRefused(filter.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Creates `PUBLISH` packet with QoS 0.
+pub(crate) fn publish(topic: &str, payload: &[u8]) -> Result<Vec<u8>, MqttError> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 2);
+    encode_bytes(&mut body, topic.as_bytes())?;
+    body.extend_from_slice(payload);
+    encode_packet(PUBLISH, &body)
+}
+
+/// Encodes JSON `payload` into a serialized frame of protocol version `V`.
+#[cfg(feature = "definitions")]
+fn encode_json<V: Versioned>(
+    definitions: &MessageDefinitions,
+    payload: &[u8],
+    sequence: Sequence,
+) -> crate::error::Result<Vec<u8>> {
+    let json = std::str::from_utf8(payload)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+    let message = DynamicMessage::from_json(json)?;
+    let frame = definitions.encode::<V>(&message, sequence)?;
+
+    let mut bytes = Vec::new();
+    Sender::versioned(&mut bytes, V::v()).send(&frame)?;
+    Ok(bytes)
+}
+
+/// Substitutes frame header fields into a topic template.
+fn publication_topic<V: MaybeVersioned>(template: &str, frame: &Frame<V>) -> String {
+    if !template.contains('{') {
+        return template.to_string();
+    }
+    template
+        .replace(TOPIC_SYSTEM_ID, &frame.system_id().to_string())
+        .replace(TOPIC_COMPONENT_ID, &frame.component_id().to_string())
+        .replace(TOPIC_MESSAGE_ID, &frame.message_id().to_string())
+}
+
+fn parse_packet(header: u8, body: &[u8]) -> Result<MqttPacket, MqttError> {
+    Ok(match header & 0xF0 {
+        CONNACK => MqttPacket::ConnAck(*body.get(1).ok_or(MqttError::MalformedPacket)?),
+        PUBLISH => {
+            let (topic, mut rest) = decode_bytes(body)?;
+            // Packet identifier is present for QoS 1 and 2
+            if header & QOS_MASK != 0 {
+                rest = rest.get(2..).ok_or(MqttError::MalformedPacket)?;
+            }
+            MqttPacket::Publish {
+                topic: String::from_utf8_lossy(topic).to_string(),
+                payload: rest.to_vec(),
+            }
+        }
+        SUBACK => MqttPacket::SubAck(body.get(2..).ok_or(MqttError::MalformedPacket)?.to_vec()),
+        PINGRESP => MqttPacket::PingResp,
+        _ => MqttPacket::Other(header),
+    })
+}
+
+fn encode_packet(header: u8, body: &[u8]) -> Result<Vec<u8>, MqttError> {
+    if body.len() > MAX_PACKET_SIZE {
+        return Err(MqttError::TooLarge(body.len()));
+    }
+
+    let mut bytes = Vec::with_capacity(body.len() + 5);
+    bytes.push(header);
+
+    let mut remaining = body.len();
+    loop {
+        let mut byte = (remaining % 128) as u8;
+        remaining /= 128;
+        if remaining > 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    bytes.extend_from_slice(body);
+    Ok(bytes)
+}
+
+/// Decodes remaining length and returns it with the number of bytes it occupies.
+fn decode_remaining_length(bytes: &[u8]) -> Result<Option<(usize, usize)>, MqttError> {
+    let mut value = 0usize;
+    for (idx, &byte) in bytes.iter().enumerate().take(4) {
+        value += ((byte & 0x7F) as usize) << (7 * idx);
+        if byte & 0x80 == 0 {
+            if value > MAX_PACKET_SIZE {
+                return Err(MqttError::MalformedPacket);
+            }
+            return Ok(Some((value, idx + 1)));
+        }
+    }
+
+    match bytes.len() {
+        0..=3 => Ok(None),
+        _ => Err(MqttError::MalformedPacket),
+    }
+}
+
+fn encode_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) -> Result<(), MqttError> {
+    let size = u16::try_from(bytes.len()).map_err(|_| MqttError::TooLarge(bytes.len()))?;
+    buffer.extend_from_slice(&size.to_be_bytes());
+    buffer.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn decode_bytes(bytes: &[u8]) -> Result<(&[u8], &[u8]), MqttError> {
+    let size = bytes.get(..2).ok_or(MqttError::MalformedPacket)?;
+    let size = u16::from_be_bytes([size[0], size[1]]) as usize;
+    let value = bytes.get(2..2 + size).ok_or(MqttError::MalformedPacket)?;
+    Ok((value, &bytes[2 + size..]))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod wire_tests {
+    use super::*;
+
+    use mavio::io::Sender;
+
+    use crate::prelude::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, V2};
+
+    fn heartbeat_bytes() -> Vec<u8> {
+        let frame = Endpoint::v2(MavLinkId::new(1, 42))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let mut bytes = Vec::new();
+        Sender::versioned(&mut bytes, V2).send(&frame).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn session_is_established() {
+        let bytes = connect(&MqttSession {
+            client_id: "maviola",
+            keep_alive_secs: 60,
+            credentials: Some(("user", "secret")),
+        })
+        .unwrap();
+        assert_eq!(bytes[0], CONNECT);
+        assert_eq!(bytes[1] as usize, bytes.len() - 2);
+        assert_eq!(&bytes[4..8], b"MQTT");
+        assert_eq!(bytes[8], PROTOCOL_LEVEL);
+        assert_eq!(bytes[9], FLAG_CLEAN_SESSION | FLAG_USERNAME | FLAG_PASSWORD);
+        assert_eq!(&bytes[10..12], &[0, 60]);
+
+        let mut decoder = MqttDecoder::default();
+        decoder.push(&[CONNACK, 2, 0, 0]);
+        check_connack(decoder.next().unwrap().unwrap()).unwrap();
+        decoder.push(&[CONNACK, 2, 0, 5]);
+        assert!(matches!(
+            check_connack(decoder.next().unwrap().unwrap()),
+            Err(MqttError::ConnectionRefused(5))
+        ));
+
+        let filters = vec!["mavlink/in/#".to_string(), "forbidden".to_string()];
+        decoder.push(&[SUBACK, 4, 0, 1, 0, SUBACK_FAILURE]);
+        assert!(matches!(
+            check_suback(decoder.next().unwrap().unwrap(), &filters),
+            Err(MqttError::SubscriptionRefused(filter)) if filter == "forbidden"
+        ));
+
+        let filters = vec!["x".repeat(u16::MAX as usize + 1)];
+        assert!(matches!(
+            subscribe(&filters),
+            Err(MqttError::TooLarge(size)) if size == u16::MAX as usize + 1
+        ));
+    }
+
+    #[test]
+    fn packets_are_decoded_incrementally() {
+        let payload = vec![42u8; 300];
+        let mut bytes = publish("mavlink/in", &payload).unwrap();
+        // Two-byte remaining length
+        assert_eq!(&bytes[1..3], &[0xB8, 0x02]);
+        bytes.extend([PINGRESP, 0]);
+        // QoS 1 publication with packet identifier
+        bytes.extend([PUBLISH | 0x02, 7, 0, 2, b'q', b'1', 0, 9, 0xFD]);
+
+        let mut decoder = MqttDecoder::default();
+        let mut packets = Vec::new();
+        for byte in bytes {
+            decoder.push(&[byte]);
+            while let Some(packet) = decoder.next().unwrap() {
+                packets.push(packet);
+            }
+        }
+
+        assert_eq!(
+            packets,
+            vec![
+                MqttPacket::Publish {
+                    topic: "mavlink/in".to_string(),
+                    payload: payload.clone(),
+                },
+                MqttPacket::PingResp,
+                MqttPacket::Publish {
+                    topic: "q1".to_string(),
+                    payload: vec![0xFD],
+                },
+            ]
+        );
+        assert_eq!(
+            MqttSubscriber::new::<V2>(MqttFormat::Raw).next_frame(packets[0].clone()),
+            Some(payload)
+        );
+
+        let mut decoder = MqttDecoder::default();
+        decoder.push(&[PUBLISH, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert!(matches!(decoder.next(), Err(MqttError::MalformedPacket)));
+    }
+
+    #[test]
+    fn frames_are_published_to_topics() {
+        let frame = heartbeat_bytes();
+        let mut publisher = MqttPublisher::new(
+            "mavlink/{system_id}/{component_id}/{message_id}",
+            MqttFormat::Raw,
+        );
+
+        publisher.push(&frame[..5]);
+        assert!(publisher.next().is_none());
+        publisher.push(&frame[5..]);
+
+        let mut decoder = MqttDecoder::default();
+        decoder.push(&publisher.next().unwrap());
+        assert_eq!(
+            decoder.next().unwrap().unwrap(),
+            MqttPacket::Publish {
+                topic: "mavlink/1/42/0".to_string(),
+                payload: frame,
+            }
+        );
+    }
+}
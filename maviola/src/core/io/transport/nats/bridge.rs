@@ -0,0 +1,128 @@
+use std::net::SocketAddr;
+
+use crate::core::consts::DEFAULT_NATS_CLIENT_NAME;
+use crate::core::io::transport::nats::parse_server;
+use crate::core::io::transport::nats::wire::{self, NatsSession};
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::error::NatsError;
+
+use crate::prelude::*;
+
+/// <sup>`nats`</sup>
+/// NATS bridge configuration.
+///
+/// Maps connection onto a [NATS](https://nats.io/) subject. Outgoing frames are published to the
+/// subject and frames published to the same subject by other clients are received. This allows
+/// several services to share one vehicle link through an existing message broker. Server
+/// connection is a single channel.
+///
+/// Each frame is published as a separate message, that contains serialized MAVLink frame. Bridge
+/// does not receive frames it published by itself. Subject can't contain wildcards, since it is
+/// used for publishing.
+///
+/// Only plain TCP connections are supported, servers that require TLS are rejected.
+///
+/// # Usage
+///
+/// Create a synchronous bridge node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::NatsBridge;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             NatsBridge::new("nats://127.0.0.1:4222", "vehicles.1.mavlink")   // Configure NATS bridge
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous bridge node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::NatsBridge;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             NatsBridge::new("nats://127.0.0.1:4222", "vehicles.1.mavlink")   // Configure NATS bridge
+///                 .unwrap()
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NatsBridge {
+    pub(crate) addr: SocketAddr,
+    pub(crate) subject: String,
+    pub(crate) name: String,
+    pub(crate) credentials: Option<(String, String)>,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl NatsBridge {
+    /// Instantiates a NATS bridge configuration for a `subject`.
+    ///
+    /// Accepts `nats://` or `tcp://` server URLs such as `nats://127.0.0.1:4222`. Scheme can be
+    /// omitted, port defaults to `4222`.
+    pub fn new(server: &str, subject: &str) -> Result<Self> {
+        let addr = parse_server(server)?;
+        if !wire::is_valid_subject(subject) {
+            return Err(NatsError::InvalidSubject(subject.to_string()).into());
+        }
+
+        Ok(Self {
+            addr,
+            subject: subject.to_string(),
+            name: DEFAULT_NATS_CLIENT_NAME.to_string(),
+            credentials: None,
+            info: ConnectionInfo::new(ConnectionDetails::NatsBridge {
+                server_addr: addr,
+                subject: subject.to_string(),
+            }),
+        })
+    }
+
+    /// Sets client name, that is reported to the server for monitoring.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets user name and password.
+    pub fn with_credentials(mut self, user: impl Into<String>, pass: impl Into<String>) -> Self {
+        self.credentials = Some((user.into(), pass.into()));
+        self
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+
+    /// Session parameters sent to the server.
+    pub(crate) fn session(&self) -> NatsSession<'_> {
+        NatsSession {
+            name: &self.name,
+            credentials: self
+                .credentials
+                .as_ref()
+                .map(|(user, pass)| (user.as_str(), pass.as_str())),
+        }
+    }
+}
+
+impl ConnectionConf for NatsBridge {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
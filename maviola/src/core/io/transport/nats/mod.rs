@@ -0,0 +1,22 @@
+pub mod bridge;
+pub(crate) mod wire;
+
+use std::net::SocketAddr;
+
+use crate::core::utils::net::{resolve_with_default_port, strip_url_scheme};
+use crate::error::NatsError;
+
+use crate::prelude::*;
+
+const NATS_SCHEMES: [&str; 2] = ["nats://", "tcp://"];
+const DEFAULT_NATS_PORT: u16 = 4222;
+
+/// Parses NATS server URL.
+///
+/// Accepts `nats://` and `tcp://` URLs as well as plain addresses. Port defaults to `4222`.
+fn parse_server(url: &str) -> Result<SocketAddr> {
+    match strip_url_scheme(url, &NATS_SCHEMES) {
+        Some(addr) => resolve_with_default_port(addr, DEFAULT_NATS_PORT),
+        None => Err(NatsError::UnsupportedServer(url.to_string()).into()),
+    }
+}
@@ -0,0 +1,326 @@
+//! Minimal implementation of [NATS](https://docs.nats.io/reference/reference-protocols/nats-protocol)
+//! client protocol.
+//!
+//! Codec does not perform any I/O and is shared by synchronous and asynchronous transports.
+
+use crate::core::io::FrameSplitter;
+use crate::error::NatsError;
+
+/// Size of a buffer for reading from NATS streams.
+pub(crate) const READ_BUFFER_SIZE: usize = 1024;
+/// `PONG` message, that answers server pings.
+pub(crate) const PONG: &[u8] = b"PONG\r\n";
+
+const CRLF: &[u8] = b"\r\n";
+/// Identifier of the only subscription of a bridge.
+const SUBSCRIPTION_ID: &str = "1";
+/// Control lines longer than this are considered malformed.
+const MAX_CONTROL_LINE_SIZE: usize = 64 * 1024;
+/// Payloads larger than this are considered malformed, MAVLink frames are much smaller.
+const MAX_PAYLOAD_SIZE: usize = 256 * 1024;
+
+/// Message received from a NATS server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum NatsMessage {
+    /// Server information in JSON format.
+    Info(String),
+    /// Message delivered to a subscription.
+    Msg { subject: String, payload: Vec<u8> },
+    /// Keep alive request.
+    Ping,
+    /// Keep alive response.
+    Pong,
+    /// Acknowledgement in verbose mode.
+    Ok,
+    /// Protocol error.
+    Err(String),
+}
+
+/// Client session parameters sent in `CONNECT` message.
+#[derive(Clone, Debug)]
+pub(crate) struct NatsSession<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) credentials: Option<(&'a str, &'a str)>,
+}
+
+/// Incremental decoder of NATS messages.
+#[derive(Debug, Default)]
+pub(crate) struct NatsDecoder {
+    buffer: Vec<u8>,
+}
+
+/// Converts a stream of serialized MAVLink frames into `PUB` messages.
+#[derive(Debug)]
+pub(crate) struct NatsPublisher {
+    subject: String,
+    splitter: FrameSplitter,
+}
+
+impl NatsDecoder {
+    /// Appends bytes received from a server.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next complete message.
+    ///
+    /// Returns [`None`], if more bytes are required.
+    pub(crate) fn next(&mut self) -> Result<Option<NatsMessage>, NatsError> {
+        let line_size = match find_crlf(&self.buffer) {
+            Some(size) => size,
+            None if self.buffer.len() > MAX_CONTROL_LINE_SIZE => {
+                return Err(NatsError::MalformedMessage)
+            }
+            None => return Ok(None),
+        };
+        let line = String::from_utf8_lossy(&self.buffer[..line_size]).to_string();
+        let (op, args) = match line.split_once([' ', '\t']) {
+            Some((op, args)) => (op.to_ascii_uppercase(), args.trim()),
+            None => (line.to_ascii_uppercase(), ""),
+        };
+
+        let mut message_size = line_size + CRLF.len();
+        let message = match op.as_str() {
+            "MSG" => {
+                let (subject, payload_size) = parse_msg_args(args)?;
+                let payload_start = message_size;
+                message_size += payload_size + CRLF.len();
+                if self.buffer.len() < message_size {
+                    return Ok(None);
+                }
+                if &self.buffer[message_size - CRLF.len()..message_size] != CRLF {
+                    return Err(NatsError::MalformedMessage);
+                }
+                NatsMessage::Msg {
+                    subject,
+                    payload: self.buffer[payload_start..payload_start + payload_size].to_vec(),
+                }
+            }
+            "INFO" => NatsMessage::Info(args.to_string()),
+            "PING" => NatsMessage::Ping,
+            "PONG" => NatsMessage::Pong,
+            "+OK" => NatsMessage::Ok,
+            "-ERR" => NatsMessage::Err(args.trim_matches('\'').to_string()),
+            _ => return Err(NatsError::MalformedMessage),
+        };
+
+        self.buffer.drain(..message_size);
+        Ok(Some(message))
+    }
+}
+
+impl NatsPublisher {
+    pub(crate) fn new(subject: &str) -> Self {
+        Self {
+            subject: subject.to_string(),
+            splitter: FrameSplitter::default(),
+        }
+    }
+
+    /// Appends serialized frame bytes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.splitter.push(bytes);
+    }
+
+    /// Returns the next `PUB` message, if a complete frame is available.
+    pub(crate) fn next(&mut self) -> Option<Vec<u8>> {
+        self.splitter
+            .next()
+            .map(|frame| publish(&self.subject, &frame))
+    }
+}
+
+/// Validates server information received upon connection.
+pub(crate) fn check_info(message: NatsMessage) -> Result<(), NatsError> {
+    match message {
+        NatsMessage::Info(info) if info.replace(' ', "").contains("\"tls_required\":true") => {
+            Err(NatsError::TlsRequired)
+        }
+        NatsMessage::Info(_) => Ok(()),
+        NatsMessage::Err(err) => Err(NatsError::Rejected(err)),
+        _ => Err(NatsError::MalformedMessage),
+    }
+}
+
+/// Creates `CONNECT` message followed by `PING`.
+///
+/// Server answers with `PONG` once connection is accepted. Messages published by the client are
+/// not delivered back to its own subscriptions.
+pub(crate) fn connect(session: &NatsSession) -> Vec<u8> {
+    let mut options = format!(
+        "{{\"verbose\":false,\"pedantic\":false,\"echo\":false,\"protocol\":1,\"lang\":\"rust\",\"version\":\"{}\",\"name\":{}",
+        env!("CARGO_PKG_VERSION"),
+        json_string(session.name)
+    );
+    if let Some((user, pass)) = session.credentials {
+        options.push_str(&format!(
+            ",\"user\":{},\"pass\":{}",
+            json_string(user),
+            json_string(pass)
+        ));
+    }
+    options.push('}');
+
+    format!("CONNECT {options}\r\nPING\r\n").into_bytes()
+}
+
+/// Validates server response to `CONNECT` message.
+///
+/// Returns `false`, if response is not received yet.
+pub(crate) fn check_connected(message: NatsMessage) -> Result<bool, NatsError> {
+    match message {
+        NatsMessage::Pong => Ok(true),
+        NatsMessage::Err(err) => Err(NatsError::Rejected(err)),
+        _ => Ok(false),
+    }
+}
+
+/// Creates `SUB` message for a `subject`.
+pub(crate) fn subscribe(subject: &str) -> Vec<u8> {
+    format!("SUB {subject} {SUBSCRIPTION_ID}\r\n").into_bytes()
+}
+
+/// Creates `PUB` message.
+pub(crate) fn publish(subject: &str, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = format!("PUB {subject} {}\r\n", payload.len()).into_bytes();
+    bytes.extend_from_slice(payload);
+    bytes.extend_from_slice(CRLF);
+    bytes
+}
+
+/// Checks, that `subject` can be used both for publishing and subscription.
+pub(crate) fn is_valid_subject(subject: &str) -> bool {
+    !subject.is_empty()
+        && subject
+            .split('.')
+            .all(|token| !token.is_empty() && token != "*" && token != ">")
+        && !subject.chars().any(char::is_whitespace)
+}
+
+fn parse_msg_args(args: &str) -> Result<(String, usize), NatsError> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+    // MSG <subject> <sid> [reply-to] <#bytes>
+    let (subject, size) = match args.as_slice() {
+        [subject, _, size] | [subject, _, _, size] => (subject, size),
+        _ => return Err(NatsError::MalformedMessage),
+    };
+    let size: usize = size.parse().map_err(|_| NatsError::MalformedMessage)?;
+    if size > MAX_PAYLOAD_SIZE {
+        return Err(NatsError::MalformedMessage);
+    }
+    Ok((subject.to_string(), size))
+}
+
+fn find_crlf(bytes: &[u8]) -> Option<usize> {
+    bytes.windows(CRLF.len()).position(|window| window == CRLF)
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            char if char.is_control() => result.push_str(&format!("\\u{:04x}", char as u32)),
+            char => result.push(char),
+        }
+    }
+    result.push('"');
+    result
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod wire_tests {
+    use super::*;
+
+    #[test]
+    fn session_is_established() {
+        check_info(NatsMessage::Info("{\"server_id\":\"test\"}".to_string())).unwrap();
+        assert!(matches!(
+            check_info(NatsMessage::Info("{\"tls_required\": true}".to_string())),
+            Err(NatsError::TlsRequired)
+        ));
+
+        let bytes = connect(&NatsSession {
+            name: "maviola",
+            credentials: Some(("user", "se\"cret")),
+        });
+        let bytes = String::from_utf8(bytes).unwrap();
+        assert!(bytes.starts_with("CONNECT {"));
+        assert!(bytes.contains("\"echo\":false"));
+        assert!(bytes.contains("\"pass\":\"se\\\"cret\""));
+        assert!(bytes.ends_with("}\r\nPING\r\n"));
+
+        assert!(!check_connected(NatsMessage::Ok).unwrap());
+        assert!(check_connected(NatsMessage::Pong).unwrap());
+        assert!(matches!(
+            check_connected(NatsMessage::Err("Authorization Violation".to_string())),
+            Err(NatsError::Rejected(err)) if err == "Authorization Violation"
+        ));
+
+        assert_eq!(subscribe("vehicle.1"), b"SUB vehicle.1 1\r\n");
+        assert!(is_valid_subject("vehicle.1"));
+        assert!(!is_valid_subject("vehicle.*"));
+        assert!(!is_valid_subject("vehicle 1"));
+    }
+
+    #[test]
+    fn messages_are_decoded_incrementally() {
+        let payload = vec![0xFD, b'\r', b'\n', 42];
+        let mut bytes = b"INFO {\"server_id\":\"test\"}\r\nPING\r\n".to_vec();
+        bytes.extend(b"MSG vehicle.1 1 4\r\n");
+        bytes.extend(&payload);
+        bytes.extend(b"\r\nmsg vehicle.1 1 inbox.1 1\r\nX\r\n-ERR 'Stale Connection'\r\n");
+
+        let mut decoder = NatsDecoder::default();
+        let mut messages = Vec::new();
+        for byte in bytes {
+            decoder.push(&[byte]);
+            while let Some(message) = decoder.next().unwrap() {
+                messages.push(message);
+            }
+        }
+
+        assert_eq!(
+            messages,
+            vec![
+                NatsMessage::Info("{\"server_id\":\"test\"}".to_string()),
+                NatsMessage::Ping,
+                NatsMessage::Msg {
+                    subject: "vehicle.1".to_string(),
+                    payload,
+                },
+                NatsMessage::Msg {
+                    subject: "vehicle.1".to_string(),
+                    payload: b"X".to_vec(),
+                },
+                NatsMessage::Err("Stale Connection".to_string()),
+            ]
+        );
+
+        let mut decoder = NatsDecoder::default();
+        decoder.push(b"MSG vehicle.1 1 1\r\nXYZ");
+        assert!(matches!(decoder.next(), Err(NatsError::MalformedMessage)));
+    }
+
+    #[test]
+    fn frames_are_published_to_subject() {
+        let mut publisher = NatsPublisher::new("vehicle.1");
+        // Heartbeat header of a MAVLink 1 frame with a 9-byte payload
+        let frame = [[0xFE, 9, 0, 1, 1, 0].as_slice(), &[0; 11]].concat();
+
+        publisher.push(&frame[..3]);
+        assert!(publisher.next().is_none());
+        publisher.push(&frame[3..]);
+
+        let mut expected = b"PUB vehicle.1 17\r\n".to_vec();
+        expected.extend(&frame);
+        expected.extend(CRLF);
+        assert_eq!(publisher.next().unwrap(), expected);
+    }
+}
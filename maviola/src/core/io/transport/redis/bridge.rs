@@ -0,0 +1,116 @@
+use std::net::SocketAddr;
+
+use crate::core::io::transport::redis::parse_server;
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// <sup>`redis`</sup>
+/// Redis Pub/Sub bridge configuration.
+///
+/// Maps connection onto a [Redis](https://redis.io/) Pub/Sub channel. Outgoing frames are
+/// published to the channel and frames published to the same channel by other clients are
+/// received. This allows several services to share one vehicle link through an existing message
+/// broker. Server connection is a single channel.
+///
+/// Each frame is published as a separate message, that contains serialized MAVLink frame. Bridge
+/// opens two server connections, since Redis does not accept commands from subscribed clients.
+/// Frames published by the bridge itself are filtered out from the subscription.
+///
+/// Only plain TCP connections are supported.
+///
+/// # Usage
+///
+/// Create a synchronous bridge node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::RedisBridge;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             RedisBridge::new("redis://127.0.0.1:6379", "vehicles:1:mavlink")   // Configure Redis bridge
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous bridge node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::RedisBridge;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             RedisBridge::new("redis://127.0.0.1:6379", "vehicles:1:mavlink")   // Configure Redis bridge
+///                 .unwrap()
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RedisBridge {
+    pub(crate) addr: SocketAddr,
+    pub(crate) channel: String,
+    pub(crate) credentials: Option<(Option<String>, String)>,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl RedisBridge {
+    /// Instantiates a Redis bridge configuration for a Pub/Sub `channel`.
+    ///
+    /// Accepts `redis://` or `tcp://` server URLs such as `redis://127.0.0.1:6379`. Scheme can be
+    /// omitted, port defaults to `6379`. Use [`RedisBridge::with_password`] or
+    /// [`RedisBridge::with_credentials`] instead of putting credentials into URL.
+    pub fn new(server: &str, channel: &str) -> Result<Self> {
+        let addr = parse_server(server)?;
+
+        Ok(Self {
+            addr,
+            channel: channel.to_string(),
+            credentials: None,
+            info: ConnectionInfo::new(ConnectionDetails::RedisBridge {
+                server_addr: addr,
+                channel: channel.to_string(),
+            }),
+        })
+    }
+
+    /// Sets server password.
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.credentials = Some((None, password.into()));
+        self
+    }
+
+    /// Sets user name and password.
+    ///
+    /// Requires server with ACL support.
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((Some(username.into()), password.into()));
+        self
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for RedisBridge {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
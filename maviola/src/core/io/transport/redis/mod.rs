@@ -0,0 +1,25 @@
+pub mod bridge;
+pub(crate) mod wire;
+
+use std::net::SocketAddr;
+
+use crate::core::utils::net::{resolve_with_default_port, strip_url_scheme};
+use crate::error::RedisError;
+
+use crate::prelude::*;
+
+const REDIS_SCHEMES: [&str; 2] = ["redis://", "tcp://"];
+const DEFAULT_REDIS_PORT: u16 = 6379;
+
+/// Parses Redis server URL.
+///
+/// Accepts `redis://` and `tcp://` URLs as well as plain addresses. Port defaults to `6379`.
+/// Credentials and database number are not accepted as a part of URL.
+fn parse_server(url: &str) -> Result<SocketAddr> {
+    match strip_url_scheme(url, &REDIS_SCHEMES) {
+        Some(addr) if !addr.contains(['@', '/']) => {
+            resolve_with_default_port(addr, DEFAULT_REDIS_PORT)
+        }
+        _ => Err(RedisError::UnsupportedServer(url.to_string()).into()),
+    }
+}
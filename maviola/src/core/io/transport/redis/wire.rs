@@ -0,0 +1,355 @@
+//! Minimal implementation of [RESP2](https://redis.io/docs/latest/develop/reference/protocol-spec/)
+//! protocol for Redis Pub/Sub.
+//!
+//! Codec does not perform any I/O and is shared by synchronous and asynchronous transports.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::core::io::FrameSplitter;
+use crate::error::RedisError;
+
+/// Size of a buffer for reading from Redis streams.
+pub(crate) const READ_BUFFER_SIZE: usize = 1024;
+
+const CRLF: &[u8] = b"\r\n";
+/// Lines longer than this are considered malformed.
+const MAX_LINE_SIZE: usize = 64 * 1024;
+/// Bulk strings larger than this are considered malformed, MAVLink frames are much smaller.
+const MAX_BULK_SIZE: usize = 256 * 1024;
+/// Maximum number of published messages awaiting their echo.
+const MAX_PENDING_ECHOES: usize = 1024;
+
+/// Reply received from a Redis server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RedisReply {
+    /// Simple string.
+    Status(String),
+    /// Error message.
+    Error(String),
+    /// Integer.
+    Integer(i64),
+    /// Bulk string or null.
+    Bulk(Option<Vec<u8>>),
+    /// Array or null.
+    Array(Option<Vec<RedisReply>>),
+}
+
+/// Incremental decoder of Redis replies.
+#[derive(Debug, Default)]
+pub(crate) struct RedisDecoder {
+    buffer: Vec<u8>,
+}
+
+/// Messages published by a bridge, that are expected to be delivered back to its subscription.
+///
+/// Redis delivers published messages to all subscribers including the publisher itself. Since
+/// messages of a single client are delivered in order, echoes are recognized by comparing
+/// incoming payloads with the oldest published ones.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Echoes {
+    pending: Arc<Mutex<VecDeque<Vec<u8>>>>,
+}
+
+/// Converts a stream of serialized MAVLink frames into `PUBLISH` commands.
+#[derive(Debug)]
+pub(crate) struct RedisPublisher {
+    channel: String,
+    splitter: FrameSplitter,
+    echoes: Echoes,
+}
+
+impl RedisDecoder {
+    /// Appends bytes received from a server.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next complete reply.
+    ///
+    /// Returns [`None`], if more bytes are required.
+    pub(crate) fn next(&mut self) -> Result<Option<RedisReply>, RedisError> {
+        match parse_reply(&self.buffer)? {
+            Some((reply, size)) => {
+                self.buffer.drain(..size);
+                Ok(Some(reply))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Echoes {
+    /// Remembers a published message.
+    pub(crate) fn record(&self, payload: &[u8]) {
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(err) => err.into_inner(),
+        };
+        if pending.len() >= MAX_PENDING_ECHOES {
+            pending.pop_front();
+        }
+        pending.push_back(payload.to_vec());
+    }
+
+    /// Checks, whether an incoming message is an echo of a published one.
+    ///
+    /// Older published messages are forgotten, since their echoes will never arrive.
+    pub(crate) fn is_echo(&self, payload: &[u8]) -> bool {
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(err) => err.into_inner(),
+        };
+        match pending.iter().position(|published| published == payload) {
+            Some(idx) => {
+                pending.drain(..=idx);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl RedisPublisher {
+    /// Creates publisher, that records published messages to `echoes`.
+    pub(crate) fn new(channel: &str, echoes: Echoes) -> Self {
+        Self {
+            channel: channel.to_string(),
+            splitter: FrameSplitter::default(),
+            echoes,
+        }
+    }
+
+    /// Appends serialized frame bytes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.splitter.push(bytes);
+    }
+
+    /// Returns the next `PUBLISH` command, if a complete frame is available.
+    pub(crate) fn next(&mut self) -> Option<Vec<u8>> {
+        let frame = self.splitter.next()?;
+        self.echoes.record(&frame);
+        Some(command(&[b"PUBLISH", self.channel.as_bytes(), &frame]))
+    }
+}
+
+/// Creates `AUTH` command.
+///
+/// Without `username` authenticates with a legacy server password.
+pub(crate) fn auth(username: Option<&str>, password: &str) -> Vec<u8> {
+    match username {
+        Some(username) => command(&[b"AUTH", username.as_bytes(), password.as_bytes()]),
+        None => command(&[b"AUTH", password.as_bytes()]),
+    }
+}
+
+/// Validates server response to `AUTH` command.
+pub(crate) fn check_ok(reply: RedisReply) -> Result<(), RedisError> {
+    match reply {
+        RedisReply::Status(status) if status == "OK" => Ok(()),
+        RedisReply::Error(err) => Err(RedisError::Rejected(err)),
+        _ => Err(RedisError::MalformedReply),
+    }
+}
+
+/// Creates `SUBSCRIBE` command for a `channel`.
+pub(crate) fn subscribe(channel: &str) -> Vec<u8> {
+    command(&[b"SUBSCRIBE", channel.as_bytes()])
+}
+
+/// Validates server response to `SUBSCRIBE` command.
+pub(crate) fn check_subscribed(reply: RedisReply, channel: &str) -> Result<(), RedisError> {
+    match reply {
+        RedisReply::Error(err) => Err(RedisError::Rejected(err)),
+        RedisReply::Array(Some(items)) => match items.as_slice() {
+            [RedisReply::Bulk(Some(kind)), RedisReply::Bulk(Some(name)), RedisReply::Integer(_)]
+                if kind == b"subscribe" && name == channel.as_bytes() =>
+            {
+                Ok(())
+            }
+            _ => Err(RedisError::MalformedReply),
+        },
+        _ => Err(RedisError::MalformedReply),
+    }
+}
+
+/// Extracts payload of a message published to a `channel`.
+///
+/// Other replies are ignored.
+pub(crate) fn published_payload(reply: RedisReply, channel: &str) -> Option<Vec<u8>> {
+    let items = match reply {
+        RedisReply::Array(Some(items)) => items,
+        _ => return None,
+    };
+    match <[RedisReply; 3]>::try_from(items) {
+        Ok(
+            [RedisReply::Bulk(Some(kind)), RedisReply::Bulk(Some(name)), RedisReply::Bulk(Some(payload))],
+        ) if kind == b"message" && name == channel.as_bytes() => Some(payload),
+        _ => None,
+    }
+}
+
+/// Encodes command as an array of bulk strings.
+fn command(args: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        bytes.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        bytes.extend_from_slice(arg);
+        bytes.extend_from_slice(CRLF);
+    }
+    bytes
+}
+
+/// Parses reply at the beginning of `bytes`.
+///
+/// Returns reply and its size, or [`None`], if more bytes are required.
+fn parse_reply(bytes: &[u8]) -> Result<Option<(RedisReply, usize)>, RedisError> {
+    let line_size = match bytes.windows(CRLF.len()).position(|window| window == CRLF) {
+        Some(size) => size,
+        None if bytes.len() > MAX_LINE_SIZE => return Err(RedisError::MalformedReply),
+        None => return Ok(None),
+    };
+    let line = String::from_utf8_lossy(&bytes[1..line_size]).to_string();
+    let mut size = line_size + CRLF.len();
+
+    let reply = match bytes[0] {
+        b'+' => RedisReply::Status(line),
+        b'-' => RedisReply::Error(line),
+        b':' => RedisReply::Integer(parse_len(&line)?),
+        b'$' => match parse_len(&line)? {
+            -1 => RedisReply::Bulk(None),
+            len if len < 0 || len as usize > MAX_BULK_SIZE => {
+                return Err(RedisError::MalformedReply)
+            }
+            len => {
+                let start = size;
+                size += len as usize + CRLF.len();
+                if bytes.len() < size {
+                    return Ok(None);
+                }
+                if &bytes[size - CRLF.len()..size] != CRLF {
+                    return Err(RedisError::MalformedReply);
+                }
+                RedisReply::Bulk(Some(bytes[start..start + len as usize].to_vec()))
+            }
+        },
+        b'*' => match parse_len(&line)? {
+            -1 => RedisReply::Array(None),
+            len if len < 0 => return Err(RedisError::MalformedReply),
+            len => {
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    match parse_reply(&bytes[size..])? {
+                        Some((item, item_size)) => {
+                            items.push(item);
+                            size += item_size;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                RedisReply::Array(Some(items))
+            }
+        },
+        _ => return Err(RedisError::MalformedReply),
+    };
+
+    Ok(Some((reply, size)))
+}
+
+fn parse_len(line: &str) -> Result<i64, RedisError> {
+    line.parse().map_err(|_| RedisError::MalformedReply)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod wire_tests {
+    use super::*;
+
+    #[test]
+    fn session_is_established() {
+        assert_eq!(
+            auth(Some("default"), "secret"),
+            b"*3\r\n$4\r\nAUTH\r\n$7\r\ndefault\r\n$6\r\nsecret\r\n"
+        );
+        assert_eq!(
+            auth(None, "secret"),
+            b"*2\r\n$4\r\nAUTH\r\n$6\r\nsecret\r\n"
+        );
+        check_ok(RedisReply::Status("OK".to_string())).unwrap();
+        assert!(matches!(
+            check_ok(RedisReply::Error("WRONGPASS invalid".to_string())),
+            Err(RedisError::Rejected(err)) if err == "WRONGPASS invalid"
+        ));
+
+        assert_eq!(
+            subscribe("mavlink"),
+            b"*2\r\n$9\r\nSUBSCRIBE\r\n$7\r\nmavlink\r\n"
+        );
+        let mut decoder = RedisDecoder::default();
+        decoder.push(b"*3\r\n$9\r\nsubscribe\r\n$7\r\nmavlink\r\n:1\r\n");
+        check_subscribed(decoder.next().unwrap().unwrap(), "mavlink").unwrap();
+    }
+
+    #[test]
+    fn replies_are_decoded_incrementally() {
+        let payload = vec![0xFD, b'\r', b'\n', 42];
+        let mut bytes = b"*3\r\n$7\r\nmessage\r\n$7\r\nmavlink\r\n$4\r\n".to_vec();
+        bytes.extend(&payload);
+        bytes.extend(b"\r\n:2\r\n$-1\r\n*-1\r\n-ERR unknown\r\n");
+
+        let mut decoder = RedisDecoder::default();
+        let mut replies = Vec::new();
+        for byte in bytes {
+            decoder.push(&[byte]);
+            while let Some(reply) = decoder.next().unwrap() {
+                replies.push(reply);
+            }
+        }
+
+        assert_eq!(replies.len(), 5);
+        assert_eq!(
+            published_payload(replies[0].clone(), "mavlink"),
+            Some(payload)
+        );
+        assert_eq!(published_payload(replies[0].clone(), "other"), None);
+        assert_eq!(
+            &replies[1..],
+            &[
+                RedisReply::Integer(2),
+                RedisReply::Bulk(None),
+                RedisReply::Array(None),
+                RedisReply::Error("ERR unknown".to_string()),
+            ]
+        );
+
+        let mut decoder = RedisDecoder::default();
+        decoder.push(b"$1\r\nXYZ");
+        assert!(matches!(decoder.next(), Err(RedisError::MalformedReply)));
+    }
+
+    #[test]
+    fn echoes_are_recognized() {
+        let echoes = Echoes::default();
+        let mut publisher = RedisPublisher::new("mavlink", echoes.clone());
+        // Heartbeat header of a MAVLink 1 frame with a 9-byte payload
+        let frame = [[0xFE, 9, 0, 1, 1, 0].as_slice(), &[0; 11]].concat();
+        let other = [[0xFE, 9, 1, 1, 1, 0].as_slice(), &[0; 11]].concat();
+
+        publisher.push(&frame[..3]);
+        assert!(publisher.next().is_none());
+        publisher.push(&frame[3..]);
+        assert_eq!(
+            publisher.next().unwrap(),
+            command(&[b"PUBLISH", b"mavlink", &frame])
+        );
+        publisher.push(&other);
+        publisher.next().unwrap();
+
+        // Echo of the second message means that the first one won't arrive
+        assert!(echoes.is_echo(&other));
+        assert!(!echoes.is_echo(&frame));
+    }
+}
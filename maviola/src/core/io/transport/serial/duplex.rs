@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use crate::core::consts::DEFAULT_HALF_DUPLEX_TURNAROUND;
+
+/// <sup>[`sync`](crate::sync) | `serial`</sup>
+/// Half-duplex mode of a [`SerialPort`](crate::core::io::SerialPort).
+///
+/// On half-duplex links (single-wire UART, shared RS-485 bus) only one side can transmit at a
+/// time. In half-duplex mode transmission is gated by incoming traffic:
+///
+/// * A frame is transmitted only once the line was idle (no incoming bytes) for at least
+///   [`rx_turnaround`](Self::rx_turnaround), so the peer has time to switch its transceiver back
+///   to receiving.
+/// * Once the frame was physically transmitted, the line is held for
+///   [`tx_turnaround`](Self::tx_turnaround), before the next frame can be sent.
+/// * With [`backoff`](Self::backoff) enabled, a transmitter, that found the line busy, waits for
+///   a random part of the backoff window after the line became idle and checks it again. This
+///   carrier-sense-style backoff prevents several devices from starting transmission at the same
+///   moment.
+///
+/// By default, both turnaround delays are equal to [`DEFAULT_HALF_DUPLEX_TURNAROUND`] and backoff
+/// is disabled.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use maviola::core::io::HalfDuplex;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             SerialPort::new("/dev/ttyUSB0", 57600)
+///                 .unwrap()
+///                 .half_duplex(                       // Enable half-duplex mode
+///                     HalfDuplex::new()
+///                         .rx_turnaround(Duration::from_millis(5))
+///                         .backoff(Duration::from_millis(20))
+///                 )
+///         ).build().unwrap();
+/// ```
+///
+/// [`DEFAULT_HALF_DUPLEX_TURNAROUND`]: crate::core::consts::DEFAULT_HALF_DUPLEX_TURNAROUND
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HalfDuplex {
+    pub(crate) rx_turnaround: Duration,
+    pub(crate) tx_turnaround: Duration,
+    pub(crate) backoff: Option<Duration>,
+}
+
+impl HalfDuplex {
+    /// Creates half-duplex settings with default turnaround delays and without backoff.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets minimum time the line should be idle after the last incoming byte before transmitting.
+    pub fn rx_turnaround(self, delay: Duration) -> Self {
+        Self {
+            rx_turnaround: delay,
+            ..self
+        }
+    }
+
+    /// Sets time the line is held after transmission, before the next frame can be sent.
+    pub fn tx_turnaround(self, delay: Duration) -> Self {
+        Self {
+            tx_turnaround: delay,
+            ..self
+        }
+    }
+
+    /// Enables carrier-sense-style backoff with the specified maximum `window`.
+    ///
+    /// Zero `window` disables backoff.
+    pub fn backoff(self, window: Duration) -> Self {
+        Self {
+            backoff: (!window.is_zero()).then_some(window),
+            ..self
+        }
+    }
+
+    /// Minimum time the line should be idle after the last incoming byte before transmitting.
+    pub fn rx_turnaround_delay(&self) -> Duration {
+        self.rx_turnaround
+    }
+
+    /// Time the line is held after transmission.
+    pub fn tx_turnaround_delay(&self) -> Duration {
+        self.tx_turnaround
+    }
+
+    /// Maximum backoff window, if backoff is enabled.
+    pub fn backoff_window(&self) -> Option<Duration> {
+        self.backoff
+    }
+}
+
+impl Default for HalfDuplex {
+    fn default() -> Self {
+        Self {
+            rx_turnaround: DEFAULT_HALF_DUPLEX_TURNAROUND,
+            tx_turnaround: DEFAULT_HALF_DUPLEX_TURNAROUND,
+            backoff: None,
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod duplex;
+pub mod port;
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo, HalfDuplex};
+
+use crate::prelude::*;
+
+/// <sup>[`sync`](crate::sync) | `serial`</sup>
+/// Serial port connection configuration.
+///
+/// Connects to a serial device such as a USB-to-UART adapter or a flight controller attached over
+/// USB. The port is configured in raw mode with 8 data bits, no parity, one stop bit, and without
+/// flow control.
+///
+/// On Unix-like systems `path` is a path to a device (for example, `/dev/ttyUSB0`). On Windows
+/// it is a port name (for example, `COM3`), which is converted to a device path automatically.
+///
+/// Half-duplex links (single-wire UART, shared RS-485 bus) are supported by
+/// [`SerialPort::half_duplex`].
+///
+/// Nodes built with [`SerialPort`] will try to reopen the port once it was closed (for example,
+/// when a USB device was unplugged).
+///
+/// Available only for synchronous API.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             SerialPort::new("/dev/ttyUSB0", 57600)    // Configure serial port connection
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct SerialPort {
+    pub(crate) path: PathBuf,
+    pub(crate) baud_rate: u32,
+    pub(crate) half_duplex: Option<HalfDuplex>,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl SerialPort {
+    /// Instantiates a serial port configuration.
+    ///
+    /// Accepts as `path` anything that can be converted to [`PathBuf`]. The port is not opened
+    /// until the node is built, so this method only validates, that `baud_rate` is not zero.
+    pub fn new(path: impl Into<PathBuf>, baud_rate: u32) -> Result<Self> {
+        let path: PathBuf = path.into();
+
+        if baud_rate == 0 {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid baud rate for {path:?}: {baud_rate}"),
+            )));
+        }
+
+        let info = ConnectionInfo::new(ConnectionDetails::SerialPort {
+            path: path.clone(),
+            baud_rate,
+        });
+        Ok(Self {
+            path,
+            baud_rate,
+            half_duplex: None,
+            info,
+        })
+    }
+
+    /// Enables half-duplex mode with specified settings.
+    ///
+    /// See [`HalfDuplex`] for details.
+    pub fn half_duplex(self, settings: HalfDuplex) -> Self {
+        Self {
+            half_duplex: Some(settings),
+            ..self
+        }
+    }
+
+    /// Path to a serial device.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Baud rate.
+    pub fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    /// Half-duplex settings.
+    ///
+    /// Returns [`None`] if port operates in full-duplex mode.
+    pub fn half_duplex_settings(&self) -> Option<&HalfDuplex> {
+        self.half_duplex.as_ref()
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for SerialPort {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
 
 use crate::prelude::*;
 
@@ -75,6 +75,14 @@ impl SockServer {
         let info = ConnectionInfo::new(ConnectionDetails::SockServer { path: path.clone() });
         Ok(Self { path, info })
     }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
 }
 
 impl ConnectionConf for SockServer {
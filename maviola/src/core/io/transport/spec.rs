@@ -0,0 +1,273 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[cfg(feature = "serial")]
+use crate::core::consts::DEFAULT_SERIAL_BAUD_RATE;
+#[cfg(feature = "serial")]
+use crate::core::io::SerialPort;
+use crate::core::io::{
+    ConnectionConf, ConnectionDetails, ConnectionInfo, TcpClient, TcpServer, UdpClient, UdpServer,
+};
+
+use crate::prelude::*;
+
+/// Connection configuration parsed from an endpoint URL.
+///
+/// Allows applications to accept connection endpoints from command line arguments or configuration
+/// files. Follows the URL conventions of `pymavlink` and `mavlink-router`:
+///
+/// | URL                             | Connection                        |
+/// |---------------------------------|-----------------------------------|
+/// | `tcp:host:port`                 | [`TcpClient`]                     |
+/// | `tcpout:host:port`              | [`TcpClient`]                     |
+/// | `tcpin:host:port`               | [`TcpServer`]                     |
+/// | `udp:host:port`                 | [`UdpServer`]                     |
+/// | `udpin:host:port`               | [`UdpServer`]                     |
+/// | `udpout:host:port`              | [`UdpClient`]                     |
+/// | `serial:path[:baud_rate]`       | `SerialPort` (`serial` feature)   |
+///
+/// MAVSDK-style URLs with `//` after the scheme (i.e. `udpin://0.0.0.0:14550`) are accepted as
+/// well. Empty host (i.e. `udp://:14550`) means all interfaces. Serial baud rate may be separated
+/// either by `:` or `,` and defaults to [`DEFAULT_SERIAL_BAUD_RATE`] when omitted.
+///
+/// [`DEFAULT_SERIAL_BAUD_RATE`]: crate::core::consts::DEFAULT_SERIAL_BAUD_RATE
+///
+/// Connection specs are created by `Connection::parse` of the corresponding API or
+/// [`ConnectionSpec::parse`], and can be used wherever the wrapped connection can. Specs are
+/// displayed and (de)serialized as canonical URLs (i.e. `udp:` becomes `udpin:`).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::sync::io::Connection;
+///
+/// let node = Node::sync::<V2>()
+///     /* define other node parameters */
+/// #   .system_id(1)
+/// #   .component_id(1)
+///     .connection(Connection::parse("tcpout:127.0.0.1:5760").unwrap())
+///     .build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub enum ConnectionSpec {
+    /// TCP client (`tcp:` or `tcpout:`).
+    TcpClient(TcpClient),
+    /// TCP server (`tcpin:`).
+    TcpServer(TcpServer),
+    /// UDP client (`udpout:`).
+    UdpClient(UdpClient),
+    /// UDP server (`udp:` or `udpin:`).
+    UdpServer(UdpServer),
+    /// Serial port (`serial:`).
+    #[cfg(feature = "serial")]
+    SerialPort(SerialPort),
+}
+
+impl ConnectionSpec {
+    /// Parses connection configuration from an endpoint `url`.
+    ///
+    /// Returns [`std::io::ErrorKind::InvalidInput`] error if URL is malformed, its scheme is
+    /// unknown, or its address can't be resolved.
+    pub fn parse(url: &str) -> Result<Self> {
+        let (scheme, location) = url
+            .split_once(':')
+            .ok_or_else(|| invalid_url(url, "missing scheme"))?;
+        let location = location.strip_prefix("//").unwrap_or(location);
+
+        match scheme.to_ascii_lowercase().as_str() {
+            "tcp" | "tcpout" => Ok(Self::TcpClient(TcpClient::new(socket_addr(location))?)),
+            "tcpin" => Ok(Self::TcpServer(TcpServer::new(socket_addr(location))?)),
+            "udp" | "udpin" => Ok(Self::UdpServer(UdpServer::new(socket_addr(location))?)),
+            "udpout" => Ok(Self::UdpClient(UdpClient::new(socket_addr(location))?)),
+            #[cfg(feature = "serial")]
+            "serial" => {
+                let (path, baud_rate) = serial_location(url, location)?;
+                Ok(Self::SerialPort(SerialPort::new(path, baud_rate)?))
+            }
+            #[cfg(not(feature = "serial"))]
+            "serial" => Err(invalid_url(url, "serial ports require `serial` feature")),
+            _ => Err(invalid_url(url, "unknown scheme")),
+        }
+    }
+}
+
+impl FromStr for ConnectionSpec {
+    type Err = Error;
+
+    fn from_str(url: &str) -> Result<Self> {
+        Self::parse(url)
+    }
+}
+
+impl Display for ConnectionSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.info().details() {
+            ConnectionDetails::TcpClient { remote_addr } => write!(f, "tcpout:{remote_addr}"),
+            ConnectionDetails::TcpServer { bind_addr } => write!(f, "tcpin:{bind_addr}"),
+            ConnectionDetails::UdpClient { remote_addr } => write!(f, "udpout:{remote_addr}"),
+            ConnectionDetails::UdpServer { bind_addr } => write!(f, "udpin:{bind_addr}"),
+            #[cfg(feature = "serial")]
+            ConnectionDetails::SerialPort { path, baud_rate } => {
+                write!(f, "serial:{}:{baud_rate}", path.display())
+            }
+            details => write!(f, "{details:?}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConnectionSpec {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ConnectionSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> std::result::Result<Self, D::Error> {
+        let url = String::deserialize(d)?;
+        Self::parse(&url).map_err(serde::de::Error::custom)
+    }
+}
+
+impl ConnectionConf for ConnectionSpec {
+    fn info(&self) -> &ConnectionInfo {
+        match self {
+            ConnectionSpec::TcpClient(conn) => conn.info(),
+            ConnectionSpec::TcpServer(conn) => conn.info(),
+            ConnectionSpec::UdpClient(conn) => conn.info(),
+            ConnectionSpec::UdpServer(conn) => conn.info(),
+            #[cfg(feature = "serial")]
+            ConnectionSpec::SerialPort(conn) => conn.info(),
+        }
+    }
+}
+
+/// Replaces empty host with unspecified address, so `:14550` binds to all interfaces.
+fn socket_addr(location: &str) -> String {
+    match location.strip_prefix(':') {
+        Some(port) => format!("0.0.0.0:{port}"),
+        None => location.to_string(),
+    }
+}
+
+#[cfg(feature = "serial")]
+fn serial_location<'a>(url: &str, location: &'a str) -> Result<(&'a str, u32)> {
+    let (path, baud_rate) = match location.rsplit_once([':', ',']) {
+        // Windows paths like `C:\...` and `\\.\COM3` contain no baud rate after the separator
+        Some((path, baud_rate)) if baud_rate.chars().all(|c| c.is_ascii_digit()) => {
+            let baud_rate = baud_rate
+                .parse()
+                .map_err(|_| invalid_url(url, "invalid baud rate"))?;
+            (path, baud_rate)
+        }
+        _ => (location, DEFAULT_SERIAL_BAUD_RATE),
+    };
+
+    if path.is_empty() {
+        return Err(invalid_url(url, "missing serial port path"));
+    }
+    Ok((path, baud_rate))
+}
+
+fn invalid_url(url: &str, reason: &str) -> Error {
+    Error::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("invalid connection URL {url:?}: {reason}"),
+    ))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod spec_tests {
+    use super::*;
+    use crate::core::io::ConnectionDetails;
+
+    fn details(url: &str) -> ConnectionDetails {
+        ConnectionSpec::parse(url).unwrap().info().details().clone()
+    }
+
+    #[test]
+    fn network_urls_are_parsed() {
+        assert!(matches!(
+            details("tcpout:127.0.0.1:5760"),
+            ConnectionDetails::TcpClient { remote_addr } if remote_addr.port() == 5760
+        ));
+        assert!(matches!(
+            details("tcp:127.0.0.1:5760"),
+            ConnectionDetails::TcpClient { .. }
+        ));
+        assert!(matches!(
+            details("tcpin:127.0.0.1:5760"),
+            ConnectionDetails::TcpServer { .. }
+        ));
+        assert!(matches!(
+            details("udpin:0.0.0.0:14550"),
+            ConnectionDetails::UdpServer { bind_addr } if bind_addr.port() == 14550
+        ));
+        assert!(matches!(
+            details("udpout:127.0.0.1:14550"),
+            ConnectionDetails::UdpClient { .. }
+        ));
+        assert!(matches!(
+            details("udp://:14540"),
+            ConnectionDetails::UdpServer { bind_addr } if bind_addr.ip().is_unspecified()
+        ));
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected() {
+        assert!(ConnectionSpec::parse("127.0.0.1:5760").is_err());
+        assert!(ConnectionSpec::parse("ftp:127.0.0.1:5760").is_err());
+        assert!(ConnectionSpec::parse("tcpout:127.0.0.1").is_err());
+        assert!("udpin:0.0.0.0:14550".parse::<ConnectionSpec>().is_ok());
+    }
+
+    #[test]
+    fn specs_are_displayed_as_canonical_urls() {
+        for (url, canonical) in [
+            ("tcp:127.0.0.1:5760", "tcpout:127.0.0.1:5760"),
+            ("tcpin:127.0.0.1:5760", "tcpin:127.0.0.1:5760"),
+            ("udp://:14550", "udpin:0.0.0.0:14550"),
+            ("udpout:127.0.0.1:14550", "udpout:127.0.0.1:14550"),
+        ] {
+            let spec = ConnectionSpec::parse(url).unwrap();
+            assert_eq!(spec.to_string(), canonical);
+            assert_eq!(
+                ConnectionSpec::parse(&spec.to_string())
+                    .unwrap()
+                    .to_string(),
+                canonical
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serial")]
+    fn serial_urls_are_parsed() {
+        let spec = |url| serial_location(url, url.split_once(':').unwrap().1).unwrap();
+
+        assert_eq!(spec("serial:/dev/ttyACM0:115200"), ("/dev/ttyACM0", 115200));
+        assert_eq!(spec("serial:/dev/ttyUSB0,921600"), ("/dev/ttyUSB0", 921600));
+        assert_eq!(
+            spec("serial:/dev/ttyS0"),
+            ("/dev/ttyS0", DEFAULT_SERIAL_BAUD_RATE)
+        );
+        assert!(matches!(
+            details("serial:/dev/ttyACM0:115200"),
+            ConnectionDetails::SerialPort {
+                baud_rate: 115200,
+                ..
+            }
+        ));
+        assert!(ConnectionSpec::parse("serial:").is_err());
+        assert!(ConnectionSpec::parse("serial:/dev/ttyACM0:0").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use mavio::consts::{
+    CHECKSUM_SIZE, HEADER_V1_SIZE, HEADER_V2_SIZE, SIGNATURE_LENGTH, STX_V1, STX_V2,
+};
+
+/// MAVLink `v2` incompatibility flag of signed frames.
+const MAVLINK_IFLAG_SIGNED: u8 = 0x01;
+
+/// Splits a stream of serialized MAVLink frames into separate frames.
+///
+/// Used by message-oriented transports, that wrap each frame into a separate message.
+#[derive(Debug, Default)]
+pub(crate) struct FrameSplitter {
+    buffer: Vec<u8>,
+}
+
+impl FrameSplitter {
+    /// Appends serialized frame bytes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the next complete frame, if available.
+    pub(crate) fn next(&mut self) -> Option<Vec<u8>> {
+        let size = frame_size(&self.buffer)?;
+        if self.buffer.len() < size {
+            return None;
+        }
+        Some(self.buffer.drain(..size).collect())
+    }
+}
+
+/// Size of a serialized MAVLink frame, that starts at the beginning of `bytes`.
+///
+/// Unknown bytes are passed one by one.
+fn frame_size(bytes: &[u8]) -> Option<usize> {
+    let payload_size = *bytes.get(1)? as usize;
+    match bytes[0] {
+        STX_V1 => Some(HEADER_V1_SIZE + payload_size + CHECKSUM_SIZE),
+        STX_V2 => {
+            let signature_size = match bytes.get(2)? & MAVLINK_IFLAG_SIGNED {
+                0 => 0,
+                _ => SIGNATURE_LENGTH,
+            };
+            Some(HEADER_V2_SIZE + payload_size + CHECKSUM_SIZE + signature_size)
+        }
+        _ => Some(1),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod splitter_tests {
+    use super::*;
+
+    use mavio::io::Sender;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, MavLinkId, V1, V2};
+
+    #[test]
+    fn frames_are_split() {
+        let v1 = Endpoint::v1(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let v2 = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        Sender::versioned(&mut bytes, V1).send(&v1).unwrap();
+        let v1_size = bytes.len();
+        Sender::versioned(&mut bytes, V2).send(&v2).unwrap();
+
+        let mut splitter = FrameSplitter::default();
+        splitter.push(&bytes[..v1_size + 3]);
+        assert_eq!(splitter.next().unwrap(), bytes[..v1_size]);
+        assert!(splitter.next().is_none());
+
+        splitter.push(&bytes[v1_size + 3..]);
+        assert_eq!(splitter.next().unwrap(), bytes[v1_size..]);
+        assert!(splitter.next().is_none());
+    }
+}
@@ -1,6 +1,11 @@
 use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
 
-use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::core::consts::DEFAULT_FAILBACK_INTERVAL;
+use crate::core::io::{
+    ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo, HostResolution, Resolver,
+    SystemResolver,
+};
 use crate::core::utils::net::resolve_socket_addr;
 
 use crate::prelude::*;
@@ -49,10 +54,53 @@ use crate::prelude::*;
 ///         ).build().await.unwrap();
 /// # }
 /// ```
+///
+/// # Fallback addresses
+///
+/// TCP client can be configured with an ordered list of fallback server addresses by
+/// [`TcpClient::with_fallback_addr`]. This covers a common primary / secondary GCS or relay server
+/// deployment without setting up a network of nodes.
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TcpClient::new("10.0.0.1:5600").unwrap()
+///                 .with_fallback_addr("10.0.0.2:5600").unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Client connects to the first reachable address. When connection is lost, client reconnects to
+/// the first reachable address in the order of priority (fail over). While connected to a
+/// fallback address, client checks higher-priority addresses within
+/// [`TcpClient::with_failback_interval`] and reconnects once any of them becomes reachable
+/// (fail back). Connection is closed only when none of the addresses are reachable.
+///
+/// # Dynamic hosts
+///
+/// Server address is resolved once, when configuration is created. Clients created by
+/// [`TcpClient::from_host`] resolve host each time connection is built, and, with
+/// [`TcpClient::with_resolve_interval`], close connection once host changes its address. Put such
+/// clients into a [`Network`] with [`Network::retry`] strategy to follow servers behind dynamic
+/// DNS. A custom [`Resolver`] can be set by [`TcpClient::from_host_with_resolver`].
+///
+/// # Encryption
+///
+/// With `tls` feature enabled, connection can be encrypted and authenticated by TLS, see
+/// `TcpClient::with_tls`.
 #[derive(Clone, Debug)]
 pub struct TcpClient {
     pub(crate) addr: SocketAddr,
+    pub(crate) fallback_addrs: Vec<SocketAddr>,
+    pub(crate) failback_interval: Duration,
+    pub(crate) resolution: Option<HostResolution>,
     pub(crate) info: ConnectionInfo,
+    #[cfg(feature = "tls")]
+    pub(crate) tls: Option<crate::core::io::TlsConnector>,
 }
 
 impl TcpClient {
@@ -63,7 +111,119 @@ impl TcpClient {
     pub fn new(addr: impl ToSocketAddrs) -> Result<Self> {
         let addr = resolve_socket_addr(addr)?;
         let info = ConnectionInfo::new(ConnectionDetails::TcpClient { remote_addr: addr });
-        Ok(Self { addr, info })
+        Ok(Self {
+            addr,
+            fallback_addrs: Vec::new(),
+            failback_interval: DEFAULT_FAILBACK_INTERVAL,
+            resolution: None,
+            info,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// Instantiates a TCP client configuration for a server `host`, that may change its
+    /// address over time.
+    ///
+    /// Unlike [`TcpClient::new`], client remembers the `host` (for example, `relay.example.com:5600`)
+    /// and resolves it again each time connection is built. Nodes repaired by a [`Network`] will
+    /// therefore reconnect to the current address of the host. Use
+    /// [`TcpClient::with_resolve_interval`] to track address changes of established connections.
+    ///
+    /// Host is resolved by [`SystemResolver`](crate::core::io::SystemResolver). Use
+    /// [`TcpClient::from_host_with_resolver`] to provide a custom [`Resolver`].
+    pub fn from_host(host: impl ToString) -> Result<Self> {
+        Self::from_host_with_resolver(host, SystemResolver)
+    }
+
+    /// Instantiates a TCP client configuration for a server `host` resolved by a custom
+    /// `resolver`.
+    ///
+    /// Host is resolved immediately and then each time connection is built. See
+    /// [`TcpClient::from_host`] for details.
+    pub fn from_host_with_resolver(host: impl ToString, resolver: impl Resolver) -> Result<Self> {
+        let resolution = HostResolution::new(host.to_string(), resolver);
+        let mut client = Self::new(resolution.resolve()?)?;
+        client.resolution = Some(resolution);
+        Ok(client)
+    }
+
+    /// Enables periodic resolution of the server host for established connections.
+    ///
+    /// Once host is resolved to a different address, the connection is closed. Put client into a
+    /// [`Network`] with [`Network::retry`] strategy to reconnect to the new address automatically.
+    ///
+    /// Makes sense only for clients created by [`TcpClient::from_host`] or
+    /// [`TcpClient::from_host_with_resolver`]. By default, host is resolved only when connection is
+    /// built.
+    pub fn with_resolve_interval(mut self, interval: Duration) -> Self {
+        self.resolution
+            .get_or_insert_with(|| HostResolution::new(self.addr.to_string(), SystemResolver))
+            .set_refresh_interval(interval);
+        self
+    }
+
+    /// Adds a fallback server address.
+    ///
+    /// Fallback addresses are used in the order they were added. The address passed to
+    /// [`TcpClient::new`] always has the highest priority.
+    pub fn with_fallback_addr(mut self, addr: impl ToSocketAddrs) -> Result<Self> {
+        self.fallback_addrs.push(resolve_socket_addr(addr)?);
+        Ok(self)
+    }
+
+    /// Sets interval between health checks of higher-priority addresses.
+    ///
+    /// Makes sense only if fallback addresses were specified by [`TcpClient::with_fallback_addr`].
+    /// Default interval is [`DEFAULT_FAILBACK_INTERVAL`].
+    pub fn with_failback_interval(self, interval: Duration) -> Self {
+        Self {
+            failback_interval: interval,
+            ..self
+        }
+    }
+
+    /// Server addresses in the order of priority.
+    pub fn addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        std::iter::once(self.addr).chain(self.fallback_addrs.iter().copied())
+    }
+
+    /// <sup>`tls`</sup>
+    /// Encrypts connection by TLS with settings of a `connector`.
+    ///
+    /// TLS session is established once connection to the server is built. Connection fails, if
+    /// server certificate is not trusted or doesn't match the server name of the `connector`. TLS
+    /// can't be combined with [fallback addresses](Self::with_fallback_addr).
+    ///
+    /// Channel taps observe decrypted bytes.
+    #[cfg(feature = "tls")]
+    pub fn with_tls(self, connector: crate::core::io::TlsConnector) -> Self {
+        Self {
+            tls: Some(connector),
+            ..self
+        }
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+
+    /// <sup>⛔</sup>
+    /// Reports options, that can't be combined with TLS.
+    pub(crate) fn tls_diagnostics(&self) -> Vec<crate::error::ConfigDiagnostic> {
+        #[cfg(feature = "tls")]
+        if self.tls.is_some() && !self.fallback_addrs.is_empty() {
+            return vec![crate::error::ConfigDiagnostic::TlsConflict(
+                self.info.details().clone(),
+                "fallback addresses",
+            )];
+        }
+
+        Vec::new()
     }
 }
 
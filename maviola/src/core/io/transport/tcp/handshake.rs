@@ -0,0 +1,24 @@
+/// Outcome of an authentication handshake with a client of [`TcpServer`].
+///
+/// Returned by `TcpHandshake` implementations from either
+/// [`sync::io`](crate::sync::io) or [`asnc::io`](crate::asnc::io) modules.
+///
+/// [`TcpServer`]: crate::core::io::TcpServer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HandshakeOutcome {
+    /// Client is accepted and its channel is attached to the connection.
+    Accept,
+    /// Client is rejected and its stream is closed.
+    Reject,
+}
+
+/// <sup>⛔</sup>
+/// Authentication handshake performed by [`TcpServer`](crate::core::io::TcpServer) with each
+/// accepted client.
+#[derive(Clone, Debug)]
+pub(crate) enum ServerHandshake {
+    #[cfg(feature = "sync")]
+    Sync(std::sync::Arc<dyn crate::sync::io::TcpHandshake>),
+    #[cfg(feature = "async")]
+    Async(std::sync::Arc<dyn crate::asnc::io::DynTcpHandshake>),
+}
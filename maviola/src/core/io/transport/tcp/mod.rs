@@ -1,2 +1,5 @@
 pub mod client;
+pub mod handshake;
 pub mod server;
+#[cfg(feature = "tls")]
+pub mod tls;
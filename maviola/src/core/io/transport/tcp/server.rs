@@ -1,6 +1,8 @@
 use std::net::{SocketAddr, ToSocketAddrs};
 
-use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::core::io::{
+    ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo, ServerHandshake,
+};
 use crate::core::utils::net::resolve_socket_addr;
 
 use crate::prelude::*;
@@ -13,6 +15,12 @@ use crate::prelude::*;
 ///
 /// Use [`TcpClient`] to create a TCP client node.
 ///
+/// Access can be gated per client by an authentication handshake set by
+/// [`TcpServer::sync_handshake`] or [`TcpServer::asnc_handshake`] depending on the API mode.
+///
+/// With `tls` feature enabled, client connections can be encrypted and authenticated by TLS, see
+/// `TcpServer::with_tls`.
+///
 /// # Usage
 ///
 /// Create a synchronous TCP server node:
@@ -56,6 +64,9 @@ use crate::prelude::*;
 pub struct TcpServer {
     pub(crate) addr: SocketAddr,
     pub(crate) info: ConnectionInfo,
+    pub(crate) handshake: Option<ServerHandshake>,
+    #[cfg(feature = "tls")]
+    pub(crate) tls: Option<crate::core::io::TlsAcceptor>,
 }
 
 impl TcpServer {
@@ -66,7 +77,87 @@ impl TcpServer {
     pub fn new(addr: impl ToSocketAddrs) -> Result<Self> {
         let addr = resolve_socket_addr(addr)?;
         let info = ConnectionInfo::new(ConnectionDetails::TcpServer { bind_addr: addr });
-        Ok(Self { addr, info })
+        Ok(Self {
+            addr,
+            info,
+            handshake: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Sets authentication handshake performed with each accepted client by a synchronous server.
+    ///
+    /// Client channel is attached to the connection only if handshake
+    /// [accepts](crate::core::io::HandshakeOutcome::Accept) the client. Asynchronous nodes can't be
+    /// built with this handshake.
+    ///
+    /// See [`TcpHandshake`](crate::sync::io::TcpHandshake) for details.
+    #[cfg(feature = "sync")]
+    pub fn sync_handshake(self, handshake: impl crate::sync::io::TcpHandshake) -> Self {
+        Self {
+            handshake: Some(ServerHandshake::Sync(std::sync::Arc::new(handshake))),
+            ..self
+        }
+    }
+
+    /// <sup>[`async`](crate::asnc)</sup>
+    /// Sets authentication handshake performed with each accepted client by an asynchronous server.
+    ///
+    /// Client channel is attached to the connection only if handshake
+    /// [accepts](crate::core::io::HandshakeOutcome::Accept) the client. Synchronous nodes can't be
+    /// built with this handshake.
+    ///
+    /// See [`TcpHandshake`](crate::asnc::io::TcpHandshake) for details.
+    #[cfg(feature = "async")]
+    pub fn asnc_handshake(self, handshake: impl crate::asnc::io::TcpHandshake) -> Self {
+        Self {
+            handshake: Some(ServerHandshake::Async(std::sync::Arc::new(handshake))),
+            ..self
+        }
+    }
+
+    /// <sup>`tls`</sup>
+    /// Encrypts client connections by TLS with settings of an `acceptor`.
+    ///
+    /// TLS session is established with each accepted client before its channel is attached to the
+    /// connection. Clients, that fail to complete TLS handshake within [`TCP_HANDSHAKE_TIMEOUT`],
+    /// are disconnected. Clients are authenticated by their certificates, if acceptor requires
+    /// them. TLS can't be combined with [`sync_handshake`](Self::sync_handshake) or
+    /// [`asnc_handshake`](Self::asnc_handshake).
+    ///
+    /// Channel taps observe decrypted bytes.
+    ///
+    /// [`TCP_HANDSHAKE_TIMEOUT`]: crate::core::consts::TCP_HANDSHAKE_TIMEOUT
+    #[cfg(feature = "tls")]
+    pub fn with_tls(self, acceptor: crate::core::io::TlsAcceptor) -> Self {
+        Self {
+            tls: Some(acceptor),
+            ..self
+        }
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+
+    /// <sup>⛔</sup>
+    /// Reports options, that can't be combined with TLS.
+    pub(crate) fn tls_diagnostics(&self) -> Vec<crate::error::ConfigDiagnostic> {
+        #[cfg(feature = "tls")]
+        if self.tls.is_some() && self.handshake.is_some() {
+            return vec![crate::error::ConfigDiagnostic::TlsConflict(
+                self.info.details().clone(),
+                "authentication handshake",
+            )];
+        }
+
+        Vec::new()
     }
 }
 
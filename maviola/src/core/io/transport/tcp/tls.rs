@@ -0,0 +1,136 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ServerConfig};
+
+use crate::prelude::*;
+
+/// <sup>`tls`</sup>
+/// TLS settings of a [`TcpServer`].
+///
+/// Wraps a [`rustls::ServerConfig`] with server certificate chain and, optionally, a client
+/// certificate verifier. Require client certificates to authenticate both sides of a link. Set
+/// acceptor by [`TcpServer::with_tls`].
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use maviola::core::io::TlsAcceptor;
+/// use maviola::prelude::*;
+/// # use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+/// # fn load() -> (Vec<CertificateDer<'static>>, PrivateKeyDer<'static>, rustls::RootCertStore) { unimplemented!() }
+///
+/// // Load server certificate chain, its private key, and trusted client CA certificates
+/// let (certs, key, client_roots) = load();
+///
+/// let verifier = rustls::server::WebPkiClientVerifier::builder(Arc::new(client_roots))
+///     .build()
+///     .unwrap();
+/// let config = rustls::ServerConfig::builder()
+///     .with_client_cert_verifier(verifier)
+///     .with_single_cert(certs, key)
+///     .unwrap();
+///
+/// let node = Node::sync::<V2>()
+///     .connection(
+///         TcpServer::new("0.0.0.0:5600").unwrap()
+///             .with_tls(TlsAcceptor::new(config))
+///     ).build().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    config: Arc<ServerConfig>,
+}
+
+/// <sup>`tls`</sup>
+/// TLS settings of a [`TcpClient`].
+///
+/// Wraps a [`rustls::ClientConfig`] with trusted root certificates and, optionally, a client
+/// certificate, together with the name of the server, that should be presented by its
+/// certificate. Set connector by [`TcpClient::with_tls`].
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::io::TlsConnector;
+/// use maviola::prelude::*;
+/// # use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+/// # fn load() -> (rustls::RootCertStore, Vec<CertificateDer<'static>>, PrivateKeyDer<'static>) { unimplemented!() }
+///
+/// // Load trusted CA certificates, client certificate chain, and its private key
+/// let (roots, certs, key) = load();
+///
+/// let config = rustls::ClientConfig::builder()
+///     .with_root_certificates(roots)
+///     .with_client_auth_cert(certs, key)
+///     .unwrap();
+///
+/// let node = Node::sync::<V2>()
+///     .connection(
+///         TcpClient::new("203.0.113.10:5600").unwrap()
+///             .with_tls(TlsConnector::new(config, "relay.example.com").unwrap())
+///     ).build().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TlsConnector {
+    config: Arc<ClientConfig>,
+    server_name: ServerName<'static>,
+}
+
+impl TlsAcceptor {
+    /// Creates acceptor from a server `config`.
+    pub fn new(config: impl Into<Arc<ServerConfig>>) -> Self {
+        Self {
+            config: config.into(),
+        }
+    }
+
+    /// Server configuration.
+    pub fn config(&self) -> &Arc<ServerConfig> {
+        &self.config
+    }
+}
+
+impl TlsConnector {
+    /// Creates connector from a client `config` and a `server_name` (DNS name or IP address),
+    /// that should be presented by the server certificate.
+    ///
+    /// Returns [`Error::Io`] with [`InvalidInput`](std::io::ErrorKind::InvalidInput) kind, if
+    /// `server_name` is not valid.
+    pub fn new(config: impl Into<Arc<ClientConfig>>, server_name: impl ToString) -> Result<Self> {
+        let server_name = ServerName::try_from(server_name.to_string()).map_err(|err| {
+            Error::from(std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+        })?;
+
+        Ok(Self {
+            config: config.into(),
+            server_name,
+        })
+    }
+
+    /// Client configuration.
+    pub fn config(&self) -> &Arc<ClientConfig> {
+        &self.config
+    }
+
+    /// Name of the server, that should be presented by its certificate.
+    pub fn server_name(&self) -> &ServerName<'static> {
+        &self.server_name
+    }
+}
+
+impl Debug for TlsAcceptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsAcceptor").finish_non_exhaustive()
+    }
+}
+
+impl Debug for TlsConnector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConnector")
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
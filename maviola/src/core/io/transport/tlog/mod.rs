@@ -0,0 +1,90 @@
+//! Telemetry logs in `.tlog` format.
+//!
+//! Each record of a `.tlog` file consists of a timestamp in microseconds since UNIX epoch encoded as
+//! a big-endian 64-bit unsigned integer, followed by a raw MAVLink packet. This is the format used
+//! by QGroundControl and MAVProxy to store flight sessions.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub mod reader;
+pub mod writer;
+
+/// Converts system time to `.tlog` timestamp in microseconds since UNIX epoch.
+pub(crate) fn tlog_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_micros() as u64)
+        .unwrap_or_default()
+}
+
+/// Schedules replay of `.tlog` records according to their timestamps.
+///
+/// The first scheduled record defines the origin of the playback.
+#[derive(Clone, Debug)]
+pub(crate) struct TlogPlayback {
+    speed: Option<f64>,
+    origin: Option<(u64, Instant)>,
+}
+
+impl TlogPlayback {
+    /// Creates playback with the specified speed multiplier.
+    ///
+    /// If `speed` is [`None`], then all records are scheduled immediately.
+    pub(crate) fn new(speed: Option<f64>) -> Self {
+        Self {
+            speed,
+            origin: None,
+        }
+    }
+
+    /// Returns the instant at which a record with the specified `timestamp` should be replayed.
+    ///
+    /// Records with timestamps preceding the origin are scheduled immediately.
+    pub(crate) fn schedule(&mut self, timestamp: u64) -> Instant {
+        let now = Instant::now();
+        let speed = match self.speed {
+            Some(speed) => speed,
+            None => return now,
+        };
+
+        let (origin_timestamp, origin_at) = *self.origin.get_or_insert((timestamp, now));
+        let elapsed = timestamp.saturating_sub(origin_timestamp) as f64 / speed;
+
+        origin_at + Duration::from_secs_f64(elapsed / 1_000_000.0)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tlog_timestamps() {
+        assert_eq!(tlog_timestamp(UNIX_EPOCH), 0);
+        assert_eq!(
+            tlog_timestamp(UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456)),
+            1_700_000_000_123_456
+        );
+        assert_eq!(tlog_timestamp(UNIX_EPOCH - Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn tlog_playback_schedule() {
+        let mut playback = TlogPlayback::new(Some(2.0));
+        let origin = playback.schedule(1_000_000);
+        assert!(origin <= Instant::now());
+
+        assert_eq!(
+            playback.schedule(3_000_000),
+            origin + Duration::from_secs(1)
+        );
+        assert_eq!(playback.schedule(500_000), origin);
+
+        let mut playback = TlogPlayback::new(None);
+        playback.schedule(0);
+        assert!(playback.schedule(10_000_000) <= Instant::now());
+    }
+}
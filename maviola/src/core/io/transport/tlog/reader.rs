@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// Replays a telemetry log in `.tlog` format.
+///
+/// Unlike [`FileReader`], which emits frames as fast as they can be read, [`TlogReader`] respects
+/// timestamps of the recorded frames and replays them with the original timing. Playback can be
+/// accelerated or slowed down by [`TlogReader::speed`]. Frames of MAVLink protocol version other
+/// than the one of the node are skipped.
+///
+/// Connection is closed once the whole log was replayed. Nodes built with [`TlogReader`] can't
+/// perform write actions.
+///
+/// # Usage
+///
+/// Create a synchronous node that replays a log with the original timing:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TlogReader::new("/tmp/maviola.tlog")  // Configure telemetry log reader
+///                 .unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous node that replays a log twice as fast:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TlogReader::new("/tmp/maviola.tlog")  // Configure telemetry log reader
+///                 .unwrap()
+///                 .speed(2.0)                       // Replay twice as fast
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlogReader {
+    pub(crate) path: PathBuf,
+    pub(crate) info: ConnectionInfo,
+    pub(crate) speed: Option<f64>,
+}
+
+impl TlogReader {
+    /// Instantiates a telemetry log reader configuration.
+    ///
+    /// Accepts as `path` anything that can be converted to [`PathBuf`], validates that file already
+    /// exist and indeed is a file.
+    ///
+    /// By default, frames are replayed with the original timing.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path: PathBuf = path.into();
+
+        if !Path::exists(path.as_path()) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("file does not exists: {path:?}"),
+            )));
+        }
+
+        if !Path::is_file(path.as_path()) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("not a file: {path:?}"),
+            )));
+        }
+
+        let info = ConnectionInfo::new(ConnectionDetails::TlogReader { path: path.clone() });
+        Ok(Self {
+            path,
+            info,
+            speed: Some(1.0),
+        })
+    }
+
+    /// Sets playback speed multiplier.
+    ///
+    /// For example, `2.0` replays the log twice as fast as it was recorded, while `0.5` replays it
+    /// at half the speed. Values that are not positive finite numbers disable delays between frames
+    /// the same way as [`TlogReader::unthrottled`] does.
+    pub fn speed(self, multiplier: f64) -> Self {
+        let speed = (multiplier.is_finite() && multiplier > 0.0).then_some(multiplier);
+        Self { speed, ..self }
+    }
+
+    /// Replays frames as fast as possible ignoring their timestamps.
+    pub fn unthrottled(self) -> Self {
+        Self {
+            speed: None,
+            ..self
+        }
+    }
+
+    /// Path to a telemetry log.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Playback speed multiplier.
+    ///
+    /// Returns [`None`] if playback is [unthrottled](TlogReader::unthrottled).
+    pub fn playback_speed(&self) -> Option<f64> {
+        self.speed
+    }
+}
+
+impl ConnectionConf for TlogReader {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::io::{ConnectionConf, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// Records frames passing through a connection to a telemetry log in `.tlog` format.
+///
+/// Wraps another connection and stores every incoming and outgoing frame together with a
+/// microsecond timestamp, so the session can be replayed later by tools like QGroundControl or
+/// MAVProxy. The wrapped connection keeps its [`ConnectionInfo`] and routing, so [`TlogWriter`]
+/// can be used anywhere the original connection is accepted, including [`Network`] connections.
+///
+/// If the wrapped connection is restored after failure, records are appended to the same file.
+///
+/// # Usage
+///
+/// Create a synchronous node that records a TCP session:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TlogWriter::new(
+///                 "/tmp/maviola.tlog",
+///                 TcpClient::new("127.0.0.1:5600").unwrap()
+///             ).unwrap()
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous node that records a UDP session:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             TlogWriter::new(
+///                 "/tmp/maviola.tlog",
+///                 UdpServer::new("127.0.0.1:14550").unwrap()
+///             ).unwrap()
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlogWriter<C> {
+    pub(crate) path: PathBuf,
+    pub(crate) connection: C,
+}
+
+impl<C: ConnectionConf> TlogWriter<C> {
+    /// Instantiates a telemetry log writer for a `connection`.
+    ///
+    /// Accepts as `path` anything that can be converted to [`PathBuf`], validates that file does
+    /// not exist.
+    pub fn new(path: impl Into<PathBuf>, connection: C) -> Result<Self> {
+        let path: PathBuf = path.into();
+
+        if Path::exists(path.as_path()) {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("file already exists: {path:?}"),
+            )));
+        }
+
+        Ok(Self { path, connection })
+    }
+
+    /// Path to a telemetry log.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Wrapped connection.
+    pub fn connection(&self) -> &C {
+        &self.connection
+    }
+}
+
+impl<C: ConnectionConf> ConnectionConf for TlogWriter<C> {
+    fn info(&self) -> &ConnectionInfo {
+        self.connection.info()
+    }
+}
@@ -1,7 +1,11 @@
 use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
 
-use crate::core::consts::DEFAULT_UDP_HOST;
-use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::core::consts::{DEFAULT_FAILBACK_INTERVAL, DEFAULT_FAILOVER_TIMEOUT, DEFAULT_UDP_HOST};
+use crate::core::io::{
+    ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo, HostResolution, Resolver,
+    SystemResolver,
+};
 use crate::core::utils::net::resolve_socket_addr;
 
 use crate::prelude::*;
@@ -60,11 +64,58 @@ use crate::prelude::*;
 ///         ).build().await.unwrap();
 /// # }
 /// ```
+///
+/// # Fallback addresses
+///
+/// UDP client can be configured with an ordered list of fallback server addresses by
+/// [`UdpClient::with_fallback_addr`]:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use maviola::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             UdpClient::new("10.0.0.1:14550").unwrap()
+///                 .with_fallback_addr("10.0.0.2:14550").unwrap()
+///                 .with_failover_timeout(Duration::from_secs(2))
+///         ).build().unwrap();
+/// ```
+///
+/// Since UDP is connection-less, the health of a server is determined by incoming traffic. If the
+/// active server hasn't sent anything within [`UdpClient::with_failover_timeout`], client switches
+/// to the next address (fail over). While a fallback address is active, outgoing frames are
+/// duplicated to higher-priority addresses once per [`UdpClient::with_failback_interval`]. Once a
+/// higher-priority server responds, it becomes active again (fail back). Frames received from
+/// inactive addresses are discarded.
+///
+/// # Unreachable servers
+///
+/// Clients without fallback addresses use connected sockets. Once the operating system reports,
+/// that server port is unreachable (ICMP port unreachable), the connection is closed instead of
+/// sending frames into the void. Node then emits `Event::ConnectionLost` or restores connection
+/// according to its [`RetryStrategy`](crate::core::io::RetryStrategy). Clients with fallback
+/// addresses use unconnected sockets and rely on [`UdpClient::with_failover_timeout`] instead.
+///
+/// # Dynamic hosts
+///
+/// Server address is resolved once, when configuration is created. Clients created by
+/// [`UdpClient::from_host`] resolve host each time connection is built, and, with
+/// [`UdpClient::with_resolve_interval`], close connection once host changes its address. Put such
+/// clients into a [`Network`] with [`Network::retry`] strategy to follow servers behind dynamic
+/// DNS. A custom [`Resolver`] can be set by [`UdpClient::from_host_with_resolver`].
 #[derive(Clone, Debug)]
 pub struct UdpClient {
     pub(crate) addr: SocketAddr,
     pub(crate) host: String,
     pub(crate) bind_addr: Option<SocketAddr>,
+    pub(crate) fallback_addrs: Vec<SocketAddr>,
+    pub(crate) failover_timeout: Duration,
+    pub(crate) failback_interval: Duration,
+    pub(crate) resolution: Option<HostResolution>,
     pub(crate) info: ConnectionInfo,
 }
 
@@ -81,6 +132,10 @@ impl UdpClient {
             addr,
             host,
             bind_addr: None,
+            fallback_addrs: Vec::new(),
+            failover_timeout: DEFAULT_FAILOVER_TIMEOUT,
+            failback_interval: DEFAULT_FAILBACK_INTERVAL,
+            resolution: None,
             info,
         })
     }
@@ -93,10 +148,9 @@ impl UdpClient {
         resolve_socket_addr(format!("{host}:80"))?;
 
         Ok(Self {
-            addr: self.addr,
             host: host.to_string(),
             bind_addr: None,
-            info: self.info,
+            ..self
         })
     }
 
@@ -106,12 +160,95 @@ impl UdpClient {
     /// [`UdpClient::with_host`].
     pub fn with_bind_addr(self, addr: impl ToSocketAddrs) -> Result<Self> {
         Ok(Self {
-            addr: self.addr,
-            host: self.host,
             bind_addr: Some(resolve_socket_addr(addr)?),
-            info: self.info,
+            ..self
         })
     }
+
+    /// Instantiates a UDP client configuration for a server `host`, that may change its
+    /// address over time.
+    ///
+    /// Unlike [`UdpClient::new`], client remembers the `host` (for example, `relay.example.com:5600`)
+    /// and resolves it again each time connection is built. Nodes repaired by a [`Network`] will
+    /// therefore reconnect to the current address of the host. Use
+    /// [`UdpClient::with_resolve_interval`] to track address changes of established connections.
+    ///
+    /// Host is resolved by [`SystemResolver`](crate::core::io::SystemResolver). Use
+    /// [`UdpClient::from_host_with_resolver`] to provide a custom [`Resolver`].
+    pub fn from_host(host: impl ToString) -> Result<Self> {
+        Self::from_host_with_resolver(host, SystemResolver)
+    }
+
+    /// Instantiates a UDP client configuration for a server `host` resolved by a custom
+    /// `resolver`.
+    ///
+    /// Host is resolved immediately and then each time connection is built. See
+    /// [`UdpClient::from_host`] for details.
+    pub fn from_host_with_resolver(host: impl ToString, resolver: impl Resolver) -> Result<Self> {
+        let resolution = HostResolution::new(host.to_string(), resolver);
+        let mut client = Self::new(resolution.resolve()?)?;
+        client.resolution = Some(resolution);
+        Ok(client)
+    }
+
+    /// Enables periodic resolution of the server host for established connections.
+    ///
+    /// Once host is resolved to a different address, the connection is closed. Put client into a
+    /// [`Network`] with [`Network::retry`] strategy to reconnect to the new address automatically.
+    ///
+    /// Makes sense only for clients created by [`UdpClient::from_host`] or
+    /// [`UdpClient::from_host_with_resolver`]. By default, host is resolved only when connection is
+    /// built.
+    pub fn with_resolve_interval(mut self, interval: Duration) -> Self {
+        self.resolution
+            .get_or_insert_with(|| HostResolution::new(self.addr.to_string(), SystemResolver))
+            .set_refresh_interval(interval);
+        self
+    }
+
+    /// Adds a fallback server address.
+    ///
+    /// Fallback addresses are used in the order they were added. The address passed to
+    /// [`UdpClient::new`] always has the highest priority.
+    pub fn with_fallback_addr(mut self, addr: impl ToSocketAddrs) -> Result<Self> {
+        self.fallback_addrs.push(resolve_socket_addr(addr)?);
+        Ok(self)
+    }
+
+    /// Sets a period of silence after which the active server is considered to be unavailable.
+    ///
+    /// Makes sense only if fallback addresses were specified by [`UdpClient::with_fallback_addr`].
+    /// Default timeout is [`DEFAULT_FAILOVER_TIMEOUT`].
+    pub fn with_failover_timeout(self, timeout: Duration) -> Self {
+        Self {
+            failover_timeout: timeout,
+            ..self
+        }
+    }
+
+    /// Sets interval between health checks of higher-priority addresses.
+    ///
+    /// Makes sense only if fallback addresses were specified by [`UdpClient::with_fallback_addr`].
+    /// Default interval is [`DEFAULT_FAILBACK_INTERVAL`].
+    pub fn with_failback_interval(self, interval: Duration) -> Self {
+        Self {
+            failback_interval: interval,
+            ..self
+        }
+    }
+
+    /// Server addresses in the order of priority.
+    pub fn addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        std::iter::once(self.addr).chain(self.fallback_addrs.iter().copied())
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
 }
 
 impl ConnectionConf for UdpClient {
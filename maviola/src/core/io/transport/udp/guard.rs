@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::core::consts::UDP_MAX_REPORTED_SENDERS;
+
+use crate::prelude::*;
+
+/// Range of IP addresses in CIDR notation.
+///
+/// Used by [`UdpServer::with_allowlist`] to restrict senders of UDP datagrams. Can be parsed from
+/// strings like `192.168.1.0/24` or `fd00::/8`. A single address without prefix length is a range,
+/// that contains only this address.
+///
+/// IPv4 ranges contain IPv4-mapped IPv6 addresses (like `::ffff:192.168.1.1`), since such
+/// addresses are reported by dual-stack sockets.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::io::CidrRange;
+///
+/// let range: CidrRange = "192.168.1.0/24".parse().unwrap();
+///
+/// assert!(range.contains("192.168.1.42".parse().unwrap()));
+/// assert!(!range.contains("192.168.2.1".parse().unwrap()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CidrRange {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+/// <sup>⛔</sup>
+/// Decides, which senders are accepted by a UDP server.
+///
+/// Shared between clones of [`UdpServer`] configuration, so the locked sender can be inspected
+/// while server is running.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UdpSenderGuard {
+    lock: UdpLock,
+    allowlist: Vec<CidrRange>,
+    locked: Arc<RwLock<Option<SocketAddr>>>,
+    rejected: Arc<Mutex<HashSet<SocketAddr>>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum UdpLock {
+    #[default]
+    Unlocked,
+    FirstSender,
+    Sender(SocketAddr),
+}
+
+impl CidrRange {
+    /// Creates a range from an address and a prefix length.
+    ///
+    /// Host bits of the address are ignored. Returns an error, if prefix is longer than the
+    /// address (`32` bits for IPv4 and `128` bits for IPv6).
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<Self> {
+        let max_prefix = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix > max_prefix {
+            return Err(Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("prefix length {prefix} exceeds {max_prefix} bits"),
+            )));
+        }
+
+        Ok(Self {
+            addr: mask(addr, prefix),
+            prefix,
+        })
+    }
+
+    /// Network address of the range.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Prefix length in bits.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns `true`, if `addr` belongs to this range.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let addr = match (self.addr, addr) {
+            (IpAddr::V4(_), IpAddr::V6(v6)) => match v6.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => return false,
+            },
+            (_, addr) => addr,
+        };
+        mask(addr, self.prefix) == self.addr
+    }
+}
+
+impl FromStr for CidrRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid CIDR range: {s}"),
+            ))
+        };
+
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let addr = IpAddr::from_str(addr).map_err(|_| invalid())?;
+        let prefix = match prefix {
+            Some(prefix) => u8::from_str(prefix).map_err(|_| invalid())?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        Self::new(addr, prefix)
+    }
+}
+
+impl Display for CidrRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+impl From<IpAddr> for CidrRange {
+    /// Creates a range, that contains only the specified address.
+    fn from(value: IpAddr) -> Self {
+        let prefix = if value.is_ipv4() { 32 } else { 128 };
+        Self {
+            addr: value,
+            prefix,
+        }
+    }
+}
+
+impl UdpSenderGuard {
+    /// Accepts only the first sender.
+    pub(crate) fn lock_to_first_sender(&mut self) {
+        self.lock = UdpLock::FirstSender;
+    }
+
+    /// Accepts only the specified sender.
+    pub(crate) fn lock_to(&mut self, addr: SocketAddr) {
+        self.lock = UdpLock::Sender(addr);
+    }
+
+    /// Accepts only senders within provided ranges.
+    pub(crate) fn set_allowlist(&mut self, ranges: Vec<CidrRange>) {
+        self.allowlist = ranges;
+    }
+
+    /// Returns `true`, if server accepts data only from a single sender.
+    pub(crate) fn is_locking(&self) -> bool {
+        !matches!(self.lock, UdpLock::Unlocked)
+    }
+
+    /// Sender, that server is locked to, if any.
+    pub(crate) fn locked_sender(&self) -> Option<SocketAddr> {
+        match self.lock {
+            UdpLock::Unlocked => None,
+            UdpLock::FirstSender => *self.read_locked(),
+            UdpLock::Sender(addr) => Some(addr),
+        }
+    }
+
+    /// Forgets the first sender and rejected senders, when server is started.
+    pub(crate) fn reset(&self) {
+        *self.write_locked() = None;
+        self.lock_rejected().clear();
+    }
+
+    /// Returns `true`, if data from a new sender with `addr` should be accepted.
+    ///
+    /// If server is locked to the first sender, then the first accepted sender locks the server.
+    pub(crate) fn admit(&self, addr: SocketAddr) -> bool {
+        let allowed = self.allowlist.is_empty()
+            || self.allowlist.iter().any(|range| range.contains(addr.ip()));
+        if !allowed {
+            return false;
+        }
+
+        match self.lock {
+            UdpLock::Unlocked => true,
+            UdpLock::Sender(locked) => locked == addr,
+            UdpLock::FirstSender => {
+                let mut locked = self.write_locked();
+                match *locked {
+                    Some(locked) => locked == addr,
+                    None => {
+                        *locked = Some(addr);
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records rejected sender with `addr`.
+    ///
+    /// Returns `true`, if this sender wasn't rejected before and should be reported. At most
+    /// [`UDP_MAX_REPORTED_SENDERS`] are remembered, then senders are reported again.
+    pub(crate) fn reject(&self, addr: SocketAddr) -> bool {
+        let mut rejected = self.lock_rejected();
+        if rejected.len() >= UDP_MAX_REPORTED_SENDERS {
+            rejected.clear();
+        }
+        rejected.insert(addr)
+    }
+
+    fn read_locked(&self) -> std::sync::RwLockReadGuard<'_, Option<SocketAddr>> {
+        match self.locked.read() {
+            Ok(locked) => locked,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write_locked(&self) -> std::sync::RwLockWriteGuard<'_, Option<SocketAddr>> {
+        match self.locked.write() {
+            Ok(locked) => locked,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn lock_rejected(&self) -> std::sync::MutexGuard<'_, HashSet<SocketAddr>> {
+        match self.rejected.lock() {
+            Ok(rejected) => rejected,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+fn mask(addr: IpAddr, prefix: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(addr) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            IpAddr::V4((u32::from(addr) & mask).into())
+        }
+        IpAddr::V6(addr) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            IpAddr::V6((u128::from(addr) & mask).into())
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod guard_tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn cidr_ranges_are_parsed_and_matched() {
+        let range: CidrRange = "10.1.2.3/8".parse().unwrap();
+        assert_eq!(range.to_string(), "10.0.0.0/8");
+        assert!(range.contains("10.200.0.1".parse().unwrap()));
+        assert!(range.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!range.contains("11.0.0.1".parse().unwrap()));
+
+        let range: CidrRange = "fd00::/8".parse().unwrap();
+        assert!(range.contains("fd12::1".parse().unwrap()));
+        assert!(!range.contains("10.0.0.1".parse().unwrap()));
+
+        let range: CidrRange = "0.0.0.0/0".parse().unwrap();
+        assert!(range.contains("8.8.8.8".parse().unwrap()));
+
+        let range: CidrRange = "127.0.0.1".parse().unwrap();
+        assert_eq!(range.prefix(), 32);
+
+        assert!("10.0.0.0/33".parse::<CidrRange>().is_err());
+        assert!("10.0.0/8".parse::<CidrRange>().is_err());
+    }
+
+    #[test]
+    fn guard_locks_to_first_sender() {
+        let mut guard = UdpSenderGuard::default();
+        assert!(guard.admit(addr("127.0.0.1:5000")));
+        assert!(guard.locked_sender().is_none());
+
+        guard.lock_to_first_sender();
+        let shared = guard.clone();
+        assert!(guard.admit(addr("127.0.0.1:5000")));
+        assert!(!guard.admit(addr("127.0.0.1:5001")));
+        assert_eq!(shared.locked_sender(), Some(addr("127.0.0.1:5000")));
+
+        shared.reset();
+        assert!(guard.locked_sender().is_none());
+        assert!(guard.admit(addr("127.0.0.1:5001")));
+    }
+
+    #[test]
+    fn guard_applies_allowlist() {
+        let mut guard = UdpSenderGuard::default();
+        guard.set_allowlist(vec!["192.168.0.0/16".parse().unwrap()]);
+        guard.lock_to(addr("10.0.0.1:5000"));
+
+        // Locked sender should be within allowed ranges as well
+        assert!(!guard.admit(addr("10.0.0.1:5000")));
+        assert!(!guard.admit(addr("192.168.1.1:5000")));
+
+        guard.lock_to(addr("192.168.1.1:5000"));
+        assert!(guard.admit(addr("192.168.1.1:5000")));
+
+        assert!(guard.reject(addr("10.0.0.1:5000")));
+        assert!(!guard.reject(addr("10.0.0.1:5000")));
+    }
+}
@@ -1,6 +1,11 @@
 use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo};
+use crate::core::io::transport::udp::guard::UdpSenderGuard;
+use crate::core::io::{
+    ChannelInfo, ChannelTap, CidrRange, ConnectionConf, ConnectionDetails, ConnectionInfo,
+};
 use crate::core::utils::net::resolve_socket_addr;
 
 use crate::prelude::*;
@@ -12,6 +17,16 @@ use crate::prelude::*;
 ///
 /// Each incoming connection will be considered as a separate channel.
 ///
+/// Since UDP has no notion of connection, by default server keeps sending frames to every peer it
+/// has ever received data from. Use [`UdpServer::with_client_timeout`] to forget peers, that
+/// became silent, and [`UdpServer::with_max_clients`] to limit the number of peers. Current peers
+/// are available through [`UdpServer::peers`].
+///
+/// Open UDP ports accept datagrams from anyone. To protect server from spoofed senders, use
+/// [`UdpServer::lock_to_first_sender`] or [`UdpServer::lock_to`] to accept data only from a single
+/// sender, and [`UdpServer::with_allowlist`] to accept data only from particular networks. Data
+/// from other senders is dropped and reported as [`SenderRejected`] node events.
+///
 /// Use [`UdpClient`] to create a TCP client node.
 ///
 /// # Usage
@@ -53,10 +68,23 @@ use crate::prelude::*;
 ///         ).build().await.unwrap();
 /// # }
 /// ```
+///
+/// [`SenderRejected`]: crate::sync::node::Event::SenderRejected
 #[derive(Clone, Debug)]
 pub struct UdpServer {
     pub(crate) addr: SocketAddr,
     pub(crate) info: ConnectionInfo,
+    pub(crate) client_timeout: Option<Duration>,
+    pub(crate) max_clients: Option<usize>,
+    pub(crate) peers: UdpPeers,
+    pub(crate) guard: UdpSenderGuard,
+}
+
+/// <sup>⛔</sup>
+/// Peers of a UDP server shared between clones of its configuration.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UdpPeers {
+    inner: Arc<RwLock<Vec<(SocketAddr, ChannelInfo)>>>,
 }
 
 impl UdpServer {
@@ -67,7 +95,141 @@ impl UdpServer {
     pub fn new(addr: impl ToSocketAddrs) -> Result<Self> {
         let addr = resolve_socket_addr(addr)?;
         let info = ConnectionInfo::new(ConnectionDetails::UdpServer { bind_addr: addr });
-        Ok(Self { addr, info })
+        Ok(Self {
+            addr,
+            info,
+            client_timeout: None,
+            max_clients: None,
+            peers: UdpPeers::default(),
+            guard: UdpSenderGuard::default(),
+        })
+    }
+
+    /// Removes peers, that haven't sent anything within `timeout`.
+    ///
+    /// Channels of removed peers are closed. Peer is added again once it sends new data.
+    pub fn with_client_timeout(self, timeout: Duration) -> Self {
+        Self {
+            client_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Limits the number of simultaneous peers.
+    ///
+    /// Data from new peers is discarded, while limit is reached.
+    pub fn with_max_clients(self, max_clients: usize) -> Self {
+        Self {
+            max_clients: Some(max_clients),
+            ..self
+        }
+    }
+
+    /// Locks server to the first sender it receives data from.
+    ///
+    /// Datagrams from other senders are dropped. Lock is released, once server is restarted, for
+    /// example, when connection is restored after failure. Channel of the locked sender is marked
+    /// as [`locked`](crate::core::io::ChannelDetails::UdpServer::locked).
+    pub fn lock_to_first_sender(mut self) -> Self {
+        self.guard.lock_to_first_sender();
+        self
+    }
+
+    /// Locks server to a sender with the specified address.
+    ///
+    /// Accepts as `addr` anything that implements [`ToSocketAddrs`]. Datagrams from other senders
+    /// are dropped.
+    pub fn lock_to(mut self, addr: impl ToSocketAddrs) -> Result<Self> {
+        self.guard.lock_to(resolve_socket_addr(addr)?);
+        Ok(self)
+    }
+
+    /// Accepts data only from senders within specified IP address `ranges`.
+    ///
+    /// Replaces previously set allowlist. Can be combined with sender lock, in which case locked
+    /// sender should be within allowed ranges as well.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use maviola::core::io::CidrRange;
+    /// use maviola::prelude::*;
+    ///
+    /// let server = UdpServer::new("0.0.0.0:14550")
+    ///     .unwrap()
+    ///     .with_allowlist([
+    ///         "192.168.1.0/24".parse::<CidrRange>().unwrap(),
+    ///         "10.0.0.0/8".parse().unwrap(),
+    ///     ])
+    ///     .lock_to_first_sender();
+    /// ```
+    pub fn with_allowlist(mut self, ranges: impl IntoIterator<Item = CidrRange>) -> Self {
+        self.guard.set_allowlist(ranges.into_iter().collect());
+        self
+    }
+
+    /// Address of a sender, that server is locked to.
+    ///
+    /// Returns [`None`], if server is not locked or, when locked to the first sender, hasn't
+    /// received any data yet. Clones of the server configuration share the lock state.
+    pub fn locked_sender(&self) -> Option<SocketAddr> {
+        self.guard.locked_sender()
+    }
+
+    /// Channels of current peers in the order they appeared.
+    ///
+    /// Clones of the server configuration share peers. Keep a clone to observe peers while the
+    /// server is running.
+    pub fn peers(&self) -> Vec<ChannelInfo> {
+        self.peers
+            .read()
+            .iter()
+            .map(|(_, info)| info.clone())
+            .collect()
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl UdpPeers {
+    /// Returns `true`, if another peer can be added without exceeding `max_clients`.
+    pub(crate) fn has_capacity(&self, max_clients: Option<usize>) -> bool {
+        match max_clients {
+            Some(max_clients) => self.read().len() < max_clients,
+            None => true,
+        }
+    }
+
+    pub(crate) fn insert(&self, addr: SocketAddr, info: ChannelInfo) {
+        self.write().push((addr, info));
+    }
+
+    pub(crate) fn remove(&self, addr: &SocketAddr) {
+        self.write().retain(|(peer_addr, _)| peer_addr != addr);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.write().clear();
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<(SocketAddr, ChannelInfo)>> {
+        match self.inner.read() {
+            Ok(peers) => peers,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<(SocketAddr, ChannelInfo)>> {
+        match self.inner.write() {
+            Ok(peers) => peers,
+            Err(poisoned) => poisoned.into_inner(),
+        }
     }
 }
 
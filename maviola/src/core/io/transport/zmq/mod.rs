@@ -0,0 +1,30 @@
+pub mod publisher;
+pub mod subscriber;
+pub(crate) mod zmtp;
+
+use std::net::SocketAddr;
+
+use crate::core::utils::net::resolve_socket_addr;
+use crate::error::ZmqError;
+
+use crate::prelude::*;
+
+const TCP_SCHEME: &str = "tcp://";
+
+/// Parses ZeroMQ `tcp://` endpoint.
+///
+/// Endpoints without scheme are treated as TCP addresses, `*` host means all interfaces.
+fn parse_endpoint(endpoint: &str) -> Result<SocketAddr> {
+    let addr = match endpoint.strip_prefix(TCP_SCHEME) {
+        Some(addr) => addr,
+        None if endpoint.contains("://") => {
+            return Err(ZmqError::UnsupportedEndpoint(endpoint.to_string()).into())
+        }
+        None => endpoint,
+    };
+
+    match addr.strip_prefix("*:") {
+        Some(port) => resolve_socket_addr(format!("0.0.0.0:{port}")),
+        None => resolve_socket_addr(addr),
+    }
+}
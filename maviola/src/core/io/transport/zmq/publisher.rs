@@ -0,0 +1,112 @@
+use std::net::SocketAddr;
+
+use crate::core::io::transport::zmq::parse_endpoint;
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// <sup>`zmq`</sup>
+/// ZeroMQ publisher configuration.
+///
+/// Binds to a TCP endpoint as a ZeroMQ `PUB` socket and publishes outgoing frames to connected
+/// `SUB` sockets. Each subscriber will be considered as a separate channel. Use [`ZmqSub`] to create
+/// a subscriber node.
+///
+/// Publisher speaks ZMTP 3.0 with `NULL` security mechanism and is compatible with `libzmq`
+/// subscribers. Each frame is published as a two-part message: topic set by
+/// [`ZmqPub::with_topic`] and the serialized MAVLink frame. Without topic, frames are published as
+/// single-part messages. As with any ZeroMQ publisher, frames are sent only to subscribers with
+/// a matching subscription, and frames sent before subscription arrives are dropped. Publishers do
+/// not receive frames.
+///
+/// [`ZmqSub`]: crate::core::io::ZmqSub
+///
+/// # Usage
+///
+/// Create a synchronous publisher node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::ZmqPub;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             ZmqPub::new("tcp://*:5556")    // Configure ZeroMQ publisher
+///                 .unwrap()
+///                 .with_topic("mavlink")
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous publisher node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::ZmqPub;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             ZmqPub::new("tcp://*:5556")    // Configure ZeroMQ publisher
+///                 .unwrap()
+///                 .with_topic("mavlink")
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ZmqPub {
+    pub(crate) addr: SocketAddr,
+    pub(crate) topic: String,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl ZmqPub {
+    /// Instantiates a ZeroMQ publisher configuration.
+    ///
+    /// Accepts `tcp://` endpoints such as `tcp://127.0.0.1:5556` or `tcp://*:5556`. Scheme can be
+    /// omitted, other transports are not supported.
+    pub fn new(endpoint: &str) -> Result<Self> {
+        let addr = parse_endpoint(endpoint)?;
+        Ok(Self {
+            addr,
+            topic: String::new(),
+            info: Self::make_info(addr, ""),
+        })
+    }
+
+    /// Sets topic, under which frames are published.
+    pub fn with_topic(self, topic: impl Into<String>) -> Self {
+        let topic = topic.into();
+        Self {
+            info: Self::make_info(self.addr, &topic),
+            topic,
+            ..self
+        }
+    }
+
+    fn make_info(bind_addr: SocketAddr, topic: &str) -> ConnectionInfo {
+        ConnectionInfo::new(ConnectionDetails::ZmqPub {
+            bind_addr,
+            topic: topic.to_string(),
+        })
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for ZmqPub {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
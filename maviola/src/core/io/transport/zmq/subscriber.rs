@@ -0,0 +1,112 @@
+use std::net::SocketAddr;
+
+use crate::core::io::transport::zmq::parse_endpoint;
+use crate::core::io::{ChannelTap, ConnectionConf, ConnectionDetails, ConnectionInfo};
+
+use crate::prelude::*;
+
+/// <sup>`zmq`</sup>
+/// ZeroMQ subscriber configuration.
+///
+/// Connects to a ZeroMQ `PUB` socket bound to a TCP endpoint and receives published frames. Use
+/// [`ZmqPub`] to create a publisher node.
+///
+/// Subscriber speaks ZMTP 3.0 with `NULL` security mechanism and is compatible with `libzmq`
+/// publishers. Subscriber subscribes to the topic set by [`ZmqSub::with_topic`] (all messages by
+/// default) and expects the last part of each message to contain a serialized MAVLink frame,
+/// preceding parts are treated as topic envelopes. Subscribers do not send frames: outgoing frames
+/// are discarded.
+///
+/// [`ZmqPub`]: crate::core::io::ZmqPub
+///
+/// # Usage
+///
+/// Create a synchronous subscriber node:
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::core::io::ZmqSub;
+///
+/// let node = Node::sync::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             ZmqSub::new("tcp://127.0.0.1:5556")    // Configure ZeroMQ subscriber
+///                 .unwrap()
+///                 .with_topic("mavlink")
+///         ).build().unwrap();
+/// ```
+///
+/// Create an asynchronous subscriber node:
+///
+/// ```rust,no_run
+/// # #[tokio::main] async fn main() {
+/// use maviola::prelude::*;
+/// use maviola::core::io::ZmqSub;
+///
+/// let node = Node::asnc::<V2>()
+///         /* define other node parameters */
+/// #       .system_id(1)
+/// #       .component_id(1)
+///         .connection(
+///             ZmqSub::new("tcp://127.0.0.1:5556")    // Configure ZeroMQ subscriber
+///                 .unwrap()
+///                 .with_topic("mavlink")
+///         ).build().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ZmqSub {
+    pub(crate) addr: SocketAddr,
+    pub(crate) topic: String,
+    pub(crate) info: ConnectionInfo,
+}
+
+impl ZmqSub {
+    /// Instantiates a ZeroMQ subscriber configuration.
+    ///
+    /// Accepts `tcp://` endpoints such as `tcp://127.0.0.1:5556`. Scheme can be omitted, other
+    /// transports are not supported.
+    pub fn new(endpoint: &str) -> Result<Self> {
+        let addr = parse_endpoint(endpoint)?;
+        Ok(Self {
+            addr,
+            topic: String::new(),
+            info: Self::make_info(addr, ""),
+        })
+    }
+
+    /// Sets topic prefix to subscribe to.
+    ///
+    /// Empty topic (default) subscribes to all messages.
+    pub fn with_topic(self, topic: impl Into<String>) -> Self {
+        let topic = topic.into();
+        Self {
+            info: Self::make_info(self.addr, &topic),
+            topic,
+            ..self
+        }
+    }
+
+    fn make_info(remote_addr: SocketAddr, topic: &str) -> ConnectionInfo {
+        ConnectionInfo::new(ConnectionDetails::ZmqSub {
+            remote_addr,
+            topic: topic.to_string(),
+        })
+    }
+
+    /// Registers a [`ChannelTap`], that observes raw bytes of all channels of this connection.
+    ///
+    /// Replaces previously registered tap.
+    pub fn with_tap(mut self, tap: impl ChannelTap) -> Self {
+        self.info.set_tap(tap);
+        self
+    }
+}
+
+impl ConnectionConf for ZmqSub {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
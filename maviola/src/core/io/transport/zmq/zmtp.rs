@@ -0,0 +1,420 @@
+//! Minimal implementation of [ZMTP 3.0](https://rfc.zeromq.org/spec/23/) with `NULL` security
+//! mechanism sufficient for `PUB` / `SUB` sockets.
+//!
+//! Codec does not perform any I/O and is shared by synchronous and asynchronous transports.
+
+use std::mem;
+
+use crate::error::ZmqError;
+
+/// Size of ZMTP greeting.
+pub(crate) const GREETING_SIZE: usize = 64;
+/// Size of a buffer for reading from ZMTP streams.
+pub(crate) const READ_BUFFER_SIZE: usize = 1024;
+
+const SIGNATURE_START: u8 = 0xFF;
+const SIGNATURE_END: u8 = 0x7F;
+const VERSION_MAJOR: u8 = 3;
+const VERSION_MINOR: u8 = 0;
+const MECHANISM_OFFSET: usize = 12;
+const MECHANISM_SIZE: usize = 20;
+const NULL_MECHANISM: &[u8] = b"NULL";
+
+const FLAG_MORE: u8 = 0x01;
+const FLAG_LONG: u8 = 0x02;
+const FLAG_COMMAND: u8 = 0x04;
+/// Frames larger than this are considered malformed, MAVLink messages are much smaller.
+const MAX_FRAME_SIZE: u64 = 64 * 1024;
+
+const READY: &[u8] = b"READY";
+const ERROR: &[u8] = b"ERROR";
+const SUBSCRIBE: &[u8] = b"SUBSCRIBE";
+const CANCEL: &[u8] = b"CANCEL";
+const SOCKET_TYPE: &[u8] = b"Socket-Type";
+const SUBSCRIBE_MARKER: u8 = 0x01;
+const CANCEL_MARKER: u8 = 0x00;
+
+/// Type of ZMTP socket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SocketType {
+    Pub,
+    Sub,
+}
+
+/// Command or message received from a ZMTP peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ZmtpEvent {
+    /// Message with all its parts.
+    Message(Vec<Vec<u8>>),
+    /// Command with its name and data.
+    Command(Vec<u8>, Vec<u8>),
+}
+
+/// Incremental decoder of ZMTP traffic, that follows the handshake.
+#[derive(Debug, Default)]
+pub(crate) struct ZmtpDecoder {
+    buffer: Vec<u8>,
+    parts: Vec<Vec<u8>>,
+}
+
+/// Topic prefixes subscribed by a `SUB` peer.
+#[derive(Debug, Default)]
+pub(crate) struct Subscriptions {
+    topics: Vec<Vec<u8>>,
+}
+
+impl SocketType {
+    fn name(&self) -> &'static [u8] {
+        match self {
+            SocketType::Pub => b"PUB",
+            SocketType::Sub => b"SUB",
+        }
+    }
+
+    fn accepts(&self, peer: &[u8]) -> bool {
+        match self {
+            SocketType::Pub => peer == b"SUB" || peer == b"XSUB",
+            SocketType::Sub => peer == b"PUB" || peer == b"XPUB",
+        }
+    }
+}
+
+impl ZmtpDecoder {
+    /// Appends bytes received from a peer.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next complete command or message.
+    ///
+    /// Returns [`None`], if more bytes are required.
+    pub(crate) fn next(&mut self) -> Result<Option<ZmtpEvent>, ZmqError> {
+        loop {
+            let (flags, header_size, body_size) = match self.header()? {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+            if self.buffer.len() < header_size + body_size {
+                return Ok(None);
+            }
+
+            let body = self.buffer[header_size..header_size + body_size].to_vec();
+            self.buffer.drain(..header_size + body_size);
+
+            if flags & FLAG_COMMAND != 0 {
+                return parse_command(body).map(Some);
+            }
+
+            self.parts.push(body);
+            if flags & FLAG_MORE == 0 {
+                return Ok(Some(ZmtpEvent::Message(mem::take(&mut self.parts))));
+            }
+        }
+    }
+
+    fn header(&self) -> Result<Option<(u8, usize, usize)>, ZmqError> {
+        let flags = match self.buffer.first() {
+            Some(&flags) => flags,
+            None => return Ok(None),
+        };
+
+        if flags & FLAG_LONG == 0 {
+            return Ok(self.buffer.get(1).map(|&size| (flags, 2, size as usize)));
+        }
+
+        let size = match self.buffer.get(1..9) {
+            Some(size) => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(size);
+                u64::from_be_bytes(bytes)
+            }
+            None => return Ok(None),
+        };
+        if size > MAX_FRAME_SIZE {
+            return Err(ZmqError::MalformedFrame);
+        }
+
+        Ok(Some((flags, 9, size as usize)))
+    }
+}
+
+impl Subscriptions {
+    /// Updates subscriptions from a subscription message or command.
+    ///
+    /// Other events are ignored.
+    pub(crate) fn handle(&mut self, event: &ZmtpEvent) {
+        match event {
+            ZmtpEvent::Message(parts) if parts.len() == 1 => match parts[0].split_first() {
+                Some((&SUBSCRIBE_MARKER, topic)) => self.subscribe(topic),
+                Some((&CANCEL_MARKER, topic)) => self.cancel(topic),
+                _ => {}
+            },
+            ZmtpEvent::Command(name, topic) if name == SUBSCRIBE => self.subscribe(topic),
+            ZmtpEvent::Command(name, topic) if name == CANCEL => self.cancel(topic),
+            _ => {}
+        }
+    }
+
+    /// Returns `true`, if a message with the specified first part should be sent to the peer.
+    pub(crate) fn matches(&self, key: &[u8]) -> bool {
+        self.topics.iter().any(|topic| key.starts_with(topic))
+    }
+
+    fn subscribe(&mut self, topic: &[u8]) {
+        self.topics.push(topic.to_vec());
+    }
+
+    fn cancel(&mut self, topic: &[u8]) {
+        if let Some(idx) = self.topics.iter().position(|known| known == topic) {
+            self.topics.remove(idx);
+        }
+    }
+}
+
+/// Creates a greeting, that announces ZMTP 3.0 with `NULL` security mechanism.
+pub(crate) fn greeting() -> [u8; GREETING_SIZE] {
+    let mut greeting = [0u8; GREETING_SIZE];
+    greeting[0] = SIGNATURE_START;
+    greeting[9] = SIGNATURE_END;
+    greeting[10] = VERSION_MAJOR;
+    greeting[11] = VERSION_MINOR;
+    greeting[MECHANISM_OFFSET..MECHANISM_OFFSET + NULL_MECHANISM.len()]
+        .copy_from_slice(NULL_MECHANISM);
+    greeting
+}
+
+/// Validates peer greeting.
+pub(crate) fn check_greeting(greeting: &[u8]) -> Result<(), ZmqError> {
+    if greeting.len() < GREETING_SIZE
+        || greeting[0] != SIGNATURE_START
+        || greeting[9] != SIGNATURE_END
+        || greeting[10] < VERSION_MAJOR
+    {
+        return Err(ZmqError::InvalidGreeting);
+    }
+
+    let mechanism = &greeting[MECHANISM_OFFSET..MECHANISM_OFFSET + MECHANISM_SIZE];
+    let mechanism = match mechanism.iter().position(|&byte| byte == 0) {
+        Some(end) => &mechanism[..end],
+        None => mechanism,
+    };
+    if mechanism != NULL_MECHANISM {
+        return Err(ZmqError::UnsupportedMechanism(
+            String::from_utf8_lossy(mechanism).to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Creates `READY` command, that completes `NULL` handshake.
+pub(crate) fn ready(socket_type: SocketType) -> Vec<u8> {
+    let name = socket_type.name();
+
+    let mut body = Vec::new();
+    body.push(READY.len() as u8);
+    body.extend_from_slice(READY);
+    body.push(SOCKET_TYPE.len() as u8);
+    body.extend_from_slice(SOCKET_TYPE);
+    body.extend_from_slice(&(name.len() as u32).to_be_bytes());
+    body.extend_from_slice(name);
+
+    let mut bytes = Vec::new();
+    encode_frame(&mut bytes, &body, FLAG_COMMAND);
+    bytes
+}
+
+/// Validates `READY` command of a peer.
+pub(crate) fn check_ready(event: ZmtpEvent, socket_type: SocketType) -> Result<(), ZmqError> {
+    let (name, data) = match event {
+        ZmtpEvent::Command(name, data) => (name, data),
+        ZmtpEvent::Message(_) => return Err(ZmqError::MalformedFrame),
+    };
+
+    if name == ERROR {
+        let reason = data.get(1..).unwrap_or_default();
+        return Err(ZmqError::Rejected(
+            String::from_utf8_lossy(reason).to_string(),
+        ));
+    }
+    if name != READY {
+        return Err(ZmqError::MalformedFrame);
+    }
+
+    let peer = peer_socket_type(&data)?;
+    if !socket_type.accepts(peer) {
+        return Err(ZmqError::IncompatibleSocket(
+            String::from_utf8_lossy(peer).to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Creates a subscription message for a `topic` prefix.
+pub(crate) fn subscription(topic: &[u8]) -> Vec<u8> {
+    let mut body = vec![SUBSCRIBE_MARKER];
+    body.extend_from_slice(topic);
+    encode_message(&[&body])
+}
+
+/// Creates a message with a serialized MAVLink `frame` published under a `topic`.
+///
+/// Empty topic produces a single-part message.
+pub(crate) fn publication(topic: &[u8], frame: &[u8]) -> Vec<u8> {
+    if topic.is_empty() {
+        encode_message(&[frame])
+    } else {
+        encode_message(&[topic, frame])
+    }
+}
+
+/// Key, which subscriptions of a publication are matched against.
+pub(crate) fn publication_key<'a>(topic: &'a [u8], frame: &'a [u8]) -> &'a [u8] {
+    if topic.is_empty() {
+        frame
+    } else {
+        topic
+    }
+}
+
+/// Extracts a serialized MAVLink frame from a published message.
+///
+/// Frame is the last part of the message, the preceding parts are topic envelopes.
+pub(crate) fn published_frame(event: ZmtpEvent) -> Option<Vec<u8>> {
+    match event {
+        ZmtpEvent::Message(mut parts) => parts.pop(),
+        ZmtpEvent::Command(_, _) => None,
+    }
+}
+
+fn encode_message(parts: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for (idx, part) in parts.iter().enumerate() {
+        let flags = if idx + 1 < parts.len() { FLAG_MORE } else { 0 };
+        encode_frame(&mut bytes, part, flags);
+    }
+    bytes
+}
+
+fn encode_frame(bytes: &mut Vec<u8>, body: &[u8], flags: u8) {
+    if body.len() > u8::MAX as usize {
+        bytes.push(flags | FLAG_LONG);
+        bytes.extend_from_slice(&(body.len() as u64).to_be_bytes());
+    } else {
+        bytes.push(flags);
+        bytes.push(body.len() as u8);
+    }
+    bytes.extend_from_slice(body);
+}
+
+fn parse_command(body: Vec<u8>) -> Result<ZmtpEvent, ZmqError> {
+    let (&name_size, rest) = body.split_first().ok_or(ZmqError::MalformedFrame)?;
+    let name_size = name_size as usize;
+    if rest.len() < name_size {
+        return Err(ZmqError::MalformedFrame);
+    }
+    let (name, data) = rest.split_at(name_size);
+    Ok(ZmtpEvent::Command(name.to_vec(), data.to_vec()))
+}
+
+fn peer_socket_type(mut properties: &[u8]) -> Result<&[u8], ZmqError> {
+    while let Some((&name_size, rest)) = properties.split_first() {
+        let name_size = name_size as usize;
+        let name = rest.get(..name_size).ok_or(ZmqError::MalformedFrame)?;
+        let value_size = rest
+            .get(name_size..name_size + 4)
+            .ok_or(ZmqError::MalformedFrame)?;
+        let value_size =
+            u32::from_be_bytes([value_size[0], value_size[1], value_size[2], value_size[3]])
+                as usize;
+        let value_start = name_size + 4;
+        let value = rest
+            .get(value_start..value_start + value_size)
+            .ok_or(ZmqError::MalformedFrame)?;
+
+        if name.eq_ignore_ascii_case(SOCKET_TYPE) {
+            return Ok(value);
+        }
+        properties = &rest[value_start + value_size..];
+    }
+
+    Err(ZmqError::MalformedFrame)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod zmtp_tests {
+    use super::*;
+
+    #[test]
+    fn handshake() {
+        check_greeting(&greeting()).unwrap();
+
+        let mut curve = greeting();
+        curve[MECHANISM_OFFSET..MECHANISM_OFFSET + 5].copy_from_slice(b"CURVE");
+        assert!(matches!(
+            check_greeting(&curve),
+            Err(ZmqError::UnsupportedMechanism(mechanism)) if mechanism == "CURVE"
+        ));
+        assert!(matches!(
+            check_greeting(&[0; GREETING_SIZE]),
+            Err(ZmqError::InvalidGreeting)
+        ));
+
+        let mut decoder = ZmtpDecoder::default();
+        decoder.push(&ready(SocketType::Pub));
+        let event = decoder.next().unwrap().unwrap();
+        check_ready(event.clone(), SocketType::Sub).unwrap();
+        assert!(matches!(
+            check_ready(event, SocketType::Pub),
+            Err(ZmqError::IncompatibleSocket(peer)) if peer == "PUB"
+        ));
+    }
+
+    #[test]
+    fn messages_are_decoded_incrementally() {
+        let long_frame = vec![42u8; 300];
+        let mut bytes = publication(b"mavlink", &long_frame);
+        bytes.extend(publication(b"", &[1, 2, 3]));
+
+        let mut decoder = ZmtpDecoder::default();
+        let mut events = Vec::new();
+        for byte in bytes {
+            decoder.push(&[byte]);
+            while let Some(event) = decoder.next().unwrap() {
+                events.push(event);
+            }
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                ZmtpEvent::Message(vec![b"mavlink".to_vec(), long_frame.clone()]),
+                ZmtpEvent::Message(vec![vec![1, 2, 3]]),
+            ]
+        );
+        assert_eq!(published_frame(events[0].clone()), Some(long_frame));
+    }
+
+    #[test]
+    fn subscriptions() {
+        let mut decoder = ZmtpDecoder::default();
+        let mut subscriptions = Subscriptions::default();
+        assert!(!subscriptions.matches(b"mavlink/1"));
+
+        decoder.push(&subscription(b"mavlink/"));
+        subscriptions.handle(&decoder.next().unwrap().unwrap());
+        assert!(subscriptions.matches(b"mavlink/1"));
+        assert!(!subscriptions.matches(b"video"));
+
+        subscriptions.handle(&ZmtpEvent::Command(CANCEL.to_vec(), b"mavlink/".to_vec()));
+        assert!(!subscriptions.matches(b"mavlink/1"));
+
+        subscriptions.handle(&ZmtpEvent::Command(SUBSCRIBE.to_vec(), Vec::new()));
+        assert!(subscriptions.matches(b"anything"));
+    }
+}
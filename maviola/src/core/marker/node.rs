@@ -2,7 +2,9 @@
 
 use std::fmt::Debug;
 
+use crate::core::io::ConnectionInfo;
 use crate::core::utils::Sealed;
+use crate::error::ConfigDiagnostic;
 use crate::protocol::{ComponentId, DeviceId, Endpoint, MaybeVersioned, SystemId, Unset};
 
 /// <sup>🔒</sup>
@@ -60,12 +62,22 @@ impl MaybeConnConf for Unset {}
 ///
 /// 🔒 This trait is sealed 🔒
 pub trait HasConnConf: MaybeConnConf {
+    /// Connection information.
+    fn info(&self) -> &ConnectionInfo;
+
     /// Returns `true` if it makes sense to restart the node after connection failure.
     ///
     /// A blanket implementation always returns `false`.
     fn is_repairable(&self) -> bool {
         false
     }
+
+    /// Problems found in connection configuration.
+    ///
+    /// A blanket implementation returns no diagnostics.
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Vec::new()
+    }
 }
 
 /// <sup>🔒</sup>
@@ -3,6 +3,16 @@
 pub mod consts;
 pub mod io;
 pub mod marker;
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-mission",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-ftp",
+    feature = "msrv-utils-camera",
+    feature = "msrv-utils-gimbal",
+    feature = "msrv-utils-timesync"
+))]
+pub mod msrv;
 pub mod network;
 pub mod node;
 pub mod utils;
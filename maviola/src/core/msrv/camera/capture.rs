@@ -0,0 +1,121 @@
+use std::time::Instant;
+
+use crate::core::msrv::camera::info::decode_str;
+use crate::core::msrv::camera::operation::Exchange;
+use crate::dialects::common::enums::MavCmd;
+use crate::dialects::common::messages::CameraImageCaptured;
+use crate::dialects::Common;
+use crate::error::CameraError;
+
+use crate::core::msrv::camera::{CameraOperation, CameraSettings, CameraStep};
+use crate::prelude::*;
+
+/// Image reported by a camera in `CAMERA_IMAGE_CAPTURED` message.
+#[derive(Clone, Debug)]
+pub struct CapturedImage {
+    message: CameraImageCaptured,
+}
+
+impl CapturedImage {
+    /// Zero-based index of the image since the camera was armed.
+    pub fn index(&self) -> i32 {
+        self.message.image_index
+    }
+
+    /// Returns `true`, if the image was captured successfully.
+    pub fn is_success(&self) -> bool {
+        self.message.capture_result == 1
+    }
+
+    /// URL of the image file, if provided.
+    pub fn file_url(&self) -> Option<String> {
+        let url = decode_str(&self.message.file_url);
+        if url.is_empty() {
+            None
+        } else {
+            Some(url)
+        }
+    }
+
+    /// Original `CAMERA_IMAGE_CAPTURED` message.
+    pub fn message(&self) -> &CameraImageCaptured {
+        &self.message
+    }
+}
+
+impl From<CameraImageCaptured> for CapturedImage {
+    fn from(message: CameraImageCaptured) -> Self {
+        Self { message }
+    }
+}
+
+/// Captures a single image with `MAV_CMD_IMAGE_START_CAPTURE`.
+///
+/// Once the command is accepted, operation waits for `CAMERA_IMAGE_CAPTURED` for
+/// [`CameraSettings::capture_timeout`]. The command is not resent after it was acknowledged, so a
+/// camera won't take the same picture twice.
+///
+/// Each capture carries a `sequence` number, that cameras use to detect retransmitted commands. It
+/// should start from `1` and increase with each capture.
+#[derive(Debug)]
+pub struct ImageCapture {
+    exchange: Exchange,
+}
+
+impl ImageCapture {
+    /// Creates an operation, that captures an image with a `sequence` number.
+    pub fn new(settings: CameraSettings, sequence: u32) -> Self {
+        Self {
+            exchange: Exchange::new(
+                settings,
+                MavCmd::ImageStartCapture,
+                [0.0, 0.0, 1.0, sequence as f32, 0.0, 0.0, 0.0],
+            ),
+        }
+    }
+}
+
+impl CameraOperation for ImageCapture {
+    type Output = CapturedImage;
+
+    fn start(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> CameraStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return CameraStep::Wait,
+        };
+        if let Common::CameraImageCaptured(image) = message {
+            let image = CapturedImage::from(image);
+            return if image.is_success() {
+                CameraStep::Finished(Ok(image))
+            } else {
+                CameraStep::Finished(Err(CameraError::CaptureFailed(image.index()).into()))
+            };
+        }
+
+        match self.exchange.ack(&message, now) {
+            Some(Ok(())) => {
+                let timeout = self.exchange.settings.capture_timeout();
+                self.exchange.wait_until(now + timeout);
+                CameraStep::Wait
+            }
+            Some(Err(err)) => CameraStep::Finished(Err(err.into())),
+            None => CameraStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
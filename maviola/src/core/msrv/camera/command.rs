@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use crate::core::msrv::camera::operation::Exchange;
+use crate::dialects::common::enums::MavCmd;
+
+use crate::core::msrv::camera::{CameraOperation, CameraSettings, CameraStep};
+use crate::prelude::*;
+
+/// Camera command, that only requires an acknowledgement.
+///
+/// Sends a `COMMAND_LONG` and finishes once the camera responds with `COMMAND_ACK`. Commands that
+/// are acknowledged as in progress keep waiting for the final result.
+#[derive(Debug)]
+pub struct CameraCommand {
+    exchange: Exchange,
+}
+
+impl CameraCommand {
+    /// Creates an operation, that sends an arbitrary `command` with `params`.
+    pub fn new(settings: CameraSettings, command: MavCmd, params: [f32; 7]) -> Self {
+        Self {
+            exchange: Exchange::new(settings, command, params),
+        }
+    }
+
+    /// Starts capturing `count` images every `interval` with `MAV_CMD_IMAGE_START_CAPTURE`.
+    ///
+    /// If `count` is `0`, then images are captured until
+    /// [`stop_image_capture`](Self::stop_image_capture).
+    pub fn start_image_capture(settings: CameraSettings, interval: Duration, count: u32) -> Self {
+        Self::new(
+            settings,
+            MavCmd::ImageStartCapture,
+            [
+                0.0,
+                interval.as_secs_f32(),
+                count as f32,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            ],
+        )
+    }
+
+    /// Stops image capture with `MAV_CMD_IMAGE_STOP_CAPTURE`.
+    pub fn stop_image_capture(settings: CameraSettings) -> Self {
+        Self::new(settings, MavCmd::ImageStopCapture, [0.0; 7])
+    }
+
+    /// Starts video streaming with `MAV_CMD_VIDEO_START_STREAMING`.
+    ///
+    /// Stream `0` addresses all streams of the camera.
+    pub fn start_video_streaming(settings: CameraSettings, stream_id: u8) -> Self {
+        Self::new(
+            settings,
+            MavCmd::VideoStartStreaming,
+            [stream_id as f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        )
+    }
+
+    /// Stops video streaming with `MAV_CMD_VIDEO_STOP_STREAMING`.
+    ///
+    /// Stream `0` addresses all streams of the camera.
+    pub fn stop_video_streaming(settings: CameraSettings, stream_id: u8) -> Self {
+        Self::new(
+            settings,
+            MavCmd::VideoStopStreaming,
+            [stream_id as f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        )
+    }
+}
+
+impl CameraOperation for CameraCommand {
+    type Output = ();
+
+    fn start(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> CameraStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return CameraStep::Wait,
+        };
+
+        match self.exchange.ack(&message, now) {
+            Some(result) => CameraStep::Finished(result.map_err(Error::from)),
+            None => CameraStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
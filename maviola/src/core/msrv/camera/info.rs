@@ -0,0 +1,133 @@
+use std::time::Instant;
+
+use crate::core::msrv::camera::operation::Exchange;
+use crate::dialects::common::enums::{CameraCapFlags, MavCmd};
+use crate::dialects::common::messages::CameraInformation;
+use crate::dialects::Common;
+
+use crate::core::msrv::camera::{CameraOperation, CameraSettings, CameraStep};
+use crate::prelude::*;
+
+/// Camera description received in `CAMERA_INFORMATION` message.
+#[derive(Clone, Debug)]
+pub struct CameraInfo {
+    message: CameraInformation,
+}
+
+impl CameraInfo {
+    /// Name of the camera vendor.
+    pub fn vendor_name(&self) -> String {
+        decode_str(&self.message.vendor_name)
+    }
+
+    /// Name of the camera model.
+    pub fn model_name(&self) -> String {
+        decode_str(&self.message.model_name)
+    }
+
+    /// Firmware version encoded as `(Dev & 0xff) << 24 | (Patch & 0xff) << 16 | (Minor & 0xff) << 8
+    /// | (Major & 0xff)`.
+    pub fn firmware_version(&self) -> u32 {
+        self.message.firmware_version
+    }
+
+    /// Horizontal and vertical image resolution in pixels.
+    pub fn resolution(&self) -> (u16, u16) {
+        (self.message.resolution_h, self.message.resolution_v)
+    }
+
+    /// Capabilities of the camera.
+    pub fn capabilities(&self) -> CameraCapFlags {
+        self.message.flags
+    }
+
+    /// URI of a camera definition file, if provided.
+    pub fn definition_uri(&self) -> Option<String> {
+        let uri = decode_str(&self.message.cam_definition_uri);
+        if uri.is_empty() {
+            None
+        } else {
+            Some(uri)
+        }
+    }
+
+    /// Original `CAMERA_INFORMATION` message.
+    pub fn message(&self) -> &CameraInformation {
+        &self.message
+    }
+}
+
+impl From<CameraInformation> for CameraInfo {
+    fn from(message: CameraInformation) -> Self {
+        Self { message }
+    }
+}
+
+/// Requests `CAMERA_INFORMATION` with `MAV_CMD_REQUEST_MESSAGE`.
+///
+/// The requested message may arrive before or after the command is acknowledged. Once the command
+/// is accepted, operation waits for the message for another timeout.
+#[derive(Debug)]
+pub struct CameraInformationRequest {
+    exchange: Exchange,
+}
+
+impl CameraInformationRequest {
+    /// Creates an operation, that requests camera information.
+    pub fn new(settings: CameraSettings) -> Self {
+        let message_id = CameraInformation::message_id() as f32;
+        Self {
+            exchange: Exchange::new(
+                settings,
+                MavCmd::RequestMessage,
+                [message_id, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            ),
+        }
+    }
+}
+
+impl CameraOperation for CameraInformationRequest {
+    type Output = CameraInfo;
+
+    fn start(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> CameraStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return CameraStep::Wait,
+        };
+        if let Common::CameraInformation(info) = message {
+            return CameraStep::Finished(Ok(info.into()));
+        }
+
+        match self.exchange.ack(&message, now) {
+            Some(Ok(())) => {
+                let timeout = self.exchange.settings.timeout();
+                self.exchange.wait_until(now + timeout);
+                CameraStep::Wait
+            }
+            Some(Err(err)) => CameraStep::Finished(Err(err.into())),
+            None => CameraStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> CameraStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
+
+/// Decodes a string field padded with zeros.
+pub(super) fn decode_str(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).to_string()
+}
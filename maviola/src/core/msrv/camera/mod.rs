@@ -0,0 +1,263 @@
+//! # Camera protocol
+//!
+//! Implements client side of [camera protocol](https://mavlink.io/en/services/camera.html), that
+//! controls cameras with `COMMAND_LONG` commands.
+//!
+//! Like [file transfers](crate::core::msrv::ftp), camera operations are implemented as I/O-free
+//! state machines, that implement [`CameraOperation`] trait. Each command is matched with a
+//! `COMMAND_ACK` for the same command and resent with increased `confirmation` on timeout:
+//!
+//! * [`CameraCommand`] starts or stops image capture and video streaming.
+//! * [`CameraInformationRequest`] requests [`CameraInfo`] with `MAV_CMD_REQUEST_MESSAGE`.
+//! * [`ImageCapture`] captures a single image and waits for a [`CapturedImage`].
+//!
+//! Edge nodes provide `camera_client` method, that returns a client bound to the node and a camera
+//! defined by [`CameraSettings`]. Client drives these state machines over node connection.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use maviola::core::msrv::camera::CameraSettings;
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let node = Node::sync::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().unwrap();
+//!
+//! let camera = node.camera_client(CameraSettings::new(MavLinkId::new(1, 100)));
+//!
+//! let info = camera.information().unwrap();
+//! println!("{} {}", info.vendor_name(), info.model_name());
+//!
+//! let image = camera.capture_image().unwrap();
+//! println!("image #{}: {:?}", image.index(), image.file_url());
+//!
+//! camera.start_video_streaming(0).unwrap();
+//! ```
+
+mod capture;
+mod command;
+mod info;
+mod operation;
+mod settings;
+
+pub use capture::{CapturedImage, ImageCapture};
+pub use command::CameraCommand;
+pub use info::{CameraInfo, CameraInformationRequest};
+pub use operation::{CameraOperation, CameraStep};
+pub use settings::CameraSettings;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::dialects::common::enums::{MavCmd, MavResult};
+    use crate::dialects::common::messages::{
+        CameraImageCaptured, CameraInformation, CommandAck, CommandLong,
+    };
+    use crate::error::CameraError;
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const CAMERA_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 100,
+    };
+
+    fn settings() -> CameraSettings {
+        CameraSettings::new(CAMERA_ID)
+    }
+
+    fn frame(id: MavLinkId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(id).next_frame(message).unwrap()
+    }
+
+    fn ack(command: &CommandLong, result: MavResult) -> Frame<V2> {
+        frame(
+            CAMERA_ID,
+            &CommandAck {
+                command: command.command,
+                result,
+                progress: 0,
+                result_param2: 0,
+                target_system: 255,
+                target_component: 190,
+            },
+        )
+    }
+
+    fn sent<T>(step: CameraStep<T>) -> CommandLong {
+        match step {
+            CameraStep::Send(command) => command,
+            _ => panic!("command expected"),
+        }
+    }
+
+    fn information() -> CameraInformation {
+        let mut info = CameraInformation {
+            resolution_h: 1920,
+            resolution_v: 1080,
+            ..Default::default()
+        };
+        info.vendor_name[..4].copy_from_slice(b"Acme");
+        info.model_name[..3].copy_from_slice(b"X-1");
+        info
+    }
+
+    fn captured(image_index: i32, capture_result: i8) -> CameraImageCaptured {
+        let mut image = CameraImageCaptured {
+            image_index,
+            capture_result,
+            ..Default::default()
+        };
+        image.file_url[..9].copy_from_slice(b"/img1.jpg");
+        image
+    }
+
+    #[test]
+    fn camera_commands() {
+        let now = Instant::now();
+        let mut streaming = CameraCommand::start_video_streaming(settings(), 2);
+
+        let command = sent(streaming.start(now));
+        assert!(matches!(command.command, MavCmd::VideoStartStreaming));
+        assert_eq!(command.param1, 2.0);
+        assert_eq!(command.target_system, CAMERA_ID.system);
+        assert_eq!(command.target_component, CAMERA_ID.component);
+
+        // Acknowledgement of another command
+        let other_command = sent(CameraCommand::stop_image_capture(settings()).start(now));
+        assert!(matches!(
+            streaming.handle(&ack(&other_command, MavResult::Accepted), now),
+            CameraStep::Wait
+        ));
+
+        assert!(matches!(
+            streaming.handle(&ack(&command, MavResult::Accepted), now),
+            CameraStep::Finished(Ok(()))
+        ));
+
+        let mut capture =
+            CameraCommand::start_image_capture(settings(), Duration::from_millis(500), 10);
+        let command = sent(capture.start(now));
+        assert_eq!((command.param2, command.param3), (0.5, 10.0));
+        assert!(matches!(
+            capture.handle(&ack(&command, MavResult::Denied), now),
+            CameraStep::Finished(Err(Error::Camera(CameraError::Rejected(MavResult::Denied))))
+        ));
+    }
+
+    #[test]
+    fn request_information() {
+        let now = Instant::now();
+
+        let mut request = CameraInformationRequest::new(settings());
+        let command = sent(request.start(now));
+        assert!(matches!(command.command, MavCmd::RequestMessage));
+        assert_eq!(command.param1, CameraInformation::message_id() as f32);
+
+        assert!(matches!(
+            request.handle(&ack(&command, MavResult::Accepted), now),
+            CameraStep::Wait
+        ));
+        let info = match request.handle(&frame(CAMERA_ID, &information()), now) {
+            CameraStep::Finished(Ok(info)) => info,
+            _ => panic!("camera information expected"),
+        };
+        assert_eq!(info.vendor_name(), "Acme");
+        assert_eq!(info.model_name(), "X-1");
+        assert_eq!(info.resolution(), (1920, 1080));
+        assert!(info.definition_uri().is_none());
+
+        // Information may arrive before acknowledgement
+        let mut request = CameraInformationRequest::new(settings());
+        request.start(now);
+        assert!(matches!(
+            request.handle(&frame(MavLinkId::new(2, 100), &information()), now),
+            CameraStep::Wait
+        ));
+        assert!(matches!(
+            request.handle(&frame(CAMERA_ID, &information()), now),
+            CameraStep::Finished(Ok(_))
+        ));
+    }
+
+    #[test]
+    fn capture_image() {
+        let settings = settings().with_capture_timeout(Duration::from_secs(10));
+        let now = Instant::now();
+
+        let mut capture = ImageCapture::new(settings, 7);
+        let command = sent(capture.start(now));
+        assert!(matches!(command.command, MavCmd::ImageStartCapture));
+        assert_eq!((command.param3, command.param4), (1.0, 7.0));
+
+        assert!(matches!(
+            capture.handle(&ack(&command, MavResult::Accepted), now),
+            CameraStep::Wait
+        ));
+        assert_eq!(capture.deadline(), now + Duration::from_secs(10));
+        // Acknowledged command is not resent
+        assert!(matches!(
+            capture.check(now + settings.timeout()),
+            CameraStep::Wait
+        ));
+
+        let image = match capture.handle(&frame(CAMERA_ID, &captured(3, 1)), now) {
+            CameraStep::Finished(Ok(image)) => image,
+            _ => panic!("captured image expected"),
+        };
+        assert_eq!(image.index(), 3);
+        assert_eq!(image.file_url().unwrap(), "/img1.jpg");
+
+        let mut capture = ImageCapture::new(settings, 8);
+        capture.start(now);
+        assert!(matches!(
+            capture.handle(&frame(CAMERA_ID, &captured(4, 0)), now),
+            CameraStep::Finished(Err(Error::Camera(CameraError::CaptureFailed(4))))
+        ));
+    }
+
+    #[test]
+    fn retries_and_times_out() {
+        let timeout = Duration::from_millis(100);
+        let settings = settings().with_timeout(timeout).with_retries(2);
+        let mut command = CameraCommand::stop_video_streaming(settings, 0);
+
+        let now = Instant::now();
+        let first = sent(command.start(now));
+        assert_eq!(first.confirmation, 0);
+        assert!(matches!(command.check(now), CameraStep::Wait));
+
+        let mut now = command.deadline();
+        for confirmation in 1..=2 {
+            assert_eq!(sent(command.check(now)).confirmation, confirmation);
+            now = command.deadline();
+        }
+        assert!(matches!(
+            command.check(now),
+            CameraStep::Finished(Err(Error::Camera(CameraError::Timeout)))
+        ));
+
+        // Command in progress postpones the deadline, but is not resent
+        let mut command = CameraCommand::stop_video_streaming(settings, 0);
+        let sent_command = sent(command.start(now));
+        let later = now + timeout / 2;
+        assert!(matches!(
+            command.handle(&ack(&sent_command, MavResult::InProgress), later),
+            CameraStep::Wait
+        ));
+        assert_eq!(command.deadline(), later + timeout);
+        assert!(matches!(
+            command.check(command.deadline()),
+            CameraStep::Finished(Err(Error::Camera(CameraError::Timeout)))
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Instant;
+
+use crate::dialects::common::enums::{MavCmd, MavResult};
+use crate::dialects::common::messages::CommandLong;
+use crate::dialects::Common;
+use crate::error::CameraError;
+
+use crate::core::msrv::camera::CameraSettings;
+use crate::prelude::*;
+
+/// Action requested by a [`CameraOperation`].
+#[derive(Debug)]
+pub enum CameraStep<T> {
+    /// Nothing to send, wait for the next frame or [`CameraOperation::deadline`].
+    Wait,
+    /// Send a command to the camera and continue.
+    Send(CommandLong),
+    /// Operation is finished.
+    Finished(Result<T>),
+}
+
+/// Camera operation state machine.
+///
+/// Operations do not perform any I/O. The caller should send commands requested by returned
+/// [`CameraStep`]s, pass all incoming frames to [`CameraOperation::handle`], and call
+/// [`CameraOperation::check`] once [`CameraOperation::deadline`] is reached. Methods should not be
+/// called after operation is finished.
+pub trait CameraOperation {
+    /// Result of a successful operation.
+    type Output;
+
+    /// Starts the operation.
+    fn start(&mut self, now: Instant) -> CameraStep<Self::Output>;
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other components, unrelated messages, and acknowledgements of other commands
+    /// are ignored.
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> CameraStep<Self::Output>;
+
+    /// Checks whether the camera response has timed out.
+    ///
+    /// Resends an unacknowledged command with increased `confirmation`, if retries are not
+    /// exhausted. Otherwise, fails the operation with [`CameraError::Timeout`].
+    fn check(&mut self, now: Instant) -> CameraStep<Self::Output>;
+
+    /// Time, when [`CameraOperation::check`] should be called, if no frames were received.
+    fn deadline(&self) -> Instant;
+}
+
+/// Tracks a sent command, its acknowledgement and retries.
+#[derive(Debug)]
+pub(super) struct Exchange {
+    pub(super) settings: CameraSettings,
+    command: CommandLong,
+    attempts: usize,
+    deadline: Instant,
+    acknowledged: bool,
+}
+
+impl Exchange {
+    pub(super) fn new(settings: CameraSettings, command: MavCmd, params: [f32; 7]) -> Self {
+        let camera = settings.camera();
+        Self {
+            settings,
+            command: CommandLong {
+                target_system: camera.system,
+                target_component: camera.component,
+                command,
+                confirmation: 0,
+                param1: params[0],
+                param2: params[1],
+                param3: params[2],
+                param4: params[3],
+                param5: params[4],
+                param6: params[5],
+                param7: params[6],
+            },
+            attempts: 0,
+            deadline: Instant::now() + settings.timeout(),
+            acknowledged: false,
+        }
+    }
+
+    pub(super) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Waits for another message until `deadline` without resending the command.
+    pub(super) fn wait_until(&mut self, deadline: Instant) {
+        self.deadline = deadline;
+    }
+
+    pub(super) fn send<T>(&mut self, now: Instant) -> CameraStep<T> {
+        self.attempts = 0;
+        self.deadline = now + self.settings.timeout();
+        CameraStep::Send(self.command.clone())
+    }
+
+    /// Decodes a message sent by the camera.
+    pub(super) fn decode<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Option<Common> {
+        if !self.settings.is_camera(frame) {
+            return None;
+        }
+        frame.decode::<Common>().ok()
+    }
+
+    /// Handles a `COMMAND_ACK` for the sent command.
+    ///
+    /// Returns [`None`] for unrelated messages and for [`MavResult::InProgress`], that postpones
+    /// the deadline. Otherwise, returns whether the command was accepted.
+    pub(super) fn ack(
+        &mut self,
+        message: &Common,
+        now: Instant,
+    ) -> Option<std::result::Result<(), CameraError>> {
+        let ack = match message {
+            Common::CommandAck(ack) if ack.command as u16 == self.command.command as u16 => ack,
+            _ => return None,
+        };
+
+        self.acknowledged = true;
+        match ack.result {
+            MavResult::Accepted => Some(Ok(())),
+            MavResult::InProgress => {
+                self.deadline = now + self.settings.timeout();
+                None
+            }
+            result => Some(Err(CameraError::Rejected(result))),
+        }
+    }
+
+    pub(super) fn check<T>(&mut self, now: Instant) -> CameraStep<T> {
+        if now < self.deadline {
+            return CameraStep::Wait;
+        }
+
+        if !self.acknowledged && self.attempts < self.settings.retries() {
+            self.attempts += 1;
+            self.deadline = now + self.settings.timeout();
+            self.command.confirmation = self.attempts.min(u8::MAX as usize) as u8;
+            return CameraStep::Send(self.command.clone());
+        }
+        CameraStep::Finished(Err(CameraError::Timeout.into()))
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use crate::core::consts::{
+    DEFAULT_CAMERA_CAPTURE_TIMEOUT, DEFAULT_CAMERA_RETRIES, DEFAULT_CAMERA_TIMEOUT,
+};
+
+use crate::prelude::*;
+
+/// Settings of camera operations.
+///
+/// Defines a camera component, how long to wait for its acknowledgements, and how long to wait
+/// for a captured image to be reported.
+///
+/// If component `ID` of a camera is `0`, then commands are addressed to all components of the
+/// camera system and responses are accepted from any of them.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraSettings {
+    camera: MavLinkId,
+    timeout: Duration,
+    retries: usize,
+    capture_timeout: Duration,
+}
+
+impl CameraSettings {
+    /// Creates settings for operations with a `camera`.
+    ///
+    /// Uses [`DEFAULT_CAMERA_TIMEOUT`], [`DEFAULT_CAMERA_RETRIES`], and
+    /// [`DEFAULT_CAMERA_CAPTURE_TIMEOUT`].
+    pub fn new(camera: MavLinkId) -> Self {
+        Self {
+            camera,
+            timeout: DEFAULT_CAMERA_TIMEOUT,
+            retries: DEFAULT_CAMERA_RETRIES,
+            capture_timeout: DEFAULT_CAMERA_CAPTURE_TIMEOUT,
+        }
+    }
+
+    /// Sets time to wait for a camera response before a command is resent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times a command is resent before operation fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets time to wait for `CAMERA_IMAGE_CAPTURED` once image capture is acknowledged.
+    pub fn with_capture_timeout(mut self, capture_timeout: Duration) -> Self {
+        self.capture_timeout = capture_timeout;
+        self
+    }
+
+    /// Camera component.
+    pub fn camera(&self) -> MavLinkId {
+        self.camera
+    }
+
+    /// Time to wait for a camera response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Number of times a command is resent before operation fails.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Time to wait for a captured image to be reported.
+    pub fn capture_timeout(&self) -> Duration {
+        self.capture_timeout
+    }
+
+    pub(super) fn is_camera<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        frame.system_id() == self.camera.system
+            && (self.camera.component == 0 || frame.component_id() == self.camera.component)
+    }
+}
@@ -0,0 +1,160 @@
+use std::time::Instant;
+
+use crate::core::msrv::ftp::transfer::{encode_path, Exchange};
+use crate::error::FtpError;
+
+use crate::core::msrv::ftp::{
+    ftp_crc32, FtpNakCode, FtpOpcode, FtpPayload, FtpSettings, FtpStep, FtpTransfer,
+    MAX_FTP_DATA_SIZE,
+};
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Open,
+    Read,
+    Terminate,
+    Verify,
+}
+
+/// Reads a remote file.
+///
+/// Opens the file with `OpenFileRO` command, reads it chunk by chunk with `ReadFile`, and closes
+/// the session with `TerminateSession`. If [`FtpSettings::with_crc_check`] is enabled, then
+/// received contents are verified with `CalcFileCRC32` command.
+///
+/// If transfer fails while the session is open, then session termination is requested.
+#[derive(Debug)]
+pub struct FileDownload {
+    exchange: Exchange,
+    path: String,
+    stage: Stage,
+    session: u8,
+    size: u32,
+    data: Vec<u8>,
+}
+
+impl FileDownload {
+    /// Creates a transfer, that reads a file at `path`.
+    pub fn new(settings: FtpSettings, path: impl Into<String>) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            path: path.into(),
+            stage: Stage::Open,
+            session: 0,
+            size: 0,
+            data: Vec::new(),
+        }
+    }
+
+    fn next(&mut self, now: Instant) -> FtpStep<Vec<u8>> {
+        if self.data.len() < self.size as usize {
+            let request = FtpPayload::request(FtpOpcode::ReadFile)
+                .with_session(self.session)
+                .with_offset(self.data.len() as u32)
+                .with_size(MAX_FTP_DATA_SIZE);
+            return self.exchange.send(request, now);
+        }
+
+        self.stage = Stage::Terminate;
+        let request = FtpPayload::request(FtpOpcode::TerminateSession).with_session(self.session);
+        self.exchange.send(request, now)
+    }
+
+    fn finish(&mut self, now: Instant) -> FtpStep<Vec<u8>> {
+        if !self.exchange.settings.crc_check() {
+            return FtpStep::Finished(None, Ok(std::mem::take(&mut self.data)));
+        }
+
+        self.stage = Stage::Verify;
+        let request =
+            FtpPayload::request(FtpOpcode::CalcFileCRC32).with_data(self.path.as_bytes().to_vec());
+        self.exchange.send(request, now)
+    }
+}
+
+impl FtpTransfer for FileDownload {
+    type Output = Vec<u8>;
+
+    fn start(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        let path = match encode_path(&self.path) {
+            Ok(path) => path,
+            Err(err) => return self.exchange.fail(None, err),
+        };
+
+        self.stage = Stage::Open;
+        let request = FtpPayload::request(FtpOpcode::OpenFileRO).with_data(path);
+        self.exchange.send(request, now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> FtpStep<Self::Output> {
+        let response = match self.exchange.response(frame) {
+            Some(response) => response,
+            None => return FtpStep::Wait,
+        };
+        let is_nak = response.opcode() == FtpOpcode::Nak;
+
+        match self.stage {
+            Stage::Open if is_nak => self.exchange.reject(&response, None),
+            Stage::Open => {
+                self.session = response.session();
+                self.size = match response.data_u32() {
+                    Some(size) => size,
+                    None => {
+                        let err = FtpError::InvalidResponse(FtpOpcode::OpenFileRO);
+                        return self.exchange.fail(Some(self.session), err);
+                    }
+                };
+                self.data = Vec::with_capacity(self.size as usize);
+                self.stage = Stage::Read;
+                self.next(now)
+            }
+            Stage::Read => match response.nak_code() {
+                // File was truncated while being read
+                Some((FtpNakCode::EOF, _)) => {
+                    self.size = self.data.len() as u32;
+                    self.next(now)
+                }
+                Some(_) => self.exchange.reject(&response, Some(self.session)),
+                None => {
+                    if response.data().is_empty() {
+                        self.size = self.data.len() as u32;
+                    }
+                    self.data.extend_from_slice(response.data());
+                    self.next(now)
+                }
+            },
+            // Session is closed either way
+            Stage::Terminate => self.finish(now),
+            Stage::Verify if is_nak => self.exchange.reject(&response, None),
+            Stage::Verify => {
+                let expected = ftp_crc32(&self.data);
+                match response.data_u32() {
+                    Some(actual) if actual == expected => {
+                        FtpStep::Finished(None, Ok(std::mem::take(&mut self.data)))
+                    }
+                    Some(actual) => {
+                        let err = FtpError::CrcMismatch { expected, actual };
+                        self.exchange.fail(None, err)
+                    }
+                    None => {
+                        let err = FtpError::InvalidResponse(FtpOpcode::CalcFileCRC32);
+                        self.exchange.fail(None, err)
+                    }
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::Instant;
+
+use crate::core::msrv::ftp::transfer::{encode_path, Exchange};
+
+use crate::core::msrv::ftp::{
+    FtpNakCode, FtpOpcode, FtpPayload, FtpSettings, FtpStep, FtpTransfer,
+};
+use crate::prelude::*;
+
+/// Entry of a remote directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FtpEntry {
+    /// Regular file.
+    File {
+        /// File name.
+        name: String,
+        /// File size in bytes.
+        size: u32,
+    },
+    /// Directory.
+    Directory {
+        /// Directory name.
+        name: String,
+    },
+}
+
+impl FtpEntry {
+    /// Name of a file or a directory.
+    pub fn name(&self) -> &str {
+        match self {
+            FtpEntry::File { name, .. } => name,
+            FtpEntry::Directory { name } => name,
+        }
+    }
+
+    /// Returns `true`, if entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        matches!(self, FtpEntry::Directory { .. })
+    }
+
+    /// Parses an entry of `ListDirectory` response.
+    ///
+    /// Returns [`None`] for skipped entries, that server marks with `S`.
+    fn parse(raw: &[u8]) -> Option<Self> {
+        let (kind, body) = raw.split_first()?;
+        let body = String::from_utf8_lossy(body);
+
+        match kind {
+            b'F' => {
+                let (name, size) = match body.split_once('\t') {
+                    Some((name, size)) => (name, size.trim().parse().unwrap_or_default()),
+                    None => (body.as_ref(), 0),
+                };
+                Some(FtpEntry::File {
+                    name: name.to_string(),
+                    size,
+                })
+            }
+            b'D' => Some(FtpEntry::Directory {
+                name: body.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Lists a remote directory.
+///
+/// Requests entries with `ListDirectory` command until the server responds with
+/// [`FtpNakCode::EOF`]. Each response may contain several entries, the next request starts from
+/// the first entry, that was not received yet.
+#[derive(Debug)]
+pub struct DirectoryListing {
+    exchange: Exchange,
+    path: String,
+    offset: u32,
+    entries: Vec<FtpEntry>,
+}
+
+impl DirectoryListing {
+    /// Creates a transfer, that lists a directory at `path`.
+    pub fn new(settings: FtpSettings, path: impl Into<String>) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            path: path.into(),
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn next(&mut self, path: Vec<u8>, now: Instant) -> FtpStep<Vec<FtpEntry>> {
+        let request = FtpPayload::request(FtpOpcode::ListDirectory)
+            .with_offset(self.offset)
+            .with_data(path);
+        self.exchange.send(request, now)
+    }
+}
+
+impl FtpTransfer for DirectoryListing {
+    type Output = Vec<FtpEntry>;
+
+    fn start(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        match encode_path(&self.path) {
+            Ok(path) => self.next(path, now),
+            Err(err) => self.exchange.fail(None, err),
+        }
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> FtpStep<Self::Output> {
+        let response = match self.exchange.response(frame) {
+            Some(response) => response,
+            None => return FtpStep::Wait,
+        };
+
+        match response.nak_code() {
+            Some((FtpNakCode::EOF, _)) => {
+                return FtpStep::Finished(None, Ok(std::mem::take(&mut self.entries)))
+            }
+            Some(_) => return self.exchange.reject(&response, None),
+            None => {}
+        }
+
+        let received = self.offset;
+        for raw in response.data().split(|byte| *byte == 0) {
+            if raw.is_empty() {
+                continue;
+            }
+            self.offset += 1;
+            if let Some(entry) = FtpEntry::parse(raw) {
+                self.entries.push(entry);
+            }
+        }
+
+        // Servers that do not send EOF finish listing with an empty response
+        if self.offset == received {
+            return FtpStep::Finished(None, Ok(std::mem::take(&mut self.entries)));
+        }
+
+        // Path was validated when transfer started
+        self.next(self.path.as_bytes().to_vec(), now)
+    }
+
+    fn check(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
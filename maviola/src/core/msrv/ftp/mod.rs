@@ -0,0 +1,380 @@
+//! # File transfer protocol
+//!
+//! Implements client side of [MAVLink FTP](https://mavlink.io/en/services/ftp.html), that
+//! transfers files and directory listings inside `FILE_TRANSFER_PROTOCOL` messages.
+//!
+//! Like [mission](crate::core::msrv::mission) transfers, file transfers are implemented as
+//! I/O-free state machines, that implement [`FtpTransfer`] trait. Each request is matched with a
+//! response by its sequence number and resent with the same sequence number on timeout:
+//!
+//! * [`DirectoryListing`] lists a remote directory.
+//! * [`FileDownload`] downloads a file in chunks of [`MAX_FTP_DATA_SIZE`] bytes.
+//! * [`FileUpload`] uploads a file in chunks of [`MAX_FTP_DATA_SIZE`] bytes.
+//!
+//! Edge nodes provide `ftp_client` method, that returns a client bound to the node and an FTP
+//! server defined by [`FtpSettings`]. Client drives these state machines over node connection.
+//! Messages are encoded and decoded by [`FtpPayload`], that can be used to implement a server.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use maviola::core::msrv::ftp::FtpSettings;
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let node = Node::sync::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().unwrap();
+//!
+//! let ftp = node.ftp_client(FtpSettings::new(MavLinkId::new(1, 1)).with_crc_check(true));
+//!
+//! for entry in ftp.list_directory("/logs").unwrap() {
+//!     println!("{}", entry.name());
+//! }
+//! let params = ftp.read_file("@PARAM/param.pck").unwrap();
+//! ftp.write_file("/backup/param.pck", &params).unwrap();
+//! ```
+
+mod download;
+mod list;
+mod payload;
+mod settings;
+mod transfer;
+mod upload;
+
+pub use download::FileDownload;
+pub use list::{DirectoryListing, FtpEntry};
+pub use payload::{ftp_crc32, FtpNakCode, FtpOpcode, FtpPayload, MAX_FTP_DATA_SIZE};
+pub use settings::FtpSettings;
+pub use transfer::{FtpStep, FtpTransfer};
+pub use upload::FileUpload;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::dialects::Common;
+    use crate::error::FtpError;
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const GCS_ID: MavLinkId = MavLinkId {
+        system: 255,
+        component: 190,
+    };
+    const VEHICLE_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    /// Minimal in-memory FTP server.
+    struct Server {
+        endpoint: Endpoint<V2>,
+        files: HashMap<String, Vec<u8>>,
+        sessions: HashMap<u8, String>,
+        entries_per_response: usize,
+        requests: usize,
+    }
+
+    impl Server {
+        fn new() -> Self {
+            Self {
+                endpoint: Endpoint::v2(VEHICLE_ID),
+                files: HashMap::new(),
+                sessions: HashMap::new(),
+                entries_per_response: 2,
+                requests: 0,
+            }
+        }
+
+        fn with_file(mut self, path: &str, data: Vec<u8>) -> Self {
+            self.files.insert(path.to_string(), data);
+            self
+        }
+
+        fn respond(&mut self, request: &FtpPayload) -> FtpPayload {
+            self.requests += 1;
+            let path = String::from_utf8_lossy(request.data()).to_string();
+
+            match request.opcode() {
+                FtpOpcode::ListDirectory => {
+                    let mut names: Vec<_> = self
+                        .files
+                        .iter()
+                        .filter_map(|(name, data)| {
+                            name.strip_prefix(&format!("{path}/"))
+                                .map(|name| format!("F{name}\t{}", data.len()))
+                        })
+                        .collect();
+                    names.sort();
+                    names.push("S".to_string());
+                    names.push("Dsubdir".to_string());
+
+                    let entries: Vec<_> = names
+                        .iter()
+                        .skip(request.offset() as usize)
+                        .take(self.entries_per_response)
+                        .collect();
+                    if entries.is_empty() {
+                        return FtpPayload::nak(request, FtpNakCode::EOF, 0);
+                    }
+                    let mut data = Vec::new();
+                    for entry in entries {
+                        data.extend_from_slice(entry.as_bytes());
+                        data.push(0);
+                    }
+                    FtpPayload::ack(request).with_data(data)
+                }
+                FtpOpcode::OpenFileRO | FtpOpcode::CreateFile => {
+                    if request.opcode() == FtpOpcode::CreateFile {
+                        self.files.insert(path.clone(), Vec::new());
+                    }
+                    let size = match self.files.get(&path) {
+                        Some(data) => data.len() as u32,
+                        None => return FtpPayload::nak(request, FtpNakCode::FileNotFound, 0),
+                    };
+                    let session = self.sessions.len() as u8;
+                    self.sessions.insert(session, path);
+                    FtpPayload::ack(request)
+                        .with_session(session)
+                        .with_data(size.to_le_bytes().to_vec())
+                }
+                FtpOpcode::ReadFile => {
+                    let data = &self.files[&self.sessions[&request.session()]];
+                    let offset = request.offset() as usize;
+                    if offset >= data.len() {
+                        return FtpPayload::nak(request, FtpNakCode::EOF, 0);
+                    }
+                    let end = (offset + request.size()).min(data.len());
+                    FtpPayload::ack(request).with_data(data[offset..end].to_vec())
+                }
+                FtpOpcode::WriteFile => {
+                    let path = self.sessions[&request.session()].clone();
+                    let data = self.files.get_mut(&path).unwrap();
+                    let offset = request.offset() as usize;
+                    data.resize(offset, 0);
+                    data.extend_from_slice(request.data());
+                    FtpPayload::ack(request)
+                }
+                FtpOpcode::TerminateSession => {
+                    self.sessions.remove(&request.session());
+                    FtpPayload::ack(request)
+                }
+                FtpOpcode::CalcFileCRC32 => match self.files.get(&path) {
+                    Some(data) => {
+                        FtpPayload::ack(request).with_data(ftp_crc32(data).to_le_bytes().to_vec())
+                    }
+                    None => FtpPayload::nak(request, FtpNakCode::FileNotFound, 0),
+                },
+                _ => FtpPayload::nak(request, FtpNakCode::UnknownCommand, 0),
+            }
+        }
+
+        fn receive(&mut self, frame: &Frame<V2>) -> Frame<V2> {
+            let request = match frame.decode::<Common>().unwrap() {
+                Common::FileTransferProtocol(msg) => FtpPayload::parse(&msg.payload).unwrap(),
+                _ => panic!("invalid message"),
+            };
+            let response = self.respond(&request);
+            self.endpoint
+                .next_frame(&response.to_message(0, GCS_ID))
+                .unwrap()
+        }
+    }
+
+    fn run<T: FtpTransfer>(server: &mut Server, mut transfer: T) -> Result<T::Output> {
+        let client = Endpoint::v2(GCS_ID);
+        let mut step = transfer.start(Instant::now());
+
+        loop {
+            let message = match step {
+                FtpStep::Wait => panic!("server should always respond"),
+                FtpStep::Send(message) => message,
+                FtpStep::Finished(message, result) => {
+                    if let Some(message) = message {
+                        server.receive(&client.next_frame(&message).unwrap());
+                    }
+                    return result;
+                }
+            };
+            let response = server.receive(&client.next_frame(&message).unwrap());
+            step = transfer.handle(&response, Instant::now());
+        }
+    }
+
+    #[test]
+    fn payload_encoding() {
+        let request = FtpPayload::request(FtpOpcode::ReadFile)
+            .with_seq_number(513)
+            .with_session(3)
+            .with_offset(0x01020304)
+            .with_size(MAX_FTP_DATA_SIZE);
+        let bytes = request.to_bytes();
+
+        assert_eq!(&bytes[0..6], &[1, 2, 3, 5, 239, 0]);
+        assert_eq!(&bytes[8..12], &[4, 3, 2, 1]);
+        assert_eq!(FtpPayload::parse(&bytes).unwrap().offset(), 0x01020304);
+
+        let nak = FtpPayload::nak(&request, FtpNakCode::FailErrno, 13);
+        let nak = FtpPayload::parse(&nak.to_bytes()).unwrap();
+        assert_eq!(nak.seq_number(), 514);
+        assert_eq!(nak.req_opcode(), FtpOpcode::ReadFile);
+        assert_eq!(nak.nak_code(), Some((FtpNakCode::FailErrno, Some(13))));
+
+        assert_eq!(ftp_crc32(b"123456789"), 0x2DFD2D88);
+    }
+
+    #[test]
+    fn list_directory() {
+        let mut server = Server::new()
+            .with_file("/logs/1.bin", vec![0; 10])
+            .with_file("/logs/2.bin", vec![0; 20])
+            .with_file("/logs/3.bin", vec![0; 30])
+            .with_file("/other.txt", vec![0; 40]);
+
+        let entries = run(
+            &mut server,
+            DirectoryListing::new(FtpSettings::new(VEHICLE_ID), "/logs"),
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[1],
+            FtpEntry::File {
+                name: "2.bin".to_string(),
+                size: 20
+            }
+        );
+        assert!(entries[3].is_dir());
+        // Three pages of two entries (including a skipped one) and EOF
+        assert_eq!(server.requests, 4);
+    }
+
+    #[test]
+    fn read_file_in_chunks() {
+        let contents: Vec<u8> = (0..600).map(|i| i as u8).collect();
+        let mut server = Server::new().with_file("/data.bin", contents.clone());
+
+        let settings = FtpSettings::new(VEHICLE_ID).with_crc_check(true);
+        let data = run(&mut server, FileDownload::new(settings, "/data.bin")).unwrap();
+
+        assert_eq!(data, contents);
+        assert!(server.sessions.is_empty());
+        // Open, three reads, terminate, and CRC
+        assert_eq!(server.requests, 6);
+    }
+
+    #[test]
+    fn write_file_in_chunks() {
+        let contents: Vec<u8> = (0..500).map(|i| (i * 7) as u8).collect();
+        let mut server = Server::new();
+
+        let settings = FtpSettings::new(VEHICLE_ID).with_crc_check(true);
+        run(
+            &mut server,
+            FileUpload::new(settings, "/data.bin", contents.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(server.files["/data.bin"], contents);
+        assert!(server.sessions.is_empty());
+    }
+
+    #[test]
+    fn rejected_requests() {
+        let mut server = Server::new();
+
+        let result = run(
+            &mut server,
+            FileDownload::new(FtpSettings::new(VEHICLE_ID), "/missing.bin"),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Ftp(FtpError::Rejected {
+                code: FtpNakCode::FileNotFound,
+                errno: None
+            }))
+        ));
+
+        let result = run(
+            &mut server,
+            FileDownload::new(FtpSettings::new(VEHICLE_ID), "/".repeat(240)),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Ftp(FtpError::PathTooLong(240)))
+        ));
+    }
+
+    #[test]
+    fn ignores_stale_responses_and_other_peers() {
+        let mut server = Server::new().with_file("/data.bin", vec![1, 2, 3]);
+        let client = Endpoint::v2(GCS_ID);
+        let mut download = FileDownload::new(FtpSettings::new(VEHICLE_ID), "/data.bin");
+
+        let open = match download.start(Instant::now()) {
+            FtpStep::Send(message) => message,
+            _ => panic!("open request expected"),
+        };
+        let response = server.receive(&client.next_frame(&open).unwrap());
+
+        let message = match response.decode::<Common>().unwrap() {
+            Common::FileTransferProtocol(msg) => msg,
+            _ => panic!("invalid message"),
+        };
+        let other = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&message)
+            .unwrap();
+        assert!(matches!(
+            download.handle(&other, Instant::now()),
+            FtpStep::Wait
+        ));
+
+        assert!(matches!(
+            download.handle(&response, Instant::now()),
+            FtpStep::Send(_)
+        ));
+        // Duplicate response to a retried request
+        assert!(matches!(
+            download.handle(&response, Instant::now()),
+            FtpStep::Wait
+        ));
+    }
+
+    #[test]
+    fn retries_and_times_out() {
+        let timeout = Duration::from_millis(100);
+        let settings = FtpSettings::new(VEHICLE_ID)
+            .with_timeout(timeout)
+            .with_retries(2);
+        let mut listing = DirectoryListing::new(settings, "/");
+
+        let now = Instant::now();
+        let first = match listing.start(now) {
+            FtpStep::Send(message) => message,
+            _ => panic!("list request expected"),
+        };
+        assert_eq!(listing.deadline(), now + timeout);
+        assert!(matches!(listing.check(now), FtpStep::Wait));
+
+        let mut now = listing.deadline();
+        for _ in 0..2 {
+            match listing.check(now) {
+                FtpStep::Send(message) => assert_eq!(message.payload, first.payload),
+                _ => panic!("retry expected"),
+            }
+            now = listing.deadline();
+        }
+
+        assert!(matches!(
+            listing.check(now),
+            FtpStep::Finished(None, Err(Error::Ftp(FtpError::Timeout)))
+        ));
+    }
+}
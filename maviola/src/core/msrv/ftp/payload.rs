@@ -0,0 +1,362 @@
+use crate::dialects::common::messages::FileTransferProtocol;
+
+use crate::prelude::*;
+
+/// Size of `FILE_TRANSFER_PROTOCOL` payload.
+pub(super) const PAYLOAD_SIZE: usize = 251;
+/// Size of FTP header within `FILE_TRANSFER_PROTOCOL` payload.
+const HEADER_SIZE: usize = 12;
+/// Maximum size of data, that fits into a single FTP message.
+pub const MAX_FTP_DATA_SIZE: usize = PAYLOAD_SIZE - HEADER_SIZE;
+
+/// MAVLink FTP operation code.
+///
+/// See [MAVLink FTP](https://mavlink.io/en/services/ftp.html#opcodes) specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FtpOpcode {
+    /// Ignored, always ACKed.
+    None = 0,
+    /// Terminates open read session.
+    TerminateSession = 1,
+    /// Terminates all open read sessions.
+    ResetSessions = 2,
+    /// Lists directory entries starting from the entry with index set by offset.
+    ListDirectory = 3,
+    /// Opens file for reading, returns session and file size.
+    OpenFileRO = 4,
+    /// Reads data from file at offset.
+    ReadFile = 5,
+    /// Creates file for writing, returns session.
+    CreateFile = 6,
+    /// Writes data to file at offset.
+    WriteFile = 7,
+    /// Removes file.
+    RemoveFile = 8,
+    /// Creates directory.
+    CreateDirectory = 9,
+    /// Removes directory, which should be empty.
+    RemoveDirectory = 10,
+    /// Opens file for writing, returns session.
+    OpenFileWO = 11,
+    /// Truncates file to offset.
+    TruncateFile = 12,
+    /// Renames file.
+    Rename = 13,
+    /// Calculates `CRC32` of a file.
+    CalcFileCRC32 = 14,
+    /// Reads file in bursts.
+    BurstReadFile = 15,
+    /// Positive response.
+    Ack = 128,
+    /// Negative response.
+    Nak = 129,
+}
+
+/// Error code of a negative FTP response.
+///
+/// See [MAVLink FTP](https://mavlink.io/en/services/ftp.html#error_codes) specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FtpNakCode {
+    /// No error.
+    None = 0,
+    /// Unknown failure.
+    Fail = 1,
+    /// Command failed, error number is sent as well.
+    FailErrno = 2,
+    /// Payload size is invalid.
+    InvalidDataSize = 3,
+    /// Session is not currently open.
+    InvalidSession = 4,
+    /// All available sessions are already in use.
+    NoSessionsAvailable = 5,
+    /// Offset is past the end of file or directory.
+    EOF = 6,
+    /// Unknown command opcode.
+    UnknownCommand = 7,
+    /// File or directory already exists.
+    FileExists = 8,
+    /// File or directory is write protected.
+    FileProtected = 9,
+    /// File or directory not found.
+    FileNotFound = 10,
+}
+
+/// Payload of a `FILE_TRANSFER_PROTOCOL` message.
+///
+/// MAVLink FTP messages are packed into the `payload` field of `FILE_TRANSFER_PROTOCOL`. Payload
+/// starts with a 12-byte header followed by up to [`MAX_FTP_DATA_SIZE`] bytes of data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FtpPayload {
+    seq_number: u16,
+    session: u8,
+    opcode: FtpOpcode,
+    req_opcode: FtpOpcode,
+    size: u8,
+    burst_complete: bool,
+    offset: u32,
+    data: Vec<u8>,
+}
+
+impl FtpOpcode {
+    /// Converts a raw value into an opcode.
+    ///
+    /// Returns [`None`] for unknown opcodes.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => Self::None,
+            1 => Self::TerminateSession,
+            2 => Self::ResetSessions,
+            3 => Self::ListDirectory,
+            4 => Self::OpenFileRO,
+            5 => Self::ReadFile,
+            6 => Self::CreateFile,
+            7 => Self::WriteFile,
+            8 => Self::RemoveFile,
+            9 => Self::CreateDirectory,
+            10 => Self::RemoveDirectory,
+            11 => Self::OpenFileWO,
+            12 => Self::TruncateFile,
+            13 => Self::Rename,
+            14 => Self::CalcFileCRC32,
+            15 => Self::BurstReadFile,
+            128 => Self::Ack,
+            129 => Self::Nak,
+            _ => return None,
+        })
+    }
+}
+
+impl FtpNakCode {
+    /// Converts a raw value into an error code.
+    ///
+    /// Unknown codes are treated as [`FtpNakCode::Fail`].
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            2 => Self::FailErrno,
+            3 => Self::InvalidDataSize,
+            4 => Self::InvalidSession,
+            5 => Self::NoSessionsAvailable,
+            6 => Self::EOF,
+            7 => Self::UnknownCommand,
+            8 => Self::FileExists,
+            9 => Self::FileProtected,
+            10 => Self::FileNotFound,
+            _ => Self::Fail,
+        }
+    }
+}
+
+impl FtpPayload {
+    /// Creates a request with specified `opcode`.
+    pub fn request(opcode: FtpOpcode) -> Self {
+        Self {
+            seq_number: 0,
+            session: 0,
+            opcode,
+            req_opcode: FtpOpcode::None,
+            size: 0,
+            burst_complete: false,
+            offset: 0,
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates a positive response to a `request`.
+    pub fn ack(request: &FtpPayload) -> Self {
+        Self::response(request, FtpOpcode::Ack)
+    }
+
+    /// Creates a negative response to a `request` with an error `code`.
+    ///
+    /// The `errno` is sent only with [`FtpNakCode::FailErrno`].
+    pub fn nak(request: &FtpPayload, code: FtpNakCode, errno: u8) -> Self {
+        let mut data = vec![code as u8];
+        if code == FtpNakCode::FailErrno {
+            data.push(errno);
+        }
+        Self::response(request, FtpOpcode::Nak).with_data(data)
+    }
+
+    fn response(request: &FtpPayload, opcode: FtpOpcode) -> Self {
+        Self {
+            seq_number: request.seq_number.wrapping_add(1),
+            session: request.session,
+            opcode,
+            req_opcode: request.opcode,
+            size: 0,
+            burst_complete: false,
+            offset: request.offset,
+            data: Vec::new(),
+        }
+    }
+
+    /// Sets sequence number.
+    pub fn with_seq_number(mut self, seq_number: u16) -> Self {
+        self.seq_number = seq_number;
+        self
+    }
+
+    /// Sets session `ID`.
+    pub fn with_session(mut self, session: u8) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Sets offset.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets data and updates size accordingly.
+    ///
+    /// Data longer than [`MAX_FTP_DATA_SIZE`] is truncated.
+    pub fn with_data(mut self, mut data: Vec<u8>) -> Self {
+        data.truncate(MAX_FTP_DATA_SIZE);
+        self.size = data.len() as u8;
+        self.data = data;
+        self
+    }
+
+    /// Sets size without data.
+    ///
+    /// Requests like [`FtpOpcode::ReadFile`] carry no data and use size to define the number of
+    /// requested bytes. Size larger than [`MAX_FTP_DATA_SIZE`] is clamped.
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size.min(MAX_FTP_DATA_SIZE) as u8;
+        self
+    }
+
+    /// Sequence number of the message.
+    pub fn seq_number(&self) -> u16 {
+        self.seq_number
+    }
+
+    /// Session `ID`.
+    pub fn session(&self) -> u8 {
+        self.session
+    }
+
+    /// Operation code.
+    pub fn opcode(&self) -> FtpOpcode {
+        self.opcode
+    }
+
+    /// Operation code of a request, this message responds to.
+    pub fn req_opcode(&self) -> FtpOpcode {
+        self.req_opcode
+    }
+
+    /// Size of data or, for requests without data, the number of requested bytes.
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Returns `true`, if this is the last message of a burst.
+    pub fn burst_complete(&self) -> bool {
+        self.burst_complete
+    }
+
+    /// Offset within a file or a directory listing.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Message data.
+    pub fn data(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Error code and error number of a negative response.
+    ///
+    /// Returns [`None`], if message is not a [`FtpOpcode::Nak`].
+    pub fn nak_code(&self) -> Option<(FtpNakCode, Option<u8>)> {
+        if self.opcode != FtpOpcode::Nak {
+            return None;
+        }
+        let code = FtpNakCode::from_u8(self.data.first().copied().unwrap_or(1));
+        let errno = match code {
+            FtpNakCode::FailErrno => self.data.get(1).copied(),
+            _ => None,
+        };
+        Some((code, errno))
+    }
+
+    /// Parses FTP payload from the `payload` of `FILE_TRANSFER_PROTOCOL` message.
+    ///
+    /// Returns [`None`], if payload contains unknown opcode or invalid data size.
+    pub fn parse(payload: &[u8; PAYLOAD_SIZE]) -> Option<Self> {
+        let size = payload[4] as usize;
+        if size > MAX_FTP_DATA_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            seq_number: u16::from_le_bytes([payload[0], payload[1]]),
+            session: payload[2],
+            opcode: FtpOpcode::from_u8(payload[3])?,
+            req_opcode: FtpOpcode::from_u8(payload[5])?,
+            size: size as u8,
+            burst_complete: payload[6] != 0,
+            offset: u32::from_le_bytes([payload[8], payload[9], payload[10], payload[11]]),
+            data: payload[HEADER_SIZE..HEADER_SIZE + size].to_vec(),
+        })
+    }
+
+    /// Encodes FTP payload into the `payload` of `FILE_TRANSFER_PROTOCOL` message.
+    pub fn to_bytes(&self) -> [u8; PAYLOAD_SIZE] {
+        let mut payload = [0u8; PAYLOAD_SIZE];
+
+        payload[0..2].copy_from_slice(&self.seq_number.to_le_bytes());
+        payload[2] = self.session;
+        payload[3] = self.opcode as u8;
+        payload[4] = self.size;
+        payload[5] = self.req_opcode as u8;
+        payload[6] = self.burst_complete as u8;
+        payload[8..12].copy_from_slice(&self.offset.to_le_bytes());
+        payload[HEADER_SIZE..HEADER_SIZE + self.data.len()].copy_from_slice(&self.data);
+
+        payload
+    }
+
+    /// Reads little-endian `u32` from the beginning of data.
+    ///
+    /// Used to extract file size and `CRC32` from responses.
+    pub(super) fn data_u32(&self) -> Option<u32> {
+        self.data
+            .get(0..4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Creates a `FILE_TRANSFER_PROTOCOL` message addressed to a `target`.
+    pub fn to_message(&self, target_network: u8, target: MavLinkId) -> FileTransferProtocol {
+        FileTransferProtocol {
+            target_network,
+            target_system: target.system,
+            target_component: target.component,
+            payload: self.to_bytes(),
+        }
+    }
+}
+
+/// Calculates `CRC32` of file contents the same way as `CalcFileCRC32` command does.
+///
+/// Implements the reflected `0xEDB88320` polynomial with zero initial value and without final
+/// inversion, as used by PX4 and ArduPilot.
+pub fn ftp_crc32(data: &[u8]) -> u32 {
+    let mut crc = 0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
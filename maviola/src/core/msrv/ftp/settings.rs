@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_FTP_RETRIES, DEFAULT_FTP_TIMEOUT};
+
+use crate::prelude::*;
+
+/// Settings of a file transfer.
+///
+/// Defines an FTP server, how long to wait for its responses, and whether transferred files should
+/// be verified with `CalcFileCRC32` command.
+///
+/// If component `ID` of a peer is `0`, then responses are accepted from any component of the peer
+/// system.
+#[derive(Clone, Copy, Debug)]
+pub struct FtpSettings {
+    peer: MavLinkId,
+    target_network: u8,
+    timeout: Duration,
+    retries: usize,
+    verify_crc: bool,
+}
+
+impl FtpSettings {
+    /// Creates settings for a file transfer with a `peer`.
+    ///
+    /// Uses [`DEFAULT_FTP_TIMEOUT`] and [`DEFAULT_FTP_RETRIES`]. CRC verification is disabled.
+    pub fn new(peer: MavLinkId) -> Self {
+        Self {
+            peer,
+            target_network: 0,
+            timeout: DEFAULT_FTP_TIMEOUT,
+            retries: DEFAULT_FTP_RETRIES,
+            verify_crc: false,
+        }
+    }
+
+    /// Sets `target_network` field of outgoing messages.
+    ///
+    /// The default value `0` addresses the local network.
+    pub fn with_target_network(mut self, target_network: u8) -> Self {
+        self.target_network = target_network;
+        self
+    }
+
+    /// Sets time to wait for a peer response before the last request is resent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times the last request is resent before transfer fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Enables or disables verification of transferred files with `CalcFileCRC32` command.
+    ///
+    /// Not all FTP servers implement this command, so verification is disabled by default.
+    pub fn with_crc_check(mut self, verify_crc: bool) -> Self {
+        self.verify_crc = verify_crc;
+        self
+    }
+
+    /// FTP server.
+    pub fn peer(&self) -> MavLinkId {
+        self.peer
+    }
+
+    /// Value of `target_network` field of outgoing messages.
+    pub fn target_network(&self) -> u8 {
+        self.target_network
+    }
+
+    /// Time to wait for a peer response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Number of times the last request is resent before transfer fails.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Returns `true`, if transferred files are verified with `CalcFileCRC32` command.
+    pub fn crc_check(&self) -> bool {
+        self.verify_crc
+    }
+
+    pub(super) fn is_peer<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        frame.system_id() == self.peer.system
+            && (self.peer.component == 0 || frame.component_id() == self.peer.component)
+    }
+}
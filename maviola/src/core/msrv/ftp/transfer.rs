@@ -0,0 +1,159 @@
+use std::time::Instant;
+
+use crate::dialects::common::messages::FileTransferProtocol;
+use crate::dialects::Common;
+use crate::error::FtpError;
+
+use crate::core::msrv::ftp::{FtpNakCode, FtpOpcode, FtpPayload, FtpSettings, MAX_FTP_DATA_SIZE};
+use crate::prelude::*;
+
+/// Action requested by a [`FtpTransfer`].
+#[derive(Debug)]
+pub enum FtpStep<T> {
+    /// Nothing to send, wait for the next frame or [`FtpTransfer::deadline`].
+    Wait,
+    /// Send a request to the FTP server and continue.
+    Send(FileTransferProtocol),
+    /// Transfer is finished. The message, if present, should be sent to the server before
+    /// returning the result.
+    Finished(Option<FileTransferProtocol>, Result<T>),
+}
+
+/// File transfer state machine.
+///
+/// Transfers do not perform any I/O. The caller should send messages requested by returned
+/// [`FtpStep`]s, pass all incoming frames to [`FtpTransfer::handle`], and call
+/// [`FtpTransfer::check`] once [`FtpTransfer::deadline`] is reached. Methods should not be called
+/// after transfer is finished.
+pub trait FtpTransfer {
+    /// Result of a successful transfer.
+    type Output;
+
+    /// Starts the transfer.
+    fn start(&mut self, now: Instant) -> FtpStep<Self::Output>;
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other peers, unrelated messages, and responses to other requests are ignored.
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> FtpStep<Self::Output>;
+
+    /// Checks whether the server response has timed out.
+    ///
+    /// Resends the last request with the same sequence number, if retries are not exhausted.
+    /// Otherwise, fails the transfer with [`FtpError::Timeout`].
+    fn check(&mut self, now: Instant) -> FtpStep<Self::Output>;
+
+    /// Time, when [`FtpTransfer::check`] should be called, if no frames were received.
+    fn deadline(&self) -> Instant;
+}
+
+/// Tracks the last sent request, sequence numbers and retries.
+#[derive(Debug)]
+pub(super) struct Exchange {
+    pub(super) settings: FtpSettings,
+    seq_number: u16,
+    last: Option<FtpPayload>,
+    attempts: usize,
+    deadline: Instant,
+}
+
+impl Exchange {
+    pub(super) fn new(settings: FtpSettings) -> Self {
+        Self {
+            settings,
+            seq_number: 0,
+            last: None,
+            attempts: 0,
+            deadline: Instant::now() + settings.timeout(),
+        }
+    }
+
+    pub(super) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    pub(super) fn send<T>(&mut self, request: FtpPayload, now: Instant) -> FtpStep<T> {
+        let request = request.with_seq_number(self.seq_number);
+        self.seq_number = self.seq_number.wrapping_add(1);
+
+        let message = self.message(&request);
+        self.last = Some(request);
+        self.attempts = 0;
+        self.deadline = now + self.settings.timeout();
+        FtpStep::Send(message)
+    }
+
+    /// Decodes a response to the last request.
+    ///
+    /// Returns [`None`] for frames from other peers, unrelated messages, and stale responses.
+    pub(super) fn response<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Option<FtpPayload> {
+        if !self.settings.is_peer(frame) {
+            return None;
+        }
+        let request = self.last.as_ref()?;
+
+        let response = match frame.decode::<Common>() {
+            Ok(Common::FileTransferProtocol(msg)) => FtpPayload::parse(&msg.payload)?,
+            _ => return None,
+        };
+
+        let is_response = matches!(response.opcode(), FtpOpcode::Ack | FtpOpcode::Nak)
+            && response.req_opcode() == request.opcode()
+            && response.seq_number() == request.seq_number().wrapping_add(1);
+        if is_response {
+            Some(response)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn check<T>(&mut self, now: Instant) -> FtpStep<T> {
+        if now < self.deadline {
+            return FtpStep::Wait;
+        }
+
+        match &self.last {
+            Some(request) if self.attempts < self.settings.retries() => {
+                self.attempts += 1;
+                self.deadline = now + self.settings.timeout();
+                FtpStep::Send(self.message(request))
+            }
+            _ => FtpStep::Finished(None, Err(FtpError::Timeout.into())),
+        }
+    }
+
+    /// Fails the transfer, terminating open `session` if present.
+    ///
+    /// Termination request is not retried, server will eventually close the session by itself.
+    pub(super) fn fail<T>(&mut self, session: Option<u8>, err: FtpError) -> FtpStep<T> {
+        let terminate = session.map(|session| {
+            let request = FtpPayload::request(FtpOpcode::TerminateSession)
+                .with_session(session)
+                .with_seq_number(self.seq_number);
+            self.seq_number = self.seq_number.wrapping_add(1);
+            self.message(&request)
+        });
+        FtpStep::Finished(terminate, Err(err.into()))
+    }
+
+    /// Fails the transfer with an error carried by a negative `response`.
+    pub(super) fn reject<T>(&mut self, response: &FtpPayload, session: Option<u8>) -> FtpStep<T> {
+        let (code, errno) = response.nak_code().unwrap_or((FtpNakCode::Fail, None));
+        self.fail(session, FtpError::Rejected { code, errno })
+    }
+
+    fn message(&self, payload: &FtpPayload) -> FileTransferProtocol {
+        payload.to_message(self.settings.target_network(), self.settings.peer())
+    }
+}
+
+pub(super) fn encode_path(path: &str) -> std::result::Result<Vec<u8>, FtpError> {
+    if path.len() > MAX_FTP_DATA_SIZE {
+        return Err(FtpError::PathTooLong(path.len()));
+    }
+    Ok(path.as_bytes().to_vec())
+}
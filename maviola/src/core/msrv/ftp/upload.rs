@@ -0,0 +1,141 @@
+use std::time::Instant;
+
+use crate::core::msrv::ftp::transfer::{encode_path, Exchange};
+use crate::error::FtpError;
+
+use crate::core::msrv::ftp::{
+    ftp_crc32, FtpOpcode, FtpPayload, FtpSettings, FtpStep, FtpTransfer, MAX_FTP_DATA_SIZE,
+};
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Create,
+    Write,
+    Terminate,
+    Verify,
+}
+
+/// Writes a remote file.
+///
+/// Creates the file with `CreateFile` command (existing files are truncated by the server), writes
+/// contents chunk by chunk with `WriteFile`, and closes the session with `TerminateSession`. If
+/// [`FtpSettings::with_crc_check`] is enabled, then written file is verified with `CalcFileCRC32`
+/// command.
+///
+/// If transfer fails while the session is open, then session termination is requested.
+#[derive(Debug)]
+pub struct FileUpload {
+    exchange: Exchange,
+    path: String,
+    stage: Stage,
+    session: u8,
+    offset: usize,
+    data: Vec<u8>,
+}
+
+impl FileUpload {
+    /// Creates a transfer, that writes `data` to a file at `path`.
+    pub fn new(settings: FtpSettings, path: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            path: path.into(),
+            stage: Stage::Create,
+            session: 0,
+            offset: 0,
+            data,
+        }
+    }
+
+    fn next(&mut self, now: Instant) -> FtpStep<()> {
+        if self.offset < self.data.len() {
+            let end = (self.offset + MAX_FTP_DATA_SIZE).min(self.data.len());
+            let request = FtpPayload::request(FtpOpcode::WriteFile)
+                .with_session(self.session)
+                .with_offset(self.offset as u32)
+                .with_data(self.data[self.offset..end].to_vec());
+            return self.exchange.send(request, now);
+        }
+
+        self.stage = Stage::Terminate;
+        let request = FtpPayload::request(FtpOpcode::TerminateSession).with_session(self.session);
+        self.exchange.send(request, now)
+    }
+
+    fn finish(&mut self, now: Instant) -> FtpStep<()> {
+        if !self.exchange.settings.crc_check() {
+            return FtpStep::Finished(None, Ok(()));
+        }
+
+        self.stage = Stage::Verify;
+        let request =
+            FtpPayload::request(FtpOpcode::CalcFileCRC32).with_data(self.path.as_bytes().to_vec());
+        self.exchange.send(request, now)
+    }
+}
+
+impl FtpTransfer for FileUpload {
+    type Output = ();
+
+    fn start(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        let path = match encode_path(&self.path) {
+            Ok(path) => path,
+            Err(err) => return self.exchange.fail(None, err),
+        };
+
+        self.stage = Stage::Create;
+        let request = FtpPayload::request(FtpOpcode::CreateFile).with_data(path);
+        self.exchange.send(request, now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> FtpStep<Self::Output> {
+        let response = match self.exchange.response(frame) {
+            Some(response) => response,
+            None => return FtpStep::Wait,
+        };
+        let is_nak = response.opcode() == FtpOpcode::Nak;
+
+        match self.stage {
+            Stage::Create if is_nak => self.exchange.reject(&response, None),
+            Stage::Create => {
+                self.session = response.session();
+                self.stage = Stage::Write;
+                self.next(now)
+            }
+            Stage::Write if is_nak => self.exchange.reject(&response, Some(self.session)),
+            Stage::Write => {
+                self.offset = (self.offset + MAX_FTP_DATA_SIZE).min(self.data.len());
+                self.next(now)
+            }
+            // Session is closed either way
+            Stage::Terminate => self.finish(now),
+            Stage::Verify if is_nak => self.exchange.reject(&response, None),
+            Stage::Verify => {
+                let expected = ftp_crc32(&self.data);
+                match response.data_u32() {
+                    Some(actual) if actual == expected => FtpStep::Finished(None, Ok(())),
+                    Some(actual) => {
+                        let err = FtpError::CrcMismatch { expected, actual };
+                        self.exchange.fail(None, err)
+                    }
+                    None => {
+                        let err = FtpError::InvalidResponse(FtpOpcode::CalcFileCRC32);
+                        self.exchange.fail(None, err)
+                    }
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> FtpStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::Instant;
+
+use crate::core::msrv::gcs::operation::{Exchange, GcsMessage};
+use crate::dialects::common::enums::{MavCmd, MavResult};
+use crate::dialects::common::messages::CommandLong;
+use crate::dialects::Common;
+use crate::error::GcsError;
+
+use crate::core::msrv::gcs::{GcsOperation, GcsSettings, GcsStep};
+use crate::prelude::*;
+
+/// Value of `MAV_CMD_COMPONENT_ARM_DISARM` second parameter, that bypasses pre-arm checks or
+/// disarms a flying vehicle.
+const FORCE_ARM_DISARM: f32 = 21196.0;
+
+/// Vehicle command, that only requires an acknowledgement.
+///
+/// Sends a `COMMAND_LONG` to the vehicle and finishes once the vehicle responds with
+/// `COMMAND_ACK`. Commands that are acknowledged as in progress keep waiting for the final result.
+#[derive(Debug)]
+pub struct VehicleCommand {
+    exchange: Exchange,
+    command: MavCmd,
+}
+
+impl VehicleCommand {
+    /// Creates an operation, that sends an arbitrary `command` with `params` to the vehicle.
+    pub fn new(settings: GcsSettings, command: MavCmd, params: [f32; 7]) -> Self {
+        let vehicle = settings.vehicle();
+        let message = CommandLong {
+            target_system: vehicle.system,
+            target_component: vehicle.component,
+            command,
+            confirmation: 0,
+            param1: params[0],
+            param2: params[1],
+            param3: params[2],
+            param4: params[3],
+            param5: params[4],
+            param6: params[5],
+            param7: params[6],
+        };
+
+        Self {
+            exchange: Exchange::new(settings, GcsMessage::Command(message)),
+            command,
+        }
+    }
+
+    /// Arms or disarms the vehicle with `MAV_CMD_COMPONENT_ARM_DISARM`.
+    ///
+    /// When `force` is set, vehicle is armed without pre-arm checks or disarmed in flight.
+    pub fn arm_disarm(settings: GcsSettings, arm: bool, force: bool) -> Self {
+        let arm = if arm { 1.0 } else { 0.0 };
+        let force = if force { FORCE_ARM_DISARM } else { 0.0 };
+        Self::new(
+            settings,
+            MavCmd::ComponentArmDisarm,
+            [arm, force, 0.0, 0.0, 0.0, 0.0, 0.0],
+        )
+    }
+
+    /// Arms the vehicle.
+    pub fn arm(settings: GcsSettings) -> Self {
+        Self::arm_disarm(settings, true, false)
+    }
+
+    /// Disarms the vehicle.
+    pub fn disarm(settings: GcsSettings) -> Self {
+        Self::arm_disarm(settings, false, false)
+    }
+}
+
+impl GcsOperation for VehicleCommand {
+    type Output = ();
+
+    fn start(&mut self, now: Instant) -> GcsStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GcsStep<Self::Output> {
+        let ack = match self.exchange.decode(frame) {
+            Some(Common::CommandAck(ack)) if ack.command as u16 == self.command as u16 => ack,
+            _ => return GcsStep::Wait,
+        };
+
+        match ack.result {
+            MavResult::Accepted => GcsStep::Finished(Ok(())),
+            MavResult::InProgress => {
+                self.exchange.postpone(now);
+                GcsStep::Wait
+            }
+            result => GcsStep::Finished(Err(GcsError::Rejected(result).into())),
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> GcsStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
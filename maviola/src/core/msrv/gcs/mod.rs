@@ -0,0 +1,237 @@
+//! # Ground control station client
+//!
+//! Combines microservices, that ground control software usually needs to control a vehicle:
+//! commands, [parameters](crate::core::msrv::params), and [missions](crate::core::msrv::mission).
+//!
+//! Like other clients, operations are implemented as I/O-free state machines, that implement
+//! [`GcsOperation`] trait. Requests are resent on timeout:
+//!
+//! * [`VehicleCommand`] sends a `COMMAND_LONG` and waits for a `COMMAND_ACK` for the same command.
+//!   Arming and disarming are provided by [`VehicleCommand::arm_disarm`].
+//! * [`ParamRead`] requests a parameter value with `PARAM_REQUEST_READ`.
+//! * [`ParamWrite`] sets a parameter value with `PARAM_SET`.
+//!
+//! Edge nodes provide `gcs_client` method, that returns a client bound to the node and a vehicle
+//! defined by [`GcsSettings`]. Client drives these state machines over node connection and
+//! uploads or downloads missions with mission protocol.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! # #[tokio::main] async fn main() {
+//! use maviola::core::msrv::gcs::GcsSettings;
+//! use maviola::core::msrv::params::ParamValue;
+//! use maviola::prelude::*;
+//! use maviola::asnc::prelude::*;
+//!
+//! let node = Node::asnc::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().await.unwrap();
+//!
+//! let gcs = node.gcs_client(GcsSettings::new(MavLinkId::new(1, 1)));
+//!
+//! let value = gcs.read_param("MIS_TAKEOFF_ALT").await.unwrap();
+//! println!("takeoff altitude: {value:?}");
+//! gcs.write_param("MIS_TAKEOFF_ALT", ParamValue::Real32(10.0)).await.unwrap();
+//!
+//! let mission = gcs.download_mission().await.unwrap();
+//! gcs.upload_mission(mission).await.unwrap();
+//!
+//! gcs.arm().await.unwrap();
+//! # }
+//! ```
+
+mod command;
+mod operation;
+mod param;
+mod settings;
+
+pub use command::VehicleCommand;
+pub use operation::{GcsMessage, GcsOperation, GcsStep};
+pub use param::{ParamRead, ParamWrite};
+pub use settings::GcsSettings;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::core::msrv::params::{encode_name, ParamEncoding, ParamValue};
+    use crate::dialects::common::enums::{MavCmd, MavParamType, MavResult};
+    use crate::dialects::common::messages::{
+        CommandAck, CommandLong, ParamValue as ParamValueMessage,
+    };
+    use crate::error::{GcsError, ParamError};
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const VEHICLE_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn settings() -> GcsSettings {
+        GcsSettings::new(VEHICLE_ID)
+    }
+
+    fn frame(id: MavLinkId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(id).next_frame(message).unwrap()
+    }
+
+    fn ack(id: MavLinkId, command: &CommandLong, result: MavResult) -> Frame<V2> {
+        frame(
+            id,
+            &CommandAck {
+                command: command.command,
+                result,
+                progress: 0,
+                result_param2: 0,
+                target_system: 255,
+                target_component: 190,
+            },
+        )
+    }
+
+    fn param_value(name: &str, value: ParamValue, encoding: ParamEncoding) -> Frame<V2> {
+        frame(
+            VEHICLE_ID,
+            &ParamValueMessage {
+                param_id: encode_name(name),
+                param_value: value.encode(encoding),
+                param_type: value.param_type(),
+                param_count: 1,
+                param_index: 0,
+            },
+        )
+    }
+
+    fn sent<T>(step: GcsStep<T>) -> GcsMessage {
+        match step {
+            GcsStep::Send(message) => message,
+            _ => panic!("message expected"),
+        }
+    }
+
+    fn sent_command<T>(step: GcsStep<T>) -> CommandLong {
+        match sent(step) {
+            GcsMessage::Command(command) => command,
+            message => panic!("command expected: {message:?}"),
+        }
+    }
+
+    #[test]
+    fn arming_commands() {
+        let now = Instant::now();
+
+        let mut arm = VehicleCommand::arm(settings());
+        let command = sent_command(arm.start(now));
+        assert!(matches!(command.command, MavCmd::ComponentArmDisarm));
+        assert_eq!((command.param1, command.param2), (1.0, 0.0));
+        assert_eq!(command.target_system, VEHICLE_ID.system);
+        assert_eq!(command.target_component, VEHICLE_ID.component);
+
+        // Acknowledgement from another component
+        assert!(matches!(
+            arm.handle(
+                &ack(MavLinkId::new(1, 100), &command, MavResult::Accepted),
+                now
+            ),
+            GcsStep::Wait
+        ));
+        assert!(matches!(
+            arm.handle(&ack(VEHICLE_ID, &command, MavResult::InProgress), now),
+            GcsStep::Wait
+        ));
+        assert!(matches!(
+            arm.handle(&ack(VEHICLE_ID, &command, MavResult::Accepted), now),
+            GcsStep::Finished(Ok(()))
+        ));
+
+        let mut disarm = VehicleCommand::arm_disarm(settings(), false, true);
+        let command = sent_command(disarm.start(now));
+        assert_eq!((command.param1, command.param2), (0.0, 21196.0));
+        assert!(matches!(
+            disarm.handle(&ack(VEHICLE_ID, &command, MavResult::Denied), now),
+            GcsStep::Finished(Err(Error::Gcs(GcsError::Rejected(MavResult::Denied))))
+        ));
+    }
+
+    #[test]
+    fn params_are_read_and_written() {
+        let now = Instant::now();
+        let settings = settings().with_encoding(ParamEncoding::CCast);
+
+        let mut read = ParamRead::new(settings, "SYSID_THISMAV");
+        match sent(read.start(now)) {
+            GcsMessage::ParamRequestRead(request) => {
+                assert_eq!(request.param_id, encode_name("SYSID_THISMAV"));
+                assert_eq!(request.param_index, -1);
+            }
+            message => panic!("parameter request expected: {message:?}"),
+        }
+
+        // Value of another parameter
+        assert!(matches!(
+            read.handle(
+                &param_value("SYSID_MYGCS", ParamValue::Int16(255), ParamEncoding::CCast),
+                now
+            ),
+            GcsStep::Wait
+        ));
+        assert!(matches!(
+            read.handle(
+                &param_value("SYSID_THISMAV", ParamValue::Int16(1), ParamEncoding::CCast),
+                now
+            ),
+            GcsStep::Finished(Ok(ParamValue::Int16(1)))
+        ));
+
+        let mut write = ParamWrite::new(settings, "WPNAV_SPEED", ParamValue::Real32(500.0));
+        match sent(write.start(now)) {
+            GcsMessage::ParamSet(request) => {
+                assert_eq!(request.param_value, 500.0);
+                assert!(matches!(request.param_type, MavParamType::Real32));
+            }
+            message => panic!("parameter set expected: {message:?}"),
+        }
+        // Vehicle reports the adjusted value
+        assert!(matches!(
+            write.handle(
+                &param_value("WPNAV_SPEED", ParamValue::Real32(400.0), ParamEncoding::CCast),
+                now
+            ),
+            GcsStep::Finished(Ok(ParamValue::Real32(value))) if value == 400.0
+        ));
+
+        let mut invalid = ParamRead::new(settings, "A_VERY_LONG_PARAMETER_NAME");
+        assert!(matches!(
+            invalid.start(now),
+            GcsStep::Finished(Err(Error::Param(ParamError::InvalidName(_))))
+        ));
+    }
+
+    #[test]
+    fn retries_and_times_out() {
+        let timeout = Duration::from_millis(100);
+        let settings = settings().with_timeout(timeout).with_retries(2);
+        let mut arm = VehicleCommand::arm(settings);
+
+        let now = Instant::now();
+        let first = sent_command(arm.start(now));
+        assert_eq!(first.confirmation, 0);
+        assert!(matches!(arm.check(now), GcsStep::Wait));
+
+        let mut now = arm.deadline();
+        for confirmation in 1..=2 {
+            assert_eq!(sent_command(arm.check(now)).confirmation, confirmation);
+            now = arm.deadline();
+        }
+        assert!(matches!(
+            arm.check(now),
+            GcsStep::Finished(Err(Error::Gcs(GcsError::Timeout)))
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+use std::time::Instant;
+
+use crate::core::msrv::gcs::GcsSettings;
+use crate::dialects::common::messages::{CommandLong, ParamRequestRead, ParamSet};
+use crate::dialects::Common;
+use crate::error::GcsError;
+
+use crate::prelude::*;
+
+/// Ground control station message, that should be sent to a vehicle.
+#[derive(Clone, Debug)]
+pub enum GcsMessage {
+    /// Command, that should be acknowledged.
+    Command(CommandLong),
+    /// Requests a parameter value.
+    ParamRequestRead(ParamRequestRead),
+    /// Sets a parameter value.
+    ParamSet(ParamSet),
+}
+
+impl GcsMessage {
+    /// Sends message using an edge node or a frame sender.
+    pub fn send<V: Versioned>(&self, sender: &impl SendMessage<V>) -> Result<()> {
+        match self {
+            GcsMessage::Command(message) => sender.send(message),
+            GcsMessage::ParamRequestRead(message) => sender.send(message),
+            GcsMessage::ParamSet(message) => sender.send(message),
+        }
+    }
+}
+
+/// Action requested by a [`GcsOperation`].
+#[derive(Debug)]
+pub enum GcsStep<T> {
+    /// Nothing to send, wait for the next frame or [`GcsOperation::deadline`].
+    Wait,
+    /// Send a message to the vehicle and continue.
+    Send(GcsMessage),
+    /// Operation is finished.
+    Finished(Result<T>),
+}
+
+/// Ground control station operation state machine.
+///
+/// Operations do not perform any I/O. The caller should send messages requested by returned
+/// [`GcsStep`]s, pass all incoming frames to [`GcsOperation::handle`], and call
+/// [`GcsOperation::check`] once [`GcsOperation::deadline`] is reached. Methods should not be
+/// called after operation is finished.
+pub trait GcsOperation {
+    /// Result of a successful operation.
+    type Output;
+
+    /// Starts the operation.
+    fn start(&mut self, now: Instant) -> GcsStep<Self::Output>;
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other components and unrelated messages are ignored.
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GcsStep<Self::Output>;
+
+    /// Checks whether the vehicle response has timed out.
+    ///
+    /// Resends the request, if retries are not exhausted. Otherwise, fails the operation with
+    /// [`GcsError::Timeout`].
+    fn check(&mut self, now: Instant) -> GcsStep<Self::Output>;
+
+    /// Time, when [`GcsOperation::check`] should be called, if no frames were received.
+    fn deadline(&self) -> Instant;
+}
+
+/// Tracks a request sent to a vehicle and its retries.
+#[derive(Debug)]
+pub(super) struct Exchange {
+    pub(super) settings: GcsSettings,
+    message: GcsMessage,
+    attempts: usize,
+    deadline: Instant,
+}
+
+impl Exchange {
+    pub(super) fn new(settings: GcsSettings, message: GcsMessage) -> Self {
+        Self {
+            settings,
+            message,
+            attempts: 0,
+            deadline: Instant::now() + settings.timeout(),
+        }
+    }
+
+    pub(super) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Postpones the deadline without resending the request.
+    pub(super) fn postpone(&mut self, now: Instant) {
+        self.deadline = now + self.settings.timeout();
+    }
+
+    pub(super) fn send<T>(&mut self, now: Instant) -> GcsStep<T> {
+        self.attempts = 0;
+        self.deadline = now + self.settings.timeout();
+        GcsStep::Send(self.message.clone())
+    }
+
+    /// Decodes a message sent by the vehicle.
+    pub(super) fn decode<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Option<Common> {
+        let vehicle = self.settings.vehicle();
+        let is_vehicle = frame.system_id() == vehicle.system
+            && (vehicle.component == 0 || frame.component_id() == vehicle.component);
+        if !is_vehicle {
+            return None;
+        }
+        frame.decode::<Common>().ok()
+    }
+
+    /// Resends the request with increased `confirmation` for commands, if retries are not
+    /// exhausted.
+    pub(super) fn check<T>(&mut self, now: Instant) -> GcsStep<T> {
+        if now < self.deadline {
+            return GcsStep::Wait;
+        }
+
+        if self.attempts < self.settings.retries() {
+            self.attempts += 1;
+            self.deadline = now + self.settings.timeout();
+            if let GcsMessage::Command(command) = &mut self.message {
+                command.confirmation = self.attempts.min(u8::MAX as usize) as u8;
+            }
+            return GcsStep::Send(self.message.clone());
+        }
+        GcsStep::Finished(Err(GcsError::Timeout.into()))
+    }
+}
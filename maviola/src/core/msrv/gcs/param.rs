@@ -0,0 +1,125 @@
+use std::time::Instant;
+
+use crate::core::msrv::gcs::operation::{Exchange, GcsMessage};
+use crate::core::msrv::params::{decode_name, encode_name, ParamValue, PARAM_ID_LEN};
+use crate::dialects::common::messages::{ParamRequestRead, ParamSet};
+use crate::dialects::Common;
+use crate::error::{GcsError, ParamError};
+
+use crate::core::msrv::gcs::{GcsOperation, GcsSettings, GcsStep};
+use crate::prelude::*;
+
+/// Reads a parameter of the vehicle by name.
+///
+/// Sends `PARAM_REQUEST_READ` and finishes once the vehicle reports the value of this parameter
+/// with `PARAM_VALUE`.
+#[derive(Debug)]
+pub struct ParamRead {
+    exchange: Exchange,
+    name: String,
+}
+
+/// Sets a parameter of the vehicle.
+///
+/// Sends `PARAM_SET` and finishes once the vehicle reports the value of this parameter with
+/// `PARAM_VALUE`. The reported value is returned, since vehicles may adjust the requested one.
+#[derive(Debug)]
+pub struct ParamWrite {
+    exchange: Exchange,
+    name: String,
+}
+
+impl ParamRead {
+    /// Creates an operation, that reads parameter with specified `name`.
+    pub fn new(settings: GcsSettings, name: impl Into<String>) -> Self {
+        let name = name.into();
+        let vehicle = settings.vehicle();
+        let message = ParamRequestRead {
+            target_system: vehicle.system,
+            target_component: vehicle.component,
+            param_id: encode_name(&name),
+            param_index: -1,
+        };
+
+        Self {
+            exchange: Exchange::new(settings, GcsMessage::ParamRequestRead(message)),
+            name,
+        }
+    }
+}
+
+impl ParamWrite {
+    /// Creates an operation, that sets parameter with specified `name` to `value`.
+    pub fn new(settings: GcsSettings, name: impl Into<String>, value: ParamValue) -> Self {
+        let name = name.into();
+        let vehicle = settings.vehicle();
+        let message = ParamSet {
+            target_system: vehicle.system,
+            target_component: vehicle.component,
+            param_id: encode_name(&name),
+            param_value: value.encode(settings.encoding()),
+            param_type: value.param_type(),
+        };
+
+        Self {
+            exchange: Exchange::new(settings, GcsMessage::ParamSet(message)),
+            name,
+        }
+    }
+}
+
+macro_rules! impl_param_operation {
+    ($ty:ty) => {
+        impl GcsOperation for $ty {
+            type Output = ParamValue;
+
+            fn start(&mut self, now: Instant) -> GcsStep<Self::Output> {
+                if self.name.is_empty() || self.name.len() > PARAM_ID_LEN {
+                    return GcsStep::Finished(Err(
+                        ParamError::InvalidName(self.name.clone()).into()
+                    ));
+                }
+                self.exchange.send(now)
+            }
+
+            fn handle<V: MaybeVersioned>(
+                &mut self,
+                frame: &Frame<V>,
+                _: Instant,
+            ) -> GcsStep<Self::Output> {
+                handle_param_value(&self.exchange, &self.name, frame)
+            }
+
+            fn check(&mut self, now: Instant) -> GcsStep<Self::Output> {
+                self.exchange.check(now)
+            }
+
+            fn deadline(&self) -> Instant {
+                self.exchange.deadline()
+            }
+        }
+    };
+}
+
+impl_param_operation!(ParamRead);
+impl_param_operation!(ParamWrite);
+
+/// Decodes `PARAM_VALUE` for a parameter with specified `name`.
+fn handle_param_value<V: MaybeVersioned>(
+    exchange: &Exchange,
+    name: &str,
+    frame: &Frame<V>,
+) -> GcsStep<ParamValue> {
+    let message = match exchange.decode(frame) {
+        Some(Common::ParamValue(message)) if decode_name(&message.param_id) == name => message,
+        _ => return GcsStep::Wait,
+    };
+
+    let encoding = exchange.settings.encoding();
+    GcsStep::Finished(
+        match ParamValue::decode(message.param_value, message.param_type, encoding) {
+            Some(value) => Ok(value),
+            None => Err(GcsError::UnsupportedParamType(name.to_string()).into()),
+        },
+    )
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_GCS_RETRIES, DEFAULT_GCS_TIMEOUT};
+use crate::core::msrv::mission::MissionSettings;
+use crate::core::msrv::params::ParamEncoding;
+
+use crate::prelude::*;
+
+/// Settings of ground control station operations.
+///
+/// Defines a vehicle component, how long to wait for its responses, and how parameter values are
+/// packed into parameter messages.
+#[derive(Clone, Copy, Debug)]
+pub struct GcsSettings {
+    vehicle: MavLinkId,
+    timeout: Duration,
+    retries: usize,
+    encoding: ParamEncoding,
+}
+
+impl GcsSettings {
+    /// Creates settings for operations with a `vehicle` component, usually an autopilot.
+    ///
+    /// Uses [`DEFAULT_GCS_TIMEOUT`], [`DEFAULT_GCS_RETRIES`], and [`ParamEncoding::Bytewise`]
+    /// parameter encoding.
+    pub fn new(vehicle: MavLinkId) -> Self {
+        Self {
+            vehicle,
+            timeout: DEFAULT_GCS_TIMEOUT,
+            retries: DEFAULT_GCS_RETRIES,
+            encoding: ParamEncoding::default(),
+        }
+    }
+
+    /// Sets time to wait for a vehicle response before a request is resent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times a request is resent before operation fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets parameter encoding, that should match the one used by the vehicle.
+    pub fn with_encoding(mut self, encoding: ParamEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Vehicle component.
+    pub fn vehicle(&self) -> MavLinkId {
+        self.vehicle
+    }
+
+    /// Time to wait for a vehicle response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Number of times a request is resent before operation fails.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Parameter encoding.
+    pub fn encoding(&self) -> ParamEncoding {
+        self.encoding
+    }
+
+    /// Settings of mission transfers with the vehicle.
+    ///
+    /// Mission transfers use their own timeouts and retries.
+    pub fn mission(&self) -> MissionSettings {
+        MissionSettings::new(self.vehicle)
+    }
+}
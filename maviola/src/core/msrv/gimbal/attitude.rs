@@ -0,0 +1,184 @@
+use crate::dialects::common::enums::{
+    GimbalDeviceErrorFlags, GimbalDeviceFlags, GimbalManagerFlags,
+};
+use crate::dialects::common::messages::{GimbalDeviceAttitudeStatus, GimbalManagerSetAttitude};
+use crate::dialects::Common;
+
+use crate::core::msrv::gimbal::GimbalSettings;
+use crate::prelude::*;
+
+/// Attitude setpoint sent to the gimbal manager in `GIMBAL_MANAGER_SET_ATTITUDE` message.
+///
+/// Attitude is defined by a quaternion in `[w, x, y, z]` order. Whether yaw is relative to vehicle
+/// heading or to North is defined by [`GimbalManagerFlags`]. Angular velocities are in radians per
+/// second and are unused by default.
+#[derive(Clone, Copy, Debug)]
+pub struct GimbalSetpoint {
+    q: [f32; 4],
+    angular_velocity: [f32; 3],
+    flags: GimbalManagerFlags,
+}
+
+impl GimbalSetpoint {
+    /// Creates setpoint from an attitude quaternion in `[w, x, y, z]` order.
+    ///
+    /// Pass [`f32::NAN`] as all components of the quaternion to control the gimbal with angular
+    /// velocities only.
+    pub fn quaternion(q: [f32; 4]) -> Self {
+        Self {
+            q,
+            angular_velocity: [f32::NAN; 3],
+            flags: GimbalManagerFlags::default(),
+        }
+    }
+
+    /// Creates setpoint from Euler angles in radians.
+    pub fn from_euler(roll: f32, pitch: f32, yaw: f32) -> Self {
+        Self::quaternion(euler_to_quaternion(roll, pitch, yaw))
+    }
+
+    /// Sets angular velocities around `x`, `y` and `z` axes in radians per second.
+    pub fn with_angular_velocity(mut self, x: f32, y: f32, z: f32) -> Self {
+        self.angular_velocity = [x, y, z];
+        self
+    }
+
+    /// Sets gimbal manager flags.
+    pub fn with_flags(mut self, flags: GimbalManagerFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Attitude quaternion in `[w, x, y, z]` order.
+    pub fn q(&self) -> [f32; 4] {
+        self.q
+    }
+
+    /// Angular velocities around `x`, `y` and `z` axes in radians per second.
+    pub fn angular_velocity(&self) -> [f32; 3] {
+        self.angular_velocity
+    }
+
+    /// Gimbal manager flags.
+    pub fn flags(&self) -> GimbalManagerFlags {
+        self.flags
+    }
+
+    /// Creates `GIMBAL_MANAGER_SET_ATTITUDE` message addressed to the gimbal manager.
+    pub fn message(&self, settings: &GimbalSettings) -> GimbalManagerSetAttitude {
+        let manager = settings.manager();
+        GimbalManagerSetAttitude {
+            target_system: manager.system,
+            target_component: manager.component,
+            flags: self.flags,
+            gimbal_device_id: settings.device_id(),
+            q: self.q,
+            angular_velocity_x: self.angular_velocity[0],
+            angular_velocity_y: self.angular_velocity[1],
+            angular_velocity_z: self.angular_velocity[2],
+        }
+    }
+}
+
+/// Gimbal attitude received in `GIMBAL_DEVICE_ATTITUDE_STATUS` message.
+#[derive(Clone, Debug)]
+pub struct GimbalAttitude {
+    message: GimbalDeviceAttitudeStatus,
+}
+
+impl GimbalAttitude {
+    /// Attitude quaternion in `[w, x, y, z]` order.
+    pub fn q(&self) -> [f32; 4] {
+        self.message.q
+    }
+
+    /// Attitude as Euler angles `(roll, pitch, yaw)` in radians.
+    pub fn euler(&self) -> (f32, f32, f32) {
+        quaternion_to_euler(self.message.q)
+    }
+
+    /// Angular velocities around `x`, `y` and `z` axes in radians per second.
+    pub fn angular_velocity(&self) -> [f32; 3] {
+        [
+            self.message.angular_velocity_x,
+            self.message.angular_velocity_y,
+            self.message.angular_velocity_z,
+        ]
+    }
+
+    /// Gimbal device flags.
+    pub fn flags(&self) -> GimbalDeviceFlags {
+        self.message.flags
+    }
+
+    /// Gimbal device failures.
+    pub fn failure_flags(&self) -> GimbalDeviceErrorFlags {
+        self.message.failure_flags
+    }
+
+    /// Gimbal device `ID` reported by the manager, `0` for gimbal devices, that speak MAVLink.
+    pub fn device_id(&self) -> u8 {
+        self.message.gimbal_device_id
+    }
+
+    /// Original `GIMBAL_DEVICE_ATTITUDE_STATUS` message.
+    pub fn message(&self) -> &GimbalDeviceAttitudeStatus {
+        &self.message
+    }
+
+    /// <sup>⛔</sup>
+    /// Decodes gimbal attitude from a `frame`, if it is sent by the gimbal defined by `settings`.
+    pub(crate) fn from_frame<V: MaybeVersioned>(
+        frame: &Frame<V>,
+        settings: &GimbalSettings,
+    ) -> Option<Self> {
+        if !settings.is_gimbal_system(frame) {
+            return None;
+        }
+
+        let message = match frame.decode::<Common>() {
+            Ok(Common::GimbalDeviceAttitudeStatus(message)) => message,
+            _ => return None,
+        };
+
+        // Gimbal devices, that speak MAVLink, report their attitude themselves
+        let device = settings.device();
+        if device != settings.manager() && frame.component_id() != device.component {
+            return None;
+        }
+        if !settings.is_device_id(message.gimbal_device_id) {
+            return None;
+        }
+
+        Some(message.into())
+    }
+}
+
+impl From<GimbalDeviceAttitudeStatus> for GimbalAttitude {
+    fn from(message: GimbalDeviceAttitudeStatus) -> Self {
+        Self { message }
+    }
+}
+
+fn euler_to_quaternion(roll: f32, pitch: f32, yaw: f32) -> [f32; 4] {
+    let (sr, cr) = (roll / 2.0).sin_cos();
+    let (sp, cp) = (pitch / 2.0).sin_cos();
+    let (sy, cy) = (yaw / 2.0).sin_cos();
+
+    [
+        cr * cp * cy + sr * sp * sy,
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+    ]
+}
+
+fn quaternion_to_euler(q: [f32; 4]) -> (f32, f32, f32) {
+    let [w, x, y, z] = q;
+
+    let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+    let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+    let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+
+    (roll, pitch, yaw)
+}
@@ -0,0 +1,129 @@
+use std::time::Instant;
+
+use crate::core::msrv::gimbal::operation::Exchange;
+use crate::dialects::common::enums::{GimbalManagerFlags, MavCmd};
+
+use crate::core::msrv::gimbal::{GimbalOperation, GimbalSettings, GimbalStep};
+use crate::prelude::*;
+
+/// Leave primary or secondary control of `MAV_CMD_DO_GIMBAL_MANAGER_CONFIGURE` unchanged.
+const CONTROL_UNCHANGED: f32 = -1.0;
+/// Release primary or secondary control of `MAV_CMD_DO_GIMBAL_MANAGER_CONFIGURE`.
+const CONTROL_RELEASE: f32 = -3.0;
+
+/// Gimbal manager command, that only requires an acknowledgement.
+///
+/// Sends a `COMMAND_LONG` to the gimbal manager and finishes once the manager responds with
+/// `COMMAND_ACK`. Commands that are acknowledged as in progress keep waiting for the final result.
+#[derive(Debug)]
+pub struct GimbalCommand {
+    exchange: Exchange,
+}
+
+impl GimbalCommand {
+    /// Creates an operation, that sends an arbitrary `command` with `params` to the gimbal
+    /// manager.
+    pub fn new(settings: GimbalSettings, command: MavCmd, params: [f32; 7]) -> Self {
+        Self {
+            exchange: Exchange::new(settings, settings.manager(), command, params),
+        }
+    }
+
+    /// Takes primary control over the gimbal with `MAV_CMD_DO_GIMBAL_MANAGER_CONFIGURE`.
+    ///
+    /// The `controller` is usually the node itself. Secondary control is left unchanged.
+    pub fn take_control(settings: GimbalSettings, controller: MavLinkId) -> Self {
+        Self::new(
+            settings,
+            MavCmd::DoGimbalManagerConfigure,
+            [
+                controller.system as f32,
+                controller.component as f32,
+                CONTROL_UNCHANGED,
+                CONTROL_UNCHANGED,
+                0.0,
+                0.0,
+                settings.device_id() as f32,
+            ],
+        )
+    }
+
+    /// Releases primary control over the gimbal with `MAV_CMD_DO_GIMBAL_MANAGER_CONFIGURE`.
+    ///
+    /// Control is released only if the sender is currently in control.
+    pub fn release_control(settings: GimbalSettings) -> Self {
+        Self::new(
+            settings,
+            MavCmd::DoGimbalManagerConfigure,
+            [
+                CONTROL_RELEASE,
+                CONTROL_RELEASE,
+                CONTROL_UNCHANGED,
+                CONTROL_UNCHANGED,
+                0.0,
+                0.0,
+                settings.device_id() as f32,
+            ],
+        )
+    }
+
+    /// Points the gimbal with `MAV_CMD_DO_GIMBAL_MANAGER_PITCHYAW`.
+    ///
+    /// Angles are in degrees and angular rates are in degrees per second. Pass [`f32::NAN`] to
+    /// leave the corresponding angle or rate unused. Whether yaw is relative to vehicle heading
+    /// or to North is defined by `flags`.
+    pub fn pitch_yaw(
+        settings: GimbalSettings,
+        pitch: f32,
+        yaw: f32,
+        pitch_rate: f32,
+        yaw_rate: f32,
+        flags: GimbalManagerFlags,
+    ) -> Self {
+        Self::new(
+            settings,
+            MavCmd::DoGimbalManagerPitchyaw,
+            [
+                pitch,
+                yaw,
+                pitch_rate,
+                yaw_rate,
+                flags.bits() as f32,
+                0.0,
+                settings.device_id() as f32,
+            ],
+        )
+    }
+}
+
+impl GimbalOperation for GimbalCommand {
+    type Output = ();
+
+    fn start(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GimbalStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return GimbalStep::Wait,
+        };
+
+        match self.exchange.ack(&message, now) {
+            Some(result) => GimbalStep::Finished(result.map_err(Error::from)),
+            None => GimbalStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
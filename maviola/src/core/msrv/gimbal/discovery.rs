@@ -0,0 +1,343 @@
+use std::time::Instant;
+
+use crate::core::msrv::gimbal::operation::Exchange;
+use crate::dialects::common::enums::{GimbalDeviceCapFlags, GimbalManagerCapFlags};
+use crate::dialects::common::messages::{GimbalDeviceInformation, GimbalManagerInformation};
+use crate::dialects::Common;
+
+use crate::core::msrv::gimbal::{GimbalOperation, GimbalSettings, GimbalStep};
+use crate::prelude::*;
+
+/// Gimbal manager description received in `GIMBAL_MANAGER_INFORMATION` message.
+#[derive(Clone, Debug)]
+pub struct GimbalManagerInfo {
+    message: GimbalManagerInformation,
+}
+
+impl GimbalManagerInfo {
+    /// `ID` of the gimbal device controlled by the manager.
+    pub fn device_id(&self) -> u8 {
+        self.message.gimbal_device_id
+    }
+
+    /// Capabilities of the gimbal manager.
+    pub fn capabilities(&self) -> GimbalManagerCapFlags {
+        self.message.cap_flags
+    }
+
+    /// Minimum and maximum roll angles in radians.
+    pub fn roll_limits(&self) -> (f32, f32) {
+        (self.message.roll_min, self.message.roll_max)
+    }
+
+    /// Minimum and maximum pitch angles in radians.
+    pub fn pitch_limits(&self) -> (f32, f32) {
+        (self.message.pitch_min, self.message.pitch_max)
+    }
+
+    /// Minimum and maximum yaw angles in radians.
+    pub fn yaw_limits(&self) -> (f32, f32) {
+        (self.message.yaw_min, self.message.yaw_max)
+    }
+
+    /// Original `GIMBAL_MANAGER_INFORMATION` message.
+    pub fn message(&self) -> &GimbalManagerInformation {
+        &self.message
+    }
+}
+
+impl From<GimbalManagerInformation> for GimbalManagerInfo {
+    fn from(message: GimbalManagerInformation) -> Self {
+        Self { message }
+    }
+}
+
+/// Gimbal device description received in `GIMBAL_DEVICE_INFORMATION` message.
+#[derive(Clone, Debug)]
+pub struct GimbalDeviceInfo {
+    message: GimbalDeviceInformation,
+}
+
+impl GimbalDeviceInfo {
+    /// Name of the gimbal vendor.
+    pub fn vendor_name(&self) -> String {
+        decode_str(&self.message.vendor_name)
+    }
+
+    /// Name of the gimbal model.
+    pub fn model_name(&self) -> String {
+        decode_str(&self.message.model_name)
+    }
+
+    /// Custom name of the gimbal given to it by the user.
+    pub fn custom_name(&self) -> String {
+        decode_str(&self.message.custom_name)
+    }
+
+    /// Firmware version encoded as `(Dev & 0xff) << 24 | (Patch & 0xff) << 16 | (Minor & 0xff) << 8
+    /// | (Major & 0xff)`.
+    pub fn firmware_version(&self) -> u32 {
+        self.message.firmware_version
+    }
+
+    /// Unique hardware `ID` of the gimbal, `0` if unknown.
+    pub fn uid(&self) -> u64 {
+        self.message.uid
+    }
+
+    /// Capabilities of the gimbal device.
+    pub fn capabilities(&self) -> GimbalDeviceCapFlags {
+        self.message.cap_flags
+    }
+
+    /// Gimbal device `ID` reported by the manager, `0` for gimbal devices, that speak MAVLink.
+    pub fn device_id(&self) -> u8 {
+        self.message.gimbal_device_id
+    }
+
+    /// Original `GIMBAL_DEVICE_INFORMATION` message.
+    pub fn message(&self) -> &GimbalDeviceInformation {
+        &self.message
+    }
+}
+
+impl From<GimbalDeviceInformation> for GimbalDeviceInfo {
+    fn from(message: GimbalDeviceInformation) -> Self {
+        Self { message }
+    }
+}
+
+/// Gimbal manager and device descriptions collected by [`GimbalDiscovery`].
+#[derive(Clone, Debug)]
+pub struct GimbalInfo {
+    /// Gimbal manager description.
+    pub manager: GimbalManagerInfo,
+    /// Gimbal device description.
+    pub device: GimbalDeviceInfo,
+}
+
+/// Requests `GIMBAL_MANAGER_INFORMATION` from the gimbal manager with `MAV_CMD_REQUEST_MESSAGE`.
+///
+/// The requested message may arrive before or after the command is acknowledged. Once the command
+/// is accepted, operation waits for the message for another timeout. If gimbal device `ID` is
+/// set, then information about other devices of the same manager is ignored.
+#[derive(Debug)]
+pub struct GimbalManagerInformationRequest {
+    exchange: Exchange,
+}
+
+impl GimbalManagerInformationRequest {
+    /// Creates an operation, that requests gimbal manager information.
+    pub fn new(settings: GimbalSettings) -> Self {
+        Self {
+            exchange: Exchange::request_message(
+                settings,
+                settings.manager(),
+                GimbalManagerInformation::message_id(),
+            ),
+        }
+    }
+}
+
+impl GimbalOperation for GimbalManagerInformationRequest {
+    type Output = GimbalManagerInfo;
+
+    fn start(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GimbalStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return GimbalStep::Wait,
+        };
+        if let Common::GimbalManagerInformation(info) = message {
+            if self.exchange.settings.is_device_id(info.gimbal_device_id) {
+                return GimbalStep::Finished(Ok(info.into()));
+            }
+            return GimbalStep::Wait;
+        }
+        ack_request(&mut self.exchange, &message, now)
+    }
+
+    fn check(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
+
+/// Requests `GIMBAL_DEVICE_INFORMATION` with `MAV_CMD_REQUEST_MESSAGE`.
+///
+/// Gimbal devices, that speak MAVLink, are asked directly. Otherwise, information is requested
+/// from the gimbal manager.
+#[derive(Debug)]
+pub struct GimbalDeviceInformationRequest {
+    exchange: Exchange,
+}
+
+impl GimbalDeviceInformationRequest {
+    /// Creates an operation, that requests gimbal device information.
+    pub fn new(settings: GimbalSettings) -> Self {
+        Self {
+            exchange: Exchange::request_message(
+                settings,
+                settings.device(),
+                GimbalDeviceInformation::message_id(),
+            ),
+        }
+    }
+}
+
+impl GimbalOperation for GimbalDeviceInformationRequest {
+    type Output = GimbalDeviceInfo;
+
+    fn start(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.send(now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GimbalStep<Self::Output> {
+        let message = match self.exchange.decode(frame) {
+            Some(message) => message,
+            None => return GimbalStep::Wait,
+        };
+        if let Common::GimbalDeviceInformation(info) = message {
+            if self.exchange.settings.is_device_id(info.gimbal_device_id) {
+                return GimbalStep::Finished(Ok(info.into()));
+            }
+            return GimbalStep::Wait;
+        }
+        ack_request(&mut self.exchange, &message, now)
+    }
+
+    fn check(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
+
+/// Discovers a gimbal device of a gimbal manager.
+///
+/// Performs gimbal protocol handshake: requests `GIMBAL_MANAGER_INFORMATION` from the manager to
+/// learn the `ID` of the gimbal device it controls, then requests `GIMBAL_DEVICE_INFORMATION` for
+/// this device. Use [`GimbalInfo::manager`] device `ID` in [`GimbalSettings::with_device_id`] to
+/// address the discovered device in subsequent operations.
+#[derive(Debug)]
+pub struct GimbalDiscovery {
+    manager: GimbalManagerInformationRequest,
+    device: Option<(GimbalManagerInfo, GimbalDeviceInformationRequest)>,
+}
+
+impl GimbalDiscovery {
+    /// Creates an operation, that discovers a gimbal device.
+    pub fn new(settings: GimbalSettings) -> Self {
+        Self {
+            manager: GimbalManagerInformationRequest::new(settings),
+            device: None,
+        }
+    }
+}
+
+impl GimbalOperation for GimbalDiscovery {
+    type Output = GimbalInfo;
+
+    fn start(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        self.device = None;
+        match self.manager.start(now) {
+            GimbalStep::Send(command) => GimbalStep::Send(command),
+            _ => GimbalStep::Wait,
+        }
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GimbalStep<Self::Output> {
+        if let Some((manager, device)) = &mut self.device {
+            return match device.handle(frame, now) {
+                GimbalStep::Finished(Ok(device)) => GimbalStep::Finished(Ok(GimbalInfo {
+                    manager: manager.clone(),
+                    device,
+                })),
+                GimbalStep::Finished(Err(err)) => GimbalStep::Finished(Err(err)),
+                GimbalStep::Send(command) => GimbalStep::Send(command),
+                GimbalStep::Wait => GimbalStep::Wait,
+            };
+        }
+
+        match self.manager.handle(frame, now) {
+            GimbalStep::Finished(Ok(manager)) => {
+                let settings = self
+                    .manager
+                    .exchange
+                    .settings
+                    .with_device_id(manager.device_id());
+                let mut device = GimbalDeviceInformationRequest::new(settings);
+                let step = device.start(now);
+                self.device = Some((manager, device));
+                match step {
+                    GimbalStep::Send(command) => GimbalStep::Send(command),
+                    _ => GimbalStep::Wait,
+                }
+            }
+            GimbalStep::Finished(Err(err)) => GimbalStep::Finished(Err(err)),
+            GimbalStep::Send(command) => GimbalStep::Send(command),
+            GimbalStep::Wait => GimbalStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> GimbalStep<Self::Output> {
+        match &mut self.device {
+            Some((_, device)) => match device.check(now) {
+                GimbalStep::Finished(Err(err)) => GimbalStep::Finished(Err(err)),
+                GimbalStep::Send(command) => GimbalStep::Send(command),
+                _ => GimbalStep::Wait,
+            },
+            None => match self.manager.check(now) {
+                GimbalStep::Finished(Err(err)) => GimbalStep::Finished(Err(err)),
+                GimbalStep::Send(command) => GimbalStep::Send(command),
+                _ => GimbalStep::Wait,
+            },
+        }
+    }
+
+    fn deadline(&self) -> Instant {
+        match &self.device {
+            Some((_, device)) => device.deadline(),
+            None => self.manager.deadline(),
+        }
+    }
+}
+
+/// Handles acknowledgement of a message request.
+fn ack_request<T>(exchange: &mut Exchange, message: &Common, now: Instant) -> GimbalStep<T> {
+    match exchange.ack(message, now) {
+        Some(Ok(())) => {
+            let timeout = exchange.settings.timeout();
+            exchange.wait_until(now + timeout);
+            GimbalStep::Wait
+        }
+        Some(Err(err)) => GimbalStep::Finished(Err(err.into())),
+        None => GimbalStep::Wait,
+    }
+}
+
+/// Decodes a string field padded with zeros.
+fn decode_str(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).to_string()
+}
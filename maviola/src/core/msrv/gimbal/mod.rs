@@ -0,0 +1,329 @@
+//! # Gimbal protocol v2
+//!
+//! Implements client side of [gimbal protocol v2](https://mavlink.io/en/services/gimbal_v2.html),
+//! that controls gimbal devices through a gimbal manager.
+//!
+//! Like [camera protocol](crate::core::msrv::camera), gimbal operations are implemented as I/O-free
+//! state machines, that implement [`GimbalOperation`] trait. Each command is matched with a
+//! `COMMAND_ACK` for the same command and resent with increased `confirmation` on timeout:
+//!
+//! * [`GimbalDiscovery`] performs manager/device handshake and returns [`GimbalInfo`].
+//! * [`GimbalManagerInformationRequest`] requests [`GimbalManagerInfo`] with
+//!   `MAV_CMD_REQUEST_MESSAGE`.
+//! * [`GimbalDeviceInformationRequest`] requests [`GimbalDeviceInfo`] with
+//!   `MAV_CMD_REQUEST_MESSAGE`.
+//! * [`GimbalCommand`] takes or releases control over the gimbal and points it with pitch and yaw
+//!   angles.
+//!
+//! Attitude setpoints are streamed to the manager as `GIMBAL_MANAGER_SET_ATTITUDE` messages
+//! created from [`GimbalSetpoint`]. Gimbal attitude is received as [`GimbalAttitude`] decoded from
+//! `GIMBAL_DEVICE_ATTITUDE_STATUS` messages.
+//!
+//! Edge nodes provide `gimbal_client` method, that returns a client bound to the node and a gimbal
+//! defined by [`GimbalSettings`]. Client drives these state machines over node connection.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use maviola::core::msrv::gimbal::{GimbalSetpoint, GimbalSettings};
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let node = Node::sync::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().unwrap();
+//!
+//! let gimbal = node.gimbal_client(GimbalSettings::new(MavLinkId::new(1, 1)));
+//!
+//! let info = gimbal.discover().unwrap();
+//! println!("{} {}", info.device.vendor_name(), info.device.model_name());
+//!
+//! // Address the discovered gimbal device
+//! let gimbal = node.gimbal_client(
+//!     gimbal.settings().with_device_id(info.manager.device_id())
+//! );
+//!
+//! gimbal.take_control().unwrap();
+//! gimbal.set_attitude(&GimbalSetpoint::from_euler(0.0, -0.5, 0.0)).unwrap();
+//!
+//! for attitude in gimbal.attitudes().take(10) {
+//!     println!("{:?}", attitude.euler());
+//! }
+//!
+//! gimbal.release_control().unwrap();
+//! ```
+
+mod attitude;
+mod command;
+mod discovery;
+mod operation;
+mod settings;
+
+pub use attitude::{GimbalAttitude, GimbalSetpoint};
+pub use command::GimbalCommand;
+pub use discovery::{
+    GimbalDeviceInfo, GimbalDeviceInformationRequest, GimbalDiscovery, GimbalInfo,
+    GimbalManagerInfo, GimbalManagerInformationRequest,
+};
+pub use operation::{GimbalOperation, GimbalStep};
+pub use settings::GimbalSettings;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::dialects::common::enums::{GimbalManagerFlags, MavCmd, MavResult};
+    use crate::dialects::common::messages::{
+        CommandAck, CommandLong, GimbalDeviceAttitudeStatus, GimbalDeviceInformation,
+        GimbalManagerInformation,
+    };
+    use crate::error::GimbalError;
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const MANAGER_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+    const DEVICE_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 154,
+    };
+
+    fn settings() -> GimbalSettings {
+        GimbalSettings::new(MANAGER_ID)
+    }
+
+    fn frame(id: MavLinkId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(id).next_frame(message).unwrap()
+    }
+
+    fn ack(id: MavLinkId, command: &CommandLong, result: MavResult) -> Frame<V2> {
+        frame(
+            id,
+            &CommandAck {
+                command: command.command,
+                result,
+                progress: 0,
+                result_param2: 0,
+                target_system: 255,
+                target_component: 190,
+            },
+        )
+    }
+
+    fn sent<T>(step: GimbalStep<T>) -> CommandLong {
+        match step {
+            GimbalStep::Send(command) => command,
+            _ => panic!("command expected"),
+        }
+    }
+
+    fn manager_information(gimbal_device_id: u8) -> GimbalManagerInformation {
+        GimbalManagerInformation {
+            gimbal_device_id,
+            pitch_min: -1.5,
+            pitch_max: 0.5,
+            ..Default::default()
+        }
+    }
+
+    fn device_information(gimbal_device_id: u8) -> GimbalDeviceInformation {
+        let mut info = GimbalDeviceInformation {
+            gimbal_device_id,
+            uid: 42,
+            ..Default::default()
+        };
+        info.vendor_name[..4].copy_from_slice(b"Acme");
+        info.model_name[..3].copy_from_slice(b"G-1");
+        info
+    }
+
+    fn attitude_status(gimbal_device_id: u8, q: [f32; 4]) -> GimbalDeviceAttitudeStatus {
+        GimbalDeviceAttitudeStatus {
+            gimbal_device_id,
+            q,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn discovery_handshake() {
+        let now = Instant::now();
+        let mut discovery = GimbalDiscovery::new(settings());
+
+        let command = sent(discovery.start(now));
+        assert!(matches!(command.command, MavCmd::RequestMessage));
+        assert_eq!(
+            command.param1,
+            GimbalManagerInformation::message_id() as f32
+        );
+        assert_eq!(command.target_component, MANAGER_ID.component);
+
+        assert!(matches!(
+            discovery.handle(&ack(MANAGER_ID, &command, MavResult::Accepted), now),
+            GimbalStep::Wait
+        ));
+
+        // Manager reports a gimbal device, that speaks MAVLink
+        let command = sent(discovery.handle(
+            &frame(MANAGER_ID, &manager_information(DEVICE_ID.component)),
+            now,
+        ));
+        assert_eq!(command.param1, GimbalDeviceInformation::message_id() as f32);
+        assert_eq!(command.target_system, DEVICE_ID.system);
+        assert_eq!(command.target_component, DEVICE_ID.component);
+
+        // Device information is expected from the device itself
+        assert!(matches!(
+            discovery.handle(&frame(MANAGER_ID, &device_information(0)), now),
+            GimbalStep::Wait
+        ));
+        let info = match discovery.handle(&frame(DEVICE_ID, &device_information(0)), now) {
+            GimbalStep::Finished(Ok(info)) => info,
+            _ => panic!("gimbal information expected"),
+        };
+        assert_eq!(info.manager.device_id(), DEVICE_ID.component);
+        assert_eq!(info.manager.pitch_limits(), (-1.5, 0.5));
+        assert_eq!(info.device.vendor_name(), "Acme");
+        assert_eq!(info.device.model_name(), "G-1");
+        assert_eq!(info.device.uid(), 42);
+    }
+
+    #[test]
+    fn discovery_of_non_mavlink_devices() {
+        let now = Instant::now();
+        let mut discovery = GimbalDiscovery::new(settings());
+        discovery.start(now);
+
+        // Non-MAVLink devices are described by the manager
+        let command = sent(discovery.handle(&frame(MANAGER_ID, &manager_information(1)), now));
+        assert_eq!(command.target_component, MANAGER_ID.component);
+
+        // Information about another device of the same manager
+        assert!(matches!(
+            discovery.handle(&frame(MANAGER_ID, &device_information(2)), now),
+            GimbalStep::Wait
+        ));
+        assert!(matches!(
+            discovery.handle(&frame(MANAGER_ID, &device_information(1)), now),
+            GimbalStep::Finished(Ok(_))
+        ));
+    }
+
+    #[test]
+    fn gimbal_commands() {
+        let now = Instant::now();
+        let settings = settings().with_device_id(DEVICE_ID.component);
+
+        let mut take_control = GimbalCommand::take_control(settings, MavLinkId::new(255, 190));
+        let command = sent(take_control.start(now));
+        assert!(matches!(command.command, MavCmd::DoGimbalManagerConfigure));
+        assert_eq!((command.param1, command.param2), (255.0, 190.0));
+        assert_eq!((command.param3, command.param4), (-1.0, -1.0));
+        assert_eq!(command.param7, DEVICE_ID.component as f32);
+        assert_eq!(command.target_component, MANAGER_ID.component);
+
+        // Acknowledgement from another component
+        assert!(matches!(
+            take_control.handle(&ack(DEVICE_ID, &command, MavResult::Accepted), now),
+            GimbalStep::Wait
+        ));
+        assert!(matches!(
+            take_control.handle(&ack(MANAGER_ID, &command, MavResult::Accepted), now),
+            GimbalStep::Finished(Ok(()))
+        ));
+
+        let mut pitch_yaw = GimbalCommand::pitch_yaw(
+            settings,
+            -45.0,
+            10.0,
+            f32::NAN,
+            f32::NAN,
+            GimbalManagerFlags::YAW_LOCK,
+        );
+        let command = sent(pitch_yaw.start(now));
+        assert!(matches!(command.command, MavCmd::DoGimbalManagerPitchyaw));
+        assert_eq!((command.param1, command.param2), (-45.0, 10.0));
+        assert_eq!(command.param5, GimbalManagerFlags::YAW_LOCK.bits() as f32);
+        assert!(matches!(
+            pitch_yaw.handle(&ack(MANAGER_ID, &command, MavResult::Denied), now),
+            GimbalStep::Finished(Err(Error::Gimbal(GimbalError::Rejected(MavResult::Denied))))
+        ));
+    }
+
+    #[test]
+    fn setpoints_and_attitudes() {
+        let settings = settings().with_device_id(DEVICE_ID.component);
+
+        let setpoint =
+            GimbalSetpoint::from_euler(0.1, -0.5, 1.0).with_flags(GimbalManagerFlags::YAW_LOCK);
+        let message = setpoint.message(&settings);
+        assert_eq!(message.target_system, MANAGER_ID.system);
+        assert_eq!(message.target_component, MANAGER_ID.component);
+        assert_eq!(message.gimbal_device_id, DEVICE_ID.component);
+        assert!(message.angular_velocity_x.is_nan());
+
+        let attitude = GimbalAttitude::from_frame(
+            &frame(DEVICE_ID, &attitude_status(0, setpoint.q())),
+            &settings,
+        )
+        .unwrap();
+        let (roll, pitch, yaw) = attitude.euler();
+        assert!((roll - 0.1).abs() < 1e-5);
+        assert!((pitch + 0.5).abs() < 1e-5);
+        assert!((yaw - 1.0).abs() < 1e-5);
+
+        // Attitude of another device
+        assert!(GimbalAttitude::from_frame(
+            &frame(MavLinkId::new(1, 155), &attitude_status(0, setpoint.q())),
+            &settings,
+        )
+        .is_none());
+        // Attitude from another system
+        assert!(GimbalAttitude::from_frame(
+            &frame(MavLinkId::new(2, 154), &attitude_status(0, setpoint.q())),
+            &settings,
+        )
+        .is_none());
+
+        // Non-MAVLink devices are reported by the manager
+        let settings = settings.with_device_id(1);
+        assert!(GimbalAttitude::from_frame(
+            &frame(MANAGER_ID, &attitude_status(1, setpoint.q())),
+            &settings,
+        )
+        .is_some());
+        assert!(GimbalAttitude::from_frame(
+            &frame(MANAGER_ID, &attitude_status(2, setpoint.q())),
+            &settings,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn retries_and_times_out() {
+        let timeout = Duration::from_millis(100);
+        let settings = settings().with_timeout(timeout).with_retries(2);
+        let mut discovery = GimbalDiscovery::new(settings);
+
+        let now = Instant::now();
+        let first = sent(discovery.start(now));
+        assert_eq!(first.confirmation, 0);
+        assert!(matches!(discovery.check(now), GimbalStep::Wait));
+
+        let mut now = discovery.deadline();
+        for confirmation in 1..=2 {
+            assert_eq!(sent(discovery.check(now)).confirmation, confirmation);
+            now = discovery.deadline();
+        }
+        assert!(matches!(
+            discovery.check(now),
+            GimbalStep::Finished(Err(Error::Gimbal(GimbalError::Timeout)))
+        ));
+    }
+}
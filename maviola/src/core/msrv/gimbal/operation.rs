@@ -0,0 +1,169 @@
+use std::time::Instant;
+
+use crate::dialects::common::enums::{MavCmd, MavResult};
+use crate::dialects::common::messages::CommandLong;
+use crate::dialects::Common;
+use crate::error::GimbalError;
+use crate::protocol::MessageId;
+
+use crate::core::msrv::gimbal::GimbalSettings;
+use crate::prelude::*;
+
+/// Action requested by a [`GimbalOperation`].
+#[derive(Debug)]
+pub enum GimbalStep<T> {
+    /// Nothing to send, wait for the next frame or [`GimbalOperation::deadline`].
+    Wait,
+    /// Send a command to the gimbal and continue.
+    Send(CommandLong),
+    /// Operation is finished.
+    Finished(Result<T>),
+}
+
+/// Gimbal operation state machine.
+///
+/// Operations do not perform any I/O. The caller should send commands requested by returned
+/// [`GimbalStep`]s, pass all incoming frames to [`GimbalOperation::handle`], and call
+/// [`GimbalOperation::check`] once [`GimbalOperation::deadline`] is reached. Methods should not be
+/// called after operation is finished.
+pub trait GimbalOperation {
+    /// Result of a successful operation.
+    type Output;
+
+    /// Starts the operation.
+    fn start(&mut self, now: Instant) -> GimbalStep<Self::Output>;
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other components, unrelated messages, and acknowledgements of other commands
+    /// are ignored.
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> GimbalStep<Self::Output>;
+
+    /// Checks whether the gimbal response has timed out.
+    ///
+    /// Resends an unacknowledged command with increased `confirmation`, if retries are not
+    /// exhausted. Otherwise, fails the operation with [`GimbalError::Timeout`].
+    fn check(&mut self, now: Instant) -> GimbalStep<Self::Output>;
+
+    /// Time, when [`GimbalOperation::check`] should be called, if no frames were received.
+    fn deadline(&self) -> Instant;
+}
+
+/// Tracks a command sent to a gimbal component, its acknowledgement and retries.
+#[derive(Debug)]
+pub(super) struct Exchange {
+    pub(super) settings: GimbalSettings,
+    target: MavLinkId,
+    command: CommandLong,
+    attempts: usize,
+    deadline: Instant,
+    acknowledged: bool,
+}
+
+impl Exchange {
+    pub(super) fn new(
+        settings: GimbalSettings,
+        target: MavLinkId,
+        command: MavCmd,
+        params: [f32; 7],
+    ) -> Self {
+        Self {
+            settings,
+            target,
+            command: CommandLong {
+                target_system: target.system,
+                target_component: target.component,
+                command,
+                confirmation: 0,
+                param1: params[0],
+                param2: params[1],
+                param3: params[2],
+                param4: params[3],
+                param5: params[4],
+                param6: params[5],
+                param7: params[6],
+            },
+            attempts: 0,
+            deadline: Instant::now() + settings.timeout(),
+            acknowledged: false,
+        }
+    }
+
+    /// Creates an exchange, that requests a message with `MAV_CMD_REQUEST_MESSAGE`.
+    pub(super) fn request_message(
+        settings: GimbalSettings,
+        target: MavLinkId,
+        message_id: MessageId,
+    ) -> Self {
+        let params = [message_id as f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        Self::new(settings, target, MavCmd::RequestMessage, params)
+    }
+
+    pub(super) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Waits for another message until `deadline` without resending the command.
+    pub(super) fn wait_until(&mut self, deadline: Instant) {
+        self.deadline = deadline;
+    }
+
+    pub(super) fn send<T>(&mut self, now: Instant) -> GimbalStep<T> {
+        self.attempts = 0;
+        self.deadline = now + self.settings.timeout();
+        GimbalStep::Send(self.command.clone())
+    }
+
+    /// Decodes a message sent by the target component.
+    pub(super) fn decode<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Option<Common> {
+        let is_target = frame.system_id() == self.target.system
+            && (self.target.component == 0 || frame.component_id() == self.target.component);
+        if !is_target {
+            return None;
+        }
+        frame.decode::<Common>().ok()
+    }
+
+    /// Handles a `COMMAND_ACK` for the sent command.
+    ///
+    /// Returns [`None`] for unrelated messages and for [`MavResult::InProgress`], that postpones
+    /// the deadline. Otherwise, returns whether the command was accepted.
+    pub(super) fn ack(
+        &mut self,
+        message: &Common,
+        now: Instant,
+    ) -> Option<std::result::Result<(), GimbalError>> {
+        let ack = match message {
+            Common::CommandAck(ack) if ack.command as u16 == self.command.command as u16 => ack,
+            _ => return None,
+        };
+
+        self.acknowledged = true;
+        match ack.result {
+            MavResult::Accepted => Some(Ok(())),
+            MavResult::InProgress => {
+                self.deadline = now + self.settings.timeout();
+                None
+            }
+            result => Some(Err(GimbalError::Rejected(result))),
+        }
+    }
+
+    pub(super) fn check<T>(&mut self, now: Instant) -> GimbalStep<T> {
+        if now < self.deadline {
+            return GimbalStep::Wait;
+        }
+
+        if !self.acknowledged && self.attempts < self.settings.retries() {
+            self.attempts += 1;
+            self.deadline = now + self.settings.timeout();
+            self.command.confirmation = self.attempts.min(u8::MAX as usize) as u8;
+            return GimbalStep::Send(self.command.clone());
+        }
+        GimbalStep::Finished(Err(GimbalError::Timeout.into()))
+    }
+}
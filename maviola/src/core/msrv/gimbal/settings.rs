@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_GIMBAL_RETRIES, DEFAULT_GIMBAL_TIMEOUT};
+use crate::protocol::ComponentId;
+
+use crate::prelude::*;
+
+/// Largest gimbal device `ID` reserved for gimbals, that are not MAVLink components.
+const NON_MAVLINK_DEVICE_ID_MAX: u8 = 6;
+
+/// Settings of gimbal operations.
+///
+/// Defines a gimbal manager component, a gimbal device controlled by this manager, and how long to
+/// wait for manager acknowledgements.
+///
+/// Gimbal device `ID` is either a component `ID` of a gimbal device, that speaks MAVLink, or a
+/// number from `1` to `6` for gimbals connected to the manager by other means. Device `ID` `0`
+/// addresses all gimbal devices of the manager. Use [`GimbalDiscovery`] to find out the device `ID`
+/// from the manager.
+///
+/// [`GimbalDiscovery`]: crate::core::msrv::gimbal::GimbalDiscovery
+#[derive(Clone, Copy, Debug)]
+pub struct GimbalSettings {
+    manager: MavLinkId,
+    device_id: u8,
+    timeout: Duration,
+    retries: usize,
+}
+
+impl GimbalSettings {
+    /// Creates settings for operations with a gimbal `manager`.
+    ///
+    /// Addresses all gimbal devices of the manager. Uses [`DEFAULT_GIMBAL_TIMEOUT`] and
+    /// [`DEFAULT_GIMBAL_RETRIES`].
+    pub fn new(manager: MavLinkId) -> Self {
+        Self {
+            manager,
+            device_id: 0,
+            timeout: DEFAULT_GIMBAL_TIMEOUT,
+            retries: DEFAULT_GIMBAL_RETRIES,
+        }
+    }
+
+    /// Sets gimbal device `ID`.
+    pub fn with_device_id(mut self, device_id: u8) -> Self {
+        self.device_id = device_id;
+        self
+    }
+
+    /// Sets time to wait for a manager response before a command is resent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times a command is resent before operation fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Gimbal manager component.
+    pub fn manager(&self) -> MavLinkId {
+        self.manager
+    }
+
+    /// Gimbal device `ID`.
+    pub fn device_id(&self) -> u8 {
+        self.device_id
+    }
+
+    /// Time to wait for a manager response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Number of times a command is resent before operation fails.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Component, that describes the gimbal device.
+    ///
+    /// Gimbal devices, that speak MAVLink, describe themselves. Otherwise, gimbal manager is
+    /// responsible for that.
+    pub(super) fn device(&self) -> MavLinkId {
+        match self.device_id {
+            0..=NON_MAVLINK_DEVICE_ID_MAX => self.manager,
+            device_id => MavLinkId::new(self.manager.system, device_id as ComponentId),
+        }
+    }
+
+    /// Returns `true`, if `device_id` reported in a message matches the gimbal device.
+    pub(super) fn is_device_id(&self, device_id: u8) -> bool {
+        self.device_id == 0 || device_id == 0 || device_id == self.device_id
+    }
+
+    /// Returns `true`, if `frame` is sent by the system of the gimbal manager.
+    pub(super) fn is_gimbal_system<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        frame.system_id() == self.manager.system
+    }
+}
@@ -0,0 +1,278 @@
+//! # Mission protocol
+//!
+//! Implements [mission protocol](https://mavlink.io/en/services/mission.html) handshake for
+//! uploading and downloading lists of `MISSION_ITEM_INT` items.
+//!
+//! Transfers are implemented as I/O-free state machines, that implement [`MissionTransfer`]
+//! trait. They consume incoming frames and tell which messages should be sent to the peer and
+//! when to retry:
+//!
+//! * [`MissionSender`] sends mission items. It uploads a mission to a peer or serves a download
+//!   request (`MISSION_REQUEST_LIST`) received from a peer.
+//! * [`MissionReceiver`] receives mission items. It downloads a mission from a peer or serves an
+//!   upload initiated by a peer with `MISSION_COUNT`.
+//!
+//! Edge nodes provide `upload_mission`, `download_mission`, and `run_mission_transfer` helpers,
+//! that drive these state machines over node connection.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use maviola::core::msrv::mission::MissionSettings;
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let node = Node::sync::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().unwrap();
+//!
+//! let items = node.download_mission(MissionSettings::new(MavLinkId::new(1, 1))).unwrap();
+//! node.upload_mission(MissionSettings::new(MavLinkId::new(2, 1)), items).unwrap();
+//! ```
+
+mod receiver;
+mod sender;
+mod settings;
+mod transfer;
+
+pub use receiver::MissionReceiver;
+pub use sender::MissionSender;
+pub use settings::MissionSettings;
+pub use transfer::{MissionMessage, MissionStep, MissionTransfer};
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::dialects::common::enums::{MavCmd, MavFrame, MavMissionResult, MavMissionType};
+    use crate::dialects::common::messages::{MissionAck, MissionItemInt};
+    use crate::dialects::Common;
+    use crate::error::MissionError;
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const GCS_ID: MavLinkId = MavLinkId {
+        system: 255,
+        component: 190,
+    };
+    const VEHICLE_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn item(z: f32) -> MissionItemInt {
+        MissionItemInt {
+            target_system: 0,
+            target_component: 0,
+            seq: 0,
+            frame: MavFrame::GlobalRelativeAlt,
+            command: MavCmd::NavWaypoint,
+            current: 0,
+            autocontinue: 1,
+            param1: 0.0,
+            param2: 0.0,
+            param3: 0.0,
+            param4: 0.0,
+            x: 0,
+            y: 0,
+            z,
+            mission_type: MavMissionType::Mission,
+        }
+    }
+
+    fn frame(endpoint: &Endpoint<V2>, message: &MissionMessage) -> Frame<V2> {
+        match message {
+            MissionMessage::RequestList(message) => endpoint.next_frame(message),
+            MissionMessage::Count(message) => endpoint.next_frame(message),
+            MissionMessage::RequestInt(message) => endpoint.next_frame(message),
+            MissionMessage::ItemInt(message) => endpoint.next_frame(message),
+            MissionMessage::Ack(message) => endpoint.next_frame(message),
+        }
+        .unwrap()
+    }
+
+    struct Side<T: MissionTransfer> {
+        transfer: T,
+        endpoint: Endpoint<V2>,
+        result: Option<Result<T::Output>>,
+    }
+
+    impl<T: MissionTransfer> Side<T> {
+        fn new(transfer: T, id: MavLinkId) -> Self {
+            Self {
+                transfer,
+                endpoint: Endpoint::v2(id),
+                result: None,
+            }
+        }
+
+        fn apply(&mut self, step: MissionStep<T::Output>) -> Option<Frame<V2>> {
+            match step {
+                MissionStep::Wait => None,
+                MissionStep::Send(message) => Some(frame(&self.endpoint, &message)),
+                MissionStep::Finished(message, result) => {
+                    self.result = Some(result);
+                    message.map(|message| frame(&self.endpoint, &message))
+                }
+            }
+        }
+
+        fn receive(&mut self, frame: &Frame<V2>) -> Option<Frame<V2>> {
+            if self.result.is_some() {
+                return None;
+            }
+            let step = self.transfer.handle(frame, Instant::now());
+            self.apply(step)
+        }
+    }
+
+    fn exchange<A: MissionTransfer, B: MissionTransfer>(
+        a: &mut Side<A>,
+        b: &mut Side<B>,
+        mut frame: Option<Frame<V2>>,
+    ) {
+        let mut to_b = true;
+        while let Some(current) = frame {
+            frame = if to_b {
+                b.receive(&current)
+            } else {
+                a.receive(&current)
+            };
+            to_b = !to_b;
+        }
+    }
+
+    #[test]
+    fn upload_mission() {
+        let items = vec![item(10.0), item(20.0), item(30.0)];
+        let mut sender = Side::new(
+            MissionSender::new(MissionSettings::new(VEHICLE_ID), items),
+            GCS_ID,
+        );
+
+        let step = sender.transfer.start(Instant::now());
+        let count_frame = sender.apply(step).unwrap();
+        let count = match count_frame.decode::<Common>().unwrap() {
+            Common::MissionCount(count) => count,
+            _ => panic!("invalid message"),
+        };
+        assert_eq!(count.count, 3);
+        assert_eq!(count.target_system, VEHICLE_ID.system);
+
+        let mut receiver = Side::new(
+            MissionReceiver::for_upload(MissionSettings::new(GCS_ID), count.count),
+            VEHICLE_ID,
+        );
+        let step = receiver.transfer.start(Instant::now());
+        let request = receiver.apply(step);
+        exchange(&mut receiver, &mut sender, request);
+
+        assert!(sender.result.unwrap().is_ok());
+        let items = receiver.result.unwrap().unwrap();
+        assert_eq!(items.len(), 3);
+        for (seq, item) in items.iter().enumerate() {
+            assert_eq!(item.seq as usize, seq);
+            assert_eq!(item.z, (seq + 1) as f32 * 10.0);
+            assert_eq!(item.target_system, VEHICLE_ID.system);
+        }
+    }
+
+    #[test]
+    fn download_empty_mission() {
+        let mut receiver = Side::new(
+            MissionReceiver::new(MissionSettings::new(VEHICLE_ID)),
+            GCS_ID,
+        );
+        let mut sender = Side::new(
+            MissionSender::new(MissionSettings::new(GCS_ID), Vec::new()),
+            VEHICLE_ID,
+        );
+
+        let step = receiver.transfer.start(Instant::now());
+        assert!(matches!(
+            step,
+            MissionStep::Send(MissionMessage::RequestList(_))
+        ));
+
+        let step = sender.transfer.start(Instant::now());
+        let count = sender.apply(step);
+        exchange(&mut sender, &mut receiver, count);
+
+        assert!(receiver.result.unwrap().unwrap().is_empty());
+        assert!(sender.result.unwrap().is_ok());
+    }
+
+    #[test]
+    fn ignores_other_peers_and_mission_types() {
+        let settings = MissionSettings::new(VEHICLE_ID).with_mission_type(MavMissionType::Fence);
+        let mut sender = MissionSender::new(settings, vec![item(10.0)]);
+        sender.start(Instant::now());
+
+        let ack = MissionAck {
+            target_system: GCS_ID.system,
+            target_component: GCS_ID.component,
+            type_: MavMissionResult::MavMissionAccepted,
+            mission_type: MavMissionType::Mission,
+            opaque_id: 0,
+        };
+        let ack = frame(&Endpoint::v2(VEHICLE_ID), &MissionMessage::Ack(ack));
+        assert!(matches!(
+            sender.handle(&ack, Instant::now()),
+            MissionStep::Wait
+        ));
+
+        let ack = MissionAck {
+            target_system: GCS_ID.system,
+            target_component: GCS_ID.component,
+            type_: MavMissionResult::MavMissionDenied,
+            mission_type: MavMissionType::Fence,
+            opaque_id: 0,
+        };
+        let other = frame(
+            &Endpoint::v2(MavLinkId::new(2, 1)),
+            &MissionMessage::Ack(ack.clone()),
+        );
+        assert!(matches!(
+            sender.handle(&other, Instant::now()),
+            MissionStep::Wait
+        ));
+
+        let ack = frame(&Endpoint::v2(VEHICLE_ID), &MissionMessage::Ack(ack));
+        assert!(matches!(
+            sender.handle(&ack, Instant::now()),
+            MissionStep::Finished(None, Err(Error::Mission(MissionError::Rejected(_))))
+        ));
+    }
+
+    #[test]
+    fn retries_and_times_out() {
+        let timeout = Duration::from_millis(100);
+        let settings = MissionSettings::new(VEHICLE_ID)
+            .with_timeout(timeout)
+            .with_retries(2);
+        let mut receiver = MissionReceiver::new(settings);
+
+        let now = Instant::now();
+        receiver.start(now);
+        assert_eq!(receiver.deadline(), now + timeout);
+        assert!(matches!(receiver.check(now), MissionStep::Wait));
+
+        let mut now = receiver.deadline();
+        for _ in 0..2 {
+            assert!(matches!(
+                receiver.check(now),
+                MissionStep::Send(MissionMessage::RequestList(_))
+            ));
+            now = receiver.deadline();
+        }
+
+        assert!(matches!(
+            receiver.check(now),
+            MissionStep::Finished(None, Err(Error::Mission(MissionError::Timeout)))
+        ));
+    }
+}
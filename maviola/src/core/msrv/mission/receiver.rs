@@ -0,0 +1,140 @@
+use std::time::Instant;
+
+use crate::core::msrv::mission::transfer::Exchange;
+use crate::dialects::common::enums::MavMissionResult;
+use crate::dialects::common::messages::{
+    MissionAck, MissionItemInt, MissionRequestInt, MissionRequestList,
+};
+use crate::dialects::Common;
+use crate::error::MissionError;
+
+use crate::core::msrv::mission::{MissionMessage, MissionSettings, MissionStep, MissionTransfer};
+use crate::prelude::*;
+
+/// Receives mission items from a peer.
+///
+/// Requests items one by one with `MISSION_REQUEST_INT` and completes the transfer with
+/// `MISSION_ACK`, once all items are received.
+///
+/// Receivers created by [`MissionReceiver::new`] download a mission: they request the number of
+/// items with `MISSION_REQUEST_LIST` and wait for `MISSION_COUNT`. Use
+/// [`MissionReceiver::for_upload`] to serve an upload, once `MISSION_COUNT` is received from a peer.
+#[derive(Debug)]
+pub struct MissionReceiver {
+    exchange: Exchange,
+    count: Option<u16>,
+    items: Vec<MissionItemInt>,
+}
+
+impl MissionReceiver {
+    /// Creates a receiver, that downloads a mission from a peer.
+    pub fn new(settings: MissionSettings) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            count: None,
+            items: Vec::new(),
+        }
+    }
+
+    /// Creates a receiver, that accepts mission upload of `count` items initiated by a peer.
+    pub fn for_upload(settings: MissionSettings, count: u16) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            count: Some(count),
+            items: Vec::with_capacity(count as usize),
+        }
+    }
+
+    fn next(&mut self, now: Instant) -> MissionStep<Vec<MissionItemInt>> {
+        let settings = &self.exchange.settings;
+        let (target_system, target_component) = (settings.peer().system, settings.peer().component);
+        let mission_type = settings.mission_type();
+
+        if self.items.len() < self.count.unwrap_or_default() as usize {
+            let request = MissionRequestInt {
+                target_system,
+                target_component,
+                seq: self.items.len() as u16,
+                mission_type,
+            };
+            return self.exchange.send(MissionMessage::RequestInt(request), now);
+        }
+
+        let ack = MissionAck {
+            target_system,
+            target_component,
+            type_: MavMissionResult::MavMissionAccepted,
+            mission_type,
+            opaque_id: 0,
+        };
+        MissionStep::Finished(
+            Some(MissionMessage::Ack(ack)),
+            Ok(std::mem::take(&mut self.items)),
+        )
+    }
+}
+
+impl MissionTransfer for MissionReceiver {
+    type Output = Vec<MissionItemInt>;
+
+    fn start(&mut self, now: Instant) -> MissionStep<Self::Output> {
+        if self.count.is_some() {
+            return self.next(now);
+        }
+
+        let settings = &self.exchange.settings;
+        let request = MissionRequestList {
+            target_system: settings.peer().system,
+            target_component: settings.peer().component,
+            mission_type: settings.mission_type(),
+        };
+        self.exchange
+            .send(MissionMessage::RequestList(request), now)
+    }
+
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> MissionStep<Self::Output> {
+        let settings = &self.exchange.settings;
+        if !settings.is_peer(frame) {
+            return MissionStep::Wait;
+        }
+
+        match frame.decode::<Common>() {
+            // Peer may repeat the count, if our first request was lost
+            Ok(Common::MissionCount(msg)) if settings.is_mission_type(msg.mission_type) => {
+                if !self.items.is_empty() {
+                    return MissionStep::Wait;
+                }
+                self.count = Some(msg.count);
+                self.next(now)
+            }
+            Ok(Common::MissionItemInt(msg)) if settings.is_mission_type(msg.mission_type) => {
+                if self.count.is_none() || msg.seq as usize != self.items.len() {
+                    return MissionStep::Wait;
+                }
+                self.items.push(msg);
+                self.next(now)
+            }
+            Ok(Common::MissionAck(msg)) if settings.is_mission_type(msg.mission_type) => {
+                match msg.type_ {
+                    MavMissionResult::MavMissionAccepted => MissionStep::Wait,
+                    result => {
+                        MissionStep::Finished(None, Err(MissionError::Rejected(result).into()))
+                    }
+                }
+            }
+            _ => MissionStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> MissionStep<Self::Output> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
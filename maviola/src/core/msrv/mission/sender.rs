@@ -0,0 +1,104 @@
+use std::time::Instant;
+
+use crate::core::msrv::mission::transfer::Exchange;
+use crate::dialects::common::enums::MavMissionResult;
+use crate::dialects::common::messages::{MissionCount, MissionItemInt};
+use crate::dialects::Common;
+use crate::error::MissionError;
+
+use crate::core::msrv::mission::{MissionMessage, MissionSettings, MissionStep, MissionTransfer};
+use crate::prelude::*;
+
+/// Sends mission items to a peer.
+///
+/// Announces the number of items with `MISSION_COUNT` and then sends items requested by the peer
+/// with `MISSION_REQUEST_INT` (or legacy `MISSION_REQUEST`) until the peer completes the transfer
+/// with `MISSION_ACK`.
+///
+/// Use sender to upload a mission to a peer, or to serve a download, once `MISSION_REQUEST_LIST`
+/// is received from a peer.
+///
+/// Target `ID`s, sequence numbers, and mission type of items are set by the sender.
+#[derive(Debug)]
+pub struct MissionSender {
+    exchange: Exchange,
+    items: Vec<MissionItemInt>,
+}
+
+impl MissionSender {
+    /// Creates a sender of mission `items`.
+    pub fn new(settings: MissionSettings, items: Vec<MissionItemInt>) -> Self {
+        Self {
+            exchange: Exchange::new(settings),
+            items,
+        }
+    }
+
+    fn item(&self, seq: u16) -> Option<MissionItemInt> {
+        let settings = &self.exchange.settings;
+        let mut item = self.items.get(seq as usize)?.clone();
+
+        item.target_system = settings.peer().system;
+        item.target_component = settings.peer().component;
+        item.seq = seq;
+        item.mission_type = settings.mission_type();
+
+        Some(item)
+    }
+
+    fn request(&mut self, seq: u16, now: Instant) -> MissionStep<()> {
+        match self.item(seq) {
+            Some(item) => self.exchange.send(MissionMessage::ItemInt(item), now),
+            None => MissionStep::Wait,
+        }
+    }
+}
+
+impl MissionTransfer for MissionSender {
+    type Output = ();
+
+    fn start(&mut self, now: Instant) -> MissionStep<()> {
+        let settings = &self.exchange.settings;
+        let count = MissionCount {
+            target_system: settings.peer().system,
+            target_component: settings.peer().component,
+            count: self.items.len() as u16,
+            mission_type: settings.mission_type(),
+            opaque_id: 0,
+        };
+
+        self.exchange.send(MissionMessage::Count(count), now)
+    }
+
+    fn handle<V: MaybeVersioned>(&mut self, frame: &Frame<V>, now: Instant) -> MissionStep<()> {
+        let settings = &self.exchange.settings;
+        if !settings.is_peer(frame) {
+            return MissionStep::Wait;
+        }
+
+        match frame.decode::<Common>() {
+            Ok(Common::MissionRequestInt(msg)) if settings.is_mission_type(msg.mission_type) => {
+                self.request(msg.seq, now)
+            }
+            Ok(Common::MissionRequest(msg)) if settings.is_mission_type(msg.mission_type) => {
+                self.request(msg.seq, now)
+            }
+            Ok(Common::MissionAck(msg)) if settings.is_mission_type(msg.mission_type) => {
+                let result = match msg.type_ {
+                    MavMissionResult::MavMissionAccepted => Ok(()),
+                    result => Err(MissionError::Rejected(result).into()),
+                };
+                MissionStep::Finished(None, result)
+            }
+            _ => MissionStep::Wait,
+        }
+    }
+
+    fn check(&mut self, now: Instant) -> MissionStep<()> {
+        self.exchange.check(now)
+    }
+
+    fn deadline(&self) -> Instant {
+        self.exchange.deadline()
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_MISSION_RETRIES, DEFAULT_MISSION_TIMEOUT};
+use crate::dialects::common::enums::MavMissionType;
+
+use crate::prelude::*;
+
+/// Settings of a mission transfer.
+///
+/// Defines a peer, the type of transferred mission, and how long to wait for the peer responses.
+///
+/// If component `ID` of a peer is `0`, then responses are accepted from any component of the peer
+/// system.
+#[derive(Clone, Copy, Debug)]
+pub struct MissionSettings {
+    peer: MavLinkId,
+    mission_type: MavMissionType,
+    timeout: Duration,
+    retries: usize,
+}
+
+impl MissionSettings {
+    /// Creates settings for a transfer of [`MavMissionType::Mission`] with a `peer`.
+    ///
+    /// Uses [`DEFAULT_MISSION_TIMEOUT`] and [`DEFAULT_MISSION_RETRIES`].
+    pub fn new(peer: MavLinkId) -> Self {
+        Self {
+            peer,
+            mission_type: MavMissionType::Mission,
+            timeout: DEFAULT_MISSION_TIMEOUT,
+            retries: DEFAULT_MISSION_RETRIES,
+        }
+    }
+
+    /// Sets the type of transferred mission.
+    pub fn with_mission_type(mut self, mission_type: MavMissionType) -> Self {
+        self.mission_type = mission_type;
+        self
+    }
+
+    /// Sets time to wait for a peer response before the last message is resent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times the last message is resent before transfer fails.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Peer, that participates in transfer.
+    pub fn peer(&self) -> MavLinkId {
+        self.peer
+    }
+
+    /// Type of transferred mission.
+    pub fn mission_type(&self) -> MavMissionType {
+        self.mission_type
+    }
+
+    /// Time to wait for a peer response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Number of times the last message is resent before transfer fails.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    pub(super) fn is_peer<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        frame.system_id() == self.peer.system
+            && (self.peer.component == 0 || frame.component_id() == self.peer.component)
+    }
+
+    pub(super) fn is_mission_type(&self, mission_type: MavMissionType) -> bool {
+        mission_type as u8 == self.mission_type as u8
+    }
+}
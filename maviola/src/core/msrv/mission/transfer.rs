@@ -0,0 +1,127 @@
+use std::time::Instant;
+
+use crate::dialects::common::messages::{
+    MissionAck, MissionCount, MissionItemInt, MissionRequestInt, MissionRequestList,
+};
+use crate::error::MissionError;
+
+use crate::core::msrv::mission::MissionSettings;
+use crate::prelude::*;
+
+/// Mission protocol message, that should be sent to a peer.
+#[derive(Clone, Debug)]
+pub enum MissionMessage {
+    /// Requests a list of mission items from a peer.
+    RequestList(MissionRequestList),
+    /// Announces the number of mission items.
+    Count(MissionCount),
+    /// Requests a mission item with a specific sequence number.
+    RequestInt(MissionRequestInt),
+    /// Mission item.
+    ItemInt(MissionItemInt),
+    /// Completes the transfer.
+    Ack(MissionAck),
+}
+
+impl MissionMessage {
+    /// Sends message using an edge node or a frame sender.
+    pub fn send<V: Versioned>(&self, sender: &impl SendMessage<V>) -> Result<()> {
+        match self {
+            MissionMessage::RequestList(message) => sender.send(message),
+            MissionMessage::Count(message) => sender.send(message),
+            MissionMessage::RequestInt(message) => sender.send(message),
+            MissionMessage::ItemInt(message) => sender.send(message),
+            MissionMessage::Ack(message) => sender.send(message),
+        }
+    }
+}
+
+/// Action requested by a [`MissionTransfer`].
+#[derive(Debug)]
+pub enum MissionStep<T> {
+    /// Nothing to send, wait for the next frame or [`MissionTransfer::deadline`].
+    Wait,
+    /// Send a message to the peer and continue.
+    Send(MissionMessage),
+    /// Transfer is finished. The message, if present, should be sent to the peer before returning
+    /// the result.
+    Finished(Option<MissionMessage>, Result<T>),
+}
+
+/// Mission transfer state machine.
+///
+/// Transfers do not perform any I/O. The caller should send messages requested by returned
+/// [`MissionStep`]s, pass all incoming frames to [`MissionTransfer::handle`], and call
+/// [`MissionTransfer::check`] once [`MissionTransfer::deadline`] is reached. Methods should not be
+/// called after transfer is finished.
+pub trait MissionTransfer {
+    /// Result of a successful transfer.
+    type Output;
+
+    /// Starts the transfer.
+    fn start(&mut self, now: Instant) -> MissionStep<Self::Output>;
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other peers, of other mission types, and unrelated messages are ignored.
+    fn handle<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        now: Instant,
+    ) -> MissionStep<Self::Output>;
+
+    /// Checks whether the peer response has timed out.
+    ///
+    /// Resends the last message, if retries are not exhausted. Otherwise, fails the transfer with
+    /// [`MissionError::Timeout`].
+    fn check(&mut self, now: Instant) -> MissionStep<Self::Output>;
+
+    /// Time, when [`MissionTransfer::check`] should be called, if no frames were received.
+    fn deadline(&self) -> Instant;
+}
+
+/// Tracks the last sent message and retries.
+#[derive(Debug)]
+pub(super) struct Exchange {
+    pub(super) settings: MissionSettings,
+    last: Option<MissionMessage>,
+    attempts: usize,
+    deadline: Instant,
+}
+
+impl Exchange {
+    pub(super) fn new(settings: MissionSettings) -> Self {
+        Self {
+            settings,
+            last: None,
+            attempts: 0,
+            deadline: Instant::now() + settings.timeout(),
+        }
+    }
+
+    pub(super) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    pub(super) fn send<T>(&mut self, message: MissionMessage, now: Instant) -> MissionStep<T> {
+        self.last = Some(message.clone());
+        self.attempts = 0;
+        self.deadline = now + self.settings.timeout();
+        MissionStep::Send(message)
+    }
+
+    pub(super) fn check<T>(&mut self, now: Instant) -> MissionStep<T> {
+        if now < self.deadline {
+            return MissionStep::Wait;
+        }
+
+        match &self.last {
+            Some(message) if self.attempts < self.settings.retries() => {
+                self.attempts += 1;
+                self.deadline = now + self.settings.timeout();
+                MissionStep::Send(message.clone())
+            }
+            _ => MissionStep::Finished(None, Err(MissionError::Timeout.into())),
+        }
+    }
+}
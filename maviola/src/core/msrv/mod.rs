@@ -0,0 +1,61 @@
+//! # MAVLink microservices
+//!
+//! Utilities, that implement stateful MAVLink
+//! [microservices](https://mavlink.io/en/services/) on top of Maviola nodes.
+//!
+//! Each microservice is available under a corresponding `msrv-utils-*` feature flag. Use
+//! `msrv-utils-all` to enable all of them.
+//!
+//! Currently supported microservices:
+//!
+//! * [`params`] — [parameter protocol](https://mavlink.io/en/services/parameter.html) server,
+//!   requires `msrv-utils-params` feature.
+//! * [`mission`] — [mission protocol](https://mavlink.io/en/services/mission.html) upload and
+//!   download, requires `msrv-utils-mission` feature.
+//! * [`streams`] — [message interval](https://mavlink.io/en/services/message_interval.html)
+//!   protocol and periodic message streams, requires `msrv-utils-streams` feature.
+//! * [`ftp`] — [file transfer protocol](https://mavlink.io/en/services/ftp.html) client,
+//!   requires `msrv-utils-ftp` feature.
+//! * [`camera`] — [camera protocol](https://mavlink.io/en/services/camera.html) client, requires
+//!   `msrv-utils-camera` feature.
+//! * [`gimbal`] — [gimbal protocol v2](https://mavlink.io/en/services/gimbal_v2.html) client,
+//!   requires `msrv-utils-gimbal` feature.
+//! * [`timesync`] — [time synchronisation](https://mavlink.io/en/services/timesync.html) protocol
+//!   responder and latency measurement, requires `msrv-utils-timesync` feature.
+//! * [`gcs`] — ground control station client, that combines commands, parameters, and missions,
+//!   requires `msrv-utils-gcs` feature.
+//!
+//! Services attached to the same node share a single event subscription and handler. Incoming
+//! frames are routed to services by message `ID`, so each service decodes only the messages it
+//! handles.
+
+#[cfg(feature = "msrv-utils-params")]
+pub mod params;
+
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+pub(crate) mod registry;
+
+#[cfg(feature = "msrv-utils-mission")]
+pub mod mission;
+
+#[cfg(feature = "msrv-utils-streams")]
+pub mod streams;
+
+#[cfg(feature = "msrv-utils-ftp")]
+pub mod ftp;
+
+#[cfg(feature = "msrv-utils-camera")]
+pub mod camera;
+
+#[cfg(feature = "msrv-utils-gimbal")]
+pub mod gimbal;
+
+#[cfg(feature = "msrv-utils-timesync")]
+pub mod timesync;
+
+#[cfg(feature = "msrv-utils-gcs")]
+pub mod gcs;
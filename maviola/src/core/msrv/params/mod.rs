@@ -0,0 +1,21 @@
+//! # Parameter protocol
+//!
+//! Implements the server side of the MAVLink
+//! [parameter protocol](https://mavlink.io/en/services/parameter.html).
+//!
+//! [`ParamServer`] stores a table of typed parameters and, once attached to an edge node, answers
+//! `PARAM_REQUEST_LIST`, `PARAM_REQUEST_READ`, and `PARAM_SET` requests automatically. Parameters
+//! changed by peers are reported to node subscribers as [`ParamChanged`] custom events.
+//!
+//! Parameter messages are defined in the `common` dialect, which is enabled by
+//! `msrv-utils-params` feature.
+
+mod server;
+mod value;
+
+pub use server::{ParamChanged, ParamServer};
+pub use value::{ParamEncoding, ParamValue};
+
+pub(crate) use server::ParamService;
+#[cfg(feature = "msrv-utils-gcs")]
+pub(crate) use server::{decode_name, encode_name, PARAM_ID_LEN};
@@ -0,0 +1,502 @@
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::Instant;
+
+use crate::core::msrv::registry::{Microservice, ServiceOutput};
+use crate::core::node::CustomEvent;
+use crate::dialects::common::messages::{
+    ParamRequestList, ParamRequestRead, ParamSet, ParamValue as ParamValueMessage,
+};
+use crate::dialects::Common;
+use crate::error::ParamError;
+use crate::protocol::{ComponentId, MessageId, SystemId};
+
+use crate::core::msrv::params::{ParamEncoding, ParamValue};
+use crate::prelude::*;
+
+/// Maximum length of parameter name in bytes.
+pub(crate) const PARAM_ID_LEN: usize = 16;
+
+/// Messages handled by parameter server.
+const PARAM_REQUESTS: [MessageId; 3] = [
+    ParamRequestRead::message_id(),
+    ParamRequestList::message_id(),
+    ParamSet::message_id(),
+];
+
+/// Parameter protocol server.
+///
+/// Stores a table of typed parameters and answers parameter protocol requests once attached to an
+/// edge node by `attach_param_server`. Parameters are announced in the order of declaration, which
+/// defines their indexes.
+///
+/// Server can be cloned, all clones share the same parameter table. Values set locally by
+/// [`ParamServer::set`] are broadcast to the peers of all nodes this server is attached to.
+///
+/// When a peer changes a parameter with `PARAM_SET`, node emits a [`ParamChanged`] payload wrapped
+/// into a custom event.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::msrv::params::{ParamChanged, ParamServer};
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let params = ParamServer::new();
+/// params.declare("SYSID_THISMAV", 1u8).unwrap();
+/// params.declare("WPNAV_SPEED", 5.0f32).unwrap();
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+/// node.attach_param_server(&params);
+///
+/// for event in node.events() {
+///     if let Event::Custom(event) = event {
+///         if let Some(changed) = event.downcast_ref::<ParamChanged>() {
+///             println!("{} changed to {:?}", changed.name, changed.value);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParamServer {
+    table: Arc<RwLock<ParamTable>>,
+    encoding: ParamEncoding,
+    subscribers: Arc<Mutex<Vec<mpsc::Sender<usize>>>>,
+}
+
+/// Parameter changed by a remote peer.
+///
+/// Emitted by edge nodes with an attached [`ParamServer`] as a payload of a custom event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamChanged {
+    /// Parameter name.
+    pub name: String,
+    /// Value before the change.
+    pub previous: ParamValue,
+    /// New value.
+    pub value: ParamValue,
+    /// Peer, that changed the parameter.
+    pub source: MavLinkId,
+}
+
+/// Messages, that server should send in response to a request.
+#[derive(Debug, Default)]
+pub(crate) struct ParamResponse {
+    pub(crate) messages: Vec<ParamValueMessage>,
+    pub(crate) changed: Option<ParamChanged>,
+}
+
+/// Parameter server attached to a node.
+pub(crate) struct ParamService {
+    server: ParamServer,
+    changes: mpsc::Receiver<usize>,
+}
+
+#[derive(Debug, Default)]
+struct ParamTable {
+    params: Vec<(String, ParamValue)>,
+    indexes: HashMap<String, usize>,
+}
+
+impl ParamServer {
+    /// Creates an empty parameter server with [`ParamEncoding::Bytewise`] encoding.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the encoding of parameter values.
+    pub fn with_encoding(mut self, encoding: ParamEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Encoding of parameter values.
+    pub fn encoding(&self) -> ParamEncoding {
+        self.encoding
+    }
+
+    /// Declares a new parameter with initial `value`.
+    ///
+    /// Parameter names are limited to 16 bytes. Returns [`ParamError::InvalidName`] for empty or
+    /// too long names and [`ParamError::AlreadyExists`] if parameter is already declared.
+    pub fn declare(&self, name: &str, value: impl Into<ParamValue>) -> Result<()> {
+        if name.is_empty() || name.len() > PARAM_ID_LEN {
+            return Err(ParamError::InvalidName(name.to_string()).into());
+        }
+
+        let mut table = self.table.write()?;
+        if table.indexes.contains_key(name) {
+            return Err(ParamError::AlreadyExists(name.to_string()).into());
+        }
+
+        let index = table.params.len();
+        table.params.push((name.to_string(), value.into()));
+        table.indexes.insert(name.to_string(), index);
+
+        Ok(())
+    }
+
+    /// Sets a new `value` of a declared parameter and announces it to peers.
+    ///
+    /// Returns [`ParamError::NotFound`] for undeclared parameters and [`ParamError::TypeMismatch`]
+    /// if value type differs from the declared one.
+    pub fn set(&self, name: &str, value: impl Into<ParamValue>) -> Result<()> {
+        let value = value.into();
+
+        let index = {
+            let mut table = self.table.write()?;
+            let index = match table.indexes.get(name) {
+                Some(index) => *index,
+                None => return Err(ParamError::NotFound(name.to_string()).into()),
+            };
+
+            let current = &mut table.params[index].1;
+            if !current.same_type(&value) {
+                return Err(ParamError::TypeMismatch {
+                    name: name.to_string(),
+                    expected: *current,
+                    actual: value,
+                }
+                .into());
+            }
+            *current = value;
+
+            index
+        };
+
+        self.subscribers
+            .lock()?
+            .retain(|subscriber| subscriber.send(index).is_ok());
+
+        Ok(())
+    }
+
+    /// Returns the value of a parameter, if declared.
+    pub fn get(&self, name: &str) -> Option<ParamValue> {
+        let table = self.table.read().ok()?;
+        table.indexes.get(name).map(|index| table.params[*index].1)
+    }
+
+    /// Number of declared parameters.
+    pub fn len(&self) -> usize {
+        self.table
+            .read()
+            .map(|table| table.params.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns `true`, if there are no declared parameters.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a snapshot of all parameters in the order of declaration.
+    pub fn params(&self) -> Vec<(String, ParamValue)> {
+        self.table
+            .read()
+            .map(|table| table.params.clone())
+            .unwrap_or_default()
+    }
+
+    /// Subscribes to indexes of parameters changed locally.
+    pub(crate) fn subscribe(&self) -> mpsc::Receiver<usize> {
+        let (tx, rx) = mpsc::channel();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(tx);
+        }
+        rx
+    }
+
+    /// Creates `PARAM_VALUE` message for a parameter with specified index.
+    pub(crate) fn value_message(&self, index: usize) -> Option<ParamValueMessage> {
+        let table = self.table.read().ok()?;
+        table.value_message(index, self.encoding)
+    }
+
+    /// Handles parameter protocol request addressed to a node with specified `id`.
+    ///
+    /// Frames, that are not parameter requests, or addressed to other components are ignored.
+    pub(crate) fn respond<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        id: MavLinkId,
+    ) -> ParamResponse {
+        let mut response = ParamResponse::default();
+
+        match frame.decode::<Common>() {
+            Ok(Common::ParamRequestList(msg)) => {
+                if !is_addressed(msg.target_system, msg.target_component, id) {
+                    return response;
+                }
+                if let Ok(table) = self.table.read() {
+                    response.messages = (0..table.params.len())
+                        .filter_map(|index| table.value_message(index, self.encoding))
+                        .collect();
+                }
+            }
+            Ok(Common::ParamRequestRead(msg)) => {
+                if !is_addressed(msg.target_system, msg.target_component, id) {
+                    return response;
+                }
+                if let Ok(table) = self.table.read() {
+                    let index = if msg.param_index < 0 {
+                        table.indexes.get(&decode_name(&msg.param_id)).copied()
+                    } else {
+                        Some(msg.param_index as usize)
+                    };
+                    response.messages = index
+                        .and_then(|index| table.value_message(index, self.encoding))
+                        .into_iter()
+                        .collect();
+                }
+            }
+            Ok(Common::ParamSet(msg)) => {
+                if !is_addressed(msg.target_system, msg.target_component, id) {
+                    return response;
+                }
+                let name = decode_name(&msg.param_id);
+                let value = ParamValue::decode(msg.param_value, msg.param_type, self.encoding);
+
+                if let Ok(mut table) = self.table.write() {
+                    let index = match table.indexes.get(&name) {
+                        Some(index) => *index,
+                        None => return response,
+                    };
+
+                    let previous = table.params[index].1;
+                    // Values of a different type are rejected by replying with the current value
+                    if let Some(value) = value.filter(|value| value.same_type(&previous)) {
+                        table.params[index].1 = value;
+                        if value != previous {
+                            response.changed = Some(ParamChanged {
+                                name,
+                                previous,
+                                value,
+                                source: MavLinkId::new(frame.system_id(), frame.component_id()),
+                            });
+                        }
+                    }
+
+                    response.messages = table
+                        .value_message(index, self.encoding)
+                        .into_iter()
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+
+        response
+    }
+}
+
+impl ParamService {
+    pub(crate) fn new(server: &ParamServer) -> Self {
+        Self {
+            server: server.clone(),
+            changes: server.subscribe(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Microservice<V> for ParamService {
+    fn name(&self) -> &'static str {
+        "parameter server"
+    }
+
+    fn message_ids(&self) -> &'static [MessageId] {
+        &PARAM_REQUESTS
+    }
+
+    fn handle(&mut self, frame: &Frame<V>, id: MavLinkId, output: &mut ServiceOutput) {
+        let response = self.server.respond(frame, id);
+
+        for message in response.messages {
+            output.messages.push(Box::new(message));
+        }
+        if let Some(changed) = response.changed {
+            log::trace!("parameter changed: {changed:?}");
+            output.events.push(CustomEvent::new(changed));
+        }
+    }
+
+    fn poll(&mut self, _: Instant, output: &mut ServiceOutput) -> Option<Instant> {
+        while let Ok(index) = self.changes.try_recv() {
+            if let Some(message) = self.server.value_message(index) {
+                output.messages.push(Box::new(message));
+            }
+        }
+        None
+    }
+}
+
+impl ParamTable {
+    fn value_message(&self, index: usize, encoding: ParamEncoding) -> Option<ParamValueMessage> {
+        let (name, value) = self.params.get(index)?;
+
+        Some(ParamValueMessage {
+            param_id: encode_name(name),
+            param_value: value.encode(encoding),
+            param_type: value.param_type(),
+            param_count: self.params.len() as u16,
+            param_index: index as u16,
+        })
+    }
+}
+
+fn is_addressed(target_system: SystemId, target_component: ComponentId, id: MavLinkId) -> bool {
+    target_system == id.system && (target_component == 0 || target_component == id.component)
+}
+
+pub(crate) fn encode_name(name: &str) -> [u8; PARAM_ID_LEN] {
+    let mut param_id = [0u8; PARAM_ID_LEN];
+    let len = name.len().min(PARAM_ID_LEN);
+    param_id[..len].copy_from_slice(&name.as_bytes()[..len]);
+    param_id
+}
+
+pub(crate) fn decode_name(param_id: &[u8; PARAM_ID_LEN]) -> String {
+    let len = param_id
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(PARAM_ID_LEN);
+    String::from_utf8_lossy(&param_id[..len]).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialects::common::enums::MavParamType;
+    use crate::dialects::common::messages::{ParamRequestList, ParamRequestRead, ParamSet};
+    use crate::protocol::Endpoint;
+
+    const SERVER_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn request(message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(255, 190))
+            .next_frame(message)
+            .unwrap()
+    }
+
+    fn server() -> ParamServer {
+        let server = ParamServer::new();
+        server.declare("SYSID_THISMAV", 1u8).unwrap();
+        server.declare("WPNAV_SPEED", 5.0f32).unwrap();
+        server
+    }
+
+    #[test]
+    fn declare_and_set_params() {
+        let server = server();
+
+        assert_eq!(server.len(), 2);
+        assert!(server.declare("WPNAV_SPEED", 1.0f32).is_err());
+        assert!(server.declare("", 1.0f32).is_err());
+        assert!(server.declare("VERY_LONG_PARAM_NAME", 1.0f32).is_err());
+
+        server.set("WPNAV_SPEED", 7.5f32).unwrap();
+        assert_eq!(server.get("WPNAV_SPEED"), Some(ParamValue::Real32(7.5)));
+        assert!(server.set("WPNAV_SPEED", 7i32).is_err());
+        assert!(server.set("UNKNOWN", 7i32).is_err());
+        assert!(server.get("UNKNOWN").is_none());
+    }
+
+    #[test]
+    fn local_changes_are_announced() {
+        let server = server();
+        let changes = server.subscribe();
+
+        server.set("WPNAV_SPEED", 2.0f32).unwrap();
+
+        let index = changes.try_recv().unwrap();
+        let message = server.value_message(index).unwrap();
+        assert_eq!(decode_name(&message.param_id), "WPNAV_SPEED");
+        assert_eq!(message.param_value, 2.0);
+    }
+
+    #[test]
+    fn respond_to_requests() {
+        let server = server();
+
+        let response = server.respond(
+            &request(&ParamRequestList {
+                target_system: 1,
+                target_component: 0,
+            }),
+            SERVER_ID,
+        );
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(response.messages[1].param_index, 1);
+        assert_eq!(response.messages[1].param_count, 2);
+
+        let response = server.respond(
+            &request(&ParamRequestRead {
+                target_system: 1,
+                target_component: 1,
+                param_id: encode_name("WPNAV_SPEED"),
+                param_index: -1,
+            }),
+            SERVER_ID,
+        );
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(response.messages[0].param_index, 1);
+
+        let response = server.respond(
+            &request(&ParamRequestList {
+                target_system: 2,
+                target_component: 0,
+            }),
+            SERVER_ID,
+        );
+        assert!(response.messages.is_empty());
+    }
+
+    #[test]
+    fn respond_to_param_set() {
+        let server = server();
+
+        let response = server.respond(
+            &request(&ParamSet {
+                target_system: 1,
+                target_component: 1,
+                param_id: encode_name("SYSID_THISMAV"),
+                param_value: ParamValue::UInt8(3).encode(ParamEncoding::Bytewise),
+                param_type: MavParamType::Uint8,
+            }),
+            SERVER_ID,
+        );
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(
+            response.changed,
+            Some(ParamChanged {
+                name: "SYSID_THISMAV".to_string(),
+                previous: ParamValue::UInt8(1),
+                value: ParamValue::UInt8(3),
+                source: MavLinkId::new(255, 190),
+            })
+        );
+        assert_eq!(server.get("SYSID_THISMAV"), Some(ParamValue::UInt8(3)));
+
+        let response = server.respond(
+            &request(&ParamSet {
+                target_system: 1,
+                target_component: 1,
+                param_id: encode_name("SYSID_THISMAV"),
+                param_value: 1.0,
+                param_type: MavParamType::Real32,
+            }),
+            SERVER_ID,
+        );
+        assert_eq!(response.messages.len(), 1);
+        assert!(response.changed.is_none());
+        assert_eq!(server.get("SYSID_THISMAV"), Some(ParamValue::UInt8(3)));
+    }
+}
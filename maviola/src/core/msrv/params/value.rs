@@ -0,0 +1,193 @@
+use crate::dialects::common::enums::MavParamType;
+
+/// Typed value of a MAVLink parameter.
+///
+/// MAVLink transfers all parameter values within a 32-bit float field, therefore only types that
+/// fit into 4 bytes are supported. The way values are packed into this field is defined by
+/// [`ParamEncoding`].
+///
+/// Parameter values can be created from the corresponding primitive types:
+///
+/// ```rust
+/// use maviola::core::msrv::params::ParamValue;
+///
+/// assert_eq!(ParamValue::from(42i32), ParamValue::Int32(42));
+/// assert_eq!(ParamValue::from(0.5f32), ParamValue::Real32(0.5));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParamValue {
+    /// 8-bit unsigned integer.
+    UInt8(u8),
+    /// 8-bit signed integer.
+    Int8(i8),
+    /// 16-bit unsigned integer.
+    UInt16(u16),
+    /// 16-bit signed integer.
+    Int16(i16),
+    /// 32-bit unsigned integer.
+    UInt32(u32),
+    /// 32-bit signed integer.
+    Int32(i32),
+    /// 32-bit floating point number.
+    Real32(f32),
+}
+
+/// Defines how parameter values are packed into the 32-bit float `param_value` field.
+///
+/// Peers should agree on encoding. Autopilots announce the supported encoding with
+/// `MAV_PROTOCOL_CAPABILITY_PARAM_ENCODE_BYTEWISE` and `MAV_PROTOCOL_CAPABILITY_PARAM_ENCODE_C_CAST`
+/// capabilities.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParamEncoding {
+    /// Bytes of a value are copied into the field as is (used by PX4).
+    #[default]
+    Bytewise,
+    /// Value is cast to a float (used by ArduPilot).
+    ///
+    /// Integers with absolute value larger than 2<sup>24</sup> lose precision.
+    CCast,
+}
+
+impl ParamValue {
+    /// MAVLink type of the value.
+    pub fn param_type(&self) -> MavParamType {
+        match self {
+            ParamValue::UInt8(_) => MavParamType::Uint8,
+            ParamValue::Int8(_) => MavParamType::Int8,
+            ParamValue::UInt16(_) => MavParamType::Uint16,
+            ParamValue::Int16(_) => MavParamType::Int16,
+            ParamValue::UInt32(_) => MavParamType::Uint32,
+            ParamValue::Int32(_) => MavParamType::Int32,
+            ParamValue::Real32(_) => MavParamType::Real32,
+        }
+    }
+
+    /// Returns `true` if both values have the same type.
+    pub fn same_type(&self, other: &ParamValue) -> bool {
+        self.param_type() as u8 == other.param_type() as u8
+    }
+
+    /// Packs value into the `param_value` field of parameter messages.
+    pub fn encode(&self, encoding: ParamEncoding) -> f32 {
+        match encoding {
+            ParamEncoding::Bytewise => {
+                let mut bytes = [0u8; 4];
+                match *self {
+                    ParamValue::UInt8(value) => bytes[..1].copy_from_slice(&value.to_le_bytes()),
+                    ParamValue::Int8(value) => bytes[..1].copy_from_slice(&value.to_le_bytes()),
+                    ParamValue::UInt16(value) => bytes[..2].copy_from_slice(&value.to_le_bytes()),
+                    ParamValue::Int16(value) => bytes[..2].copy_from_slice(&value.to_le_bytes()),
+                    ParamValue::UInt32(value) => bytes = value.to_le_bytes(),
+                    ParamValue::Int32(value) => bytes = value.to_le_bytes(),
+                    ParamValue::Real32(value) => bytes = value.to_le_bytes(),
+                }
+                f32::from_le_bytes(bytes)
+            }
+            ParamEncoding::CCast => match *self {
+                ParamValue::UInt8(value) => value as f32,
+                ParamValue::Int8(value) => value as f32,
+                ParamValue::UInt16(value) => value as f32,
+                ParamValue::Int16(value) => value as f32,
+                ParamValue::UInt32(value) => value as f32,
+                ParamValue::Int32(value) => value as f32,
+                ParamValue::Real32(value) => value,
+            },
+        }
+    }
+
+    /// Unpacks value of a specified `param_type` from the `param_value` field of parameter
+    /// messages.
+    ///
+    /// Returns [`None`] for 64-bit types, since they can't be transferred within this field.
+    pub fn decode(value: f32, param_type: MavParamType, encoding: ParamEncoding) -> Option<Self> {
+        Some(match encoding {
+            ParamEncoding::Bytewise => {
+                let bytes = value.to_le_bytes();
+                match param_type {
+                    MavParamType::Uint8 => ParamValue::UInt8(bytes[0]),
+                    MavParamType::Int8 => ParamValue::Int8(i8::from_le_bytes([bytes[0]])),
+                    MavParamType::Uint16 => {
+                        ParamValue::UInt16(u16::from_le_bytes([bytes[0], bytes[1]]))
+                    }
+                    MavParamType::Int16 => {
+                        ParamValue::Int16(i16::from_le_bytes([bytes[0], bytes[1]]))
+                    }
+                    MavParamType::Uint32 => ParamValue::UInt32(u32::from_le_bytes(bytes)),
+                    MavParamType::Int32 => ParamValue::Int32(i32::from_le_bytes(bytes)),
+                    MavParamType::Real32 => ParamValue::Real32(value),
+                    _ => return None,
+                }
+            }
+            ParamEncoding::CCast => match param_type {
+                MavParamType::Uint8 => ParamValue::UInt8(value as u8),
+                MavParamType::Int8 => ParamValue::Int8(value as i8),
+                MavParamType::Uint16 => ParamValue::UInt16(value as u16),
+                MavParamType::Int16 => ParamValue::Int16(value as i16),
+                MavParamType::Uint32 => ParamValue::UInt32(value as u32),
+                MavParamType::Int32 => ParamValue::Int32(value as i32),
+                MavParamType::Real32 => ParamValue::Real32(value),
+                _ => return None,
+            },
+        })
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<$ty> for ParamValue {
+                fn from(value: $ty) -> Self {
+                    ParamValue::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(
+    u8 => UInt8,
+    i8 => Int8,
+    u16 => UInt16,
+    i16 => Int16,
+    u32 => UInt32,
+    i32 => Int32,
+    f32 => Real32
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [ParamValue; 7] = [
+        ParamValue::UInt8(200),
+        ParamValue::Int8(-100),
+        ParamValue::UInt16(60000),
+        ParamValue::Int16(-30000),
+        ParamValue::UInt32(4_000_000_000),
+        ParamValue::Int32(-2_000_000_000),
+        ParamValue::Real32(-0.25),
+    ];
+
+    #[test]
+    fn bytewise_encoding_is_lossless() {
+        for value in VALUES {
+            let encoded = value.encode(ParamEncoding::Bytewise);
+            let decoded =
+                ParamValue::decode(encoded, value.param_type(), ParamEncoding::Bytewise).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn c_cast_encoding() {
+        for value in VALUES[..4].iter().chain(&VALUES[6..]) {
+            let encoded = value.encode(ParamEncoding::CCast);
+            let decoded =
+                ParamValue::decode(encoded, value.param_type(), ParamEncoding::CCast).unwrap();
+            assert_eq!(decoded, *value);
+        }
+
+        assert_eq!(ParamValue::Int32(-7).encode(ParamEncoding::CCast), -7.0);
+        assert!(ParamValue::decode(1.0, MavParamType::Real64, ParamEncoding::CCast).is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! # 🔒 Registry of microservices attached to a node
+//!
+//! All microservices attached to a node share a single event subscription and a single handler.
+//! Incoming frames are dispatched only to services, that declared their message `ID`s, so each
+//! frame is decoded by interested services only.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::core::node::CustomEvent;
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+/// Stateful MAVLink microservice, that can be attached to an edge node.
+pub(crate) trait Microservice<V: MaybeVersioned>: Send {
+    /// Service name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// `ID`s of incoming messages, this service handles.
+    fn message_ids(&self) -> &'static [MessageId];
+
+    /// Handles incoming frame addressed to a node with specified `id`.
+    ///
+    /// Called only for frames with message `ID`s returned by [`Microservice::message_ids`].
+    fn handle(&mut self, frame: &Frame<V>, id: MavLinkId, output: &mut ServiceOutput);
+
+    /// Produces messages, that service emits on its own.
+    ///
+    /// Returns the instant, when service should be polled next time. Services, that return
+    /// [`None`], are polled at the pooling interval of the handler.
+    fn poll(&mut self, now: Instant, output: &mut ServiceOutput) -> Option<Instant>;
+}
+
+/// Messages and events produced by microservices.
+#[derive(Default)]
+pub(crate) struct ServiceOutput {
+    pub(crate) messages: Vec<Box<dyn Message>>,
+    pub(crate) events: Vec<CustomEvent>,
+}
+
+/// Microservices attached to a node.
+///
+/// Registry can be cloned, all clones share the same services.
+pub(crate) struct ServiceRegistry<V: MaybeVersioned> {
+    inner: Arc<Mutex<RegistryInner<V>>>,
+}
+
+struct RegistryInner<V: MaybeVersioned> {
+    services: Vec<Box<dyn Microservice<V>>>,
+    routes: HashMap<MessageId, Vec<usize>>,
+}
+
+impl<V: MaybeVersioned> ServiceRegistry<V> {
+    /// Adds a service to the registry.
+    ///
+    /// Returns `true`, if this is the first registered service and the handler should be started.
+    pub(crate) fn register(&self, service: Box<dyn Microservice<V>>) -> Result<bool> {
+        let mut inner = self.inner.lock()?;
+
+        let index = inner.services.len();
+        for message_id in service.message_ids() {
+            inner.routes.entry(*message_id).or_default().push(index);
+        }
+        log::debug!("microservice registered: {}", service.name());
+        inner.services.push(service);
+
+        Ok(index == 0)
+    }
+
+    /// Returns `true`, if a service with the specified `name` is registered.
+    #[cfg(feature = "msrv-utils-timesync")]
+    pub(crate) fn contains(&self, name: &str) -> Result<bool> {
+        let inner = self.inner.lock()?;
+        Ok(inner.services.iter().any(|service| service.name() == name))
+    }
+
+    /// Dispatches incoming frame to the services, that handle its message.
+    pub(crate) fn dispatch(&self, frame: &Frame<V>, id: MavLinkId) -> Result<ServiceOutput> {
+        let mut output = ServiceOutput::default();
+        let mut inner = self.inner.lock()?;
+        let inner = &mut *inner;
+
+        if let Some(indexes) = inner.routes.get(&frame.message_id()) {
+            for index in indexes {
+                inner.services[*index].handle(frame, id, &mut output);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Polls all services and returns their output and the instant, when any of them is due.
+    pub(crate) fn poll(&self, now: Instant) -> Result<(ServiceOutput, Option<Instant>)> {
+        let mut output = ServiceOutput::default();
+        let mut next_due: Option<Instant> = None;
+
+        for service in self.inner.lock()?.services.iter_mut() {
+            if let Some(due) = service.poll(now, &mut output) {
+                next_due = Some(match next_due {
+                    Some(next_due) => next_due.min(due),
+                    None => due,
+                });
+            }
+        }
+
+        Ok((output, next_due))
+    }
+}
+
+impl<V: MaybeVersioned> Clone for ServiceRegistry<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Default for ServiceRegistry<V> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RegistryInner {
+                services: Vec::new(),
+                routes: HashMap::new(),
+            })),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Debug for ServiceRegistry<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let services: Vec<&'static str> = self
+            .inner
+            .lock()
+            .map(|inner| {
+                inner
+                    .services
+                    .iter()
+                    .map(|service| service.name())
+                    .collect()
+            })
+            .unwrap_or_default();
+        f.debug_struct("ServiceRegistry")
+            .field("services", &services)
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod registry_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    use super::*;
+
+    struct Counter {
+        message_ids: &'static [MessageId],
+        handled: Arc<AtomicUsize>,
+    }
+
+    impl<V: MaybeVersioned> Microservice<V> for Counter {
+        fn name(&self) -> &'static str {
+            "counter"
+        }
+
+        fn message_ids(&self) -> &'static [MessageId] {
+            self.message_ids
+        }
+
+        fn handle(&mut self, _: &Frame<V>, _: MavLinkId, output: &mut ServiceOutput) {
+            self.handled.fetch_add(1, Ordering::SeqCst);
+            output.messages.push(Box::new(Heartbeat::default()));
+        }
+
+        fn poll(&mut self, now: Instant, _: &mut ServiceOutput) -> Option<Instant> {
+            Some(now)
+        }
+    }
+
+    #[test]
+    fn frames_are_dispatched_by_message_id() {
+        let registry = ServiceRegistry::<V2>::default();
+        let heartbeats = Arc::new(AtomicUsize::new(0));
+        let others = Arc::new(AtomicUsize::new(0));
+
+        assert!(registry
+            .register(Box::new(Counter {
+                message_ids: &[0],
+                handled: heartbeats.clone(),
+            }))
+            .unwrap());
+        assert!(!registry
+            .register(Box::new(Counter {
+                message_ids: &[76],
+                handled: others.clone(),
+            }))
+            .unwrap());
+
+        let frame = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let output = registry.dispatch(&frame, MavLinkId::new(1, 1)).unwrap();
+
+        assert_eq!(output.messages.len(), 1);
+        assert_eq!(heartbeats.load(Ordering::SeqCst), 1);
+        assert_eq!(others.load(Ordering::SeqCst), 0);
+
+        let now = Instant::now();
+        let (_, next_due) = registry.poll(now).unwrap();
+        assert_eq!(next_due, Some(now));
+    }
+}
@@ -0,0 +1,697 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::core::msrv::registry::{Microservice, ServiceOutput};
+use crate::core::node::CustomEvent;
+use crate::dialects::common::enums::{MavCmd, MavResult};
+use crate::dialects::common::messages::{CommandAck, CommandLong, MessageInterval};
+use crate::dialects::Common;
+use crate::error::StreamError;
+use crate::protocol::{ComponentId, MessageId, SystemId};
+
+use crate::prelude::*;
+
+/// `ID` of `MESSAGE_INTERVAL` message, that can be requested by `REQUEST_MESSAGE` command.
+const MESSAGE_INTERVAL_ID: MessageId = MessageInterval::message_id();
+
+/// Messages handled by stream rate controller.
+const STREAM_COMMANDS: [MessageId; 1] = [CommandLong::message_id()];
+
+type Generator = Arc<dyn Fn() -> Box<dyn Message> + Send + Sync>;
+
+/// Message interval protocol controller.
+///
+/// Holds periodic outgoing messages (streams) and emits them at their current rates once attached
+/// to an edge node by `attach_stream_controller`. Each stream is defined by a generator closure,
+/// that produces the next message, and a default interval.
+///
+/// Peers may throttle, upsample, disable, or reset streams with `SET_MESSAGE_INTERVAL` commands,
+/// query current rates with `GET_MESSAGE_INTERVAL` and `REQUEST_MESSAGE` for `MESSAGE_INTERVAL`,
+/// or request a single message of a registered stream with `REQUEST_MESSAGE`. Commands are
+/// acknowledged with `COMMAND_ACK`.
+///
+/// Controller can be cloned, all clones share the same streams. When a peer changes the interval
+/// of a stream, node emits an [`IntervalChanged`] payload wrapped into a custom event.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use maviola::core::msrv::streams::{IntervalChanged, StreamRateController};
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+/// use maviola::dialects::common::messages::SysStatus;
+///
+/// let streams = StreamRateController::new();
+/// streams.register(Some(Duration::from_secs(1)), || SysStatus {
+///     voltage_battery: 12000,
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+/// node.attach_stream_controller(&streams);
+///
+/// for event in node.events() {
+///     if let Event::Custom(event) = event {
+///         if let Some(changed) = event.downcast_ref::<IntervalChanged>() {
+///             println!("message #{} interval: {:?}", changed.message_id, changed.interval);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct StreamRateController {
+    streams: Arc<RwLock<HashMap<MessageId, Stream>>>,
+}
+
+/// Stream interval changed by a remote peer.
+///
+/// Emitted by edge nodes with an attached [`StreamRateController`] as a payload of a custom event.
+/// Intervals equal to [`None`] mean, that stream is disabled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalChanged {
+    /// `ID` of a streamed message.
+    pub message_id: MessageId,
+    /// Interval before the change.
+    pub previous: Option<Duration>,
+    /// New interval.
+    pub interval: Option<Duration>,
+    /// Peer, that changed the interval.
+    pub source: MavLinkId,
+}
+
+/// Messages, that controller should send in response to a request.
+#[derive(Default)]
+pub(crate) struct StreamResponse {
+    pub(crate) messages: Vec<Box<dyn Message>>,
+    pub(crate) changed: Option<IntervalChanged>,
+}
+
+/// Tracks deadlines of streams emitted by a single node.
+#[derive(Debug, Default)]
+pub(crate) struct StreamScheduler {
+    deadlines: HashMap<MessageId, (Duration, Instant)>,
+}
+
+/// Stream rate controller attached to a node.
+pub(crate) struct StreamService {
+    controller: StreamRateController,
+    scheduler: StreamScheduler,
+}
+
+#[derive(Clone)]
+struct Stream {
+    default_interval: Option<Duration>,
+    interval: Option<Duration>,
+    generator: Generator,
+}
+
+impl StreamRateController {
+    /// Creates a controller without streams.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a stream of messages produced by `generator` and returns message `ID`.
+    ///
+    /// The `interval` is used as a default stream interval, [`None`] means that stream is disabled
+    /// until requested by a peer. Generator is called each time a message should be sent.
+    ///
+    /// Returns [`StreamError::AlreadyRegistered`] if stream for this message already exists and
+    /// [`StreamError::ZeroInterval`] if `interval` is zero.
+    pub fn register<M: Message + 'static>(
+        &self,
+        interval: Option<Duration>,
+        generator: impl Fn() -> M + Send + Sync + 'static,
+    ) -> Result<MessageId> {
+        let message_id = generator().id();
+        if interval == Some(Duration::ZERO) {
+            return Err(StreamError::ZeroInterval(message_id).into());
+        }
+
+        let mut streams = self.streams.write()?;
+        if streams.contains_key(&message_id) {
+            return Err(StreamError::AlreadyRegistered(message_id).into());
+        }
+
+        streams.insert(
+            message_id,
+            Stream {
+                default_interval: interval,
+                interval,
+                generator: Arc::new(move || Box::new(generator())),
+            },
+        );
+
+        Ok(message_id)
+    }
+
+    /// Removes a stream of messages with specified `ID`.
+    ///
+    /// Returns [`StreamError::NotRegistered`] if there is no such stream.
+    pub fn unregister(&self, message_id: MessageId) -> Result<()> {
+        match self.streams.write()?.remove(&message_id) {
+            Some(_) => Ok(()),
+            None => Err(StreamError::NotRegistered(message_id).into()),
+        }
+    }
+
+    /// Sets a new `interval` for a stream, [`None`] disables the stream.
+    ///
+    /// Returns [`StreamError::NotRegistered`] if there is no such stream and
+    /// [`StreamError::ZeroInterval`] if `interval` is zero.
+    pub fn set_interval(&self, message_id: MessageId, interval: Option<Duration>) -> Result<()> {
+        if interval == Some(Duration::ZERO) {
+            return Err(StreamError::ZeroInterval(message_id).into());
+        }
+
+        match self.streams.write()?.get_mut(&message_id) {
+            Some(stream) => {
+                stream.interval = interval;
+                Ok(())
+            }
+            None => Err(StreamError::NotRegistered(message_id).into()),
+        }
+    }
+
+    /// Restores the default interval of a stream.
+    ///
+    /// Returns [`StreamError::NotRegistered`] if there is no such stream.
+    pub fn reset_interval(&self, message_id: MessageId) -> Result<()> {
+        match self.streams.write()?.get_mut(&message_id) {
+            Some(stream) => {
+                stream.interval = stream.default_interval;
+                Ok(())
+            }
+            None => Err(StreamError::NotRegistered(message_id).into()),
+        }
+    }
+
+    /// Current interval of a stream.
+    ///
+    /// Returns [`None`] if stream is disabled or not registered.
+    pub fn interval(&self, message_id: MessageId) -> Option<Duration> {
+        let streams = self.streams.read().ok()?;
+        streams.get(&message_id).and_then(|stream| stream.interval)
+    }
+
+    /// Returns `true` if stream for a message with specified `ID` is registered.
+    pub fn is_registered(&self, message_id: MessageId) -> bool {
+        self.streams
+            .read()
+            .map(|streams| streams.contains_key(&message_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns a snapshot of all registered streams and their current intervals ordered by
+    /// message `ID`.
+    pub fn streams(&self) -> Vec<(MessageId, Option<Duration>)> {
+        let mut streams: Vec<_> = self
+            .streams
+            .read()
+            .map(|streams| {
+                streams
+                    .iter()
+                    .map(|(id, stream)| (*id, stream.interval))
+                    .collect()
+            })
+            .unwrap_or_default();
+        streams.sort_by_key(|(id, _)| *id);
+        streams
+    }
+
+    /// Handles message interval request addressed to a node with specified `id`.
+    ///
+    /// Frames, that are not message interval commands, or addressed to other components are
+    /// ignored.
+    pub(crate) fn respond<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        id: MavLinkId,
+    ) -> StreamResponse {
+        let mut response = StreamResponse::default();
+
+        let command = match frame.decode::<Common>() {
+            Ok(Common::CommandLong(command)) => command,
+            _ => return response,
+        };
+        if !is_addressed(command.target_system, command.target_component, id) {
+            return response;
+        }
+
+        let source = MavLinkId::new(frame.system_id(), frame.component_id());
+        let result = match command.command {
+            MavCmd::SetMessageInterval => self.handle_set(&command, source, &mut response),
+            MavCmd::GetMessageInterval => match message_id(command.param1) {
+                Some(message_id) => {
+                    response.messages.push(self.interval_message(message_id));
+                    MavResult::Accepted
+                }
+                None => MavResult::Denied,
+            },
+            MavCmd::RequestMessage => self.handle_request(&command, &mut response),
+            _ => return response,
+        };
+
+        response.messages.insert(
+            0,
+            Box::new(CommandAck {
+                command: command.command,
+                result,
+                progress: 0,
+                result_param2: 0,
+                target_system: source.system,
+                target_component: source.component,
+            }),
+        );
+
+        response
+    }
+
+    /// Returns streams, that are currently enabled, with their intervals and generators.
+    fn active(&self) -> Vec<(MessageId, Duration, Generator)> {
+        self.streams
+            .read()
+            .map(|streams| {
+                streams
+                    .iter()
+                    .filter_map(|(id, stream)| {
+                        stream
+                            .interval
+                            .map(|interval| (*id, interval, stream.generator.clone()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn handle_set(
+        &self,
+        command: &CommandLong,
+        source: MavLinkId,
+        response: &mut StreamResponse,
+    ) -> MavResult {
+        let message_id = match message_id(command.param1) {
+            Some(message_id) => message_id,
+            None => return MavResult::Denied,
+        };
+
+        let mut streams = match self.streams.write() {
+            Ok(streams) => streams,
+            Err(_) => return MavResult::Failed,
+        };
+        let stream = match streams.get_mut(&message_id) {
+            Some(stream) => stream,
+            None => return MavResult::Unsupported,
+        };
+
+        let interval = match command.param2 {
+            -1.0 => None,
+            0.0 => stream.default_interval,
+            value if value >= 1.0 => Some(Duration::from_micros(value as u64)),
+            _ => return MavResult::Denied,
+        };
+
+        let previous = stream.interval;
+        stream.interval = interval;
+        if previous != interval {
+            response.changed = Some(IntervalChanged {
+                message_id,
+                previous,
+                interval,
+                source,
+            });
+        }
+
+        MavResult::Accepted
+    }
+
+    fn handle_request(&self, command: &CommandLong, response: &mut StreamResponse) -> MavResult {
+        match message_id(command.param1) {
+            Some(MESSAGE_INTERVAL_ID) => match message_id(command.param2) {
+                Some(message_id) => {
+                    response.messages.push(self.interval_message(message_id));
+                    MavResult::Accepted
+                }
+                None => MavResult::Denied,
+            },
+            Some(message_id) => {
+                let generator = self.streams.read().ok().and_then(|streams| {
+                    streams
+                        .get(&message_id)
+                        .map(|stream| stream.generator.clone())
+                });
+                match generator {
+                    Some(generator) => {
+                        response.messages.push(generator());
+                        MavResult::Accepted
+                    }
+                    None => MavResult::Unsupported,
+                }
+            }
+            None => MavResult::Denied,
+        }
+    }
+
+    fn interval_message(&self, message_id: MessageId) -> Box<dyn Message> {
+        let interval_us = match self.streams.read() {
+            Ok(streams) => match streams.get(&message_id) {
+                Some(Stream {
+                    interval: Some(interval),
+                    ..
+                }) => interval.as_micros().clamp(1, i32::MAX as u128) as i32,
+                Some(_) => -1,
+                None => 0,
+            },
+            Err(_) => 0,
+        };
+
+        Box::new(MessageInterval {
+            message_id: message_id as u16,
+            interval_us,
+        })
+    }
+}
+
+impl Debug for StreamRateController {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamRateController")
+            .field("streams", &self.streams())
+            .finish()
+    }
+}
+
+impl StreamScheduler {
+    /// Generates messages of streams, that are due at `now`.
+    ///
+    /// Returns generated messages and the instant, when the next stream is due. Streams with
+    /// changed intervals are rescheduled and sent immediately.
+    pub(crate) fn poll(
+        &mut self,
+        controller: &StreamRateController,
+        now: Instant,
+    ) -> (Vec<Box<dyn Message>>, Option<Instant>) {
+        let active = controller.active();
+        self.deadlines
+            .retain(|id, _| active.iter().any(|(active_id, _, _)| active_id == id));
+
+        let mut messages = Vec::new();
+        let mut next_due: Option<Instant> = None;
+
+        for (id, interval, generator) in active {
+            let deadline = match self.deadlines.get(&id) {
+                Some((scheduled, deadline)) if *scheduled == interval => *deadline,
+                _ => now,
+            };
+
+            let deadline = if deadline <= now {
+                messages.push(generator());
+                // Missed ticks are skipped instead of being sent in a burst
+                let next = deadline + interval;
+                if next <= now {
+                    now + interval
+                } else {
+                    next
+                }
+            } else {
+                deadline
+            };
+
+            self.deadlines.insert(id, (interval, deadline));
+            next_due = Some(match next_due {
+                Some(next_due) => next_due.min(deadline),
+                None => deadline,
+            });
+        }
+
+        (messages, next_due)
+    }
+}
+
+impl StreamService {
+    pub(crate) fn new(controller: &StreamRateController) -> Self {
+        Self {
+            controller: controller.clone(),
+            scheduler: StreamScheduler::default(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Microservice<V> for StreamService {
+    fn name(&self) -> &'static str {
+        "stream rate controller"
+    }
+
+    fn message_ids(&self) -> &'static [MessageId] {
+        &STREAM_COMMANDS
+    }
+
+    fn handle(&mut self, frame: &Frame<V>, id: MavLinkId, output: &mut ServiceOutput) {
+        let response = self.controller.respond(frame, id);
+
+        output.messages.extend(response.messages);
+        if let Some(changed) = response.changed {
+            log::trace!("stream interval changed: {changed:?}");
+            output.events.push(CustomEvent::new(changed));
+        }
+    }
+
+    fn poll(&mut self, now: Instant, output: &mut ServiceOutput) -> Option<Instant> {
+        let (messages, next_due) = self.scheduler.poll(&self.controller, now);
+        output.messages.extend(messages);
+        next_due
+    }
+}
+
+fn is_addressed(target_system: SystemId, target_component: ComponentId, id: MavLinkId) -> bool {
+    target_system == id.system && (target_component == 0 || target_component == id.component)
+}
+
+fn message_id(param: f32) -> Option<MessageId> {
+    if param.is_finite() && param >= 0.0 && param.fract() == 0.0 {
+        Some(param as MessageId)
+    } else {
+        None
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod controller_tests {
+    use super::*;
+
+    use crate::dialects::common::messages::{Heartbeat, SysStatus};
+    use crate::protocol::Endpoint;
+
+    const CONTROLLER_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn command(command: MavCmd, param1: f32, param2: f32) -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(255, 190))
+            .next_frame(&CommandLong {
+                target_system: 1,
+                target_component: 0,
+                command,
+                confirmation: 0,
+                param1,
+                param2,
+                param3: 0.0,
+                param4: 0.0,
+                param5: 0.0,
+                param6: 0.0,
+                param7: 0.0,
+            })
+            .unwrap()
+    }
+
+    fn controller() -> (StreamRateController, MessageId) {
+        let controller = StreamRateController::new();
+        let id = controller
+            .register(Some(Duration::from_secs(1)), SysStatus::default)
+            .unwrap();
+        (controller, id)
+    }
+
+    fn ack_result(response: &StreamResponse) -> MavResult {
+        let frame = Endpoint::v2(CONTROLLER_ID)
+            .next_frame(response.messages[0].as_ref())
+            .unwrap();
+        match frame.decode::<Common>().unwrap() {
+            Common::CommandAck(ack) => ack.result,
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+
+    fn interval_us(response: &StreamResponse) -> i32 {
+        let frame = Endpoint::v2(CONTROLLER_ID)
+            .next_frame(response.messages[1].as_ref())
+            .unwrap();
+        match frame.decode::<Common>().unwrap() {
+            Common::MessageInterval(msg) => msg.interval_us,
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+
+    #[test]
+    fn register_and_set_intervals() {
+        let (controller, id) = controller();
+
+        assert!(controller.is_registered(id));
+        assert!(controller.register(None, SysStatus::default).is_err());
+        assert!(controller
+            .register(Some(Duration::ZERO), Heartbeat::default)
+            .is_err());
+
+        controller
+            .set_interval(id, Some(Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(controller.interval(id), Some(Duration::from_millis(100)));
+        controller.set_interval(id, None).unwrap();
+        assert_eq!(controller.interval(id), None);
+        controller.reset_interval(id).unwrap();
+        assert_eq!(controller.interval(id), Some(Duration::from_secs(1)));
+
+        assert!(controller.set_interval(0, None).is_err());
+        controller.unregister(id).unwrap();
+        assert!(controller.streams().is_empty());
+    }
+
+    #[test]
+    fn respond_to_set_message_interval() {
+        let (controller, id) = controller();
+
+        let response = controller.respond(
+            &command(MavCmd::SetMessageInterval, id as f32, 200_000.0),
+            CONTROLLER_ID,
+        );
+        assert!(matches!(ack_result(&response), MavResult::Accepted));
+        assert_eq!(
+            response.changed,
+            Some(IntervalChanged {
+                message_id: id,
+                previous: Some(Duration::from_secs(1)),
+                interval: Some(Duration::from_millis(200)),
+                source: MavLinkId::new(255, 190),
+            })
+        );
+
+        let response = controller.respond(
+            &command(MavCmd::SetMessageInterval, id as f32, -1.0),
+            CONTROLLER_ID,
+        );
+        assert!(response.changed.is_some());
+        assert_eq!(controller.interval(id), None);
+
+        let response = controller.respond(
+            &command(MavCmd::SetMessageInterval, id as f32, 0.0),
+            CONTROLLER_ID,
+        );
+        assert!(response.changed.is_some());
+        assert_eq!(controller.interval(id), Some(Duration::from_secs(1)));
+
+        let response = controller.respond(
+            &command(MavCmd::SetMessageInterval, 0.0, 100.0),
+            CONTROLLER_ID,
+        );
+        assert!(matches!(ack_result(&response), MavResult::Unsupported));
+        assert!(response.changed.is_none());
+
+        let response = controller.respond(
+            &command(MavCmd::SetMessageInterval, id as f32, 100.0),
+            MavLinkId::new(2, 1),
+        );
+        assert!(response.messages.is_empty());
+    }
+
+    #[test]
+    fn respond_to_interval_requests() {
+        let (controller, id) = controller();
+
+        let response = controller.respond(
+            &command(MavCmd::GetMessageInterval, id as f32, 0.0),
+            CONTROLLER_ID,
+        );
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(interval_us(&response), 1_000_000);
+
+        controller.set_interval(id, None).unwrap();
+        let response = controller.respond(
+            &command(
+                MavCmd::RequestMessage,
+                MESSAGE_INTERVAL_ID as f32,
+                id as f32,
+            ),
+            CONTROLLER_ID,
+        );
+        assert_eq!(interval_us(&response), -1);
+
+        let response = controller.respond(
+            &command(MavCmd::GetMessageInterval, 0.0, 0.0),
+            CONTROLLER_ID,
+        );
+        assert_eq!(interval_us(&response), 0);
+    }
+
+    #[test]
+    fn respond_to_message_requests() {
+        let (controller, id) = controller();
+
+        let response = controller.respond(
+            &command(MavCmd::RequestMessage, id as f32, 0.0),
+            CONTROLLER_ID,
+        );
+        assert!(matches!(ack_result(&response), MavResult::Accepted));
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(response.messages[1].id(), id);
+
+        let response =
+            controller.respond(&command(MavCmd::RequestMessage, 0.0, 0.0), CONTROLLER_ID);
+        assert!(matches!(ack_result(&response), MavResult::Unsupported));
+        assert_eq!(response.messages.len(), 1);
+    }
+
+    #[test]
+    fn scheduler_follows_intervals() {
+        let (controller, id) = controller();
+        let mut scheduler = StreamScheduler::default();
+        let start = Instant::now();
+
+        let (messages, next) = scheduler.poll(&controller, start);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(next, Some(start + Duration::from_secs(1)));
+
+        let (messages, _) = scheduler.poll(&controller, start + Duration::from_millis(500));
+        assert!(messages.is_empty());
+
+        // Upsampled stream is sent immediately and rescheduled
+        controller
+            .set_interval(id, Some(Duration::from_millis(100)))
+            .unwrap();
+        let now = start + Duration::from_millis(600);
+        let (messages, next) = scheduler.poll(&controller, now);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(next, Some(now + Duration::from_millis(100)));
+
+        // Missed ticks are skipped
+        let now = start + Duration::from_millis(1000);
+        let (messages, next) = scheduler.poll(&controller, now);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(next, Some(now + Duration::from_millis(100)));
+
+        controller.set_interval(id, None).unwrap();
+        let (messages, next) = scheduler.poll(&controller, now + Duration::from_secs(1));
+        assert!(messages.is_empty());
+        assert!(next.is_none());
+    }
+}
@@ -0,0 +1,17 @@
+//! # Message interval protocol
+//!
+//! Implements the responder side of the MAVLink
+//! [message interval](https://mavlink.io/en/services/message_interval.html) protocol.
+//!
+//! [`StreamRateController`] holds periodic outgoing messages (streams) produced by user-defined
+//! generators. Once attached to an edge node, it emits each stream at its current rate and answers
+//! `SET_MESSAGE_INTERVAL`, `GET_MESSAGE_INTERVAL`, and `REQUEST_MESSAGE` commands automatically.
+//! Rates changed by peers are reported to node subscribers as [`IntervalChanged`] custom events.
+//!
+//! Command messages are defined in the `common` dialect, which is enabled by
+//! `msrv-utils-streams` feature.
+
+mod controller;
+
+pub(crate) use controller::StreamService;
+pub use controller::{IntervalChanged, StreamRateController};
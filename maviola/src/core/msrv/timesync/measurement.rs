@@ -0,0 +1,245 @@
+use std::time::{Duration, Instant};
+
+use crate::core::utils::decode_message;
+use crate::dialects::common::messages::Timesync;
+use crate::error::TimesyncError;
+
+use crate::core::msrv::timesync::{unix_nanos, TimesyncSettings};
+use crate::prelude::*;
+
+/// Action requested by a [`LatencyMeasurement`].
+#[derive(Debug)]
+pub enum TimesyncStep {
+    /// Nothing to send, wait for the next frame or [`LatencyMeasurement::deadline`].
+    Wait,
+    /// Send a `TIMESYNC` request to the peer and continue.
+    Send(Timesync),
+    /// Measurement is finished.
+    Finished(Result<TimesyncStats>),
+}
+
+/// Result of a single `TIMESYNC` exchange.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimesyncSample {
+    rtt: Duration,
+    offset: i64,
+}
+
+/// Statistics of a latency measurement.
+///
+/// Contains at least one sample.
+#[derive(Clone, Debug)]
+pub struct TimesyncStats {
+    samples: Vec<TimesyncSample>,
+    lost: usize,
+}
+
+/// Latency measurement state machine.
+///
+/// Sends a series of `TIMESYNC` requests to a peer and matches responses by their `ts1` field.
+/// Each answered request produces a [`TimesyncSample`] with round-trip time and clock offset.
+/// Requests, that were not answered within [`TimesyncSettings::timeout`], are counted as lost.
+///
+/// Measurement does not perform any I/O. The caller should send requests returned by
+/// [`TimesyncStep::Send`], pass all incoming frames to [`LatencyMeasurement::handle`], and call
+/// [`LatencyMeasurement::check`] once [`LatencyMeasurement::deadline`] is reached. Methods should
+/// not be called after measurement is finished.
+#[derive(Debug)]
+pub struct LatencyMeasurement {
+    settings: TimesyncSettings,
+    base: (Instant, i64),
+    pending: Option<(i64, Instant)>,
+    next_request: Instant,
+    sent: usize,
+    samples: Vec<TimesyncSample>,
+    lost: usize,
+}
+
+impl TimesyncSample {
+    /// Round-trip time of the exchange.
+    pub fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
+    /// Clock offset of the peer relative to the local clock in nanoseconds.
+    ///
+    /// Positive offset means that peer clock is ahead.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+}
+
+impl TimesyncStats {
+    /// Samples of answered exchanges in the order of requests.
+    pub fn samples(&self) -> &[TimesyncSample] {
+        self.samples.as_slice()
+    }
+
+    /// Number of requests, that were not answered.
+    pub fn lost(&self) -> usize {
+        self.lost
+    }
+
+    /// Minimum round-trip time.
+    pub fn rtt_min(&self) -> Duration {
+        self.samples
+            .iter()
+            .map(|sample| sample.rtt)
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Maximum round-trip time.
+    pub fn rtt_max(&self) -> Duration {
+        self.samples
+            .iter()
+            .map(|sample| sample.rtt)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Mean round-trip time.
+    pub fn rtt_mean(&self) -> Duration {
+        let total: Duration = self.samples.iter().map(|sample| sample.rtt).sum();
+        total / self.samples.len().max(1) as u32
+    }
+
+    /// Estimated clock offset of the peer in nanoseconds.
+    ///
+    /// Taken from the sample with the lowest round-trip time, since its offset is least affected by
+    /// asymmetric delays.
+    pub fn offset(&self) -> i64 {
+        self.samples
+            .iter()
+            .min_by_key(|sample| sample.rtt)
+            .map(|sample| sample.offset)
+            .unwrap_or_default()
+    }
+
+    /// Mean clock offset of the peer in nanoseconds.
+    pub fn offset_mean(&self) -> i64 {
+        let total: i128 = self
+            .samples
+            .iter()
+            .map(|sample| sample.offset as i128)
+            .sum();
+        (total / self.samples.len().max(1) as i128) as i64
+    }
+}
+
+impl LatencyMeasurement {
+    /// Creates a latency measurement defined by `settings`.
+    pub fn new(settings: TimesyncSettings) -> Self {
+        let now = Instant::now();
+        Self {
+            settings,
+            base: (now, unix_nanos()),
+            pending: None,
+            next_request: now,
+            sent: 0,
+            samples: Vec::new(),
+            lost: 0,
+        }
+    }
+
+    /// Settings of the measurement.
+    pub fn settings(&self) -> &TimesyncSettings {
+        &self.settings
+    }
+
+    /// Starts the measurement by sending the first request.
+    pub fn start(&mut self, now: Instant) -> TimesyncStep {
+        self.request(now)
+    }
+
+    /// Handles incoming frame.
+    ///
+    /// Frames from other components, unrelated messages, requests, and responses to other
+    /// requests are ignored.
+    pub fn handle<V: MaybeVersioned>(&mut self, frame: &Frame<V>, now: Instant) -> TimesyncStep {
+        let (ts1, sent_at) = match self.pending {
+            Some(pending) => pending,
+            None => return TimesyncStep::Wait,
+        };
+        if !self.settings.is_peer(frame) {
+            return TimesyncStep::Wait;
+        }
+        let response = match decode_message::<Timesync, V>(frame) {
+            Some(response) if response.tc1 != 0 && response.ts1 == ts1 => response,
+            _ => return TimesyncStep::Wait,
+        };
+
+        let received = self.nanos(now);
+        self.pending = None;
+        self.samples.push(TimesyncSample {
+            rtt: now.saturating_duration_since(sent_at),
+            offset: response.tc1 - ((ts1 as i128 + received as i128) / 2) as i64,
+        });
+
+        self.check(now)
+    }
+
+    /// Checks whether the pending response has timed out and whether the next request is due.
+    ///
+    /// Once all requests are sent and answered or lost, finishes the measurement. Fails with
+    /// [`TimesyncError::Timeout`], if none of the requests were answered.
+    pub fn check(&mut self, now: Instant) -> TimesyncStep {
+        if let Some((_, sent_at)) = self.pending {
+            if now < sent_at + self.settings.timeout() {
+                return TimesyncStep::Wait;
+            }
+            self.pending = None;
+            self.lost += 1;
+        }
+
+        if self.sent >= self.settings.samples() {
+            return self.finish();
+        }
+        if now < self.next_request {
+            return TimesyncStep::Wait;
+        }
+        self.request(now)
+    }
+
+    /// Time, when [`LatencyMeasurement::check`] should be called, if no frames were received.
+    pub fn deadline(&self) -> Instant {
+        match self.pending {
+            Some((_, sent_at)) => sent_at + self.settings.timeout(),
+            None => self.next_request,
+        }
+    }
+
+    fn request(&mut self, now: Instant) -> TimesyncStep {
+        let ts1 = self.nanos(now).max(1);
+        self.pending = Some((ts1, now));
+        self.next_request = now + self.settings.interval();
+        self.sent += 1;
+
+        TimesyncStep::Send(Timesync {
+            tc1: 0,
+            ts1,
+            target_system: self.settings.peer().system,
+            target_component: self.settings.peer().component,
+        })
+    }
+
+    fn finish(&mut self) -> TimesyncStep {
+        if self.samples.is_empty() {
+            return TimesyncStep::Finished(Err(TimesyncError::Timeout.into()));
+        }
+        TimesyncStep::Finished(Ok(TimesyncStats {
+            samples: std::mem::take(&mut self.samples),
+            lost: self.lost,
+        }))
+    }
+
+    /// Converts `instant` to nanoseconds since Unix epoch.
+    fn nanos(&self, instant: Instant) -> i64 {
+        let (base_instant, base_nanos) = self.base;
+        let elapsed = match instant.checked_duration_since(base_instant) {
+            Some(elapsed) => elapsed.as_nanos() as i64,
+            None => -(base_instant.duration_since(instant).as_nanos() as i64),
+        };
+        base_nanos + elapsed
+    }
+}
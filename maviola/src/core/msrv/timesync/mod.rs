@@ -0,0 +1,205 @@
+//! # Time synchronisation protocol
+//!
+//! Implements MAVLink [time synchronisation](https://mavlink.io/en/services/timesync.html)
+//! protocol, that measures round-trip time and clock offset between two components.
+//!
+//! Active edge nodes answer `TIMESYNC` requests addressed to them automatically. Requests are
+//! answered with the current Unix time in nanoseconds as `tc1` and the original `ts1` of the
+//! request.
+//!
+//! Latency is measured by [`LatencyMeasurement`], an I/O-free state machine, that sends a series of
+//! requests to a peer defined by [`TimesyncSettings`] and collects [`TimesyncStats`]. Edge nodes
+//! provide `measure_latency` method, that drives this state machine over node connection.
+//!
+//! Clock offsets are meaningful only for peers, that use Unix time in nanoseconds for `TIMESYNC`,
+//! as Maviola nodes do. Round-trip time is measured with local monotonic clock and does not depend
+//! on the peer clock.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let node = Node::sync::<V2>()
+//!     .id(MavLinkId::new(255, 190))
+//!     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+//!     .build().unwrap();
+//!
+//! let stats = node.measure_latency(MavLinkId::new(1, 1)).unwrap();
+//! println!(
+//!     "rtt: {:?}..{:?}, offset: {} ns, lost: {}",
+//!     stats.rtt_min(),
+//!     stats.rtt_max(),
+//!     stats.offset(),
+//!     stats.lost()
+//! );
+//! ```
+
+mod measurement;
+mod responder;
+mod settings;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub use measurement::{LatencyMeasurement, TimesyncSample, TimesyncStats, TimesyncStep};
+pub(crate) use responder::TimesyncResponder;
+pub use settings::TimesyncSettings;
+
+/// Current Unix time in nanoseconds.
+fn unix_nanos() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as i64)
+        .unwrap_or_default()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod timesync_tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    use crate::dialects::common::messages::Timesync;
+    use crate::error::TimesyncError;
+    use crate::protocol::Endpoint;
+
+    use crate::prelude::*;
+
+    const LOCAL_ID: MavLinkId = MavLinkId {
+        system: 255,
+        component: 190,
+    };
+    const PEER_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn frame(id: MavLinkId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(id).next_frame(message).unwrap()
+    }
+
+    fn sent(step: TimesyncStep) -> Timesync {
+        match step {
+            TimesyncStep::Send(request) => request,
+            _ => panic!("request expected"),
+        }
+    }
+
+    fn response(request: &Timesync, tc1: i64) -> Frame<V2> {
+        frame(
+            PEER_ID,
+            &Timesync {
+                tc1,
+                ts1: request.ts1,
+                target_system: LOCAL_ID.system,
+                target_component: LOCAL_ID.component,
+            },
+        )
+    }
+
+    #[test]
+    fn responder_answers_addressed_requests() {
+        let request = Timesync {
+            tc1: 0,
+            ts1: 42,
+            target_system: PEER_ID.system,
+            target_component: 0,
+        };
+
+        let reply = TimesyncResponder::respond(&frame(LOCAL_ID, &request), PEER_ID).unwrap();
+        assert!(reply.tc1 > 0);
+        assert_eq!(reply.ts1, 42);
+        assert_eq!(reply.target_system, LOCAL_ID.system);
+        assert_eq!(reply.target_component, LOCAL_ID.component);
+
+        // Responses and requests to other systems are ignored
+        let other = MavLinkId::new(2, 1);
+        assert!(TimesyncResponder::respond(&frame(LOCAL_ID, &request), other).is_none());
+        assert!(TimesyncResponder::respond(&frame(PEER_ID, &reply), LOCAL_ID).is_none());
+    }
+
+    #[test]
+    fn measurement_collects_samples() {
+        let settings = TimesyncSettings::new(PEER_ID)
+            .with_samples(2)
+            .with_interval(Duration::from_millis(100));
+        let start = Instant::now();
+        let mut measurement = LatencyMeasurement::new(settings);
+
+        let request = sent(measurement.start(start));
+        assert_eq!(request.tc1, 0);
+        assert_eq!(request.target_system, PEER_ID.system);
+
+        // Responses to unknown requests and from other systems are ignored
+        let mut unrelated = request.clone();
+        unrelated.ts1 += 1;
+        let now = start + Duration::from_millis(10);
+        assert!(matches!(
+            measurement.handle(&response(&unrelated, request.ts1), now),
+            TimesyncStep::Wait
+        ));
+        let mut reply = request.clone();
+        reply.tc1 = request.ts1;
+        let foreign = frame(MavLinkId::new(2, 1), &reply);
+        assert!(matches!(
+            measurement.handle(&foreign, now),
+            TimesyncStep::Wait
+        ));
+
+        // Peer clock is 1 ms ahead, round trip takes 20 ms
+        let now = start + Duration::from_millis(20);
+        let peer_time = request.ts1 + 10_000_000 + 1_000_000;
+        assert!(matches!(
+            measurement.handle(&response(&request, peer_time), now),
+            TimesyncStep::Wait
+        ));
+        assert_eq!(measurement.deadline(), start + Duration::from_millis(100));
+
+        let now = start + Duration::from_millis(100);
+        let request = sent(measurement.check(now));
+        let now = now + Duration::from_millis(40);
+        let peer_time = request.ts1 + 20_000_000 + 1_000_000;
+        let stats = match measurement.handle(&response(&request, peer_time), now) {
+            TimesyncStep::Finished(Ok(stats)) => stats,
+            _ => panic!("statistics expected"),
+        };
+
+        assert_eq!(stats.samples().len(), 2);
+        assert_eq!(stats.lost(), 0);
+        assert_eq!(stats.rtt_min(), Duration::from_millis(20));
+        assert_eq!(stats.rtt_max(), Duration::from_millis(40));
+        assert_eq!(stats.rtt_mean(), Duration::from_millis(30));
+        assert_eq!(stats.offset(), 1_000_000);
+        assert_eq!(stats.offset_mean(), 1_000_000);
+    }
+
+    #[test]
+    fn lost_samples() {
+        let settings = TimesyncSettings::new(PEER_ID)
+            .with_samples(2)
+            .with_interval(Duration::from_millis(10))
+            .with_timeout(Duration::from_millis(50));
+        let start = Instant::now();
+        let mut measurement = LatencyMeasurement::new(settings);
+
+        sent(measurement.start(start));
+        assert_eq!(measurement.deadline(), start + Duration::from_millis(50));
+        assert!(matches!(
+            measurement.check(start + Duration::from_millis(20)),
+            TimesyncStep::Wait
+        ));
+
+        let now = start + Duration::from_millis(50);
+        sent(measurement.check(now));
+        let result = measurement.check(now + Duration::from_millis(50));
+        assert!(matches!(
+            result,
+            TimesyncStep::Finished(Err(Error::Timesync(TimesyncError::Timeout)))
+        ));
+    }
+}
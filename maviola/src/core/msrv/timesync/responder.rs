@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use crate::core::msrv::registry::{Microservice, ServiceOutput};
+use crate::core::utils::{decode_message, Guarded, SharedCloser, Switch};
+use crate::dialects::common::messages::Timesync;
+use crate::protocol::MessageId;
+
+use crate::core::msrv::timesync::unix_nanos;
+use crate::prelude::*;
+
+/// Messages handled by timesync responder.
+const TIMESYNC_REQUESTS: [MessageId; 1] = [Timesync::message_id()];
+
+/// Answers `TIMESYNC` requests addressed to a node while it is active.
+///
+/// Requests are answered with the current Unix time in nanoseconds as `tc1` and the original `ts1`
+/// of the request.
+pub(crate) struct TimesyncResponder {
+    is_active: Guarded<SharedCloser, Switch>,
+}
+
+impl TimesyncResponder {
+    /// Service name, that is used to make sure, that responder is registered once.
+    pub(crate) const NAME: &'static str = "timesync responder";
+
+    pub(crate) fn new(is_active: Guarded<SharedCloser, Switch>) -> Self {
+        Self { is_active }
+    }
+
+    /// Creates a response to a `TIMESYNC` request addressed to a node with specified `id`.
+    ///
+    /// Returns [`None`] for responses and requests addressed to other nodes.
+    pub(crate) fn respond<V: MaybeVersioned>(frame: &Frame<V>, id: MavLinkId) -> Option<Timesync> {
+        let request = decode_message::<Timesync, V>(frame)?;
+
+        let is_addressed = request.target_system == 0
+            || (request.target_system == id.system
+                && (request.target_component == 0 || request.target_component == id.component));
+        if request.tc1 != 0 || !is_addressed {
+            return None;
+        }
+
+        Some(Timesync {
+            tc1: unix_nanos(),
+            ts1: request.ts1,
+            target_system: frame.system_id(),
+            target_component: frame.component_id(),
+        })
+    }
+}
+
+impl<V: MaybeVersioned> Microservice<V> for TimesyncResponder {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn message_ids(&self) -> &'static [MessageId] {
+        &TIMESYNC_REQUESTS
+    }
+
+    fn handle(&mut self, frame: &Frame<V>, id: MavLinkId, output: &mut ServiceOutput) {
+        if !self.is_active.is() {
+            return;
+        }
+        if let Some(response) = Self::respond(frame, id) {
+            output.messages.push(Box::new(response));
+        }
+    }
+
+    fn poll(&mut self, _: Instant, _: &mut ServiceOutput) -> Option<Instant> {
+        None
+    }
+}
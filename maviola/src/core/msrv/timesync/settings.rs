@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use crate::core::consts::{
+    DEFAULT_TIMESYNC_INTERVAL, DEFAULT_TIMESYNC_SAMPLES, DEFAULT_TIMESYNC_TIMEOUT,
+};
+
+use crate::prelude::*;
+
+/// Settings of a latency measurement.
+///
+/// Defines a peer component, that answers `TIMESYNC` requests, the number of request/response
+/// exchanges, and how long to wait for each response.
+#[derive(Clone, Copy, Debug)]
+pub struct TimesyncSettings {
+    peer: MavLinkId,
+    samples: usize,
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl TimesyncSettings {
+    /// Creates settings for a latency measurement with a `peer`.
+    ///
+    /// Peer component `ID` `0` accepts responses from any component of the peer system. Uses
+    /// [`DEFAULT_TIMESYNC_SAMPLES`], [`DEFAULT_TIMESYNC_INTERVAL`], and
+    /// [`DEFAULT_TIMESYNC_TIMEOUT`].
+    pub fn new(peer: MavLinkId) -> Self {
+        Self {
+            peer,
+            samples: DEFAULT_TIMESYNC_SAMPLES,
+            interval: DEFAULT_TIMESYNC_INTERVAL,
+            timeout: DEFAULT_TIMESYNC_TIMEOUT,
+        }
+    }
+
+    /// Sets the number of `TIMESYNC` exchanges.
+    ///
+    /// At least one exchange is always performed.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Sets the interval between consecutive `TIMESYNC` requests.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets time to wait for a response before the sample is considered lost.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Peer component.
+    pub fn peer(&self) -> MavLinkId {
+        self.peer
+    }
+
+    /// Number of `TIMESYNC` exchanges.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Interval between consecutive `TIMESYNC` requests.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Time to wait for a response.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns `true`, if `frame` is sent by the peer.
+    pub(super) fn is_peer<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        frame.system_id() == self.peer.system
+            && (self.peer.component == 0 || frame.component_id() == self.peer.component)
+    }
+}
+
+impl From<MavLinkId> for TimesyncSettings {
+    fn from(peer: MavLinkId) -> Self {
+        Self::new(peer)
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::prelude::*;
+
+/// Egress rate limit of a network connection.
+///
+/// Limits the amount of bytes and frames per second, that the network sends to a particular
+/// connection. This prevents slow links, like telemetry radios, from being flooded by frames
+/// received from fast connections. Both limits allow bursts up to the specified size, and frames
+/// exceeding any of them are dropped.
+///
+/// Clones of the limit share counters of sent and dropped frames. Keep a clone to observe them
+/// while the network is running. Attach limit with
+/// [`ConnectionOptions::limit`](crate::core::network::ConnectionOptions::limit).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::BandwidthLimit;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// // 57600 baud radio link has roughly 5 KiB/s of throughput
+/// let radio_limit = BandwidthLimit::new()
+///     .with_bytes_per_sec(5000.0, 1000)
+///     .with_frames_per_sec(100.0, 20);
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .add_connection(TcpClient::new("127.0.0.1:14550").unwrap())
+///             .limit(radio_limit.clone())
+///     )
+///     .build().unwrap();
+///
+/// println!("dropped frames: {}", radio_limit.dropped_frames());
+/// ```
+#[derive(Clone, Default)]
+pub struct BandwidthLimit {
+    bytes: Option<Rate>,
+    frames: Option<Rate>,
+    counters: Arc<Counters>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rate {
+    per_sec: f64,
+    burst: f64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    sent_frames: AtomicU64,
+    sent_bytes: AtomicU64,
+    dropped_frames: AtomicU64,
+    dropped_bytes: AtomicU64,
+}
+
+impl BandwidthLimit {
+    /// Creates a limit, that allows any traffic.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits outgoing traffic to `rate` bytes per second with bursts of up to `burst` bytes.
+    ///
+    /// Burst is never less than the size of the largest MAVLink frame.
+    pub fn with_bytes_per_sec(mut self, rate: f64, burst: usize) -> Self {
+        self.bytes = Some(Rate {
+            per_sec: rate,
+            burst: burst.max(MAX_FRAME_SIZE) as f64,
+        });
+        self
+    }
+
+    /// Limits outgoing traffic to `rate` frames per second with bursts of up to `burst` frames.
+    ///
+    /// Burst is never less than one frame.
+    pub fn with_frames_per_sec(mut self, rate: f64, burst: usize) -> Self {
+        self.frames = Some(Rate {
+            per_sec: rate,
+            burst: burst.max(1) as f64,
+        });
+        self
+    }
+
+    /// Number of frames sent to the connection.
+    pub fn sent_frames(&self) -> u64 {
+        self.counters.sent_frames.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes sent to the connection.
+    pub fn sent_bytes(&self) -> u64 {
+        self.counters.sent_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Number of frames dropped due to exceeded limits.
+    pub fn dropped_frames(&self) -> u64 {
+        self.counters.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes in frames dropped due to exceeded limits.
+    pub fn dropped_bytes(&self) -> u64 {
+        self.counters.dropped_bytes.load(Ordering::Relaxed)
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that applies this limit to outgoing frames.
+    pub(crate) fn tracker(&self) -> BandwidthTracker {
+        BandwidthTracker {
+            bytes: self.bytes.map(TokenBucket::new),
+            frames: self.frames.map(TokenBucket::new),
+            limit: self.clone(),
+        }
+    }
+}
+
+impl Debug for BandwidthLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BandwidthLimit")
+            .field("bytes", &self.bytes)
+            .field("frames", &self.frames)
+            .field("dropped_frames", &self.dropped_frames())
+            .finish_non_exhaustive()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Keeps track of outgoing traffic for [`BandwidthLimit`].
+pub(crate) struct BandwidthTracker {
+    limit: BandwidthLimit,
+    bytes: Option<TokenBucket>,
+    frames: Option<TokenBucket>,
+}
+
+struct TokenBucket {
+    rate: Rate,
+    tokens: f64,
+    updated_at: Option<Instant>,
+}
+
+impl BandwidthTracker {
+    /// Returns `true` if frame can be sent at `now` and updates counters.
+    pub(crate) fn allow<V: MaybeVersioned>(&mut self, frame: &Frame<V>, now: Instant) -> bool {
+        let size = frame.header().size() + frame.body_length();
+
+        let allowed = [(&mut self.bytes, size as f64), (&mut self.frames, 1.0)]
+            .into_iter()
+            .all(|(bucket, amount)| match bucket {
+                Some(bucket) => bucket.has(amount, now),
+                None => true,
+            });
+
+        let counters = &self.limit.counters;
+        if allowed {
+            if let Some(bucket) = &mut self.bytes {
+                bucket.take(size as f64);
+            }
+            if let Some(bucket) = &mut self.frames {
+                bucket.take(1.0);
+            }
+            counters.sent_frames.fetch_add(1, Ordering::Relaxed);
+            counters
+                .sent_bytes
+                .fetch_add(size as u64, Ordering::Relaxed);
+        } else {
+            counters.dropped_frames.fetch_add(1, Ordering::Relaxed);
+            counters
+                .dropped_bytes
+                .fetch_add(size as u64, Ordering::Relaxed);
+        }
+
+        allowed
+    }
+}
+
+impl TokenBucket {
+    fn new(rate: Rate) -> Self {
+        Self {
+            rate,
+            tokens: rate.burst,
+            updated_at: None,
+        }
+    }
+
+    /// Refills bucket at `now` and returns `true`, if it has enough tokens for `amount`.
+    fn has(&mut self, amount: f64, now: Instant) -> bool {
+        if let Some(updated_at) = self.updated_at {
+            let elapsed = now.saturating_duration_since(updated_at).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.rate.per_sec).min(self.rate.burst);
+        }
+        self.updated_at = Some(now);
+
+        self.tokens >= amount
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+}
+
+/// Size of the largest `MAVLink 2` frame: header, payload, checksum, and signature.
+const MAX_FRAME_SIZE: usize = 10 + 255 + 2 + 13;
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    fn heartbeat() -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn frames_per_sec_are_limited() {
+        let limit = BandwidthLimit::new().with_frames_per_sec(10.0, 2);
+        let mut tracker = limit.tracker();
+        let frame = heartbeat();
+        let start = Instant::now();
+
+        assert!(tracker.allow(&frame, start));
+        assert!(tracker.allow(&frame, start));
+        assert!(!tracker.allow(&frame, start));
+
+        assert!(tracker.allow(&frame, start + Duration::from_millis(100)));
+        assert!(!tracker.allow(&frame, start + Duration::from_millis(150)));
+
+        assert_eq!(limit.sent_frames(), 3);
+        assert_eq!(limit.dropped_frames(), 2);
+    }
+
+    #[test]
+    fn bytes_per_sec_are_limited() {
+        let frame = heartbeat();
+        let size = frame.header().size() + frame.body_length();
+
+        let limit = BandwidthLimit::new().with_bytes_per_sec(size as f64, MAX_FRAME_SIZE);
+        let mut tracker = limit.tracker();
+        let start = Instant::now();
+
+        let burst = MAX_FRAME_SIZE / size;
+        for _ in 0..burst {
+            assert!(tracker.allow(&frame, start));
+        }
+        assert!(!tracker.allow(&frame, start));
+        assert!(tracker.allow(&frame, start + Duration::from_secs(1)));
+
+        assert_eq!(limit.sent_bytes(), ((burst + 1) * size) as u64);
+        assert_eq!(limit.dropped_bytes(), size as u64);
+    }
+}
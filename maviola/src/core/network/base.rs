@@ -1,11 +1,18 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::time::Duration;
 
-use crate::core::io::{ConnectionConf, ConnectionInfo, RetryStrategy};
-use crate::core::marker::{MaybeConnConf, NodeKind, Proxy};
+use crate::core::io::{ConnectionConf, ConnectionDetails, ConnectionInfo, RetryStrategy};
+use crate::core::marker::{HasConnConf, MaybeConnConf, NodeKind, Proxy};
+use crate::core::network::{
+    ConnectionOptions, NetworkHandle, NetworkInjectors, NetworkTap, NodeOptions, RoutingMode,
+    RoutingTable,
+};
 use crate::core::node::{IntoNodeConf, NodeConf};
 use crate::core::utils::UniqueId;
+use crate::error::{ConfigDiagnostic, ConfigError};
+use crate::protocol::MessageId;
 
 use crate::prelude::*;
 
@@ -16,6 +23,16 @@ use crate::prelude::*;
 /// receives a message from one of its clients, then this message will be forwarded to all other
 /// clients of this server and all other nodes.
 ///
+/// Addressed frames can be delivered only to connections, where their targets were seen. See
+/// [`Network::routing`] and [`RoutingMode::TargetAware`].
+///
+/// Frames passing through the network can be captured with `tap`, and frames can be sent to a
+/// particular connection with `injector`. See [`TappedFrame`] for details.
+///
+/// Connections can be added to or removed from a running network with a [`NetworkHandle`].
+///
+/// [`TappedFrame`]: crate::core::network::TappedFrame
+///
 /// # Examples
 ///
 /// Create a synchronous node with a network containing two TCP servers:
@@ -81,6 +98,14 @@ use crate::prelude::*;
 pub struct Network<V: MaybeVersioned, C: MaybeConnConf> {
     pub(crate) info: ConnectionInfo,
     pub(crate) nodes: HashMap<UniqueId, NodeConf<Proxy, V, C>>,
+    pub(crate) options: HashMap<UniqueId, NodeOptions>,
+    pub(crate) max_frame_ages: HashMap<MessageId, Duration>,
+    pub(crate) dedup: Option<Duration>,
+    pub(crate) routing: RoutingMode,
+    pub(crate) routing_table: RoutingTable,
+    pub(crate) tap: NetworkTap<V>,
+    pub(crate) injectors: NetworkInjectors<V>,
+    pub(crate) handle: NetworkHandle<V, C>,
     pub(crate) retry: RetryStrategy,
     pub(crate) stop_on_node_down: bool,
     pub(crate) _version: PhantomData<V>,
@@ -89,19 +114,72 @@ pub struct Network<V: MaybeVersioned, C: MaybeConnConf> {
 impl<V: MaybeVersioned, C: MaybeConnConf> Network<V, C> {
     /// Adds node configuration to the network.
     ///
-    /// Accepts anything that can be converted into a [`NodeConf`].
+    /// Accepts anything that can be converted into a [`NodeConf`]. Returns [`ConnectionOptions`],
+    /// that configure how the network treats this node.
     ///
     /// Make sure, that node configuration was built with the protocol version, that matches target
     /// node. In particular, do not forget to set [`NodeBuilder::version`], if you are using a
     /// versioned node.
     ///
     /// [`NodeBuilder::version`]: crate::core::node::NodeBuilder::version
-    pub fn add_node<K: NodeKind>(mut self, node: impl IntoNodeConf<K, V, C>) -> Self {
-        let node = node.into_node_conf().into_proxy();
-        self.nodes.insert(UniqueId::new(), node);
+    pub fn add_node<K: NodeKind>(
+        mut self,
+        node: impl IntoNodeConf<K, V, C>,
+    ) -> ConnectionOptions<V, C> {
+        let mut node = node.into_node_conf().into_proxy();
+        // Channel events of network nodes are relayed to the network connection
+        node.channel_events = true;
+
+        let id = UniqueId::new();
+        self.nodes.insert(id, node);
+        ConnectionOptions::new(self, id)
+    }
+
+    /// Sets the maximum age of outgoing frames with specified `message_id`.
+    ///
+    /// Frames older than `max_age` are considered stale and are not sent to the network nodes.
+    /// Frames forwarded by callbacks are aged since the original frame was received (see
+    /// [`OutgoingFrame::age`]), so this limits the time a frame may spend inside a congested
+    /// router. This is useful for messages like setpoints, that are harmful when delivered late.
+    ///
+    /// Frames with other message `ID`s are never dropped due to their age.
+    ///
+    /// [`OutgoingFrame::age`]: crate::core::io::OutgoingFrame::age
+    pub fn max_frame_age(mut self, message_id: MessageId, max_age: Duration) -> Self {
+        self.max_frame_ages.insert(message_id, max_age);
+        self
+    }
+
+    /// Drops duplicate incoming frames received within a sliding `window`.
+    ///
+    /// When the same system is reachable over several connections (for example, a telemetry radio
+    /// and Wi-Fi), each of its frames is received once per link. With deduplication enabled, a
+    /// frame with the same system `ID`, component `ID`, sequence, and checksum as a frame received
+    /// by any network connection less than `window` ago is dropped.
+    ///
+    /// Deduplication is disabled by default. The window should be shorter than the time it takes
+    /// a system to wrap its sequence counter around.
+    pub fn dedup(mut self, window: Duration) -> Self {
+        self.dedup = Some(window);
+        self
+    }
+
+    /// Defines, how frames are routed between network connections.
+    ///
+    /// By default, frames are broadcast to all connections. With [`RoutingMode::TargetAware`],
+    /// addressed frames are sent only to connections, where their targets were seen.
+    pub fn routing(mut self, mode: RoutingMode) -> Self {
+        self.routing = mode;
         self
     }
 
+    /// Table of routes learned by the network.
+    ///
+    /// Returned table is shared with the network, see [`RoutingTable`] for details.
+    pub fn routing_table(&self) -> RoutingTable {
+        self.routing_table.clone()
+    }
+
     /// Defines retry strategy for a network.
     ///
     /// When node goes down and it [`NodeConf::is_repairable`], then network will attempt to restore
@@ -124,8 +202,52 @@ impl<V: MaybeVersioned, C: MaybeConnConf> Network<V, C> {
     }
 }
 
+impl<V: MaybeVersioned, C: HasConnConf> Network<V, C> {
+    /// Validates network configuration.
+    ///
+    /// Checks, that connections do not share the same endpoint, and validates configurations of
+    /// all nodes. Returns [`Error::Config`] with all found problems, if configuration is
+    /// conflicting. Networks are validated together with the node, that uses them as a connection.
+    pub fn validate(&self) -> Result<()> {
+        Ok(ConfigError::check(self.diagnostics())?)
+    }
+
+    pub(crate) fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut endpoints: Vec<&ConnectionDetails> = Vec::new();
+
+        for node in self.nodes.values() {
+            let details = node.connection_conf.info().details();
+            if has_exclusive_endpoint(details) {
+                if !endpoints.contains(&details) {
+                    endpoints.push(details);
+                } else {
+                    let diagnostic = ConfigDiagnostic::DuplicateEndpoint(details.clone());
+                    if !diagnostics.contains(&diagnostic) {
+                        diagnostics.push(diagnostic);
+                    }
+                }
+            }
+
+            diagnostics.extend(node.diagnostics());
+        }
+
+        diagnostics
+    }
+}
+
 impl<V: MaybeVersioned, C: MaybeConnConf> ConnectionConf for Network<V, C> {
     fn info(&self) -> &ConnectionInfo {
         &self.info
     }
 }
+
+/// Returns `true` if two connections with the same details will conflict with each other.
+fn has_exclusive_endpoint(details: &ConnectionDetails) -> bool {
+    match details {
+        ConnectionDetails::Network => false,
+        #[cfg(feature = "unstable")]
+        ConnectionDetails::Custom { .. } => false,
+        _ => true,
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::error::{BridgeError, FrameError};
+use crate::protocol::{CrcExtra, Dialect, MavLinkVersion, MessageId, Payload};
+
+use crate::prelude::*;
+
+/// Largest message `ID` supported by `MAVLink 1`.
+const MESSAGE_ID_V1_MAX: MessageId = 255;
+
+/// Converts frames sent by a network to a connection into a particular MAVLink protocol version.
+///
+/// Bridges allow to connect `MAVLink 1` and `MAVLink 2` devices within the same versionless
+/// [`Network`]. Each bridged connection receives frames only in its own protocol version:
+///
+/// * `MAVLink 1` frames are upgraded to `MAVLink 2` using `CRC_EXTRA` of the message.
+/// * `MAVLink 2` frames are downgraded to `MAVLink 1`, when possible. Frames with message `ID`s
+///   above `255`, signed frames, and messages with non-empty extension fields (as defined by the
+///   dialect) can't be represented in `MAVLink 1` and are rejected.
+///
+/// Messages are looked up in the dialect `D` specified upon creation. Frames of unknown messages
+/// and frames with invalid checksums are rejected as well. Rejected frames are logged and counted
+/// by [`VersionBridge::rejected`]. Clones of a bridge share counters.
+///
+/// Attach bridge with
+/// [`ConnectionOptions::bridge`](crate::core::network::ConnectionOptions::bridge). Network has to
+/// be [`Versionless`] in order to carry frames of both protocol versions.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::VersionBridge;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let legacy = VersionBridge::v1::<DefaultDialect>();
+///
+/// let node = Node::sync::<Versionless>()
+///     .connection(
+///         Network::sync()
+///             // Modern devices receive only `MAVLink 2` frames
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .bridge(VersionBridge::v2::<DefaultDialect>())
+///             // Legacy devices receive only `MAVLink 1` frames
+///             .add_connection(TcpServer::new("127.0.0.1:5601").unwrap())
+///             .bridge(legacy.clone())
+///     )
+///     .build().unwrap();
+///
+/// println!("rejected frames: {}", legacy.rejected());
+/// ```
+#[derive(Clone)]
+pub struct VersionBridge {
+    version: MavLinkVersion,
+    dialect: &'static str,
+    crc_extra: fn(MessageId) -> Option<CrcExtra>,
+    downgrade: fn(&Payload) -> Result<Payload>,
+    counters: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    converted: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl VersionBridge {
+    /// Creates a bridge, that converts frames to `MAVLink 1` using messages of dialect `D`.
+    pub fn v1<D: Dialect>() -> Self {
+        Self::new::<D>(MavLinkVersion::V1)
+    }
+
+    /// Creates a bridge, that converts frames to `MAVLink 2` using messages of dialect `D`.
+    pub fn v2<D: Dialect>() -> Self {
+        Self::new::<D>(MavLinkVersion::V2)
+    }
+
+    fn new<D: Dialect>(version: MavLinkVersion) -> Self {
+        Self {
+            version,
+            dialect: D::name(),
+            crc_extra: crc_extra::<D>,
+            downgrade: downgrade::<D>,
+            counters: Default::default(),
+        }
+    }
+
+    /// Target protocol version.
+    pub fn version(&self) -> MavLinkVersion {
+        self.version
+    }
+
+    /// Number of frames converted to the target protocol version.
+    pub fn converted(&self) -> u64 {
+        self.counters.converted.load(Ordering::Relaxed)
+    }
+
+    /// Number of frames, that can't be converted to the target protocol version.
+    pub fn rejected(&self) -> u64 {
+        self.counters.rejected.load(Ordering::Relaxed)
+    }
+
+    /// Converts a frame into the target protocol version.
+    ///
+    /// Frames, that already have the target version, are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`BridgeError`] if frame can't be represented in `MAVLink 1`.
+    /// * Returns [`FrameError::NotInDialect`] if message is unknown to the bridge dialect.
+    /// * Returns [`FrameError::Checksum`] if frame has invalid checksum.
+    /// * Returns [`FrameError::Version`] if converted frame doesn't match the version `V`.
+    pub fn convert<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Result<Frame<V>> {
+        if frame.version() == self.version {
+            return Ok(frame.clone());
+        }
+
+        let result = self.rebuild(frame);
+        match &result {
+            Ok(_) => self.counters.converted.fetch_add(1, Ordering::Relaxed),
+            Err(_) => self.counters.rejected.fetch_add(1, Ordering::Relaxed),
+        };
+        result
+    }
+
+    fn rebuild<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Result<Frame<V>> {
+        let message_id = frame.message_id();
+
+        if self.version == MavLinkVersion::V1 {
+            if message_id > MESSAGE_ID_V1_MAX {
+                return Err(BridgeError::MessageId(message_id).into());
+            }
+            if frame.signature().is_some() {
+                return Err(BridgeError::Signed(message_id).into());
+            }
+        }
+
+        let crc_extra = match (self.crc_extra)(message_id) {
+            Some(crc_extra) => crc_extra,
+            None => return Err(FrameError::NotInDialect(message_id).into()),
+        };
+        frame.validate_checksum_with_crc_extra(crc_extra)?;
+
+        let builder = Frame::builder()
+            .sequence(frame.sequence())
+            .system_id(frame.system_id())
+            .component_id(frame.component_id())
+            .message_id(message_id);
+
+        let converted = match self.version {
+            MavLinkVersion::V1 => {
+                let payload = (self.downgrade)(frame.payload())?;
+                builder
+                    .version(V1)
+                    .payload(payload.bytes())
+                    .crc_extra(crc_extra)
+                    .build()
+                    .into_versionless()
+            }
+            MavLinkVersion::V2 => builder
+                .version(V2)
+                .payload(frame.payload().bytes())
+                .crc_extra(crc_extra)
+                .build()
+                .into_versionless(),
+        };
+
+        Ok(converted.try_into_versioned()?)
+    }
+}
+
+impl Debug for VersionBridge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VersionBridge")
+            .field("version", &self.version)
+            .field("dialect", &self.dialect)
+            .field("rejected", &self.rejected())
+            .finish_non_exhaustive()
+    }
+}
+
+fn crc_extra<D: Dialect>(message_id: MessageId) -> Option<CrcExtra> {
+    D::message_info(message_id)
+        .ok()
+        .map(|info| info.crc_extra())
+}
+
+/// Re-encodes `MAVLink 2` payload as `MAVLink 1`, unless it has non-empty extension fields.
+fn downgrade<D: Dialect>(payload: &Payload) -> Result<Payload> {
+    let message = D::decode(payload)?;
+    let downgraded = message.encode(MavLinkVersion::V1)?;
+
+    let restored = D::decode(&downgraded)?.encode(MavLinkVersion::V2)?;
+    if restored.bytes() != message.encode(MavLinkVersion::V2)?.bytes() {
+        return Err(BridgeError::Extensions(payload.id()).into());
+    }
+
+    Ok(downgraded)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod bridge_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::dialects::Minimal;
+    use crate::error::Error;
+    use crate::protocol::{Endpoint, IntoPayload, Message};
+
+    fn frame_v1(message: &impl Message) -> Frame<Versionless> {
+        Endpoint::v1(MavLinkId::new(1, 1))
+            .next_frame(message)
+            .unwrap()
+            .into_versionless()
+    }
+
+    fn frame_v2(message: &impl Message) -> Frame<Versionless> {
+        Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(message)
+            .unwrap()
+            .into_versionless()
+    }
+
+    #[test]
+    fn frames_are_upgraded() {
+        let bridge = VersionBridge::v2::<Minimal>();
+        let frame = frame_v1(&Heartbeat::default());
+
+        let upgraded = bridge.convert(&frame).unwrap();
+        assert_eq!(upgraded.version(), MavLinkVersion::V2);
+        assert_eq!(upgraded.sequence(), frame.sequence());
+        assert!(upgraded.payload_length() < frame.payload_length());
+        assert!(upgraded
+            .validate_checksum_with_crc_extra(Heartbeat::crc_extra())
+            .is_ok());
+
+        // Frames of target version are not converted
+        bridge.convert(&upgraded).unwrap();
+        assert_eq!(bridge.converted(), 1);
+    }
+
+    #[test]
+    fn frames_are_downgraded() {
+        let bridge = VersionBridge::v1::<Minimal>();
+        let message = Heartbeat {
+            custom_mode: 42,
+            ..Default::default()
+        };
+        let frame = frame_v2(&message);
+
+        let downgraded = bridge.convert(&frame).unwrap();
+        assert_eq!(downgraded.version(), MavLinkVersion::V1);
+        assert_eq!(
+            downgraded.payload_length(),
+            message.encode(MavLinkVersion::V1).unwrap().length()
+        );
+        let decoded: Minimal = downgraded.decode().unwrap();
+        assert!(matches!(decoded, Minimal::Heartbeat(heartbeat) if heartbeat.custom_mode == 42));
+    }
+
+    #[test]
+    fn unrepresentable_frames_are_rejected() {
+        let bridge = VersionBridge::v1::<Minimal>();
+
+        let result = bridge.convert(&frame_v2(&ProtocolVersion::default()));
+        assert!(matches!(
+            result,
+            Err(Error::Bridge(BridgeError::MessageId(300)))
+        ));
+
+        let corrupted = Frame::builder()
+            .sequence(0)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .message_id(Heartbeat::message_id())
+            .payload(&[1])
+            .crc_extra(Heartbeat::crc_extra().wrapping_add(1))
+            .build()
+            .into_versionless();
+        assert!(matches!(
+            bridge.convert(&corrupted),
+            Err(Error::Frame(FrameError::Checksum))
+        ));
+
+        assert_eq!(bridge.rejected(), 2);
+        assert_eq!(bridge.converted(), 0);
+    }
+}
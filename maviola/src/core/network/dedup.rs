@@ -0,0 +1,109 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::protocol::{Checksum, ComponentId, Sequence, SystemId};
+
+use crate::prelude::*;
+
+/// Identity of a frame on the wire.
+type FrameKey = (SystemId, ComponentId, Sequence, Checksum);
+
+/// Drops incoming frames, that were already received by a network within a sliding window.
+///
+/// Frames are identified by system `ID`, component `ID`, sequence, and checksum. Shared between
+/// incoming handlers of all network connections. Enabled by [`Network::dedup`].
+#[derive(Clone, Debug)]
+pub(crate) struct FrameDeduplicator {
+    window: Duration,
+    seen: Arc<Mutex<SeenFrames>>,
+}
+
+#[derive(Debug, Default)]
+struct SeenFrames {
+    keys: HashMap<FrameKey, Instant>,
+    order: VecDeque<(Instant, FrameKey)>,
+}
+
+impl FrameDeduplicator {
+    /// Creates a deduplicator, that remembers frames for a `window`.
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: Default::default(),
+        }
+    }
+
+    /// Returns `true`, if the same frame has been seen within the window before `now`.
+    ///
+    /// Otherwise, remembers the frame and returns `false`.
+    pub(crate) fn is_duplicate<V: MaybeVersioned>(&self, frame: &Frame<V>, now: Instant) -> bool {
+        let key = (
+            frame.system_id(),
+            frame.component_id(),
+            frame.sequence(),
+            frame.checksum(),
+        );
+
+        let mut seen = match self.seen.lock() {
+            Ok(seen) => seen,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        seen.evict(now, self.window);
+
+        if seen.keys.contains_key(&key) {
+            return true;
+        }
+        seen.keys.insert(key, now);
+        seen.order.push_back((now, key));
+
+        false
+    }
+}
+
+impl SeenFrames {
+    fn evict(&mut self, now: Instant, window: Duration) {
+        while let Some((seen_at, key)) = self.order.front().copied() {
+            if now.saturating_duration_since(seen_at) < window {
+                break;
+            }
+            self.order.pop_front();
+            self.keys.remove(&key);
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod dedup_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    #[test]
+    fn frames_are_deduplicated_within_window() {
+        let window = Duration::from_millis(100);
+        let dedup = FrameDeduplicator::new(window);
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+        let next_frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+
+        let now = Instant::now();
+        assert!(!dedup.is_duplicate(&frame, now));
+        assert!(dedup.is_duplicate(&frame, now + window / 2));
+        assert!(!dedup.is_duplicate(&next_frame, now + window / 2));
+
+        // Frames from other systems are distinct
+        let other = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        assert!(!dedup.is_duplicate(&other, now));
+
+        // Frame is forgotten once window has passed
+        assert!(!dedup.is_duplicate(&frame, now + window));
+    }
+}
@@ -0,0 +1,335 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::core::io::ConnectionInfo;
+use crate::core::utils::UniqueId;
+
+#[cfg(doc)]
+use crate::prelude::*;
+
+/// A group of [`Network`] connections, where only one link carries traffic at a time.
+///
+/// Connections are prioritized in the order they were added to the group. Traffic is routed
+/// through the highest-priority healthy connection, that is called the active link. Other members
+/// of the group keep their connections established and receive automatic heartbeats, but other
+/// outgoing frames are not sent to them, and frames received by them are ignored.
+///
+/// A link is considered healthy, while its node is running and frames (usually heartbeats of
+/// remote peers) are received at least once per [`timeout`](Self::timeout). Freshly started links
+/// are presumed to be healthy until the timeout passes. Once the active link fails, traffic is
+/// switched to the next healthy link. Once a higher-priority link receives frames again, traffic
+/// falls back to it after the [`recovery`](Self::recovery) delay. Each switchover is reported by
+/// `LinkActivated` event of a node.
+///
+/// Clones of a group share the same state, so active link can be observed while the network is
+/// running. A group should not be shared between networks. Attach connections with
+/// [`ConnectionOptions::failover`](crate::core::network::ConnectionOptions::failover).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use maviola::core::network::FailoverGroup;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let links = FailoverGroup::new(Duration::from_secs(3))
+///     .with_recovery(Duration::from_secs(10));
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync()
+///             // Primary link
+///             .add_connection(UdpClient::new("10.0.0.1:14550").unwrap())
+///             .failover(links.clone())
+///             // Backup link
+///             .add_connection(TcpClient::new("10.0.1.1:5760").unwrap())
+///             .failover(links.clone())
+///     )
+///     .build().unwrap();
+///
+/// println!("active link: {:?}", links.active());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FailoverGroup {
+    timeout: Duration,
+    recovery: Duration,
+    inner: Arc<Mutex<FailoverState>>,
+}
+
+#[derive(Debug, Default)]
+struct FailoverState {
+    members: Vec<FailoverMember>,
+    active: Option<UniqueId>,
+}
+
+#[derive(Debug)]
+struct FailoverMember {
+    id: UniqueId,
+    info: ConnectionInfo,
+    up_since: Option<Instant>,
+    last_frame_at: Option<Instant>,
+    healthy_since: Option<Instant>,
+}
+
+impl FailoverGroup {
+    /// Creates a group, which links are considered failed, if no frames were received within
+    /// `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            recovery: Duration::ZERO,
+            inner: Default::default(),
+        }
+    }
+
+    /// Sets the delay, during which a recovered higher-priority link should stay healthy before
+    /// traffic falls back to it.
+    ///
+    /// By default, traffic falls back, as soon as a higher-priority link receives a frame.
+    pub fn with_recovery(mut self, recovery: Duration) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Time without received frames, after which a link is considered failed.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Delay, before traffic falls back to a recovered higher-priority link.
+    pub fn recovery(&self) -> Duration {
+        self.recovery
+    }
+
+    /// Connection of the active link, if any.
+    pub fn active(&self) -> Option<ConnectionInfo> {
+        let state = self.lock();
+        let active = state.active?;
+        state
+            .members
+            .iter()
+            .find(|member| member.id == active)
+            .map(|member| member.info.clone())
+    }
+
+    /// Connections of the group in the order of their priority.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        self.lock()
+            .members
+            .iter()
+            .map(|member| member.info.clone())
+            .collect()
+    }
+
+    /// <sup>⛔</sup>
+    /// Adds a network node to the group with the lowest priority.
+    pub(crate) fn add(&self, id: UniqueId, info: ConnectionInfo) {
+        self.lock().members.push(FailoverMember {
+            id,
+            info,
+            up_since: None,
+            last_frame_at: None,
+            healthy_since: None,
+        });
+    }
+
+    /// <sup>⛔</sup>
+    /// Removes a network node from the group.
+    pub(crate) fn remove(&self, id: UniqueId) {
+        let mut state = self.lock();
+        state.members.retain(|member| member.id != id);
+        if state.active == Some(id) {
+            state.active = None;
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if network node is the active link of the group.
+    pub(crate) fn is_active(&self, id: UniqueId) -> bool {
+        self.lock().active == Some(id)
+    }
+
+    /// <sup>⛔</sup>
+    /// Marks network node as started.
+    pub(crate) fn set_up(&self, id: UniqueId) {
+        self.update_member(id, |member| {
+            member.up_since = Some(Instant::now());
+            member.last_frame_at = None;
+            member.healthy_since = None;
+        });
+    }
+
+    /// <sup>⛔</sup>
+    /// Marks network node as stopped.
+    pub(crate) fn set_down(&self, id: UniqueId) {
+        self.update_member(id, |member| {
+            member.up_since = None;
+            member.healthy_since = None;
+        });
+    }
+
+    /// <sup>⛔</sup>
+    /// Records a frame received by a network node.
+    pub(crate) fn record_frame(&self, id: UniqueId, received_at: Instant) {
+        let timeout = self.timeout;
+        self.update_member(id, |member| {
+            let was_healthy = member
+                .last_frame_at
+                .is_some_and(|last| received_at.saturating_duration_since(last) <= timeout);
+            if !was_healthy || member.healthy_since.is_none() {
+                member.healthy_since = Some(received_at);
+            }
+            member.last_frame_at = Some(received_at);
+        });
+    }
+
+    /// <sup>⛔</sup>
+    /// Chooses the active link at the moment `now`.
+    ///
+    /// Returns connection of the new active link, if it has changed. If none of the links are
+    /// healthy, the active link is kept.
+    pub(crate) fn update(&self, now: Instant) -> Option<ConnectionInfo> {
+        let mut state = self.lock();
+        let active = state.active;
+
+        let next = state
+            .members
+            .iter()
+            .find(|member| {
+                member.is_healthy(now, self.timeout)
+                    && (Some(member.id) == active || member.has_recovered(now, self.recovery))
+            })
+            .or_else(|| {
+                state
+                    .members
+                    .iter()
+                    .find(|member| member.is_healthy(now, self.timeout))
+            })
+            .map(|member| (member.id, member.info.clone()));
+
+        match next {
+            Some((id, info)) if Some(id) != active => {
+                state.active = Some(id);
+                Some(info)
+            }
+            _ => None,
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if `other` shares state with this group.
+    pub(crate) fn is_same(&self, other: &FailoverGroup) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    fn update_member(&self, id: UniqueId, f: impl FnOnce(&mut FailoverMember)) {
+        if let Some(member) = self
+            .lock()
+            .members
+            .iter_mut()
+            .find(|member| member.id == id)
+        {
+            f(member);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FailoverState> {
+        match self.inner.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl FailoverMember {
+    /// Returns `true`, if node is running and received frames within `timeout`.
+    ///
+    /// Nodes, that haven't received frames since they were started, are healthy until `timeout`
+    /// passes.
+    fn is_healthy(&self, now: Instant, timeout: Duration) -> bool {
+        let up_since = match self.up_since {
+            Some(up_since) => up_since,
+            None => return false,
+        };
+        let last_seen = match self.last_frame_at {
+            Some(last_frame_at) => last_frame_at.max(up_since),
+            None => up_since,
+        };
+        now.saturating_duration_since(last_seen) <= timeout
+    }
+
+    /// Returns `true`, if node has been receiving frames for at least `recovery`.
+    fn has_recovered(&self, now: Instant, recovery: Duration) -> bool {
+        self.healthy_since
+            .is_some_and(|since| now.saturating_duration_since(since) >= recovery)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod failover_tests {
+    use super::*;
+    use crate::core::io::ConnectionDetails;
+
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn member(group: &FailoverGroup) -> (UniqueId, ConnectionInfo) {
+        let id = UniqueId::new();
+        let info = ConnectionInfo::new(ConnectionDetails::Network);
+        group.add(id, info.clone());
+        group.set_up(id);
+        (id, info)
+    }
+
+    #[test]
+    fn failover_switches_and_falls_back() {
+        let group = FailoverGroup::new(TIMEOUT).with_recovery(TIMEOUT / 2);
+        let (primary, primary_info) = member(&group);
+        let (backup, backup_info) = member(&group);
+        let start = Instant::now();
+
+        // Freshly started links are presumed healthy
+        assert_eq!(group.update(start).unwrap().id(), primary_info.id());
+        assert!(group.is_active(primary));
+        assert!(group.update(start).is_none());
+
+        // Primary is silent, while backup receives frames
+        let later = start + TIMEOUT * 2;
+        group.record_frame(backup, later);
+        assert_eq!(group.update(later).unwrap().id(), backup_info.id());
+        assert_eq!(group.active().unwrap().id(), backup_info.id());
+
+        // Primary recovers, but has to stay healthy for the recovery delay
+        group.record_frame(primary, later);
+        group.record_frame(backup, later + TIMEOUT / 4);
+        assert!(group.update(later + TIMEOUT / 4).is_none());
+        group.record_frame(primary, later + TIMEOUT / 2);
+        group.record_frame(backup, later + TIMEOUT / 2);
+        assert_eq!(
+            group.update(later + TIMEOUT / 2).unwrap().id(),
+            primary_info.id()
+        );
+
+        // Stopped links are never active
+        group.set_down(primary);
+        assert_eq!(
+            group.update(later + TIMEOUT / 2).unwrap().id(),
+            backup_info.id()
+        );
+
+        // Active link is kept, if none of the links are healthy
+        group.set_down(backup);
+        assert!(group.update(later + TIMEOUT / 2).is_none());
+        assert!(group.is_active(backup));
+
+        group.remove(backup);
+        assert!(group.active().is_none());
+        assert_eq!(group.connections().len(), 1);
+    }
+}
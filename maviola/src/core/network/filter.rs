@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::core::io::{ChannelInfo, ConnectionInfo};
+#[cfg(feature = "scripting")]
+use crate::core::network::{FrameScript, ScriptContext, ScriptVerdict};
+use crate::protocol::{ComponentId, MessageId, SystemId};
+
+use crate::prelude::*;
+
+/// Declarative filter of frames routed through a particular connection of a [`Network`].
+///
+/// Filter is applied in both directions: frames received by a filtered connection are not passed
+/// to the network, if they are rejected, and frames, that network routes to this connection are
+/// not sent. Similar to `mavlink-router`, filter can be set up for message, system and component
+/// `ID`s:
+///
+/// * `allow_*` methods define an allow-list. Once such a list is set, only frames with specified
+///   `ID`s pass. Subsequent calls extend the list.
+/// * `block_*` methods define a block-list. Blocked `ID`s are always rejected, even if they were
+///   explicitly allowed.
+///
+/// System and component `ID`s are checked against the sender of a frame. A frame passes, if it
+/// satisfies all configured rules. An empty filter passes everything.
+///
+/// When `scripting` feature is enabled, policies that can't be expressed by lists of `ID`s can be
+/// defined by a [`FrameScript`] set with `script` method.
+///
+/// Attach filters with
+/// [`ConnectionOptions::filter`](crate::core::network::ConnectionOptions::filter).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::ConnectionFilter;
+/// use maviola::dialects::minimal::messages::Heartbeat;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             // Only heartbeats of the system `1` will be routed through this server
+///             .add_connection(TcpServer::new("127.0.0.1:5601").unwrap())
+///             .filter(
+///                 ConnectionFilter::new()
+///                     .allow_message_ids([Heartbeat::message_id()])
+///                     .allow_system_ids([1]),
+///             )
+///     )
+///     .build().unwrap();
+/// ```
+#[cfg_attr(
+    feature = "scripting",
+    doc = "",
+    doc = "[`FrameScript`]: crate::core::network::FrameScript"
+)]
+#[cfg_attr(
+    not(feature = "scripting"),
+    doc = "",
+    doc = "[`FrameScript`]: https://docs.rs/maviola/latest/maviola/core/network/struct.FrameScript.html"
+)]
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionFilter {
+    message_ids: IdRules<MessageId>,
+    system_ids: IdRules<SystemId>,
+    component_ids: IdRules<ComponentId>,
+    #[cfg(feature = "scripting")]
+    script: Option<FrameScript>,
+}
+
+#[derive(Clone, Debug)]
+struct IdRules<T> {
+    allowed: Option<HashSet<T>>,
+    blocked: HashSet<T>,
+}
+
+impl ConnectionFilter {
+    /// Creates a filter, that passes all frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Passes only frames with specified message `ID`s.
+    pub fn allow_message_ids(mut self, ids: impl IntoIterator<Item = MessageId>) -> Self {
+        self.message_ids.allow(ids);
+        self
+    }
+
+    /// Rejects frames with specified message `ID`s.
+    pub fn block_message_ids(mut self, ids: impl IntoIterator<Item = MessageId>) -> Self {
+        self.message_ids.block(ids);
+        self
+    }
+
+    /// Passes only frames sent by systems with specified `ID`s.
+    pub fn allow_system_ids(mut self, ids: impl IntoIterator<Item = SystemId>) -> Self {
+        self.system_ids.allow(ids);
+        self
+    }
+
+    /// Rejects frames sent by systems with specified `ID`s.
+    pub fn block_system_ids(mut self, ids: impl IntoIterator<Item = SystemId>) -> Self {
+        self.system_ids.block(ids);
+        self
+    }
+
+    /// Passes only frames sent by components with specified `ID`s.
+    pub fn allow_component_ids(mut self, ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        self.component_ids.allow(ids);
+        self
+    }
+
+    /// Rejects frames sent by components with specified `ID`s.
+    pub fn block_component_ids(mut self, ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        self.component_ids.block(ids);
+        self
+    }
+
+    /// <sup>`scripting`</sup>
+    /// Sets a routing script, that is evaluated for frames passing declarative rules.
+    ///
+    /// Frame is routed only if script returns [`ScriptVerdict::Accept`]. Subsequent calls replace
+    /// the previous script.
+    #[cfg(feature = "scripting")]
+    pub fn script(self, script: FrameScript) -> Self {
+        Self {
+            script: Some(script),
+            ..self
+        }
+    }
+
+    /// Returns `true`, if frame passes declarative rules of the filter.
+    ///
+    /// Routing scripts depend on connection properties and therefore are not evaluated by this
+    /// method.
+    pub fn matches<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        self.message_ids.matches(&frame.message_id())
+            && self.system_ids.matches(&frame.system_id())
+            && self.component_ids.matches(&frame.component_id())
+    }
+
+    /// Returns `true`, if frame received by a `channel` of a `connection` passes the filter.
+    #[allow(unused_variables)]
+    pub(crate) fn matches_incoming<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        connection: &ConnectionInfo,
+        channel: &ChannelInfo,
+    ) -> bool {
+        if !self.matches(frame) {
+            return false;
+        }
+
+        #[cfg(feature = "scripting")]
+        if let Some(script) = &self.script {
+            let context = ScriptContext::incoming(connection, channel);
+            return script.evaluate(frame, &context) == ScriptVerdict::Accept;
+        }
+
+        true
+    }
+
+    /// Returns `true`, if frame routed to a `connection` passes the filter.
+    #[allow(unused_variables)]
+    pub(crate) fn matches_outgoing<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        connection: &ConnectionInfo,
+    ) -> bool {
+        if !self.matches(frame) {
+            return false;
+        }
+
+        #[cfg(feature = "scripting")]
+        if let Some(script) = &self.script {
+            let context = ScriptContext::outgoing(connection);
+            return script.evaluate(frame, &context) == ScriptVerdict::Accept;
+        }
+
+        true
+    }
+}
+
+impl<T> Default for IdRules<T> {
+    fn default() -> Self {
+        Self {
+            allowed: None,
+            blocked: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> IdRules<T> {
+    fn allow(&mut self, ids: impl IntoIterator<Item = T>) {
+        self.allowed.get_or_insert_with(HashSet::new).extend(ids);
+    }
+
+    fn block(&mut self, ids: impl IntoIterator<Item = T>) {
+        self.blocked.extend(ids);
+    }
+
+    fn matches(&self, id: &T) -> bool {
+        if self.blocked.contains(id) {
+            return false;
+        }
+        match &self.allowed {
+            Some(allowed) => allowed.contains(id),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::protocol::Endpoint;
+
+    fn frame(system_id: SystemId, component_id: ComponentId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(system_id, component_id))
+            .next_frame(message)
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_filter_passes_everything() {
+        let filter = ConnectionFilter::new();
+
+        assert!(filter.matches(&frame(1, 1, &Heartbeat::default())));
+        assert!(filter.matches(&frame(255, 190, &ProtocolVersion::default())));
+    }
+
+    #[test]
+    fn allowed_ids_restrict_frames() {
+        let filter = ConnectionFilter::new()
+            .allow_message_ids([Heartbeat::message_id()])
+            .allow_system_ids([1, 2]);
+
+        assert!(filter.matches(&frame(1, 1, &Heartbeat::default())));
+        assert!(filter.matches(&frame(2, 1, &Heartbeat::default())));
+        assert!(!filter.matches(&frame(3, 1, &Heartbeat::default())));
+        assert!(!filter.matches(&frame(1, 1, &ProtocolVersion::default())));
+    }
+
+    #[test]
+    fn blocked_ids_take_precedence() {
+        let filter = ConnectionFilter::new()
+            .allow_component_ids([1, 2])
+            .block_component_ids([2]);
+
+        assert!(filter.matches(&frame(1, 1, &Heartbeat::default())));
+        assert!(!filter.matches(&frame(1, 2, &Heartbeat::default())));
+        assert!(!filter.matches(&frame(1, 3, &Heartbeat::default())));
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use crate::core::io::{ConnectionId, ConnectionInfo};
+use crate::core::marker::{HasConnConf, MaybeConnConf, NodeKind, Proxy};
+use crate::core::node::{IntoNodeConf, NodeConf};
+use crate::core::utils::{Closable, UniqueId};
+use crate::error::NodeError;
+
+use crate::prelude::*;
+
+/// Handle for changing topology of a running [`Network`].
+///
+/// Connections can be added to and removed from a network at runtime, for example, when a ground
+/// control station user adds a new link. Network handle is available from a node with a network
+/// connection as `network_handle`.
+///
+/// Added connections are started by a network and repaired according to its
+/// [`Network::retry`] strategy. Removed connections are closed and are not repaired. Each change
+/// of topology is announced by `Event::ConnectionAdded` / `Event::ConnectionRemoved` node events.
+/// Connections, that were given up by the network, are announced as removed as well.
+///
+/// Connections can be temporarily [paused](Self::pause_connection) without closing them. Paused
+/// connections stop reading from and writing to their transports, until they are
+/// [resumed](Self::resume_connection).
+///
+/// Unlike a network, which stops, once all its connections were given up, a network, which
+/// connections were explicitly removed, keeps running, so new connections can be added later.
+///
+/// Clones share the same network.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync().add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     )
+///     .build().unwrap();
+///
+/// let network = node.network_handle().unwrap();
+///
+/// // Add a link at runtime
+/// let conn_id = network.add_connection(TcpClient::new("127.0.0.1:5601").unwrap()).unwrap();
+///
+/// // Pause and resume it
+/// network.pause_connection(conn_id).unwrap();
+/// network.resume_connection(conn_id).unwrap();
+///
+/// // Remove it later
+/// network.remove_connection(conn_id).unwrap();
+/// ```
+pub struct NetworkHandle<V: MaybeVersioned, C: MaybeConnConf> {
+    inner: Arc<Mutex<NetworkHandleInner<V, C>>>,
+}
+
+/// <sup>⛔</sup>
+/// Topology change requested by a [`NetworkHandle`].
+pub(crate) enum NetworkCommand<V: MaybeVersioned, C: MaybeConnConf> {
+    /// Connection of a new node should be started.
+    Add(UniqueId, Box<NodeConf<Proxy, V, C>>),
+    /// Connection of a node should be closed and never repaired.
+    Remove(UniqueId),
+}
+
+struct NetworkHandleInner<V: MaybeVersioned, C: MaybeConnConf> {
+    state: Option<Closable>,
+    info: Option<ConnectionInfo>,
+    connections: Vec<(UniqueId, ConnectionInfo)>,
+    commands: Vec<NetworkCommand<V, C>>,
+}
+
+impl<V: MaybeVersioned, C: HasConnConf> NetworkHandle<V, C> {
+    /// Adds node configuration to a running network.
+    ///
+    /// Returns `ID` of the added connection, that can be passed to [`Self::remove_connection`].
+    /// Connection is started by the network asynchronously. If connection can't be established,
+    /// it is repaired according to [`Network::retry`] strategy.
+    ///
+    /// Accepts anything that can be converted into a [`NodeConf`]. The same considerations as for
+    /// [`Network::add_node`] apply.
+    ///
+    /// Connections added to a paused network are paused as well.
+    ///
+    /// Returns [`NodeError::Inactive`], if network is not running.
+    pub fn add_node<K: NodeKind>(&self, node: impl IntoNodeConf<K, V, C>) -> Result<ConnectionId> {
+        let mut node = node.into_node_conf().into_proxy();
+        // Channel events of network nodes are relayed to the network connection
+        node.channel_events = true;
+        let info = node.connection_conf.info().clone();
+
+        let mut inner = self.lock()?;
+        if !inner.is_running() {
+            return Err(NodeError::Inactive.into());
+        }
+
+        if matches!(&inner.info, Some(network) if network.is_paused()) {
+            info.pause_switch().pause();
+        }
+
+        let id = UniqueId::new();
+        inner.connections.push((id, info.clone()));
+        inner.commands.push(NetworkCommand::Add(id, Box::new(node)));
+
+        Ok(info.id())
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> NetworkHandle<V, C> {
+    /// Removes connection from a running network.
+    ///
+    /// Connection is closed and won't be repaired.
+    ///
+    /// Returns [`NodeError::Inactive`], if network is not running, and
+    /// [`NodeError::UnknownConnection`], if connection with `connection_id` is not a part of the
+    /// network.
+    pub fn remove_connection(&self, connection_id: ConnectionId) -> Result<()> {
+        let mut inner = self.lock()?;
+        if !inner.is_running() {
+            return Err(NodeError::Inactive.into());
+        }
+
+        let position = inner
+            .connections
+            .iter()
+            .position(|(_, info)| info.id() == connection_id)
+            .ok_or(NodeError::UnknownConnection(connection_id))?;
+        let (id, _) = inner.connections.remove(position);
+        inner.commands.push(NetworkCommand::Remove(id));
+
+        Ok(())
+    }
+
+    /// Pauses connection of a running network without closing it.
+    ///
+    /// Paused connection stops reading from its transport, so transport flow control, like TCP
+    /// backpressure, applies to the remote side. Outgoing frames are kept by the connection and
+    /// written once it is [resumed](Self::resume_connection). Connection remains paused, when it is
+    /// repaired after failure.
+    ///
+    /// Returns [`NodeError::Inactive`], if network is not running, and
+    /// [`NodeError::UnknownConnection`], if connection with `connection_id` is not a part of the
+    /// network.
+    pub fn pause_connection(&self, connection_id: ConnectionId) -> Result<()> {
+        self.find(connection_id)?.pause_switch().pause();
+        Ok(())
+    }
+
+    /// Resumes connection paused by [`Self::pause_connection`].
+    ///
+    /// Returns [`NodeError::Inactive`], if network is not running, and
+    /// [`NodeError::UnknownConnection`], if connection with `connection_id` is not a part of the
+    /// network.
+    pub fn resume_connection(&self, connection_id: ConnectionId) -> Result<()> {
+        self.find(connection_id)?.pause_switch().resume();
+        Ok(())
+    }
+
+    /// Information about connections of a running network.
+    ///
+    /// Includes connections, that are being repaired. Returns an empty list, if network is not
+    /// running.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        match self.inner.lock() {
+            Ok(inner) if inner.is_running() => inner
+                .connections
+                .iter()
+                .map(|(_, info)| info.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `true`, if network is running.
+    pub fn is_running(&self) -> bool {
+        match self.inner.lock() {
+            Ok(inner) => inner.is_running(),
+            Err(_) => false,
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Pauses or resumes all connections of a running network.
+    pub(crate) fn set_paused(&self, paused: bool) {
+        for info in self.connections() {
+            if paused {
+                info.pause_switch().pause();
+            } else {
+                info.pause_switch().resume();
+            }
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Binds handle to a network, that has started with the provided `connections`.
+    pub(crate) fn start(
+        &self,
+        state: Closable,
+        info: ConnectionInfo,
+        connections: impl IntoIterator<Item = (UniqueId, ConnectionInfo)>,
+    ) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.state = Some(state);
+            inner.info = Some(info);
+            inner.connections = connections.into_iter().collect();
+            inner.commands.clear();
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Takes topology changes requested since the last call.
+    pub(crate) fn take_commands(&self) -> Vec<NetworkCommand<V, C>> {
+        match self.inner.lock() {
+            Ok(mut inner) => std::mem::take(&mut inner.commands),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Forgets connection, that was given up by the network.
+    pub(crate) fn forget(&self, id: UniqueId) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.connections.retain(|(conn_id, _)| *conn_id != id);
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Unbinds handle from a stopped network.
+    pub(crate) fn stop(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.state = None;
+            inner.info = None;
+            inner.connections.clear();
+            inner.commands.clear();
+        }
+    }
+
+    fn find(&self, connection_id: ConnectionId) -> Result<ConnectionInfo> {
+        let inner = self.lock()?;
+        if !inner.is_running() {
+            return Err(NodeError::Inactive.into());
+        }
+
+        inner
+            .connections
+            .iter()
+            .find(|(_, info)| info.id() == connection_id)
+            .map(|(_, info)| info.clone())
+            .ok_or(NodeError::UnknownConnection(connection_id).into())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, NetworkHandleInner<V, C>>> {
+        self.inner
+            .lock()
+            .map_err(|_| Error::from(NodeError::Inactive))
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> NetworkHandleInner<V, C> {
+    fn is_running(&self) -> bool {
+        matches!(&self.state, Some(state) if !state.is_closed())
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> Clone for NetworkHandle<V, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> Default for NetworkHandle<V, C> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(NetworkHandleInner {
+                state: None,
+                info: None,
+                connections: Vec::new(),
+                commands: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> Debug for NetworkHandle<V, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkHandle")
+            .field("is_running", &self.is_running())
+            .field("connections", &self.connections())
+            .finish()
+    }
+}
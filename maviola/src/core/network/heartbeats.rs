@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(doc)]
+use crate::prelude::*;
+
+/// Runtime switch of automatic heartbeats sent to a [`Network`] connection.
+///
+/// By default, automatic heartbeats of a node (and its components) are sent to all network
+/// connections. Connections added with a toggle receive these heartbeats only while the toggle is
+/// [enabled](Self::is_enabled). This allows, for example, to announce the node to ground stations,
+/// but never send its heartbeats toward a flight controller link. Heartbeats of other systems
+/// forwarded by the node and `HEARTBEAT` messages sent explicitly are not affected.
+///
+/// Clones of a toggle share the same state, so heartbeats can be switched while the network is
+/// running. A toggle may be shared by several connections.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::HeartbeatToggle;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let autopilot_heartbeats = HeartbeatToggle::new(false);
+///
+/// let mut node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 191))
+///     .connection(
+///         Network::sync()
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .add_connection(TcpClient::new("127.0.0.1:5760").unwrap())
+///             .heartbeats(autopilot_heartbeats.clone())
+///     )
+///     .build().unwrap();
+/// node.activate().unwrap();
+///
+/// // Start announcing the node to the autopilot
+/// autopilot_heartbeats.enable();
+/// ```
+#[derive(Clone, Debug)]
+pub struct HeartbeatToggle {
+    enabled: Arc<AtomicBool>,
+}
+
+impl HeartbeatToggle {
+    /// Creates a toggle, which is initially `enabled` or disabled.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+        }
+    }
+
+    /// Enables automatic heartbeats.
+    pub fn enable(&self) {
+        self.set(true);
+    }
+
+    /// Disables automatic heartbeats.
+    pub fn disable(&self) {
+        self.set(false);
+    }
+
+    /// Enables or disables automatic heartbeats.
+    pub fn set(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Release);
+    }
+
+    /// Returns `true`, if automatic heartbeats are enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+}
+
+impl Default for HeartbeatToggle {
+    /// Creates an enabled toggle.
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
@@ -0,0 +1,225 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::core::io::{BroadcastScope, ConnectionId, ConnectionInfo};
+use crate::core::network::FrameDeduplicator;
+use crate::core::utils::UniqueId;
+
+use crate::prelude::*;
+
+/// A group of [`Network`] connections, that act as a single logical link.
+///
+/// Every frame sent to one of the group connections is transmitted over all of them
+/// simultaneously. For example, a vehicle may be reachable over both LTE and a telemetry radio,
+/// and sending each frame over both links improves reliability on lossy field links. Frames
+/// received by several connections of the group within the deduplication
+/// [`window`](Self::window) are passed further only once.
+///
+/// Frames are identified by system `ID`, component `ID`, sequence, and checksum, the same way as
+/// by [`Network::dedup`]. Unlike the latter, deduplication is limited to the group connections.
+///
+/// Connections of a group share routes: addressed frames are sent over all group connections, if
+/// their target was seen by any of them. Frames received by a group connection are not forwarded
+/// to other connections of the same group.
+///
+/// Clones of a group share the same state. A group should not be shared between networks. Attach
+/// connections with [`ConnectionOptions::mirror`](crate::core::network::ConnectionOptions::mirror).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use maviola::core::network::MirrorGroup;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let links = MirrorGroup::new(Duration::from_secs(1));
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync()
+///             // LTE link
+///             .add_connection(TcpClient::new("10.0.0.1:5760").unwrap())
+///             .mirror(links.clone())
+///             // Telemetry radio bridged to UDP
+///             .add_connection(UdpClient::new("192.168.1.1:14550").unwrap())
+///             .mirror(links.clone())
+///     )
+///     .build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct MirrorGroup {
+    window: Duration,
+    dedup: FrameDeduplicator,
+    members: Arc<Mutex<Vec<MirrorMember>>>,
+}
+
+#[derive(Clone, Debug)]
+struct MirrorMember {
+    id: UniqueId,
+    info: ConnectionInfo,
+}
+
+impl MirrorGroup {
+    /// Creates a group, which drops frames already received by any of its connections less than
+    /// `window` ago.
+    ///
+    /// The window should be longer than the difference in latencies of the group links, but
+    /// shorter than the time it takes a system to wrap its sequence counter around.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            dedup: FrameDeduplicator::new(window),
+            members: Default::default(),
+        }
+    }
+
+    /// Deduplication window.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Connections of the group in the order they were added.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        self.lock()
+            .iter()
+            .map(|member| member.info.clone())
+            .collect()
+    }
+
+    /// <sup>⛔</sup>
+    /// Adds a network node to the group.
+    pub(crate) fn add(&self, id: UniqueId, info: ConnectionInfo) {
+        self.lock().push(MirrorMember { id, info });
+    }
+
+    /// <sup>⛔</sup>
+    /// Removes a network node from the group.
+    pub(crate) fn remove(&self, id: UniqueId) {
+        self.lock().retain(|member| member.id != id);
+    }
+
+    /// <sup>⛔</sup>
+    /// Network nodes of the group.
+    pub(crate) fn members(&self) -> Vec<UniqueId> {
+        self.lock().iter().map(|member| member.id).collect()
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if the same frame has been received by any of the group connections within
+    /// the window before `now`.
+    pub(crate) fn is_duplicate<V: MaybeVersioned>(&self, frame: &Frame<V>, now: Instant) -> bool {
+        self.dedup.is_duplicate(frame, now)
+    }
+
+    /// <sup>⛔</sup>
+    /// Adjusts broadcast `scope` of an outgoing frame for a network node with the specified `id`.
+    ///
+    /// Frames sent exactly to other group connections are sent over this node as well, while
+    /// frames excluding other group connections are not sent over this node. Returns `None`, if
+    /// frame should not be sent.
+    pub(crate) fn mirror_scope(
+        &self,
+        id: UniqueId,
+        scope: BroadcastScope,
+    ) -> Option<BroadcastScope> {
+        match scope {
+            BroadcastScope::ExactChannel(channel_id)
+                if self.is_sibling(id, channel_id.connection_id()) =>
+            {
+                Some(BroadcastScope::All)
+            }
+            BroadcastScope::ExactConnection(conn_id) if self.is_sibling(id, conn_id) => {
+                Some(BroadcastScope::All)
+            }
+            BroadcastScope::ExceptChannel(channel_id)
+                if self.is_sibling(id, channel_id.connection_id()) =>
+            {
+                None
+            }
+            BroadcastScope::ExceptConnection(conn_id) if self.is_sibling(id, conn_id) => None,
+            scope => Some(scope),
+        }
+    }
+
+    /// Returns `true`, if connection belongs to a group member other than network node `id`.
+    fn is_sibling(&self, id: UniqueId, conn_id: ConnectionId) -> bool {
+        self.lock()
+            .iter()
+            .any(|member| member.id != id && member.info.id() == conn_id)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<MirrorMember>> {
+        match self.members.lock() {
+            Ok(members) => members,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod mirror_tests {
+    use super::*;
+    use crate::core::io::{ChannelId, ConnectionDetails};
+
+    #[test]
+    fn mirror_scope_spans_group_connections() {
+        let group = MirrorGroup::new(Duration::from_millis(100));
+        let (lte, radio, other) = (UniqueId::new(), UniqueId::new(), UniqueId::new());
+        let lte_info = ConnectionInfo::new(ConnectionDetails::Network);
+        let radio_info = ConnectionInfo::new(ConnectionDetails::Network);
+        group.add(lte, lte_info.clone());
+        group.add(radio, radio_info.clone());
+
+        let lte_channel = ChannelId::new(lte_info.id());
+        let foreign_channel = ChannelId::new(ConnectionId::new());
+
+        // Responses to one link are mirrored to others
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExactChannel(lte_channel)),
+            Some(BroadcastScope::All)
+        );
+        assert_eq!(
+            group.mirror_scope(lte, BroadcastScope::ExactChannel(lte_channel)),
+            Some(BroadcastScope::ExactChannel(lte_channel))
+        );
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExactConnection(lte_info.id())),
+            Some(BroadcastScope::All)
+        );
+
+        // Frames are not forwarded back to the group
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExceptChannel(lte_channel)),
+            None
+        );
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExceptConnection(lte_info.id())),
+            None
+        );
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExceptChannel(foreign_channel)),
+            Some(BroadcastScope::ExceptChannel(foreign_channel))
+        );
+
+        // Connections outside the group are not affected
+        assert_eq!(
+            group.mirror_scope(other, BroadcastScope::ExactChannel(foreign_channel)),
+            Some(BroadcastScope::ExactChannel(foreign_channel))
+        );
+
+        group.remove(lte);
+        assert_eq!(group.members(), vec![radio]);
+        assert_eq!(group.connections()[0].id(), radio_info.id());
+        assert_eq!(
+            group.mirror_scope(radio, BroadcastScope::ExceptChannel(lte_channel)),
+            Some(BroadcastScope::ExceptChannel(lte_channel))
+        );
+    }
+}
@@ -7,7 +7,49 @@
 //! receives a message from one of its clients, then this message will be forwarded to all other
 //! clients of this server and all other nodes.
 
+mod bandwidth;
 mod base;
+mod bridge;
+mod dedup;
+mod failover;
+mod filter;
+mod handle;
+mod heartbeats;
+mod mirror;
+mod options;
+mod pin;
+mod resequence;
+mod routing;
+#[cfg(feature = "scripting")]
+mod script;
+mod suppression;
+mod tap;
+mod telemetry;
+mod translation;
 pub(crate) mod types;
 
+pub use bandwidth::BandwidthLimit;
+pub(crate) use bandwidth::BandwidthTracker;
 pub use base::Network;
+pub use bridge::VersionBridge;
+pub(crate) use dedup::FrameDeduplicator;
+pub use failover::FailoverGroup;
+pub use filter::ConnectionFilter;
+pub(crate) use handle::NetworkCommand;
+pub use handle::NetworkHandle;
+pub use heartbeats::HeartbeatToggle;
+pub use mirror::MirrorGroup;
+pub use options::ConnectionOptions;
+pub(crate) use options::NodeOptions;
+pub use pin::VersionPin;
+pub(crate) use resequence::ResequenceTracker;
+pub use resequence::Resequencer;
+pub use routing::{Route, RoutingMode, RoutingTable};
+#[cfg(feature = "scripting")]
+pub use script::{FrameScript, ScriptContext, ScriptVerdict};
+pub use suppression::ForwardSuppression;
+pub(crate) use tap::{InjectionTargets, NetworkInjectors, NetworkTap};
+pub use tap::{TapDirection, TappedFrame};
+pub use telemetry::TelemetryPolicy;
+pub(crate) use telemetry::TelemetryTracker;
+pub use translation::{SysIdTranslation, TranslationEntry};
@@ -0,0 +1,306 @@
+use std::time::Duration;
+
+use crate::core::io::{ConnectionConf, ConnectionInfo, RetryStrategy};
+use crate::core::marker::{HasConnConf, MaybeConnConf, NodeKind};
+use crate::core::network::{
+    BandwidthLimit, ConnectionFilter, FailoverGroup, ForwardSuppression, HeartbeatToggle,
+    MirrorGroup, Resequencer, RoutingMode, RoutingTable, SysIdTranslation, TelemetryPolicy,
+    VersionBridge, VersionPin,
+};
+use crate::core::node::IntoNodeConf;
+use crate::core::utils::UniqueId;
+use crate::protocol::{IdRemapper, MessageId};
+
+use crate::prelude::*;
+
+/// Options of a [`Network`] connection.
+///
+/// Returned by [`Network::add_node`] and `add_connection` of a synchronous or asynchronous
+/// network. Options are applied to the node, that was added last, and can be combined with each
+/// other. Methods, that configure the network itself, or add other nodes, are available as well,
+/// so connections can be added and configured in a single chain. Options can be converted back
+/// to a [`Network`].
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use maviola::core::io::RetryStrategy;
+/// use maviola::core::network::{BandwidthLimit, ConnectionFilter};
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<Versionless>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             // Radio link pinned to `MAVLink 1` with limited bandwidth, that carries heartbeats only
+///             .add_connection(UdpClient::new("192.168.1.1:14550").unwrap())
+///             .version(V1)
+///             .limit(BandwidthLimit::new().with_frames_per_sec(10.0, 5))
+///             .filter(ConnectionFilter::new().allow_message_ids([0]))
+///             .retry(RetryStrategy::Always(Duration::from_secs(1)))
+///     )
+///     .build().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ConnectionOptions<V: MaybeVersioned, C: MaybeConnConf> {
+    network: Network<V, C>,
+    id: UniqueId,
+}
+
+/// <sup>⛔</sup>
+/// Options of a network node stored by a [`Network`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeOptions {
+    pub(crate) standby: bool,
+    pub(crate) failover: Option<FailoverGroup>,
+    pub(crate) mirror: Option<MirrorGroup>,
+    pub(crate) filter: Option<ConnectionFilter>,
+    pub(crate) telemetry: Option<TelemetryPolicy>,
+    pub(crate) limit: Option<BandwidthLimit>,
+    pub(crate) bridge: Option<VersionBridge>,
+    pub(crate) pin: Option<VersionPin>,
+    pub(crate) translation: Option<SysIdTranslation>,
+    pub(crate) remapper: Option<IdRemapper>,
+    pub(crate) heartbeats: Option<HeartbeatToggle>,
+    pub(crate) suppression: Option<ForwardSuppression>,
+    pub(crate) resequencer: Option<Resequencer>,
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> ConnectionOptions<V, C> {
+    pub(crate) fn new(network: Network<V, C>, id: UniqueId) -> Self {
+        Self { network, id }
+    }
+
+    /// Makes connection a warm standby.
+    ///
+    /// Standby node is started together with the network: its connection is established, and it
+    /// receives heartbeats from the network. However, it is excluded from routing: other outgoing
+    /// frames are not sent to a standby node, and frames received by it are ignored.
+    ///
+    /// Once one of the active nodes goes down, the network activates the earliest added standby
+    /// node. Activated node stays active even if the failed node is later restored according to
+    /// the [`Network::retry`] strategy. Since the connection is already established, this reduces
+    /// failover time compared to adding a spare connection only after the primary one is lost.
+    pub fn standby(mut self) -> Self {
+        self.options().standby = true;
+        self
+    }
+
+    /// Filters connection frames by a [`ConnectionFilter`].
+    ///
+    /// Frames received by this node, that are rejected by the `filter`, won't be routed to the
+    /// network. Frames routed by the network to this node are sent only if they pass the `filter`.
+    pub fn filter(mut self, filter: ConnectionFilter) -> Self {
+        self.options().filter = Some(filter);
+        self
+    }
+
+    /// Adapts outgoing telemetry to link quality by a [`TelemetryPolicy`].
+    ///
+    /// Frames received by this node are used to estimate link quality, while frames routed by the
+    /// network to this node are dropped, if they exceed rates allowed by the `policy`.
+    pub fn telemetry(mut self, policy: TelemetryPolicy) -> Self {
+        self.options().telemetry = Some(policy);
+        self
+    }
+
+    /// Restricts outgoing traffic by a [`BandwidthLimit`].
+    ///
+    /// Frames routed by the network to this node are dropped, if they exceed the `limit`. Counters
+    /// of sent and dropped frames are available from clones of the `limit`.
+    pub fn limit(mut self, limit: BandwidthLimit) -> Self {
+        self.options().limit = Some(limit);
+        self
+    }
+
+    /// Converts outgoing frames by a [`VersionBridge`].
+    ///
+    /// Frames routed by the network to this node are upgraded or downgraded to the protocol
+    /// version of the `bridge`. Frames, that can't be converted, are dropped. Network should be
+    /// [`Versionless`] to carry frames of both protocol versions.
+    pub fn bridge(mut self, bridge: VersionBridge) -> Self {
+        self.options().bridge = Some(bridge);
+        self
+    }
+
+    /// Pins connection to a particular MAVLink protocol version by a [`VersionPin`].
+    ///
+    /// Frames of other protocol versions received by this node are not routed to the network, and
+    /// frames of other versions routed by the network to this node are not sent. Depending on the
+    /// `pin`, such frames are either rejected or converted. Network should be [`Versionless`] to
+    /// carry frames of both protocol versions.
+    pub fn pin(mut self, pin: VersionPin) -> Self {
+        self.options().pin = Some(pin);
+        self
+    }
+
+    /// Pins connection to a particular MAVLink protocol `version`.
+    ///
+    /// Frames of other protocol versions are rejected in both directions. A shorthand for
+    /// [`pin`](Self::pin) with a strict [`VersionPin::new`]. Use [`pin`](Self::pin) directly to
+    /// convert frames instead or to count rejected frames.
+    pub fn version<Version: Versioned>(self, version: Version) -> Self {
+        self.pin(VersionPin::new(version))
+    }
+
+    /// Translates system `ID`s of the connection by a [`SysIdTranslation`].
+    ///
+    /// Systems connected through this node are visible to the network under virtual system `ID`s,
+    /// and frames routed by the network to virtual systems are addressed to the real ones.
+    pub fn translation(mut self, translation: SysIdTranslation) -> Self {
+        self.options().translation = Some(translation);
+        self
+    }
+
+    /// Rewrites connection frames by an [`IdRemapper`].
+    ///
+    /// Frames received from this node and frames routed by the network to this node get system
+    /// and component `ID`s rewritten according to the mapping table of the `remapper`.
+    pub fn remapper(mut self, remapper: IdRemapper) -> Self {
+        self.options().remapper = Some(remapper);
+        self
+    }
+
+    /// Sends automatic heartbeats to the connection only while `heartbeats` toggle is enabled.
+    ///
+    /// Heartbeats emitted by the node, that uses this network as a connection, and its components
+    /// are not sent to this node, while toggle is disabled. See [`HeartbeatToggle`] for details.
+    pub fn heartbeats(mut self, heartbeats: HeartbeatToggle) -> Self {
+        self.options().heartbeats = Some(heartbeats);
+        self
+    }
+
+    /// Doesn't send forwarded frames suppressed by a [`ForwardSuppression`].
+    ///
+    /// Frames received by this node are still passed to the network. See [`ForwardSuppression`]
+    /// for details.
+    pub fn suppression(mut self, suppression: ForwardSuppression) -> Self {
+        self.options().suppression = Some(suppression);
+        self
+    }
+
+    /// Rewrites sequence numbers of outgoing frames by a [`Resequencer`].
+    ///
+    /// Frames routed by the network to this node get continuous sequence numbers for each sender,
+    /// even if they were received from different connections. Signed frames are signed again by
+    /// the signer of this node. See [`Resequencer`] for details.
+    pub fn resequencer(mut self, resequencer: Resequencer) -> Self {
+        self.options().resequencer = Some(resequencer);
+        self
+    }
+
+    /// Adds node configuration to the network.
+    ///
+    /// See [`Network::add_node`].
+    pub fn add_node<K: NodeKind>(self, node: impl IntoNodeConf<K, V, C>) -> Self {
+        self.network.add_node(node)
+    }
+
+    /// Sets the maximum age of outgoing frames with specified `message_id`.
+    ///
+    /// See [`Network::max_frame_age`].
+    pub fn max_frame_age(self, message_id: MessageId, max_age: Duration) -> Network<V, C> {
+        self.network.max_frame_age(message_id, max_age)
+    }
+
+    /// Drops duplicate incoming frames received within a sliding `window`.
+    ///
+    /// See [`Network::dedup`].
+    pub fn dedup(self, window: Duration) -> Network<V, C> {
+        self.network.dedup(window)
+    }
+
+    /// Defines, how frames are routed between network connections.
+    ///
+    /// See [`Network::routing`].
+    pub fn routing(self, mode: RoutingMode) -> Network<V, C> {
+        self.network.routing(mode)
+    }
+
+    /// Table of routes learned by the network.
+    ///
+    /// See [`Network::routing_table`].
+    pub fn routing_table(&self) -> RoutingTable {
+        self.network.routing_table()
+    }
+
+    /// Defines retry strategy for a network.
+    ///
+    /// See [`Network::retry`].
+    pub fn retry(self, retry: RetryStrategy) -> Network<V, C> {
+        self.network.retry(retry)
+    }
+
+    /// Defines, whether entire network should go down, when one of the nodes is disconnected.
+    ///
+    /// See [`Network::stop_on_node_down`].
+    pub fn stop_on_node_down(self, value: bool) -> Network<V, C> {
+        self.network.stop_on_node_down(value)
+    }
+
+    /// Returns the network, this connection was added to.
+    pub fn network(&self) -> &Network<V, C> {
+        &self.network
+    }
+
+    fn options(&mut self) -> &mut NodeOptions {
+        self.network.options.entry(self.id).or_default()
+    }
+}
+
+impl<V: MaybeVersioned, C: HasConnConf> ConnectionOptions<V, C> {
+    /// Adds connection to a [`FailoverGroup`].
+    ///
+    /// Nodes are prioritized in the order they were added to the `group`. Only the active link of
+    /// the group carries traffic, other members receive only heartbeats, and frames received by
+    /// them are ignored. See [`FailoverGroup`] for details.
+    pub fn failover(mut self, group: FailoverGroup) -> Self {
+        let (id, info) = (self.id, self.conn_info());
+        group.add(id, info);
+        if let Some(previous) = self.options().failover.replace(group) {
+            previous.remove(id);
+        }
+        self
+    }
+
+    /// Adds connection to a [`MirrorGroup`].
+    ///
+    /// Every frame sent to one of the `group` nodes is sent over all of them, and frames received
+    /// by several nodes of the group are passed further only once. See [`MirrorGroup`] for
+    /// details.
+    pub fn mirror(mut self, group: MirrorGroup) -> Self {
+        let (id, info) = (self.id, self.conn_info());
+        group.add(id, info);
+        if let Some(previous) = self.options().mirror.replace(group) {
+            previous.remove(id);
+        }
+        self
+    }
+
+    /// Validates network configuration.
+    ///
+    /// See [`Network::validate`].
+    pub fn validate(&self) -> Result<()> {
+        self.network.validate()
+    }
+
+    fn conn_info(&self) -> ConnectionInfo {
+        self.network.nodes[&self.id].connection_conf.info().clone()
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> From<ConnectionOptions<V, C>> for Network<V, C> {
+    fn from(value: ConnectionOptions<V, C>) -> Self {
+        value.network
+    }
+}
+
+impl<V: MaybeVersioned, C: MaybeConnConf> ConnectionConf for ConnectionOptions<V, C> {
+    fn info(&self) -> &ConnectionInfo {
+        self.network.info()
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::core::network::VersionBridge;
+use crate::protocol::MavLinkVersion;
+
+use crate::prelude::*;
+
+/// Pins a network connection to a particular MAVLink protocol version.
+///
+/// Version pins allow to mix connections of different protocol versions within the same
+/// [`Versionless`] [`Network`]. Frames of the wrong protocol version are stopped at the connection
+/// boundary in both directions:
+///
+/// * Frames received by a pinned connection are not routed to the network.
+/// * Frames routed by the network to a pinned connection are not sent.
+///
+/// A strict pin created by [`VersionPin::new`] rejects such frames. A pin created by
+/// [`VersionPin::converting`] converts them to the pinned version by a [`VersionBridge`] instead
+/// and rejects only those frames, that can't be converted. Rejected frames are logged and counted
+/// by [`VersionPin::rejected`]. Clones of a pin share counters.
+///
+/// Attach pin with [`ConnectionOptions::pin`](crate::core::network::ConnectionOptions::pin), or
+/// pin connection strictly with
+/// [`ConnectionOptions::version`](crate::core::network::ConnectionOptions::version).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::{VersionBridge, VersionPin};
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let legacy = VersionPin::new(V1);
+///
+/// let node = Node::sync::<Versionless>()
+///     .connection(
+///         Network::sync()
+///             // Only `MAVLink 1` frames pass this connection
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .pin(legacy.clone())
+///             // Frames of this connection are converted to `MAVLink 2`
+///             .add_connection(TcpServer::new("127.0.0.1:5601").unwrap())
+///             .pin(VersionPin::converting(VersionBridge::v2::<DefaultDialect>()))
+///     )
+///     .build().unwrap();
+///
+/// println!("rejected frames: {}", legacy.rejected());
+/// ```
+#[derive(Clone)]
+pub struct VersionPin {
+    version: MavLinkVersion,
+    bridge: Option<VersionBridge>,
+    rejected: Arc<AtomicU64>,
+}
+
+impl VersionPin {
+    /// Creates a pin, that rejects frames, which protocol version differs from `version`.
+    pub fn new<V: Versioned>(_version: V) -> Self {
+        Self {
+            version: V::version(),
+            bridge: None,
+            rejected: Default::default(),
+        }
+    }
+
+    /// Creates a pin, that converts frames of other protocol versions by a `bridge`.
+    ///
+    /// Connection is pinned to the [`VersionBridge::version`] of the `bridge`.
+    pub fn converting(bridge: VersionBridge) -> Self {
+        Self {
+            version: bridge.version(),
+            bridge: Some(bridge),
+            rejected: Default::default(),
+        }
+    }
+
+    /// Pinned protocol version.
+    pub fn version(&self) -> MavLinkVersion {
+        self.version
+    }
+
+    /// Bridge, that converts frames to the pinned version, if any.
+    pub fn bridge(&self) -> Option<&VersionBridge> {
+        self.bridge.as_ref()
+    }
+
+    /// Number of frames rejected by this pin.
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// <sup>⛔</sup>
+    /// Applies pin to a frame crossing the connection boundary.
+    ///
+    /// Returns frame of the pinned version or [`None`], if frame was rejected.
+    pub(crate) fn apply<V: MaybeVersioned>(&self, frame: Frame<V>) -> Option<Frame<V>> {
+        if frame.version() == self.version {
+            return Some(frame);
+        }
+
+        let bridge = match &self.bridge {
+            Some(bridge) => bridge,
+            None => {
+                log::trace!(
+                    "frame of protocol version {:?} rejected by {:?} pin: message #{}",
+                    frame.version(),
+                    self.version,
+                    frame.message_id()
+                );
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+
+        match bridge.convert(&frame) {
+            Ok(converted) => Some(converted),
+            Err(err) => {
+                log::trace!("frame rejected by {:?} pin: {err}", self.version);
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+}
+
+impl Debug for VersionPin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VersionPin")
+            .field("version", &self.version)
+            .field("bridge", &self.bridge)
+            .field("rejected", &self.rejected())
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod pin_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::dialects::Minimal;
+    use crate::protocol::{Endpoint, Message};
+
+    fn frame_v1() -> Frame<Versionless> {
+        Endpoint::v1(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap()
+            .into_versionless()
+    }
+
+    fn frame_v2(message: &impl Message) -> Frame<Versionless> {
+        Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(message)
+            .unwrap()
+            .into_versionless()
+    }
+
+    #[test]
+    fn strict_pin_rejects_other_versions() {
+        let pin = VersionPin::new(V1);
+
+        assert!(pin.apply(frame_v1()).is_some());
+        assert!(pin.apply(frame_v2(&Heartbeat::default())).is_none());
+        assert_eq!(pin.clone().rejected(), 1);
+    }
+
+    #[test]
+    fn converting_pin_converts_other_versions() {
+        let pin = VersionPin::converting(VersionBridge::v1::<Minimal>());
+        assert_eq!(pin.version(), MavLinkVersion::V1);
+
+        let converted = pin.apply(frame_v2(&Heartbeat::default())).unwrap();
+        assert_eq!(converted.version(), MavLinkVersion::V1);
+
+        assert!(pin.apply(frame_v2(&ProtocolVersion::default())).is_none());
+        assert_eq!(pin.rejected(), 1);
+        assert_eq!(pin.bridge().unwrap().converted(), 1);
+    }
+}
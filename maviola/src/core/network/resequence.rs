@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::protocol::{CrcExtra, FrameProcessor, MavLinkVersion, Sequence};
+
+use crate::prelude::*;
+
+/// Rewrites sequence numbers of frames sent to a network connection.
+///
+/// When a network merges frames received from several connections into one, frames of the same
+/// system and component may arrive through different links. Such frames have sequence numbers
+/// from independent counters, and recipients (usually ground control stations) that estimate
+/// losses from gaps in sequence numbers report garbage statistics.
+///
+/// Resequencer keeps a separate sequence counter for each sender (system and component `ID`) and
+/// rewrites sequence numbers of frames routed by the network to a particular connection, so each
+/// sender has a continuous sequence on this link. The first frame of a sender keeps its original
+/// sequence number.
+///
+/// Since MAVLink checksum depends on message `CRC_EXTRA`, frames of messages, that are not known to
+/// the connection, are sent unchanged. MAVLink signature covers frame sequence number, so signed
+/// frames are signed again, if message signing is configured for the connection. Otherwise, they
+/// are sent unchanged to preserve their signatures. Frames, that were sent unchanged, are counted
+/// by [`Resequencer::skipped`]. Clones of a resequencer share counters.
+///
+/// Attach resequencer with
+/// [`ConnectionOptions::resequencer`](crate::core::network::ConnectionOptions::resequencer). Each
+/// connection keeps its own sequence counters.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::Resequencer;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let resequencer = Resequencer::new();
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync()
+///             // Vehicle is connected through two redundant links
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .add_connection(UdpServer::new("127.0.0.1:14551").unwrap())
+///             // Ground station sees continuous sequences of vehicle frames
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .resequencer(resequencer.clone())
+///     )
+///     .build().unwrap();
+///
+/// println!("resequenced frames: {}", resequencer.resequenced());
+/// ```
+#[derive(Clone, Default)]
+pub struct Resequencer {
+    counters: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    resequenced: AtomicU64,
+    skipped: AtomicU64,
+}
+
+/// <sup>⛔</sup>
+/// Keeps sequence counters of senders for a [`Resequencer`].
+pub(crate) struct ResequenceTracker {
+    resequencer: Resequencer,
+    sequences: HashMap<MavLinkId, Sequence>,
+}
+
+impl Resequencer {
+    /// Creates a resequencer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of frames with rewritten sequence numbers.
+    pub fn resequenced(&self) -> u64 {
+        self.counters.resequenced.load(Ordering::Relaxed)
+    }
+
+    /// Number of frames, that were sent unchanged, since they can't be rewritten.
+    pub fn skipped(&self) -> u64 {
+        self.counters.skipped.load(Ordering::Relaxed)
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that rewrites sequence numbers of outgoing frames.
+    pub(crate) fn tracker(&self) -> ResequenceTracker {
+        ResequenceTracker {
+            resequencer: self.clone(),
+            sequences: Default::default(),
+        }
+    }
+}
+
+impl Debug for Resequencer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resequencer")
+            .field("resequenced", &self.resequenced())
+            .field("skipped", &self.skipped())
+            .finish()
+    }
+}
+
+impl ResequenceTracker {
+    /// <sup>⛔</sup>
+    /// Rewrites sequence number of a frame, that is about to be sent.
+    ///
+    /// Returns [`None`], if frame should be sent unchanged.
+    pub(crate) fn apply<V: MaybeVersioned>(
+        &mut self,
+        frame: &Frame<V>,
+        processor: &FrameProcessor,
+    ) -> Option<Frame<V>> {
+        let sender = MavLinkId::new(frame.system_id(), frame.component_id());
+        let sequence = match self.sequences.get(&sender) {
+            Some(last) => last.wrapping_add(1),
+            None => frame.sequence(),
+        };
+
+        if frame.sequence() == sequence {
+            self.sequences.insert(sender, sequence);
+            return None;
+        }
+
+        let resequenced = self.rewrite(frame, sequence, processor);
+        let counter = match resequenced {
+            Some(_) => {
+                self.sequences.insert(sender, sequence);
+                &self.resequencer.counters.resequenced
+            }
+            None => &self.resequencer.counters.skipped,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        resequenced
+    }
+
+    fn rewrite<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        sequence: Sequence,
+        processor: &FrameProcessor,
+    ) -> Option<Frame<V>> {
+        let signer = processor.signer();
+        if frame.is_signed() && signer.is_none() {
+            log::trace!(
+                "can't resequence signed frame without signer: message #{}",
+                frame.message_id()
+            );
+            return None;
+        }
+
+        let crc_extra = match processor.crc_extra(frame.message_id()) {
+            Some(crc_extra) => crc_extra,
+            None => {
+                log::trace!(
+                    "can't resequence frame of unknown message #{}",
+                    frame.message_id()
+                );
+                return None;
+            }
+        };
+
+        let mut resequenced = with_sequence(frame, sequence, crc_extra)?;
+        if let (true, Some(signer)) = (frame.is_signed(), signer) {
+            signer.sign_frame(&mut resequenced).ok()?;
+        }
+
+        Some(resequenced)
+    }
+}
+
+/// Rebuilds a frame with a new sequence number.
+fn with_sequence<V: MaybeVersioned>(
+    frame: &Frame<V>,
+    sequence: Sequence,
+    crc_extra: CrcExtra,
+) -> Option<Frame<V>> {
+    let frame = frame.to_versionless();
+
+    let rebuilt = match frame.version() {
+        MavLinkVersion::V1 => frame
+            .try_into_versioned::<V1>()
+            .ok()?
+            .to_builder()
+            .sequence(sequence)
+            .crc_extra(crc_extra)
+            .build()
+            .into_versionless(),
+        MavLinkVersion::V2 => frame
+            .try_into_versioned::<V2>()
+            .ok()?
+            .to_builder()
+            .sequence(sequence)
+            .crc_extra(crc_extra)
+            .build()
+            .into_versionless(),
+    };
+
+    rebuilt.try_into_versioned::<V>().ok()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod resequence_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, FrameSigner};
+
+    fn frames(id: MavLinkId, n: usize) -> Vec<Frame<V2>> {
+        let endpoint = Endpoint::v2(id);
+        (0..n)
+            .map(|_| endpoint.next_frame(&Heartbeat::default()).unwrap())
+            .collect()
+    }
+
+    fn send(
+        tracker: &mut ResequenceTracker,
+        frame: &Frame<V2>,
+        processor: &FrameProcessor,
+    ) -> Frame<V2> {
+        tracker
+            .apply(frame, processor)
+            .unwrap_or_else(|| frame.clone())
+    }
+
+    #[test]
+    fn merged_links_are_resequenced() {
+        let resequencer = Resequencer::new();
+        let mut tracker = resequencer.tracker();
+        let processor = FrameProcessor::builder().build();
+
+        let vehicle = MavLinkId::new(1, 1);
+        let link_a = frames(vehicle, 3);
+        let link_b = frames(vehicle, 3);
+        let other = frames(MavLinkId::new(2, 1), 2);
+
+        let merged = [
+            &link_a[0], &link_b[0], &other[0], &link_a[1], &link_b[1], &other[1], &link_a[2],
+            &link_b[2],
+        ];
+        let sent: Vec<Frame<V2>> = merged
+            .iter()
+            .map(|frame| send(&mut tracker, frame, &processor))
+            .collect();
+
+        let vehicle_sequences: Vec<Sequence> = sent
+            .iter()
+            .filter(|frame| frame.system_id() == 1)
+            .map(|frame| frame.sequence())
+            .collect();
+        assert_eq!(vehicle_sequences, vec![0, 1, 2, 3, 4, 5]);
+
+        let other_sequences: Vec<Sequence> = sent
+            .iter()
+            .filter(|frame| frame.system_id() == 2)
+            .map(|frame| frame.sequence())
+            .collect();
+        assert_eq!(other_sequences, vec![0, 1]);
+
+        assert!(sent
+            .iter()
+            .all(|frame| frame.decode::<crate::dialects::Minimal>().is_ok()));
+        assert_eq!(resequencer.resequenced(), 5);
+        assert_eq!(resequencer.skipped(), 0);
+    }
+
+    #[test]
+    fn signed_frames_are_re_signed() {
+        let resequencer = Resequencer::new();
+        let signer = FrameSigner::new(1, "key");
+
+        let mut signed = frames(MavLinkId::new(1, 1), 2);
+        for frame in signed.iter_mut() {
+            signer.sign_frame(frame).unwrap();
+        }
+
+        // Without signer, signed frames are sent unchanged
+        let mut tracker = resequencer.tracker();
+        let processor = FrameProcessor::builder().build();
+        send(&mut tracker, &signed[1], &processor);
+        assert!(tracker.apply(&signed[0], &processor).is_none());
+        assert_eq!(resequencer.skipped(), 1);
+
+        // With signer, signed frames are signed again
+        let mut tracker = resequencer.tracker();
+        let processor = FrameProcessor::builder().signer(signer.clone()).build();
+        send(&mut tracker, &signed[1], &processor);
+        let frame = tracker.apply(&signed[0], &processor).unwrap();
+        assert_eq!(frame.sequence(), 2);
+        assert!(signer.has_valid_signature(&frame));
+        assert_eq!(resequencer.resequenced(), 1);
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use crate::core::io::ConnectionInfo;
+use crate::core::utils::UniqueId;
+use crate::protocol::TargetFields;
+
+use crate::prelude::*;
+
+/// Defines, how a [`Network`] routes frames between its connections.
+///
+/// Set by [`Network::routing`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoutingMode {
+    /// Every frame is sent to all connections except the one, it was received from (default).
+    #[default]
+    Broadcast,
+    /// Addressed frames are sent only to connections, where their target was seen.
+    ///
+    /// Frames of messages with `target_system` / `target_component` fields (see [`TargetFields`])
+    /// are delivered only to connections, that received heartbeats from the target. If target
+    /// component was never seen, then frame is sent to connections of its system. Frames
+    /// addressed to unknown systems are dropped, as required by MAVLink
+    /// [routing](https://mavlink.io/en/guide/routing.html) rules.
+    ///
+    /// Broadcast frames (with target system `0`) and frames without target fields are sent to all
+    /// connections.
+    TargetAware,
+}
+
+/// Table of MAVLink components seen by connections of a [`Network`].
+///
+/// Network learns routes from heartbeats received by its connections. Routes of a connection are
+/// removed, once it is stopped. The table is used to route addressed frames, when network uses
+/// [`RoutingMode::TargetAware`]. However, routes are learned regardless of the routing mode.
+///
+/// Clones of the table share the same routes. Obtain the table with [`Network::routing_table`]
+/// before passing the network to a node to inspect its [`routes`](Self::routes) while the network
+/// is running.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::RoutingMode;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let network = Network::sync()
+///     .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///     .routing(RoutingMode::TargetAware);
+/// let routing_table = network.routing_table();
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(network)
+///     .build().unwrap();
+///
+/// for route in routing_table.routes() {
+///     println!("{:?} is reachable through {:?}", route.id(), route.connection());
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct RoutingTable {
+    inner: Arc<RwLock<HashMap<UniqueId, ConnectionRoutes>>>,
+}
+
+/// Route to a MAVLink component within a [`Network`].
+#[derive(Clone, Debug)]
+pub struct Route {
+    id: MavLinkId,
+    connection: ConnectionInfo,
+    last_seen: Instant,
+}
+
+struct ConnectionRoutes {
+    connection: ConnectionInfo,
+    peers: HashMap<MavLinkId, Instant>,
+}
+
+impl RoutingTable {
+    /// All known routes.
+    pub fn routes(&self) -> Vec<Route> {
+        let inner = match self.inner.read() {
+            Ok(inner) => inner,
+            Err(_) => return Vec::new(),
+        };
+
+        inner
+            .values()
+            .flat_map(|routes| {
+                routes.peers.iter().map(|(id, last_seen)| Route {
+                    id: *id,
+                    connection: routes.connection.clone(),
+                    last_seen: *last_seen,
+                })
+            })
+            .collect()
+    }
+
+    /// Connections, where component with specified `id` was seen.
+    pub fn connections_of(&self, id: MavLinkId) -> Vec<ConnectionInfo> {
+        let inner = match self.inner.read() {
+            Ok(inner) => inner,
+            Err(_) => return Vec::new(),
+        };
+
+        inner
+            .values()
+            .filter(|routes| routes.peers.contains_key(&id))
+            .map(|routes| routes.connection.clone())
+            .collect()
+    }
+
+    /// <sup>⛔</sup>
+    /// Records, that component with specified `id` was seen by a network `node`.
+    pub(crate) fn observe(&self, node: UniqueId, connection: &ConnectionInfo, id: MavLinkId) {
+        if let Ok(mut inner) = self.inner.write() {
+            let routes = inner.entry(node).or_insert_with(|| ConnectionRoutes {
+                connection: connection.clone(),
+                peers: HashMap::new(),
+            });
+            // Node connection changes, when node is restarted
+            routes.connection = connection.clone();
+            routes.peers.insert(id, Instant::now());
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Removes all routes of a network `node`.
+    pub(crate) fn forget(&self, node: UniqueId) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.remove(&node);
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true`, if frame should be routed to a network `node`.
+    pub(crate) fn should_route<V: MaybeVersioned>(&self, node: UniqueId, frame: &Frame<V>) -> bool {
+        let target = match TargetFields::frame_target(frame) {
+            Some(target) if target.system != 0 => target,
+            _ => return true,
+        };
+
+        let inner = match self.inner.read() {
+            Ok(inner) => inner,
+            Err(_) => return true,
+        };
+        let routes = match inner.get(&node) {
+            Some(routes) => routes,
+            None => return false,
+        };
+
+        if target.component != 0 {
+            if routes.peers.contains_key(&target) {
+                return true;
+            }
+            if inner
+                .values()
+                .any(|other| other.peers.contains_key(&target))
+            {
+                return false;
+            }
+        }
+
+        routes.peers.keys().any(|id| id.system == target.system)
+    }
+}
+
+impl Debug for RoutingTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutingTable")
+            .field("routes", &self.routes())
+            .finish()
+    }
+}
+
+impl Route {
+    /// MAVLink `ID` of a component.
+    pub fn id(&self) -> MavLinkId {
+        self.id
+    }
+
+    /// Connection, through which the component is reachable.
+    pub fn connection(&self) -> &ConnectionInfo {
+        &self.connection
+    }
+
+    /// Instant, when the last heartbeat from the component was received.
+    pub fn last_seen(&self) -> Instant {
+        self.last_seen
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod routing_tests {
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn addressed_frames_are_routed_to_seen_targets() {
+        use crate::core::io::ConnectionDetails;
+        use crate::dialects::common::messages::ParamRequestList;
+
+        let table = RoutingTable::default();
+        let (vehicles, cameras) = (UniqueId::new(), UniqueId::new());
+        let connection = ConnectionInfo::new(ConnectionDetails::Unknown);
+
+        table.observe(vehicles, &connection, MavLinkId::new(1, 1));
+        table.observe(vehicles, &connection, MavLinkId::new(2, 1));
+        table.observe(cameras, &connection, MavLinkId::new(1, 100));
+
+        let gcs = Endpoint::v2(MavLinkId::new(255, 190));
+        let request = |target_system, target_component| {
+            gcs.next_frame(&ParamRequestList {
+                target_system,
+                target_component,
+            })
+            .unwrap()
+        };
+
+        // Exact component
+        assert!(table.should_route(vehicles, &request(1, 1)));
+        assert!(!table.should_route(cameras, &request(1, 1)));
+        assert!(table.should_route(cameras, &request(1, 100)));
+        assert!(!table.should_route(vehicles, &request(1, 100)));
+
+        // Any component of a system
+        assert!(table.should_route(vehicles, &request(1, 0)));
+        assert!(table.should_route(cameras, &request(1, 0)));
+
+        // Unknown component of a known system
+        assert!(table.should_route(vehicles, &request(2, 42)));
+        assert!(!table.should_route(cameras, &request(2, 42)));
+
+        // Unknown system
+        assert!(!table.should_route(vehicles, &request(3, 1)));
+
+        // Broadcast
+        assert!(table.should_route(cameras, &request(0, 0)));
+
+        table.forget(cameras);
+        assert!(!table.should_route(cameras, &request(1, 100)));
+        assert_eq!(table.connections_of(MavLinkId::new(1, 100)).len(), 0);
+        assert_eq!(table.routes().len(), 2);
+    }
+
+    #[test]
+    fn frames_without_targets_are_broadcast() {
+        let table = RoutingTable::default();
+        let frame = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+
+        assert!(table.should_route(UniqueId::new(), &frame));
+    }
+}
@@ -0,0 +1,864 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionDetails, ConnectionInfo};
+use crate::error::ScriptError;
+
+use crate::prelude::*;
+
+/// <sup>`scripting`</sup>
+/// Routing script evaluated for each frame passing through a connection of a [`Network`].
+///
+/// Scripts allow operators to define routing and filtering policies in configuration files instead
+/// of code. A script is compiled once and then evaluated for every frame. Evaluation is sandboxed:
+/// scripts can only inspect frame header and connection properties and return a
+/// [`ScriptVerdict`]. There are no loops, assignments or side effects, and all type errors are
+/// reported at compile time.
+///
+/// Attach scripts to network connections by
+/// [`ConnectionFilter::script`](crate::core::network::ConnectionFilter::script).
+///
+/// # Syntax
+///
+/// Script consists of rules, one per line. Empty lines and everything after `#` outside of string
+/// literals are ignored. Each rule is either `accept` or `drop`, optionally followed by `if` and a
+/// condition. Rules are checked from top to bottom, the first rule with a satisfied condition (or
+/// without a condition at all) defines the verdict. If no rule matches, then frame is accepted.
+///
+/// Conditions are composed of comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`), membership checks
+/// (`in [a, b, c]`), boolean operators (`and`, `or`, `not`), and parentheses. Literals are
+/// integers (decimal or hexadecimal with `0x` prefix), strings in double quotes, `true` and
+/// `false`. Conditions can't be nested deeper than 64 levels. The following variables are
+/// available:
+///
+/// | Variable       | Type    | Description                                                    |
+/// |----------------|---------|----------------------------------------------------------------|
+/// | `message_id`   | integer | Message `ID`.                                                  |
+/// | `system_id`    | integer | System `ID` of the frame sender.                               |
+/// | `component_id` | integer | Component `ID` of the frame sender.                            |
+/// | `sequence`     | integer | Frame sequence number.                                         |
+/// | `version`      | integer | MAVLink protocol version (`1` or `2`).                         |
+/// | `signed`       | boolean | Whether frame is signed.                                       |
+/// | `incoming`     | boolean | Frame was received by this connection.                         |
+/// | `outgoing`     | boolean | Frame is routed to this connection by the network.             |
+/// | `connection`   | string  | Kind of the connection, such as `"tcp_server"` or `"serial"`.  |
+/// | `peer`         | string  | Address or path of the remote side of a channel (if known).    |
+///
+/// The `peer` variable is available only for incoming frames. For outgoing frames it is always
+/// an empty string, since frame may be sent to several channels at once.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::network::FrameScript;
+///
+/// let script = FrameScript::parse(r#"
+///     ## Drop frames of unknown systems coming from the outside
+///     drop if incoming and connection == "udp_server" and not system_id in [1, 2, 255]
+///     ## Do not forward high-rate telemetry to the ground station link
+///     drop if outgoing and message_id in [30, 31, 32] # ATTITUDE, ATTITUDE_QUATERNION, LOCAL_POSITION_NED
+///     accept
+/// "#).unwrap();
+/// ```
+///
+/// Load a script from a file and attach it to a network connection:
+///
+/// ```rust,no_run
+/// use maviola::core::network::{ConnectionFilter, FrameScript};
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .filter(
+///                 ConnectionFilter::new()
+///                     .script(FrameScript::load("/etc/maviola/gcs.rules").unwrap()),
+///             )
+///     )
+///     .build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FrameScript {
+    rules: Arc<[Rule]>,
+}
+
+/// <sup>`scripting`</sup>
+/// Verdict of a [`FrameScript`] for a particular frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScriptVerdict {
+    /// Frame should be routed.
+    #[default]
+    Accept,
+    /// Frame should be dropped.
+    Drop,
+}
+
+/// <sup>`scripting`</sup>
+/// Connection properties available to a [`FrameScript`] during evaluation.
+#[derive(Copy, Clone, Debug)]
+pub struct ScriptContext<'a> {
+    incoming: bool,
+    connection: &'a ConnectionDetails,
+    channel: Option<&'a ChannelDetails>,
+}
+
+impl FrameScript {
+    /// Compiles script from its `source`.
+    ///
+    /// Returns [`Error::Script`] if script has syntax errors, unknown variables, or compares
+    /// values of different types.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for (idx, line) in source.lines().enumerate() {
+            let line_no = idx + 1;
+
+            let tokens = tokenize(line).map_err(|message| ScriptError {
+                line: line_no,
+                message,
+            })?;
+            if tokens.is_empty() {
+                continue;
+            }
+
+            let rule = Parser::new(tokens).rule().map_err(|message| ScriptError {
+                line: line_no,
+                message,
+            })?;
+            rules.push(rule);
+        }
+
+        Ok(Self {
+            rules: rules.into(),
+        })
+    }
+
+    /// Loads and compiles script from a file at the specified `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(&source)
+    }
+
+    /// Number of rules in this script.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true`, if script has no rules and therefore accepts all frames.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluates script for a `frame` within the specified `context`.
+    pub fn evaluate<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        context: &ScriptContext,
+    ) -> ScriptVerdict {
+        let scope = Scope { frame, context };
+
+        for rule in self.rules.iter() {
+            let matches = match &rule.condition {
+                Some(condition) => condition.eval(&scope),
+                None => true,
+            };
+            if matches {
+                return rule.verdict;
+            }
+        }
+
+        ScriptVerdict::Accept
+    }
+}
+
+impl<'a> ScriptContext<'a> {
+    /// Creates context for a frame received by a `channel` of a `connection`.
+    pub fn incoming(connection: &'a ConnectionInfo, channel: &'a ChannelInfo) -> Self {
+        Self {
+            incoming: true,
+            connection: connection.details(),
+            channel: Some(channel.details()),
+        }
+    }
+
+    /// Creates context for a frame, that is about to be sent to a `connection`.
+    pub fn outgoing(connection: &'a ConnectionInfo) -> Self {
+        Self {
+            incoming: false,
+            connection: connection.details(),
+            channel: None,
+        }
+    }
+
+    fn connection_kind(&self) -> &'static str {
+        match self.connection {
+            ConnectionDetails::TcpServer { .. } => "tcp_server",
+            ConnectionDetails::TcpClient { .. } => "tcp_client",
+            ConnectionDetails::UdpServer { .. } => "udp_server",
+            ConnectionDetails::UdpClient { .. } => "udp_client",
+            ConnectionDetails::FileWriter { .. } => "file_writer",
+            ConnectionDetails::FileReader { .. } => "file_reader",
+            ConnectionDetails::TlogReader { .. } => "tlog_reader",
+            #[cfg(unix)]
+            ConnectionDetails::SockServer { .. } => "sock_server",
+            #[cfg(unix)]
+            ConnectionDetails::SockClient { .. } => "sock_client",
+            #[cfg(all(feature = "ipc", unix))]
+            ConnectionDetails::IpcServer { .. } => "ipc_server",
+            #[cfg(all(feature = "ipc", unix))]
+            ConnectionDetails::IpcClient { .. } => "ipc_client",
+            #[cfg(feature = "serial")]
+            ConnectionDetails::SerialPort { .. } => "serial",
+            #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+            ConnectionDetails::BluetoothClient { .. } => "bluetooth_client",
+            #[cfg(feature = "zmq")]
+            ConnectionDetails::ZmqPub { .. } => "zmq_pub",
+            #[cfg(feature = "zmq")]
+            ConnectionDetails::ZmqSub { .. } => "zmq_sub",
+            #[cfg(feature = "mqtt")]
+            ConnectionDetails::MqttBridge { .. } => "mqtt_bridge",
+            #[cfg(feature = "nats")]
+            ConnectionDetails::NatsBridge { .. } => "nats_bridge",
+            #[cfg(feature = "redis")]
+            ConnectionDetails::RedisBridge { .. } => "redis_bridge",
+            ConnectionDetails::Network => "network",
+            #[cfg(feature = "unstable")]
+            ConnectionDetails::Custom { .. } => "custom",
+            ConnectionDetails::Unknown => "unknown",
+        }
+    }
+
+    fn peer(&self) -> String {
+        match self.channel {
+            Some(ChannelDetails::TcpServer { peer_addr, .. })
+            | Some(ChannelDetails::UdpServer { peer_addr, .. }) => peer_addr.to_string(),
+            Some(ChannelDetails::TcpClient { server_addr })
+            | Some(ChannelDetails::UdpClient { server_addr, .. }) => server_addr.to_string(),
+            Some(ChannelDetails::FileWriter { path })
+            | Some(ChannelDetails::FileReader { path })
+            | Some(ChannelDetails::TlogReader { path }) => path.display().to_string(),
+            #[cfg(unix)]
+            Some(ChannelDetails::SockServer { path })
+            | Some(ChannelDetails::SockClient { path }) => path.display().to_string(),
+            #[cfg(all(feature = "ipc", unix))]
+            Some(ChannelDetails::IpcServer { path }) | Some(ChannelDetails::IpcClient { path }) => {
+                path.display().to_string()
+            }
+            #[cfg(feature = "serial")]
+            Some(ChannelDetails::SerialPort { path, .. }) => path.display().to_string(),
+            #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+            Some(ChannelDetails::BluetoothClient { addr, .. }) => addr.to_string(),
+            #[cfg(feature = "zmq")]
+            Some(ChannelDetails::ZmqPub { peer_addr, .. }) => peer_addr.to_string(),
+            #[cfg(feature = "zmq")]
+            Some(ChannelDetails::ZmqSub { server_addr, .. }) => server_addr.to_string(),
+            #[cfg(feature = "mqtt")]
+            Some(ChannelDetails::MqttBridge { broker_addr, .. }) => broker_addr.to_string(),
+            #[cfg(feature = "nats")]
+            Some(ChannelDetails::NatsBridge { server_addr, .. }) => server_addr.to_string(),
+            #[cfg(feature = "redis")]
+            Some(ChannelDetails::RedisBridge { server_addr, .. }) => server_addr.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                Internals                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+struct Rule {
+    verdict: ScriptVerdict,
+    condition: Option<Expr>,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CmpOp, Operand),
+    In(Operand, Vec<Operand>),
+    Operand(Operand),
+}
+
+#[derive(Debug)]
+enum Operand {
+    Var(Var),
+    Const(Value),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Var {
+    MessageId,
+    SystemId,
+    ComponentId,
+    Sequence,
+    Version,
+    Signed,
+    Incoming,
+    Outgoing,
+    Connection,
+    Peer,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
+enum Value {
+    Int(i64),
+    Bool(bool),
+    Str(String),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Type {
+    Int,
+    Bool,
+    Str,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Cmp(CmpOp),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+struct Scope<'a, V: MaybeVersioned> {
+    frame: &'a Frame<V>,
+    context: &'a ScriptContext<'a>,
+}
+
+impl Expr {
+    fn eval<V: MaybeVersioned>(&self, scope: &Scope<V>) -> bool {
+        match self {
+            Expr::Or(left, right) => left.eval(scope) || right.eval(scope),
+            Expr::And(left, right) => left.eval(scope) && right.eval(scope),
+            Expr::Not(expr) => !expr.eval(scope),
+            Expr::Compare(left, op, right) => {
+                let (left, right) = (left.eval(scope), right.eval(scope));
+                match op {
+                    CmpOp::Eq => left == right,
+                    CmpOp::Ne => left != right,
+                    CmpOp::Lt => left < right,
+                    CmpOp::Le => left <= right,
+                    CmpOp::Gt => left > right,
+                    CmpOp::Ge => left >= right,
+                }
+            }
+            Expr::In(operand, list) => {
+                let value = operand.eval(scope);
+                list.iter().any(|item| item.eval(scope) == value)
+            }
+            Expr::Operand(operand) => operand.eval(scope) == Value::Bool(true),
+        }
+    }
+}
+
+impl Operand {
+    fn eval<V: MaybeVersioned>(&self, scope: &Scope<V>) -> Value {
+        let frame = scope.frame;
+        match self {
+            Operand::Const(value) => value.clone(),
+            Operand::Var(var) => match var {
+                Var::MessageId => Value::Int(frame.message_id() as i64),
+                Var::SystemId => Value::Int(frame.system_id() as i64),
+                Var::ComponentId => Value::Int(frame.component_id() as i64),
+                Var::Sequence => Value::Int(frame.sequence() as i64),
+                Var::Version => Value::Int(match frame.version() {
+                    MavLinkVersion::V1 => 1,
+                    MavLinkVersion::V2 => 2,
+                }),
+                Var::Signed => Value::Bool(frame.is_signed()),
+                Var::Incoming => Value::Bool(scope.context.incoming),
+                Var::Outgoing => Value::Bool(!scope.context.incoming),
+                Var::Connection => Value::Str(scope.context.connection_kind().to_string()),
+                Var::Peer => Value::Str(scope.context.peer()),
+            },
+        }
+    }
+
+    fn ty(&self) -> Type {
+        match self {
+            Operand::Const(Value::Int(_)) => Type::Int,
+            Operand::Const(Value::Bool(_)) => Type::Bool,
+            Operand::Const(Value::Str(_)) => Type::Str,
+            Operand::Var(var) => match var {
+                Var::MessageId
+                | Var::SystemId
+                | Var::ComponentId
+                | Var::Sequence
+                | Var::Version => Type::Int,
+                Var::Signed | Var::Incoming | Var::Outgoing => Type::Bool,
+                Var::Connection | Var::Peer => Type::Str,
+            },
+        }
+    }
+}
+
+impl Var {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "message_id" => Var::MessageId,
+            "system_id" => Var::SystemId,
+            "component_id" => Var::ComponentId,
+            "sequence" => Var::Sequence,
+            "version" => Var::Version,
+            "signed" => Var::Signed,
+            "incoming" => Var::Incoming,
+            "outgoing" => Var::Outgoing,
+            "connection" => Var::Connection,
+            "peer" => Var::Peer,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Type::Int => "integer",
+            Type::Bool => "boolean",
+            Type::Str => "string",
+        })
+    }
+}
+
+fn tokenize(code: &str) -> core::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = code.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ch if ch.is_whitespace() => {
+                chars.next();
+            }
+            '#' => break,
+            '(' | ')' | '[' | ']' | ',' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    _ => Token::Comma,
+                });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let with_eq = chars.next_if_eq(&'=').is_some();
+                tokens.push(Token::Cmp(match (ch, with_eq) {
+                    ('=', true) => CmpOp::Eq,
+                    ('!', true) => CmpOp::Ne,
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::Le,
+                    ('>', false) => CmpOp::Gt,
+                    ('>', true) => CmpOp::Ge,
+                    _ => return Err(format!("unexpected character '{ch}'")),
+                }));
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(ch) => value.push(ch),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            ch if ch.is_ascii_alphanumeric() || ch == '_' => {
+                let mut word = String::new();
+                while let Some(ch) = chars.next_if(|ch| ch.is_ascii_alphanumeric() || *ch == '_') {
+                    word.push(ch);
+                }
+                tokens.push(if ch.is_ascii_digit() {
+                    Token::Int(parse_int(&word)?)
+                } else {
+                    Token::Ident(word)
+                });
+            }
+            _ => return Err(format!("unexpected character '{ch}'")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_int(word: &str) -> core::result::Result<i64, String> {
+    let parsed = match word.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => word.parse(),
+    };
+    parsed.map_err(|_| format!("invalid integer '{word}'"))
+}
+
+/// Maximum nesting depth of conditions, that keeps recursive descent away from stack overflow.
+const MAX_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn rule(mut self) -> core::result::Result<Rule, String> {
+        let verdict = match self.next() {
+            Some(Token::Ident(word)) if word == "accept" => ScriptVerdict::Accept,
+            Some(Token::Ident(word)) if word == "drop" => ScriptVerdict::Drop,
+            _ => return Err("rule should start with 'accept' or 'drop'".to_string()),
+        };
+
+        let condition = match self.next() {
+            None => None,
+            Some(Token::Ident(word)) if word == "if" => {
+                let condition = self.or()?;
+                if let Some(token) = self.peek() {
+                    return Err(format!("unexpected {token:?} after condition"));
+                }
+                Some(condition)
+            }
+            Some(token) => return Err(format!("expected 'if', got {token:?}")),
+        };
+
+        Ok(Rule { verdict, condition })
+    }
+
+    fn or(&mut self) -> core::result::Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.next_if_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> core::result::Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.next_if_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> core::result::Result<Expr, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!(
+                "condition is nested deeper than {MAX_DEPTH} levels"
+            ));
+        }
+        self.depth += 1;
+        let expr = self.nested();
+        self.depth -= 1;
+        expr
+    }
+
+    fn nested(&mut self) -> core::result::Result<Expr, String> {
+        if self.next_if_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+
+        if self.peek() == Some(&Token::LParen) {
+            self.next();
+            let expr = self.or()?;
+            return match self.next() {
+                Some(Token::RParen) => Ok(expr),
+                _ => Err("expected ')'".to_string()),
+            };
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> core::result::Result<Expr, String> {
+        let left = self.operand()?;
+
+        if let Some(Token::Cmp(op)) = self.peek() {
+            let op = *op;
+            self.next();
+            let right = self.operand()?;
+
+            if left.ty() != right.ty() {
+                return Err(format!("can't compare {} with {}", left.ty(), right.ty()));
+            }
+            if left.ty() != Type::Int && !matches!(op, CmpOp::Eq | CmpOp::Ne) {
+                return Err(format!("{} values can't be ordered", left.ty()));
+            }
+
+            return Ok(Expr::Compare(left, op, right));
+        }
+
+        if self.next_if_keyword("in") {
+            let list = self.list()?;
+            if let Some(item) = list.iter().find(|item| item.ty() != left.ty()) {
+                return Err(format!("can't compare {} with {}", left.ty(), item.ty()));
+            }
+            return Ok(Expr::In(left, list));
+        }
+
+        if left.ty() != Type::Bool {
+            return Err(format!("expected boolean condition, got {}", left.ty()));
+        }
+        Ok(Expr::Operand(left))
+    }
+
+    fn operand(&mut self) -> core::result::Result<Operand, String> {
+        match self.next() {
+            Some(Token::Int(value)) => Ok(Operand::Const(Value::Int(value))),
+            Some(Token::Str(value)) => Ok(Operand::Const(Value::Str(value))),
+            Some(Token::Ident(word)) => match word.as_str() {
+                "true" => Ok(Operand::Const(Value::Bool(true))),
+                "false" => Ok(Operand::Const(Value::Bool(false))),
+                name => match Var::from_name(name) {
+                    Some(var) => Ok(Operand::Var(var)),
+                    None => Err(format!("unknown variable '{name}'")),
+                },
+            },
+            Some(token) => Err(format!("expected value, got {token:?}")),
+            None => Err("unexpected end of rule".to_string()),
+        }
+    }
+
+    fn list(&mut self) -> core::result::Result<Vec<Operand>, String> {
+        match self.next() {
+            Some(Token::LBracket) => {}
+            _ => return Err("expected '[' after 'in'".to_string()),
+        }
+
+        let mut items = Vec::new();
+        loop {
+            items.push(self.operand()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RBracket) => break,
+                _ => return Err("expected ',' or ']'".to_string()),
+            }
+        }
+        Ok(items)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(word)) if word == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::protocol::{ComponentId, Endpoint, SystemId};
+
+    fn frame(system_id: SystemId, component_id: ComponentId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(system_id, component_id))
+            .next_frame(message)
+            .unwrap()
+    }
+
+    fn server() -> (ConnectionInfo, ChannelInfo) {
+        let bind_addr = "127.0.0.1:5600".parse().unwrap();
+        let connection = ConnectionInfo::new(ConnectionDetails::TcpServer { bind_addr });
+        let channel = connection.make_channel_info(ChannelDetails::TcpServer {
+            server_addr: bind_addr,
+            peer_addr: "10.0.0.2:41000".parse().unwrap(),
+        });
+        (connection, channel)
+    }
+
+    #[test]
+    fn empty_script_accepts_everything() {
+        let script = FrameScript::parse("\n  # nothing but comments\n").unwrap();
+        let (connection, _) = server();
+
+        assert!(script.is_empty());
+        assert_eq!(
+            script.evaluate(
+                &frame(1, 1, &Heartbeat::default()),
+                &ScriptContext::outgoing(&connection)
+            ),
+            ScriptVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let script = FrameScript::parse(
+            r#"
+            accept if system_id == 1 and message_id == 0
+            drop if system_id in [1, 2] or component_id >= 0xF0  # blocked
+            "#,
+        )
+        .unwrap();
+        let (connection, _) = server();
+        let context = ScriptContext::outgoing(&connection);
+
+        assert_eq!(script.len(), 2);
+        let verdict = |frame: Frame<V2>| script.evaluate(&frame, &context);
+        assert_eq!(
+            verdict(frame(1, 1, &Heartbeat::default())),
+            ScriptVerdict::Accept
+        );
+        assert_eq!(
+            verdict(frame(1, 1, &ProtocolVersion::default())),
+            ScriptVerdict::Drop
+        );
+        assert_eq!(
+            verdict(frame(2, 1, &Heartbeat::default())),
+            ScriptVerdict::Drop
+        );
+        assert_eq!(
+            verdict(frame(3, 250, &Heartbeat::default())),
+            ScriptVerdict::Drop
+        );
+        assert_eq!(
+            verdict(frame(3, 1, &Heartbeat::default())),
+            ScriptVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn connection_properties_are_available() {
+        let script = FrameScript::parse(
+            r#"
+            drop if outgoing and not signed
+            drop if incoming and connection == "tcp_server" and peer != "10.0.0.2:41000"
+            accept if incoming and version == 2
+            drop
+            "#,
+        )
+        .unwrap();
+        let (connection, channel) = server();
+        let heartbeat = frame(1, 1, &Heartbeat::default());
+
+        assert_eq!(
+            script.evaluate(&heartbeat, &ScriptContext::outgoing(&connection)),
+            ScriptVerdict::Drop
+        );
+        assert_eq!(
+            script.evaluate(&heartbeat, &ScriptContext::incoming(&connection, &channel)),
+            ScriptVerdict::Accept
+        );
+
+        let other_channel = connection.make_channel_info(ChannelDetails::TcpServer {
+            server_addr: "127.0.0.1:5600".parse().unwrap(),
+            peer_addr: "10.0.0.3:41000".parse().unwrap(),
+        });
+        assert_eq!(
+            script.evaluate(
+                &heartbeat,
+                &ScriptContext::incoming(&connection, &other_channel)
+            ),
+            ScriptVerdict::Drop
+        );
+    }
+
+    #[test]
+    fn comment_sign_is_allowed_in_strings() {
+        let script = FrameScript::parse(
+            r##"
+            drop if peer == "10.0.0.2:41000" or peer == "#41000" # comment with "quotes"
+            "##,
+        )
+        .unwrap();
+        let (connection, channel) = server();
+
+        assert_eq!(script.len(), 1);
+        assert_eq!(
+            script.evaluate(
+                &frame(1, 1, &Heartbeat::default()),
+                &ScriptContext::incoming(&connection, &channel)
+            ),
+            ScriptVerdict::Drop
+        );
+    }
+
+    #[test]
+    fn deeply_nested_conditions_are_rejected() {
+        let nested = |depth: usize| {
+            format!(
+                "drop if {}incoming{}",
+                "(not ".repeat(depth),
+                ")".repeat(depth)
+            )
+        };
+
+        assert!(FrameScript::parse(&nested(MAX_DEPTH / 2 - 1)).is_ok());
+        match FrameScript::parse(&nested(100_000)) {
+            Err(Error::Script(err)) => assert_eq!(err.line, 1),
+            other => panic!("deeply nested script should be rejected, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_scripts_are_rejected() {
+        for (source, line) in [
+            ("forward if system_id == 1", 1),
+            ("accept\ndrop if unknown_var == 1", 2),
+            ("drop if system_id == \"1\"", 1),
+            ("drop if connection > \"tcp\"", 1),
+            ("drop if system_id", 1),
+            ("drop if (incoming", 1),
+            ("drop if message_id in [1, true]", 1),
+            ("\n\ndrop if peer == \"open", 3),
+            ("drop incoming", 1),
+            ("drop if peer == \"open # comment", 1),
+        ] {
+            match FrameScript::parse(source) {
+                Err(Error::Script(err)) => assert_eq!(err.line, line, "{source:?}"),
+                other => panic!("script {source:?} should be rejected, got {other:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use crate::protocol::MessageId;
+
+#[cfg(doc)]
+use crate::prelude::*;
+
+/// `HEARTBEAT` message `ID`.
+const HEARTBEAT: MessageId = 0;
+/// `RADIO_STATUS` message `ID`.
+const RADIO_STATUS: MessageId = 109;
+
+/// Messages, that are not forwarded to a particular connection of a [`Network`].
+///
+/// When a network bridges two segments, heartbeats and radio status reports of each segment are
+/// forwarded to the other one, which confuses peer tracking on both sides. Connections added with
+/// a suppression do not receive frames with specified message `ID`s, that were forwarded from other
+/// connections by callbacks. Frames received by such connections are still delivered to the node
+/// events, and frames sent by the node itself (including its automatic heartbeats) are not
+/// affected.
+///
+/// Use [`ForwardSuppression::heartbeats`] to suppress `HEARTBEAT` and `RADIO_STATUS` messages.
+///
+/// Attach suppression with
+/// [`ConnectionOptions::suppression`](crate::core::network::ConnectionOptions::suppression).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::ForwardSuppression;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpClient::new("127.0.0.1:5760").unwrap())
+///             .suppression(ForwardSuppression::heartbeats())
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .suppression(ForwardSuppression::heartbeats())
+///     )
+///     .build().unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ForwardSuppression {
+    message_ids: HashSet<MessageId>,
+}
+
+impl ForwardSuppression {
+    /// Creates a suppression, that doesn't suppress any messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a suppression of forwarded `HEARTBEAT` and `RADIO_STATUS` messages.
+    pub fn heartbeats() -> Self {
+        Self::new().with_message_ids([HEARTBEAT, RADIO_STATUS])
+    }
+
+    /// Suppresses forwarding of messages with specified `ids`.
+    ///
+    /// Subsequent calls extend the list.
+    pub fn with_message_ids(mut self, ids: impl IntoIterator<Item = MessageId>) -> Self {
+        self.message_ids.extend(ids);
+        self
+    }
+
+    /// Message `ID`s, that are not forwarded.
+    pub fn message_ids(&self) -> impl Iterator<Item = MessageId> + '_ {
+        self.message_ids.iter().copied()
+    }
+
+    /// Returns `true`, if forwarding of messages with specified `message_id` is suppressed.
+    pub fn suppresses(&self, message_id: MessageId) -> bool {
+        self.message_ids.contains(&message_id)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod suppression_tests {
+    use super::*;
+
+    #[test]
+    fn heartbeats_are_suppressed() {
+        let suppression = ForwardSuppression::heartbeats();
+        assert!(suppression.suppresses(HEARTBEAT));
+        assert!(suppression.suppresses(RADIO_STATUS));
+        assert!(!suppression.suppresses(33));
+
+        let suppression = suppression.with_message_ids([33]);
+        assert!(suppression.suppresses(33));
+        assert!(!ForwardSuppression::new().suppresses(HEARTBEAT));
+    }
+}
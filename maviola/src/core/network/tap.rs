@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::core::io::{ConnectionId, OutgoingFrame};
+use crate::core::utils::{Closable, Closer};
+
+use crate::prelude::*;
+
+/// Direction of a frame captured by a [`Network`] tap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TapDirection {
+    /// Frame was received by a network connection.
+    Incoming,
+    /// Frame was sent to a network connection.
+    Outgoing,
+}
+
+/// Frame captured by a [`Network`] tap.
+///
+/// Incoming frames are captured after they passed filters, translations and deduplication of the
+/// connection, that received them. Outgoing frames are captured right before they are sent to a
+/// connection, that is, after routing, rate limiting and conversions. Frames sent by
+/// [injectors](Network::injector) are captured as outgoing.
+#[derive(Clone, Debug)]
+pub struct TappedFrame<V: MaybeVersioned> {
+    direction: TapDirection,
+    connection_id: ConnectionId,
+    frame: Frame<V>,
+}
+
+/// <sup>⛔</sup>
+/// Subscribers to frames passing through a [`Network`].
+///
+/// Clones share the same subscribers.
+pub(crate) struct NetworkTap<V: MaybeVersioned> {
+    subscribers: Arc<Mutex<Vec<TapSubscriber<V>>>>,
+}
+
+/// <sup>⛔</sup>
+/// Receives tapped frames, returns `false`, once subscriber is gone.
+pub(crate) type TapSubscriber<V> = Box<dyn Fn(&TappedFrame<V>) -> bool + Send + Sync>;
+
+/// <sup>⛔</sup>
+/// Frame injectors of a [`Network`] bound to particular connections.
+///
+/// Injectors are created before the network is running. Each injector provides a pump, that is
+/// started by a running network and passes injected frames to the [`InjectionTargets`].
+pub(crate) struct NetworkInjectors<V: MaybeVersioned> {
+    inner: Arc<NetworkInjectorsInner<V>>,
+}
+
+/// <sup>⛔</sup>
+/// Moves frames from an injector to injection targets until the provided state is closed.
+pub(crate) type InjectionPump<V> = Box<dyn Fn(Closable, InjectionTargets<V>) + Send + Sync>;
+
+/// <sup>⛔</sup>
+/// Delivers injected frames to running network connections.
+///
+/// Clones share the same targets.
+pub(crate) struct InjectionTargets<V: MaybeVersioned> {
+    targets: Arc<RwLock<HashMap<ConnectionId, InjectionTarget<V>>>>,
+    tap: NetworkTap<V>,
+}
+
+/// <sup>⛔</sup>
+/// Sends an injected frame to a running network connection.
+pub(crate) type InjectionTarget<V> = Box<dyn Fn(OutgoingFrame<V>) + Send + Sync>;
+
+struct NetworkInjectorsInner<V: MaybeVersioned> {
+    state: Closer,
+    pumps: Mutex<Vec<InjectionPump<V>>>,
+}
+
+impl<V: MaybeVersioned> TappedFrame<V> {
+    /// Direction of the frame.
+    pub fn direction(&self) -> TapDirection {
+        self.direction
+    }
+
+    /// `ID` of a network connection, that received or sent the frame.
+    pub fn connection_id(&self) -> ConnectionId {
+        self.connection_id
+    }
+
+    /// Captured frame.
+    pub fn frame(&self) -> &Frame<V> {
+        &self.frame
+    }
+
+    /// Returns captured frame.
+    pub fn into_frame(self) -> Frame<V> {
+        self.frame
+    }
+}
+
+impl<V: MaybeVersioned> NetworkTap<V> {
+    /// Adds a subscriber.
+    pub(crate) fn subscribe(&self, subscriber: TapSubscriber<V>) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(subscriber);
+        }
+    }
+
+    /// Publishes a frame received by or sent to a connection.
+    ///
+    /// Frames are cloned only when there are subscribers. Subscribers, that are gone, are removed.
+    pub(crate) fn publish(
+        &self,
+        direction: TapDirection,
+        connection_id: ConnectionId,
+        frame: &Frame<V>,
+    ) {
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => return,
+        };
+        if subscribers.is_empty() {
+            return;
+        }
+
+        let tapped = TappedFrame {
+            direction,
+            connection_id,
+            frame: frame.clone(),
+        };
+        subscribers.retain(|subscriber| subscriber(&tapped));
+    }
+
+    /// Removes all subscribers, so they will know, that network is stopped.
+    pub(crate) fn close(&self) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.clear();
+        }
+    }
+}
+
+impl<V: MaybeVersioned> NetworkInjectors<V> {
+    /// State of injectors, which is closed, once all copies of network configuration are gone.
+    pub(crate) fn state(&self) -> Closable {
+        self.inner.state.to_closable()
+    }
+
+    /// Registers a pump of a new injector.
+    pub(crate) fn add(&self, pump: InjectionPump<V>) {
+        if let Ok(mut pumps) = self.inner.pumps.lock() {
+            pumps.push(pump);
+        }
+    }
+
+    /// Starts pumps of all injectors, which will stop, once network `state` is closed.
+    pub(crate) fn start(&self, state: &Closable, targets: &InjectionTargets<V>) {
+        let pumps = match self.inner.pumps.lock() {
+            Ok(pumps) => pumps,
+            Err(err) => err.into_inner(),
+        };
+        for pump in pumps.iter() {
+            pump(state.clone(), targets.clone());
+        }
+    }
+}
+
+impl<V: MaybeVersioned> InjectionTargets<V> {
+    /// Creates injection targets, that report injected frames to a network `tap`.
+    pub(crate) fn new(tap: NetworkTap<V>) -> Self {
+        Self {
+            targets: Default::default(),
+            tap,
+        }
+    }
+
+    /// Sets a `target` of a running connection.
+    ///
+    /// Replaces previous target, when connection is restarted.
+    pub(crate) fn set(&self, connection_id: ConnectionId, target: InjectionTarget<V>) {
+        if let Ok(mut targets) = self.targets.write() {
+            targets.insert(connection_id, target);
+        }
+    }
+
+    /// Removes target of a stopped connection.
+    pub(crate) fn remove(&self, connection_id: ConnectionId) {
+        if let Ok(mut targets) = self.targets.write() {
+            targets.remove(&connection_id);
+        }
+    }
+
+    /// Sends injected `frame` to a connection.
+    ///
+    /// Frames injected into connections, that are not running, are dropped.
+    pub(crate) fn inject(&self, connection_id: ConnectionId, frame: OutgoingFrame<V>) {
+        let targets = match self.targets.read() {
+            Ok(targets) => targets,
+            Err(_) => return,
+        };
+
+        match targets.get(&connection_id) {
+            Some(target) => {
+                self.tap
+                    .publish(TapDirection::Outgoing, connection_id, frame.frame());
+                target(frame);
+            }
+            None => {
+                log::trace!("injected frame dropped: connection {connection_id:?} is not running")
+            }
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Clone for NetworkTap<V> {
+    fn clone(&self) -> Self {
+        Self {
+            subscribers: self.subscribers.clone(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Default for NetworkTap<V> {
+    fn default() -> Self {
+        Self {
+            subscribers: Default::default(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Debug for NetworkTap<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers.len(),
+            Err(_) => 0,
+        };
+        f.debug_struct("NetworkTap")
+            .field("subscribers", &subscribers)
+            .finish()
+    }
+}
+
+impl<V: MaybeVersioned> Clone for NetworkInjectors<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Default for NetworkInjectors<V> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(NetworkInjectorsInner {
+                state: Closer::new(),
+                pumps: Default::default(),
+            }),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Debug for NetworkInjectors<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let injectors = match self.inner.pumps.lock() {
+            Ok(pumps) => pumps.len(),
+            Err(_) => 0,
+        };
+        f.debug_struct("NetworkInjectors")
+            .field("injectors", &injectors)
+            .finish()
+    }
+}
+
+impl<V: MaybeVersioned> Clone for InjectionTargets<V> {
+    fn clone(&self) -> Self {
+        Self {
+            targets: self.targets.clone(),
+            tap: self.tap.clone(),
+        }
+    }
+}
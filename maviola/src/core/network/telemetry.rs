@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "common")]
+use crate::dialects::common::messages::RadioStatus;
+use crate::protocol::{ComponentId, MessageId, SystemId};
+
+use crate::prelude::*;
+
+/// Scale of rates will never go below this value.
+const MIN_RATE_SCALE: f64 = 1.0 / 64.0;
+
+/// Policy, that adapts rates of outgoing telemetry to the quality of a link.
+///
+/// Policy limits outgoing rates of configured messages routed through a particular connection of
+/// a [`Network`]. Nominal rates are multiplied by a common scale, that is decreased when link
+/// degrades and increased, when link recovers. The scaled rate never goes below the minimum rate
+/// of a message. Messages without configured rates are not affected, and frames exceeding the
+/// current rate are dropped. Rates are tracked separately for each sender of a message.
+///
+/// The same policy can be applied to messages sent by a node with `send_periodically`, see
+/// [`PeriodicSender::set_telemetry`](crate::core::node::PeriodicSender::set_telemetry).
+///
+/// Link quality is a number between `0.0` (link is saturated) and `1.0` (link is idle):
+///
+/// * When `common` dialect is enabled, quality is updated from `RADIO_STATUS` messages received
+///   from the connection. SiK-compatible radios report the amount of free space in their transmit
+///   buffer in the `txbuf` field, which is used as a quality.
+/// * Any other source of link quality can be reported by [`TelemetryPolicy::report_quality`].
+///
+/// Each quality report below the [`degrade`](Self::with_thresholds) threshold multiplies the scale
+/// by a [`backoff`](Self::with_backoff) factor, and each report above the
+/// [`recover`](Self::with_thresholds) threshold divides it until it reaches `1.0`. Reports between
+/// thresholds keep the current scale, which prevents rates from flapping.
+///
+/// Clones of the policy share the link state. Keep a clone to report quality or inspect the current
+/// [`scale`](Self::scale) while the network is running. Attach policy with
+/// [`ConnectionOptions::telemetry`](crate::core::network::ConnectionOptions::telemetry).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::TelemetryPolicy;
+/// use maviola::protocol::MessageId;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// const ATTITUDE: MessageId = 30;
+/// const GLOBAL_POSITION_INT: MessageId = 33;
+///
+/// let policy = TelemetryPolicy::new()
+///     // Send attitude at 50 Hz, but not slower than 5 Hz
+///     .with_rate(ATTITUDE, 50.0, 5.0)
+///     .with_rate(GLOBAL_POSITION_INT, 10.0, 2.0);
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .add_connection(TcpClient::new("127.0.0.1:14550").unwrap())
+///             .telemetry(policy.clone())
+///     )
+///     .build().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TelemetryPolicy {
+    rates: HashMap<MessageId, MessageRate>,
+    degrade_below: f64,
+    recover_above: f64,
+    backoff: f64,
+    link: Arc<RwLock<LinkState>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct MessageRate {
+    nominal: f64,
+    min: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LinkState {
+    quality: Option<f64>,
+    scale: f64,
+}
+
+impl Default for TelemetryPolicy {
+    fn default() -> Self {
+        Self {
+            rates: HashMap::new(),
+            degrade_below: 0.5,
+            recover_above: 0.8,
+            backoff: 0.5,
+            link: Arc::new(RwLock::new(LinkState {
+                quality: None,
+                scale: 1.0,
+            })),
+        }
+    }
+}
+
+impl TelemetryPolicy {
+    /// Creates a policy without message rates.
+    ///
+    /// By default, rates are halved when link quality drops below `0.5` and restored when it
+    /// exceeds `0.8`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets nominal `rate` and `min_rate` in Hz for outgoing messages with specified `message_id`.
+    pub fn with_rate(mut self, message_id: MessageId, rate: f64, min_rate: f64) -> Self {
+        self.rates.insert(
+            message_id,
+            MessageRate {
+                nominal: rate,
+                min: min_rate.min(rate),
+            },
+        );
+        self
+    }
+
+    /// Sets link quality thresholds.
+    ///
+    /// Rates are decreased, when quality drops below `degrade`, and increased, when it exceeds
+    /// `recover`. The `recover` threshold is clamped to be not less than `degrade`.
+    pub fn with_thresholds(mut self, degrade: f64, recover: f64) -> Self {
+        self.degrade_below = degrade;
+        self.recover_above = recover.max(degrade);
+        self
+    }
+
+    /// Sets a factor between `0.0` and `1.0`, by which rates are multiplied on each degraded link
+    /// report.
+    pub fn with_backoff(mut self, factor: f64) -> Self {
+        self.backoff = factor.clamp(MIN_RATE_SCALE, 1.0);
+        self
+    }
+
+    /// Reports link quality between `0.0` and `1.0` and adjusts rates.
+    pub fn report_quality(&self, quality: f64) {
+        let quality = quality.clamp(0.0, 1.0);
+
+        if let Ok(mut link) = self.link.write() {
+            link.quality = Some(quality);
+            if quality < self.degrade_below {
+                link.scale = (link.scale * self.backoff).max(MIN_RATE_SCALE);
+            } else if quality > self.recover_above {
+                link.scale = (link.scale / self.backoff).min(1.0);
+            }
+        }
+    }
+
+    /// The last reported link quality.
+    pub fn quality(&self) -> Option<f64> {
+        self.link.read().ok().and_then(|link| link.quality)
+    }
+
+    /// Current scale of nominal rates.
+    ///
+    /// Equals `1.0` for a healthy link.
+    pub fn scale(&self) -> f64 {
+        self.link.read().map(|link| link.scale).unwrap_or(1.0)
+    }
+
+    /// Current rate in Hz for outgoing messages with specified `message_id`.
+    ///
+    /// Returns [`None`] if messages with this `ID` are not limited.
+    pub fn rate(&self, message_id: MessageId) -> Option<f64> {
+        let rate = self.rates.get(&message_id)?;
+        Some((rate.nominal * self.scale()).max(rate.min))
+    }
+
+    /// <sup>⛔</sup>
+    /// Stretches `interval` between messages with specified `message_id` according to the
+    /// current scale.
+    ///
+    /// The resulting rate is not less than the minimum rate of a message, if it is configured.
+    pub(crate) fn scale_interval(&self, message_id: MessageId, interval: Duration) -> Duration {
+        let rate = 1.0 / interval.as_secs_f64();
+        let mut scaled = rate * self.scale();
+        if let Some(message_rate) = self.rates.get(&message_id) {
+            scaled = scaled.max(message_rate.min);
+        }
+
+        if scaled >= rate {
+            return interval;
+        }
+        Duration::from_secs_f64(1.0 / scaled)
+    }
+
+    /// <sup>⛔</sup>
+    /// Updates link quality from a frame received by the connection.
+    pub(crate) fn observe<V: MaybeVersioned>(&self, frame: &Frame<V>) {
+        #[cfg(feature = "common")]
+        if frame.message_id() == RadioStatus::message_id() {
+            if let Ok(status) = RadioStatus::try_from(frame.payload()) {
+                self.report_quality(status.txbuf as f64 / 100.0);
+            }
+        }
+        #[cfg(not(feature = "common"))]
+        let _ = frame;
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that applies this policy to outgoing frames.
+    pub(crate) fn tracker(&self) -> TelemetryTracker {
+        TelemetryTracker {
+            policy: self.clone(),
+            buckets: Default::default(),
+        }
+    }
+}
+
+impl Debug for TelemetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelemetryPolicy")
+            .field("rates", &self.rates)
+            .field("degrade_below", &self.degrade_below)
+            .field("recover_above", &self.recover_above)
+            .field("backoff", &self.backoff)
+            .field("scale", &self.scale())
+            .finish_non_exhaustive()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Keeps track of outgoing message rates for [`TelemetryPolicy`].
+///
+/// Each sender may send a message in bursts of up to one second worth of messages at the current
+/// rate.
+pub(crate) struct TelemetryTracker {
+    policy: TelemetryPolicy,
+    buckets: HashMap<(SystemId, ComponentId, MessageId), TokenBucket>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TelemetryTracker {
+    /// Returns `true` if `frame` can be sent at `now`.
+    pub(crate) fn allow<V: MaybeVersioned>(&mut self, frame: &Frame<V>, now: Instant) -> bool {
+        let message_id = frame.message_id();
+        let rate = match self.policy.rate(message_id) {
+            Some(rate) => rate,
+            None => return true,
+        };
+
+        if rate <= 0.0 {
+            return false;
+        }
+
+        let capacity = rate.max(1.0);
+        let key = (frame.system_id(), frame.component_id(), message_id);
+        let bucket = self.buckets.entry(key).or_insert(TokenBucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::protocol::Endpoint;
+
+    fn frame(id: MavLinkId, message: &dyn Message) -> Frame<V2> {
+        Endpoint::v2(id).next_frame(message).unwrap()
+    }
+
+    #[test]
+    fn rates_adapt_with_hysteresis() {
+        let policy = TelemetryPolicy::new().with_rate(30, 40.0, 5.0);
+        assert_eq!(policy.rate(30), Some(40.0));
+        assert_eq!(policy.rate(33), None);
+
+        policy.report_quality(0.3);
+        assert_eq!(policy.rate(30), Some(20.0));
+        policy.report_quality(0.1);
+        assert_eq!(policy.rate(30), Some(10.0));
+
+        // Quality between thresholds keeps rates
+        policy.report_quality(0.6);
+        assert_eq!(policy.rate(30), Some(10.0));
+
+        // Minimum rate is respected
+        for _ in 0..10 {
+            policy.report_quality(0.0);
+        }
+        assert_eq!(policy.rate(30), Some(5.0));
+        assert_eq!(policy.quality(), Some(0.0));
+
+        for _ in 0..10 {
+            policy.report_quality(1.0);
+        }
+        assert_eq!(policy.rate(30), Some(40.0));
+        assert_eq!(policy.scale(), 1.0);
+    }
+
+    #[test]
+    fn tracker_limits_outgoing_rate() {
+        let heartbeat_id = Heartbeat::message_id();
+        let policy = TelemetryPolicy::new().with_rate(heartbeat_id, 4.0, 1.0);
+        let mut tracker = policy.tracker();
+        let start = Instant::now();
+
+        let heartbeat = frame(MavLinkId::new(1, 1), &Heartbeat::default());
+        let version = frame(MavLinkId::new(1, 1), &ProtocolVersion::default());
+
+        // Burst of up to one second worth of messages is allowed
+        for _ in 0..4 {
+            assert!(tracker.allow(&heartbeat, start));
+        }
+        assert!(!tracker.allow(&heartbeat, start));
+        assert!(tracker.allow(&version, start));
+
+        // Tokens are restored with the nominal rate
+        assert!(tracker.allow(&heartbeat, start + Duration::from_millis(250)));
+        assert!(!tracker.allow(&heartbeat, start + Duration::from_millis(300)));
+
+        // And with the scaled rate, once link degrades
+        policy.report_quality(0.0);
+        assert!(!tracker.allow(&heartbeat, start + Duration::from_millis(600)));
+        assert!(tracker.allow(&heartbeat, start + Duration::from_millis(750)));
+    }
+
+    #[test]
+    fn tracker_limits_rate_per_sender() {
+        let heartbeat_id = Heartbeat::message_id();
+        let policy = TelemetryPolicy::new().with_rate(heartbeat_id, 1.0, 1.0);
+        let mut tracker = policy.tracker();
+        let start = Instant::now();
+
+        let autopilot = frame(MavLinkId::new(1, 1), &Heartbeat::default());
+        let camera = frame(MavLinkId::new(1, 100), &Heartbeat::default());
+        let vehicle = frame(MavLinkId::new(2, 1), &Heartbeat::default());
+
+        assert!(tracker.allow(&autopilot, start));
+        assert!(!tracker.allow(&autopilot, start));
+        assert!(tracker.allow(&camera, start));
+        assert!(tracker.allow(&vehicle, start));
+        assert!(!tracker.allow(&vehicle, start));
+    }
+
+    #[test]
+    fn intervals_are_scaled() {
+        let heartbeat_id = Heartbeat::message_id();
+        let policy = TelemetryPolicy::new().with_rate(heartbeat_id, 10.0, 4.0);
+        let interval = Duration::from_millis(100);
+
+        assert_eq!(policy.scale_interval(heartbeat_id, interval), interval);
+
+        policy.report_quality(0.0);
+        assert_eq!(
+            policy.scale_interval(heartbeat_id, interval),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            policy.scale_interval(300, interval),
+            Duration::from_millis(200)
+        );
+
+        // Minimum rate of a message is respected
+        policy.report_quality(0.0);
+        assert_eq!(
+            policy.scale_interval(heartbeat_id, interval),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            policy.scale_interval(300, interval),
+            Duration::from_millis(400)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn quality_is_taken_from_radio_status() {
+        let policy = TelemetryPolicy::new();
+        let frame = Endpoint::v2(MavLinkId::new(51, 68))
+            .next_frame(&RadioStatus {
+                rssi: 200,
+                remrssi: 190,
+                txbuf: 25,
+                noise: 40,
+                remnoise: 45,
+                rxerrors: 0,
+                fixed: 0,
+            })
+            .unwrap();
+
+        policy.observe(&frame);
+        assert_eq!(policy.quality(), Some(0.25));
+        assert_eq!(policy.scale(), 0.5);
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, RwLock};
+
+use crate::core::io::{BroadcastScope, ChannelId, ChannelInfo, ConnectionId, OutgoingFrame};
+use crate::protocol::{FrameProcessor, SystemId, TargetFields};
+
+use crate::prelude::*;
+
+/// Translation of MAVLink system `ID`s for a connection of a [`Network`].
+///
+/// Nested MAVLink networks often contain several systems with the same `ID`. For example, in
+/// staging environments, multiple identical vehicles with system `ID` `1` may be connected through
+/// a single gateway. Translation assigns a virtual system `ID` from a pool to each pair of a
+/// channel and a real system `ID`, so the rest of the network sees these systems as distinct.
+///
+/// Frames are rewritten in both directions:
+///
+/// * Frames received from a translated system get its virtual system `ID` as a sender. Target
+///   fields of addressed messages, that point to a translated system of the same channel, are
+///   rewritten as well.
+/// * Frames sent to the connection, which `target_system` is a virtual system `ID`, are addressed
+///   to the real system `ID` and sent only to the channel of that system.
+///
+/// Target fields are known for messages of the `common` dialect and its dependencies. Outgoing
+/// messages without target fields are broadcast without changes. Frames of unknown messages and
+/// signed frames are handled as described in
+/// [rewritten frames](crate::protocol::FrameProcessor#rewritten-frames).
+///
+/// When the pool of virtual system `ID`s is exhausted, frames from new systems are dropped.
+/// Virtual `ID`s are released, once the corresponding channel is closed.
+///
+/// Clones of translation share the mapping table. Keep a clone to inspect
+/// [`entries`](Self::entries) while the network is running. Attach translation with
+/// [`ConnectionOptions::translation`](crate::core::network::ConnectionOptions::translation).
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::network::SysIdTranslation;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// // Vehicles connected to the gateway will get system IDs from 11 to 20
+/// let translation = SysIdTranslation::new(11..=20).only_systems([1]);
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .translation(translation.clone())
+///     )
+///     .build().unwrap();
+///
+/// for entry in translation.entries() {
+///     println!("{} -> {} {:?}", entry.system_id(), entry.virtual_id(), entry.channel());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SysIdTranslation {
+    pool: RangeInclusive<SystemId>,
+    systems: Option<HashSet<SystemId>>,
+    table: Arc<RwLock<BTreeMap<SystemId, TranslationEntry>>>,
+}
+
+/// Entry of a [`SysIdTranslation`] mapping table.
+#[derive(Clone, Debug)]
+pub struct TranslationEntry {
+    virtual_id: SystemId,
+    system_id: SystemId,
+    channel: ChannelInfo,
+}
+
+impl SysIdTranslation {
+    /// Creates translation, that assigns virtual system `ID`s from a `pool`.
+    ///
+    /// By default, all systems are translated. Make sure, that the pool does not intersect with
+    /// system `ID`s used elsewhere in the network.
+    pub fn new(pool: RangeInclusive<SystemId>) -> Self {
+        Self {
+            pool,
+            systems: None,
+            table: Default::default(),
+        }
+    }
+
+    /// Translates only systems with specified `ID`s, frames from other systems are passed as is.
+    pub fn only_systems(mut self, system_ids: impl IntoIterator<Item = SystemId>) -> Self {
+        self.systems = Some(system_ids.into_iter().collect());
+        self
+    }
+
+    /// Pool of virtual system `ID`s.
+    pub fn pool(&self) -> RangeInclusive<SystemId> {
+        self.pool.clone()
+    }
+
+    /// Current mapping table ordered by virtual system `ID`s.
+    pub fn entries(&self) -> Vec<TranslationEntry> {
+        self.table
+            .read()
+            .map(|table| table.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Virtual system `ID` assigned to a system with `system_id` within a channel.
+    pub fn virtual_id(&self, channel_id: ChannelId, system_id: SystemId) -> Option<SystemId> {
+        self.table.read().ok()?.values().find_map(|entry| {
+            (entry.channel.id() == channel_id && entry.system_id == system_id)
+                .then_some(entry.virtual_id)
+        })
+    }
+
+    /// Resolves a virtual system `ID` into the corresponding mapping table entry.
+    pub fn resolve(&self, virtual_id: SystemId) -> Option<TranslationEntry> {
+        self.table.read().ok()?.get(&virtual_id).cloned()
+    }
+
+    /// <sup>⛔</sup>
+    /// Releases virtual system `ID`s assigned to systems of a closed channel.
+    pub(crate) fn release(&self, channel_id: ChannelId) {
+        if let Ok(mut table) = self.table.write() {
+            table.retain(|_, entry| entry.channel.id() != channel_id);
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Translates a frame received from a `channel`.
+    ///
+    /// Returns [`None`], if frame should be dropped.
+    pub(crate) fn translate_incoming<V: MaybeVersioned>(
+        &self,
+        frame: Frame<V>,
+        channel: &ChannelInfo,
+        processor: &FrameProcessor,
+    ) -> Option<Frame<V>> {
+        if !self.is_translated(frame.system_id()) {
+            return Some(frame);
+        }
+
+        let virtual_id = match self.assign(frame.system_id(), channel) {
+            Some(virtual_id) => virtual_id,
+            None => {
+                log::warn!(
+                    "[{channel:?}] no virtual system ID left for system #{}",
+                    frame.system_id()
+                );
+                return None;
+            }
+        };
+
+        let mut payload = frame.payload().bytes().to_vec();
+        if let Some(fields) = TargetFields::of(frame.message_id()) {
+            let target = fields.target(&payload);
+            if self.is_translated(target.system) {
+                if let Some(target_id) = self.virtual_id(channel.id(), target.system) {
+                    fields.set_target(&mut payload, target_id, target.component);
+                }
+            }
+        }
+
+        let id = MavLinkId::new(virtual_id, frame.component_id());
+        processor.rewrite(&frame, id, &payload)
+    }
+
+    /// <sup>⛔</sup>
+    /// Translates a frame, that is about to be sent to a connection with `connection_id`.
+    ///
+    /// Frames addressed to virtual systems of this connection are scoped to the channel of the
+    /// corresponding real system. Returns `false`, if frame should be dropped.
+    pub(crate) fn translate_outgoing<V: MaybeVersioned>(
+        &self,
+        frame: &mut OutgoingFrame<V>,
+        connection_id: ConnectionId,
+        processor: &FrameProcessor,
+    ) -> bool {
+        let fields = match TargetFields::of(frame.frame().message_id()) {
+            Some(fields) => fields,
+            None => return true,
+        };
+        let mut payload = frame.frame().payload().bytes().to_vec();
+        let target = fields.target(&payload);
+
+        let entry = match self.resolve(target.system) {
+            Some(entry) if entry.channel.id().connection_id() == connection_id => entry,
+            _ => return true,
+        };
+        if !frame.should_send_to(entry.channel.id()) {
+            return false;
+        }
+
+        fields.set_target(&mut payload, entry.system_id, target.component);
+        let id = MavLinkId::new(frame.frame().system_id(), frame.frame().component_id());
+        match processor.rewrite(frame.frame(), id, &payload) {
+            Some(translated) => {
+                frame.replace_frame(translated);
+                frame.set_scope(BroadcastScope::ExactChannel(entry.channel.id()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_translated(&self, system_id: SystemId) -> bool {
+        match &self.systems {
+            Some(systems) => systems.contains(&system_id),
+            None => true,
+        }
+    }
+
+    fn assign(&self, system_id: SystemId, channel: &ChannelInfo) -> Option<SystemId> {
+        if let Some(virtual_id) = self.virtual_id(channel.id(), system_id) {
+            return Some(virtual_id);
+        }
+
+        let mut table = self.table.write().ok()?;
+        let virtual_id = self.pool.clone().find(|id| !table.contains_key(id))?;
+        table.insert(
+            virtual_id,
+            TranslationEntry {
+                virtual_id,
+                system_id,
+                channel: channel.clone(),
+            },
+        );
+        log::debug!("[{channel:?}] system #{system_id} is translated to #{virtual_id}");
+
+        Some(virtual_id)
+    }
+}
+
+impl Debug for SysIdTranslation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysIdTranslation")
+            .field("pool", &self.pool)
+            .field("systems", &self.systems)
+            .field("entries", &self.entries())
+            .finish()
+    }
+}
+
+impl TranslationEntry {
+    /// Virtual system `ID` visible to the network.
+    pub fn virtual_id(&self) -> SystemId {
+        self.virtual_id
+    }
+
+    /// Real system `ID`.
+    pub fn system_id(&self) -> SystemId {
+        self.system_id
+    }
+
+    /// Channel, through which the system is connected.
+    pub fn channel(&self) -> &ChannelInfo {
+        &self.channel
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod translation_tests {
+    use crate::core::io::{ChannelDetails, ConnectionDetails, ConnectionInfo};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, FrameSigner};
+
+    use super::*;
+
+    fn channel(connection: &ConnectionInfo) -> ChannelInfo {
+        ChannelInfo::new(
+            connection.id(),
+            ChannelDetails::TcpServer {
+                server_addr: "127.0.0.1:5600".parse().unwrap(),
+                peer_addr: "10.0.0.2:41000".parse().unwrap(),
+            },
+        )
+    }
+
+    #[test]
+    fn incoming_systems_get_virtual_ids() {
+        let translation = SysIdTranslation::new(11..=12).only_systems([1]);
+        let processor = FrameProcessor::builder().build();
+        let connection = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let (first, second, third) = (
+            channel(&connection),
+            channel(&connection),
+            channel(&connection),
+        );
+
+        let vehicle = Endpoint::v2(MavLinkId::new(1, 1));
+        let heartbeat = || vehicle.next_frame(&Heartbeat::default()).unwrap();
+
+        let frame = translation
+            .translate_incoming(heartbeat(), &first, &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 11);
+        assert!(frame.decode::<crate::dialects::Minimal>().is_ok());
+
+        let frame = translation
+            .translate_incoming(heartbeat(), &second, &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 12);
+        assert_eq!(translation.virtual_id(first.id(), 1), Some(11));
+
+        // Pool is exhausted
+        assert!(translation
+            .translate_incoming(heartbeat(), &third, &processor)
+            .is_none());
+
+        // Other systems are not translated
+        let frame = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let frame = translation
+            .translate_incoming(frame, &third, &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        translation.release(first.id());
+        let frame = translation
+            .translate_incoming(heartbeat(), &third, &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 11);
+        assert_eq!(translation.resolve(11).unwrap().system_id(), 1);
+        assert_eq!(translation.entries().len(), 2);
+    }
+
+    #[test]
+    fn signed_frames_are_signed_again_by_processor_signer() {
+        let signer = FrameSigner::new(1, "abc");
+        let connection = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let vehicle = channel(&connection);
+
+        let mut frame = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        signer.sign_frame(&mut frame).unwrap();
+
+        let processor = FrameProcessor::builder().signer(signer.clone()).build();
+        let translated = SysIdTranslation::new(11..=20)
+            .translate_incoming(frame.clone(), &vehicle, &processor)
+            .unwrap();
+        assert_eq!(translated.system_id(), 11);
+        assert!(signer.has_valid_signature(&translated));
+
+        // Without signer, signature is dropped
+        let processor = FrameProcessor::builder().build();
+        let translated = SysIdTranslation::new(11..=20)
+            .translate_incoming(frame, &vehicle, &processor)
+            .unwrap();
+        assert!(!translated.is_signed());
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn outgoing_frames_are_addressed_to_real_systems() {
+        use crate::dialects::common::messages::ParamRequestList;
+
+        let translation = SysIdTranslation::new(11..=20);
+        let processor = FrameProcessor::builder().build();
+        let connection = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let vehicle = channel(&connection);
+
+        let heartbeat = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        translation
+            .translate_incoming(heartbeat, &vehicle, &processor)
+            .unwrap();
+
+        let gcs = Endpoint::v2(MavLinkId::new(255, 190));
+        let request = |target_system| {
+            OutgoingFrame::new(
+                gcs.next_frame(&ParamRequestList {
+                    target_system,
+                    target_component: 1,
+                })
+                .unwrap(),
+            )
+        };
+
+        let mut frame = request(11);
+        assert!(translation.translate_outgoing(&mut frame, connection.id(), &processor));
+        assert_eq!(frame.frame().system_id(), 255);
+        assert_eq!(
+            TargetFields::frame_target(frame.frame()),
+            Some(MavLinkId::new(1, 1))
+        );
+        assert_eq!(frame.scope(), BroadcastScope::ExactChannel(vehicle.id()));
+        assert!(frame.frame().decode::<crate::dialects::Common>().is_ok());
+
+        // Frames to unknown systems are not changed
+        let mut frame = request(21);
+        assert!(translation.translate_outgoing(&mut frame, connection.id(), &processor));
+        assert_eq!(frame.scope(), BroadcastScope::All);
+
+        // Frames are not sent back to the channel, they came from
+        let mut frame = request(11);
+        frame.set_scope(BroadcastScope::ExceptChannel(vehicle.id()));
+        assert!(!translation.translate_outgoing(&mut frame, connection.id(), &processor));
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use crate::core::io::{ConnectionInfo, RetryStrategy};
 use crate::core::marker::Proxy;
 use crate::core::node::NodeApi;
@@ -18,6 +21,15 @@ pub(crate) struct NetworkConnInfo {
     pub(crate) connection: ConnectionInfo,
 }
 
+/// Routing role of a node within a network.
+///
+/// Standby nodes keep their connections established and receive heartbeats, but are excluded from
+/// routing until activated.
+#[derive(Clone, Debug)]
+pub(crate) struct NetworkNodeRole {
+    standby: Arc<AtomicBool>,
+}
+
 pub(crate) enum RestartNodeEvent<V: MaybeVersioned, A: NodeApi<V>> {
     New(UniqueId, Node<Proxy, V, A>),
     Retry(UniqueId, RetryStrategy),
@@ -30,6 +42,23 @@ impl NetworkConnState {
     }
 }
 
+impl NetworkNodeRole {
+    pub(crate) fn new(standby: bool) -> Self {
+        Self {
+            standby: Arc::new(AtomicBool::new(standby)),
+        }
+    }
+
+    pub(crate) fn is_standby(&self) -> bool {
+        self.standby.load(Ordering::Acquire)
+    }
+
+    /// Activates standby node. Returns `true` if node was in standby.
+    pub(crate) fn activate(&self) -> bool {
+        self.standby.swap(false, Ordering::AcqRel)
+    }
+}
+
 impl Debug for NetworkConnInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NetworkConnInfo")
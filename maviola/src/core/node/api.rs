@@ -18,6 +18,13 @@ pub trait NodeApiInternal<V: MaybeVersioned>: Sealed {
     /// we want to mark this method as something, that should never be used without caution.
     unsafe fn route_frame_internal(&self, frame: Frame<V>, scope: BroadcastScope) -> Result<()>;
 
+    /// <sup>⛔ | 💢</sup>
+    /// Routes MAVLink frame only to connections, where its target was seen.
+    ///
+    /// There is nothing particularly unsafe in this method in the sense of unsafe Rust. However,
+    /// we want to mark this method as something, that should never be used without caution.
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()>;
+
     /// <sup>⛔</sup>
     /// Message processor that is responsible for message signing and frame compatibility.
     fn processor_internal(&self) -> &FrameProcessor;
@@ -38,6 +45,10 @@ impl<V: MaybeVersioned> NodeApiInternal<V> for Unset {
         unreachable!()
     }
 
+    unsafe fn route_targeted_internal(&self, _: Frame<V>) -> Result<()> {
+        unreachable!()
+    }
+
     fn processor_internal(&self) -> &FrameProcessor {
         unreachable!()
     }
@@ -4,9 +4,17 @@ use std::time::Duration;
 
 use crate::core::io::{BroadcastScope, ConnectionInfo};
 use crate::core::marker::{Edge, NodeKind, Proxy, Unset};
-use crate::core::node::{NodeApi, NodeBuilder, SendFrameInternal, SendMessageInternal};
+use crate::core::node::{
+    ComponentIds, NodeApi, NodeBuilder, NodeProfile, SendFrameInternal, SendMessageInternal,
+    ShutdownMessages,
+};
 use crate::core::utils::{Guarded, Sealed, SharedCloser, Switch};
-use crate::protocol::{ComponentId, DialectSpec, FrameProcessor, SystemId};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::protocol::{
+    ComponentId, DialectSpec, FrameProcessor, SignerHandle, SignerStats, SystemId,
+};
+#[cfg(feature = "definitions")]
+use crate::protocol::{DynamicMessage, MessageDefinitions, MessageDescriptor};
 
 use crate::prelude::*;
 
@@ -64,7 +72,8 @@ use crate::prelude::*;
 /// [`FrameSigner`] that can be provided upon node configuration. It can be configured with
 /// incoming and outgoing [`SignStrategy`] for a fine-grained control over what and when should be
 /// signed. It is possible to validate several authenticated links with additional keys, but only
-/// one link `ID` / key pair will be used to sign frames.
+/// one link `ID` / key pair will be used to sign frames. Keys and strategies can be changed while
+/// node is running with [`Node::signer_handle`].
 ///
 /// ## Multiple Connections
 ///
@@ -174,7 +183,10 @@ pub struct Node<K: NodeKind, V: MaybeVersioned, A: NodeApi<V>> {
     pub(crate) is_active: Guarded<SharedCloser, Switch>,
     pub(crate) heartbeat_timeout: Duration,
     pub(crate) heartbeat_interval: Duration,
+    pub(crate) heartbeat: Heartbeat,
+    pub(crate) shutdown_messages: ShutdownMessages,
     pub(crate) processor: Arc<FrameProcessor>,
+    pub(crate) components: ComponentIds,
     pub(crate) _version: PhantomData<V>,
 }
 
@@ -183,6 +195,21 @@ impl Node<Proxy, Versionless, Unset> {
     pub fn builder() -> NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
         NodeBuilder::new()
     }
+
+    /// Instantiates [`NodeBuilder`] with [`NodeProfile::GroundStation`] defaults.
+    pub fn gcs_profile() -> NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
+        NodeBuilder::new().profile(NodeProfile::GroundStation)
+    }
+
+    /// Instantiates [`NodeBuilder`] with [`NodeProfile::Companion`] defaults.
+    pub fn companion_profile() -> NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
+        NodeBuilder::new().profile(NodeProfile::Companion)
+    }
+
+    /// Instantiates [`NodeBuilder`] with [`NodeProfile::Autopilot`] defaults.
+    pub fn autopilot_profile() -> NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
+        NodeBuilder::new().profile(NodeProfile::Autopilot)
+    }
 }
 
 impl<K: NodeKind, V: MaybeVersioned, A: NodeApi<V>> Node<K, V, A> {
@@ -219,6 +246,67 @@ impl<K: NodeKind, V: MaybeVersioned, A: NodeApi<V>> Node<K, V, A> {
         self.processor.known_dialects()
     }
 
+    /// <sup>`definitions`</sup>
+    /// Message definitions used to describe frames, if set.
+    ///
+    /// Definitions can be set by [`NodeBuilder::definitions`](super::NodeBuilder::definitions).
+    #[cfg(feature = "definitions")]
+    pub fn definitions(&self) -> Option<&MessageDefinitions> {
+        self.processor.definitions()
+    }
+
+    /// <sup>`definitions`</sup>
+    /// Describes MAVLink frame according to node message definitions.
+    ///
+    /// Resolves message name, field names, units, and enum labels. Returns
+    /// [`DefinitionsError::NotConfigured`] if node has no [`Node::definitions`] and
+    /// [`DefinitionsError::UnknownMessage`] if frame message is not defined.
+    ///
+    /// [`DefinitionsError::NotConfigured`]: crate::error::DefinitionsError::NotConfigured
+    /// [`DefinitionsError::UnknownMessage`]: crate::error::DefinitionsError::UnknownMessage
+    #[cfg(feature = "definitions")]
+    pub fn describe(&self, frame: &Frame<V>) -> Result<MessageDescriptor> {
+        self.definitions()
+            .ok_or(crate::error::DefinitionsError::NotConfigured)?
+            .describe(frame)
+    }
+
+    /// <sup>`definitions`</sup>
+    /// Decodes MAVLink frame into a [`DynamicMessage`] according to node message definitions.
+    ///
+    /// Returns the same errors as [`Node::describe`].
+    #[cfg(feature = "definitions")]
+    pub fn decode_dynamic(&self, frame: &Frame<V>) -> Result<DynamicMessage> {
+        self.definitions()
+            .ok_or(crate::error::DefinitionsError::NotConfigured)?
+            .decode(frame)
+    }
+
+    /// Handle to the node [`FrameSigner`].
+    ///
+    /// Allows to change keys, links, and signing strategies of a running node. Changes take effect
+    /// for all frames processed after the call. Signer is initially set by
+    /// [`NodeBuilder::signer`]. Dependent nodes without own signer share the handle of the node
+    /// they were built from.
+    ///
+    /// See [`SignerHandle`] for details.
+    pub fn signer_handle(&self) -> SignerHandle {
+        self.processor.signer_handle()
+    }
+
+    /// Message signing statistics.
+    ///
+    /// Counts frames signed, re-signed, verified, passed unsigned, and rejected by the node signer.
+    /// Frames rejected by the signer are reported as `Event::Invalid` with
+    /// [`ValidationReport::signature_rejection`] telling the reason.
+    ///
+    /// Returns empty statistics, if message signing is disabled. See [`SignerStats`] for details.
+    ///
+    /// [`ValidationReport::signature_rejection`]: crate::core::node::ValidationReport::signature_rejection
+    pub fn signer_stats(&self) -> SignerStats {
+        self.processor.signer_handle().stats()
+    }
+
     /// Returns `true` if node is connected.
     ///
     /// All nodes are connected by default, they can become disconnected only if I/O transport
@@ -281,6 +369,38 @@ impl<V: Versioned, A: NodeApi<V>> Node<Edge<V>, V, A> {
 
         self.is_active.set(false);
     }
+
+    /// <sup>⛔</sup>
+    /// Deactivates the node and sends shutdown messages to all channels.
+    ///
+    /// Returns `true` if at least one message has been sent.
+    pub(crate) fn send_shutdown_messages(&mut self) -> bool {
+        self.deactivate();
+
+        if self.state.is_closed() {
+            return false;
+        }
+
+        let mut sent = false;
+        for message in self.shutdown_messages.iter() {
+            let result = self.kind.endpoint.next_frame(message).map_err(Error::from);
+            let result = result.and_then(|mut frame| {
+                self.processor.process_new(&mut frame)?;
+                self.send_frame(&frame)
+            });
+
+            match result {
+                Ok(_) => sent = true,
+                Err(err) => log::warn!(
+                    "[{:?}] unable to send shutdown message #{}: {err:?}",
+                    self.info(),
+                    message.id()
+                ),
+            }
+        }
+
+        sent
+    }
 }
 
 impl<V: MaybeVersioned, A: NodeApi<V>> Node<Edge<V>, V, A> {
@@ -310,6 +430,10 @@ impl<K: NodeKind, V: MaybeVersioned, A: NodeApi<V>> SendFrameInternal<V> for Nod
     unsafe fn route_frame_internal(&self, frame: Frame<V>, scope: BroadcastScope) -> Result<()> {
         self.api.route_frame_internal(frame, scope)
     }
+
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()> {
+        self.api.route_targeted_internal(frame)
+    }
 }
 
 impl<V: MaybeVersioned, A: NodeApi<V>> SendMessageInternal<V> for Node<Edge<V>, V, A> {
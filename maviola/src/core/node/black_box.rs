@@ -0,0 +1,382 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::core::consts::DEFAULT_BLACK_BOX_CAPACITY;
+use crate::core::io::{tlog_timestamp, ChannelInfo, FrameMeta};
+use crate::core::node::ValidationReport;
+
+use crate::prelude::*;
+
+/// Black box recorder, that keeps the latest node events.
+///
+/// Black box stores the last [`BlackBox::capacity`] events of a node (and, optionally, only those,
+/// that are not older than [`BlackBox::max_age`]) in a ring buffer. Each [`BlackBoxRecord`]
+/// contains a timestamp, a channel, which received a frame or caused an event, and, for frames,
+/// the result of frame validation.
+///
+/// Records can be dumped on demand by [`BlackBox::dump`] as a human-readable log or by
+/// [`BlackBox::dump_tlog`] as a telemetry log, that can be replayed by
+/// [`TlogReader`](crate::core::io::TlogReader). Once [`BlackBox::with_dump_on_error`] is set,
+/// records are appended to the specified file each time node loses connection. This is useful for
+/// investigation of sporadic disconnects in production.
+///
+/// This is a shared handle: clones refer to the same records. Set black box with
+/// [`NodeBuilder::black_box`](crate::core::node::NodeBuilder::black_box) and keep a clone to
+/// access records.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "sync")] {
+/// use std::time::Duration;
+/// use maviola::core::node::BlackBox;
+/// use maviola::prelude::*;
+///
+/// let black_box = BlackBox::new()
+///     // Keep up to 5000 events
+///     .with_capacity(5000)
+///     // Forget events older than 30 seconds
+///     .with_max_age(Duration::from_secs(30))
+///     // Dump events, once connection is lost
+///     .with_dump_on_error("/tmp/maviola.blackbox");
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 17))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .black_box(black_box.clone())
+///     .build().unwrap();
+///
+/// /* something went wrong */
+///
+/// black_box.dump("/tmp/incident.blackbox").unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlackBox {
+    capacity: usize,
+    max_age: Option<Duration>,
+    dump_path: Option<PathBuf>,
+    records: Arc<Mutex<VecDeque<BlackBoxRecord>>>,
+}
+
+/// Record of a [`BlackBox`].
+#[derive(Clone, Debug)]
+pub struct BlackBoxRecord {
+    timestamp: SystemTime,
+    channel: Option<ChannelInfo>,
+    entry: BlackBoxEntry,
+}
+
+/// Content of a [`BlackBoxRecord`].
+#[derive(Clone, Debug)]
+pub enum BlackBoxEntry {
+    /// Received frame, that passed validation.
+    Frame(Frame<Versionless>),
+    /// Received frame, that failed validation.
+    Invalid(Frame<Versionless>, ValidationReport),
+    /// Other node event in its debug representation.
+    Event(String),
+}
+
+impl BlackBox {
+    /// Creates a black box, that keeps up to [`DEFAULT_BLACK_BOX_CAPACITY`] records of any age.
+    pub fn new() -> Self {
+        Self {
+            capacity: DEFAULT_BLACK_BOX_CAPACITY,
+            max_age: None,
+            dump_path: None,
+            records: Default::default(),
+        }
+    }
+
+    /// Sets maximum number of kept records.
+    ///
+    /// Black box always keeps at least one record.
+    pub fn with_capacity(mut self, records: usize) -> Self {
+        self.capacity = records.max(1);
+        self
+    }
+
+    /// Sets maximum age of kept records.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets a file, to which records are appended, once node loses connection.
+    ///
+    /// Each dump starts with a header line, that contains the reason of the dump.
+    pub fn with_dump_on_error(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dump_path = Some(path.into());
+        self
+    }
+
+    /// Maximum number of kept records.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Maximum age of kept records.
+    ///
+    /// [`None`] means, that records are limited only by [`BlackBox::capacity`].
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// A file, to which records are dumped on errors, if any.
+    pub fn dump_path(&self) -> Option<&Path> {
+        self.dump_path.as_deref()
+    }
+
+    /// Returns a snapshot of kept records from the oldest to the latest.
+    pub fn records(&self) -> Vec<BlackBoxRecord> {
+        let mut records = self.lock();
+        self.forget_outdated(&mut records, SystemTime::now());
+        records.iter().cloned().collect()
+    }
+
+    /// Removes all records.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Writes kept records to a file at `path` as a human-readable log.
+    ///
+    /// Each line contains a record timestamp in microseconds since Unix epoch, a channel (or `-`,
+    /// if event is not related to a particular channel), and the record itself. Existing file is
+    /// overwritten.
+    pub fn dump(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_records(&mut writer, &self.records())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes received frames (including invalid ones) to a file at `path` as a telemetry log.
+    ///
+    /// Resulting file can be replayed by [`TlogReader`](crate::core::io::TlogReader) or opened by
+    /// other tools, that support `.tlog` format. Existing file is overwritten.
+    pub fn dump_tlog(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        for record in self.records() {
+            let frame = match &record.entry {
+                BlackBoxEntry::Frame(frame) | BlackBoxEntry::Invalid(frame, _) => frame,
+                BlackBoxEntry::Event(_) => continue,
+            };
+            writer.write_all(&tlog_timestamp(record.timestamp).to_be_bytes())?;
+            mavio::io::Sender::new(&mut writer).send(frame)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// <sup>⛔</sup>
+    /// Adds a new record and forgets records, that exceed capacity or maximum age.
+    pub(crate) fn record(&self, record: BlackBoxRecord) {
+        let mut records = self.lock();
+        let now = record.timestamp;
+
+        records.push_back(record);
+        while records.len() > self.capacity {
+            records.pop_front();
+        }
+        self.forget_outdated(&mut records, now);
+    }
+
+    /// <sup>⛔</sup>
+    /// Appends kept records to [`BlackBox::dump_path`] (if set) due to the specified `reason`.
+    pub(crate) fn dump_on_error(&self, reason: &str) -> Result<()> {
+        let path = match &self.dump_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut writer = BufWriter::new(file);
+        writeln!(
+            writer,
+            "# black box dump at {}: {reason}",
+            tlog_timestamp(SystemTime::now())
+        )?;
+        write_records(&mut writer, &self.records())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn forget_outdated(&self, records: &mut VecDeque<BlackBoxRecord>, now: SystemTime) {
+        let max_age = match self.max_age {
+            Some(max_age) => max_age,
+            None => return,
+        };
+
+        while let Some(record) = records.front() {
+            match now.duration_since(record.timestamp) {
+                Ok(age) if age > max_age => records.pop_front(),
+                _ => break,
+            };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<BlackBoxRecord>> {
+        match self.records.lock() {
+            Ok(records) => records,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl Default for BlackBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlackBoxRecord {
+    /// <sup>⛔</sup>
+    /// Creates a record of an `entry` related to a `channel` with the current timestamp.
+    pub(crate) fn new(channel: Option<ChannelInfo>, entry: BlackBoxEntry) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            channel,
+            entry,
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a record of a received frame `entry` with the receive timestamp from `meta`.
+    pub(crate) fn received(meta: &FrameMeta, entry: BlackBoxEntry) -> Self {
+        Self {
+            timestamp: meta.timestamp(),
+            channel: Some(meta.channel().clone()),
+            entry,
+        }
+    }
+
+    /// Time, when frame was received or event was recorded.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Channel, that received a frame or caused an event, if any.
+    pub fn channel(&self) -> Option<&ChannelInfo> {
+        self.channel.as_ref()
+    }
+
+    /// Recorded frame or event.
+    pub fn entry(&self) -> &BlackBoxEntry {
+        &self.entry
+    }
+}
+
+/// Writes records as a human-readable log, one record per line.
+fn write_records(writer: &mut impl Write, records: &[BlackBoxRecord]) -> Result<()> {
+    for record in records {
+        write!(writer, "{} ", tlog_timestamp(record.timestamp))?;
+        match &record.channel {
+            Some(channel) => write!(writer, "{channel:?} ")?,
+            None => write!(writer, "- ")?,
+        }
+
+        match &record.entry {
+            BlackBoxEntry::Frame(frame) => {
+                writeln!(writer, "frame {} ok", describe_frame(frame))?;
+            }
+            BlackBoxEntry::Invalid(frame, report) => {
+                writeln!(writer, "frame {} invalid: {report}", describe_frame(frame))?;
+            }
+            BlackBoxEntry::Event(event) => writeln!(writer, "event {event}")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn describe_frame(frame: &Frame<Versionless>) -> String {
+    format!(
+        "{:?} sys={} comp={} seq={} msg={} len={}",
+        frame.version(),
+        frame.system_id(),
+        frame.component_id(),
+        frame.sequence(),
+        frame.message_id(),
+        frame.payload_length(),
+    )
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod black_box_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::error::FrameError;
+    use crate::protocol::Endpoint;
+
+    fn frame() -> Frame<Versionless> {
+        Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap()
+            .into_versionless()
+    }
+
+    #[test]
+    fn records_are_limited_by_capacity() {
+        let black_box = BlackBox::new().with_capacity(2);
+
+        for event in ["first", "second", "third"] {
+            black_box.record(BlackBoxRecord::new(
+                None,
+                BlackBoxEntry::Event(event.to_string()),
+            ));
+        }
+
+        let records = black_box.clone().records();
+        assert_eq!(records.len(), 2);
+        assert!(matches!(records[0].entry(), BlackBoxEntry::Event(event) if event == "second"));
+        assert!(matches!(records[1].entry(), BlackBoxEntry::Event(event) if event == "third"));
+    }
+
+    #[test]
+    fn records_are_limited_by_age() {
+        let black_box = BlackBox::new().with_max_age(Duration::from_secs(10));
+
+        let mut outdated = BlackBoxRecord::new(None, BlackBoxEntry::Frame(frame()));
+        outdated.timestamp -= Duration::from_secs(11);
+        black_box.record(outdated);
+        black_box.record(BlackBoxRecord::new(None, BlackBoxEntry::Frame(frame())));
+
+        assert_eq!(black_box.records().len(), 1);
+    }
+
+    #[test]
+    fn records_are_dumped() {
+        let black_box = BlackBox::new();
+        black_box.record(BlackBoxRecord::new(None, BlackBoxEntry::Frame(frame())));
+        black_box.record(BlackBoxRecord::new(
+            None,
+            BlackBoxEntry::Invalid(
+                frame(),
+                ValidationReport::rejected(FrameError::Checksum, None),
+            ),
+        ));
+
+        let mut log = Vec::new();
+        write_records(&mut log, &black_box.records()).unwrap();
+        let log = String::from_utf8(log).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(" - frame V2 sys=1 comp=1 seq=0 msg=0 "));
+        assert!(lines[0].ends_with(" ok"));
+        assert!(lines[1].contains(" invalid: "));
+    }
+}
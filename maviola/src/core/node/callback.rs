@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use crate::core::io::{BroadcastScope, ChannelId, ChannelInfo, ConnectionId, OutgoingFrame};
 use crate::core::utils::Sealed;
 
@@ -18,6 +20,16 @@ pub trait CallbackApiInternal<V: MaybeVersioned>: Sealed {
     /// <sup>⛔</sup>
     /// Process frame according to the defined rules.
     fn process_frame(&self, frame: &Frame<V>) -> Result<Frame<V>>;
+
+    /// <sup>⛔</sup>
+    /// Instant when the original frame was received.
+    fn original_received_at(&self) -> Instant;
+
+    /// <sup>⛔</sup>
+    /// Creates an outgoing frame, that keeps the receive time of the original frame.
+    fn outgoing_frame(&self, frame: Frame<V>, scope: BroadcastScope) -> OutgoingFrame<V> {
+        OutgoingFrame::forwarded(frame, scope, self.original_received_at())
+    }
 }
 
 /// <sup>🔒</sup>
@@ -45,17 +57,16 @@ pub trait CallbackApi<V: MaybeVersioned>: CallbackApiInternal<V> {
     /// Send frame to all channels including the one which has sent the original frame.
     fn send(&self, frame: &Frame<V>) -> Result<()> {
         let frame = self.process_frame(frame)?;
-        unsafe { self.send_internal(OutgoingFrame::scoped(frame, BroadcastScope::All)) }
+        unsafe { self.send_internal(self.outgoing_frame(frame, BroadcastScope::All)) }
     }
 
     /// Respond directly to the channel which sent the original frame.
     fn respond(&self, frame: &Frame<V>) -> Result<()> {
         let frame = self.process_frame(frame)?;
         unsafe {
-            self.send_internal(OutgoingFrame::scoped(
-                frame,
-                BroadcastScope::ExactChannel(self.channel_id()),
-            ))
+            self.send_internal(
+                self.outgoing_frame(frame, BroadcastScope::ExactChannel(self.channel_id())),
+            )
         }
     }
 
@@ -63,10 +74,9 @@ pub trait CallbackApi<V: MaybeVersioned>: CallbackApiInternal<V> {
     fn broadcast(&self, frame: &Frame<V>) -> Result<()> {
         let frame = self.process_frame(frame)?;
         unsafe {
-            self.send_internal(OutgoingFrame::scoped(
-                frame,
-                BroadcastScope::ExceptChannel(self.channel_id()),
-            ))
+            self.send_internal(
+                self.outgoing_frame(frame, BroadcastScope::ExceptChannel(self.channel_id())),
+            )
         }
     }
 
@@ -78,7 +88,7 @@ pub trait CallbackApi<V: MaybeVersioned>: CallbackApiInternal<V> {
     fn broadcast_within(&self, frame: &Frame<V>) -> Result<()> {
         let frame = self.process_frame(frame)?;
         unsafe {
-            self.send_internal(OutgoingFrame::scoped(
+            self.send_internal(self.outgoing_frame(
                 frame,
                 BroadcastScope::ExceptChannelWithin(self.channel_id()),
             ))
@@ -89,7 +99,7 @@ pub trait CallbackApi<V: MaybeVersioned>: CallbackApiInternal<V> {
     fn broadcast_except(&self, frame: &Frame<V>) -> Result<()> {
         let frame = self.process_frame(frame)?;
         unsafe {
-            self.send_internal(OutgoingFrame::scoped(
+            self.send_internal(self.outgoing_frame(
                 frame,
                 BroadcastScope::ExceptConnection(self.connection_id()),
             ))
@@ -100,10 +110,9 @@ pub trait CallbackApi<V: MaybeVersioned>: CallbackApiInternal<V> {
     fn forward(&self, frame: &Frame<V>, connection_id: ConnectionId) -> Result<()> {
         let frame = self.process_frame(frame)?;
         unsafe {
-            self.send_internal(OutgoingFrame::scoped(
-                frame,
-                BroadcastScope::ExactConnection(connection_id),
-            ))
+            self.send_internal(
+                self.outgoing_frame(frame, BroadcastScope::ExactConnection(connection_id)),
+            )
         }
     }
 }
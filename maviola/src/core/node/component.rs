@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::error::NodeError;
+use crate::protocol::ComponentId;
+
+use crate::prelude::*;
+
+/// <sup>⛔</sup>
+/// Component `ID`s of additional components attached to an edge node.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComponentIds {
+    inner: Arc<Mutex<HashSet<ComponentId>>>,
+}
+
+/// <sup>⛔</sup>
+/// Component `ID` acquired by an additional component.
+///
+/// Component `ID` is released, once lease is dropped.
+#[derive(Debug)]
+pub(crate) struct ComponentLease {
+    ids: ComponentIds,
+    component_id: ComponentId,
+}
+
+impl ComponentIds {
+    /// Acquires a `component_id`, that is not used by other components.
+    pub(crate) fn acquire(&self, component_id: ComponentId) -> Result<ComponentLease> {
+        if !self.inner.lock()?.insert(component_id) {
+            return Err(NodeError::ComponentInUse(component_id).into());
+        }
+
+        Ok(ComponentLease {
+            ids: self.clone(),
+            component_id,
+        })
+    }
+}
+
+impl ComponentLease {
+    /// Acquired component `ID`.
+    pub(crate) fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+}
+
+impl Drop for ComponentLease {
+    fn drop(&mut self) {
+        if let Ok(mut ids) = self.ids.inner.lock() {
+            ids.remove(&self.component_id);
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod component_tests {
+    use super::*;
+
+    #[test]
+    fn component_ids_are_released() {
+        let ids = ComponentIds::default();
+
+        let lease = ids.acquire(100).unwrap();
+        assert!(ids.acquire(100).is_err());
+        assert!(ids.acquire(101).is_ok());
+
+        drop(lease);
+        assert!(ids.acquire(100).is_ok());
+    }
+}
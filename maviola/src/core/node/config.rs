@@ -0,0 +1,431 @@
+//! Serializable node configuration.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::io::{ConnectionSpec, RetryStrategy};
+use crate::error::{ConfigDiagnostic, ConfigError};
+use crate::protocol::{ComponentId, MessageId, SignedLinkId, SystemId};
+
+use crate::prelude::*;
+
+/// Serializable node configuration.
+///
+/// Describes settings, that are usually provided by a deployment rather than by code: node
+/// identity, connection (a single endpoint or a network of endpoints), retry strategy, heartbeat
+/// settings, message signing, and compatibility rules. Allows to configure router daemons entirely
+/// from a TOML or JSON file.
+///
+/// Durations are defined in seconds and connections are defined by endpoint URLs (see
+/// [`ConnectionSpec`]). Signing keys are never stored in configuration: [`SigningConfig`] refers to
+/// an environment variable, that contains the secret key.
+///
+/// Configuration is applied by [`NodeBuilder::from_config`](crate::core::node::NodeBuilder::from_config).
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::node::NodeConfig;
+///
+/// let config = NodeConfig::from_toml(r#"
+///     heartbeat_timeout = 2.5
+///     retry = { Always = 1.0 }
+///
+///     [connection]
+///     connections = ["tcpin:127.0.0.1:5760", "udpin:0.0.0.0:14550"]
+///     stop_on_node_down = false
+/// "#).unwrap();
+///
+/// let restored = NodeConfig::from_json(&config.to_json().unwrap()).unwrap();
+/// assert_eq!(restored.to_toml().unwrap(), config.to_toml().unwrap());
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeConfig {
+    /// System `ID` of an edge node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_id: Option<SystemId>,
+    /// Component `ID` of an edge node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<ComponentId>,
+    /// Heartbeat timeout in seconds.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::core::utils::duration_secs::option"
+    )]
+    pub heartbeat_timeout: Option<Duration>,
+    /// Heartbeat interval in seconds.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::core::utils::duration_secs::option"
+    )]
+    pub heartbeat_interval: Option<Duration>,
+    /// Retry strategy of the node connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryStrategy>,
+    /// Node connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ConnectionConfig>,
+    /// Message signing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
+    /// Compatibility rules for `MAVLink 2` incompatibility flags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compat: Option<CompatProcessor>,
+}
+
+/// Connection of a [`NodeConfig`].
+///
+/// Serialized either as an endpoint URL or as a [`NetworkConfig`] table.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum ConnectionConfig {
+    /// Single connection defined by an endpoint URL.
+    Url(ConnectionSpec),
+    /// Network of connections.
+    Network(NetworkConfig),
+}
+
+/// Serializable configuration of a [`Network`](crate::core::network::Network).
+///
+/// Each connection becomes a network node with default settings.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Endpoint URLs of network connections.
+    pub connections: Vec<ConnectionSpec>,
+    /// Retry strategy for network connections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryStrategy>,
+    /// Window of frame deduplication in seconds.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::core::utils::duration_secs::option"
+    )]
+    pub dedup: Option<Duration>,
+    /// Whether network stops, once any connection is down and can't be restored.
+    pub stop_on_node_down: bool,
+}
+
+/// Serializable message signing settings.
+///
+/// Contains everything required to create a [`FrameSigner`] except the secret key, which is read
+/// from the environment variable [`SigningConfig::key_env`] by [`SigningConfig::to_signer`].
+/// Strategies, that are not set, have [`FrameSigner`] defaults.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Main link `ID`.
+    pub link_id: SignedLinkId,
+    /// Name of the environment variable, that contains the main secret key.
+    pub key_env: String,
+    /// Signing strategy for incoming messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incoming: Option<SignStrategy>,
+    /// Signing strategy for outgoing messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outgoing: Option<SignStrategy>,
+    /// <sup>`⍚` |</sup>
+    /// Signing strategy for messages with unknown links.
+    ///
+    /// Available only when `unstable` Cargo feature is set.
+    #[cfg(feature = "unstable")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unknown_links: Option<SignStrategy>,
+    /// Incoming strategies overridden for particular peers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub peers: Vec<PeerSigningConfig>,
+    /// Message `IDs` excluded from message signing and verification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<MessageId>,
+}
+
+/// Incoming signing strategy of a particular peer within [`SigningConfig`].
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct PeerSigningConfig {
+    /// System `ID` of a peer.
+    pub system_id: SystemId,
+    /// Signing strategy for incoming messages of this peer.
+    pub incoming: SignStrategy,
+}
+
+/// A trait for sources of [`NodeConfig`].
+///
+/// Implemented for [`NodeConfig`] itself and for configuration file paths (see
+/// [`NodeConfig::load`]). Strings are treated as paths.
+pub trait IntoNodeConfig {
+    /// Obtains node configuration.
+    fn into_node_config(self) -> Result<NodeConfig>;
+}
+
+impl NodeConfig {
+    /// Loads configuration from a file.
+    ///
+    /// Format is defined by file extension: `.toml` or `.json`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(invalid_config(format!(
+                "unknown format of {path:?}, use `.toml` or `.json` file"
+            ))),
+        }
+    }
+
+    /// Parses configuration from a TOML string.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(invalid_config)
+    }
+
+    /// Parses configuration from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(invalid_config)
+    }
+
+    /// Serializes configuration to a TOML string.
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(invalid_config)
+    }
+
+    /// Serializes configuration to a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(invalid_config)
+    }
+
+    /// Node identity, if both system and component `IDs` are defined.
+    pub fn id(&self) -> Option<MavLinkId> {
+        Some(MavLinkId::new(self.system_id?, self.component_id?))
+    }
+}
+
+impl SigningConfig {
+    /// Creates signing settings from an existing `signer`, which main key can be read from
+    /// `key_env` environment variable.
+    ///
+    /// Auxiliary links are not preserved.
+    pub fn from_signer(signer: &FrameSigner, key_env: impl Into<String>) -> Self {
+        Self {
+            link_id: signer.link_id(),
+            key_env: key_env.into(),
+            incoming: Some(signer.incoming()),
+            outgoing: Some(signer.outgoing()),
+            #[cfg(feature = "unstable")]
+            unknown_links: Some(signer.unknown_links()),
+            peers: signer
+                .peers()
+                .map(|(system_id, incoming)| PeerSigningConfig {
+                    system_id,
+                    incoming,
+                })
+                .collect(),
+            exclude: signer.exclude().collect(),
+        }
+    }
+
+    /// Creates a [`FrameSigner`] with the secret key read from [`SigningConfig::key_env`].
+    ///
+    /// Returns [`ConfigDiagnostic::MissingSigningKey`], if the environment variable is not set.
+    pub fn to_signer(&self) -> Result<FrameSigner> {
+        let key = std::env::var(&self.key_env).map_err(|_| {
+            ConfigError::from(ConfigDiagnostic::MissingSigningKey(self.key_env.clone()))
+        })?;
+
+        let mut builder = FrameSigner::builder()
+            .link_id(self.link_id)
+            .key(key.as_str())
+            .exclude(self.exclude.as_slice());
+        if let Some(strategy) = self.incoming {
+            builder = builder.incoming(strategy);
+        }
+        if let Some(strategy) = self.outgoing {
+            builder = builder.outgoing(strategy);
+        }
+        #[cfg(feature = "unstable")]
+        if let Some(strategy) = self.unknown_links {
+            builder = builder.unknown_links(strategy);
+        }
+        for peer in &self.peers {
+            builder = builder.peer_incoming(peer.system_id, peer.incoming);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+impl IntoNodeConfig for NodeConfig {
+    /// Passes [`NodeConfig`] without change.
+    fn into_node_config(self) -> Result<NodeConfig> {
+        Ok(self)
+    }
+}
+
+impl IntoNodeConfig for &NodeConfig {
+    /// Clones [`NodeConfig`].
+    fn into_node_config(self) -> Result<NodeConfig> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoNodeConfig for &Path {
+    /// Loads [`NodeConfig`] from a file.
+    fn into_node_config(self) -> Result<NodeConfig> {
+        NodeConfig::load(self)
+    }
+}
+
+impl IntoNodeConfig for PathBuf {
+    /// Loads [`NodeConfig`] from a file.
+    fn into_node_config(self) -> Result<NodeConfig> {
+        NodeConfig::load(self)
+    }
+}
+
+impl IntoNodeConfig for &str {
+    /// Loads [`NodeConfig`] from a file.
+    fn into_node_config(self) -> Result<NodeConfig> {
+        NodeConfig::load(self)
+    }
+}
+
+impl IntoNodeConfig for String {
+    /// Loads [`NodeConfig`] from a file.
+    fn into_node_config(self) -> Result<NodeConfig> {
+        NodeConfig::load(self)
+    }
+}
+
+fn invalid_config(err: impl std::fmt::Display) -> Error {
+    Error::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("invalid node configuration: {err}"),
+    ))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    const ROUTER_TOML: &str = r#"
+        system_id = 1
+        component_id = 195
+        heartbeat_timeout = 2.5
+        heartbeat_interval = 0.5
+        retry = { Attempts = [5, 1.5] }
+
+        [connection]
+        connections = ["tcpin:127.0.0.1:5760", "udp://:14550"]
+        retry = { Always = 2.0 }
+        dedup = 0.1
+
+        [signing]
+        link_id = 7
+        key_env = "MAVIOLA_CONFIG_TESTS_KEY"
+        incoming = "Strict"
+        exclude = [0]
+
+        [[signing.peers]]
+        system_id = 42
+        incoming = "Proxy"
+    "#;
+
+    #[test]
+    fn config_is_parsed_from_toml() {
+        let config = NodeConfig::from_toml(ROUTER_TOML).unwrap();
+
+        assert_eq!(config.id(), Some(MavLinkId::new(1, 195)));
+        assert_eq!(config.heartbeat_timeout, Some(Duration::from_millis(2500)));
+        assert!(matches!(
+            config.retry,
+            Some(RetryStrategy::Attempts(5, interval)) if interval == Duration::from_millis(1500)
+        ));
+
+        let Some(ConnectionConfig::Network(network)) = &config.connection else {
+            panic!("network connection expected");
+        };
+        assert_eq!(network.connections.len(), 2);
+        assert_eq!(network.connections[1].to_string(), "udpin:0.0.0.0:14550");
+        assert_eq!(network.dedup, Some(Duration::from_millis(100)));
+        assert!(!network.stop_on_node_down);
+
+        let signing = config.signing.as_ref().unwrap();
+        assert_eq!(signing.incoming, Some(SignStrategy::Strict));
+        assert_eq!(signing.peers[0].system_id, 42);
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let config = NodeConfig::from_toml(ROUTER_TOML).unwrap();
+
+        let toml = config.to_toml().unwrap();
+        assert_eq!(
+            NodeConfig::from_toml(&toml).unwrap().to_toml().unwrap(),
+            toml
+        );
+
+        let json = config.to_json().unwrap();
+        assert_eq!(
+            NodeConfig::from_json(&json).unwrap().to_toml().unwrap(),
+            toml
+        );
+    }
+
+    #[test]
+    fn single_connection_is_url() {
+        let config = NodeConfig::from_json(r#"{"connection": "tcpout:127.0.0.1:5760"}"#).unwrap();
+
+        assert!(matches!(config.connection, Some(ConnectionConfig::Url(_))));
+        assert!(config
+            .to_json()
+            .unwrap()
+            .contains("\"tcpout:127.0.0.1:5760\""));
+        assert!(config.id().is_none());
+    }
+
+    #[test]
+    fn signer_requires_key_from_environment() {
+        let signer = FrameSigner::builder()
+            .link_id(3)
+            .key("secret")
+            .outgoing(SignStrategy::Strip)
+            .peer_incoming(10, SignStrategy::Proxy)
+            .build();
+        let signing = SigningConfig::from_signer(&signer, "MAVIOLA_CONFIG_TESTS_MISSING_KEY");
+
+        let json = serde_json::to_string(&signing).unwrap();
+        assert!(!json.contains("secret"));
+        assert!(signing.to_signer().is_err());
+
+        std::env::set_var("MAVIOLA_CONFIG_TESTS_KEY", "secret");
+        let signing = SigningConfig {
+            key_env: "MAVIOLA_CONFIG_TESTS_KEY".into(),
+            ..signing
+        };
+        let restored = signing.to_signer().unwrap();
+        assert_eq!(restored.link_id(), 3);
+        assert_eq!(restored.outgoing(), SignStrategy::Strip);
+        assert_eq!(restored.incoming_for(10), SignStrategy::Proxy);
+        assert!(restored.has_valid_signature(&{
+            let mut frame = Endpoint::v2(MavLinkId::new(1, 1))
+                .next_frame(&crate::dialects::minimal::messages::Heartbeat::default())
+                .unwrap();
+            signer.sign_frame(&mut frame).unwrap();
+            frame
+        }));
+    }
+
+    #[test]
+    fn invalid_config_is_rejected() {
+        assert!(NodeConfig::from_toml("connection = \"ftp:127.0.0.1:21\"").is_err());
+        assert!(NodeConfig::from_toml("heartbeat_timeout = -1.0").is_err());
+        assert!(NodeConfig::load("node.yaml").is_err());
+    }
+}
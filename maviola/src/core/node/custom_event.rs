@@ -0,0 +1,72 @@
+use std::any::{type_name, Any};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+#[cfg(doc)]
+use crate::core::node::Node;
+
+/// Application-defined event payload.
+///
+/// Custom events allow to publish application-level signals (like "mission upload complete") into
+/// the node event stream. These events are delivered to all event subscribers alongside frames and
+/// peer updates. Use `Node::emit` to publish an event.
+///
+/// The payload can be of any type that is [`Send`] and [`Sync`]. Since events are broadcast to
+/// multiple subscribers, the payload is stored behind an [`Arc`] and cloning [`CustomEvent`] is
+/// cheap. Subscribers use [`CustomEvent::downcast_ref`] to access the payload of the expected type.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::node::CustomEvent;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct MissionUploaded { items: usize }
+///
+/// let event = CustomEvent::new(MissionUploaded { items: 12 });
+///
+/// assert!(event.is::<MissionUploaded>());
+/// assert_eq!(event.downcast_ref::<MissionUploaded>().unwrap().items, 12);
+/// assert!(event.downcast_ref::<String>().is_none());
+/// ```
+#[derive(Clone)]
+pub struct CustomEvent {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl CustomEvent {
+    /// Creates a custom event from a `payload`.
+    pub fn new<T: Any + Send + Sync>(payload: T) -> Self {
+        Self {
+            payload: Arc::new(payload),
+            type_name: type_name::<T>(),
+        }
+    }
+
+    /// Returns `true` if payload has type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Returns a reference to the payload if it has type `T`, or [`None`] otherwise.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    /// Name of the payload type.
+    ///
+    /// Intended for diagnostic purposes only. The exact contents of this string are not guaranteed
+    /// to be stable between compiler versions.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl Debug for CustomEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomEvent")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
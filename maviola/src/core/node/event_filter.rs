@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use crate::protocol::{ComponentId, MessageId, SystemId};
+
+use crate::prelude::*;
+
+/// <sup>[`async`](crate::asnc)</sup>
+/// Filter of node events for subscriptions.
+///
+/// Filtered subscriptions are matched when events are distributed to subscribers. Events, that
+/// don't match the filter, never reach the subscription channel. This saves clones of frames,
+/// when several tasks are interested only in a few message types on a busy link.
+///
+/// Filter conditions apply to frames (both valid and invalid). Other events, such as peer updates,
+/// are delivered unless [`EventFilter::frames_only`] is set.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::node::EventFilter;
+///
+/// // Heartbeats and `GLOBAL_POSITION_INT` messages sent by system `1`
+/// let filter = EventFilter::message_ids([0, 33]).system_id(1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    message_ids: Option<HashSet<MessageId>>,
+    system_id: Option<SystemId>,
+    component_id: Option<ComponentId>,
+    frames_only: bool,
+}
+
+impl EventFilter {
+    /// Creates a filter, that accepts all events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a filter, that accepts only frames with specified message `ID`s.
+    pub fn message_ids(ids: impl IntoIterator<Item = MessageId>) -> Self {
+        Self::new().with_message_ids(ids)
+    }
+
+    /// Accepts only frames with specified message `ID`s.
+    ///
+    /// Replaces previously set message `ID`s.
+    pub fn with_message_ids(mut self, ids: impl IntoIterator<Item = MessageId>) -> Self {
+        self.message_ids = Some(ids.into_iter().collect());
+        self
+    }
+
+    /// Accepts only frames sent by a system with specified `ID`.
+    pub fn system_id(mut self, system_id: SystemId) -> Self {
+        self.system_id = Some(system_id);
+        self
+    }
+
+    /// Accepts only frames sent by a component with specified `ID`.
+    pub fn component_id(mut self, component_id: ComponentId) -> Self {
+        self.component_id = Some(component_id);
+        self
+    }
+
+    /// Skips all events except frames.
+    pub fn frames_only(mut self) -> Self {
+        self.frames_only = true;
+        self
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true` if `frame` satisfies the filter.
+    pub(crate) fn matches_frame<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
+        if let Some(ids) = &self.message_ids {
+            if !ids.contains(&frame.message_id()) {
+                return false;
+            }
+        }
+        if let Some(system_id) = self.system_id {
+            if frame.system_id() != system_id {
+                return false;
+            }
+        }
+        if let Some(component_id) = self.component_id {
+            if frame.component_id() != component_id {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true` if events other than frames are accepted.
+    pub(crate) fn matches_other(&self) -> bool {
+        !self.frames_only
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod event_filter_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+    use crate::protocol::Endpoint;
+
+    #[test]
+    fn frames_are_filtered() {
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let heartbeat = endpoint.next_frame(&Heartbeat::default()).unwrap();
+        let version = endpoint.next_frame(&ProtocolVersion::default()).unwrap();
+
+        assert!(EventFilter::new().matches_frame(&heartbeat));
+        assert!(EventFilter::new().matches_other());
+
+        let filter = EventFilter::message_ids([0]);
+        assert!(filter.matches_frame(&heartbeat));
+        assert!(!filter.matches_frame(&version));
+
+        assert!(filter.clone().system_id(1).matches_frame(&heartbeat));
+        assert!(!filter.clone().system_id(2).matches_frame(&heartbeat));
+        assert!(!filter.clone().component_id(2).matches_frame(&heartbeat));
+        assert!(!filter.frames_only().matches_other());
+    }
+}
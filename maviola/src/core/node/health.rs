@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::core::io::{ChannelId, ChannelInfo};
+use crate::core::node::{ConnectionState, ConnectionStatus};
+
+#[cfg(doc)]
+use crate::core::node::Node;
+
+/// Snapshot of a node health.
+///
+/// Returned by `health` method of a [`Node`]. Unlike node events, the snapshot can be taken at
+/// any moment, which makes it suitable for liveness and readiness probes of supervisors, such as
+/// Kubernetes or systemd watchdog.
+#[derive(Clone, Debug)]
+pub struct NodeHealth {
+    /// Status of the node connection.
+    pub connection: ConnectionStatus,
+    /// Health of open channels in the order they were opened.
+    pub channels: Vec<ChannelHealth>,
+    /// Status of connection restoration.
+    pub retry: RetryStatus,
+    /// Status of the heartbeat emitter.
+    pub heartbeat: HeartbeatStatus,
+    /// Depths of node queues.
+    pub queues: QueueDepths,
+}
+
+/// Health of a particular channel within [`NodeHealth`].
+#[derive(Clone, Debug)]
+pub struct ChannelHealth {
+    /// Channel information.
+    pub info: ChannelInfo,
+    /// When the last frame was received from this channel, if any.
+    pub last_frame_at: Option<Instant>,
+}
+
+/// Status of connection restoration within [`NodeHealth`].
+///
+/// Connections are restored according to [`RetryStrategy`](crate::core::io::RetryStrategy) of a
+/// node.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetryStatus {
+    /// Connection is restored once lost.
+    ///
+    /// Connections are not restored, if retry strategy is
+    /// [`RetryStrategy::Never`](crate::core::io::RetryStrategy::Never) or transport can't be
+    /// repaired.
+    pub is_supervised: bool,
+    /// Connection is being restored.
+    pub is_restoring: bool,
+    /// Number of failed attempts since connection was lost.
+    pub failed_attempts: u64,
+    /// Number of times connection was restored.
+    pub restorations: u64,
+    /// There are no attempts left to restore connection.
+    pub is_exhausted: bool,
+}
+
+/// Status of the heartbeat emitter within [`NodeHealth`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HeartbeatStatus {
+    /// Heartbeats are emitted.
+    ///
+    /// Only active edge nodes emit heartbeats.
+    pub is_active: bool,
+    /// Heartbeat interval.
+    pub interval: Duration,
+    /// When the last heartbeat was sent, if any.
+    pub last_sent_at: Option<Instant>,
+}
+
+/// Depths of node queues within [`NodeHealth`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueDepths {
+    /// Events waiting to be received from the node.
+    ///
+    /// Only events of the node itself are accounted, receivers created from the node have their
+    /// own queues.
+    pub events: usize,
+    /// Outgoing frames, that are not yet written to any channel.
+    pub outgoing: usize,
+}
+
+/// <sup>⛔</sup>
+/// Collects node health information for [`NodeHealth`] snapshots.
+///
+/// This is a shared handle: clones refer to the same data.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HealthTracker {
+    channels: Arc<Mutex<HashMap<ChannelId, Instant>>>,
+    retry: Arc<Mutex<RetryStatus>>,
+    heartbeat: Arc<Mutex<Option<Instant>>>,
+}
+
+impl NodeHealth {
+    /// Returns `true`, if node connection is not closed.
+    ///
+    /// Lost connections are considered alive, since they still may be restored.
+    pub fn is_alive(&self) -> bool {
+        self.connection.state() != ConnectionState::Closed
+    }
+
+    /// Returns `true`, if node connection is active and has at least one open channel.
+    pub fn is_ready(&self) -> bool {
+        self.connection.is_active() && !self.channels.is_empty()
+    }
+
+    /// When the last frame was received from any of the open channels, if any.
+    pub fn last_frame_at(&self) -> Option<Instant> {
+        self.channels
+            .iter()
+            .filter_map(|channel| channel.last_frame_at)
+            .max()
+    }
+}
+
+impl HealthTracker {
+    /// Creates a tracker, that shares connection health with this one, but tracks heartbeats of
+    /// another node.
+    pub(crate) fn reuse(&self) -> Self {
+        Self {
+            channels: self.channels.clone(),
+            retry: self.retry.clone(),
+            heartbeat: Default::default(),
+        }
+    }
+
+    /// Records a frame received from a channel.
+    pub(crate) fn record_frame(&self, channel_id: ChannelId, received_at: Instant) {
+        lock(&self.channels).insert(channel_id, received_at);
+    }
+
+    /// Forgets the last frame time of a closed channel.
+    pub(crate) fn forget_channel(&self, channel_id: ChannelId) {
+        lock(&self.channels).remove(&channel_id);
+    }
+
+    /// Records a sent heartbeat.
+    pub(crate) fn record_heartbeat(&self) {
+        *lock(&self.heartbeat) = Some(Instant::now());
+    }
+
+    /// Marks connection as restored once lost.
+    pub(crate) fn supervise(&self) {
+        lock(&self.retry).is_supervised = true;
+    }
+
+    /// Records lost connection.
+    pub(crate) fn record_lost(&self) {
+        let mut retry = lock(&self.retry);
+        retry.is_restoring = true;
+        retry.failed_attempts = 0;
+    }
+
+    /// Records failed attempt to restore connection.
+    pub(crate) fn record_failed_attempt(&self) {
+        lock(&self.retry).failed_attempts += 1;
+    }
+
+    /// Records restored connection.
+    pub(crate) fn record_restored(&self) {
+        let mut retry = lock(&self.retry);
+        retry.is_restoring = false;
+        retry.restorations += 1;
+    }
+
+    /// Records, that there are no attempts left to restore connection.
+    pub(crate) fn record_exhausted(&self) {
+        let mut retry = lock(&self.retry);
+        retry.is_restoring = false;
+        retry.is_exhausted = true;
+    }
+
+    /// Creates a snapshot of node health.
+    pub(crate) fn snapshot(
+        &self,
+        connection: ConnectionStatus,
+        is_heartbeat_active: bool,
+        heartbeat_interval: Duration,
+        queues: QueueDepths,
+    ) -> NodeHealth {
+        let channels = {
+            let last_frames = lock(&self.channels);
+            connection
+                .channels()
+                .iter()
+                .map(|info| ChannelHealth {
+                    info: info.clone(),
+                    last_frame_at: last_frames.get(&info.id()).copied(),
+                })
+                .collect()
+        };
+
+        NodeHealth {
+            connection,
+            channels,
+            retry: *lock(&self.retry),
+            heartbeat: HeartbeatStatus {
+                is_active: is_heartbeat_active,
+                interval: heartbeat_interval,
+                last_sent_at: *lock(&self.heartbeat),
+            },
+            queues,
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(err) => err.into_inner(),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod health_tests {
+    use super::*;
+    use crate::core::io::{ChannelDetails, ConnectionEvent, ConnectionId};
+
+    #[test]
+    fn health_snapshot_tracks_open_channels() {
+        let tracker = HealthTracker::default();
+        let connection_id = ConnectionId::new();
+        let first = ChannelInfo::new(connection_id, ChannelDetails::Unknown);
+        let second = ChannelInfo::new(connection_id, ChannelDetails::Unknown);
+
+        let mut status = ConnectionStatus::default();
+        status.apply(&ConnectionEvent::ChannelOpened(first.clone()));
+        status.apply(&ConnectionEvent::ChannelOpened(second.clone()));
+
+        let received_at = Instant::now();
+        tracker.record_frame(first.id(), received_at);
+
+        let health = tracker.snapshot(
+            status.clone(),
+            false,
+            Duration::from_secs(1),
+            QueueDepths::default(),
+        );
+        assert!(health.is_alive());
+        assert!(health.is_ready());
+        assert_eq!(health.channels.len(), 2);
+        assert_eq!(health.channels[0].last_frame_at, Some(received_at));
+        assert_eq!(health.channels[1].last_frame_at, None);
+        assert_eq!(health.last_frame_at(), Some(received_at));
+
+        status.apply(&ConnectionEvent::ChannelClosed(first.clone()));
+        tracker.forget_channel(first.id());
+        assert!(lock(&tracker.channels).is_empty());
+        let health = tracker.snapshot(
+            status.clone(),
+            false,
+            Duration::from_secs(1),
+            QueueDepths::default(),
+        );
+        assert_eq!(health.channels.len(), 1);
+        assert_eq!(health.last_frame_at(), None);
+
+        status.close();
+        let health = tracker.snapshot(
+            status,
+            false,
+            Duration::from_secs(1),
+            QueueDepths::default(),
+        );
+        assert!(!health.is_alive());
+        assert!(!health.is_ready());
+    }
+
+    #[test]
+    fn health_tracker_tracks_retries() {
+        let tracker = HealthTracker::default();
+        tracker.supervise();
+
+        tracker.record_lost();
+        tracker.record_failed_attempt();
+        tracker.record_failed_attempt();
+        let retry = *lock(&tracker.retry);
+        assert!(retry.is_restoring);
+        assert_eq!(retry.failed_attempts, 2);
+
+        tracker.record_restored();
+        tracker.record_lost();
+        tracker.record_exhausted();
+        let retry = *lock(&tracker.reuse().retry);
+        assert!(retry.is_supervised);
+        assert!(!retry.is_restoring);
+        assert!(retry.is_exhausted);
+        assert_eq!(retry.failed_attempts, 0);
+        assert_eq!(retry.restorations, 1);
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt::{Display, Formatter};
+
+use crate::error::FrameError;
+use crate::protocol::{FrameProcessor, MavSTX, SignatureRejection};
+
+use crate::prelude::*;
+
+/// Reason, why incoming data hasn't passed validation.
+///
+/// See [`ValidationReport::kind`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvalidKind {
+    /// Frame checksum does not match `CRC_EXTRA` of a known message.
+    Checksum,
+    /// Message is not in known dialects.
+    UnknownMessage,
+    /// Frame signature is invalid.
+    Signature,
+    /// Frame has incompatibility flags, that are not supported.
+    IncompatFlags,
+    /// Frame or its start belongs to a different MAVLink protocol version.
+    Version,
+    /// Bytes, that can't be parsed as a MAVLink frame.
+    Malformed,
+}
+
+/// Structured report on incoming data, that hasn't passed validation.
+///
+/// Reports are carried by `Event::Invalid` for frames, that were received, but haven't passed
+/// validation, and by `Event::Malformed` for raw bytes, that were dropped before a frame could be
+/// parsed. Diagnostic tools may use reports to tell link corruption
+/// ([`ValidationReport::is_link_corruption`]) from dialect or protocol version mismatch
+/// ([`ValidationReport::is_dialect_mismatch`]).
+///
+/// Checksum is validated for unsigned frames with messages from known dialects, even if frame was
+/// rejected for other reasons. A failed checksum takes precedence over other reasons, since
+/// a damaged frame may fail any other check. Signed frames are authenticated by their signature
+/// instead.
+#[derive(Clone, Debug)]
+pub struct ValidationReport {
+    kind: InvalidKind,
+    error: Option<FrameError>,
+    checksum_valid: Option<bool>,
+    signature: Option<SignatureRejection>,
+    raw: Option<Vec<u8>>,
+}
+
+impl ValidationReport {
+    /// <sup>⛔</sup>
+    /// Creates a report for a frame rejected with `error`.
+    ///
+    /// Result of checksum validation should be [`None`], if `CRC_EXTRA` of the frame message is
+    /// unknown.
+    pub(crate) fn rejected(error: FrameError, checksum_valid: Option<bool>) -> Self {
+        let kind = match (&error, checksum_valid) {
+            (_, Some(false)) | (FrameError::Checksum, _) => InvalidKind::Checksum,
+            (FrameError::NotInDialect(_), _) => InvalidKind::UnknownMessage,
+            (FrameError::Signature, _) => InvalidKind::Signature,
+            (FrameError::Incompatible(_), _) => InvalidKind::IncompatFlags,
+            (FrameError::Version(_), _) => InvalidKind::Version,
+        };
+
+        Self {
+            kind,
+            error: Some(error),
+            checksum_valid,
+            signature: None,
+            raw: None,
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a report for `raw` bytes dropped before a frame could be parsed.
+    ///
+    /// Bytes starting with a magic byte of a protocol version, that does not match `V`, are
+    /// reported as [`InvalidKind::Version`].
+    pub(crate) fn malformed<V: MaybeVersioned>(raw: Vec<u8>) -> Self {
+        let version = raw
+            .first()
+            .and_then(|&byte| MavSTX::from(byte).to_mavlink_version());
+        let kind = match version {
+            Some(version) if !V::matches(version) => InvalidKind::Version,
+            _ => InvalidKind::Malformed,
+        };
+
+        Self {
+            kind,
+            error: None,
+            checksum_valid: None,
+            signature: None,
+            raw: Some(raw),
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Validates checksum of an incoming `frame` and processes it by a frame `processor`.
+    ///
+    /// Returns a report, if frame hasn't passed validation.
+    pub(crate) fn validate_incoming<V: MaybeVersioned>(
+        frame: &mut Frame<V>,
+        processor: &FrameProcessor,
+    ) -> core::result::Result<(), Self> {
+        // Signed frames are covered by signature, that is validated by frame processor
+        let checksum_valid = match frame.is_signed() {
+            true => None,
+            false => processor
+                .crc_extra(frame.message_id())
+                .map(|crc_extra| frame.validate_checksum_with_crc_extra(crc_extra).is_ok()),
+        };
+        let result = match checksum_valid {
+            Some(false) => Err(FrameError::Checksum),
+            _ => processor.process_incoming(frame),
+        };
+
+        result.map_err(|err| {
+            let rejection = match err {
+                FrameError::Signature => processor.signature_rejection(frame),
+                _ => None,
+            };
+            Self::rejected(err, checksum_valid).with_signature_rejection(rejection)
+        })
+    }
+
+    /// <sup>⛔</sup>
+    /// Sets the reason, why frame was rejected by a signer.
+    ///
+    /// Ignored, unless frame was rejected due to invalid signature.
+    pub(crate) fn with_signature_rejection(
+        mut self,
+        rejection: Option<SignatureRejection>,
+    ) -> Self {
+        if self.kind == InvalidKind::Signature {
+            self.signature = rejection;
+        }
+        self
+    }
+
+    /// Reason, why data hasn't passed validation.
+    pub fn kind(&self) -> InvalidKind {
+        self.kind
+    }
+
+    /// Error, that caused frame rejection.
+    ///
+    /// Returns [`None`] for raw bytes, that weren't parsed into a frame.
+    pub fn error(&self) -> Option<&FrameError> {
+        self.error.as_ref()
+    }
+
+    /// Returns `true`, if frame checksum doesn't match its message.
+    pub fn checksum_failed(&self) -> bool {
+        self.checksum_valid == Some(false)
+    }
+
+    /// Result of checksum validation.
+    ///
+    /// Returns [`None`], if checksum wasn't validated, since message is unknown, frame is signed,
+    /// or no frame was parsed.
+    pub fn checksum_valid(&self) -> Option<bool> {
+        self.checksum_valid
+    }
+
+    /// Returns `true`, if message is not in known dialects.
+    pub fn is_unknown_message(&self) -> bool {
+        self.kind == InvalidKind::UnknownMessage
+    }
+
+    /// Returns `true`, if frame signature is invalid.
+    pub fn is_signature_invalid(&self) -> bool {
+        self.kind == InvalidKind::Signature
+    }
+
+    /// Reason, why frame was rejected by a [`FrameSigner`].
+    ///
+    /// Returns [`None`], unless frame was rejected due to invalid signature.
+    pub fn signature_rejection(&self) -> Option<SignatureRejection> {
+        self.signature
+    }
+
+    /// Returns `true`, if frame has unsupported incompatibility flags.
+    pub fn has_unsupported_flags(&self) -> bool {
+        self.kind == InvalidKind::IncompatFlags
+    }
+
+    /// Returns `true`, if data was most likely damaged during transfer.
+    pub fn is_link_corruption(&self) -> bool {
+        matches!(self.kind, InvalidKind::Checksum | InvalidKind::Malformed)
+    }
+
+    /// Returns `true`, if peer most likely uses a different dialect or protocol version.
+    pub fn is_dialect_mismatch(&self) -> bool {
+        matches!(
+            self.kind,
+            InvalidKind::UnknownMessage | InvalidKind::Version
+        )
+    }
+
+    /// Raw bytes, that were dropped before a frame could be parsed.
+    ///
+    /// Returns [`None`] for received frames.
+    pub fn raw_bytes(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
+    }
+}
+
+impl Display for InvalidKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            InvalidKind::Checksum => "checksum mismatch",
+            InvalidKind::UnknownMessage => "unknown message",
+            InvalidKind::Signature => "invalid signature",
+            InvalidKind::IncompatFlags => "unsupported incompat flags",
+            InvalidKind::Version => "protocol version mismatch",
+            InvalidKind::Malformed => "malformed data",
+        })
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(signature) = &self.signature {
+            write!(f, " ({signature})")?;
+        } else if let Some(error) = &self.error {
+            write!(f, " ({error})")?;
+        }
+        if let Some(raw) = &self.raw {
+            write!(f, " ({} bytes)", raw.len())?;
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod invalid_tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_classified() {
+        let report = ValidationReport::rejected(FrameError::Signature, Some(true));
+        assert_eq!(report.kind(), InvalidKind::Signature);
+        assert_eq!(report.checksum_valid(), Some(true));
+        assert!(!report.is_link_corruption());
+        assert!(report.raw_bytes().is_none());
+
+        let report = ValidationReport::rejected(FrameError::NotInDialect(0), None);
+        assert!(report.is_unknown_message());
+        assert!(report.is_dialect_mismatch());
+        assert_eq!(report.checksum_valid(), None);
+
+        let report = ValidationReport::rejected(FrameError::Signature, None)
+            .with_signature_rejection(Some(SignatureRejection::UnknownLink));
+        assert_eq!(
+            report.signature_rejection(),
+            Some(SignatureRejection::UnknownLink)
+        );
+
+        // Checksum failure takes precedence
+        let report = ValidationReport::rejected(FrameError::Signature, Some(false));
+        assert_eq!(report.kind(), InvalidKind::Checksum);
+        assert!(report.checksum_failed());
+        assert!(report.is_link_corruption());
+        let report = report.with_signature_rejection(Some(SignatureRejection::Unsigned));
+        assert!(report.signature_rejection().is_none());
+    }
+
+    #[test]
+    fn raw_bytes_are_classified() {
+        let report = ValidationReport::malformed::<V2>(vec![0x01, 0x02]);
+        assert_eq!(report.kind(), InvalidKind::Malformed);
+        assert_eq!(report.raw_bytes(), Some([0x01, 0x02].as_slice()));
+        assert!(report.error().is_none());
+
+        let report = ValidationReport::malformed::<V2>(vec![0xFE, 0x09]);
+        assert_eq!(report.kind(), InvalidKind::Version);
+        assert!(report.is_dialect_mismatch());
+
+        let report = ValidationReport::malformed::<Versionless>(vec![0xFE, 0x09]);
+        assert_eq!(report.kind(), InvalidKind::Malformed);
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL;
+use crate::core::utils::{default_heartbeat_message, make_heartbeat_message};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::protocol::DialectVersion;
+
+use crate::prelude::*;
+
+/// Liveness settings of a node.
+///
+/// Proxy nodes do not emit heartbeats on their own, since they don't have system and component
+/// `ID`s. Keepalive allows such nodes (including nodes over a
+/// [`Network`](crate::core::network::Network)) to signal liveness of the process itself and to
+/// detect a silent link:
+///
+/// * [`Keepalive::heartbeats`] makes node emit heartbeats every [`Keepalive::interval`] on behalf
+///   of a caller-provided identity. Heartbeats also serve as ping frames for transports, that do
+///   not have connections (i.e. UDP).
+/// * [`Keepalive::with_stale_after`] makes node emit `Event::ConnectionStale`, once no frames were
+///   received for the specified duration, and `Event::ConnectionAlive`, once frames are received
+///   again.
+///
+/// Set keepalive with [`NodeBuilder::keepalive`](crate::core::node::NodeBuilder::keepalive).
+///
+/// # Usage
+///
+/// ```rust
+/// use std::time::Duration;
+/// use maviola::core::node::Keepalive;
+/// use maviola::prelude::*;
+///
+/// let keepalive = Keepalive::heartbeats(MavLinkId::new(1, 195))
+///     // Emit heartbeats twice per second
+///     .with_interval(Duration::from_millis(500))
+///     // Report connection as stale after 3 seconds of silence
+///     .with_stale_after(Duration::from_secs(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Keepalive {
+    identity: Option<MavLinkId>,
+    interval: Duration,
+    heartbeat: Heartbeat,
+    stale_after: Option<Duration>,
+}
+
+impl Keepalive {
+    /// Creates keepalive settings, that neither emit heartbeats nor track staleness.
+    ///
+    /// Use [`Keepalive::with_stale_after`] to track staleness without emitting heartbeats.
+    pub fn new() -> Self {
+        Self {
+            identity: None,
+            interval: DEFAULT_HEARTBEAT_INTERVAL,
+            heartbeat: default_heartbeat_message(),
+            stale_after: None,
+        }
+    }
+
+    /// Creates keepalive settings, that emit heartbeats on behalf of the specified `identity`
+    /// every [`DEFAULT_HEARTBEAT_INTERVAL`].
+    pub fn heartbeats(identity: MavLinkId) -> Self {
+        Self {
+            identity: Some(identity),
+            ..Self::new()
+        }
+    }
+
+    /// Sets interval between emitted heartbeats.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets contents of emitted heartbeats.
+    ///
+    /// The `mavlink_version` field is ignored and replaced with the version of the node dialect.
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+
+    /// Sets a duration without received frames, after which connection is considered to be stale.
+    pub fn with_stale_after(mut self, stale_after: Duration) -> Self {
+        self.stale_after = Some(stale_after);
+        self
+    }
+
+    /// Identity, on behalf of which heartbeats are emitted, if any.
+    pub fn identity(&self) -> Option<MavLinkId> {
+        self.identity
+    }
+
+    /// Interval between emitted heartbeats.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Contents of emitted heartbeats.
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+
+    /// Duration without received frames, after which connection is considered to be stale.
+    ///
+    /// [`None`] means, that staleness is not tracked.
+    pub fn stale_after(&self) -> Option<Duration> {
+        self.stale_after
+    }
+
+    /// <sup>⛔</sup>
+    /// Returns `true` if keepalive neither emits heartbeats nor tracks staleness.
+    pub(crate) fn is_noop(&self) -> bool {
+        self.identity.is_none() && self.stale_after.is_none()
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a heartbeat frame of protocol version `V` with the next sequence of `endpoint`.
+    ///
+    /// Versionless nodes emit `MAVLink 2` frames. Returns [`None`] if identity is not set.
+    pub(crate) fn heartbeat_frame<V: MaybeVersioned>(
+        &self,
+        endpoint: &Endpoint<Versionless>,
+        dialect_version: Option<DialectVersion>,
+    ) -> Option<Result<Frame<V>>> {
+        self.identity?;
+
+        let message = make_heartbeat_message(&self.heartbeat, dialect_version);
+        let frame = if V::matches(MavLinkVersion::V2) {
+            endpoint.next_frame::<V2>(&message)
+        } else {
+            endpoint.next_frame::<V1>(&message)
+        };
+
+        Some(
+            frame
+                .map_err(Error::from)
+                .and_then(|frame| frame.try_into_versioned::<V>().map_err(Error::from)),
+        )
+    }
+}
+
+impl Default for Keepalive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod keepalive_tests {
+    use super::*;
+
+    #[test]
+    fn heartbeat_frames_follow_node_version() {
+        let keepalive = Keepalive::heartbeats(MavLinkId::new(10, 195));
+        let endpoint = Endpoint::versionless(MavLinkId::new(10, 195));
+
+        let frame = keepalive
+            .heartbeat_frame::<V1>(&endpoint, None)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(frame.version(), MavLinkVersion::V1));
+        assert_eq!(frame.system_id(), 10);
+        assert_eq!(frame.component_id(), 195);
+
+        let frame = keepalive
+            .heartbeat_frame::<Versionless>(&endpoint, None)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(frame.version(), MavLinkVersion::V2));
+        assert_eq!(frame.sequence(), 1);
+    }
+
+    #[test]
+    fn no_heartbeats_without_identity() {
+        let keepalive = Keepalive::new().with_stale_after(Duration::from_secs(1));
+        let endpoint = Endpoint::versionless(MavLinkId::new(10, 195));
+
+        assert!(!keepalive.is_noop());
+        assert!(keepalive.heartbeat_frame::<V2>(&endpoint, None).is_none());
+        assert!(Keepalive::new().is_noop());
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::protocol::MessageId;
+
+#[cfg(doc)]
+use crate::core::node::{Node, NodeBuilder};
+#[cfg(doc)]
+use crate::protocol::FrameProcessor;
+
+/// Number of bits used to subdivide each power of two into linear buckets.
+///
+/// With `6` bits, each power of two is split into `64` buckets, which gives a relative error of at
+/// most `1/64` (~1.6%) for any recorded value.
+const PRECISION_BITS: u32 = 6;
+
+/// Latency histogram with a bounded relative error.
+///
+/// Values are stored in nanoseconds using a high dynamic range (HDR) bucketing scheme: each power
+/// of two is split into a fixed number of linear sub-buckets. This allows to track latencies from
+/// nanoseconds to hours without pre-allocating space for all possible values, while keeping
+/// relative error of reported percentiles within ~1.6%.
+///
+/// Histograms are obtained from [`LatencyStats`] as snapshots.
+#[derive(Clone, Default)]
+pub struct LatencyHistogram {
+    buckets: BTreeMap<usize, u64>,
+    count: u64,
+    total: u128,
+    min: u64,
+    max: u64,
+}
+
+/// End-to-end latency statistics of the node pipeline.
+///
+/// Tracks latencies per MAVLink message `ID` as [`LatencyHistogram`]s for both directions:
+///
+/// * **incoming**: from the moment when frame was received from a channel to the moment when the
+///   corresponding event was delivered to a node's user (including [`FrameProcessor`]).
+/// * **outgoing**: from the moment when frame was submitted to a node for sending to the moment
+///   when it was written to the wire. A frame broadcast to several channels is recorded once per
+///   channel.
+///
+/// This is a shared handle: clones of [`LatencyStats`] refer to the same data. Pass it to
+/// [`NodeBuilder::latency_stats`] and keep a clone to inspect statistics while [`Node`] is running.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::node::LatencyStats;
+/// use maviola::dialects::minimal::messages::Heartbeat;
+/// # use maviola::protocol::Message;
+///
+/// let stats = LatencyStats::new();
+///
+/// /* pass `stats.clone()` to a node builder and run the node */
+///
+/// if let Some(histogram) = stats.incoming(Heartbeat::message_id()) {
+///     println!("heartbeat p99 latency: {:?}", histogram.percentile(99.0));
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct LatencyStats {
+    inner: Arc<RwLock<LatencyStatsInner>>,
+}
+
+#[derive(Default)]
+struct LatencyStatsInner {
+    incoming: HashMap<MessageId, LatencyHistogram>,
+    outgoing: HashMap<MessageId, LatencyHistogram>,
+}
+
+impl LatencyHistogram {
+    /// Creates an empty histogram.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a latency value.
+    ///
+    /// Values larger than [`u64::MAX`] nanoseconds are saturated.
+    pub fn record(&mut self, latency: Duration) {
+        let value = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+
+        *self.buckets.entry(bucket_index(value)).or_default() += 1;
+
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        if value > self.max {
+            self.max = value;
+        }
+        self.count += 1;
+        self.total += value as u128;
+    }
+
+    /// Number of recorded values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns `true` if no values were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Minimum recorded latency.
+    ///
+    /// Returns [`Duration::ZERO`] for empty histograms.
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min)
+    }
+
+    /// Maximum recorded latency.
+    ///
+    /// Returns [`Duration::ZERO`] for empty histograms.
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max)
+    }
+
+    /// Mean latency.
+    ///
+    /// Returns [`Duration::ZERO`] for empty histograms.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total / self.count as u128) as u64)
+    }
+
+    /// Latency at the specified `percentile`.
+    ///
+    /// The `percentile` is clamped to `0.0..=100.0`. The returned value is the highest latency
+    /// that is equivalent (within histogram precision) to the value at this percentile, but never
+    /// less than [`Self::min`] and never greater than [`Self::max`].
+    ///
+    /// Returns [`Duration::ZERO`] for empty histograms.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+
+        let mut seen = 0;
+        for (&index, &count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                let value = bucket_upper_bound(index).clamp(self.min, self.max);
+                return Duration::from_nanos(value);
+            }
+        }
+
+        self.max()
+    }
+
+    /// Merges values from `other` histogram into this one.
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+
+        for (&index, &count) in &other.buckets {
+            *self.buckets.entry(index).or_default() += count;
+        }
+
+        if self.count == 0 || other.min < self.min {
+            self.min = other.min;
+        }
+        if other.max > self.max {
+            self.max = other.max;
+        }
+        self.count += other.count;
+        self.total += other.total;
+    }
+}
+
+impl Debug for LatencyHistogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatencyHistogram")
+            .field("count", &self.count)
+            .field("min", &self.min())
+            .field("mean", &self.mean())
+            .field("p50", &self.percentile(50.0))
+            .field("p99", &self.percentile(99.0))
+            .field("max", &self.max())
+            .finish()
+    }
+}
+
+impl LatencyStats {
+    /// Creates empty latency statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a snapshot of incoming latency histogram for a message with specified `ID`.
+    ///
+    /// Returns [`None`] if no latencies were recorded for this message.
+    pub fn incoming(&self, message_id: MessageId) -> Option<LatencyHistogram> {
+        self.read(|inner| inner.incoming.get(&message_id).cloned())
+    }
+
+    /// Returns a snapshot of outgoing latency histogram for a message with specified `ID`.
+    ///
+    /// Returns [`None`] if no latencies were recorded for this message.
+    pub fn outgoing(&self, message_id: MessageId) -> Option<LatencyHistogram> {
+        self.read(|inner| inner.outgoing.get(&message_id).cloned())
+    }
+
+    /// Returns snapshots of incoming latency histograms for all recorded message `ID`s.
+    pub fn incoming_all(&self) -> HashMap<MessageId, LatencyHistogram> {
+        self.read(|inner| inner.incoming.clone())
+    }
+
+    /// Returns snapshots of outgoing latency histograms for all recorded message `ID`s.
+    pub fn outgoing_all(&self) -> HashMap<MessageId, LatencyHistogram> {
+        self.read(|inner| inner.outgoing.clone())
+    }
+
+    /// Removes all recorded latencies.
+    pub fn reset(&self) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.incoming.clear();
+            inner.outgoing.clear();
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Records latency of an incoming frame.
+    pub(crate) fn record_incoming(&self, message_id: MessageId, latency: Duration) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner
+                .incoming
+                .entry(message_id)
+                .or_default()
+                .record(latency);
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Records latency of an outgoing frame.
+    pub(crate) fn record_outgoing(&self, message_id: MessageId, latency: Duration) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner
+                .outgoing
+                .entry(message_id)
+                .or_default()
+                .record(latency);
+        }
+    }
+
+    fn read<T: Default>(&self, f: impl FnOnce(&LatencyStatsInner) -> T) -> T {
+        match self.inner.read() {
+            Ok(inner) => f(&inner),
+            Err(_) => T::default(),
+        }
+    }
+}
+
+impl Debug for LatencyStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatencyStats").finish_non_exhaustive()
+    }
+}
+
+fn bucket_index(value: u64) -> usize {
+    if value < (1 << PRECISION_BITS) {
+        return value as usize;
+    }
+
+    let msb = u64::BITS - 1 - value.leading_zeros();
+    let shift = msb - PRECISION_BITS;
+    let sub_bucket = (value >> shift) as usize - (1 << PRECISION_BITS);
+
+    ((shift as usize + 1) << PRECISION_BITS) + sub_bucket
+}
+
+fn bucket_lower_bound(index: usize) -> u64 {
+    let group = index >> PRECISION_BITS;
+    let sub_bucket = (index & ((1 << PRECISION_BITS) - 1)) as u64;
+
+    if group == 0 {
+        return sub_bucket;
+    }
+
+    ((1 << PRECISION_BITS) + sub_bucket) << (group - 1)
+}
+
+fn bucket_upper_bound(index: usize) -> u64 {
+    let group = index >> PRECISION_BITS;
+    if group <= 1 {
+        return bucket_lower_bound(index);
+    }
+
+    bucket_lower_bound(index).saturating_add((1 << (group - 1)) - 1)
+}
+
+#[cfg(test)]
+mod latency_tests {
+    use super::*;
+
+    #[test]
+    fn bucket_bounds_are_consistent() {
+        for value in [0, 1, 63, 64, 65, 127, 128, 1_000, 1_000_000, u64::MAX] {
+            let index = bucket_index(value);
+            let lower = bucket_lower_bound(index);
+            let upper = bucket_upper_bound(index);
+            assert!(lower <= value && value <= upper);
+            assert!(upper - lower <= value >> PRECISION_BITS);
+        }
+    }
+
+    #[test]
+    fn histogram_percentiles() {
+        let mut histogram = LatencyHistogram::new();
+        for micros in 1..=1000 {
+            histogram.record(Duration::from_micros(micros));
+        }
+
+        assert_eq!(histogram.count(), 1000);
+        assert_eq!(histogram.min(), Duration::from_micros(1));
+        assert_eq!(histogram.max(), Duration::from_micros(1000));
+
+        let p50 = histogram.percentile(50.0).as_micros() as f64;
+        assert!((p50 - 500.0).abs() / 500.0 < 0.02, "p50: {p50}");
+        let p99 = histogram.percentile(99.0).as_micros() as f64;
+        assert!((p99 - 990.0).abs() / 990.0 < 0.02, "p99: {p99}");
+        assert_eq!(histogram.percentile(100.0), Duration::from_micros(1000));
+    }
+
+    #[test]
+    fn stats_are_shared_between_clones() {
+        let stats = LatencyStats::new();
+        let other = stats.clone();
+
+        other.record_incoming(0, Duration::from_millis(1));
+        other.record_outgoing(1, Duration::from_millis(2));
+
+        assert_eq!(stats.incoming(0).unwrap().count(), 1);
+        assert!(stats.incoming(1).is_none());
+        assert_eq!(stats.outgoing(1).unwrap().count(), 1);
+
+        stats.reset();
+        assert!(other.incoming_all().is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use crate::core::io::ChannelId;
+use crate::protocol::{MavLinkId, SystemId};
+
+/// <sup>⛔</sup>
+/// Channels, where peers of a node were seen.
+///
+/// Complements the node peer registry: updated upon each peer heartbeat and cleared, once peers
+/// are lost. Used to resolve peer-identity broadcast scopes, such as
+/// [`BroadcastScope::System`](crate::core::io::BroadcastScope::System).
+///
+/// This is a shared handle: clones refer to the same data. Unlike the peer registry, it is always
+/// guarded by a blocking lock, so frame senders of both synchronous and asynchronous API may
+/// consult it without awaiting.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PeerLocations {
+    inner: Arc<RwLock<HashMap<MavLinkId, HashSet<ChannelId>>>>,
+}
+
+impl PeerLocations {
+    /// Records, that peer with specified `id` was seen at a channel with `channel_id`.
+    pub(crate) fn observe(&self, id: MavLinkId, channel_id: ChannelId) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.entry(id).or_default().insert(channel_id);
+        }
+    }
+
+    /// Forgets channels of a peer with specified `id`.
+    pub(crate) fn forget(&self, id: MavLinkId) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.remove(&id);
+        }
+    }
+
+    /// Forgets channels of all peers.
+    pub(crate) fn clear(&self) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.clear();
+        }
+    }
+
+    /// Channels, where any component of a system with specified `system_id` was seen.
+    pub(crate) fn channels_of(&self, system_id: SystemId) -> HashSet<ChannelId> {
+        let inner = match self.inner.read() {
+            Ok(inner) => inner,
+            Err(_) => return HashSet::new(),
+        };
+
+        inner
+            .iter()
+            .filter(|(id, _)| id.system == system_id)
+            .flat_map(|(_, channels)| channels.iter().copied())
+            .collect()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod locations_tests {
+    use super::*;
+    use crate::core::io::ConnectionId;
+
+    #[test]
+    fn channels_are_resolved_by_system() {
+        let locations = PeerLocations::default();
+        let connection_id = ConnectionId::new();
+        let (channel_1, channel_2, channel_3) = (
+            ChannelId::new(connection_id),
+            ChannelId::new(connection_id),
+            ChannelId::new(connection_id),
+        );
+
+        locations.observe(MavLinkId::new(1, 1), channel_1);
+        locations.observe(MavLinkId::new(1, 100), channel_2);
+        locations.observe(MavLinkId::new(255, 190), channel_3);
+
+        let channels = locations.channels_of(1);
+        assert_eq!(channels.len(), 2);
+        assert!(channels.contains(&channel_1) && channels.contains(&channel_2));
+        assert!(locations.channels_of(2).is_empty());
+
+        locations.forget(MavLinkId::new(1, 1));
+        assert_eq!(locations.channels_of(1), HashSet::from([channel_2]));
+
+        locations.clone().clear();
+        assert!(locations.channels_of(255).is_empty());
+    }
+}
@@ -2,18 +2,60 @@
 
 mod api;
 mod base;
+mod black_box;
 mod callback;
+mod component;
+#[cfg(feature = "config")]
+mod config;
+mod custom_event;
+#[cfg(feature = "async")]
+mod event_filter;
+mod health;
+mod invalid;
+mod keepalive;
+mod latency;
+mod locations;
 mod node_builder;
 mod node_conf;
+mod periodic;
+mod profile;
 mod send;
+mod shutdown;
+mod stats;
+mod status;
+mod validation;
 
 pub use api::NodeApi;
 pub use base::Node;
+pub use black_box::{BlackBox, BlackBoxEntry, BlackBoxRecord};
 pub use callback::CallbackApi;
+#[cfg(feature = "config")]
+pub use config::{
+    ConnectionConfig, IntoNodeConfig, NetworkConfig, NodeConfig, PeerSigningConfig, SigningConfig,
+};
+pub use custom_event::CustomEvent;
+#[cfg(feature = "async")]
+pub use event_filter::EventFilter;
+pub use health::{ChannelHealth, HeartbeatStatus, NodeHealth, QueueDepths, RetryStatus};
+pub use invalid::{InvalidKind, ValidationReport};
+pub use keepalive::Keepalive;
+pub use latency::{LatencyHistogram, LatencyStats};
 pub use node_builder::NodeBuilder;
 pub use node_conf::{IntoNodeConf, NodeConf};
+pub use periodic::PeriodicSender;
+pub use profile::NodeProfile;
 pub use send::{SendFrame, SendMessage, SendVersionlessMessage};
+pub use shutdown::ShutdownReport;
+pub use stats::{ConnectionTraffic, ErrorCounts, StatsReport};
+pub use status::{ConnectionState, ConnectionStatus};
 
 pub(crate) use api::NodeApiInternal;
 pub(crate) use callback::CallbackApiInternal;
+pub(crate) use component::{ComponentIds, ComponentLease};
+pub(crate) use health::HealthTracker;
+pub(crate) use locations::PeerLocations;
+pub(crate) use periodic::PeriodicTasks;
 pub(crate) use send::{SendFrameInternal, SendMessageInternal};
+pub(crate) use shutdown::ShutdownMessages;
+pub(crate) use stats::TrafficStats;
+pub(crate) use status::peer_list;
@@ -1,18 +1,32 @@
 use std::marker::PhantomData;
 use std::time::Duration;
 
-use crate::core::consts::{DEFAULT_HEARTBEAT_INTERVAL, DEFAULT_HEARTBEAT_TIMEOUT};
+use crate::core::consts::DEFAULT_HEARTBEAT_TIMEOUT;
+use crate::core::io::{EgressPriorities, OutboundQueue, RetryStrategy};
 use crate::core::marker::{
     Edge, HasComponentId, HasConnConf, HasSystemId, MaybeComponentId, MaybeConnConf, MaybeSystemId,
     Proxy, Unset,
 };
 use crate::core::node::node_conf::IntoNodeConf;
-use crate::core::node::{NodeApi, NodeConf};
+use crate::core::node::validation;
+use crate::core::node::{
+    BlackBox, Keepalive, LatencyStats, NodeApi, NodeConf, NodeProfile, ShutdownMessages,
+};
+#[cfg(feature = "config")]
+use crate::core::node::{IntoNodeConfig, NodeConfig};
+use crate::core::utils::{default_heartbeat_message, ThreadSettings};
+use crate::dialects::minimal::messages::Heartbeat;
+#[cfg(feature = "config")]
+use crate::error::ConfigDiagnostic;
+use crate::error::ConfigError;
+#[cfg(feature = "definitions")]
+use crate::protocol::MessageDefinitions;
 #[cfg(feature = "unsafe")]
 use crate::protocol::ProcessFrame;
 use crate::protocol::{
-    ComponentId, CustomFrameProcessors, IntoCompatProcessor, IntoFrameSigner, KnownDialects,
-    SystemId,
+    AnomalyDetector, ComponentId, CustomFrameProcessors, FrameMiddleware, IdRemapper,
+    IntoCompatProcessor, IntoFrameSigner, KnownDialects, LinkQualityMonitor, MiddlewareChain,
+    MiddlewarePosition, ProcessingOrder, RateGovernor, SystemId,
 };
 
 use crate::prelude::*;
@@ -30,11 +44,34 @@ pub struct NodeBuilder<
     pub(crate) component_id: C,
     pub(crate) conn_conf: CC,
     pub(crate) heartbeat_timeout: Duration,
-    pub(crate) heartbeat_interval: Duration,
+    pub(crate) heartbeat_interval: Option<Duration>,
+    pub(crate) heartbeat: Heartbeat,
     pub(crate) dialects: KnownDialects,
     pub(crate) signer: Option<FrameSigner>,
     pub(crate) compat: Option<CompatProcessor>,
+    pub(crate) processing_order: ProcessingOrder,
     pub(crate) processors: CustomFrameProcessors,
+    pub(crate) middleware: MiddlewareChain,
+    pub(crate) id_remapper: Option<IdRemapper>,
+    pub(crate) anomaly_detector: Option<AnomalyDetector>,
+    pub(crate) latency_stats: Option<LatencyStats>,
+    pub(crate) rate_governor: Option<RateGovernor>,
+    pub(crate) link_quality: Option<LinkQualityMonitor>,
+    pub(crate) black_box: Option<BlackBox>,
+    pub(crate) keepalive: Option<Keepalive>,
+    pub(crate) egress_priorities: Option<EgressPriorities>,
+    #[cfg(feature = "config")]
+    pub(crate) config: Option<NodeConfig>,
+    pub(crate) io_threads: Option<ThreadSettings>,
+    pub(crate) handler_threads: Option<ThreadSettings>,
+    #[cfg(feature = "sync")]
+    pub(crate) event_channel: crate::sync::node::EventChannel,
+    #[cfg(feature = "sync")]
+    pub(crate) io_pool: Option<crate::sync::io::IoPool>,
+    pub(crate) retry: RetryStrategy,
+    pub(crate) outbound_queue: Option<OutboundQueue>,
+    pub(crate) shutdown_messages: ShutdownMessages,
+    pub(crate) channel_events: bool,
     pub(crate) _version: PhantomData<V>,
     pub(crate) _api: PhantomData<A>,
 }
@@ -47,17 +84,84 @@ impl NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
             component_id: Unset,
             conn_conf: Unset,
             heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
-            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            heartbeat_interval: None,
+            heartbeat: default_heartbeat_message(),
             dialects: Default::default(),
             signer: None,
             compat: None,
+            processing_order: Default::default(),
             processors: Default::default(),
+            middleware: Default::default(),
+            id_remapper: None,
+            anomaly_detector: None,
+            latency_stats: None,
+            rate_governor: None,
+            link_quality: None,
+            black_box: None,
+            keepalive: None,
+            egress_priorities: None,
+            #[cfg(feature = "config")]
+            config: None,
+            io_threads: None,
+            handler_threads: None,
+            #[cfg(feature = "sync")]
+            event_channel: Default::default(),
+            #[cfg(feature = "sync")]
+            io_pool: None,
+            retry: Default::default(),
+            outbound_queue: None,
+            shutdown_messages: Default::default(),
+            channel_events: false,
             _version: PhantomData,
             _api: PhantomData,
         }
     }
 }
 
+#[cfg(feature = "config")]
+impl NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
+    /// <sup>`config`</sup>
+    /// Instantiate [`NodeBuilder`] from a [`NodeConfig`] or a configuration file.
+    ///
+    /// Applies heartbeat settings, retry strategy, message signing, and compatibility rules. Node
+    /// identity and connection define the type of a builder. They are applied by
+    /// [`NodeBuilder::id_from_config`] and by `connection_from_config` of the chosen API.
+    ///
+    /// Returns [`ConfigError`] if configuration refers to a signing key, that is not available.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use maviola::core::node::NodeBuilder;
+    /// use maviola::prelude::*;
+    ///
+    /// let node = NodeBuilder::from_config("router.toml").unwrap()
+    ///     .version::<V2>()
+    ///     .sync()
+    ///     .connection_from_config().unwrap()
+    ///     .build().unwrap();
+    /// ```
+    pub fn from_config(source: impl IntoNodeConfig) -> Result<Self> {
+        let config = source.into_node_config()?;
+        let mut builder = Self::new();
+
+        if let Some(heartbeat_timeout) = config.heartbeat_timeout {
+            builder.heartbeat_timeout = heartbeat_timeout;
+        }
+        builder.heartbeat_interval = config.heartbeat_interval;
+        if let Some(retry) = config.retry {
+            builder.retry = retry;
+        }
+        if let Some(signing) = &config.signing {
+            builder.signer = Some(signing.to_signer()?);
+        }
+        builder.compat = config.compat;
+        builder.config = Some(config);
+
+        Ok(builder)
+    }
+}
+
 impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf>
     NodeBuilder<S, C, V, CC, Unset>
 {
@@ -69,10 +173,33 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
             _api: PhantomData,
         }
@@ -88,14 +215,62 @@ impl<V: MaybeVersioned, CC: MaybeConnConf, A: NodeApi<V>> NodeBuilder<Unset, Uns
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: self._api,
         }
     }
+
+    /// <sup>`config`</sup>
+    /// Set [`NodeConf::system_id`] and [`NodeConf::component_id`] from a configuration, this
+    /// builder was created from by [`NodeBuilder::from_config`].
+    ///
+    /// Returns [`ConfigDiagnostic::MissingConfigValue`](crate::error::ConfigDiagnostic::MissingConfigValue),
+    /// if configuration doesn't define both `IDs`.
+    #[cfg(feature = "config")]
+    pub fn id_from_config(self) -> Result<NodeBuilder<HasSystemId, HasComponentId, V, CC, A>> {
+        let config = self.config.as_ref();
+        let system_id = config.and_then(|config| config.system_id);
+        let component_id = config.and_then(|config| config.component_id);
+
+        match (system_id, component_id) {
+            (Some(system_id), Some(component_id)) => {
+                Ok(self.id(MavLinkId::new(system_id, component_id)))
+            }
+            (None, _) => {
+                Err(ConfigError::from(ConfigDiagnostic::MissingConfigValue("system_id")).into())
+            }
+            (_, None) => {
+                Err(ConfigError::from(ConfigDiagnostic::MissingConfigValue("component_id")).into())
+            }
+        }
+    }
 }
 
 impl<C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf, A: NodeApi<V>>
@@ -109,10 +284,33 @@ impl<C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf, A: NodeApi<V>>
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: self._api,
         }
@@ -133,10 +331,33 @@ impl<S: MaybeSystemId, V: MaybeVersioned, CC: MaybeConnConf, A: NodeApi<V>>
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: self._api,
         }
@@ -159,6 +380,74 @@ impl<
         }
     }
 
+    /// Applies preset [`NodeProfile`] for a common MAVLink role.
+    ///
+    /// Sets [`NodeConf::heartbeat_message`], [`NodeConf::heartbeat_timeout`],
+    /// [`NodeConf::heartbeat_interval`], and [`NodeConf::compat`] to the values defined by the
+    /// profile. Message signing is left untouched, use [`NodeProfile::signer`] to create a signer
+    /// suitable for the role.
+    ///
+    /// Builder methods called after the profile was applied override profile defaults.
+    pub fn profile(self, profile: NodeProfile) -> Self {
+        NodeBuilder {
+            heartbeat: profile.heartbeat_message(),
+            heartbeat_timeout: profile.heartbeat_timeout(),
+            heartbeat_interval: Some(profile.heartbeat_interval()),
+            compat: Some(profile.compat()),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::retry`].
+    ///
+    /// When set, node will restore its connection according to the specified strategy, once the
+    /// underlying transport fails. Node itself stays alive while connection is restored and emits
+    /// a `ConnectionRestored` event after each successful attempt.
+    ///
+    /// Only [repairable](NodeConf::is_repairable) connections, like TCP and UDP clients or serial
+    /// ports, can be restored.
+    pub fn retry(self, retry: RetryStrategy) -> Self {
+        NodeBuilder { retry, ..self }
+    }
+
+    /// Set [`NodeConf::channel_events`].
+    ///
+    /// When enabled, node emits `ChannelOpened` and `ChannelClosed` events each time a channel of
+    /// its connection is opened or closed, for example, when a client connects to or disconnects
+    /// from a server. Disabled by default.
+    pub fn channel_events(self, enabled: bool) -> Self {
+        NodeBuilder {
+            channel_events: enabled,
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::outbound_queue`].
+    ///
+    /// Frames sent while connection is being restored will be spooled to the `queue` and
+    /// transmitted after reconnection. Requires [`NodeBuilder::retry`] strategy to be set.
+    ///
+    /// See [`OutboundQueue`] for details.
+    pub fn outbound_queue(self, queue: OutboundQueue) -> Self {
+        NodeBuilder {
+            outbound_queue: Some(queue),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::egress_priorities`].
+    ///
+    /// Overrides priority classes of outgoing frames, that define the order in which pending
+    /// frames are written to channels.
+    ///
+    /// See [`EgressPriorities`] for details.
+    pub fn egress_priorities(self, priorities: EgressPriorities) -> Self {
+        NodeBuilder {
+            egress_priorities: Some(priorities),
+            ..self
+        }
+    }
+
     /// Set [`NodeConf::signer`].
     ///
     /// Accepts anything, that implements [`IntoFrameSigner`].
@@ -177,6 +466,123 @@ impl<
         }
     }
 
+    /// Set [`NodeConf::processing_order`].
+    ///
+    /// Defines, whether [`compat`](Self::compat) processor is applied before or after the
+    /// [`signer`](Self::signer) for incoming and outgoing frames. Orders, that invalidate
+    /// signatures, will be rejected during validation.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "sync")] {
+    /// use maviola::prelude::*;
+    /// use maviola::protocol::{IncompatFlags, ProcessingOrder, StageOrder};
+    /// use maviola::sync::prelude::*;
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .signer(FrameSigner::new(1, "secret"))
+    ///     .compat(CompatProcessor::builder()
+    ///         .incompat_flags(IncompatFlags::BIT_2)
+    ///         .incoming(CompatStrategy::Enforce)
+    ///     )
+    ///     // Validate signatures before incoming flags are enforced
+    ///     .processing_order(ProcessingOrder::new(
+    ///         StageOrder::SignerFirst,
+    ///         StageOrder::CompatFirst,
+    ///     ))
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().unwrap();
+    /// # }
+    /// ```
+    pub fn processing_order(self, order: ProcessingOrder) -> Self {
+        NodeBuilder {
+            processing_order: order,
+            ..self
+        }
+    }
+
+    /// Set [`IdRemapper`], that rewrites system and component `ID`s of incoming and outgoing
+    /// frames.
+    ///
+    /// Frames are remapped by the node frame processor. To remap frames of a particular connection
+    /// of a network, use [`ConnectionOptions::remapper`] instead.
+    ///
+    /// [`ConnectionOptions::remapper`]: crate::core::network::ConnectionOptions::remapper
+    pub fn id_remapper(self, remapper: IdRemapper) -> Self {
+        NodeBuilder {
+            id_remapper: Some(remapper),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::anomaly_detector`].
+    ///
+    /// When set, node will inspect incoming heartbeats and report suspicious peer behavior as
+    /// anomaly events.
+    pub fn anomaly_detector(self, detector: AnomalyDetector) -> Self {
+        NodeBuilder {
+            anomaly_detector: Some(detector),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::latency_stats`].
+    ///
+    /// When set, node will record pipeline latencies of incoming and outgoing frames. Keep a clone
+    /// of [`LatencyStats`] to access collected histograms.
+    pub fn latency_stats(self, stats: LatencyStats) -> Self {
+        NodeBuilder {
+            latency_stats: Some(stats),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::rate_governor`].
+    ///
+    /// When set, node will drop incoming messages, that exceed the maximum rates configured in
+    /// [`RateGovernor`].
+    pub fn rate_governor(self, governor: RateGovernor) -> Self {
+        NodeBuilder {
+            rate_governor: Some(governor),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::link_quality`].
+    ///
+    /// When set, node will track link quality of its peers and report peers with degraded links
+    /// as events.
+    pub fn link_quality(self, monitor: LinkQualityMonitor) -> Self {
+        NodeBuilder {
+            link_quality: Some(monitor),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::black_box`].
+    ///
+    /// When set, node will record its latest events into a [`BlackBox`]. Keep a clone of
+    /// [`BlackBox`] to access or dump records.
+    pub fn black_box(self, black_box: BlackBox) -> Self {
+        NodeBuilder {
+            black_box: Some(black_box),
+            ..self
+        }
+    }
+
+    /// Set [`NodeConf::keepalive`].
+    ///
+    /// When set, node will emit heartbeats on behalf of [`Keepalive::identity`] and report stale
+    /// connection as events. This is mostly useful for proxy nodes, that do not emit heartbeats
+    /// on their own.
+    pub fn keepalive(self, keepalive: Keepalive) -> Self {
+        NodeBuilder {
+            keepalive: Some(keepalive),
+            ..self
+        }
+    }
+
     /// Set main [`NodeConf::dialect`].
     ///
     /// Dialect should be specified via [turbofish](https://turbo.fish/about) syntax.
@@ -234,6 +640,19 @@ impl<
         self
     }
 
+    /// <sup>`definitions`</sup>
+    /// Sets MAVLink message definitions.
+    ///
+    /// Definitions are used by [`Node::describe`] to resolve message names, field units, and enum
+    /// labels of received frames.
+    ///
+    /// [`Node::describe`]: crate::core::node::Node::describe
+    #[cfg(feature = "definitions")]
+    pub fn definitions(mut self, definitions: MessageDefinitions) -> Self {
+        self.dialects = self.dialects.with_definitions(definitions);
+        self
+    }
+
     /// Adds a custom frame processor, that implements [`ProcessFrame`].
     #[cfg(feature = "unsafe")]
     pub fn add_processor(
@@ -245,6 +664,62 @@ impl<
         self
     }
 
+    /// Adds a [`FrameMiddleware`] at the specified [`MiddlewarePosition`].
+    ///
+    /// Middleware is applied to incoming and outgoing frames together with built-in processing
+    /// stages. Middleware added at the same position is applied in the order it was added.
+    ///
+    /// # Usage
+    ///
+    /// Collect metrics of unsigned outgoing frames and rewrite frames after they were signed:
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "sync")] {
+    /// use maviola::prelude::*;
+    /// use maviola::protocol::{BuiltinStage, FrameDirection, FrameMiddleware, MavFrame};
+    /// use maviola::protocol::MiddlewarePosition::{After, Before};
+    /// use maviola::error::FrameError;
+    /// use maviola::sync::prelude::*;
+    ///
+    /// type Result<T> = std::result::Result<T, FrameError>;
+    ///
+    /// #[derive(Debug, Default)]
+    /// struct Metrics(usize);
+    ///
+    /// impl FrameMiddleware for Metrics {
+    ///     fn process(&mut self, _: &mut MavFrame, _: FrameDirection) -> Result<()> {
+    ///         self.0 += 1;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Debug, Default)]
+    /// struct Rewriter;
+    ///
+    /// impl FrameMiddleware for Rewriter {
+    ///     fn process(&mut self, frame: &mut MavFrame, _: FrameDirection) -> Result<()> {
+    ///         /* replace frame */
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .signer(FrameSigner::new(1, "secret"))
+    ///     .add_middleware(Before(BuiltinStage::Signer), Metrics::default())
+    ///     .add_middleware(After(BuiltinStage::Signer), Rewriter)
+    ///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+    ///     .build().unwrap();
+    /// # }
+    /// ```
+    pub fn add_middleware(
+        mut self,
+        position: MiddlewarePosition,
+        middleware: impl FrameMiddleware + 'static,
+    ) -> Self {
+        self.middleware.add(position, middleware);
+        self
+    }
+
     /// <sup>⛔</sup>
     /// Helper method that create a new processor from configuration extended with the provided one.
     pub(crate) fn reuse_processor(&self, other: &FrameProcessor) -> FrameProcessor {
@@ -256,10 +731,15 @@ impl<
         if let Some(compat) = self.compat {
             builder = builder.compat(compat);
         }
+        if let Some(remapper) = self.id_remapper.clone() {
+            builder = builder.remapper(remapper);
+        }
 
         let mut processor = builder
+            .order(self.processing_order)
             .dialects(self.dialects.clone())
             .processors(self.processors.clone())
+            .middleware(self.middleware.clone())
             .build();
 
         processor.extend_with(other);
@@ -286,13 +766,102 @@ impl<V: Versioned, CC: MaybeConnConf, A: NodeApi<V>>
         heartbeat_interval: Duration,
     ) -> NodeBuilder<HasSystemId, HasComponentId, V, CC, A> {
         NodeBuilder {
-            heartbeat_interval,
+            heartbeat_interval: Some(heartbeat_interval),
             ..self
         }
     }
+
+    /// Set [`NodeConf::heartbeat_message`].
+    ///
+    /// Defines the type of the node, its autopilot, mode and status announced to peers. The
+    /// `mavlink_version` field is always set to the version of the node dialect.
+    ///
+    /// Similar to [`heartbeat_interval`](NodeBuilder::heartbeat_interval), this method is
+    /// available only for nodes with specified system and component `ID`s and MAVLink protocol
+    /// version.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "sync")] {
+    /// use maviola::dialects::minimal::enums::{MavAutopilot, MavState, MavType};
+    /// use maviola::dialects::minimal::messages::Heartbeat;
+    /// use maviola::prelude::*;
+    /// use maviola::sync::prelude::*;
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .id(MavLinkId::new(1, 1))
+    ///     .heartbeat_message(Heartbeat {
+    ///         type_: MavType::Quadrotor,
+    ///         autopilot: MavAutopilot::Generic,
+    ///         system_status: MavState::Standby,
+    ///         ..Default::default()
+    ///     })
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().unwrap();
+    /// # }
+    /// ```
+    pub fn heartbeat_message(self, heartbeat: Heartbeat) -> Self {
+        NodeBuilder { heartbeat, ..self }
+    }
+
+    /// Adds a message to [`NodeConf::shutdown_messages`].
+    ///
+    /// Messages are emitted in the order they were added when node is shut down explicitly
+    /// (see [`Node::shutdown`](crate::sync::node::EdgeNode::shutdown) for synchronous and
+    /// [`Node::shutdown`](crate::asnc::node::EdgeNode::shutdown) for asynchronous API). This allows
+    /// peers to distinguish clean shutdown from a link loss.
+    ///
+    /// Similar to [`heartbeat_interval`](NodeBuilder::heartbeat_interval), this method is
+    /// available only for nodes with specified system and component `ID`s and MAVLink protocol
+    /// version.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "sync")] {
+    /// use std::time::Duration;
+    ///
+    /// use maviola::dialects::minimal::enums::MavState;
+    /// use maviola::dialects::minimal::messages::Heartbeat;
+    /// use maviola::prelude::*;
+    /// use maviola::sync::prelude::*;
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .id(MavLinkId::new(1, 1))
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .shutdown_message(Heartbeat {
+    ///         system_status: MavState::Poweroff,
+    ///         ..Default::default()
+    ///     })
+    ///     .build().unwrap();
+    ///
+    /// // Peers will receive a heartbeat with `MAV_STATE_POWEROFF` status
+    /// node.shutdown(Duration::from_secs(1));
+    /// # }
+    /// ```
+    pub fn shutdown_message(mut self, message: impl Message + Send + Sync + 'static) -> Self {
+        self.shutdown_messages.push(message);
+        self
+    }
 }
 
 impl<V: MaybeVersioned, CC: HasConnConf, A: NodeApi<V>> NodeBuilder<Unset, Unset, V, CC, A> {
+    /// Validates node configuration without building it.
+    ///
+    /// See [`NodeConf::validate`] for details.
+    pub fn validate(&self) -> Result<()> {
+        let diagnostics = validation::common::<V>(
+            self.signer.as_ref(),
+            self.compat.as_ref(),
+            self.processing_order,
+            &self.conn_conf,
+            self.retry,
+            self.outbound_queue.as_ref(),
+        );
+        Ok(ConfigError::check(diagnostics)?)
+    }
+
     /// Build and instance of [`NodeConf`] without defined [`NodeConf::system_id`] and
     /// [`NodeConf::component_id`].
     pub fn conf(self) -> NodeConf<Proxy, V, CC> {
@@ -301,10 +870,31 @@ impl<V: MaybeVersioned, CC: HasConnConf, A: NodeApi<V>> NodeBuilder<Unset, Unset
             connection_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
         }
     }
@@ -313,6 +903,26 @@ impl<V: MaybeVersioned, CC: HasConnConf, A: NodeApi<V>> NodeBuilder<Unset, Unset
 impl<V: MaybeVersioned, CC: HasConnConf, A: NodeApi<V>>
     NodeBuilder<HasSystemId, HasComponentId, V, CC, A>
 {
+    /// Validates node configuration without building it.
+    ///
+    /// See [`NodeConf::validate`] for details.
+    pub fn validate(&self) -> Result<()> {
+        let mut diagnostics = validation::common::<V>(
+            self.signer.as_ref(),
+            self.compat.as_ref(),
+            self.processing_order,
+            &self.conn_conf,
+            self.retry,
+            self.outbound_queue.as_ref(),
+        );
+        diagnostics.extend(validation::edge(
+            self.system_id.0,
+            self.heartbeat_interval,
+            self.heartbeat_timeout,
+        ));
+        Ok(ConfigError::check(diagnostics)?)
+    }
+
     /// Build and instance of [`NodeConf`] with defined [`NodeConf::system_id`] and
     /// [`NodeConf::component_id`].
     pub fn conf(self) -> NodeConf<Edge<V>, V, CC> {
@@ -324,10 +934,31 @@ impl<V: MaybeVersioned, CC: HasConnConf, A: NodeApi<V>>
             connection_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
         }
     }
@@ -4,10 +4,18 @@ use std::marker::PhantomData;
 use std::time::Duration;
 
 use crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL;
+use crate::core::io::{EgressPriorities, OutboundQueue, RetryStrategy};
 use crate::core::marker::{Edge, HasConnConf, MaybeConnConf, NodeKind, Proxy, Unset};
-use crate::core::node::NodeBuilder;
+use crate::core::node::validation;
+use crate::core::node::{BlackBox, Keepalive, LatencyStats, NodeBuilder, ShutdownMessages};
+use crate::core::utils::{default_heartbeat_message, ThreadSettings};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::error::{ConfigDiagnostic, ConfigError};
+#[cfg(feature = "definitions")]
+use crate::protocol::MessageDefinitions;
 use crate::protocol::{
-    ComponentId, CustomFrameProcessors, DialectSpec, FrameProcessor, FrameSigner, KnownDialects,
+    AnomalyDetector, ComponentId, CustomFrameProcessors, DialectSpec, FrameProcessor, FrameSigner,
+    IdRemapper, KnownDialects, LinkQualityMonitor, MiddlewareChain, ProcessingOrder, RateGovernor,
     SystemId,
 };
 
@@ -24,11 +32,32 @@ pub struct NodeConf<K: NodeKind, V: MaybeVersioned, C: MaybeConnConf> {
     pub(crate) kind: K,
     pub(crate) connection_conf: C,
     pub(crate) heartbeat_timeout: Duration,
-    pub(crate) heartbeat_interval: Duration,
+    pub(crate) heartbeat_interval: Option<Duration>,
+    pub(crate) heartbeat: Heartbeat,
     pub(crate) dialects: KnownDialects,
     pub(crate) signer: Option<FrameSigner>,
     pub(crate) compat: Option<CompatProcessor>,
+    pub(crate) processing_order: ProcessingOrder,
     pub(crate) processors: CustomFrameProcessors,
+    pub(crate) middleware: MiddlewareChain,
+    pub(crate) id_remapper: Option<IdRemapper>,
+    pub(crate) anomaly_detector: Option<AnomalyDetector>,
+    pub(crate) latency_stats: Option<LatencyStats>,
+    pub(crate) rate_governor: Option<RateGovernor>,
+    pub(crate) link_quality: Option<LinkQualityMonitor>,
+    pub(crate) black_box: Option<BlackBox>,
+    pub(crate) keepalive: Option<Keepalive>,
+    pub(crate) egress_priorities: Option<EgressPriorities>,
+    pub(crate) io_threads: Option<ThreadSettings>,
+    pub(crate) handler_threads: Option<ThreadSettings>,
+    #[cfg(feature = "sync")]
+    pub(crate) event_channel: crate::sync::node::EventChannel,
+    #[cfg(feature = "sync")]
+    pub(crate) io_pool: Option<crate::sync::io::IoPool>,
+    pub(crate) retry: RetryStrategy,
+    pub(crate) outbound_queue: Option<OutboundQueue>,
+    pub(crate) shutdown_messages: ShutdownMessages,
+    pub(crate) channel_events: bool,
     pub(crate) _version: PhantomData<V>,
 }
 
@@ -129,6 +158,13 @@ impl<K: NodeKind, V: MaybeVersioned, C: HasConnConf> NodeConf<K, V, C> {
         self.dialects.known()
     }
 
+    /// <sup>`definitions`</sup>
+    /// Message definitions used to describe frames, if set.
+    #[cfg(feature = "definitions")]
+    pub fn definitions(&self) -> Option<&MessageDefinitions> {
+        self.dialects.definitions()
+    }
+
     /// Signature configuration.
     #[inline(always)]
     pub fn signer(&self) -> Option<&FrameSigner> {
@@ -141,6 +177,112 @@ impl<K: NodeKind, V: MaybeVersioned, C: HasConnConf> NodeConf<K, V, C> {
         self.compat.as_ref()
     }
 
+    /// Order, in which compatibility processor and signer are applied to frames.
+    #[inline(always)]
+    pub fn processing_order(&self) -> ProcessingOrder {
+        self.processing_order
+    }
+
+    /// Anomaly detector applied to incoming heartbeats.
+    #[inline(always)]
+    pub fn anomaly_detector(&self) -> Option<&AnomalyDetector> {
+        self.anomaly_detector.as_ref()
+    }
+
+    /// Latency statistics updated by the node.
+    #[inline(always)]
+    pub fn latency_stats(&self) -> Option<&LatencyStats> {
+        self.latency_stats.as_ref()
+    }
+
+    /// Rate governor applied to incoming messages.
+    #[inline(always)]
+    pub fn rate_governor(&self) -> Option<&RateGovernor> {
+        self.rate_governor.as_ref()
+    }
+
+    /// Link quality monitor applied to incoming frames and heartbeats.
+    #[inline(always)]
+    pub fn link_quality(&self) -> Option<&LinkQualityMonitor> {
+        self.link_quality.as_ref()
+    }
+
+    /// Black box, that records the latest node events.
+    #[inline(always)]
+    pub fn black_box(&self) -> Option<&BlackBox> {
+        self.black_box.as_ref()
+    }
+
+    /// Keepalive settings, that define node liveness signals.
+    #[inline(always)]
+    pub fn keepalive(&self) -> Option<&Keepalive> {
+        self.keepalive.as_ref()
+    }
+
+    /// Settings of I/O threads.
+    #[inline(always)]
+    pub fn io_threads(&self) -> Option<&ThreadSettings> {
+        self.io_threads.as_ref()
+    }
+
+    /// Settings of handler threads.
+    #[inline(always)]
+    pub fn handler_threads(&self) -> Option<&ThreadSettings> {
+        self.handler_threads.as_ref()
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Shared pool of I/O workers of a synchronous node.
+    ///
+    /// If [`None`], each channel of the node connection is handled by its own threads.
+    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub fn io_pool(&self) -> Option<&crate::sync::io::IoPool> {
+        self.io_pool.as_ref()
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Channel implementation used by the event bus of a synchronous node.
+    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub fn event_channel(&self) -> &crate::sync::node::EventChannel {
+        &self.event_channel
+    }
+
+    /// Strategy for restoring node connection after failure.
+    ///
+    /// Default strategy is [`RetryStrategy::Never`].
+    #[inline(always)]
+    pub fn retry(&self) -> RetryStrategy {
+        self.retry
+    }
+
+    /// Queue of outgoing frames sent while connection is being restored.
+    ///
+    /// See [`OutboundQueue`] for details.
+    #[inline(always)]
+    pub fn outbound_queue(&self) -> Option<&OutboundQueue> {
+        self.outbound_queue.as_ref()
+    }
+
+    /// Whether node emits events when channels of its connection are opened or closed.
+    ///
+    /// Can be changed for a running node by `Node::channel_events`.
+    #[inline(always)]
+    pub fn channel_events(&self) -> bool {
+        self.channel_events
+    }
+
+    /// Priority classes of outgoing frames.
+    ///
+    /// If not set, frames are classified by [`FramePriority::of`] their message `ID`.
+    ///
+    /// [`FramePriority::of`]: crate::core::io::FramePriority::of
+    #[inline(always)]
+    pub fn egress_priorities(&self) -> Option<&EgressPriorities> {
+        self.egress_priorities.as_ref()
+    }
+
     /// Returns `true` if it makes sense to restart the node after connection failure.
     pub fn is_repairable(&self) -> bool {
         self.connection_conf.is_repairable()
@@ -155,10 +297,15 @@ impl<K: NodeKind, V: MaybeVersioned, C: HasConnConf> NodeConf<K, V, C> {
         if let Some(compat) = self.compat {
             builder = builder.compat(compat);
         }
+        if let Some(remapper) = self.id_remapper.clone() {
+            builder = builder.remapper(remapper);
+        }
 
         builder
+            .order(self.processing_order)
             .dialects(self.dialects.clone())
             .processors(self.processors.clone())
+            .middleware(self.middleware.clone())
             .build()
     }
 }
@@ -171,6 +318,67 @@ impl<V: Versioned, C: HasConnConf> NodeConf<Edge<V>, V, C> {
     /// Default interval is [`DEFAULT_HEARTBEAT_INTERVAL`](crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL).
     pub fn heartbeat_interval(&self) -> Duration {
         self.heartbeat_interval
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL)
+    }
+
+    /// Contents of heartbeats sent by the node.
+    ///
+    /// The `mavlink_version` field is ignored and replaced with the version of the node dialect.
+    pub fn heartbeat_message(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+
+    /// Messages emitted by the node on shutdown.
+    pub fn shutdown_messages(&self) -> impl Iterator<Item = &dyn Message> {
+        self.shutdown_messages.iter()
+    }
+}
+
+impl<V: MaybeVersioned, C: HasConnConf> NodeConf<Proxy, V, C> {
+    /// Validates node configuration.
+    ///
+    /// Returns [`Error::Config`] with all found problems, if configuration is conflicting.
+    /// Builders call this method before creating a node.
+    pub fn validate(&self) -> Result<()> {
+        Ok(ConfigError::check(self.diagnostics())?)
+    }
+
+    pub(crate) fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        validation::common::<V>(
+            self.signer.as_ref(),
+            self.compat.as_ref(),
+            self.processing_order,
+            &self.connection_conf,
+            self.retry,
+            self.outbound_queue.as_ref(),
+        )
+    }
+}
+
+impl<V: MaybeVersioned, C: HasConnConf> NodeConf<Edge<V>, V, C> {
+    /// Validates node configuration.
+    ///
+    /// In addition to checks performed for [`Proxy`] nodes, ensures that node can be activated:
+    /// it should have a non-broadcast system `ID`, and explicitly set heartbeat interval should be
+    /// less than [`NodeConf::heartbeat_timeout`].
+    ///
+    /// Returns [`Error::Config`] with all found problems, if configuration is conflicting.
+    /// Builders call this method before creating a node.
+    pub fn validate(&self) -> Result<()> {
+        let mut diagnostics = validation::common::<V>(
+            self.signer.as_ref(),
+            self.compat.as_ref(),
+            self.processing_order,
+            &self.connection_conf,
+            self.retry,
+            self.outbound_queue.as_ref(),
+        );
+        diagnostics.extend(validation::edge(
+            self.system_id(),
+            self.heartbeat_interval,
+            self.heartbeat_timeout,
+        ));
+        Ok(ConfigError::check(diagnostics)?)
     }
 }
 
@@ -184,11 +392,32 @@ impl<K: NodeKind, V: MaybeVersioned, C: MaybeConnConf> NodeConf<K, V, C> {
             kind: Proxy,
             connection_conf: self.connection_conf,
             heartbeat_timeout: self.heartbeat_timeout,
-            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            heartbeat_interval: None,
+            heartbeat: default_heartbeat_message(),
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: Default::default(),
+            channel_events: false,
             _version: self._version,
         }
     }
@@ -205,6 +434,7 @@ impl<K: NodeKind, V: MaybeVersioned, C: MaybeConnConf> IntoNodeConf<K, V, C> for
 mod tests {
     use crate::core::io::ConnectionDetails;
     use crate::core::io::TcpClient;
+    use crate::protocol::{IncompatFlags, StageOrder};
 
     use super::*;
 
@@ -245,4 +475,109 @@ mod tests {
             ConnectionDetails::TcpClient { .. }
         ));
     }
+
+    #[test]
+    fn node_conf_validation() {
+        let node_conf = NodeConf::builder()
+            .sync()
+            .version::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new("localhost:5600").unwrap())
+            .signer(FrameSigner::new(1, "abc"))
+            .heartbeat_timeout(Duration::from_millis(100))
+            .conf();
+        node_conf.validate().unwrap();
+
+        let node_conf = NodeConf::builder()
+            .sync()
+            .version::<V1>()
+            .id(MavLinkId::new(0, 1))
+            .connection(TcpClient::new("localhost:5600").unwrap())
+            .signer(FrameSigner::new(1, "abc"))
+            .heartbeat_timeout(Duration::from_millis(100))
+            .heartbeat_interval(Duration::from_millis(100))
+            .conf();
+
+        match node_conf.validate() {
+            Err(Error::Config(err)) => {
+                assert_eq!(err.diagnostics().len(), 3);
+                assert!(err.diagnostics().contains(&ConfigDiagnostic::SignerOnV1));
+                assert!(err
+                    .diagnostics()
+                    .contains(&ConfigDiagnostic::MissingSystemId));
+                assert!(err.diagnostics().contains(
+                    &ConfigDiagnostic::HeartbeatIntervalExceedsTimeout {
+                        interval: Duration::from_millis(100),
+                        timeout: Duration::from_millis(100),
+                    }
+                ));
+            }
+            result => panic!("unexpected validation result: {result:?}"),
+        }
+
+        // Proxies are not activated, so only common settings are validated
+        let proxy_conf = node_conf.into_proxy();
+        match proxy_conf.validate() {
+            Err(Error::Config(err)) => {
+                assert_eq!(err.diagnostics(), &[ConfigDiagnostic::SignerOnV1]);
+            }
+            result => panic!("unexpected validation result: {result:?}"),
+        }
+
+        // Invalid nodes are not built
+        assert!(matches!(proxy_conf.update().build(), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn node_conf_processing_order_validation() {
+        let builder = || {
+            NodeConf::builder()
+                .sync()
+                .version::<V2>()
+                .id(MavLinkId::new(1, 1))
+                .connection(TcpClient::new("localhost:5600").unwrap())
+                .signer(FrameSigner::new(1, "abc"))
+                .compat(
+                    CompatProcessor::builder()
+                        .incompat_flags(IncompatFlags::BIT_2)
+                        .incoming(CompatStrategy::Enforce)
+                        .outgoing(CompatStrategy::Enforce),
+                )
+        };
+
+        match builder().conf().validate() {
+            Err(Error::Config(err)) => {
+                assert_eq!(
+                    err.diagnostics(),
+                    &[ConfigDiagnostic::CompatBeforeSignatureCheck]
+                );
+            }
+            result => panic!("unexpected validation result: {result:?}"),
+        }
+
+        let node_conf = builder()
+            .processing_order(ProcessingOrder::new(
+                StageOrder::SignerFirst,
+                StageOrder::CompatFirst,
+            ))
+            .conf();
+        node_conf.validate().unwrap();
+        assert_eq!(
+            node_conf.processing_order().incoming(),
+            StageOrder::SignerFirst
+        );
+
+        let node_conf = builder()
+            .processing_order(ProcessingOrder::new(
+                StageOrder::SignerFirst,
+                StageOrder::SignerFirst,
+            ))
+            .conf();
+        match node_conf.validate() {
+            Err(Error::Config(err)) => {
+                assert_eq!(err.diagnostics(), &[ConfigDiagnostic::CompatAfterSigning]);
+            }
+            result => panic!("unexpected validation result: {result:?}"),
+        }
+    }
 }
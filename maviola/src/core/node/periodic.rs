@@ -0,0 +1,352 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::network::TelemetryPolicy;
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+type Generator = Box<dyn FnMut() -> Box<dyn Message> + Send>;
+type DueMessages = Vec<(PeriodicSender, Box<dyn Message>)>;
+
+/// Handle of a periodic message task.
+///
+/// Periodic tasks are started by `send_periodically` of an edge node. Each task calls a generator
+/// closure and sends the produced message at a fixed rate, i.e. `MANUAL_CONTROL` or
+/// `RC_CHANNELS_OVERRIDE` for RC passthrough. All tasks of a node share a single scheduler.
+///
+/// Messages are scheduled on fixed deadlines, so delays of a particular send do not accumulate.
+/// If scheduler falls behind by more than one interval, missed messages are skipped instead of
+/// being sent in a burst. The largest observed delay is reported by [`PeriodicSender::max_jitter`].
+///
+/// Rates can be adapted to the link quality by a [`TelemetryPolicy`] set with
+/// [`PeriodicSender::set_telemetry`].
+///
+/// Tasks are stopped by [`PeriodicSender::stop`] or automatically, once node is closed. Handles
+/// can be cloned, all clones control the same task. Dropping a handle does not stop the task.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+/// use maviola::dialects::minimal::messages::Heartbeat;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5760").unwrap())
+///     .build().unwrap();
+///
+/// let mut counter = 0;
+/// let task = node.send_periodically(Duration::from_millis(20), move || {
+///     counter += 1;
+///     Heartbeat { custom_mode: counter, ..Default::default() }
+/// });
+///
+/// // Later
+/// task.stop();
+/// ```
+#[derive(Clone)]
+pub struct PeriodicSender {
+    inner: Arc<PeriodicState>,
+}
+
+struct PeriodicState {
+    interval_nanos: AtomicU64,
+    is_stopped: AtomicBool,
+    sent: AtomicU64,
+    skipped: AtomicU64,
+    max_jitter_nanos: AtomicU64,
+    telemetry: Mutex<Option<TelemetryPolicy>>,
+}
+
+/// <sup>⛔</sup>
+/// Periodic tasks of a node, that are polled by a single scheduler.
+///
+/// Clones share the same tasks.
+#[derive(Clone, Default)]
+pub(crate) struct PeriodicTasks {
+    inner: Arc<Mutex<PeriodicSchedule>>,
+}
+
+#[derive(Default)]
+struct PeriodicSchedule {
+    tasks: Vec<PeriodicTask>,
+    is_running: bool,
+}
+
+struct PeriodicTask {
+    sender: PeriodicSender,
+    generator: Generator,
+    next_at: Instant,
+}
+
+impl PeriodicSender {
+    fn new(interval: Duration) -> Self {
+        Self {
+            inner: Arc::new(PeriodicState {
+                interval_nanos: AtomicU64::new(interval.as_nanos() as u64),
+                is_stopped: AtomicBool::new(false),
+                sent: AtomicU64::new(0),
+                skipped: AtomicU64::new(0),
+                max_jitter_nanos: AtomicU64::new(0),
+                telemetry: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Interval between messages.
+    pub fn interval(&self) -> Duration {
+        Duration::from_nanos(self.inner.interval_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Changes interval between messages.
+    ///
+    /// New interval is applied after the next message is sent. Zero interval pauses the task until
+    /// a non-zero interval is set.
+    pub fn set_interval(&self, interval: Duration) {
+        self.inner
+            .interval_nanos
+            .store(interval.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Adapts rate of the task to the link quality according to telemetry `policy`.
+    ///
+    /// Interval between messages is stretched by the current [`TelemetryPolicy::scale`], but the
+    /// rate never goes below the minimum rate configured for the message. Pass [`None`] to send
+    /// messages at the nominal interval again.
+    pub fn set_telemetry(&self, policy: Option<TelemetryPolicy>) {
+        match self.inner.telemetry.lock() {
+            Ok(mut telemetry) => *telemetry = policy,
+            Err(err) => *err.into_inner() = policy,
+        }
+    }
+
+    /// Stops the task.
+    pub fn stop(&self) {
+        self.inner.is_stopped.store(true, Ordering::Release);
+    }
+
+    /// Returns `true`, if task was stopped manually or since node was closed.
+    pub fn is_stopped(&self) -> bool {
+        self.inner.is_stopped.load(Ordering::Acquire)
+    }
+
+    /// Number of sent messages.
+    pub fn sent(&self) -> u64 {
+        self.inner.sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages skipped, since scheduler fell behind.
+    pub fn skipped(&self) -> u64 {
+        self.inner.skipped.load(Ordering::Relaxed)
+    }
+
+    /// The largest observed delay between a scheduled deadline and the actual send.
+    pub fn max_jitter(&self) -> Duration {
+        Duration::from_nanos(self.inner.max_jitter_nanos.load(Ordering::Relaxed))
+    }
+
+    /// <sup>⛔</sup>
+    /// Records, that a message produced by the task was sent.
+    pub(crate) fn record_sent(&self) {
+        self.inner.sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn scaled_interval(&self, message_id: MessageId, interval: Duration) -> Duration {
+        let telemetry = match self.inner.telemetry.lock() {
+            Ok(telemetry) => telemetry,
+            Err(err) => err.into_inner(),
+        };
+        match telemetry.as_ref() {
+            Some(policy) => policy.scale_interval(message_id, interval),
+            None => interval,
+        }
+    }
+
+    fn record_jitter(&self, jitter: Duration) {
+        self.inner
+            .max_jitter_nanos
+            .fetch_max(jitter.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Debug for PeriodicSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PeriodicSender")
+            .field("interval", &self.interval())
+            .field("is_stopped", &self.is_stopped())
+            .field("sent", &self.sent())
+            .field("skipped", &self.skipped())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PeriodicTasks {
+    /// <sup>⛔</sup>
+    /// Adds a task, that sends messages produced by `generator` every `interval`.
+    ///
+    /// The first message is sent immediately. Returns task handle and `true`, if scheduler is not
+    /// running and has to be spawned.
+    pub(crate) fn add<M: Message + 'static>(
+        &self,
+        interval: Duration,
+        mut generator: impl FnMut() -> M + Send + 'static,
+    ) -> (PeriodicSender, bool) {
+        let sender = PeriodicSender::new(interval);
+        let task = PeriodicTask {
+            sender: sender.clone(),
+            generator: Box::new(move || Box::new(generator())),
+            next_at: Instant::now(),
+        };
+
+        match self.inner.lock() {
+            Ok(mut schedule) => {
+                schedule.tasks.push(task);
+                let should_spawn = !schedule.is_running;
+                schedule.is_running = true;
+                (sender, should_spawn)
+            }
+            Err(_) => {
+                sender.stop();
+                (sender, false)
+            }
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Produces messages of tasks due at `now` and removes stopped tasks.
+    ///
+    /// Returns produced messages with their task handles, and the next deadline, if any.
+    pub(crate) fn poll(&self, now: Instant) -> (DueMessages, Option<Instant>) {
+        let mut schedule = match self.inner.lock() {
+            Ok(schedule) => schedule,
+            Err(_) => return (Vec::new(), None),
+        };
+        schedule.tasks.retain(|task| !task.sender.is_stopped());
+
+        let mut messages = Vec::new();
+        let mut next_due: Option<Instant> = None;
+        for task in schedule.tasks.iter_mut() {
+            let interval = task.sender.interval();
+            // Paused tasks are resumed immediately, once interval is set
+            if interval.is_zero() {
+                task.next_at = now;
+                continue;
+            }
+
+            if task.next_at <= now {
+                let jitter = now - task.next_at;
+                task.sender.record_jitter(jitter);
+                let message = (task.generator)();
+                let interval = task.sender.scaled_interval(message.id(), interval);
+                messages.push((task.sender.clone(), message));
+
+                let missed = (jitter.as_nanos() / interval.as_nanos()) as u32;
+                if missed > 0 {
+                    task.sender
+                        .inner
+                        .skipped
+                        .fetch_add(missed as u64, Ordering::Relaxed);
+                }
+                task.next_at += interval * (missed + 1);
+            }
+
+            next_due = Some(match next_due {
+                Some(next_due) => next_due.min(task.next_at),
+                None => task.next_at,
+            });
+        }
+
+        (messages, next_due)
+    }
+
+    /// <sup>⛔</sup>
+    /// Stops all tasks, once scheduler is stopped.
+    pub(crate) fn stop_all(&self) {
+        if let Ok(mut schedule) = self.inner.lock() {
+            for task in schedule.tasks.drain(..) {
+                task.sender.stop();
+            }
+            schedule.is_running = false;
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod periodic_tests {
+    use super::*;
+    use crate::dialects::minimal::messages::Heartbeat;
+
+    #[test]
+    fn tasks_are_scheduled_on_fixed_deadlines() {
+        let tasks = PeriodicTasks::default();
+        let (sender, should_spawn) = tasks.add(Duration::from_millis(10), Heartbeat::default);
+        assert!(should_spawn);
+        assert!(!tasks.add(Duration::from_secs(1), Heartbeat::default).1);
+
+        let start = Instant::now();
+        let (messages, next_due) = tasks.poll(start);
+        assert_eq!(messages.len(), 2);
+        assert!(next_due.unwrap() <= start + Duration::from_millis(10));
+
+        // Delays do not accumulate
+        let (messages, next_due) = tasks.poll(start + Duration::from_millis(13));
+        assert_eq!(messages.len(), 1);
+        assert!(next_due.unwrap() <= start + Duration::from_millis(20));
+        assert!(sender.max_jitter() >= Duration::from_millis(3));
+
+        // Missed messages are skipped
+        let (messages, _) = tasks.poll(start + Duration::from_millis(55));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(sender.skipped(), 3);
+
+        // Zero interval pauses the task
+        sender.set_interval(Duration::ZERO);
+        let (messages, _) = tasks.poll(start + Duration::from_millis(100));
+        assert!(messages.is_empty());
+        sender.set_interval(Duration::from_millis(10));
+        let (messages, _) = tasks.poll(start + Duration::from_millis(101));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(sender.skipped(), 3);
+
+        sender.stop();
+        let (messages, _) = tasks.poll(start + Duration::from_millis(200));
+        assert!(messages.is_empty());
+
+        tasks.stop_all();
+        assert!(tasks.add(Duration::from_secs(1), Heartbeat::default).1);
+    }
+
+    #[test]
+    fn intervals_are_scaled_by_telemetry_policy() {
+        let tasks = PeriodicTasks::default();
+        let (sender, _) = tasks.add(Duration::from_millis(10), Heartbeat::default);
+        let policy = TelemetryPolicy::new();
+        sender.set_telemetry(Some(policy.clone()));
+
+        let (_, next_due) = tasks.poll(Instant::now());
+        let next_at = next_due.unwrap();
+
+        // Rate is halved, once link degrades
+        policy.report_quality(0.0);
+        let (messages, next_due) = tasks.poll(next_at);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(next_due.unwrap(), next_at + Duration::from_millis(20));
+        assert_eq!(sender.skipped(), 0);
+
+        // Nominal interval is restored without policy
+        sender.set_telemetry(None);
+        let next_at = next_due.unwrap();
+        let (_, next_due) = tasks.poll(next_at);
+        assert_eq!(next_due.unwrap(), next_at + Duration::from_millis(10));
+    }
+}
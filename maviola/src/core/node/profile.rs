@@ -0,0 +1,237 @@
+use std::time::Duration;
+
+use crate::core::consts::{DEFAULT_HEARTBEAT_INTERVAL, DEFAULT_HEARTBEAT_TIMEOUT};
+use crate::dialects::minimal::enums::{MavAutopilot, MavModeFlag, MavState, MavType};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::protocol::{ComponentId, IncompatFlags, MessageId, SecretKey, SignedLinkId};
+
+use crate::prelude::*;
+
+/// `RADIO_STATUS` message, that is accepted unsigned even when signing is required.
+///
+/// Radio modems inject this message into the stream and can't sign it.
+const RADIO_STATUS_MESSAGE_ID: MessageId = 109;
+
+/// Heartbeat timeout for ground stations, tolerates telemetry radio dropouts.
+const GCS_HEARTBEAT_TIMEOUT: Duration = Duration::from_millis(3500);
+/// Heartbeat timeout for autopilots, matches common ground station link loss failsafe.
+const AUTOPILOT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Preset node configuration for a common MAVLink role.
+///
+/// Profiles encode sane defaults for heartbeat contents, heartbeat timeouts, compatibility checks,
+/// and message signing. Apply a profile with [`NodeBuilder::profile`] or start with one of
+/// [`Node::gcs_profile`], [`Node::companion_profile`], or [`Node::autopilot_profile`]. Every
+/// setting of a profile can be overridden by calling the corresponding builder method after the
+/// profile was applied.
+///
+/// Profile defines the following settings:
+///
+/// * [`NodeConf::heartbeat_message`] announces node type and autopilot.
+/// * [`NodeConf::heartbeat_timeout`] is increased for nodes, that usually talk over lossy radio
+///   links.
+/// * [`NodeConf::heartbeat_interval`] is set to [`DEFAULT_HEARTBEAT_INTERVAL`].
+/// * [`NodeConf::compat`] rejects incoming frames with unknown incompatibility flags, as required
+///   by MAVLink specification.
+///
+/// Since profiles can't provide a secret key, message signing is not enabled. Use
+/// [`NodeProfile::signer`] to create a [`FrameSigner`] with signing strategies suitable for the
+/// role.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "sync")] {
+/// use std::time::Duration;
+///
+/// use maviola::core::node::NodeProfile;
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::gcs_profile()
+///     .sync()
+///     .version::<V2>()
+///     .id(MavLinkId::new(255, NodeProfile::GroundStation.component_id()))
+///     .signer(NodeProfile::GroundStation.signer(1, "secret"))
+///     // Override profile defaults
+///     .heartbeat_timeout(Duration::from_secs(10))
+///     .connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///     .build().unwrap();
+/// # }
+/// ```
+///
+/// [`NodeBuilder::profile`]: crate::core::node::NodeBuilder::profile
+/// [`NodeConf::heartbeat_message`]: crate::core::node::NodeConf::heartbeat_message
+/// [`NodeConf::heartbeat_timeout`]: crate::core::node::NodeConf::heartbeat_timeout
+/// [`NodeConf::heartbeat_interval`]: crate::core::node::NodeConf::heartbeat_interval
+/// [`NodeConf::compat`]: crate::core::node::NodeConf::compat
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NodeProfile {
+    /// Ground control station.
+    GroundStation,
+    /// Onboard companion computer, that usually talks to the autopilot over a wired link.
+    Companion,
+    /// Flight controller.
+    Autopilot,
+}
+
+impl NodeProfile {
+    /// Conventional component `ID` of a node with this role.
+    pub fn component_id(&self) -> ComponentId {
+        match self {
+            NodeProfile::GroundStation => 190,
+            NodeProfile::Companion => 191,
+            NodeProfile::Autopilot => 1,
+        }
+    }
+
+    /// Heartbeat, that announces a node with this role.
+    pub fn heartbeat_message(&self) -> Heartbeat {
+        match self {
+            NodeProfile::GroundStation => Heartbeat {
+                type_: MavType::Gcs,
+                autopilot: MavAutopilot::Invalid,
+                base_mode: MavModeFlag::empty(),
+                custom_mode: 0,
+                system_status: MavState::Active,
+                mavlink_version: 0,
+            },
+            NodeProfile::Companion => Heartbeat {
+                type_: MavType::OnboardController,
+                autopilot: MavAutopilot::Invalid,
+                base_mode: MavModeFlag::empty(),
+                custom_mode: 0,
+                system_status: MavState::Active,
+                mavlink_version: 0,
+            },
+            NodeProfile::Autopilot => Heartbeat {
+                type_: MavType::Generic,
+                autopilot: MavAutopilot::Generic,
+                base_mode: MavModeFlag::CUSTOM_MODE_ENABLED,
+                custom_mode: 0,
+                system_status: MavState::Standby,
+                mavlink_version: 0,
+            },
+        }
+    }
+
+    /// Time after which silent peers are considered lost.
+    pub fn heartbeat_timeout(&self) -> Duration {
+        match self {
+            NodeProfile::GroundStation => GCS_HEARTBEAT_TIMEOUT,
+            NodeProfile::Companion => DEFAULT_HEARTBEAT_TIMEOUT,
+            NodeProfile::Autopilot => AUTOPILOT_HEARTBEAT_TIMEOUT,
+        }
+    }
+
+    /// Interval between heartbeats.
+    pub fn heartbeat_interval(&self) -> Duration {
+        DEFAULT_HEARTBEAT_INTERVAL
+    }
+
+    /// Compatibility processor, that rejects incoming frames with unknown incompatibility flags.
+    ///
+    /// Outgoing frames are passed as they are, so the processor can be combined with any signing
+    /// order.
+    pub fn compat(&self) -> CompatProcessor {
+        CompatProcessor::builder()
+            .incompat_flags(IncompatFlags::empty())
+            .incoming(CompatStrategy::Reject)
+            .outgoing(CompatStrategy::Proxy)
+            .build()
+    }
+
+    /// Creates frame signer with signing strategies suitable for this role.
+    ///
+    /// * Ground stations validate incoming signatures and sign outgoing frames.
+    /// * Companion computers validate incoming signatures and re-sign forwarded frames with their
+    ///   own key.
+    /// * Autopilots reject unsigned incoming frames and sign outgoing frames.
+    ///
+    /// All profiles accept unsigned `RADIO_STATUS` messages, since radio modems can't sign them.
+    pub fn signer<K: Into<SecretKey>>(&self, link_id: SignedLinkId, key: K) -> FrameSigner {
+        let (incoming, outgoing) = match self {
+            NodeProfile::GroundStation => (SignStrategy::Sign, SignStrategy::Sign),
+            NodeProfile::Companion => (SignStrategy::Sign, SignStrategy::ReSign),
+            NodeProfile::Autopilot => (SignStrategy::Strict, SignStrategy::Sign),
+        };
+
+        FrameSigner::builder()
+            .link_id(link_id)
+            .key(key)
+            .incoming(incoming)
+            .outgoing(outgoing)
+            .exclude(&[RADIO_STATUS_MESSAGE_ID])
+            .build()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod profile_tests {
+    use super::*;
+
+    use crate::core::node::NodeBuilder;
+    use crate::protocol::Endpoint;
+
+    #[test]
+    fn profiles_are_overridable() {
+        let builder = NodeBuilder::new().profile(NodeProfile::GroundStation);
+        assert!(matches!(builder.heartbeat.type_, MavType::Gcs));
+        assert_eq!(builder.heartbeat_timeout, GCS_HEARTBEAT_TIMEOUT);
+        assert_eq!(builder.heartbeat_interval, Some(DEFAULT_HEARTBEAT_INTERVAL));
+        assert!(builder.compat.is_some());
+        assert!(builder.signer.is_none());
+
+        let builder = builder.heartbeat_timeout(Duration::from_secs(10));
+        assert_eq!(builder.heartbeat_timeout, Duration::from_secs(10));
+        assert!(matches!(builder.heartbeat.type_, MavType::Gcs));
+    }
+
+    #[test]
+    fn profile_compat_rejects_unknown_flags() {
+        let compat = NodeProfile::Autopilot.compat();
+        let message = NodeProfile::Autopilot.heartbeat_message();
+        let crc_extra = Heartbeat::crc_extra();
+
+        let mut frame = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&message)
+            .unwrap();
+        assert!(compat
+            .process_incoming_with_crc_extra(&mut frame, crc_extra)
+            .is_ok());
+
+        let signer = NodeProfile::Autopilot.signer(1, "secret");
+        signer.sign_frame(&mut frame).unwrap();
+        assert!(compat
+            .process_incoming_with_crc_extra(&mut frame, crc_extra)
+            .is_ok());
+
+        let mut frame = Frame::builder()
+            .sequence(0)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .incompat_flags(IncompatFlags::BIT_2)
+            .message(&message)
+            .unwrap()
+            .build();
+        assert!(compat
+            .process_incoming_with_crc_extra(&mut frame, crc_extra)
+            .is_err());
+    }
+
+    #[test]
+    fn profile_signers() {
+        let signer = NodeProfile::Autopilot.signer(1, "secret");
+        assert_eq!(signer.incoming(), SignStrategy::Strict);
+        assert_eq!(signer.outgoing(), SignStrategy::Sign);
+        assert!(signer.exclude().any(|id| id == RADIO_STATUS_MESSAGE_ID));
+
+        let signer = NodeProfile::Companion.signer(1, "secret");
+        assert_eq!(signer.outgoing(), SignStrategy::ReSign);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::core::io::BroadcastScope;
 use crate::core::utils::Sealed;
-use crate::protocol::{DialectSpec, FrameProcessor};
+use crate::error::NodeError;
+use crate::protocol::{AddressedMessage, DialectSpec, FrameProcessor};
 
 use crate::prelude::*;
 
@@ -20,6 +21,13 @@ pub trait SendFrameInternal<V: MaybeVersioned>: Sealed {
     /// There is nothing particularly unsafe in this method in the sense of unsafe Rust. However,
     /// we want to mark this method as something, that should never be used without caution.
     unsafe fn route_frame_internal(&self, frame: Frame<V>, scope: BroadcastScope) -> Result<()>;
+
+    /// <sup>⛔ | 💢</sup>
+    /// Routes MAVLink frame only to connections, where its target was seen.
+    ///
+    /// There is nothing particularly unsafe in this method in the sense of unsafe Rust. However,
+    /// we want to mark this method as something, that should never be used without caution.
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()>;
 }
 
 /// <sup>⛔</sup>
@@ -127,6 +135,25 @@ pub trait SendMessage<V: Versioned>: SendFrame<V> + SendMessageInternal<V> {
         self.broadcast_frame(&frame, scope)
     }
 
+    /// Sends MAVLink message to a component with specified `target` `ID`.
+    ///
+    /// Fills `target_system` and `target_component` fields of the message (see [`TargetFields`]).
+    /// Within a [`Network`], the frame is sent only to connections, where the target was seen,
+    /// regardless of [`RoutingMode`]. If target component was never seen, then frame is sent to
+    /// connections of its system. Frames addressed to unknown systems are dropped.
+    ///
+    /// Returns [`NodeError::NotAddressed`] for messages without target fields.
+    ///
+    /// [`TargetFields`]: crate::protocol::TargetFields
+    /// [`RoutingMode`]: crate::core::network::RoutingMode
+    fn send_to(&self, message: &impl Message, target: MavLinkId) -> Result<()> {
+        let message =
+            AddressedMessage::new(message, target).ok_or(NodeError::NotAddressed(message.id()))?;
+        let mut frame = self.next_frame(&message)?;
+        self.processor_internal().process_outgoing(&mut frame)?;
+        unsafe { self.route_targeted_internal(frame) }
+    }
+
     /// Creates a next frame from MAVLink message.
     ///
     /// If [`FrameSigner`] is set and the node has `MAVLink 2` protocol version, then frame will
@@ -134,7 +161,7 @@ pub trait SendMessage<V: Versioned>: SendFrame<V> + SendMessageInternal<V> {
     /// compatibility and incompatibility flags.
     fn next_frame(&self, message: &impl Message) -> Result<Frame<V>> {
         let mut frame = self.endpoint().next_frame(message)?;
-        self.processor_internal().process_new(&mut frame);
+        self.processor_internal().process_new(&mut frame)?;
         Ok(frame)
     }
 }
@@ -174,6 +201,22 @@ pub trait SendVersionlessMessage:
         self.broadcast_frame(&frame, scope)
     }
 
+    /// Sends MAVLink message with a specified MAVLink protocol version to a component with
+    /// specified `target` `ID`.
+    ///
+    /// See [`SendMessage::send_to`] for details.
+    fn send_versioned_to<V: Versioned>(
+        &self,
+        message: &impl Message,
+        target: MavLinkId,
+    ) -> Result<()> {
+        let message =
+            AddressedMessage::new(message, target).ok_or(NodeError::NotAddressed(message.id()))?;
+        let mut frame = self.next_frame_versioned::<V>(&message)?;
+        self.processor_internal().process_outgoing(&mut frame)?;
+        unsafe { self.route_targeted_internal(frame) }
+    }
+
     /// Create a next frame from MAVLink message with a specified protocol version.
     ///
     /// After creation, the frame will be converted into a [`Versionless`] form.
@@ -182,7 +225,7 @@ pub trait SendVersionlessMessage:
         message: &impl Message,
     ) -> Result<Frame<Versionless>> {
         let mut frame = self.endpoint().next_frame::<V>(message)?;
-        self.processor_internal().process_new(&mut frame);
+        self.processor_internal().process_new(&mut frame)?;
         Ok(frame)
     }
 }
@@ -0,0 +1,79 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::core::io::FlushProgress;
+
+use crate::prelude::*;
+
+/// Outcome of a graceful node shutdown.
+///
+/// Reports how many outgoing frames, that were queued when shutdown started, have been flushed
+/// to transports before the deadline, and how many were dropped. Shutdown messages of edge nodes
+/// are accounted as queued frames.
+///
+/// A frame is considered flushed, once it or any subsequent frame was written by at least one
+/// transport channel. Frames, that can't reach any channel, for example, when a server has no
+/// clients, are reported as dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    flushed: usize,
+    dropped: usize,
+    timed_out: bool,
+}
+
+impl ShutdownReport {
+    /// <sup>⛔</sup>
+    /// Creates a report from flush progress at the `start` and at the `end` of the shutdown.
+    pub(crate) fn new(start: &FlushProgress, end: &FlushProgress, timed_out: bool) -> Self {
+        Self {
+            flushed: end.flushed_since(start),
+            dropped: end.pending(),
+            timed_out,
+        }
+    }
+
+    /// Number of outgoing frames flushed to transports during shutdown.
+    pub fn flushed(&self) -> usize {
+        self.flushed
+    }
+
+    /// Number of outgoing frames dropped, since they were not flushed before the deadline.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns `true`, if deadline was reached before all frames were flushed and all channels
+    /// were stopped.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+}
+
+/// <sup>⛔</sup>
+/// Sequence of messages, that edge node emits on shutdown.
+#[derive(Clone, Default)]
+pub(crate) struct ShutdownMessages {
+    messages: Vec<Arc<dyn Message + Send + Sync>>,
+}
+
+impl ShutdownMessages {
+    /// Appends message to the end of the sequence.
+    pub(crate) fn push(&mut self, message: impl Message + Send + Sync + 'static) {
+        self.messages.push(Arc::new(message));
+    }
+
+    /// Iterates over messages in the order they should be sent.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &dyn Message> {
+        self.messages
+            .iter()
+            .map(|message| message.as_ref() as &dyn Message)
+    }
+}
+
+impl Debug for ShutdownMessages {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.messages.iter().map(|message| message.id()))
+            .finish()
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::core::consts::STATS_REPORT_TOP_TALKERS;
+use crate::core::io::ConnectionId;
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+#[cfg(doc)]
+use crate::core::node::Node;
+
+/// Aggregated summary of node traffic for a period of time.
+///
+/// Reports are delivered as node events once periodic reporting is enabled by `stats_report`
+/// method of a [`Node`]. Each report covers only the traffic since the previous one.
+#[derive(Clone, Debug, Default)]
+pub struct StatsReport {
+    /// Period of time covered by this report.
+    pub period: Duration,
+    /// The most frequent incoming messages as pairs of message `ID` and frame count sorted in
+    /// descending order.
+    pub top_talkers: Vec<(MessageId, u64)>,
+    /// Traffic per connection.
+    ///
+    /// For [`Network`] nodes, traffic is accounted per each network connection.
+    pub connections: HashMap<ConnectionId, ConnectionTraffic>,
+    /// Error counts.
+    pub errors: ErrorCounts,
+}
+
+/// Traffic of a particular connection within [`StatsReport`].
+///
+/// Outgoing frames are accounted once per channel they were written to.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionTraffic {
+    /// Number of incoming frames.
+    pub frames_in: u64,
+    /// Size of incoming frames in bytes.
+    pub bytes_in: u64,
+    /// Number of outgoing frames.
+    pub frames_out: u64,
+    /// Size of outgoing frames in bytes.
+    pub bytes_out: u64,
+}
+
+/// Error counts within [`StatsReport`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorCounts {
+    /// Incoming frames rejected by the frame processor (i.e. reported as invalid frames).
+    pub invalid: u64,
+    /// Incoming frames dropped by a [`RateGovernor`](crate::protocol::RateGovernor).
+    pub rate_limited: u64,
+    /// Detected peer anomalies.
+    pub anomalies: u64,
+}
+
+/// <sup>⛔</sup>
+/// Collects node traffic statistics for [`StatsReport`]s.
+///
+/// This is a shared handle: clones refer to the same data. Statistics are collected only after
+/// being [enabled](Self::enable).
+#[derive(Clone, Default)]
+pub(crate) struct TrafficStats {
+    enabled: Arc<AtomicBool>,
+    inner: Arc<Mutex<TrafficStatsInner>>,
+}
+
+#[derive(Default)]
+struct TrafficStatsInner {
+    messages: HashMap<MessageId, u64>,
+    connections: HashMap<ConnectionId, ConnectionTraffic>,
+    errors: ErrorCounts,
+}
+
+impl TrafficStats {
+    /// Starts collecting statistics.
+    pub(crate) fn enable(&self) {
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Records an incoming frame received from a specified connection.
+    pub(crate) fn record_incoming<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        connection_id: ConnectionId,
+    ) {
+        self.update(|inner| {
+            *inner.messages.entry(frame.message_id()).or_default() += 1;
+
+            let traffic = inner.connections.entry(connection_id).or_default();
+            traffic.frames_in += 1;
+            traffic.bytes_in += frame_size(frame);
+        });
+    }
+
+    /// Records an outgoing frame written to a specified connection.
+    pub(crate) fn record_outgoing<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        connection_id: ConnectionId,
+    ) {
+        self.update(|inner| {
+            let traffic = inner.connections.entry(connection_id).or_default();
+            traffic.frames_out += 1;
+            traffic.bytes_out += frame_size(frame);
+        });
+    }
+
+    /// Records an invalid incoming frame.
+    pub(crate) fn record_invalid(&self) {
+        self.update(|inner| inner.errors.invalid += 1);
+    }
+
+    /// Records an incoming frame dropped due to exceeded rate.
+    pub(crate) fn record_rate_limited(&self) {
+        self.update(|inner| inner.errors.rate_limited += 1);
+    }
+
+    /// Records a detected peer anomaly.
+    pub(crate) fn record_anomaly(&self) {
+        self.update(|inner| inner.errors.anomalies += 1);
+    }
+
+    /// Creates a report covering the specified `period` and resets collected statistics.
+    pub(crate) fn take_report(&self, period: Duration) -> StatsReport {
+        let inner = match self.inner.lock() {
+            Ok(mut inner) => std::mem::take(&mut *inner),
+            Err(_) => TrafficStatsInner::default(),
+        };
+
+        let mut top_talkers: Vec<(MessageId, u64)> = inner.messages.into_iter().collect();
+        top_talkers
+            .sort_by(|(id_a, count_a), (id_b, count_b)| count_b.cmp(count_a).then(id_a.cmp(id_b)));
+        top_talkers.truncate(STATS_REPORT_TOP_TALKERS);
+
+        StatsReport {
+            period,
+            top_talkers,
+            connections: inner.connections,
+            errors: inner.errors,
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TrafficStatsInner)) {
+        if !self.enabled.load(Ordering::Acquire) {
+            return;
+        }
+
+        if let Ok(mut inner) = self.inner.lock() {
+            f(&mut inner);
+        }
+    }
+}
+
+impl Debug for TrafficStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrafficStats").finish_non_exhaustive()
+    }
+}
+
+fn frame_size<V: MaybeVersioned>(frame: &Frame<V>) -> u64 {
+    (frame.header().size() + frame.body_length()) as u64
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, MavLinkId};
+
+    #[test]
+    fn stats_are_collected_once_enabled() {
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+        let connection_id = ConnectionId::new();
+
+        let stats = TrafficStats::default();
+        stats.record_incoming(&frame, connection_id);
+        stats.record_invalid();
+        let report = stats.take_report(Duration::from_secs(1));
+        assert!(report.top_talkers.is_empty());
+        assert!(report.connections.is_empty());
+
+        stats.enable();
+        stats.record_incoming(&frame, connection_id);
+        stats.record_incoming(&frame, connection_id);
+        stats.record_outgoing(&frame, connection_id);
+        stats.record_invalid();
+        stats.record_rate_limited();
+
+        let report = stats.take_report(Duration::from_secs(1));
+        assert_eq!(report.top_talkers, vec![(Heartbeat::message_id(), 2)]);
+
+        let traffic = report.connections.get(&connection_id).unwrap();
+        assert_eq!(traffic.frames_in, 2);
+        assert_eq!(traffic.frames_out, 1);
+        assert_eq!(traffic.bytes_in, 2 * traffic.bytes_out);
+        assert_eq!(traffic.bytes_out, frame_size(&frame));
+
+        assert_eq!(report.errors.invalid, 1);
+        assert_eq!(report.errors.rate_limited, 1);
+        assert_eq!(report.errors.anomalies, 0);
+
+        let report = stats.take_report(Duration::from_secs(1));
+        assert!(report.top_talkers.is_empty());
+        assert_eq!(report.errors, ErrorCounts::default());
+    }
+
+    #[test]
+    fn top_talkers_are_limited() {
+        let stats = TrafficStats::default();
+        let extra = 5;
+
+        if let Ok(mut inner) = stats.inner.lock() {
+            for message_id in 0..(STATS_REPORT_TOP_TALKERS as MessageId + extra) {
+                inner.messages.insert(message_id, message_id as u64);
+            }
+        }
+
+        let report = stats.take_report(Duration::from_secs(1));
+        assert_eq!(report.top_talkers.len(), STATS_REPORT_TOP_TALKERS);
+        assert_eq!(
+            report.top_talkers.first().unwrap().0,
+            STATS_REPORT_TOP_TALKERS as MessageId + extra - 1
+        );
+        assert_eq!(report.top_talkers.last().unwrap().0, extra);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::core::io::{ChannelInfo, ConnectionEvent};
+use crate::protocol::Peer;
+
+use crate::prelude::*;
+
+#[cfg(doc)]
+use crate::core::node::Node;
+
+/// State of a node connection within [`ConnectionStatus`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Connection is active.
+    #[default]
+    Active,
+    /// Connection was lost due to failure of the underlying transport. It may be restored
+    /// according to the retry strategy of a node.
+    Lost,
+    /// Connection is closed and won't be restored.
+    Closed,
+}
+
+/// Current status of a node connection.
+///
+/// Watched by `watch_connection` method of a [`Node`]. Status changes when connection is lost,
+/// restored, or closed, and when channels are opened or closed within the connection.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionStatus {
+    state: ConnectionState,
+    channels: Vec<ChannelInfo>,
+}
+
+impl ConnectionStatus {
+    /// Connection state.
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Returns `true`, if connection is active.
+    pub fn is_active(&self) -> bool {
+        self.state == ConnectionState::Active
+    }
+
+    /// Channels, that are currently open within the connection, in the order they were opened.
+    pub fn channels(&self) -> &[ChannelInfo] {
+        self.channels.as_slice()
+    }
+
+    /// Updates status according to a connection `event`.
+    ///
+    /// Returns `true`, if status has changed.
+    pub(crate) fn apply(&mut self, event: &ConnectionEvent) -> bool {
+        match event {
+            ConnectionEvent::ChannelOpened(channel) => {
+                self.channels.push(channel.clone());
+                true
+            }
+            ConnectionEvent::ChannelClosed(channel) => {
+                let len = self.channels.len();
+                self.channels.retain(|opened| opened.id() != channel.id());
+                self.channels.len() != len
+            }
+            ConnectionEvent::Lost => self.set_state(ConnectionState::Lost),
+            ConnectionEvent::Restored => self.set_state(ConnectionState::Active),
+            ConnectionEvent::Malformed(..)
+            | ConnectionEvent::ConnectionAdded(_)
+            | ConnectionEvent::ConnectionRemoved(_)
+            | ConnectionEvent::SenderRejected(..)
+            | ConnectionEvent::LinkActivated(_) => false,
+        }
+    }
+
+    /// Marks connection as closed.
+    ///
+    /// Returns `true`, if status has changed.
+    pub(crate) fn close(&mut self) -> bool {
+        let had_channels = !self.channels.is_empty();
+        self.channels.clear();
+        self.set_state(ConnectionState::Closed) || had_channels
+    }
+
+    fn set_state(&mut self, state: ConnectionState) -> bool {
+        // Closed connection is never reopened
+        if self.state == state || self.state == ConnectionState::Closed {
+            return false;
+        }
+        self.state = state;
+        true
+    }
+}
+
+/// Collects current peers ordered by their `ID`s.
+pub(crate) fn peer_list(peers: &HashMap<MavLinkId, Peer>) -> Vec<Peer> {
+    let mut peers: Vec<Peer> = peers.values().cloned().collect();
+    peers.sort_by_key(|peer| (peer.id.system, peer.id.component));
+    peers
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod status_tests {
+    use super::*;
+    use crate::core::io::{ChannelDetails, ConnectionId};
+
+    fn channel(connection_id: ConnectionId) -> ChannelInfo {
+        ChannelInfo::new(connection_id, ChannelDetails::Unknown)
+    }
+
+    #[test]
+    fn connection_status_tracks_events() {
+        let mut status = ConnectionStatus::default();
+        let connection_id = ConnectionId::new();
+        let first = channel(connection_id);
+        let second = channel(connection_id);
+
+        assert!(status.apply(&ConnectionEvent::ChannelOpened(first.clone())));
+        assert!(status.apply(&ConnectionEvent::ChannelOpened(second.clone())));
+        assert!(status.apply(&ConnectionEvent::ChannelClosed(first.clone())));
+        assert!(!status.apply(&ConnectionEvent::ChannelClosed(first)));
+        assert_eq!(status.channels().len(), 1);
+        assert_eq!(status.channels()[0].id(), second.id());
+
+        assert!(status.apply(&ConnectionEvent::Lost));
+        assert!(!status.apply(&ConnectionEvent::Lost));
+        assert_eq!(status.state(), ConnectionState::Lost);
+        assert!(status.apply(&ConnectionEvent::Restored));
+        assert!(status.is_active());
+
+        assert!(status.close());
+        assert!(status.channels().is_empty());
+        assert!(!status.apply(&ConnectionEvent::Restored));
+        assert_eq!(status.state(), ConnectionState::Closed);
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use crate::core::io::{OutboundQueue, RetryStrategy};
+use crate::core::marker::HasConnConf;
+use crate::error::ConfigDiagnostic;
+use crate::protocol::{
+    CompatStrategy, MavLinkVersion, ProcessingOrder, SignStrategy, StageOrder, SystemId,
+};
+
+use crate::prelude::*;
+
+/// Diagnoses settings relevant for all kinds of nodes.
+pub(super) fn common<V: MaybeVersioned>(
+    signer: Option<&FrameSigner>,
+    compat: Option<&CompatProcessor>,
+    order: ProcessingOrder,
+    conn_conf: &impl HasConnConf,
+    retry: RetryStrategy,
+    outbound_queue: Option<&OutboundQueue>,
+) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let v1_only = V::matches(MavLinkVersion::V1) && !V::matches(MavLinkVersion::V2);
+    if signer.is_some() && v1_only {
+        diagnostics.push(ConfigDiagnostic::SignerOnV1);
+    }
+
+    if let (Some(signer), Some(compat)) = (signer, compat) {
+        diagnostics.extend(processing_order(signer, compat, order));
+    }
+
+    if !matches!(retry, RetryStrategy::Never) && !conn_conf.is_repairable() {
+        diagnostics.push(ConfigDiagnostic::UnrepairableConnection(
+            conn_conf.info().details().clone(),
+        ));
+    }
+
+    if outbound_queue.is_some() && matches!(retry, RetryStrategy::Never) {
+        diagnostics.push(ConfigDiagnostic::OutboundQueueWithoutRetry);
+    }
+
+    diagnostics.extend(conn_conf.diagnostics());
+    diagnostics
+}
+
+/// Diagnoses order of compatibility processing and signing.
+///
+/// Signature covers frame flags, so they can't be changed between signing and sending, or between
+/// receiving and signature validation.
+fn processing_order(
+    signer: &FrameSigner,
+    compat: &CompatProcessor,
+    order: ProcessingOrder,
+) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if order.outgoing() == StageOrder::SignerFirst
+        && signs(signer.outgoing())
+        && alters_flags(compat, compat.outgoing())
+    {
+        diagnostics.push(ConfigDiagnostic::CompatAfterSigning);
+    }
+
+    let validates_incoming =
+        signs(signer.incoming()) || signer.peers().any(|(_, strategy)| signs(strategy));
+    if order.incoming() == StageOrder::CompatFirst
+        && validates_incoming
+        && alters_flags(compat, compat.incoming())
+    {
+        diagnostics.push(ConfigDiagnostic::CompatBeforeSignatureCheck);
+    }
+
+    diagnostics
+}
+
+fn signs(strategy: SignStrategy) -> bool {
+    matches!(
+        strategy,
+        SignStrategy::Sign | SignStrategy::ReSign | SignStrategy::Strict
+    )
+}
+
+fn alters_flags(compat: &CompatProcessor, strategy: CompatStrategy) -> bool {
+    let incompat = compat.incompat_flags().is_some()
+        && matches!(
+            strategy,
+            CompatStrategy::Enforce | CompatStrategy::EnforceProxy
+        );
+    let compat = compat.compat_flags().is_some()
+        && matches!(
+            strategy,
+            CompatStrategy::Enforce | CompatStrategy::RejectSet
+        );
+    incompat || compat
+}
+
+/// Diagnoses settings required to activate an edge node.
+///
+/// Heartbeat interval is checked only if it was set explicitly.
+pub(super) fn edge(
+    system_id: SystemId,
+    heartbeat_interval: Option<Duration>,
+    heartbeat_timeout: Duration,
+) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if system_id == 0 {
+        diagnostics.push(ConfigDiagnostic::MissingSystemId);
+    }
+
+    if let Some(interval) = heartbeat_interval {
+        if interval >= heartbeat_timeout {
+            diagnostics.push(ConfigDiagnostic::HeartbeatIntervalExceedsTimeout {
+                interval,
+                timeout: heartbeat_timeout,
+            });
+        }
+    }
+
+    diagnostics
+}
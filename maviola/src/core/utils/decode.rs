@@ -0,0 +1,47 @@
+use crate::protocol::Payload;
+
+use crate::prelude::*;
+
+/// Decodes a frame into a message of type `M`.
+///
+/// Returns [`None`] if frame contains another message or has invalid checksum.
+pub(crate) fn decode_message<M, V>(frame: &Frame<V>) -> Option<M>
+where
+    M: Message + for<'a> TryFrom<&'a Payload>,
+    V: MaybeVersioned,
+{
+    let message = M::try_from(frame.payload()).ok()?;
+    if message.id() != frame.message_id() {
+        return None;
+    }
+    frame
+        .validate_checksum_with_crc_extra(message.crc_extra())
+        .ok()?;
+    Some(message)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod decode_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::{Heartbeat, ProtocolVersion};
+
+    #[test]
+    fn messages_are_decoded_by_type() {
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let frame = endpoint
+            .next_frame(&Heartbeat {
+                custom_mode: 42,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let heartbeat: Heartbeat = decode_message(&frame).unwrap();
+        assert_eq!(heartbeat.custom_mode, 42);
+        assert!(decode_message::<ProtocolVersion, _>(&frame).is_none());
+    }
+}
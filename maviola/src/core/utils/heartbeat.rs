@@ -1,16 +1,25 @@
+use crate::dialects::minimal as dialect;
 use crate::protocol::DialectVersion;
 
-pub(crate) fn make_heartbeat_message(
-    version: Option<DialectVersion>,
-) -> mavio::dialects::minimal::messages::Heartbeat {
-    use crate::dialects::minimal as dialect;
-
+/// Heartbeat emitted by nodes, unless other contents are specified.
+pub(crate) fn default_heartbeat_message() -> dialect::messages::Heartbeat {
     dialect::messages::Heartbeat {
         type_: Default::default(),
         autopilot: dialect::enums::MavAutopilot::Generic,
         base_mode: Default::default(),
         custom_mode: 0,
         system_status: dialect::enums::MavState::Active,
+        mavlink_version: 0,
+    }
+}
+
+/// Creates heartbeat from a `template` with `mavlink_version` set to the dialect `version`.
+pub(crate) fn make_heartbeat_message(
+    template: &dialect::messages::Heartbeat,
+    version: Option<DialectVersion>,
+) -> dialect::messages::Heartbeat {
+    dialect::messages::Heartbeat {
         mavlink_version: version.unwrap_or_default(),
+        ..template.clone()
     }
 }
@@ -1,6 +1,9 @@
 //! Common utils.
 
 pub mod closable;
+mod decode;
+#[cfg(feature = "serde")]
+pub(crate) mod duration_secs;
 mod flipper;
 mod heartbeat;
 pub(crate) mod net;
@@ -10,17 +13,23 @@ pub(crate) mod sealed;
 #[cfg(test)]
 #[allow(dead_code)]
 pub(crate) mod test;
+mod threads;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) mod trace;
 mod unique_id;
 
 #[doc(inline)]
 pub use closable::{Closable, Closer, SharedCloser};
 #[doc(inline)]
 pub use flipper::{Flag, Flipper, Guarded, Switch};
+#[doc(inline)]
+pub use threads::{ThreadPriority, ThreadSettings};
 
 #[cfg(feature = "unsafe")]
 pub use mavio::utils::TryUpdateFrom;
 
-pub(crate) use heartbeat::make_heartbeat_message;
+pub(crate) use decode::decode_message;
+pub(crate) use heartbeat::{default_heartbeat_message, make_heartbeat_message};
 pub(crate) use sealed::Sealed;
 pub(crate) use unique_id::UniqueId;
 
@@ -29,3 +29,32 @@ pub(crate) fn pick_unused_port() -> Result<portpicker::Port> {
         std::io::Error::new(std::io::ErrorKind::NotFound, "can't find an unused port").into(),
     )
 }
+
+/// Strips one of the supported `schemes` from a server URL.
+///
+/// Returns [`None`], if URL has an unsupported scheme. URLs without scheme are returned as is.
+#[cfg(any(feature = "mqtt", feature = "nats", feature = "redis"))]
+pub(crate) fn strip_url_scheme<'a>(url: &'a str, schemes: &[&str]) -> Option<&'a str> {
+    match schemes.iter().find_map(|scheme| url.strip_prefix(scheme)) {
+        Some(addr) => Some(addr.trim_end_matches('/')),
+        None if url.contains("://") => None,
+        None => Some(url.trim_end_matches('/')),
+    }
+}
+
+/// Resolves socket address, that may omit port.
+///
+/// Missing port is substituted by `default_port`.
+#[cfg(any(feature = "mqtt", feature = "nats", feature = "redis"))]
+pub(crate) fn resolve_with_default_port(addr: &str, default_port: u16) -> Result<SocketAddr> {
+    let has_port = match addr.rsplit_once(':') {
+        Some((host, port)) => {
+            port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']'))
+        }
+        None => false,
+    };
+    match has_port {
+        true => resolve_socket_addr(addr),
+        false => resolve_socket_addr(format!("{addr}:{default_port}")),
+    }
+}
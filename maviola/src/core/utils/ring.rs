@@ -236,12 +236,12 @@ mod tests {
     fn extend_ring_capacity() {
         let mut ring = RingBuffer::new(2);
 
-        assert!(matches!(ring.push(1), None));
-        assert!(matches!(ring.push(2), None));
+        assert!(ring.push(1).is_none());
+        assert!(ring.push(2).is_none());
 
         _ = ring.resize(3);
 
-        assert!(matches!(ring.push(3), None));
+        assert!(ring.push(3).is_none());
         assert!(matches!(ring.push(4), Some(1)));
     }
 }
@@ -21,5 +21,5 @@ pub fn init_logger() {
 }
 
 pub fn initialize() {
-    INIT.call_once(|| init_logger());
+    INIT.call_once(init_logger);
 }
@@ -0,0 +1,291 @@
+//! OS-level settings for threads spawned by synchronous nodes.
+
+#[cfg(doc)]
+use crate::core::node::NodeBuilder;
+
+/// Priority of an OS thread.
+///
+/// Relative priorities ([`ThreadPriority::Lowest`] ... [`ThreadPriority::Highest`]) are mapped to
+/// niceness on Linux, to priorities within the default scheduling policy on other Unix systems,
+/// and to the corresponding thread priority classes on Windows.
+///
+/// [`ThreadPriority::Realtime`] requests real-time scheduling (`SCHED_FIFO` on Unix,
+/// `THREAD_PRIORITY_TIME_CRITICAL` on Windows). The value is clamped to the range supported by
+/// the OS. Raising priority above normal usually requires elevated privileges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThreadPriority {
+    /// The lowest priority.
+    Lowest,
+    /// Priority below normal.
+    Low,
+    /// Default priority.
+    #[default]
+    Normal,
+    /// Priority above normal.
+    High,
+    /// The highest non-real-time priority.
+    Highest,
+    /// Real-time priority.
+    Realtime(u8),
+}
+
+/// Settings applied to OS threads spawned by a node.
+///
+/// Thread settings are applied by synchronous nodes to their I/O and handler threads, use
+/// [`NodeBuilder::io_threads`] and [`NodeBuilder::handler_threads`] respectively. This way,
+/// control-loop companions can ensure, that a thread which writes MAVLink frames preempts bulk
+/// processing threads.
+///
+/// Settings are applied on a best-effort basis: if OS rejects them (for example, due to lack of
+/// privileges), a warning is logged, and the thread runs with default settings.
+///
+/// Settings take effect only when `thread_control` feature is enabled, otherwise they are
+/// ignored.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::core::utils::{ThreadPriority, ThreadSettings};
+///
+/// let settings = ThreadSettings::new()
+///     .with_priority(ThreadPriority::High)
+///     .with_core_affinity([2, 3]);
+///
+/// assert_eq!(settings.priority(), Some(ThreadPriority::High));
+/// assert_eq!(settings.core_affinity(), Some([2, 3].as_slice()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThreadSettings {
+    priority: Option<ThreadPriority>,
+    core_affinity: Option<Vec<usize>>,
+}
+
+impl ThreadSettings {
+    /// Creates settings that keep OS defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets thread priority.
+    pub fn with_priority(self, priority: ThreadPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    /// Pins thread to a set of CPU cores defined by their indices.
+    ///
+    /// Core affinity is supported on Linux and Windows only. On Windows, only the first 64 cores
+    /// (32 cores for 32-bit systems) can be used.
+    pub fn with_core_affinity(self, cores: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            core_affinity: Some(cores.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Thread priority.
+    ///
+    /// Returns [`None`] if priority is not set.
+    pub fn priority(&self) -> Option<ThreadPriority> {
+        self.priority
+    }
+
+    /// CPU cores to which thread is pinned.
+    ///
+    /// Returns [`None`] if core affinity is not set.
+    pub fn core_affinity(&self) -> Option<&[usize]> {
+        self.core_affinity.as_deref()
+    }
+
+    /// <sup>⛔</sup>
+    /// Applies settings to the current thread.
+    ///
+    /// Does nothing, if `thread_control` feature is disabled.
+    #[cfg(feature = "sync")]
+    pub(crate) fn apply(&self) -> std::io::Result<()> {
+        #[cfg(feature = "thread_control")]
+        {
+            if let Some(priority) = self.priority {
+                os::set_priority(priority)?;
+            }
+            if let Some(cores) = &self.core_affinity {
+                os::set_core_affinity(cores)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "thread_control", unix))]
+mod os {
+    use std::io::{Error, ErrorKind};
+
+    use super::ThreadPriority;
+
+    pub(super) fn set_priority(priority: ThreadPriority) -> std::io::Result<()> {
+        if let ThreadPriority::Realtime(value) = priority {
+            return set_sched_param(libc::SCHED_FIFO, value as f64 / u8::MAX as f64);
+        }
+        set_relative_priority(priority)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn set_relative_priority(priority: ThreadPriority) -> std::io::Result<()> {
+        let nice = match priority {
+            ThreadPriority::Lowest => 19,
+            ThreadPriority::Low => 10,
+            ThreadPriority::High => -10,
+            ThreadPriority::Highest => -20,
+            _ => 0,
+        };
+
+        // On Linux, niceness is a per-thread attribute
+        // SAFETY: `gettid` and `setpriority` do not access memory
+        let result = unsafe {
+            let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+            libc::setpriority(libc::PRIO_PROCESS, tid, nice)
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn set_relative_priority(priority: ThreadPriority) -> std::io::Result<()> {
+        let level = match priority {
+            ThreadPriority::Lowest => 0.0,
+            ThreadPriority::Low => 0.25,
+            ThreadPriority::High => 0.75,
+            ThreadPriority::Highest => 1.0,
+            _ => 0.5,
+        };
+        set_sched_param(libc::SCHED_OTHER, level)
+    }
+
+    /// Sets scheduling `policy` with priority at `level` (from `0.0` to `1.0`) of the range
+    /// supported by the policy.
+    fn set_sched_param(policy: libc::c_int, level: f64) -> std::io::Result<()> {
+        // SAFETY: `param` is a valid pointer during the call
+        let result = unsafe {
+            let min = libc::sched_get_priority_min(policy);
+            let max = libc::sched_get_priority_max(policy);
+            if min < 0 || max < 0 {
+                return Err(Error::last_os_error());
+            }
+
+            let mut param: libc::sched_param = std::mem::zeroed();
+            param.sched_priority = min + ((max - min) as f64 * level).round() as libc::c_int;
+            libc::pthread_setschedparam(libc::pthread_self(), policy, &param)
+        };
+        match result {
+            0 => Ok(()),
+            code => Err(Error::from_raw_os_error(code)),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(super) fn set_core_affinity(cores: &[usize]) -> std::io::Result<()> {
+        // SAFETY: `set` is a valid pointer during the calls
+        let result = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_ZERO(&mut set);
+            for &core in cores {
+                if core >= libc::CPU_SETSIZE as usize {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("invalid CPU core index: {core}"),
+                    ));
+                }
+                libc::CPU_SET(core, &mut set);
+            }
+            // Zero PID stands for the current thread
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(super) fn set_core_affinity(_: &[usize]) -> std::io::Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "core affinity is not supported on this platform",
+        ))
+    }
+}
+
+#[cfg(all(feature = "thread_control", windows))]
+mod os {
+    use std::io::{Error, ErrorKind};
+
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentThread, SetThreadAffinityMask, SetThreadPriority, THREAD_PRIORITY_ABOVE_NORMAL,
+        THREAD_PRIORITY_BELOW_NORMAL, THREAD_PRIORITY_HIGHEST, THREAD_PRIORITY_LOWEST,
+        THREAD_PRIORITY_NORMAL, THREAD_PRIORITY_TIME_CRITICAL,
+    };
+
+    use super::ThreadPriority;
+
+    pub(super) fn set_priority(priority: ThreadPriority) -> std::io::Result<()> {
+        let priority = match priority {
+            ThreadPriority::Lowest => THREAD_PRIORITY_LOWEST,
+            ThreadPriority::Low => THREAD_PRIORITY_BELOW_NORMAL,
+            ThreadPriority::Normal => THREAD_PRIORITY_NORMAL,
+            ThreadPriority::High => THREAD_PRIORITY_ABOVE_NORMAL,
+            ThreadPriority::Highest => THREAD_PRIORITY_HIGHEST,
+            ThreadPriority::Realtime(_) => THREAD_PRIORITY_TIME_CRITICAL,
+        };
+
+        // SAFETY: current thread pseudo-handle is always valid
+        match unsafe { SetThreadPriority(GetCurrentThread(), priority) } {
+            0 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn set_core_affinity(cores: &[usize]) -> std::io::Result<()> {
+        let mut mask: usize = 0;
+        for &core in cores {
+            if core >= usize::BITS as usize {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("invalid CPU core index: {core}"),
+                ));
+            }
+            mask |= 1 << core;
+        }
+
+        // SAFETY: current thread pseudo-handle is always valid
+        match unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } {
+            0 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(feature = "thread_control", not(any(unix, windows))))]
+mod os {
+    use std::io::{Error, ErrorKind};
+
+    use super::ThreadPriority;
+
+    pub(super) fn set_priority(_: ThreadPriority) -> std::io::Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "thread priority is not supported on this platform",
+        ))
+    }
+
+    pub(super) fn set_core_affinity(_: &[usize]) -> std::io::Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "core affinity is not supported on this platform",
+        ))
+    }
+}
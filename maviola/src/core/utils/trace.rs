@@ -0,0 +1,144 @@
+//! <sup>⛔</sup>
+//! Structured tracing spans.
+//!
+//! When `tracing` feature is enabled, spans are provided by [`tracing`](https://docs.rs/tracing).
+//! Otherwise, a no-op [`Span`] with the same interface is used, so callers do not have to be
+//! feature-gated.
+
+#[cfg(feature = "async")]
+use std::future::Future;
+
+use crate::core::io::{ChannelInfo, ConnectionInfo, OutgoingFrame};
+
+use crate::prelude::*;
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::Span;
+
+/// <sup>⛔</sup>
+/// No-op span used when `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Span;
+
+/// <sup>⛔</sup>
+/// Guard of the entered no-op [`Span`].
+#[cfg(all(feature = "sync", not(feature = "tracing")))]
+pub(crate) struct Entered;
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    /// Returns current span.
+    #[inline(always)]
+    pub(crate) fn current() -> Self {
+        Self
+    }
+
+    /// Enters this span until returned guard is dropped.
+    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub(crate) fn enter(&self) -> Entered {
+        Entered
+    }
+
+    /// Executes `f` within this span.
+    #[inline(always)]
+    pub(crate) fn in_scope<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        f()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of a connection described by `info`.
+#[cfg(feature = "tracing")]
+pub(crate) fn connection_span(info: &ConnectionInfo) -> Span {
+    tracing::info_span!("connection", id = ?info.id(), details = ?info.details())
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of a connection described by `info`.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn connection_span(_: &ConnectionInfo) -> Span {
+    Span
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of a channel described by `info`.
+///
+/// Channel span is a child of the current span, which is usually a span of the connection.
+#[cfg(feature = "tracing")]
+pub(crate) fn channel_span(info: &ChannelInfo) -> Span {
+    tracing::info_span!("channel", id = ?info.id(), details = ?info.details())
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of a channel described by `info`.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn channel_span(_: &ChannelInfo) -> Span {
+    Span
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of incoming `frame` processing, that has been received from a `channel`.
+#[cfg(feature = "tracing")]
+pub(crate) fn incoming_frame_span<V: MaybeVersioned>(
+    frame: &Frame<V>,
+    channel: &ChannelInfo,
+) -> Span {
+    tracing::debug_span!(
+        "incoming_frame",
+        system_id = frame.system_id(),
+        component_id = frame.component_id(),
+        message_id = frame.message_id(),
+        sequence = frame.sequence(),
+        channel = ?channel.id(),
+    )
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of incoming `frame` processing, that has been received from a `channel`.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn incoming_frame_span<V: MaybeVersioned>(_: &Frame<V>, _: &ChannelInfo) -> Span {
+    Span
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of outgoing `frame` routing.
+#[cfg(feature = "tracing")]
+pub(crate) fn outgoing_frame_span<V: MaybeVersioned>(frame: &OutgoingFrame<V>) -> Span {
+    let inner = frame.frame();
+    tracing::debug_span!(
+        "outgoing_frame",
+        system_id = inner.system_id(),
+        component_id = inner.component_id(),
+        message_id = inner.message_id(),
+        sequence = inner.sequence(),
+        scope = ?frame.scope(),
+    )
+}
+
+/// <sup>⛔</sup>
+/// Creates a span of outgoing `frame` routing.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn outgoing_frame_span<V: MaybeVersioned>(_: &OutgoingFrame<V>) -> Span {
+    Span
+}
+
+/// <sup>⛔</sup>
+/// Instruments `future` with a `span`, that is entered each time the future is polled.
+#[cfg(all(feature = "async", feature = "tracing"))]
+pub(crate) fn instrument<F: Future>(future: F, span: Span) -> impl Future<Output = F::Output> {
+    tracing::Instrument::instrument(future, span)
+}
+
+/// <sup>⛔</sup>
+/// Instruments `future` with a `span`, that is entered each time the future is polled.
+#[cfg(all(feature = "async", not(feature = "tracing")))]
+#[inline(always)]
+pub(crate) fn instrument<F: Future>(future: F, _: Span) -> impl Future<Output = F::Output> {
+    future
+}
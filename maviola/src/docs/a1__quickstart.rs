@@ -88,6 +88,8 @@ Other available transports are:
 * [`UdpServer`] / [`UdpClient`]
 * [`SockServer`] / [`SockClient`] (Unix-like systems only)
 * [`FileWriter`] / [`FileReader`]
+* [`TlogWriter`] (records frames of another connection to a `.tlog` file) / [`TlogReader`]
+  (replays `.tlog` files with the original timing)
 
 You can learn, how to create your own transports in
 [Custom Transport](crate::docs::c2__custom_transport) section of this documentation.
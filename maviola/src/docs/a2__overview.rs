@@ -94,20 +94,22 @@ reason you might refrain from using asynchronous API to reduce the amount of dep
 
 When comparing two current implementation, we must confess that asynchronous API is somewhat more
 elaborated. The main reason is that despite being slightly faster (10-15%) current synchronous API
-is way more eager. Currently, we use several threads per connection. That means that while you may
+is way more eager. By default, we use several threads per channel. That means that while you may
 squeeze some performance, the cost may be too high. By the rules of thumb, we suggest to use
 synchronous API when you either have relatively few connections (dozens) or you are allowed to use
 all cores of your machine. Especially in the latter case.
 
+For servers with many clients, synchronous nodes can share an
+[`IoPool`](crate::sync::io::IoPool) set by
+[`NodeBuilder::io_pool`](crate::core::node::NodeBuilder::io_pool). Channels of TCP and Unix socket
+connections are then multiplexed by a fixed number of worker threads. Our benchmarks show, that a
+TCP server with a hundred clients needs a dozen of threads instead of four hundreds and receives
+frames considerably faster on machines with few cores.
+
 Another consideration related to previous one is that there is simply no way to run synchronous API
 using one thread. At the same time benchmarks for single-threaded Tokio runtime show considerable
 performance.
 
-In the next versions we may introduce a more granular approach to threads by the means of thread
-pools. We are certainly looking into it. But since [Tokio](https://tokio.rs/) is already a thread
-pool with a fine-grained control over the resources we are not rushing forward. Such changes require
-extensive benchmarks and significant efforts.
-
 ## Prelude
 
 We suggest to use [`prelude`] with the corresponding [`sync::prelude`] / [`asnc::prelude`]
@@ -28,6 +28,13 @@ let node = Node::sync::<V2>()
 
 Check [`CompatProcessor`] documentation for details.
 
+When node has both a compatibility processor and a [signer](crate::docs::b2__signing), the former is
+applied first by default. Since MAVLink signature covers frame flags, you may want to validate
+signatures of incoming frames before their flags are changed. Use
+[`NodeBuilder::processing_order`](crate::core::node::NodeBuilder::processing_order) to set the
+[`ProcessingOrder`] explicitly. Orders, that will render signatures invalid, are rejected when node
+is built.
+
 <em>[← Message Signing](crate::docs::b2__signing) | [Networks & Routing →](crate::docs::b4__networks_and_routing)</em>
  */
 
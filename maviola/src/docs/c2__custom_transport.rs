@@ -7,6 +7,9 @@ Custom transports are still require `unstable` Cargo feature to be enabled. Refe
 [`asnc::io`] module documentation to learn how to build custom connections using
 [`sync::ConnectionBuilder`] or [`asnc::ConnectionBuilder`] respectively.
 
+Asynchronous connection builders and [`asnc::TcpHandshake`] are implemented with native `async fn`
+in traits, there is no need in `async_trait` or manual boxing of futures.
+
 There is an [issue](https://gitlab.com/mavka/libs/maviola/-/issues/2) dedicated to stabilization of
 this part of the API you can track.
 
@@ -16,4 +19,5 @@ this part of the API you can track.
 [`sync::ConnectionBuilder`]: crate::sync::io::ConnectionBuilder
 [`asnc::io`]: crate::asnc::io
 [`asnc::ConnectionBuilder`]: crate::asnc::io::ConnectionBuilder
+[`asnc::TcpHandshake`]: crate::asnc::io::TcpHandshake
  */
@@ -42,8 +42,8 @@ impl<V: MaybeVersioned> UpdateFrameUnsafe<V> for Scrambler {
         _: &mut Checksum,
         _: &mut Option<Signature>,
     ) {
-        for i in 0..payload.len() {
-            payload[i] = payload[i] ^ 0xff;
+        for byte in payload.iter_mut() {
+            *byte ^= 0xff;
         }
     }
 }
@@ -104,9 +104,23 @@ let node = Node::sync::<V2>()
 Our node will scramble outgoing frames and unscramble incoming frames. Only nodes with the same
 processor will be able to communicate with our node. Definitely not secure. But you've got the idea.
 
+## Middleware
+
+If you don't need low-level access to frame internals, then [`FrameMiddleware`] is a safe
+alternative available without any feature flags. Middleware is added with
+[`NodeBuilder::add_middleware`] at a [`MiddlewarePosition`] relative to built-in processing
+stages. For example, a stage placed [`Before`](MiddlewarePosition::Before) the
+[`Signer`](BuiltinStage::Signer) will see outgoing frames before they are signed, while a stage
+placed [`After`](MiddlewarePosition::After) it will see them already signed.
+
+Positions [`First`](MiddlewarePosition::First) and [`Last`](MiddlewarePosition::Last) wrap the
+whole pipeline including custom processors.
+
 <em>[← Custom Transport](crate::docs::c2__custom_transport) | [Ad-hoc Dialects →](crate::docs::c4__ad_hoc_dialects)</em>
  */
 
+#[cfg(doc)]
+use crate::core::node::NodeBuilder;
 #[cfg(doc)]
 use crate::prelude::*;
 #[cfg(doc)]
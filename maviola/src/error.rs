@@ -9,10 +9,12 @@
 //! wrap them as the corresponding variants of [`Error`]. All such low-level MAVLink abstractions
 //! are available in [`crate::core`].
 
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::sync::{mpsc, Arc, PoisonError};
+use std::time::Duration;
 
-use crate::protocol::MessageId;
+use crate::core::io::{ConnectionDetails, ConnectionId};
+use crate::protocol::{ComponentId, MessageId};
 
 /// <sup>[`mavio`](https://crates.io/crates/mavio)</sup>
 #[doc(inline)]
@@ -85,6 +87,84 @@ pub enum Error {
     #[error("multi-threading error: {0:?}")]
     Sync(#[from] SyncError),
 
+    /// Invalid configuration.
+    #[error("invalid configuration: {0}")]
+    Config(#[from] ConfigError),
+
+    /// Frame can't be converted between `MAVLink 1` and `MAVLink 2`.
+    #[error("version bridge error: {0}")]
+    Bridge(#[from] BridgeError),
+
+    /// Parameter protocol errors.
+    #[cfg(feature = "msrv-utils-params")]
+    #[error("parameter error: {0}")]
+    Param(#[from] ParamError),
+
+    /// Mission protocol errors.
+    #[cfg(feature = "msrv-utils-mission")]
+    #[error("mission error: {0}")]
+    Mission(#[from] MissionError),
+
+    /// File transfer protocol errors.
+    #[cfg(feature = "msrv-utils-ftp")]
+    #[error("FTP error: {0}")]
+    Ftp(#[from] FtpError),
+
+    /// Camera protocol errors.
+    #[cfg(feature = "msrv-utils-camera")]
+    #[error("camera error: {0}")]
+    Camera(#[from] CameraError),
+
+    /// Gimbal protocol errors.
+    #[cfg(feature = "msrv-utils-gimbal")]
+    #[error("gimbal error: {0}")]
+    Gimbal(#[from] GimbalError),
+
+    /// Ground control station client errors.
+    #[cfg(feature = "msrv-utils-gcs")]
+    #[error("GCS error: {0}")]
+    Gcs(#[from] GcsError),
+
+    /// Time synchronisation protocol errors.
+    #[cfg(feature = "msrv-utils-timesync")]
+    #[error("timesync error: {0}")]
+    Timesync(#[from] TimesyncError),
+
+    /// Message interval protocol errors.
+    #[cfg(feature = "msrv-utils-streams")]
+    #[error("stream error: {0}")]
+    Stream(#[from] StreamError),
+
+    /// Routing script errors.
+    #[cfg(feature = "scripting")]
+    #[error("script error: {0}")]
+    Script(#[from] ScriptError),
+
+    /// Message definitions errors.
+    #[cfg(feature = "definitions")]
+    #[error("message definitions error: {0}")]
+    Definitions(#[from] DefinitionsError),
+
+    /// ZeroMQ transport errors.
+    #[cfg(feature = "zmq")]
+    #[error("ZeroMQ error: {0}")]
+    Zmq(#[from] ZmqError),
+
+    /// MQTT transport errors.
+    #[cfg(feature = "mqtt")]
+    #[error("MQTT error: {0}")]
+    Mqtt(#[from] MqttError),
+
+    /// NATS transport errors.
+    #[cfg(feature = "nats")]
+    #[error("NATS error: {0}")]
+    Nats(#[from] NatsError),
+
+    /// Redis transport errors.
+    #[cfg(feature = "redis")]
+    #[error("Redis error: {0}")]
+    Redis(#[from] RedisError),
+
     /// Other errors.
     #[error("error: {0}")]
     Other(String),
@@ -132,6 +212,430 @@ pub enum NodeError {
     /// Attempt to use a frame with message ID that can't be recognised by a dialect.
     #[error("provided frame with ID = {0} can't be decoded in current dialect {1}")]
     NotInDialect(MessageId, &'static str),
+
+    /// Component `ID` is already used by the node or one of its components.
+    #[error("component ID {0} is already used by the node")]
+    ComponentInUse(ComponentId),
+
+    /// Message with specified `ID` has no target fields and can't be sent to a particular
+    /// component.
+    #[error("message with ID = {0} has no target fields")]
+    NotAddressed(MessageId),
+
+    /// Connection with specified `ID` is not a part of the network.
+    #[error("connection {0:?} is not a part of the network")]
+    UnknownConnection(ConnectionId),
+}
+
+/// Errors of conversion between `MAVLink 1` and `MAVLink 2` frames.
+///
+/// Returned by [`VersionBridge::convert`](crate::core::network::VersionBridge::convert) for frames,
+/// that can't be represented in the target protocol version.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BridgeError {
+    /// Message `ID` exceeds the range of `MAVLink 1`.
+    #[error("message ID {0} can't be represented in MAVLink 1")]
+    MessageId(MessageId),
+
+    /// Frame is signed, while `MAVLink 1` has no message signing.
+    #[error("signed frame with message ID {0} can't be represented in MAVLink 1")]
+    Signed(MessageId),
+
+    /// Message has non-empty extension fields, that are not supported by `MAVLink 1`.
+    #[error("message ID {0} has extension fields, that can't be represented in MAVLink 1")]
+    Extensions(MessageId),
+}
+
+/// Invalid configuration error.
+///
+/// Returned by `validate` methods of node builders, node configurations, and networks. Nodes are
+/// validated before they are built. The error contains all problems found in configuration, use
+/// [`ConfigError::diagnostics`] to inspect them.
+#[derive(Clone, Debug, thiserror::Error)]
+pub struct ConfigError {
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+/// Configuration problem, that prevents a node from working properly.
+///
+/// Each diagnostic explains, how the problem can be fixed.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ConfigDiagnostic {
+    /// Frame signer is set for a node, that supports only `MAVLink 1` frames.
+    #[error("frame signer is set for a MAVLink 1 node: MAVLink 1 frames can't be signed, remove the signer or switch node to MAVLink 2")]
+    SignerOnV1,
+
+    /// Heartbeat interval is not less than heartbeat timeout.
+    #[error("heartbeat interval {interval:?} is not less than heartbeat timeout {timeout:?}: peers will consider node inactive between heartbeats, decrease the interval or increase the timeout")]
+    HeartbeatIntervalExceedsTimeout {
+        /// Heartbeat interval.
+        interval: Duration,
+        /// Heartbeat timeout.
+        timeout: Duration,
+    },
+
+    /// Compatibility processor changes flags of outgoing frames after they were signed.
+    #[error("compatibility processor changes flags of outgoing frames after signer: signatures of these frames will be invalid, apply compatibility processor first or don't enforce flags for outgoing frames")]
+    CompatAfterSigning,
+
+    /// Compatibility processor changes flags of incoming frames before their signatures are validated.
+    #[error("compatibility processor changes flags of incoming frames before signer: valid signatures will be rejected, apply signer first or don't enforce flags for incoming frames")]
+    CompatBeforeSignatureCheck,
+
+    /// Edge node has system `ID` reserved for broadcast.
+    #[error("edge node has system ID 0 reserved for broadcast: its heartbeats will be ignored by peers once activated, set a non-zero system ID")]
+    MissingSystemId,
+
+    /// Several connections of a network share the same endpoint.
+    #[error("several connections of a network use the same endpoint {0:?}: remove duplicate connections")]
+    DuplicateEndpoint(ConnectionDetails),
+
+    /// Retry strategy is set for a node, which connection can't be restored.
+    #[error("retry strategy is set for a node with connection {0:?}, that can't be restored: use a repairable connection or remove the retry strategy")]
+    UnrepairableConnection(ConnectionDetails),
+
+    /// Outbound queue is set for a node, which connection is never restored.
+    #[error("outbound queue is set for a node without retry strategy: spooled frames will never be transmitted, set a retry strategy or remove the queue")]
+    OutboundQueueWithoutRetry,
+
+    /// Authentication handshake of a TCP server doesn't match API mode of the node.
+    #[error("TCP server {0:?} has authentication handshake for another API mode: clients can't be authenticated, use `sync_handshake` for synchronous and `asnc_handshake` for asynchronous nodes")]
+    HandshakeModeMismatch(ConnectionDetails),
+
+    /// TLS is enabled for a TCP connection together with an option, that can't be combined with it.
+    #[cfg(feature = "tls")]
+    #[error("TLS is enabled for connection {0:?} together with {1}, that can't be used over TLS: remove either TLS or {1}")]
+    TlsConflict(ConnectionDetails, &'static str),
+
+    /// Connection transport is not available in API mode of the node.
+    #[error("connection {0:?} is not available for asynchronous nodes: use synchronous API for this transport")]
+    UnsupportedTransport(ConnectionDetails),
+
+    /// Node configuration doesn't define a value, that is required to build a node.
+    #[error(
+        "node configuration doesn't define `{0}`: add it to the configuration or set it in code"
+    )]
+    MissingConfigValue(&'static str),
+
+    /// Environment variable with a signing key, referred by node configuration, is not set.
+    #[error("signing key environment variable {0:?} is not set: export the secret key or remove the signing section from the node configuration")]
+    MissingSigningKey(String),
+}
+
+/// Parameter protocol errors.
+///
+/// Returned by [`ParamServer`](crate::core::msrv::params::ParamServer) methods.
+#[cfg(feature = "msrv-utils-params")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ParamError {
+    /// Parameter name is empty or longer than 16 bytes.
+    #[error("invalid parameter name: {0:?}")]
+    InvalidName(String),
+
+    /// Parameter with the same name is already declared.
+    #[error("parameter {0:?} already exists")]
+    AlreadyExists(String),
+
+    /// Parameter is not declared.
+    #[error("parameter {0:?} not found")]
+    NotFound(String),
+
+    /// Value type differs from the declared type of parameter.
+    #[error("parameter {name:?} has value {expected:?}, can't set {actual:?} of a different type")]
+    TypeMismatch {
+        /// Parameter name.
+        name: String,
+        /// Current value.
+        expected: crate::core::msrv::params::ParamValue,
+        /// Rejected value.
+        actual: crate::core::msrv::params::ParamValue,
+    },
+}
+
+/// Mission protocol errors.
+///
+/// Returned when a mission transfer implemented by
+/// [`MissionTransfer`](crate::core::msrv::mission::MissionTransfer) fails.
+#[cfg(feature = "msrv-utils-mission")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum MissionError {
+    /// Peer didn't respond after all retries.
+    #[error("mission transfer timed out")]
+    Timeout,
+
+    /// Peer rejected or cancelled the transfer.
+    #[error("mission transfer rejected: {0:?}")]
+    Rejected(crate::dialects::common::enums::MavMissionResult),
+}
+
+/// File transfer protocol errors.
+///
+/// Returned when a file transfer implemented by
+/// [`FtpTransfer`](crate::core::msrv::ftp::FtpTransfer) fails.
+#[cfg(feature = "msrv-utils-ftp")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum FtpError {
+    /// Server didn't respond after all retries.
+    #[error("file transfer timed out")]
+    Timeout,
+
+    /// Server responded with `NAK`.
+    #[error("file transfer rejected: {code:?}")]
+    Rejected {
+        /// Error code.
+        code: crate::core::msrv::ftp::FtpNakCode,
+        /// Error number of a failed file system call, sent with
+        /// [`FtpNakCode::FailErrno`](crate::core::msrv::ftp::FtpNakCode::FailErrno).
+        errno: Option<u8>,
+    },
+
+    /// `CRC32` calculated by the server doesn't match transferred contents.
+    #[error("CRC32 mismatch: expected {expected:#010x}, got {actual:#010x}")]
+    CrcMismatch {
+        /// Checksum of transferred contents.
+        expected: u32,
+        /// Checksum reported by the server.
+        actual: u32,
+    },
+
+    /// Path doesn't fit into a single message.
+    #[error("path is too long: {0} bytes")]
+    PathTooLong(usize),
+
+    /// Server response to a request with this opcode is malformed.
+    #[error("invalid response to {0:?}")]
+    InvalidResponse(crate::core::msrv::ftp::FtpOpcode),
+}
+
+/// Camera protocol errors.
+///
+/// Returned when a camera operation implemented by
+/// [`CameraOperation`](crate::core::msrv::camera::CameraOperation) fails.
+#[cfg(feature = "msrv-utils-camera")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum CameraError {
+    /// Camera didn't respond after all retries.
+    #[error("camera operation timed out")]
+    Timeout,
+
+    /// Camera rejected the command.
+    #[error("camera command rejected: {0:?}")]
+    Rejected(crate::dialects::common::enums::MavResult),
+
+    /// Camera reported, that image capture has failed.
+    #[error("image capture failed: image index {0}")]
+    CaptureFailed(i32),
+}
+
+/// Gimbal protocol errors.
+///
+/// Returned when a gimbal operation implemented by
+/// [`GimbalOperation`](crate::core::msrv::gimbal::GimbalOperation) fails.
+#[cfg(feature = "msrv-utils-gimbal")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum GimbalError {
+    /// Gimbal manager or device didn't respond after all retries.
+    #[error("gimbal operation timed out")]
+    Timeout,
+
+    /// Gimbal manager rejected the command.
+    #[error("gimbal command rejected: {0:?}")]
+    Rejected(crate::dialects::common::enums::MavResult),
+}
+
+/// Ground control station client errors.
+///
+/// Returned when an operation implemented by
+/// [`GcsOperation`](crate::core::msrv::gcs::GcsOperation) fails.
+#[cfg(feature = "msrv-utils-gcs")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum GcsError {
+    /// Vehicle didn't respond after all retries.
+    #[error("GCS operation timed out")]
+    Timeout,
+
+    /// Vehicle rejected the command.
+    #[error("command rejected: {0:?}")]
+    Rejected(crate::dialects::common::enums::MavResult),
+
+    /// Vehicle reported a parameter of a type, that can't be represented by
+    /// [`ParamValue`](crate::core::msrv::params::ParamValue).
+    #[error("unsupported type of parameter {0:?}")]
+    UnsupportedParamType(String),
+}
+
+/// Time synchronisation protocol errors.
+///
+/// Returned when a latency measurement implemented by
+/// [`LatencyMeasurement`](crate::core::msrv::timesync::LatencyMeasurement) fails.
+#[cfg(feature = "msrv-utils-timesync")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum TimesyncError {
+    /// Peer didn't respond to any of `TIMESYNC` requests.
+    #[error("timesync measurement timed out")]
+    Timeout,
+}
+
+/// Message interval protocol errors.
+///
+/// Returned by [`StreamRateController`](crate::core::msrv::streams::StreamRateController) methods.
+#[cfg(feature = "msrv-utils-streams")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum StreamError {
+    /// Stream for a message with this `ID` is already registered.
+    #[error("stream for message {0} already registered")]
+    AlreadyRegistered(MessageId),
+
+    /// There is no stream for a message with this `ID`.
+    #[error("stream for message {0} is not registered")]
+    NotRegistered(MessageId),
+
+    /// Stream interval is zero, use [`None`] to disable the stream.
+    #[error("stream for message {0} can't have zero interval")]
+    ZeroInterval(MessageId),
+}
+
+/// MQTT transport errors.
+///
+/// Returned by [`MqttBridge`](crate::core::io::MqttBridge) connections.
+#[cfg(feature = "mqtt")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum MqttError {
+    /// Broker URL scheme is not supported, only `mqtt://` and `tcp://` brokers are available.
+    #[error("unsupported broker: {0}")]
+    UnsupportedBroker(String),
+
+    /// Broker refused connection with the specified return code.
+    #[error("connection refused by broker: code {0}")]
+    ConnectionRefused(u8),
+
+    /// Broker refused subscription to a topic filter.
+    #[error("subscription refused: {0}")]
+    SubscriptionRefused(String),
+
+    /// Received packet is malformed or unexpected.
+    #[error("malformed MQTT packet")]
+    MalformedPacket,
+
+    /// String or packet exceeds size limit of MQTT protocol.
+    #[error("{0} bytes exceed MQTT size limit")]
+    TooLarge(usize),
+}
+
+/// NATS transport errors.
+///
+/// Returned by [`NatsBridge`](crate::core::io::NatsBridge) connections.
+#[cfg(feature = "nats")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum NatsError {
+    /// Server URL scheme is not supported, only `nats://` and `tcp://` servers are available.
+    #[error("unsupported server: {0}")]
+    UnsupportedServer(String),
+
+    /// Subject is empty, contains wildcards or whitespaces.
+    #[error("invalid subject: {0}")]
+    InvalidSubject(String),
+
+    /// Server requires TLS, which is not supported.
+    #[error("server requires TLS")]
+    TlsRequired,
+
+    /// Server rejected connection or reported a protocol error.
+    #[error("rejected by server: {0}")]
+    Rejected(String),
+
+    /// Received message is malformed or unexpected.
+    #[error("malformed NATS message")]
+    MalformedMessage,
+}
+
+/// Redis transport errors.
+///
+/// Returned by [`RedisBridge`](crate::core::io::RedisBridge) connections.
+#[cfg(feature = "redis")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum RedisError {
+    /// Server URL is not supported, only `redis://` and `tcp://` servers without credentials and
+    /// database are available.
+    #[error("unsupported server: {0}")]
+    UnsupportedServer(String),
+
+    /// Server rejected a command.
+    #[error("rejected by server: {0}")]
+    Rejected(String),
+
+    /// Received reply is malformed or unexpected.
+    #[error("malformed Redis reply")]
+    MalformedReply,
+}
+
+/// ZeroMQ transport errors.
+///
+/// Returned by [`ZmqPub`](crate::core::io::ZmqPub) and [`ZmqSub`](crate::core::io::ZmqSub)
+/// connections.
+#[cfg(feature = "zmq")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ZmqError {
+    /// Endpoint transport is not supported, only `tcp://` endpoints are available.
+    #[error("unsupported endpoint: {0}")]
+    UnsupportedEndpoint(String),
+
+    /// Peer greeting is not a valid ZMTP 3.x greeting.
+    #[error("invalid ZMTP greeting")]
+    InvalidGreeting,
+
+    /// Peer requested security mechanism other than `NULL`.
+    #[error("unsupported security mechanism: {0}")]
+    UnsupportedMechanism(String),
+
+    /// Peer socket type can't be connected to this socket.
+    #[error("incompatible socket type: {0}")]
+    IncompatibleSocket(String),
+
+    /// Peer rejected the handshake.
+    #[error("handshake rejected: {0}")]
+    Rejected(String),
+
+    /// Peer sent a malformed ZMTP frame or command.
+    #[error("malformed ZMTP frame")]
+    MalformedFrame,
+}
+
+/// Routing script compilation error.
+///
+/// Returned when [`FrameScript`](crate::core::network::FrameScript) can't be compiled.
+#[cfg(feature = "scripting")]
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct ScriptError {
+    /// Script line (starting from `1`), where the error occurred.
+    pub line: usize,
+    /// Error description.
+    pub message: String,
+}
+
+/// Message definitions errors.
+///
+/// Returned by [`MessageDefinitions`](crate::protocol::MessageDefinitions) and
+/// [`Node::describe`](crate::core::node::Node::describe).
+#[cfg(feature = "definitions")]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum DefinitionsError {
+    /// MAVLink XML definitions can't be loaded.
+    #[error("can't load definitions: {0}")]
+    Load(String),
+
+    /// Node has no message definitions.
+    #[error("message definitions are not configured")]
+    NotConfigured,
+
+    /// Message `ID` is not present in definitions.
+    #[error("unknown message ID: {0}")]
+    UnknownMessage(MessageId),
+
+    /// Message can't be encoded according to definitions.
+    #[error("invalid message: {0}")]
+    InvalidMessage(String),
 }
 
 /// Error that happens, when caller attempts to send message to a closed channel.
@@ -248,6 +752,42 @@ impl From<IncompatFlagsError> for Error {
     }
 }
 
+impl ConfigError {
+    /// <sup>⛔</sup>
+    /// Returns an error, if there are any `diagnostics`.
+    pub(crate) fn check(diagnostics: Vec<ConfigDiagnostic>) -> core::result::Result<(), Self> {
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+        Err(Self { diagnostics })
+    }
+
+    /// Problems found in configuration.
+    pub fn diagnostics(&self) -> &[ConfigDiagnostic] {
+        self.diagnostics.as_slice()
+    }
+}
+
+impl From<ConfigDiagnostic> for ConfigError {
+    fn from(diagnostic: ConfigDiagnostic) -> Self {
+        Self {
+            diagnostics: vec![diagnostic],
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(diagnostic, f)?;
+        }
+        Ok(())
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //                                Recv/Send                                  //
 ///////////////////////////////////////////////////////////////////////////////
@@ -354,6 +894,82 @@ impl From<mpsc::TryRecvError> for Error {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+//                             Crossbeam Channel                             //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(feature = "crossbeam")]
+impl<T> From<crossbeam_channel::SendError<T>> for SendError<T> {
+    fn from(value: crossbeam_channel::SendError<T>) -> Self {
+        SendError(value.0)
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl From<crossbeam_channel::RecvError> for RecvError {
+    fn from(_: crossbeam_channel::RecvError) -> Self {
+        RecvError::Disconnected
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl From<crossbeam_channel::RecvTimeoutError> for RecvTimeoutError {
+    fn from(value: crossbeam_channel::RecvTimeoutError) -> Self {
+        match value {
+            crossbeam_channel::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+            crossbeam_channel::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+        }
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl From<crossbeam_channel::TryRecvError> for TryRecvError {
+    fn from(value: crossbeam_channel::TryRecvError) -> Self {
+        match value {
+            crossbeam_channel::TryRecvError::Empty => TryRecvError::Empty,
+            crossbeam_channel::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                   Flume                                   //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(feature = "flume")]
+impl<T> From<flume::SendError<T>> for SendError<T> {
+    fn from(value: flume::SendError<T>) -> Self {
+        SendError(value.0)
+    }
+}
+
+#[cfg(feature = "flume")]
+impl From<flume::RecvError> for RecvError {
+    fn from(_: flume::RecvError) -> Self {
+        RecvError::Disconnected
+    }
+}
+
+#[cfg(feature = "flume")]
+impl From<flume::RecvTimeoutError> for RecvTimeoutError {
+    fn from(value: flume::RecvTimeoutError) -> Self {
+        match value {
+            flume::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+            flume::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+        }
+    }
+}
+
+#[cfg(feature = "flume")]
+impl From<flume::TryRecvError> for TryRecvError {
+    fn from(value: flume::TryRecvError) -> Self {
+        match value {
+            flume::TryRecvError::Empty => TryRecvError::Empty,
+            flume::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //                              Tokio: Broadcast                             //
 ///////////////////////////////////////////////////////////////////////////////
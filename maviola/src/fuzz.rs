@@ -0,0 +1,346 @@
+//! # Fuzzing entry points
+//!
+//! Feeds arbitrary bytes through the same processing path as data received by node channels:
+//! frames are parsed from a byte stream, skipped bytes are reported as malformed, and each frame
+//! is validated and processed by a [`FrameProcessor`] (compatibility, signing, `ID` remapping,
+//! middleware). Accepted frames are checked against routing by target fields and processed as
+//! outgoing frames, the way they are forwarded by callbacks.
+//!
+//! Unlike fuzzing of a MAVLink parser, this allows to catch panics in high-level processing of
+//! incoming frames. Each call returns a structured [`FuzzReport`], so fuzz targets may assert
+//! additional invariants.
+//!
+//! Fuzz targets for `cargo-fuzz` are located in the `fuzz` directory of the repository.
+//!
+//! This module is available under `fuzz` feature.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use maviola::fuzz::FrameFuzzer;
+//! use maviola::prelude::*;
+//!
+//! let fuzzer = FrameFuzzer::<Versionless>::default();
+//! let report = fuzzer.feed(&[0xFD, 0x09, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0xFF]);
+//!
+//! assert_eq!(report.accepted().count(), 0);
+//! assert_eq!(report.trailing(), 11);
+//! ```
+
+use std::io::{Cursor, ErrorKind};
+use std::marker::PhantomData;
+
+use crate::core::io::{Captured, Receiver};
+use crate::core::node::ValidationReport;
+use crate::error::FrameError;
+use crate::protocol::{
+    CompatProcessor, FrameProcessor, FrameProcessorBuilder, FrameSigner, IdRemapper, KnownDialects,
+    TargetFields,
+};
+
+use crate::prelude::*;
+
+/// Default `ID` of a node, that receives fuzzed data.
+const DEFAULT_FUZZER_ID: MavLinkId = MavLinkId {
+    system: 1,
+    component: 1,
+};
+
+/// Feeds arbitrary bytes into incoming frames processing.
+///
+/// Frame processor is configured in the same way as for nodes and is shared between calls to
+/// [`feed`](Self::feed), so stateful processing, such as signature timestamps, is preserved.
+///
+/// See [module](self) documentation for details.
+pub struct FrameFuzzer<V: MaybeVersioned> {
+    config: FrameProcessorBuilder,
+    processor: FrameProcessor,
+    id: MavLinkId,
+    _version: PhantomData<V>,
+}
+
+/// Outcome of processing a single frame or a sequence of bytes.
+#[derive(Clone, Debug)]
+pub enum FuzzOutcome<V: MaybeVersioned> {
+    /// Bytes, that were skipped before a frame could be parsed.
+    Malformed(ValidationReport),
+    /// Frame, that hasn't passed validation.
+    Invalid(Frame<V>, ValidationReport),
+    /// Frame, that passed validation.
+    Accepted {
+        /// Frame after incoming processing.
+        frame: Frame<V>,
+        /// Whether frame is addressed to the fuzzer node according to its target fields.
+        addressed: bool,
+        /// Result of processing the frame as outgoing, when it is forwarded.
+        forwarded: core::result::Result<Frame<V>, FrameError>,
+    },
+}
+
+/// Outcomes of processing a chunk of bytes by [`FrameFuzzer::feed`].
+#[derive(Clone, Debug)]
+pub struct FuzzReport<V: MaybeVersioned> {
+    outcomes: Vec<FuzzOutcome<V>>,
+    trailing: usize,
+}
+
+impl<V: MaybeVersioned> Default for FrameFuzzer<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: MaybeVersioned> FrameFuzzer<V> {
+    /// Creates a fuzzer with a default frame processor, that knows only default dialects.
+    pub fn new() -> Self {
+        Self::from_config(FrameProcessor::builder())
+    }
+
+    /// Sets `ID` of a node, that receives fuzzed data.
+    ///
+    /// Defines, whether accepted frames are addressed to the node. By default, system and
+    /// component `ID`s are `1`.
+    pub fn with_id(mut self, id: MavLinkId) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets message signer.
+    pub fn with_signer(self, signer: FrameSigner) -> Self {
+        let config = self.config.clone().signer(signer);
+        self.reconfigured(config)
+    }
+
+    /// Sets compatibility processor.
+    pub fn with_compat(self, compat: CompatProcessor) -> Self {
+        let config = self.config.clone().compat(compat);
+        self.reconfigured(config)
+    }
+
+    /// Sets known dialects.
+    pub fn with_dialects(self, dialects: KnownDialects) -> Self {
+        let config = self.config.clone().dialects(dialects);
+        self.reconfigured(config)
+    }
+
+    /// Sets `ID` remapper.
+    pub fn with_remapper(self, remapper: IdRemapper) -> Self {
+        let config = self.config.clone().remapper(remapper);
+        self.reconfigured(config)
+    }
+
+    /// Frame processor of the fuzzer.
+    pub fn processor(&self) -> &FrameProcessor {
+        &self.processor
+    }
+
+    /// Feeds arbitrary `data` into incoming frames processing.
+    ///
+    /// Data is treated as a chunk of a byte stream received by a channel. Bytes after the last
+    /// parsed frame are counted as [`trailing`](FuzzReport::trailing), since channel would wait
+    /// for more data.
+    pub fn feed(&self, data: &[u8]) -> FuzzReport<V> {
+        let id = self.id;
+        let mut reader = Captured::new(Cursor::new(data));
+        let mut outcomes = Vec::new();
+        let mut parsed = 0;
+
+        loop {
+            let position = reader.get_ref().position();
+            let result = Receiver::new::<V>(&mut reader).recv();
+
+            let mut frame = match result.map_err(Error::from) {
+                Ok(frame) => frame,
+                Err(Error::Io(err)) if err.kind() == ErrorKind::UnexpectedEof => break,
+                // Receiver should always consume bytes, otherwise channel would stall
+                Err(_) if reader.get_ref().position() == position => break,
+                Err(_) => continue,
+            };
+            parsed = reader.get_ref().position() as usize;
+
+            if let Some(skipped) = reader.take_skipped(&frame) {
+                outcomes.push(FuzzOutcome::Malformed(ValidationReport::malformed::<V>(
+                    skipped,
+                )));
+            }
+
+            if let Err(report) = ValidationReport::validate_incoming(&mut frame, &self.processor) {
+                outcomes.push(FuzzOutcome::Invalid(frame, report));
+                continue;
+            }
+
+            let addressed = TargetFields::is_addressed_to(&frame, id);
+            let mut forwarded = frame.clone();
+            let forwarded = self
+                .processor
+                .process_outgoing(&mut forwarded)
+                .map(|_| forwarded);
+
+            outcomes.push(FuzzOutcome::Accepted {
+                frame,
+                addressed,
+                forwarded,
+            });
+        }
+
+        FuzzReport {
+            outcomes,
+            trailing: data.len().saturating_sub(parsed),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> FrameFuzzer<V> {
+    fn from_config(config: FrameProcessorBuilder) -> Self {
+        Self {
+            processor: config.clone().build(),
+            config,
+            id: DEFAULT_FUZZER_ID,
+            _version: PhantomData,
+        }
+    }
+
+    fn reconfigured(self, config: FrameProcessorBuilder) -> Self {
+        Self {
+            id: self.id,
+            ..Self::from_config(config)
+        }
+    }
+}
+
+impl<V: MaybeVersioned> FuzzReport<V> {
+    /// Outcomes in the order of data processing.
+    pub fn outcomes(&self) -> &[FuzzOutcome<V>] {
+        &self.outcomes
+    }
+
+    /// Frames, that passed validation.
+    pub fn accepted(&self) -> impl Iterator<Item = &Frame<V>> {
+        self.outcomes.iter().filter_map(|outcome| match outcome {
+            FuzzOutcome::Accepted { frame, .. } => Some(frame),
+            _ => None,
+        })
+    }
+
+    /// Reports on frames and bytes, that haven't passed validation.
+    pub fn rejected(&self) -> impl Iterator<Item = &ValidationReport> {
+        self.outcomes.iter().filter_map(|outcome| match outcome {
+            FuzzOutcome::Malformed(report) | FuzzOutcome::Invalid(_, report) => Some(report),
+            FuzzOutcome::Accepted { .. } => None,
+        })
+    }
+
+    /// Number of bytes after the last parsed frame.
+    pub fn trailing(&self) -> usize {
+        self.trailing
+    }
+}
+
+/// Feeds arbitrary `data` into incoming frames processing with a default frame processor.
+///
+/// See [`FrameFuzzer::feed`] for details.
+pub fn fuzz_incoming<V: MaybeVersioned>(data: &[u8]) -> FuzzReport<V> {
+    FrameFuzzer::default().feed(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::node::InvalidKind;
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::SignStrategy;
+
+    fn heartbeat_bytes(frame: &Frame<V2>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        crate::core::io::Sender::new(&mut bytes)
+            .send(frame)
+            .unwrap();
+        bytes
+    }
+
+    fn heartbeat() -> Frame<V2> {
+        Frame::builder()
+            .sequence(0)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .message(&Heartbeat::default())
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn frames_and_garbage_are_reported() {
+        let mut data = vec![0x01, 0x02];
+        data.extend(heartbeat_bytes(&heartbeat()));
+        data.extend(heartbeat_bytes(&heartbeat()));
+        data.extend([0xFD, 0x09]);
+
+        let report = fuzz_incoming::<V2>(&data);
+
+        assert_eq!(report.accepted().count(), 2);
+        assert_eq!(report.rejected().count(), 1);
+        assert!(matches!(
+            report.outcomes()[0],
+            FuzzOutcome::Malformed(ref report) if report.raw_bytes() == Some(&[0x01, 0x02][..])
+        ));
+        assert_eq!(report.trailing(), 2);
+    }
+
+    #[test]
+    fn frames_are_validated() {
+        let mut frame = heartbeat();
+        let mut bytes = heartbeat_bytes(&frame);
+        // Corrupt payload
+        bytes[10] ^= 0xFF;
+        let report = fuzz_incoming::<V2>(&bytes);
+        let kinds: Vec<_> = report.rejected().map(|report| report.kind()).collect();
+        assert_eq!(kinds, [InvalidKind::Checksum]);
+
+        let signer = FrameSigner::builder()
+            .link_id(1)
+            .key("abcdef")
+            .incoming(SignStrategy::Strict)
+            .outgoing(SignStrategy::Sign)
+            .build();
+        let fuzzer = FrameFuzzer::<V2>::new().with_signer(signer.clone());
+
+        let report = fuzzer.feed(&heartbeat_bytes(&frame));
+        let kinds: Vec<_> = report.rejected().map(|report| report.kind()).collect();
+        assert_eq!(kinds, [InvalidKind::Signature]);
+
+        assert!(signer.process_outgoing(&mut frame).is_ok());
+        let report = fuzzer.feed(&heartbeat_bytes(&frame));
+        match report.outcomes() {
+            [FuzzOutcome::Accepted {
+                addressed,
+                forwarded,
+                ..
+            }] => {
+                assert!(addressed);
+                assert!(forwarded.as_ref().unwrap().is_signed());
+            }
+            outcomes => panic!("unexpected outcomes: {outcomes:?}"),
+        }
+    }
+
+    #[test]
+    fn arbitrary_bytes_are_consumed() {
+        let mut state = 0x5EEDu64;
+        let data: Vec<u8> = (0..4096)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                // Bias towards magic bytes to reach frame parsing
+                match (state >> 33) % 8 {
+                    0 => 0xFD,
+                    1 => 0xFE,
+                    _ => (state >> 40) as u8,
+                }
+            })
+            .collect();
+
+        let report = fuzz_incoming::<Versionless>(&data);
+        assert!(report.trailing() <= data.len());
+    }
+}
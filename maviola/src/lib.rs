@@ -79,9 +79,9 @@ need certain features, you have to explicitly opt-in.
 ### API Modes
 
 * `sync` enables synchronous API (see [`sync`] module and
-   [Synchronous API](crate::docs::a3__sync_api)).
+  [Synchronous API](crate::docs::a3__sync_api)).
 * `async` enables asynchronous API (see [`asnc`] module and
-   [Asynchronous API](crate::docs::a4__async_api)).
+  [Asynchronous API](crate::docs::a4__async_api)).
 
 These features are not mutually exclusive, you can use both synchronous and asynchronous API in
 different parts of the project.
@@ -95,6 +95,92 @@ Rust.
 
 Check [Dialects](crate::docs::a2__overview#dialects) documentation section for details.
 
+### Thread Control
+
+The `thread_control` feature allows to set priority and core affinity of threads spawned by
+synchronous nodes (see [`ThreadSettings`](crate::core::utils::ThreadSettings)). This feature
+relies on OS-specific APIs and is supported on Unix-like systems and Windows.
+
+### Serial Ports
+
+The `serial` feature enables [`SerialPort`] transport for synchronous API. It is supported on
+Unix-like systems and Windows.
+
+### TLS
+
+The `tls` feature enables encryption of [`TcpServer`](crate::core::io::TcpServer) and
+[`TcpClient`](crate::core::io::TcpClient) connections by TLS based on
+[`rustls`](https://docs.rs/rustls). Settings are provided by [`TlsAcceptor`] and
+[`TlsConnector`]. Both synchronous and asynchronous API are supported.
+
+### Shared Memory
+
+The `ipc` feature enables [`IpcServer`] and [`IpcClient`] transports, that exchange frames between
+processes on the same host through shared memory ring buffers. They have lower latency than Unix
+sockets and are intended for high-rate telemetry on companion computers. Only synchronous API on
+Unix-like systems is supported.
+
+### Bluetooth
+
+The `bluetooth` feature enables [`BluetoothClient`] transport, that connects to Bluetooth classic
+serial devices, such as HC-05 modules, over RFCOMM. Both synchronous and asynchronous API are
+supported. It is available only on Linux with BlueZ stack.
+
+### ZeroMQ
+
+The `zmq` feature enables [`ZmqPub`] and [`ZmqSub`] transports, that plug nodes into ZeroMQ
+message buses. Both synchronous and asynchronous API are supported. Transports implement ZMTP
+protocol natively and do not require `libzmq`.
+
+### MQTT
+
+The `mqtt` feature enables [`MqttBridge`] transport, that publishes frames to an MQTT broker and
+receives frames from subscribed topics. Both synchronous and asynchronous API are supported.
+Frames can be exchanged as JSON, if `definitions` feature is enabled as well.
+
+### NATS & Redis
+
+The `nats` and `redis` features enable [`NatsBridge`] and [`RedisBridge`] transports respectively.
+Each maps a connection onto a NATS subject or a Redis Pub/Sub channel carrying raw frames, so
+several services can share one vehicle link through an existing message broker. Both synchronous
+and asynchronous API are supported.
+
+### Tracing
+
+The `tracing` feature instruments nodes with [`tracing`](https://docs.rs/tracing) spans: one per
+connection, per channel, and per processed frame. Frame spans carry system, component, and message
+IDs, so routing decisions and errors can be correlated in production observability stacks. Log
+records emitted by Maviola become attributed to these spans, once converted to tracing events by
+[`tracing-log`](https://docs.rs/tracing-log).
+
+### Microservices
+
+Utilities implementing MAVLink [microservices](https://mavlink.io/en/services/) are available
+under `msrv-utils-*` feature flags and located in `core::msrv` module. For example,
+`msrv-utils-params` enables a parameter protocol server, that can be attached to edge nodes, and
+`msrv-utils-mission` adds mission upload and download helpers. Use `msrv-utils-all` to enable all
+microservices.
+
+### Soak Testing
+
+The `soak` feature enables `soak` module with a harness, that runs synchronous nodes for a long
+time while checking invariants such as memory growth, sequence regressions, and stuck channels.
+It produces a machine-readable report, that can be used to qualify releases of applications built
+with Maviola.
+
+### Fuzzing
+
+The `fuzz` feature enables `fuzz` module, that feeds arbitrary bytes through
+parsing and processing of incoming frames, including message signing, compatibility checks, and
+routing. It is used by `cargo-fuzz` targets located in the `fuzz` directory of the repository.
+
+### Testing Utils
+
+The `test_utils` feature enables [`test_utils`] module with tools for integration tests of
+applications. For example, [`SimulatedLink`] connects nodes by an in-memory link with configurable
+frame loss, latency, reordering, and bandwidth. This feature enables `unsafe` and `unstable`
+features and should not be used in production builds.
+
 ### Unstable Features
 
 Some parts of the API are still considered to be unstable and available only under the
@@ -108,6 +194,105 @@ Maviola is based on [Mavio](https://gitlab.com/mavka/libs/mavio), a low-level li
 probably be a better option.
 */
 
+// Links to entities behind disabled features lead to `docs.rs`.
+#![cfg_attr(
+    all(feature = "ipc", unix),
+    doc = "",
+    doc = "[`IpcServer`]: crate::core::io::IpcServer",
+    doc = "[`IpcClient`]: crate::core::io::IpcClient"
+)]
+#![cfg_attr(
+    not(all(feature = "ipc", unix)),
+    doc = "",
+    doc = "[`IpcServer`]: https://docs.rs/maviola/latest/maviola/core/io/struct.IpcServer.html",
+    doc = "[`IpcClient`]: https://docs.rs/maviola/latest/maviola/core/io/struct.IpcClient.html"
+)]
+#![cfg_attr(
+    feature = "mqtt",
+    doc = "",
+    doc = "[`MqttBridge`]: crate::core::io::MqttBridge"
+)]
+#![cfg_attr(
+    not(feature = "mqtt"),
+    doc = "",
+    doc = "[`MqttBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.MqttBridge.html"
+)]
+#![cfg_attr(
+    feature = "tls",
+    doc = "",
+    doc = "[`TlsAcceptor`]: crate::core::io::TlsAcceptor",
+    doc = "[`TlsConnector`]: crate::core::io::TlsConnector"
+)]
+#![cfg_attr(
+    not(feature = "tls"),
+    doc = "",
+    doc = "[`TlsAcceptor`]: https://docs.rs/maviola/latest/maviola/core/io/struct.TlsAcceptor.html",
+    doc = "[`TlsConnector`]: https://docs.rs/maviola/latest/maviola/core/io/struct.TlsConnector.html"
+)]
+#![cfg_attr(
+    all(feature = "bluetooth", target_os = "linux"),
+    doc = "",
+    doc = "[`BluetoothClient`]: crate::core::io::BluetoothClient"
+)]
+#![cfg_attr(
+    not(all(feature = "bluetooth", target_os = "linux")),
+    doc = "",
+    doc = "[`BluetoothClient`]: https://docs.rs/maviola/latest/maviola/core/io/struct.BluetoothClient.html"
+)]
+#![cfg_attr(
+    feature = "test_utils",
+    doc = "",
+    doc = "[`test_utils`]: crate::test_utils",
+    doc = "[`SimulatedLink`]: crate::test_utils::simulated::SimulatedLink"
+)]
+#![cfg_attr(
+    not(feature = "test_utils"),
+    doc = "",
+    doc = "[`test_utils`]: https://docs.rs/maviola/latest/maviola/test_utils/index.html",
+    doc = "[`SimulatedLink`]: https://docs.rs/maviola/latest/maviola/test_utils/simulated/struct.SimulatedLink.html"
+)]
+#![cfg_attr(
+    feature = "serial",
+    doc = "",
+    doc = "[`SerialPort`]: crate::core::io::SerialPort"
+)]
+#![cfg_attr(
+    not(feature = "serial"),
+    doc = "",
+    doc = "[`SerialPort`]: https://docs.rs/maviola/latest/maviola/core/io/struct.SerialPort.html"
+)]
+#![cfg_attr(
+    feature = "zmq",
+    doc = "",
+    doc = "[`ZmqPub`]: crate::core::io::ZmqPub",
+    doc = "[`ZmqSub`]: crate::core::io::ZmqSub"
+)]
+#![cfg_attr(
+    not(feature = "zmq"),
+    doc = "",
+    doc = "[`ZmqPub`]: https://docs.rs/maviola/latest/maviola/core/io/struct.ZmqPub.html",
+    doc = "[`ZmqSub`]: https://docs.rs/maviola/latest/maviola/core/io/struct.ZmqSub.html"
+)]
+#![cfg_attr(
+    feature = "nats",
+    doc = "",
+    doc = "[`NatsBridge`]: crate::core::io::NatsBridge"
+)]
+#![cfg_attr(
+    not(feature = "nats"),
+    doc = "",
+    doc = "[`NatsBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.NatsBridge.html"
+)]
+#![cfg_attr(
+    feature = "redis",
+    doc = "",
+    doc = "[`RedisBridge`]: crate::core::io::RedisBridge"
+)]
+#![cfg_attr(
+    not(feature = "redis"),
+    doc = "",
+    doc = "[`RedisBridge`]: https://docs.rs/maviola/latest/maviola/core/io/struct.RedisBridge.html"
+)]
 #![warn(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![doc(
@@ -119,19 +304,22 @@ probably be a better option.
 pub mod asnc;
 pub mod core;
 pub mod error;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod prelude;
 pub mod protocol;
+#[cfg(feature = "soak")]
+pub mod soak;
 #[cfg(feature = "sync")]
 pub mod sync;
 
-#[cfg(any(doc, doctest, rustdoc))]
+#[cfg(any(doc, doctest))]
 pub mod docs;
 
 #[cfg(feature = "test_utils")]
-#[doc(hidden)]
 pub mod test_utils;
 
-#[doc(inline = true)]
+#[doc(inline)]
 /// <sup>[`mavio`](https://crates.io/crates/mavio)</sup>
 /// MAVLink dialects
 pub use mavio::dialects;
@@ -12,7 +12,9 @@ pub use crate::protocol::{
     Versionless, V1, V2,
 };
 
-pub use crate::core::io::{FileReader, FileWriter, TcpClient, TcpServer, UdpClient, UdpServer};
+pub use crate::core::io::{
+    FileReader, FileWriter, TcpClient, TcpServer, TlogReader, TlogWriter, UdpClient, UdpServer,
+};
 #[cfg(unix)]
 pub use crate::core::io::{SockClient, SockServer};
 pub use crate::core::network::Network;
@@ -0,0 +1,585 @@
+//! MAVLink peer anomaly detection.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::core::consts::{
+    DEFAULT_ANOMALY_PEER_TTL, DEFAULT_HEARTBEAT_WINDOW, DEFAULT_MAX_HEARTBEATS,
+};
+use crate::core::io::{ChannelId, ChannelInfo};
+use crate::dialects::minimal::enums::{MavAutopilot, MavType};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::protocol::SystemId;
+
+use crate::prelude::*;
+
+/// Detector for impossible or suspicious behavior of MAVLink peers.
+///
+/// Anomaly detector inspects incoming heartbeats and reports an [`Anomaly`] when:
+///
+/// * a peer sends heartbeats far more frequently than the protocol suggests
+///   ([`Anomaly::HeartbeatFlood`]),
+/// * the same system / component `ID` reports different `MAV_TYPE` or autopilot from different
+///   channels ([`Anomaly::ConflictingIdentity`]),
+/// * a channel, that carried heartbeats of a single system, suddenly reports another system `ID`
+///   ([`Anomaly::SystemIdChanged`]).
+///
+/// Detected anomalies are emitted as `Event::Anomaly` node events. Set detector with
+/// [`NodeBuilder::anomaly_detector`](crate::core::node::NodeBuilder::anomaly_detector).
+///
+/// Detector forgets peers, that sent no heartbeats for [`AnomalyDetector::peer_ttl`], and
+/// everything observed on a channel, once this channel is closed.
+///
+/// # Usage
+///
+/// ```rust
+/// use std::time::Duration;
+/// use maviola::protocol::AnomalyDetector;
+///
+/// let detector = AnomalyDetector::new()
+///     // More than 5 heartbeats within 1 second is a flood
+///     .with_max_heartbeats(5, Duration::from_secs(1))
+///     // Multi-system channels are expected in our setup
+///     .with_system_id_changes(false);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnomalyDetector {
+    max_heartbeats: usize,
+    heartbeat_window: Duration,
+    conflicts: bool,
+    system_id_changes: bool,
+    peer_ttl: Duration,
+}
+
+/// Anomaly in peer behavior detected by [`AnomalyDetector`].
+#[derive(Clone, Debug)]
+pub enum Anomaly {
+    /// Peer sent more heartbeats than allowed within a time window.
+    ///
+    /// Reported once per flooding episode. If heartbeat rate of the peer returns to normal, the next
+    /// flood will be reported again.
+    HeartbeatFlood {
+        /// Flooding peer.
+        id: MavLinkId,
+        /// Channel of the last heartbeat.
+        channel: ChannelInfo,
+        /// Number of heartbeats within [`window`](Anomaly::HeartbeatFlood::window).
+        count: usize,
+        /// Time window.
+        window: Duration,
+    },
+    /// Peer with the same system and component `ID` reports conflicting `MAV_TYPE` or autopilot
+    /// from different channels.
+    ConflictingIdentity {
+        /// Peer identity.
+        id: MavLinkId,
+        /// Channel of the conflicting heartbeat.
+        channel: ChannelInfo,
+        /// `MAV_TYPE` reported by the conflicting heartbeat.
+        mav_type: MavType,
+        /// Autopilot reported by the conflicting heartbeat.
+        autopilot: MavAutopilot,
+        /// Channel, where this identity was first seen.
+        known_channel: ChannelInfo,
+        /// `MAV_TYPE` previously known for this identity.
+        known_mav_type: MavType,
+        /// Autopilot previously known for this identity.
+        known_autopilot: MavAutopilot,
+    },
+    /// Channel, that carried heartbeats from a single system, reports another system `ID`.
+    SystemIdChanged {
+        /// Channel, where system `ID` has changed.
+        channel: ChannelInfo,
+        /// System `ID` previously seen on this channel.
+        previous: SystemId,
+        /// New system `ID`.
+        current: SystemId,
+    },
+}
+
+impl AnomalyDetector {
+    /// Creates an anomaly detector with default settings.
+    ///
+    /// By default, all anomalies are detected and a peer is considered to be flooding, when it
+    /// sends more than [`DEFAULT_MAX_HEARTBEATS`] within [`DEFAULT_HEARTBEAT_WINDOW`]. Peers are
+    /// forgotten after [`DEFAULT_ANOMALY_PEER_TTL`] of silence.
+    pub fn new() -> Self {
+        Self {
+            max_heartbeats: DEFAULT_MAX_HEARTBEATS,
+            heartbeat_window: DEFAULT_HEARTBEAT_WINDOW,
+            conflicts: true,
+            system_id_changes: true,
+            peer_ttl: DEFAULT_ANOMALY_PEER_TTL,
+        }
+    }
+
+    /// Sets maximum number of heartbeats a single peer may send within a `window`.
+    ///
+    /// Setting `count` to `0` disables heartbeat flood detection.
+    pub fn with_max_heartbeats(mut self, count: usize, window: Duration) -> Self {
+        self.max_heartbeats = count;
+        self.heartbeat_window = window;
+        self
+    }
+
+    /// Enables or disables detection of conflicting identities (enabled by default).
+    pub fn with_conflicts(mut self, value: bool) -> Self {
+        self.conflicts = value;
+        self
+    }
+
+    /// Enables or disables detection of system `ID` changes on a channel (enabled by default).
+    pub fn with_system_id_changes(mut self, value: bool) -> Self {
+        self.system_id_changes = value;
+        self
+    }
+
+    /// Sets time after which a peer, that has sent no heartbeats, is forgotten.
+    ///
+    /// Once forgotten, the peer is treated as a new one. The `ttl` should be greater than
+    /// [`AnomalyDetector::heartbeat_window`], otherwise floods may be missed.
+    pub fn with_peer_ttl(mut self, ttl: Duration) -> Self {
+        self.peer_ttl = ttl;
+        self
+    }
+
+    /// Maximum number of heartbeats within [`AnomalyDetector::heartbeat_window`].
+    ///
+    /// Zero means, that heartbeat flood detection is disabled.
+    pub fn max_heartbeats(&self) -> usize {
+        self.max_heartbeats
+    }
+
+    /// Time window for heartbeat rate measurement.
+    pub fn heartbeat_window(&self) -> Duration {
+        self.heartbeat_window
+    }
+
+    /// Returns `true` if detection of conflicting identities is enabled.
+    pub fn detects_conflicts(&self) -> bool {
+        self.conflicts
+    }
+
+    /// Returns `true` if detection of system `ID` changes is enabled.
+    pub fn detects_system_id_changes(&self) -> bool {
+        self.system_id_changes
+    }
+
+    /// Time after which a peer, that has sent no heartbeats, is forgotten.
+    pub fn peer_ttl(&self) -> Duration {
+        self.peer_ttl
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that applies this detector to incoming heartbeats.
+    pub(crate) fn tracker(&self) -> AnomalyTracker {
+        AnomalyTracker {
+            detector: self.clone(),
+            records: Default::default(),
+        }
+    }
+}
+
+impl Default for AnomalyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Keeps track of peer behavior for [`AnomalyDetector`].
+///
+/// This is a shared handle: clones refer to the same data.
+#[derive(Clone)]
+pub(crate) struct AnomalyTracker {
+    detector: AnomalyDetector,
+    records: Arc<Mutex<PeerRecords>>,
+}
+
+#[derive(Default)]
+struct PeerRecords {
+    heartbeats: HashMap<MavLinkId, VecDeque<Instant>>,
+    flooding: HashSet<MavLinkId>,
+    identities: HashMap<MavLinkId, Identity>,
+    channels: HashMap<ChannelId, HashSet<SystemId>>,
+    last_seen: HashMap<MavLinkId, (Instant, ChannelId)>,
+    last_eviction: Option<Instant>,
+}
+
+struct Identity {
+    mav_type: MavType,
+    autopilot: MavAutopilot,
+    channel: ChannelInfo,
+}
+
+impl AnomalyTracker {
+    /// Inspects a heartbeat received from a peer with specified `id` via `channel`.
+    pub(crate) fn inspect(
+        &self,
+        heartbeat: &Heartbeat,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+    ) -> Vec<Anomaly> {
+        self.inspect_at(heartbeat, id, channel, Instant::now())
+    }
+
+    /// Forgets everything observed on a closed channel with specified `channel_id`.
+    pub(crate) fn forget_channel(&self, channel_id: ChannelId) {
+        let mut records = self.records();
+
+        let ids: Vec<MavLinkId> = records
+            .last_seen
+            .iter()
+            .filter(|(_, (_, channel))| *channel == channel_id)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in ids {
+            records.forget_peer(id);
+        }
+
+        records
+            .identities
+            .retain(|_, identity| identity.channel.id() != channel_id);
+        records.channels.remove(&channel_id);
+    }
+
+    fn inspect_at(
+        &self,
+        heartbeat: &Heartbeat,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+        now: Instant,
+    ) -> Vec<Anomaly> {
+        let mut records = self.records();
+        records.evict_inactive(self.detector.peer_ttl, now);
+        records.last_seen.insert(id, (now, channel.id()));
+
+        let mut anomalies = Vec::new();
+
+        if let Some(anomaly) = records.check_flood(&self.detector, id, channel, now) {
+            anomalies.push(anomaly);
+        }
+        if let Some(anomaly) = records.check_identity(&self.detector, heartbeat, id, channel) {
+            anomalies.push(anomaly);
+        }
+        if let Some(anomaly) = records.check_system_id(&self.detector, id, channel) {
+            anomalies.push(anomaly);
+        }
+
+        anomalies
+    }
+
+    fn records(&self) -> MutexGuard<'_, PeerRecords> {
+        // Records are always consistent, since they are modified only under the lock
+        match self.records.lock() {
+            Ok(records) => records,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl PeerRecords {
+    /// Forgets peers, that sent no heartbeats for longer than `ttl`, and channels without known
+    /// peers.
+    ///
+    /// Performed at most once per `ttl`.
+    fn evict_inactive(&mut self, ttl: Duration, now: Instant) {
+        let is_expired = |since: Instant| now.saturating_duration_since(since) > ttl;
+
+        match self.last_eviction {
+            Some(last_eviction) if !is_expired(last_eviction) => return,
+            _ => self.last_eviction = Some(now),
+        }
+
+        let expired: Vec<MavLinkId> = self
+            .last_seen
+            .iter()
+            .filter(|(_, (seen_at, _))| is_expired(*seen_at))
+            .map(|(&id, _)| id)
+            .collect();
+        for id in expired {
+            self.forget_peer(id);
+        }
+
+        let active: HashSet<ChannelId> = self
+            .last_seen
+            .values()
+            .map(|(_, channel_id)| *channel_id)
+            .collect();
+        self.channels
+            .retain(|channel_id, _| active.contains(channel_id));
+    }
+
+    fn forget_peer(&mut self, id: MavLinkId) {
+        self.heartbeats.remove(&id);
+        self.flooding.remove(&id);
+        self.identities.remove(&id);
+        self.last_seen.remove(&id);
+    }
+
+    fn check_flood(
+        &mut self,
+        detector: &AnomalyDetector,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+        now: Instant,
+    ) -> Option<Anomaly> {
+        let max_heartbeats = detector.max_heartbeats;
+        let window = detector.heartbeat_window;
+        if max_heartbeats == 0 {
+            return None;
+        }
+
+        let timestamps = self.heartbeats.entry(id).or_default();
+        timestamps.push_back(now);
+        while let Some(&oldest) = timestamps.front() {
+            let expired = now.saturating_duration_since(oldest) > window;
+            if !expired && timestamps.len() <= max_heartbeats + 1 {
+                break;
+            }
+            timestamps.pop_front();
+        }
+
+        let count = timestamps.len();
+        if count <= max_heartbeats {
+            self.flooding.remove(&id);
+            return None;
+        }
+        if !self.flooding.insert(id) {
+            return None;
+        }
+
+        Some(Anomaly::HeartbeatFlood {
+            id,
+            channel: channel.clone(),
+            count,
+            window,
+        })
+    }
+
+    fn check_identity(
+        &mut self,
+        detector: &AnomalyDetector,
+        heartbeat: &Heartbeat,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+    ) -> Option<Anomaly> {
+        if !detector.conflicts {
+            return None;
+        }
+
+        let known = match self.identities.get_mut(&id) {
+            Some(known) => known,
+            None => {
+                self.identities.insert(
+                    id,
+                    Identity {
+                        mav_type: heartbeat.type_,
+                        autopilot: heartbeat.autopilot,
+                        channel: channel.clone(),
+                    },
+                );
+                return None;
+            }
+        };
+
+        // Peer may legitimately change its identity on its own channel (for example, after reboot)
+        if known.channel.id() == channel.id() {
+            known.mav_type = heartbeat.type_;
+            known.autopilot = heartbeat.autopilot;
+            return None;
+        }
+
+        if known.mav_type as u8 == heartbeat.type_ as u8
+            && known.autopilot as u8 == heartbeat.autopilot as u8
+        {
+            return None;
+        }
+
+        Some(Anomaly::ConflictingIdentity {
+            id,
+            channel: channel.clone(),
+            mav_type: heartbeat.type_,
+            autopilot: heartbeat.autopilot,
+            known_channel: known.channel.clone(),
+            known_mav_type: known.mav_type,
+            known_autopilot: known.autopilot,
+        })
+    }
+
+    fn check_system_id(
+        &mut self,
+        detector: &AnomalyDetector,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+    ) -> Option<Anomaly> {
+        if !detector.system_id_changes {
+            return None;
+        }
+
+        let systems = self.channels.entry(channel.id()).or_default();
+        let previous = match systems.iter().next() {
+            Some(&previous) if systems.len() == 1 && previous != id.system => Some(previous),
+            _ => None,
+        };
+        systems.insert(id.system);
+
+        previous.map(|previous| Anomaly::SystemIdChanged {
+            channel: channel.clone(),
+            previous,
+            current: id.system,
+        })
+    }
+}
+
+#[cfg(test)]
+mod anomaly_tests {
+    use super::*;
+    use crate::core::io::{ChannelDetails, ConnectionDetails, ConnectionInfo};
+
+    fn channel(conn: &ConnectionInfo) -> ChannelInfo {
+        conn.make_channel_info(ChannelDetails::Unknown)
+    }
+
+    fn heartbeat(mav_type: MavType) -> Heartbeat {
+        Heartbeat {
+            type_: mav_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn heartbeat_flood_is_reported_once() {
+        let conn = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let channel = channel(&conn);
+        let tracker = AnomalyDetector::new()
+            .with_max_heartbeats(3, Duration::from_secs(10))
+            .tracker();
+        let id = MavLinkId::new(1, 1);
+
+        let mut floods = 0;
+        for _ in 0..10 {
+            for anomaly in tracker.inspect(&Heartbeat::default(), id, &channel) {
+                if let Anomaly::HeartbeatFlood { count, .. } = anomaly {
+                    assert_eq!(count, 4);
+                    floods += 1;
+                }
+            }
+        }
+
+        assert_eq!(floods, 1);
+    }
+
+    #[test]
+    fn conflicting_identity() {
+        let conn = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let (channel_1, channel_2) = (channel(&conn), channel(&conn));
+        let tracker = AnomalyDetector::new().tracker();
+        let id = MavLinkId::new(1, 1);
+
+        let quadrotor = heartbeat(MavType::Quadrotor);
+        let fixed_wing = heartbeat(MavType::FixedWing);
+
+        assert!(tracker.inspect(&quadrotor, id, &channel_1).is_empty());
+        // Identity is refreshed by heartbeats from its own channel
+        assert!(tracker.inspect(&fixed_wing, id, &channel_1).is_empty());
+        assert!(tracker.inspect(&fixed_wing, id, &channel_2).is_empty());
+
+        let anomalies = tracker.inspect(&quadrotor, id, &channel_2);
+        assert!(matches!(
+            anomalies.as_slice(),
+            [Anomaly::ConflictingIdentity { .. }]
+        ));
+    }
+
+    #[test]
+    fn system_id_changed() {
+        let conn = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let channel = channel(&conn);
+        let tracker = AnomalyDetector::new().tracker();
+
+        assert!(tracker
+            .inspect(&Heartbeat::default(), MavLinkId::new(1, 1), &channel)
+            .is_empty());
+        assert!(tracker
+            .inspect(&Heartbeat::default(), MavLinkId::new(1, 2), &channel)
+            .is_empty());
+
+        let anomalies = tracker.inspect(&Heartbeat::default(), MavLinkId::new(2, 1), &channel);
+        assert!(matches!(
+            anomalies.as_slice(),
+            [Anomaly::SystemIdChanged {
+                previous: 1,
+                current: 2,
+                ..
+            }]
+        ));
+
+        // Channel is already known to carry several systems
+        assert!(tracker
+            .inspect(&Heartbeat::default(), MavLinkId::new(3, 1), &channel)
+            .is_empty());
+    }
+
+    #[test]
+    fn closed_channels_are_forgotten() {
+        let conn = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let (channel_1, channel_2) = (channel(&conn), channel(&conn));
+        let tracker = AnomalyDetector::new().tracker();
+        let id = MavLinkId::new(1, 1);
+
+        assert!(tracker
+            .inspect(&heartbeat(MavType::Quadrotor), id, &channel_1)
+            .is_empty());
+        tracker.forget_channel(channel_1.id());
+
+        {
+            let records = tracker.records();
+            assert!(records.heartbeats.is_empty());
+            assert!(records.identities.is_empty());
+            assert!(records.channels.is_empty());
+            assert!(records.last_seen.is_empty());
+        }
+
+        // Peer moved to another channel is not a conflict
+        assert!(tracker
+            .inspect(&heartbeat(MavType::FixedWing), id, &channel_2)
+            .is_empty());
+    }
+
+    #[test]
+    fn inactive_peers_are_forgotten() {
+        let conn = ConnectionInfo::new(ConnectionDetails::Unknown);
+        let channel = channel(&conn);
+        let tracker = AnomalyDetector::new()
+            .with_peer_ttl(Duration::from_secs(10))
+            .tracker();
+        let start = Instant::now();
+
+        for system_id in 0..100 {
+            tracker.inspect_at(
+                &Heartbeat::default(),
+                MavLinkId::new(system_id, 1),
+                &channel,
+                start,
+            );
+        }
+        assert_eq!(tracker.records().last_seen.len(), 100);
+
+        let later = start + Duration::from_secs(11);
+        let anomalies = tracker.inspect_at(
+            &Heartbeat::default(),
+            MavLinkId::new(200, 1),
+            &channel,
+            later,
+        );
+        assert!(anomalies.is_empty());
+
+        let records = tracker.records();
+        assert_eq!(records.last_seen.len(), 1);
+        assert_eq!(records.heartbeats.len(), 1);
+        assert_eq!(records.identities.len(), 1);
+        assert_eq!(records.channels[&channel.id()].len(), 1);
+    }
+}
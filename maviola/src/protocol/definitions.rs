@@ -0,0 +1,844 @@
+//! Introspection of MAVLink frames based on XML message definitions.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mavinspect::protocol::{Dialect, Enum, MavType, Message, MessageField, Protocol};
+use mavinspect::Inspector;
+
+use crate::error::DefinitionsError;
+use crate::protocol::{DynamicMessage, MavLinkVersion, MessageId, Sequence};
+
+use crate::prelude::*;
+
+/// <sup>`definitions`</sup>
+/// MAVLink message definitions used to describe frames at runtime.
+///
+/// Generated dialects know how to encode and decode their messages, but they carry no metadata
+/// about message and field names, units, or enum labels. Message definitions are loaded from
+/// MAVLink XML files by [MAVInspect](https://crates.io/crates/mavinspect) and allow to turn any
+/// frame into a [`MessageDescriptor`] without per-dialect code. This is useful for generic user
+/// interfaces and log annotators.
+///
+/// Definitions are cheap to clone, all clones share the same underlying data. Messages and enums
+/// from all loaded dialects are merged, if several dialects define the same message `ID`, the one
+/// that was added last wins.
+///
+/// Attach definitions to a node with
+/// [`NodeBuilder::definitions`](crate::core::node::NodeBuilder::definitions) and use
+/// [`Node::describe`](crate::core::node::Node::describe) to describe received frames.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::protocol::MessageDefinitions;
+///
+/// let definitions = MessageDefinitions::load(&["./message_definitions/standard"]).unwrap();
+///
+/// let heartbeat = definitions.message(0).unwrap();
+/// assert_eq!(heartbeat.name(), "HEARTBEAT");
+/// ```
+#[derive(Clone, Default)]
+pub struct MessageDefinitions {
+    inner: Arc<DefinitionsInner>,
+}
+
+#[derive(Default)]
+struct DefinitionsInner {
+    messages: HashMap<MessageId, Message>,
+    enums: HashMap<String, Enum>,
+}
+
+/// <sup>`definitions`</sup>
+/// Description of a MAVLink frame payload.
+///
+/// Created by [`MessageDefinitions::describe`] or
+/// [`Node::describe`](crate::core::node::Node::describe).
+///
+/// Fields are listed in the order they are declared in the XML definition, extension fields come
+/// last. Extension fields truncated by the sender are reported with zero values.
+///
+/// The [`Display`] implementation renders descriptor as a single line suitable for logs:
+///
+/// ```text
+/// HEARTBEAT { type: 2 (MAV_TYPE_QUADROTOR), autopilot: 3 (MAV_AUTOPILOT_ARDUPILOTMEGA), ... }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MessageDescriptor {
+    id: MessageId,
+    name: String,
+    description: String,
+    fields: Vec<FieldDescriptor>,
+}
+
+/// <sup>`definitions`</sup>
+/// Description of a message field within [`MessageDescriptor`].
+#[derive(Clone, Debug)]
+pub struct FieldDescriptor {
+    name: String,
+    description: String,
+    value: FieldValue,
+    units: Option<String>,
+    enum_name: Option<String>,
+    labels: Vec<String>,
+}
+
+/// <sup>`definitions`</sup>
+/// Decoded value of a message field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    /// Signed integer.
+    Int(i64),
+    /// Unsigned integer.
+    UInt(u64),
+    /// Floating point number.
+    Float(f64),
+    /// Character array, trimmed at the first `NUL` byte.
+    Text(String),
+    /// Array of non-character values.
+    Array(Vec<FieldValue>),
+}
+
+impl MessageDefinitions {
+    /// Creates message definitions from the provided messages and enums.
+    pub fn new(
+        messages: impl IntoIterator<Item = Message>,
+        enums: impl IntoIterator<Item = Enum>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(DefinitionsInner {
+                messages: messages.into_iter().map(|msg| (msg.id(), msg)).collect(),
+                enums: enums
+                    .into_iter()
+                    .map(|enm| (enm.name().to_string(), enm))
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Loads message definitions from the directories with MAVLink XML files.
+    ///
+    /// All dialects found in `sources` will be merged. Returns [`DefinitionsError::Load`] if
+    /// definitions can't be parsed.
+    pub fn load<T>(sources: &[T]) -> Result<Self>
+    where
+        T: Into<PathBuf> + Clone,
+    {
+        let protocol = Inspector::builder()
+            .set_sources(sources)
+            .build()
+            .and_then(|inspector| inspector.parse())
+            .map_err(|err| DefinitionsError::Load(err.to_string()))?;
+
+        Ok(Self::from_protocol(&protocol))
+    }
+
+    /// Creates message definitions from all dialects of a parsed MAVLink [`Protocol`].
+    pub fn from_protocol(protocol: &Protocol) -> Self {
+        Self::from_dialects(protocol.dialects())
+    }
+
+    /// Creates message definitions from a collection of parsed MAVLink dialects.
+    pub fn from_dialects<'a>(dialects: impl IntoIterator<Item = &'a Dialect>) -> Self {
+        let mut messages = Vec::new();
+        let mut enums = Vec::new();
+
+        for dialect in dialects {
+            messages.extend(dialect.messages().into_iter().cloned());
+            enums.extend(dialect.enums().into_iter().cloned());
+        }
+
+        Self::new(messages, enums)
+    }
+
+    /// Returns message definition by message `ID`.
+    pub fn message(&self, id: MessageId) -> Option<&Message> {
+        self.inner.messages.get(&id)
+    }
+
+    /// Returns enum definition by enum name.
+    pub fn enum_by_name(&self, name: &str) -> Option<&Enum> {
+        self.inner.enums.get(name)
+    }
+
+    /// Iterates over all known message definitions.
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.inner.messages.values()
+    }
+
+    /// Describes MAVLink frame according to message definitions.
+    ///
+    /// Returns [`DefinitionsError::UnknownMessage`] if there are no definitions for the frame
+    /// message `ID`.
+    pub fn describe<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Result<MessageDescriptor> {
+        let message = self
+            .message(frame.message_id())
+            .ok_or(DefinitionsError::UnknownMessage(frame.message_id()))?;
+
+        // MAVLink 2 truncates trailing zero bytes of the payload
+        let mut payload = frame.payload().bytes().to_vec();
+        payload.resize(message.size_v2().max(payload.len()), 0);
+
+        let mut offset = 0;
+        let mut values = HashMap::new();
+        for field in message.reordered_fields() {
+            let size = field.r#type().size();
+            let value = decode_value(field.r#type(), &payload[offset..offset + size]);
+            values.insert(field.name(), value);
+            offset += size;
+        }
+
+        let fields = message
+            .fields()
+            .iter()
+            .map(|field| {
+                let value = values.remove(field.name()).unwrap();
+                self.describe_field(field, value)
+            })
+            .collect();
+
+        Ok(MessageDescriptor {
+            id: message.id(),
+            name: message.name().to_string(),
+            description: message.description().to_string(),
+            fields,
+        })
+    }
+
+    /// Decodes MAVLink frame into a [`DynamicMessage`] according to message definitions.
+    ///
+    /// Returns [`DefinitionsError::UnknownMessage`] if there are no definitions for the frame
+    /// message `ID`.
+    pub fn decode<V: MaybeVersioned>(&self, frame: &Frame<V>) -> Result<DynamicMessage> {
+        Ok(DynamicMessage::new(frame, self.describe(frame)?))
+    }
+
+    /// Encodes a [`DynamicMessage`] into MAVLink frame with the specified `sequence` according to
+    /// message definitions.
+    ///
+    /// Missing fields are encoded as zeros, fields unknown to definitions are ignored. Returns
+    /// [`DefinitionsError::UnknownMessage`] if there are no definitions for the message `ID`, and
+    /// [`DefinitionsError::InvalidMessage`] if field values do not fit their types or message
+    /// can't be represented in the requested protocol version.
+    pub fn encode<V: Versioned>(
+        &self,
+        message: &DynamicMessage,
+        sequence: Sequence,
+    ) -> Result<Frame<V>> {
+        let definition = self
+            .message(message.id())
+            .ok_or(DefinitionsError::UnknownMessage(message.id()))?;
+
+        let size = match V::version() {
+            MavLinkVersion::V1 if message.id() > u8::MAX as MessageId => {
+                return Err(invalid(format!(
+                    "message ID {} can't be sent over MAVLink 1",
+                    message.id()
+                )));
+            }
+            MavLinkVersion::V1 => definition.size_v1(),
+            MavLinkVersion::V2 => definition.size_v2(),
+        };
+
+        let mut payload = vec![0u8; definition.size_v2()];
+        let mut offset = 0;
+        for field in definition.reordered_fields() {
+            let size = field.r#type().size();
+            if let Some(value) = message.get(field.name()) {
+                encode_value(field.r#type(), value, &mut payload[offset..offset + size])
+                    .map_err(|err| invalid(format!("field `{}`: {err}", field.name())))?;
+            }
+            offset += size;
+        }
+        payload.truncate(size);
+
+        Ok(Frame::builder()
+            .sequence(sequence)
+            .system_id(message.system_id())
+            .component_id(message.component_id())
+            .version(V::v())
+            .message_id(message.id())
+            .payload(&payload)
+            .crc_extra(definition.crc_extra())
+            .build())
+    }
+
+    fn describe_field(&self, field: &MessageField, value: FieldValue) -> FieldDescriptor {
+        let mut labels = Vec::new();
+
+        let enm = field.r#enum().and_then(|name| self.enum_by_name(name));
+        if let (Some(enm), Some(raw)) = (enm, value.as_u64()) {
+            if field.bitmask() || enm.bitmask() {
+                for entry in enm.entries() {
+                    let flag = entry.value() as u64;
+                    if flag != 0 && raw & flag == flag {
+                        labels.push(entry.name().to_string());
+                    }
+                }
+            } else if let Some(entry) = enm.entries().iter().find(|e| e.value() as u64 == raw) {
+                labels.push(entry.name().to_string());
+            }
+        }
+
+        FieldDescriptor {
+            name: field.name().to_string(),
+            description: field.description().to_string(),
+            value,
+            units: field.units().map(|units| units.to_str().to_string()),
+            enum_name: field.r#enum().map(str::to_string),
+            labels,
+        }
+    }
+}
+
+impl Debug for MessageDefinitions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageDefinitions")
+            .field("messages", &self.inner.messages.len())
+            .field("enums", &self.inner.enums.len())
+            .finish()
+    }
+}
+
+impl MessageDescriptor {
+    /// Message `ID`.
+    pub fn id(&self) -> MessageId {
+        self.id
+    }
+
+    /// Message name as defined in XML definitions (i.e. `HEARTBEAT`).
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Message description.
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// Message fields in the order of declaration.
+    pub fn fields(&self) -> &[FieldDescriptor] {
+        self.fields.as_slice()
+    }
+
+    /// Returns field by its name.
+    pub fn field(&self, name: &str) -> Option<&FieldDescriptor> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl Display for MessageDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {{ ", self.name)?;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{field}")?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl FieldDescriptor {
+    /// Field name as defined in XML definitions (i.e. `custom_mode`).
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Field description.
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// Decoded field value.
+    pub fn value(&self) -> &FieldValue {
+        &self.value
+    }
+
+    /// Units of measurement (i.e. `m/s`), if specified.
+    pub fn units(&self) -> Option<&str> {
+        self.units.as_deref()
+    }
+
+    /// Name of the enum, that defines field values, if any.
+    pub fn enum_name(&self) -> Option<&str> {
+        self.enum_name.as_deref()
+    }
+
+    /// Enum entry names matching the field value.
+    ///
+    /// For regular enums this contains at most one entry. For bitmasks, all flags set in the value
+    /// are listed.
+    pub fn labels(&self) -> &[String] {
+        self.labels.as_slice()
+    }
+}
+
+impl Display for FieldDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)?;
+        if let Some(units) = &self.units {
+            write!(f, " {units}")?;
+        }
+        if !self.labels.is_empty() {
+            write!(f, " ({})", self.labels.join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FieldValue {
+    /// Returns value as an unsigned integer, if it is a non-negative integer.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            FieldValue::UInt(value) => Some(*value),
+            FieldValue::Int(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns numeric value as a floating point number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::UInt(value) => Some(*value as f64),
+            FieldValue::Int(value) => Some(*value as f64),
+            FieldValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Int(value) => write!(f, "{value}"),
+            FieldValue::UInt(value) => write!(f, "{value}"),
+            FieldValue::Float(value) => write!(f, "{value}"),
+            FieldValue::Text(value) => write!(f, "{value:?}"),
+            FieldValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn decode_value(r#type: &MavType, bytes: &[u8]) -> FieldValue {
+    match r#type {
+        MavType::UInt8 | MavType::UInt8MavlinkVersion => FieldValue::UInt(bytes[0] as u64),
+        MavType::UInt16 => FieldValue::UInt(u16::from_le_bytes([bytes[0], bytes[1]]) as u64),
+        MavType::UInt32 => FieldValue::UInt(u32::from_le_bytes(le_bytes(bytes)) as u64),
+        MavType::UInt64 => FieldValue::UInt(u64::from_le_bytes(le_bytes(bytes))),
+        MavType::Int8 => FieldValue::Int(bytes[0] as i8 as i64),
+        MavType::Int16 => FieldValue::Int(i16::from_le_bytes([bytes[0], bytes[1]]) as i64),
+        MavType::Int32 => FieldValue::Int(i32::from_le_bytes(le_bytes(bytes)) as i64),
+        MavType::Int64 => FieldValue::Int(i64::from_le_bytes(le_bytes(bytes))),
+        MavType::Float => FieldValue::Float(f32::from_le_bytes(le_bytes(bytes)) as f64),
+        MavType::Double => FieldValue::Float(f64::from_le_bytes(le_bytes(bytes))),
+        MavType::Char => FieldValue::Text(text(bytes)),
+        MavType::Array(base, _) => match base.as_ref() {
+            MavType::Char => FieldValue::Text(text(bytes)),
+            base => FieldValue::Array(
+                bytes
+                    .chunks(base.size())
+                    .map(|chunk| decode_value(base, chunk))
+                    .collect(),
+            ),
+        },
+    }
+}
+
+/// Writes field `value` into `bytes` allocated for a field of the specified type.
+fn encode_value(
+    r#type: &MavType,
+    value: &FieldValue,
+    bytes: &mut [u8],
+) -> core::result::Result<(), String> {
+    match (r#type, value) {
+        (MavType::Float, value) => bytes.copy_from_slice(&(float(value)? as f32).to_le_bytes()),
+        (MavType::Double, value) => bytes.copy_from_slice(&float(value)?.to_le_bytes()),
+        (MavType::Char, FieldValue::Text(text)) => encode_text(text, bytes)?,
+        (MavType::Array(base, _), value) if **base == MavType::Char => match value {
+            FieldValue::Text(text) => encode_text(text, bytes)?,
+            value => return Err(format!("{value} is not a text")),
+        },
+        (MavType::Array(base, _), FieldValue::Array(values)) => {
+            let size = base.size();
+            if values.len() * size > bytes.len() {
+                return Err(format!("array has more than {} items", bytes.len() / size));
+            }
+            for (value, chunk) in values.iter().zip(bytes.chunks_mut(size)) {
+                encode_value(base, value, chunk)?;
+            }
+        }
+        (MavType::Array(_, _), value) => return Err(format!("{value} is not an array")),
+        (r#type, value) => encode_integer(r#type, value, bytes)?,
+    }
+    Ok(())
+}
+
+fn encode_integer(
+    r#type: &MavType,
+    value: &FieldValue,
+    bytes: &mut [u8],
+) -> core::result::Result<(), String> {
+    let int = match value {
+        FieldValue::UInt(value) => *value as i128,
+        FieldValue::Int(value) => *value as i128,
+        value => return Err(format!("{value} is not an integer")),
+    };
+    let (min, max) = match r#type {
+        MavType::Int8 => (i8::MIN as i128, i8::MAX as i128),
+        MavType::Int16 => (i16::MIN as i128, i16::MAX as i128),
+        MavType::Int32 => (i32::MIN as i128, i32::MAX as i128),
+        MavType::Int64 => (i64::MIN as i128, i64::MAX as i128),
+        // Unsigned integers and characters
+        _ => (0, (1i128 << (8 * bytes.len())) - 1),
+    };
+    if int < min || int > max {
+        return Err(format!("{int} is out of range"));
+    }
+    // Two's complement representation is truncated to the field size
+    bytes.copy_from_slice(&int.to_le_bytes()[..bytes.len()]);
+    Ok(())
+}
+
+fn encode_text(text: &str, bytes: &mut [u8]) -> core::result::Result<(), String> {
+    if text.len() > bytes.len() {
+        return Err(format!("text is longer than {} bytes", bytes.len()));
+    }
+    bytes[..text.len()].copy_from_slice(text.as_bytes());
+    Ok(())
+}
+
+fn float(value: &FieldValue) -> core::result::Result<f64, String> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{value} is not a number"))
+}
+
+pub(super) fn invalid(message: String) -> Error {
+    DefinitionsError::InvalidMessage(message).into()
+}
+
+fn le_bytes<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut buf = [0u8; N];
+    buf.copy_from_slice(&bytes[..N]);
+    buf
+}
+
+fn text(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).to_string()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod definitions_tests {
+    use mavinspect::protocol::builders::{
+        EnumBuilder, EnumEntryBuilder, MessageBuilder, MessageFieldBuilder,
+    };
+    use mavinspect::protocol::{EnumEntry, Units};
+    use mavinspect::utils::Builder;
+
+    use crate::dialects::minimal::enums::{MavAutopilot, MavModeFlag, MavState, MavType as Type};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::V2;
+
+    use super::*;
+
+    fn field(name: &str, r#type: MavType, r#enum: Option<&str>, bitmask: bool) -> MessageField {
+        MessageFieldBuilder::new()
+            .set_name(name)
+            .set_type(r#type)
+            .set_enum(r#enum.map(str::to_string))
+            .set_bitmask(bitmask)
+            .build()
+    }
+
+    fn entry(value: u32, name: &str) -> EnumEntry {
+        EnumEntryBuilder::new()
+            .set_value(value)
+            .set_name(name)
+            .build()
+    }
+
+    fn definitions() -> MessageDefinitions {
+        let heartbeat = MessageBuilder::new()
+            .set_id(0)
+            .set_name("HEARTBEAT")
+            .set_fields(vec![
+                field("type", MavType::UInt8, Some("MAV_TYPE"), false),
+                field("autopilot", MavType::UInt8, Some("MAV_AUTOPILOT"), false),
+                field("base_mode", MavType::UInt8, Some("MAV_MODE_FLAG"), true),
+                field("custom_mode", MavType::UInt32, None, false),
+                field("system_status", MavType::UInt8, Some("MAV_STATE"), false),
+                field("mavlink_version", MavType::UInt8MavlinkVersion, None, false),
+            ])
+            .build();
+
+        let named_value = MessageBuilder::new()
+            .set_id(251)
+            .set_name("NAMED_VALUE_FLOAT")
+            .set_fields(vec![
+                MessageFieldBuilder::new()
+                    .set_name("time_boot_ms")
+                    .set_type(MavType::UInt32)
+                    .set_units(Some(Units::parse("ms").unwrap()))
+                    .build(),
+                field(
+                    "name",
+                    MavType::Array(Box::new(MavType::Char), 10),
+                    None,
+                    false,
+                ),
+                field("value", MavType::Float, None, false),
+            ])
+            .build();
+
+        let mav_type = EnumBuilder::new()
+            .set_name("MAV_TYPE")
+            .set_entries(&[
+                entry(1, "MAV_TYPE_FIXED_WING"),
+                entry(2, "MAV_TYPE_QUADROTOR"),
+            ])
+            .build();
+        let mav_autopilot = EnumBuilder::new()
+            .set_name("MAV_AUTOPILOT")
+            .set_entries(&[entry(3, "MAV_AUTOPILOT_ARDUPILOTMEGA")])
+            .build();
+        let mav_mode_flag = EnumBuilder::new()
+            .set_name("MAV_MODE_FLAG")
+            .set_bitmask(true)
+            .set_entries(&[
+                entry(128, "MAV_MODE_FLAG_SAFETY_ARMED"),
+                entry(64, "MAV_MODE_FLAG_MANUAL_INPUT_ENABLED"),
+                entry(4, "MAV_MODE_FLAG_AUTO_ENABLED"),
+            ])
+            .build();
+        let mav_state = EnumBuilder::new()
+            .set_name("MAV_STATE")
+            .set_entries(&[entry(4, "MAV_STATE_ACTIVE")])
+            .build();
+
+        MessageDefinitions::new(
+            [heartbeat, named_value],
+            [mav_type, mav_autopilot, mav_mode_flag, mav_state],
+        )
+    }
+
+    fn frame(message: &impl crate::protocol::Message) -> Frame<V2> {
+        Frame::builder()
+            .sequence(0)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .message(message)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn frame_is_described_with_enum_labels() {
+        let definitions = definitions();
+        let frame = frame(&Heartbeat {
+            type_: Type::Quadrotor,
+            autopilot: MavAutopilot::Ardupilotmega,
+            base_mode: MavModeFlag::SAFETY_ARMED | MavModeFlag::AUTO_ENABLED,
+            custom_mode: 42,
+            system_status: MavState::Active,
+            mavlink_version: 3,
+        });
+
+        let descriptor = definitions.describe(&frame).unwrap();
+
+        assert_eq!(descriptor.id(), 0);
+        assert_eq!(descriptor.name(), "HEARTBEAT");
+        assert_eq!(descriptor.fields()[0].name(), "type");
+
+        let type_ = descriptor.field("type").unwrap();
+        assert_eq!(type_.value(), &FieldValue::UInt(2));
+        assert_eq!(type_.enum_name(), Some("MAV_TYPE"));
+        assert_eq!(type_.labels(), &["MAV_TYPE_QUADROTOR".to_string()]);
+
+        let base_mode = descriptor.field("base_mode").unwrap();
+        assert_eq!(base_mode.value(), &FieldValue::UInt(132));
+        assert_eq!(
+            base_mode.labels(),
+            &[
+                "MAV_MODE_FLAG_AUTO_ENABLED".to_string(),
+                "MAV_MODE_FLAG_SAFETY_ARMED".to_string()
+            ]
+        );
+
+        let custom_mode = descriptor.field("custom_mode").unwrap();
+        assert_eq!(custom_mode.value(), &FieldValue::UInt(42));
+        assert!(custom_mode.labels().is_empty());
+
+        assert_eq!(
+            descriptor.field("mavlink_version").unwrap().value(),
+            &FieldValue::UInt(3)
+        );
+    }
+
+    #[test]
+    fn text_and_units_are_described() {
+        let definitions = definitions();
+
+        // Fields are reordered by type size and trailing zeros are truncated
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&1000u32.to_le_bytes());
+        payload.extend_from_slice(&1.5f32.to_le_bytes());
+        payload.extend_from_slice(b"speed");
+        let frame = Frame::builder()
+            .sequence(0)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .message_id(251)
+            .payload(&payload)
+            .crc_extra(170)
+            .build();
+
+        let descriptor = definitions.describe(&frame).unwrap();
+
+        let time = descriptor.field("time_boot_ms").unwrap();
+        assert_eq!(time.value(), &FieldValue::UInt(1000));
+        assert_eq!(time.units(), Some("ms"));
+        assert_eq!(
+            descriptor.field("name").unwrap().value(),
+            &FieldValue::Text("speed".to_string())
+        );
+        assert_eq!(
+            descriptor.field("value").unwrap().value(),
+            &FieldValue::Float(1.5)
+        );
+
+        assert_eq!(
+            descriptor.to_string(),
+            r#"NAMED_VALUE_FLOAT { time_boot_ms: 1000 ms, name: "speed", value: 1.5 }"#
+        );
+    }
+
+    #[test]
+    fn frame_is_decoded_to_json() {
+        let definitions = definitions();
+        let frame = frame(&Heartbeat {
+            type_: Type::FixedWing,
+            autopilot: MavAutopilot::Ardupilotmega,
+            base_mode: MavModeFlag::SAFETY_ARMED,
+            custom_mode: 42,
+            system_status: MavState::Active,
+            mavlink_version: 3,
+        });
+
+        let message = definitions.decode(&frame).unwrap();
+
+        assert_eq!(message.name(), "HEARTBEAT");
+        assert_eq!((message.system_id(), message.component_id()), (1, 1));
+        assert_eq!(message.get("custom_mode"), Some(&FieldValue::UInt(42)));
+        assert_eq!(message.fields().next().unwrap().0, "type");
+
+        assert_eq!(
+            message.to_json(),
+            concat!(
+                r#"{"component_id":1,"fields":{"autopilot":3,"base_mode":128,"custom_mode":42,"#,
+                r#""mavlink_version":3,"system_status":4,"type":1},"message_id":0,"#,
+                r#""name":"HEARTBEAT","system_id":1}"#
+            )
+        );
+
+        let map = message.into_map();
+        assert_eq!(map.get("base_mode"), Some(&FieldValue::UInt(128)));
+    }
+
+    #[test]
+    fn json_is_encoded_to_frame() {
+        let definitions = definitions();
+        let heartbeat = frame(&Heartbeat {
+            type_: Type::Quadrotor,
+            autopilot: MavAutopilot::Ardupilotmega,
+            base_mode: MavModeFlag::SAFETY_ARMED,
+            custom_mode: 42,
+            system_status: MavState::Active,
+            mavlink_version: 3,
+        });
+
+        let message =
+            DynamicMessage::from_json(&definitions.decode(&heartbeat).unwrap().to_json()).unwrap();
+        let encoded = definitions.encode::<V2>(&message, 0).unwrap();
+
+        assert_eq!((encoded.system_id(), encoded.component_id()), (1, 1));
+        assert_eq!(encoded.payload().bytes(), heartbeat.payload().bytes());
+        assert_eq!(encoded.checksum(), heartbeat.checksum());
+        assert_eq!(definitions.encode::<V2>(&message, 7).unwrap().sequence(), 7);
+
+        let message = DynamicMessage::from_json(
+            r#"{"system_id":1,"component_id":1,"message_id":251,"fields":{"name":"speed","value":1.5}}"#,
+        )
+        .unwrap();
+        let descriptor = definitions
+            .describe(&definitions.encode::<V2>(&message, 0).unwrap())
+            .unwrap();
+        assert_eq!(
+            descriptor.field("name").unwrap().value(),
+            &FieldValue::Text("speed".to_string())
+        );
+        assert_eq!(
+            descriptor.field("value").unwrap().value(),
+            &FieldValue::Float(1.5)
+        );
+        assert_eq!(
+            descriptor.field("time_boot_ms").unwrap().value(),
+            &FieldValue::UInt(0)
+        );
+    }
+
+    #[test]
+    fn invalid_json_messages_are_not_encoded() {
+        let definitions = definitions();
+
+        let out_of_range = DynamicMessage::from_json(
+            r#"{"system_id":1,"component_id":1,"message_id":0,"fields":{"type":256}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            definitions.encode::<V2>(&out_of_range, 0),
+            Err(Error::Definitions(DefinitionsError::InvalidMessage(_)))
+        ));
+
+        let unknown = DynamicMessage::from_json(
+            r#"{"system_id":1,"component_id":1,"message_id":42,"fields":{}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            definitions.encode::<V2>(&unknown, 0),
+            Err(Error::Definitions(DefinitionsError::UnknownMessage(42)))
+        ));
+
+        assert!(DynamicMessage::from_json(r#"{"system_id":1}"#).is_err());
+    }
+
+    #[test]
+    fn unknown_messages_are_rejected() {
+        let definitions = MessageDefinitions::default();
+        let frame = frame(&Heartbeat::default());
+
+        assert!(matches!(
+            definitions.describe(&frame),
+            Err(Error::Definitions(DefinitionsError::UnknownMessage(0)))
+        ));
+    }
+}
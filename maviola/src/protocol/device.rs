@@ -109,7 +109,7 @@ impl<V: Versioned> Device<V> {
     /// [`compat_flags`]: Frame::compat_flags
     pub fn next_frame(&self, message: &dyn Message) -> Result<Frame<V>> {
         let mut frame = self.endpoint.next_frame(message)?;
-        self.processor.process_new(&mut frame);
+        self.processor.process_new(&mut frame)?;
         Ok(frame)
     }
 }
@@ -133,7 +133,7 @@ impl Device<Versionless> {
         message: &dyn Message,
     ) -> Result<Frame<Versionless>> {
         let mut frame = self.endpoint.next_frame::<V>(message)?;
-        self.processor.process_new(&mut frame);
+        self.processor.process_new(&mut frame)?;
         Ok(frame)
     }
 }
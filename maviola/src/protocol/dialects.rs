@@ -2,6 +2,8 @@ use std::fmt::{Debug, Formatter};
 
 use crate::dialects::Minimal;
 use crate::error::FrameError;
+#[cfg(feature = "definitions")]
+use crate::protocol::MessageDefinitions;
 use crate::protocol::{DialectSpec, MessageId, MessageInfo};
 
 use crate::prelude::*;
@@ -12,6 +14,8 @@ pub struct KnownDialects {
     main: &'static str,
     dialects: Vec<&'static DialectSpec>,
     allow_unknown: bool,
+    #[cfg(feature = "definitions")]
+    definitions: Option<MessageDefinitions>,
 }
 
 impl KnownDialects {
@@ -22,6 +26,8 @@ impl KnownDialects {
             main: DefaultDialect::name(),
             dialects: vec![Minimal::spec(), DefaultDialect::spec()],
             allow_unknown: false,
+            #[cfg(feature = "definitions")]
+            definitions: None,
         }
     }
 
@@ -76,12 +82,8 @@ impl KnownDialects {
             return self;
         }
 
-        self.dialects = self
-            .dialects
-            .iter()
-            .filter(|&&dialect| dialect.name() != DefaultDialect::name())
-            .copied()
-            .collect();
+        self.dialects
+            .retain(|&dialect| dialect.name() != DefaultDialect::name());
 
         if DefaultDialect::name() == self.main {
             self.main = Minimal::name();
@@ -96,6 +98,14 @@ impl KnownDialects {
         self
     }
 
+    /// <sup>`definitions`</sup>
+    /// Sets message definitions used to describe frames.
+    #[cfg(feature = "definitions")]
+    pub fn with_definitions(mut self, definitions: MessageDefinitions) -> Self {
+        self.definitions = Some(definitions);
+        self
+    }
+
     /// Main dialect specification.
     pub fn main(&self) -> &'static DialectSpec {
         self.get(self.main).unwrap()
@@ -112,6 +122,13 @@ impl KnownDialects {
         self.allow_unknown
     }
 
+    /// <sup>`definitions`</sup>
+    /// Message definitions used to describe frames, if set.
+    #[cfg(feature = "definitions")]
+    pub fn definitions(&self) -> Option<&MessageDefinitions> {
+        self.definitions.as_ref()
+    }
+
     /// Returns `true`, if dialect specification with provided `name` is among the known dialects.
     pub fn contains(&self, name: &str) -> bool {
         for &dialect in &self.dialects {
@@ -203,6 +220,11 @@ impl KnownDialects {
                 self.dialects.push(dialect)
             }
         }
+
+        #[cfg(feature = "definitions")]
+        if self.definitions.is_none() {
+            self.definitions = other.definitions.clone();
+        }
     }
 }
 
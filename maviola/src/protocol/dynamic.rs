@@ -0,0 +1,216 @@
+//! Dynamic MAVLink messages decoded at runtime.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Number, Value};
+
+use crate::protocol::definitions::invalid;
+use crate::protocol::{ComponentId, FieldValue, MessageDescriptor, MessageId, SystemId};
+
+use crate::prelude::*;
+
+/// <sup>`definitions`</sup>
+/// MAVLink message decoded at runtime according to message definitions.
+///
+/// Unlike messages of generated dialects, dynamic messages are plain maps from field names to
+/// [`FieldValue`]s. This allows generic inspectors and bridges to handle messages, that are not
+/// known at compile time. Fields are kept in the order of declaration in the XML definition.
+///
+/// Created by [`MessageDefinitions::decode`](crate::protocol::MessageDefinitions::decode) or
+/// [`Node::decode_dynamic`](crate::core::node::Node::decode_dynamic). Use
+/// [`MessageDefinitions::describe`](crate::protocol::MessageDefinitions::describe) to get units
+/// and enum labels as well.
+///
+/// Dynamic messages can be serialized to JSON objects with sorted keys:
+///
+/// ```json
+/// {"component_id":1,"fields":{"autopilot":3,...,"type":2},"message_id":0,"name":"HEARTBEAT","system_id":1}
+/// ```
+///
+/// Character arrays are serialized as strings and other arrays as JSON arrays. Non-finite floating
+/// point values, that can't be represented in JSON, are serialized as `null`.
+///
+/// Messages parsed from JSON by [`DynamicMessage::from_json`] can be encoded back into frames by
+/// [`MessageDefinitions::encode`](crate::protocol::MessageDefinitions::encode).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicMessage {
+    system_id: SystemId,
+    component_id: ComponentId,
+    id: MessageId,
+    name: String,
+    fields: Vec<(String, FieldValue)>,
+}
+
+impl DynamicMessage {
+    /// Creates a dynamic message from a `frame` and its `descriptor`.
+    pub fn new<V: MaybeVersioned>(frame: &Frame<V>, descriptor: MessageDescriptor) -> Self {
+        Self {
+            system_id: frame.system_id(),
+            component_id: frame.component_id(),
+            id: descriptor.id(),
+            name: descriptor.name().to_string(),
+            fields: descriptor
+                .fields()
+                .iter()
+                .map(|field| (field.name().to_string(), field.value().clone()))
+                .collect(),
+        }
+    }
+
+    /// System `ID` of the sender.
+    pub fn system_id(&self) -> SystemId {
+        self.system_id
+    }
+
+    /// Component `ID` of the sender.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Message `ID`.
+    pub fn id(&self) -> MessageId {
+        self.id
+    }
+
+    /// Message name as defined in XML definitions (i.e. `HEARTBEAT`).
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Iterates over field names and values in the order of declaration.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns value of a field by its name.
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Converts message into a map from field names to values.
+    pub fn into_map(self) -> HashMap<String, FieldValue> {
+        self.fields.into_iter().collect()
+    }
+
+    /// Converts message into a JSON value.
+    pub fn to_json_value(&self) -> Value {
+        let fields: Map<String, Value> = self
+            .fields
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_json_value()))
+            .collect();
+
+        let mut message = Map::new();
+        message.insert("system_id".into(), self.system_id.into());
+        message.insert("component_id".into(), self.component_id.into());
+        message.insert("message_id".into(), self.id.into());
+        message.insert("name".into(), self.name.clone().into());
+        message.insert("fields".into(), Value::Object(fields));
+        Value::Object(message)
+    }
+
+    /// Serializes message to a JSON string.
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    /// Parses message from a JSON value in the format produced by [`Self::to_json_value`].
+    ///
+    /// Message name is optional. Returns
+    /// [`DefinitionsError::InvalidMessage`](crate::error::DefinitionsError::InvalidMessage) if
+    /// value is not a valid message object.
+    pub fn from_json_value(value: &Value) -> Result<Self> {
+        let header = |name: &str| {
+            value
+                .get(name)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| invalid(format!("`{name}` is missing or invalid")))
+        };
+        let id = |name: &str| {
+            header(name).and_then(|id| {
+                u8::try_from(id).map_err(|_| invalid(format!("`{name}` is out of range")))
+            })
+        };
+
+        let fields = value
+            .get("fields")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid("`fields` is missing or invalid".into()))?
+            .iter()
+            .map(|(name, value)| {
+                FieldValue::from_json_value(value)
+                    .map(|value| (name.clone(), value))
+                    .ok_or_else(|| invalid(format!("field `{name}` has invalid value {value}")))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            system_id: id("system_id")?,
+            component_id: id("component_id")?,
+            id: MessageId::try_from(header("message_id")?)
+                .map_err(|_| invalid("`message_id` is out of range".into()))?,
+            name: value
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            fields,
+        })
+    }
+
+    /// Parses message from a JSON string in the format produced by [`Self::to_json`].
+    ///
+    /// See [`Self::from_json_value`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| invalid(format!("invalid JSON: {err}")))?;
+        Self::from_json_value(&value)
+    }
+}
+
+impl FieldValue {
+    /// Converts field value into a JSON value.
+    ///
+    /// Non-finite floating point numbers are converted to [`Value::Null`].
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            FieldValue::Int(value) => (*value).into(),
+            FieldValue::UInt(value) => (*value).into(),
+            FieldValue::Float(value) => Number::from_f64(*value)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            FieldValue::Text(value) => value.clone().into(),
+            FieldValue::Array(values) => {
+                Value::Array(values.iter().map(FieldValue::to_json_value).collect())
+            }
+        }
+    }
+
+    /// Converts JSON value into a field value.
+    ///
+    /// This is the reverse of [`Self::to_json_value`], `null` is converted to `NaN`. Returns
+    /// `None` for booleans, objects, and arrays with such values.
+    pub fn from_json_value(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::Null => FieldValue::Float(f64::NAN),
+            Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                (Some(value), _) => FieldValue::UInt(value),
+                (None, Some(value)) => FieldValue::Int(value),
+                (None, None) => FieldValue::Float(number.as_f64()?),
+            },
+            Value::String(value) => FieldValue::Text(value.clone()),
+            Value::Array(values) => FieldValue::Array(
+                values
+                    .iter()
+                    .map(FieldValue::from_json_value)
+                    .collect::<Option<_>>()?,
+            ),
+            Value::Bool(_) | Value::Object(_) => return None,
+        })
+    }
+}
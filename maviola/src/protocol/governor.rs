@@ -0,0 +1,203 @@
+//! Receive-side rate limiting of incoming messages.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use crate::protocol::MessageId;
+
+use crate::prelude::*;
+
+/// Governor, that enforces maximum rates of incoming messages.
+///
+/// Rate governor drops incoming messages of a particular `ID`, when a peer sends them faster than
+/// the configured maximum rate. Dropped frames won't reach node subscribers. This protects
+/// applications from runaway publishers, like a misconfigured camera that spams its status at
+/// 200 Hz.
+///
+/// Rates are enforced per peer (system and component `ID`) and message `ID`. Each peer may send
+/// bursts of up to one second worth of messages, after that messages are passed with the
+/// configured rate.
+///
+/// Heartbeats are still used to track peers, even if they were dropped by the governor. Both
+/// passed and dropped messages are counted, use [`RateGovernor::passed`] and
+/// [`RateGovernor::dropped`] to access counters. Clones of the governor share counters, so you
+/// can keep a clone to inspect them while node is running.
+///
+/// Set governor with [`NodeBuilder::rate_governor`](crate::core::node::NodeBuilder::rate_governor).
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::protocol::{MessageId, RateGovernor};
+///
+/// const CAMERA_CAPTURE_STATUS: MessageId = 262;
+///
+/// // Accept at most 5 camera capture statuses per second from each peer
+/// let governor = RateGovernor::new()
+///     .with_limit(CAMERA_CAPTURE_STATUS, 5.0);
+///
+/// assert_eq!(governor.limit(CAMERA_CAPTURE_STATUS), Some(5.0));
+/// ```
+#[derive(Clone, Default)]
+pub struct RateGovernor {
+    limits: HashMap<MessageId, f64>,
+    counters: Arc<RwLock<HashMap<MessageId, RateCounters>>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct RateCounters {
+    passed: u64,
+    dropped: u64,
+}
+
+impl RateGovernor {
+    /// Creates a rate governor without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets maximum rate in Hz for incoming messages with specified `message_id`.
+    ///
+    /// Non-positive `max_rate` means that all messages with this `ID` will be dropped.
+    pub fn with_limit(mut self, message_id: MessageId, max_rate: f64) -> Self {
+        self.limits.insert(message_id, max_rate);
+        self
+    }
+
+    /// Maximum rate in Hz for messages with specified `message_id`.
+    ///
+    /// Returns [`None`] if messages with this `ID` are not limited.
+    pub fn limit(&self, message_id: MessageId) -> Option<f64> {
+        self.limits.get(&message_id).copied()
+    }
+
+    /// Number of governed messages with specified `message_id` passed to subscribers.
+    ///
+    /// Messages without limits are not counted.
+    pub fn passed(&self, message_id: MessageId) -> u64 {
+        self.counters(message_id).passed
+    }
+
+    /// Number of messages with specified `message_id` dropped due to exceeded rate.
+    pub fn dropped(&self, message_id: MessageId) -> u64 {
+        self.counters(message_id).dropped
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that applies this governor to incoming frames.
+    pub(crate) fn tracker(&self) -> RateTracker {
+        RateTracker {
+            governor: self.clone(),
+            buckets: Default::default(),
+        }
+    }
+
+    fn counters(&self, message_id: MessageId) -> RateCounters {
+        match self.counters.read() {
+            Ok(counters) => counters.get(&message_id).copied().unwrap_or_default(),
+            Err(_) => RateCounters::default(),
+        }
+    }
+
+    fn count(&self, message_id: MessageId, passed: bool) {
+        if let Ok(mut counters) = self.counters.write() {
+            let counters = counters.entry(message_id).or_default();
+            if passed {
+                counters.passed += 1;
+            } else {
+                counters.dropped += 1;
+            }
+        }
+    }
+}
+
+impl Debug for RateGovernor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateGovernor")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+/// <sup>⛔</sup>
+/// Keeps track of incoming message rates for [`RateGovernor`].
+pub(crate) struct RateTracker {
+    governor: RateGovernor,
+    buckets: HashMap<(MavLinkId, MessageId), TokenBucket>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateTracker {
+    /// Returns `true` if frame with `message_id` from peer with specified `id` received at `now`
+    /// should be passed to subscribers.
+    pub(crate) fn allow(&mut self, id: MavLinkId, message_id: MessageId, now: Instant) -> bool {
+        let max_rate = match self.governor.limit(message_id) {
+            Some(max_rate) => max_rate,
+            None => return true,
+        };
+
+        let allowed = max_rate > 0.0 && {
+            let capacity = max_rate.max(1.0);
+            let bucket = self.buckets.entry((id, message_id)).or_insert(TokenBucket {
+                tokens: capacity,
+                updated_at: now,
+            });
+
+            let elapsed = now.saturating_duration_since(bucket.updated_at);
+            bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * max_rate).min(capacity);
+            bucket.updated_at = now;
+
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                true
+            } else {
+                false
+            }
+        };
+
+        self.governor.count(message_id, allowed);
+        allowed
+    }
+}
+
+#[cfg(test)]
+mod governor_tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn rate_is_enforced_per_peer_and_message() {
+        let governor = RateGovernor::new().with_limit(1, 2.0).with_limit(2, 0.0);
+        let mut tracker = governor.tracker();
+        let (peer_1, peer_2) = (MavLinkId::new(1, 1), MavLinkId::new(2, 1));
+        let start = Instant::now();
+
+        // Burst of up to one second worth of messages is allowed
+        assert!(tracker.allow(peer_1, 1, start));
+        assert!(tracker.allow(peer_1, 1, start));
+        assert!(!tracker.allow(peer_1, 1, start));
+
+        // Other peers and unlimited messages are not affected
+        assert!(tracker.allow(peer_2, 1, start));
+        assert!(tracker.allow(peer_1, 0, start));
+
+        // Tokens are restored with the configured rate
+        assert!(tracker.allow(peer_1, 1, start + Duration::from_millis(500)));
+        assert!(!tracker.allow(peer_1, 1, start + Duration::from_millis(600)));
+
+        // Non-positive rate drops everything
+        assert!(!tracker.allow(peer_1, 2, start));
+
+        assert_eq!(governor.passed(1), 4);
+        assert_eq!(governor.dropped(1), 2);
+        assert_eq!(governor.dropped(2), 1);
+        assert_eq!(governor.passed(0), 0);
+    }
+}
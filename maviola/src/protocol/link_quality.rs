@@ -0,0 +1,353 @@
+//! MAVLink peer link quality monitoring.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::core::consts::{DEFAULT_LINK_QUALITY_WINDOW, DEFAULT_MAX_LINK_LOSS};
+use crate::protocol::Sequence;
+
+use crate::prelude::*;
+
+/// Number of heartbeat intervals used to estimate jitter.
+const JITTER_INTERVALS: usize = 10;
+
+/// Sequence gaps larger than this are considered to be duplicates, reordered frames, or restarts of
+/// the peer rather than losses.
+const MAX_SEQUENCE_GAP: u8 = 128;
+
+/// Monitor of link quality to MAVLink peers.
+///
+/// Link quality monitor tracks sequence numbers of frames received from each peer and arrival times
+/// of peer heartbeats. The resulting [`LinkQuality`] contains the share of frames lost over the last
+/// [`LinkQualityMonitor::window`] received frames and heartbeat arrival jitter.
+///
+/// Current link quality is available as
+/// [`Peer::link_quality`](crate::protocol::Peer::link_quality) of the items returned by
+/// `node.peers()`. Peer quality is refreshed upon each heartbeat. When link quality drops below
+/// the threshold, node emits `Event::PeerDegraded` once per degradation episode. If quality returns
+/// to normal, the next degradation will be reported again.
+///
+/// Set monitor with [`NodeBuilder::link_quality`](crate::core::node::NodeBuilder::link_quality).
+///
+/// # Usage
+///
+/// ```rust
+/// use std::time::Duration;
+/// use maviola::protocol::LinkQualityMonitor;
+///
+/// let monitor = LinkQualityMonitor::new()
+///     // Estimate losses over the last 50 frames
+///     .with_window(50)
+///     // Link with more than 5% losses is degraded
+///     .with_max_loss(5.0)
+///     // Heartbeat jitter above 200 ms is also a degradation
+///     .with_max_jitter(Duration::from_millis(200));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LinkQualityMonitor {
+    window: usize,
+    max_loss: f32,
+    max_jitter: Option<Duration>,
+}
+
+/// Quality of a link to a MAVLink peer measured by [`LinkQualityMonitor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LinkQuality {
+    received: usize,
+    lost: usize,
+    jitter: Duration,
+}
+
+impl LinkQualityMonitor {
+    /// Creates a link quality monitor with default settings.
+    ///
+    /// By default, losses are estimated over the last [`DEFAULT_LINK_QUALITY_WINDOW`] frames, a link
+    /// is degraded, when it loses more than [`DEFAULT_MAX_LINK_LOSS`] percent of frames, and
+    /// heartbeat jitter is not taken into account.
+    pub fn new() -> Self {
+        Self {
+            window: DEFAULT_LINK_QUALITY_WINDOW,
+            max_loss: DEFAULT_MAX_LINK_LOSS,
+            max_jitter: None,
+        }
+    }
+
+    /// Sets the number of the last received frames used to estimate losses.
+    ///
+    /// Window always contains at least one frame.
+    pub fn with_window(mut self, frames: usize) -> Self {
+        self.window = frames.max(1);
+        self
+    }
+
+    /// Sets maximum loss percentage, above which a link is considered to be degraded.
+    pub fn with_max_loss(mut self, percent: f32) -> Self {
+        self.max_loss = percent;
+        self
+    }
+
+    /// Sets maximum heartbeat jitter, above which a link is considered to be degraded.
+    pub fn with_max_jitter(mut self, jitter: Duration) -> Self {
+        self.max_jitter = Some(jitter);
+        self
+    }
+
+    /// Number of the last received frames used to estimate losses.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Maximum loss percentage, above which a link is considered to be degraded.
+    pub fn max_loss(&self) -> f32 {
+        self.max_loss
+    }
+
+    /// Maximum heartbeat jitter, above which a link is considered to be degraded.
+    ///
+    /// [`None`] means, that jitter is not taken into account.
+    pub fn max_jitter(&self) -> Option<Duration> {
+        self.max_jitter
+    }
+
+    /// Returns `true` if link of the specified `quality` is degraded.
+    ///
+    /// Losses are judged only when the whole [`LinkQualityMonitor::window`] is collected.
+    pub fn is_degraded(&self, quality: &LinkQuality) -> bool {
+        let is_lossy = quality.received >= self.window && quality.loss() > self.max_loss;
+        let is_jittery = match self.max_jitter {
+            Some(max_jitter) => quality.jitter > max_jitter,
+            None => false,
+        };
+        is_lossy || is_jittery
+    }
+
+    /// <sup>⛔</sup>
+    /// Creates a stateful tracker, that applies this monitor to incoming frames.
+    pub(crate) fn tracker(&self) -> LinkQualityTracker {
+        LinkQualityTracker {
+            monitor: self.clone(),
+            links: Default::default(),
+            degraded: Default::default(),
+        }
+    }
+}
+
+impl Default for LinkQualityMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkQuality {
+    /// Number of received frames within the window.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Number of frames lost within the window, as estimated from sequence gaps.
+    pub fn lost(&self) -> usize {
+        self.lost
+    }
+
+    /// Percentage of lost frames within the window (from `0.0` to `100.0`).
+    pub fn loss(&self) -> f32 {
+        let total = self.received + self.lost;
+        if total == 0 {
+            return 0.0;
+        }
+        self.lost as f32 * 100.0 / total as f32
+    }
+
+    /// Mean deviation of heartbeat intervals from their average.
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+}
+
+/// <sup>⛔</sup>
+/// Keeps track of link quality for [`LinkQualityMonitor`].
+pub(crate) struct LinkQualityTracker {
+    monitor: LinkQualityMonitor,
+    links: HashMap<MavLinkId, Link>,
+    degraded: HashSet<MavLinkId>,
+}
+
+#[derive(Default)]
+struct Link {
+    next_sequence: Option<Sequence>,
+    gaps: VecDeque<usize>,
+    lost: usize,
+    last_heartbeat: Option<Instant>,
+    intervals: VecDeque<Duration>,
+}
+
+impl LinkQualityTracker {
+    /// Records a frame received from a peer.
+    pub(crate) fn record<V: MaybeVersioned>(&mut self, frame: &Frame<V>) {
+        let id = MavLinkId::new(frame.system_id(), frame.component_id());
+        let link = self.links.entry(id).or_default();
+
+        let gap = match link.next_sequence {
+            Some(expected) => {
+                let gap = frame.sequence().wrapping_sub(expected);
+                if gap < MAX_SEQUENCE_GAP {
+                    gap as usize
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        };
+        link.next_sequence = Some(frame.sequence().wrapping_add(1));
+
+        link.gaps.push_back(gap);
+        link.lost += gap;
+        while link.gaps.len() > self.monitor.window {
+            if let Some(gap) = link.gaps.pop_front() {
+                link.lost -= gap;
+            }
+        }
+    }
+
+    /// Records a heartbeat received from a peer with specified `id` at `received_at`.
+    ///
+    /// Returns current link quality and whether a new degradation episode has started.
+    pub(crate) fn heartbeat(&mut self, id: MavLinkId, received_at: Instant) -> (LinkQuality, bool) {
+        let link = self.links.entry(id).or_default();
+
+        if let Some(last) = link.last_heartbeat {
+            link.intervals
+                .push_back(received_at.saturating_duration_since(last));
+            if link.intervals.len() > JITTER_INTERVALS {
+                link.intervals.pop_front();
+            }
+        }
+        link.last_heartbeat = Some(received_at);
+
+        let quality = link.quality();
+        if !self.monitor.is_degraded(&quality) {
+            self.degraded.remove(&id);
+            return (quality, false);
+        }
+        (quality, self.degraded.insert(id))
+    }
+}
+
+impl Link {
+    fn quality(&self) -> LinkQuality {
+        LinkQuality {
+            received: self.gaps.len(),
+            lost: self.lost,
+            jitter: self.jitter(),
+        }
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.intervals.len() < 2 {
+            return Duration::ZERO;
+        }
+
+        let count = self.intervals.len() as u32;
+        let mean = self.intervals.iter().sum::<Duration>() / count;
+        let deviation: Duration = self
+            .intervals
+            .iter()
+            .map(|&interval| interval.abs_diff(mean))
+            .sum();
+        deviation / count
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod link_quality_tests {
+    use super::*;
+
+    const PEER_ID: MavLinkId = MavLinkId {
+        system: 1,
+        component: 1,
+    };
+
+    fn frame(sequence: Sequence) -> Frame<V2> {
+        Frame::builder()
+            .sequence(sequence)
+            .system_id(PEER_ID.system)
+            .component_id(PEER_ID.component)
+            .version(V2)
+            .message_id(0)
+            .payload(&[0; 9])
+            .crc_extra(50)
+            .build()
+    }
+
+    #[test]
+    fn losses_are_estimated_from_sequence_gaps() {
+        let mut tracker = LinkQualityMonitor::new()
+            .with_window(10)
+            .with_max_loss(20.0)
+            .tracker();
+        let now = Instant::now();
+
+        // Every other frame is lost, sequence wraps around
+        for sequence in (240..=254).step_by(2).chain((0..12).step_by(2)) {
+            tracker.record(&frame(sequence));
+        }
+
+        let (quality, is_degraded) = tracker.heartbeat(PEER_ID, now);
+        assert_eq!(quality.received(), 10);
+        assert_eq!(quality.lost(), 10);
+        assert_eq!(quality.loss(), 50.0);
+        assert!(is_degraded);
+
+        // Degradation is reported once per episode
+        let (_, is_degraded) = tracker.heartbeat(PEER_ID, now);
+        assert!(!is_degraded);
+
+        // Link recovers once losses leave the window
+        for sequence in 11..21 {
+            tracker.record(&frame(sequence));
+        }
+        let (quality, is_degraded) = tracker.heartbeat(PEER_ID, now);
+        assert_eq!(quality.loss(), 0.0);
+        assert!(!is_degraded);
+
+        // Duplicates and restarts are not counted as losses
+        tracker.record(&frame(20));
+        tracker.record(&frame(200));
+        let (quality, _) = tracker.heartbeat(PEER_ID, now);
+        assert_eq!(quality.lost(), 0);
+    }
+
+    #[test]
+    fn losses_are_judged_on_full_window() {
+        let mut tracker = LinkQualityMonitor::new().with_window(10).tracker();
+
+        tracker.record(&frame(0));
+        tracker.record(&frame(5));
+
+        let (quality, is_degraded) = tracker.heartbeat(PEER_ID, Instant::now());
+        assert_eq!(quality.lost(), 4);
+        assert!(!is_degraded);
+    }
+
+    #[test]
+    fn heartbeat_jitter() {
+        let mut tracker = LinkQualityMonitor::new()
+            .with_max_jitter(Duration::from_millis(100))
+            .tracker();
+        let start = Instant::now();
+
+        for (millis, expected) in [(0, false), (1000, false), (2000, false), (3400, true)] {
+            let (_, is_degraded) =
+                tracker.heartbeat(PEER_ID, start + Duration::from_millis(millis));
+            assert_eq!(is_degraded, expected);
+        }
+
+        let (quality, _) = tracker.heartbeat(PEER_ID, start + Duration::from_millis(3400));
+        // Intervals are 1000, 1000, 1400, 0 ms with mean of 850 ms
+        assert_eq!(quality.jitter(), Duration::from_millis(425));
+    }
+}
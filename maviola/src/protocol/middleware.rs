@@ -0,0 +1,161 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use crate::error::FrameError;
+use crate::protocol::{Frame, MavFrame, MaybeVersioned};
+
+#[cfg(doc)]
+use crate::protocol::{CompatProcessor, FrameProcessor, FrameSigner};
+
+/// Stage of a frame processing pipeline.
+///
+/// Unlike custom processors available under the `unsafe` feature, middleware has no low-level
+/// access to frame internals. It may inspect frames, replace them with new ones, or reject them
+/// by returning an error. Rejected frames are dropped by the node.
+///
+/// Middleware is added to a node with
+/// [`NodeBuilder::add_middleware`](crate::core::node::NodeBuilder::add_middleware) at a specific
+/// [`MiddlewarePosition`] relative to built-in stages.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::protocol::{FrameDirection, FrameMiddleware, MavFrame};
+/// use maviola::error::FrameError;
+///
+/// /// Counts frames passing through the pipeline.
+/// #[derive(Debug, Default)]
+/// struct FrameCounter {
+///     incoming: usize,
+///     outgoing: usize,
+/// }
+///
+/// impl FrameMiddleware for FrameCounter {
+///     fn process(
+///         &mut self,
+///         _: &mut MavFrame,
+///         direction: FrameDirection,
+///     ) -> Result<(), FrameError> {
+///         match direction {
+///             FrameDirection::Incoming => self.incoming += 1,
+///             FrameDirection::Outgoing => self.outgoing += 1,
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait FrameMiddleware: Debug + Send + Sync {
+    /// Processes a frame travelling in the specified direction.
+    fn process(
+        &mut self,
+        frame: &mut MavFrame,
+        direction: FrameDirection,
+    ) -> Result<(), FrameError>;
+}
+
+/// Direction of a processed frame.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FrameDirection {
+    /// Frame received from a connection.
+    Incoming,
+    /// Frame sent to a connection.
+    Outgoing,
+}
+
+/// Built-in stage of a [`FrameProcessor`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BuiltinStage {
+    /// Compatibility checks performed by [`CompatProcessor`].
+    Compat,
+    /// Message signing performed by [`FrameSigner`].
+    Signer,
+}
+
+/// Position of a [`FrameMiddleware`] within a frame processing pipeline.
+///
+/// Positions are defined relative to built-in stages, so middleware stays in place when the
+/// [`ProcessingOrder`](crate::protocol::ProcessingOrder) of these stages is changed. Middleware
+/// added at the same position is applied in the order it was added.
+///
+/// Middleware is applied even if the corresponding built-in stage is not configured.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MiddlewarePosition {
+    /// Before all other stages including custom processors.
+    First,
+    /// Right before the specified built-in stage.
+    Before(BuiltinStage),
+    /// Right after the specified built-in stage.
+    After(BuiltinStage),
+    /// After all other stages including custom processors.
+    Last,
+}
+
+/// Ordered chain of [`FrameMiddleware`].
+#[derive(Clone, Debug, Default)]
+pub struct MiddlewareChain {
+    inner: Vec<(MiddlewarePosition, Arc<Mutex<dyn FrameMiddleware>>)>,
+}
+
+impl MiddlewareChain {
+    /// Returns `true` if chain has no middleware.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Adds middleware at the specified position.
+    pub fn add(
+        &mut self,
+        position: MiddlewarePosition,
+        middleware: impl FrameMiddleware + 'static,
+    ) {
+        self.inner
+            .push((position, Arc::new(Mutex::new(middleware))));
+    }
+
+    /// Applies all middleware at the specified position to a frame.
+    pub fn process<V: MaybeVersioned>(
+        &self,
+        frame: &mut Frame<V>,
+        position: MiddlewarePosition,
+        direction: FrameDirection,
+    ) -> Result<(), FrameError> {
+        if !self.inner.iter().any(|(pos, _)| *pos == position) {
+            return Ok(());
+        }
+
+        let mut mav_frame = frame.clone().into_mav_frame();
+
+        for (_, middleware) in self.inner.iter().filter(|(pos, _)| *pos == position) {
+            let mut middleware = match middleware.lock() {
+                Ok(middleware) => middleware,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            middleware.process(&mut mav_frame, direction)?;
+        }
+
+        *frame = match mav_frame.try_into_versioned() {
+            Ok(frame) => frame,
+            Err(err) => {
+                log::error!(
+                    "[frame processor] invalid output from middleware at {position:?}: {err:?}"
+                );
+                return Err(FrameError::from(err));
+            }
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn extend(&mut self, other: &Self) {
+        for (position, middleware) in &other.inner {
+            let exists = self
+                .inner
+                .iter()
+                .any(|(_, existing)| Arc::ptr_eq(existing, middleware));
+            if !exists {
+                self.inner.push((*position, middleware.clone()));
+            }
+        }
+    }
+}
@@ -8,26 +8,83 @@
 //! If `derive` feature is enabled, we also import derive macros from
 //! [`MAVSpec`](https://crates.io/crates/mavspec). These macros are marked with
 //! <sup>[`mavspec`](https://crates.io/crates/mavspec)</sup>.
+//!
+//! If `definitions` feature is enabled, [`MessageDefinitions`] allow to describe frames based on
+//! MAVLink XML definitions parsed by [MAVInspect](https://crates.io/crates/mavinspect) and decode
+//! them into [`DynamicMessage`]s, that can be serialized to JSON. Related entities are
+//! re-exported in [`inspect`] and marked with
+//! <sup>[`mavinspect`](https://crates.io/crates/mavinspect)</sup>.
+
+// Links to entities behind disabled features lead to `docs.rs`.
+#![cfg_attr(
+    feature = "definitions",
+    doc = "",
+    doc = "[`MessageDefinitions`]: crate::protocol::MessageDefinitions",
+    doc = "[`DynamicMessage`]: crate::protocol::DynamicMessage",
+    doc = "[`inspect`]: crate::protocol::inspect"
+)]
+#![cfg_attr(
+    not(feature = "definitions"),
+    doc = "",
+    doc = "[`MessageDefinitions`]: https://docs.rs/maviola/latest/maviola/protocol/struct.MessageDefinitions.html",
+    doc = "[`DynamicMessage`]: https://docs.rs/maviola/latest/maviola/protocol/struct.DynamicMessage.html",
+    doc = "[`inspect`]: https://docs.rs/maviola/latest/maviola/protocol/inspect/index.html"
+)]
 
+mod anomaly;
 pub mod consts;
 #[cfg(feature = "unsafe")]
 mod custom;
+#[cfg(feature = "definitions")]
+mod definitions;
 mod device;
 mod dialects;
+#[cfg(feature = "definitions")]
+mod dynamic;
+mod governor;
+mod link_quality;
+mod middleware;
 mod peer;
 mod processor;
+mod remap;
 mod signature;
+mod system;
+mod targets;
+mod timestamp_store;
 
+pub use anomaly::{Anomaly, AnomalyDetector};
 pub use device::{Device, DeviceId};
 pub use dialects::KnownDialects;
+pub use governor::RateGovernor;
+pub use link_quality::{LinkQuality, LinkQualityMonitor};
+pub use middleware::{
+    BuiltinStage, FrameDirection, FrameMiddleware, MiddlewareChain, MiddlewarePosition,
+};
 pub use peer::Peer;
-pub use processor::FrameProcessor;
+pub use processor::{FrameProcessor, ProcessingOrder, StageOrder};
+pub use remap::IdRemapper;
 pub use signature::{
-    FrameSigner, FrameSignerBuilder, IntoFrameSigner, SignStrategy, UniqueMavTimestamp,
+    FrameSigner, FrameSignerBuilder, IntoFrameSigner, SignStrategy, SignatureRejection,
+    SignerGuard, SignerHandle, SignerStats, UniqueMavTimestamp,
 };
+pub use system::{ComponentKind, RemoteComponent, RemoteSystem};
+pub use targets::TargetFields;
+pub use timestamp_store::{FileTimestampStore, TimestampStore};
+
+pub(crate) use anomaly::AnomalyTracker;
+pub(crate) use governor::RateTracker;
+pub(crate) use link_quality::LinkQualityTracker;
+#[cfg(feature = "fuzz")]
+pub(crate) use processor::FrameProcessorBuilder;
+pub(crate) use system::SystemRegistry;
+pub(crate) use targets::{readdress, AddressedMessage};
 
 #[cfg(feature = "unsafe")]
 pub use custom::{CustomFrameProcessors, ProcessFrame, ProcessFrameCase};
+#[cfg(feature = "definitions")]
+pub use definitions::{FieldDescriptor, FieldValue, MessageDefinitions, MessageDescriptor};
+#[cfg(feature = "definitions")]
+pub use dynamic::DynamicMessage;
 #[cfg(not(feature = "unsafe"))]
 #[derive(Clone, Debug, Default)]
 pub(crate) struct CustomFrameProcessors;
@@ -56,3 +113,23 @@ pub mod derive {
     #[doc(inline)]
     pub use mavspec::rust::derive::{Dialect, Enum, Message};
 }
+
+/// <sup>[`mavinspect`](https://crates.io/crates/mavinspect)</sup>
+///
+/// # MAVLink XML definitions from [MAVInspect](https://crates.io/crates/mavinspect)
+///
+/// Entities used to load and construct [`MessageDefinitions`].
+///
+/// ---
+#[cfg(feature = "definitions")]
+pub mod inspect {
+    /// <sup>[`mavinspect`](https://crates.io/crates/mavinspect)</sup>
+    #[doc(inline)]
+    pub use mavinspect::protocol::{
+        Dialect, Enum, EnumEntry, MavType, Message, MessageField, Protocol, Units,
+    };
+
+    /// <sup>[`mavinspect`](https://crates.io/crates/mavinspect)</sup>
+    #[doc(inline)]
+    pub use mavinspect::Inspector;
+}
@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::time::SystemTime;
 
-use crate::protocol::{ComponentId, MavLinkId, SystemId};
+use crate::protocol::{ComponentId, LinkQuality, MavLinkId, SystemId};
 
 /// MAVLink device with [`system_id`](Peer::system_id) and [`component_id`](Peer::component_id).
 ///
@@ -12,6 +12,7 @@ use crate::protocol::{ComponentId, MavLinkId, SystemId};
 pub struct Peer {
     pub(crate) id: MavLinkId,
     pub(crate) last_active: SystemTime,
+    pub(crate) link_quality: Option<LinkQuality>,
 }
 
 impl Peer {
@@ -23,6 +24,7 @@ impl Peer {
                 component: component_id,
             },
             last_active: SystemTime::now(),
+            link_quality: None,
         }
     }
 
@@ -43,6 +45,16 @@ impl Peer {
     pub fn last_active(&self) -> SystemTime {
         self.last_active
     }
+
+    /// Quality of the link to this peer.
+    ///
+    /// Available only when node has
+    /// [`NodeConf::link_quality`](crate::core::node::NodeConf::link_quality) monitor. Updated upon
+    /// each heartbeat of the peer.
+    #[inline]
+    pub fn link_quality(&self) -> Option<&LinkQuality> {
+        self.link_quality.as_ref()
+    }
 }
 
 impl PartialEq for Peer {
@@ -80,6 +92,7 @@ impl From<MavLinkId> for Peer {
         Self {
             id: value,
             last_active: SystemTime::now(),
+            link_quality: None,
         }
     }
 }
@@ -90,6 +103,7 @@ impl From<&MavLinkId> for Peer {
         Self {
             id: *value,
             last_active: SystemTime::now(),
+            link_quality: None,
         }
     }
 }
@@ -108,6 +122,7 @@ mod peer_tests {
                 component: 17,
             },
             last_active: UNIX_EPOCH,
+            link_quality: None,
         };
 
         let peer_1_new = Peer {
@@ -116,6 +131,7 @@ mod peer_tests {
                 component: 17,
             },
             last_active: SystemTime::now(),
+            link_quality: None,
         };
 
         let peer_2_old = Peer {
@@ -124,6 +140,7 @@ mod peer_tests {
                 component: 10,
             },
             last_active: UNIX_EPOCH,
+            link_quality: None,
         };
 
         let peer_2_new = Peer {
@@ -132,6 +149,7 @@ mod peer_tests {
                 component: 10,
             },
             last_active: peer_1_new.last_active,
+            link_quality: None,
         };
 
         assert_eq!(peer_1_new, peer_1_old);
@@ -141,6 +159,6 @@ mod peer_tests {
         assert!(peer_1_old < peer_1_new);
         assert!(peer_1_old <= peer_1_new);
 
-        assert!(!(peer_1_old < peer_2_new));
+        assert!(peer_1_old.partial_cmp(&peer_2_new).is_none());
     }
 }
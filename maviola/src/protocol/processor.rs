@@ -1,11 +1,13 @@
 use std::fmt::{Debug, Formatter};
 
 use crate::error::FrameError;
+use crate::protocol::readdress;
 #[cfg(feature = "unsafe")]
 use crate::protocol::ProcessFrameCase;
 use crate::protocol::{
-    CompatProcessor, CustomFrameProcessors, DialectSpec, Frame, FrameSigner, KnownDialects,
-    MaybeVersioned,
+    BuiltinStage, CompatProcessor, CrcExtra, CustomFrameProcessors, DialectSpec, Frame,
+    FrameDirection, FrameSigner, IdRemapper, KnownDialects, MavLinkId, MaybeVersioned, MessageId,
+    MiddlewareChain, MiddlewarePosition, SignatureRejection, SignerGuard, SignerHandle,
 };
 
 #[cfg(doc)]
@@ -23,21 +25,86 @@ use crate::protocol::Device;
 /// However, they never expose them. The reason is that frame processing is not generally idempotent.
 /// Which means you may render a frame useless by applying processor twice. Still we've found this
 /// abstraction handy and provide it for those who may want to extend Maviola functionality.
+///
+/// Signer is kept behind a [`SignerHandle`], so message signing can be changed while the processor
+/// is in use.
+///
+/// Additional stages, that implement [`FrameMiddleware`], can be inserted into processing pipeline
+/// at specific [`MiddlewarePosition`]s.
+///
+/// # Rewritten frames
+///
+/// Some stages change senders or targets of frames, that pass through them: [`IdRemapper`] and
+/// [`SysIdTranslation`]. Since MAVLink checksum depends on message `CRC_EXTRA`, frames of messages,
+/// that are not known to the processor, can't be rewritten and are dropped. MAVLink signature
+/// covers the entire frame, so rewritten frames, that were signed, are signed again, if message
+/// signing is enabled for the processor. Otherwise, such frames lose their signatures.
+///
+/// [`FrameMiddleware`]: crate::protocol::FrameMiddleware
+/// [`SysIdTranslation`]: crate::core::network::SysIdTranslation
 #[derive(Default)]
 pub struct FrameProcessor {
     compat: Option<CompatProcessor>,
-    signer: Option<FrameSigner>,
+    signer: SignerHandle,
+    order: ProcessingOrder,
     dialects: KnownDialects,
+    middleware: MiddlewareChain,
+    remapper: Option<IdRemapper>,
     #[cfg(feature = "unsafe")]
     processors: CustomFrameProcessors,
 }
 
+/// Order, in which [`CompatProcessor`] and [`FrameSigner`] are applied to frames.
+///
+/// MAVLink signature covers frame header including compatibility and incompatibility flags. This
+/// means that flags enforced by a compatibility processor after an outgoing frame has been signed
+/// will invalidate its signature. In the same way, if flags of an incoming frame are changed before
+/// signature validation, then the frame will be rejected by the signer.
+///
+/// Order is set separately for incoming and outgoing frames. By default, compatibility processor
+/// is applied first in both directions. Use [`NodeBuilder::processing_order`] to change it.
+///
+/// # Usage
+///
+/// Validate signatures of incoming frames before compatibility flags are enforced:
+///
+/// ```rust
+/// use maviola::protocol::{ProcessingOrder, StageOrder};
+///
+/// let order = ProcessingOrder::new(StageOrder::SignerFirst, StageOrder::CompatFirst);
+///
+/// assert_eq!(order.incoming(), StageOrder::SignerFirst);
+/// assert_eq!(order.outgoing(), StageOrder::CompatFirst);
+/// ```
+///
+/// [`NodeBuilder::processing_order`]: crate::core::node::NodeBuilder::processing_order
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessingOrder {
+    incoming: StageOrder,
+    outgoing: StageOrder,
+}
+
+/// Relative order of [`CompatProcessor`] and [`FrameSigner`] for frames of one direction.
+///
+/// See [`ProcessingOrder`] for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StageOrder {
+    /// Compatibility processor is applied before the signer (default).
+    #[default]
+    CompatFirst,
+    /// Signer is applied before the compatibility processor.
+    SignerFirst,
+}
+
 /// Builder for [`FrameProcessor`].
 #[derive(Clone, Default)]
 pub struct FrameProcessorBuilder {
     compat: Option<CompatProcessor>,
     signer: Option<FrameSigner>,
+    order: ProcessingOrder,
     dialects: KnownDialects,
+    middleware: MiddlewareChain,
+    remapper: Option<IdRemapper>,
     #[cfg(feature = "unsafe")]
     processors: CustomFrameProcessors,
 }
@@ -48,9 +115,25 @@ impl FrameProcessor {
         FrameProcessorBuilder::default()
     }
 
-    /// Returns an optional reference to a [`FrameSigner`].
-    pub fn signer(&self) -> Option<&FrameSigner> {
-        self.signer.as_ref()
+    /// Returns a reference to the current [`FrameSigner`], if message signing is enabled.
+    ///
+    /// Signer is locked while the returned [`SignerGuard`] is alive. Use
+    /// [`FrameProcessor::signer_snapshot`] to obtain an owned copy.
+    pub fn signer(&self) -> Option<SignerGuard<'_>> {
+        self.signer.lock()
+    }
+
+    /// Returns a snapshot of the current [`FrameSigner`], if message signing is enabled.
+    ///
+    /// Snapshot clones signer keys and peer strategies. Changes made through
+    /// [`FrameProcessor::signer_handle`] do not affect it.
+    pub fn signer_snapshot(&self) -> Option<FrameSigner> {
+        self.signer.get()
+    }
+
+    /// Returns a [`SignerHandle`], that changes message signing of this processor at runtime.
+    pub fn signer_handle(&self) -> SignerHandle {
+        self.signer.clone()
     }
 
     /// Returns an optional reference to a [`CompatProcessor`].
@@ -58,6 +141,16 @@ impl FrameProcessor {
         self.compat.as_ref()
     }
 
+    /// Returns an optional reference to an [`IdRemapper`].
+    pub fn remapper(&self) -> Option<&IdRemapper> {
+        self.remapper.as_ref()
+    }
+
+    /// Order, in which compatibility processor and signer are applied.
+    pub fn order(&self) -> ProcessingOrder {
+        self.order
+    }
+
     /// Main dialect specification.
     #[inline(always)]
     pub fn main_dialect(&self) -> &DialectSpec {
@@ -70,35 +163,105 @@ impl FrameProcessor {
         self.dialects.known()
     }
 
+    /// <sup>`definitions`</sup>
+    /// Message definitions used to describe frames, if set.
+    #[cfg(feature = "definitions")]
+    pub fn definitions(&self) -> Option<&crate::protocol::MessageDefinitions> {
+        self.dialects.definitions()
+    }
+
+    /// `CRC_EXTRA` of a message with specified `ID` from the known dialects.
+    pub(crate) fn crc_extra(&self, message_id: MessageId) -> Option<CrcExtra> {
+        self.dialects
+            .message_info_by_id(message_id)
+            .map(|info| info.crc_extra())
+    }
+
+    /// Rebuilds `frame` with a new sender `id` and `payload`.
+    ///
+    /// Follows the policy for [rewritten frames](FrameProcessor#rewritten-frames). Returns
+    /// [`None`], if frame can't be rewritten.
+    pub(crate) fn rewrite<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        id: MavLinkId,
+        payload: &[u8],
+    ) -> Option<Frame<V>> {
+        let crc_extra = match self.crc_extra(frame.message_id()) {
+            Some(crc_extra) => crc_extra,
+            None => {
+                log::trace!(
+                    "can't rewrite frame of unknown message #{}",
+                    frame.message_id()
+                );
+                return None;
+            }
+        };
+
+        let mut rewritten = readdress(frame, id, payload, crc_extra)?;
+        if frame.is_signed() {
+            if let Some(signer) = self.signer() {
+                signer.sign_frame(&mut rewritten).ok()?;
+            }
+        }
+
+        Some(rewritten)
+    }
+
+    /// Reason, why an incoming frame is rejected by the signer, if any.
+    pub(crate) fn signature_rejection<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+    ) -> Option<SignatureRejection> {
+        self.signer.read().as_ref()?.check_incoming(frame).err()
+    }
+
     /// Prepares a new outgoing frame.
-    pub fn process_new<V: MaybeVersioned>(&self, frame: &mut Frame<V>) {
-        if let Some(signer) = &self.signer {
-            signer.process_new(frame);
+    ///
+    /// Returns an error, if frame should be signed, but can't be (see
+    /// [`FrameSigner::process_new`]).
+    pub fn process_new<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> crate::error::Result<()> {
+        if let Some(signer) = self.signer.read().as_ref() {
+            signer.process_new(frame)?;
         }
+        Ok(())
     }
 
     /// Takes incoming frame and processes it according to defined signing and compatibility
     /// settings.
+    ///
+    /// Signing strategy is chosen by the frame system `ID` as defined by
+    /// [`FrameSigner::incoming_for`].
     pub fn process_incoming<V: MaybeVersioned>(
         &self,
         frame: &mut Frame<V>,
     ) -> Result<(), FrameError> {
+        let direction = FrameDirection::Incoming;
+        self.apply_middleware(frame, MiddlewarePosition::First, direction)?;
+
         #[cfg(feature = "unsafe")]
         self.apply_custom_processors(frame, ProcessFrameCase::IncomingBefore)?;
 
-        if let Some(compat) = &self.compat {
-            if let Err(err) = compat.process_incoming(frame, self.dialects.as_slice()) {
-                self.check_compat_err(err)?;
+        for stage in self.order.incoming.stages() {
+            self.apply_middleware(frame, MiddlewarePosition::Before(stage), direction)?;
+            match stage {
+                BuiltinStage::Compat => self.compat_incoming(frame)?,
+                BuiltinStage::Signer => self.sign_incoming(frame)?,
             }
+            self.apply_middleware(frame, MiddlewarePosition::After(stage), direction)?;
         }
 
-        if let Some(signer) = &self.signer {
-            signer.process_incoming(frame)?;
+        if let Some(remapper) = &self.remapper {
+            *frame = match remapper.remap_incoming(frame, self) {
+                Some(remapped) => remapped,
+                None => return Err(FrameError::NotInDialect(frame.message_id())),
+            };
         }
 
         #[cfg(feature = "unsafe")]
         self.apply_custom_processors(frame, ProcessFrameCase::IncomingAfter)?;
-        Ok(())
+
+        self.apply_middleware(frame, MiddlewarePosition::Last, direction)
     }
 
     /// Takes outgoing frame and processes it according to defined signing and compatibility
@@ -107,24 +270,77 @@ impl FrameProcessor {
         &self,
         frame: &mut Frame<V>,
     ) -> Result<(), FrameError> {
+        let direction = FrameDirection::Outgoing;
+        self.apply_middleware(frame, MiddlewarePosition::First, direction)?;
+
         #[cfg(feature = "unsafe")]
         self.apply_custom_processors(frame, ProcessFrameCase::OutgoingBefore)?;
 
-        if let Some(compat) = &self.compat {
+        if let Some(remapper) = &self.remapper {
+            *frame = match remapper.remap_outgoing(frame, self) {
+                Some(remapped) => remapped,
+                None => return Err(FrameError::NotInDialect(frame.message_id())),
+            };
+        }
+
+        for stage in self.order.outgoing.stages() {
+            self.apply_middleware(frame, MiddlewarePosition::Before(stage), direction)?;
+            match stage {
+                BuiltinStage::Compat => self.compat_outgoing(frame)?,
+                BuiltinStage::Signer => self.sign_outgoing(frame)?,
+            }
+            self.apply_middleware(frame, MiddlewarePosition::After(stage), direction)?;
+        }
+
+        #[cfg(feature = "unsafe")]
+        self.apply_custom_processors(frame, ProcessFrameCase::OutgoingAfter)?;
+
+        self.apply_middleware(frame, MiddlewarePosition::Last, direction)
+    }
+
+    fn compat_incoming<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<(), FrameError> {
+        if let Some(compat) = self.effective_compat() {
+            if let Err(err) = compat.process_incoming(frame, self.dialects.as_slice()) {
+                self.check_compat_err(err)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn compat_outgoing<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<(), FrameError> {
+        if let Some(compat) = self.effective_compat() {
             if let Err(err) = compat.process_outgoing(frame, self.dialects.as_slice()) {
                 self.check_compat_err(err)?;
             }
         }
+        Ok(())
+    }
 
-        if let Some(signer) = &self.signer {
-            signer.process_outgoing(frame)?;
+    fn sign_incoming<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<(), FrameError> {
+        if let Some(signer) = self.signer.read().as_ref() {
+            signer.process_incoming(frame)?;
         }
+        Ok(())
+    }
 
-        #[cfg(feature = "unsafe")]
-        self.apply_custom_processors(frame, ProcessFrameCase::OutgoingAfter)?;
+    fn sign_outgoing<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<(), FrameError> {
+        if let Some(signer) = self.signer.read().as_ref() {
+            signer.process_outgoing(frame)?;
+        }
         Ok(())
     }
 
+    /// Compatibility processor, that ignores signature flag, if signing has been enabled at
+    /// runtime. See [`FrameProcessorBuilder::signer`].
+    fn effective_compat(&self) -> Option<CompatProcessor> {
+        match self.compat {
+            Some(compat) if !compat.ignore_signature() && self.signer.is_enabled() => {
+                Some(compat.update().ignore_signature(true).build())
+            }
+            compat => compat,
+        }
+    }
+
     fn check_compat_err(&self, err: FrameError) -> Result<(), FrameError> {
         match err {
             FrameError::NotInDialect(_) if self.dialects.allow_unknown() => Ok(()),
@@ -132,6 +348,20 @@ impl FrameProcessor {
         }
     }
 
+    /// <sup>⛔</sup>
+    /// Applies middleware at the specified position.
+    fn apply_middleware<V: MaybeVersioned>(
+        &self,
+        frame: &mut Frame<V>,
+        position: MiddlewarePosition,
+        direction: FrameDirection,
+    ) -> Result<(), FrameError> {
+        if self.middleware.is_empty() {
+            return Ok(());
+        }
+        self.middleware.process(frame, position, direction)
+    }
+
     #[cfg(feature = "unsafe")]
     /// <sup>⛔ | 💢</sup>
     /// Applies custom processors. Works only when `unsafe` feature is enabled.
@@ -162,19 +392,24 @@ impl FrameProcessor {
         #[cfg(feature = "unsafe")]
         self.processors.extend(&other.processors);
 
+        self.middleware.extend(&other.middleware);
+
         self.dialects.append_known_dialects(&other.dialects);
 
-        if self.signer.is_none() {
-            if let Some(signer) = other.signer() {
-                self.signer = Some(signer.clone());
-            }
+        // Processors without own signer follow runtime signing changes of the other one
+        if !self.signer.is_enabled() {
+            self.signer = other.signer.clone();
         }
 
         if self.compat.is_none() {
             if let Some(compat) = other.compat() {
-                self.compat = Some(compat.clone());
+                self.compat = Some(*compat);
             }
         }
+
+        if self.order == ProcessingOrder::default() {
+            self.order = other.order;
+        }
     }
 }
 
@@ -184,14 +419,44 @@ impl Debug for FrameProcessor {
     }
 }
 
+impl ProcessingOrder {
+    /// Creates processing order from orders of incoming and outgoing frames.
+    pub const fn new(incoming: StageOrder, outgoing: StageOrder) -> Self {
+        Self { incoming, outgoing }
+    }
+
+    /// Order of processing for incoming frames.
+    pub fn incoming(&self) -> StageOrder {
+        self.incoming
+    }
+
+    /// Order of processing for outgoing frames.
+    pub fn outgoing(&self) -> StageOrder {
+        self.outgoing
+    }
+}
+
+impl StageOrder {
+    /// Built-in stages in the order they are applied.
+    fn stages(self) -> [BuiltinStage; 2] {
+        match self {
+            StageOrder::CompatFirst => [BuiltinStage::Compat, BuiltinStage::Signer],
+            StageOrder::SignerFirst => [BuiltinStage::Signer, BuiltinStage::Compat],
+        }
+    }
+}
+
 impl FrameProcessorBuilder {
     /// Builds a [`FrameProcessor`] from internal configuration.
     #[cfg(feature = "unsafe")]
     pub fn build(self) -> FrameProcessor {
         FrameProcessor {
             compat: self.compat,
-            signer: self.signer,
+            signer: SignerHandle::new(self.signer),
+            order: self.order,
             dialects: self.dialects,
+            middleware: self.middleware,
+            remapper: self.remapper,
             processors: self.processors,
         }
     }
@@ -201,8 +466,11 @@ impl FrameProcessorBuilder {
     pub fn build(self) -> FrameProcessor {
         FrameProcessor {
             compat: self.compat,
-            signer: self.signer,
+            signer: SignerHandle::new(self.signer),
+            order: self.order,
             dialects: self.dialects,
+            middleware: self.middleware,
+            remapper: self.remapper,
         }
     }
 
@@ -232,12 +500,33 @@ impl FrameProcessorBuilder {
         self
     }
 
+    /// Sets [`ProcessingOrder`] of compatibility processor and signer.
+    pub fn order(mut self, order: ProcessingOrder) -> Self {
+        self.order = order;
+        self
+    }
+
     /// Adds [`KnownDialects`] to a processor.
     pub fn dialects(mut self, dialects: KnownDialects) -> Self {
         self.dialects = dialects;
         self
     }
 
+    /// Adds an [`IdRemapper`] to a processor.
+    ///
+    /// Incoming frames are remapped after signature validation, outgoing frames are remapped
+    /// before they are signed.
+    pub fn remapper(mut self, remapper: IdRemapper) -> Self {
+        self.remapper = Some(remapper);
+        self
+    }
+
+    /// Sets [`MiddlewareChain`] applied to frames.
+    pub fn middleware(mut self, middleware: MiddlewareChain) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
     /// <sup>💢</sup>
     /// Sets custom processors, that implement [`ProcessFrame`].
     #[cfg(feature = "unsafe")]
@@ -257,7 +546,11 @@ impl FrameProcessorBuilder {
 #[cfg(test)]
 mod processor_tests {
     use super::*;
-    use crate::protocol::{IncompatFlags, SecretKey};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{
+        CompatStrategy, Endpoint, IncompatFlags, MavLinkId, SecretKey, SignStrategy,
+        SignatureRejection, V2,
+    };
 
     #[test]
     fn extend_processor_new_signer() {
@@ -327,4 +620,271 @@ mod processor_tests {
             IncompatFlags::BIT_2
         );
     }
+
+    fn signed_processor(order: ProcessingOrder) -> FrameProcessor {
+        FrameProcessor::builder()
+            .signer(FrameSigner::new(1, "abc"))
+            .compat(
+                CompatProcessor::builder()
+                    .incompat_flags(IncompatFlags::BIT_2)
+                    .outgoing(CompatStrategy::Enforce)
+                    .incoming(CompatStrategy::Enforce)
+                    .build(),
+            )
+            .dialects(KnownDialects::default().with_allow_unknown(true))
+            .order(order)
+            .build()
+    }
+
+    fn new_frame() -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn outgoing_processing_order() {
+        let processor = signed_processor(ProcessingOrder::default());
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert!(frame.incompat_flags().contains(IncompatFlags::BIT_2));
+        assert!(processor.signer().unwrap().has_valid_signature(&frame));
+
+        let processor = signed_processor(ProcessingOrder::new(
+            StageOrder::CompatFirst,
+            StageOrder::SignerFirst,
+        ));
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert!(frame.incompat_flags().contains(IncompatFlags::BIT_2));
+        assert!(!processor.signer().unwrap().has_valid_signature(&frame));
+    }
+
+    #[test]
+    fn incoming_processing_order() {
+        let signer = FrameSigner::new(1, "abc");
+        let mut signed = new_frame();
+        signer.sign_frame(&mut signed).unwrap();
+
+        let processor = signed_processor(ProcessingOrder::default());
+        assert!(processor.process_incoming(&mut signed.clone()).is_err());
+
+        let processor = signed_processor(ProcessingOrder::new(
+            StageOrder::SignerFirst,
+            StageOrder::CompatFirst,
+        ));
+        let mut frame = signed.clone();
+        processor.process_incoming(&mut frame).unwrap();
+        assert!(frame.incompat_flags().contains(IncompatFlags::BIT_2));
+    }
+
+    #[test]
+    fn signer_stats_and_rejections() {
+        let signer = FrameSigner::builder()
+            .link_id(1)
+            .key("abc")
+            .incoming(SignStrategy::Strict)
+            .build();
+        let processor = FrameProcessor::builder()
+            .signer(signer.clone())
+            .dialects(KnownDialects::default().with_allow_unknown(true))
+            .build();
+
+        let unsigned = new_frame();
+        assert!(processor.process_incoming(&mut unsigned.clone()).is_err());
+        assert_eq!(
+            processor.signature_rejection(&unsigned),
+            Some(SignatureRejection::Unsigned)
+        );
+
+        let mut unknown_link = new_frame();
+        FrameSigner::new(2, "abc")
+            .sign_frame(&mut unknown_link)
+            .unwrap();
+        assert_eq!(
+            processor.signature_rejection(&unknown_link),
+            Some(SignatureRejection::UnknownLink)
+        );
+
+        let mut forged = new_frame();
+        FrameSigner::new(1, "xyz").sign_frame(&mut forged).unwrap();
+        assert!(processor.process_incoming(&mut forged.clone()).is_err());
+        assert_eq!(
+            processor.signature_rejection(&forged),
+            Some(SignatureRejection::InvalidSignature)
+        );
+
+        let mut signed = new_frame();
+        signer.sign_frame(&mut signed).unwrap();
+        assert!(processor.signature_rejection(&signed).is_none());
+        processor.process_incoming(&mut signed).unwrap();
+        processor.process_outgoing(&mut new_frame()).unwrap();
+
+        let stats = processor.signer_handle().stats();
+        assert_eq!(stats.rejected(), 2);
+        assert_eq!(stats.verified(), 1);
+        assert_eq!(stats.signed(), 1);
+        assert_eq!(stats.resigned(), 0);
+        assert_eq!(stats.unsigned(), 0);
+
+        // Statistics survive signer replacement
+        processor
+            .signer_handle()
+            .replace(FrameSigner::new(3, "def"));
+        assert_eq!(processor.signer_handle().stats(), stats);
+    }
+
+    #[test]
+    fn rotate_signer_at_runtime() {
+        let processor = FrameProcessor::builder()
+            .compat(
+                CompatProcessor::builder()
+                    .incoming(CompatStrategy::Enforce)
+                    .build(),
+            )
+            .dialects(KnownDialects::default().with_allow_unknown(true))
+            .build();
+        let handle = processor.signer_handle();
+
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert!(!frame.is_signed());
+
+        // Signing enabled at runtime
+        handle.set_key(1, "abc");
+        let mut old_frame = new_frame();
+        processor.process_outgoing(&mut old_frame).unwrap();
+        assert!(old_frame.is_signed());
+        assert!(processor.process_incoming(&mut old_frame.clone()).is_ok());
+
+        // Previous key is still accepted after rotation
+        handle.set_key(2, "abcd");
+        assert!(handle.set_incoming(SignStrategy::Strict));
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert_eq!(frame.signature().unwrap().link_id, 2);
+        assert!(processor.process_incoming(&mut frame).is_ok());
+        assert!(processor.process_incoming(&mut old_frame.clone()).is_ok());
+
+        assert!(!handle.remove_link(2));
+        assert!(handle.remove_link(1));
+        assert!(processor.process_incoming(&mut old_frame).is_err());
+
+        handle.disable();
+        assert!(processor.signer().is_none());
+        assert!(!handle.set_outgoing(SignStrategy::Strict));
+    }
+
+    #[test]
+    fn extend_processor_shares_signer() {
+        let other = FrameProcessor::builder().build();
+        let mut this = FrameProcessor::builder().build();
+
+        this.extend_with(&other);
+        other.signer_handle().set_key(1, "abc");
+
+        assert_eq!(this.signer().unwrap().link_id(), 1);
+    }
+
+    #[test]
+    fn extend_processor_order() {
+        let order = ProcessingOrder::new(StageOrder::SignerFirst, StageOrder::CompatFirst);
+        let other = FrameProcessor::builder().order(order).build();
+        let mut this = FrameProcessor::builder().build();
+
+        this.extend_with(&other);
+
+        assert_eq!(this.order(), order);
+    }
+
+    #[derive(Debug)]
+    struct SignatureProbe(std::sync::Arc<std::sync::Mutex<Vec<bool>>>);
+
+    impl crate::protocol::FrameMiddleware for SignatureProbe {
+        fn process(
+            &mut self,
+            frame: &mut crate::protocol::MavFrame,
+            _: FrameDirection,
+        ) -> Result<(), FrameError> {
+            let signed = match frame {
+                crate::protocol::MavFrame::V1(_) => false,
+                crate::protocol::MavFrame::V2(frame) => frame.is_signed(),
+            };
+            self.0.lock().unwrap().push(signed);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Rejector;
+
+    impl crate::protocol::FrameMiddleware for Rejector {
+        fn process(
+            &mut self,
+            frame: &mut crate::protocol::MavFrame,
+            _: FrameDirection,
+        ) -> Result<(), FrameError> {
+            Err(FrameError::NotInDialect(frame.message_id()))
+        }
+    }
+
+    #[test]
+    fn middleware_positions() {
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut chain = MiddlewareChain::default();
+        chain.add(
+            MiddlewarePosition::Before(BuiltinStage::Signer),
+            SignatureProbe(log.clone()),
+        );
+        chain.add(
+            MiddlewarePosition::After(BuiltinStage::Signer),
+            SignatureProbe(log.clone()),
+        );
+
+        let processor = FrameProcessor::builder()
+            .signer(FrameSigner::new(1, "abc"))
+            .dialects(KnownDialects::default().with_allow_unknown(true))
+            .middleware(chain)
+            .build();
+
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert!(frame.is_signed());
+        assert_eq!(log.lock().unwrap().as_slice(), &[false, true]);
+    }
+
+    #[test]
+    fn middleware_rejects_frames() {
+        let mut chain = MiddlewareChain::default();
+        chain.add(MiddlewarePosition::Last, Rejector);
+        let other = FrameProcessor::builder().middleware(chain).build();
+        let mut this = FrameProcessor::builder().build();
+
+        assert!(this.process_incoming(&mut new_frame()).is_ok());
+        this.extend_with(&other);
+        this.extend_with(&other);
+        assert!(this.process_incoming(&mut new_frame()).is_err());
+        assert!(this.process_outgoing(&mut new_frame()).is_err());
+    }
+
+    #[test]
+    fn remapped_frames_are_signed() {
+        let processor = FrameProcessor::builder()
+            .signer(FrameSigner::new(1, "abc"))
+            .remapper(IdRemapper::new().outgoing(1, 201).incoming(2, 102))
+            .build();
+
+        let mut frame = new_frame();
+        processor.process_outgoing(&mut frame).unwrap();
+        assert_eq!(frame.system_id(), 201);
+        assert!(processor.signer().unwrap().has_valid_signature(&frame));
+
+        let mut frame = Endpoint::v2(MavLinkId::new(2, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        processor.signer().unwrap().sign_frame(&mut frame).unwrap();
+        processor.process_incoming(&mut frame).unwrap();
+        assert_eq!(frame.system_id(), 102);
+        assert!(processor.signer().unwrap().has_valid_signature(&frame));
+    }
 }
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use crate::protocol::{FrameProcessor, SystemId, TargetFields};
+
+use crate::prelude::*;
+
+/// Rewrites system and component `ID`s of frames according to a static mapping table.
+///
+/// Remapping is required when two MAVLink networks, that use the same system `ID`s, are bridged
+/// together. For example, when two vehicles with system `ID` `1` are connected through a single
+/// gateway. Mappings are defined separately for both directions:
+///
+/// * [`incoming`](Self::incoming) mappings rewrite senders of received frames. Target fields of
+///   addressed messages sent in the opposite direction are rewritten back.
+/// * [`outgoing`](Self::outgoing) mappings rewrite senders of sent frames. Target fields of
+///   received addressed messages are rewritten back.
+///
+/// Component mappings take precedence over system mappings. System mappings keep component `ID`s
+/// of frames intact.
+///
+/// Target fields are known for messages of the `common` dialect and its dependencies. Frames of
+/// unknown messages and signed frames are handled as described in
+/// [rewritten frames](crate::protocol::FrameProcessor#rewritten-frames).
+///
+/// Use [`NodeBuilder::id_remapper`](crate::core::node::NodeBuilder::id_remapper) to remap all
+/// frames of a node, or [`ConnectionOptions::remapper`] to remap frames of a particular network
+/// connection.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "sync")] {
+/// use maviola::protocol::IdRemapper;
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// // Vehicle behind the gateway uses system `ID` `1`, which is already taken in our network
+/// let remapper = IdRemapper::new().incoming(1, 101);
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(
+///         Network::sync()
+///             .add_connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///             .add_connection(UdpServer::new("127.0.0.1:14550").unwrap())
+///             .remapper(remapper)
+///     )
+///     .build().unwrap();
+/// # }
+/// ```
+///
+/// [`ConnectionOptions::remapper`]: crate::core::network::ConnectionOptions::remapper
+#[derive(Clone, Debug, Default)]
+pub struct IdRemapper {
+    incoming: IdMapping,
+    outgoing: IdMapping,
+}
+
+#[derive(Clone, Debug, Default)]
+struct IdMapping {
+    systems: HashMap<SystemId, SystemId>,
+    components: HashMap<MavLinkId, MavLinkId>,
+}
+
+impl IdRemapper {
+    /// Creates remapper with an empty mapping table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Received frames sent by system `from` will be seen as sent by system `to`.
+    pub fn incoming(mut self, from: SystemId, to: SystemId) -> Self {
+        self.incoming.systems.insert(from, to);
+        self
+    }
+
+    /// Received frames sent by component `from` will be seen as sent by component `to`.
+    pub fn incoming_component(mut self, from: MavLinkId, to: MavLinkId) -> Self {
+        self.incoming.components.insert(from, to);
+        self
+    }
+
+    /// Sent frames from system `from` will be seen by recipients as sent by system `to`.
+    pub fn outgoing(mut self, from: SystemId, to: SystemId) -> Self {
+        self.outgoing.systems.insert(from, to);
+        self
+    }
+
+    /// Sent frames from component `from` will be seen by recipients as sent by component `to`.
+    pub fn outgoing_component(mut self, from: MavLinkId, to: MavLinkId) -> Self {
+        self.outgoing.components.insert(from, to);
+        self
+    }
+
+    /// Returns `true`, if remapper has no mappings.
+    pub fn is_empty(&self) -> bool {
+        self.incoming.is_empty() && self.outgoing.is_empty()
+    }
+
+    /// <sup>⛔</sup>
+    /// Remaps a received frame.
+    ///
+    /// Returns [`None`], if frame should be rewritten but can't be.
+    pub(crate) fn remap_incoming<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        processor: &FrameProcessor,
+    ) -> Option<Frame<V>> {
+        remap(frame, &self.incoming, &self.outgoing, processor)
+    }
+
+    /// <sup>⛔</sup>
+    /// Remaps a frame, that is about to be sent.
+    ///
+    /// Returns [`None`], if frame should be rewritten but can't be.
+    pub(crate) fn remap_outgoing<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        processor: &FrameProcessor,
+    ) -> Option<Frame<V>> {
+        remap(frame, &self.outgoing, &self.incoming, processor)
+    }
+}
+
+impl IdMapping {
+    fn is_empty(&self) -> bool {
+        self.systems.is_empty() && self.components.is_empty()
+    }
+
+    /// Maps sender `id`.
+    fn map(&self, id: MavLinkId) -> Option<MavLinkId> {
+        if let Some(mapped) = self.components.get(&id) {
+            return Some(*mapped);
+        }
+        self.systems
+            .get(&id.system)
+            .map(|system_id| MavLinkId::new(*system_id, id.component))
+    }
+
+    /// Maps target `id` back to the original one.
+    fn unmap(&self, id: MavLinkId) -> Option<MavLinkId> {
+        let component = self
+            .components
+            .iter()
+            .find_map(|(from, to)| (*to == id).then_some(*from));
+        if component.is_some() {
+            return component;
+        }
+        self.systems.iter().find_map(|(from, to)| {
+            (*to == id.system).then_some(MavLinkId::new(*from, id.component))
+        })
+    }
+}
+
+/// Rewrites sender of a `frame` using `senders` mapping and its target fields using the reverse
+/// of `targets` mapping.
+fn remap<V: MaybeVersioned>(
+    frame: &Frame<V>,
+    senders: &IdMapping,
+    targets: &IdMapping,
+    processor: &FrameProcessor,
+) -> Option<Frame<V>> {
+    let sender = MavLinkId::new(frame.system_id(), frame.component_id());
+    let new_sender = senders.map(sender);
+
+    let mut payload = frame.payload().bytes().to_vec();
+    let mut retargeted = false;
+    if let Some(fields) = TargetFields::of(frame.message_id()) {
+        let target = fields.target(&payload);
+        if let Some(new_target) = targets.unmap(target) {
+            fields.set_target(&mut payload, new_target.system, new_target.component);
+            retargeted = true;
+        }
+    }
+
+    if new_sender.is_none() && !retargeted {
+        return Some(frame.clone());
+    }
+
+    processor.rewrite(frame, new_sender.unwrap_or(sender), &payload)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod remap_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{ComponentId, Endpoint, FrameSigner, KnownDialects};
+
+    fn heartbeat(system_id: SystemId, component_id: ComponentId) -> Frame<V2> {
+        Endpoint::v2(MavLinkId::new(system_id, component_id))
+            .next_frame(&Heartbeat::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn senders_are_remapped() {
+        let remapper = IdRemapper::new()
+            .incoming(1, 101)
+            .incoming_component(MavLinkId::new(1, 100), MavLinkId::new(102, 1))
+            .outgoing(1, 201);
+        let processor = FrameProcessor::builder().build();
+
+        let frame = remapper
+            .remap_incoming(&heartbeat(1, 1), &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 101);
+        assert_eq!(frame.component_id(), 1);
+        assert!(frame.decode::<crate::dialects::Minimal>().is_ok());
+
+        let frame = remapper
+            .remap_incoming(&heartbeat(1, 100), &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 102);
+        assert_eq!(frame.component_id(), 1);
+
+        let frame = remapper
+            .remap_incoming(&heartbeat(2, 1), &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        let frame = remapper
+            .remap_outgoing(&heartbeat(1, 1), &processor)
+            .unwrap();
+        assert_eq!(frame.system_id(), 201);
+    }
+
+    #[test]
+    fn unknown_frames_are_dropped() {
+        let remapper = IdRemapper::new().incoming(1, 101);
+        let processor = FrameProcessor::builder()
+            .dialects(KnownDialects::default().without_default_dialect())
+            .build();
+
+        let unknown = |system_id| {
+            Frame::builder()
+                .sequence(0)
+                .system_id(system_id)
+                .component_id(1)
+                .version(V2)
+                .message_id(65000)
+                .payload(&[1, 2, 3])
+                .crc_extra(42)
+                .build()
+        };
+
+        assert!(remapper.remap_incoming(&unknown(1), &processor).is_none());
+        assert!(remapper.remap_incoming(&unknown(2), &processor).is_some());
+    }
+
+    #[test]
+    fn signed_frames_are_signed_again() {
+        let signer = FrameSigner::new(1, "abc");
+        let remapper = IdRemapper::new().incoming(1, 101);
+        let processor = FrameProcessor::builder().signer(signer.clone()).build();
+
+        let mut frame = heartbeat(1, 1);
+        signer.sign_frame(&mut frame).unwrap();
+
+        let frame = remapper.remap_incoming(&frame, &processor).unwrap();
+        assert_eq!(frame.system_id(), 101);
+        assert!(signer.has_valid_signature(&frame));
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn targets_are_remapped() {
+        use crate::dialects::common::messages::ParamRequestList;
+
+        let remapper = IdRemapper::new().incoming(1, 101);
+        let processor = FrameProcessor::builder().build();
+
+        let request = Endpoint::v2(MavLinkId::new(255, 190))
+            .next_frame(&ParamRequestList {
+                target_system: 101,
+                target_component: 1,
+            })
+            .unwrap();
+
+        let frame = remapper.remap_outgoing(&request, &processor).unwrap();
+        assert_eq!(frame.system_id(), 255);
+        assert_eq!(
+            TargetFields::frame_target(&frame),
+            Some(MavLinkId::new(1, 1))
+        );
+        assert!(frame.decode::<crate::dialects::Common>().is_ok());
+    }
+}
@@ -1,14 +1,23 @@
 //! MAVLink [message signing](https://mavlink.io/en/guide/message_signing.html) tools.
 
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Debug, Formatter};
-use std::sync::atomic::AtomicU64;
-use std::sync::{atomic, Arc};
-use std::time::SystemTime;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{
+    atomic, mpsc, Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use std::thread;
+use std::time::{Instant, SystemTime};
 
-use crate::error::SignatureError;
+use crate::core::consts::{
+    DEFAULT_TIMESTAMP_SAVE_INTERVAL, TIMESTAMP_SAVE_MAX_RETRY_INTERVAL,
+    TIMESTAMP_SAVE_MIN_RETRY_INTERVAL,
+};
+use crate::error::{SignatureError, SyncError};
 use crate::protocol::{
     MavSha256, MavTimestamp, MessageId, SecretKey, Sign, SignedLinkId, Signer, SigningConf,
+    SystemId, TimestampStore,
 };
 
 use crate::prelude::*;
@@ -30,6 +39,13 @@ use builder::{NoLinkId, NoSecretKey};
 /// rejected, kept as they are, or re-signed with the main key and link `ID`. All supported links
 /// (including the main one) can be accessed with [`FrameSigner::links`].
 ///
+/// Incoming strategy can be overridden for particular peers identified by their system `ID`. This
+/// allows to require signing from some devices, while exempting legacy devices, that can't sign
+/// their frames. See [`FrameSigner::incoming_for`].
+///
+/// **⚠** Peers are identified by the system `ID` of a frame, which is not authenticated for unsigned
+/// frames. Relaxed per-peer strategies can be abused by senders, that spoof system `ID`.
+///
 /// **⚠** Secret keys are excluded from [Serde](https://serde.rs) serialization.
 ///
 /// # Examples
@@ -45,6 +61,29 @@ use builder::{NoLinkId, NoSecretKey};
 ///     .add_link(2, "key for the link #2") // Add extra link
 ///     .build();
 /// ```
+///
+/// Require signed frames from a ground station, while accepting frames of a legacy device as they
+/// are:
+///
+/// ```rust
+/// use maviola::prelude::*;
+///
+/// let signer = FrameSigner::builder()
+///     .link_id(1)
+///     .key("main key")
+///     .peer_incoming(255, SignStrategy::Strict) // Ground station has to sign its frames
+///     .peer_incoming(42, SignStrategy::Proxy)   // Legacy device is exempt from signing
+///     .build();
+///
+/// assert_eq!(signer.incoming_for(255), SignStrategy::Strict);
+/// assert_eq!(signer.incoming_for(42), SignStrategy::Proxy);
+/// assert_eq!(signer.incoming_for(1), SignStrategy::Sign);
+/// ```
+///
+/// Signer counts processed frames, counters are available as [`FrameSigner::stats`].
+///
+/// To prevent generation of stale timestamps after restart, signer can persist its timestamp in a
+/// [`TimestampStore`]. See [`FrameSignerBuilder::timestamp_store`].
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrameSigner {
@@ -52,10 +91,14 @@ pub struct FrameSigner {
     incoming: SignStrategy,
     outgoing: SignStrategy,
     unknown_links: SignStrategy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    peers: HashMap<SystemId, SignStrategy>,
     #[cfg_attr(feature = "serde", serde(skip_serializing))]
     links: HashMap<SignedLinkId, SecretKey>,
     last_timestamp: UniqueMavTimestamp,
     exclude: HashSet<MessageId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    counters: SigningCounters,
 }
 
 /// Message signing strategy.
@@ -127,6 +170,54 @@ pub enum SignStrategy {
     Strip,
 }
 
+/// Reason, why a frame was rejected by a [`FrameSigner`].
+///
+/// Available for rejected incoming frames as `ValidationReport::signature_rejection`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignatureRejection {
+    /// Frame is not signed, while [`SignStrategy::Strict`] requires signing.
+    ///
+    /// `MAVLink 1` frames, that can't be signed, are rejected for the same reason.
+    Unsigned,
+    /// Frame signature does not match any of the known keys.
+    InvalidSignature,
+    /// Frame is signed with an unknown link `ID`, while [`FrameSigner::unknown_links`] is
+    /// [`SignStrategy::Strict`].
+    UnknownLink,
+}
+
+/// Message signing statistics of a [`FrameSigner`].
+///
+/// Counts frames processed by [`FrameSigner::process_incoming`] and
+/// [`FrameSigner::process_outgoing`] in both directions. Frames with message `IDs` from
+/// [`FrameSigner::exclude`] are not counted.
+///
+/// Counters are shared by all clones of a signer and are kept, when signer is changed at runtime
+/// through a [`SignerHandle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignerStats {
+    signed: u64,
+    resigned: u64,
+    verified: u64,
+    unsigned: u64,
+    rejected: u64,
+}
+
+/// Shared counters of processed frames, that are reported as [`SignerStats`].
+#[derive(Clone, Default)]
+struct SigningCounters(Arc<SigningCountersInner>);
+
+#[derive(Default)]
+struct SigningCountersInner {
+    signed: AtomicU64,
+    resigned: AtomicU64,
+    verified: AtomicU64,
+    unsigned: AtomicU64,
+    rejected: AtomicU64,
+}
+
 /// A trait for entities, that can be converted to [`FrameSigner`].
 ///
 /// Currently, this trait is implemented for [`FrameSigner`] and [`FrameSignerBuilder`].
@@ -141,9 +232,92 @@ pub trait IntoFrameSigner {
 /// of a signer are used. Nevertheless, [`UniqueMavTimestamp`] allows to significantly reduc
 /// timestamp collisions.
 ///
+/// Timestamp can be persisted in a [`TimestampStore`] (see [`UniqueMavTimestamp::restore`]).
+///
 /// Used internally by [`FrameSigner`].
 #[derive(Clone)]
-pub struct UniqueMavTimestamp(Arc<AtomicU64>);
+pub struct UniqueMavTimestamp {
+    last: Arc<AtomicU64>,
+    persistence: Option<Arc<TimestampPersistence>>,
+}
+
+/// Saves timestamps of [`UniqueMavTimestamp`] ahead of time.
+///
+/// Timestamps are reserved for two save intervals. Once the first interval is used, the next
+/// reservation is requested from a worker thread, so signing does not wait for the store. If the
+/// store fails, reservation is not extended, and worker retries with back off. Timestamps beyond
+/// the saved reservation are not issued until it is extended.
+struct TimestampPersistence {
+    state: Arc<PersistenceState>,
+    requests: mpsc::Sender<u64>,
+}
+
+/// State of [`TimestampPersistence`] shared with its worker thread.
+struct PersistenceState {
+    store: Box<dyn TimestampStore>,
+    /// Timestamp saved in the store, generated timestamps should stay behind it.
+    reserved: AtomicU64,
+    /// Timestamp, after which the next reservation is requested.
+    refresh_at: AtomicU64,
+    /// Whether the next reservation is requested, but not saved yet.
+    is_pending: AtomicBool,
+    outcome: Mutex<SaveOutcome>,
+    saved: Condvar,
+}
+
+/// Outcome of the last attempt to save a reservation.
+#[derive(Default)]
+struct SaveOutcome {
+    attempts: u64,
+    error: Option<Error>,
+}
+
+/// Shared handle to a [`FrameSigner`], that allows to change message signing at runtime.
+///
+/// Each node keeps its signer behind a handle obtained by `signer_handle` method of a node. All
+/// clones of a handle share the same signer, and every change is applied atomically to frames
+/// processed after the call, so keys can be rotated (for example, upon receiving a
+/// [`SETUP_SIGNING`](https://mavlink.io/en/messages/common.html#SETUP_SIGNING) message) without
+/// rebuilding the node.
+///
+/// Handle may be empty, in that case message signing is disabled. Methods, that modify an existing
+/// signer, return `false` and do nothing when signing is disabled, while [`SignerHandle::set_key`]
+/// and [`SignerHandle::replace`] enable it.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::protocol::SignerHandle;
+/// use maviola::prelude::*;
+///
+/// let handle = SignerHandle::default();
+/// assert!(!handle.is_enabled());
+///
+/// // Enable signing with default strategies
+/// handle.set_key(1, "old key");
+///
+/// // Rotate the main key, while still accepting frames signed with the old one
+/// handle.set_key(2, "new key");
+/// assert!(handle.set_incoming(SignStrategy::Strict));
+///
+/// // Stop accepting the old key
+/// assert!(handle.remove_link(1));
+///
+/// let signer = handle.get().unwrap();
+/// assert_eq!(signer.link_id(), 2);
+/// assert_eq!(signer.links().count(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SignerHandle(Arc<RwLock<Option<FrameSigner>>>);
+
+/// Shared access to the current signer of a [`SignerHandle`].
+///
+/// Dereferences to [`FrameSigner`]. Returned by [`SignerHandle::lock`] and
+/// [`FrameProcessor::signer`](crate::protocol::FrameProcessor::signer).
+///
+/// **⚠** Signer can't be changed while guard is alive. Changing signer through any
+/// [`SignerHandle`] from the thread, that holds the guard, will deadlock.
+pub struct SignerGuard<'a>(RwLockReadGuard<'a, Option<FrameSigner>>);
 
 impl FrameSigner {
     /// Creates a [`FrameSigner`] with the main `link_id` / `key` and default strategies.
@@ -192,6 +366,28 @@ impl FrameSigner {
         self.incoming
     }
 
+    /// Signing strategy for incoming messages sent by a peer with specified `system_id`.
+    ///
+    /// Returns a strategy set for this peer by [`FrameSignerBuilder::peer_incoming`] or falls back
+    /// to [`Self::incoming`].
+    ///
+    /// **⚠** System `ID` of unsigned frames is not authenticated. Any sender can claim a `system_id`
+    /// of a peer with a relaxed strategy (such as [`SignStrategy::Proxy`]) and pass its frames
+    /// through, even if the default incoming strategy is [`SignStrategy::Strict`]. Relax signing
+    /// requirements for particular peers only on links where senders are trusted.
+    pub fn incoming_for(&self, system_id: SystemId) -> SignStrategy {
+        self.peers.get(&system_id).copied().unwrap_or(self.incoming)
+    }
+
+    /// Iterator over incoming strategies overridden for particular peers.
+    ///
+    /// Yields pairs of peer system `ID` and its incoming signing strategy.
+    pub fn peers(&self) -> impl Iterator<Item = (SystemId, SignStrategy)> + '_ {
+        self.peers
+            .iter()
+            .map(|(&system_id, &strategy)| (system_id, strategy))
+    }
+
     /// Signing strategy for outgoing messages.
     ///
     /// The default value is [`SignStrategy::Sign`].
@@ -219,13 +415,22 @@ impl FrameSigner {
         self.exclude.clone().into_iter()
     }
 
-    /// Takes incoming frame and processes it according to a [`Self::incoming`] signing strategy.
+    /// Message signing statistics.
+    ///
+    /// See [`SignerStats`] for details.
+    pub fn stats(&self) -> SignerStats {
+        self.counters.snapshot()
+    }
+
+    /// Takes incoming frame and processes it according to a signing strategy of its sender.
+    ///
+    /// The strategy is defined by [`Self::incoming_for`] the frame system `ID`.
     #[inline(always)]
     pub fn process_incoming<V: MaybeVersioned>(
         &self,
         frame: &mut Frame<V>,
     ) -> core::result::Result<(), SignatureError> {
-        self.process_for_strategy(frame, self.incoming)
+        self.process_for_strategy(frame, self.incoming_for(frame.system_id()))
     }
 
     /// Takes outgoing frame and processes it according to a [`Self::outgoing`] signing strategy.
@@ -240,11 +445,15 @@ impl FrameSigner {
     /// Prepare a frame that is supposed to be sent via this channel.
     ///
     /// This method will sign a frame only if channel is supposed to accept only signed frames.
+    ///
+    /// Returns an error, if frame should be signed, but timestamp can't be persisted (see
+    /// [`Self::sign_frame`]).
     #[allow(unused_variables)]
-    pub fn process_new<V: MaybeVersioned>(&self, frame: &mut Frame<V>) {
+    pub fn process_new<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<()> {
         if let SignStrategy::Strict = self.outgoing {
-            self.sign_frame(frame);
+            self.sign_frame(frame)?;
         }
+        Ok(())
     }
 
     /// Processes a [`Frame`] given the provided [`SignStrategy`].
@@ -262,31 +471,76 @@ impl FrameSigner {
         if self.exclude.contains(&frame.message_id()) {
             return Ok(());
         }
-        self.validate_for_strategy(frame, strategy)?;
-        self.sign_for_strategy(frame, strategy);
+        if self.check_for_strategy(frame, strategy).is_err() {
+            self.counters
+                .0
+                .rejected
+                .fetch_add(1, atomic::Ordering::Relaxed);
+            return Err(SignatureError);
+        }
+
+        let was_signed = frame.is_signed();
+        let signed_with_main_key = self.sign_for_strategy(frame, strategy)?;
+        let counter = match (frame.is_signed(), was_signed, signed_with_main_key) {
+            (false, _, _) => &self.counters.0.unsigned,
+            (true, true, true) => &self.counters.0.resigned,
+            (true, false, true) => &self.counters.0.signed,
+            (true, _, false) => &self.counters.0.verified,
+        };
+        counter.fetch_add(1, atomic::Ordering::Relaxed);
+
         Ok(())
     }
 
     /// Validates a [`Frame`] given the provided [`SignStrategy`].
+    ///
+    /// Use [`Self::check_for_strategy`] to learn, why frame was rejected.
     pub fn validate_for_strategy<V: MaybeVersioned>(
         &self,
         frame: &Frame<V>,
         strategy: SignStrategy,
     ) -> core::result::Result<(), SignatureError> {
+        self.check_for_strategy(frame, strategy)
+            .map_err(|_| SignatureError)
+    }
+
+    /// Checks, whether an incoming frame will be accepted according to a signing strategy of its
+    /// sender.
+    ///
+    /// Returns the reason of rejection for frames, that won't be accepted by
+    /// [`Self::process_incoming`]. Frames with message `IDs` from [`Self::exclude`] are always
+    /// accepted.
+    pub fn check_incoming<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+    ) -> core::result::Result<(), SignatureRejection> {
+        if self.exclude.contains(&frame.message_id()) {
+            return Ok(());
+        }
+        self.check_for_strategy(frame, self.incoming_for(frame.system_id()))
+    }
+
+    /// Validates a [`Frame`] given the provided [`SignStrategy`] and returns the reason of
+    /// rejection.
+    pub fn check_for_strategy<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        strategy: SignStrategy,
+    ) -> core::result::Result<(), SignatureRejection> {
         if let SignStrategy::Proxy = strategy {
             return Ok(());
         }
 
         if let SignStrategy::Strict = strategy {
             if !frame.is_signed() {
-                return Err(SignatureError);
+                return Err(SignatureRejection::Unsigned);
             }
         }
 
         match strategy {
             SignStrategy::Sign | SignStrategy::ReSign | SignStrategy::Strict => {
                 if frame.is_signed() && !self.has_valid_signature(frame) {
-                    return Err(SignatureError);
+                    return Err(self.rejection_of_signed(frame));
                 }
             }
             SignStrategy::Proxy | SignStrategy::Strip => {}
@@ -299,9 +553,17 @@ impl FrameSigner {
     ///
     /// Adds signature to `MAVLink 2` frames using main key and link `ID`. `MAVLink 2` frames will
     /// be kept untouched.
-    pub fn sign_frame<V: MaybeVersioned>(&self, frame: &mut Frame<V>) {
-        let signature_conf = self.to_signature_conf();
+    ///
+    /// Returns the error of a [`TimestampStore`] and keeps frame untouched, if signer persists its
+    /// timestamp, and the next timestamp can't be saved (see [`Self::try_next_timestamp`]).
+    pub fn sign_frame<V: MaybeVersioned>(&self, frame: &mut Frame<V>) -> Result<()> {
+        let signature_conf = SigningConf {
+            link_id: self.link_id,
+            timestamp: self.try_next_timestamp()?,
+            secret: self.key().clone(),
+        };
         signature_conf.apply(frame, &mut self.signer());
+        Ok(())
     }
 
     /// Returns `true` if frame has a valid signature.
@@ -345,6 +607,8 @@ impl FrameSigner {
     }
 
     /// Creates an instance of a signature configuration that can be used to sign frames.
+    ///
+    /// Uses [`Self::next_timestamp`], see its caveats for persisted timestamps.
     pub fn to_signature_conf(&self) -> SigningConf {
         SigningConf {
             link_id: self.link_id,
@@ -359,32 +623,64 @@ impl FrameSigner {
     /// of a signer are used. Nevertheless, this method allows to significantly reduce timestamp
     /// collisions.
     ///
-    /// Uses [`UniqueMavTimestamp`] internally.
+    /// Uses [`UniqueMavTimestamp`] internally. See [`UniqueMavTimestamp::next`] for caveats of
+    /// persisted timestamps.
     pub fn next_timestamp(&self) -> MavTimestamp {
         self.last_timestamp.next()
     }
 
+    /// Returns the next MAVLink timestamp that can be used to sign a frame, if it is persisted.
+    ///
+    /// Returns the error of a [`TimestampStore`], if timestamp can't be persisted. See
+    /// [`UniqueMavTimestamp::try_next`].
+    pub fn try_next_timestamp(&self) -> Result<MavTimestamp> {
+        self.last_timestamp.try_next()
+    }
+
     /// <sup>⛔</sup>
     /// ⚠ **DANGER** ⚠ Applies [`SignStrategy`] to a frame.
     ///
     /// This method should be never exposed to a user as it relies on preliminary frame validation.
-    fn sign_for_strategy<V: MaybeVersioned>(&self, frame: &mut Frame<V>, strategy: SignStrategy) {
+    ///
+    /// Returns `true`, if frame was signed with the main key. Returns an error, if frame should be
+    /// signed, but timestamp can't be persisted.
+    fn sign_for_strategy<V: MaybeVersioned>(
+        &self,
+        frame: &mut Frame<V>,
+        strategy: SignStrategy,
+    ) -> core::result::Result<bool, SignatureError> {
         match strategy {
-            SignStrategy::Sign => {
-                if self.should_sign(frame) {
-                    self.sign_frame(frame);
-                }
+            SignStrategy::Sign if self.should_sign(frame) => {
+                self.sign_frame(frame).map_err(|_| SignatureError)?;
+                Ok(true)
             }
-            SignStrategy::ReSign => {
-                if self.should_re_sign(frame) {
-                    self.sign_frame(frame);
-                }
+            SignStrategy::ReSign if self.should_re_sign(frame) => {
+                self.sign_frame(frame).map_err(|_| SignatureError)?;
+                Ok(true)
             }
             SignStrategy::Strip => {
                 frame.remove_signature();
+                Ok(false)
+            }
+            SignStrategy::Sign
+            | SignStrategy::ReSign
+            | SignStrategy::Strict
+            | SignStrategy::Proxy => Ok(false),
+        }
+    }
+
+    /// <sup>⛔</sup>
+    /// Tells, why a signed frame has an invalid signature.
+    fn rejection_of_signed<V: MaybeVersioned>(&self, frame: &Frame<V>) -> SignatureRejection {
+        match frame.signature() {
+            Some(signature)
+                if !self.links.contains_key(&signature.link_id)
+                    && self.unknown_links == SignStrategy::Strict =>
+            {
+                SignatureRejection::UnknownLink
             }
-            SignStrategy::Strict => {}
-            SignStrategy::Proxy => {}
+            Some(_) => SignatureRejection::InvalidSignature,
+            None => SignatureRejection::Unsigned,
         }
     }
 
@@ -392,7 +688,7 @@ impl FrameSigner {
     /// Checks, that frame should be signed for [`SignStrategy::Sign`].
     fn should_sign<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
         if let Some(signature) = frame.signature() {
-            self.links.get(&signature.link_id).is_none()
+            !self.links.contains_key(&signature.link_id)
                 && (self.unknown_links == SignStrategy::Sign
                     || self.unknown_links == SignStrategy::ReSign)
         } else {
@@ -404,7 +700,7 @@ impl FrameSigner {
     /// Checks, that frame should be signed for [`SignStrategy::ReSign`].
     fn should_re_sign<V: MaybeVersioned>(&self, frame: &Frame<V>) -> bool {
         if let Some(signature) = frame.signature() {
-            if self.links.get(&signature.link_id).is_none() {
+            if !self.links.contains_key(&signature.link_id) {
                 self.unknown_links == SignStrategy::ReSign
             } else {
                 true
@@ -415,22 +711,259 @@ impl FrameSigner {
     }
 }
 
+impl SignerHandle {
+    /// Creates a handle to an optional `signer`.
+    pub fn new(signer: Option<FrameSigner>) -> Self {
+        Self(Arc::new(RwLock::new(signer)))
+    }
+
+    /// Returns a snapshot of the current signer or `None`, if signing is disabled.
+    pub fn get(&self) -> Option<FrameSigner> {
+        self.read().clone()
+    }
+
+    /// Locks the current signer for reading or returns `None`, if signing is disabled.
+    ///
+    /// Unlike [`SignerHandle::get`], does not clone the signer. See [`SignerGuard`].
+    pub fn lock(&self) -> Option<SignerGuard<'_>> {
+        let guard = self.read();
+        guard.is_some().then_some(SignerGuard(guard))
+    }
+
+    /// Returns `true`, if message signing is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.read().is_some()
+    }
+
+    /// Replaces the current signer and returns the previous one.
+    ///
+    /// To keep signature timestamps monotonic, the new signer continues the timestamp sequence of
+    /// the previous one, unless its own timestamp is already ahead. If the new signer persists its
+    /// timestamp in a [`TimestampStore`], it keeps the store. The new signer continues
+    /// [`FrameSigner::stats`] of the previous one as well.
+    pub fn replace(&self, signer: impl IntoFrameSigner) -> Option<FrameSigner> {
+        let mut signer = signer.into_message_signer();
+        let mut current = self.write();
+
+        if let Some(current) = current.as_ref() {
+            if signer.last_timestamp.is_persisted() {
+                signer.last_timestamp.advance(current.last_timestamp.last());
+            } else if current.last_timestamp.last().as_raw_u64()
+                > signer.last_timestamp.last().as_raw_u64()
+            {
+                signer.last_timestamp = current.last_timestamp.clone();
+            }
+            signer.counters = current.counters.clone();
+        }
+
+        current.replace(signer)
+    }
+
+    /// Message signing statistics of the current signer.
+    ///
+    /// Returns empty statistics, if message signing is disabled.
+    pub fn stats(&self) -> SignerStats {
+        match self.read().as_ref() {
+            Some(signer) => signer.stats(),
+            None => SignerStats::default(),
+        }
+    }
+
+    /// Disables message signing and returns the previous signer.
+    pub fn disable(&self) -> Option<FrameSigner> {
+        self.write().take()
+    }
+
+    /// Sets the main link `ID` and secret key.
+    ///
+    /// The previous main link stays among [`FrameSigner::links`], so frames signed with the
+    /// previous key are still accepted until it is removed by [`SignerHandle::remove_link`]. If
+    /// `link_id` is already known, its key will be replaced.
+    ///
+    /// Enables message signing with default strategies, if it was disabled.
+    pub fn set_key<K: Into<SecretKey>>(&self, link_id: SignedLinkId, key: K) {
+        let key = key.into();
+        let mut signer = self.write();
+
+        match signer.as_mut() {
+            Some(signer) => {
+                signer.links.insert(link_id, key);
+                signer.link_id = link_id;
+            }
+            None => *signer = Some(FrameSigner::new(link_id, key)),
+        }
+    }
+
+    /// Adds an auxiliary link used to validate signed frames.
+    ///
+    /// If `link_id` is the main [`FrameSigner::link_id`], then the main key will be replaced.
+    pub fn add_link<K: Into<SecretKey>>(&self, link_id: SignedLinkId, key: K) -> bool {
+        let key = key.into();
+        self.modify(|signer| {
+            signer.links.insert(link_id, key);
+            true
+        })
+    }
+
+    /// Removes an auxiliary link.
+    ///
+    /// Returns `false`, if link is unknown or is the main [`FrameSigner::link_id`], that can be
+    /// changed only by [`SignerHandle::set_key`].
+    pub fn remove_link(&self, link_id: SignedLinkId) -> bool {
+        self.modify(|signer| signer.link_id != link_id && signer.links.remove(&link_id).is_some())
+    }
+
+    /// Sets [`FrameSigner::incoming`] strategy.
+    pub fn set_incoming(&self, strategy: SignStrategy) -> bool {
+        self.modify(|signer| {
+            signer.incoming = strategy;
+            true
+        })
+    }
+
+    /// Sets [`FrameSigner::outgoing`] strategy.
+    pub fn set_outgoing(&self, strategy: SignStrategy) -> bool {
+        self.modify(|signer| {
+            signer.outgoing = strategy;
+            true
+        })
+    }
+
+    /// Overrides incoming strategy for a peer with specified `system_id`.
+    ///
+    /// When `strategy` is `None`, the peer falls back to [`FrameSigner::incoming`] strategy. See
+    /// [`FrameSignerBuilder::peer_incoming`].
+    ///
+    /// **⚠** System `ID` of unsigned frames is not authenticated. Any sender can claim a `system_id`
+    /// of a peer with a relaxed strategy (such as [`SignStrategy::Proxy`]) and pass its frames
+    /// through, even if the default incoming strategy is [`SignStrategy::Strict`]. Relax signing
+    /// requirements for particular peers only on links where senders are trusted.
+    pub fn set_peer_incoming(&self, system_id: SystemId, strategy: Option<SignStrategy>) -> bool {
+        self.modify(|signer| {
+            match strategy {
+                Some(strategy) => signer.peers.insert(system_id, strategy),
+                None => signer.peers.remove(&system_id),
+            };
+            true
+        })
+    }
+
+    /// Sets message `IDs` excluded from message signing and verification.
+    pub fn set_exclude(&self, message_ids: &[MessageId]) -> bool {
+        self.modify(|signer| {
+            signer.exclude = HashSet::from_iter(message_ids.iter().copied());
+            true
+        })
+    }
+
+    /// Locks signer for reading.
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, Option<FrameSigner>> {
+        // Signer is always consistent, since it is modified only under the lock
+        match self.0.read() {
+            Ok(signer) => signer,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Option<FrameSigner>> {
+        match self.0.write() {
+            Ok(signer) => signer,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn modify(&self, modify: impl FnOnce(&mut FrameSigner) -> bool) -> bool {
+        match self.write().as_mut() {
+            Some(signer) => modify(signer),
+            None => false,
+        }
+    }
+}
+
+impl Deref for SignerGuard<'_> {
+    type Target = FrameSigner;
+
+    fn deref(&self) -> &Self::Target {
+        // Guard is created only for enabled signers
+        self.0.as_ref().unwrap()
+    }
+}
+
+impl Debug for SignerGuard<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl From<FrameSigner> for SignerHandle {
+    fn from(value: FrameSigner) -> Self {
+        Self::new(Some(value))
+    }
+}
+
 impl UniqueMavTimestamp {
     /// Creates a new [`UniqueMavTimestamp`] which is just a moment behind the current time.
     pub fn new() -> Self {
-        Self(Arc::new(AtomicU64::new(
-            MavTimestamp::from(SystemTime::now()).as_raw_u64() - 1,
-        )))
+        Self::init(MavTimestamp::from_raw_u64(Self::now_raw() - 1))
     }
 
     /// Creates a new [`UniqueMavTimestamp`] from [`MavTimestamp`].
     pub fn init(timestamp: MavTimestamp) -> Self {
-        Self(Arc::new(AtomicU64::new(timestamp.as_raw_u64())))
+        Self {
+            last: Arc::new(AtomicU64::new(timestamp.as_raw_u64())),
+            persistence: None,
+        }
+    }
+
+    /// Creates a new [`UniqueMavTimestamp`] persisted in a [`TimestampStore`].
+    ///
+    /// Restores the last saved timestamp, unless current time is already ahead. Then saves
+    /// timestamps, that are twice the [`DEFAULT_TIMESTAMP_SAVE_INTERVAL`] ahead of the generated
+    /// ones, once per interval. This way, timestamps restored after an unexpected shutdown are
+    /// never stale.
+    ///
+    /// Timestamps are saved by a dedicated worker thread, signing waits for the store only if the
+    /// reserved timestamps are exhausted before the save is complete. Still,
+    /// [`TimestampStore::save`] should not block for longer than the save interval.
+    ///
+    /// If the store fails, the worker retries with back off, while timestamps are issued from the
+    /// remaining reservation. Once reservation is exhausted, [`UniqueMavTimestamp::try_next`]
+    /// returns the error of the store until the next save succeeds, and frames are not signed.
+    ///
+    /// Returns an error, if timestamp can't be loaded or the first reservation can't be saved.
+    /// Starting from the current time instead is not safe, since system clock may be behind the
+    /// timestamps, that were already used.
+    ///
+    /// ⚠ Persistence is not preserved by [Serde](https://serde.rs) serialization.
+    pub fn restore(store: impl TimestampStore) -> Result<Self> {
+        let now = Self::now_raw() - 1;
+        let last = match store.load()? {
+            Some(stored) => stored.as_raw_u64().max(now),
+            None => now,
+        };
+
+        let persistence = TimestampPersistence::spawn(Box::new(store), last)?;
+
+        Ok(Self {
+            last: Arc::new(AtomicU64::new(last)),
+            persistence: Some(Arc::new(persistence)),
+        })
     }
 
     /// Returns the current timestamp.
     pub fn last(&self) -> MavTimestamp {
-        MavTimestamp::from_raw_u64(self.0.load(atomic::Ordering::Acquire))
+        MavTimestamp::from_raw_u64(self.last.load(atomic::Ordering::Acquire))
+    }
+
+    /// Advances the current timestamp to the provided one, unless it is already ahead.
+    pub fn advance(&self, timestamp: MavTimestamp) {
+        self.last
+            .fetch_max(timestamp.as_raw_u64(), atomic::Ordering::AcqRel);
+    }
+
+    /// Returns `true`, if timestamp is persisted in a [`TimestampStore`].
+    pub fn is_persisted(&self) -> bool {
+        self.persistence.is_some()
     }
 
     /// Returns the next MAVLink timestamp that can be used to sign a frame.
@@ -438,19 +971,199 @@ impl UniqueMavTimestamp {
     /// ⚠ It is not strictly guaranteed that the next timestamp will be unique, if multiple clones
     /// of a signer are used. Nevertheless, this method allows to significantly reduce timestamp
     /// collisions.
+    ///
+    /// **⚠** For timestamps persisted in a [`TimestampStore`], this method ignores failures of
+    /// the store and may return timestamps beyond the saved ones. Use [`Self::try_next`] instead.
     pub fn next(&self) -> MavTimestamp {
-        let last_timestamp = self.0.fetch_add(1, atomic::Ordering::Acquire);
+        let timestamp = self.advance_next();
+        if let Some(persistence) = &self.persistence {
+            _ = persistence.reserve(timestamp);
+        }
+        timestamp
+    }
+
+    /// Returns the next MAVLink timestamp that can be used to sign a frame, if it is persisted.
+    ///
+    /// Same as [`Self::next`], except that timestamps persisted in a [`TimestampStore`] are
+    /// returned only if they are behind the saved one. Returns the error of the store, if
+    /// timestamps, that were saved ahead, are exhausted, and the store has failed to save the
+    /// next ones.
+    pub fn try_next(&self) -> Result<MavTimestamp> {
+        let timestamp = self.advance_next();
+        if let Some(persistence) = &self.persistence {
+            persistence.reserve(timestamp)?;
+        }
+        Ok(timestamp)
+    }
+
+    fn advance_next(&self) -> MavTimestamp {
+        let last_timestamp = self.last.fetch_add(1, atomic::Ordering::Acquire);
         let mut timestamp = MavTimestamp::from(SystemTime::now());
 
         if timestamp.as_raw_u64() <= last_timestamp {
             timestamp = MavTimestamp::from_raw_u64(last_timestamp + 1);
         } else {
-            self.0
+            self.last
                 .store(timestamp.as_raw_u64(), atomic::Ordering::Release);
         }
 
         timestamp
     }
+
+    fn now_raw() -> u64 {
+        MavTimestamp::from(SystemTime::now()).as_raw_u64()
+    }
+}
+
+impl TimestampPersistence {
+    /// Saves the first reservation after `timestamp` and spawns a worker thread for the next
+    /// ones.
+    fn spawn(store: Box<dyn TimestampStore>, timestamp: u64) -> Result<Self> {
+        let state = Arc::new(PersistenceState {
+            store,
+            reserved: AtomicU64::new(0),
+            refresh_at: AtomicU64::new(0),
+            is_pending: AtomicBool::new(false),
+            outcome: Mutex::new(SaveOutcome::default()),
+            saved: Condvar::new(),
+        });
+        state.save(timestamp)?;
+
+        let (requests, requests_rx) = mpsc::channel();
+        {
+            let state = state.clone();
+            thread::Builder::new()
+                .name("maviola-timestamp-store".to_string())
+                .spawn(move || state.work(requests_rx))?;
+        }
+
+        Ok(Self { state, requests })
+    }
+
+    /// Checks, that `timestamp` is reserved, and requests the next reservation, if `timestamp`
+    /// has used the current one.
+    ///
+    /// Blocks only if `timestamp` has reached the saved one. Returns the error of the store, if
+    /// `timestamp` is not reserved and can't be saved.
+    fn reserve(&self, timestamp: MavTimestamp) -> Result<()> {
+        let timestamp = timestamp.as_raw_u64();
+        let state = &self.state;
+
+        if timestamp < state.reserved.load(atomic::Ordering::Acquire) {
+            if timestamp >= state.refresh_at.load(atomic::Ordering::Acquire)
+                && !state.is_pending.swap(true, atomic::Ordering::AcqRel)
+            {
+                _ = self.requests.send(timestamp);
+            }
+            return Ok(());
+        }
+
+        // Reserved timestamps are exhausted, timestamp can't be used until saved
+        let mut outcome = state.outcome();
+        if let Some(err) = &outcome.error {
+            // Worker retries on its own, there is no reason to wait for the store, that fails
+            return Err(err.clone());
+        }
+        state.is_pending.store(true, atomic::Ordering::Release);
+        if self.requests.send(timestamp).is_err() {
+            return Err(SyncError::Disconnected.into());
+        }
+
+        let attempts = outcome.attempts;
+        while timestamp >= state.reserved.load(atomic::Ordering::Acquire) {
+            if outcome.attempts != attempts {
+                if let Some(err) = &outcome.error {
+                    return Err(err.clone());
+                }
+            }
+            outcome = match state.saved.wait(outcome) {
+                Ok(outcome) => outcome,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+        }
+        Ok(())
+    }
+}
+
+impl PersistenceState {
+    /// Saves requested reservations until all handles are dropped.
+    ///
+    /// Failed saves are retried with back off. Requests received in the meantime are merged, so
+    /// only the latest timestamp is saved.
+    fn work(&self, requests: mpsc::Receiver<u64>) {
+        let _guard = WorkerGuard(self);
+
+        while let Ok(mut timestamp) = requests.recv() {
+            let mut retry_interval = TIMESTAMP_SAVE_MIN_RETRY_INTERVAL;
+
+            loop {
+                timestamp = requests.try_iter().fold(timestamp, u64::max);
+                if self.save(timestamp).is_ok() {
+                    break;
+                }
+
+                let retry_at = Instant::now() + retry_interval;
+                retry_interval = (retry_interval * 2).min(TIMESTAMP_SAVE_MAX_RETRY_INTERVAL);
+                while let Some(timeout) = retry_at.checked_duration_since(Instant::now()) {
+                    match requests.recv_timeout(timeout) {
+                        Ok(requested) => timestamp = timestamp.max(requested),
+                        Err(mpsc::RecvTimeoutError::Timeout) => break,
+                        Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Saves timestamp two save intervals ahead of `timestamp`, unless it is already saved.
+    ///
+    /// Reservation is extended only if the store has succeeded.
+    fn save(&self, timestamp: u64) -> Result<()> {
+        if timestamp < self.refresh_at.load(atomic::Ordering::Acquire) {
+            self.is_pending.store(false, atomic::Ordering::Release);
+            return Ok(());
+        }
+
+        // Raw timestamps are measured in 10 microseconds
+        let interval = DEFAULT_TIMESTAMP_SAVE_INTERVAL.as_micros() as u64 / 10;
+        let reserved = timestamp + 2 * interval;
+        let result = self.store.save(MavTimestamp::from_raw_u64(reserved));
+
+        if result.is_ok() {
+            self.reserved.fetch_max(reserved, atomic::Ordering::AcqRel);
+            self.refresh_at
+                .fetch_max(timestamp + interval, atomic::Ordering::AcqRel);
+            self.is_pending.store(false, atomic::Ordering::Release);
+        }
+        self.complete(result.clone().err());
+
+        result
+    }
+
+    /// Records the outcome of a save attempt and wakes up signers, that wait for it.
+    fn complete(&self, error: Option<Error>) {
+        let mut outcome = self.outcome();
+        outcome.attempts += 1;
+        outcome.error = error;
+        self.saved.notify_all();
+    }
+
+    fn outcome(&self) -> MutexGuard<'_, SaveOutcome> {
+        match self.outcome.lock() {
+            Ok(outcome) => outcome,
+            // Outcome is replaced as a whole, so it is consistent even if some thread panicked
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Wakes up waiting signers, if worker thread stops unexpectedly.
+struct WorkerGuard<'a>(&'a PersistenceState);
+
+impl Drop for WorkerGuard<'_> {
+    fn drop(&mut self) {
+        self.0.complete(Some(SyncError::Disconnected.into()));
+    }
 }
 
 impl Default for UniqueMavTimestamp {
@@ -486,6 +1199,63 @@ impl<'de> serde::Deserialize<'de> for UniqueMavTimestamp {
     }
 }
 
+impl SignerStats {
+    /// Unsigned frames, that were signed with the main key.
+    pub fn signed(&self) -> u64 {
+        self.signed
+    }
+
+    /// Signed frames, that were re-signed with the main key.
+    pub fn resigned(&self) -> u64 {
+        self.resigned
+    }
+
+    /// Signed frames, that passed with their original signature.
+    pub fn verified(&self) -> u64 {
+        self.verified
+    }
+
+    /// Frames, that passed without signature.
+    pub fn unsigned(&self) -> u64 {
+        self.unsigned
+    }
+
+    /// Rejected frames.
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+}
+
+impl SigningCounters {
+    fn snapshot(&self) -> SignerStats {
+        SignerStats {
+            signed: self.0.signed.load(atomic::Ordering::Relaxed),
+            resigned: self.0.resigned.load(atomic::Ordering::Relaxed),
+            verified: self.0.verified.load(atomic::Ordering::Relaxed),
+            unsigned: self.0.unsigned.load(atomic::Ordering::Relaxed),
+            rejected: self.0.rejected.load(atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+impl Debug for SigningCounters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SigningCounters")
+            .field(&self.snapshot())
+            .finish()
+    }
+}
+
+impl Display for SignatureRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SignatureRejection::Unsigned => "frame is not signed",
+            SignatureRejection::InvalidSignature => "signature does not match",
+            SignatureRejection::UnknownLink => "unknown link",
+        })
+    }
+}
+
 impl IntoFrameSigner for FrameSigner {
     /// Passes [`FrameSigner`] without change.
     fn into_message_signer(self) -> FrameSigner {
@@ -531,8 +1301,10 @@ pub mod builder {
         incoming: Option<SignStrategy>,
         outgoing: Option<SignStrategy>,
         unknown_links: Option<SignStrategy>,
+        peers: HashMap<SystemId, SignStrategy>,
         links: HashMap<SignedLinkId, SecretKey>,
         exclude: HashSet<MessageId>,
+        timestamp: Option<UniqueMavTimestamp>,
     }
 
     impl FrameSignerBuilder<NoLinkId, NoSecretKey> {
@@ -560,8 +1332,10 @@ pub mod builder {
                 incoming: None,
                 outgoing: None,
                 unknown_links: None,
+                peers: Default::default(),
                 links: Default::default(),
                 exclude: Default::default(),
+                timestamp: None,
             }
         }
     }
@@ -575,8 +1349,10 @@ pub mod builder {
                 incoming: self.incoming,
                 outgoing: self.outgoing,
                 unknown_links: self.unknown_links,
+                peers: self.peers,
                 links: self.links,
                 exclude: self.exclude,
+                timestamp: self.timestamp,
             }
         }
     }
@@ -590,8 +1366,10 @@ pub mod builder {
                 incoming: self.incoming,
                 outgoing: self.outgoing,
                 unknown_links: self.unknown_links,
+                peers: self.peers,
                 links: self.links,
                 exclude: self.exclude,
+                timestamp: self.timestamp,
             }
         }
     }
@@ -613,6 +1391,24 @@ pub mod builder {
             }
         }
 
+        /// Overrides incoming strategy for a peer with specified `system_id`.
+        ///
+        /// Use [`SignStrategy::Strict`] to require signing from a particular peer, or
+        /// [`SignStrategy::Proxy`] to exempt a legacy device, that can't sign its frames. Frames of
+        /// other peers are processed according to [`FrameSigner::incoming`].
+        ///
+        ///
+        /// **⚠** System `ID` of unsigned frames is not authenticated. Any sender can claim a `system_id`
+        /// of a peer with a relaxed strategy (such as [`SignStrategy::Proxy`]) and pass its frames
+        /// through, even if the default incoming strategy is [`SignStrategy::Strict`]. Relax signing
+        /// requirements for particular peers only on links where senders are trusted.
+        ///
+        /// See [`FrameSigner::incoming_for`].
+        pub fn peer_incoming(mut self, system_id: SystemId, strategy: SignStrategy) -> Self {
+            self.peers.insert(system_id, strategy);
+            self
+        }
+
         /// <sup>`⍚` |</sup>
         /// Set [`FrameSigner::unknown_links`].
         ///
@@ -634,6 +1430,33 @@ pub mod builder {
                 ..self
             }
         }
+
+        /// Persist signing timestamps in a [`TimestampStore`].
+        ///
+        /// Restores timestamp from the store immediately, signer then periodically saves it. See
+        /// [`UniqueMavTimestamp::restore`] for details.
+        ///
+        /// Returns an error, if timestamp can't be loaded from the store.
+        ///
+        /// # Usage
+        ///
+        /// ```rust,no_run
+        /// use maviola::protocol::FileTimestampStore;
+        /// use maviola::prelude::*;
+        ///
+        /// let signer = FrameSigner::builder()
+        ///     .link_id(1)
+        ///     .key("main key")
+        ///     .timestamp_store(FileTimestampStore::new("signing.timestamp"))
+        ///     .unwrap()
+        ///     .build();
+        /// ```
+        pub fn timestamp_store(self, store: impl TimestampStore) -> Result<Self> {
+            Ok(Self {
+                timestamp: Some(UniqueMavTimestamp::restore(store)?),
+                ..self
+            })
+        }
     }
 
     impl FrameSignerBuilder<HasLinkId, HasSecretKey> {
@@ -664,9 +1487,11 @@ pub mod builder {
                 incoming: self.incoming.unwrap_or_default(),
                 outgoing: self.outgoing.unwrap_or_default(),
                 unknown_links: self.unknown_links.unwrap_or(SignStrategy::Strict),
+                peers: self.peers,
                 links: self.links,
-                last_timestamp: Default::default(),
+                last_timestamp: self.timestamp.unwrap_or_default(),
                 exclude: self.exclude,
+                counters: Default::default(),
             }
         }
     }
@@ -0,0 +1,615 @@
+//! Remote MAVLink systems tracked by a node.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::dialects::minimal::enums::{MavAutopilot, MavModeFlag, MavState, MavType};
+use crate::dialects::minimal::messages::Heartbeat;
+use crate::protocol::{ComponentId, MavLinkVersion, Peer, Sequence, SystemId};
+
+use crate::prelude::*;
+
+/// Kind of a MAVLink component derived from `type` and `autopilot` fields of its heartbeats.
+///
+/// Peripheral types (cameras, gimbals, etc.) are recognized first. Remaining components are
+/// considered to be autopilots, if they report an autopilot other than `MAV_AUTOPILOT_INVALID`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    /// Flight controller of a vehicle.
+    Autopilot,
+    /// Ground control station.
+    GroundControlStation,
+    /// Onboard (companion) computer.
+    OnboardController,
+    /// Camera.
+    Camera,
+    /// Gimbal.
+    Gimbal,
+    /// Antenna tracker.
+    AntennaTracker,
+    /// `ADS-B`, `FLARM`, or Open Drone `ID` transceiver.
+    Transponder,
+    /// Other peripheral, like battery, servo, parachute, or `GPS`.
+    Peripheral,
+    /// Component, that does not report its kind.
+    Unknown,
+}
+
+/// MAVLink component of a [`RemoteSystem`] discovered by its heartbeats.
+///
+/// Once enabled by `Node::system_events`, node emits `Event::NewComponent` when a new component
+/// appears. Components of a system are
+/// available through [`RemoteSystem::remote_components`] or `Node::components`.
+#[derive(Clone, Debug)]
+pub struct RemoteComponent {
+    system_id: SystemId,
+    component_id: ComponentId,
+    kind: ComponentKind,
+    mav_type: MavType,
+    autopilot: MavAutopilot,
+    last_heartbeat: SystemTime,
+}
+
+/// State of a remote MAVLink system (i.e. a vehicle) aggregated from its heartbeats.
+///
+/// A system may consist of several components (autopilot, camera, companion computer). Vehicle
+/// state, such as mode, type, and autopilot, is taken from heartbeats of the primary component.
+/// The first component, that reports an autopilot other than `MAV_AUTOPILOT_INVALID`, becomes
+/// primary. Until then, the first component, that sent a heartbeat, is used.
+///
+/// Remote systems are available through `Node::system` and `Node::systems`. Once enabled by
+/// `Node::system_events`, node emits `Event::SystemChanged` when system appears or its state
+/// changes. Systems are forgotten, once all their components are lost.
+#[derive(Clone, Debug)]
+pub struct RemoteSystem {
+    system_id: SystemId,
+    component_id: ComponentId,
+    components: BTreeMap<ComponentId, RemoteComponent>,
+    mav_type: MavType,
+    autopilot: MavAutopilot,
+    base_mode: MavModeFlag,
+    custom_mode: u32,
+    system_status: MavState,
+    mavlink_version: u8,
+    version: MavLinkVersion,
+    last_sequence: Sequence,
+    last_heartbeat: SystemTime,
+}
+
+impl ComponentKind {
+    /// Derives component kind from its `heartbeat`.
+    pub fn from_heartbeat(heartbeat: &Heartbeat) -> Self {
+        match heartbeat.type_ {
+            MavType::Gcs => ComponentKind::GroundControlStation,
+            MavType::OnboardController => ComponentKind::OnboardController,
+            MavType::Camera => ComponentKind::Camera,
+            MavType::Gimbal => ComponentKind::Gimbal,
+            MavType::AntennaTracker => ComponentKind::AntennaTracker,
+            MavType::Adsb | MavType::Flarm | MavType::Odid => ComponentKind::Transponder,
+            MavType::ChargingStation
+            | MavType::Servo
+            | MavType::Battery
+            | MavType::Parachute
+            | MavType::Log
+            | MavType::Osd
+            | MavType::Imu
+            | MavType::Gps
+            | MavType::Winch => ComponentKind::Peripheral,
+            _ if heartbeat.autopilot as u8 != MavAutopilot::Invalid as u8 => {
+                ComponentKind::Autopilot
+            }
+            _ => ComponentKind::Unknown,
+        }
+    }
+}
+
+impl RemoteComponent {
+    fn new<V: MaybeVersioned>(frame: &Frame<V>, heartbeat: &Heartbeat) -> Self {
+        Self {
+            system_id: frame.system_id(),
+            component_id: frame.component_id(),
+            kind: ComponentKind::from_heartbeat(heartbeat),
+            mav_type: heartbeat.type_,
+            autopilot: heartbeat.autopilot,
+            last_heartbeat: SystemTime::now(),
+        }
+    }
+
+    /// MAVLink system `ID`.
+    pub fn system_id(&self) -> SystemId {
+        self.system_id
+    }
+
+    /// MAVLink component `ID`.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Kind of the component.
+    pub fn kind(&self) -> ComponentKind {
+        self.kind
+    }
+
+    /// Component type (`MAV_TYPE`) reported in the last heartbeat.
+    pub fn mav_type(&self) -> MavType {
+        self.mav_type
+    }
+
+    /// Autopilot type (`MAV_AUTOPILOT`) reported in the last heartbeat.
+    pub fn autopilot(&self) -> MavAutopilot {
+        self.autopilot
+    }
+
+    /// Time, when the last heartbeat of the component was received.
+    pub fn last_heartbeat(&self) -> SystemTime {
+        self.last_heartbeat
+    }
+
+    /// Updates state from a heartbeat.
+    fn apply(&mut self, heartbeat: &Heartbeat) {
+        self.kind = ComponentKind::from_heartbeat(heartbeat);
+        self.mav_type = heartbeat.type_;
+        self.autopilot = heartbeat.autopilot;
+        self.last_heartbeat = SystemTime::now();
+    }
+}
+
+impl RemoteSystem {
+    fn new<V: MaybeVersioned>(frame: &Frame<V>, heartbeat: &Heartbeat) -> Self {
+        let component = RemoteComponent::new(frame, heartbeat);
+
+        Self {
+            system_id: frame.system_id(),
+            component_id: frame.component_id(),
+            components: BTreeMap::from([(frame.component_id(), component)]),
+            mav_type: heartbeat.type_,
+            autopilot: heartbeat.autopilot,
+            base_mode: heartbeat.base_mode,
+            custom_mode: heartbeat.custom_mode,
+            system_status: heartbeat.system_status,
+            mavlink_version: heartbeat.mavlink_version,
+            version: frame.version(),
+            last_sequence: frame.sequence(),
+            last_heartbeat: SystemTime::now(),
+        }
+    }
+
+    /// MAVLink system `ID`.
+    pub fn system_id(&self) -> SystemId {
+        self.system_id
+    }
+
+    /// Component `ID` of the primary component, which reports the state of the system.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Component `ID`s of all active components of the system.
+    pub fn components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.keys().copied()
+    }
+
+    /// Active components of the system ordered by their `ID`s.
+    pub fn remote_components(&self) -> impl Iterator<Item = &RemoteComponent> {
+        self.components.values()
+    }
+
+    /// Active component with the specified `ID`, if any.
+    pub fn component(&self, component_id: ComponentId) -> Option<&RemoteComponent> {
+        self.components.get(&component_id)
+    }
+
+    /// Vehicle type (`MAV_TYPE`).
+    pub fn mav_type(&self) -> MavType {
+        self.mav_type
+    }
+
+    /// Autopilot type (`MAV_AUTOPILOT`).
+    pub fn autopilot(&self) -> MavAutopilot {
+        self.autopilot
+    }
+
+    /// System mode bitmap (`MAV_MODE_FLAG`).
+    pub fn base_mode(&self) -> MavModeFlag {
+        self.base_mode
+    }
+
+    /// Autopilot-specific flight mode.
+    pub fn custom_mode(&self) -> u32 {
+        self.custom_mode
+    }
+
+    /// System status (`MAV_STATE`).
+    pub fn system_status(&self) -> MavState {
+        self.system_status
+    }
+
+    /// Returns `true`, if system reports that it is armed.
+    pub fn is_armed(&self) -> bool {
+        self.base_mode.contains(MavModeFlag::SAFETY_ARMED)
+    }
+
+    /// MAVLink version reported in the `mavlink_version` field of a heartbeat.
+    pub fn mavlink_version(&self) -> u8 {
+        self.mavlink_version
+    }
+
+    /// Protocol version of frames sent by the primary component.
+    pub fn version(&self) -> MavLinkVersion {
+        self.version
+    }
+
+    /// Sequence of the last frame received from the primary component.
+    pub fn last_sequence(&self) -> Sequence {
+        self.last_sequence
+    }
+
+    /// Time, when the last heartbeat of the primary component was received.
+    pub fn last_heartbeat(&self) -> SystemTime {
+        self.last_heartbeat
+    }
+
+    /// Updates state from the heartbeat of the primary component.
+    fn apply<V: MaybeVersioned>(&mut self, frame: &Frame<V>, heartbeat: &Heartbeat) {
+        self.component_id = frame.component_id();
+        self.mav_type = heartbeat.type_;
+        self.autopilot = heartbeat.autopilot;
+        self.base_mode = heartbeat.base_mode;
+        self.custom_mode = heartbeat.custom_mode;
+        self.system_status = heartbeat.system_status;
+        self.mavlink_version = heartbeat.mavlink_version;
+        self.version = frame.version();
+        self.last_sequence = frame.sequence();
+        self.last_heartbeat = SystemTime::now();
+    }
+
+    /// Returns `true`, if states differ in anything except sequence and time of the last
+    /// heartbeat.
+    fn differs(&self, other: &Self) -> bool {
+        self.component_id != other.component_id
+            || self.mav_type as u8 != other.mav_type as u8
+            || self.autopilot as u8 != other.autopilot as u8
+            || self.base_mode.bits() != other.base_mode.bits()
+            || self.custom_mode != other.custom_mode
+            || self.system_status as u8 != other.system_status as u8
+            || self.mavlink_version != other.mavlink_version
+            || self.version != other.version
+    }
+}
+
+/// <sup>⛔</sup>
+/// Changes caused by a heartbeat handled by [`SystemRegistry`].
+#[derive(Debug, Default)]
+pub(crate) struct SystemUpdate {
+    /// Component, that sent its first heartbeat, if reporting of changes is enabled.
+    pub(crate) new_component: Option<RemoteComponent>,
+    /// Snapshot of a system, that is new or changed its state, if reporting of changes is enabled.
+    pub(crate) changed_system: Option<RemoteSystem>,
+}
+
+/// <sup>⛔</sup>
+/// Registry of [`RemoteSystem`]s shared between node handlers.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SystemRegistry {
+    systems: Arc<RwLock<HashMap<SystemId, RemoteSystem>>>,
+    events: Arc<AtomicBool>,
+}
+
+impl SystemRegistry {
+    /// Returns a snapshot of a system with the specified `ID`.
+    pub(crate) fn get(&self, system_id: SystemId) -> Option<RemoteSystem> {
+        self.read().get(&system_id).cloned()
+    }
+
+    /// Returns a snapshot of all known systems ordered by their `ID`s.
+    pub(crate) fn list(&self) -> Vec<RemoteSystem> {
+        let mut systems: Vec<RemoteSystem> = self.read().values().cloned().collect();
+        systems.sort_by_key(RemoteSystem::system_id);
+        systems
+    }
+
+    /// Returns snapshots of active components of a system with the specified `ID` ordered by
+    /// their `ID`s.
+    pub(crate) fn components(&self, system_id: SystemId) -> Vec<RemoteComponent> {
+        match self.read().get(&system_id) {
+            Some(system) => system.remote_components().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Enables or disables reporting of system changes.
+    pub(crate) fn set_events(&self, enabled: bool) {
+        self.events.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Updates system and component state from a heartbeat.
+    ///
+    /// Returns a new component, if this is its first heartbeat, and a snapshot of the system, if it
+    /// is new or its state has changed. Changes are returned only if reporting is enabled.
+    pub(crate) fn handle_heartbeat<V: MaybeVersioned>(
+        &self,
+        frame: &Frame<V>,
+        heartbeat: &Heartbeat,
+    ) -> SystemUpdate {
+        let mut systems = self.write();
+
+        let system = match systems.get_mut(&frame.system_id()) {
+            Some(system) => system,
+            None => {
+                let system = RemoteSystem::new(frame, heartbeat);
+                systems.insert(system.system_id, system.clone());
+                let component = system.components[&frame.component_id()].clone();
+                return SystemUpdate {
+                    new_component: self.report(component),
+                    changed_system: self.report(system),
+                };
+            }
+        };
+
+        let mut update = SystemUpdate::default();
+        match system.components.get_mut(&frame.component_id()) {
+            Some(component) => component.apply(heartbeat),
+            None => {
+                let component = RemoteComponent::new(frame, heartbeat);
+                system
+                    .components
+                    .insert(component.component_id, component.clone());
+                update.new_component = self.report(component);
+            }
+        }
+
+        let is_primary = system.component_id == frame.component_id()
+            || (system.autopilot as u8 == MavAutopilot::Invalid as u8
+                && heartbeat.autopilot as u8 != MavAutopilot::Invalid as u8);
+        if !is_primary {
+            return update;
+        }
+
+        let previous = system.clone();
+        system.apply(frame, heartbeat);
+
+        if system.differs(&previous) {
+            update.changed_system = self.report(system.clone());
+        }
+        update
+    }
+
+    /// Records sequence of a frame sent by the primary component of a known system.
+    pub(crate) fn handle_frame<V: MaybeVersioned>(&self, frame: &Frame<V>) {
+        let mut systems = self.write();
+        if let Some(system) = systems.get_mut(&frame.system_id()) {
+            if system.component_id == frame.component_id() {
+                system.last_sequence = frame.sequence();
+            }
+        }
+    }
+
+    /// Removes lost components and forgets systems without active components.
+    pub(crate) fn handle_lost_peers<'a>(&self, peers: impl IntoIterator<Item = &'a Peer>) {
+        let mut systems = self.write();
+        for peer in peers {
+            if let Some(system) = systems.get_mut(&peer.system_id()) {
+                system.components.remove(&peer.component_id());
+                if system.components.is_empty() {
+                    systems.remove(&peer.system_id());
+                }
+            }
+        }
+    }
+
+    /// Forgets all systems.
+    pub(crate) fn clear(&self) {
+        self.write().clear();
+    }
+
+    fn report<T>(&self, change: T) -> Option<T> {
+        if self.events.load(Ordering::Relaxed) {
+            Some(change)
+        } else {
+            None
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<SystemId, RemoteSystem>> {
+        match self.systems.read() {
+            Ok(systems) => systems,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<SystemId, RemoteSystem>> {
+        match self.systems.write() {
+            Ok(systems) => systems,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod system_tests {
+    use super::*;
+
+    use crate::protocol::Endpoint;
+
+    fn heartbeat(autopilot: MavAutopilot, custom_mode: u32) -> Heartbeat {
+        Heartbeat {
+            type_: MavType::Quadrotor,
+            autopilot,
+            custom_mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn system_state_is_tracked() {
+        let registry = SystemRegistry::default();
+        registry.set_events(true);
+        let autopilot = Endpoint::v2(MavLinkId::new(42, 1));
+
+        let hb = heartbeat(MavAutopilot::Ardupilotmega, 0);
+        let system = registry
+            .handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb)
+            .changed_system
+            .unwrap();
+        assert_eq!(system.system_id(), 42);
+        assert_eq!(system.autopilot() as u8, MavAutopilot::Ardupilotmega as u8);
+        assert_eq!(system.version(), MavLinkVersion::V2);
+
+        // Unchanged state is not reported
+        assert!(registry
+            .handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb)
+            .changed_system
+            .is_none());
+
+        // Mode change is reported
+        let hb = heartbeat(MavAutopilot::Ardupilotmega, 5);
+        let system = registry
+            .handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb)
+            .changed_system
+            .unwrap();
+        assert_eq!(system.custom_mode(), 5);
+
+        // Sequence is updated by any frame of the primary component
+        let frame = autopilot.next_frame(&hb).unwrap();
+        registry.handle_frame(&frame);
+        assert_eq!(registry.get(42).unwrap().last_sequence(), frame.sequence());
+    }
+
+    #[test]
+    fn primary_component_is_autopilot() {
+        let registry = SystemRegistry::default();
+        registry.set_events(true);
+        let camera = Endpoint::v2(MavLinkId::new(42, 100));
+        let autopilot = Endpoint::v2(MavLinkId::new(42, 1));
+
+        let hb = heartbeat(MavAutopilot::Invalid, 0);
+        registry.handle_heartbeat(&camera.next_frame(&hb).unwrap(), &hb);
+        assert_eq!(registry.get(42).unwrap().component_id(), 100);
+
+        let hb = heartbeat(MavAutopilot::Px4, 0);
+        let system = registry
+            .handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb)
+            .changed_system
+            .unwrap();
+        assert_eq!(system.component_id(), 1);
+        assert_eq!(system.components().collect::<Vec<_>>(), vec![1, 100]);
+
+        // Camera heartbeats no longer affect the state
+        let hb = heartbeat(MavAutopilot::Invalid, 0);
+        assert!(registry
+            .handle_heartbeat(&camera.next_frame(&hb).unwrap(), &hb)
+            .changed_system
+            .is_none());
+        assert_eq!(
+            registry.get(42).unwrap().autopilot() as u8,
+            MavAutopilot::Px4 as u8
+        );
+
+        // System is forgotten, once all components are lost
+        registry.handle_lost_peers(&[Peer::new(42, 100)]);
+        assert!(registry.get(42).is_some());
+        registry.handle_lost_peers(&[Peer::new(42, 1)]);
+        assert!(registry.get(42).is_none());
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn components_are_discovered() {
+        let registry = SystemRegistry::default();
+        let autopilot = Endpoint::v2(MavLinkId::new(42, 1));
+        let camera = Endpoint::v2(MavLinkId::new(42, 100));
+        let gimbal = Endpoint::v2(MavLinkId::new(42, 154));
+
+        // Components are tracked, but not reported until enabled
+        let hb = Heartbeat {
+            type_: MavType::Gimbal,
+            ..Default::default()
+        };
+        let update = registry.handle_heartbeat(&gimbal.next_frame(&hb).unwrap(), &hb);
+        assert!(update.new_component.is_none());
+        assert!(update.changed_system.is_none());
+        registry.set_events(true);
+
+        let hb = heartbeat(MavAutopilot::Ardupilotmega, 0);
+        let update = registry.handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb);
+        let component = update.new_component.unwrap();
+        assert_eq!(component.component_id(), 1);
+        assert_eq!(component.kind(), ComponentKind::Autopilot);
+
+        // Known components are not reported again
+        assert!(registry
+            .handle_heartbeat(&autopilot.next_frame(&hb).unwrap(), &hb)
+            .new_component
+            .is_none());
+
+        let hb = Heartbeat {
+            type_: MavType::Camera,
+            ..Default::default()
+        };
+        let component = registry
+            .handle_heartbeat(&camera.next_frame(&hb).unwrap(), &hb)
+            .new_component
+            .unwrap();
+        assert_eq!(component.kind(), ComponentKind::Camera);
+
+        let kinds: Vec<ComponentKind> = registry
+            .components(42)
+            .iter()
+            .map(RemoteComponent::kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ComponentKind::Autopilot,
+                ComponentKind::Camera,
+                ComponentKind::Gimbal
+            ]
+        );
+        assert!(registry.components(43).is_empty());
+
+        // Components, that were lost, are discovered again
+        registry.handle_lost_peers(&[Peer::new(42, 100)]);
+        assert!(registry
+            .handle_heartbeat(&camera.next_frame(&hb).unwrap(), &hb)
+            .new_component
+            .is_some());
+    }
+
+    #[test]
+    fn component_kinds() {
+        let kind = |type_, autopilot| {
+            ComponentKind::from_heartbeat(&Heartbeat {
+                type_,
+                autopilot,
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            kind(MavType::FixedWing, MavAutopilot::Px4),
+            ComponentKind::Autopilot
+        );
+        assert_eq!(
+            kind(MavType::Gcs, MavAutopilot::Invalid),
+            ComponentKind::GroundControlStation
+        );
+        assert_eq!(
+            kind(MavType::Gimbal, MavAutopilot::Ardupilotmega),
+            ComponentKind::Gimbal
+        );
+        assert_eq!(
+            kind(MavType::Adsb, MavAutopilot::Invalid),
+            ComponentKind::Transponder
+        );
+        assert_eq!(
+            kind(MavType::Generic, MavAutopilot::Invalid),
+            ComponentKind::Unknown
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use crate::error::SpecError;
+use crate::protocol::{
+    ComponentId, CrcExtra, IntoPayload, MavLinkVersion, MessageId, MessageSpec, Payload, SystemId,
+};
+
+use crate::prelude::*;
+
+/// Offsets of target fields in payloads of addressed messages from the `common` dialect (including
+/// `standard` and `minimal`), ordered by message `ID`.
+///
+/// Offsets take into account MAVLink field reordering. Extension fields are placed after the base
+/// fields and may be absent from truncated `MAVLink 2` payloads.
+const TARGET_FIELDS: [(MessageId, usize, Option<usize>); 70] = [
+    (4, 12, Some(13)),     // PING
+    (5, 0, None),          // CHANGE_OPERATOR_CONTROL
+    (11, 4, None),         // SET_MODE
+    (20, 2, Some(3)),      // PARAM_REQUEST_READ
+    (21, 0, Some(1)),      // PARAM_REQUEST_LIST
+    (23, 4, Some(5)),      // PARAM_SET
+    (37, 4, Some(5)),      // MISSION_REQUEST_PARTIAL_LIST
+    (38, 4, Some(5)),      // MISSION_WRITE_PARTIAL_LIST
+    (39, 32, Some(33)),    // MISSION_ITEM
+    (40, 2, Some(3)),      // MISSION_REQUEST
+    (41, 2, Some(3)),      // MISSION_SET_CURRENT
+    (43, 0, Some(1)),      // MISSION_REQUEST_LIST
+    (44, 2, Some(3)),      // MISSION_COUNT
+    (45, 0, Some(1)),      // MISSION_CLEAR_ALL
+    (47, 0, Some(1)),      // MISSION_ACK
+    (48, 12, None),        // SET_GPS_GLOBAL_ORIGIN
+    (50, 18, Some(19)),    // PARAM_MAP_RC
+    (51, 2, Some(3)),      // MISSION_REQUEST_INT
+    (54, 24, Some(25)),    // SAFETY_SET_ALLOWED_AREA
+    (66, 2, Some(3)),      // REQUEST_DATA_STREAM
+    (70, 16, Some(17)),    // RC_CHANNELS_OVERRIDE
+    (73, 32, Some(33)),    // MISSION_ITEM_INT
+    (75, 30, Some(31)),    // COMMAND_INT
+    (76, 30, Some(31)),    // COMMAND_LONG
+    (77, 8, Some(9)),      // COMMAND_ACK
+    (80, 2, Some(3)),      // COMMAND_CANCEL
+    (82, 36, Some(37)),    // SET_ATTITUDE_TARGET
+    (84, 50, Some(51)),    // SET_POSITION_TARGET_LOCAL_NED
+    (86, 50, Some(51)),    // SET_POSITION_TARGET_GLOBAL_INT
+    (110, 1, Some(2)),     // FILE_TRANSFER_PROTOCOL
+    (111, 16, Some(17)),   // TIMESYNC
+    (117, 4, Some(5)),     // LOG_REQUEST_LIST
+    (119, 10, Some(11)),   // LOG_REQUEST_DATA
+    (121, 0, Some(1)),     // LOG_ERASE
+    (122, 0, Some(1)),     // LOG_REQUEST_END
+    (123, 0, Some(1)),     // GPS_INJECT_DATA
+    (126, 79, Some(80)),   // SERIAL_CONTROL
+    (139, 41, Some(42)),   // SET_ACTUATOR_CONTROL_TARGET
+    (243, 52, None),       // SET_HOME_POSITION
+    (248, 3, Some(4)),     // V2_EXTENSION
+    (256, 8, Some(9)),     // SETUP_SIGNING
+    (258, 0, Some(1)),     // PLAY_TUNE
+    (266, 2, Some(3)),     // LOGGING_DATA
+    (267, 2, Some(3)),     // LOGGING_DATA_ACKED
+    (268, 2, Some(3)),     // LOGGING_ACK
+    (282, 32, Some(33)),   // GIMBAL_MANAGER_SET_ATTITUDE
+    (284, 30, Some(31)),   // GIMBAL_DEVICE_SET_ATTITUDE
+    (285, 38, Some(39)),   // GIMBAL_DEVICE_ATTITUDE_STATUS
+    (286, 50, Some(51)),   // AUTOPILOT_STATE_FOR_GIMBAL_DEVICE
+    (287, 20, Some(21)),   // GIMBAL_MANAGER_SET_PITCHYAW
+    (288, 20, Some(21)),   // GIMBAL_MANAGER_SET_MANUAL_CONTROL
+    (320, 2, Some(3)),     // PARAM_EXT_REQUEST_READ
+    (321, 0, Some(1)),     // PARAM_EXT_REQUEST_LIST
+    (323, 0, Some(1)),     // PARAM_EXT_SET
+    (385, 2, Some(3)),     // TUNNEL
+    (386, 4, Some(5)),     // CAN_FRAME
+    (387, 4, Some(5)),     // CANFD_FRAME
+    (388, 32, Some(33)),   // CAN_FILTER_MODIFY
+    (400, 4, Some(5)),     // PLAY_TUNE_V2
+    (401, 4, Some(5)),     // SUPPORTED_TUNES
+    (412, 4, Some(5)),     // REQUEST_EVENT
+    (413, 4, Some(5)),     // RESPONSE_EVENT_ERROR
+    (12900, 0, Some(1)),   // OPEN_DRONE_ID_BASIC_ID
+    (12901, 30, Some(31)), // OPEN_DRONE_ID_LOCATION
+    (12902, 4, Some(5)),   // OPEN_DRONE_ID_AUTHENTICATION
+    (12903, 0, Some(1)),   // OPEN_DRONE_ID_SELF_ID
+    (12904, 28, Some(29)), // OPEN_DRONE_ID_SYSTEM
+    (12905, 0, Some(1)),   // OPEN_DRONE_ID_OPERATOR_ID
+    (12915, 0, Some(1)),   // OPEN_DRONE_ID_MESSAGE_PACK
+    (12919, 16, Some(17)), // OPEN_DRONE_ID_SYSTEM_UPDATE
+];
+
+/// Location of target fields in a payload of an addressed MAVLink message.
+///
+/// Addressed messages carry `target_system` and, in most cases, `target_component` fields, that
+/// define the intended recipient. Offsets are known for messages of the `common` dialect, and its
+/// dependencies, regardless of whether this dialect is enabled.
+///
+/// # Usage
+///
+/// ```rust
+/// use maviola::protocol::TargetFields;
+///
+/// // `COMMAND_LONG` message
+/// let fields = TargetFields::of(76).unwrap();
+/// assert_eq!(fields.system_offset(), 30);
+/// assert_eq!(fields.component_offset(), Some(31));
+///
+/// // `HEARTBEAT` message is not addressed
+/// assert!(TargetFields::of(0).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetFields {
+    message_id: MessageId,
+    system: usize,
+    component: Option<usize>,
+}
+
+impl TargetFields {
+    /// Returns location of target fields for a message with specified `ID`.
+    ///
+    /// Returns [`None`] for messages, that are not addressed or unknown.
+    pub fn of(message_id: MessageId) -> Option<Self> {
+        let index = TARGET_FIELDS
+            .binary_search_by_key(&message_id, |(id, _, _)| *id)
+            .ok()?;
+        let (message_id, system, component) = TARGET_FIELDS[index];
+
+        Some(Self {
+            message_id,
+            system,
+            component,
+        })
+    }
+
+    /// Returns target of a `frame`, if its message is addressed.
+    ///
+    /// Target component is `0` for messages without `target_component` field.
+    pub fn frame_target<V: MaybeVersioned>(frame: &Frame<V>) -> Option<MavLinkId> {
+        Self::of(frame.message_id()).map(|fields| fields.target(frame.payload().bytes()))
+    }
+
+    /// Returns `true`, if `frame` is addressed to a component with specified `id`.
+    ///
+    /// Frames of messages without target fields are addressed to everyone. Zero target system or
+    /// component stands for broadcast and matches any `ID`.
+    pub fn is_addressed_to<V: MaybeVersioned>(frame: &Frame<V>, id: MavLinkId) -> bool {
+        match Self::frame_target(frame) {
+            Some(target) => {
+                (target.system == 0 || target.system == id.system)
+                    && (target.component == 0 || target.component == id.component)
+            }
+            None => true,
+        }
+    }
+
+    /// Message `ID`.
+    pub fn message_id(&self) -> MessageId {
+        self.message_id
+    }
+
+    /// Offset of `target_system` field in a payload.
+    pub fn system_offset(&self) -> usize {
+        self.system
+    }
+
+    /// Offset of `target_component` field in a payload, if message has such a field.
+    pub fn component_offset(&self) -> Option<usize> {
+        self.component
+    }
+
+    /// Reads target from `payload` bytes.
+    ///
+    /// Fields truncated from `MAVLink 2` payloads are considered to be zero, which stands for
+    /// broadcast.
+    pub fn target(&self, payload: &[u8]) -> MavLinkId {
+        MavLinkId::new(
+            payload.get(self.system).copied().unwrap_or(0),
+            self.component
+                .and_then(|offset| payload.get(offset).copied())
+                .unwrap_or(0),
+        )
+    }
+
+    /// Writes target system and component to `payload`.
+    ///
+    /// Payload is extended with zeros, if it was truncated. Component is ignored, if message has no
+    /// `target_component` field.
+    pub(crate) fn set_target(
+        &self,
+        payload: &mut Vec<u8>,
+        system_id: SystemId,
+        component_id: ComponentId,
+    ) {
+        let len = self.component.unwrap_or(self.system).max(self.system) + 1;
+        if payload.len() < len {
+            payload.resize(len, 0);
+        }
+
+        payload[self.system] = system_id;
+        if let Some(offset) = self.component {
+            payload[offset] = component_id;
+        }
+    }
+}
+
+/// <sup>⛔</sup>
+/// MAVLink message with target fields overridden by a specified target.
+///
+/// Encodes the wrapped message and writes target system and component to its payload. Target
+/// fields, that are absent from `MAVLink 1` payloads, are not written.
+pub(crate) struct AddressedMessage<'a> {
+    message: &'a dyn Message,
+    fields: TargetFields,
+    target: MavLinkId,
+}
+
+impl<'a> AddressedMessage<'a> {
+    /// Wraps `message` to be addressed to `target`.
+    ///
+    /// Returns [`None`] if message has no target fields.
+    pub(crate) fn new(message: &'a dyn Message, target: MavLinkId) -> Option<Self> {
+        Some(Self {
+            message,
+            fields: TargetFields::of(message.id())?,
+            target,
+        })
+    }
+}
+
+impl MessageSpec for AddressedMessage<'_> {
+    fn id(&self) -> MessageId {
+        self.message.id()
+    }
+
+    fn min_supported_mavlink_version(&self) -> MavLinkVersion {
+        self.message.min_supported_mavlink_version()
+    }
+
+    fn crc_extra(&self) -> CrcExtra {
+        self.message.crc_extra()
+    }
+}
+
+impl IntoPayload for AddressedMessage<'_> {
+    fn encode(&self, version: MavLinkVersion) -> core::result::Result<Payload, SpecError> {
+        let payload = self.message.encode(version)?;
+        let mut bytes = payload.bytes().to_vec();
+        self.fields
+            .set_target(&mut bytes, self.target.system, self.target.component);
+        if version == MavLinkVersion::V1 {
+            bytes.truncate(payload.bytes().len());
+        }
+        Ok(Payload::new(payload.id(), &bytes, version))
+    }
+}
+
+impl Message for AddressedMessage<'_> {}
+
+/// Rebuilds `frame` with a new sender `id` and `payload`.
+///
+/// Since MAVLink checksum covers the header, frame `crc_extra` is required. Rebuilt frames are not
+/// signed. Returns [`None`] for `MAVLink 1` frames, which payload exceeds the original length.
+pub(crate) fn readdress<V: MaybeVersioned>(
+    frame: &Frame<V>,
+    id: MavLinkId,
+    payload: &[u8],
+    crc_extra: CrcExtra,
+) -> Option<Frame<V>> {
+    let frame = frame.to_versionless();
+
+    let rebuilt = match frame.version() {
+        MavLinkVersion::V1 => {
+            if payload.len() > frame.payload().bytes().len() {
+                return None;
+            }
+            frame
+                .try_into_versioned::<V1>()
+                .ok()?
+                .to_builder()
+                .system_id(id.system)
+                .component_id(id.component)
+                .payload(payload)
+                .crc_extra(crc_extra)
+                .build()
+                .into_versionless()
+        }
+        MavLinkVersion::V2 => frame
+            .try_into_versioned::<V2>()
+            .ok()?
+            .to_builder()
+            .system_id(id.system)
+            .component_id(id.component)
+            .payload(payload)
+            .crc_extra(crc_extra)
+            .build()
+            .into_versionless(),
+    };
+
+    rebuilt.try_into_versioned::<V>().ok()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod targets_tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::Endpoint;
+
+    #[test]
+    fn target_fields_table_is_sorted() {
+        assert!(TARGET_FIELDS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn target_fields_match_generated_messages() {
+        use crate::dialects::common::enums::{MavCmd, MavResult};
+        use crate::dialects::common::messages::{CommandAck, CommandLong, ParamRequestList};
+
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let assert_target = |message: &dyn Message| {
+            let frame = endpoint.next_frame(message).unwrap();
+            assert_eq!(
+                TargetFields::frame_target(&frame),
+                Some(MavLinkId::new(7, 9)),
+                "message #{}",
+                message.id()
+            );
+        };
+
+        assert_target(&CommandLong {
+            target_system: 7,
+            target_component: 9,
+            command: MavCmd::ComponentArmDisarm,
+            confirmation: 0,
+            param1: 1.0,
+            param2: 2.0,
+            param3: 3.0,
+            param4: 4.0,
+            param5: 5.0,
+            param6: 6.0,
+            param7: 7.0,
+        });
+        assert_target(&ParamRequestList {
+            target_system: 7,
+            target_component: 9,
+        });
+        assert_target(&CommandAck {
+            command: MavCmd::ComponentArmDisarm,
+            result: MavResult::Accepted,
+            progress: 0,
+            result_param2: 0,
+            target_system: 7,
+            target_component: 9,
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn frames_are_matched_against_targets() {
+        use crate::dialects::common::messages::ParamRequestList;
+
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let request = endpoint
+            .next_frame(&ParamRequestList {
+                target_system: 2,
+                target_component: 0,
+            })
+            .unwrap();
+        let heartbeat = endpoint.next_frame(&Heartbeat::default()).unwrap();
+
+        assert!(TargetFields::is_addressed_to(
+            &request,
+            MavLinkId::new(2, 100)
+        ));
+        assert!(!TargetFields::is_addressed_to(
+            &request,
+            MavLinkId::new(3, 100)
+        ));
+        assert!(TargetFields::is_addressed_to(
+            &heartbeat,
+            MavLinkId::new(3, 100)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn messages_are_addressed() {
+        use crate::dialects::common::messages::{CommandAck, ParamRequestList};
+
+        let endpoint = Endpoint::v2(MavLinkId::new(1, 1));
+        let message = ParamRequestList::default();
+        let frame = endpoint
+            .next_frame(&AddressedMessage::new(&message, MavLinkId::new(7, 9)).unwrap())
+            .unwrap();
+        assert_eq!(
+            TargetFields::frame_target(&frame),
+            Some(MavLinkId::new(7, 9))
+        );
+        assert!(frame.decode::<crate::dialects::Common>().is_ok());
+
+        // `MAVLink 1` payloads are not extended
+        let message = CommandAck::default();
+        let frame = Endpoint::v1(MavLinkId::new(1, 1))
+            .next_frame(&AddressedMessage::new(&message, MavLinkId::new(7, 9)).unwrap())
+            .unwrap();
+        assert_eq!(
+            frame.payload().bytes().len(),
+            message.encode(MavLinkVersion::V1).unwrap().bytes().len()
+        );
+
+        assert!(AddressedMessage::new(&Heartbeat::default(), MavLinkId::new(7, 9)).is_none());
+    }
+
+    #[test]
+    fn readdress_frames() {
+        let frame = Endpoint::v2(MavLinkId::new(1, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        let rebuilt =
+            readdress(&frame, MavLinkId::new(11, 1), frame.payload().bytes(), 50).unwrap();
+
+        assert_eq!(rebuilt.system_id(), 11);
+        assert_eq!(rebuilt.component_id(), 1);
+        assert_eq!(rebuilt.sequence(), frame.sequence());
+        assert!(rebuilt.decode::<crate::dialects::Minimal>().is_ok());
+
+        let fields = TargetFields::of(76).unwrap();
+        let mut payload = vec![0u8; 10];
+        fields.set_target(&mut payload, 3, 4);
+        assert_eq!(fields.target(&payload), MavLinkId::new(3, 4));
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt::Debug;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::protocol::MavTimestamp;
+
+use crate::prelude::*;
+
+/// Persistent storage for the last message signing timestamp.
+///
+/// MAVLink [message signing](https://mavlink.io/en/guide/message_signing.html#timestamp)
+/// requires a signer to persist its timestamp across restarts. Otherwise, a system with a clock
+/// that was reset after reboot would produce timestamps, that were already used, and peers would
+/// either reject its frames or accept replayed ones.
+///
+/// Stores are plugged into [`FrameSigner`] with [`FrameSignerBuilder::timestamp_store`]. Signer
+/// restores its timestamp from a store, once store is plugged, and then periodically saves
+/// timestamps, that are ahead of the used ones by twice the
+/// [`DEFAULT_TIMESTAMP_SAVE_INTERVAL`](crate::core::consts::DEFAULT_TIMESTAMP_SAVE_INTERVAL).
+/// That means, that restored signer never generates stale timestamps, even if it wasn't shut down
+/// gracefully. If timestamp can't be loaded, then signer is not built at all.
+///
+/// Timestamps are saved by a dedicated worker thread once per save interval. Signing is blocked by
+/// [`TimestampStore::save`] only if saving takes longer than the save interval. Failed saves are
+/// retried with back off. Meanwhile, signer uses timestamps, that were already saved, and, once
+/// they are exhausted, refuses to sign frames until the store recovers.
+///
+/// [`FileTimestampStore`] is the provided implementation.
+///
+/// [`FrameSigner`]: crate::protocol::FrameSigner
+/// [`FrameSignerBuilder::timestamp_store`]: crate::protocol::FrameSignerBuilder::timestamp_store
+pub trait TimestampStore: Debug + Send + Sync + 'static {
+    /// Loads the last saved timestamp.
+    ///
+    /// Returns `None`, if timestamp was never saved.
+    fn load(&self) -> Result<Option<MavTimestamp>>;
+
+    /// Saves timestamp.
+    fn save(&self, timestamp: MavTimestamp) -> Result<()>;
+}
+
+/// File-backed [`TimestampStore`].
+///
+/// Keeps raw timestamp value (in 10 microseconds since MAVLink epoch) as a decimal number in a
+/// text file. The file is created upon the first save and is replaced atomically on each
+/// subsequent one. Timestamp is written to a temporary file, which is flushed to disk before it
+/// replaces the original one, so saved timestamps survive power loss.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::protocol::FileTimestampStore;
+/// use maviola::prelude::*;
+///
+/// let signer = FrameSigner::builder()
+///     .link_id(1)
+///     .key("main key")
+///     .timestamp_store(FileTimestampStore::new("/var/lib/maviola/signing.timestamp"))
+///     .unwrap()
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileTimestampStore {
+    path: PathBuf,
+}
+
+impl FileTimestampStore {
+    /// Creates a store, that keeps timestamp in a file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path to a file with timestamp.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn tmp_path(&self) -> PathBuf {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        PathBuf::from(tmp_path)
+    }
+
+    /// Flushes directory entries, so the renamed file is persisted.
+    ///
+    /// Directories can't be opened as files on Windows, where rename is persisted by the file
+    /// system itself.
+    #[cfg(unix)]
+    fn sync_dir(&self) -> std::io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::File::open(dir)?.sync_all()
+    }
+
+    #[cfg(not(unix))]
+    fn sync_dir(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl TimestampStore for FileTimestampStore {
+    fn load(&self) -> Result<Option<MavTimestamp>> {
+        let contents = match fs::read_to_string(self.path.as_path()) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let raw = contents.trim().parse::<u64>().map_err(|err| {
+            Error::from(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid signing timestamp in {:?}: {err}", self.path),
+            ))
+        })?;
+
+        Ok(Some(MavTimestamp::from_raw_u64(raw)))
+    }
+
+    fn save(&self, timestamp: MavTimestamp) -> Result<()> {
+        let tmp_path = self.tmp_path();
+
+        let mut file = fs::File::create(tmp_path.as_path())?;
+        file.write_all(timestamp.as_raw_u64().to_string().as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(tmp_path.as_path(), self.path.as_path())?;
+        self.sync_dir()?;
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod timestamp_store_tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant, SystemTime};
+
+    use crate::core::consts::DEFAULT_TIMESTAMP_SAVE_INTERVAL;
+    use crate::protocol::{FrameSigner, UniqueMavTimestamp};
+
+    fn store_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "maviola_timestamp_store_{name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(path.as_path());
+        path
+    }
+
+    #[test]
+    fn file_store_saves_and_loads() {
+        let path = store_path("saves_and_loads");
+        let store = FileTimestampStore::new(path.as_path());
+
+        assert!(store.load().unwrap().is_none());
+
+        store.save(MavTimestamp::from_raw_u64(42)).unwrap();
+        assert_eq!(store.load().unwrap().unwrap().as_raw_u64(), 42);
+
+        store.save(MavTimestamp::from_raw_u64(43)).unwrap();
+        assert_eq!(store.load().unwrap().unwrap().as_raw_u64(), 43);
+
+        fs::write(path.as_path(), "not a timestamp").unwrap();
+        assert!(store.load().is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn signer_restores_timestamp() {
+        let path = store_path("restores");
+        let store = FileTimestampStore::new(path.as_path());
+
+        let now = MavTimestamp::from(SystemTime::now()).as_raw_u64();
+        let future = now + 1_000_000_000;
+        store.save(MavTimestamp::from_raw_u64(future)).unwrap();
+
+        let signer = FrameSigner::builder()
+            .link_id(1)
+            .key("key")
+            .timestamp_store(store.clone())
+            .unwrap()
+            .build();
+        let timestamp = signer.next_timestamp().as_raw_u64();
+        assert!(timestamp > future);
+
+        // Saved timestamp is ahead of the used one
+        let saved = store.load().unwrap().unwrap().as_raw_u64();
+        assert!(saved > timestamp);
+        drop(signer);
+
+        // Restored signer continues the sequence
+        let timestamp = UniqueMavTimestamp::restore(store.clone()).unwrap().next();
+        assert!(timestamp.as_raw_u64() > saved);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn signer_saves_timestamps_periodically() {
+        let path = store_path("periodically");
+        let store = FileTimestampStore::new(path.as_path());
+
+        let timestamp = UniqueMavTimestamp::restore(store.clone()).unwrap();
+        let first = timestamp.next().as_raw_u64();
+        let saved = store.load().unwrap().unwrap().as_raw_u64();
+        assert!(saved > first);
+
+        // Timestamps within reserved interval do not require saving
+        timestamp.next();
+        assert_eq!(store.load().unwrap().unwrap().as_raw_u64(), saved);
+
+        // Timestamps beyond reserved interval are saved ahead again
+        timestamp.advance(MavTimestamp::from_raw_u64(saved + 1));
+        let next = timestamp.next().as_raw_u64();
+        let reserved = store.load().unwrap().unwrap().as_raw_u64();
+        assert!(reserved > next);
+        assert!(reserved - next <= 2 * DEFAULT_TIMESTAMP_SAVE_INTERVAL.as_micros() as u64 / 10);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn signer_saves_timestamps_in_background() {
+        let path = store_path("background");
+        let store = FileTimestampStore::new(path.as_path());
+        let interval = DEFAULT_TIMESTAMP_SAVE_INTERVAL.as_micros() as u64 / 10;
+
+        let timestamp = UniqueMavTimestamp::restore(store.clone()).unwrap();
+        let saved = store.load().unwrap().unwrap().as_raw_u64();
+
+        // Timestamps, that used half of the reservation, are still valid, while the next
+        // reservation is saved in background
+        timestamp.advance(MavTimestamp::from_raw_u64(saved - interval));
+        let next = timestamp.next().as_raw_u64();
+        assert!(next < saved);
+
+        let started_at = std::time::Instant::now();
+        while store.load().unwrap().unwrap().as_raw_u64() == saved {
+            assert!(started_at.elapsed() < std::time::Duration::from_secs(5));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(store.load().unwrap().unwrap().as_raw_u64() > next + interval);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    /// Store, that fails to save timestamps on demand.
+    #[derive(Debug, Default)]
+    struct FailingStore {
+        saved: Mutex<Option<MavTimestamp>>,
+        is_failing: AtomicBool,
+    }
+
+    impl TimestampStore for Arc<FailingStore> {
+        fn load(&self) -> Result<Option<MavTimestamp>> {
+            Ok(*self.saved.lock().unwrap())
+        }
+
+        fn save(&self, timestamp: MavTimestamp) -> Result<()> {
+            if self.is_failing.load(Ordering::Acquire) {
+                return Err(std::io::Error::other("store is failing").into());
+            }
+            *self.saved.lock().unwrap() = Some(timestamp);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failed_saves_stop_timestamps_until_retried() {
+        let store = Arc::new(FailingStore::default());
+
+        let timestamp = UniqueMavTimestamp::restore(store.clone()).unwrap();
+        let saved = store.load().unwrap().unwrap().as_raw_u64();
+
+        // Timestamps beyond the saved one are not issued, while store is failing
+        store.is_failing.store(true, Ordering::Release);
+        timestamp.advance(MavTimestamp::from_raw_u64(saved));
+        assert!(timestamp.try_next().is_err());
+        assert!(timestamp.try_next().is_err());
+        assert_eq!(store.load().unwrap().unwrap().as_raw_u64(), saved);
+
+        // Worker retries in background, so timestamps are issued once the store recovers
+        store.is_failing.store(false, Ordering::Release);
+        let started_at = Instant::now();
+        let next = loop {
+            if let Ok(next) = timestamp.try_next() {
+                break next.as_raw_u64();
+            }
+            assert!(started_at.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        };
+        assert!(next > saved);
+        assert!(store.load().unwrap().unwrap().as_raw_u64() > next);
+    }
+
+    #[test]
+    fn failing_store_is_not_restored() {
+        let store = Arc::new(FailingStore::default());
+        store.is_failing.store(true, Ordering::Release);
+
+        assert!(UniqueMavTimestamp::restore(store).is_err());
+    }
+
+    #[test]
+    fn unreadable_store_is_not_restored() {
+        let path = store_path("unreadable");
+        let store = FileTimestampStore::new(path.as_path());
+        fs::write(path.as_path(), "not a timestamp").unwrap();
+
+        assert!(UniqueMavTimestamp::restore(store.clone()).is_err());
+        assert!(FrameSigner::builder()
+            .link_id(1)
+            .key("key")
+            .timestamp_store(store)
+            .is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+}
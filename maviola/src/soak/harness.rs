@@ -0,0 +1,397 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::consts::{
+    DEFAULT_SOAK_MAX_MEMORY_GROWTH, DEFAULT_SOAK_SAMPLE_INTERVAL, DEFAULT_SOAK_STUCK_TIMEOUT,
+    DEFAULT_SOAK_WARMUP, SOAK_MAX_RECORDED_VIOLATIONS, SOAK_POOLING_INTERVAL,
+};
+use crate::core::node::CallbackApi;
+use crate::error::RecvTimeoutError;
+use crate::soak::invariants::{ChannelActivity, MemoryTracker, SequenceCheck, SequenceTracker};
+use crate::soak::{NodeReport, SoakReport, TrackingAllocator, Violation};
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+type Traffic<V> = Box<dyn Fn(&EdgeNode<V>) -> Result<()> + Send + Sync>;
+
+/// Node observed by a [`Soak`] test.
+///
+/// Node is built by the caller with any connection, so a topology is defined by the set of nodes
+/// added to a test. Optionally, node generates traffic by calling a closure at a fixed interval.
+pub struct SoakNode<V: Versioned> {
+    name: String,
+    node: EdgeNode<V>,
+    traffic: Option<(Duration, Traffic<V>)>,
+}
+
+impl<V: Versioned> SoakNode<V> {
+    /// Creates an observed `node` with a `name` used in reports.
+    ///
+    /// Enables [channel events](EdgeNode::channel_events) of the node, so closed channels are not
+    /// reported as stuck.
+    pub fn new(name: impl Into<String>, node: EdgeNode<V>) -> Self {
+        node.channel_events(true);
+        Self {
+            name: name.into(),
+            node,
+            traffic: None,
+        }
+    }
+
+    /// Calls `traffic` every `interval` to send messages from the node.
+    ///
+    /// Each call is counted as an outgoing frame, or as a send error, if it fails.
+    pub fn with_traffic(
+        mut self,
+        interval: Duration,
+        traffic: impl Fn(&EdgeNode<V>) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.traffic = Some((interval, Box::new(traffic)));
+        self
+    }
+}
+
+/// Soak test harness.
+///
+/// Runs observed nodes for a given duration and checks the following invariants:
+///
+/// * Memory growth over the baseline taken after warmup stays within a limit. Requires
+///   [`TrackingAllocator`] to be installed as a global allocator.
+/// * Frames from each source within each channel are never duplicated or reordered. Since sequence
+///   numbers are counted per source, a restarted source is reported as a regression as well.
+/// * Each channel keeps receiving frames, i.e. is not stuck for longer than the stuck timeout.
+///
+/// Node frames are observed through cloned event receivers, so other consumers are not affected.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use maviola::dialects::minimal::messages::Heartbeat;
+/// use maviola::soak::{Soak, SoakNode, TrackingAllocator};
+///
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new();
+///
+/// let server = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+/// let client = Node::sync::<V2>()
+///     .id(MavLinkId::new(2, 1))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+///
+/// let report = Soak::new(Duration::from_secs(4 * 60 * 60))
+///     .track_allocations(&ALLOCATOR)
+///     .add_node(SoakNode::new("server", server))
+///     .add_node(
+///         SoakNode::new("client", client).with_traffic(Duration::from_millis(10), |node| {
+///             node.send(&Heartbeat::default())
+///         }),
+///     )
+///     .run();
+///
+/// std::fs::write("soak.json", report.to_json()).unwrap();
+/// assert!(report.is_passed());
+/// ```
+pub struct Soak<V: Versioned> {
+    duration: Duration,
+    sample_interval: Duration,
+    warmup: Duration,
+    stuck_timeout: Duration,
+    max_memory_growth: usize,
+    allocator: Option<&'static TrackingAllocator>,
+    nodes: Vec<SoakNode<V>>,
+}
+
+impl<V: Versioned> Soak<V> {
+    /// Creates a test, that runs for a given `duration`.
+    ///
+    /// Uses [`DEFAULT_SOAK_SAMPLE_INTERVAL`], [`DEFAULT_SOAK_WARMUP`],
+    /// [`DEFAULT_SOAK_STUCK_TIMEOUT`], and [`DEFAULT_SOAK_MAX_MEMORY_GROWTH`].
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            sample_interval: DEFAULT_SOAK_SAMPLE_INTERVAL,
+            warmup: DEFAULT_SOAK_WARMUP,
+            stuck_timeout: DEFAULT_SOAK_STUCK_TIMEOUT,
+            max_memory_growth: DEFAULT_SOAK_MAX_MEMORY_GROWTH,
+            allocator: None,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Sets interval between memory samples and stuck channel checks.
+    pub fn with_sample_interval(mut self, sample_interval: Duration) -> Self {
+        self.sample_interval = sample_interval;
+        self
+    }
+
+    /// Sets time after the start of the test, when memory baseline is taken.
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Sets time without incoming frames, after which a channel is reported as stuck.
+    pub fn with_stuck_timeout(mut self, stuck_timeout: Duration) -> Self {
+        self.stuck_timeout = stuck_timeout;
+        self
+    }
+
+    /// Sets memory growth over the baseline in bytes, that is tolerated.
+    pub fn with_max_memory_growth(mut self, max_memory_growth: usize) -> Self {
+        self.max_memory_growth = max_memory_growth;
+        self
+    }
+
+    /// Checks memory growth using an `allocator`, that should be installed as a global allocator.
+    pub fn track_allocations(mut self, allocator: &'static TrackingAllocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Adds an observed node.
+    pub fn add_node(mut self, node: SoakNode<V>) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Runs the test and returns a report.
+    ///
+    /// Blocks for the test duration. Nodes are dropped once the test is finished.
+    pub fn run(self) -> SoakReport {
+        let start = Instant::now();
+        let stop = Arc::new(AtomicBool::new(false));
+        let violations = Arc::new(Mutex::new(Violations::new(start)));
+
+        let mut monitors = Vec::new();
+        let mut threads = Vec::new();
+        for soak_node in self.nodes {
+            let node = Arc::new(soak_node.node);
+            let monitor = Arc::new(Mutex::new(NodeMonitor::new(soak_node.name)));
+
+            threads.push(spawn_monitor(
+                &node,
+                monitor.clone(),
+                violations.clone(),
+                stop.clone(),
+            ));
+            if let Some((interval, traffic)) = soak_node.traffic {
+                threads.push(spawn_traffic(
+                    node.clone(),
+                    interval,
+                    traffic,
+                    monitor.clone(),
+                    stop.clone(),
+                ));
+            }
+            monitors.push((node, monitor));
+        }
+
+        let mut memory = MemoryTracker::new(self.max_memory_growth);
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed >= self.duration {
+                break;
+            }
+            thread::sleep(self.sample_interval.min(self.duration - elapsed));
+            let now = Instant::now();
+
+            if let Some(allocator) = self.allocator {
+                let live = allocator.live_bytes();
+                if let Some(baseline) = memory.sample(live, now - start >= self.warmup) {
+                    lock(&violations).push(|at| Violation::MemoryGrowth { at, baseline, live });
+                }
+            }
+
+            for (_, monitor) in &monitors {
+                let mut monitor = lock(monitor);
+                for (channel, idle) in monitor.activity.check(now, self.stuck_timeout) {
+                    monitor.report.stuck_channels += 1;
+                    let node = monitor.report.name.clone();
+                    lock(&violations).push(|at| Violation::StuckChannel {
+                        at,
+                        node,
+                        channel: format!("{channel:?}"),
+                        idle,
+                    });
+                }
+            }
+        }
+
+        stop.store(true, Ordering::Release);
+        for thread in threads {
+            let _ = thread.join();
+        }
+
+        let violations = lock(&violations);
+        SoakReport {
+            duration: start.elapsed(),
+            nodes: monitors
+                .iter()
+                .map(|(_, monitor)| lock(monitor).report.clone())
+                .collect(),
+            memory: self.allocator.map(|_| memory.report()),
+            violations: violations.recorded.clone(),
+            violation_count: violations.count,
+        }
+    }
+}
+
+struct NodeMonitor {
+    report: NodeReport,
+    sequences: SequenceTracker,
+    activity: ChannelActivity,
+}
+
+impl NodeMonitor {
+    fn new(name: String) -> Self {
+        Self {
+            report: NodeReport {
+                name,
+                ..Default::default()
+            },
+            sequences: SequenceTracker::default(),
+            activity: ChannelActivity::default(),
+        }
+    }
+}
+
+struct Violations {
+    start: Instant,
+    recorded: Vec<Violation>,
+    count: usize,
+}
+
+impl Violations {
+    fn new(start: Instant) -> Self {
+        Self {
+            start,
+            recorded: Vec::new(),
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, violation: impl FnOnce(Duration) -> Violation) {
+        let violation = violation(self.start.elapsed());
+        log::warn!("soak test violation: {violation:?}");
+
+        self.count += 1;
+        if self.recorded.len() < SOAK_MAX_RECORDED_VIOLATIONS {
+            self.recorded.push(violation);
+        }
+    }
+}
+
+fn spawn_monitor<V: Versioned>(
+    node: &EdgeNode<V>,
+    monitor: Arc<Mutex<NodeMonitor>>,
+    violations: Arc<Mutex<Violations>>,
+    stop: Arc<AtomicBool>,
+) -> thread::JoinHandle<()> {
+    let receiver = node.receiver().clone();
+
+    thread::spawn(move || {
+        while !stop.load(Ordering::Acquire) {
+            let event = match receiver.recv_timeout(SOAK_POOLING_INTERVAL) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Lagged(skipped)) => {
+                    let mut monitor = lock(&monitor);
+                    monitor.report.lagged += skipped;
+                    monitor.sequences.reset();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            let now = Instant::now();
+            let mut monitor = lock(&monitor);
+            match event {
+                Event::Frame(frame, callback) => {
+                    let channel = callback.info();
+                    monitor.report.frames_in += 1;
+                    monitor.activity.record(channel, now);
+
+                    let source = MavLinkId::new(frame.system_id(), frame.component_id());
+                    match monitor
+                        .sequences
+                        .check(channel.id(), source, frame.sequence())
+                    {
+                        SequenceCheck::Ok => {}
+                        SequenceCheck::Gap => monitor.report.sequence_gaps += 1,
+                        SequenceCheck::Regression(previous) => {
+                            monitor.report.sequence_regressions += 1;
+                            let node = monitor.report.name.clone();
+                            lock(&violations).push(|at| Violation::SequenceRegression {
+                                at,
+                                node,
+                                channel: format!("{channel:?}"),
+                                source,
+                                previous,
+                                sequence: frame.sequence(),
+                            });
+                        }
+                    }
+                }
+                Event::Invalid(_, _, callback) => monitor.activity.record(callback.info(), now),
+                Event::ChannelOpened(channel) => monitor.activity.open(&channel, now),
+                Event::ChannelClosed(channel) => {
+                    monitor.activity.close(channel.id());
+                    monitor.sequences.forget_channel(channel.id());
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+fn spawn_traffic<V: Versioned>(
+    node: Arc<EdgeNode<V>>,
+    interval: Duration,
+    traffic: Traffic<V>,
+    monitor: Arc<Mutex<NodeMonitor>>,
+    stop: Arc<AtomicBool>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut next = Instant::now();
+        while !stop.load(Ordering::Acquire) {
+            let now = Instant::now();
+            if now < next {
+                thread::sleep((next - now).min(SOAK_POOLING_INTERVAL));
+                continue;
+            }
+            next += interval;
+
+            let result = traffic(&node);
+            let mut monitor = lock(&monitor);
+            match result {
+                Ok(()) => monitor.report.frames_out += 1,
+                Err(err) => {
+                    log::trace!(
+                        "[{}] soak traffic can't be sent: {err:?}",
+                        monitor.report.name
+                    );
+                    monitor.report.send_errors += 1;
+                }
+            }
+        }
+    })
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // Monitors only count events, so state is still usable after a panic in another thread
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::core::io::{ChannelId, ChannelInfo};
+use crate::soak::MemoryReport;
+
+use crate::prelude::*;
+
+/// Result of a sequence number check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SequenceCheck {
+    /// Frame has the expected sequence number or it is the first frame of a source.
+    Ok,
+    /// Some frames were skipped, most likely lost in transport.
+    Gap,
+    /// Frame is a duplicate or arrived out of order.
+    Regression(u8),
+}
+
+/// Tracks sequence numbers of frames per channel and source.
+///
+/// Sequence numbers wrap at `255`. A frame, that is up to half of the sequence space behind the
+/// previous one, is considered a regression.
+#[derive(Debug, Default)]
+pub(super) struct SequenceTracker {
+    last: HashMap<(ChannelId, MavLinkId), u8>,
+}
+
+impl SequenceTracker {
+    pub(super) fn check(
+        &mut self,
+        channel: ChannelId,
+        source: MavLinkId,
+        sequence: u8,
+    ) -> SequenceCheck {
+        let previous = match self.last.get_mut(&(channel, source)) {
+            Some(previous) => previous,
+            None => {
+                self.last.insert((channel, source), sequence);
+                return SequenceCheck::Ok;
+            }
+        };
+
+        match sequence.wrapping_sub(*previous) {
+            1 => {
+                *previous = sequence;
+                SequenceCheck::Ok
+            }
+            2..=127 => {
+                *previous = sequence;
+                SequenceCheck::Gap
+            }
+            // Previous number is kept, so that a single late frame is reported only once
+            _ => SequenceCheck::Regression(*previous),
+        }
+    }
+
+    pub(super) fn forget_channel(&mut self, channel: ChannelId) {
+        self.last.retain(|(id, _), _| *id != channel);
+    }
+
+    /// Forgets all sources, for example, when monitor has skipped some frames.
+    pub(super) fn reset(&mut self) {
+        self.last.clear();
+    }
+}
+
+#[derive(Debug)]
+struct ChannelState {
+    info: ChannelInfo,
+    last_active: Instant,
+    stuck: bool,
+}
+
+/// Tracks activity of channels to detect ones, that stopped receiving frames.
+#[derive(Debug, Default)]
+pub(super) struct ChannelActivity {
+    channels: HashMap<ChannelId, ChannelState>,
+}
+
+impl ChannelActivity {
+    /// Records an incoming frame from a channel, opening the channel if it is not tracked yet.
+    pub(super) fn record(&mut self, info: &ChannelInfo, now: Instant) {
+        let state = self
+            .channels
+            .entry(info.id())
+            .or_insert_with(|| ChannelState {
+                info: info.clone(),
+                last_active: now,
+                stuck: false,
+            });
+        state.last_active = now;
+        state.stuck = false;
+    }
+
+    pub(super) fn open(&mut self, info: &ChannelInfo, now: Instant) {
+        self.channels.insert(
+            info.id(),
+            ChannelState {
+                info: info.clone(),
+                last_active: now,
+                stuck: false,
+            },
+        );
+    }
+
+    pub(super) fn close(&mut self, id: ChannelId) {
+        self.channels.remove(&id);
+    }
+
+    /// Returns channels, that became stuck since the previous check, with their idle time.
+    ///
+    /// A stuck channel is reported again only after it receives a frame.
+    pub(super) fn check(
+        &mut self,
+        now: Instant,
+        timeout: Duration,
+    ) -> Vec<(ChannelInfo, Duration)> {
+        let mut stuck = Vec::new();
+        for state in self.channels.values_mut() {
+            let idle = now.saturating_duration_since(state.last_active);
+            if !state.stuck && idle > timeout {
+                state.stuck = true;
+                stuck.push((state.info.clone(), idle));
+            }
+        }
+        stuck
+    }
+}
+
+/// Tracks memory samples against the baseline.
+#[derive(Debug)]
+pub(super) struct MemoryTracker {
+    max_growth: usize,
+    report: MemoryReport,
+    exceeded: bool,
+}
+
+impl MemoryTracker {
+    pub(super) fn new(max_growth: usize) -> Self {
+        Self {
+            max_growth,
+            report: MemoryReport::default(),
+            exceeded: false,
+        }
+    }
+
+    /// Records the number of `live` bytes.
+    ///
+    /// Baseline is taken from the first sample after warmup. Returns the baseline, once growth
+    /// over the baseline exceeds the limit. Growth is reported again only after memory returns
+    /// within the limit.
+    pub(super) fn sample(&mut self, live: usize, after_warmup: bool) -> Option<usize> {
+        self.report.samples += 1;
+        self.report.last = live;
+        self.report.peak = self.report.peak.max(live);
+
+        if !after_warmup {
+            return None;
+        }
+        let baseline = *self.report.baseline.get_or_insert(live);
+
+        let exceeded = live.saturating_sub(baseline) > self.max_growth;
+        let is_new = exceeded && !self.exceeded;
+        self.exceeded = exceeded;
+        if is_new {
+            Some(baseline)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn report(&self) -> MemoryReport {
+        self.report.clone()
+    }
+}
@@ -0,0 +1,171 @@
+//! # Soak testing
+//!
+//! Harness for long-running tests of synchronous nodes, that checks invariants while nodes
+//! exchange traffic. It helps to qualify releases of applications, such as gateways and routers,
+//! built with Maviola.
+//!
+//! A topology is defined by a set of [`SoakNode`]s, each wraps an edge node built by the caller
+//! and an optional traffic generator. [`Soak`] runs these nodes for a given duration and produces a
+//! [`SoakReport`] with traffic statistics and detected [`Violation`]s. The report can be encoded as
+//! JSON by [`SoakReport::to_json`].
+//!
+//! Memory growth is checked only if [`TrackingAllocator`] is installed as a global allocator of
+//! the test binary.
+//!
+//! This module is available under `soak` feature.
+
+mod alloc;
+mod harness;
+mod invariants;
+mod report;
+
+pub use alloc::TrackingAllocator;
+pub use harness::{Soak, SoakNode};
+pub use report::{MemoryReport, NodeReport, SoakReport, Violation};
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::invariants::*;
+    use super::*;
+
+    use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionId};
+    use crate::dialects::minimal::messages::Heartbeat;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn sequence_regressions_are_detected() {
+        let mut tracker = SequenceTracker::default();
+        let channel = ChannelInfo::new(ConnectionId::new(), ChannelDetails::Unknown).id();
+        let other = ChannelInfo::new(ConnectionId::new(), ChannelDetails::Unknown).id();
+        let source = MavLinkId::new(1, 1);
+
+        assert_eq!(tracker.check(channel, source, 254), SequenceCheck::Ok);
+        assert_eq!(tracker.check(channel, source, 255), SequenceCheck::Ok);
+        assert_eq!(tracker.check(channel, source, 0), SequenceCheck::Ok);
+        assert_eq!(tracker.check(channel, source, 3), SequenceCheck::Gap);
+        assert_eq!(
+            tracker.check(channel, source, 3),
+            SequenceCheck::Regression(3)
+        );
+        assert_eq!(
+            tracker.check(channel, source, 1),
+            SequenceCheck::Regression(3)
+        );
+        assert_eq!(tracker.check(channel, source, 4), SequenceCheck::Ok);
+
+        // Sources are tracked per channel
+        assert_eq!(tracker.check(other, source, 0), SequenceCheck::Ok);
+        tracker.forget_channel(channel);
+        assert_eq!(tracker.check(channel, source, 0), SequenceCheck::Ok);
+    }
+
+    #[test]
+    fn stuck_channels_are_reported_once() {
+        let mut activity = ChannelActivity::default();
+        let channel = ChannelInfo::new(ConnectionId::new(), ChannelDetails::Unknown);
+        let timeout = Duration::from_secs(1);
+        let start = Instant::now();
+
+        activity.open(&channel, start);
+        assert!(activity.check(start + timeout, timeout).is_empty());
+
+        let stuck = activity.check(start + timeout * 2, timeout);
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].1, timeout * 2);
+        assert!(activity.check(start + timeout * 3, timeout).is_empty());
+
+        activity.record(&channel, start + timeout * 3);
+        assert_eq!(activity.check(start + timeout * 5, timeout).len(), 1);
+
+        activity.close(channel.id());
+        activity.record(&channel, start + timeout * 5);
+        activity.close(channel.id());
+        assert!(activity.check(start + timeout * 10, timeout).is_empty());
+    }
+
+    #[test]
+    fn memory_growth_is_checked_after_warmup() {
+        let mut memory = MemoryTracker::new(100);
+
+        assert_eq!(memory.sample(10_000, false), None);
+        assert_eq!(memory.sample(1_000, true), None);
+        assert_eq!(memory.sample(1_100, true), None);
+        assert_eq!(memory.sample(1_101, true), Some(1_000));
+        assert_eq!(memory.sample(2_000, true), None);
+        assert_eq!(memory.sample(1_000, true), None);
+        assert_eq!(memory.sample(2_000, true), Some(1_000));
+
+        let report = memory.report();
+        assert_eq!(report.baseline, Some(1_000));
+        assert_eq!(report.peak, 10_000);
+        assert_eq!(report.last, 2_000);
+        assert_eq!(report.samples, 7);
+    }
+
+    #[test]
+    fn report_is_encoded_as_json() {
+        let report = SoakReport {
+            duration: Duration::from_millis(1500),
+            nodes: vec![NodeReport {
+                name: "gcs \"main\"".to_string(),
+                frames_in: 10,
+                ..Default::default()
+            }],
+            memory: None,
+            violations: vec![Violation::SequenceRegression {
+                at: Duration::from_secs(1),
+                node: "gcs".to_string(),
+                channel: "Unknown".to_string(),
+                source: MavLinkId::new(1, 2),
+                previous: 5,
+                sequence: 4,
+            }],
+            violation_count: 1,
+        };
+
+        assert_eq!(
+            report.to_json(),
+            "{\"passed\":false,\"duration\":1.5,\"violation_count\":1,\"nodes\":[\
+            {\"name\":\"gcs \\\"main\\\"\",\"frames_in\":10,\"frames_out\":0,\"send_errors\":0,\
+            \"lagged\":0,\"sequence_gaps\":0,\"sequence_regressions\":0,\"stuck_channels\":0}],\
+            \"memory\":null,\"violations\":[{\"kind\":\"sequence_regression\",\"at\":1,\
+            \"node\":\"gcs\",\"channel\":\"Unknown\",\"source\":[1,2],\"previous\":5,\
+            \"sequence\":4}]}"
+        );
+    }
+
+    #[test]
+    fn soak_runs_nodes() {
+        let addr = format!("127.0.0.1:{}", portpicker::pick_unused_port().unwrap());
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpServer::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+
+        let report = Soak::new(Duration::from_millis(300))
+            .with_sample_interval(Duration::from_millis(50))
+            .with_stuck_timeout(Duration::from_secs(1))
+            .add_node(SoakNode::new("server", server))
+            .add_node(
+                SoakNode::new("client", client).with_traffic(Duration::from_millis(10), |node| {
+                    node.send(&Heartbeat::default())
+                }),
+            )
+            .run();
+
+        assert!(report.is_passed(), "{report:?}");
+        assert!(report.memory.is_none());
+        assert!(report.nodes[0].frames_in > 0);
+        assert!(report.nodes[1].frames_out > 0);
+        assert_eq!(report.nodes[0].sequence_regressions, 0);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// Result of a soak test.
+///
+/// Returned by [`Soak::run`](crate::soak::Soak::run). Use [`SoakReport::to_json`] to store a
+/// machine-readable version of the report.
+#[derive(Clone, Debug, Default)]
+pub struct SoakReport {
+    /// Actual duration of the test.
+    pub duration: Duration,
+    /// Reports of observed nodes in the order they were added.
+    pub nodes: Vec<NodeReport>,
+    /// Memory usage, if allocations were tracked.
+    pub memory: Option<MemoryReport>,
+    /// Recorded violations in the order they were detected.
+    ///
+    /// Only the first [`SOAK_MAX_RECORDED_VIOLATIONS`] are recorded, see
+    /// [`SoakReport::violation_count`] for the total number.
+    ///
+    /// [`SOAK_MAX_RECORDED_VIOLATIONS`]: crate::core::consts::SOAK_MAX_RECORDED_VIOLATIONS
+    pub violations: Vec<Violation>,
+    /// Total number of detected violations.
+    pub violation_count: usize,
+}
+
+/// Traffic and invariant statistics of a node within [`SoakReport`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeReport {
+    /// Node name.
+    pub name: String,
+    /// Number of received frames.
+    pub frames_in: u64,
+    /// Number of messages sent by node traffic generator.
+    pub frames_out: u64,
+    /// Number of messages, that traffic generator failed to send.
+    pub send_errors: u64,
+    /// Number of frames skipped by the monitor, because it was lagging behind the node.
+    pub lagged: u64,
+    /// Number of sequence gaps, i.e. frames lost in transport.
+    pub sequence_gaps: u64,
+    /// Number of sequence regressions, i.e. duplicated or reordered frames.
+    pub sequence_regressions: u64,
+    /// Number of times node channels were stuck.
+    pub stuck_channels: u64,
+}
+
+/// Memory usage within [`SoakReport`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Live bytes after warmup, if warmup has finished.
+    pub baseline: Option<usize>,
+    /// The maximum of sampled live bytes.
+    pub peak: usize,
+    /// Live bytes at the last sample.
+    pub last: usize,
+    /// Number of samples.
+    pub samples: u64,
+}
+
+/// Invariant violation detected by a soak test.
+///
+/// Each violation carries the time since the start of the test, when it was detected.
+#[derive(Clone, Debug)]
+pub enum Violation {
+    /// Live memory has grown over the baseline for more than the allowed limit.
+    MemoryGrowth {
+        /// Time since the start of the test.
+        at: Duration,
+        /// Live bytes after warmup.
+        baseline: usize,
+        /// Live bytes, when violation was detected.
+        live: usize,
+    },
+    /// Node received a duplicated or reordered frame.
+    SequenceRegression {
+        /// Time since the start of the test.
+        at: Duration,
+        /// Node name.
+        node: String,
+        /// Channel, that received the frame.
+        channel: String,
+        /// Frame source.
+        source: MavLinkId,
+        /// Sequence number of the previous frame.
+        previous: u8,
+        /// Sequence number of the received frame.
+        sequence: u8,
+    },
+    /// Node channel hasn't received frames for longer than the stuck timeout.
+    StuckChannel {
+        /// Time since the start of the test.
+        at: Duration,
+        /// Node name.
+        node: String,
+        /// Stuck channel.
+        channel: String,
+        /// Time since the last frame received by the channel.
+        idle: Duration,
+    },
+}
+
+impl SoakReport {
+    /// Returns `true`, if no violations were detected.
+    pub fn is_passed(&self) -> bool {
+        self.violation_count == 0
+    }
+
+    /// Encodes report as JSON.
+    ///
+    /// Durations are encoded as seconds, and MAVLink `ID`s as `[system, component]` pairs.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, out: &mut String) -> std::fmt::Result {
+        write!(
+            out,
+            "{{\"passed\":{},\"duration\":{},\"violation_count\":{},\"nodes\":[",
+            self.is_passed(),
+            self.duration.as_secs_f64(),
+            self.violation_count,
+        )?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write!(
+                out,
+                "{{\"name\":{},\"frames_in\":{},\"frames_out\":{},\"send_errors\":{},\
+                \"lagged\":{},\"sequence_gaps\":{},\"sequence_regressions\":{},\
+                \"stuck_channels\":{}}}",
+                json_str(&node.name),
+                node.frames_in,
+                node.frames_out,
+                node.send_errors,
+                node.lagged,
+                node.sequence_gaps,
+                node.sequence_regressions,
+                node.stuck_channels,
+            )?;
+        }
+
+        out.push_str("],\"memory\":");
+        match &self.memory {
+            Some(memory) => write!(
+                out,
+                "{{\"baseline\":{},\"peak\":{},\"last\":{},\"samples\":{}}}",
+                memory
+                    .baseline
+                    .map_or("null".to_string(), |baseline| baseline.to_string()),
+                memory.peak,
+                memory.last,
+                memory.samples,
+            )?,
+            None => out.push_str("null"),
+        }
+
+        out.push_str(",\"violations\":[");
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            violation.write_json(out)?;
+        }
+        out.push_str("]}");
+
+        Ok(())
+    }
+}
+
+impl Violation {
+    /// Time since the start of the test, when violation was detected.
+    pub fn at(&self) -> Duration {
+        match self {
+            Violation::MemoryGrowth { at, .. }
+            | Violation::SequenceRegression { at, .. }
+            | Violation::StuckChannel { at, .. } => *at,
+        }
+    }
+
+    fn write_json(&self, out: &mut String) -> std::fmt::Result {
+        match self {
+            Violation::MemoryGrowth { at, baseline, live } => write!(
+                out,
+                "{{\"kind\":\"memory_growth\",\"at\":{},\"baseline\":{baseline},\"live\":{live}}}",
+                at.as_secs_f64(),
+            ),
+            Violation::SequenceRegression {
+                at,
+                node,
+                channel,
+                source,
+                previous,
+                sequence,
+            } => write!(
+                out,
+                "{{\"kind\":\"sequence_regression\",\"at\":{},\"node\":{},\"channel\":{},\
+                \"source\":[{},{}],\"previous\":{previous},\"sequence\":{sequence}}}",
+                at.as_secs_f64(),
+                json_str(node),
+                json_str(channel),
+                source.system,
+                source.component,
+            ),
+            Violation::StuckChannel {
+                at,
+                node,
+                channel,
+                idle,
+            } => write!(
+                out,
+                "{{\"kind\":\"stuck_channel\",\"at\":{},\"node\":{},\"channel\":{},\"idle\":{}}}",
+                at.as_secs_f64(),
+                json_str(node),
+                json_str(channel),
+                idle.as_secs_f64(),
+            ),
+        }
+    }
+}
+
+fn json_str(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 2);
+    encoded.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(encoded, "\\u{:04x}", ch as u32);
+            }
+            ch => encoded.push(ch),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
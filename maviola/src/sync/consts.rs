@@ -5,14 +5,30 @@ pub(crate) const CONN_STOP_POOLING_INTERVAL: Duration = Duration::from_millis(1)
 pub(crate) const CHANNEL_STOP_POOLING_INTERVAL: Duration = Duration::from_micros(100);
 pub(crate) const CHANNEL_STOP_JOIN_POOLING_INTERVAL: Duration = Duration::from_millis(100);
 pub(crate) const CHANNEL_STOP_JOIN_ATTEMPTS: usize = 50;
+pub(crate) const CHANNEL_PAUSE_POOLING_INTERVAL: Duration = Duration::from_millis(5);
+
+pub(crate) const POOL_MIN_IDLE_INTERVAL: Duration = Duration::from_micros(50);
+pub(crate) const POOL_MAX_IDLE_INTERVAL: Duration = Duration::from_millis(2);
+pub(crate) const POOL_WAIT_INTERVAL: Duration = Duration::from_millis(100);
+pub(crate) const POOL_READ_BUFFER_SIZE: usize = 1024;
 
 pub(crate) const EVENTS_RECV_POOLING_INTERVAL: Duration = Duration::from_millis(1);
 
+pub(crate) const CONN_EVENTS_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+pub(crate) const RECONNECT_RELAY_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+pub(crate) const TLOG_RELAY_POOLING_INTERVAL: Duration = Duration::from_millis(10);
+
 pub(crate) const TCP_READ_TIMEOUT: Option<Duration> = None;
 pub(crate) const TCP_WRITE_TIMEOUT: Option<Duration> = None;
 
 pub(crate) const UDP_RETRIES: usize = 5;
 pub(crate) const UDP_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+pub(crate) const UDP_FAILOVER_READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub(crate) const TCP_FAILOVER_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+pub(crate) const TCP_FAILBACK_POOLING_INTERVAL: Duration = Duration::from_millis(50);
+
+pub(crate) const HOST_RESOLUTION_POOLING_INTERVAL: Duration = Duration::from_millis(50);
 
 #[cfg(unix)]
 pub(crate) const SOCK_ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
@@ -20,3 +36,19 @@ pub(crate) const SOCK_ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
 pub(crate) const SOCK_READ_TIMEOUT: Option<Duration> = Some(Duration::from_millis(500));
 #[cfg(unix)]
 pub(crate) const SOCK_WRITE_TIMEOUT: Option<Duration> = Some(Duration::from_micros(50));
+
+#[cfg(all(feature = "ipc", unix))]
+pub(crate) const IPC_ACCEPT_INTERVAL: Duration = Duration::from_millis(10);
+#[cfg(all(feature = "ipc", unix))]
+pub(crate) const IPC_READ_TIMEOUT: Duration = Duration::from_millis(100);
+#[cfg(all(feature = "ipc", unix))]
+pub(crate) const IPC_SPIN_DURATION: Duration = Duration::from_millis(1);
+#[cfg(all(feature = "ipc", unix))]
+pub(crate) const IPC_IDLE_INTERVAL: Duration = Duration::from_micros(100);
+#[cfg(all(feature = "ipc", unix))]
+pub(crate) const IPC_PEER_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "serial")]
+pub(crate) const SERIAL_READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub(crate) const SHUTDOWN_FLUSH_POOLING_INTERVAL: Duration = Duration::from_millis(5);
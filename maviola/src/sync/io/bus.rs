@@ -1,10 +1,8 @@
 use std::time::Duration;
 
-use crate::core::io::{IncomingFrame, OutgoingFrame};
+use crate::core::io::{FlushTracker, IncomingFrame, OutgoingFrame};
 use crate::core::utils::Closable;
-#[cfg(feature = "unstable")]
-use crate::error::TryRecvResult;
-use crate::error::{RecvResult, RecvTimeoutResult, SendError, SendResult};
+use crate::error::{RecvResult, RecvTimeoutResult, SendError, SendResult, TryRecvResult};
 
 use crate::prelude::*;
 use crate::sync::prelude::*;
@@ -20,6 +18,7 @@ use crate::sync::io::{Channel, Connection};
 pub struct OutgoingFrameSender<V: MaybeVersioned> {
     sender: mpmc::Sender<OutgoingFrame<V>>,
     state: Closable,
+    tracker: FlushTracker,
 }
 
 /// <sup>[`sync`](crate::sync)</sup>
@@ -75,23 +74,38 @@ pub fn incoming_channel<V: MaybeVersioned>() -> (IncomingFrameProducer<V>, Incom
 
 impl<V: MaybeVersioned> OutgoingFrameSender<V> {
     fn new(sender: mpmc::Sender<OutgoingFrame<V>>, state: Closable) -> Self {
-        Self { sender, state }
+        Self {
+            sender,
+            state,
+            tracker: FlushTracker::default(),
+        }
     }
 
     /// Sends frame to all possible channels.
     #[inline(always)]
+    #[allow(clippy::result_large_err)]
     pub fn send(&self, frame: Frame<V>) -> SendResult<OutgoingFrame<V>> {
         self.send_raw(OutgoingFrame::new(frame))
     }
 
     /// Sends outgoing frame with specified routing.
-    pub fn send_raw(&self, frame: OutgoingFrame<V>) -> SendResult<OutgoingFrame<V>> {
-        if self.state.is_closed() {
+    ///
+    /// Frames are rejected once connection is closed or is being shut down.
+    #[allow(clippy::result_large_err)]
+    pub fn send_raw(&self, mut frame: OutgoingFrame<V>) -> SendResult<OutgoingFrame<V>> {
+        if self.state.is_closed() || self.tracker.is_draining() {
             return Err(SendError(frame));
         }
 
+        frame.track_flush(&self.tracker);
         self.sender.send(frame)
     }
+
+    /// <sup>⛔</sup>
+    /// Tracker of frames sent by this sender, that are not yet flushed to transports.
+    pub(crate) fn flush_tracker(&self) -> &FlushTracker {
+        &self.tracker
+    }
 }
 
 impl<V: MaybeVersioned> OutgoingFrameHandler<V> {
@@ -110,6 +124,12 @@ impl<V: MaybeVersioned> OutgoingFrameHandler<V> {
     pub fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<OutgoingFrame<V>> {
         self.receiver.recv_timeout(timeout)
     }
+
+    /// Attempts to receive outgoing frame without blocking.
+    #[inline(always)]
+    pub fn try_recv(&self) -> TryRecvResult<OutgoingFrame<V>> {
+        self.receiver.try_recv()
+    }
 }
 
 impl<V: MaybeVersioned> IncomingFrameProducer<V> {
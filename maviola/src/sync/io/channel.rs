@@ -1,13 +1,23 @@
-use std::io::{Read, Write};
+use std::io::{Cursor, ErrorKind, Read, Write};
+use std::sync::{mpsc, Arc};
 use std::thread;
 
-use crate::core::io::{ChannelInfo, ConnectionInfo, IncomingFrame};
+use crate::core::io::{
+    Captured, ChannelGuard, ChannelInfo, ConnectionEvent, ConnectionInfo, EgressQueue,
+    FlushTracker, IncomingFrame, OutgoingFrame, PauseSwitch, SharedTap, Tapped,
+};
 use crate::core::io::{Receiver, Sender};
-use crate::core::utils::{Closable, SharedCloser};
+use crate::core::node::ValidationReport;
+use crate::core::utils::{trace, Closable, SharedCloser};
+use crate::error::TryRecvError;
 use crate::sync::consts::{
-    CHANNEL_STOP_JOIN_ATTEMPTS, CHANNEL_STOP_JOIN_POOLING_INTERVAL, CHANNEL_STOP_POOLING_INTERVAL,
+    CHANNEL_PAUSE_POOLING_INTERVAL, CHANNEL_STOP_JOIN_ATTEMPTS, CHANNEL_STOP_JOIN_POOLING_INTERVAL,
+    CHANNEL_STOP_POOLING_INTERVAL, POOL_READ_BUFFER_SIZE,
+};
+use crate::sync::io::{
+    IncomingFrameProducer, IoPool, OutgoingFrameHandler, OutgoingFrameSender, TaskStatus,
 };
-use crate::sync::io::{IncomingFrameProducer, OutgoingFrameHandler, OutgoingFrameSender};
+use crate::sync::utils::spawn_io;
 
 use crate::prelude::*;
 
@@ -20,6 +30,7 @@ pub struct ChannelFactory<V: MaybeVersioned> {
     pub(super) sender: OutgoingFrameSender<V>,
     pub(super) send_handler: OutgoingFrameHandler<V>,
     pub(super) producer: IncomingFrameProducer<V>,
+    pub(super) events: mpsc::Sender<ConnectionEvent>,
 }
 
 impl<V: MaybeVersioned> ChannelFactory<V> {
@@ -38,6 +49,10 @@ impl<V: MaybeVersioned> ChannelFactory<V> {
             writer,
             send_handler: self.send_handler.clone(),
             producer: self.producer.clone(),
+            events: self.events.clone(),
+            tracker: self.sender.flush_tracker().clone(),
+            tap: self.info.tap().cloned(),
+            pause: self.info.pause_switch().clone(),
         }
     }
 
@@ -65,6 +80,10 @@ impl<V: MaybeVersioned> ChannelFactory<V> {
     pub fn send_handler(&self) -> &OutgoingFrameHandler<V> {
         &self.send_handler
     }
+
+    pub(in crate::sync) fn event_sender(&self) -> &mpsc::Sender<ConnectionEvent> {
+        &self.events
+    }
 }
 
 /// <sup>[`sync`](crate::sync)</sup>
@@ -79,6 +98,10 @@ pub struct Channel<V: MaybeVersioned, R: Read, W: Write> {
     writer: W,
     send_handler: OutgoingFrameHandler<V>,
     producer: IncomingFrameProducer<V>,
+    events: mpsc::Sender<ConnectionEvent>,
+    tracker: FlushTracker,
+    tap: Option<SharedTap>,
+    pause: PauseSwitch,
 }
 
 impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Channel<V, R, W> {
@@ -95,59 +118,135 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
         let info = self.info;
         let conn_state = self.conn_state;
         let state = SharedCloser::new();
+        let channel_guard = self.tracker.open_channel();
+        let span = trace::channel_span(&info);
+        let _entered = span.enter();
 
         log::trace!("[{info:?}] spawning peer connection");
+        let events = self.events;
+        _ = events.send(ConnectionEvent::ChannelOpened(info.clone()));
 
         let write_handler = {
+            let handler_state =
+                HandlerState::new(self.pause.clone(), state.clone(), conn_state.clone());
             let info = info.clone();
             let send_handler = self.send_handler;
-            let frame_writer = Sender::new(self.writer);
+            let frame_writer =
+                Sender::new(Tapped::new(self.writer, self.tap.clone(), info.clone()));
 
-            thread::spawn(move || Self::write_handler(info, send_handler, frame_writer))
+            spawn_io(move || Self::write_handler(info, handler_state, send_handler, frame_writer))
         };
 
         let read_handler = {
-            let conn_state = conn_state.clone();
-            let state = state.clone();
+            let handler_state = HandlerState::new(self.pause, state.clone(), conn_state.clone());
             let info = info.clone();
             let producer = self.producer;
-            let frame_reader = Receiver::new(self.reader);
+            let events = events.clone();
+            let reader = Captured::new(Tapped::new(self.reader, self.tap, info.clone()));
 
-            thread::spawn(move || {
-                Self::read_handler(state, conn_state, info, producer, frame_reader)
-            })
+            spawn_io(move || Self::read_handler(info, handler_state, producer, events, reader))
         };
 
         {
             let info = info.clone();
             let state = state.clone();
-            thread::spawn(move || {
-                Self::handle_stop(state, conn_state, info, write_handler, read_handler);
+            spawn_io(move || {
+                Self::handle_stop(state, conn_state, info, events, write_handler, read_handler);
+                drop(channel_guard);
             });
         }
 
         state.clone()
     }
 
+    /// Spawn this channel in an I/O `pool`.
+    ///
+    /// Instead of dedicated threads, reader and writer of the channel are polled by workers of the
+    /// [`IoPool`]. Reader and writer should not block: once they are not ready, they should return
+    /// [`WouldBlock`](ErrorKind::WouldBlock) or [`TimedOut`](ErrorKind::TimedOut) errors, otherwise
+    /// they will occupy a pool worker.
+    ///
+    /// Returns [`SharedCloser`] which can be used to control channel state in the same way as for
+    /// [`Channel::spawn`].
+    pub fn spawn_in(self, pool: &IoPool) -> SharedCloser {
+        let info = self.info;
+        let state = SharedCloser::new();
+        let span = trace::channel_span(&info);
+
+        log::trace!("[{info:?}] spawning pooled peer connection");
+        _ = self
+            .events
+            .send(ConnectionEvent::ChannelOpened(info.clone()));
+        let stop = Arc::new(PooledStop {
+            info: info.clone(),
+            events: self.events,
+            _guard: self.tracker.open_channel(),
+        });
+
+        let mut writer = PooledWriter {
+            info: info.clone(),
+            state: state.clone(),
+            conn_state: self.conn_state.clone(),
+            send_handler: self.send_handler,
+            writer: Tapped::new(self.writer, self.tap.clone(), info.clone()),
+            buffer: Vec::new(),
+            written: 0,
+            frame: None,
+            queue: EgressQueue::new(),
+            pause: self.pause.clone(),
+            _stop: stop.clone(),
+        };
+        {
+            let span = span.clone();
+            pool.submit(move || span.in_scope(|| writer.poll()));
+        }
+
+        let mut reader = PooledReader {
+            reader: Tapped::new(self.reader, self.tap, info.clone()),
+            info,
+            state: state.clone(),
+            conn_state: self.conn_state,
+            producer: self.producer,
+            buffer: Vec::new(),
+            pause: self.pause,
+            _stop: stop,
+        };
+        pool.submit(move || span.in_scope(|| reader.poll()));
+
+        state
+    }
+
     fn write_handler(
         info: ChannelInfo,
+        state: HandlerState,
         send_handler: OutgoingFrameHandler<V>,
-        mut frame_writer: Sender<W, V>,
+        mut frame_writer: Sender<Tapped<W>, V>,
     ) -> Result<()> {
+        let mut queue = EgressQueue::new();
+
         loop {
-            let out_frame = match send_handler.recv() {
-                Ok(out_frame) => out_frame,
-                Err(err) => {
-                    frame_writer.flush().map_err(Error::from)?;
-                    return Err(Error::from(err));
+            if queue.is_empty() {
+                match send_handler.recv() {
+                    Ok(out_frame) if out_frame.should_send_to(info.id()) => queue.push(out_frame),
+                    Ok(_) => continue,
+                    Err(err) => {
+                        frame_writer.flush().map_err(Error::from)?;
+                        return Err(Error::from(err));
+                    }
                 }
-            };
-
-            if !out_frame.should_send_to(info.id()) {
-                continue;
             }
+            queue.fill(info.id(), || send_handler.try_recv());
+
+            let out_frame = match queue.pop() {
+                Some(out_frame) => out_frame,
+                None => continue,
+            };
 
             log::trace!("[{info:?}] received outgoing frame from API");
+            // Handler could wait for outgoing frames, when connection was paused
+            if !state.wait_resumed() {
+                return Ok(());
+            }
             loop {
                 if let Err(err) = frame_writer.send(out_frame.frame()) {
                     let err = Error::from(err);
@@ -157,6 +256,8 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
                         }
                         return Err(Error::Io(err));
                     }
+                } else {
+                    out_frame.record_written(info.connection_id());
                 }
                 log::trace!("[{info:?}] written outgoing frame");
                 break;
@@ -165,18 +266,18 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
     }
 
     fn read_handler(
-        state: SharedCloser,
-        conn_state: Closable,
         info: ChannelInfo,
+        state: HandlerState,
         producer: IncomingFrameProducer<V>,
-        mut frame_reader: Receiver<R, V>,
+        events: mpsc::Sender<ConnectionEvent>,
+        mut reader: Captured<Tapped<R>>,
     ) -> Result<()> {
         loop {
-            if conn_state.is_closed() || state.is_closed() {
+            if state.is_closed() || !state.wait_resumed() {
                 return Ok(());
             }
 
-            let frame = match frame_reader.recv() {
+            let frame = match Receiver::new::<V>(&mut reader).recv() {
                 Ok(frame) => frame,
                 Err(err) => {
                     let err = Error::from(err);
@@ -190,7 +291,14 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
                 }
             };
             log::trace!("[{info:?}] received incoming frame");
+            // Handler could wait for incoming frames, when connection was paused
+            if !state.wait_resumed() {
+                return Ok(());
+            }
 
+            if let Some(skipped) = reader.take_skipped(&frame) {
+                report_skipped::<V>(&info, &events, skipped);
+            }
             producer.send(IncomingFrame::new(frame, info.clone()))?;
             log::trace!("[{info:?}] sent incoming frame to API");
         }
@@ -200,6 +308,7 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
         mut state: SharedCloser,
         conn_state: Closable,
         info: ChannelInfo,
+        events: mpsc::Sender<ConnectionEvent>,
         write_handler: thread::JoinHandle<Result<()>>,
         read_handler: thread::JoinHandle<Result<()>>,
     ) {
@@ -210,6 +319,7 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
         {
             thread::sleep(CHANNEL_STOP_POOLING_INTERVAL);
         }
+        _ = events.send(ConnectionEvent::ChannelClosed(info.clone()));
         state.close();
 
         for i in 0..CHANNEL_STOP_JOIN_ATTEMPTS {
@@ -240,3 +350,278 @@ impl<V: MaybeVersioned, R: Read + Send + 'static, W: Write + Send + 'static> Cha
         log::trace!("[{info:?}] handlers stopped");
     }
 }
+
+/// State of a channel shared by its read and write handlers.
+struct HandlerState {
+    pause: PauseSwitch,
+    state: SharedCloser,
+    conn_state: Closable,
+}
+
+impl HandlerState {
+    fn new(pause: PauseSwitch, state: SharedCloser, conn_state: Closable) -> Self {
+        Self {
+            pause,
+            state,
+            conn_state,
+        }
+    }
+
+    /// Returns `true`, if channel or connection is closed.
+    fn is_closed(&self) -> bool {
+        self.state.is_closed() || self.conn_state.is_closed()
+    }
+
+    /// Blocks, while connection is paused.
+    ///
+    /// Returns `false`, if channel or connection was closed while waiting.
+    fn wait_resumed(&self) -> bool {
+        while self.pause.is_paused() {
+            if self.is_closed() {
+                return false;
+            }
+            thread::sleep(CHANNEL_PAUSE_POOLING_INTERVAL);
+        }
+        true
+    }
+}
+
+/// Notifies about closed pooled channel, once both its reader and writer are finished.
+struct PooledStop {
+    info: ChannelInfo,
+    events: mpsc::Sender<ConnectionEvent>,
+    _guard: ChannelGuard,
+}
+
+/// Writes outgoing frames of a pooled channel without blocking.
+struct PooledWriter<V: MaybeVersioned, W: Write> {
+    info: ChannelInfo,
+    state: SharedCloser,
+    conn_state: Closable,
+    send_handler: OutgoingFrameHandler<V>,
+    writer: Tapped<W>,
+    buffer: Vec<u8>,
+    written: usize,
+    frame: Option<OutgoingFrame<V>>,
+    queue: EgressQueue<V>,
+    pause: PauseSwitch,
+    _stop: Arc<PooledStop>,
+}
+
+/// Reads incoming frames of a pooled channel without blocking.
+struct PooledReader<V: MaybeVersioned, R: Read> {
+    info: ChannelInfo,
+    state: SharedCloser,
+    conn_state: Closable,
+    producer: IncomingFrameProducer<V>,
+    reader: Tapped<R>,
+    buffer: Vec<u8>,
+    pause: PauseSwitch,
+    _stop: Arc<PooledStop>,
+}
+
+impl Drop for PooledStop {
+    fn drop(&mut self) {
+        _ = self
+            .events
+            .send(ConnectionEvent::ChannelClosed(self.info.clone()));
+        log::trace!("[{:?}] pooled handlers stopped", self.info);
+    }
+}
+
+impl<V: MaybeVersioned, W: Write> PooledWriter<V, W> {
+    fn poll(&mut self) -> TaskStatus {
+        if self.state.is_closed() || self.conn_state.is_closed() {
+            return self.finish(Ok(()));
+        }
+
+        if self.written < self.buffer.len() {
+            return match self.writer.write(&self.buffer[self.written..]) {
+                Ok(0) => self.finish(Err(std::io::Error::from(ErrorKind::WriteZero).into())),
+                Ok(n) => {
+                    self.written += n;
+                    if self.written == self.buffer.len() {
+                        self.complete();
+                    }
+                    TaskStatus::Busy
+                }
+                Err(err) if is_pending(&err) => TaskStatus::Idle,
+                Err(err) => self.finish(Err(err.into())),
+            };
+        }
+
+        // Partially written frame is completed to keep the stream consistent
+        if self.pause.is_paused() {
+            return TaskStatus::Idle;
+        }
+
+        if self.queue.is_empty() {
+            match self.send_handler.try_recv() {
+                Ok(out_frame) if out_frame.should_send_to(self.info.id()) => {
+                    self.queue.push(out_frame)
+                }
+                Ok(_) | Err(TryRecvError::Lagged(_)) => return TaskStatus::Busy,
+                Err(TryRecvError::Empty) => return TaskStatus::Idle,
+                Err(err @ TryRecvError::Disconnected) => {
+                    _ = self.writer.flush();
+                    return self.finish(Err(Error::from(err)));
+                }
+            }
+        }
+        let send_handler = &self.send_handler;
+        self.queue.fill(self.info.id(), || send_handler.try_recv());
+
+        let out_frame = match self.queue.pop() {
+            Some(out_frame) => out_frame,
+            None => return TaskStatus::Busy,
+        };
+        log::trace!("[{:?}] received outgoing frame from API", self.info);
+
+        let mut sender: Sender<&mut Vec<u8>, V> = Sender::new(&mut self.buffer);
+        if let Err(err) = sender.send(out_frame.frame()) {
+            return self.finish(Err(Error::from(err)));
+        }
+        self.frame = Some(out_frame);
+
+        TaskStatus::Busy
+    }
+
+    fn complete(&mut self) {
+        if let Err(err) = self.writer.flush() {
+            if !is_pending(&err) {
+                log::debug!("[{:?}] unable to flush writer: {err:?}", self.info);
+            }
+        }
+        if let Some(out_frame) = self.frame.take() {
+            out_frame.record_written(self.info.connection_id());
+        }
+        self.buffer.clear();
+        self.written = 0;
+        log::trace!("[{:?}] written outgoing frame", self.info);
+    }
+
+    fn finish(&mut self, result: Result<()>) -> TaskStatus {
+        if let Err(err) = result {
+            log::debug!(
+                "[{:?}] write handler finished with error: {err:?}",
+                self.info
+            )
+        }
+        self.state.close();
+        TaskStatus::Finished
+    }
+}
+
+impl<V: MaybeVersioned, R: Read> PooledReader<V, R> {
+    fn poll(&mut self) -> TaskStatus {
+        if self.state.is_closed() || self.conn_state.is_closed() {
+            return self.finish(Ok(()));
+        }
+
+        if self.pause.is_paused() {
+            return TaskStatus::Idle;
+        }
+
+        let mut chunk = [0u8; POOL_READ_BUFFER_SIZE];
+        match self.reader.read(&mut chunk) {
+            Ok(0) => {
+                return self.finish(Err(std::io::Error::from(ErrorKind::UnexpectedEof).into()))
+            }
+            Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+            Err(err) if is_pending(&err) => return TaskStatus::Idle,
+            Err(err) => return self.finish(Err(err.into())),
+        }
+
+        match self.produce() {
+            Ok(_) => TaskStatus::Busy,
+            Err(err) => self.finish(Err(err)),
+        }
+    }
+
+    /// Sends all complete frames from the buffer to the API.
+    fn produce(&mut self) -> Result<()> {
+        let mut consumed = 0;
+        let mut skipped = Vec::new();
+
+        while consumed < self.buffer.len() {
+            let mut cursor = Cursor::new(&self.buffer[consumed..]);
+            let result = Receiver::new::<V>(&mut cursor).recv();
+            let position = cursor.position() as usize;
+
+            match result.map_err(Error::from) {
+                Ok(frame) => {
+                    let frame_size = frame.header().size() + frame.body_length();
+                    skipped.extend_from_slice(
+                        &self.buffer[consumed..consumed + position - frame_size],
+                    );
+                    self.report_skipped(&mut skipped);
+                    consumed += position;
+                    log::trace!("[{:?}] received incoming frame", self.info);
+                    self.producer
+                        .send(IncomingFrame::new(frame, self.info.clone()))?;
+                }
+                Err(Error::Io(err)) if err.kind() == ErrorKind::UnexpectedEof => {
+                    // Frame is incomplete. Frames can't be longer than that, so the start of the
+                    // buffer is garbage, that only looks like a frame header.
+                    if self.buffer.len() - consumed > MAX_FRAME_SIZE {
+                        skipped.push(self.buffer[consumed]);
+                        consumed += 1;
+                        continue;
+                    }
+                    break;
+                }
+                Err(_) => {
+                    let dropped = position.max(1);
+                    skipped.extend_from_slice(&self.buffer[consumed..consumed + dropped]);
+                    consumed += dropped;
+                }
+            }
+        }
+        self.report_skipped(&mut skipped);
+
+        self.buffer.drain(..consumed);
+        Ok(())
+    }
+
+    fn report_skipped(&self, skipped: &mut Vec<u8>) {
+        if !skipped.is_empty() {
+            report_skipped::<V>(&self.info, &self._stop.events, std::mem::take(skipped));
+        }
+    }
+
+    fn finish(&mut self, result: Result<()>) -> TaskStatus {
+        if let Err(err) = result {
+            log::debug!(
+                "[{:?}] read handler finished with error: {err:?}",
+                self.info
+            )
+        }
+        self.state.close();
+        TaskStatus::Finished
+    }
+}
+
+/// Reports bytes, that were skipped by frame receiver of a channel.
+fn report_skipped<V: MaybeVersioned>(
+    info: &ChannelInfo,
+    events: &mpsc::Sender<ConnectionEvent>,
+    skipped: Vec<u8>,
+) {
+    log::trace!("[{info:?}] skipped {} malformed bytes", skipped.len());
+    let report = ValidationReport::malformed::<V>(skipped);
+    _ = events.send(ConnectionEvent::Malformed(info.clone(), report));
+}
+
+/// Maximum size of a MAVLink frame.
+const MAX_FRAME_SIZE: usize = mavio::consts::HEADER_MAX_SIZE
+    + u8::MAX as usize
+    + mavio::consts::CHECKSUM_SIZE
+    + mavio::consts::SIGNATURE_LENGTH;
+
+/// Returns `true` if I/O operation can't be performed right now and should be retried later.
+fn is_pending(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+    )
+}
@@ -1,14 +1,19 @@
 use std::fmt::Debug;
+use std::sync::{mpsc, Mutex};
 use std::thread;
 use std::thread::JoinHandle;
 
-use crate::core::io::{ConnectionConf, ConnectionInfo};
+use crate::core::io::{ConnectionConf, ConnectionEvent, ConnectionInfo};
+use crate::core::network::NetworkHandle;
 use crate::core::utils::{Closable, SharedCloser};
+use crate::error::ConfigDiagnostic;
 use crate::sync::consts::CONN_STOP_POOLING_INTERVAL;
 use crate::sync::io::{
-    incoming_channel, outgoing_channel, ChannelFactory, IncomingFrameReceiver, OutgoingFrameSender,
+    incoming_channel, outgoing_channel, ChannelFactory, IncomingFrameProducer,
+    IncomingFrameReceiver, OutgoingFrameSender,
 };
 use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
 
 use crate::prelude::*;
 
@@ -30,6 +35,14 @@ pub trait ConnectionBuilder<V: MaybeVersioned>: ConnectionConf {
     fn is_repairable(&self) -> bool {
         false
     }
+
+    /// Problems found in connection configuration, that prevent connection from working properly.
+    ///
+    /// Nodes are validated against these diagnostics before being built. A blanket implementation
+    /// returns no diagnostics.
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Vec::new()
+    }
 }
 
 /// <sup>[`sync`](crate::sync)</sup>
@@ -39,7 +52,11 @@ pub struct Connection<V: MaybeVersioned> {
     info: ConnectionInfo,
     sender: OutgoingFrameSender<V>,
     receiver: IncomingFrameReceiver<V>,
+    producer: IncomingFrameProducer<V>,
+    events: Mutex<Option<mpsc::Receiver<ConnectionEvent>>>,
+    event_sender: mpsc::Sender<ConnectionEvent>,
     state: SharedCloser,
+    network: Option<NetworkHandle<V, ConnConf<V>>>,
 }
 
 /// <sup>[`sync`](crate::sync)</sup>
@@ -56,7 +73,7 @@ impl ConnectionHandler {
         F: Send + 'static,
     {
         Self {
-            inner: thread::spawn(func),
+            inner: spawn_io(func),
         }
     }
 
@@ -73,9 +90,13 @@ impl ConnectionHandler {
     pub(crate) fn handle<V: MaybeVersioned>(self, conn: &Connection<V>) {
         let mut state = conn.state.clone();
         let info = conn.info.clone();
+        let events = conn.event_sender.clone();
 
-        thread::spawn(move || {
+        spawn_io(move || {
             let result = self.inner.join();
+            if !state.is_closed() {
+                _ = events.send(ConnectionEvent::Lost);
+            }
             state.close();
 
             match result {
@@ -94,12 +115,17 @@ impl<V: MaybeVersioned> Connection<V> {
     pub fn new(info: ConnectionInfo, state: SharedCloser) -> (Self, ChannelFactory<V>) {
         let (sender, send_handler) = outgoing_channel(state.to_closable());
         let (producer, receiver) = incoming_channel();
+        let (event_sender, events) = mpsc::channel();
 
         let connection = Self {
             info,
             sender: sender.clone(),
             receiver,
+            producer: producer.clone(),
+            events: Mutex::new(Some(events)),
+            event_sender: event_sender.clone(),
             state,
+            network: None,
         };
 
         let chan_factory = ChannelFactory {
@@ -108,6 +134,7 @@ impl<V: MaybeVersioned> Connection<V> {
             sender,
             send_handler,
             producer,
+            events: event_sender,
         };
 
         (connection, chan_factory)
@@ -134,6 +161,34 @@ impl<V: MaybeVersioned> Connection<V> {
         &self.receiver
     }
 
+    /// Producer of incoming frames, that allows to inject frames into the connection.
+    pub(in crate::sync) fn producer(&self) -> &IncomingFrameProducer<V> {
+        &self.producer
+    }
+
+    /// Takes receiver of connection lifecycle events.
+    ///
+    /// Receiver holds all events since connection was created, so it can be taken only once.
+    /// Returns [`None`] for subsequent calls and for [reused](Self::reuse) connections, since
+    /// events are reported to the original connection.
+    pub(in crate::sync) fn take_events(&self) -> Option<mpsc::Receiver<ConnectionEvent>> {
+        match self.events.lock() {
+            Ok(mut events) => events.take(),
+            Err(err) => err.into_inner().take(),
+        }
+    }
+
+    /// Binds connection to a [`Network`], that can be managed through a [`NetworkHandle`].
+    pub(in crate::sync) fn with_network(mut self, handle: NetworkHandle<V, ConnConf<V>>) -> Self {
+        self.network = Some(handle);
+        self
+    }
+
+    /// Handle of a [`Network`], if this is a network connection.
+    pub(in crate::sync) fn network_handle(&self) -> Option<&NetworkHandle<V, ConnConf<V>>> {
+        self.network.as_ref()
+    }
+
     pub(in crate::sync) fn reuse(&self) -> Self {
         let mut state = SharedCloser::new();
 
@@ -141,12 +196,16 @@ impl<V: MaybeVersioned> Connection<V> {
             info: self.info.clone(),
             sender: self.sender.clone(),
             receiver: self.receiver.clone(),
+            producer: self.producer.clone(),
+            events: Mutex::new(None),
+            event_sender: self.event_sender.clone(),
             state: state.clone(),
+            network: self.network.clone(),
         };
 
         let parent_state = self.state.to_closable();
 
-        thread::spawn(move || {
+        spawn_io(move || {
             while !parent_state.is_closed() && !state.is_closed() {
                 thread::sleep(CONN_STOP_POOLING_INTERVAL);
             }
@@ -171,6 +230,21 @@ impl<V: MaybeVersioned> Drop for Connection<V> {
     }
 }
 
+/// Forwards pending lifecycle events from one connection to another.
+pub(in crate::sync) fn forward_events(
+    receiver: Option<&mpsc::Receiver<ConnectionEvent>>,
+    sender: &mpsc::Sender<ConnectionEvent>,
+) {
+    let receiver = match receiver {
+        Some(receiver) => receiver,
+        None => return,
+    };
+
+    while let Ok(event) = receiver.try_recv() {
+        _ = sender.send(event);
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //                                  Tests                                    //
 ///////////////////////////////////////////////////////////////////////////////
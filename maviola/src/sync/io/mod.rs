@@ -12,13 +12,25 @@
 //!
 //! In most cases channels and connections are hidden to library user. Dealing with these
 //! abstractions is necessary only to those who are interested in creating custom connections.
+//!
+//! ## Transport extensions
+//!
+//! [`TcpHandshake`] allows to authenticate clients of a [`TcpServer`](crate::core::io::TcpServer)
+//! before they are attached to a connection.
 
 mod bus;
 mod channel;
 mod connection;
+mod pool;
 mod transport;
 
+pub use pool::IoPool;
+pub use transport::TcpHandshake;
+
+pub(crate) use pool::TaskStatus;
+
 pub(super) use bus::{incoming_channel, outgoing_channel};
+pub(super) use connection::forward_events;
 
 /// <sup>`⍚` |</sup>
 #[cfg(feature = "unstable")]
@@ -39,4 +51,6 @@ pub(in crate::sync) use bus::{
 #[cfg(not(feature = "unstable"))]
 pub(in crate::sync) use channel::ChannelFactory;
 #[cfg(not(feature = "unstable"))]
-pub(in crate::sync) use connection::{Connection, ConnectionBuilder, ConnectionHandler};
+pub use connection::Connection;
+#[cfg(not(feature = "unstable"))]
+pub(in crate::sync) use connection::{ConnectionBuilder, ConnectionHandler};
@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+use crate::sync::consts::{POOL_MAX_IDLE_INTERVAL, POOL_MIN_IDLE_INTERVAL, POOL_WAIT_INTERVAL};
+use crate::sync::utils::spawn_io_worker;
+
+#[cfg(doc)]
+use crate::core::node::NodeBuilder;
+
+/// <sup>[`sync`](crate::sync)</sup>
+/// Shared pool of I/O worker threads.
+///
+/// By default, synchronous API spawns several threads per each channel: one for reading, one for
+/// writing, and one that watches the others. This is fine for a handful of connections, but a
+/// router with hundreds of TCP clients ends up with hundreds of threads. When I/O pool is set by
+/// [`NodeBuilder::io_pool`], channels of TCP and Unix socket connections are instead multiplexed
+/// by a fixed number of worker threads.
+///
+/// Workers poll channels in turns. Once a whole round of channels had nothing to do, a single
+/// worker keeps polling them with back off, while other workers are parked until some channel has
+/// work again. Therefore, pooled channels trade a fraction of a millisecond of latency for a
+/// bounded number of threads. Settings of [`NodeBuilder::io_threads`] are applied to worker
+/// threads.
+///
+/// Pool is a cheap to clone handle, the same pool can be shared between several nodes. Workers are
+/// spawned once the first channel is attached and exit when all handles are dropped and all
+/// channels are closed.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+/// use maviola::sync::io::IoPool;
+///
+/// let pool = IoPool::new(4);
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(1, 1))
+///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+///     .io_pool(pool.clone())
+///     .build().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct IoPool {
+    handle: Arc<PoolHandle>,
+}
+
+/// <sup>⛔</sup>
+/// Result of a single poll of a pooled task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TaskStatus {
+    /// Task has done some work and should be polled again soon.
+    Busy,
+    /// Task has nothing to do right now.
+    Idle,
+    /// Task is finished and should be removed from the pool.
+    Finished,
+}
+
+type Task = Box<dyn FnMut() -> TaskStatus + Send>;
+
+/// Keeps workers alive while at least one [`IoPool`] handle exists.
+struct PoolHandle {
+    shared: Arc<PoolShared>,
+}
+
+struct PoolShared {
+    size: usize,
+    queue: Mutex<TaskQueue>,
+    available: Condvar,
+    tasks: AtomicUsize,
+    is_spawned: AtomicBool,
+}
+
+#[derive(Default)]
+struct TaskQueue {
+    tasks: VecDeque<Task>,
+    /// Consecutive polls of tasks that had nothing to do, counted across all workers.
+    idle_polls: usize,
+    /// Changes each time a task has done some work or a new task is submitted.
+    activity: u64,
+    /// Worker, that polls idle tasks while other workers are parked.
+    poller: Option<ThreadId>,
+    is_orphaned: bool,
+}
+
+impl IoPool {
+    /// Creates a pool with the specified number of worker threads.
+    ///
+    /// Pool always has at least one worker.
+    pub fn new(size: usize) -> Self {
+        Self {
+            handle: Arc::new(PoolHandle {
+                shared: Arc::new(PoolShared {
+                    size: size.max(1),
+                    queue: Mutex::new(TaskQueue::default()),
+                    available: Condvar::new(),
+                    tasks: AtomicUsize::new(0),
+                    is_spawned: AtomicBool::new(false),
+                }),
+            }),
+        }
+    }
+
+    /// Number of worker threads.
+    pub fn size(&self) -> usize {
+        self.handle.shared.size
+    }
+
+    /// Number of tasks (channel readers, writers, and watchers) currently handled by the pool.
+    pub fn tasks(&self) -> usize {
+        self.handle.shared.tasks.load(Ordering::Acquire)
+    }
+
+    /// <sup>⛔</sup>
+    /// Submits a task, that will be polled by workers until it is finished.
+    ///
+    /// Tasks should never block: a blocked task occupies a worker.
+    pub(crate) fn submit(&self, task: impl FnMut() -> TaskStatus + Send + 'static) {
+        let shared = &self.handle.shared;
+        shared.tasks.fetch_add(1, Ordering::AcqRel);
+        {
+            let mut queue = shared.lock();
+            queue.tasks.push_back(Box::new(task));
+            queue.wake();
+        }
+        shared.available.notify_all();
+
+        if !shared.is_spawned.swap(true, Ordering::AcqRel) {
+            for _ in 0..shared.size {
+                let shared = shared.clone();
+                spawn_io_worker(move || shared.work());
+            }
+        }
+    }
+}
+
+impl Debug for IoPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IoPool")
+            .field("size", &self.size())
+            .field("tasks", &self.tasks())
+            .finish()
+    }
+}
+
+impl Drop for PoolHandle {
+    fn drop(&mut self) {
+        self.shared.lock().is_orphaned = true;
+        self.shared.available.notify_all();
+    }
+}
+
+impl TaskQueue {
+    /// Records that there is some work to do.
+    ///
+    /// Returns `true`, if there are parked workers, that should be notified.
+    fn wake(&mut self) -> bool {
+        self.idle_polls = 0;
+        self.activity = self.activity.wrapping_add(1);
+        self.poller.take().is_some()
+    }
+}
+
+impl PoolShared {
+    fn work(&self) {
+        let worker = thread::current().id();
+        let mut idle_interval = POOL_MIN_IDLE_INTERVAL;
+
+        while let Some(mut task) = self.next() {
+            let status = match catch_unwind(AssertUnwindSafe(&mut task)) {
+                Ok(status) => status,
+                Err(_) => {
+                    log::error!("pooled I/O task panicked");
+                    TaskStatus::Finished
+                }
+            };
+
+            let mut queue = self.lock();
+            match status {
+                TaskStatus::Finished => {
+                    if self.tasks.fetch_sub(1, Ordering::AcqRel) == 1 {
+                        self.available.notify_all();
+                    }
+                    continue;
+                }
+                TaskStatus::Busy => {
+                    if queue.wake() {
+                        self.available.notify_all();
+                    }
+                }
+                TaskStatus::Idle => queue.idle_polls += 1,
+            }
+            queue.tasks.push_back(task);
+
+            // Nothing to do unless a whole round of tasks was idle
+            if queue.idle_polls < self.tasks.load(Ordering::Acquire) {
+                continue;
+            }
+            queue.idle_polls = 0;
+
+            // One worker keeps polling with back off, others are parked
+            let poller = *queue.poller.get_or_insert_with(|| {
+                idle_interval = POOL_MIN_IDLE_INTERVAL;
+                worker
+            });
+            if poller == worker {
+                drop(queue);
+                thread::sleep(idle_interval);
+                idle_interval = (idle_interval * 2).min(POOL_MAX_IDLE_INTERVAL);
+            } else {
+                self.park(queue);
+            }
+        }
+
+        let mut queue = self.lock();
+        if queue.poller == Some(worker) {
+            queue.poller = None;
+        }
+    }
+
+    /// Parks worker until some task has done some work, a new task is submitted, or the pool is
+    /// drained.
+    fn park(&self, mut queue: MutexGuard<'_, TaskQueue>) {
+        let activity = queue.activity;
+        while queue.activity == activity && !self.is_drained(&queue) {
+            queue = match self.available.wait_timeout(queue, POOL_WAIT_INTERVAL) {
+                Ok((queue, _)) => queue,
+                Err(err) => err.into_inner().0,
+            };
+        }
+    }
+
+    fn is_drained(&self, queue: &TaskQueue) -> bool {
+        queue.is_orphaned && self.tasks.load(Ordering::Acquire) == 0
+    }
+
+    /// Waits for the next task.
+    ///
+    /// Returns [`None`] once all pool handles are dropped and there are no tasks left.
+    fn next(&self) -> Option<Task> {
+        let mut queue = self.lock();
+        loop {
+            if let Some(task) = queue.tasks.pop_front() {
+                return Some(task);
+            }
+            if self.is_drained(&queue) {
+                return None;
+            }
+            queue = match self.available.wait_timeout(queue, POOL_WAIT_INTERVAL) {
+                Ok((queue, _)) => queue,
+                Err(err) => err.into_inner().0,
+            };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TaskQueue> {
+        match self.queue.lock() {
+            Ok(queue) => queue,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod pool_tests {
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn tasks_are_polled_until_finished() {
+        let pool = IoPool::new(2);
+        let (tx, rx) = mpsc::channel();
+
+        for id in 0..10 {
+            let tx = tx.clone();
+            let mut polls = 0;
+            pool.submit(move || {
+                polls += 1;
+                match polls {
+                    3 => {
+                        tx.send(id).unwrap();
+                        TaskStatus::Finished
+                    }
+                    _ => TaskStatus::Idle,
+                }
+            });
+        }
+
+        let mut finished: Vec<_> = (0..10)
+            .map(|_| rx.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        finished.sort();
+        assert_eq!(finished, (0..10).collect::<Vec<_>>());
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while pool.tasks() > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(pool.tasks(), 0);
+    }
+
+    #[test]
+    fn idle_workers_are_parked() {
+        let pool = IoPool::new(4);
+        let pollers = Arc::new(Mutex::new(std::collections::HashSet::new()));
+
+        for _ in 0..8 {
+            let pollers = pollers.clone();
+            pool.submit(move || {
+                pollers.lock().unwrap().insert(thread::current().id());
+                TaskStatus::Idle
+            });
+        }
+
+        thread::sleep(Duration::from_millis(50));
+        pollers.lock().unwrap().clear();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(pollers.lock().unwrap().len(), 1);
+
+        let (tx, rx) = mpsc::channel();
+        pool.submit(move || {
+            tx.send(()).unwrap();
+            TaskStatus::Finished
+        });
+        rx.recv_timeout(Duration::from_secs(1)).unwrap();
+    }
+
+    #[test]
+    fn panicked_tasks_are_removed() {
+        let pool = IoPool::new(1);
+        let (tx, rx) = mpsc::channel();
+
+        pool.submit(|| panic!("task failure"));
+        pool.submit(move || {
+            tx.send(()).unwrap();
+            TaskStatus::Finished
+        });
+
+        rx.recv_timeout(Duration::from_secs(1)).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+use crate::core::io::{rfcomm, BluetoothClient, ChannelDetails};
+use crate::core::utils::SharedCloser;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::io_pool;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for BluetoothClient {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let writer = rfcomm::connect(self.addr, self.channel)?;
+        let reader = writer.try_clone()?;
+        let pool = io_pool();
+        writer.set_nonblocking(pool.is_some())?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::BluetoothClient {
+                addr: self.addr,
+                channel: self.channel,
+            });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = match &pool {
+            Some(pool) => channel.spawn_in(pool),
+            None => channel.spawn(),
+        };
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::core::io::{ChannelDetails, IpcClient};
+use crate::core::utils::SharedCloser;
+use crate::sync::io::transport::ipc::segment::{IpcLink, Role, Segment};
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for IpcClient {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let path = self.path.clone();
+        let segment = Arc::new(Segment::open(path.as_path())?);
+        let session = segment.connect()?;
+        let (reader, writer) = IpcLink::new(segment, Role::Client, session).split();
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::IpcClient { path });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = channel.spawn();
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
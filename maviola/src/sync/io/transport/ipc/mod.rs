@@ -0,0 +1,3 @@
+pub mod client;
+mod segment;
+pub mod server;
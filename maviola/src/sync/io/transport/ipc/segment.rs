@@ -0,0 +1,598 @@
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::os::fd::AsRawFd;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::consts::MAX_IPC_CAPACITY;
+use crate::sync::consts::{
+    IPC_IDLE_INTERVAL, IPC_PEER_TIMEOUT, IPC_READ_TIMEOUT, IPC_SPIN_DURATION,
+};
+
+/// Magic number at the start of every segment.
+const MAGIC: u32 = u32::from_le_bytes(*b"MVIP");
+/// Version of segment layout.
+const LAYOUT_VERSION: u32 = 1;
+/// Size of a segment header, ring buffers start right after it.
+const HEADER_SIZE: usize = 512;
+/// Offset of the first ring cursor.
+const CURSORS_OFFSET: usize = 64;
+/// Ring cursors are placed on separate cache lines, so reader and writer don't contend.
+const CURSOR_STRIDE: usize = 64;
+
+/// Ring buffer for frames sent by server.
+const SERVER_RING: usize = 0;
+/// Ring buffer for frames sent by client.
+const CLIENT_RING: usize = 1;
+
+/// Server is waiting for a client.
+const STATE_FREE: u32 = 0;
+/// Client is resetting ring buffers.
+const STATE_CLAIMED: u32 = 1;
+/// Client is attached.
+const STATE_CONNECTED: u32 = 2;
+/// Either side has detached, server should free the segment.
+const STATE_CLOSED: u32 = 3;
+
+/// Layout of a segment header.
+///
+/// Segments are shared between processes, so all fields are accessed atomically.
+#[repr(C)]
+struct Header {
+    magic: AtomicU32,
+    version: AtomicU32,
+    capacity: AtomicU64,
+    state: AtomicU32,
+    server_closed: AtomicU32,
+    session: AtomicU64,
+    server_alive_at: AtomicU64,
+    client_alive_at: AtomicU64,
+}
+
+/// Side of a shared memory link.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Role {
+    Server,
+    Client,
+}
+
+/// Shared memory segment mapped into the address space of a process.
+///
+/// Segment consists of a [`Header`] and two single-producer / single-consumer ring buffers, one
+/// for each direction.
+pub(super) struct Segment {
+    ptr: *mut u8,
+    len: usize,
+    capacity: usize,
+    _file: File,
+}
+
+// Segment memory is accessed only through atomics and ring buffers guarded by them.
+unsafe impl Send for Segment {}
+unsafe impl Sync for Segment {}
+
+/// Session of a client attached to a [`Segment`].
+///
+/// Once client is detached, the link becomes inactive and never transfers data again, even if
+/// another client has attached to the same segment.
+pub(super) struct IpcLink {
+    segment: Arc<Segment>,
+    role: Role,
+    session: u64,
+}
+
+/// Reads data sent by the peer of an [`IpcLink`].
+///
+/// Returns [`ErrorKind::TimedOut`], if no data arrived within [`IPC_READ_TIMEOUT`], so the reading
+/// thread may check whether the channel is closed. Returns end of file, once link is inactive.
+///
+/// Detaches the link, once dropped.
+pub(super) struct IpcReader {
+    link: Arc<IpcLink>,
+    last_active: Instant,
+}
+
+/// Writes data to the peer of an [`IpcLink`].
+///
+/// Waits, while ring buffer is full. Returns [`ErrorKind::BrokenPipe`], once link is inactive.
+pub(super) struct IpcWriter {
+    link: Arc<IpcLink>,
+}
+
+impl Segment {
+    /// Creates a segment with ring buffers of `capacity` bytes at `path`.
+    ///
+    /// Capacity must be a power of two. Fails, if `path` already exists.
+    pub(super) fn create(path: &Path, capacity: usize) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        let len = HEADER_SIZE + 2 * capacity;
+        file.set_len(len as u64)?;
+
+        let segment = Self::map(file, len, capacity)?;
+        let header = segment.header();
+        header.capacity.store(capacity as u64, Ordering::Relaxed);
+        header.version.store(LAYOUT_VERSION, Ordering::Relaxed);
+        header.state.store(STATE_FREE, Ordering::Relaxed);
+        header.server_alive_at.store(now(), Ordering::Relaxed);
+        header.magic.store(MAGIC, Ordering::Release);
+
+        Ok(segment)
+    }
+
+    /// Opens a segment created by server at `path`.
+    pub(super) fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len < HEADER_SIZE {
+            return Err(invalid_segment(path));
+        }
+
+        let mut segment = Self::map(file, len, 0)?;
+        let header = segment.header();
+        if header.magic.load(Ordering::Acquire) != MAGIC
+            || header.version.load(Ordering::Relaxed) != LAYOUT_VERSION
+        {
+            return Err(invalid_segment(path));
+        }
+
+        // Header can be written by anyone with access to the file, capacity should not be trusted
+        let capacity = match usize::try_from(header.capacity.load(Ordering::Relaxed)) {
+            Ok(capacity) if capacity.is_power_of_two() && capacity <= MAX_IPC_CAPACITY => capacity,
+            _ => return Err(invalid_segment(path)),
+        };
+        let expected_len = capacity
+            .checked_mul(2)
+            .and_then(|rings_len| rings_len.checked_add(HEADER_SIZE));
+        if expected_len != Some(len) {
+            return Err(invalid_segment(path));
+        }
+
+        segment.capacity = capacity;
+        Ok(segment)
+    }
+
+    fn map(file: File, len: usize, capacity: usize) -> std::io::Result<Self> {
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            ptr: ptr as *mut u8,
+            len,
+            capacity,
+            _file: file,
+        })
+    }
+
+    /// Attaches a client to the segment.
+    ///
+    /// Returns `ID` of a new session. Fails, if server is not running or already has a client.
+    pub(super) fn connect(&self) -> std::io::Result<u64> {
+        let header = self.header();
+        if !self.is_alive(Role::Server) {
+            return Err(std::io::Error::new(
+                ErrorKind::ConnectionRefused,
+                "shared memory server is not running",
+            ));
+        }
+        if header
+            .state
+            .compare_exchange(
+                STATE_FREE,
+                STATE_CLAIMED,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return Err(std::io::Error::new(
+                ErrorKind::AddrInUse,
+                "shared memory server already has a client",
+            ));
+        }
+
+        for ring in [SERVER_RING, CLIENT_RING] {
+            self.head(ring).store(0, Ordering::Relaxed);
+            self.tail(ring).store(0, Ordering::Relaxed);
+        }
+        let session = header.session.fetch_add(1, Ordering::Relaxed) + 1;
+        self.touch(Role::Client);
+        header.state.store(STATE_CONNECTED, Ordering::Release);
+
+        Ok(session)
+    }
+
+    /// Returns `ID` of a session, if client has attached to the segment.
+    pub(super) fn accept(&self) -> Option<u64> {
+        let header = self.header();
+        match header.state.load(Ordering::Acquire) {
+            STATE_CONNECTED => Some(header.session.load(Ordering::Relaxed)),
+            _ => None,
+        }
+    }
+
+    /// Frees segment for the next client, once channel of the previous session is closed.
+    pub(super) fn release(&self) {
+        self.header().state.store(STATE_FREE, Ordering::Release);
+    }
+
+    /// Marks segment as abandoned by server.
+    pub(super) fn shutdown(&self) {
+        let header = self.header();
+        header.server_closed.store(1, Ordering::Release);
+        header.state.store(STATE_CLOSED, Ordering::Release);
+    }
+
+    /// Reports, that `role` side is alive.
+    pub(super) fn touch(&self, role: Role) {
+        self.alive_at(role).store(now(), Ordering::Relaxed);
+    }
+
+    fn is_alive(&self, role: Role) -> bool {
+        if role == Role::Server && self.header().server_closed.load(Ordering::Acquire) != 0 {
+            return false;
+        }
+        let alive_at = self.alive_at(role).load(Ordering::Relaxed);
+        now().saturating_sub(alive_at) < IPC_PEER_TIMEOUT.as_nanos() as u64
+    }
+
+    /// Writes as much of `buf` into `ring` as fits, returns the number of written bytes.
+    ///
+    /// Fails, if ring cursors are corrupted by the peer.
+    fn push(&self, ring: usize, buf: &[u8]) -> std::io::Result<usize> {
+        let head = self.head(ring).load(Ordering::Relaxed);
+        let tail = self.tail(ring).load(Ordering::Acquire);
+        let free = self.capacity - self.used(head, tail)?;
+        let n = free.min(buf.len());
+        if n == 0 {
+            return Ok(0);
+        }
+
+        let start = head as usize & (self.capacity - 1);
+        let first = n.min(self.capacity - start);
+        unsafe {
+            let data = self.data(ring);
+            std::ptr::copy_nonoverlapping(buf.as_ptr(), data.add(start), first);
+            std::ptr::copy_nonoverlapping(buf.as_ptr().add(first), data, n - first);
+        }
+        self.head(ring)
+            .store(head.wrapping_add(n as u64), Ordering::Release);
+
+        Ok(n)
+    }
+
+    /// Reads available data from `ring` into `buf`, returns the number of read bytes.
+    ///
+    /// Fails, if ring cursors are corrupted by the peer.
+    fn pop(&self, ring: usize, buf: &mut [u8]) -> std::io::Result<usize> {
+        let tail = self.tail(ring).load(Ordering::Relaxed);
+        let head = self.head(ring).load(Ordering::Acquire);
+        let n = self.used(head, tail)?.min(buf.len());
+        if n == 0 {
+            return Ok(0);
+        }
+
+        let start = tail as usize & (self.capacity - 1);
+        let first = n.min(self.capacity - start);
+        unsafe {
+            let data = self.data(ring);
+            std::ptr::copy_nonoverlapping(data.add(start), buf.as_mut_ptr(), first);
+            std::ptr::copy_nonoverlapping(data, buf.as_mut_ptr().add(first), n - first);
+        }
+        self.tail(ring)
+            .store(tail.wrapping_add(n as u64), Ordering::Release);
+
+        Ok(n)
+    }
+
+    /// Number of bytes between `tail` and `head` cursors of a ring buffer.
+    ///
+    /// Cursors are written by both processes, so they are validated before use.
+    fn used(&self, head: u64, tail: u64) -> std::io::Result<usize> {
+        let used = head.wrapping_sub(tail);
+        if used > self.capacity as u64 {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "shared memory ring buffer is corrupted",
+            ));
+        }
+        Ok(used as usize)
+    }
+
+    fn header(&self) -> &Header {
+        unsafe { &*(self.ptr as *const Header) }
+    }
+
+    fn alive_at(&self, role: Role) -> &AtomicU64 {
+        match role {
+            Role::Server => &self.header().server_alive_at,
+            Role::Client => &self.header().client_alive_at,
+        }
+    }
+
+    fn head(&self, ring: usize) -> &AtomicU64 {
+        self.cursor(2 * ring)
+    }
+
+    fn tail(&self, ring: usize) -> &AtomicU64 {
+        self.cursor(2 * ring + 1)
+    }
+
+    fn cursor(&self, idx: usize) -> &AtomicU64 {
+        unsafe { &*(self.ptr.add(CURSORS_OFFSET + idx * CURSOR_STRIDE) as *const AtomicU64) }
+    }
+
+    fn data(&self, ring: usize) -> *mut u8 {
+        unsafe { self.ptr.add(HEADER_SIZE + ring * self.capacity) }
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+impl IpcLink {
+    /// Creates a link for a `session` of a `segment` as seen from the `role` side.
+    pub(super) fn new(segment: Arc<Segment>, role: Role, session: u64) -> Self {
+        Self {
+            segment,
+            role,
+            session,
+        }
+    }
+
+    /// Splits link into reader and writer.
+    pub(super) fn split(self) -> (IpcReader, IpcWriter) {
+        let link = Arc::new(self);
+        (
+            IpcReader {
+                link: link.clone(),
+                last_active: Instant::now(),
+            },
+            IpcWriter { link },
+        )
+    }
+
+    fn is_active(&self) -> bool {
+        let header = self.segment.header();
+        header.state.load(Ordering::Acquire) == STATE_CONNECTED
+            && header.session.load(Ordering::Relaxed) == self.session
+            && self.segment.is_alive(self.peer())
+    }
+
+    fn detach(&self) {
+        let header = self.segment.header();
+        if header.session.load(Ordering::Relaxed) == self.session {
+            _ = header.state.compare_exchange(
+                STATE_CONNECTED,
+                STATE_CLOSED,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    fn peer(&self) -> Role {
+        match self.role {
+            Role::Server => Role::Client,
+            Role::Client => Role::Server,
+        }
+    }
+
+    fn rx_ring(&self) -> usize {
+        match self.role {
+            Role::Server => CLIENT_RING,
+            Role::Client => SERVER_RING,
+        }
+    }
+
+    fn tx_ring(&self) -> usize {
+        match self.role {
+            Role::Server => SERVER_RING,
+            Role::Client => CLIENT_RING,
+        }
+    }
+}
+
+impl Read for IpcReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let started_at = Instant::now();
+        loop {
+            let n = self.link.segment.pop(self.link.rx_ring(), buf)?;
+            if n > 0 {
+                self.last_active = Instant::now();
+                return Ok(n);
+            }
+
+            self.link.segment.touch(self.link.role);
+            if !self.link.is_active() {
+                return Ok(0);
+            }
+            if started_at.elapsed() >= IPC_READ_TIMEOUT {
+                return Err(ErrorKind::TimedOut.into());
+            }
+            idle(self.last_active);
+        }
+    }
+}
+
+impl Drop for IpcReader {
+    fn drop(&mut self) {
+        self.link.detach();
+    }
+}
+
+impl Write for IpcWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let started_at = Instant::now();
+        loop {
+            if !self.link.is_active() {
+                return Err(ErrorKind::BrokenPipe.into());
+            }
+            let n = self.link.segment.push(self.link.tx_ring(), buf)?;
+            if n > 0 {
+                return Ok(n);
+            }
+            idle(started_at);
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Waits for the peer without giving up the CPU shortly after the last activity, so bursts of
+/// frames are passed with minimal latency, and sleeps otherwise.
+fn idle(last_active: Instant) {
+    if last_active.elapsed() < IPC_SPIN_DURATION {
+        thread::yield_now();
+    } else {
+        thread::sleep(IPC_IDLE_INTERVAL);
+    }
+}
+
+/// Monotonic time in nanoseconds, which is consistent between processes.
+fn now() -> u64 {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+    }
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32).as_nanos() as u64
+}
+
+fn invalid_segment(path: &Path) -> std::io::Error {
+    std::io::Error::new(
+        ErrorKind::InvalidData,
+        format!("not a shared memory segment: {path:?}"),
+    )
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod segment_tests {
+    use super::*;
+
+    #[test]
+    fn data_wraps_around_ring_buffers() {
+        let path = std::env::temp_dir().join(format!(
+            "maviola_segment_{}.ipc",
+            portpicker::pick_unused_port().unwrap()
+        ));
+        let server = Arc::new(Segment::create(path.as_path(), 16).unwrap());
+        let client = Arc::new(Segment::open(path.as_path()).unwrap());
+        std::fs::remove_file(path.as_path()).unwrap();
+
+        let session = client.connect().unwrap();
+        assert!(client.connect().is_err());
+        assert_eq!(server.accept(), Some(session));
+
+        let (mut server_reader, mut server_writer) =
+            IpcLink::new(server.clone(), Role::Server, session).split();
+        let (mut client_reader, mut client_writer) =
+            IpcLink::new(client.clone(), Role::Client, session).split();
+
+        let mut buf = [0u8; 16];
+        for i in 0..10u8 {
+            let data = [i; 12];
+            assert_eq!(client_writer.write(&data).unwrap(), 12);
+            server_reader.read_exact(&mut buf[..12]).unwrap();
+            assert_eq!(buf[..12], data);
+
+            server_writer.write_all(&data[..5]).unwrap();
+            client_reader.read_exact(&mut buf[..5]).unwrap();
+            assert_eq!(buf[..5], data[..5]);
+        }
+
+        // Ring buffer is full
+        assert_eq!(client_writer.write(&[0u8; 20]).unwrap(), 16);
+
+        // Detached links are inactive, once pending data is read
+        drop(client_reader);
+        assert_eq!(server_reader.read(&mut buf).unwrap(), 16);
+        assert_eq!(server_reader.read(&mut buf).unwrap(), 0);
+        assert!(server_writer.write(&[0u8; 1]).is_err());
+
+        // Segment is freed for the next client
+        server.release();
+        let next_session = client.connect().unwrap();
+        assert_ne!(next_session, session);
+        assert!(client_writer.write(&[0u8; 1]).is_err());
+
+        server.shutdown();
+        assert!(client.connect().is_err());
+    }
+
+    #[test]
+    fn corrupted_cursors_are_rejected() {
+        let path = std::env::temp_dir().join(format!(
+            "maviola_segment_{}.ipc",
+            portpicker::pick_unused_port().unwrap()
+        ));
+        let segment = Segment::create(path.as_path(), 16).unwrap();
+        std::fs::remove_file(path.as_path()).unwrap();
+
+        segment.head(SERVER_RING).store(4, Ordering::Relaxed);
+        segment.tail(SERVER_RING).store(8, Ordering::Relaxed);
+        let err = segment.push(SERVER_RING, &[0u8; 4]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        segment.head(CLIENT_RING).store(100, Ordering::Relaxed);
+        let err = segment.pop(CLIENT_RING, &mut [0u8; 4]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn overflowing_capacity_is_rejected() {
+        let path = std::env::temp_dir().join(format!(
+            "maviola_segment_{}.ipc",
+            portpicker::pick_unused_port().unwrap()
+        ));
+
+        // Ring buffers of this capacity wrap segment length to the size of a header
+        let mut header = vec![0u8; HEADER_SIZE];
+        header[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        header[4..8].copy_from_slice(&LAYOUT_VERSION.to_le_bytes());
+        header[8..16].copy_from_slice(&(1u64 << 63).to_le_bytes());
+        std::fs::write(path.as_path(), header.as_slice()).unwrap();
+
+        let result = Segment::open(path.as_path());
+        std::fs::remove_file(path.as_path()).unwrap();
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidData);
+    }
+}
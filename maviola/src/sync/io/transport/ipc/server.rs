@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::thread;
+
+use crate::core::io::{ChannelDetails, ConnectionConf, IpcServer};
+use crate::core::utils::{Closer, SharedCloser};
+use crate::sync::consts::IPC_ACCEPT_INTERVAL;
+use crate::sync::io::transport::ipc::segment::{IpcLink, Role, Segment};
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for IpcServer {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let path = self.path.clone();
+        let segment = Arc::new(Segment::create(path.as_path(), self.capacity)?);
+
+        let conn_state = Closer::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), conn_state.to_shared());
+
+        let info = self.info().clone();
+
+        let handler = ConnectionHandler::spawn(move || -> Result<()> {
+            let mut channel_state: Option<SharedCloser> = None;
+
+            while !conn_state.is_closed() {
+                segment.touch(Role::Server);
+
+                if matches!(&channel_state, Some(state) if state.is_closed()) {
+                    log::debug!("[{info:?}] client detached");
+                    channel_state = None;
+                    segment.release();
+                }
+
+                if channel_state.is_none() {
+                    if let Some(session) = segment.accept() {
+                        log::debug!("[{info:?}] client attached, session: {session}");
+                        let (reader, writer) =
+                            IpcLink::new(segment.clone(), Role::Server, session).split();
+                        let chan_info = info
+                            .make_channel_info(ChannelDetails::IpcServer { path: path.clone() });
+                        let channel = chan_factory.build(chan_info, reader, writer);
+                        channel_state = Some(channel.spawn());
+                    }
+                }
+
+                thread::sleep(IPC_ACCEPT_INTERVAL);
+            }
+
+            segment.shutdown();
+            if let Err(err) = std::fs::remove_file(path.as_path()) {
+                log::debug!("[{info:?}] can't remove shared memory segment: {err:?}");
+            }
+
+            Ok(())
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+}
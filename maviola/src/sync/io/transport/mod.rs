@@ -1,7 +1,26 @@
 //! # 🔒 Synchronous transport implementations
 
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+mod bluetooth;
 mod file;
+#[cfg(all(feature = "ipc", unix))]
+mod ipc;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis;
+mod resolution;
+#[cfg(feature = "serial")]
+mod serial;
 #[cfg(unix)]
 mod sock;
+mod spec;
 mod tcp;
+mod tlog;
 mod udp;
+#[cfg(feature = "zmq")]
+mod zmq;
+
+pub use tcp::TcpHandshake;
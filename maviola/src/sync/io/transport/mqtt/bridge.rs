@@ -0,0 +1,69 @@
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::mqtt_wire::{MqttPublisher, MqttSubscriber};
+use crate::core::io::{ChannelDetails, MqttBridge};
+use crate::core::utils::SharedCloser;
+use crate::sync::consts::TCP_WRITE_TIMEOUT;
+use crate::sync::io::transport::mqtt::stream::{handshake, ping, MqttReader, MqttWriter};
+use crate::sync::io::transport::tcp::server::on_channel_close_handler;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for MqttBridge {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        stream.set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+
+        let decoder = handshake(&mut stream, self)?;
+
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::MqttBridge {
+                broker_addr: self.addr,
+                client_id: self.client_id.clone(),
+            });
+        let reader = MqttReader::new(
+            stream.try_clone()?,
+            decoder,
+            MqttSubscriber::new::<V>(self.format.clone()),
+        );
+        let writer = Arc::new(Mutex::new(stream.try_clone()?));
+        let publisher = MqttPublisher::new(&self.publish_topic, self.format.clone());
+
+        let channel_state = chan_factory
+            .build(
+                chan_info,
+                reader,
+                MqttWriter::new(writer.clone(), publisher),
+            )
+            .spawn();
+        if let Some(interval) = self.ping_interval() {
+            let state = channel_state.to_closable();
+            spawn_io(move || ping(writer, interval, state));
+        }
+        on_channel_close_handler(channel_state.to_closable(), stream);
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,2 @@
+mod bridge;
+mod stream;
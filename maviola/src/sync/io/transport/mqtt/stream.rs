@@ -0,0 +1,161 @@
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
+use crate::core::io::mqtt_wire::{self, MqttDecoder, MqttPacket, MqttPublisher, MqttSubscriber};
+use crate::core::io::MqttBridge;
+use crate::core::utils::Closable;
+
+use crate::prelude::*;
+
+/// Reads serialized MAVLink frames from payloads of messages published to subscribed topics.
+pub(super) struct MqttReader<R: Read> {
+    inner: R,
+    decoder: MqttDecoder,
+    subscriber: MqttSubscriber,
+    pending: Vec<u8>,
+    consumed: usize,
+}
+
+/// Publishes serialized MAVLink frames to a broker.
+///
+/// Stream is shared with the keep alive pinger.
+pub(super) struct MqttWriter<W: Write> {
+    inner: Arc<Mutex<W>>,
+    publisher: MqttPublisher,
+}
+
+/// Establishes MQTT session over a blocking `stream` and subscribes to topics.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) fn handshake<S: Read + Write>(stream: &mut S, conf: &MqttBridge) -> Result<MqttDecoder> {
+    let mut decoder = MqttDecoder::default();
+
+    stream.write_all(&mqtt_wire::connect(&conf.session())?)?;
+    mqtt_wire::check_connack(next_packet(stream, &mut decoder)?)?;
+
+    if !conf.subscriptions.is_empty() {
+        stream.write_all(&mqtt_wire::subscribe(&conf.subscriptions)?)?;
+        let packet = loop {
+            match next_packet(stream, &mut decoder)? {
+                MqttPacket::SubAck(codes) => break MqttPacket::SubAck(codes),
+                packet => log::trace!("packet received before subscription: {packet:?}"),
+            }
+        };
+        mqtt_wire::check_suback(packet, &conf.subscriptions)?;
+    }
+
+    Ok(decoder)
+}
+
+/// Sends `PINGREQ` packets with the specified `interval` until `state` is closed.
+pub(super) fn ping<W: Write>(writer: Arc<Mutex<W>>, interval: Duration, state: Closable) {
+    let mut last_ping = Instant::now();
+
+    while !state.is_closed() {
+        thread::sleep(SERVER_HANG_UP_TIMEOUT.min(interval));
+        if last_ping.elapsed() < interval {
+            continue;
+        }
+        last_ping = Instant::now();
+
+        let result = match writer.lock() {
+            Ok(mut writer) => writer.write_all(&mqtt_wire::PINGREQ),
+            Err(err) => err.into_inner().write_all(&mqtt_wire::PINGREQ),
+        };
+        if let Err(err) = result {
+            log::debug!("can't ping MQTT broker: {err:?}");
+            return;
+        }
+    }
+}
+
+fn next_packet<R: Read>(stream: &mut R, decoder: &mut MqttDecoder) -> Result<MqttPacket> {
+    let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(packet) = decoder.next()? {
+            return Ok(packet);
+        }
+        let bytes_read = stream.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+impl<R: Read> MqttReader<R> {
+    pub(super) fn new(inner: R, decoder: MqttDecoder, subscriber: MqttSubscriber) -> Self {
+        Self {
+            inner,
+            decoder,
+            subscriber,
+            pending: Vec::new(),
+            consumed: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for MqttReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut buffer = [0u8; mqtt_wire::READ_BUFFER_SIZE];
+
+        loop {
+            if self.consumed < self.pending.len() {
+                let available = &self.pending[self.consumed..];
+                let size = available.len().min(buf.len());
+                buf[..size].copy_from_slice(&available[..size]);
+                self.consumed += size;
+                return Ok(size);
+            }
+
+            match self.decoder.next() {
+                Ok(Some(packet)) => {
+                    if let Some(frame) = self.subscriber.next_frame(packet) {
+                        self.pending = frame;
+                        self.consumed = 0;
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                }
+            }
+
+            let bytes_read = self.inner.read(&mut buffer)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.decoder.push(&buffer[..bytes_read]);
+        }
+    }
+}
+
+impl<W: Write> MqttWriter<W> {
+    pub(super) fn new(inner: Arc<Mutex<W>>, publisher: MqttPublisher) -> Self {
+        Self { inner, publisher }
+    }
+}
+
+impl<W: Write> Write for MqttWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.publisher.push(buf);
+        while let Some(packet) = self.publisher.next() {
+            match self.inner.lock() {
+                Ok(mut inner) => inner.write_all(&packet)?,
+                Err(err) => err.into_inner().write_all(&packet)?,
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.inner.lock() {
+            Ok(mut inner) => inner.flush(),
+            Err(err) => err.into_inner().flush(),
+        }
+    }
+}
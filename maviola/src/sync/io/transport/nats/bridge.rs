@@ -0,0 +1,54 @@
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::nats_wire::NatsPublisher;
+use crate::core::io::{ChannelDetails, NatsBridge};
+use crate::core::utils::SharedCloser;
+use crate::sync::consts::TCP_WRITE_TIMEOUT;
+use crate::sync::io::transport::nats::stream::{handshake, NatsReader, NatsWriter};
+use crate::sync::io::transport::tcp::server::on_channel_close_handler;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for NatsBridge {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        stream.set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+
+        let decoder = handshake(&mut stream, self)?;
+
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::NatsBridge {
+                server_addr: self.addr,
+                subject: self.subject.clone(),
+            });
+        let writer = Arc::new(Mutex::new(stream.try_clone()?));
+        let reader = NatsReader::new(stream.try_clone()?, writer.clone(), decoder);
+        let writer = NatsWriter::new(writer, NatsPublisher::new(&self.subject));
+
+        let channel_state = chan_factory.build(chan_info, reader, writer).spawn();
+        on_channel_close_handler(channel_state.to_closable(), stream);
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
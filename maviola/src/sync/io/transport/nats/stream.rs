@@ -0,0 +1,142 @@
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::core::io::nats_wire::{self, NatsDecoder, NatsMessage, NatsPublisher};
+use crate::core::io::NatsBridge;
+
+use crate::prelude::*;
+
+/// Reads serialized MAVLink frames from messages delivered to the subscription.
+///
+/// Answers server pings using the shared writer.
+pub(super) struct NatsReader<R: Read, W: Write> {
+    inner: R,
+    writer: Arc<Mutex<W>>,
+    decoder: NatsDecoder,
+    pending: Vec<u8>,
+    consumed: usize,
+}
+
+/// Publishes serialized MAVLink frames to a subject.
+///
+/// Stream is shared with the reader, that answers server pings.
+pub(super) struct NatsWriter<W: Write> {
+    inner: Arc<Mutex<W>>,
+    publisher: NatsPublisher,
+}
+
+/// Establishes NATS session over a blocking `stream` and subscribes to the subject.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) fn handshake<S: Read + Write>(stream: &mut S, conf: &NatsBridge) -> Result<NatsDecoder> {
+    let mut decoder = NatsDecoder::default();
+
+    nats_wire::check_info(next_message(stream, &mut decoder)?)?;
+    stream.write_all(&nats_wire::connect(&conf.session()))?;
+    while !nats_wire::check_connected(next_message(stream, &mut decoder)?)? {}
+    stream.write_all(&nats_wire::subscribe(&conf.subject))?;
+
+    Ok(decoder)
+}
+
+fn next_message<R: Read>(stream: &mut R, decoder: &mut NatsDecoder) -> Result<NatsMessage> {
+    let mut buffer = [0u8; nats_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(message) = decoder.next()? {
+            return Ok(message);
+        }
+        let bytes_read = stream.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+fn write_shared<W: Write>(writer: &Mutex<W>, bytes: &[u8]) -> std::io::Result<()> {
+    match writer.lock() {
+        Ok(mut writer) => writer.write_all(bytes),
+        Err(err) => err.into_inner().write_all(bytes),
+    }
+}
+
+impl<R: Read, W: Write> NatsReader<R, W> {
+    pub(super) fn new(inner: R, writer: Arc<Mutex<W>>, decoder: NatsDecoder) -> Self {
+        Self {
+            inner,
+            writer,
+            decoder,
+            pending: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    fn handle(&mut self, message: NatsMessage) -> std::io::Result<()> {
+        match message {
+            NatsMessage::Msg { payload, .. } => {
+                self.pending = payload;
+                self.consumed = 0;
+            }
+            NatsMessage::Ping => write_shared(&self.writer, nats_wire::PONG)?,
+            NatsMessage::Err(err) => log::warn!("NATS server error: {err}"),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read, W: Write> Read for NatsReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut buffer = [0u8; nats_wire::READ_BUFFER_SIZE];
+
+        loop {
+            if self.consumed < self.pending.len() {
+                let available = &self.pending[self.consumed..];
+                let size = available.len().min(buf.len());
+                buf[..size].copy_from_slice(&available[..size]);
+                self.consumed += size;
+                return Ok(size);
+            }
+
+            match self.decoder.next() {
+                Ok(Some(message)) => {
+                    self.handle(message)?;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                }
+            }
+
+            let bytes_read = self.inner.read(&mut buffer)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.decoder.push(&buffer[..bytes_read]);
+        }
+    }
+}
+
+impl<W: Write> NatsWriter<W> {
+    pub(super) fn new(inner: Arc<Mutex<W>>, publisher: NatsPublisher) -> Self {
+        Self { inner, publisher }
+    }
+}
+
+impl<W: Write> Write for NatsWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.publisher.push(buf);
+        while let Some(message) = self.publisher.next() {
+            write_shared(&self.inner, &message)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.inner.lock() {
+            Ok(mut inner) => inner.flush(),
+            Err(err) => err.into_inner().flush(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::core::io::redis_wire::{Echoes, RedisPublisher};
+use crate::core::io::{ChannelDetails, RedisBridge};
+use crate::core::utils::SharedCloser;
+use crate::sync::consts::TCP_WRITE_TIMEOUT;
+use crate::sync::io::transport::redis::stream::{
+    connect, drain_replies, subscribe, RedisReader, RedisWriter,
+};
+use crate::sync::io::transport::tcp::server::on_channel_close_handler;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for RedisBridge {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        // Subscribed clients can't publish, so each direction has its own server connection
+        let (mut sub_stream, mut sub_decoder) = connect(self.addr, self)?;
+        subscribe(&mut sub_stream, &mut sub_decoder, &self.channel)?;
+        let (pub_stream, pub_decoder) = connect(self.addr, self)?;
+
+        for stream in [&sub_stream, &pub_stream] {
+            stream.set_read_timeout(None)?;
+            stream.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+        }
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::RedisBridge {
+                server_addr: self.addr,
+                channel: self.channel.clone(),
+            });
+
+        {
+            let info = self.info.clone();
+            let reader = pub_stream.try_clone()?;
+            spawn_io(move || {
+                if let Err(err) = drain_replies(reader, pub_decoder) {
+                    log::debug!("[{info:?}] publisher connection failed: {err:?}");
+                }
+            });
+        }
+
+        let echoes = Echoes::default();
+        let reader = RedisReader::new(
+            sub_stream.try_clone()?,
+            sub_decoder,
+            &self.channel,
+            echoes.clone(),
+        );
+        let writer = RedisWriter::new(
+            pub_stream.try_clone()?,
+            RedisPublisher::new(&self.channel, echoes),
+        );
+
+        let channel_state = chan_factory.build(chan_info, reader, writer).spawn();
+        on_channel_close_handler(channel_state.to_closable(), sub_stream);
+        on_channel_close_handler(channel_state.to_closable(), pub_stream);
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::redis_wire::{self, Echoes, RedisDecoder, RedisPublisher, RedisReply};
+use crate::core::io::RedisBridge;
+
+use crate::prelude::*;
+
+/// Reads serialized MAVLink frames from messages published to the channel.
+///
+/// Skips echoes of messages published by the bridge itself.
+pub(super) struct RedisReader<R: Read> {
+    inner: R,
+    decoder: RedisDecoder,
+    channel: String,
+    echoes: Echoes,
+    pending: Vec<u8>,
+    consumed: usize,
+}
+
+/// Publishes serialized MAVLink frames to the channel.
+pub(super) struct RedisWriter<W: Write> {
+    inner: W,
+    publisher: RedisPublisher,
+}
+
+/// Connects to a Redis server and authenticates, if credentials are set.
+///
+/// Returned stream has handshake timeouts, that should be reset once handshake is complete.
+pub(super) fn connect(addr: SocketAddr, conf: &RedisBridge) -> Result<(TcpStream, RedisDecoder)> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+
+    let mut decoder = RedisDecoder::default();
+    if let Some((username, password)) = &conf.credentials {
+        stream.write_all(&redis_wire::auth(username.as_deref(), password))?;
+        redis_wire::check_ok(next_reply(&mut stream, &mut decoder)?)?;
+    }
+
+    Ok((stream, decoder))
+}
+
+/// Subscribes to the channel.
+pub(super) fn subscribe<S: Read + Write>(
+    stream: &mut S,
+    decoder: &mut RedisDecoder,
+    channel: &str,
+) -> Result<()> {
+    stream.write_all(&redis_wire::subscribe(channel))?;
+    redis_wire::check_subscribed(next_reply(stream, decoder)?, channel)?;
+    Ok(())
+}
+
+/// Reads replies to published messages until the stream is closed.
+///
+/// Replies are only checked for errors, since number of receivers doesn't matter.
+pub(super) fn drain_replies<R: Read>(mut reader: R, mut decoder: RedisDecoder) -> Result<()> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+
+    loop {
+        while let Some(reply) = decoder.next()? {
+            if let RedisReply::Error(err) = reply {
+                log::warn!("Redis server error: {err}");
+            }
+        }
+
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+fn next_reply<R: Read>(stream: &mut R, decoder: &mut RedisDecoder) -> Result<RedisReply> {
+    let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+    loop {
+        if let Some(reply) = decoder.next()? {
+            return Ok(reply);
+        }
+        let bytes_read = stream.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    }
+}
+
+impl<R: Read> RedisReader<R> {
+    pub(super) fn new(inner: R, decoder: RedisDecoder, channel: &str, echoes: Echoes) -> Self {
+        Self {
+            inner,
+            decoder,
+            channel: channel.to_string(),
+            echoes,
+            pending: Vec::new(),
+            consumed: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for RedisReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut buffer = [0u8; redis_wire::READ_BUFFER_SIZE];
+
+        loop {
+            if self.consumed < self.pending.len() {
+                let available = &self.pending[self.consumed..];
+                let size = available.len().min(buf.len());
+                buf[..size].copy_from_slice(&available[..size]);
+                self.consumed += size;
+                return Ok(size);
+            }
+
+            match self.decoder.next() {
+                Ok(Some(reply)) => {
+                    if let Some(payload) = redis_wire::published_payload(reply, &self.channel) {
+                        if !self.echoes.is_echo(&payload) {
+                            self.pending = payload;
+                            self.consumed = 0;
+                        }
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                }
+            }
+
+            let bytes_read = self.inner.read(&mut buffer)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.decoder.push(&buffer[..bytes_read]);
+        }
+    }
+}
+
+impl<W: Write> RedisWriter<W> {
+    pub(super) fn new(inner: W, publisher: RedisPublisher) -> Self {
+        Self { inner, publisher }
+    }
+}
+
+impl<W: Write> Write for RedisWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.publisher.push(buf);
+        while let Some(command) = self.publisher.next() {
+            self.inner.write_all(&command)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
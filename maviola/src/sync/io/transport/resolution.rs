@@ -0,0 +1,67 @@
+use std::net::SocketAddr;
+use std::thread;
+use std::time::Instant;
+
+use crate::core::io::HostResolution;
+use crate::core::utils::SharedCloser;
+use crate::sync::consts::HOST_RESOLUTION_POOLING_INTERVAL;
+use crate::sync::io::ConnectionHandler;
+
+use crate::prelude::*;
+
+/// Resolves the current address of a client host.
+///
+/// Returns `addr` as is, if client was not created from host.
+pub(super) fn resolve_client_addr(
+    resolution: Option<&HostResolution>,
+    addr: SocketAddr,
+) -> Result<SocketAddr> {
+    match resolution {
+        Some(resolution) => resolution.resolve(),
+        None => Ok(addr),
+    }
+}
+
+/// Spawns a handler of a client connection to `addr`, that finishes, when the `state` becomes
+/// closed or the host of the client is resolved to a different address.
+///
+/// Falls back to [`ConnectionHandler::spawn_from_state`], if periodic resolution is disabled.
+pub(super) fn spawn_client_handler(
+    state: SharedCloser,
+    resolution: Option<&HostResolution>,
+    addr: SocketAddr,
+) -> ConnectionHandler {
+    let (resolution, interval) = match resolution {
+        Some(resolution) => match resolution.refresh_interval() {
+            Some(interval) => (resolution.clone(), interval),
+            None => return ConnectionHandler::spawn_from_state(state),
+        },
+        None => return ConnectionHandler::spawn_from_state(state),
+    };
+
+    ConnectionHandler::spawn(move || {
+        let mut last_check = Instant::now();
+
+        while !state.is_closed() {
+            thread::sleep(HOST_RESOLUTION_POOLING_INTERVAL);
+            if last_check.elapsed() < interval {
+                continue;
+            }
+            last_check = Instant::now();
+
+            match resolution.resolve() {
+                Ok(resolved) if resolved != addr => {
+                    log::info!(
+                        "{} is resolved to {resolved} instead of {addr}, closing connection",
+                        resolution.host()
+                    );
+                    break;
+                }
+                Ok(_) => {}
+                Err(err) => log::debug!("can't resolve {}: {err:?}", resolution.host()),
+            }
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,289 @@
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+
+use crate::core::io::HalfDuplex;
+use crate::sync::consts::SERIAL_READ_TIMEOUT;
+use crate::sync::io::transport::serial::duplex::HalfDuplexLine;
+
+/// Serial device configured in raw mode.
+///
+/// Reads are bounded by [`SERIAL_READ_TIMEOUT`]: if no data arrived in time, then
+/// [`ErrorKind::TimedOut`] is returned, so the reading thread may check whether the channel is
+/// closed.
+///
+/// In half-duplex mode, each write is transmitted entirely once the line is idle and the line is
+/// held until data was physically sent (see [`HalfDuplex`]).
+pub(super) struct SerialDevice {
+    file: File,
+    line: Option<HalfDuplexLine>,
+}
+
+impl SerialDevice {
+    /// Opens a serial device at `path` and sets specified `baud_rate`.
+    ///
+    /// Enables half-duplex mode, if `half_duplex` settings are provided.
+    pub(super) fn open(
+        path: &Path,
+        baud_rate: u32,
+        half_duplex: Option<HalfDuplex>,
+    ) -> std::io::Result<Self> {
+        let file = os::open(path)?;
+        os::configure(&file, baud_rate)?;
+        Ok(Self {
+            file,
+            line: half_duplex.map(HalfDuplexLine::new),
+        })
+    }
+
+    /// Creates a new handle to the same device.
+    ///
+    /// Handles share the state of a half-duplex line.
+    pub(super) fn try_clone(&self) -> std::io::Result<Self> {
+        Ok(Self {
+            file: self.file.try_clone()?,
+            line: self.line.clone(),
+        })
+    }
+}
+
+impl Read for SerialDevice {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.file.read(buf) {
+            Ok(0) if !buf.is_empty() => Err(ErrorKind::TimedOut.into()),
+            Ok(n) => {
+                if let Some(line) = &self.line {
+                    line.mark_rx();
+                }
+                Ok(n)
+            }
+            result => result,
+        }
+    }
+}
+
+impl Write for SerialDevice {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let line = match &self.line {
+            Some(line) => line,
+            None => return self.file.write(buf),
+        };
+
+        line.wait_for_idle();
+        self.file.write_all(buf)?;
+        os::drain(&self.file)?;
+        line.release();
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(unix)]
+mod os {
+    use std::fs::{File, OpenOptions};
+    use std::io::{Error, ErrorKind};
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::path::Path;
+
+    use super::SERIAL_READ_TIMEOUT;
+
+    pub(super) fn open(path: &Path) -> std::io::Result<File> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY)
+            .open(path)
+    }
+
+    pub(super) fn configure(file: &File, baud_rate: u32) -> std::io::Result<()> {
+        let fd = file.as_raw_fd();
+        let speed = speed(baud_rate)?;
+        // Read timeout is measured in tenths of a second
+        let timeout = (SERIAL_READ_TIMEOUT.as_millis() / 100).clamp(1, u8::MAX as u128);
+
+        // SAFETY: `fd` is a valid descriptor and `termios` is a valid pointer during the calls
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            check(libc::tcgetattr(fd, &mut termios))?;
+
+            libc::cfmakeraw(&mut termios);
+            termios.c_cflag |= libc::CLOCAL | libc::CREAD;
+            termios.c_cflag &= !(libc::CSTOPB | libc::PARENB | libc::CRTSCTS);
+            termios.c_iflag &= !(libc::IXON | libc::IXOFF | libc::IXANY);
+            termios.c_cc[libc::VMIN] = 0;
+            termios.c_cc[libc::VTIME] = timeout as libc::cc_t;
+
+            check(libc::cfsetispeed(&mut termios, speed))?;
+            check(libc::cfsetospeed(&mut termios, speed))?;
+            check(libc::tcsetattr(fd, libc::TCSANOW, &termios))?;
+            check(libc::tcflush(fd, libc::TCIOFLUSH))?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits until all written data is transmitted.
+    pub(super) fn drain(file: &File) -> std::io::Result<()> {
+        // SAFETY: `fd` is a valid descriptor during the call
+        check(unsafe { libc::tcdrain(file.as_raw_fd()) })
+    }
+
+    fn check(result: libc::c_int) -> std::io::Result<()> {
+        match result {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+
+    /// On Linux, only standard baud rates are supported by `termios`.
+    #[cfg(target_os = "linux")]
+    fn speed(baud_rate: u32) -> std::io::Result<libc::speed_t> {
+        Ok(match baud_rate {
+            50 => libc::B50,
+            75 => libc::B75,
+            110 => libc::B110,
+            134 => libc::B134,
+            150 => libc::B150,
+            200 => libc::B200,
+            300 => libc::B300,
+            600 => libc::B600,
+            1200 => libc::B1200,
+            1800 => libc::B1800,
+            2400 => libc::B2400,
+            4800 => libc::B4800,
+            9600 => libc::B9600,
+            19200 => libc::B19200,
+            38400 => libc::B38400,
+            57600 => libc::B57600,
+            115200 => libc::B115200,
+            230400 => libc::B230400,
+            460800 => libc::B460800,
+            500000 => libc::B500000,
+            576000 => libc::B576000,
+            921600 => libc::B921600,
+            1000000 => libc::B1000000,
+            1152000 => libc::B1152000,
+            1500000 => libc::B1500000,
+            2000000 => libc::B2000000,
+            2500000 => libc::B2500000,
+            3000000 => libc::B3000000,
+            3500000 => libc::B3500000,
+            4000000 => libc::B4000000,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("unsupported baud rate: {baud_rate}"),
+                ))
+            }
+        })
+    }
+
+    /// On BSD-derived systems, speed is the baud rate itself.
+    #[cfg(not(target_os = "linux"))]
+    fn speed(baud_rate: u32) -> std::io::Result<libc::speed_t> {
+        libc::speed_t::try_from(baud_rate).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("unsupported baud rate: {baud_rate}"),
+            )
+        })
+    }
+}
+
+#[cfg(windows)]
+mod os {
+    use std::fs::{File, OpenOptions};
+    use std::io::Error;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::{Path, PathBuf};
+
+    use windows_sys::Win32::Devices::Communication::{
+        GetCommState, SetCommState, SetCommTimeouts, COMMTIMEOUTS, DCB, NOPARITY, ONESTOPBIT,
+    };
+
+    use super::SERIAL_READ_TIMEOUT;
+
+    // Bit fields of `DCB`
+    const DCB_BINARY: u32 = 1;
+    const DCB_DTR_CONTROL_ENABLE: u32 = 1 << 4;
+    const DCB_RTS_CONTROL_ENABLE: u32 = 1 << 12;
+
+    pub(super) fn open(path: &Path) -> std::io::Result<File> {
+        // Ports like `COM10` are accessible only through device namespace
+        let path = match path.to_str() {
+            Some(name) if !name.starts_with(r"\\") => PathBuf::from(format!(r"\\.\{name}")),
+            _ => path.to_path_buf(),
+        };
+        OpenOptions::new().read(true).write(true).open(path)
+    }
+
+    pub(super) fn configure(file: &File, baud_rate: u32) -> std::io::Result<()> {
+        let handle = file.as_raw_handle() as windows_sys::Win32::Foundation::HANDLE;
+
+        // SAFETY: `handle` is a valid handle and structures are valid pointers during the calls
+        unsafe {
+            let mut dcb: DCB = std::mem::zeroed();
+            dcb.DCBlength = std::mem::size_of::<DCB>() as u32;
+            check(GetCommState(handle, &mut dcb))?;
+
+            dcb.BaudRate = baud_rate;
+            dcb._bitfield = DCB_BINARY | DCB_DTR_CONTROL_ENABLE | DCB_RTS_CONTROL_ENABLE;
+            dcb.ByteSize = 8;
+            dcb.Parity = NOPARITY;
+            dcb.StopBits = ONESTOPBIT;
+            check(SetCommState(handle, &dcb))?;
+
+            // Return as soon as any data is available, or once the timeout has passed
+            let timeouts = COMMTIMEOUTS {
+                ReadIntervalTimeout: u32::MAX,
+                ReadTotalTimeoutMultiplier: u32::MAX,
+                ReadTotalTimeoutConstant: SERIAL_READ_TIMEOUT.as_millis() as u32,
+                WriteTotalTimeoutMultiplier: 0,
+                WriteTotalTimeoutConstant: 0,
+            };
+            check(SetCommTimeouts(handle, &timeouts))?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits until all written data is transmitted.
+    pub(super) fn drain(file: &File) -> std::io::Result<()> {
+        // Flushes buffers of a communications device by `FlushFileBuffers`
+        file.sync_all()
+    }
+
+    fn check(result: windows_sys::Win32::Foundation::BOOL) -> std::io::Result<()> {
+        match result {
+            0 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod os {
+    use std::fs::File;
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    pub(super) fn open(_: &Path) -> std::io::Result<File> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "serial ports are not supported on this platform",
+        ))
+    }
+
+    pub(super) fn configure(_: &File, _: u32) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn drain(_: &File) -> std::io::Result<()> {
+        Ok(())
+    }
+}
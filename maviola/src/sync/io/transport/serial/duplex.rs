@@ -0,0 +1,142 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::io::HalfDuplex;
+
+/// Shared state of a half-duplex line.
+///
+/// Reader marks the line as busy, once it receives data, while writer waits until the line is idle
+/// according to [`HalfDuplex`] settings before transmitting.
+#[derive(Clone, Debug)]
+pub(super) struct HalfDuplexLine {
+    settings: HalfDuplex,
+    last_rx: Arc<Mutex<Option<Instant>>>,
+}
+
+impl HalfDuplexLine {
+    pub(super) fn new(settings: HalfDuplex) -> Self {
+        Self {
+            settings,
+            last_rx: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Records, that data was received from the line.
+    pub(super) fn mark_rx(&self) {
+        if let Ok(mut last_rx) = self.last_rx.lock() {
+            *last_rx = Some(Instant::now());
+        }
+    }
+
+    /// Blocks until the line is free for transmission.
+    pub(super) fn wait_for_idle(&self) {
+        let mut was_busy = false;
+
+        loop {
+            let remaining = self.settings.rx_turnaround.saturating_sub(self.idle_for());
+            if !remaining.is_zero() {
+                was_busy = true;
+                thread::sleep(remaining);
+                continue;
+            }
+
+            match self.settings.backoff {
+                Some(window) if was_busy => {
+                    was_busy = false;
+                    thread::sleep(jitter(window));
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Holds the line after transmission.
+    pub(super) fn release(&self) {
+        if !self.settings.tx_turnaround.is_zero() {
+            thread::sleep(self.settings.tx_turnaround);
+        }
+    }
+
+    fn idle_for(&self) -> Duration {
+        match self.last_rx.lock() {
+            Ok(last_rx) => match *last_rx {
+                Some(instant) => instant.elapsed(),
+                None => Duration::MAX,
+            },
+            Err(_) => Duration::MAX,
+        }
+    }
+}
+
+/// Random duration within `[0, window]`.
+fn jitter(window: Duration) -> Duration {
+    let random = RandomState::new().hash_one(Instant::now());
+    let nanos = window.as_nanos().min(u64::MAX as u128 - 1) as u64;
+    Duration::from_nanos(random % (nanos + 1))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                  Tests                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_line_is_available_immediately() {
+        let line = HalfDuplexLine::new(HalfDuplex::new().rx_turnaround(Duration::from_secs(10)));
+
+        let started = Instant::now();
+        line.wait_for_idle();
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn transmission_waits_for_rx_turnaround() {
+        let turnaround = Duration::from_millis(30);
+        let line = HalfDuplexLine::new(HalfDuplex::new().rx_turnaround(turnaround));
+
+        line.mark_rx();
+        let started = Instant::now();
+        line.wait_for_idle();
+        assert!(started.elapsed() >= turnaround - Duration::from_millis(1));
+    }
+
+    #[test]
+    fn busy_line_is_backed_off() {
+        let turnaround = Duration::from_millis(10);
+        let window = Duration::from_millis(20);
+        let line = HalfDuplexLine::new(HalfDuplex::new().rx_turnaround(turnaround).backoff(window));
+
+        // Incoming traffic keeps the line busy for a while
+        line.mark_rx();
+        let rx_line = line.clone();
+        let traffic = thread::spawn(move || {
+            for _ in 0..5 {
+                thread::sleep(Duration::from_millis(2));
+                rx_line.mark_rx();
+            }
+        });
+
+        let started = Instant::now();
+        line.wait_for_idle();
+        let waited = started.elapsed();
+        traffic.join().unwrap();
+
+        assert!(waited >= turnaround - Duration::from_millis(1));
+        assert!(waited < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_is_within_window() {
+        let window = Duration::from_millis(3);
+        for _ in 0..100 {
+            assert!(jitter(window) <= window);
+        }
+        assert_eq!(jitter(Duration::ZERO), Duration::ZERO);
+    }
+}
@@ -0,0 +1,3 @@
+mod device;
+mod duplex;
+pub mod port;
@@ -0,0 +1,148 @@
+use crate::core::io::{ChannelDetails, SerialPort};
+use crate::core::utils::SharedCloser;
+use crate::sync::io::transport::serial::device::SerialDevice;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for SerialPort {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let path = self.path.clone();
+        let baud_rate = self.baud_rate;
+
+        let writer = SerialDevice::open(path.as_path(), baud_rate, self.half_duplex)?;
+        let reader = writer.try_clone()?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::SerialPort { path, baud_rate });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = channel.spawn();
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod serial_port_tests {
+    use std::ffi::CStr;
+    use std::fs::File;
+    use std::os::fd::FromRawFd;
+    use std::time::Duration;
+
+    use crate::core::io::{ChannelDetails, Sender};
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::protocol::{Endpoint, MavLinkId};
+    use crate::sync::node::Event;
+    use crate::sync::prelude::*;
+
+    use super::*;
+
+    /// Opens a pseudo-terminal and returns its master side and the path of the slave device.
+    fn open_pty() -> (File, String) {
+        // SAFETY: all pointers are valid during the calls, `master` is owned by the returned file
+        unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(master >= 0);
+            assert_eq!(libc::grantpt(master), 0);
+            assert_eq!(libc::unlockpt(master), 0);
+
+            let mut name = [0 as libc::c_char; 128];
+            assert_eq!(libc::ptsname_r(master, name.as_mut_ptr(), name.len()), 0);
+            let path = CStr::from_ptr(name.as_ptr()).to_str().unwrap().to_string();
+
+            (File::from_raw_fd(master), path)
+        }
+    }
+
+    #[test]
+    fn serial_port_node_receives_frames() {
+        let (master, path) = open_pty();
+
+        let node = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(SerialPort::new(path.as_str(), 57600).unwrap())
+            .build()
+            .unwrap();
+
+        let endpoint = Endpoint::v2(MavLinkId::new(2, 1));
+        let frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+        let mut sender = Sender::versioned(master, V2);
+        sender.send(&frame).unwrap();
+
+        let (received, callback) = loop {
+            match node.recv_timeout(Duration::from_secs(1)).unwrap() {
+                Event::Frame(frame, callback) => break (frame, callback),
+                _ => continue,
+            }
+        };
+
+        assert_eq!(received.system_id(), 2);
+        match callback.info().details() {
+            ChannelDetails::SerialPort {
+                path: chan_path,
+                baud_rate,
+            } => {
+                assert_eq!(chan_path.to_str().unwrap(), path);
+                assert_eq!(*baud_rate, 57600);
+            }
+            details => panic!("unexpected channel details: {details:?}"),
+        }
+    }
+
+    #[test]
+    fn half_duplex_serial_port_node_sends_frames() {
+        use crate::core::io::{HalfDuplex, Receiver};
+        use std::time::Instant;
+
+        let (master, path) = open_pty();
+        let turnaround = Duration::from_millis(50);
+
+        let node = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                SerialPort::new(path.as_str(), 57600)
+                    .unwrap()
+                    .half_duplex(HalfDuplex::new().rx_turnaround(turnaround)),
+            )
+            .build()
+            .unwrap();
+
+        // Peer occupies the line
+        let endpoint = Endpoint::v2(MavLinkId::new(2, 1));
+        let frame = endpoint.next_frame(&Heartbeat::default()).unwrap();
+        let mut sender = Sender::versioned(master.try_clone().unwrap(), V2);
+        sender.send(&frame).unwrap();
+        loop {
+            if let Event::Frame(..) = node.recv_timeout(Duration::from_secs(1)).unwrap() {
+                break;
+            }
+        }
+
+        let sent_at = Instant::now();
+        node.send(&Heartbeat::default()).unwrap();
+
+        let mut receiver = Receiver::versioned(master, V2);
+        let received = receiver.recv().unwrap();
+        assert_eq!(received.system_id(), 1);
+        assert!(sent_at.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn zero_baud_rate_is_rejected() {
+        assert!(SerialPort::new("/dev/ttyUSB0", 0).is_err());
+    }
+}
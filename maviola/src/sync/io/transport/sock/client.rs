@@ -4,6 +4,7 @@ use crate::core::io::ChannelDetails;
 use crate::core::utils::SharedCloser;
 use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::sync::marker::ConnConf;
+use crate::sync::utils::io_pool;
 
 use crate::prelude::*;
 
@@ -12,6 +13,8 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for SockClient {
         let path = self.path.clone();
         let writer = UnixStream::connect(path.as_path())?;
         let reader = writer.try_clone()?;
+        let pool = io_pool();
+        writer.set_nonblocking(pool.is_some())?;
 
         let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
 
@@ -19,7 +22,10 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for SockClient {
             .info()
             .make_channel_info(ChannelDetails::SockClient { path });
         let channel = chan_factory.build(chan_info, reader, writer);
-        let channel_state = channel.spawn();
+        let channel_state = match &pool {
+            Some(pool) => channel.spawn_in(pool),
+            None => channel.spawn(),
+        };
 
         let handler = ConnectionHandler::spawn_from_state(channel_state);
 
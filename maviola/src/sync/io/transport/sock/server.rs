@@ -7,6 +7,7 @@ use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
 use crate::core::utils::{Closable, Closer};
 use crate::sync::consts::{SOCK_ACCEPT_INTERVAL, SOCK_READ_TIMEOUT, SOCK_WRITE_TIMEOUT};
 use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::utils::{io_pool, spawn_io};
 
 use crate::prelude::*;
 use crate::sync::marker::ConnConf;
@@ -37,15 +38,19 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for SockServer {
                     },
                 };
                 let reader = writer.try_clone()?;
+                let pool = io_pool();
 
-                writer.set_nonblocking(false)?;
+                writer.set_nonblocking(pool.is_some())?;
                 writer.set_write_timeout(SOCK_WRITE_TIMEOUT)?;
                 reader.set_read_timeout(SOCK_READ_TIMEOUT)?;
 
                 let chan_info =
                     info.make_channel_info(ChannelDetails::SockServer { path: path.clone() });
                 let channel = chan_factory.build(chan_info, reader, writer);
-                channel.spawn().discard();
+                match &pool {
+                    Some(pool) => channel.spawn_in(pool).discard(),
+                    None => channel.spawn().discard(),
+                }
             }
 
             Ok(())
@@ -60,7 +65,7 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for SockServer {
 }
 
 fn on_close_handler(state: Closable, path: PathBuf, info: ConnectionInfo) {
-    thread::spawn(move || {
+    spawn_io(move || {
         while !state.is_closed() {
             thread::sleep(SERVER_HANG_UP_TIMEOUT);
         }
@@ -0,0 +1,46 @@
+use crate::core::io::ConnectionSpec;
+use crate::error::ConfigDiagnostic;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ConnectionSpec {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        builder(self).build()
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        builder::<V>(self).is_repairable()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        builder::<V>(self).diagnostics()
+    }
+}
+
+fn builder<V: MaybeVersioned>(spec: &ConnectionSpec) -> &dyn ConnectionBuilder<V> {
+    match spec {
+        ConnectionSpec::TcpClient(conn) => conn,
+        ConnectionSpec::TcpServer(conn) => conn,
+        ConnectionSpec::UdpClient(conn) => conn,
+        ConnectionSpec::UdpServer(conn) => conn,
+        #[cfg(feature = "serial")]
+        ConnectionSpec::SerialPort(conn) => conn,
+    }
+}
+
+impl Connection<Versionless> {
+    /// Parses connection configuration from an endpoint `url`, like `tcpout:127.0.0.1:5760`,
+    /// `udpin:0.0.0.0:14550`, or `serial:/dev/ttyACM0:115200`.
+    ///
+    /// Returned [`ConnectionSpec`] can be used by nodes of any protocol version. See
+    /// [`ConnectionSpec`] for supported URLs.
+    pub fn parse(url: &str) -> Result<ConnectionSpec> {
+        ConnectionSpec::parse(url)
+    }
+}
@@ -1,29 +1,47 @@
-use std::net::TcpStream;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
 
 use crate::core::io::ChannelDetails;
 use crate::core::utils::SharedCloser;
-use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::error::{ConfigDiagnostic, ConfigError};
+use crate::sync::io::transport::resolution::{resolve_client_addr, spawn_client_handler};
+use crate::sync::io::transport::tcp::failover::FailoverTcpStream;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler, IoPool};
 use crate::sync::marker::ConnConf;
+use crate::sync::utils::io_pool;
 
 use crate::prelude::*;
 
 impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpClient {
     fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
-        let server_addr = self.addr;
-        let writer = TcpStream::connect(server_addr)?;
-        let reader = writer.try_clone()?;
+        let addr = resolve_client_addr(self.resolution.as_ref(), self.addr)?;
 
-        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+        ConfigError::check(<Self as ConnectionBuilder<V>>::diagnostics(self))?;
 
-        let chan_info = connection
-            .info()
-            .make_channel_info(ChannelDetails::TcpClient { server_addr });
-        let channel = chan_factory.build(chan_info, reader, writer);
-        let channel_state = channel.spawn();
+        #[cfg(feature = "tls")]
+        if let Some(connector) = &self.tls {
+            let (reader, writer) = super::tls::connect(connector, TcpStream::connect(addr)?)?;
+            return Ok(self.spawn_channel(addr, reader, writer, None));
+        }
+
+        if self.fallback_addrs.is_empty() {
+            let writer = TcpStream::connect(addr)?;
+            let reader = writer.try_clone()?;
+            let pool = io_pool();
+            writer.set_nonblocking(pool.is_some())?;
 
-        let handler = ConnectionHandler::spawn_from_state(channel_state);
+            Ok(self.spawn_channel(addr, reader, writer, pool.as_ref()))
+        } else {
+            let addrs = std::iter::once(addr).chain(self.fallback_addrs.iter().copied());
+            let writer = FailoverTcpStream::connect(addrs.collect())?;
+            let reader = writer.try_clone()?;
+            let failback = writer.try_clone()?;
 
-        Ok((connection, handler))
+            let (connection, handler) = self.spawn_channel(addr, reader, writer, None);
+            failback.spawn_failback(self.failback_interval, connection.state());
+
+            Ok((connection, handler))
+        }
     }
 
     fn to_conf(&self) -> ConnConf<V> {
@@ -33,4 +51,33 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpClient {
     fn is_repairable(&self) -> bool {
         true
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.tls_diagnostics()
+    }
+}
+
+impl TcpClient {
+    fn spawn_channel<V: MaybeVersioned>(
+        &self,
+        addr: SocketAddr,
+        reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+        pool: Option<&IoPool>,
+    ) -> (Connection<V>, ConnectionHandler) {
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::TcpClient { server_addr: addr });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = match pool {
+            Some(pool) => channel.spawn_in(pool),
+            None => channel.spawn(),
+        };
+
+        let handler = spawn_client_handler(channel_state, self.resolution.as_ref(), addr);
+
+        (connection, handler)
+    }
 }
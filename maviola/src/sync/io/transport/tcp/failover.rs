@@ -0,0 +1,209 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::utils::Closable;
+use crate::sync::consts::{TCP_FAILBACK_POOLING_INTERVAL, TCP_FAILOVER_CONNECT_TIMEOUT};
+use crate::sync::utils::spawn_io;
+
+/// TCP stream that reconnects to a priority-ordered list of server addresses.
+///
+/// Implements [`Read`] and [`Write`]. All clones share the same underlying connection. When
+/// connection is lost, the first reachable address becomes active. If none of the addresses are
+/// reachable, then the original error is returned.
+pub struct FailoverTcpStream {
+    shared: Arc<Shared>,
+    stream: TcpStream,
+    generation: u64,
+}
+
+struct Shared {
+    addrs: Vec<SocketAddr>,
+    current: Mutex<Current>,
+}
+
+struct Current {
+    stream: TcpStream,
+    index: usize,
+    generation: u64,
+    closed: bool,
+}
+
+impl FailoverTcpStream {
+    /// Connects to the first reachable address among `addrs`.
+    pub fn connect(addrs: Vec<SocketAddr>) -> std::io::Result<Self> {
+        let (stream, index) = connect_first(&addrs, addrs.len())?;
+
+        Ok(Self {
+            stream: stream.try_clone()?,
+            generation: 0,
+            shared: Arc::new(Shared {
+                addrs,
+                current: Mutex::new(Current {
+                    stream,
+                    index,
+                    generation: 0,
+                    closed: false,
+                }),
+            }),
+        })
+    }
+
+    /// Creates a new handle to the same connection.
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        Ok(Self {
+            shared: self.shared.clone(),
+            stream: self.stream.try_clone()?,
+            generation: self.generation,
+        })
+    }
+
+    /// Spawns a thread that periodically checks higher-priority addresses and reconnects to them
+    /// once they are available.
+    ///
+    /// Stops and shuts down the connection when `state` is closed.
+    pub fn spawn_failback(&self, interval: Duration, state: Closable) {
+        let shared = self.shared.clone();
+
+        spawn_io(move || {
+            let mut last_check = Instant::now();
+
+            while !state.is_closed() {
+                thread::sleep(TCP_FAILBACK_POOLING_INTERVAL);
+                if last_check.elapsed() < interval {
+                    continue;
+                }
+                last_check = Instant::now();
+
+                let (index, generation) = match shared.lock() {
+                    Ok(current) => (current.index, current.generation),
+                    Err(_) => break,
+                };
+                if index == 0 {
+                    continue;
+                }
+
+                if let Ok((stream, new_index)) = connect_first(&shared.addrs, index) {
+                    if let Ok(mut current) = shared.lock() {
+                        if current.generation == generation {
+                            log::info!(
+                                "failing back from {} to {}",
+                                shared.addrs[current.index],
+                                shared.addrs[new_index]
+                            );
+                            current.replace(stream, new_index);
+                        }
+                    }
+                }
+            }
+
+            if let Ok(mut current) = shared.lock() {
+                current.closed = true;
+                _ = current.stream.shutdown(Shutdown::Both);
+            }
+        });
+    }
+
+    fn refresh(&mut self) -> std::io::Result<()> {
+        let current = self.shared.lock()?;
+        if current.generation != self.generation {
+            self.stream = current.stream.try_clone()?;
+            self.generation = current.generation;
+        }
+        Ok(())
+    }
+
+    fn reconnect(&mut self, err: std::io::Error) -> std::io::Result<()> {
+        {
+            let mut current = self.shared.lock()?;
+
+            if current.closed {
+                return Err(err);
+            }
+
+            if current.generation == self.generation {
+                let failed = self.shared.addrs[current.index];
+                let (stream, index) =
+                    connect_first(&self.shared.addrs, self.shared.addrs.len()).map_err(|_| err)?;
+
+                log::info!("failing over from {failed} to {}", self.shared.addrs[index]);
+                current.replace(stream, index);
+            }
+        }
+
+        self.refresh()
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> std::io::Result<MutexGuard<'_, Current>> {
+        self.current
+            .lock()
+            .map_err(|err| std::io::Error::other(err.to_string()))
+    }
+}
+
+impl Current {
+    fn replace(&mut self, stream: TcpStream, index: usize) {
+        _ = self.stream.shutdown(Shutdown::Both);
+        self.stream = stream;
+        self.index = index;
+        self.generation += 1;
+    }
+}
+
+impl Read for FailoverTcpStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            self.refresh()?;
+
+            match self.stream.read(buf) {
+                Ok(0) if !buf.is_empty() => {
+                    self.reconnect(ErrorKind::UnexpectedEof.into())?;
+                }
+                Ok(bytes_read) => return Ok(bytes_read),
+                Err(err) if is_timeout(&err) => return Err(err),
+                Err(err) => self.reconnect(err)?,
+            }
+        }
+    }
+}
+
+impl Write for FailoverTcpStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.refresh()?;
+
+        match self.stream.write(buf) {
+            Ok(bytes_written) => Ok(bytes_written),
+            Err(err) if is_timeout(&err) => Err(err),
+            Err(err) => {
+                self.reconnect(err)?;
+                // Signal the caller to retry writing the whole frame to a new connection
+                Err(ErrorKind::TimedOut.into())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+fn connect_first(addrs: &[SocketAddr], limit: usize) -> std::io::Result<(TcpStream, usize)> {
+    let mut last_err = std::io::Error::from(ErrorKind::NotFound);
+
+    for (index, addr) in addrs.iter().enumerate().take(limit) {
+        match TcpStream::connect_timeout(addr, TCP_FAILOVER_CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok((stream, index)),
+            Err(err) => last_err = err,
+        }
+    }
+
+    Err(last_err)
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(err.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+}
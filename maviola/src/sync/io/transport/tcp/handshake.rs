@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+use std::net::TcpStream;
+
+use crate::core::io::HandshakeOutcome;
+
+use crate::prelude::*;
+
+/// <sup>[`sync`](crate::sync)</sup>
+/// Authentication handshake performed by a synchronous [`TcpServer`] with each accepted client.
+///
+/// Handshake is performed before the client channel is attached to the connection, so the client
+/// neither receives nor sends frames until it is [accepted](HandshakeOutcome::Accept). It has
+/// direct access to the client stream and can, for example, exchange tokens over the first bytes
+/// or read and verify the first frame. Errors are considered as rejections.
+///
+/// Each handshake is performed in a separate thread, read and write operations on the stream time
+/// out after [`TCP_HANDSHAKE_TIMEOUT`].
+///
+/// Set handshake by [`TcpServer::sync_handshake`].
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use std::io::Read;
+/// use std::net::TcpStream;
+/// use maviola::core::io::HandshakeOutcome;
+/// use maviola::sync::io::TcpHandshake;
+/// use maviola::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct TokenHandshake([u8; 4]);
+///
+/// impl TcpHandshake for TokenHandshake {
+///     fn handshake(&self, stream: &mut TcpStream) -> Result<HandshakeOutcome> {
+///         let mut token = [0u8; 4];
+///         stream.read_exact(&mut token)?;
+///
+///         Ok(match token == self.0 {
+///             true => HandshakeOutcome::Accept,
+///             false => HandshakeOutcome::Reject,
+///         })
+///     }
+/// }
+///
+/// let node = Node::sync::<V2>()
+///     .connection(
+///         TcpServer::new("127.0.0.1:5600").unwrap()
+///             .sync_handshake(TokenHandshake(*b"mav1"))
+///     ).build().unwrap();
+/// ```
+///
+/// [`TCP_HANDSHAKE_TIMEOUT`]: crate::core::consts::TCP_HANDSHAKE_TIMEOUT
+pub trait TcpHandshake: Debug + Send + Sync + 'static {
+    /// Performs handshake with a client connected through `stream`.
+    fn handshake(&self, stream: &mut TcpStream) -> Result<HandshakeOutcome>;
+}
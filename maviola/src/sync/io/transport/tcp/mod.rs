@@ -1,2 +1,8 @@
 pub mod client;
+mod failover;
+mod handshake;
 pub mod server;
+#[cfg(feature = "tls")]
+mod tls;
+
+pub use handshake::TcpHandshake;
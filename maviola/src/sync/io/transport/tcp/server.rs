@@ -1,17 +1,26 @@
-use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
 use std::thread;
 
-use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
-use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
+use crate::core::consts::{SERVER_HANG_UP_TIMEOUT, TCP_HANDSHAKE_TIMEOUT};
+use crate::core::io::{
+    ChannelDetails, ChannelInfo, ConnectionConf, ConnectionInfo, HandshakeOutcome, ServerHandshake,
+};
 use crate::core::utils::{Closable, Closer};
+use crate::error::{ConfigDiagnostic, ConfigError};
 use crate::sync::consts::{TCP_READ_TIMEOUT, TCP_WRITE_TIMEOUT};
-use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::io::{
+    ChannelFactory, Connection, ConnectionBuilder, ConnectionHandler, TaskStatus, TcpHandshake,
+};
 use crate::sync::marker::ConnConf;
+use crate::sync::utils::{io_pool, spawn_io};
 
 use crate::prelude::*;
 
 impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
     fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        ConfigError::check(<Self as ConnectionBuilder<V>>::diagnostics(self))?;
+
         let server_addr = self.addr;
         let listener = TcpListener::bind(self.addr)?;
 
@@ -19,6 +28,13 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
         let (connection, chan_factory) = Connection::new(self.info.clone(), conn_state.to_shared());
 
         let info = self.info().clone();
+        let handshake = match &self.handshake {
+            Some(ServerHandshake::Sync(handshake)) => Some(handshake.clone()),
+            _ => None,
+        };
+
+        #[cfg(feature = "tls")]
+        let tls = self.tls.clone();
 
         let handler = ConnectionHandler::spawn(move || -> Result<()> {
             on_close_handler(conn_state.to_closable(), server_addr, info.clone());
@@ -30,18 +46,27 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
 
                 let stream = stream?;
                 let peer_addr = stream.peer_addr()?;
-                let writer = stream;
-                let reader = writer.try_clone()?;
-
-                writer.set_write_timeout(TCP_WRITE_TIMEOUT)?;
-                writer.set_read_timeout(TCP_READ_TIMEOUT)?;
-
                 let chan_info = info.make_channel_info(ChannelDetails::TcpServer {
                     server_addr,
                     peer_addr,
                 });
-                let channel = chan_factory.build(chan_info, reader, writer);
-                channel.spawn().discard();
+
+                #[cfg(feature = "tls")]
+                if let Some(acceptor) = tls.clone() {
+                    let chan_factory = chan_factory.clone();
+                    spawn_io(move || {
+                        attach_tls_channel(&acceptor, &chan_factory, chan_info, stream)
+                    });
+                    continue;
+                }
+
+                match handshake.clone() {
+                    None => attach_channel(&chan_factory, chan_info, stream)?,
+                    Some(handshake) => {
+                        let chan_factory = chan_factory.clone();
+                        spawn_io(move || authenticate(handshake, &chan_factory, chan_info, stream));
+                    }
+                }
             }
 
             Ok(())
@@ -53,10 +78,114 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for TcpServer {
     fn to_conf(&self) -> ConnConf<V> {
         ConnConf::new(self.clone())
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = match &self.handshake {
+            #[cfg(feature = "async")]
+            Some(ServerHandshake::Async(_)) => vec![ConfigDiagnostic::HandshakeModeMismatch(
+                self.info.details().clone(),
+            )],
+            _ => Vec::new(),
+        };
+
+        diagnostics.extend(self.tls_diagnostics());
+
+        diagnostics
+    }
+}
+
+fn attach_channel<V: MaybeVersioned>(
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    stream: TcpStream,
+) -> Result<()> {
+    let writer = stream;
+    let reader = writer.try_clone()?;
+    let stream = writer.try_clone()?;
+    let pool = io_pool();
+
+    writer.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+    writer.set_read_timeout(TCP_READ_TIMEOUT)?;
+    writer.set_nonblocking(pool.is_some())?;
+
+    let channel = chan_factory.build(chan_info, reader, writer);
+    let channel_state = match &pool {
+        Some(pool) => channel.spawn_in(pool),
+        None => channel.spawn(),
+    };
+    on_channel_close_handler(channel_state.to_closable(), stream);
+    channel_state.discard();
+
+    Ok(())
+}
+
+/// Establishes TLS session with a client and attaches its channel, if session is established.
+#[cfg(feature = "tls")]
+fn attach_tls_channel<V: MaybeVersioned>(
+    acceptor: &crate::core::io::TlsAcceptor,
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    stream: TcpStream,
+) {
+    let shutdown = match stream.try_clone() {
+        Ok(stream) => stream,
+        Err(err) => {
+            log::debug!("[{chan_info:?}] can't attach TLS client: {err:?}");
+            return;
+        }
+    };
+
+    match super::tls::accept(acceptor, stream) {
+        Ok((reader, writer)) => {
+            log::debug!("[{chan_info:?}] TLS session established");
+            let channel_state = chan_factory.build(chan_info, reader, writer).spawn();
+            on_channel_close_handler(channel_state.to_closable(), shutdown);
+            channel_state.discard();
+        }
+        Err(err) => {
+            log::info!("[{chan_info:?}] TLS handshake failed: {err:?}");
+            _ = shutdown.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Performs handshake with a client and attaches its channel, if client is accepted.
+fn authenticate<V: MaybeVersioned>(
+    handshake: Arc<dyn TcpHandshake>,
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    mut stream: TcpStream,
+) {
+    let outcome = stream
+        .set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT)))
+        .map_err(Error::from)
+        .and_then(|_| handshake.handshake(&mut stream));
+
+    match outcome {
+        Ok(HandshakeOutcome::Accept) => {
+            log::debug!("[{chan_info:?}] client accepted");
+            if let Err(err) = attach_channel(chan_factory, chan_info.clone(), stream) {
+                log::debug!("[{chan_info:?}] can't attach accepted client: {err:?}");
+            }
+        }
+        Ok(HandshakeOutcome::Reject) => {
+            log::info!("[{chan_info:?}] client rejected");
+            _ = stream.shutdown(Shutdown::Both);
+        }
+        Err(err) => {
+            log::info!("[{chan_info:?}] client rejected: {err:?}");
+            _ = stream.shutdown(Shutdown::Both);
+        }
+    }
 }
 
-fn on_close_handler(state: Closable, addr: SocketAddr, info: ConnectionInfo) {
-    thread::spawn(move || {
+pub(in crate::sync::io::transport) fn on_close_handler(
+    state: Closable,
+    addr: SocketAddr,
+    info: ConnectionInfo,
+) {
+    spawn_io(move || {
         while !state.is_closed() {
             thread::sleep(SERVER_HANG_UP_TIMEOUT);
         }
@@ -65,3 +194,25 @@ fn on_close_handler(state: Closable, addr: SocketAddr, info: ConnectionInfo) {
         _ = TcpStream::connect(addr);
     });
 }
+
+/// Shuts down peer stream once its channel is closed, so the blocked reader releases the socket
+/// and the peer gets notified.
+pub(in crate::sync::io::transport) fn on_channel_close_handler(state: Closable, stream: TcpStream) {
+    if let Some(pool) = io_pool() {
+        pool.submit(move || match state.is_closed() {
+            true => {
+                _ = stream.shutdown(Shutdown::Both);
+                TaskStatus::Finished
+            }
+            false => TaskStatus::Idle,
+        });
+        return;
+    }
+
+    spawn_io(move || {
+        while !state.is_closed() {
+            thread::sleep(SERVER_HANG_UP_TIMEOUT);
+        }
+        _ = stream.shutdown(Shutdown::Both);
+    });
+}
@@ -0,0 +1,127 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rustls::{ClientConnection, Connection, ServerConnection};
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::{TlsAcceptor, TlsConnector};
+use crate::sync::consts::{TCP_READ_TIMEOUT, TCP_WRITE_TIMEOUT};
+
+/// Reading half of a TLS stream over [`TcpStream`].
+///
+/// Socket is read without holding the lock on the TLS session, so writer is not blocked while
+/// reader waits for incoming data.
+pub(super) struct TlsReader {
+    conn: Arc<Mutex<Connection>>,
+    stream: TcpStream,
+    pending: Vec<u8>,
+}
+
+/// Writing half of a TLS stream over [`TcpStream`].
+pub(super) struct TlsWriter {
+    conn: Arc<Mutex<Connection>>,
+    stream: TcpStream,
+}
+
+/// Establishes TLS session with a client over server-side `stream`.
+pub(super) fn accept(
+    acceptor: &TlsAcceptor,
+    stream: TcpStream,
+) -> std::io::Result<(TlsReader, TlsWriter)> {
+    let conn = ServerConnection::new(acceptor.config().clone()).map_err(std::io::Error::other)?;
+    split(conn.into(), stream)
+}
+
+/// Establishes TLS session with a server over client-side `stream`.
+pub(super) fn connect(
+    connector: &TlsConnector,
+    stream: TcpStream,
+) -> std::io::Result<(TlsReader, TlsWriter)> {
+    let conn = ClientConnection::new(connector.config().clone(), connector.server_name().clone())
+        .map_err(std::io::Error::other)?;
+    split(conn.into(), stream)
+}
+
+fn split(mut conn: Connection, mut stream: TcpStream) -> std::io::Result<(TlsReader, TlsWriter)> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+
+    while conn.is_handshaking() {
+        conn.complete_io(&mut stream)?;
+    }
+
+    stream.set_read_timeout(TCP_READ_TIMEOUT)?;
+    stream.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+
+    let conn = Arc::new(Mutex::new(conn));
+    Ok((
+        TlsReader {
+            conn: conn.clone(),
+            stream: stream.try_clone()?,
+            pending: Vec::new(),
+        },
+        TlsWriter { conn, stream },
+    ))
+}
+
+impl Read for TlsReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            {
+                let mut conn = lock(&self.conn);
+
+                match conn.reader().read(buf) {
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                    result => return result,
+                }
+
+                if !self.pending.is_empty() {
+                    let bytes_read = conn.read_tls(&mut self.pending.as_slice())?;
+                    self.pending.drain(..bytes_read);
+                    conn.process_new_packets()
+                        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+                    while conn.wants_write() {
+                        conn.write_tls(&mut self.stream)?;
+                    }
+                    continue;
+                }
+            }
+
+            let mut chunk = [0u8; 4096];
+            let bytes_read = self.stream.read(&mut chunk)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.pending.extend_from_slice(&chunk[..bytes_read]);
+        }
+    }
+}
+
+impl Write for TlsWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut conn = lock(&self.conn);
+        let bytes_written = conn.writer().write(buf)?;
+        while conn.wants_write() {
+            conn.write_tls(&mut self.stream)?;
+        }
+        Ok(bytes_written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let mut conn = lock(&self.conn);
+        conn.writer().flush()?;
+        while conn.wants_write() {
+            conn.write_tls(&mut self.stream)?;
+        }
+        self.stream.flush()
+    }
+}
+
+fn lock(conn: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    match conn.lock() {
+        Ok(conn) => conn,
+        Err(err) => err.into_inner(),
+    }
+}
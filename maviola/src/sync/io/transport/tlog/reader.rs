@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::thread;
+use std::time::Instant;
+
+use crate::core::io::{ChannelDetails, ChannelInfo, IncomingFrame, Receiver, TlogPlayback};
+use crate::core::utils::{Closable, SharedCloser};
+use crate::sync::consts::TLOG_RELAY_POOLING_INTERVAL;
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler, IncomingFrameProducer};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for TlogReader {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let path = self.path.clone();
+        let reader = BufReader::new(File::open(path.as_path())?);
+
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), state.clone());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::TlogReader { path });
+        let playback = TlogPlayback::new(self.speed);
+
+        let handler = ConnectionHandler::spawn(move || {
+            let producer = chan_factory.producer().clone();
+            replay(state.to_closable(), chan_info, producer, playback, reader)
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+}
+
+/// Replays records until the end of the log is reached or connection is closed.
+fn replay<V: MaybeVersioned>(
+    state: Closable,
+    info: ChannelInfo,
+    producer: IncomingFrameProducer<V>,
+    mut playback: TlogPlayback,
+    mut reader: impl Read,
+) -> Result<()> {
+    let mut timestamp = [0u8; 8];
+
+    loop {
+        if let Err(err) = reader.read_exact(&mut timestamp) {
+            if let std::io::ErrorKind::UnexpectedEof = err.kind() {
+                log::debug!("[{info:?}] telemetry log replayed");
+                return Ok(());
+            }
+            return Err(Error::from(err));
+        }
+        let frame = Receiver::versionless(&mut reader).recv()?;
+        let deadline = playback.schedule(u64::from_be_bytes(timestamp));
+
+        let frame = match frame.try_into_versioned::<V>() {
+            Ok(frame) => frame,
+            Err(err) => {
+                log::trace!("[{info:?}] skipping frame: {err:?}");
+                continue;
+            }
+        };
+
+        loop {
+            if state.is_closed() {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            thread::sleep(TLOG_RELAY_POOLING_INTERVAL.min(deadline - now));
+        }
+
+        producer.send(IncomingFrame::new(frame, info.clone()))?;
+        log::trace!("[{info:?}] replayed incoming frame");
+    }
+}
@@ -0,0 +1,128 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::{mpsc, Arc};
+use std::time::SystemTime;
+
+use crate::core::io::{tlog_timestamp, Sender};
+use crate::core::utils::{Closable, SharedCloser};
+use crate::error::{ConfigDiagnostic, RecvTimeoutError};
+use crate::sync::consts::TLOG_RELAY_POOLING_INTERVAL;
+use crate::sync::io::{forward_events, Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned, C: ConnectionBuilder<V> + Clone + 'static> ConnectionBuilder<V>
+    for TlogWriter<C>
+{
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path.as_path())?;
+
+        let (inner, inner_handler) = self.connection.build()?;
+        inner_handler.handle(&inner);
+
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = Connection::new(inner.info().clone(), state.clone());
+        let (records, records_rx) = mpsc::channel();
+
+        {
+            let (state, inner_state) = (state.to_closable(), inner.state());
+            let receiver = inner.receiver().clone();
+            let producer = chan_factory.producer().clone();
+            let events = (inner.take_events(), chan_factory.event_sender().clone());
+            let records = records.clone();
+
+            spawn_io(move || {
+                while is_open(&state, &inner_state) {
+                    forward_events(events.0.as_ref(), &events.1);
+
+                    let frame = match receiver.recv_timeout(TLOG_RELAY_POOLING_INTERVAL) {
+                        Ok(frame) => frame,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+                    };
+
+                    _ = records.send((SystemTime::now(), frame.shared_frame().clone()));
+                    if producer.send(frame).is_err() {
+                        break;
+                    }
+                }
+                forward_events(events.0.as_ref(), &events.1);
+            });
+        }
+
+        {
+            let (state, inner_state) = (state.to_closable(), inner.state());
+            let send_handler = chan_factory.send_handler().clone();
+            let sender = inner.sender().clone();
+
+            spawn_io(move || {
+                while is_open(&state, &inner_state) {
+                    let frame = match send_handler.recv_timeout(TLOG_RELAY_POOLING_INTERVAL) {
+                        Ok(frame) => frame,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
+                    };
+
+                    _ = records.send((SystemTime::now(), frame.shared_frame().clone()));
+                    if sender.send_raw(frame).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        let handler = ConnectionHandler::spawn(move || {
+            let result = write_records(file, records_rx);
+            drop(inner);
+            result
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        self.connection.is_repairable()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.connection.diagnostics()
+    }
+}
+
+fn is_open(state: &Closable, inner_state: &Closable) -> bool {
+    !state.is_closed() && !inner_state.is_closed()
+}
+
+/// Writes records until both relays are finished.
+fn write_records<V: MaybeVersioned>(
+    file: File,
+    records: mpsc::Receiver<(SystemTime, Arc<Frame<V>>)>,
+) -> Result<()> {
+    let mut writer = BufWriter::new(file);
+
+    loop {
+        let (time, frame) = match records.recv_timeout(TLOG_RELAY_POOLING_INTERVAL) {
+            Ok(record) => record,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                writer.flush()?;
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        writer.write_all(&tlog_timestamp(time).to_be_bytes())?;
+        Sender::new(&mut writer).send(frame.as_ref())?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
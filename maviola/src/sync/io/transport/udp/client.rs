@@ -1,8 +1,13 @@
-use std::net::UdpSocket;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Instant;
 
-use crate::core::io::ChannelDetails;
+use crate::core::io::{AddressFailover, ChannelDetails};
 use crate::core::utils::net::{pick_unused_port, resolve_socket_addr};
 use crate::core::utils::SharedCloser;
+use crate::sync::consts::UDP_FAILOVER_READ_TIMEOUT;
+use crate::sync::io::transport::resolution::{resolve_client_addr, spawn_client_handler};
+use crate::sync::io::transport::udp::failover_rw::FailoverUdpRW;
 use crate::sync::io::transport::udp::udp_rw::UdpRW;
 use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::sync::marker::ConnConf;
@@ -15,28 +20,33 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpClient {
             None => resolve_socket_addr(format!("{}:{}", self.host, pick_unused_port()?))?,
             Some(bind_addr) => bind_addr,
         };
-        let server_addr = self.addr;
 
+        let addr = resolve_client_addr(self.resolution.as_ref(), self.addr)?;
         let udp_socket = UdpSocket::bind(bind_addr)?;
-        udp_socket.connect(server_addr)?;
 
-        let writer = UdpRW::new(udp_socket);
-        let reader = writer.try_clone()?;
+        if self.fallback_addrs.is_empty() {
+            udp_socket.connect(addr)?;
 
-        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+            let writer = UdpRW::new(udp_socket);
+            let reader = writer.try_clone()?;
 
-        let chan_info = connection
-            .info()
-            .make_channel_info(ChannelDetails::UdpClient {
-                server_addr,
-                bind_addr,
-            });
-        let channel = chan_factory.build(chan_info, reader, writer);
-        let channel_state = channel.spawn();
+            Ok(self.spawn_channel(addr, bind_addr, reader, writer))
+        } else {
+            udp_socket.set_read_timeout(Some(UDP_FAILOVER_READ_TIMEOUT))?;
+            let failover = AddressFailover::new(
+                std::iter::once(addr)
+                    .chain(self.fallback_addrs.iter().copied())
+                    .collect(),
+                self.failover_timeout,
+                self.failback_interval,
+                Instant::now(),
+            );
 
-        let handler = ConnectionHandler::spawn_from_state(channel_state);
+            let writer = FailoverUdpRW::new(udp_socket, failover);
+            let reader = writer.try_clone()?;
 
-        Ok((connection, handler))
+            Ok(self.spawn_channel(addr, bind_addr, reader, writer))
+        }
     }
 
     fn to_conf(&self) -> ConnConf<V> {
@@ -47,3 +57,28 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpClient {
         true
     }
 }
+
+impl UdpClient {
+    fn spawn_channel<V: MaybeVersioned>(
+        &self,
+        addr: SocketAddr,
+        bind_addr: SocketAddr,
+        reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+    ) -> (Connection<V>, ConnectionHandler) {
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection
+            .info()
+            .make_channel_info(ChannelDetails::UdpClient {
+                server_addr: addr,
+                bind_addr,
+            });
+        let channel = chan_factory.build(chan_info, reader, writer);
+        let channel_state = channel.spawn();
+
+        let handler = spawn_client_handler(channel_state, self.resolution.as_ref(), addr);
+
+        (connection, handler)
+    }
+}
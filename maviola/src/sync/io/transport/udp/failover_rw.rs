@@ -0,0 +1,116 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::UdpSocket;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use crate::core::io::AddressFailover;
+use crate::sync::consts::{UDP_RETRIES, UDP_RETRY_INTERVAL};
+
+/// A wrapper around unconnected [`UdpSocket`] that implements [`Read`] and [`Write`] and switches
+/// between remote addresses according to [`AddressFailover`].
+///
+/// Incoming datagrams are buffered, so frames can be read in arbitrary chunks.
+pub struct FailoverUdpRW {
+    socket: UdpSocket,
+    failover: Arc<Mutex<AddressFailover>>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl FailoverUdpRW {
+    /// Creates a new UDP reader/writer.
+    ///
+    /// The `socket` should have a read timeout, otherwise failover checks will be performed only
+    /// on writes or when data is received.
+    pub fn new(socket: UdpSocket, failover: AddressFailover) -> Self {
+        Self {
+            socket,
+            failover: Arc::new(Mutex::new(failover)),
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Creates a new independently owned handle to the underlying socket sharing failover state.
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        Ok(Self {
+            socket: self.socket.try_clone()?,
+            failover: self.failover.clone(),
+            buf: Vec::new(),
+            pos: 0,
+        })
+    }
+
+    fn with_failover<T>(&self, f: impl FnOnce(&mut AddressFailover) -> T) -> std::io::Result<T> {
+        match self.failover.lock() {
+            Ok(mut failover) => Ok(f(&mut failover)),
+            Err(err) => Err(std::io::Error::other(err.to_string())),
+        }
+    }
+}
+
+impl Read for FailoverUdpRW {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos >= self.buf.len() {
+            self.buf.resize(u16::MAX as usize, 0);
+            self.pos = 0;
+
+            match self.socket.recv_from(&mut self.buf) {
+                Ok((bytes_read, from)) => {
+                    if self.with_failover(|failover| failover.on_received(from, Instant::now()))? {
+                        self.buf.truncate(bytes_read);
+                    } else {
+                        self.buf.clear();
+                    }
+                }
+                Err(err) => {
+                    self.buf.clear();
+                    return match err.kind() {
+                        ErrorKind::WouldBlock | ErrorKind::TimedOut => {
+                            self.with_failover(|failover| failover.check(Instant::now()))?;
+                            Err(ErrorKind::TimedOut.into())
+                        }
+                        _ => Err(err),
+                    };
+                }
+            }
+        }
+
+        let bytes_read = buf.len().min(self.buf.len() - self.pos);
+        buf[0..bytes_read].copy_from_slice(&self.buf[self.pos..self.pos + bytes_read]);
+        self.pos += bytes_read;
+
+        Ok(bytes_read)
+    }
+}
+
+impl Write for FailoverUdpRW {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let (active, probes) = self.with_failover(|failover| {
+            let now = Instant::now();
+            failover.check(now);
+            (failover.active(), failover.probe(now).to_vec())
+        })?;
+
+        for addr in probes {
+            if let Err(err) = self.socket.send_to(buf, addr) {
+                log::trace!("failed to probe {addr}: {err:?}");
+            }
+        }
+
+        let mut res = Ok(0);
+        for i in 0..UDP_RETRIES {
+            res = self.socket.send_to(buf, active);
+            if res.is_ok() || i == UDP_RETRIES - 1 {
+                break;
+            }
+            thread::sleep(UDP_RETRY_INTERVAL);
+        }
+        res
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
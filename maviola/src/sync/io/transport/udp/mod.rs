@@ -1,3 +1,4 @@
 pub mod client;
+mod failover_rw;
 pub mod server;
 mod udp_rw;
@@ -2,13 +2,14 @@ use std::collections::HashMap;
 use std::net::{SocketAddr, UdpSocket};
 use std::sync::mpsc;
 use std::thread;
+use std::time::Instant;
 
 use crate::core::consts::{DEFAULT_UDP_HOST, SERVER_HANG_UP_TIMEOUT};
-use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionInfo};
+use crate::core::io::{ChannelDetails, ConnectionConf, ConnectionEvent, ConnectionInfo};
 use crate::core::utils::net::{pick_unused_port, resolve_socket_addr};
 use crate::core::utils::{Closable, Closer};
 use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
-use crate::sync::utils::{MpscReader, MpscWriter};
+use crate::sync::utils::{spawn_io, MpscReader, MpscWriter};
 
 use crate::prelude::*;
 use crate::sync::marker::ConnConf;
@@ -25,6 +26,16 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
         let on_close_bind_addr =
             resolve_socket_addr(format!("{}:{}", DEFAULT_UDP_HOST, pick_unused_port()?))?;
 
+        let client_timeout = self.client_timeout;
+        let max_clients = self.max_clients;
+        let known_peers = self.peers.clone();
+        known_peers.clear();
+        let guard = self.guard.clone();
+        guard.reset();
+        if client_timeout.is_some() {
+            udp_socket.set_read_timeout(Some(SERVER_HANG_UP_TIMEOUT))?;
+        }
+
         let handler = ConnectionHandler::spawn(move || -> Result<()> {
             on_close_handler(
                 conn_state.to_closable(),
@@ -33,24 +44,66 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                 info.clone(),
             );
 
-            let mut peers = HashMap::new();
+            let mut peers: HashMap<SocketAddr, (mpsc::Sender<Vec<u8>>, Instant)> = HashMap::new();
             let mut buf = [0u8; 512];
 
-            loop {
+            let result = loop {
                 if conn_state.is_closed() {
-                    return Ok(());
+                    break Ok(());
+                }
+
+                if let Some(timeout) = client_timeout {
+                    peers.retain(|peer_addr, (_, last_seen)| {
+                        let is_active = last_seen.elapsed() < timeout;
+                        if !is_active {
+                            log::debug!("[{info:?}] UDP peer {peer_addr} expired");
+                            known_peers.remove(peer_addr);
+                        }
+                        is_active
+                    });
                 }
 
-                let (bytes_read, peer_addr) = udp_socket.recv_from(buf.as_mut_slice())?;
+                let (bytes_read, peer_addr) = match udp_socket.recv_from(buf.as_mut_slice()) {
+                    Ok(received) => received,
+                    Err(err)
+                        if matches!(
+                            err.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        continue
+                    }
+                    Err(err) => break Err(err.into()),
+                };
 
                 #[allow(clippy::map_entry)]
                 if !peers.contains_key(&peer_addr) {
-                    let udp_socket = udp_socket.try_clone()?;
+                    if !known_peers.has_capacity(max_clients) {
+                        log::trace!("[{info:?}] UDP peer {peer_addr} rejected: too many clients");
+                        continue;
+                    }
+
+                    if !guard.admit(peer_addr) {
+                        log::trace!(
+                            "[{info:?}] UDP peer {peer_addr} rejected: sender is not allowed"
+                        );
+                        if guard.reject(peer_addr) {
+                            _ = chan_factory
+                                .event_sender()
+                                .send(ConnectionEvent::SenderRejected(info.clone(), peer_addr));
+                        }
+                        continue;
+                    }
+
+                    let udp_socket = match udp_socket.try_clone() {
+                        Ok(udp_socket) => udp_socket,
+                        Err(err) => break Err(err.into()),
+                    };
 
                     let (writer_tx, writer_rx) = mpsc::channel();
                     let (reader_tx, reader_rx) = mpsc::channel();
 
-                    peers.insert(peer_addr, reader_tx);
+                    peers.insert(peer_addr, (reader_tx, Instant::now()));
 
                     let writer = MpscWriter::new(writer_tx);
                     let reader = MpscReader::new(reader_rx);
@@ -58,7 +111,9 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                     let chan_info = info.make_channel_info(ChannelDetails::UdpServer {
                         server_addr,
                         peer_addr,
+                        locked: guard.is_locking(),
                     });
+                    known_peers.insert(peer_addr, chan_info.clone());
                     let channel = chan_factory.build(chan_info, reader, writer);
                     channel.spawn().discard();
 
@@ -71,9 +126,15 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for UdpServer {
                     );
                 }
 
-                let reader_tx = peers.get(&peer_addr).unwrap();
-                reader_tx.send(buf[0..bytes_read].to_vec())?;
-            }
+                let (reader_tx, last_seen) = peers.get_mut(&peer_addr).unwrap();
+                *last_seen = Instant::now();
+                if let Err(err) = reader_tx.send(buf[0..bytes_read].to_vec()) {
+                    break Err(err.into());
+                }
+            };
+
+            known_peers.clear();
+            result
         });
 
         Ok((connection, handler))
@@ -92,7 +153,7 @@ impl UdpServer {
         udp_socket: UdpSocket,
         writer_rx: mpsc::Receiver<Vec<u8>>,
     ) {
-        thread::spawn(move || loop {
+        spawn_io(move || loop {
             if conn_state.is_closed() {
                 return;
             }
@@ -118,7 +179,7 @@ fn on_close_handler(
     server_addr: SocketAddr,
     info: ConnectionInfo,
 ) {
-    thread::spawn(move || {
+    spawn_io(move || {
         while !state.is_closed() {
             thread::sleep(SERVER_HANG_UP_TIMEOUT);
         }
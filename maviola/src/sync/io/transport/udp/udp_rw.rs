@@ -5,29 +5,52 @@ use std::thread;
 use crate::sync::consts::{UDP_RETRIES, UDP_RETRY_INTERVAL};
 
 /// A wrapper around [`UdpSocket`] that implements [`Read`] and [`Write`].
+///
+/// Incoming datagrams are buffered, so frames can be read in arbitrary chunks.
 pub struct UdpRW {
     socket: UdpSocket,
+    buf: Vec<u8>,
+    pos: usize,
 }
 
 impl UdpRW {
     /// Creates a new UDP reader/writer.
     pub fn new(socket: UdpSocket) -> Self {
-        Self { socket }
+        Self {
+            socket,
+            buf: Vec::new(),
+            pos: 0,
+        }
     }
 
     /// Creates a new independently owned handle to the underlying socket.
     ///
     /// This is a thin wrapper around [`UdpSocket::try_clone`].
     pub fn try_clone(&self) -> std::io::Result<Self> {
-        Ok(Self {
-            socket: self.socket.try_clone()?,
-        })
+        Ok(Self::new(self.socket.try_clone()?))
     }
 }
 
 impl Read for UdpRW {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.socket.recv(buf)
+        while self.pos >= self.buf.len() {
+            self.buf.resize(u16::MAX as usize, 0);
+            self.pos = 0;
+
+            match self.socket.recv(&mut self.buf) {
+                Ok(bytes_read) => self.buf.truncate(bytes_read),
+                Err(err) => {
+                    self.buf.clear();
+                    return Err(err);
+                }
+            }
+        }
+
+        let bytes_read = buf.len().min(self.buf.len() - self.pos);
+        buf[0..bytes_read].copy_from_slice(&self.buf[self.pos..self.pos + bytes_read]);
+        self.pos += bytes_read;
+
+        Ok(bytes_read)
     }
 }
 
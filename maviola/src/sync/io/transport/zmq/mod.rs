@@ -0,0 +1,3 @@
+mod publisher;
+mod stream;
+mod subscriber;
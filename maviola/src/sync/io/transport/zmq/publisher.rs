@@ -0,0 +1,112 @@
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::zmtp::{SocketType, Subscriptions, ZmtpDecoder};
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionConf, ZmqPub};
+use crate::core::utils::Closer;
+use crate::sync::consts::{TCP_READ_TIMEOUT, TCP_WRITE_TIMEOUT};
+use crate::sync::io::transport::tcp::server::{on_channel_close_handler, on_close_handler};
+use crate::sync::io::transport::zmq::stream::{handshake, ZmtpReader, ZmtpWriter};
+use crate::sync::io::{ChannelFactory, Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ZmqPub {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let server_addr = self.addr;
+        let listener = TcpListener::bind(self.addr)?;
+
+        let conn_state = Closer::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), conn_state.to_shared());
+
+        let info = self.info().clone();
+        let topic = self.topic.clone();
+
+        let handler = ConnectionHandler::spawn(move || -> Result<()> {
+            on_close_handler(conn_state.to_closable(), server_addr, info.clone());
+
+            for stream in listener.incoming() {
+                if conn_state.is_closed() {
+                    break;
+                }
+
+                let stream = stream?;
+                let peer_addr = stream.peer_addr()?;
+                let chan_info = info.make_channel_info(ChannelDetails::ZmqPub {
+                    server_addr,
+                    peer_addr,
+                    topic: topic.clone(),
+                });
+
+                let chan_factory = chan_factory.clone();
+                let topic = topic.clone();
+                spawn_io(move || accept_subscriber(&chan_factory, chan_info, stream, &topic));
+            }
+
+            Ok(())
+        });
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+}
+
+/// Performs ZMTP handshake with a subscriber and attaches its channel.
+fn accept_subscriber<V: MaybeVersioned>(
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    mut stream: TcpStream,
+    topic: &str,
+) {
+    let decoder = stream
+        .set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT)))
+        .map_err(Error::from)
+        .and_then(|_| handshake(&mut stream, SocketType::Pub));
+
+    match decoder {
+        Ok(decoder) => {
+            log::debug!("[{chan_info:?}] subscriber connected");
+            if let Err(err) =
+                attach_channel(chan_factory, chan_info.clone(), stream, topic, decoder)
+            {
+                log::debug!("[{chan_info:?}] can't attach subscriber: {err:?}");
+            }
+        }
+        Err(err) => {
+            log::info!("[{chan_info:?}] subscriber rejected: {err:?}");
+            _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn attach_channel<V: MaybeVersioned>(
+    chan_factory: &ChannelFactory<V>,
+    chan_info: ChannelInfo,
+    stream: TcpStream,
+    topic: &str,
+    decoder: ZmtpDecoder,
+) -> Result<()> {
+    let writer = stream;
+    let reader = writer.try_clone()?;
+    let stream = writer.try_clone()?;
+
+    writer.set_write_timeout(TCP_WRITE_TIMEOUT)?;
+    writer.set_read_timeout(TCP_READ_TIMEOUT)?;
+
+    let subscriptions = Arc::new(Mutex::new(Subscriptions::default()));
+    let reader = ZmtpReader::publisher(reader, decoder, subscriptions.clone());
+    let writer = ZmtpWriter::new(writer, topic, subscriptions);
+
+    let channel_state = chan_factory.build(chan_info, reader, writer).spawn();
+    on_channel_close_handler(channel_state.to_closable(), stream);
+    channel_state.discard();
+
+    Ok(())
+}
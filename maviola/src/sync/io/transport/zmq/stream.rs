@@ -0,0 +1,169 @@
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::core::io::zmtp::{self, SocketType, Subscriptions, ZmtpDecoder, ZmtpEvent};
+use crate::core::io::FrameSplitter;
+
+use crate::prelude::*;
+
+/// Reads frames published to a `SUB` socket or subscriptions sent to a `PUB` socket.
+///
+/// Yields serialized MAVLink frames for subscribers. For publishers, updates subscriptions and
+/// never yields any data until the peer disconnects.
+pub(super) struct ZmtpReader<R: Read> {
+    inner: R,
+    decoder: ZmtpDecoder,
+    subscriptions: Option<Arc<Mutex<Subscriptions>>>,
+    pending: Vec<u8>,
+    consumed: usize,
+}
+
+/// Publishes serialized MAVLink frames to a `SUB` peer according to its subscriptions.
+pub(super) struct ZmtpWriter<W: Write> {
+    inner: W,
+    topic: Vec<u8>,
+    splitter: FrameSplitter,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+}
+
+/// Performs ZMTP handshake over a blocking `stream`.
+///
+/// Returns decoder with bytes, that were received after the handshake.
+pub(super) fn handshake<S: Read + Write>(
+    stream: &mut S,
+    socket_type: SocketType,
+) -> Result<ZmtpDecoder> {
+    stream.write_all(&zmtp::greeting())?;
+    let mut greeting = [0u8; zmtp::GREETING_SIZE];
+    stream.read_exact(&mut greeting)?;
+    zmtp::check_greeting(&greeting)?;
+
+    stream.write_all(&zmtp::ready(socket_type))?;
+    let mut decoder = ZmtpDecoder::default();
+    let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+    let event = loop {
+        if let Some(event) = decoder.next()? {
+            break event;
+        }
+        let bytes_read = stream.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        decoder.push(&buffer[..bytes_read]);
+    };
+    zmtp::check_ready(event, socket_type)?;
+
+    Ok(decoder)
+}
+
+impl<R: Read> ZmtpReader<R> {
+    pub(super) fn subscriber(inner: R, decoder: ZmtpDecoder) -> Self {
+        Self::new(inner, decoder, None)
+    }
+
+    pub(super) fn publisher(
+        inner: R,
+        decoder: ZmtpDecoder,
+        subscriptions: Arc<Mutex<Subscriptions>>,
+    ) -> Self {
+        Self::new(inner, decoder, Some(subscriptions))
+    }
+
+    fn new(
+        inner: R,
+        decoder: ZmtpDecoder,
+        subscriptions: Option<Arc<Mutex<Subscriptions>>>,
+    ) -> Self {
+        Self {
+            inner,
+            decoder,
+            subscriptions,
+            pending: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    fn handle(&mut self, event: ZmtpEvent) {
+        match &self.subscriptions {
+            Some(subscriptions) => match subscriptions.lock() {
+                Ok(mut subscriptions) => subscriptions.handle(&event),
+                Err(err) => err.into_inner().handle(&event),
+            },
+            None => {
+                if let Some(frame) = zmtp::published_frame(event) {
+                    self.pending = frame;
+                    self.consumed = 0;
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for ZmtpReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut buffer = [0u8; zmtp::READ_BUFFER_SIZE];
+
+        loop {
+            if self.consumed < self.pending.len() {
+                let available = &self.pending[self.consumed..];
+                let size = available.len().min(buf.len());
+                buf[..size].copy_from_slice(&available[..size]);
+                self.consumed += size;
+                return Ok(size);
+            }
+
+            match self.decoder.next() {
+                Ok(Some(event)) => {
+                    self.handle(event);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                }
+            }
+
+            let bytes_read = self.inner.read(&mut buffer)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.decoder.push(&buffer[..bytes_read]);
+        }
+    }
+}
+
+impl<W: Write> ZmtpWriter<W> {
+    pub(super) fn new(inner: W, topic: &str, subscriptions: Arc<Mutex<Subscriptions>>) -> Self {
+        Self {
+            inner,
+            topic: topic.as_bytes().to_vec(),
+            splitter: FrameSplitter::default(),
+            subscriptions,
+        }
+    }
+
+    fn is_subscribed(&self, frame: &[u8]) -> bool {
+        let key = zmtp::publication_key(&self.topic, frame);
+        match self.subscriptions.lock() {
+            Ok(subscriptions) => subscriptions.matches(key),
+            Err(err) => err.into_inner().matches(key),
+        }
+    }
+}
+
+impl<W: Write> Write for ZmtpWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.splitter.push(buf);
+        while let Some(frame) = self.splitter.next() {
+            if self.is_subscribed(&frame) {
+                self.inner
+                    .write_all(&zmtp::publication(&self.topic, &frame))?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::Write;
+use std::net::TcpStream;
+
+use crate::core::consts::TCP_HANDSHAKE_TIMEOUT;
+use crate::core::io::zmtp::{self, SocketType};
+use crate::core::io::{ChannelDetails, ZmqSub};
+use crate::core::utils::SharedCloser;
+use crate::sync::io::transport::tcp::server::on_channel_close_handler;
+use crate::sync::io::transport::zmq::stream::{handshake, ZmtpReader};
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+
+use crate::prelude::*;
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ZmqSub {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        stream.set_read_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(TCP_HANDSHAKE_TIMEOUT))?;
+
+        let decoder = handshake(&mut stream, SocketType::Sub)?;
+        stream.write_all(&zmtp::subscription(self.topic.as_bytes()))?;
+
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(None)?;
+
+        let (connection, chan_factory) = Connection::new(self.info.clone(), SharedCloser::new());
+
+        let chan_info = connection.info().make_channel_info(ChannelDetails::ZmqSub {
+            server_addr: self.addr,
+            topic: self.topic.clone(),
+        });
+        let reader = ZmtpReader::subscriber(stream.try_clone()?, decoder);
+        // Subscribers can't send messages, outgoing frames are discarded
+        let channel_state = chan_factory
+            .build(chan_info, reader, std::io::sink())
+            .spawn();
+        on_channel_close_handler(channel_state.to_closable(), stream);
+
+        let handler = ConnectionHandler::spawn_from_state(channel_state);
+
+        Ok((connection, handler))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+
+    fn is_repairable(&self) -> bool {
+        true
+    }
+}
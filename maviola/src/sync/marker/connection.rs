@@ -1,5 +1,7 @@
+use crate::core::io::ConnectionInfo;
 use crate::core::marker::{HasConnConf, MaybeConnConf};
 use crate::core::utils::Sealed;
+use crate::error::ConfigDiagnostic;
 use crate::sync::io::ConnectionBuilder;
 
 use crate::prelude::*;
@@ -11,14 +13,22 @@ pub struct ConnConf<V: MaybeVersioned>(pub(in crate::sync) Box<dyn ConnectionBui
 
 impl<V: MaybeVersioned> Sealed for ConnConf<V> {}
 impl<V: MaybeVersioned> HasConnConf for ConnConf<V> {
+    fn info(&self) -> &ConnectionInfo {
+        self.0.info()
+    }
+
     fn is_repairable(&self) -> bool {
         self.0.is_repairable()
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        self.0.diagnostics()
+    }
 }
 impl<V: MaybeVersioned> MaybeConnConf for ConnConf<V> {}
 
 impl<V: MaybeVersioned> ConnConf<V> {
-    pub(in crate::sync) fn new(builder: impl ConnectionBuilder<V> + 'static) -> Self {
+    pub(crate) fn new(builder: impl ConnectionBuilder<V> + 'static) -> Self {
         Self(Box::new(builder))
     }
 
@@ -60,6 +60,13 @@
 //! It is possible to get a list of active peers by [`Node::peers`] or check for peers availability
 //! using [`Node::has_peers`].
 //!
+//! Peers, that belong to the same MAVLink system, are aggregated into a
+//! [`RemoteSystem`](crate::protocol::RemoteSystem). Its state (mode, status, autopilot) is
+//! available through [`Node::system`]. Changes are emitted as [`Event::SystemChanged`], once enabled by
+//! [`Node::system_events`].
+//! Components of a system are available through [`Node::components`]. New components are reported
+//! as [`Event::NewComponent`] with their kind derived from heartbeats.
+//!
 //! ## Custom connections
 //!
 //! It is possible to create a custom connection by implementing a
@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
+use crate::core::network::ConnectionOptions;
 use crate::core::utils::Closer;
+use crate::error::ConfigDiagnostic;
 use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
 use crate::sync::marker::ConnConf;
 use crate::sync::network::conn_handler::NetworkConnectionHandler;
@@ -12,6 +14,7 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for Network<V, ConnConf<V>> {
         let state = Closer::new();
 
         let (conn, chan_factory) = Connection::new(self.info.clone(), state.to_shared());
+        let conn = conn.with_network(self.handle.clone());
 
         let conn_handler = NetworkConnectionHandler::new(state, self, chan_factory)?;
         let handler = ConnectionHandler::spawn(move || conn_handler.handle());
@@ -23,9 +26,35 @@ impl<V: MaybeVersioned> ConnectionBuilder<V> for Network<V, ConnConf<V>> {
         ConnConf::new(Network {
             info: self.info.clone(),
             nodes: self.nodes.clone(),
+            options: self.options.clone(),
+            max_frame_ages: self.max_frame_ages.clone(),
+            dedup: self.dedup,
+            routing: self.routing,
+            routing_table: self.routing_table.clone(),
+            tap: self.tap.clone(),
+            injectors: self.injectors.clone(),
+            handle: self.handle.clone(),
             retry: self.retry,
             stop_on_node_down: self.stop_on_node_down,
             _version: PhantomData,
         })
     }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Network::diagnostics(self)
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for ConnectionOptions<V, ConnConf<V>> {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        self.network().build()
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        self.network().to_conf()
+    }
+
+    fn diagnostics(&self) -> Vec<ConfigDiagnostic> {
+        Network::diagnostics(self.network())
+    }
 }
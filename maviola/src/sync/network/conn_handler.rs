@@ -1,17 +1,32 @@
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use crate::core::consts::NETWORK_POOLING_INTERVAL;
-use crate::core::io::{ConnectionInfo, IncomingFrame, RetryStrategy};
+use crate::core::io::{
+    ChannelInfo, ConnectionEvent, ConnectionInfo, IncomingFrame, OutgoingFrame, RetryStrategy,
+};
 use crate::core::marker::Proxy;
-use crate::core::network::types::{NetworkConnInfo, NetworkConnState, RestartNodeEvent};
+use crate::core::network::types::{
+    NetworkConnInfo, NetworkConnState, NetworkNodeRole, RestartNodeEvent,
+};
+use crate::core::network::{
+    BandwidthLimit, BandwidthTracker, ConnectionFilter, FailoverGroup, ForwardSuppression,
+    FrameDeduplicator, HeartbeatToggle, InjectionTargets, MirrorGroup, NetworkCommand,
+    NetworkHandle, NetworkInjectors, NetworkTap, NodeOptions, ResequenceTracker, Resequencer,
+    RoutingMode, RoutingTable, SysIdTranslation, TapDirection, TelemetryPolicy, TelemetryTracker,
+    VersionBridge, VersionPin,
+};
 use crate::core::node::NodeConf;
 use crate::core::utils::{Closer, UniqueId};
+use crate::dialects::minimal::messages::Heartbeat;
 use crate::error::{NodeError, RecvTimeoutError};
+use crate::protocol::{FrameProcessor, IdRemapper, MessageId};
 use crate::sync::io::{ChannelFactory, IncomingFrameProducer, OutgoingFrameHandler};
 use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
 
 use crate::prelude::*;
 use crate::sync::prelude::*;
@@ -24,7 +39,20 @@ pub(super) struct NetworkConnectionHandler<V: MaybeVersioned> {
     stop_on_node_down: bool,
     node_configs: HashMap<UniqueId, NodeConf<Proxy, V, ConnConf<V>>>,
     nodes: HashMap<UniqueId, Node<Proxy, V, SyncApi<V>>>,
+    options: HashMap<UniqueId, NodeOptions>,
+    standby: Vec<UniqueId>,
+    roles: HashMap<UniqueId, NetworkNodeRole>,
+    failover_groups: Vec<FailoverGroup>,
+    max_frame_ages: HashMap<MessageId, Duration>,
+    dedup: Option<FrameDeduplicator>,
+    routing: RoutingMode,
+    routing_table: RoutingTable,
+    tap: NetworkTap<V>,
+    injectors: NetworkInjectors<V>,
+    handle: NetworkHandle<V, ConnConf<V>>,
+    injection_targets: InjectionTargets<V>,
     producer: IncomingFrameProducer<V>,
+    events: mpsc::Sender<ConnectionEvent>,
     send_handler: OutgoingFrameHandler<V>,
     closed_nodes_chan: ClosedNodesChannel,
     node_events_chan: RestartEventsChannel<V>,
@@ -35,8 +63,21 @@ struct IncomingEventsHandler<V: MaybeVersioned> {
     id: UniqueId,
     info: NetworkConnInfo,
     state: NetworkConnState,
+    role: NetworkNodeRole,
+    failover: Option<FailoverGroup>,
+    mirror: Option<MirrorGroup>,
+    filter: Option<ConnectionFilter>,
+    policy: Option<TelemetryPolicy>,
+    pin: Option<VersionPin>,
+    translation: Option<SysIdTranslation>,
+    remapper: Option<IdRemapper>,
+    dedup: Option<FrameDeduplicator>,
+    routing_table: RoutingTable,
+    tap: NetworkTap<V>,
+    processor: Arc<FrameProcessor>,
     receiver: EventReceiver<V>,
     producer: IncomingFrameProducer<V>,
+    events: mpsc::Sender<ConnectionEvent>,
 }
 
 /// Handles outgoing frames of a particular [`Node`] withing a [`Network`].
@@ -44,6 +85,24 @@ struct OutgoingFramesHandler<V: MaybeVersioned> {
     id: UniqueId,
     info: NetworkConnInfo,
     state: NetworkConnState,
+    role: NetworkNodeRole,
+    failover: Option<FailoverGroup>,
+    mirror: Option<MirrorGroup>,
+    filter: Option<ConnectionFilter>,
+    telemetry: Option<TelemetryTracker>,
+    bandwidth: Option<BandwidthTracker>,
+    bridge: Option<VersionBridge>,
+    pin: Option<VersionPin>,
+    translation: Option<SysIdTranslation>,
+    remapper: Option<IdRemapper>,
+    heartbeats: Option<HeartbeatToggle>,
+    suppression: Option<ForwardSuppression>,
+    resequence: Option<ResequenceTracker>,
+    routing: RoutingMode,
+    routing_table: RoutingTable,
+    tap: NetworkTap<V>,
+    processor: Arc<FrameProcessor>,
+    max_frame_ages: HashMap<MessageId, Duration>,
     send_handler: OutgoingFrameHandler<V>,
     sender: FrameSender<V, Proxy>,
 }
@@ -78,6 +137,27 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
     ) -> Result<Self> {
         let node_configs = network.nodes.clone();
         let mut nodes = HashMap::new();
+        let roles = node_configs
+            .keys()
+            .map(|id| (*id, NetworkNodeRole::new(is_standby(&network.options, id))))
+            .collect();
+        // Unique IDs grow monotonically, so standby nodes are ordered as they were added
+        let mut standby: Vec<UniqueId> = node_configs
+            .keys()
+            .filter(|id| is_standby(&network.options, id))
+            .copied()
+            .collect();
+        standby.sort();
+        let mut failover_groups: Vec<FailoverGroup> = Vec::new();
+        for group in network
+            .options
+            .values()
+            .filter_map(|opts| opts.failover.as_ref())
+        {
+            if !failover_groups.iter().any(|known| known.is_same(group)) {
+                failover_groups.push(group.clone());
+            }
+        }
 
         for (id, node_conf) in &node_configs {
             let node = node_conf.clone().build()?;
@@ -92,7 +172,20 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
             stop_on_node_down: network.stop_on_node_down,
             node_configs,
             nodes,
+            options: network.options.clone(),
+            standby,
+            roles,
+            failover_groups,
+            max_frame_ages: network.max_frame_ages.clone(),
+            dedup: network.dedup.map(FrameDeduplicator::new),
+            routing: network.routing,
+            routing_table: network.routing_table.clone(),
+            tap: network.tap.clone(),
+            injectors: network.injectors.clone(),
+            handle: network.handle.clone(),
+            injection_targets: InjectionTargets::new(network.tap.clone()),
             producer: chan_factory.producer().clone(),
+            events: chan_factory.event_sender().clone(),
             send_handler: chan_factory.send_handler().clone(),
             closed_nodes_chan: ClosedNodesChannel::new(),
             node_events_chan: RestartEventsChannel::synchronous(),
@@ -106,12 +199,34 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
         for (id, node) in &self.nodes {
             self.spawn_node_handlers(*id, node, self.closed_nodes_chan.tx.clone())?;
         }
+        self.injectors.start(&state, &self.injection_targets);
+        self.handle.start(
+            state.clone(),
+            info.clone(),
+            self.node_configs
+                .iter()
+                .map(|(id, conf)| (*id, conf.connection().info().clone())),
+        );
 
         while !state.is_closed() {
+            for command in self.handle.take_commands() {
+                match command {
+                    NetworkCommand::Add(id, node_conf) => {
+                        if let Err(err) = self.on_connection_added(id, *node_conf) {
+                            log::error!("[{info:?}] can't add connection: {err:?}");
+                        }
+                    }
+                    NetworkCommand::Remove(id) => self.on_connection_removed(id),
+                }
+            }
+
             if let Ok(event) = self.node_events_chan.rx.try_recv() {
                 match event {
+                    // Nodes restarted after their connections were removed are dropped
                     RestartNodeEvent::New(id, node) => {
-                        self.nodes.insert(id, node);
+                        if self.node_configs.contains_key(&id) {
+                            self.nodes.insert(id, node);
+                        }
                     }
                     RestartNodeEvent::Retry(id, strategy) => {
                         if self.on_node_restart_retry(id, strategy).is_err() {
@@ -119,7 +234,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                         }
                     }
                     RestartNodeEvent::GiveUp(id) => {
-                        if self.on_node_give_up(id).is_err() {
+                        if self.on_node_give_up(id).is_err() || self.node_configs.is_empty() {
                             break;
                         }
                     }
@@ -145,11 +260,11 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                 }
             };
 
-            if self.node_configs.is_empty() {
-                break;
-            }
+            self.update_failover();
         }
 
+        self.handle.stop();
+        self.tap.close();
         log::info!("[{info:?}] main handler stopped");
         Ok(())
     }
@@ -159,6 +274,13 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
             let conn_info = node_conf.connection_conf.0.info();
             log::info!("[{:?}] node {conn_info:?} stopped", &self.info);
 
+            self.routing_table.forget(id);
+            self.injection_targets.remove(conn_info.id());
+            self.activate_standby(id);
+            if let Some(group) = self.options(id).failover {
+                group.set_down(id);
+            }
+
             if node_conf.is_repairable() {
                 let tx = self.node_events_chan.tx.clone();
 
@@ -169,7 +291,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                             .send(RestartNodeEvent::GiveUp(id))?;
                     }
                     RetryStrategy::Attempts(attempts, interval) => {
-                        thread::spawn(move || {
+                        spawn_io(move || {
                             thread::sleep(interval);
                             tx.send(RestartNodeEvent::Retry(
                                 id,
@@ -179,7 +301,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                         });
                     }
                     RetryStrategy::Always(interval) => {
-                        thread::spawn(move || {
+                        spawn_io(move || {
                             thread::sleep(interval);
                             tx.send(RestartNodeEvent::Retry(id, RetryStrategy::Always(interval)))
                                 .unwrap();
@@ -235,7 +357,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                             .send(RestartNodeEvent::GiveUp(id))?;
                     }
                     RetryStrategy::Attempts(attempts, interval) => {
-                        thread::spawn(move || {
+                        spawn_io(move || {
                             thread::sleep(interval);
                             tx.send(RestartNodeEvent::Retry(
                                 id,
@@ -245,7 +367,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
                         });
                     }
                     RetryStrategy::Always(interval) => {
-                        thread::spawn(move || {
+                        spawn_io(move || {
                             thread::sleep(interval);
                             tx.send(RestartNodeEvent::Retry(id, RetryStrategy::Always(interval)))
                                 .unwrap();
@@ -260,14 +382,18 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
     }
 
     fn on_node_give_up(&mut self, id: UniqueId) -> Result<()> {
-        if let Some(conf) = self.node_configs.get(&id) {
-            log::info!(
-                "[{:?}] give up node {:?}",
-                self.info,
-                conf.connection().info()
-            );
-        }
-        self.node_configs.remove(&id);
+        // Connection was already removed
+        let conf = match self.node_configs.remove(&id) {
+            Some(conf) => conf,
+            None => return Ok(()),
+        };
+        let conn_info = conf.connection().info().clone();
+        log::info!("[{:?}] give up node {conn_info:?}", self.info);
+
+        self.handle.forget(id);
+        _ = self
+            .events
+            .send(ConnectionEvent::ConnectionRemoved(conn_info));
 
         if self.stop_on_node_down {
             return Err(Error::from(NodeError::Inactive));
@@ -276,6 +402,105 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
         Ok(())
     }
 
+    fn on_connection_added(
+        &mut self,
+        id: UniqueId,
+        node_conf: NodeConf<Proxy, V, ConnConf<V>>,
+    ) -> Result<()> {
+        let conn_info = node_conf.connection().info().clone();
+        log::info!("[{:?}] add node {conn_info:?}", self.info);
+
+        self.roles.insert(id, NetworkNodeRole::new(false));
+        self.node_configs.insert(id, node_conf.clone());
+        _ = self
+            .events
+            .send(ConnectionEvent::ConnectionAdded(conn_info.clone()));
+
+        match node_conf.build() {
+            Ok(node) => {
+                self.spawn_node_handlers(id, &node, self.closed_nodes_chan.tx.clone())?;
+                self.nodes.insert(id, node);
+            }
+            // Failed connections are repaired according to the retry strategy
+            Err(err) => {
+                log::warn!("[{:?}] can't start node {conn_info:?}: {err:?}", self.info);
+                self.on_node_stopped(id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_connection_removed(&mut self, id: UniqueId) {
+        let node_conf = match self.node_configs.remove(&id) {
+            Some(node_conf) => node_conf,
+            None => return,
+        };
+        let conn_info = node_conf.connection().info().clone();
+        log::info!("[{:?}] remove node {conn_info:?}", self.info);
+
+        self.routing_table.forget(id);
+        self.injection_targets.remove(conn_info.id());
+        self.activate_standby(id);
+        self.standby.retain(|standby_id| *standby_id != id);
+        self.roles.remove(&id);
+        if let Some(options) = self.options.remove(&id) {
+            if let Some(group) = options.failover {
+                group.remove(id);
+            }
+            if let Some(group) = options.mirror {
+                group.remove(id);
+            }
+        }
+        // Node is closed, once dropped
+        self.nodes.remove(&id);
+
+        _ = self
+            .events
+            .send(ConnectionEvent::ConnectionRemoved(conn_info));
+    }
+
+    /// Options of a node with specified `id`, if any.
+    fn options(&self, id: UniqueId) -> NodeOptions {
+        self.options.get(&id).cloned().unwrap_or_default()
+    }
+
+    fn update_failover(&self) {
+        let now = Instant::now();
+
+        for group in &self.failover_groups {
+            if let Some(conn_info) = group.update(now) {
+                log::info!("[{:?}] failover link {conn_info:?} activated", self.info);
+                _ = self.events.send(ConnectionEvent::LinkActivated(conn_info));
+            }
+        }
+    }
+
+    fn activate_standby(&self, failed_id: UniqueId) {
+        match self.roles.get(&failed_id) {
+            Some(role) if !role.is_standby() => {}
+            _ => return,
+        }
+
+        for id in &self.standby {
+            if !self.nodes.contains_key(id) {
+                continue;
+            }
+            if let Some(role) = self.roles.get(id) {
+                if role.activate() {
+                    if let Some(node_conf) = self.node_configs.get(id) {
+                        log::info!(
+                            "[{:?}] standby node {:?} activated",
+                            self.info,
+                            node_conf.connection().info()
+                        );
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
     fn restart_node(
         &self,
         id: UniqueId,
@@ -313,12 +538,43 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
             connection: node.state.clone(),
         };
 
+        let role = self
+            .roles
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| NetworkNodeRole::new(false));
+        let options = self.options(id);
+        if let Some(group) = &options.failover {
+            group.set_up(id);
+        }
+
+        let sender = node.frame_sender().clone();
+        self.injection_targets.set(
+            node.info().id(),
+            Box::new(move |frame| {
+                _ = sender.send_raw(frame);
+            }),
+        );
+
         let in_handler = IncomingEventsHandler {
             id,
             info: info.clone(),
             state: state.clone(),
-            receiver: node.receiver().clone(),
+            role: role.clone(),
+            failover: options.failover.clone(),
+            mirror: options.mirror.clone(),
+            filter: options.filter.clone(),
+            policy: options.telemetry.clone(),
+            pin: options.pin.clone(),
+            translation: options.translation.clone(),
+            remapper: options.remapper.clone(),
+            dedup: self.dedup.clone(),
+            routing_table: self.routing_table.clone(),
+            tap: self.tap.clone(),
+            processor: node.processor.clone(),
+            receiver: node.receiver().share(),
             producer: self.producer.clone(),
+            events: self.events.clone(),
         }
         .spawn();
 
@@ -326,6 +582,24 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
             id,
             info: info.clone(),
             state: state.clone(),
+            role,
+            telemetry: options.telemetry.as_ref().map(TelemetryPolicy::tracker),
+            bandwidth: options.limit.as_ref().map(BandwidthLimit::tracker),
+            resequence: options.resequencer.as_ref().map(Resequencer::tracker),
+            failover: options.failover,
+            mirror: options.mirror,
+            filter: options.filter,
+            bridge: options.bridge,
+            pin: options.pin,
+            translation: options.translation,
+            remapper: options.remapper,
+            heartbeats: options.heartbeats,
+            suppression: options.suppression,
+            routing: self.routing,
+            routing_table: self.routing_table.clone(),
+            tap: self.tap.clone(),
+            processor: node.processor.clone(),
+            max_frame_ages: self.max_frame_ages.clone(),
             send_handler: self.send_handler.clone(),
             sender: node.frame_sender().clone(),
         }
@@ -348,7 +622,7 @@ impl<V: MaybeVersioned> NetworkConnectionHandler<V> {
 impl<V: MaybeVersioned> IncomingEventsHandler<V> {
     /// Spawns incoming events handler.
     fn spawn(self) -> JoinHandle<UniqueId> {
-        thread::spawn(move || {
+        spawn_io(move || {
             let id = self.id;
             let info = self.info.clone();
 
@@ -360,6 +634,67 @@ impl<V: MaybeVersioned> IncomingEventsHandler<V> {
         })
     }
 
+    /// Returns `true`, if node is the active link of its failover group (if any).
+    fn is_active_link(&self) -> bool {
+        match &self.failover {
+            Some(group) => group.is_active(self.id),
+            None => true,
+        }
+    }
+
+    /// Returns `true`, if frame received by a `channel` passes connection filter (if any).
+    fn passes_filter(&self, frame: &Frame<V>, channel: &ChannelInfo) -> bool {
+        match &self.filter {
+            Some(filter) => filter.matches_incoming(frame, &self.info.connection, channel),
+            None => true,
+        }
+    }
+
+    /// Applies version pin to a received frame, if set.
+    ///
+    /// Returns [`None`], if frame should be dropped.
+    fn pin(&self, frame: Frame<V>) -> Option<Frame<V>> {
+        match &self.pin {
+            Some(pin) => pin.apply(frame),
+            None => Some(frame),
+        }
+    }
+
+    /// Returns `true`, if the same frame has been recently received by the network or by other
+    /// connections of the mirror group (if any).
+    fn is_duplicate(&self, frame: &Frame<V>, received_at: Instant) -> bool {
+        if let Some(mirror) = &self.mirror {
+            if mirror.is_duplicate(frame, received_at) {
+                return true;
+            }
+        }
+
+        match &self.dedup {
+            Some(dedup) => dedup.is_duplicate(frame, received_at),
+            None => false,
+        }
+    }
+
+    /// Translates system `ID`s of a frame received by a `channel`, if translation is set.
+    ///
+    /// Returns [`None`], if frame should be dropped.
+    fn translate(&self, frame: Frame<V>, channel: &ChannelInfo) -> Option<Frame<V>> {
+        match &self.translation {
+            Some(translation) => translation.translate_incoming(frame, channel, &self.processor),
+            None => Some(frame),
+        }
+    }
+
+    /// Rewrites system and component `ID`s of a received frame, if remapper is set.
+    ///
+    /// Returns [`None`], if frame should be dropped.
+    fn remap(&self, frame: Frame<V>) -> Option<Frame<V>> {
+        match &self.remapper {
+            Some(remapper) => remapper.remap_incoming(&frame, &self.processor),
+            None => Some(frame),
+        }
+    }
+
     /// Handles incoming events.
     fn handle(self) -> Result<()> {
         let state = self.state.clone();
@@ -368,6 +703,29 @@ impl<V: MaybeVersioned> IncomingEventsHandler<V> {
             let (frame, callback) = match self.receiver.recv_timeout(NETWORK_POOLING_INTERVAL) {
                 Ok(event) => match event {
                     Event::Frame(frame, callback) => (frame, callback),
+                    Event::ChannelOpened(channel) => {
+                        _ = self.events.send(ConnectionEvent::ChannelOpened(channel));
+                        continue;
+                    }
+                    Event::ChannelClosed(channel) => {
+                        if let Some(translation) = &self.translation {
+                            translation.release(channel.id());
+                        }
+                        _ = self.events.send(ConnectionEvent::ChannelClosed(channel));
+                        continue;
+                    }
+                    Event::Malformed(channel, report) => {
+                        _ = self
+                            .events
+                            .send(ConnectionEvent::Malformed(channel, report));
+                        continue;
+                    }
+                    Event::SenderRejected(info, addr) => {
+                        _ = self
+                            .events
+                            .send(ConnectionEvent::SenderRejected(info, addr));
+                        continue;
+                    }
                     _ => continue,
                 },
                 Err(err) => match err {
@@ -376,8 +734,52 @@ impl<V: MaybeVersioned> IncomingEventsHandler<V> {
                 },
             };
 
+            if let Some(policy) = &self.policy {
+                policy.observe(&frame);
+            }
+
+            if let Some(group) = &self.failover {
+                group.record_frame(self.id, callback.received_at());
+            }
+
+            if self.role.is_standby() || !self.is_active_link() {
+                continue;
+            }
+
+            let frame = match self.pin(frame) {
+                Some(frame) => frame,
+                None => continue,
+            };
+
+            if !self.passes_filter(&frame, callback.info()) {
+                continue;
+            }
+
+            if self.is_duplicate(&frame, callback.received_at()) {
+                continue;
+            }
+
+            let frame = match self.translate(frame, callback.info()) {
+                Some(frame) => frame,
+                None => continue,
+            };
+
+            let frame = match self.remap(frame) {
+                Some(frame) => frame,
+                None => continue,
+            };
+
+            if frame.message_id() == Heartbeat::message_id() {
+                let id = MavLinkId::new(frame.system_id(), frame.component_id());
+                self.routing_table
+                    .observe(self.id, &self.info.connection, id);
+            }
+
+            self.tap
+                .publish(TapDirection::Incoming, self.info.connection.id(), &frame);
+
             self.producer
-                .send(IncomingFrame::new(frame, callback.into()))?;
+                .send(IncomingFrame::with_meta(frame, callback.into()))?;
         }
 
         Ok(())
@@ -387,7 +789,7 @@ impl<V: MaybeVersioned> IncomingEventsHandler<V> {
 impl<V: MaybeVersioned> OutgoingFramesHandler<V> {
     /// Spawns outgoing frames handler.
     fn spawn(self) -> JoinHandle<UniqueId> {
-        thread::spawn(move || {
+        spawn_io(move || {
             let id = self.id;
             let info = self.info.clone();
 
@@ -399,8 +801,203 @@ impl<V: MaybeVersioned> OutgoingFramesHandler<V> {
         })
     }
 
+    /// Returns `true`, if node is the active link of its failover group (if any).
+    fn is_active_link(&self) -> bool {
+        match &self.failover {
+            Some(group) => group.is_active(self.id),
+            None => true,
+        }
+    }
+
+    /// Returns `true`, if frame passes connection filter (if any).
+    fn passes_filter(&self, frame: &Frame<V>) -> bool {
+        match &self.filter {
+            Some(filter) => filter.matches_outgoing(frame, &self.info.connection),
+            None => true,
+        }
+    }
+
+    /// Returns `true`, if frame is not an automatic heartbeat suppressed by heartbeat toggle (if
+    /// any).
+    fn allows_heartbeat(&self, frame: &OutgoingFrame<V>) -> bool {
+        match &self.heartbeats {
+            Some(toggle) if frame.is_node_heartbeat() => toggle.is_enabled(),
+            _ => true,
+        }
+    }
+
+    /// Returns `true`, if frame is not a forwarded frame suppressed by forwarding suppression (if
+    /// any).
+    fn allows_forwarding(&self, frame: &OutgoingFrame<V>) -> bool {
+        match &self.suppression {
+            Some(suppression) if frame.is_forwarded() => {
+                !suppression.suppresses(frame.frame().message_id())
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns `true`, if frame should be routed to this node according to routing table.
+    ///
+    /// Routing table is consulted for all frames in [`RoutingMode::TargetAware`] mode and for
+    /// targeted frames regardless of the mode.
+    fn is_routed(&self, frame: &OutgoingFrame<V>) -> bool {
+        match self.routing {
+            RoutingMode::TargetAware => self.has_route(frame.frame()),
+            RoutingMode::Broadcast if frame.is_targeted() => self.has_route(frame.frame()),
+            RoutingMode::Broadcast => true,
+        }
+    }
+
+    /// Returns `true`, if frame target was seen by this node or by any node of its mirror group
+    /// (if any).
+    fn has_route(&self, frame: &Frame<V>) -> bool {
+        match &self.mirror {
+            Some(mirror) => mirror
+                .members()
+                .into_iter()
+                .any(|id| self.routing_table.should_route(id, frame)),
+            None => self.routing_table.should_route(self.id, frame),
+        }
+    }
+
+    /// Adjusts broadcast scope of a frame according to the mirror group, if set.
+    ///
+    /// Returns `false`, if frame should not be sent over this node.
+    fn mirror(&self, frame: &mut OutgoingFrame<V>) -> bool {
+        let mirror = match &self.mirror {
+            Some(mirror) => mirror,
+            None => return true,
+        };
+
+        match mirror.mirror_scope(self.id, frame.scope()) {
+            Some(scope) => {
+                frame.set_scope(scope);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true`, if frame is not older than the maximum age for its message (if any).
+    fn is_fresh(&self, frame: &OutgoingFrame<V>) -> bool {
+        match self.max_frame_ages.get(&frame.frame().message_id()) {
+            Some(max_age) => frame.age() <= *max_age,
+            None => true,
+        }
+    }
+
+    /// Returns `true`, if frame does not exceed rates of telemetry policy (if any).
+    fn allows_rate(&mut self, frame: &Frame<V>) -> bool {
+        match &mut self.telemetry {
+            Some(tracker) => tracker.allow(frame, Instant::now()),
+            None => true,
+        }
+    }
+
+    /// Converts frame to the protocol version of the bridge, if set.
+    ///
+    /// Returns `false`, if frame can't be converted and should be dropped.
+    fn bridge(&self, frame: &mut OutgoingFrame<V>) -> bool {
+        let bridge = match &self.bridge {
+            Some(bridge) if frame.frame().version() != bridge.version() => bridge,
+            _ => return true,
+        };
+
+        match bridge.convert(frame.frame()) {
+            Ok(converted) => {
+                frame.replace_frame(converted);
+                true
+            }
+            Err(err) => {
+                log::warn!("[{}] frame rejected by version bridge: {err}", self.info);
+                false
+            }
+        }
+    }
+
+    /// Converts or rejects frames of protocol versions other than the pinned one, if pin is set.
+    ///
+    /// Returns `false`, if frame should be dropped.
+    fn pin(&self, frame: &mut OutgoingFrame<V>) -> bool {
+        let pin = match &self.pin {
+            Some(pin) if frame.frame().version() != pin.version() => pin,
+            _ => return true,
+        };
+
+        match pin.apply(frame.frame().clone()) {
+            Some(pinned) => {
+                frame.replace_frame(pinned);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true`, if frame does not exceed bandwidth limit (if any).
+    fn allows_bandwidth(&mut self, frame: &Frame<V>) -> bool {
+        let allowed = match &mut self.bandwidth {
+            Some(tracker) => tracker.allow(frame, Instant::now()),
+            None => true,
+        };
+        if !allowed {
+            log::trace!(
+                "[{}] frame dropped by bandwidth limit: message #{}",
+                self.info,
+                frame.message_id()
+            );
+        }
+        allowed
+    }
+
+    /// Addresses frames sent to virtual systems to the real ones, if translation is set.
+    ///
+    /// Returns `false`, if frame should be dropped.
+    fn translate(&self, frame: &mut OutgoingFrame<V>) -> bool {
+        match &self.translation {
+            Some(translation) => {
+                translation.translate_outgoing(frame, self.info.connection.id(), &self.processor)
+            }
+            None => true,
+        }
+    }
+
+    /// Rewrites sequence number of a frame, if resequencer is set.
+    fn resequence(&mut self, frame: &mut OutgoingFrame<V>) {
+        if let Some(tracker) = &mut self.resequence {
+            if let Some(resequenced) = tracker.apply(frame.frame(), &self.processor) {
+                frame.replace_frame(resequenced);
+            }
+        }
+    }
+
+    /// Rewrites system and component `ID`s of a frame, if remapper is set.
+    ///
+    /// Returns `false`, if frame should be dropped.
+    fn remap(&self, frame: &mut OutgoingFrame<V>) -> bool {
+        let remapper = match &self.remapper {
+            Some(remapper) => remapper,
+            None => return true,
+        };
+
+        match remapper.remap_outgoing(frame.frame(), &self.processor) {
+            Some(remapped) => {
+                frame.replace_frame(remapped);
+                true
+            }
+            None => {
+                log::trace!(
+                    "[{}] frame can't be remapped: message #{}",
+                    self.info,
+                    frame.frame().message_id()
+                );
+                false
+            }
+        }
+    }
+
     /// Handles outgoing frames.
-    fn handle(self) -> Result<()> {
+    fn handle(mut self) -> Result<()> {
         let state = self.state.clone();
 
         while !state.is_closed() {
@@ -416,6 +1013,69 @@ impl<V: MaybeVersioned> OutgoingFramesHandler<V> {
                 continue;
             }
 
+            if !self.mirror(&mut frame) {
+                continue;
+            }
+
+            if !self.allows_heartbeat(&frame) || !self.allows_forwarding(&frame) {
+                continue;
+            }
+
+            if (self.role.is_standby() || !self.is_active_link())
+                && frame.frame().message_id() != Heartbeat::message_id()
+            {
+                continue;
+            }
+
+            if !self.is_fresh(&frame) {
+                log::trace!(
+                    "[{}] stale frame dropped: message #{}",
+                    self.info,
+                    frame.frame().message_id()
+                );
+                continue;
+            }
+
+            if !self.passes_filter(frame.frame()) {
+                continue;
+            }
+
+            if !self.is_routed(&frame) {
+                continue;
+            }
+
+            if !self.allows_rate(frame.frame()) {
+                continue;
+            }
+
+            if !self.translate(&mut frame) {
+                continue;
+            }
+
+            if !self.remap(&mut frame) {
+                continue;
+            }
+
+            if !self.bridge(&mut frame) {
+                continue;
+            }
+
+            if !self.pin(&mut frame) {
+                continue;
+            }
+
+            if !self.allows_bandwidth(frame.frame()) {
+                continue;
+            }
+
+            self.resequence(&mut frame);
+
+            self.tap.publish(
+                TapDirection::Outgoing,
+                self.info.connection.id(),
+                frame.frame(),
+            );
+
             self.sender.send_raw(frame)?;
         }
 
@@ -431,7 +1091,7 @@ impl NodeStateHandler {
         let info = self.info.clone();
         let state_change_tx = self.on_close_tx.clone();
 
-        thread::spawn(move || {
+        spawn_io(move || {
             if let Err(err) = self.handle() {
                 log::error!("[{info}] stop handler exited with error: {err:?}")
             }
@@ -467,3 +1127,8 @@ impl<V: MaybeVersioned> RestartEventsChannel<V> {
         Self { tx, rx }
     }
 }
+
+/// Returns `true`, if node with specified `id` is a warm standby.
+fn is_standby(options: &HashMap<UniqueId, NodeOptions>, id: &UniqueId) -> bool {
+    options.get(id).is_some_and(|options| options.standby)
+}
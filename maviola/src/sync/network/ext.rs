@@ -1,10 +1,19 @@
 use std::marker::PhantomData;
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
 
-use crate::core::io::{ConnectionDetails, ConnectionInfo};
-use crate::core::marker::Unset;
-use crate::core::utils::UniqueId;
-use crate::sync::io::ConnectionBuilder;
+use crate::core::consts::{NETWORK_POOLING_INTERVAL, NETWORK_TAP_CAPACITY};
+use crate::core::io::{ConnectionDetails, ConnectionId, ConnectionInfo};
+use crate::core::marker::{Proxy, Unset};
+use crate::core::network::{ConnectionOptions, InjectionTargets, NetworkHandle, TappedFrame};
+use crate::core::node::{PeerLocations, TrafficStats};
+use crate::core::utils::Closable;
+use crate::error::RecvTimeoutError;
+use crate::protocol::FrameProcessor;
+use crate::sync::io::{outgoing_channel, ConnectionBuilder, OutgoingFrameHandler};
 use crate::sync::marker::ConnConf;
+use crate::sync::node::FrameSender;
+use crate::sync::utils::spawn_io;
 
 use crate::prelude::*;
 
@@ -15,6 +24,14 @@ impl Network<Versionless, Unset> {
         Network {
             info: ConnectionInfo::new(ConnectionDetails::Network),
             nodes: Default::default(),
+            options: Default::default(),
+            max_frame_ages: Default::default(),
+            dedup: Default::default(),
+            routing: Default::default(),
+            routing_table: Default::default(),
+            tap: Default::default(),
+            injectors: Default::default(),
+            handle: Default::default(),
             retry: Default::default(),
             stop_on_node_down: Default::default(),
             _version: PhantomData,
@@ -25,17 +42,13 @@ impl Network<Versionless, Unset> {
 impl<V: MaybeVersioned> Network<V, ConnConf<V>> {
     /// <sup>[`sync`](crate::sync)</sup>
     /// Adds connection to a network.
+    ///
+    /// Returns [`ConnectionOptions`], that configure how the network treats this connection.
     pub fn add_connection(
-        mut self,
+        self,
         conn_conf: impl ConnectionBuilder<V> + 'static,
-    ) -> Network<V, ConnConf<V>> {
-        let node = Node::sync::<V>().connection(conn_conf).conf();
-        self.nodes.insert(UniqueId::new(), node);
-
-        Network {
-            nodes: self.nodes,
-            ..self
-        }
+    ) -> ConnectionOptions<V, ConnConf<V>> {
+        self.add_node(Node::sync::<V>().connection(conn_conf).conf())
     }
 
     /// <sup>[`sync`](crate::sync)</sup>
@@ -44,21 +57,205 @@ impl<V: MaybeVersioned> Network<V, ConnConf<V>> {
     /// Signed connections will process incoming and outgoing frames according to corresponding
     /// signing strategies.
     pub fn add_signed_connection(
-        mut self,
+        self,
         conn_conf: impl ConnectionBuilder<V> + 'static,
         signer: FrameSigner,
-    ) -> Network<V, ConnConf<V>> {
-        let node = Node::sync::<V>()
-            .connection(conn_conf)
-            .signer(signer)
-            .conf();
-        self.nodes.insert(UniqueId::new(), node);
+    ) -> ConnectionOptions<V, ConnConf<V>> {
+        self.add_node(
+            Node::sync::<V>()
+                .connection(conn_conf)
+                .signer(signer)
+                .conf(),
+        )
+    }
 
-        Network {
-            nodes: self.nodes,
-            ..self
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Creates a frame sender, that injects frames into a network connection with specified `ID`.
+    ///
+    /// Injected frames are sent directly to the connection, bypassing filters, routing, and
+    /// conversions of the network. Frames are sent as they are, without signing or other
+    /// processing. Frames injected into connections, that are not running at the moment, are
+    /// dropped.
+    ///
+    /// Injector should be created before network is passed to a node. It stays valid, when the
+    /// connection is restarted.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use maviola::prelude::*;
+    /// use maviola::sync::prelude::*;
+    /// # use maviola::dialects::minimal::messages::Heartbeat;
+    /// # let frame = Endpoint::v2(MavLinkId::new(1, 1)).next_frame(&Heartbeat::default()).unwrap();
+    ///
+    /// let autopilot = TcpClient::new("127.0.0.1:5760").unwrap();
+    /// let autopilot_id = autopilot.id();
+    ///
+    /// let network = Network::sync()
+    ///     .add_connection(autopilot)
+    ///     .add_connection(UdpServer::new("127.0.0.1:14550").unwrap());
+    /// let injector = network.injector(autopilot_id);
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .connection(network)
+    ///     .build().unwrap();
+    ///
+    /// // Send frame to autopilot only
+    /// injector.send_frame(&frame).unwrap();
+    /// ```
+    pub fn injector(&self, connection_id: ConnectionId) -> FrameSender<V, Proxy> {
+        let (sender, handler) = outgoing_channel(self.injectors.state());
+        let handler = Arc::new(Mutex::new(Some(handler)));
+
+        self.injectors.add(Box::new(move |state, targets| {
+            let handler = handler.clone();
+            spawn_io(move || pump_injected_frames(connection_id, &handler, &state, &targets));
+        }));
+
+        FrameSender::new(
+            sender,
+            Arc::new(FrameProcessor::default()),
+            None,
+            TrafficStats::default(),
+            None,
+            PeerLocations::default(),
+        )
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Captures frames passing through the network.
+    ///
+    /// Returns a blocking iterator over frames received or sent by network connections (see
+    /// [`TappedFrame`] for details). Tap is read-only and does not affect routing. Iterator
+    /// finishes, once network is stopped. Frames are dropped, when tap is not consumed fast
+    /// enough and [`NETWORK_TAP_CAPACITY`] frames are pending.
+    ///
+    /// Tap should be created before network is passed to a node.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use maviola::prelude::*;
+    /// use maviola::sync::prelude::*;
+    ///
+    /// let network = Network::sync()
+    ///     .add_connection(TcpClient::new("127.0.0.1:5760").unwrap())
+    ///     .add_connection(UdpServer::new("127.0.0.1:14550").unwrap());
+    /// let tap = network.tap();
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .connection(network)
+    ///     .build().unwrap();
+    ///
+    /// for tapped in tap {
+    ///     println!(
+    ///         "{:?} {:?}: message #{}",
+    ///         tapped.direction(),
+    ///         tapped.connection_id(),
+    ///         tapped.frame().message_id()
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// [`NETWORK_TAP_CAPACITY`]: crate::core::consts::NETWORK_TAP_CAPACITY
+    pub fn tap(&self) -> impl Iterator<Item = TappedFrame<V>> {
+        let (tx, rx) = mpsc::sync_channel(NETWORK_TAP_CAPACITY);
+
+        self.tap
+            .subscribe(Box::new(move |frame| match tx.try_send(frame.clone()) {
+                Ok(_) | Err(TrySendError::Full(_)) => true,
+                Err(TrySendError::Disconnected(_)) => false,
+            }));
+
+        rx.into_iter()
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionOptions<V, ConnConf<V>> {
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Adds connection to a network.
+    ///
+    /// See [`Network::add_connection`].
+    pub fn add_connection(
+        self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+    ) -> ConnectionOptions<V, ConnConf<V>> {
+        Network::from(self).add_connection(conn_conf)
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Adds a signed connection to a network.
+    ///
+    /// See [`Network::add_signed_connection`].
+    pub fn add_signed_connection(
+        self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+        signer: FrameSigner,
+    ) -> ConnectionOptions<V, ConnConf<V>> {
+        Network::from(self).add_signed_connection(conn_conf, signer)
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Creates a frame sender, that injects frames into a network connection with specified `ID`.
+    ///
+    /// See [`Network::injector`].
+    pub fn injector(&self, connection_id: ConnectionId) -> FrameSender<V, Proxy> {
+        self.network().injector(connection_id)
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Captures frames passing through the network.
+    ///
+    /// See [`Network::tap`].
+    pub fn tap(&self) -> impl Iterator<Item = TappedFrame<V>> {
+        self.network().tap()
+    }
+}
+
+impl<V: MaybeVersioned> NetworkHandle<V, ConnConf<V>> {
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Adds connection to a running network.
+    ///
+    /// See [`NetworkHandle::add_node`] for details.
+    pub fn add_connection(
+        &self,
+        conn_conf: impl ConnectionBuilder<V> + 'static,
+    ) -> Result<ConnectionId> {
+        self.add_node(Node::sync::<V>().connection(conn_conf).conf())
+    }
+}
+
+/// Passes frames of an injector to a connection until network `state` is closed.
+///
+/// Injector `handler` is taken from a shared slot and returned back, once network is stopped, so
+/// it can be reused, when network is started again.
+fn pump_injected_frames<V: MaybeVersioned>(
+    connection_id: ConnectionId,
+    slot: &Mutex<Option<OutgoingFrameHandler<V>>>,
+    state: &Closable,
+    targets: &InjectionTargets<V>,
+) {
+    let handler = match slot.lock() {
+        Ok(mut slot) => slot.take(),
+        Err(err) => err.into_inner().take(),
+    };
+    let handler = match handler {
+        Some(handler) => handler,
+        None => return,
+    };
+
+    while !state.is_closed() {
+        match handler.recv_timeout(NETWORK_POOLING_INTERVAL) {
+            Ok(frame) => targets.inject(connection_id, frame),
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Lagged(_)) => continue,
         }
     }
+
+    match slot.lock() {
+        Ok(mut slot) => *slot = Some(handler),
+        Err(err) => *err.into_inner() = Some(handler),
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -73,9 +270,14 @@ mod tests {
     use std::time::Duration;
 
     use crate::core::consts::SERVER_HANG_UP_TIMEOUT;
-    use crate::core::io::RetryStrategy;
+    use crate::core::io::{ConnectionDetails, RetryStrategy};
+    use crate::core::network::{
+        BandwidthLimit, ConnectionFilter, FailoverGroup, ForwardSuppression, HeartbeatToggle,
+        MirrorGroup, TelemetryPolicy, VersionBridge,
+    };
     use crate::core::utils::net::pick_unused_port;
     use crate::dialects::minimal::messages::Heartbeat;
+    use crate::error::ConfigDiagnostic;
 
     use crate::sync::prelude::*;
 
@@ -98,7 +300,7 @@ mod tests {
             .add_node(Node::sync::<V2>().connection(TcpServer::new(addr_1.as_str()).unwrap()))
             .add_connection(TcpServer::new(addr_2.as_str()).unwrap());
 
-        assert_eq!(network.nodes.len(), 2);
+        assert_eq!(network.network().nodes.len(), 2);
 
         let server = Node::sync::<V2>()
             .id(MavLinkId::new(1, 0))
@@ -118,6 +320,7 @@ mod tests {
             .connection(TcpClient::new(addr_2.as_str()).unwrap())
             .build()
             .unwrap();
+        wait();
 
         server.send(&Heartbeat::default()).unwrap();
 
@@ -136,6 +339,113 @@ mod tests {
         assert_eq!(frame.component_id(), 1);
     }
 
+    #[test]
+    fn network_hot_plugged_connections() {
+        let addr_1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let vehicle = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpServer::new(addr_2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let gcs = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 0))
+            .connection(Network::sync().add_connection(TcpServer::new(addr_1.as_str()).unwrap()))
+            .build()
+            .unwrap();
+        wait();
+
+        let network = gcs.network_handle().unwrap();
+        assert!(network.is_running());
+        assert_eq!(network.connections().len(), 1);
+
+        let conn_id = network
+            .add_connection(TcpClient::new(addr_2.as_str()).unwrap())
+            .unwrap();
+        wait();
+        assert_eq!(network.connections().len(), 2);
+
+        gcs.send(&Heartbeat::default()).unwrap();
+        vehicle.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        network.remove_connection(conn_id).unwrap();
+        wait();
+        assert_eq!(network.connections().len(), 1);
+        assert!(network.remove_connection(conn_id).is_err());
+
+        gcs.send(&Heartbeat::default()).unwrap();
+        assert!(vehicle.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        let events: Vec<_> = std::iter::from_fn(|| gcs.try_recv().ok()).collect();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::ConnectionAdded(info) if info.id() == conn_id)));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::ConnectionRemoved(info) if info.id() == conn_id)));
+
+        drop(gcs);
+        assert!(!network.is_running());
+        assert!(network
+            .add_connection(TcpClient::new(addr_2.as_str()).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn network_paused_connections() {
+        let addr_1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let vehicle_1 = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpServer::new(addr_1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let vehicle_2 = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpServer::new(addr_2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        let client_1 = TcpClient::new(addr_1.as_str()).unwrap();
+        let conn_id = client_1.info().id();
+        let gcs = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(client_1)
+                    .add_connection(TcpClient::new(addr_2.as_str()).unwrap()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        gcs.pause_connection(conn_id).unwrap();
+        assert!(!gcs.is_paused());
+
+        gcs.send(&Heartbeat::default()).unwrap();
+        vehicle_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(vehicle_1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        gcs.resume_connection(conn_id).unwrap();
+        vehicle_1.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        gcs.pause();
+        assert!(gcs.is_paused());
+        gcs.send(&Heartbeat::default()).unwrap();
+        assert!(vehicle_1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert!(vehicle_2.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        gcs.resume();
+        vehicle_1.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        vehicle_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        let unknown = vehicle_1.info().id();
+        assert!(gcs.pause_connection(unknown).is_err());
+    }
+
     #[test]
     fn network_reconnect() {
         let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
@@ -169,4 +479,1061 @@ mod tests {
         client.send(&Heartbeat::default()).unwrap();
         server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
     }
+
+    #[test]
+    fn network_standby_failover() {
+        let addr_primary = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_standby = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let primary = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 0))
+            .connection(TcpServer::new(addr_primary.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let standby = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 0))
+            .connection(TcpServer::new(addr_standby.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        let network = Network::sync()
+            .add_connection(TcpClient::new(addr_primary.as_str()).unwrap())
+            .add_connection(TcpClient::new(addr_standby.as_str()).unwrap())
+            .standby();
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        // Heartbeats are sent to standby connections as well
+        client.send(&Heartbeat::default()).unwrap();
+        primary.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        standby.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        // Frames received by standby connections are ignored
+        standby.send(&Heartbeat::default()).unwrap();
+        assert!(client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        primary.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        // Standby connection is activated once primary connection is down, the latter will be
+        // discovered upon sending frames to a closed server
+        drop(primary);
+        wait();
+        for _ in 0..2 {
+            client.send(&Heartbeat::default()).unwrap();
+            wait();
+        }
+
+        standby.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 3);
+    }
+
+    #[test]
+    fn network_failover_group() {
+        let addr_primary = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_backup = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let primary = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 0))
+            .connection(TcpServer::new(addr_primary.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let backup = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 0))
+            .connection(TcpServer::new(addr_backup.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        let links = FailoverGroup::new(WAIT_DURATION * 3);
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpClient::new(addr_primary.as_str()).unwrap())
+                    .failover(links.clone())
+                    .add_connection(TcpClient::new(addr_backup.as_str()).unwrap())
+                    .failover(links.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let connections = links.connections();
+        assert_eq!(links.active().unwrap().id(), connections[0].id());
+
+        // Frames received by inactive links are ignored
+        backup.send(&Heartbeat::default()).unwrap();
+        assert!(client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        primary.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        // Traffic is switched to backup link, once primary link becomes silent
+        for _ in 0..5 {
+            backup.send(&Heartbeat::default()).unwrap();
+            wait();
+        }
+        assert_eq!(links.active().unwrap().id(), connections[1].id());
+        assert!(std::iter::from_fn(|| client.try_recv().ok()).any(
+            |event| matches!(event, Event::LinkActivated(info) if info.id() == connections[1].id())
+        ));
+
+        // Traffic falls back to primary link, once it recovers
+        primary.send(&Heartbeat::default()).unwrap();
+        wait();
+        assert_eq!(links.active().unwrap().id(), connections[0].id());
+        assert!(std::iter::from_fn(|| client.try_recv().ok()).any(
+            |event| matches!(event, Event::LinkActivated(info) if info.id() == connections[0].id())
+        ));
+    }
+
+    #[test]
+    fn network_dedup() {
+        let addr_radio = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_wifi = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let ground = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_radio.as_str()).unwrap())
+                    .add_connection(TcpServer::new(addr_wifi.as_str()).unwrap())
+                    .dedup(Duration::from_secs(1)),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        // Vehicle sends each frame over both links
+        let vehicle = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpClient::new(addr_radio.as_str()).unwrap())
+                    .add_connection(TcpClient::new(addr_wifi.as_str()).unwrap()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        vehicle.send(&Heartbeat::default()).unwrap();
+        vehicle.send(&Heartbeat::default()).unwrap();
+
+        let (first, _) = ground.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        let (second, _) = ground.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_ne!(first.sequence(), second.sequence());
+        assert!(ground.recv_frame_timeout(RECV_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn network_mirror_group() {
+        let addr_lte = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_radio = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let links = MirrorGroup::new(Duration::from_secs(1));
+        let ground = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_lte.as_str()).unwrap())
+                    .mirror(links.clone())
+                    .add_connection(TcpServer::new(addr_radio.as_str()).unwrap())
+                    .mirror(links.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let vehicle = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpClient::new(addr_lte.as_str()).unwrap())
+                    .add_connection(TcpClient::new(addr_radio.as_str()).unwrap()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        // Frames received over both links are passed only once
+        vehicle.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = ground.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(ground.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Responses are sent over all links of the group
+        callback.respond(&frame).unwrap();
+        for _ in 0..2 {
+            let (response, _) = vehicle.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+            assert_eq!(response.sequence(), frame.sequence());
+        }
+        assert!(vehicle.recv_frame_timeout(RECV_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn network_filtered_connection() {
+        let addr_filtered = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_open = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr_filtered.as_str()).unwrap())
+            .filter(ConnectionFilter::new().block_system_ids([1, 3]))
+            .add_connection(TcpServer::new(addr_open.as_str()).unwrap());
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let filtered_client = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_filtered.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let open_client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_open.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Outgoing frames are filtered
+        server.send(&Heartbeat::default()).unwrap();
+        open_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(filtered_client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Incoming frames are filtered
+        filtered_client.send(&Heartbeat::default()).unwrap();
+        assert!(server.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Other connections are not affected
+        open_client.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+    }
+
+    #[test]
+    fn network_tap_and_injector() {
+        use crate::core::network::TapDirection;
+
+        let addr_a = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_b = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let server_a = TcpServer::new(addr_a.as_str()).unwrap();
+        let server_b = TcpServer::new(addr_b.as_str()).unwrap();
+        let (id_a, id_b) = (server_a.id(), server_b.id());
+
+        let network = Network::sync()
+            .add_connection(server_a)
+            .add_connection(server_b);
+        let mut tap = network.tap();
+        let injector = network.injector(id_a);
+
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let client_a = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_a.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_b = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_b.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Injected frames are sent only to the specified connection
+        let frame = Endpoint::v2(MavLinkId::new(9, 1))
+            .next_frame(&Heartbeat::default())
+            .unwrap();
+        injector.send_frame(&frame).unwrap();
+        let (frame, _) = client_a.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 9);
+        assert!(client_b.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert!(server.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        let tapped = tap.next().unwrap();
+        assert_eq!(tapped.direction(), TapDirection::Outgoing);
+        assert_eq!(tapped.connection_id(), id_a);
+        assert_eq!(tapped.frame().system_id(), 9);
+
+        // Incoming frames are tapped with connection, that received them
+        client_b.send(&Heartbeat::default()).unwrap();
+        server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        let tapped = tap.next().unwrap();
+        assert_eq!(tapped.direction(), TapDirection::Incoming);
+        assert_eq!(tapped.connection_id(), id_b);
+        assert_eq!(tapped.frame().system_id(), 3);
+
+        // Outgoing frames are tapped for each connection
+        server.send(&Heartbeat::default()).unwrap();
+        client_a.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        client_b.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        let mut connections: Vec<ConnectionId> = tap
+            .by_ref()
+            .take(2)
+            .inspect(|tapped| {
+                assert_eq!(tapped.direction(), TapDirection::Outgoing);
+                assert_eq!(tapped.frame().system_id(), 1);
+            })
+            .map(|tapped| tapped.connection_id())
+            .collect();
+        connections.sort_by_key(|id| *id == id_b);
+        assert_eq!(connections, vec![id_a, id_b]);
+
+        // Tap finishes, once network is stopped
+        drop(server);
+        wait();
+        assert!(tap.next().is_none());
+    }
+
+    #[test]
+    fn network_toggled_heartbeats() {
+        let addr_toggled = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_open = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let heartbeats = HeartbeatToggle::new(false);
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr_toggled.as_str()).unwrap())
+            .heartbeats(heartbeats.clone())
+            .add_connection(TcpServer::new(addr_open.as_str()).unwrap());
+        let mut server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .heartbeat_interval(Duration::from_millis(20))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let toggled_client = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_toggled.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let open_client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_open.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        server.activate().unwrap();
+        open_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(toggled_client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Heartbeats sent explicitly are not suppressed
+        server.send(&Heartbeat::default()).unwrap();
+        toggled_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        heartbeats.enable();
+        let (frame, _) = toggled_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 1);
+    }
+
+    #[test]
+    fn network_suppressed_forwarding() {
+        use crate::dialects::minimal::messages::ProtocolVersion;
+
+        let addr_suppressed = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_open = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr_suppressed.as_str()).unwrap())
+            .suppression(ForwardSuppression::heartbeats())
+            .add_connection(TcpServer::new(addr_open.as_str()).unwrap());
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let suppressed_client = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_suppressed.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let open_client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_open.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Forwarded heartbeats are not sent to the suppressed connection
+        open_client.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        assert!(suppressed_client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Heartbeats received from the suppressed connection are delivered and forwarded
+        suppressed_client.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 3);
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = open_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 3);
+
+        // Other forwarded messages are not suppressed
+        open_client.send(&ProtocolVersion::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = suppressed_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        // Heartbeats sent by the node itself are not suppressed
+        server.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = suppressed_client.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "scripting")]
+    fn network_scripted_connection() {
+        use crate::core::network::FrameScript;
+
+        let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let script = FrameScript::parse(
+            r#"
+            drop if incoming and system_id == 3
+            drop if outgoing and system_id == 1 and connection == "tcp_server"
+            "#,
+        )
+        .unwrap();
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr.as_str()).unwrap())
+            .filter(ConnectionFilter::new().script(script));
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let blocked_client = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let allowed_client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Outgoing frames are filtered
+        server.send(&Heartbeat::default()).unwrap();
+        assert!(allowed_client.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Incoming frames are filtered
+        blocked_client.send(&Heartbeat::default()).unwrap();
+        allowed_client.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert!(server.recv_frame_timeout(RECV_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn network_duplicate_endpoints_are_rejected() {
+        let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr.as_str()).unwrap())
+            .add_connection(TcpClient::new(addr.as_str()).unwrap())
+            .add_connection(TcpServer::new(addr.as_str()).unwrap());
+
+        match network.validate() {
+            Err(Error::Config(err)) => {
+                assert_eq!(err.diagnostics().len(), 1);
+                assert!(matches!(
+                    err.diagnostics()[0],
+                    ConfigDiagnostic::DuplicateEndpoint(ConnectionDetails::TcpServer { .. })
+                ));
+            }
+            result => panic!("unexpected validation result: {result:?}"),
+        }
+
+        let node = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(network)
+            .build();
+        assert!(matches!(node, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn network_reconnects_to_resolved_addr() {
+        use std::net::SocketAddr;
+        use std::sync::{Arc, Mutex};
+
+        use crate::core::io::Resolver;
+
+        #[derive(Clone, Debug)]
+        struct SwitchingResolver(Arc<Mutex<SocketAddr>>);
+
+        impl Resolver for SwitchingResolver {
+            fn resolve(&self, _: &str) -> Result<SocketAddr> {
+                Ok(*self.0.lock().unwrap())
+            }
+        }
+
+        let addr_old = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_new = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let server_old = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 0))
+            .connection(TcpServer::new(addr_old.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let server_new = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 0))
+            .connection(TcpServer::new(addr_new.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        let resolver = SwitchingResolver(Arc::new(Mutex::new(addr_old.parse().unwrap())));
+        let network = Network::sync()
+            .add_connection(
+                TcpClient::from_host_with_resolver("relay.example.com:5600", resolver.clone())
+                    .unwrap()
+                    .with_resolve_interval(RECONNECT_INTERVAL),
+            )
+            .retry(RetryStrategy::Always(RECONNECT_INTERVAL));
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        client.send(&Heartbeat::default()).unwrap();
+        server_old.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        // Host now points to a new server
+        *resolver.0.lock().unwrap() = addr_new.parse().unwrap();
+        wait();
+        wait();
+
+        client.send(&Heartbeat::default()).unwrap();
+        server_new.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(server_old.recv_frame_timeout(RECV_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn network_adaptive_connection() {
+        use crate::dialects::minimal::messages::ProtocolVersion;
+
+        let addr_adaptive = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let policy = TelemetryPolicy::new().with_rate(ProtocolVersion::message_id(), 2.0, 1.0);
+
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_adaptive.as_str()).unwrap())
+                    .telemetry(policy.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_adaptive.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Link degrades, rate is decreased to the minimum
+        policy.report_quality(0.0);
+        assert_eq!(policy.rate(ProtocolVersion::message_id()), Some(1.0));
+
+        for _ in 0..3 {
+            server.send(&ProtocolVersion::default()).unwrap();
+            server.send(&Heartbeat::default()).unwrap();
+        }
+
+        let mut received = Vec::new();
+        while let Ok((frame, _)) = client.recv_frame_timeout(RECV_TIMEOUT) {
+            received.push(frame.message_id());
+        }
+        let count = |id| received.iter().filter(|received| **received == id).count();
+        assert_eq!(count(ProtocolVersion::message_id()), 1);
+        assert_eq!(count(Heartbeat::message_id()), 3);
+    }
+
+    #[test]
+    fn network_limited_connection() {
+        let addr_limited = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let limit = BandwidthLimit::new().with_frames_per_sec(0.01, 2);
+
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_limited.as_str()).unwrap())
+                    .limit(limit.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_limited.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        for _ in 0..5 {
+            server.send(&Heartbeat::default()).unwrap();
+        }
+
+        let mut received = 0;
+        while client.recv_frame_timeout(RECV_TIMEOUT).is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, 2);
+        assert_eq!(limit.sent_frames(), 2);
+        assert_eq!(limit.dropped_frames(), 3);
+    }
+
+    #[test]
+    fn network_connection_with_several_options() {
+        let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let limit = BandwidthLimit::new().with_frames_per_sec(0.01, 2);
+
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr.as_str()).unwrap())
+                    .filter(ConnectionFilter::new().block_system_ids([3]))
+                    .limit(limit.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Outgoing frames are limited
+        for _ in 0..5 {
+            server.send(&Heartbeat::default()).unwrap();
+        }
+        let mut received = 0;
+        while client.recv_frame_timeout(RECV_TIMEOUT).is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, 2);
+        assert_eq!(limit.dropped_frames(), 3);
+
+        // Incoming frames are filtered
+        client.send(&Heartbeat::default()).unwrap();
+        assert!(server.recv_frame_timeout(RECV_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn network_bridged_connections() {
+        use crate::dialects::minimal::messages::ProtocolVersion;
+        use crate::dialects::Minimal;
+        use crate::protocol::MavLinkVersion;
+
+        let addr_v1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_v2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let bridge_v1 = VersionBridge::v1::<Minimal>();
+        let bridge_v2 = VersionBridge::v2::<Minimal>();
+
+        let server = Node::sync::<Versionless>()
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_v1.as_str()).unwrap())
+                    .bridge(bridge_v1.clone())
+                    .add_connection(TcpServer::new(addr_v2.as_str()).unwrap())
+                    .bridge(bridge_v2.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client_v1 = Node::sync::<V1>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_v1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_v2 = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_v2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // `MAVLink 1` frames are upgraded
+        client_v1.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = client_v2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert_eq!(frame.version(), MavLinkVersion::V2);
+
+        // `MAVLink 2` frames are downgraded
+        client_v2.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = client_v1.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 3);
+        assert_eq!(frame.version(), MavLinkVersion::V1);
+
+        // Messages, that can't be represented in `MAVLink 1`, are rejected
+        client_v2.send(&ProtocolVersion::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        assert!(client_v1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        assert_eq!(bridge_v2.converted(), 1);
+        assert_eq!(bridge_v1.converted(), 1);
+        assert_eq!(bridge_v1.rejected(), 1);
+    }
+
+    #[test]
+    fn network_pinned_connections() {
+        use crate::core::network::VersionPin;
+        use crate::protocol::MavLinkVersion;
+
+        let addr_v1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_v2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let pin_v1 = VersionPin::new(V1);
+
+        let server = Node::sync::<Versionless>()
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_v1.as_str()).unwrap())
+                    .pin(pin_v1.clone())
+                    .add_connection(TcpServer::new(addr_v2.as_str()).unwrap())
+                    .version(V2),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client_v1 = Node::sync::<V1>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_v1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let misconfigured = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr_v1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_v2 = Node::sync::<V2>()
+            .id(MavLinkId::new(4, 1))
+            .connection(TcpClient::new(addr_v2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Frames of the pinned version pass
+        client_v1.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.version(), MavLinkVersion::V1);
+
+        // Incoming frames of other versions are rejected
+        misconfigured.send(&Heartbeat::default()).unwrap();
+        assert!(server.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert_eq!(pin_v1.rejected(), 1);
+
+        // Outgoing frames of other versions are rejected
+        client_v2.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        assert!(client_v1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert!(misconfigured.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert_eq!(pin_v1.rejected(), 2);
+    }
+
+    #[test]
+    fn network_remapped_connections() {
+        use crate::protocol::IdRemapper;
+
+        let addr_a = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_b = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let server = Node::sync::<V2>()
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_a.as_str()).unwrap())
+                    .remapper(IdRemapper::new().incoming(1, 101).outgoing(1, 201))
+                    .add_connection(TcpServer::new(addr_b.as_str()).unwrap()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client_a = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_a.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_b = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_b.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        client_a.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 101);
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = client_b.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 101);
+
+        client_b.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 1);
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = client_a.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 201);
+        assert!(frame.decode::<crate::dialects::Minimal>().is_ok());
+    }
+
+    #[test]
+    fn network_resequenced_connections() {
+        use crate::core::network::Resequencer;
+
+        let addr_a = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_b = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_gcs = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let resequencer = Resequencer::new();
+
+        let server = Node::sync::<V2>()
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr_a.as_str()).unwrap())
+                    .add_connection(TcpServer::new(addr_b.as_str()).unwrap())
+                    .add_connection(TcpServer::new(addr_gcs.as_str()).unwrap())
+                    .resequencer(resequencer.clone()),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        // The same vehicle is connected through two links
+        let link_a = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_a.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let link_b = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_b.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let gcs = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 190))
+            .connection(TcpClient::new(addr_gcs.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        let mut sequences = Vec::new();
+        for link in [&link_a, &link_b, &link_a, &link_b] {
+            link.send(&Heartbeat::default()).unwrap();
+            let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+            callback.broadcast(&frame).unwrap();
+            let (frame, _) = gcs.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+            assert!(frame.decode::<crate::dialects::Minimal>().is_ok());
+            sequences.push(frame.sequence());
+        }
+
+        assert_eq!(sequences, vec![0, 1, 2, 3]);
+        assert_eq!(resequencer.resequenced(), 3);
+    }
+
+    #[test]
+    fn network_drops_stale_frames() {
+        let addr = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 0))
+            .connection(
+                Network::sync()
+                    .add_connection(TcpServer::new(addr.as_str()).unwrap())
+                    .max_frame_age(Heartbeat::message_id(), WAIT_DURATION / 2),
+            )
+            .build()
+            .unwrap();
+        wait();
+
+        let client_1 = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_2 = Node::sync::<V2>()
+            .id(MavLinkId::new(3, 1))
+            .connection(TcpClient::new(addr.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        // Frames forwarded with a delay are considered stale
+        client_1.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        wait();
+        callback.broadcast(&frame).unwrap();
+        assert!(client_2.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        client_1.send(&Heartbeat::default()).unwrap();
+        let (frame, callback) = server.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        callback.broadcast(&frame).unwrap();
+        let (frame, _) = client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        // Frames sent by the node itself are aged since submission
+        server.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn network_target_aware_routing() {
+        use crate::core::network::RoutingMode;
+        use crate::dialects::common::messages::ParamRequestList;
+
+        let addr_1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr_1.as_str()).unwrap())
+            .add_connection(TcpServer::new(addr_2.as_str()).unwrap())
+            .routing(RoutingMode::TargetAware);
+        let routing_table = network.routing_table();
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 190))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let client_1 = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_2 = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        client_1.send(&Heartbeat::default()).unwrap();
+        client_2.send(&Heartbeat::default()).unwrap();
+        wait();
+        assert_eq!(routing_table.routes().len(), 2);
+        assert_eq!(routing_table.connections_of(MavLinkId::new(2, 1)).len(), 1);
+
+        server
+            .send(&ParamRequestList {
+                target_system: 2,
+                target_component: 1,
+            })
+            .unwrap();
+
+        let (frame, _) = client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.message_id(), ParamRequestList::message_id());
+        assert!(client_1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Frames without targets are broadcast
+        server.send(&Heartbeat::default()).unwrap();
+        client_1.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+    }
+    #[test]
+    #[cfg(feature = "common")]
+    fn network_send_to_target() {
+        use crate::dialects::common::messages::ParamRequestList;
+        use crate::error::NodeError;
+
+        let addr_1 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+        let addr_2 = format!("127.0.0.1:{}", pick_unused_port().unwrap());
+
+        let network = Network::sync()
+            .add_connection(TcpServer::new(addr_1.as_str()).unwrap())
+            .add_connection(TcpServer::new(addr_2.as_str()).unwrap());
+        let server = Node::sync::<V2>()
+            .id(MavLinkId::new(255, 190))
+            .connection(network)
+            .build()
+            .unwrap();
+        wait();
+
+        let client_1 = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpClient::new(addr_1.as_str()).unwrap())
+            .build()
+            .unwrap();
+        let client_2 = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(addr_2.as_str()).unwrap())
+            .build()
+            .unwrap();
+        wait();
+
+        client_1.send(&Heartbeat::default()).unwrap();
+        client_2.send(&Heartbeat::default()).unwrap();
+        wait();
+
+        // Target fields are filled and frame is sent only where target was seen
+        server
+            .send_to(&ParamRequestList::default(), MavLinkId::new(2, 1))
+            .unwrap();
+        let (frame, _) = client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        let message = ParamRequestList::try_from(frame.payload()).unwrap();
+        assert_eq!(message.target_system, 2);
+        assert_eq!(message.target_component, 1);
+        assert!(client_1.recv_frame_timeout(RECV_TIMEOUT).is_err());
+
+        // Network in broadcast mode still broadcasts regular frames
+        server
+            .send(&ParamRequestList {
+                target_system: 2,
+                target_component: 1,
+            })
+            .unwrap();
+        client_1.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        client_2.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+
+        assert!(matches!(
+            server.send_to(&Heartbeat::default(), MavLinkId::new(2, 1)),
+            Err(Error::Node(NodeError::NotAddressed(0)))
+        ));
+    }
 }
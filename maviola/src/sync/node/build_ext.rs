@@ -3,16 +3,24 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+use crate::core::consts::DEFAULT_HEARTBEAT_INTERVAL;
 use crate::core::marker::{
     Edge, HasComponentId, HasSystemId, MaybeComponentId, MaybeConnConf, MaybeSystemId, NodeKind,
     Unset,
 };
+#[cfg(feature = "config")]
+use crate::core::network::Network;
+#[cfg(feature = "config")]
+use crate::core::node::{ConnectionConfig, NetworkConfig};
 use crate::core::node::{Node, NodeBuilder, NodeConf};
-use crate::core::utils::Guarded;
-use crate::sync::io::ConnectionBuilder;
+use crate::core::utils::{Guarded, ThreadSettings};
+use crate::sync::io::{ConnectionBuilder, IoPool};
 use crate::sync::marker::ConnConf;
 use crate::sync::node::{EdgeNode, ProxyNode, SyncApi};
 
+#[cfg(feature = "config")]
+use crate::error::{ConfigDiagnostic, ConfigError};
+
 use crate::prelude::*;
 
 impl NodeBuilder<Unset, Unset, Versionless, Unset, Unset> {
@@ -33,10 +41,33 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned> NodeBuilder<S, C,
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: PhantomData,
         }
@@ -60,14 +91,98 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned, CC: MaybeConnConf
             conn_conf: self.conn_conf,
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
             _api: PhantomData,
         }
     }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Set [`NodeConf::io_threads`].
+    ///
+    /// These settings will be applied to all threads, that read and write frames to the underlying
+    /// connection. Use high priority to make sure, that outgoing frames are written in time, even
+    /// when CPU is busy with other work.
+    ///
+    /// Settings take effect only when `thread_control` feature is enabled.
+    pub fn io_threads(self, settings: ThreadSettings) -> Self {
+        NodeBuilder {
+            io_threads: Some(settings),
+            ..self
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Set [`NodeConf::io_pool`].
+    ///
+    /// Channels of TCP and Unix socket connections will be handled by workers of the shared
+    /// [`IoPool`] instead of dedicated threads. Use it for servers with many clients, the number
+    /// of I/O threads will stay the same regardless of the number of clients.
+    ///
+    /// Settings of [`NodeBuilder::io_threads`] are applied to pool workers.
+    pub fn io_pool(self, pool: IoPool) -> Self {
+        NodeBuilder {
+            io_pool: Some(pool),
+            ..self
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Set [`NodeConf::handler_threads`].
+    ///
+    /// These settings will be applied to threads, that process incoming frames, track peers, and
+    /// emit heartbeats.
+    ///
+    /// Settings take effect only when `thread_control` feature is enabled.
+    pub fn handler_threads(self, settings: ThreadSettings) -> Self {
+        NodeBuilder {
+            handler_threads: Some(settings),
+            retry: self.retry,
+            ..self
+        }
+    }
+
+    /// <sup>`⍚` | [`sync`](crate::sync)</sup>
+    /// Set [`NodeConf::event_channel`].
+    ///
+    /// Node events will be delivered to receivers through channels of the specified
+    /// [`ChannelFlavor`]. Default flavor is [`StdChannel`].
+    ///
+    /// [`ChannelFlavor`]: crate::sync::utils::mpmc::ChannelFlavor
+    /// [`StdChannel`]: crate::sync::utils::mpmc::StdChannel
+    #[cfg(feature = "unstable")]
+    pub fn event_channel<F: crate::sync::utils::mpmc::ChannelFlavor>(self) -> Self {
+        NodeBuilder {
+            event_channel: crate::sync::node::EventChannel::new::<F>(),
+            ..self
+        }
+    }
 }
 
 impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned>
@@ -85,14 +200,61 @@ impl<S: MaybeSystemId, C: MaybeComponentId, V: MaybeVersioned>
             conn_conf: ConnConf::new(conn_conf),
             heartbeat_timeout: self.heartbeat_timeout,
             heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: self.config,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: self._version,
             _api: self._api,
         }
     }
+
+    /// <sup>[`sync`](crate::sync)</sup> <sup>`config`</sup>
+    /// Set [`NodeConf::connection`] from a configuration, this builder was created from by
+    /// [`NodeBuilder::from_config`].
+    ///
+    /// Network configuration becomes a [`Network`] with a node for each connection.
+    ///
+    /// Returns [`ConfigDiagnostic::MissingConfigValue`], if configuration doesn't define a
+    /// connection.
+    #[cfg(feature = "config")]
+    #[allow(clippy::type_complexity)]
+    pub fn connection_from_config(self) -> Result<NodeBuilder<S, C, V, ConnConf<V>, SyncApi<V>>> {
+        match self
+            .config
+            .as_ref()
+            .and_then(|config| config.connection.clone())
+        {
+            Some(ConnectionConfig::Url(spec)) => Ok(self.connection(spec)),
+            Some(ConnectionConfig::Network(config)) => Ok(self.connection(network(&config))),
+            None => {
+                Err(ConfigError::from(ConfigDiagnostic::MissingConfigValue("connection")).into())
+            }
+        }
+    }
 }
 
 impl<K: NodeKind, V: MaybeVersioned> NodeConf<K, V, ConnConf<V>> {
@@ -115,6 +277,7 @@ impl<V: MaybeVersioned> NodeBuilder<Unset, Unset, V, ConnConf<V>, SyncApi<V>> {
     /// <sup>[`sync`](crate::sync)</sup>
     /// Creates a [`ProxyNode`] with synchronous API.
     pub fn build(self) -> Result<ProxyNode<V>> {
+        self.validate()?;
         Node::try_from_conf(self.conf())
     }
 }
@@ -123,6 +286,7 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, ConnConf<V>,
     /// <sup>[`sync`](crate::sync)</sup>
     /// Creates an [`EdgeNode`] with synchronous API.
     pub fn build(self) -> Result<EdgeNode<V>> {
+        self.validate()?;
         Node::try_from_conf(self.conf())
     }
 }
@@ -136,9 +300,9 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, SyncA
     /// several nodes per connection representing different MAVLink components.
     ///
     /// The new edge node will inherit known dialects and frame processing settings from a "parent"
-    /// node. If [`signer`] and [`compat`] are not set explicitly, then they will be inherited as
-    /// well. All [custom processors](crate::docs::c3__custom_processing) from the "parent" node
-    /// will be added to the new one.
+    /// node. If [`signer`], [`compat`], and [`processing_order`] are not set explicitly, then they
+    /// will be inherited as well. All [custom processors](crate::docs::c3__custom_processing) from
+    /// the "parent" node will be added to the new one.
     ///
     /// **⚠** The [`heartbeat_timeout`] setting of a "parent" [`ProxyNode`] node will be ignored!
     ///
@@ -182,6 +346,7 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, SyncA
     ///
     /// [`signer`]: Self::signer
     /// [`compat`]: Self::compat
+    /// [`processing_order`]: Self::processing_order
     /// [`heartbeat_timeout`]: Self::heartbeat_timeout
     pub fn build_from(self, node: &ProxyNode<V>) -> EdgeNode<V> {
         let processor = Arc::new(self.reuse_processor(node.processor.as_ref()));
@@ -192,13 +357,41 @@ impl<V: MaybeVersioned> NodeBuilder<HasSystemId, HasComponentId, V, Unset, SyncA
                 self.system_id.0,
                 self.component_id.0,
             ))),
-            api: SyncApi::new(connection, processor.clone()),
+            api: SyncApi::new(
+                connection,
+                processor.clone(),
+                self.latency_stats.clone(),
+                self.egress_priorities.clone(),
+                self.handler_threads.clone(),
+                self.event_channel,
+                node.api.health_tracker().reuse(),
+            ),
             state: Default::default(),
             is_active: Guarded::from(node.api.share_state()),
             heartbeat_timeout: self.heartbeat_timeout,
-            heartbeat_interval: self.heartbeat_interval,
+            heartbeat_interval: self
+                .heartbeat_interval
+                .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL),
+            heartbeat: self.heartbeat,
+            shutdown_messages: self.shutdown_messages,
             processor: processor.clone(),
+            components: Default::default(),
             _version: node._version,
         }
     }
 }
+
+#[cfg(feature = "config")]
+fn network<V: MaybeVersioned>(config: &NetworkConfig) -> Network<V, ConnConf<V>> {
+    let mut network = Network::sync::<V>().stop_on_node_down(config.stop_on_node_down);
+    for spec in &config.connections {
+        network = network.add_connection(spec.clone()).into();
+    }
+    if let Some(retry) = config.retry {
+        network = network.retry(retry);
+    }
+    if let Some(window) = config.dedup {
+        network = network.dedup(window);
+    }
+    network
+}
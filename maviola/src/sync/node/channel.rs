@@ -0,0 +1,125 @@
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter};
+
+use crate::sync::node::Event;
+use crate::sync::utils::mpmc::{self, ChannelFlavor, StdChannel};
+
+use crate::prelude::*;
+
+type EventBus<V> = (mpmc::Sender<Event<V>>, mpmc::Receiver<Event<V>>);
+
+/// <sup>[`sync`](crate::sync)</sup>
+/// Channel implementation used by the event bus of a synchronous node.
+///
+/// By default, node events are delivered through [`mpsc`](std::sync::mpsc) channels. When
+/// `unstable` feature is enabled, another implementation can be plugged with
+/// [`NodeBuilder::event_channel`] by implementing
+/// [`ChannelFlavor`](crate::sync::utils::mpmc::ChannelFlavor). This allows to use channels from
+/// crates like `crossbeam-channel` or `flume`, that may have lower wakeup overhead under high
+/// frame rates.
+///
+/// [`NodeBuilder::event_channel`]: crate::core::node::NodeBuilder::event_channel
+#[derive(Clone, Copy)]
+pub struct EventChannel {
+    flavor: &'static str,
+    v1: fn() -> EventBus<V1>,
+    v2: fn() -> EventBus<V2>,
+    versionless: fn() -> EventBus<Versionless>,
+}
+
+impl EventChannel {
+    /// <sup>`⍚` | [`sync`](crate::sync)</sup>
+    /// Creates event channel based on the specified channel flavor.
+    #[cfg(feature = "unstable")]
+    pub fn new<F: ChannelFlavor>() -> Self {
+        Self::with_flavor::<F>()
+    }
+
+    /// Name of the channel flavor.
+    pub fn flavor(&self) -> &str {
+        self.flavor
+    }
+
+    pub(in crate::sync) fn channel<V: MaybeVersioned>(&self) -> EventBus<V> {
+        let bus: Box<dyn Any> = if TypeId::of::<V>() == TypeId::of::<V1>() {
+            Box::new((self.v1)())
+        } else if TypeId::of::<V>() == TypeId::of::<V2>() {
+            Box::new((self.v2)())
+        } else {
+            Box::new((self.versionless)())
+        };
+
+        // Protocol versions are sealed, so one of the branches above always matches
+        *bus.downcast::<EventBus<V>>().unwrap()
+    }
+
+    fn with_flavor<F: ChannelFlavor>() -> Self {
+        Self {
+            flavor: std::any::type_name::<F>(),
+            v1: mpmc::channel_with::<F, Event<V1>>,
+            v2: mpmc::channel_with::<F, Event<V2>>,
+            versionless: mpmc::channel_with::<F, Event<Versionless>>,
+        }
+    }
+}
+
+impl Default for EventChannel {
+    fn default() -> Self {
+        Self::with_flavor::<StdChannel>()
+    }
+}
+
+impl Debug for EventChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventChannel").field(&self.flavor).finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod channel_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::protocol::Peer;
+    use crate::sync::utils::mpmc::{ChannelReceiver, ChannelSender};
+
+    use super::*;
+
+    static CHANNELS_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl ChannelFlavor for Counted {
+        fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>)
+        {
+            CHANNELS_CREATED.fetch_add(1, Ordering::SeqCst);
+            StdChannel::unbounded()
+        }
+    }
+
+    fn assert_delivers<V: MaybeVersioned>(channel: &EventChannel) {
+        let (tx, rx) = channel.channel::<V>();
+        tx.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_millis(500)).unwrap(),
+            Event::NewPeer(_)
+        ));
+    }
+
+    #[test]
+    fn event_channel_uses_flavor_for_all_versions() {
+        let channel = EventChannel::with_flavor::<Counted>();
+        assert!(channel.flavor().ends_with("Counted"));
+
+        assert_delivers::<V1>(&channel);
+        assert_delivers::<V2>(&channel);
+        assert_delivers::<Versionless>(&channel);
+
+        // Each bus has an input channel and a channel per receiver
+        assert_eq!(CHANNELS_CREATED.load(Ordering::SeqCst), 6);
+    }
+}
@@ -17,11 +17,34 @@ impl<V: MaybeVersioned> NodeConf<Edge<V>, V, ConnConf<V>> {
             component_id: HasComponentId(self.kind.endpoint.component_id()),
             conn_conf: self.connection_conf,
             heartbeat_timeout: self.heartbeat_timeout,
-            heartbeat_interval: self.heartbeat_timeout,
+            heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: None,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
             _api: PhantomData,
         }
@@ -37,11 +60,34 @@ impl<V: MaybeVersioned> NodeConf<Proxy, V, ConnConf<V>> {
             component_id: Unset,
             conn_conf: self.connection_conf,
             heartbeat_timeout: self.heartbeat_timeout,
-            heartbeat_interval: self.heartbeat_timeout,
+            heartbeat_interval: self.heartbeat_interval,
+            heartbeat: self.heartbeat,
             dialects: self.dialects,
             signer: self.signer,
             compat: self.compat,
+            processing_order: self.processing_order,
             processors: self.processors,
+            middleware: self.middleware,
+            id_remapper: self.id_remapper,
+            anomaly_detector: self.anomaly_detector,
+            latency_stats: self.latency_stats,
+            rate_governor: self.rate_governor,
+            link_quality: self.link_quality,
+            black_box: self.black_box,
+            keepalive: self.keepalive,
+            egress_priorities: self.egress_priorities,
+            #[cfg(feature = "config")]
+            config: None,
+            io_threads: self.io_threads,
+            handler_threads: self.handler_threads,
+            #[cfg(feature = "sync")]
+            event_channel: self.event_channel,
+            #[cfg(feature = "sync")]
+            io_pool: self.io_pool,
+            retry: self.retry,
+            outbound_queue: self.outbound_queue,
+            shutdown_messages: self.shutdown_messages,
+            channel_events: self.channel_events,
             _version: PhantomData,
             _api: PhantomData,
         }
@@ -1,7 +1,12 @@
+use std::net::SocketAddr;
 use std::thread;
 
-use crate::error::{FrameError, TryRecvError};
-use crate::protocol::Peer;
+use crate::core::io::{ChannelInfo, ConnectionInfo};
+use crate::core::node::{CustomEvent, StatsReport, ValidationReport};
+use crate::error::TryRecvError;
+use crate::protocol::{
+    Anomaly, ComponentId, ComponentKind, LinkQuality, Peer, RemoteSystem, SystemId,
+};
 use crate::sync::consts::EVENTS_RECV_POOLING_INTERVAL;
 use crate::sync::node::{Callback, EventReceiver};
 
@@ -15,10 +20,111 @@ pub enum Event<V: MaybeVersioned> {
     NewPeer(Peer),
     /// A [`Peer`] was lost due to the timeout.
     PeerLost(Peer),
+    /// Quality of the link to a [`Peer`] dropped below the threshold of [`LinkQualityMonitor`].
+    ///
+    /// Emitted once per degradation episode. If link quality returns to normal, the next degradation
+    /// will be reported again.
+    ///
+    /// [`LinkQualityMonitor`]: crate::protocol::LinkQualityMonitor
+    PeerDegraded(Peer, LinkQuality),
+    /// New [`RemoteSystem`] appeared or its state (mode, status, autopilot) has changed.
+    ///
+    /// Contains a snapshot of the system state. Current state is available through
+    /// [`Node::system`](crate::core::node::Node::system). Emitted only when enabled by
+    /// [`Node::system_events`](crate::core::node::Node::system_events).
+    SystemChanged(RemoteSystem),
+    /// New component of a remote system sent its first heartbeat.
+    ///
+    /// Unlike [`Event::NewPeer`], carries the [`ComponentKind`] derived from the heartbeat.
+    /// Components of a system are available through
+    /// [`Node::components`](crate::core::node::Node::components). Emitted only when enabled by
+    /// [`Node::system_events`](crate::core::node::Node::system_events).
+    NewComponent {
+        /// MAVLink system `ID`.
+        system_id: SystemId,
+        /// MAVLink component `ID`.
+        component_id: ComponentId,
+        /// Kind of the component.
+        component_kind: ComponentKind,
+    },
+    /// Node connection was lost due to failure of the underlying transport.
+    ///
+    /// If [`NodeConf::retry`] strategy is set, node attempts to restore the connection and emits
+    /// [`Event::ConnectionRestored`] once succeeded.
+    ///
+    /// [`NodeConf::retry`]: crate::core::node::NodeConf::retry
+    ConnectionLost(ConnectionInfo),
+    /// Node connection was restored after failure according to the [`NodeConf::retry`] strategy.
+    ///
+    /// [`NodeConf::retry`]: crate::core::node::NodeConf::retry
+    ConnectionRestored(ConnectionInfo),
+    /// No frames were received by node connection for the duration specified by
+    /// [`Keepalive::stale_after`].
+    ///
+    /// Emitted once per staleness episode. Once frames are received again, node emits
+    /// [`Event::ConnectionAlive`].
+    ///
+    /// [`Keepalive::stale_after`]: crate::core::node::Keepalive::stale_after
+    ConnectionStale(ConnectionInfo),
+    /// Node connection received frames after it was reported by [`Event::ConnectionStale`].
+    ConnectionAlive(ConnectionInfo),
+    /// Connection was added to a running [`Network`] by a [`NetworkHandle`].
+    ///
+    /// [`NetworkHandle`]: crate::core::network::NetworkHandle
+    ConnectionAdded(ConnectionInfo),
+    /// Connection was removed from a running [`Network`] by a [`NetworkHandle`] or was given up
+    /// by the network after all repair attempts have failed.
+    ///
+    /// [`NetworkHandle`]: crate::core::network::NetworkHandle
+    ConnectionRemoved(ConnectionInfo),
+    /// Connection became the active link of a [`FailoverGroup`] within a [`Network`].
+    ///
+    /// Emitted, once the first link is activated and on each switchover.
+    ///
+    /// [`FailoverGroup`]: crate::core::network::FailoverGroup
+    LinkActivated(ConnectionInfo),
+    /// New channel was opened within node connection, for example, a client connected to
+    /// a [`TcpServer`](crate::core::io::TcpServer).
+    ///
+    /// Emitted only when enabled by
+    /// [`Node::channel_events`](crate::core::node::Node::channel_events).
+    ChannelOpened(ChannelInfo),
+    /// Channel within node connection was closed, for example, a client disconnected from
+    /// a [`TcpServer`](crate::core::io::TcpServer).
+    ///
+    /// Emitted only when enabled by
+    /// [`Node::channel_events`](crate::core::node::Node::channel_events).
+    ChannelClosed(ChannelInfo),
     /// New [`Frame`] received.
+    ///
+    /// Arrival time and channel of the frame are available as [`Callback::meta`].
     Frame(Frame<V>, Callback<V>),
     /// New [`Frame`] received, but it hasn't passed validation.
-    Invalid(Frame<V>, FrameError, Callback<V>),
+    ///
+    /// The [`ValidationReport`] tells, why frame was rejected.
+    Invalid(Frame<V>, ValidationReport, Callback<V>),
+    /// Channel dropped bytes, that can't be parsed as a [`Frame`].
+    ///
+    /// Raw bytes are available as [`ValidationReport::raw_bytes`].
+    Malformed(ChannelInfo, ValidationReport),
+    /// Connection dropped data from a sender with the specified address, since sender is not
+    /// allowed.
+    ///
+    /// Emitted by a [`UdpServer`](crate::core::io::UdpServer) locked to a particular sender or
+    /// restricted by an allowlist. Each rejected sender is reported once.
+    SenderRejected(ConnectionInfo, SocketAddr),
+    /// Suspicious peer behavior detected by [`AnomalyDetector`].
+    ///
+    /// [`AnomalyDetector`]: crate::protocol::AnomalyDetector
+    Anomaly(Anomaly),
+    /// Periodic summary of node traffic enabled by [`Node::stats_report`].
+    ///
+    /// [`Node::stats_report`]: crate::core::node::Node::stats_report
+    StatsReport(StatsReport),
+    /// Application-defined event published by [`Node::emit`].
+    ///
+    /// [`Node::emit`]: crate::core::node::Node::emit
+    Custom(CustomEvent),
 }
 
 pub(crate) struct EventsIterator<V: MaybeVersioned> {
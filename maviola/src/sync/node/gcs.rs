@@ -0,0 +1,133 @@
+use std::time::Instant;
+
+use crate::core::msrv::gcs::{
+    GcsOperation, GcsSettings, GcsStep, ParamRead, ParamWrite, VehicleCommand,
+};
+use crate::core::msrv::params::ParamValue;
+use crate::dialects::common::enums::MavCmd;
+use crate::dialects::common::messages::MissionItemInt;
+use crate::error::RecvTimeoutError;
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+/// <sup>[`sync`](crate::sync) | `msrv-utils-gcs`</sup>
+/// Blocking [ground control station](crate::core::msrv::gcs) client bound to an edge node and
+/// a vehicle.
+///
+/// Created by [`Node::gcs_client`]. Combines vehicle commands, parameter protocol, and mission
+/// protocol in a single API. Each method blocks until the vehicle responds. Only frames received
+/// after an operation is started are passed to it.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::msrv::gcs::GcsSettings;
+/// use maviola::prelude::*;
+/// use maviola::asnc::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+///
+/// let gcs = node.gcs_client(GcsSettings::new(MavLinkId::new(1, 1)));
+/// let sysid = gcs.read_param("MAV_SYS_ID").unwrap();
+/// gcs.arm().unwrap();
+/// ```
+pub struct GcsClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: GcsSettings,
+}
+
+impl<'a, V: Versioned> GcsClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: GcsSettings) -> Self {
+        Self { node, settings }
+    }
+
+    /// Settings of ground control station operations.
+    pub fn settings(&self) -> &GcsSettings {
+        &self.settings
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Sends an arbitrary `command` with `params` to the vehicle and waits for acknowledgement.
+    pub fn command(&self, command: MavCmd, params: [f32; 7]) -> Result<()> {
+        self.run(VehicleCommand::new(self.settings, command, params))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Arms the vehicle.
+    ///
+    /// Use [`VehicleCommand::arm_disarm`] with [`run`](Self::run) to bypass pre-arm checks.
+    pub fn arm(&self) -> Result<()> {
+        self.run(VehicleCommand::arm(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Disarms the vehicle.
+    pub fn disarm(&self) -> Result<()> {
+        self.run(VehicleCommand::disarm(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Reads parameter with specified `name`.
+    pub fn read_param(&self, name: &str) -> Result<ParamValue> {
+        self.run(ParamRead::new(self.settings, name))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Sets parameter with specified `name` to `value`.
+    ///
+    /// Returns the value reported by the vehicle, which may differ from the requested one.
+    pub fn write_param(&self, name: &str, value: impl Into<ParamValue>) -> Result<ParamValue> {
+        self.run(ParamWrite::new(self.settings, name, value.into()))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Uploads mission `items` to the vehicle.
+    ///
+    /// See [`Node::upload_mission`] for details.
+    pub fn upload_mission(&self, items: Vec<MissionItemInt>) -> Result<()> {
+        self.node.upload_mission(self.settings.mission(), items)
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Downloads mission items from the vehicle.
+    ///
+    /// See [`Node::download_mission`] for details.
+    pub fn download_mission(&self) -> Result<Vec<MissionItemInt>> {
+        self.node.download_mission(self.settings.mission())
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Runs a ground control station `operation` over node connection.
+    ///
+    /// Operation uses its own settings instead of the client ones.
+    pub fn run<T: GcsOperation>(&self, mut operation: T) -> Result<T::Output> {
+        let receiver = self.node.receiver().clone();
+        let mut step = operation.start(Instant::now());
+
+        loop {
+            match step {
+                GcsStep::Wait => {}
+                GcsStep::Send(message) => message.send(self.node)?,
+                GcsStep::Finished(result) => return result,
+            }
+
+            let timeout = operation
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout) {
+                Ok((frame, _)) => match operation.handle(&frame, Instant::now()) {
+                    GcsStep::Wait => operation.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    operation.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::Instant;
+
+use crate::core::msrv::gimbal::{
+    GimbalAttitude, GimbalCommand, GimbalDeviceInfo, GimbalDeviceInformationRequest,
+    GimbalDiscovery, GimbalInfo, GimbalManagerInfo, GimbalManagerInformationRequest,
+    GimbalOperation, GimbalSetpoint, GimbalSettings, GimbalStep,
+};
+use crate::dialects::common::enums::GimbalManagerFlags;
+use crate::error::{GimbalError, RecvTimeoutError};
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+/// <sup>[`sync`](crate::sync) | `msrv-utils-gimbal`</sup>
+/// Blocking [gimbal protocol](crate::core::msrv::gimbal) client bound to an edge node and a gimbal.
+///
+/// Created by [`Node::gimbal_client`]. Each command method blocks until the gimbal manager
+/// acknowledges the command and sends the requested message, if any. Only frames received after
+/// an operation is started are passed to it.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use maviola::core::msrv::gimbal::GimbalSettings;
+/// use maviola::prelude::*;
+/// use maviola::sync::prelude::*;
+///
+/// let node = Node::sync::<V2>()
+///     .id(MavLinkId::new(255, 190))
+///     .connection(TcpClient::new("127.0.0.1:5600").unwrap())
+///     .build().unwrap();
+///
+/// let gimbal = node.gimbal_client(GimbalSettings::new(MavLinkId::new(1, 1)));
+/// gimbal.take_control().unwrap();
+/// gimbal.set_pitch_yaw(-45.0, 0.0).unwrap();
+/// ```
+pub struct GimbalClient<'a, V: Versioned> {
+    node: &'a EdgeNode<V>,
+    settings: GimbalSettings,
+}
+
+impl<'a, V: Versioned> GimbalClient<'a, V> {
+    pub(super) fn new(node: &'a EdgeNode<V>, settings: GimbalSettings) -> Self {
+        Self { node, settings }
+    }
+
+    /// Settings of gimbal operations.
+    pub fn settings(&self) -> &GimbalSettings {
+        &self.settings
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Discovers gimbal device of the gimbal manager.
+    ///
+    /// See [`GimbalDiscovery`] for details.
+    pub fn discover(&self) -> Result<GimbalInfo> {
+        self.run(GimbalDiscovery::new(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Requests gimbal manager information.
+    pub fn manager_information(&self) -> Result<GimbalManagerInfo> {
+        self.run(GimbalManagerInformationRequest::new(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Requests gimbal device information.
+    pub fn device_information(&self) -> Result<GimbalDeviceInfo> {
+        self.run(GimbalDeviceInformationRequest::new(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Takes primary control over the gimbal on behalf of the node.
+    pub fn take_control(&self) -> Result<()> {
+        let id = MavLinkId::new(self.node.system_id(), self.node.component_id());
+        self.run(GimbalCommand::take_control(self.settings, id))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Releases primary control over the gimbal.
+    pub fn release_control(&self) -> Result<()> {
+        self.run(GimbalCommand::release_control(self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Points the gimbal to `pitch` and `yaw` angles in degrees. Yaw is relative to vehicle
+    /// heading.
+    ///
+    /// Use [`GimbalCommand::pitch_yaw`] with [`run`](Self::run) to control angular rates and
+    /// yaw lock.
+    pub fn set_pitch_yaw(&self, pitch: f32, yaw: f32) -> Result<()> {
+        self.run(GimbalCommand::pitch_yaw(
+            self.settings,
+            pitch,
+            yaw,
+            f32::NAN,
+            f32::NAN,
+            GimbalManagerFlags::default(),
+        ))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Sends an attitude `setpoint` to the gimbal manager.
+    ///
+    /// Setpoints are not acknowledged. They should be streamed at a constant rate while the
+    /// gimbal is controlled this way.
+    pub fn set_attitude(&self, setpoint: &GimbalSetpoint) -> Result<()> {
+        self.node.send(&setpoint.message(&self.settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Waits for the next gimbal attitude report within the settings timeout.
+    pub fn attitude(&self) -> Result<GimbalAttitude> {
+        let mut attitude = None;
+        let result = self.node.recv_matching(
+            |frame| {
+                attitude = GimbalAttitude::from_frame(frame, &self.settings);
+                attitude.is_some()
+            },
+            self.settings.timeout(),
+        );
+
+        match (result, attitude) {
+            (Ok(_), Some(attitude)) => Ok(attitude),
+            (Err(err @ RecvTimeoutError::Disconnected), _) => Err(err.into()),
+            _ => Err(GimbalError::Timeout.into()),
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Subscribes to gimbal attitude reports.
+    ///
+    /// Blocks while the node is active.
+    pub fn attitudes(&self) -> impl Iterator<Item = GimbalAttitude> + 'a {
+        let settings = self.settings;
+        self.node
+            .frames()
+            .filter_map(move |(frame, _)| GimbalAttitude::from_frame(&frame, &settings))
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Runs a gimbal `operation` over node connection.
+    ///
+    /// Operation uses its own settings instead of the client ones.
+    pub fn run<T: GimbalOperation>(&self, mut operation: T) -> Result<T::Output> {
+        let receiver = self.node.receiver().clone();
+        let mut step = operation.start(Instant::now());
+
+        loop {
+            match step {
+                GimbalStep::Wait => {}
+                GimbalStep::Send(command) => self.node.send(&command)?,
+                GimbalStep::Finished(result) => return result,
+            }
+
+            let timeout = operation
+                .deadline()
+                .saturating_duration_since(Instant::now());
+            step = match receiver.recv_frame_timeout(timeout) {
+                Ok((frame, _)) => match operation.handle(&frame, Instant::now()) {
+                    GimbalStep::Wait => operation.check(Instant::now()),
+                    step => step,
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {
+                    operation.check(Instant::now())
+                }
+                Err(err @ RecvTimeoutError::Disconnected) => return Err(err.into()),
+            };
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+use crate::core::io::{ConnectionEvent, ConnectionInfo};
+use crate::core::node::{ConnectionStatus, HealthTracker};
+use crate::core::utils::{Closable, ThreadSettings};
+use crate::protocol::AnomalyTracker;
+use crate::sync::consts::CONN_EVENTS_POOLING_INTERVAL;
+use crate::sync::node::api::EventSender;
+use crate::sync::node::watch::WatchSender;
+use crate::sync::node::Event;
+use crate::sync::utils::spawn_with;
+
+use crate::prelude::*;
+
+/// Reports lifecycle events of a node connection as node [`Event`]s.
+pub(in crate::sync::node) struct ConnectionEventsHandler<V: MaybeVersioned> {
+    pub(in crate::sync::node) info: ConnectionInfo,
+    pub(in crate::sync::node) receiver: mpsc::Receiver<ConnectionEvent>,
+    pub(in crate::sync::node) status_watch: WatchSender<ConnectionStatus>,
+    pub(in crate::sync::node) event_sender: EventSender<V>,
+    pub(in crate::sync::node) health: HealthTracker,
+    pub(in crate::sync::node) anomalies: Option<AnomalyTracker>,
+    pub(in crate::sync::node) channel_events: Arc<AtomicBool>,
+}
+
+impl<V: MaybeVersioned> ConnectionEventsHandler<V> {
+    pub(in crate::sync::node) fn spawn(self, state: Closable, threads: Option<&ThreadSettings>) {
+        spawn_with(threads, move || {
+            let info = &self.info;
+            let mut lost = false;
+
+            loop {
+                // Events sent right before connection was closed should still be reported
+                let is_closed = state.is_closed();
+
+                let event = match self.receiver.recv_timeout(CONN_EVENTS_POOLING_INTERVAL) {
+                    Ok(event) => event,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if is_closed {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+
+                self.status_watch
+                    .send_if_modified(|status| status.apply(&event));
+
+                let event = match event {
+                    ConnectionEvent::ChannelOpened(channel) => {
+                        if !self.channel_events.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        Event::ChannelOpened(channel)
+                    }
+                    ConnectionEvent::ChannelClosed(channel) => {
+                        self.health.forget_channel(channel.id());
+                        if let Some(anomalies) = self.anomalies.as_ref() {
+                            anomalies.forget_channel(channel.id());
+                        }
+                        if !self.channel_events.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        Event::ChannelClosed(channel)
+                    }
+                    ConnectionEvent::Lost if lost => continue,
+                    ConnectionEvent::Lost => {
+                        lost = true;
+                        Event::ConnectionLost(info.clone())
+                    }
+                    ConnectionEvent::Restored => {
+                        lost = false;
+                        Event::ConnectionRestored(info.clone())
+                    }
+                    ConnectionEvent::Malformed(channel, report) => {
+                        Event::Malformed(channel, report)
+                    }
+                    ConnectionEvent::ConnectionAdded(info) => Event::ConnectionAdded(info),
+                    ConnectionEvent::ConnectionRemoved(info) => Event::ConnectionRemoved(info),
+                    ConnectionEvent::LinkActivated(info) => Event::LinkActivated(info),
+                    ConnectionEvent::SenderRejected(info, addr) => {
+                        Event::SenderRejected(info, addr)
+                    }
+                };
+
+                if let Err(err) = self.event_sender.send(event) {
+                    log::trace!("[{info:?}] failed to report connection event: {err:?}");
+                    break;
+                }
+            }
+
+            self.status_watch.send_if_modified(ConnectionStatus::close);
+            log::trace!("[{info:?}] connection events handler stopped");
+        });
+    }
+}
@@ -4,8 +4,11 @@ use std::time::Duration;
 
 use crate::core::io::ConnectionInfo;
 use crate::core::marker::Proxy;
-use crate::core::utils::{make_heartbeat_message, Guarded, SharedCloser, Switch};
+use crate::core::node::HealthTracker;
+use crate::core::utils::{make_heartbeat_message, Guarded, SharedCloser, Switch, ThreadSettings};
+use crate::dialects::minimal::messages::Heartbeat;
 use crate::protocol::DialectVersion;
+use crate::sync::utils::spawn_with;
 
 use crate::prelude::*;
 use crate::sync::prelude::*;
@@ -15,26 +18,35 @@ pub(in crate::sync::node) struct HeartbeatEmitter<V: Versioned> {
     pub(in crate::sync::node) endpoint: Endpoint<V>,
     pub(in crate::sync::node) interval: Duration,
     pub(in crate::sync::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::sync::node) heartbeat: Heartbeat,
     pub(in crate::sync::node) dialect_version: Option<DialectVersion>,
+    pub(in crate::sync::node) health: Option<HealthTracker>,
     pub(in crate::sync::node) _version: PhantomData<V>,
 }
 
 impl<V: Versioned> HeartbeatEmitter<V> {
-    pub(in crate::sync::node) fn spawn(self, mut is_active: Guarded<SharedCloser, Switch>) {
-        let heartbeat_message = make_heartbeat_message(self.dialect_version);
-
-        thread::spawn(move || {
+    pub(in crate::sync::node) fn spawn(
+        self,
+        mut is_active: Guarded<SharedCloser, Switch>,
+        threads: Option<&ThreadSettings>,
+    ) {
+        let heartbeat_message = make_heartbeat_message(&self.heartbeat, self.dialect_version);
+
+        spawn_with(threads, move || {
             let info = &self.info;
 
             while is_active.is() {
                 let frame = self.endpoint.next_frame(&heartbeat_message).unwrap();
 
                 log::trace!("[{info:?}] broadcasting heartbeat");
-                if let Err(err) = self.sender.send_frame(&frame) {
+                if let Err(err) = self.sender.send_heartbeat(&frame) {
                     log::trace!("[{info:?}] heartbeat can't be broadcast: {err:?}");
                     is_active.set(false);
                     break;
                 }
+                if let Some(health) = &self.health {
+                    health.record_heartbeat();
+                }
 
                 thread::sleep(self.interval);
             }
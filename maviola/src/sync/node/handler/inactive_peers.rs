@@ -4,23 +4,29 @@ use std::thread;
 use std::time::{Duration, SystemTime};
 
 use crate::core::io::ConnectionInfo;
-use crate::core::utils::Closable;
-use crate::protocol::Peer;
+use crate::core::node::{peer_list, PeerLocations};
+use crate::core::utils::{Closable, ThreadSettings};
+use crate::protocol::{Peer, SystemRegistry};
 use crate::sync::node::api::EventSender;
+use crate::sync::node::watch::WatchSender;
 use crate::sync::node::Event;
+use crate::sync::utils::spawn_with;
 
 use crate::prelude::*;
 
 pub(in crate::sync::node) struct InactivePeersHandler<V: MaybeVersioned> {
     pub(in crate::sync::node) info: ConnectionInfo,
     pub(in crate::sync::node) peers: Arc<RwLock<HashMap<MavLinkId, Peer>>>,
+    pub(in crate::sync::node) locations: PeerLocations,
+    pub(in crate::sync::node) peers_watch: WatchSender<Vec<Peer>>,
+    pub(in crate::sync::node) systems: SystemRegistry,
     pub(in crate::sync::node) timeout: Duration,
     pub(in crate::sync::node) event_sender: EventSender<V>,
 }
 
 impl<V: MaybeVersioned> InactivePeersHandler<V> {
-    pub(in crate::sync::node) fn spawn(self, state: Closable) {
-        thread::spawn(move || {
+    pub(in crate::sync::node) fn spawn(self, state: Closable, threads: Option<&ThreadSettings>) {
+        spawn_with(threads, move || {
             while !state.is_closed() {
                 thread::sleep(self.timeout);
 
@@ -67,12 +73,22 @@ impl<V: MaybeVersioned> InactivePeersHandler<V> {
 
         match self.peers.write() {
             Ok(mut peers) => {
-                for id in inactive_peers {
-                    if let Some(peer) = peers.remove(&id) {
-                        if let Err(err) = self.event_sender.send(Event::PeerLost(peer)) {
-                            log::trace!("[{info:?}] failed to report lost peer event: {err:?}");
-                            return Err(Error::from(err));
-                        }
+                let lost_peers: Vec<Peer> = inactive_peers
+                    .iter()
+                    .filter_map(|id| peers.remove(id))
+                    .collect();
+                if !lost_peers.is_empty() {
+                    self.peers_watch.send(peer_list(&peers));
+                }
+                self.systems.handle_lost_peers(&lost_peers);
+                for peer in &lost_peers {
+                    self.locations.forget(peer.id);
+                }
+
+                for peer in lost_peers {
+                    if let Err(err) = self.event_sender.send(Event::PeerLost(peer)) {
+                        log::trace!("[{info:?}] failed to report lost peer event: {err:?}");
+                        return Err(Error::from(err));
                     }
                 }
             }
@@ -90,8 +106,13 @@ impl<V: MaybeVersioned> InactivePeersHandler<V> {
             for peer in peers.values() {
                 let _ = self.event_sender.send(Event::PeerLost(peer.clone()));
             }
-            peers.clear();
+            if !peers.is_empty() {
+                peers.clear();
+                self.peers_watch.send(Vec::new());
+            }
         }
+        self.systems.clear();
+        self.locations.clear();
         log::trace!("[{:?}] inactive peers handler stopped", self.info);
     }
 }
@@ -1,17 +1,23 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use std::thread;
+use std::time::Instant;
 
 use crate::core::consts::INCOMING_FRAMES_POOLING_INTERVAL;
-use crate::core::io::ConnectionInfo;
+use crate::core::io::{ChannelInfo, ConnectionInfo};
 use crate::core::marker::Proxy;
-use crate::core::utils::Closable;
+use crate::core::node::{peer_list, HealthTracker, PeerLocations, TrafficStats};
+use crate::core::utils::{trace, Closable, ThreadSettings};
+use crate::dialects::minimal::messages::Heartbeat;
 use crate::dialects::Minimal;
 use crate::error::RecvTimeoutError;
-use crate::protocol::Peer;
+use crate::protocol::{
+    AnomalyTracker, LinkQuality, LinkQualityTracker, Peer, RateTracker, SystemRegistry,
+};
 use crate::sync::io::IncomingFrameReceiver;
 use crate::sync::node::api::EventSender;
+use crate::sync::node::watch::WatchSender;
 use crate::sync::node::{Callback, Event};
+use crate::sync::utils::spawn_with;
 
 use crate::prelude::*;
 use crate::sync::prelude::*;
@@ -19,22 +25,34 @@ use crate::sync::prelude::*;
 pub(in crate::sync::node) struct IncomingFramesHandler<V: MaybeVersioned> {
     pub(in crate::sync::node) info: ConnectionInfo,
     pub(in crate::sync::node) peers: Arc<RwLock<HashMap<MavLinkId, Peer>>>,
+    pub(in crate::sync::node) locations: PeerLocations,
+    pub(in crate::sync::node) peers_watch: WatchSender<Vec<Peer>>,
     pub(in crate::sync::node) receiver: IncomingFrameReceiver<V>,
     pub(in crate::sync::node) event_sender: EventSender<V>,
     pub(in crate::sync::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::sync::node) anomalies: Option<AnomalyTracker>,
+    pub(in crate::sync::node) governor: Option<RateTracker>,
+    pub(in crate::sync::node) link_quality: Option<LinkQualityTracker>,
+    pub(in crate::sync::node) systems: SystemRegistry,
+    pub(in crate::sync::node) stats: TrafficStats,
+    pub(in crate::sync::node) health: HealthTracker,
 }
 
 impl<V: MaybeVersioned> IncomingFramesHandler<V> {
-    pub(in crate::sync::node) fn spawn(self, state: Closable) {
-        thread::spawn(move || {
-            let info = &self.info;
+    pub(in crate::sync::node) fn spawn(
+        mut self,
+        state: Closable,
+        threads: Option<&ThreadSettings>,
+    ) {
+        spawn_with(threads, move || {
+            let info = self.info.clone();
 
             while !state.is_closed() {
                 let (frame, callback) =
                     match self.receiver.recv_timeout(INCOMING_FRAMES_POOLING_INTERVAL) {
                         Ok(frame) => {
-                            let (frame, channel) = frame.into();
-                            let callback = Callback::new(channel, self.sender.clone());
+                            let (frame, meta) = frame.into();
+                            let callback = Callback::new(meta, self.sender.clone());
                             (frame, callback)
                         }
                         Err(err) => match err {
@@ -45,16 +63,11 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
                         },
                     };
 
-                if let Ok(Minimal::Heartbeat(_)) = frame.decode() {
-                    let peer = Peer::new(frame.system_id(), frame.component_id());
-                    log::trace!("[{info:?}] received heartbeat from {peer:?}");
-
-                    if self.handle_new_peer(peer).is_err() {
-                        break;
-                    }
-                }
-
-                if self.handle_incoming_frame(frame, callback).is_err() {
+                let span = trace::incoming_frame_span(&frame, callback.info());
+                if span
+                    .in_scope(|| self.handle_frame(frame, callback))
+                    .is_err()
+                {
                     break;
                 }
             }
@@ -63,6 +76,47 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
         });
     }
 
+    fn handle_frame(&mut self, frame: Frame<V>, callback: Callback<V>) -> Result<()> {
+        self.stats
+            .record_incoming(&frame, callback.info().connection_id());
+        self.health
+            .record_frame(callback.info().id(), callback.received_at());
+        if let Some(tracker) = self.link_quality.as_mut() {
+            tracker.record(&frame);
+        }
+
+        if let Ok(Minimal::Heartbeat(heartbeat)) = frame.decode() {
+            let mut peer = Peer::new(frame.system_id(), frame.component_id());
+            log::trace!("[{:?}] received heartbeat from {peer:?}", &self.info);
+
+            self.handle_anomalies(&heartbeat, peer.id, callback.info())?;
+
+            let degraded = self.inspect_link(&mut peer, callback.received_at());
+
+            self.locations.observe(peer.id, callback.info().id());
+            self.handle_new_peer(peer)?;
+
+            if let Some((peer, quality)) = degraded {
+                self.handle_degraded_peer(peer, quality)?;
+            }
+
+            self.handle_system(&frame, &heartbeat)?;
+        } else {
+            self.systems.handle_frame(&frame);
+        }
+
+        if !self.is_within_rate(&frame, callback.received_at()) {
+            log::trace!(
+                "[{:?}] frame dropped due to exceeded rate: {frame:?}",
+                &self.info
+            );
+            self.stats.record_rate_limited();
+            return Ok(());
+        }
+
+        self.handle_incoming_frame(frame, callback)
+    }
+
     fn handle_new_peer(&self, peer: Peer) -> Result<()> {
         let info = &self.info;
 
@@ -72,6 +126,7 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
                 peers.insert(peer.id, peer.clone());
 
                 if !has_peer {
+                    self.peers_watch.send(peer_list(&peers));
                     if let Err(err) = self.event_sender.send(Event::NewPeer(peer)) {
                         log::trace!("[{info:?}] failed to report new peer: {err:?}");
                         return Err(Error::from(err));
@@ -87,6 +142,98 @@ impl<V: MaybeVersioned> IncomingFramesHandler<V> {
         Ok(())
     }
 
+    fn handle_system(&self, frame: &Frame<V>, heartbeat: &Heartbeat) -> Result<()> {
+        let update = self.systems.handle_heartbeat(frame, heartbeat);
+
+        if let Some(component) = update.new_component {
+            let event = Event::NewComponent {
+                system_id: component.system_id(),
+                component_id: component.component_id(),
+                component_kind: component.kind(),
+            };
+            if let Err(err) = self.event_sender.send(event) {
+                log::trace!("[{:?}] failed to report new component: {err:?}", &self.info);
+                return Err(Error::from(err));
+            }
+        }
+
+        let system = match update.changed_system {
+            Some(system) => system,
+            None => return Ok(()),
+        };
+
+        if let Err(err) = self.event_sender.send(Event::SystemChanged(system)) {
+            log::trace!("[{:?}] failed to report system state: {err:?}", &self.info);
+            return Err(Error::from(err));
+        }
+
+        Ok(())
+    }
+
+    fn handle_anomalies(
+        &mut self,
+        heartbeat: &Heartbeat,
+        id: MavLinkId,
+        channel: &ChannelInfo,
+    ) -> Result<()> {
+        let tracker = match self.anomalies.as_ref() {
+            Some(tracker) => tracker,
+            None => return Ok(()),
+        };
+
+        for anomaly in tracker.inspect(heartbeat, id, channel) {
+            log::warn!("[{:?}] peer anomaly detected: {anomaly:?}", &self.info);
+            self.stats.record_anomaly();
+            if let Err(err) = self.event_sender.send(Event::Anomaly(anomaly)) {
+                log::trace!("[{:?}] failed to report anomaly: {err:?}", &self.info);
+                return Err(Error::from(err));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inspect_link(
+        &mut self,
+        peer: &mut Peer,
+        received_at: Instant,
+    ) -> Option<(Peer, LinkQuality)> {
+        let tracker = self.link_quality.as_mut()?;
+
+        let (quality, is_degraded) = tracker.heartbeat(peer.id, received_at);
+        peer.link_quality = Some(quality);
+
+        if is_degraded {
+            Some((peer.clone(), quality))
+        } else {
+            None
+        }
+    }
+
+    fn handle_degraded_peer(&self, peer: Peer, quality: LinkQuality) -> Result<()> {
+        log::warn!(
+            "[{:?}] link to {peer:?} is degraded: {quality:?}",
+            &self.info
+        );
+        if let Err(err) = self.event_sender.send(Event::PeerDegraded(peer, quality)) {
+            log::trace!("[{:?}] failed to report degraded peer: {err:?}", &self.info);
+            return Err(Error::from(err));
+        }
+
+        Ok(())
+    }
+
+    fn is_within_rate(&mut self, frame: &Frame<V>, received_at: Instant) -> bool {
+        match self.governor.as_mut() {
+            Some(tracker) => tracker.allow(
+                MavLinkId::new(frame.system_id(), frame.component_id()),
+                frame.message_id(),
+                received_at,
+            ),
+            None => true,
+        }
+    }
+
     fn handle_incoming_frame(&self, frame: Frame<V>, callback: Callback<V>) -> Result<()> {
         let event_send_result = self.event_sender.send(Event::Frame(frame, callback));
 
@@ -0,0 +1,94 @@
+use std::time::Instant;
+
+use crate::core::consts::KEEPALIVE_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::marker::Proxy;
+use crate::core::node::Keepalive;
+use crate::core::utils::ThreadSettings;
+use crate::error::RecvTimeoutError;
+use crate::protocol::DialectVersion;
+use crate::sync::node::api::EventSender;
+use crate::sync::node::Event;
+use crate::sync::utils::spawn_with;
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+pub(in crate::sync::node) struct KeepaliveHandler<V: MaybeVersioned> {
+    pub(in crate::sync::node) info: ConnectionInfo,
+    pub(in crate::sync::node) keepalive: Keepalive,
+    pub(in crate::sync::node) dialect_version: Option<DialectVersion>,
+    pub(in crate::sync::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::sync::node) receiver: EventReceiver<V>,
+    pub(in crate::sync::node) event_sender: EventSender<V>,
+}
+
+impl<V: MaybeVersioned> KeepaliveHandler<V> {
+    pub(in crate::sync::node) fn spawn(self, threads: Option<&ThreadSettings>) {
+        spawn_with(threads, move || {
+            let info = &self.info;
+            let endpoint = self.keepalive.identity().map(Endpoint::versionless);
+            let mut heartbeat_at = Instant::now();
+            let mut received_at = Instant::now();
+            let mut is_stale = false;
+
+            while !self.receiver.state().is_closed() {
+                if let Some(endpoint) = &endpoint {
+                    if heartbeat_at <= Instant::now() {
+                        self.send_heartbeat(endpoint);
+                        heartbeat_at += self.keepalive.interval();
+                    }
+                }
+
+                match self.receiver.recv_timeout(KEEPALIVE_POOLING_INTERVAL) {
+                    Ok(Event::Frame(..) | Event::Invalid(..))
+                    | Err(RecvTimeoutError::Lagged(_)) => {
+                        received_at = Instant::now();
+                        if is_stale {
+                            is_stale = false;
+                            self.emit(Event::ConnectionAlive(info.clone()));
+                        }
+                    }
+                    Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                if let Some(stale_after) = self.keepalive.stale_after() {
+                    if !is_stale && received_at.elapsed() >= stale_after {
+                        is_stale = true;
+                        self.emit(Event::ConnectionStale(info.clone()));
+                    }
+                }
+            }
+
+            log::debug!("[{info:?}] keepalive handler stopped");
+        });
+    }
+
+    fn send_heartbeat(&self, endpoint: &Endpoint<Versionless>) {
+        let info = &self.info;
+
+        let frame = match self
+            .keepalive
+            .heartbeat_frame::<V>(endpoint, self.dialect_version)
+        {
+            Some(Ok(frame)) => frame,
+            Some(Err(err)) => {
+                log::error!("[{info:?}] can't create keepalive heartbeat: {err:?}");
+                return;
+            }
+            None => return,
+        };
+
+        log::trace!("[{info:?}] broadcasting keepalive heartbeat");
+        if let Err(err) = self.sender.send_heartbeat(&frame) {
+            log::trace!("[{info:?}] keepalive heartbeat can't be broadcast: {err:?}");
+        }
+    }
+
+    fn emit(&self, event: Event<V>) {
+        if let Err(err) = self.event_sender.send(event) {
+            log::trace!("[{:?}] can't emit keepalive event: {err:?}", self.info);
+        }
+    }
+}
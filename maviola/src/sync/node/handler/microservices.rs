@@ -0,0 +1,85 @@
+use std::time::Instant;
+
+use crate::core::consts::MICROSERVICES_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::marker::Proxy;
+use crate::core::msrv::registry::{ServiceOutput, ServiceRegistry};
+use crate::core::utils::ThreadSettings;
+use crate::error::RecvTimeoutError;
+use crate::sync::node::api::EventSender;
+use crate::sync::node::Event;
+use crate::sync::utils::spawn_with;
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+pub(in crate::sync::node) struct MicroservicesHandler<V: Versioned> {
+    pub(in crate::sync::node) info: ConnectionInfo,
+    pub(in crate::sync::node) endpoint: Endpoint<V>,
+    pub(in crate::sync::node) registry: ServiceRegistry<V>,
+    pub(in crate::sync::node) receiver: EventReceiver<V>,
+    pub(in crate::sync::node) sender: FrameSender<V, Proxy>,
+    pub(in crate::sync::node) event_sender: EventSender<V>,
+}
+
+impl<V: Versioned> MicroservicesHandler<V> {
+    pub(in crate::sync::node) fn spawn(self, threads: Option<&ThreadSettings>) {
+        spawn_with(threads, move || {
+            let info = &self.info;
+
+            while !self.receiver.state().is_closed() {
+                let next_due = match self.poll() {
+                    Ok(next_due) => next_due,
+                    Err(_) => break,
+                };
+
+                let timeout = next_due
+                    .map(|next_due| next_due.saturating_duration_since(Instant::now()))
+                    .unwrap_or(MICROSERVICES_POOLING_INTERVAL)
+                    .min(MICROSERVICES_POOLING_INTERVAL);
+
+                match self.receiver.recv_timeout(timeout) {
+                    Ok(Event::Frame(frame, _)) => {
+                        if self.handle_frame(&frame).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) | Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Lagged(_)) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            log::debug!("[{info:?}] microservices handler stopped");
+        });
+    }
+
+    fn poll(&self) -> Result<Option<Instant>> {
+        let (output, next_due) = self.registry.poll(Instant::now())?;
+        self.emit(output)?;
+        Ok(next_due)
+    }
+
+    fn handle_frame(&self, frame: &Frame<V>) -> Result<()> {
+        let output = self.registry.dispatch(frame, self.endpoint.id())?;
+        self.emit(output)
+    }
+
+    fn emit(&self, output: ServiceOutput) -> Result<()> {
+        for message in output.messages {
+            let frame = self.endpoint.next_frame(message.as_ref())?;
+            self.sender.send_frame(&frame).map_err(|err| {
+                log::trace!(
+                    "[{:?}] microservice message can't be sent: {err:?}",
+                    self.info
+                );
+                err
+            })?;
+        }
+
+        for event in output.events {
+            self.event_sender.send(Event::Custom(event))?;
+        }
+
+        Ok(())
+    }
+}
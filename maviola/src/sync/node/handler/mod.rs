@@ -1,9 +1,33 @@
 //! # 🔒 Core node handlers
 
+mod black_box;
+mod connection_events;
 mod heartbeats;
 mod inactive_peers;
 mod incoming_frames;
+mod keepalive;
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+mod microservices;
+mod periodic;
+mod reconnect;
+mod stats;
 
+pub(super) use black_box::BlackBoxHandler;
+pub(super) use connection_events::ConnectionEventsHandler;
 pub(super) use heartbeats::HeartbeatEmitter;
 pub(super) use inactive_peers::InactivePeersHandler;
 pub(super) use incoming_frames::IncomingFramesHandler;
+pub(super) use keepalive::KeepaliveHandler;
+#[cfg(any(
+    feature = "msrv-utils-params",
+    feature = "msrv-utils-streams",
+    feature = "msrv-utils-timesync"
+))]
+pub(super) use microservices::MicroservicesHandler;
+pub(super) use periodic::PeriodicHandler;
+pub(super) use reconnect::ConnectionSupervisor;
+pub(super) use stats::StatsReporter;
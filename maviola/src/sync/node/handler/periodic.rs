@@ -0,0 +1,58 @@
+use std::thread;
+use std::time::Instant;
+
+use crate::core::consts::PERIODIC_POOLING_INTERVAL;
+use crate::core::io::ConnectionInfo;
+use crate::core::marker::Proxy;
+use crate::core::node::PeriodicTasks;
+use crate::core::utils::{Closable, ThreadSettings};
+use crate::sync::utils::spawn_with;
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+pub(in crate::sync::node) struct PeriodicHandler<V: Versioned> {
+    pub(in crate::sync::node) info: ConnectionInfo,
+    pub(in crate::sync::node) endpoint: Endpoint<V>,
+    pub(in crate::sync::node) tasks: PeriodicTasks,
+    pub(in crate::sync::node) sender: FrameSender<V, Proxy>,
+}
+
+impl<V: Versioned> PeriodicHandler<V> {
+    pub(in crate::sync::node) fn spawn(self, state: Closable, threads: Option<&ThreadSettings>) {
+        spawn_with(threads, move || {
+            let info = &self.info;
+
+            while !state.is_closed() {
+                let (messages, next_due) = self.tasks.poll(Instant::now());
+
+                for (task, message) in messages {
+                    let frame = match self.endpoint.next_frame(message.as_ref()) {
+                        Ok(frame) => frame,
+                        Err(err) => {
+                            log::error!("[{info:?}] can't encode periodic message: {err:?}");
+                            task.stop();
+                            continue;
+                        }
+                    };
+
+                    match self.sender.send_frame(&frame) {
+                        Ok(_) => task.record_sent(),
+                        Err(err) => {
+                            log::trace!("[{info:?}] periodic message can't be sent: {err:?}");
+                        }
+                    }
+                }
+
+                let timeout = next_due
+                    .map(|next_due| next_due.saturating_duration_since(Instant::now()))
+                    .unwrap_or(PERIODIC_POOLING_INTERVAL)
+                    .min(PERIODIC_POOLING_INTERVAL);
+                thread::sleep(timeout);
+            }
+
+            self.tasks.stop_all();
+            log::debug!("[{info:?}] periodic tasks scheduler stopped");
+        });
+    }
+}
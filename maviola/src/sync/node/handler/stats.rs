@@ -0,0 +1,49 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::io::ConnectionInfo;
+use crate::core::node::TrafficStats;
+use crate::core::utils::{Closable, ThreadSettings};
+use crate::sync::node::api::EventSender;
+use crate::sync::node::Event;
+use crate::sync::utils::spawn_with;
+
+use crate::prelude::*;
+
+pub(in crate::sync::node) struct StatsReporter<V: MaybeVersioned> {
+    pub(in crate::sync::node) info: ConnectionInfo,
+    pub(in crate::sync::node) stats: TrafficStats,
+    pub(in crate::sync::node) interval: Duration,
+    pub(in crate::sync::node) event_sender: EventSender<V>,
+}
+
+impl<V: MaybeVersioned> StatsReporter<V> {
+    pub(in crate::sync::node) fn spawn(
+        self,
+        state: Closable,
+        reporter_state: Closable,
+        threads: Option<&ThreadSettings>,
+    ) {
+        spawn_with(threads, move || {
+            let info = &self.info;
+            let mut reported_at = Instant::now();
+
+            loop {
+                thread::sleep(self.interval);
+                if state.is_closed() || reporter_state.is_closed() {
+                    break;
+                }
+
+                let report = self.stats.take_report(reported_at.elapsed());
+                reported_at = Instant::now();
+
+                if let Err(err) = self.event_sender.send(Event::StatsReport(report)) {
+                    log::trace!("[{info:?}] failed to report stats: {err:?}");
+                    break;
+                }
+            }
+
+            log::trace!("[{info:?}] stats reporter stopped");
+        });
+    }
+}
@@ -3,20 +3,42 @@
 pub(in crate::sync) mod api;
 mod build_ext;
 mod callback;
+#[cfg(feature = "msrv-utils-camera")]
+mod camera;
+mod channel;
+mod component;
 mod conf_ext;
 mod event;
 mod ext;
+#[cfg(feature = "msrv-utils-ftp")]
+mod ftp;
+#[cfg(feature = "msrv-utils-gcs")]
+mod gcs;
+#[cfg(feature = "msrv-utils-gimbal")]
+mod gimbal;
 mod handler;
 mod receive;
 mod receiver;
 mod sender;
+mod watch;
 
 pub use api::SyncApi;
 pub use callback::Callback;
+#[cfg(feature = "msrv-utils-camera")]
+pub use camera::CameraClient;
+pub use channel::EventChannel;
+pub use component::NodeComponent;
 pub use event::Event;
+#[cfg(feature = "msrv-utils-ftp")]
+pub use ftp::FtpClient;
+#[cfg(feature = "msrv-utils-gcs")]
+pub use gcs::GcsClient;
+#[cfg(feature = "msrv-utils-gimbal")]
+pub use gimbal::GimbalClient;
 pub use receive::{ReceiveEvent, ReceiveFrame};
 pub use receiver::EventReceiver;
 pub use sender::FrameSender;
+pub use watch::Watcher;
 
 use crate::core::marker::{Edge, Proxy};
 use crate::core::node::Node;
@@ -59,13 +81,55 @@ use crate::core::node::Node;
 ///         Event::PeerLost(peer) => {
 ///             /* handle a peer, that becomes inactive */
 ///         }
+///         Event::PeerDegraded(peer, quality) => {
+///             /* handle a peer with lossy or unstable link */
+///         }
+///         Event::SystemChanged(system) => {
+///             /* handle changes of vehicle mode or status */
+///         }
+///         Event::NewComponent { system_id, component_id, component_kind } => {
+///             /* handle a new camera, gimbal, or other component */
+///         }
 ///         Event::Frame(frame, callback) => {
 ///             // Send back any incoming frame directly to its sender's channel
 ///             callback.respond(&frame).unwrap();
 ///         }
-///         Event::Invalid(frame, err, callback) => {
+///         Event::Invalid(frame, report, callback) => {
 ///             /* Process invalid frame */
 ///         }
+///         Event::Malformed(channel, report) => {
+///             /* Inspect bytes, that are not MAVLink frames */
+///         }
+///         Event::SenderRejected(info, addr) => {
+///             /* Report a sender, that is not allowed by connection */
+///         }
+///         Event::Anomaly(anomaly) => {
+///             /* Report suspicious peer behavior */
+///         }
+///         Event::ConnectionLost(info) => {
+///             /* Connection failed */
+///         }
+///         Event::ConnectionRestored(info) => {
+///             /* Connection was restored after failure */
+///         }
+///         Event::ConnectionStale(info) | Event::ConnectionAlive(info) => {
+///             /* Connection became silent or received frames again */
+///         }
+///         Event::ConnectionAdded(info) | Event::ConnectionRemoved(info) => {
+///             /* Network connection was added or removed at runtime */
+///         }
+///         Event::LinkActivated(info) => {
+///             /* Traffic was switched to another link of a failover group */
+///         }
+///         Event::ChannelOpened(info) | Event::ChannelClosed(info) => {
+///             /* Channel (i.e. TCP client) connected or disconnected */
+///         }
+///         Event::StatsReport(report) => {
+///             /* Log or forward traffic summary */
+///         }
+///         Event::Custom(event) => {
+///             /* Handle application-defined event */
+///         }
 ///     }
 /// }
 /// ```
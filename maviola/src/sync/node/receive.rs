@@ -27,6 +27,28 @@ pub trait ReceiveEvent<V: MaybeVersioned>: Sealed {
     /// Attempts to receive MAVLink [`Event`] without blocking.
     fn try_recv(&self) -> TryRecvResult<Event<V>>;
 
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Receives up to `max` node [`Event`]s into a `buffer`.
+    ///
+    /// Blocks until at least one event received, then takes all pending events without blocking
+    /// until `max` events are collected. Received events are appended to the `buffer`. Returns the
+    /// number of received events which is `0` only if `max` is `0`.
+    ///
+    /// Pending events are taken from the channel in batches instead of one by one. For receivers,
+    /// that joined a group, each batch is taken under a single lock.
+    ///
+    /// Use this method instead of [`recv`] when you need to process a high volume of events, and
+    /// you want to reuse the same buffer between calls.
+    ///
+    /// [`recv`]: Self::recv
+    fn recv_many(&self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize>;
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Takes up to `max` pending node [`Event`]s without blocking.
+    ///
+    /// Returns an empty vector if there are no pending events or the channel is closed.
+    fn drain(&self, max: usize) -> Vec<Event<V>>;
+
     /// <sup>[`sync`](crate::sync)</sup>
     /// Subscribes to node events.
     ///
@@ -55,7 +77,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`recv`] instead to receive [`Event::Invalid`] event that contain invalid
-    /// frame with the corresponding error.
+    /// frame with the corresponding validation report.
     ///
     /// [`recv_frame_timeout`]: Self::recv_frame_timeout
     /// [`try_recv_frame`]: Self::try_recv_frame
@@ -82,7 +104,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`recv_timeout`] instead to receive [`Event::Invalid`] event that contains
-    /// invalid frame with the corresponding error.
+    /// invalid frame with the corresponding validation report.
     ///
     /// [`recv_frame`]: Self::recv_frame
     /// [`try_recv_frame`]: Self::try_recv_frame
@@ -124,7 +146,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`] or [`try_recv`] instead to receive [`Event::Invalid`] event that contains invalid
-    /// frame with the corresponding error.
+    /// frame with the corresponding validation report.
     ///
     /// [`recv_frame`]: Self::recv_frame
     /// [`recv_frame_timeout`]: Self::recv_frame_timeout
@@ -145,7 +167,7 @@ pub trait ReceiveFrame<V: MaybeVersioned>: ReceiveEvent<V> {
     ///
     /// **⚠** This method skips all invalid frames. If you are interested in such frames, use
     /// [`events`], [`recv`], [`recv_timeout`], or [`try_recv`] instead to receive
-    /// [`Event::Invalid`] event that contains invalid frame with the corresponding error.
+    /// [`Event::Invalid`] event that contains invalid frame with the corresponding validation report.
     ///
     /// [`recv`]: ReceiveEvent::recv
     /// [`recv_timeout`]: ReceiveEvent::recv_timeout
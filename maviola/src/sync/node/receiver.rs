@@ -1,11 +1,12 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::core::utils::{Closable, Sealed};
+use crate::core::node::{LatencyStats, TrafficStats, ValidationReport};
+use crate::core::utils::{trace, Closable, Sealed};
 use crate::error::{
     RecvError, RecvResult, RecvTimeoutError, RecvTimeoutResult, TryRecvError, TryRecvResult,
 };
-use crate::protocol::FrameProcessor;
+use crate::protocol::{FrameProcessor, TargetFields};
 use crate::sync::node::event::EventsIterator;
 
 use crate::prelude::*;
@@ -17,12 +18,19 @@ use crate::sync::prelude::*;
 /// **⚠** In order to have access to [`EventReceiver`] methods, you have to import
 /// [`ReceiveEvent`] and [`ReceiveFrame`] traits. You may import [`sync::prelude`] as well.
 ///
+/// By default, each receiver gets all node events. Receivers that joined a group by
+/// [`EventReceiver::join_group`] share events: each event is delivered to exactly one member of
+/// the group.
+///
 /// [`sync::prelude`]: crate::sync::prelude
 #[derive(Clone)]
 pub struct EventReceiver<V: MaybeVersioned> {
-    inner: mpmc::Receiver<Event<V>>,
+    inner: Subscription<V>,
+    target: Option<MavLinkId>,
     state: Closable,
     processor: Arc<FrameProcessor>,
+    latency: Option<LatencyStats>,
+    stats: TrafficStats,
 }
 
 impl<V: MaybeVersioned> EventReceiver<V> {
@@ -30,11 +38,94 @@ impl<V: MaybeVersioned> EventReceiver<V> {
         receiver: mpmc::Receiver<Event<V>>,
         state: Closable,
         processor: Arc<FrameProcessor>,
+        latency: Option<LatencyStats>,
+        stats: TrafficStats,
     ) -> Self {
         Self {
-            inner: receiver,
+            inner: Subscription::Broadcast(Arc::new(receiver)),
+            target: None,
             state,
             processor,
+            latency,
+            stats,
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Creates a receiver, that joined a group of receivers with specified `name`.
+    ///
+    /// Each event is delivered to exactly one member of the group, which allows to process
+    /// expensive frames in parallel by a pool of workers. Cloning the returned receiver adds a new
+    /// member to the same group. Groups with different names are independent of each other and
+    /// of broadcast receivers.
+    ///
+    /// # Usage
+    ///
+    /// ```rust,no_run
+    /// use std::thread;
+    /// use maviola::prelude::*;
+    /// use maviola::sync::prelude::*;
+    ///
+    /// let node = Node::sync::<V2>()
+    ///     .connection(TcpServer::new("127.0.0.1:5600").unwrap())
+    ///     .build().unwrap();
+    ///
+    /// let workers = node.receiver().join_group("workers");
+    ///
+    /// for _ in 0..4 {
+    ///     let worker = workers.clone();
+    ///     thread::spawn(move || {
+    ///         for (frame, callback) in worker.frames() {
+    ///             /* each frame is processed by exactly one worker */
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn join_group(&self, name: &str) -> Self {
+        let inner = match &self.inner {
+            Subscription::Broadcast(receiver) => receiver.join_group(name),
+            Subscription::Group(receiver) if receiver.group() == name => receiver.clone(),
+            Subscription::Group(receiver) => receiver.join_group(name),
+        };
+
+        Self {
+            inner: Subscription::Group(inner),
+            target: self.target,
+            state: self.state.clone(),
+            processor: self.processor.clone(),
+            latency: self.latency.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Creates a receiver, that skips frames addressed to other systems or components.
+    ///
+    /// Frames of messages without target fields and frames addressed to all systems or components
+    /// are kept, as well as events other than frames. Used by [`NodeComponent::receiver`] to
+    /// deliver frames addressed to a particular component.
+    ///
+    /// [`NodeComponent::receiver`]: crate::sync::node::NodeComponent::receiver
+    pub fn addressed_to(&self, id: MavLinkId) -> Self {
+        Self {
+            target: Some(id),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a receiver, that shares subscription with this one.
+    ///
+    /// Unlike a cloned receiver, which gets its own copy of each node event, each event is
+    /// delivered to only one of the receivers sharing a subscription. Used when node events are
+    /// consumed by internal handlers instead of the node itself.
+    pub(in crate::sync) fn share(&self) -> Self {
+        Self {
+            inner: self.inner.share(),
+            target: self.target,
+            state: self.state.clone(),
+            processor: self.processor.clone(),
+            latency: self.latency.clone(),
+            stats: self.stats.clone(),
         }
     }
 
@@ -42,19 +133,88 @@ impl<V: MaybeVersioned> EventReceiver<V> {
         &self.state
     }
 
+    /// Number of events, that were delivered to this receiver, but not yet received.
+    pub(in crate::sync) fn pending(&self) -> usize {
+        self.inner.len()
+    }
+
     pub(super) fn recv(&self) -> core::result::Result<Event<V>, RecvError> {
-        Ok(self.process_event(self.inner.recv()?))
+        loop {
+            let event = self.inner.recv()?;
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
     }
 
     pub(in crate::sync) fn recv_timeout(
         &self,
         timeout: Duration,
     ) -> core::result::Result<Event<V>, RecvTimeoutError> {
-        Ok(self.process_event(self.inner.recv_timeout(timeout)?))
+        let deadline = Instant::now() + timeout;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let event = self.inner.recv_timeout(timeout)?;
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
     }
 
     pub(super) fn try_recv(&self) -> core::result::Result<Event<V>, TryRecvError> {
-        Ok(self.process_event(self.inner.try_recv()?))
+        loop {
+            let event = self.inner.try_recv()?;
+            if self.accepts(&event) {
+                return Ok(self.process_event(event));
+            }
+        }
+    }
+
+    pub(super) fn recv_many(
+        &self,
+        buffer: &mut Vec<Event<V>>,
+        max: usize,
+    ) -> core::result::Result<usize, RecvError> {
+        if max == 0 {
+            return Ok(0);
+        }
+
+        buffer.push(self.recv()?);
+        Ok(1 + self.drain_into(buffer, max - 1))
+    }
+
+    pub(super) fn drain(&self, max: usize) -> Vec<Event<V>> {
+        let mut buffer = Vec::new();
+        self.drain_into(&mut buffer, max);
+        buffer
+    }
+
+    /// Takes pending events in batches, skipping events not accepted by the receiver.
+    fn drain_into(&self, buffer: &mut Vec<Event<V>>, max: usize) -> usize {
+        let mut events = Vec::new();
+        let mut received = 0;
+        while received < max {
+            if self.inner.drain_into(&mut events, max - received) == 0 {
+                break;
+            }
+            for event in events.drain(..) {
+                if self.accepts(&event) {
+                    buffer.push(self.process_event(event));
+                    received += 1;
+                }
+            }
+        }
+        received
+    }
+
+    /// Returns `false` for frames, that are not addressed to the receiver target (if any).
+    fn accepts(&self, event: &Event<V>) -> bool {
+        match (&self.target, event) {
+            (Some(id), Event::Frame(frame, _)) | (Some(id), Event::Invalid(frame, _, _)) => {
+                TargetFields::is_addressed_to(frame, *id)
+            }
+            _ => true,
+        }
     }
 
     fn process_event(&self, event: Event<V>) -> Event<V> {
@@ -62,18 +222,111 @@ impl<V: MaybeVersioned> EventReceiver<V> {
             Event::Frame(mut frame, mut callback) => {
                 callback.set_processor(self.processor.clone());
 
-                if let Err(err) = self.processor.process_incoming(&mut frame) {
-                    return Event::Invalid(frame, err, callback);
+                let span = trace::incoming_frame_span(&frame, callback.info());
+                if let Err(report) = span
+                    .in_scope(|| ValidationReport::validate_incoming(&mut frame, &self.processor))
+                {
+                    self.stats.record_invalid();
+                    return Event::Invalid(frame, report, callback);
+                }
+
+                if let Some(latency) = &self.latency {
+                    latency.record_incoming(frame.message_id(), callback.received_at().elapsed());
                 }
 
                 Event::Frame(frame, callback)
             }
-            Event::Invalid(frame, err, mut callback) => {
+            Event::Invalid(frame, report, mut callback) => {
                 callback.set_processor(self.processor.clone());
-                Event::Invalid(frame, err, callback)
+                Event::Invalid(frame, report, callback)
             }
+            Event::Malformed(channel, report) => Event::Malformed(channel, report),
+            Event::SenderRejected(info, addr) => Event::SenderRejected(info, addr),
             Event::NewPeer(peer) => Event::NewPeer(peer),
             Event::PeerLost(peer) => Event::PeerLost(peer),
+            Event::PeerDegraded(peer, quality) => Event::PeerDegraded(peer, quality),
+            Event::SystemChanged(system) => Event::SystemChanged(system),
+            Event::NewComponent {
+                system_id,
+                component_id,
+                component_kind,
+            } => Event::NewComponent {
+                system_id,
+                component_id,
+                component_kind,
+            },
+            Event::Anomaly(anomaly) => Event::Anomaly(anomaly),
+            Event::ConnectionLost(info) => Event::ConnectionLost(info),
+            Event::ConnectionRestored(info) => Event::ConnectionRestored(info),
+            Event::ConnectionStale(info) => Event::ConnectionStale(info),
+            Event::ConnectionAlive(info) => Event::ConnectionAlive(info),
+            Event::ConnectionAdded(info) => Event::ConnectionAdded(info),
+            Event::ConnectionRemoved(info) => Event::ConnectionRemoved(info),
+            Event::LinkActivated(info) => Event::LinkActivated(info),
+            Event::ChannelOpened(info) => Event::ChannelOpened(info),
+            Event::ChannelClosed(info) => Event::ChannelClosed(info),
+            Event::StatsReport(report) => Event::StatsReport(report),
+            Event::Custom(event) => Event::Custom(event),
+        }
+    }
+}
+
+enum Subscription<V: MaybeVersioned> {
+    Broadcast(Arc<mpmc::Receiver<Event<V>>>),
+    Group(mpmc::GroupReceiver<Event<V>>),
+}
+
+impl<V: MaybeVersioned> Clone for Subscription<V> {
+    fn clone(&self) -> Self {
+        match self {
+            Subscription::Broadcast(receiver) => {
+                Subscription::Broadcast(Arc::new(receiver.as_ref().clone()))
+            }
+            Subscription::Group(receiver) => Subscription::Group(receiver.clone()),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Subscription<V> {
+    fn share(&self) -> Self {
+        match self {
+            Subscription::Broadcast(receiver) => Subscription::Broadcast(receiver.clone()),
+            Subscription::Group(receiver) => Subscription::Group(receiver.clone()),
+        }
+    }
+
+    fn recv(&self) -> RecvResult<Event<V>> {
+        match self {
+            Subscription::Broadcast(receiver) => receiver.recv(),
+            Subscription::Group(receiver) => receiver.recv(),
+        }
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<Event<V>> {
+        match self {
+            Subscription::Broadcast(receiver) => receiver.recv_timeout(timeout),
+            Subscription::Group(receiver) => receiver.recv_timeout(timeout),
+        }
+    }
+
+    fn try_recv(&self) -> TryRecvResult<Event<V>> {
+        match self {
+            Subscription::Broadcast(receiver) => receiver.try_recv(),
+            Subscription::Group(receiver) => receiver.try_recv(),
+        }
+    }
+
+    fn drain_into(&self, buffer: &mut Vec<Event<V>>, max: usize) -> usize {
+        match self {
+            Subscription::Broadcast(receiver) => receiver.drain_into(buffer, max),
+            Subscription::Group(receiver) => receiver.drain_into(buffer, max),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Subscription::Broadcast(receiver) => receiver.len(),
+            Subscription::Group(receiver) => receiver.len(),
         }
     }
 }
@@ -96,9 +349,78 @@ impl<V: MaybeVersioned> ReceiveEvent<V> for EventReceiver<V> {
         self.try_recv()
     }
 
+    #[inline(always)]
+    fn recv_many(&self, buffer: &mut Vec<Event<V>>, max: usize) -> RecvResult<usize> {
+        self.recv_many(buffer, max)
+    }
+
+    #[inline(always)]
+    fn drain(&self, max: usize) -> Vec<Event<V>> {
+        self.drain(max)
+    }
+
     fn events(&self) -> impl Iterator<Item = Event<V>> {
         EventsIterator::new(self.clone())
     }
 }
 
 impl<V: MaybeVersioned> ReceiveFrame<V> for EventReceiver<V> {}
+
+#[cfg(test)]
+mod receiver_tests {
+    use super::*;
+    use std::thread;
+
+    use crate::core::utils::Closer;
+    use crate::protocol::Peer;
+
+    #[test]
+    fn recv_many_and_drain() {
+        let state = Closer::new();
+        let (tx, rx) = mpmc::channel();
+        let event_receiver: EventReceiver<V2> = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        );
+
+        for _ in 0..5 {
+            tx.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        }
+        thread::sleep(Duration::from_millis(50));
+
+        let mut buffer = Vec::new();
+        assert_eq!(event_receiver.recv_many(&mut buffer, 3).unwrap(), 3);
+        assert_eq!(event_receiver.recv_many(&mut buffer, 0).unwrap(), 0);
+        assert_eq!(buffer.len(), 3);
+
+        assert_eq!(event_receiver.drain(10).len(), 2);
+        assert!(event_receiver.drain(10).is_empty());
+    }
+
+    #[test]
+    fn shared_receivers_do_not_duplicate_events() {
+        let state = Closer::new();
+        let (tx, rx) = mpmc::channel();
+        let event_receiver: EventReceiver<V2> = EventReceiver::new(
+            rx,
+            state.to_closable(),
+            Arc::new(FrameProcessor::default()),
+            None,
+            Default::default(),
+        );
+        let shared = event_receiver.share();
+        let cloned = event_receiver.clone();
+
+        for _ in 0..4 {
+            tx.send(Event::NewPeer(Peer::new(1, 1))).unwrap();
+        }
+        thread::sleep(Duration::from_millis(50));
+
+        assert_eq!(shared.drain(2).len(), 2);
+        assert_eq!(event_receiver.drain(10).len(), 2);
+        assert_eq!(cloned.drain(10).len(), 4);
+    }
+}
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
-use crate::core::io::{BroadcastScope, OutgoingFrame};
+use crate::core::io::{BroadcastScope, EgressPriorities, OutgoingFrame};
 use crate::core::marker::{Edge, NodeKind, Proxy};
-use crate::core::node::{SendFrameInternal, SendMessageInternal};
-use crate::core::utils::Sealed;
+use crate::core::node::{
+    LatencyStats, PeerLocations, SendFrameInternal, SendMessageInternal, TrafficStats,
+};
+use crate::core::utils::{trace, Sealed};
 use crate::error::SendResult;
 use crate::protocol::FrameProcessor;
 use crate::sync::io::OutgoingFrameSender;
@@ -24,16 +26,31 @@ use crate::prelude::*;
 pub struct FrameSender<V: MaybeVersioned, K: NodeKind> {
     inner: OutgoingFrameSender<V>,
     processor: Arc<FrameProcessor>,
+    latency: Option<LatencyStats>,
+    stats: TrafficStats,
+    priorities: Option<Arc<EgressPriorities>>,
+    locations: PeerLocations,
     kind: K,
 }
 
 impl<V: MaybeVersioned> FrameSender<V, Proxy> {
     /// <sup>⛔</sup>
     /// Creates a new proxy frame sender.
-    pub(super) fn new(sender: OutgoingFrameSender<V>, processor: Arc<FrameProcessor>) -> Self {
+    pub(in crate::sync) fn new(
+        sender: OutgoingFrameSender<V>,
+        processor: Arc<FrameProcessor>,
+        latency: Option<LatencyStats>,
+        stats: TrafficStats,
+        priorities: Option<EgressPriorities>,
+        locations: PeerLocations,
+    ) -> Self {
         Self {
             inner: sender,
             processor,
+            latency,
+            stats,
+            priorities: priorities.map(Arc::new),
+            locations,
             kind: Proxy,
         }
     }
@@ -44,6 +61,10 @@ impl<V: MaybeVersioned> FrameSender<V, Proxy> {
         FrameSender {
             inner: self.inner,
             processor: self.processor,
+            latency: self.latency,
+            stats: self.stats,
+            priorities: self.priorities,
+            locations: self.locations,
             kind,
         }
     }
@@ -53,11 +74,30 @@ impl<V: MaybeVersioned, K: NodeKind> FrameSender<V, K> {
     /// <sup>⛔</sup>
     /// Sends outgoing frame without processing.
     #[inline(always)]
+    #[allow(clippy::result_large_err)]
     pub(in crate::sync) fn send_raw(
         &self,
-        frame: OutgoingFrame<V>,
+        mut frame: OutgoingFrame<V>,
     ) -> SendResult<OutgoingFrame<V>> {
-        self.inner.send_raw(frame)
+        frame.track_latency(self.latency.as_ref());
+        frame.track_stats(&self.stats);
+        frame.resolve_peers(&self.locations);
+        if let Some(priorities) = &self.priorities {
+            frame.prioritize(priorities);
+        }
+        trace::outgoing_frame_span(&frame).in_scope(|| self.inner.send_raw(frame))
+    }
+
+    /// <sup>⛔</sup>
+    /// Processes and sends an automatic heartbeat `frame` emitted by a node or its component.
+    ///
+    /// Unlike [`SendFrame::send_frame`], marks frame as a node heartbeat, so networks may suppress
+    /// it for particular connections.
+    pub(in crate::sync) fn send_heartbeat(&self, frame: &Frame<V>) -> Result<()> {
+        let mut frame = frame.clone();
+        self.processor.process_outgoing(&mut frame)?;
+        self.send_raw(OutgoingFrame::node_heartbeat(frame))
+            .map_err(Error::from)
     }
 
     /// <sup>⛔</sup>
@@ -86,6 +126,12 @@ impl<V: MaybeVersioned, K: NodeKind> SendFrameInternal<V> for FrameSender<V, K>
         self.send_raw(OutgoingFrame::scoped(frame, scope))
             .map_err(Error::from)
     }
+
+    #[inline(always)]
+    unsafe fn route_targeted_internal(&self, frame: Frame<V>) -> Result<()> {
+        self.send_raw(OutgoingFrame::targeted(frame))
+            .map_err(Error::from)
+    }
 }
 
 impl<V: MaybeVersioned, K: NodeKind> SendFrame<V> for FrameSender<V, K> {}
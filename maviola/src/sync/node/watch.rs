@@ -0,0 +1,259 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::{RecvError, RecvTimeoutError};
+
+/// <sup>[`sync`](crate::sync)</sup>
+/// Blocking observer of a value derived from node state.
+///
+/// Returned by `watch_peers` and `watch_connection` methods of a node. Unlike node events, a
+/// watcher does not queue changes: it always yields the latest value, and waiting returns once the
+/// value has changed since it was last seen by this watcher. This is the synchronous counterpart of
+/// [`tokio::sync::watch::Receiver`] used by the asynchronous API.
+///
+/// Each clone tracks seen values independently. Once node handlers are stopped and the node is
+/// dropped, waiting fails with [`RecvError::Disconnected`], while [`Watcher::get`] keeps returning
+/// the last value.
+pub struct Watcher<T: Clone> {
+    shared: Arc<WatchShared<T>>,
+    seen: u64,
+}
+
+impl<T: Clone> Watcher<T> {
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Returns the current value without marking it as seen.
+    pub fn get(&self) -> T {
+        self.shared.lock().value.clone()
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Returns `true`, if value has changed since it was last seen.
+    pub fn has_changed(&self) -> bool {
+        self.shared.lock().version != self.seen
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Returns the current value and marks it as seen.
+    pub fn get_and_update(&mut self) -> T {
+        let state = self.shared.lock();
+        self.seen = state.version;
+        state.value.clone()
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Blocks until value changes and returns the new one.
+    ///
+    /// Returns immediately, if value has already changed since it was last seen.
+    pub fn wait(&mut self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if state.version != self.seen {
+                self.seen = state.version;
+                return Ok(state.value.clone());
+            }
+            if state.senders == 0 {
+                return Err(RecvError::Disconnected);
+            }
+            state = match self.shared.changed.wait(state) {
+                Ok(state) => state,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+        }
+    }
+
+    /// <sup>[`sync`](crate::sync)</sup>
+    /// Blocks until value changes or `timeout` is reached.
+    ///
+    /// Returns immediately, if value has already changed since it was last seen.
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.lock();
+        loop {
+            if state.version != self.seen {
+                self.seen = state.version;
+                return Ok(state.value.clone());
+            }
+            if state.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if timeout.is_zero() {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = match self.shared.changed.wait_timeout(state, timeout) {
+                Ok((state, _)) => state,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+    }
+}
+
+impl<T: Clone> Clone for Watcher<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<T: Clone + Debug> Debug for Watcher<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Watcher")
+            .field("value", &self.get())
+            .field("seen", &self.seen)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Publishes values to [`Watcher`]s.
+///
+/// Watchers are disconnected, once all senders are dropped.
+pub(in crate::sync::node) struct WatchSender<T: Clone> {
+    shared: Arc<WatchShared<T>>,
+}
+
+impl<T: Clone> WatchSender<T> {
+    pub(in crate::sync::node) fn new(value: T) -> Self {
+        Self {
+            shared: Arc::new(WatchShared {
+                state: Mutex::new(WatchState {
+                    value,
+                    version: 0,
+                    senders: 1,
+                }),
+                changed: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Creates a watcher, that has seen the current value.
+    pub(in crate::sync::node) fn watcher(&self) -> Watcher<T> {
+        let seen = self.shared.lock().version;
+        Watcher {
+            shared: self.shared.clone(),
+            seen,
+        }
+    }
+
+    /// Returns the current value.
+    pub(in crate::sync::node) fn get(&self) -> T {
+        self.shared.lock().value.clone()
+    }
+
+    /// Modifies value in place and notifies watchers, if `modify` returns `true`.
+    pub(in crate::sync::node) fn send_if_modified(&self, modify: impl FnOnce(&mut T) -> bool) {
+        let mut state = self.shared.lock();
+        if modify(&mut state.value) {
+            state.version = state.version.wrapping_add(1);
+            self.shared.changed.notify_all();
+        }
+    }
+
+    /// Replaces value and notifies watchers.
+    pub(in crate::sync::node) fn send(&self, value: T) {
+        self.send_if_modified(|current| {
+            *current = value;
+            true
+        });
+    }
+}
+
+impl<T: Clone> Clone for WatchSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Drop for WatchSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+struct WatchShared<T> {
+    state: Mutex<WatchState<T>>,
+    changed: Condvar,
+}
+
+impl<T> WatchShared<T> {
+    fn lock(&self) -> MutexGuard<'_, WatchState<T>> {
+        // Value is always consistent, since it is replaced or modified under the lock
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+struct WatchState<T> {
+    value: T,
+    version: u64,
+    senders: usize,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//                                 Tests                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod watch_tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn watcher_waits_for_changes() {
+        let sender = WatchSender::new(0);
+        let mut watcher = sender.watcher();
+
+        assert!(!watcher.has_changed());
+        assert!(matches!(
+            watcher.wait_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        ));
+
+        let handle = {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                sender.send(1);
+                sender.send_if_modified(|_| false);
+            })
+        };
+        assert_eq!(watcher.wait().unwrap(), 1);
+        handle.join().unwrap();
+
+        // Only the latest value is observed
+        sender.send(2);
+        sender.send(3);
+        let mut other = watcher.clone();
+        assert_eq!(watcher.wait().unwrap(), 3);
+        assert!(!watcher.has_changed());
+        assert_eq!(other.get_and_update(), 3);
+        assert!(!other.has_changed());
+    }
+
+    #[test]
+    fn watcher_disconnects() {
+        let sender = WatchSender::new("value");
+        let mut watcher = sender.watcher();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            drop(sender);
+        });
+
+        assert!(matches!(watcher.wait(), Err(RecvError::Disconnected)));
+        assert_eq!(watcher.get(), "value");
+        handle.join().unwrap();
+    }
+}
@@ -9,4 +9,7 @@ pub use crate::sync::node::{
     SyncApi,
 };
 
+#[cfg(feature = "serial")]
+pub use crate::core::io::SerialPort;
+
 pub(crate) use crate::sync::utils::mpmc;
@@ -10,6 +10,11 @@ use crate::sync::io::ConnectionBuilder;
 mod busy_rw;
 pub mod mpmc;
 mod mpsc_rw;
+mod threads;
 
 pub use busy_rw::{BusyReader, BusyWriter};
 pub use mpsc_rw::{MpscReader, MpscWriter};
+
+pub(crate) use threads::{
+    io_pool, spawn_io, spawn_io_worker, spawn_with, with_io_pool, with_io_threads,
+};
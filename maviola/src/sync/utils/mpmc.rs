@@ -13,6 +13,20 @@
 //! [`Receiver`] can be cloned. A cloned receiver becomes an independent listener for channel's
 //! messages.
 //!
+//! In addition to broadcasting, receivers may join a named group by [`Receiver::join_group`]. Each
+//! message is delivered to exactly one member of a [`GroupReceiver`] group, which allows to
+//! distribute work between several worker threads.
+//!
+//! Messages are passed through channels created by a [`ChannelFlavor`]. By default, channels from
+//! [`mpsc`] are used ([`StdChannel`]). Channels from `crossbeam-channel` and `flume` are available
+//! as `CrossbeamChannel` and `FlumeChannel` flavors under `crossbeam` and `flume` features. Other
+//! implementations can be plugged by implementing [`ChannelFlavor`], [`ChannelSender`], and
+//! [`ChannelReceiver`] and creating a bus with [`channel_with`] or [`retentive_channel_with`].
+//!
+//! Messages are relayed from senders to receivers by a bus thread, that blocks on the flavor's
+//! receiver, so an idle bus doesn't consume CPU. The thread exits, once all senders or all
+//! receivers are dropped.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -36,9 +50,10 @@
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::sync::{mpsc, Arc, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock, TryLockError, Weak};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::core::utils::{Closable, Closer, RingBuffer, UniqueId};
 use crate::error::{
@@ -51,11 +66,10 @@ const POOLING_TIMEOUT: Duration = Duration::from_micros(50);
 /// <sup>`⍚` | [`sync`](crate::sync)</sup>
 /// MPMC sender.
 ///
-/// Behaves almost identical to [`mpsc::Sender`]. The latter [`mpsc`] sender can be obtained through
-/// the [`Sender::into_inner`] method.
-#[derive(Clone, Debug)]
+/// Behaves almost identical to [`mpsc::Sender`]. The underlying channel sender can be obtained
+/// through the [`Sender::into_inner`] method.
 pub struct Sender<T> {
-    inner: mpsc::Sender<T>,
+    inner: Arc<InputSender<T>>,
     state: Closable,
 }
 
@@ -63,18 +77,19 @@ pub struct Sender<T> {
 /// MPMC receiver.
 ///
 /// Behaves almost identical to [`mpsc::Receiver`] except that it can be cloned. The underlying
-/// [`mpsc`] receiver can be obtained through the [`Receiver::into_inner`] method.
+/// channel receiver can be obtained through the [`Receiver::into_inner`] method.
 ///
 /// Each cloned receiver will receive its own message.
 pub struct Receiver<T: Clone + Sync + Send + 'static> {
-    inner: mpsc::Receiver<T>,
+    inner: Box<dyn ChannelReceiver<T>>,
     guard: RecvGuard<T>,
+    pending: Pending,
 }
 
 /// <sup>`⍚` | [`sync`](crate::sync)</sup>
 /// Guards connection for the [`Receiver`].
 ///
-/// When receiver is obtained from an inner [`mpsc::Receiver`] by calling [`Receiver::into_inner`],
+/// When receiver is obtained from an inner [`ChannelReceiver`] by calling [`Receiver::into_inner`],
 /// then an instance of [`RecvGuard`] is returned. Once guard is dropped, the corresponding receiver
 /// will be disconnected from the bus.
 ///
@@ -84,6 +99,280 @@ pub struct RecvGuard<T: Clone + Sync + Send + 'static> {
     bus: Arc<BroadcastBus<T>>,
 }
 
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Member of a group of MPMC receivers.
+///
+/// Unlike [`Receiver`], each message is delivered to exactly one member of the group. Members
+/// compete for messages: the member that asks for the next message first, receives it. Groups are
+/// identified by name and created by [`Receiver::join_group`].
+///
+/// Cloning a group receiver adds a new member to the same group. The group is disconnected from the
+/// bus, once all its members are dropped.
+///
+/// # Usage
+///
+/// ```rust
+/// # #[cfg(feature = "unstable")]{
+/// use std::thread;
+/// use maviola::sync::utils::mpmc;
+///
+/// let (tx, rx) = mpmc::channel();
+/// let worker_1 = rx.join_group("workers");
+/// let worker_2 = rx.join_group("workers");
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// // Broadcast receiver gets all messages
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// assert_eq!(rx.recv().unwrap(), 2);
+///
+/// // Each message is delivered to a single group member
+/// assert_eq!(worker_1.recv().unwrap(), 1);
+/// assert_eq!(worker_2.recv().unwrap(), 2);
+/// # }
+/// ```
+pub struct GroupReceiver<T: Clone + Sync + Send + 'static> {
+    group: Arc<Group<T>>,
+}
+
+struct Group<T: Clone + Sync + Send + 'static> {
+    name: String,
+    inner: Mutex<Box<dyn ChannelReceiver<T>>>,
+    guard: RecvGuard<T>,
+    pending: Pending,
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Sending half of a channel used by MPMC bus.
+///
+/// Implemented for [`mpsc::Sender`]. Implement this trait to plug channels from other crates. See
+/// [`ChannelFlavor`] for details.
+pub trait ChannelSender<T>: Send + Sync {
+    /// Sends a value, returning it back if channel is disconnected.
+    fn send(&self, value: T) -> SendResult<T>;
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Receiving half of a channel used by MPMC bus.
+///
+/// Implemented for [`mpsc::Receiver`]. Implement this trait to plug channels from other crates.
+/// See [`ChannelFlavor`] for details.
+pub trait ChannelReceiver<T>: Send {
+    /// Blocks until a value is received or channel is disconnected.
+    fn recv(&self) -> RecvResult<T>;
+
+    /// Blocks until a value is received, channel is disconnected, or `timeout` is reached.
+    fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T>;
+
+    /// Returns a pending value without blocking.
+    fn try_recv(&self) -> TryRecvResult<T>;
+
+    /// Moves up to `max` pending values to `buffer` without blocking.
+    ///
+    /// Returns the number of moved values. Default implementation calls
+    /// [`ChannelReceiver::try_recv`] until channel is empty. Override this method, if channel
+    /// supports receiving several values at once.
+    fn try_recv_many(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        let mut received = 0;
+        while received < max {
+            match self.try_recv() {
+                Ok(value) => buffer.push(value),
+                Err(_) => break,
+            }
+            received += 1;
+        }
+        received
+    }
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Channel implementation used by MPMC bus.
+///
+/// MPMC bus relays each message from a single input channel to one channel per receiver. Flavor
+/// defines how these unbounded channels are created. Default flavor is [`StdChannel`], pass your
+/// own flavor to [`channel_with`] or [`retentive_channel_with`] to use another implementation.
+///
+/// The bus blocks on [`ChannelReceiver::recv`] of the input channel, so flavors should implement
+/// it without polling.
+///
+/// # Usage
+///
+/// Plugging a channel from another crate (a wrapper around [`mpsc`] channel is used here for
+/// brevity):
+///
+/// ```rust
+/// # #[cfg(feature = "unstable")]{
+/// use std::sync::mpsc;
+/// use std::time::Duration;
+/// use maviola::error::{RecvResult, RecvTimeoutResult, SendError, SendResult, TryRecvResult};
+/// use maviola::sync::utils::mpmc::{self, ChannelFlavor, ChannelReceiver, ChannelSender};
+///
+/// struct Wrapped;
+///
+/// struct WrappedSender<T>(mpsc::Sender<T>);
+///
+/// impl<T: Send> ChannelSender<T> for WrappedSender<T> {
+///     fn send(&self, value: T) -> SendResult<T> {
+///         self.0.send(value).map_err(|err| SendError(err.0))
+///     }
+/// }
+///
+/// struct WrappedReceiver<T>(mpsc::Receiver<T>);
+///
+/// impl<T: Send> ChannelReceiver<T> for WrappedReceiver<T> {
+///     fn recv(&self) -> RecvResult<T> {
+///         self.0.recv().map_err(Into::into)
+///     }
+///
+///     fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+///         self.0.recv_timeout(timeout).map_err(Into::into)
+///     }
+///
+///     fn try_recv(&self) -> TryRecvResult<T> {
+///         self.0.try_recv().map_err(Into::into)
+///     }
+/// }
+///
+/// impl ChannelFlavor for Wrapped {
+///     fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>) {
+///         let (tx, rx) = mpsc::channel();
+///         (Box::new(WrappedSender(tx)), Box::new(WrappedReceiver(rx)))
+///     }
+/// }
+///
+/// let (tx, rx) = mpmc::channel_with::<Wrapped, _>();
+/// tx.send(1).unwrap();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// # }
+/// ```
+pub trait ChannelFlavor: 'static {
+    /// Creates a new channel, returning the sender/receiver halves.
+    fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>);
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Default [`ChannelFlavor`] based on [`mpsc::channel`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdChannel;
+
+impl ChannelFlavor for StdChannel {
+    fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>) {
+        let (tx, rx) = mpsc::channel();
+        (Box::new(tx), Box::new(rx))
+    }
+}
+
+impl<T: Send> ChannelSender<T> for mpsc::Sender<T> {
+    fn send(&self, value: T) -> SendResult<T> {
+        mpsc::Sender::send(self, value).map_err(SendError::from)
+    }
+}
+
+impl<T: Send> ChannelReceiver<T> for mpsc::Receiver<T> {
+    fn recv(&self) -> RecvResult<T> {
+        mpsc::Receiver::recv(self).map_err(RecvError::from)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+        mpsc::Receiver::recv_timeout(self, timeout).map_err(RecvTimeoutError::from)
+    }
+
+    fn try_recv(&self) -> TryRecvResult<T> {
+        mpsc::Receiver::try_recv(self).map_err(TryRecvError::from)
+    }
+
+    fn try_recv_many(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        let len = buffer.len();
+        buffer.extend(self.try_iter().take(max));
+        buffer.len() - len
+    }
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync) | `crossbeam`</sup>
+/// [`ChannelFlavor`] based on [`crossbeam_channel::unbounded`].
+#[cfg(feature = "crossbeam")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrossbeamChannel;
+
+#[cfg(feature = "crossbeam")]
+impl ChannelFlavor for CrossbeamChannel {
+    fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>) {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        (Box::new(tx), Box::new(rx))
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T: Send> ChannelSender<T> for crossbeam_channel::Sender<T> {
+    fn send(&self, value: T) -> SendResult<T> {
+        crossbeam_channel::Sender::send(self, value).map_err(SendError::from)
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T: Send> ChannelReceiver<T> for crossbeam_channel::Receiver<T> {
+    fn recv(&self) -> RecvResult<T> {
+        crossbeam_channel::Receiver::recv(self).map_err(RecvError::from)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+        crossbeam_channel::Receiver::recv_timeout(self, timeout).map_err(RecvTimeoutError::from)
+    }
+
+    fn try_recv(&self) -> TryRecvResult<T> {
+        crossbeam_channel::Receiver::try_recv(self).map_err(TryRecvError::from)
+    }
+
+    fn try_recv_many(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        let len = buffer.len();
+        buffer.extend(self.try_iter().take(max));
+        buffer.len() - len
+    }
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync) | `flume`</sup>
+/// [`ChannelFlavor`] based on [`flume::unbounded`].
+#[cfg(feature = "flume")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FlumeChannel;
+
+#[cfg(feature = "flume")]
+impl ChannelFlavor for FlumeChannel {
+    fn unbounded<T: Send + 'static>() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>) {
+        let (tx, rx) = flume::unbounded();
+        (Box::new(tx), Box::new(rx))
+    }
+}
+
+#[cfg(feature = "flume")]
+impl<T: Send> ChannelSender<T> for flume::Sender<T> {
+    fn send(&self, value: T) -> SendResult<T> {
+        flume::Sender::send(self, value).map_err(SendError::from)
+    }
+}
+
+#[cfg(feature = "flume")]
+impl<T: Send> ChannelReceiver<T> for flume::Receiver<T> {
+    fn recv(&self) -> RecvResult<T> {
+        flume::Receiver::recv(self).map_err(RecvError::from)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+        flume::Receiver::recv_timeout(self, timeout).map_err(RecvTimeoutError::from)
+    }
+
+    fn try_recv(&self) -> TryRecvResult<T> {
+        flume::Receiver::try_recv(self).map_err(TryRecvError::from)
+    }
+
+    fn try_recv_many(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        let len = buffer.len();
+        buffer.extend(self.try_iter().take(max));
+        buffer.len() - len
+    }
+}
+
 unsafe impl<T: Send> Send for Sender<T> {}
 unsafe impl<T: Sync> Sync for Sender<T> {}
 
@@ -97,10 +386,10 @@ impl<T> Sender<T> {
             return Err(SendError(value));
         }
 
-        self.inner.send(value).map_err(SendError::from)
+        self.inner.send(value)
     }
 
-    /// Returns inner [`mpsc::Sender`].
+    /// Returns inner [`ChannelSender`].
     ///
     /// # Limitation
     ///
@@ -109,19 +398,37 @@ impl<T> Sender<T> {
     /// disconnected right before or slightly after the message was sent.
     #[must_use]
     #[allow(dead_code)]
-    pub fn into_inner(self) -> mpsc::Sender<T> {
+    pub fn into_inner(self) -> Arc<dyn ChannelSender<T>>
+    where
+        T: Send + 'static,
+    {
         self.inner
     }
 }
 
-impl<T: Clone + Sync + Send + 'static> Debug for Receiver<T> {
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Receiver")
-            .field("inner", &self.inner)
+        f.debug_struct("Sender")
+            .field("state", &self.state)
             .finish_non_exhaustive()
     }
 }
 
+impl<T: Clone + Sync + Send + 'static> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
 unsafe impl<T: Clone + Send + Sync + 'static> Send for Receiver<T> {}
 unsafe impl<T: Clone + Send + Sync + 'static> Sync for Receiver<T> {}
 unsafe impl<T: Clone + Send + Sync + 'static> Send for RecvGuard<T> {}
@@ -132,7 +439,7 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     ///
     /// Behaves identical to [`mpsc::Receiver::recv`] but returns [`RecvError`].
     pub fn recv(&self) -> RecvResult<T> {
-        self.inner.recv().map_err(RecvError::from)
+        self.pending.take(self.inner.recv())
     }
 
     /// Attempts to wait for a value on this receiver, returning an error if the
@@ -140,16 +447,34 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     ///
     /// Behaves identical to [`mpsc::Receiver::recv_timeout`] but returns [`RecvTimeoutError`].
     pub fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
-        self.inner
-            .recv_timeout(timeout)
-            .map_err(RecvTimeoutError::from)
+        self.pending.take(self.inner.recv_timeout(timeout))
     }
 
     /// Attempts to return a pending value on this receiver without blocking.
     ///
     /// Behaves identical to [`mpsc::Receiver::try_recv`] but returns [`TryRecvError`].
     pub fn try_recv(&self) -> TryRecvResult<T> {
-        self.inner.try_recv().map_err(TryRecvError::from)
+        self.pending.take(self.inner.try_recv())
+    }
+
+    /// Moves up to `max` pending values to `buffer` without blocking.
+    ///
+    /// Returns the number of moved values. Unlike calling [`Receiver::try_recv`] in a loop, values
+    /// are taken from the channel in a single batch.
+    pub fn drain_into(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        self.pending
+            .take_many(self.inner.try_recv_many(buffer, max))
+    }
+
+    /// Number of messages, that were delivered to this receiver, but not yet received.
+    pub fn len(&self) -> usize {
+        self.pending.get()
+    }
+
+    /// Returns `true`, if there are no messages waiting to be received.
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     /// Creates a new receiver subscribed to the message bus.
@@ -157,7 +482,7 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     /// If original receiver was created by [`retentive_channel`], then the new receiver will be fed
     /// with the recent events immediately after creation.
     pub fn subscribe(&self) -> Receiver<T> {
-        let (id, rx) = self.guard.bus.add(true);
+        let (id, rx, pending) = self.guard.bus.add(true);
 
         Receiver {
             inner: rx,
@@ -165,13 +490,25 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
                 id,
                 bus: self.guard.bus.clone(),
             },
+            pending,
         }
     }
 
-    /// Returns inner [`mpsc::Receiver`].
+    /// Joins a group of receivers with specified `name`.
+    ///
+    /// Each message will be delivered to exactly one member of the group. If group does not exist,
+    /// it will be created. See [`GroupReceiver`] for details.
+    pub fn join_group(&self, name: &str) -> GroupReceiver<T> {
+        GroupReceiver {
+            group: BroadcastBus::join_group(&self.guard.bus, name),
+        }
+    }
+
+    /// Returns inner [`ChannelReceiver`].
     ///
     /// Returns inner receiver and [`RecvGuard`]. When guard is dropped, the receiver will be
-    /// disconnected from the bus.
+    /// disconnected from the bus. Messages received by the inner receiver are not accounted by
+    /// [`Receiver::len`].
     ///
     /// # Usage
     ///
@@ -180,13 +517,13 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     /// ```rust
     /// # #[cfg(feature = "unstable")]{
     /// use std::thread;
-    /// use std::sync::mpsc;
+    /// use maviola::error::RecvResult;
     /// use maviola::sync::utils::mpmc;
     ///
     /// let (tx, rx) = mpmc::channel();
     /// let (rx_inner, _guard) = rx.into_inner();
     ///
-    /// let handler = thread::spawn(move || -> Result<(), mpsc::RecvError> { rx_inner.recv() });
+    /// let handler = thread::spawn(move || -> RecvResult<()> { rx_inner.recv() });
     ///
     /// assert!(tx.send(()).is_ok());
     /// assert!(handler.join().unwrap().is_ok());
@@ -198,13 +535,13 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     /// ```rust
     /// # #[cfg(feature = "unstable")]{
     /// use std::thread;
-    /// use std::sync::mpsc;
+    /// use maviola::error::RecvResult;
     /// use maviola::sync::utils::mpmc;
     ///
     /// let (tx, rx) = mpmc::channel();
     /// let (rx_inner, guard) = rx.into_inner();
     ///
-    /// let handler = { thread::spawn(move || -> Result<(), mpsc::RecvError> { rx_inner.recv() }) };
+    /// let handler = { thread::spawn(move || -> RecvResult<()> { rx_inner.recv() }) };
     ///
     /// drop(guard);
     ///
@@ -214,7 +551,7 @@ impl<T: Clone + Sync + Send + 'static> Receiver<T> {
     /// ```
     #[must_use]
     #[allow(dead_code)]
-    pub fn into_inner(self) -> (mpsc::Receiver<T>, RecvGuard<T>) {
+    pub fn into_inner(self) -> (Box<dyn ChannelReceiver<T>>, RecvGuard<T>) {
         (self.inner, self.guard)
     }
 }
@@ -251,6 +588,116 @@ impl<T: Clone + Sync + Send + 'static> Drop for RecvGuard<T> {
     }
 }
 
+impl<T: Clone + Sync + Send + 'static> GroupReceiver<T> {
+    /// Attempts to wait for a value on this receiver, returning an error if the
+    /// corresponding channel has hung up.
+    ///
+    /// Blocks while other members of the group are waiting for their messages.
+    pub fn recv(&self) -> RecvResult<T> {
+        let inner = self
+            .group
+            .inner
+            .lock()
+            .map_err(|_| RecvError::Disconnected)?;
+        self.group.pending.take(inner.recv())
+    }
+
+    /// Attempts to wait for a value on this receiver, returning an error if the
+    /// corresponding channel has hung up, or if it waits more than `timeout`.
+    ///
+    /// The time spent waiting for other members of the group counts towards `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.group.inner.try_lock() {
+                Ok(inner) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    return self.group.pending.take(inner.recv_timeout(timeout));
+                }
+                Err(TryLockError::WouldBlock) => {
+                    if Instant::now() >= deadline {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    thread::sleep(POOLING_TIMEOUT);
+                }
+                Err(TryLockError::Poisoned(_)) => return Err(RecvTimeoutError::Disconnected),
+            }
+        }
+    }
+
+    /// Attempts to return a pending value on this receiver without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if another member of the group is waiting for a message.
+    pub fn try_recv(&self) -> TryRecvResult<T> {
+        match self.group.inner.try_lock() {
+            Ok(inner) => self.group.pending.take(inner.try_recv()),
+            Err(TryLockError::WouldBlock) => Err(TryRecvError::Empty),
+            Err(TryLockError::Poisoned(_)) => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Moves up to `max` pending values to `buffer` without blocking.
+    ///
+    /// Returns the number of moved values. Values are taken from the group under a single lock, so
+    /// other members can't take messages in between. Returns `0`, if another member of the group
+    /// is waiting for a message.
+    pub fn drain_into(&self, buffer: &mut Vec<T>, max: usize) -> usize {
+        match self.group.inner.try_lock() {
+            Ok(inner) => self
+                .group
+                .pending
+                .take_many(inner.try_recv_many(buffer, max)),
+            Err(_) => 0,
+        }
+    }
+
+    /// Name of the group.
+    pub fn group(&self) -> &str {
+        self.group.name.as_str()
+    }
+
+    /// Number of messages, that were delivered to the group, but not yet received by any member.
+    pub fn len(&self) -> usize {
+        self.group.pending.get()
+    }
+
+    /// Returns `true`, if there are no messages waiting to be received by the group.
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Creates a receiver, that joined a group with specified `name`.
+    ///
+    /// See [`Receiver::join_group`].
+    pub fn join_group(&self, name: &str) -> GroupReceiver<T> {
+        GroupReceiver {
+            group: BroadcastBus::join_group(&self.group.guard.bus, name),
+        }
+    }
+}
+
+impl<T: Clone + Sync + Send + 'static> Clone for GroupReceiver<T> {
+    /// Adds a new member to the same group.
+    fn clone(&self) -> Self {
+        Self {
+            group: self.group.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Sync + Send + 'static> Debug for GroupReceiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GroupReceiver")
+            .field("group", &self.group.name)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe impl<T: Clone + Send + Sync + 'static> Send for GroupReceiver<T> {}
+unsafe impl<T: Clone + Send + Sync + 'static> Sync for GroupReceiver<T> {}
+
 /// <sup>`⍚` | [`sync`](crate::sync)</sup>
 /// Creates a new synchronous channel, returning the sender/receiver halves.
 ///
@@ -270,6 +717,18 @@ pub fn channel<T: Clone + Sync + Send + 'static>() -> (Sender<T>, Receiver<T>) {
     retentive_channel(0)
 }
 
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Creates a new synchronous channel based on the specified [`ChannelFlavor`].
+///
+/// Behaves identical to [`channel`], except that messages are passed through channels created by
+/// `F` instead of [`mpsc`] channels.
+#[must_use]
+#[inline(always)]
+pub fn channel_with<F: ChannelFlavor, T: Clone + Sync + Send + 'static>() -> (Sender<T>, Receiver<T>)
+{
+    retentive_channel_with::<F, T>(0)
+}
+
 /// <sup>`⍚` | [`sync`](crate::sync)</sup>
 /// Creates a new synchronous channel with retention, returning the sender/receiver halves.
 ///
@@ -285,26 +744,42 @@ pub fn channel<T: Clone + Sync + Send + 'static>() -> (Sender<T>, Receiver<T>) {
 /// which data will be broadcast. Upon cloning, the new receiver will be fed with recent events
 /// specified by `depth`.
 #[must_use]
+#[inline(always)]
 pub fn retentive_channel<T: Clone + Sync + Send + 'static>(
     depth: usize,
 ) -> (Sender<T>, Receiver<T>) {
-    let (send_tx, send_rx) = mpsc::channel();
+    retentive_channel_with::<StdChannel, T>(depth)
+}
+
+/// <sup>`⍚` | [`sync`](crate::sync)</sup>
+/// Creates a new synchronous channel with retention based on the specified [`ChannelFlavor`].
+///
+/// Behaves identical to [`retentive_channel`], except that messages are passed through channels
+/// created by `F` instead of [`mpsc`] channels.
+#[must_use]
+pub fn retentive_channel_with<F: ChannelFlavor, T: Clone + Sync + Send + 'static>(
+    depth: usize,
+) -> (Sender<T>, Receiver<T>) {
+    let (send_tx, send_rx) = F::unbounded();
     let state = Closer::new();
 
     let sender = Sender {
-        inner: send_tx,
+        inner: Arc::new(InputSender(Arc::from(send_tx))),
         state: state.to_closable(),
     };
 
     let receiver = {
         let bus = BroadcastBus {
             recv_txs: Default::default(),
+            groups: Default::default(),
             recent: Arc::new(RwLock::new(RingBuffer::new(depth))),
             depth,
+            unbounded: F::unbounded,
+            input: Arc::downgrade(&sender.inner),
             _state: state,
         };
 
-        let (id, rx) = bus.add(false);
+        let (id, rx, pending) = bus.add(false);
         let bus = Arc::new(bus);
 
         let receiver = Receiver {
@@ -313,6 +788,7 @@ pub fn retentive_channel<T: Clone + Sync + Send + 'static>(
                 id,
                 bus: bus.clone(),
             },
+            pending,
         };
 
         bus.start(send_rx);
@@ -327,28 +803,80 @@ pub fn retentive_channel<T: Clone + Sync + Send + 'static>(
 //                                 Private                                   //
 ///////////////////////////////////////////////////////////////////////////////
 
-struct BroadcastBus<T> {
-    recv_txs: Arc<RwLock<HashMap<UniqueId, mpsc::Sender<T>>>>,
+type UnboundedChannel<T> = fn() -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>);
+
+/// Input channel of a bus.
+///
+/// Carries messages wrapped into `Some`, while `None` wakes up the relay thread to stop it.
+struct InputSender<T>(Arc<dyn ChannelSender<Option<T>>>);
+
+impl<T> InputSender<T> {
+    fn wake(&self) {
+        let _ = self.0.send(None);
+    }
+}
+
+impl<T> ChannelSender<T> for InputSender<T> {
+    fn send(&self, value: T) -> SendResult<T> {
+        self.0
+            .send(Some(value))
+            .map_err(|err| SendError(err.0.expect("sent value is returned back")))
+    }
+}
+type ReceiverSenders<T> = Arc<RwLock<HashMap<UniqueId, (Box<dyn ChannelSender<T>>, Pending)>>>;
+
+/// Counts messages delivered to a receiver, but not yet received.
+///
+/// Counter is incremented before a message is sent to a receiver channel, so it never underflows.
+#[derive(Clone, Default)]
+struct Pending(Arc<AtomicUsize>);
+
+impl Pending {
+    fn push(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn take<R, E>(&self, result: Result<R, E>) -> Result<R, E> {
+        if result.is_ok() {
+            self.0.fetch_sub(1, Ordering::AcqRel);
+        }
+        result
+    }
+
+    fn take_many(&self, n: usize) -> usize {
+        if n > 0 {
+            self.0.fetch_sub(n, Ordering::AcqRel);
+        }
+        n
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+struct BroadcastBus<T: Clone + Sync + Send + 'static> {
+    recv_txs: ReceiverSenders<T>,
+    groups: Mutex<HashMap<String, Weak<Group<T>>>>,
     recent: Arc<RwLock<RingBuffer<T>>>,
     depth: usize,
+    unbounded: UnboundedChannel<T>,
+    input: Weak<InputSender<T>>,
     _state: Closer,
 }
 
 impl<T: Clone + Sync + Send + 'static> BroadcastBus<T> {
-    fn start(&self, send_rx: mpsc::Receiver<T>) {
+    fn start(&self, send_rx: Box<dyn ChannelReceiver<Option<T>>>) {
         let recv_txs = self.recv_txs.clone();
         let latest = self.recent.clone();
         let depth = self.depth;
 
+        // The thread is parked until a message arrives. It exits, once all senders are gone, or
+        // when it is woken up by a dropped bus.
         thread::spawn(move || loop {
-            if recv_txs.read().unwrap().is_empty() {
-                return;
-            }
-
-            let data = match send_rx.recv_timeout(POOLING_TIMEOUT) {
-                Ok(data) => data,
-                Err(mpsc::RecvTimeoutError::Timeout) => continue,
-                Err(mpsc::RecvTimeoutError::Disconnected) => {
+            let data = match send_rx.recv() {
+                Ok(Some(data)) => data,
+                Ok(None) | Err(_) => {
                     let mut recv_txs = recv_txs.write().unwrap();
                     recv_txs.clear();
                     return;
@@ -369,11 +897,20 @@ impl<T: Clone + Sync + Send + 'static> BroadcastBus<T> {
                 let mut failed_recv_tx_ids = Vec::new();
 
                 {
-                    for (id, recv_tx) in recv_txs.iter() {
-                        let recv_tx = recv_tx.clone();
-                        let data = data.clone();
-                        if recv_tx.send(data).is_err() {
-                            failed_recv_tx_ids.push(*id);
+                    // The last receiver takes the original message, so a bus with a single
+                    // receiver never clones messages.
+                    let mut data = Some(data);
+                    let mut recv_txs = recv_txs.iter().peekable();
+                    while let Some((id, (recv_tx, pending))) = recv_txs.next() {
+                        let data = match recv_txs.peek() {
+                            Some(_) => data.clone(),
+                            None => data.take(),
+                        };
+                        if let Some(data) = data {
+                            pending.push();
+                            if recv_tx.send(data).is_err() {
+                                failed_recv_tx_ids.push(*id);
+                            }
                         }
                     }
                 }
@@ -390,13 +927,15 @@ impl<T: Clone + Sync + Send + 'static> BroadcastBus<T> {
         });
     }
 
-    fn add(&self, push_recent: bool) -> (UniqueId, mpsc::Receiver<T>) {
-        let (recv_tx, recv_rx) = mpsc::channel();
+    fn add(&self, push_recent: bool) -> (UniqueId, Box<dyn ChannelReceiver<T>>, Pending) {
+        let (recv_tx, recv_rx) = (self.unbounded)();
         let id = UniqueId::new();
+        let pending = Pending::default();
 
         if push_recent && self.depth > 0 {
             let recent = self.recent.read().unwrap();
             for msg in recent.iter() {
+                pending.push();
                 if recv_tx.send(msg.clone()).is_err() {
                     break;
                 }
@@ -405,22 +944,51 @@ impl<T: Clone + Sync + Send + 'static> BroadcastBus<T> {
 
         {
             let mut recv_txs = self.recv_txs.write().unwrap();
-            recv_txs.insert(id, recv_tx);
+            recv_txs.insert(id, (recv_tx, pending.clone()));
         }
 
-        (id, recv_rx)
+        (id, recv_rx, pending)
     }
 
     fn remove(&self, id: &UniqueId) {
         let mut recv_txs = self.recv_txs.write().unwrap();
         recv_txs.remove(id);
     }
+
+    fn join_group(bus: &Arc<Self>, name: &str) -> Arc<Group<T>> {
+        let mut groups = bus.groups.lock().unwrap();
+        groups.retain(|_, group| group.strong_count() > 0);
+
+        if let Some(group) = groups.get(name).and_then(Weak::upgrade) {
+            return group;
+        }
+
+        let (id, rx, pending) = bus.add(true);
+        let group = Arc::new(Group {
+            name: name.to_string(),
+            inner: Mutex::new(rx),
+            guard: RecvGuard {
+                id,
+                bus: bus.clone(),
+            },
+            pending,
+        });
+        groups.insert(name.to_string(), Arc::downgrade(&group));
+
+        group
+    }
 }
 
-impl<T> Drop for BroadcastBus<T> {
+impl<T: Clone + Sync + Send + 'static> Drop for BroadcastBus<T> {
     fn drop(&mut self) {
-        let mut recv_txs = self.recv_txs.write().unwrap();
-        recv_txs.clear();
+        {
+            let mut recv_txs = self.recv_txs.write().unwrap();
+            recv_txs.clear();
+        }
+
+        if let Some(input) = self.input.upgrade() {
+            input.wake();
+        }
     }
 }
 
@@ -467,6 +1035,55 @@ mod tests {
         rx_2.try_recv().unwrap();
     }
 
+    #[test]
+    fn mpmc_len_counts_pending_messages() {
+        let (tx, rx) = channel();
+        let rx_other = rx.clone();
+        let group = rx.join_group("workers");
+
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        wait();
+
+        assert_eq!(rx.len(), 2);
+        assert_eq!(rx_other.len(), 2);
+        assert_eq!(group.len(), 2);
+
+        rx.recv().unwrap();
+        group.try_recv().unwrap();
+        assert_eq!(rx.len(), 1);
+        assert_eq!(rx_other.len(), 2);
+        assert_eq!(group.len(), 1);
+
+        rx.try_recv().unwrap();
+        assert!(rx.is_empty());
+        assert!(rx.try_recv().is_err());
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn mpmc_drain_into_takes_batch() {
+        let (tx, rx) = channel();
+        let group = rx.join_group("workers");
+
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+        wait();
+
+        let mut buffer = Vec::new();
+        assert_eq!(rx.drain_into(&mut buffer, 3), 3);
+        assert_eq!(rx.drain_into(&mut buffer, 10), 2);
+        assert_eq!(rx.drain_into(&mut buffer, 10), 0);
+        assert_eq!(buffer, vec![0, 1, 2, 3, 4]);
+        assert_eq!(rx.len(), 0);
+
+        let mut buffer = Vec::new();
+        assert_eq!(group.drain_into(&mut buffer, 4), 4);
+        assert_eq!(buffer, vec![0, 1, 2, 3]);
+        assert_eq!(group.len(), 1);
+    }
+
     #[test]
     fn mpmc_close_on_sender_dropped() {
         let (tx, rx) = channel();
@@ -536,7 +1153,7 @@ mod tests {
         let (tx, rx) = channel();
         let (rx_inner, _guard) = rx.into_inner();
 
-        let handler = thread::spawn(move || -> Result<(), mpsc::RecvError> { rx_inner.recv() });
+        let handler = thread::spawn(move || -> RecvResult<()> { rx_inner.recv() });
 
         assert!(tx.send(()).is_ok());
         assert!(handler.join().unwrap().is_ok());
@@ -547,7 +1164,7 @@ mod tests {
         let (tx, rx) = channel();
         let (rx_inner, guard) = rx.into_inner();
 
-        let handler = { thread::spawn(move || -> Result<(), mpsc::RecvError> { rx_inner.recv() }) };
+        let handler = { thread::spawn(move || -> RecvResult<()> { rx_inner.recv() }) };
 
         drop(guard);
 
@@ -555,6 +1172,168 @@ mod tests {
         assert!(handler.join().unwrap().is_err());
     }
 
+    #[test]
+    fn group_members_receive_each_message_once() {
+        let (tx, rx) = channel();
+        let workers: Vec<_> = (0..3).map(|_| rx.join_group("workers")).collect();
+        let other = rx.join_group("other");
+
+        let handlers: Vec<_> = workers
+            .into_iter()
+            .map(|worker| {
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    while let Ok(value) = worker.recv_timeout(WAIT_LONG_DURATION) {
+                        received.push(value);
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        for i in 0..100 {
+            tx.send(i).unwrap();
+        }
+
+        let mut received: Vec<usize> = handlers
+            .into_iter()
+            .flat_map(|handler| handler.join().unwrap())
+            .collect();
+        received.sort();
+
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+        assert_eq!(other.recv_timeout(RECV_TIMEOUT).unwrap(), 0);
+        assert_eq!(rx.recv_timeout(RECV_TIMEOUT).unwrap(), 0);
+    }
+
+    #[test]
+    fn group_disconnects_when_members_are_dropped() {
+        let (tx, rx) = channel();
+        let worker = rx.join_group("workers");
+        drop(rx);
+
+        assert!(tx.send(1).is_ok());
+        assert_eq!(worker.clone().recv().unwrap(), 1);
+
+        drop(worker);
+        assert!(tx.send(2).is_err());
+    }
+
+    #[test]
+    fn messages_are_cloned_only_for_extra_receivers() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counted(Arc<AtomicUsize>);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Self(self.0.clone())
+            }
+        }
+
+        let clones = Arc::new(AtomicUsize::new(0));
+
+        let (tx, rx) = channel();
+        tx.send(Counted(clones.clone())).unwrap();
+        rx.recv_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(clones.load(Ordering::SeqCst), 0);
+
+        let rx_2 = rx.clone();
+        tx.send(Counted(clones.clone())).unwrap();
+        rx.recv_timeout(WAIT_LONG_DURATION).unwrap();
+        rx_2.recv_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(clones.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn relay_stops_when_receivers_dropped() {
+        static ALIVE: AtomicUsize = AtomicUsize::new(0);
+
+        struct Tracked;
+
+        struct TrackedReceiver<T>(mpsc::Receiver<T>);
+
+        impl<T: Send> ChannelReceiver<T> for TrackedReceiver<T> {
+            fn recv(&self) -> RecvResult<T> {
+                self.0.recv().map_err(Into::into)
+            }
+
+            fn recv_timeout(&self, timeout: Duration) -> RecvTimeoutResult<T> {
+                self.0.recv_timeout(timeout).map_err(Into::into)
+            }
+
+            fn try_recv(&self) -> TryRecvResult<T> {
+                self.0.try_recv().map_err(Into::into)
+            }
+        }
+
+        impl<T> Drop for TrackedReceiver<T> {
+            fn drop(&mut self) {
+                ALIVE.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        impl ChannelFlavor for Tracked {
+            fn unbounded<T: Send + 'static>(
+            ) -> (Box<dyn ChannelSender<T>>, Box<dyn ChannelReceiver<T>>) {
+                let (tx, rx) = mpsc::channel();
+                ALIVE.fetch_add(1, Ordering::SeqCst);
+                (Box::new(tx), Box::new(TrackedReceiver(rx)))
+            }
+        }
+
+        let (tx, rx) = channel_with::<Tracked, usize>();
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(ALIVE.load(Ordering::SeqCst), 2);
+
+        // Relay thread releases its input channel, while sender is still alive
+        drop(rx);
+        wait_long();
+        assert_eq!(ALIVE.load(Ordering::SeqCst), 0);
+        assert!(tx.send(2).is_err());
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn crossbeam_flavor() {
+        check_flavor::<CrossbeamChannel>();
+    }
+
+    #[cfg(feature = "flume")]
+    #[test]
+    fn flume_flavor() {
+        check_flavor::<FlumeChannel>();
+    }
+
+    #[cfg(any(feature = "crossbeam", feature = "flume"))]
+    fn check_flavor<F: ChannelFlavor>() {
+        let (tx, rx) = channel_with::<F, usize>();
+        let rx_2 = rx.clone();
+        let group = rx.join_group("workers");
+
+        for i in 0..4 {
+            tx.send(i).unwrap();
+        }
+        assert_eq!(rx.recv_timeout(WAIT_LONG_DURATION).unwrap(), 0);
+        assert_eq!(rx_2.recv_timeout(WAIT_LONG_DURATION).unwrap(), 0);
+        assert_eq!(group.recv_timeout(WAIT_LONG_DURATION).unwrap(), 0);
+
+        wait_long();
+        let mut buffer = Vec::new();
+        assert_eq!(rx.drain_into(&mut buffer, 10), 3);
+        assert_eq!(group.drain_into(&mut buffer, 10), 3);
+        assert_eq!(buffer, vec![1, 2, 3, 1, 2, 3]);
+
+        drop(tx);
+        assert!(rx_2.recv_timeout(WAIT_LONG_DURATION).is_ok());
+        wait_long();
+        assert!(rx_2.try_recv().is_ok());
+        assert!(rx_2.try_recv().is_ok());
+        assert!(matches!(rx_2.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+
     // The duration should be long enough to test on slow machines, when running tests in parallel
     // (like in the case of CI)
     const WAIT_DURATION: Duration = Duration::from_millis(10);
@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::thread::{self, JoinHandle};
+
+use crate::core::utils::trace::Span;
+use crate::core::utils::ThreadSettings;
+use crate::sync::io::IoPool;
+
+thread_local! {
+    static IO_THREADS: RefCell<Option<ThreadSettings>> = const { RefCell::new(None) };
+    static IO_POOL: RefCell<Option<IoPool>> = const { RefCell::new(None) };
+}
+
+/// <sup>⛔</sup>
+/// Runs `f` with `settings` for all I/O threads spawned within it.
+///
+/// Settings are inherited by threads spawned by [`spawn_io`], so I/O threads spawned later (for
+/// example, for clients accepted by a server) will use the same settings. If `settings` are
+/// [`None`], then settings of the current thread are kept.
+pub(crate) fn with_io_threads<T>(settings: Option<&ThreadSettings>, f: impl FnOnce() -> T) -> T {
+    let settings = match settings {
+        Some(settings) => settings.clone(),
+        None => return f(),
+    };
+
+    let previous = IO_THREADS.with(|io| io.replace(Some(settings)));
+    let result = f();
+    IO_THREADS.with(|io| io.replace(previous));
+    result
+}
+
+/// <sup>⛔</sup>
+/// Runs `f` with I/O `pool` available to all channels spawned within it.
+///
+/// Pool is inherited by threads spawned by [`spawn_io`] the same way as settings defined by
+/// [`with_io_threads`]. If `pool` is [`None`], then pool of the current thread is kept.
+pub(crate) fn with_io_pool<T>(pool: Option<&IoPool>, f: impl FnOnce() -> T) -> T {
+    let pool = match pool {
+        Some(pool) => pool.clone(),
+        None => return f(),
+    };
+
+    let previous = IO_POOL.with(|io| io.replace(Some(pool)));
+    let result = f();
+    IO_POOL.with(|io| io.replace(previous));
+    result
+}
+
+/// <sup>⛔</sup>
+/// I/O pool defined by [`with_io_pool`] for the current thread.
+pub(crate) fn io_pool() -> Option<IoPool> {
+    IO_POOL.with(|io| io.borrow().clone())
+}
+
+/// <sup>⛔</sup>
+/// Spawns an I/O thread with settings defined by [`with_io_threads`] and pool defined by
+/// [`with_io_pool`].
+///
+/// Spawned thread runs within the current tracing span.
+pub(crate) fn spawn_io<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let settings = IO_THREADS.with(|io| io.borrow().clone());
+    let pool = io_pool();
+    let span = Span::current();
+
+    thread::spawn(move || {
+        if let Some(settings) = &settings {
+            apply(settings);
+        }
+        IO_THREADS.with(|io| io.replace(settings));
+        IO_POOL.with(|io| io.replace(pool));
+        span.in_scope(f)
+    })
+}
+
+/// <sup>⛔</sup>
+/// Spawns an I/O pool worker with settings defined by [`with_io_threads`].
+///
+/// Unlike [`spawn_io`], the pool is not inherited, otherwise workers would keep their own pool
+/// alive.
+pub(crate) fn spawn_io_worker<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let settings = IO_THREADS.with(|io| io.borrow().clone());
+
+    thread::spawn(move || {
+        if let Some(settings) = &settings {
+            apply(settings);
+        }
+        IO_THREADS.with(|io| io.replace(settings));
+        f()
+    })
+}
+
+/// <sup>⛔</sup>
+/// Spawns a thread with specified `settings`.
+///
+/// Spawned thread runs within the current tracing span.
+pub(crate) fn spawn_with<F, T>(settings: Option<&ThreadSettings>, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let settings = settings.cloned();
+    let span = Span::current();
+
+    thread::spawn(move || {
+        if let Some(settings) = &settings {
+            apply(settings);
+        }
+        span.in_scope(f)
+    })
+}
+
+fn apply(settings: &ThreadSettings) {
+    if let Err(err) = settings.apply() {
+        log::warn!("unable to apply thread settings {settings:?}: {err:?}");
+    }
+}
+
+#[cfg(test)]
+mod threads_tests {
+    use super::*;
+
+    #[test]
+    fn io_thread_settings_are_inherited() {
+        let settings = ThreadSettings::new().with_core_affinity([0]);
+
+        let inherited = with_io_threads(Some(&settings), || {
+            spawn_io(|| spawn_io(|| IO_THREADS.with(|io| io.borrow().clone())).join())
+        })
+        .join()
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(inherited, Some(settings));
+        assert!(IO_THREADS.with(|io| io.borrow().is_none()));
+    }
+
+    #[test]
+    fn io_pool_is_inherited() {
+        let pool = IoPool::new(1);
+
+        let inherited = with_io_pool(Some(&pool), || spawn_io(io_pool).join().unwrap()).unwrap();
+
+        assert_eq!(inherited.size(), 1);
+        assert!(io_pool().is_none());
+    }
+}
@@ -1,3 +1,16 @@
+//! # Testing utils
+//!
+//! Tools for integration tests of applications built with Maviola and for documentation examples.
+//! Available under the `test_utils` feature flag, that should not be enabled in production builds.
+//!
+//! * [`simulated`] provides [`SimulatedLink`](simulated::SimulatedLink), a connection, that
+//!   emulates an unreliable radio link with conditions adjustable at runtime.
+//! * [`topology`] builds several interconnected in-process nodes.
+//! * [`smalltalk`] is an ad-hoc dialect used in documentation.
+
+pub mod simulated;
+pub mod topology;
+
 pub mod smalltalk {
     //! # SmallTalk Ad-hoc Dialect
     //!
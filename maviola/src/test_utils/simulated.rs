@@ -0,0 +1,725 @@
+//! # Simulated links
+//!
+//! [`SimulatedLink`] is an in-memory connection, that emulates an unreliable radio link between two
+//! nodes. Frame loss, latency distribution, reordering, and bandwidth are defined by
+//! [`LinkConditions`] and can be changed at runtime, while nodes are communicating. This allows to
+//! test retries, deduplication, and timeouts of applications without real radios.
+//!
+//! Links are created in pairs. Each end of a pair is a connection builder for both synchronous and
+//! asynchronous nodes, conditions of each end are applied to frames sent from this end.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use maviola::dialects::minimal::messages::Heartbeat;
+//! use maviola::test_utils::simulated::{Latency, LinkConditions, SimulatedLink};
+//!
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let (gcs_link, vehicle_link) = SimulatedLink::pair(
+//!     LinkConditions::perfect().with_latency(Latency::uniform(
+//!         Duration::from_millis(5),
+//!         Duration::from_millis(15),
+//!     )),
+//! );
+//!
+//! let gcs = Node::sync::<V2>().id(MavLinkId::new(1, 1))
+//!     .connection(gcs_link.clone())
+//!     .build().unwrap();
+//! let vehicle = Node::sync::<V2>().id(MavLinkId::new(2, 1))
+//!     .connection(vehicle_link)
+//!     .build().unwrap();
+//!
+//! gcs.send(&Heartbeat::default()).unwrap();
+//! vehicle.recv_frame_timeout(Duration::from_millis(100)).unwrap();
+//!
+//! // Radio goes down
+//! gcs_link.set_conditions(LinkConditions::lossy(1.0));
+//! gcs.send(&Heartbeat::default()).unwrap();
+//! assert!(vehicle.recv_frame_timeout(Duration::from_millis(50)).is_err());
+//! assert_eq!(gcs_link.stats().dropped(), 1);
+//! ```
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::f64::consts::PI;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::asnc::io as asnc_io;
+use crate::asnc::marker::AsyncConnConf;
+use crate::asnc::runtime;
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionDetails, ConnectionInfo};
+use crate::core::io::{ConnectionConf, IncomingFrame};
+use crate::core::utils::SharedCloser;
+use crate::error::RecvTimeoutError;
+use crate::sync::io as sync_io;
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+use crate::test_utils::topology::{LinkRng, LinkStats, DEFAULT_LINK_SEED};
+
+use crate::prelude::*;
+
+const SIMULATED_CONN_NAME: &str = "simulated";
+
+/// How often link threads and tasks check for due frames and whether connection is closed.
+const SIMULATED_LINK_POOLING_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Distribution of a delay, after which a frame is delivered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Latency {
+    /// The same delay for all frames.
+    Fixed(Duration),
+    /// Delay uniformly distributed between `min` and `max`.
+    Uniform {
+        /// Minimum delay.
+        min: Duration,
+        /// Maximum delay.
+        max: Duration,
+    },
+    /// Normally distributed delay. Negative samples are clamped to zero.
+    Normal {
+        /// Mean delay.
+        mean: Duration,
+        /// Standard deviation.
+        std_dev: Duration,
+    },
+}
+
+impl Default for Latency {
+    fn default() -> Self {
+        Self::Fixed(Duration::ZERO)
+    }
+}
+
+impl Latency {
+    /// Delay uniformly distributed between `min` and `max`.
+    pub fn uniform(min: Duration, max: Duration) -> Self {
+        Self::Uniform {
+            min: min.min(max),
+            max: max.max(min),
+        }
+    }
+
+    /// Normally distributed delay with specified `mean` and standard deviation `std_dev`.
+    pub fn normal(mean: Duration, std_dev: Duration) -> Self {
+        Self::Normal { mean, std_dev }
+    }
+
+    fn sample(&self, rng: &mut LinkRng) -> Duration {
+        match *self {
+            Latency::Fixed(latency) => latency,
+            Latency::Uniform { min, max } => min + (max - min).mul_f64(rng.random()),
+            Latency::Normal { mean, std_dev } => {
+                // Box-Muller transform
+                let u1 = 1.0 - rng.random();
+                let u2 = rng.random();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+                let secs = mean.as_secs_f64() + z * std_dev.as_secs_f64();
+                Duration::from_secs_f64(secs.max(0.0))
+            }
+        }
+    }
+}
+
+/// Conditions of a [`SimulatedLink`] in one direction.
+///
+/// Frames are delivered in the order they were sent, even if [`Latency`] has a spread. Use
+/// [`with_reordering`](Self::with_reordering) to let frames overtake each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkConditions {
+    loss: f64,
+    latency: Latency,
+    reordering: f64,
+    reordering_delay: Duration,
+    bandwidth: Option<u64>,
+}
+
+impl LinkConditions {
+    /// Link, that delivers all frames without delay.
+    pub fn perfect() -> Self {
+        Self::default()
+    }
+
+    /// Link, that loses frames with probability `loss` between `0.0` and `1.0`.
+    pub fn lossy(loss: f64) -> Self {
+        Self::perfect().with_loss(loss)
+    }
+
+    /// Sets probability of frame loss between `0.0` and `1.0`.
+    pub fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets distribution of delay, after which frames are delivered.
+    pub fn with_latency(mut self, latency: Latency) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Holds back frames with `probability` between `0.0` and `1.0` for an additional `delay`.
+    ///
+    /// Frames sent during this `delay` overtake the held back frame.
+    pub fn with_reordering(mut self, probability: f64, delay: Duration) -> Self {
+        self.reordering = probability.clamp(0.0, 1.0);
+        self.reordering_delay = delay;
+        self
+    }
+
+    /// Limits throughput of the link to specified number of `bytes_per_second`.
+    ///
+    /// Frames, that exceed the throughput, are queued and wait for their turn to be transmitted.
+    pub fn with_bandwidth(mut self, bytes_per_second: u64) -> Self {
+        self.bandwidth = Some(bytes_per_second.max(1));
+        self
+    }
+
+    /// Removes throughput limit.
+    pub fn with_unlimited_bandwidth(mut self) -> Self {
+        self.bandwidth = None;
+        self
+    }
+
+    /// Probability of frame loss.
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+
+    /// Distribution of delay, after which frames are delivered.
+    pub fn latency(&self) -> Latency {
+        self.latency
+    }
+
+    /// Probability of frame being held back.
+    pub fn reordering(&self) -> f64 {
+        self.reordering
+    }
+
+    /// Additional delay of held back frames.
+    pub fn reordering_delay(&self) -> Duration {
+        self.reordering_delay
+    }
+
+    /// Throughput limit in bytes per second.
+    pub fn bandwidth(&self) -> Option<u64> {
+        self.bandwidth
+    }
+}
+
+/// End of a simulated link between two nodes.
+///
+/// Created in pairs by [`SimulatedLink::pair`]. Clones share the same link, so a clone can be kept
+/// to change [conditions](Self::set_conditions) and read [stats](Self::stats) after the original
+/// was passed to a node.
+///
+/// Each end can be built into a connection of a synchronous or asynchronous node. Connection has a
+/// single channel, that exchanges frames with the other end. Frames sent before the other end is
+/// built are delivered, once it is built.
+///
+/// See [module](self) documentation for details.
+pub struct SimulatedLink<V: MaybeVersioned> {
+    info: ConnectionInfo,
+    outgoing: Arc<LinkDirection<V>>,
+    incoming: Arc<LinkDirection<V>>,
+}
+
+impl<V: MaybeVersioned> SimulatedLink<V> {
+    /// Creates a pair of linked ends with the same `conditions` in both directions.
+    pub fn pair(conditions: LinkConditions) -> (Self, Self) {
+        Self::pair_seeded(conditions, DEFAULT_LINK_SEED)
+    }
+
+    /// Creates a pair of linked ends with a `seed` for pseudo-random faults.
+    ///
+    /// Faults are reproducible for the same seed as long as frames are sent in the same order.
+    pub fn pair_seeded(conditions: LinkConditions, seed: u64) -> (Self, Self) {
+        let a_to_b = Arc::new(LinkDirection::new(conditions, seed));
+        let b_to_a = Arc::new(LinkDirection::new(conditions, seed.wrapping_add(1)));
+
+        let end =
+            |name: &str, outgoing: &Arc<LinkDirection<V>>, incoming: &Arc<LinkDirection<V>>| Self {
+                info: ConnectionInfo::new(ConnectionDetails::Custom {
+                    name: SIMULATED_CONN_NAME.to_string(),
+                    details: name.to_string(),
+                }),
+                outgoing: outgoing.clone(),
+                incoming: incoming.clone(),
+            };
+
+        (end("a", &a_to_b, &b_to_a), end("b", &b_to_a, &a_to_b))
+    }
+
+    /// Conditions applied to frames sent from this end.
+    pub fn conditions(&self) -> LinkConditions {
+        self.outgoing.conditions()
+    }
+
+    /// Changes conditions applied to frames sent from this end.
+    ///
+    /// Frames, that are already in flight, are not affected.
+    pub fn set_conditions(&self, conditions: LinkConditions) {
+        match self.outgoing.conditions.write() {
+            Ok(mut current) => *current = conditions,
+            Err(err) => *err.into_inner() = conditions,
+        }
+    }
+
+    /// Counters of frames sent from this end.
+    pub fn stats(&self) -> &LinkStats {
+        &self.outgoing.stats
+    }
+
+    /// Number of frames sent from this end, that are not yet delivered.
+    pub fn in_flight(&self) -> usize {
+        self.outgoing.queue().pending.len()
+    }
+
+    fn make_channel_info(&self) -> ChannelInfo {
+        self.info.make_channel_info(ChannelDetails::Custom {
+            conn_name: SIMULATED_CONN_NAME.to_string(),
+            channel_name: match self.info.details() {
+                ConnectionDetails::Custom { details, .. } => details.clone(),
+                _ => String::new(),
+            },
+            details: format!("{:?}", self.conditions()),
+        })
+    }
+}
+
+impl<V: MaybeVersioned> Clone for SimulatedLink<V> {
+    fn clone(&self) -> Self {
+        Self {
+            info: self.info.clone(),
+            outgoing: self.outgoing.clone(),
+            incoming: self.incoming.clone(),
+        }
+    }
+}
+
+impl<V: MaybeVersioned> Debug for SimulatedLink<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatedLink")
+            .field("info", &self.info)
+            .field("conditions", &self.conditions())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionConf for SimulatedLink<V> {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
+
+impl<V: MaybeVersioned> sync_io::ConnectionBuilder<V> for SimulatedLink<V> {
+    fn build(&self) -> Result<(sync_io::Connection<V>, sync_io::ConnectionHandler)> {
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = sync_io::Connection::new(self.info.clone(), state.clone());
+        let chan_info = self.make_channel_info();
+
+        let (outgoing, send_handler) = (self.outgoing.clone(), chan_factory.send_handler().clone());
+        let (closable, info) = (state.to_closable(), chan_info.clone());
+        spawn_io(move || {
+            while !closable.is_closed() {
+                match send_handler.recv_timeout(SIMULATED_LINK_POOLING_INTERVAL) {
+                    Ok(frame) => outgoing.transmit_to(&info, frame),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                    Err(_) => continue,
+                }
+            }
+            log::trace!("[{info:?}] simulated link writer stopped");
+        });
+
+        let (incoming, producer) = (self.incoming.clone(), chan_factory.producer().clone());
+        let (closable, info) = (state.to_closable(), chan_info);
+        spawn_io(move || {
+            'reader: while !closable.is_closed() {
+                for frame in incoming.take_due(Instant::now()) {
+                    if producer
+                        .send(IncomingFrame::new(frame, info.clone()))
+                        .is_err()
+                    {
+                        break 'reader;
+                    }
+                }
+                thread::sleep(incoming.wait_interval());
+            }
+            log::trace!("[{info:?}] simulated link reader stopped");
+        });
+
+        Ok((
+            connection,
+            sync_io::ConnectionHandler::spawn_from_state(state),
+        ))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+}
+
+impl<V: MaybeVersioned> asnc_io::ConnectionBuilder<V> for SimulatedLink<V> {
+    async fn build(&self) -> Result<(asnc_io::Connection<V>, asnc_io::ConnectionHandler)> {
+        let state = SharedCloser::new();
+        let (connection, mut chan_factory) =
+            asnc_io::Connection::new(self.info.clone(), state.clone());
+        let chan_info = self.make_channel_info();
+
+        let (outgoing, mut send_handler) =
+            (self.outgoing.clone(), chan_factory.send_handler().clone());
+        let (closable, info) = (state.to_closable(), chan_info.clone());
+        runtime::spawn(async move {
+            while !closable.is_closed() {
+                match send_handler
+                    .recv_timeout(SIMULATED_LINK_POOLING_INTERVAL)
+                    .await
+                {
+                    Ok(frame) => outgoing.transmit_to(&info, frame),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                    Err(_) => continue,
+                }
+            }
+            log::trace!("[{info:?}] simulated link writer stopped");
+        });
+
+        let (incoming, producer) = (self.incoming.clone(), chan_factory.producer().clone());
+        let (closable, info) = (state.to_closable(), chan_info);
+        runtime::spawn(async move {
+            'reader: while !closable.is_closed() {
+                for frame in incoming.take_due(Instant::now()) {
+                    if producer
+                        .send(IncomingFrame::new(frame, info.clone()))
+                        .is_err()
+                    {
+                        break 'reader;
+                    }
+                }
+                runtime::sleep(incoming.wait_interval()).await;
+            }
+            log::trace!("[{info:?}] simulated link reader stopped");
+        });
+
+        Ok((
+            connection,
+            asnc_io::ConnectionHandler::spawn_from_state(state),
+        ))
+    }
+
+    fn to_conf(&self) -> AsyncConnConf<V> {
+        AsyncConnConf::new(self.clone())
+    }
+}
+
+/// One direction of a simulated link.
+struct LinkDirection<V: MaybeVersioned> {
+    conditions: RwLock<LinkConditions>,
+    queue: Mutex<LinkQueue<V>>,
+    stats: LinkStats,
+}
+
+/// Frames in flight and state of the pseudo-random faults.
+struct LinkQueue<V: MaybeVersioned> {
+    rng: LinkRng,
+    pending: BinaryHeap<Reverse<InFlight<V>>>,
+    seq: u64,
+    last_in_order: Instant,
+    wire_free_at: Instant,
+}
+
+impl<V: MaybeVersioned> LinkDirection<V> {
+    fn new(conditions: LinkConditions, seed: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            conditions: RwLock::new(conditions),
+            queue: Mutex::new(LinkQueue {
+                rng: LinkRng::new(seed),
+                pending: BinaryHeap::new(),
+                seq: 0,
+                last_in_order: now,
+                wire_free_at: now,
+            }),
+            stats: LinkStats::default(),
+        }
+    }
+
+    fn conditions(&self) -> LinkConditions {
+        match self.conditions.read() {
+            Ok(conditions) => *conditions,
+            Err(err) => *err.into_inner(),
+        }
+    }
+
+    fn queue(&self) -> MutexGuard<'_, LinkQueue<V>> {
+        match self.queue.lock() {
+            Ok(queue) => queue,
+            Err(err) => err.into_inner(),
+        }
+    }
+
+    fn transmit_to(&self, info: &ChannelInfo, frame: crate::core::io::OutgoingFrame<V>) {
+        if !frame.should_send_to(info.id()) {
+            return;
+        }
+        self.transmit(frame.frame().clone(), Instant::now());
+        frame.record_written(info.connection_id());
+    }
+
+    fn transmit(&self, frame: Frame<V>, now: Instant) {
+        let conditions = self.conditions();
+        let mut queue = self.queue();
+
+        if queue.rng.happens(conditions.loss) {
+            self.stats.record_dropped();
+            return;
+        }
+
+        let departure = match conditions.bandwidth {
+            Some(bytes_per_second) => {
+                let size = frame.header().size() + frame.body_length();
+                let start = queue.wire_free_at.max(now);
+                queue.wire_free_at =
+                    start + Duration::from_secs_f64(size as f64 / bytes_per_second as f64);
+                queue.wire_free_at
+            }
+            None => now,
+        };
+        let mut deliver_at = departure + conditions.latency.sample(&mut queue.rng);
+
+        if queue.rng.happens(conditions.reordering) {
+            deliver_at += conditions.reordering_delay;
+            self.stats.record_reordered();
+        } else {
+            deliver_at = deliver_at.max(queue.last_in_order);
+            queue.last_in_order = deliver_at;
+        }
+
+        queue.seq += 1;
+        let seq = queue.seq;
+        queue.pending.push(Reverse(InFlight {
+            deliver_at,
+            seq,
+            frame,
+        }));
+        self.stats.record_sent();
+    }
+
+    fn take_due(&self, now: Instant) -> Vec<Frame<V>> {
+        let mut queue = self.queue();
+        let mut frames = Vec::new();
+        while let Some(Reverse(in_flight)) = queue.pending.peek() {
+            if in_flight.deliver_at > now {
+                break;
+            }
+            if let Some(Reverse(in_flight)) = queue.pending.pop() {
+                frames.push(in_flight.frame);
+            }
+        }
+        frames
+    }
+
+    /// Time to wait until the next frame is due, but no longer than a pooling interval.
+    fn wait_interval(&self) -> Duration {
+        match self.queue().pending.peek() {
+            Some(Reverse(in_flight)) => in_flight
+                .deliver_at
+                .saturating_duration_since(Instant::now())
+                .min(SIMULATED_LINK_POOLING_INTERVAL),
+            None => SIMULATED_LINK_POOLING_INTERVAL,
+        }
+    }
+}
+
+/// Frame scheduled for delivery.
+struct InFlight<V: MaybeVersioned> {
+    deliver_at: Instant,
+    seq: u64,
+    frame: Frame<V>,
+}
+
+impl<V: MaybeVersioned> PartialEq for InFlight<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: MaybeVersioned> Eq for InFlight<V> {}
+
+impl<V: MaybeVersioned> PartialOrd for InFlight<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: MaybeVersioned> Ord for InFlight<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.deliver_at, self.seq).cmp(&(other.deliver_at, other.seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+    use crate::sync::prelude::*;
+
+    const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn frame(sequence: u8) -> Frame<V2> {
+        Frame::builder()
+            .sequence(sequence)
+            .system_id(1)
+            .component_id(1)
+            .version(V2)
+            .message(&Heartbeat::default())
+            .unwrap()
+            .build()
+    }
+
+    fn delivered(direction: &LinkDirection<V2>, at: Instant) -> Vec<u8> {
+        direction
+            .take_due(at)
+            .iter()
+            .map(|frame| frame.sequence())
+            .collect()
+    }
+
+    #[test]
+    fn frames_are_delivered_in_order_despite_jitter() {
+        let direction = LinkDirection::new(
+            LinkConditions::perfect().with_latency(Latency::uniform(
+                Duration::from_millis(1),
+                Duration::from_millis(50),
+            )),
+            7,
+        );
+
+        let now = Instant::now();
+        for seq in 0..20 {
+            direction.transmit(frame(seq), now);
+        }
+
+        assert!(delivered(&direction, now).is_empty());
+        let received = delivered(&direction, now + Duration::from_millis(50));
+        assert_eq!(received, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn frames_are_reordered() {
+        let direction = LinkDirection::new(
+            LinkConditions::perfect().with_reordering(0.5, Duration::from_millis(10)),
+            7,
+        );
+
+        let now = Instant::now();
+        for seq in 0..20 {
+            direction.transmit(frame(seq), now);
+        }
+
+        let received = delivered(&direction, now + Duration::from_millis(10));
+        assert_eq!(received.len(), 20);
+        assert_ne!(received, (0..20).collect::<Vec<_>>());
+        assert!(direction.stats.reordered() > 0);
+    }
+
+    #[test]
+    fn bandwidth_is_limited() {
+        let frame_size = {
+            let frame = frame(0);
+            (frame.header().size() + frame.body_length()) as u64
+        };
+        // Exactly one frame per 10 milliseconds
+        let direction = LinkDirection::new(
+            LinkConditions::perfect().with_bandwidth(frame_size * 100),
+            7,
+        );
+
+        let now = Instant::now();
+        for seq in 0..3 {
+            direction.transmit(frame(seq), now);
+        }
+
+        assert_eq!(delivered(&direction, now + Duration::from_millis(11)), [0]);
+        assert_eq!(delivered(&direction, now + Duration::from_millis(21)), [1]);
+        assert_eq!(delivered(&direction, now + Duration::from_millis(31)), [2]);
+    }
+
+    #[test]
+    fn latency_distributions() {
+        let mut rng = LinkRng::new(7);
+        let (min, max) = (Duration::from_millis(10), Duration::from_millis(20));
+
+        let uniform = Latency::uniform(max, min);
+        assert!((0..100)
+            .map(|_| uniform.sample(&mut rng))
+            .all(|latency| (min..=max).contains(&latency)));
+
+        let normal = Latency::normal(Duration::from_millis(1), Duration::from_millis(10));
+        let samples: Vec<_> = (0..1000).map(|_| normal.sample(&mut rng)).collect();
+        assert!(samples.contains(&Duration::ZERO));
+        assert!(samples.iter().any(|latency| *latency > max));
+    }
+
+    #[test]
+    fn conditions_are_changed_at_runtime() {
+        let (a, b) = SimulatedLink::pair(LinkConditions::perfect());
+        let node_a = Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(a.clone())
+            .build()
+            .unwrap();
+        let node_b = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(b.clone())
+            .build()
+            .unwrap();
+
+        node_a.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = node_b.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 1);
+
+        a.set_conditions(LinkConditions::lossy(1.0));
+        node_a.send(&Heartbeat::default()).unwrap();
+        assert!(node_b.recv_frame_timeout(RECV_TIMEOUT).is_err());
+        assert_eq!(a.stats().dropped(), 1);
+
+        // Conditions are applied independently in each direction
+        node_b.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = node_a.recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert_eq!(frame.system_id(), 2);
+        assert_eq!(b.stats().sent(), 1);
+    }
+
+    #[tokio::test]
+    async fn async_nodes_are_linked() {
+        use crate::asnc::prelude::*;
+
+        let (a, b) = SimulatedLink::pair(
+            LinkConditions::perfect().with_latency(Latency::Fixed(Duration::from_millis(20))),
+        );
+        let node_a = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(a)
+            .build()
+            .await
+            .unwrap();
+        let mut node_b = Node::asnc::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(b)
+            .build()
+            .await
+            .unwrap();
+
+        let sent_at = Instant::now();
+        node_a.send(&Heartbeat::default()).unwrap();
+        let (frame, _) = node_b.recv_frame_timeout(RECV_TIMEOUT).await.unwrap();
+        assert_eq!(frame.system_id(), 1);
+        assert!(sent_at.elapsed() >= Duration::from_millis(20));
+    }
+}
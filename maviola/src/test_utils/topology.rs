@@ -0,0 +1,609 @@
+//! # In-process network topologies
+//!
+//! Builds interconnected in-process nodes for integration tests. Nodes are connected by in-memory
+//! links, that pass frames without any I/O and may emulate faults like frame loss and latency.
+//!
+//! Each link is a separate channel of a node connection, so nodes with several links behave like
+//! servers with several clients: frames sent by a node are delivered to all its links.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use maviola::dialects::minimal::messages::Heartbeat;
+//! use maviola::test_utils::topology::{LinkProfile, Topology};
+//!
+//! use maviola::prelude::*;
+//! use maviola::sync::prelude::*;
+//!
+//! let nodes = Topology::new()
+//!     .node("gcs")
+//!     .node("vehicle")
+//!     .link("gcs", "vehicle", LinkProfile::lossy(0.02))
+//!     .build::<V2>()
+//!     .unwrap();
+//!
+//! nodes.node("gcs").send(&Heartbeat::default()).unwrap();
+//! # let _ = nodes.node("vehicle").recv_frame_timeout(Duration::from_millis(100));
+//! # assert!(nodes.link_stats("gcs", "vehicle").unwrap().sent() <= 1);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::io::{ChannelDetails, ChannelInfo, ConnectionDetails, ConnectionInfo};
+use crate::core::io::{ConnectionConf, IncomingFrame};
+use crate::core::utils::{Closable, SharedCloser};
+use crate::sync::io::{Connection, ConnectionBuilder, ConnectionHandler};
+use crate::sync::marker::ConnConf;
+use crate::sync::utils::spawn_io;
+
+use crate::prelude::*;
+use crate::sync::prelude::*;
+
+/// Default seed for pseudo-random faults, makes tests reproducible.
+pub(super) const DEFAULT_LINK_SEED: u64 = 0x5EED_1505_CAFE_F00D;
+
+const LOOPBACK_CONN_NAME: &str = "loopback";
+
+/// How often link threads check whether connection is closed.
+const LOOPBACK_POOLING_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Fault profile of a link between two nodes.
+///
+/// Faults are applied independently in each direction. Frame loss is pseudo-random, but
+/// reproducible for the same [`seed`](Self::with_seed).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkProfile {
+    loss: f64,
+    latency: Duration,
+    seed: u64,
+}
+
+impl Default for LinkProfile {
+    fn default() -> Self {
+        Self::perfect()
+    }
+}
+
+impl LinkProfile {
+    /// Link, that delivers all frames without delay.
+    pub fn perfect() -> Self {
+        Self {
+            loss: 0.0,
+            latency: Duration::ZERO,
+            seed: DEFAULT_LINK_SEED,
+        }
+    }
+
+    /// Link, that loses frames with probability `loss` between `0.0` and `1.0`.
+    pub fn lossy(loss: f64) -> Self {
+        Self::perfect().with_loss(loss)
+    }
+
+    /// Sets probability of frame loss between `0.0` and `1.0`.
+    pub fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a delay, after which frames are delivered.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets a seed for pseudo-random faults.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Probability of frame loss.
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+
+    /// Delay, after which frames are delivered.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+}
+
+/// Counters of frames passed through a link in one direction.
+///
+/// Counters are shared between clones and updated while nodes are running.
+#[derive(Clone, Debug, Default)]
+pub struct LinkStats {
+    sent: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    reordered: Arc<AtomicU64>,
+}
+
+impl LinkStats {
+    /// Number of frames, that passed the fault profile and were sent to the peer.
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of frames lost according to the fault profile.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of sent frames, that were held back and let subsequent frames overtake them.
+    ///
+    /// Frames are reordered only by [`SimulatedLink`](super::simulated::SimulatedLink).
+    pub fn reordered(&self) -> u64 {
+        self.reordered.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn record_sent(&self) {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_reordered(&self) {
+        self.reordered.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Pseudo-random generator of link faults (xorshift64*).
+#[derive(Clone, Debug)]
+pub(super) struct LinkRng(u64);
+
+impl LinkRng {
+    pub(super) fn new(seed: u64) -> Self {
+        // Xorshift state should never be zero
+        Self(seed | 1)
+    }
+
+    /// Returns pseudo-random number in `[0.0, 1.0)`.
+    pub(super) fn random(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let value = self.0.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (value >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns `true` with specified `probability`.
+    pub(super) fn happens(&mut self, probability: f64) -> bool {
+        probability > 0.0 && self.random() < probability
+    }
+}
+
+/// Declarative description of in-process nodes and links between them.
+///
+/// Nodes are identified by names. Nodes added by [`Topology::node`] get system `ID`s in the order
+/// of declaration starting from `1` and component `ID` `1`.
+///
+/// See [module](self) documentation for details.
+#[derive(Clone, Debug, Default)]
+pub struct Topology {
+    nodes: Vec<(String, MavLinkId)>,
+    links: Vec<(String, String, LinkProfile)>,
+}
+
+impl Topology {
+    /// Creates an empty topology.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node with specified `name`.
+    pub fn node(self, name: impl Into<String>) -> Self {
+        let id = MavLinkId::new(self.nodes.len() as u8 + 1, 1);
+        self.node_with_id(name, id)
+    }
+
+    /// Adds a node with specified `name` and MAVLink `id`.
+    pub fn node_with_id(mut self, name: impl Into<String>, id: MavLinkId) -> Self {
+        self.nodes.push((name.into(), id));
+        self
+    }
+
+    /// Links two nodes with a bidirectional link with specified fault `profile`.
+    pub fn link(
+        mut self,
+        a: impl Into<String>,
+        b: impl Into<String>,
+        profile: LinkProfile,
+    ) -> Self {
+        self.links.push((a.into(), b.into(), profile));
+        self
+    }
+
+    /// Builds nodes and links between them.
+    ///
+    /// Returns an error if node names are not unique or links refer to undeclared nodes.
+    pub fn build<V: MaybeVersioned>(self) -> Result<TopologyNodes<V>> {
+        let mut ends: HashMap<String, Vec<LinkEnd<V>>> = HashMap::new();
+        for (name, _) in &self.nodes {
+            if ends.insert(name.clone(), Vec::new()).is_some() {
+                return Err(Error::Other(format!("duplicate topology node: {name}")));
+            }
+        }
+
+        let mut stats = HashMap::new();
+        for (idx, (a, b, profile)) in self.links.iter().enumerate() {
+            for name in [a, b] {
+                if !ends.contains_key(name) {
+                    return Err(Error::Other(format!("unknown topology node: {name}")));
+                }
+            }
+
+            let seed = profile.seed.wrapping_add(2 * idx as u64);
+            let (a_to_b, b_from_a) = link_direction(*profile, seed);
+            let (b_to_a, a_from_b) = link_direction(*profile, seed.wrapping_add(1));
+
+            stats.insert((a.clone(), b.clone()), a_to_b.stats.clone());
+            stats.insert((b.clone(), a.clone()), b_to_a.stats.clone());
+
+            if let Some(a_ends) = ends.get_mut(a) {
+                a_ends.push(LinkEnd {
+                    peer: b.clone(),
+                    tx: a_to_b,
+                    rx: a_from_b,
+                });
+            }
+            if let Some(b_ends) = ends.get_mut(b) {
+                b_ends.push(LinkEnd {
+                    peer: a.clone(),
+                    tx: b_to_a,
+                    rx: b_from_a,
+                });
+            }
+        }
+
+        let mut nodes = HashMap::new();
+        for (name, id) in self.nodes {
+            let conn = LoopbackConnection {
+                info: ConnectionInfo::new(ConnectionDetails::Custom {
+                    name: LOOPBACK_CONN_NAME.to_string(),
+                    details: name.clone(),
+                }),
+                ends: Arc::new(Mutex::new(ends.remove(&name))),
+            };
+
+            let node = Node::sync::<V>().id(id).connection(conn).build()?;
+            nodes.insert(name, node);
+        }
+
+        Ok(TopologyNodes { nodes, stats })
+    }
+}
+
+/// Nodes built from a [`Topology`].
+///
+/// Dropping this value closes all nodes, which were not [taken](Self::take).
+pub struct TopologyNodes<V: MaybeVersioned> {
+    nodes: HashMap<String, EdgeNode<V>>,
+    stats: HashMap<(String, String), LinkStats>,
+}
+
+impl<V: MaybeVersioned> TopologyNodes<V> {
+    /// Returns node with specified `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such node.
+    pub fn node(&self, name: &str) -> &EdgeNode<V> {
+        self.nodes
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown topology node: {name}"))
+    }
+
+    /// Returns mutable reference to a node with specified `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such node.
+    pub fn node_mut(&mut self, name: &str) -> &mut EdgeNode<V> {
+        self.nodes
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("unknown topology node: {name}"))
+    }
+
+    /// Takes ownership of a node with specified `name`, for example, to move it to another thread.
+    pub fn take(&mut self, name: &str) -> Option<EdgeNode<V>> {
+        self.nodes.remove(name)
+    }
+
+    /// Counters of frames sent by node `from` to node `to`.
+    ///
+    /// Returns [`None`] if nodes are not linked.
+    pub fn link_stats(&self, from: &str, to: &str) -> Option<&LinkStats> {
+        self.stats.get(&(from.to_string(), to.to_string()))
+    }
+}
+
+impl<V: MaybeVersioned> Debug for TopologyNodes<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TopologyNodes")
+            .field("nodes", &self.nodes.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+type LinkFrame<V> = (Instant, Frame<V>);
+
+fn link_direction<V: MaybeVersioned>(profile: LinkProfile, seed: u64) -> (LinkTx<V>, LinkRx<V>) {
+    let (sender, receiver) = mpsc::channel();
+    let tx = LinkTx {
+        sender,
+        profile,
+        rng: LinkRng::new(seed),
+        stats: LinkStats::default(),
+    };
+    (tx, LinkRx { receiver })
+}
+
+/// Sending side of a link direction.
+struct LinkTx<V: MaybeVersioned> {
+    sender: mpsc::Sender<LinkFrame<V>>,
+    profile: LinkProfile,
+    rng: LinkRng,
+    stats: LinkStats,
+}
+
+impl<V: MaybeVersioned> LinkTx<V> {
+    fn transmit(&mut self, frame: Frame<V>) {
+        if self.rng.happens(self.profile.loss) {
+            self.stats.record_dropped();
+            return;
+        }
+
+        let deliver_at = Instant::now() + self.profile.latency;
+        if self.sender.send((deliver_at, frame)).is_ok() {
+            self.stats.record_sent();
+        }
+    }
+}
+
+/// Receiving side of a link direction.
+struct LinkRx<V: MaybeVersioned> {
+    receiver: mpsc::Receiver<LinkFrame<V>>,
+}
+
+/// Link attached to a node connection.
+struct LinkEnd<V: MaybeVersioned> {
+    peer: String,
+    tx: LinkTx<V>,
+    rx: LinkRx<V>,
+}
+
+/// In-memory connection of a topology node.
+///
+/// Links are moved into the connection once it is built, therefore connection can't be restored.
+#[derive(Clone)]
+struct LoopbackConnection<V: MaybeVersioned> {
+    info: ConnectionInfo,
+    ends: Arc<Mutex<Option<Vec<LinkEnd<V>>>>>,
+}
+
+impl<V: MaybeVersioned> Debug for LoopbackConnection<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopbackConnection")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionConf for LoopbackConnection<V> {
+    fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+}
+
+impl<V: MaybeVersioned> ConnectionBuilder<V> for LoopbackConnection<V> {
+    fn build(&self) -> Result<(Connection<V>, ConnectionHandler)> {
+        let ends = self
+            .ends
+            .lock()
+            .ok()
+            .and_then(|mut ends| ends.take())
+            .ok_or_else(|| Error::Other("loopback connection is already built".to_string()))?;
+
+        let state = SharedCloser::new();
+        let (connection, chan_factory) = Connection::new(self.info.clone(), state.clone());
+
+        for end in ends {
+            let chan_info = connection.info().make_channel_info(ChannelDetails::Custom {
+                conn_name: LOOPBACK_CONN_NAME.to_string(),
+                channel_name: end.peer.clone(),
+                details: format!("{:?}", end.tx.profile),
+            });
+
+            spawn_outgoing(
+                state.to_closable(),
+                chan_info.clone(),
+                chan_factory.send_handler().clone(),
+                end.tx,
+            );
+            spawn_incoming(
+                state.to_closable(),
+                chan_info,
+                chan_factory.producer().clone(),
+                end.rx,
+            );
+        }
+
+        Ok((connection, ConnectionHandler::spawn_from_state(state)))
+    }
+
+    fn to_conf(&self) -> ConnConf<V> {
+        ConnConf::new(self.clone())
+    }
+}
+
+fn spawn_outgoing<V: MaybeVersioned>(
+    state: Closable,
+    info: ChannelInfo,
+    send_handler: crate::sync::io::OutgoingFrameHandler<V>,
+    mut tx: LinkTx<V>,
+) {
+    spawn_io(move || {
+        while !state.is_closed() {
+            let frame = match send_handler.recv_timeout(LOOPBACK_POOLING_INTERVAL) {
+                Ok(frame) => frame,
+                Err(crate::error::RecvTimeoutError::Disconnected) => break,
+                Err(_) => continue,
+            };
+
+            if !frame.should_send_to(info.id()) {
+                continue;
+            }
+
+            tx.transmit(frame.frame().clone());
+            frame.record_written(info.connection_id());
+        }
+        log::trace!("[{info:?}] loopback writer stopped");
+    });
+}
+
+fn spawn_incoming<V: MaybeVersioned>(
+    state: Closable,
+    info: ChannelInfo,
+    producer: crate::sync::io::IncomingFrameProducer<V>,
+    rx: LinkRx<V>,
+) {
+    spawn_io(move || {
+        while !state.is_closed() {
+            let (deliver_at, frame) = match rx.receiver.recv_timeout(LOOPBACK_POOLING_INTERVAL) {
+                Ok(value) => value,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+
+            let delay = deliver_at.saturating_duration_since(Instant::now());
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+
+            if producer
+                .send(IncomingFrame::new(frame, info.clone()))
+                .is_err()
+            {
+                break;
+            }
+        }
+        log::trace!("[{info:?}] loopback reader stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dialects::minimal::messages::Heartbeat;
+
+    const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn frames_are_delivered_to_linked_nodes() {
+        let nodes = Topology::new()
+            .node("gcs")
+            .node("vehicle")
+            .node("camera")
+            .link("gcs", "vehicle", LinkProfile::perfect())
+            .link("vehicle", "camera", LinkProfile::perfect())
+            .build::<V2>()
+            .unwrap();
+
+        nodes.node("vehicle").send(&Heartbeat::default()).unwrap();
+        for name in ["gcs", "camera"] {
+            let (frame, _) = nodes.node(name).recv_frame_timeout(RECV_TIMEOUT).unwrap();
+            assert_eq!(frame.system_id(), 2);
+        }
+
+        // Nodes are not routers, frames are not forwarded
+        nodes.node("gcs").send(&Heartbeat::default()).unwrap();
+        nodes
+            .node("vehicle")
+            .recv_frame_timeout(RECV_TIMEOUT)
+            .unwrap();
+        assert!(nodes
+            .node("camera")
+            .recv_frame_timeout(RECV_TIMEOUT)
+            .is_err());
+
+        assert_eq!(nodes.link_stats("vehicle", "camera").unwrap().sent(), 1);
+        assert!(nodes.link_stats("gcs", "camera").is_none());
+    }
+
+    #[test]
+    fn faults_are_applied() {
+        let nodes = Topology::new()
+            .node("gcs")
+            .node_with_id("vehicle", MavLinkId::new(42, 1))
+            .link(
+                "gcs",
+                "vehicle",
+                LinkProfile::lossy(1.0).with_latency(Duration::from_millis(50)),
+            )
+            .build::<V2>()
+            .unwrap();
+
+        nodes.node("gcs").send(&Heartbeat::default()).unwrap();
+        assert!(nodes
+            .node("vehicle")
+            .recv_frame_timeout(RECV_TIMEOUT)
+            .is_err());
+        assert_eq!(nodes.link_stats("gcs", "vehicle").unwrap().dropped(), 1);
+
+        let nodes = Topology::new()
+            .node("gcs")
+            .node("vehicle")
+            .link(
+                "gcs",
+                "vehicle",
+                LinkProfile::perfect().with_latency(Duration::from_millis(50)),
+            )
+            .build::<V2>()
+            .unwrap();
+
+        let sent_at = Instant::now();
+        nodes.node("vehicle").send(&Heartbeat::default()).unwrap();
+        nodes.node("gcs").recv_frame_timeout(RECV_TIMEOUT).unwrap();
+        assert!(sent_at.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn lossy_link_is_reproducible() {
+        let mut first = link_direction::<V2>(LinkProfile::lossy(0.5), 7).0;
+        let mut second = link_direction::<V2>(LinkProfile::lossy(0.5), 7).0;
+
+        let samples: Vec<_> = (0..100).map(|_| first.rng.random()).collect();
+        assert!(samples.iter().all(|sample| (0.0..1.0).contains(sample)));
+        assert!(samples
+            .iter()
+            .zip((0..100).map(|_| second.rng.random()))
+            .all(|(a, b)| *a == b));
+
+        let lost = samples.iter().filter(|sample| **sample < 0.5).count();
+        assert!((25..75).contains(&lost));
+    }
+
+    #[test]
+    fn invalid_topology() {
+        assert!(Topology::new()
+            .node("gcs")
+            .node("gcs")
+            .build::<V2>()
+            .is_err());
+        assert!(Topology::new()
+            .node("gcs")
+            .link("gcs", "vehicle", LinkProfile::perfect())
+            .build::<V2>()
+            .is_err());
+    }
+}
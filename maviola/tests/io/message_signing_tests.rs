@@ -9,3 +9,27 @@ fn define_signing_config() {
         .outgoing(SignStrategy::Strict)
         .build();
 }
+
+#[test]
+fn peer_specific_incoming_strategies() {
+    use maviola::dialects::minimal::messages::Heartbeat;
+    use maviola::protocol::{Endpoint, MavLinkId};
+
+    let signer = FrameSigner::builder()
+        .key("abcdef")
+        .link_id(1)
+        .incoming(SignStrategy::Strict)
+        .peer_incoming(42, SignStrategy::Proxy)
+        .build();
+
+    let mut strict_frame = Endpoint::v2(MavLinkId::new(1, 1))
+        .next_frame(&Heartbeat::default())
+        .unwrap();
+    assert!(signer.process_incoming(&mut strict_frame).is_err());
+
+    let mut legacy_frame = Endpoint::v2(MavLinkId::new(42, 1))
+        .next_frame(&Heartbeat::default())
+        .unwrap();
+    assert!(signer.process_incoming(&mut legacy_frame).is_ok());
+    assert!(!legacy_frame.is_signed());
+}
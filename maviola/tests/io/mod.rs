@@ -1,2 +1,3 @@
 mod message_signing_tests;
 mod sync_node_tests;
+mod tls_tests;
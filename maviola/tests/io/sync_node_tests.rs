@@ -52,7 +52,7 @@ fn init_logger() {
 }
 
 fn initialize() {
-    INIT.call_once(|| init_logger());
+    INIT.call_once(init_logger);
 }
 
 pub fn make_tcp_server_node_v2(port: Port) -> EdgeNode<V2> {
@@ -141,6 +141,90 @@ fn messages_are_sent_and_received_clients_server() {
     }
 }
 
+#[test]
+fn connections_are_paused_and_resumed() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 1);
+    wait();
+
+    client_node.pause();
+    assert!(client_node.is_paused());
+    assert!(client_node.info().is_paused());
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    server_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait_long();
+
+    assert!(server_node.recv_frame_timeout(WAIT_DURATION).is_err());
+    assert!(client_node.recv_frame_timeout(WAIT_DURATION).is_err());
+    assert!(client_node.is_connected());
+
+    client_node.resume();
+    assert!(!client_node.is_paused());
+
+    let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+
+    let unknown = server_node.info().id();
+    assert!(client_node.pause_connection(unknown).is_err());
+    client_node
+        .pause_connection(client_node.info().id())
+        .unwrap();
+    assert!(client_node.is_paused());
+}
+
+#[test]
+fn frames_are_broadcast_by_peer_identity() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let vehicle_node = make_tcp_client_node_v2(port, 1);
+    let gcs_node = Node::sync::<V2>()
+        .id(MavLinkId::new(255, 190))
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    vehicle_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    gcs_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait_long();
+
+    let message = minimal::messages::Heartbeat::default();
+
+    server_node
+        .broadcast(&message, BroadcastScope::System(DEFAULT_TCP_CLIENT_SYS_ID))
+        .unwrap();
+    assert!(vehicle_node.recv_frame_timeout(WAIT_LONG_DURATION).is_ok());
+    assert!(gcs_node.recv_frame_timeout(WAIT_DURATION).is_err());
+
+    server_node
+        .broadcast(&message, BroadcastScope::ExceptSystem(255))
+        .unwrap();
+    assert!(vehicle_node.recv_frame_timeout(WAIT_LONG_DURATION).is_ok());
+    assert!(gcs_node.recv_frame_timeout(WAIT_DURATION).is_err());
+
+    server_node
+        .broadcast(&message, BroadcastScope::System(42))
+        .unwrap();
+    assert!(vehicle_node.recv_frame_timeout(WAIT_DURATION).is_err());
+    assert!(gcs_node.recv_frame_timeout(WAIT_DURATION).is_err());
+}
+
 #[test]
 fn events_are_received() {
     initialize();
@@ -316,3 +400,1974 @@ fn node_no_version() {
         panic!("invalid event!")
     }
 }
+
+#[test]
+fn latency_stats_are_recorded() {
+    use maviola::core::node::LatencyStats;
+
+    initialize();
+
+    let port = unused_port();
+    let server_stats = LatencyStats::new();
+    let client_stats = LatencyStats::new();
+
+    let server_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_SERVER_SYS_ID)
+        .component_id(DEFAULT_TCP_SERVER_COMP_ID)
+        .latency_stats(server_stats.clone())
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .latency_stats(client_stats.clone())
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+
+    while server_node.try_recv().is_ok() {}
+
+    let heartbeat_id = minimal::messages::Heartbeat::message_id();
+    assert_eq!(client_stats.outgoing(heartbeat_id).unwrap().count(), 1);
+    assert_eq!(server_stats.incoming(heartbeat_id).unwrap().count(), 1);
+    assert!(server_stats.outgoing(heartbeat_id).is_none());
+}
+
+#[test]
+fn custom_events_are_delivered_to_subscribers() {
+    use maviola::core::node::CustomEvent;
+
+    #[derive(Debug, PartialEq)]
+    struct MissionUploaded(usize);
+
+    initialize();
+
+    let node = Node::sync::<V2>()
+        .connection(TcpServer::new(make_addr(unused_port())).unwrap())
+        .build()
+        .unwrap();
+    let receiver = node.receiver().clone();
+
+    node.emit(Event::Custom(CustomEvent::new(MissionUploaded(12))))
+        .unwrap();
+
+    for rx in [node.receiver(), &receiver] {
+        match rx.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::Custom(event) => {
+                assert_eq!(event.downcast_ref(), Some(&MissionUploaded(12)));
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+}
+
+#[test]
+fn injected_frames_are_processed_as_incoming() {
+    use maviola::core::io::ChannelDetails;
+
+    initialize();
+
+    let node = Node::sync::<V2>()
+        .connection(TcpServer::new(make_addr(unused_port())).unwrap())
+        .build()
+        .unwrap();
+
+    let frame = Endpoint::v2(MavLinkId::new(17, 1))
+        .next_frame(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    node.inject_incoming(frame, node.injection_origin("can-bus"))
+        .unwrap();
+
+    let mut new_peer = false;
+    let mut injected = false;
+    while let Ok(event) = node.recv_timeout(WAIT_LONG_DURATION) {
+        match event {
+            Event::NewPeer(peer) => {
+                assert_eq!(peer.system_id(), 17);
+                new_peer = true;
+            }
+            Event::Frame(frame, callback) => {
+                assert_eq!(frame.system_id(), 17);
+                match callback.info().details() {
+                    ChannelDetails::Injected { origin } => assert_eq!(origin, "can-bus"),
+                    details => panic!("unexpected channel details: {details:?}"),
+                }
+                injected = true;
+            }
+            _ => {}
+        }
+        if new_peer && injected {
+            break;
+        }
+    }
+
+    assert!(new_peer);
+    assert!(injected);
+}
+
+#[test]
+fn rate_governor_drops_excessive_messages() {
+    use maviola::protocol::RateGovernor;
+
+    initialize();
+
+    let port = unused_port();
+    let heartbeat_id = minimal::messages::Heartbeat::message_id();
+    let governor = RateGovernor::new().with_limit(heartbeat_id, 1.0);
+
+    let server_node = Node::sync::<V2>()
+        .rate_governor(governor.clone())
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    for _ in 0..5 {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+    }
+    wait_long();
+
+    let mut frames = 0;
+    let mut new_peers = 0;
+    while let Ok(event) = server_node.try_recv() {
+        match event {
+            Event::Frame(..) => frames += 1,
+            Event::NewPeer(_) => new_peers += 1,
+            _ => {}
+        }
+    }
+
+    assert_eq!(frames, 1);
+    assert_eq!(new_peers, 1);
+    assert_eq!(governor.passed(heartbeat_id), 1);
+    assert_eq!(governor.dropped(heartbeat_id), 4);
+}
+
+#[test]
+fn tcp_client_fails_over_to_fallback_addr() {
+    initialize();
+
+    let (primary_port, secondary_port) = (unused_port(), unused_port());
+    let secondary_node = make_tcp_server_node_v2(secondary_port);
+
+    // Primary server is not available yet
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(
+            TcpClient::new(make_addr(primary_port))
+                .unwrap()
+                .with_fallback_addr(make_addr(secondary_port))
+                .unwrap()
+                .with_failback_interval(WAIT_DURATION),
+        )
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+
+    assert!(matches!(
+        secondary_node.try_recv().unwrap(),
+        Event::NewPeer(_)
+    ));
+
+    // Fail back once primary server is up
+    let primary_node = make_tcp_server_node_v2(primary_port);
+    wait_long();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+
+    assert!(matches!(
+        primary_node.try_recv().unwrap(),
+        Event::NewPeer(_)
+    ));
+}
+
+#[test]
+fn udp_client_fails_over_to_fallback_addr() {
+    initialize();
+
+    let (primary_port, secondary_port) = (unused_port(), unused_port());
+    let secondary_node = Node::sync::<V2>()
+        .connection(UdpServer::new(make_addr(secondary_port)).unwrap())
+        .build()
+        .unwrap();
+
+    // Primary server is silent
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(
+            UdpClient::new(make_addr(primary_port))
+                .unwrap()
+                .with_fallback_addr(make_addr(secondary_port))
+                .unwrap()
+                .with_failover_timeout(WAIT_DURATION),
+        )
+        .build()
+        .unwrap();
+
+    // Servers are silent until client talks to them, so keep sending until failover happens
+    for _ in 0..10 {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        thread::sleep(WAIT_DURATION / 2);
+    }
+
+    assert!(matches!(
+        secondary_node.try_recv().unwrap(),
+        Event::NewPeer(_)
+    ));
+}
+
+#[test]
+fn nodes_with_thread_settings_communicate() {
+    use maviola::core::utils::{ThreadPriority, ThreadSettings};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .io_threads(ThreadSettings::new().with_core_affinity([0]))
+        .handler_threads(ThreadSettings::new().with_priority(ThreadPriority::Lowest))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+
+    assert!(matches!(server_node.try_recv().unwrap(), Event::NewPeer(_)));
+    assert!(matches!(server_node.try_recv().unwrap(), Event::Frame(..)));
+}
+
+#[test]
+fn group_receivers_consume_each_frame_once() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let workers = server_node.receiver().join_group("workers");
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let worker = workers.clone();
+            thread::spawn(move || {
+                let mut frames = 0;
+                while let Ok(event) = worker.recv_timeout(WAIT_LONG_DURATION) {
+                    if let Event::Frame(..) = event {
+                        frames += 1;
+                    }
+                }
+                frames
+            })
+        })
+        .collect();
+
+    for _ in 0..10 {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+    }
+
+    let frames: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(frames, 10);
+
+    // Broadcast receivers still get all frames
+    let mut frames = 0;
+    while let Ok(event) = server_node.try_recv() {
+        if let Event::Frame(..) = event {
+            frames += 1;
+        }
+    }
+    assert_eq!(frames, 10);
+}
+
+#[test]
+fn shutdown_emits_shutdown_messages() {
+    use minimal::enums::MavState;
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .shutdown_message(minimal::messages::Heartbeat {
+            system_status: MavState::Poweroff,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let report = server_node.shutdown(WAIT_LONG_DURATION);
+    assert!(report.flushed() >= 1);
+    assert_eq!(report.dropped(), 0);
+    assert!(!report.timed_out());
+
+    let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+    match frame.decode::<minimal::Minimal>().unwrap() {
+        minimal::Minimal::Heartbeat(heartbeat) => {
+            assert!(matches!(heartbeat.system_status, MavState::Poweroff));
+        }
+        message => panic!("unexpected message: {message:?}"),
+    }
+}
+
+#[test]
+#[cfg(feature = "msrv-utils-params")]
+fn param_server_answers_requests() {
+    use maviola::core::msrv::params::{ParamChanged, ParamEncoding, ParamServer, ParamValue};
+    use maviola::dialects::common::{enums::MavParamType, messages, Common};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let params = ParamServer::new();
+    params.declare("SYSID_THISMAV", 2u8).unwrap();
+    params.declare("WPNAV_SPEED", 5.0f32).unwrap();
+    server_node.attach_param_server(&params);
+
+    let recv_param_value = || loop {
+        let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        if let Ok(Common::ParamValue(msg)) = frame.decode::<Common>() {
+            return msg;
+        }
+    };
+
+    client_node
+        .send(&messages::ParamRequestList {
+            target_system: DEFAULT_TCP_SERVER_SYS_ID,
+            target_component: 0,
+        })
+        .unwrap();
+    let values = [recv_param_value(), recv_param_value()];
+    assert_eq!(values[0].param_index, 0);
+    assert_eq!(values[1].param_index, 1);
+    assert_eq!(values[1].param_count, 2);
+
+    let mut param_id = [0u8; 16];
+    param_id[..11].copy_from_slice(b"WPNAV_SPEED");
+    client_node
+        .send(&messages::ParamSet {
+            target_system: DEFAULT_TCP_SERVER_SYS_ID,
+            target_component: 1,
+            param_id,
+            param_value: ParamValue::Real32(7.5).encode(ParamEncoding::Bytewise),
+            param_type: MavParamType::Real32,
+        })
+        .unwrap();
+    assert_eq!(recv_param_value().param_value, 7.5);
+    assert_eq!(params.get("WPNAV_SPEED"), Some(ParamValue::Real32(7.5)));
+
+    let changed = loop {
+        if let Event::Custom(event) = server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            break event.downcast_ref::<ParamChanged>().unwrap().clone();
+        }
+    };
+    assert_eq!(changed.name, "WPNAV_SPEED");
+    assert_eq!(changed.previous, ParamValue::Real32(5.0));
+    assert_eq!(changed.source, MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 0));
+
+    params.set("WPNAV_SPEED", 3.0f32).unwrap();
+    assert_eq!(recv_param_value().param_value, 3.0);
+}
+
+#[test]
+#[cfg(feature = "msrv-utils-gcs")]
+fn gcs_client_reads_and_writes_params() {
+    use maviola::core::msrv::gcs::GcsSettings;
+    use maviola::core::msrv::params::{ParamServer, ParamValue};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let params = ParamServer::new();
+    params.declare("WPNAV_SPEED", 5.0f32).unwrap();
+    server_node.attach_param_server(&params);
+
+    let gcs = client_node.gcs_client(
+        GcsSettings::new(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+            .with_timeout(WAIT_LONG_DURATION),
+    );
+
+    assert_eq!(
+        gcs.read_param("WPNAV_SPEED").unwrap(),
+        ParamValue::Real32(5.0)
+    );
+    assert_eq!(
+        gcs.write_param("WPNAV_SPEED", 7.5f32).unwrap(),
+        ParamValue::Real32(7.5)
+    );
+    assert_eq!(params.get("WPNAV_SPEED"), Some(ParamValue::Real32(7.5)));
+}
+
+#[test]
+#[cfg(feature = "msrv-utils-streams")]
+fn stream_controller_answers_interval_commands() {
+    use maviola::core::msrv::streams::{IntervalChanged, StreamRateController};
+    use maviola::dialects::common::enums::{MavCmd, MavResult};
+    use maviola::dialects::common::{messages, Common};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let streams = StreamRateController::new();
+    let message_id = streams
+        .register(None, messages::SysStatus::default)
+        .unwrap();
+    server_node.attach_stream_controller(&streams);
+
+    client_node
+        .send(&messages::CommandLong {
+            target_system: DEFAULT_TCP_SERVER_SYS_ID,
+            target_component: 0,
+            command: MavCmd::SetMessageInterval,
+            confirmation: 0,
+            param1: message_id as f32,
+            param2: 50_000.0,
+            param3: 0.0,
+            param4: 0.0,
+            param5: 0.0,
+            param6: 0.0,
+            param7: 0.0,
+        })
+        .unwrap();
+
+    let mut acknowledged = false;
+    let mut received = 0;
+    while received < 3 {
+        let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        match frame.decode::<Common>() {
+            Ok(Common::CommandAck(ack)) => {
+                assert!(matches!(ack.result, MavResult::Accepted));
+                acknowledged = true;
+            }
+            Ok(Common::SysStatus(_)) => received += 1,
+            _ => {}
+        }
+    }
+    assert!(acknowledged);
+    assert_eq!(
+        streams.interval(message_id),
+        Some(Duration::from_millis(50))
+    );
+
+    let changed = loop {
+        if let Event::Custom(event) = server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            break event.downcast_ref::<IntervalChanged>().unwrap().clone();
+        }
+    };
+    assert_eq!(changed.message_id, message_id);
+    assert_eq!(changed.previous, None);
+    assert_eq!(changed.source, MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 0));
+}
+
+#[test]
+#[cfg(all(feature = "msrv-utils-params", feature = "msrv-utils-streams"))]
+fn microservices_share_node() {
+    use maviola::core::msrv::params::ParamServer;
+    use maviola::core::msrv::streams::StreamRateController;
+    use maviola::dialects::common::{messages, Common};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let params = ParamServer::new();
+    params.declare("SYSID_THISMAV", 2u8).unwrap();
+    server_node.attach_param_server(&params);
+
+    let streams = StreamRateController::new();
+    streams
+        .register(
+            Some(Duration::from_millis(20)),
+            messages::SysStatus::default,
+        )
+        .unwrap();
+    server_node.attach_stream_controller(&streams);
+
+    client_node
+        .send(&messages::ParamRequestList {
+            target_system: DEFAULT_TCP_SERVER_SYS_ID,
+            target_component: 0,
+        })
+        .unwrap();
+
+    let mut param_values = 0;
+    let mut statuses = 0;
+    while param_values < 1 || statuses < 2 {
+        let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        match frame.decode::<Common>() {
+            Ok(Common::ParamValue(_)) => param_values += 1,
+            Ok(Common::SysStatus(_)) => statuses += 1,
+            _ => {}
+        }
+    }
+    assert_eq!(param_values, 1);
+}
+
+#[test]
+#[cfg(feature = "common")]
+fn node_components_share_connection() {
+    use maviola::dialects::common::{messages, Common};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let mut camera = server_node.add_component(100).unwrap();
+    assert!(server_node.add_component(100).is_err());
+    assert!(server_node.add_component(1).is_err());
+    camera.activate().unwrap();
+
+    // Only component emits heartbeats, since node is not active
+    let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+    assert_eq!(frame.component_id(), 100);
+
+    for target_component in [1, 100] {
+        client_node
+            .send(&messages::ParamRequestList {
+                target_system: DEFAULT_TCP_SERVER_SYS_ID,
+                target_component,
+            })
+            .unwrap();
+    }
+
+    let mut requests = Vec::new();
+    while let Ok((frame, _)) = camera.recv_frame_timeout(WAIT_LONG_DURATION) {
+        if let Ok(Common::ParamRequestList(request)) = frame.decode::<Common>() {
+            requests.push(request.target_component);
+        }
+    }
+    assert_eq!(requests, vec![100]);
+
+    drop(camera);
+    assert!(server_node.add_component(100).is_ok());
+}
+
+#[test]
+#[cfg(feature = "msrv-utils-mission")]
+fn mission_upload_and_download() {
+    use maviola::core::msrv::mission::{MissionReceiver, MissionSender, MissionSettings};
+    use maviola::dialects::common::enums::{MavCmd, MavFrame, MavMissionType};
+    use maviola::dialects::common::{messages, Common};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 1);
+    wait();
+
+    let item = |z: f32| messages::MissionItemInt {
+        target_system: 0,
+        target_component: 0,
+        seq: 0,
+        frame: MavFrame::GlobalRelativeAlt,
+        command: MavCmd::NavWaypoint,
+        current: 0,
+        autocontinue: 1,
+        param1: 0.0,
+        param2: 0.0,
+        param3: 0.0,
+        param4: 0.0,
+        x: 0,
+        y: 0,
+        z,
+        mission_type: MavMissionType::Mission,
+    };
+    let client_id = MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1);
+    let server_id = MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1);
+
+    let vehicle = thread::spawn(move || {
+        let settings = MissionSettings::new(client_id);
+        let uploaded = loop {
+            let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+            if let Ok(Common::MissionCount(msg)) = frame.decode::<Common>() {
+                break server_node
+                    .run_mission_transfer(MissionReceiver::for_upload(settings, msg.count))
+                    .unwrap();
+            }
+        };
+        loop {
+            let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+            if let Ok(Common::MissionRequestList(_)) = frame.decode::<Common>() {
+                server_node
+                    .run_mission_transfer(MissionSender::new(settings, uploaded.clone()))
+                    .unwrap();
+                break uploaded;
+            }
+        }
+    });
+
+    client_node
+        .upload_mission(
+            MissionSettings::new(server_id),
+            vec![item(10.0), item(20.0)],
+        )
+        .unwrap();
+    let downloaded = client_node
+        .download_mission(MissionSettings::new(server_id))
+        .unwrap();
+    let uploaded = vehicle.join().unwrap();
+
+    assert_eq!(uploaded.len(), 2);
+    assert_eq!(downloaded.len(), 2);
+    assert_eq!(downloaded[1].seq, 1);
+    assert_eq!(downloaded[1].z, 20.0);
+    assert_eq!(downloaded[1].target_system, DEFAULT_TCP_CLIENT_SYS_ID);
+
+    let result = client_node.download_mission(
+        MissionSettings::new(server_id)
+            .with_timeout(WAIT_DURATION)
+            .with_retries(1),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn tcp_client_node_restores_connection() {
+    use maviola::core::io::RetryStrategy;
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    wait();
+
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .retry(RetryStrategy::Always(WAIT_DURATION))
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    server_node.recv_frame_timeout(WAIT_DURATION).unwrap();
+
+    // Node stays alive, while server is down
+    drop(server_node);
+    wait();
+    assert!(client_node.is_connected());
+
+    let server_node = make_tcp_server_node_v2(port);
+    let mut lost = false;
+    loop {
+        match client_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::ConnectionLost(_) => lost = true,
+            Event::ConnectionRestored(_) => break,
+            _ => continue,
+        }
+    }
+    assert!(lost);
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, _) = server_node.recv_frame_timeout(WAIT_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+}
+
+#[test]
+fn nodes_share_io_pool() {
+    use maviola::sync::io::IoPool;
+
+    initialize();
+
+    let pool = IoPool::new(2);
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_SERVER_SYS_ID)
+        .component_id(DEFAULT_TCP_SERVER_COMP_ID)
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .io_pool(pool.clone())
+        .build()
+        .unwrap();
+    wait();
+
+    const CLIENT_COUNT: u8 = 10;
+    let client_nodes: Vec<_> = (0..CLIENT_COUNT)
+        .map(|component_id| {
+            Node::sync::<V2>()
+                .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+                .component_id(component_id)
+                .connection(TcpClient::new(make_addr(port)).unwrap())
+                .io_pool(pool.clone())
+                .build()
+                .unwrap()
+        })
+        .collect();
+    wait();
+
+    // Each channel has a reader and a writer, server channels are also watched
+    assert_eq!(pool.tasks(), CLIENT_COUNT as usize * 5);
+
+    for client_node in &client_nodes {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+    }
+    for _ in &client_nodes {
+        let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    }
+
+    server_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    for client_node in &client_nodes {
+        let (frame, _) = client_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+    }
+
+    drop(client_nodes);
+    drop(server_node);
+    wait_long();
+    assert_eq!(pool.tasks(), 0);
+}
+
+#[test]
+fn outbound_queue_transmits_frames_after_reconnect() {
+    use maviola::core::io::{OutboundQueue, RetryStrategy};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    wait();
+
+    let queue = OutboundQueue::new().with_capacity(16);
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .retry(RetryStrategy::Always(WAIT_DURATION))
+        .outbound_queue(queue.clone())
+        .build()
+        .unwrap();
+    wait();
+
+    drop(server_node);
+    loop {
+        if let Event::ConnectionLost(_) = client_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            break;
+        }
+    }
+
+    // Frames sent while connection is down are spooled
+    for _ in 0..3 {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+    }
+    wait();
+    assert_eq!(queue.len(), 3);
+
+    let server_node = make_tcp_server_node_v2(port);
+    loop {
+        if let Event::ConnectionRestored(_) = client_node.recv_timeout(WAIT_LONG_DURATION).unwrap()
+        {
+            break;
+        }
+    }
+
+    for _ in 0..3 {
+        let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn tcp_server_node_reports_channel_events() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_SERVER_SYS_ID)
+        .component_id(DEFAULT_TCP_SERVER_COMP_ID)
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .channel_events(true)
+        .build()
+        .unwrap();
+    wait();
+
+    let client = std::net::TcpStream::connect(make_addr(port)).unwrap();
+    let opened = loop {
+        match server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::ChannelOpened(channel) => break channel,
+            _ => continue,
+        }
+    };
+
+    drop(client);
+    let closed = loop {
+        match server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::ChannelClosed(channel) => break channel,
+            _ => continue,
+        }
+    };
+    assert_eq!(opened.id(), closed.id());
+
+    server_node.channel_events(false);
+    let _client = std::net::TcpStream::connect(make_addr(port)).unwrap();
+    while let Ok(event) = server_node.recv_timeout(WAIT_LONG_DURATION) {
+        assert!(!matches!(
+            event,
+            Event::ChannelOpened(_) | Event::ChannelClosed(_)
+        ));
+    }
+}
+
+#[test]
+fn tlog_writer_records_incoming_and_outgoing_frames() {
+    use std::fs::File;
+    use std::io::{BufReader, Read};
+
+    use maviola::core::io::Receiver;
+
+    initialize();
+
+    let port = unused_port();
+    let path = std::env::temp_dir().join(format!("maviola-{port}.tlog"));
+    _ = std::fs::remove_file(&path);
+
+    let server_node = make_tcp_server_node_v2(port);
+    wait();
+
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(0)
+        .connection(TlogWriter::new(&path, TcpClient::new(make_addr(port)).unwrap()).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    server_node.recv_frame_timeout(WAIT_DURATION).unwrap();
+    server_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    client_node.recv_frame_timeout(WAIT_DURATION).unwrap();
+
+    drop(client_node);
+    wait();
+
+    let mut reader = BufReader::new(File::open(&path).unwrap());
+    let mut system_ids = Vec::new();
+    for _ in 0..2 {
+        let mut timestamp = [0u8; 8];
+        reader.read_exact(&mut timestamp).unwrap();
+        assert!(u64::from_be_bytes(timestamp) > 0);
+
+        let frame = Receiver::versioned(&mut reader, V2).recv().unwrap();
+        system_ids.push(frame.system_id());
+    }
+    assert_eq!(
+        system_ids,
+        vec![DEFAULT_TCP_CLIENT_SYS_ID, DEFAULT_TCP_SERVER_SYS_ID]
+    );
+    assert_eq!(reader.read(&mut [0u8; 1]).unwrap(), 0);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn stats_report_summarizes_traffic() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    wait();
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    server_node.stats_report(WAIT_DURATION);
+    for _ in 0..3 {
+        client_node
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+    }
+
+    loop {
+        let report = match server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::StatsReport(report) => report,
+            _ => continue,
+        };
+        if report.top_talkers.is_empty() {
+            continue;
+        }
+
+        assert_eq!(
+            report.top_talkers,
+            vec![(minimal::messages::Heartbeat::message_id(), 3)]
+        );
+        let frames_in: u64 = report.connections.values().map(|t| t.frames_in).sum();
+        let bytes_in: u64 = report.connections.values().map(|t| t.bytes_in).sum();
+        assert_eq!(frames_in, 3);
+        assert!(bytes_in > 0);
+        break;
+    }
+}
+
+#[test]
+fn tlog_reader_replays_frames_with_timing() {
+    use std::fs::File;
+    use std::io::{BufWriter, Write};
+    use std::time::Instant;
+
+    use maviola::core::io::Sender;
+    use maviola::protocol::{Endpoint, MavLinkId};
+
+    initialize();
+
+    let path = std::env::temp_dir().join(format!("maviola-{}.tlog", unused_port()));
+    {
+        let heartbeat = minimal::messages::Heartbeat::default();
+        let v1_endpoint = Endpoint::v1(MavLinkId::new(10, 1));
+        let v2_endpoint = Endpoint::v2(MavLinkId::new(11, 1));
+
+        let mut writer = BufWriter::new(File::create(&path).unwrap());
+        // The trailing record keeps connection open until the checked frames are delivered
+        for (timestamp, v1) in [
+            (0, false),
+            (100_000, true),
+            (200_000, false),
+            (400_000, false),
+            (600_000, false),
+        ] {
+            writer
+                .write_all(&(1_000_000u64 + timestamp).to_be_bytes())
+                .unwrap();
+            if v1 {
+                let frame = v1_endpoint.next_frame(&heartbeat).unwrap();
+                Sender::new(&mut writer).send(&frame).unwrap();
+            } else {
+                let frame = v2_endpoint.next_frame(&heartbeat).unwrap();
+                Sender::new(&mut writer).send(&frame).unwrap();
+            }
+        }
+        writer.flush().unwrap();
+    }
+
+    let node = Node::sync::<V2>()
+        .connection(TlogReader::new(&path).unwrap().speed(2.0))
+        .build()
+        .unwrap();
+
+    let (frame, _) = node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    let started_at = Instant::now();
+    assert_eq!(frame.system_id(), 11);
+
+    for _ in 0..2 {
+        let (frame, _) = node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), 11);
+    }
+    assert!(started_at.elapsed() >= Duration::from_millis(180));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn tcp_server_authenticates_clients_by_handshake() {
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    use maviola::core::io::{HandshakeOutcome, Sender};
+    use maviola::protocol::{Endpoint, MavLinkId};
+    use maviola::sync::io::TcpHandshake;
+
+    #[derive(Debug)]
+    struct TokenHandshake;
+
+    impl TcpHandshake for TokenHandshake {
+        fn handshake(&self, stream: &mut TcpStream) -> Result<HandshakeOutcome> {
+            let mut token = [0u8; 4];
+            stream.read_exact(&mut token)?;
+
+            Ok(match &token == b"mav1" {
+                true => HandshakeOutcome::Accept,
+                false => HandshakeOutcome::Reject,
+            })
+        }
+    }
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_SERVER_SYS_ID)
+        .component_id(DEFAULT_TCP_SERVER_COMP_ID)
+        .connection(
+            TcpServer::new(make_addr(port))
+                .unwrap()
+                .sync_handshake(TokenHandshake),
+        )
+        .build()
+        .unwrap();
+    wait();
+
+    let heartbeat = minimal::messages::Heartbeat::default();
+
+    let mut rejected = TcpStream::connect(make_addr(port)).unwrap();
+    rejected.write_all(b"nope").unwrap();
+    let frame = Endpoint::v2(MavLinkId::new(30, 1))
+        .next_frame(&heartbeat)
+        .unwrap();
+    _ = Sender::new(&mut rejected).send(&frame);
+    wait();
+    assert!(server_node.try_recv_frame().is_err());
+    assert_eq!(rejected.read(&mut [0u8; 1]).unwrap_or_default(), 0);
+
+    let mut accepted = TcpStream::connect(make_addr(port)).unwrap();
+    accepted.write_all(b"mav1").unwrap();
+    let frame = Endpoint::v2(MavLinkId::new(31, 1))
+        .next_frame(&heartbeat)
+        .unwrap();
+    Sender::new(&mut accepted).send(&frame).unwrap();
+
+    let (frame, _) = server_node.recv_frame_timeout(WAIT_DURATION).unwrap();
+    assert_eq!(frame.system_id(), 31);
+}
+
+#[test]
+fn watchers_track_peers_and_connection() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let mut status = server_node.watch_connection();
+    let mut peers = server_node.watch_peers();
+    assert!(status.get().is_active());
+    assert!(peers.get().is_empty());
+    wait();
+
+    let mut client_node = make_tcp_client_node_v2(port, 1);
+    client_node.activate().unwrap();
+
+    let status = status.wait_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(status.channels().len(), 1);
+
+    let peers = peers.wait_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0].system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    assert_eq!(peers[0].component_id(), 1);
+}
+
+#[test]
+fn frame_meta_is_captured_on_receive() {
+    use std::time::{Instant, SystemTime};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 0);
+    wait();
+
+    let sent_at = SystemTime::now();
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait_long();
+    let consumed_at = Instant::now();
+
+    let (_, callback) = server_node.recv_frame().unwrap();
+    let meta = callback.meta();
+    assert!(meta.received_at() < consumed_at);
+    assert!(meta.timestamp() >= sent_at);
+    assert_eq!(meta.connection_id(), server_node.info().id());
+    assert_eq!(
+        meta.channel().connection_id(),
+        callback.info().connection_id()
+    );
+}
+
+#[test]
+fn remote_systems_are_tracked() {
+    use minimal::enums::{MavAutopilot, MavType};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 1);
+    server_node.system_events(true);
+    wait();
+
+    let heartbeat = |custom_mode| minimal::messages::Heartbeat {
+        type_: MavType::FixedWing,
+        autopilot: MavAutopilot::Px4,
+        custom_mode,
+        ..Default::default()
+    };
+    for custom_mode in [1, 1, 2] {
+        client_node.send(&heartbeat(custom_mode)).unwrap();
+    }
+    wait_long();
+
+    let mut modes = Vec::new();
+    while let Ok(event) = server_node.try_recv() {
+        if let Event::SystemChanged(system) = event {
+            assert_eq!(system.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+            modes.push(system.custom_mode());
+        }
+    }
+    assert_eq!(modes, vec![1, 2]);
+
+    let system = server_node.system(DEFAULT_TCP_CLIENT_SYS_ID).unwrap();
+    assert_eq!(system.component_id(), 1);
+    assert_eq!(system.autopilot() as u8, MavAutopilot::Px4 as u8);
+    assert_eq!(system.version(), MavLinkVersion::V2);
+    assert_eq!(system.last_sequence(), 2);
+    assert_eq!(server_node.systems().len(), 1);
+    assert!(server_node.system(42).is_none());
+}
+
+#[test]
+fn remote_components_are_discovered() {
+    use maviola::protocol::ComponentKind;
+    use minimal::enums::{MavAutopilot, MavType};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let autopilot_node = make_tcp_client_node_v2(port, 1);
+    let camera_node = make_tcp_client_node_v2(port, 100);
+    server_node.system_events(true);
+    wait();
+
+    for _ in 0..2 {
+        autopilot_node
+            .send(&minimal::messages::Heartbeat {
+                type_: MavType::Quadrotor,
+                autopilot: MavAutopilot::Ardupilotmega,
+                ..Default::default()
+            })
+            .unwrap();
+        camera_node
+            .send(&minimal::messages::Heartbeat {
+                type_: MavType::Camera,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    wait_long();
+
+    let mut discovered = Vec::new();
+    while let Ok(event) = server_node.try_recv() {
+        if let Event::NewComponent {
+            system_id,
+            component_id,
+            component_kind,
+        } = event
+        {
+            assert_eq!(system_id, DEFAULT_TCP_CLIENT_SYS_ID);
+            discovered.push((component_id, component_kind));
+        }
+    }
+    discovered.sort_by_key(|(component_id, _)| *component_id);
+    assert_eq!(
+        discovered,
+        vec![(1, ComponentKind::Autopilot), (100, ComponentKind::Camera)]
+    );
+
+    let components = server_node.components(DEFAULT_TCP_CLIENT_SYS_ID);
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[1].kind(), ComponentKind::Camera);
+    assert!(server_node.components(42).is_empty());
+}
+
+#[test]
+fn connections_are_parsed_from_urls() {
+    use maviola::sync::io::Connection;
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .connection(Connection::parse(&format!("tcpin:{}", make_addr(port))).unwrap())
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+        .component_id(1)
+        .connection(Connection::parse(&format!("tcpout://{}", make_addr(port))).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, _) = server_node.recv_frame().unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+}
+
+#[test]
+#[cfg(feature = "config")]
+fn nodes_are_built_from_config() {
+    use maviola::core::node::{NodeBuilder, NodeConfig};
+
+    initialize();
+
+    let port = unused_port();
+    let path = std::env::temp_dir().join(format!("maviola-router-{port}.toml"));
+    std::fs::write(
+        &path,
+        format!(
+            r#"
+            heartbeat_timeout = 2.0
+
+            [connection]
+            connections = ["tcpin:{}"]
+            "#,
+            make_addr(port)
+        ),
+    )
+    .unwrap();
+
+    let router = NodeBuilder::from_config(path.as_path())
+        .unwrap()
+        .version::<V2>()
+        .sync()
+        .connection_from_config()
+        .unwrap()
+        .build()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let config = NodeConfig::from_json(&format!(
+        r#"{{"system_id": {DEFAULT_TCP_CLIENT_SYS_ID}, "component_id": 1, "connection": "tcpout:{}"}}"#,
+        make_addr(port)
+    ))
+    .unwrap();
+    let client_node = NodeBuilder::from_config(config)
+        .unwrap()
+        .id_from_config()
+        .unwrap()
+        .version::<V2>()
+        .sync()
+        .connection_from_config()
+        .unwrap()
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, _) = router.recv_frame().unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    assert_eq!(router.heartbeat_timeout(), Duration::from_secs(2));
+
+    assert!(NodeBuilder::from_config(NodeConfig::default())
+        .unwrap()
+        .id_from_config()
+        .is_err());
+}
+
+#[test]
+fn matching_frames_are_received_without_stealing() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 1);
+    wait();
+
+    let handler = thread::spawn(move || {
+        wait();
+        for custom_mode in [1, 2, 3] {
+            client_node
+                .send(&minimal::messages::Heartbeat {
+                    custom_mode,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        client_node
+    });
+
+    let (heartbeat, frame, _) = server_node
+        .recv_message_matching::<minimal::messages::Heartbeat, _>(
+            |heartbeat| heartbeat.custom_mode == 2,
+            WAIT_LONG_DURATION,
+        )
+        .unwrap();
+    assert_eq!(heartbeat.custom_mode, 2);
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+
+    // Other receivers still get all frames
+    for _ in 0..3 {
+        server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    }
+
+    let result = server_node.recv_matching(|_| true, WAIT_DURATION);
+    assert!(matches!(
+        result,
+        Err(maviola::error::RecvTimeoutError::Timeout)
+    ));
+
+    let _client_node = handler.join().unwrap();
+}
+
+#[test]
+fn udp_server_expires_silent_clients() {
+    initialize();
+
+    let port = unused_port();
+    let server = UdpServer::new(make_addr(port))
+        .unwrap()
+        .with_client_timeout(WAIT_LONG_DURATION)
+        .with_max_clients(1);
+    let server_node = Node::sync::<V2>()
+        .connection(server.clone())
+        .channel_events(true)
+        .build()
+        .unwrap();
+    wait();
+
+    let client_1 = std::net::UdpSocket::bind(make_addr(unused_port())).unwrap();
+    let client_2 = std::net::UdpSocket::bind(make_addr(unused_port())).unwrap();
+
+    client_1.send_to(&[0], make_addr(port)).unwrap();
+    wait();
+    client_2.send_to(&[0], make_addr(port)).unwrap();
+    wait();
+
+    // Second client exceeds the limit
+    let peers = server.peers();
+    assert_eq!(peers.len(), 1);
+    assert!(matches!(
+        peers[0].details(),
+        maviola::core::io::ChannelDetails::UdpServer { peer_addr, .. }
+            if *peer_addr == client_1.local_addr().unwrap()
+    ));
+
+    // Silent client is removed and its channel is closed
+    let closed = loop {
+        match server_node.recv_timeout(WAIT_LONG_DURATION * 2).unwrap() {
+            Event::ChannelClosed(channel) => break channel,
+            _ => continue,
+        }
+    };
+    assert_eq!(closed.id(), peers[0].id());
+    assert!(server.peers().is_empty());
+
+    client_2.send_to(&[0], make_addr(port)).unwrap();
+    wait();
+    assert_eq!(server.peers().len(), 1);
+}
+
+#[test]
+fn udp_server_rejects_not_allowed_senders() {
+    initialize();
+
+    let port = unused_port();
+    let server = UdpServer::new(make_addr(port))
+        .unwrap()
+        .with_allowlist(["127.0.0.0/8".parse().unwrap()])
+        .lock_to_first_sender();
+    let server_node = Node::sync::<V2>()
+        .connection(server.clone())
+        .build()
+        .unwrap();
+    wait();
+
+    let client_1 = std::net::UdpSocket::bind(make_addr(unused_port())).unwrap();
+    let client_2 = std::net::UdpSocket::bind(make_addr(unused_port())).unwrap();
+
+    client_1.send_to(&[0], make_addr(port)).unwrap();
+    wait();
+    client_2.send_to(&[0], make_addr(port)).unwrap();
+    client_2.send_to(&[0], make_addr(port)).unwrap();
+    wait();
+
+    assert_eq!(server.locked_sender(), client_1.local_addr().ok());
+    let peers = server.peers();
+    assert_eq!(peers.len(), 1);
+    assert!(matches!(
+        peers[0].details(),
+        maviola::core::io::ChannelDetails::UdpServer { locked: true, .. }
+    ));
+
+    let mut rejected = Vec::new();
+    while let Ok(event) = server_node.recv_timeout(WAIT_DURATION) {
+        if let Event::SenderRejected(_, addr) = event {
+            rejected.push(addr);
+        }
+    }
+    assert_eq!(rejected, vec![client_2.local_addr().unwrap()]);
+}
+
+#[test]
+#[cfg(feature = "msrv-utils-timesync")]
+fn timesync_measures_latency() {
+    use maviola::core::msrv::timesync::TimesyncSettings;
+    use maviola::error::TimesyncError;
+
+    initialize();
+
+    let port = unused_port();
+    let server_id = MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1);
+    let mut server_node = Node::sync::<V2>()
+        .id(server_id)
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    let client_node = make_tcp_client_node_v2(port, 1);
+    wait();
+
+    let settings = TimesyncSettings::new(server_id)
+        .with_samples(3)
+        .with_interval(Duration::from_millis(10))
+        .with_timeout(WAIT_DURATION);
+
+    // Inactive nodes do not answer timesync requests
+    assert!(matches!(
+        client_node.measure_latency(settings),
+        Err(Error::Timesync(TimesyncError::Timeout))
+    ));
+
+    server_node.activate().unwrap();
+    let stats = client_node.measure_latency(settings).unwrap();
+    assert_eq!(stats.samples().len() + stats.lost(), 3);
+    assert!(stats.rtt_min() <= stats.rtt_max());
+    assert!(stats.rtt_max() < WAIT_DURATION);
+}
+
+#[test]
+fn link_quality_reports_degraded_peers() {
+    use maviola::protocol::LinkQualityMonitor;
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = Node::sync::<V2>()
+        .system_id(DEFAULT_TCP_SERVER_SYS_ID)
+        .component_id(DEFAULT_TCP_SERVER_COMP_ID)
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .link_quality(
+            LinkQualityMonitor::new()
+                .with_window(10)
+                .with_max_loss(20.0),
+        )
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    // Every other frame is lost
+    for sequence in (0..22).step_by(2) {
+        let frame = mavio::Frame::builder()
+            .sequence(sequence)
+            .system_id(DEFAULT_TCP_CLIENT_SYS_ID)
+            .component_id(1)
+            .version(V2)
+            .message(&minimal::messages::Heartbeat::default())
+            .unwrap()
+            .build();
+        client_node.send_frame(&frame).unwrap();
+    }
+    wait_long();
+
+    let mut degraded = Vec::new();
+    while let Ok(event) = server_node.try_recv() {
+        if let Event::PeerDegraded(_, quality) = event {
+            degraded.push(quality);
+        }
+    }
+    assert_eq!(degraded.len(), 1);
+    assert!(degraded[0].loss() > 20.0);
+
+    let peer = server_node.peers().next().unwrap();
+    let quality = peer.link_quality().unwrap();
+    assert_eq!(quality.received(), 10);
+    assert_eq!(quality.lost(), 10);
+}
+
+#[test]
+#[cfg(feature = "zmq")]
+fn zmq_publish_and_subscribe() {
+    use maviola::core::io::{ChannelDetails, ZmqPub, ZmqSub};
+
+    initialize();
+
+    let endpoint = format!("tcp://{}", make_addr(unused_port()));
+    let publisher = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(ZmqPub::new(&endpoint).unwrap().with_topic("mavlink/1"))
+        .build()
+        .unwrap();
+    let matching = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1))
+        .connection(ZmqSub::new(&endpoint).unwrap().with_topic("mavlink/"))
+        .build()
+        .unwrap();
+    let other = Node::sync::<V2>()
+        .connection(ZmqSub::new(&endpoint).unwrap().with_topic("video/"))
+        .build()
+        .unwrap();
+    wait();
+
+    publisher
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+
+    let (frame, callback) = matching.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+    assert!(matches!(
+        callback.info().details(),
+        ChannelDetails::ZmqSub { topic, .. } if topic == "mavlink/"
+    ));
+    assert!(other.try_recv_frame().is_err());
+
+    // Subscribers do not publish frames
+    matching
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+    assert!(matches!(
+        publisher.try_recv(),
+        Err(maviola::error::TryRecvError::Empty)
+    ));
+}
+
+#[test]
+fn channel_taps_observe_raw_bytes() {
+    use std::sync::{Arc, Mutex};
+
+    use maviola::core::io::{ChannelInfo, ChannelTap};
+
+    #[derive(Default)]
+    struct Recorder {
+        read: Mutex<Vec<u8>>,
+        written: Mutex<Vec<u8>>,
+    }
+
+    impl ChannelTap for Recorder {
+        fn on_read(&self, _: &ChannelInfo, bytes: &[u8]) {
+            self.read.lock().unwrap().extend_from_slice(bytes);
+        }
+
+        fn on_write(&self, _: &ChannelInfo, bytes: &[u8]) {
+            self.written.lock().unwrap().extend_from_slice(bytes);
+        }
+    }
+
+    initialize();
+
+    let port = unused_port();
+    let server_tap = Arc::new(Recorder::default());
+    let client_tap = Arc::new(Recorder::default());
+
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(
+            TcpServer::new(make_addr(port))
+                .unwrap()
+                .with_tap(server_tap.clone()),
+        )
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1))
+        .connection(
+            TcpClient::new(make_addr(port))
+                .unwrap()
+                .with_tap(client_tap.clone()),
+        )
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, _) = server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+
+    let mut bytes = Vec::new();
+    maviola::core::io::Sender::new(&mut bytes)
+        .send(&frame)
+        .unwrap();
+
+    assert_eq!(*client_tap.written.lock().unwrap(), bytes);
+    assert_eq!(*server_tap.read.lock().unwrap(), bytes);
+    assert!(client_tap.read.lock().unwrap().is_empty());
+    assert!(server_tap.written.lock().unwrap().is_empty());
+}
+
+#[test]
+fn black_box_records_node_events() {
+    use maviola::core::node::{BlackBox, BlackBoxEntry};
+
+    initialize();
+
+    let port = unused_port();
+    let path = std::env::temp_dir().join(format!("maviola-{port}.blackbox"));
+    let black_box = BlackBox::new().with_capacity(10);
+
+    let server_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .black_box(black_box.clone())
+        .channel_events(true)
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1))
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+    wait();
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    server_node.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    wait();
+
+    let records = black_box.records();
+    let frame_record = records
+        .iter()
+        .find(|record| matches!(record.entry(), BlackBoxEntry::Frame(_)))
+        .unwrap();
+    assert!(frame_record.channel().is_some());
+    match frame_record.entry() {
+        BlackBoxEntry::Frame(frame) => assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID),
+        _ => unreachable!(),
+    }
+    assert!(records.iter().any(|record| matches!(
+        record.entry(),
+        BlackBoxEntry::Event(event) if event.starts_with("ChannelOpened")
+    )));
+
+    black_box.dump(&path).unwrap();
+    let dump = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(dump.lines().count(), records.len());
+    assert!(dump.contains(&format!("sys={DEFAULT_TCP_CLIENT_SYS_ID}")));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn keepalive_emits_heartbeats_and_reports_staleness() {
+    use maviola::core::node::Keepalive;
+
+    initialize();
+
+    let port = unused_port();
+    let keepalive = Keepalive::heartbeats(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 195))
+        .with_interval(Duration::from_millis(50))
+        .with_stale_after(WAIT_DURATION);
+
+    let server_node = Node::sync::<V2>()
+        .connection(TcpServer::new(make_addr(port)).unwrap())
+        .keepalive(keepalive)
+        .build()
+        .unwrap();
+    let client_node = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1))
+        .connection(TcpClient::new(make_addr(port)).unwrap())
+        .build()
+        .unwrap();
+
+    let (frame, _) = client_node
+        .recv_matching(|frame| frame.message_id() == 0, WAIT_LONG_DURATION)
+        .unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+    assert_eq!(frame.component_id(), 195);
+
+    loop {
+        if let Event::ConnectionStale(_) = server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            break;
+        }
+    }
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    loop {
+        match server_node.recv_timeout(WAIT_LONG_DURATION).unwrap() {
+            Event::ConnectionAlive(_) => break,
+            Event::ConnectionStale(_) => panic!("connection is stale after receiving a frame"),
+            _ => continue,
+        }
+    }
+}
+
+#[test]
+fn periodic_messages_are_sent() {
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 10);
+    wait();
+
+    let mut counter = 0u8;
+    let periodic = server_node.send_periodically(Duration::from_millis(10), move || {
+        counter = counter.wrapping_add(1);
+        minimal::messages::Heartbeat {
+            custom_mode: counter as u32,
+            ..Default::default()
+        }
+    });
+
+    for expected in 1..=5 {
+        let (frame, _) = client_node
+            .recv_matching(|frame| frame.message_id() == 0, WAIT_LONG_DURATION)
+            .unwrap();
+        let message = minimal::messages::Heartbeat::try_from(frame.payload()).unwrap();
+        assert_eq!(message.custom_mode, expected);
+    }
+    assert!(periodic.sent() >= 5);
+
+    periodic.stop();
+    wait();
+    while client_node.try_recv().is_ok() {}
+    assert!(client_node.recv_frame_timeout(WAIT_DURATION).is_err());
+
+    // Tasks are stopped, once node is closed
+    let periodic = server_node.send_periodically(
+        Duration::from_millis(10),
+        minimal::messages::Heartbeat::default,
+    );
+    drop(server_node);
+    wait();
+    assert!(periodic.is_stopped());
+}
+
+#[test]
+fn invalid_frames_are_reported() {
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    use maviola::core::io::Sender;
+    use maviola::core::node::InvalidKind;
+    use maviola::protocol::{Endpoint, MavLinkId};
+
+    initialize();
+
+    let port = unused_port();
+    let server_node = make_tcp_server_node_v2(port);
+    wait();
+
+    let endpoint = Endpoint::v2(MavLinkId::new(30, 1));
+    let heartbeat = minimal::messages::Heartbeat::default();
+    let mut frame_bytes = Vec::new();
+    Sender::new(&mut frame_bytes)
+        .send(&endpoint.next_frame(&heartbeat).unwrap())
+        .unwrap();
+    let mut corrupted = Vec::new();
+    Sender::new(&mut corrupted)
+        .send(&endpoint.next_frame(&heartbeat).unwrap())
+        .unwrap();
+    let checksum_idx = corrupted.len() - 2;
+    corrupted[checksum_idx] ^= 0xFF;
+
+    let mut client = TcpStream::connect(make_addr(port)).unwrap();
+    let garbage = [0x01u8, 0x02, 0x03, 0x04, 0x05, 0x06];
+    client.write_all(&garbage).unwrap();
+    client.write_all(&frame_bytes).unwrap();
+    client.write_all(&corrupted).unwrap();
+    wait();
+
+    let mut malformed = None;
+    let mut invalid = None;
+    while let Ok(event) = server_node.try_recv() {
+        match event {
+            Event::Malformed(_, report) => malformed = Some(report),
+            Event::Invalid(_, report, _) => invalid = Some(report),
+            _ => continue,
+        }
+    }
+
+    let malformed = malformed.unwrap();
+    assert_eq!(malformed.kind(), InvalidKind::Malformed);
+    assert_eq!(malformed.raw_bytes(), Some(garbage.as_slice()));
+
+    let invalid = invalid.unwrap();
+    assert_eq!(invalid.kind(), InvalidKind::Checksum);
+    assert!(invalid.checksum_failed());
+    assert!(invalid.is_link_corruption());
+}
+
+#[test]
+#[cfg(all(feature = "ipc", unix))]
+fn ipc_server_exchanges_frames_with_clients() {
+    use maviola::core::io::{ChannelDetails, IpcClient, IpcServer};
+
+    initialize();
+
+    let path = std::env::temp_dir().join(format!("maviola_{}.ipc", unused_port()));
+    let server = Node::sync::<V2>()
+        .id(MavLinkId::new(DEFAULT_TCP_SERVER_SYS_ID, 1))
+        .connection(IpcServer::new(path.as_path()).unwrap())
+        .build()
+        .unwrap();
+    let make_client = || {
+        Node::sync::<V2>()
+            .id(MavLinkId::new(DEFAULT_TCP_CLIENT_SYS_ID, 1))
+            .connection(IpcClient::new(path.as_path()).unwrap())
+            .build()
+    };
+
+    let client = make_client().unwrap();
+    // Server accepts a single client at a time
+    assert!(make_client().is_err());
+    wait();
+
+    client
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, callback) = server.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_CLIENT_SYS_ID);
+    assert!(matches!(
+        callback.info().details(),
+        ChannelDetails::IpcServer { .. }
+    ));
+
+    server
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    let (frame, _) = client.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+    assert_eq!(frame.system_id(), DEFAULT_TCP_SERVER_SYS_ID);
+
+    // Server waits for the next client, once the previous one is detached
+    drop(client);
+    wait_long();
+    let client = make_client().unwrap();
+    wait();
+    client
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    assert!(server.recv_frame_timeout(WAIT_LONG_DURATION).is_ok());
+
+    // Segment is removed, once server is closed
+    drop(client);
+    drop(server);
+    wait_long();
+    assert!(!path.exists());
+}
+
+#[test]
+fn health_reports_channels_and_heartbeats() {
+    initialize();
+
+    let port = unused_port();
+    let mut server_node = make_tcp_server_node_v2(port);
+    let client_node = make_tcp_client_node_v2(port, 10);
+    wait();
+
+    let health = server_node.health();
+    assert!(health.is_alive());
+    assert!(health.is_ready());
+    assert_eq!(health.channels.len(), 1);
+    assert!(health.last_frame_at().is_none());
+    assert!(!health.heartbeat.is_active);
+    assert!(!health.retry.is_supervised);
+
+    client_node
+        .send(&minimal::messages::Heartbeat::default())
+        .unwrap();
+    wait();
+    let health = server_node.health();
+    assert!(health.channels[0].last_frame_at.is_some());
+    assert!(health.queues.events > 0);
+
+    server_node.activate().unwrap();
+    wait();
+    let health = server_node.health();
+    assert!(health.heartbeat.is_active);
+    assert!(health.heartbeat.last_sent_at.is_some());
+}
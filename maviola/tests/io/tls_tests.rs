@@ -0,0 +1,194 @@
+#![cfg(feature = "tls")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use portpicker::Port;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+
+use maviola::core::io::{TlsAcceptor, TlsConnector};
+use maviola::dialects::minimal;
+
+use maviola::prelude::*;
+
+const HOST: &str = "127.0.0.1";
+const WAIT_DURATION: Duration = Duration::from_millis(100);
+const WAIT_LONG_DURATION: Duration = Duration::from_millis(500);
+
+fn make_addr(port: Port) -> String {
+    format!("{HOST}:{port}")
+}
+
+/// Creates TLS settings for a server with a self-signed certificate issued for `localhost` and
+/// a client, that trusts this certificate.
+fn make_tls() -> (TlsAcceptor, TlsConnector) {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+    let cert = CertificateDer::from(certified.cert.der().to_vec());
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()));
+
+    let server_config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert.clone()], key)
+        .unwrap();
+
+    let mut roots = RootCertStore::empty();
+    roots.add(cert).unwrap();
+    let client_config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    (
+        TlsAcceptor::new(server_config),
+        TlsConnector::new(Arc::new(client_config), "localhost").unwrap(),
+    )
+}
+
+#[cfg(feature = "sync")]
+mod sync_tls_tests {
+    use std::thread;
+
+    use super::*;
+    use maviola::core::io::HandshakeOutcome;
+    use maviola::sync::io::TcpHandshake;
+    use maviola::sync::prelude::*;
+
+    fn make_server(port: Port, acceptor: TlsAcceptor) -> EdgeNode<V2> {
+        Node::sync::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpServer::new(make_addr(port)).unwrap().with_tls(acceptor))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn frames_are_exchanged_over_tls() {
+        let port = portpicker::pick_unused_port().unwrap();
+        let (acceptor, connector) = make_tls();
+
+        let server = make_server(port, acceptor);
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(make_addr(port)).unwrap().with_tls(connector))
+            .build()
+            .unwrap();
+        thread::sleep(WAIT_DURATION);
+
+        client
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        let (frame, _) = server.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        server
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        let (frame, _) = client.recv_frame_timeout(WAIT_LONG_DURATION).unwrap();
+        assert_eq!(frame.system_id(), 1);
+    }
+
+    #[test]
+    fn untrusted_server_is_rejected() {
+        let port = portpicker::pick_unused_port().unwrap();
+        let (acceptor, _) = make_tls();
+        let (_, connector) = make_tls();
+
+        let server = make_server(port, acceptor);
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(make_addr(port)).unwrap().with_tls(connector))
+            .build();
+        assert!(client.is_err());
+        assert!(server.recv_frame_timeout(WAIT_DURATION).is_err());
+    }
+
+    #[test]
+    fn plain_clients_are_not_attached() {
+        let port = portpicker::pick_unused_port().unwrap();
+        let (acceptor, _) = make_tls();
+
+        let server = make_server(port, acceptor);
+        let client = Node::sync::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(make_addr(port)).unwrap())
+            .build()
+            .unwrap();
+        thread::sleep(WAIT_DURATION);
+
+        client
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        assert!(server.recv_frame_timeout(WAIT_LONG_DURATION).is_err());
+    }
+
+    #[derive(Debug)]
+    struct AcceptAll;
+
+    impl TcpHandshake for AcceptAll {
+        fn handshake(&self, _: &mut std::net::TcpStream) -> Result<HandshakeOutcome> {
+            Ok(HandshakeOutcome::Accept)
+        }
+    }
+
+    #[test]
+    fn tls_conflicts_are_diagnosed() {
+        let (acceptor, connector) = make_tls();
+
+        let client = TcpClient::new("127.0.0.1:5600")
+            .unwrap()
+            .with_fallback_addr("127.0.0.1:5601")
+            .unwrap()
+            .with_tls(connector);
+        assert!(matches!(
+            Node::sync::<V2>().connection(client).build(),
+            Err(Error::Config(_))
+        ));
+
+        let server = TcpServer::new("127.0.0.1:5600")
+            .unwrap()
+            .sync_handshake(AcceptAll)
+            .with_tls(acceptor);
+        assert!(matches!(
+            Node::sync::<V2>().connection(server).build(),
+            Err(Error::Config(_))
+        ));
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_tls_tests {
+    use super::*;
+    use maviola::asnc::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn frames_are_exchanged_over_tls() {
+        let port = portpicker::pick_unused_port().unwrap();
+        let (acceptor, connector) = make_tls();
+
+        let mut server = Node::asnc::<V2>()
+            .id(MavLinkId::new(1, 1))
+            .connection(TcpServer::new(make_addr(port)).unwrap().with_tls(acceptor))
+            .build()
+            .await
+            .unwrap();
+        let mut client = Node::asnc::<V2>()
+            .id(MavLinkId::new(2, 1))
+            .connection(TcpClient::new(make_addr(port)).unwrap().with_tls(connector))
+            .build()
+            .await
+            .unwrap();
+        tokio::time::sleep(WAIT_DURATION).await;
+
+        client
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        let (frame, _) = server.recv_frame_timeout(WAIT_LONG_DURATION).await.unwrap();
+        assert_eq!(frame.system_id(), 2);
+
+        server
+            .send(&minimal::messages::Heartbeat::default())
+            .unwrap();
+        let (frame, _) = client.recv_frame_timeout(WAIT_LONG_DURATION).await.unwrap();
+        assert_eq!(frame.system_id(), 1);
+    }
+}